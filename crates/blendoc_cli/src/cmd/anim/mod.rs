@@ -0,0 +1,321 @@
+use std::io::Write;
+use std::path::PathBuf;
+
+use blendoc::blend::{ActionInfo, AnimDataInfo, AnimOptions, BlendFile, DriverInfo, FCurveInfo, IdIndex, Result, codes, read_action, read_anim_data};
+
+use crate::cmd::output::{Output, OutputArgs};
+use crate::cmd::util::{IdOrPtrSelector, parse_id_or_ptr_selector, ptr_hex, scan_ids};
+
+#[derive(clap::Args)]
+pub struct Args {
+	pub file: PathBuf,
+	#[arg(long = "id")]
+	pub id_name: Option<String>,
+	#[arg(long, conflicts_with = "id_name")]
+	pub ptr: Option<String>,
+	#[arg(long, default_value_t = 10_000)]
+	pub max_fcurves: usize,
+	#[arg(long)]
+	pub json: bool,
+	#[command(flatten)]
+	pub output: OutputArgs,
+}
+
+/// List action F-Curves and drivers; all actions without a selector.
+///
+/// An action selector lists its curves; any other animatable ID shows its
+/// `AnimData`: the assigned action and its drivers.
+pub fn run(args: Args) -> Result<()> {
+	let Args {
+		file: path,
+		id_name,
+		ptr,
+		max_fcurves,
+		json,
+		output,
+	} = args;
+
+	let blend = BlendFile::open(&path)?;
+	let dna = blend.dna()?;
+	let index = blend.pointer_index()?;
+	let ids = IdIndex::build(scan_ids(&blend, &dna)?);
+	let options = AnimOptions {
+		max_fcurves,
+		..AnimOptions::default()
+	};
+
+	let mut actions = Vec::new();
+	let mut owner = None;
+	if id_name.is_none() && ptr.is_none() {
+		for record in ids.records.iter().filter(|record| record.code == codes::ID_AC) {
+			actions.push(read_action(&dna, &index, &ids, record.old_ptr, &options)?);
+		}
+	} else {
+		let target = match parse_id_or_ptr_selector(id_name, ptr)? {
+			IdOrPtrSelector::Id(name) => ids.resolve_selector(&name)?.old_ptr,
+			IdOrPtrSelector::Ptr(ptr) => index.canonicalize_ptr(&dna, ptr)?,
+		};
+		if ids.get_by_ptr(target).is_some_and(|record| record.type_name.as_ref() == "bAction") {
+			actions.push(read_action(&dna, &index, &ids, target, &options)?);
+		} else {
+			let mut anim = read_anim_data(&dna, &index, &ids, target, &options)?;
+			actions.extend(anim.action.take());
+			owner = Some(anim);
+		}
+	}
+
+	let mut out = output.open(json)?;
+	if !out.is_text() {
+		let payload = AnimJson {
+			path: path.display().to_string(),
+			owner: owner.as_ref().map(owner_json),
+			actions: actions.iter().map(action_json).collect(),
+		};
+		out.structured(&payload, &curve_rows(&actions, owner.as_ref()))?;
+		return out.finish();
+	}
+
+	writeln!(out, "path: {}", path.display())?;
+	if let Some(owner) = &owner {
+		writeln!(out, "owner: {} ({})", owner.owner_id.as_deref().unwrap_or("-"), ptr_hex(owner.owner_ptr))?;
+		if owner.adt_ptr == 0 {
+			writeln!(out, "anim_data: -")?;
+		}
+	} else {
+		writeln!(out, "actions: {}", actions.len())?;
+	}
+	for action in &actions {
+		write_action(&mut out, action)?;
+	}
+	if let Some(owner) = owner.as_ref().filter(|owner| owner.adt_ptr != 0) {
+		writeln!(out)?;
+		writeln!(out, "drivers: {}{}", owner.drivers.len(), if owner.truncated { " (truncated)" } else { "" })?;
+		for curve in &owner.drivers {
+			write_curve(&mut out, curve)?;
+			if let Some(driver) = &curve.driver {
+				write_driver(&mut out, driver)?;
+			}
+		}
+	}
+	out.finish()
+}
+
+fn write_action(out: &mut Output, action: &ActionInfo) -> Result<()> {
+	writeln!(out)?;
+	writeln!(
+		out,
+		"action: {} ({}) curves={} keys={} frames={}{}{}",
+		action.id_name.as_deref().unwrap_or("-"),
+		ptr_hex(action.ptr),
+		action.fcurves.len(),
+		action.keyframes(),
+		range_label(action.frame_range()),
+		if action.layered { " layered" } else { "" },
+		if action.truncated { " (truncated)" } else { "" }
+	)?;
+	for curve in &action.fcurves {
+		write_curve(out, curve)?;
+	}
+	Ok(())
+}
+
+fn write_curve(out: &mut Output, curve: &FCurveInfo) -> Result<()> {
+	write!(
+		out,
+		"  {}\t{}={}\tframes={}",
+		curve.channel_label(),
+		if curve.sampled { "samples" } else { "keys" },
+		curve.keyframes,
+		range_label(curve.frame_range)
+	)?;
+	if let Some(group) = &curve.group {
+		write!(out, "\tgroup={group}")?;
+	}
+	writeln!(out)?;
+	Ok(())
+}
+
+fn write_driver(out: &mut Output, driver: &DriverInfo) -> Result<()> {
+	write!(out, "    driver: {}", driver.type_label())?;
+	if !driver.expression.is_empty() {
+		write!(out, " {:?}", driver.expression.as_ref())?;
+	}
+	writeln!(out)?;
+	for variable in &driver.variables {
+		writeln!(out, "    var {} {}", variable.name, variable.type_label())?;
+		for target in &variable.targets {
+			writeln!(
+				out,
+				"      -> {} {}",
+				target.id_name.as_deref().unwrap_or("-"),
+				target.rna_path.as_deref().unwrap_or("-")
+			)?;
+		}
+	}
+	Ok(())
+}
+
+fn range_label(range: Option<(f32, f32)>) -> String {
+	range.map_or_else(|| "-".to_owned(), |(start, end)| format!("{start}..{end}"))
+}
+
+fn owner_json(owner: &AnimDataInfo) -> OwnerJson {
+	OwnerJson {
+		ptr: ptr_hex(owner.owner_ptr),
+		id: owner.owner_id.as_deref().map(str::to_owned),
+		anim_data: (owner.adt_ptr != 0).then(|| ptr_hex(owner.adt_ptr)),
+		truncated: owner.truncated,
+		drivers: owner.drivers.iter().map(curve_json).collect(),
+	}
+}
+
+fn action_json(action: &ActionInfo) -> ActionJson {
+	let range = action.frame_range();
+	ActionJson {
+		ptr: ptr_hex(action.ptr),
+		id: action.id_name.as_deref().map(str::to_owned),
+		layered: action.layered,
+		keyframes: action.keyframes(),
+		frame_start: range.map(|range| range.0),
+		frame_end: range.map(|range| range.1),
+		truncated: action.truncated,
+		fcurves: action.fcurves.iter().map(curve_json).collect(),
+	}
+}
+
+fn curve_json(curve: &FCurveInfo) -> FCurveJson {
+	FCurveJson {
+		ptr: ptr_hex(curve.ptr),
+		rna_path: curve.rna_path.as_deref().map(str::to_owned),
+		array_index: curve.array_index,
+		group: curve.group.as_deref().map(str::to_owned),
+		keyframes: curve.keyframes,
+		sampled: curve.sampled,
+		frame_start: curve.frame_range.map(|range| range.0),
+		frame_end: curve.frame_range.map(|range| range.1),
+		driver: curve.driver.as_ref().map(|driver| DriverJson {
+			driver_type: driver.type_label(),
+			expression: driver.expression.to_string(),
+			variables: driver
+				.variables
+				.iter()
+				.map(|variable| VariableJson {
+					name: variable.name.to_string(),
+					var_type: variable.type_label(),
+					targets: variable
+						.targets
+						.iter()
+						.map(|target| TargetJson {
+							id: target.id_name.as_deref().map(str::to_owned),
+							id_ptr: ptr_hex(target.id_ptr),
+							rna_path: target.rna_path.as_deref().map(str::to_owned),
+						})
+						.collect(),
+				})
+				.collect(),
+		}),
+	}
+}
+
+fn curve_rows(actions: &[ActionInfo], owner: Option<&AnimDataInfo>) -> Vec<CurveRowCsv> {
+	let row = |source: Option<&str>, kind: &'static str, curve: &FCurveInfo| CurveRowCsv {
+		source: source.unwrap_or_default().to_owned(),
+		kind,
+		rna_path: curve.rna_path.as_deref().unwrap_or_default().to_owned(),
+		array_index: curve.array_index,
+		group: curve.group.as_deref().unwrap_or_default().to_owned(),
+		keyframes: curve.keyframes,
+		frame_start: curve.frame_range.map(|range| range.0),
+		frame_end: curve.frame_range.map(|range| range.1),
+	};
+	let mut rows: Vec<_> = actions
+		.iter()
+		.flat_map(|action| action.fcurves.iter().map(|curve| row(action.id_name.as_deref(), "fcurve", curve)))
+		.collect();
+	if let Some(owner) = owner {
+		rows.extend(owner.drivers.iter().map(|curve| row(owner.owner_id.as_deref(), "driver", curve)));
+	}
+	rows
+}
+
+#[derive(serde::Serialize)]
+struct AnimJson {
+	path: String,
+	#[serde(skip_serializing_if = "Option::is_none")]
+	owner: Option<OwnerJson>,
+	actions: Vec<ActionJson>,
+}
+
+#[derive(serde::Serialize)]
+struct OwnerJson {
+	ptr: String,
+	id: Option<String>,
+	anim_data: Option<String>,
+	truncated: bool,
+	drivers: Vec<FCurveJson>,
+}
+
+#[derive(serde::Serialize)]
+struct ActionJson {
+	ptr: String,
+	id: Option<String>,
+	layered: bool,
+	keyframes: u64,
+	frame_start: Option<f32>,
+	frame_end: Option<f32>,
+	truncated: bool,
+	fcurves: Vec<FCurveJson>,
+}
+
+#[derive(serde::Serialize)]
+struct FCurveJson {
+	ptr: String,
+	rna_path: Option<String>,
+	array_index: i64,
+	group: Option<String>,
+	keyframes: u64,
+	sampled: bool,
+	frame_start: Option<f32>,
+	frame_end: Option<f32>,
+	#[serde(skip_serializing_if = "Option::is_none")]
+	driver: Option<DriverJson>,
+}
+
+#[derive(serde::Serialize)]
+struct DriverJson {
+	#[serde(rename = "type")]
+	driver_type: String,
+	expression: String,
+	variables: Vec<VariableJson>,
+}
+
+#[derive(serde::Serialize)]
+struct VariableJson {
+	name: String,
+	#[serde(rename = "type")]
+	var_type: String,
+	targets: Vec<TargetJson>,
+}
+
+#[derive(serde::Serialize)]
+struct TargetJson {
+	id: Option<String>,
+	id_ptr: String,
+	rna_path: Option<String>,
+}
+
+/// Flat `--format csv` row: one action curve or driver curve per line.
+#[derive(serde::Serialize)]
+struct CurveRowCsv {
+	source: String,
+	kind: &'static str,
+	rna_path: String,
+	array_index: i64,
+	group: String,
+	keyframes: u64,
+	frame_start: Option<f32>,
+	frame_end: Option<f32>,
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,33 @@
+use blendoc_testkit::builder::SCENE_ID_NAMES;
+
+use crate::cmd::test_support::{TempBlend, fixture_path, run_blendoc, run_blendoc_json};
+
+#[test]
+fn character_actions_report_curve_paths_and_ranges() {
+	let fixture = fixture_path("character.blend");
+	let fixture = fixture.to_string_lossy().into_owned();
+	let json = run_blendoc_json(&["anim", &fixture, "--json"]);
+
+	let actions = json["actions"].as_array().expect("actions array");
+	for action in actions {
+		let curves = action["fcurves"].as_array().expect("fcurves array");
+		assert!(curves.iter().all(|curve| curve["keyframes"].as_u64().is_some()));
+		if let (Some(start), Some(end)) = (action["frame_start"].as_f64(), action["frame_end"].as_f64()) {
+			assert!(start <= end);
+		}
+	}
+}
+
+#[test]
+fn unanimated_scene_lists_no_actions_and_rejects_ids_without_anim_data() {
+	let target = TempBlend::scene("anim");
+	let target_arg = target.arg();
+
+	let all = run_blendoc(&["anim", target_arg]);
+	let object = run_blendoc(&["anim", target_arg, "--id", SCENE_ID_NAMES[1]]);
+
+	assert!(all.status.success(), "anim failed: {}", String::from_utf8_lossy(&all.stderr));
+	assert!(String::from_utf8_lossy(&all.stdout).contains("actions: 0"));
+	assert!(!object.status.success());
+	assert!(String::from_utf8_lossy(&object.stderr).contains("expected ID with AnimData"));
+}
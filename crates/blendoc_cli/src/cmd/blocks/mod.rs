@@ -0,0 +1,158 @@
+use std::io::Write;
+use std::path::PathBuf;
+
+use blendoc::blend::{BlendError, BlendFile, Dna, Result, codes};
+
+use crate::cmd::output::OutputArgs;
+use crate::cmd::util::{parse_block_code, ptr_hex, render_code};
+
+#[derive(clap::Args)]
+pub struct Args {
+	pub file: PathBuf,
+	#[arg(long)]
+	pub code: Option<String>,
+	#[arg(long, value_name = "INDEX|STRUCT")]
+	pub sdna: Option<String>,
+	#[arg(long = "min-len")]
+	pub min_len: Option<u64>,
+	#[arg(long, default_value_t = 0)]
+	pub skip: usize,
+	#[arg(long, default_value_t = 100)]
+	pub limit: usize,
+	#[arg(long)]
+	pub json: bool,
+	#[command(flatten)]
+	pub output: OutputArgs,
+}
+
+/// Print the raw block table in file order, one page at a time.
+///
+/// Filters apply before paging, so `--skip` counts matching blocks; block
+/// indices stay the file-order index of the block. `--limit 0` lists every
+/// match.
+pub fn run(args: Args) -> Result<()> {
+	let Args {
+		file: path,
+		code,
+		sdna,
+		min_len,
+		skip,
+		limit,
+		json,
+		output,
+	} = args;
+
+	let blend = BlendFile::open(&path)?;
+	let dna = blend.dna()?;
+	let code = code.as_deref().map(parse_block_code).transpose()?;
+	let sdna = sdna.as_deref().map(|selector| parse_sdna_selector(&dna, selector)).transpose()?;
+
+	let mut total = 0_usize;
+	let mut matched = 0_usize;
+	let mut rows = Vec::new();
+	for (idx, block) in blend.blocks().enumerate() {
+		let block = block?;
+		total += 1;
+		if code.is_some_and(|code| block.head.code != code)
+			|| sdna.is_some_and(|sdna_nr| block.head.sdna_nr != sdna_nr || codes::is_raw_code(block.head.code))
+			|| min_len.is_some_and(|min_len| block.head.len < min_len)
+		{
+			continue;
+		}
+		matched += 1;
+		if matched <= skip || (limit != 0 && rows.len() >= limit) {
+			continue;
+		}
+		let type_name = if codes::is_raw_code(block.head.code) {
+			None
+		} else {
+			dna.struct_by_sdna(block.head.sdna_nr).map(|item| dna.type_name(item.type_idx).to_owned())
+		};
+		rows.push(BlockRow {
+			index: idx,
+			file_offset: block.file_offset as u64,
+			code: render_code(block.head.code),
+			old: ptr_hex(block.head.old),
+			sdna_nr: block.head.sdna_nr,
+			type_name,
+			len: block.head.len,
+			nr: block.head.nr,
+		});
+	}
+
+	let mut out = output.open(json)?;
+	if !out.is_text() {
+		let payload = BlocksJson {
+			path: path.display().to_string(),
+			total,
+			matched,
+			skip,
+			limit,
+			blocks: &rows,
+		};
+		out.structured(&payload, &rows)?;
+		return out.finish();
+	}
+
+	writeln!(out, "path: {}", path.display())?;
+	writeln!(out, "blocks: {matched} matched of {total}")?;
+	if let (Some(first), Some(last)) = (rows.first(), rows.last()) {
+		writeln!(out, "showing: {}..{} ({} rows)", first.index, last.index, rows.len())?;
+	}
+	writeln!(out, "index\toffset\tcode\told\tsdna\ttype\tlen\tnr")?;
+	for row in &rows {
+		writeln!(
+			out,
+			"{}\t{:#x}\t{}\t{}\t{}\t{}\t{}\t{}",
+			row.index,
+			row.file_offset,
+			row.code,
+			row.old,
+			row.sdna_nr,
+			row.type_name.as_deref().unwrap_or("-"),
+			row.len,
+			row.nr
+		)?;
+	}
+	let shown = skip.saturating_add(rows.len());
+	if shown < matched {
+		writeln!(out, "more: {} (next page with --skip {shown})", matched - shown)?;
+	}
+	out.finish()
+}
+
+/// `--sdna` value: an SDNA struct index, or a struct name resolved through the DNA.
+fn parse_sdna_selector(dna: &Dna, selector: &str) -> Result<u32> {
+	if let Ok(sdna_nr) = selector.parse::<u32>() {
+		return Ok(sdna_nr);
+	}
+	dna.struct_index(selector)
+		.ok_or_else(|| BlendError::DnaStructNotFound { name: selector.to_owned() })
+}
+
+#[derive(serde::Serialize)]
+struct BlocksJson<'a> {
+	path: String,
+	total: usize,
+	matched: usize,
+	skip: usize,
+	limit: usize,
+	blocks: &'a [BlockRow],
+}
+
+/// One block header; also the `--format csv` row.
+#[derive(serde::Serialize)]
+struct BlockRow {
+	index: usize,
+	file_offset: u64,
+	code: String,
+	old: String,
+	sdna_nr: u32,
+	#[serde(rename = "type")]
+	type_name: Option<String>,
+	len: u64,
+	nr: u64,
+}
+
+#[cfg(test)]
+mod tests;
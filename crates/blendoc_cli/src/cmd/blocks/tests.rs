@@ -0,0 +1,52 @@
+use blendoc_testkit::builder::{HeaderKind, SCENE_VERT_COUNT, scene_ptrs};
+
+use crate::cmd::test_support::{TempBlend, run_blendoc, run_blendoc_json};
+
+#[test]
+fn blocks_filter_and_page_the_block_table() {
+	let target = TempBlend::scene("blocks");
+	let target_arg = target.arg();
+
+	let all = run_blendoc_json(&["blocks", target_arg, "--json"]);
+	let page = run_blendoc_json(&["blocks", target_arg, "--skip", "1", "--limit", "2", "--json"]);
+	let data = run_blendoc_json(&["blocks", target_arg, "--code", "DATA", "--json"]);
+	let scenes = run_blendoc_json(&["blocks", target_arg, "--sdna", "Scene", "--json"]);
+	let large = run_blendoc_json(&["blocks", target_arg, "--min-len", "40", "--json"]);
+	let text = run_blendoc(&["blocks", target_arg, "--limit", "2"]);
+	let missing = run_blendoc(&["blocks", target_arg, "--sdna", "Nope"]);
+
+	let codes = |json: &serde_json::Value| -> Vec<String> {
+		json["blocks"]
+			.as_array()
+			.expect("blocks array")
+			.iter()
+			.map(|row| row["code"].as_str().expect("code").to_owned())
+			.collect()
+	};
+	assert_eq!(codes(&all), ["SC", "OB", "ME", "DATA", "WO", "DNA1", "ENDB"]);
+	assert_eq!(all["total"], 7);
+	assert_eq!(all["blocks"][5]["type"], serde_json::Value::Null);
+	assert_eq!(all["blocks"][0]["type"], "Scene");
+	assert_eq!(all["blocks"][0]["old"], format!("0x{:016x}", scene_ptrs(HeaderKind::Legacy8)[0]));
+
+	assert_eq!(codes(&page), ["OB", "ME"]);
+	assert_eq!(page["matched"], 7);
+	assert_eq!(page["blocks"][0]["index"], 1);
+
+	assert_eq!(codes(&data), ["DATA"]);
+	assert_eq!(data["blocks"][0]["nr"], SCENE_VERT_COUNT);
+	assert_eq!(data["blocks"][0]["type"], "MVert");
+	assert_eq!(codes(&scenes), ["SC"]);
+	assert!(
+		large["blocks"]
+			.as_array()
+			.expect("blocks array")
+			.iter()
+			.all(|row| row["len"].as_u64() >= Some(40))
+	);
+
+	let stdout = String::from_utf8_lossy(&text.stdout);
+	assert!(stdout.contains("blocks: 7 matched of 7"), "{stdout}");
+	assert!(stdout.contains("more: 5 (next page with --skip 2)"), "{stdout}");
+	assert!(!missing.status.success());
+}
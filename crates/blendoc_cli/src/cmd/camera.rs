@@ -1,6 +1,6 @@
 use std::path::PathBuf;
 
-use blendoc::blend::{BlendFile, DecodeOptions, Value, chase_scene_camera};
+use blendoc::blend::{BlendFile, DecodeOptions, Value, chase_scene_camera, enrich_error};
 
 use crate::cmd::util::render_code;
 
@@ -30,7 +30,9 @@ pub fn run(args: Args) -> blendoc::blend::Result<()> {
 		..DecodeOptions::default()
 	};
 
-	let Some((meta, object)) = chase_scene_camera(&blend, &dna, &index, &scene_decode, &object_decode)? else {
+	let Some((meta, object)) =
+		chase_scene_camera(&blend, &dna, &index, &scene_decode, &object_decode).map_err(|err| enrich_error(err, &dna, blend.header.version))?
+	else {
 		println!("path: {}", path.display());
 		println!("camera: null or unresolved");
 		return Ok(());
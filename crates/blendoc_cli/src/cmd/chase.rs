@@ -1,11 +1,13 @@
 use std::path::PathBuf;
 
 use blendoc::blend::{
-	BlendError, BlendFile, ChaseMeta, ChasePolicy, ChaseResult, ChaseStopReason, DecodeOptions, FieldPath, IdIndex, Value, chase_from_block_code,
-	chase_from_ptr, scan_id_blocks,
+	BlendFile, ChaseMatch, ChaseMeta, ChasePolicy, ChaseResult, ChaseStopReason, DecodeOptions, FieldPath, IdIndex, Value, chase_all_from_block,
+	chase_all_from_ptr,
 };
 
-use crate::cmd::util::{RootSelector, emit_json, parse_root_selector, ptr_hex, ptr_hex_opt, render_code};
+use crate::cmd::util::{
+	RootSelector, code_root_block, code_selector_label, emit_json, parse_root_selector, ptr_hex, ptr_hex_opt, render_code, scan_ids, stop_hint,
+};
 
 #[derive(clap::Args)]
 pub struct Args {
@@ -38,7 +40,7 @@ pub fn run(args: Args) -> blendoc::blend::Result<()> {
 	let blend = BlendFile::open(&path)?;
 	let dna = blend.dna()?;
 	let index = blend.pointer_index()?;
-	let ids = IdIndex::build(scan_id_blocks(&blend, &dna)?);
+	let ids = IdIndex::build(scan_ids(&blend, &dna)?);
 
 	let mut decode = DecodeOptions::for_scene_inspect();
 	decode.include_padding = true;
@@ -47,20 +49,18 @@ pub fn run(args: Args) -> blendoc::blend::Result<()> {
 	let parsed_path = FieldPath::parse(&path_expr)?;
 	let policy = ChasePolicy::default();
 
-	let (result, root_info) = match root {
-		RootSelector::Code(block_code) => {
-			let block = blend
-				.find_first_block_by_code(block_code)?
-				.ok_or(BlendError::BlockNotFound { code: block_code })?;
+	let (matches, root_info) = match root {
+		RootSelector::Code(block_code, occurrence) => {
+			let block = code_root_block(&blend, block_code, occurrence)?;
 			let type_name = dna
 				.struct_by_sdna(block.head.sdna_nr)
 				.map(|item| dna.type_name(item.type_idx))
 				.unwrap_or("<unknown>")
 				.to_owned();
 			let root_ptr = block.head.old;
-			let result = chase_from_block_code(&blend, &dna, &index, block_code, &parsed_path, &decode, &policy)?;
+			let result = chase_all_from_block(&dna, &index, &block, &parsed_path, &decode, &policy)?;
 			let root_info = RootInfo {
-				selector: format!("code:{}", render_code(block_code)),
+				selector: code_selector_label(block_code, occurrence),
 				ptr: Some(root_ptr),
 				type_name: Some(type_name),
 				id_name: ids.get_by_ptr(root_ptr).map(|item| item.id_name.to_string()),
@@ -80,7 +80,7 @@ pub fn run(args: Args) -> blendoc::blend::Result<()> {
 					typed.base.entry.start_old.checked_add(offset)
 				})
 			});
-			let result = chase_from_ptr(&dna, &index, root_ptr, &parsed_path, &decode, &policy)?;
+			let result = chase_all_from_ptr(&dna, &index, root_ptr, &parsed_path, &decode, &policy)?;
 			let root_info = RootInfo {
 				selector: format!("ptr:0x{root_ptr:016x}"),
 				ptr: Some(root_ptr),
@@ -90,9 +90,9 @@ pub fn run(args: Args) -> blendoc::blend::Result<()> {
 			(result, root_info)
 		}
 		RootSelector::Id(name) => {
-			let row = ids.get_by_name(&name).ok_or(BlendError::IdRecordNotFound { name: name.clone() })?;
+			let row = ids.resolve_selector(&name)?;
 			let root_ptr = row.old_ptr;
-			let result = chase_from_ptr(&dna, &index, root_ptr, &parsed_path, &decode, &policy)?;
+			let result = chase_all_from_ptr(&dna, &index, root_ptr, &parsed_path, &decode, &policy)?;
 			let root_info = RootInfo {
 				selector: format!("id:{}", row.id_name),
 				ptr: Some(root_ptr),
@@ -103,13 +103,39 @@ pub fn run(args: Args) -> blendoc::blend::Result<()> {
 		}
 	};
 
+	if parsed_path.fans_out() {
+		let matches: Vec<MatchTrace> = matches
+			.iter()
+			.map(|found| MatchTrace {
+				hops: build_hop_trace(&found.result, &dna, &ids),
+				hint: found.result.stop.as_ref().and_then(|stop| stop_hint(&dna, blend.header.version, &stop.reason)),
+				found,
+			})
+			.collect();
+		if json {
+			print_matches_json(&path, &root_info, &path_expr, &matches);
+		} else {
+			print_matches(&path, &root_info, &path_expr, &matches);
+		}
+		return Ok(());
+	}
+
+	let result = matches.into_iter().next().map(|found| found.result).expect("non-fanning path yields one match");
 	let hops = build_hop_trace(&result, &dna, &ids);
+	let hint = result.stop.as_ref().and_then(|stop| stop_hint(&dna, blend.header.version, &stop.reason));
 
 	if json {
-		print_json(&path, &root_info, &path_expr, &hops, &result);
+		print_json(&path, &root_info, &path_expr, &hops, &result, hint.as_deref());
 		return Ok(());
 	}
 
+	print_root(&path, &root_info, &path_expr);
+	print_trace(&hops, &result, hint.as_deref(), "");
+
+	Ok(())
+}
+
+fn print_root(path: &std::path::Path, root_info: &RootInfo, path_expr: &str) {
 	println!("path: {}", path.display());
 	println!("root_selector: {}", root_info.selector);
 	if let Some(ptr) = root_info.ptr {
@@ -122,10 +148,13 @@ pub fn run(args: Args) -> blendoc::blend::Result<()> {
 		println!("root_id_name: {id_name}");
 	}
 	println!("path_expr: {path_expr}");
-	println!("hops: {}", hops.len());
-	for hop in &hops {
+}
+
+fn print_trace(hops: &[HopTrace], result: &ChaseResult, hint: Option<&str>, indent: &str) {
+	println!("{indent}hops: {}", hops.len());
+	for hop in hops {
 		println!(
-			"  {}: ptr=0x{:016x} canonical={} code={} sdna={} type={} id={}",
+			"{indent}  {}: ptr=0x{:016x} canonical={} code={} sdna={} type={} id={}",
 			hop.index,
 			hop.ptr,
 			format_ptr_opt(hop.canonical),
@@ -136,19 +165,36 @@ pub fn run(args: Args) -> blendoc::blend::Result<()> {
 		);
 	}
 
-	println!("result_kind: {}", value_kind(&result.value));
+	println!("{indent}result_kind: {}", value_kind(&result.value));
 	if let Value::Struct(item) = &result.value {
-		println!("result_type: {}", item.type_name);
+		println!("{indent}result_type: {}", item.type_name);
 	}
 
 	if let Some(stop) = &result.stop {
-		println!("stop_step: {}", stop.step_index);
-		println!("stop_reason: {}", format_stop_reason(&stop.reason));
+		println!("{indent}stop_step: {}", stop.step_index);
+		println!("{indent}stop_reason: {}", format_stop_reason(&stop.reason));
+		if let Some(hint) = hint {
+			println!("{indent}stop_hint: {hint}");
+		}
 	} else {
-		println!("stop_reason: none");
+		println!("{indent}stop_reason: none");
 	}
+}
 
-	Ok(())
+fn print_matches(path: &std::path::Path, root_info: &RootInfo, path_expr: &str, matches: &[MatchTrace<'_>]) {
+	print_root(path, root_info, path_expr);
+	println!("matches: {}", matches.len());
+	for item in matches {
+		println!("match: {}", item.found.path);
+		print_trace(&item.hops, &item.found.result, item.hint.as_deref(), "  ");
+	}
+}
+
+/// One fanned-out match with its rendered hop trace.
+struct MatchTrace<'a> {
+	found: &'a ChaseMatch,
+	hops: Vec<HopTrace>,
+	hint: Option<String>,
 }
 
 #[derive(Debug, Clone)]
@@ -238,45 +284,91 @@ fn format_ptr_opt(ptr: Option<u64>) -> String {
 	}
 }
 
-fn print_json(path: &std::path::Path, root: &RootInfo, path_expr: &str, hops: &[HopTrace], result: &ChaseResult) {
+fn print_json(path: &std::path::Path, root: &RootInfo, path_expr: &str, hops: &[HopTrace], result: &ChaseResult, hint: Option<&str>) {
 	let payload = ChaseJson {
 		path: path.display().to_string(),
-		root: RootJson {
-			selector: root.selector.clone(),
-			ptr: ptr_hex_opt(root.ptr),
-			type_name: root.type_name.clone(),
-			id_name: root.id_name.clone(),
-		},
+		root: root_json(root),
 		path_expr: path_expr.to_owned(),
-		hops: hops
-			.iter()
-			.map(|hop| HopJson {
-				index: hop.index,
-				ptr: ptr_hex(hop.ptr),
-				canonical: ptr_hex_opt(hop.canonical),
-				code: render_code(hop.code),
-				sdna_nr: hop.sdna_nr,
-				type_name: hop.type_name.clone(),
-				id_name: hop.id_name.clone(),
-			})
-			.collect(),
+		hops: hops_json(hops),
+		result: result_json(&result.value),
+		stop: stop_json(result, hint),
+		matches: None,
+	};
+
+	emit_json(&payload);
+}
+
+/// Fanned-out payload: top-level `hops` is empty, `result.kind` is `Matches`,
+/// and each concrete match carries its own trace.
+fn print_matches_json(path: &std::path::Path, root: &RootInfo, path_expr: &str, matches: &[MatchTrace<'_>]) {
+	let payload = ChaseJson {
+		path: path.display().to_string(),
+		root: root_json(root),
+		path_expr: path_expr.to_owned(),
+		hops: Vec::new(),
 		result: ResultJson {
-			kind: value_kind(&result.value).to_owned(),
-			type_name: if let Value::Struct(item) = &result.value {
-				Some(item.type_name.to_string())
-			} else {
-				None
-			},
+			kind: "Matches".to_owned(),
+			type_name: None,
 		},
-		stop: result.stop.as_ref().map(|stop| StopJson {
-			step: stop.step_index,
-			reason: format_stop_reason(&stop.reason),
-		}),
+		stop: None,
+		matches: Some(
+			matches
+				.iter()
+				.map(|item| MatchJson {
+					path: item.found.path.to_string(),
+					hops: hops_json(&item.hops),
+					result: result_json(&item.found.result.value),
+					stop: stop_json(&item.found.result, item.hint.as_deref()),
+				})
+				.collect(),
+		),
 	};
 
 	emit_json(&payload);
 }
 
+fn root_json(root: &RootInfo) -> RootJson {
+	RootJson {
+		selector: root.selector.clone(),
+		ptr: ptr_hex_opt(root.ptr),
+		type_name: root.type_name.clone(),
+		id_name: root.id_name.clone(),
+	}
+}
+
+fn hops_json(hops: &[HopTrace]) -> Vec<HopJson> {
+	hops.iter()
+		.map(|hop| HopJson {
+			index: hop.index,
+			ptr: ptr_hex(hop.ptr),
+			canonical: ptr_hex_opt(hop.canonical),
+			code: render_code(hop.code),
+			sdna_nr: hop.sdna_nr,
+			type_name: hop.type_name.clone(),
+			id_name: hop.id_name.clone(),
+		})
+		.collect()
+}
+
+fn result_json(value: &Value) -> ResultJson {
+	ResultJson {
+		kind: value_kind(value).to_owned(),
+		type_name: if let Value::Struct(item) = value {
+			Some(item.type_name.to_string())
+		} else {
+			None
+		},
+	}
+}
+
+fn stop_json(result: &ChaseResult, hint: Option<&str>) -> Option<StopJson> {
+	result.stop.as_ref().map(|stop| StopJson {
+		step: stop.step_index,
+		reason: format_stop_reason(&stop.reason),
+		hint: hint.map(str::to_owned),
+	})
+}
+
 #[derive(serde::Serialize)]
 struct RootJson {
 	selector: String,
@@ -309,6 +401,7 @@ struct ResultJson {
 struct StopJson {
 	step: usize,
 	reason: String,
+	hint: Option<String>,
 }
 
 #[derive(serde::Serialize)]
@@ -319,4 +412,14 @@ struct ChaseJson {
 	hops: Vec<HopJson>,
 	result: ResultJson,
 	stop: Option<StopJson>,
+	#[serde(skip_serializing_if = "Option::is_none")]
+	matches: Option<Vec<MatchJson>>,
+}
+
+#[derive(serde::Serialize)]
+struct MatchJson {
+	path: String,
+	hops: Vec<HopJson>,
+	result: ResultJson,
+	stop: Option<StopJson>,
 }
@@ -0,0 +1,64 @@
+use std::io::Write;
+
+use blendoc::blend::Result;
+use blendoc::blend::codes::{CODES, CodeKind};
+
+use crate::cmd::output::OutputArgs;
+use crate::cmd::util::render_code;
+
+#[derive(clap::Args)]
+pub struct Args {
+	#[arg(long = "id-only")]
+	pub id_only: bool,
+	#[arg(long)]
+	pub json: bool,
+	#[command(flatten)]
+	pub output: OutputArgs,
+}
+
+/// List the block codes blendoc knows, sorted by code bytes.
+///
+/// The table is `blendoc::blend::codes::CODES`, the same one block-code
+/// arguments and labels elsewhere in the CLI go through.
+pub fn run(args: Args) -> Result<()> {
+	let Args { id_only, json, output } = args;
+
+	let rows: Vec<CodeRow> = CODES
+		.iter()
+		.filter(|info| !id_only || info.kind == CodeKind::Id)
+		.map(|info| CodeRow {
+			code: render_code(info.code),
+			constant: info.constant,
+			kind: info.kind.as_str(),
+			description: info.description,
+		})
+		.collect();
+
+	let mut out = output.open(json)?;
+	if !out.is_text() {
+		out.structured(&CodesJson { codes: &rows }, &rows)?;
+		return out.finish();
+	}
+
+	for row in &rows {
+		writeln!(out, "{:<6}{:<14}{:<8}{}", row.code, row.constant, row.kind, row.description)?;
+	}
+	out.finish()
+}
+
+#[derive(serde::Serialize)]
+struct CodesJson<'a> {
+	codes: &'a [CodeRow],
+}
+
+/// One known block code; also the `--format csv` row.
+#[derive(serde::Serialize)]
+struct CodeRow {
+	code: String,
+	constant: &'static str,
+	kind: &'static str,
+	description: &'static str,
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,22 @@
+use crate::cmd::test_support::{run_blendoc, run_blendoc_json};
+
+#[test]
+fn codes_lists_the_sorted_table() {
+	let all = run_blendoc_json(&["codes", "--json"]);
+	let ids = run_blendoc_json(&["codes", "--id-only", "--json"]);
+	let text = run_blendoc(&["codes"]);
+
+	let all = all["codes"].as_array().expect("codes array");
+	let labels: Vec<&str> = all.iter().map(|row| row["code"].as_str().expect("code string")).collect();
+	assert!(labels.contains(&"DNA1") && labels.contains(&"OB"));
+	assert!(all.iter().any(|row| row["code"] == "SC" && row["constant"] == "ID_SCE" && row["kind"] == "id"));
+
+	let ids = ids["codes"].as_array().expect("codes array");
+	assert!(ids.len() < all.len());
+	assert!(ids.iter().all(|row| row["kind"] == "id"));
+
+	assert!(text.status.success());
+	let stdout = String::from_utf8_lossy(&text.stdout);
+	let first = stdout.lines().next().expect("at least one row");
+	assert!(first.starts_with("AC") && first.contains("ID_AC"), "{stdout}");
+}
@@ -0,0 +1,86 @@
+use std::fs;
+use std::path::PathBuf;
+
+use blendoc::blend::{BlendFile, Compression, Result, decode_bytes, encode_bytes, verify_round_trip, write_blend};
+
+use crate::cmd::util::emit_json;
+
+#[derive(clap::Args)]
+pub struct Args {
+	pub input: PathBuf,
+	pub output: PathBuf,
+	#[arg(long, default_value = "keep", value_parser = ["keep", "zstd", "gzip", "none"])]
+	pub compress: String,
+	#[arg(long)]
+	pub level: Option<i32>,
+	#[arg(long)]
+	pub json: bool,
+}
+
+/// Re-save a blend file block by block through the write path.
+///
+/// The copy is decoded, re-parsed, and compared block for block against the
+/// input before anything is written, so a failed copy never touches `output`.
+pub fn run(args: Args) -> Result<()> {
+	let Args {
+		input,
+		output,
+		compress,
+		level,
+		json,
+	} = args;
+
+	let blend = BlendFile::open(&input)?;
+	let target = match compress.as_str() {
+		"zstd" => Compression::Zstd,
+		"gzip" => Compression::Gzip,
+		"none" => Compression::None,
+		_ => blend.compression,
+	};
+
+	let mut decoded = Vec::with_capacity(blend.bytes().len());
+	write_blend(&blend, &mut decoded)?;
+	let encoded = encode_bytes(&decoded, target, level)?;
+
+	let (_, roundtrip) = decode_bytes(encoded.clone())?;
+	let copy = BlendFile::from_bytes(roundtrip)?;
+	let blocks = verify_round_trip(&blend, &copy)?;
+	fs::write(&output, &encoded)?;
+
+	if json {
+		emit_json(&CopyJson {
+			input: input.display().to_string(),
+			output: output.display().to_string(),
+			from: blend.compression.as_str(),
+			to: target.as_str(),
+			blocks,
+			decoded_bytes: decoded.len(),
+			output_bytes: encoded.len(),
+		});
+		return Ok(());
+	}
+
+	println!("input: {}", input.display());
+	println!("output: {}", output.display());
+	println!("from: {}", blend.compression.as_str());
+	println!("to: {}", target.as_str());
+	println!("blocks: {blocks} (verified)");
+	println!("decoded_bytes: {}", decoded.len());
+	println!("output_bytes: {}", encoded.len());
+
+	Ok(())
+}
+
+#[derive(serde::Serialize)]
+struct CopyJson {
+	input: String,
+	output: String,
+	from: &'static str,
+	to: &'static str,
+	blocks: usize,
+	decoded_bytes: usize,
+	output_bytes: usize,
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,24 @@
+use blendoc::blend::{Compression, decode_bytes};
+use blendoc_testkit::builder::{HeaderKind, scene_file};
+
+use crate::cmd::test_support::{TempBlend, run_blendoc_json};
+
+#[test]
+fn copy_recompresses_and_preserves_blocks() {
+	let bytes = scene_file(HeaderKind::Large, false);
+	let input = TempBlend::new("copy_in", &bytes);
+	let output = std::env::temp_dir().join(format!("blendoc_copy_out_{}.blend", std::process::id()));
+	let input_arg = input.arg();
+	let output_arg = output.to_string_lossy().into_owned();
+
+	let json = run_blendoc_json(&["copy", input_arg, &output_arg, "--compress", "zstd", "--json"]);
+	let written = std::fs::read(&output).expect("copy written");
+	std::fs::remove_file(&output).expect("copy removed");
+
+	assert_eq!(json["from"], "none");
+	assert_eq!(json["to"], "zstd");
+	assert_eq!(json["decoded_bytes"], bytes.len());
+	let (compression, decoded) = decode_bytes(written).expect("copy decodes");
+	assert_eq!(compression, Compression::Zstd);
+	assert_eq!(decoded, bytes);
+}
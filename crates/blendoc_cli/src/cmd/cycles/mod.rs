@@ -0,0 +1,164 @@
+use std::collections::HashMap;
+use std::io::Write;
+use std::path::PathBuf;
+
+use blendoc::blend::{BlendFile, IdGraphOptions, IdIndex, Result};
+
+use crate::cmd::graph_cache::GraphCacheArgs;
+use crate::cmd::output::OutputArgs;
+use crate::cmd::util::{ptr_hex, scan_ids};
+
+#[derive(clap::Args)]
+pub struct Args {
+	pub file: PathBuf,
+	#[arg(long = "refs-depth")]
+	pub refs_depth: Option<u32>,
+	#[arg(long = "max-edges")]
+	pub max_edges: Option<usize>,
+	#[arg(long)]
+	pub json: bool,
+	#[command(flatten)]
+	pub output: OutputArgs,
+	#[command(flatten)]
+	pub graph_cache: GraphCacheArgs,
+}
+
+/// List ID reference cycles: groups of IDs that reach each other through ID pointers.
+///
+/// Each cycle is a strongly connected component of the ID graph with more
+/// than one member, listed with the fields that connect its members.
+pub fn run(args: Args) -> Result<()> {
+	let Args {
+		file: path,
+		refs_depth,
+		max_edges,
+		json,
+		output,
+		graph_cache,
+	} = args;
+
+	let blend = BlendFile::open(&path)?;
+	let dna = blend.dna()?;
+	let index = blend.pointer_index()?;
+	let ids = IdIndex::build(scan_ids(&blend, &dna)?);
+
+	let mut options = IdGraphOptions {
+		detect_cycles: true,
+		..IdGraphOptions::default()
+	};
+	if let Some(refs_depth) = refs_depth {
+		options.ref_scan.max_depth = refs_depth;
+	}
+	if let Some(max_edges) = max_edges {
+		options.max_edges = max_edges;
+	}
+	let graph = graph_cache.build(&path, &blend, &dna, &index, &ids, &options)?;
+	let names: HashMap<u64, &str> = graph.nodes.iter().map(|node| (node.canonical, node.id_name.as_ref())).collect();
+	let name = |ptr: u64| names.get(&ptr).map_or_else(|| ptr_hex(ptr), |name| (*name).to_owned());
+
+	let cycles: Vec<CycleJson> = graph
+		.cycles
+		.iter()
+		.enumerate()
+		.map(|(idx, cycle)| CycleJson {
+			index: idx + 1,
+			members: cycle
+				.members
+				.iter()
+				.map(|ptr| MemberJson {
+					ptr: ptr_hex(*ptr),
+					id: name(*ptr),
+				})
+				.collect(),
+			edges: cycle
+				.edges
+				.iter()
+				.map(|edge| EdgeJson {
+					from: name(edge.from),
+					field: edge.field.to_string(),
+					to: name(edge.to),
+				})
+				.collect(),
+		})
+		.collect();
+	let truncated = graph.truncated.is_some();
+
+	let mut out = output.open(json)?;
+	if !out.is_text() {
+		let rows: Vec<EdgeRow> = cycles
+			.iter()
+			.flat_map(|cycle| {
+				cycle.edges.iter().map(|edge| EdgeRow {
+					cycle: cycle.index,
+					from: edge.from.clone(),
+					field: edge.field.clone(),
+					to: edge.to.clone(),
+				})
+			})
+			.collect();
+		let payload = CyclesJson {
+			path: path.display().to_string(),
+			ids: graph.nodes.len(),
+			edges: graph.edges.len(),
+			truncated,
+			cycles: &cycles,
+		};
+		out.structured(&payload, &rows)?;
+		return out.finish();
+	}
+
+	writeln!(out, "path: {}", path.display())?;
+	writeln!(out, "cycles: {} ({} IDs, {} edges scanned)", cycles.len(), graph.nodes.len(), graph.edges.len())?;
+	if truncated {
+		writeln!(out, "warning: ID graph edge budget reached; cycles may be missing")?;
+	}
+	for cycle in &cycles {
+		let members: Vec<&str> = cycle.members.iter().map(|member| member.id.as_str()).collect();
+		writeln!(out, "\ncycle {}: {} IDs: {}", cycle.index, members.len(), members.join(", "))?;
+		for edge in &cycle.edges {
+			writeln!(out, "  {} -{}-> {}", edge.from, edge.field, edge.to)?;
+		}
+	}
+	out.finish()
+}
+
+#[derive(serde::Serialize)]
+struct CyclesJson<'a> {
+	path: String,
+	ids: usize,
+	edges: usize,
+	truncated: bool,
+	cycles: &'a [CycleJson],
+}
+
+#[derive(serde::Serialize)]
+struct CycleJson {
+	index: usize,
+	members: Vec<MemberJson>,
+	edges: Vec<EdgeJson>,
+}
+
+#[derive(serde::Serialize)]
+struct MemberJson {
+	ptr: String,
+	id: String,
+}
+
+#[derive(serde::Serialize)]
+struct EdgeJson {
+	from: String,
+	field: String,
+	to: String,
+}
+
+/// One connecting edge; the `--format csv` row.
+#[derive(serde::Serialize)]
+struct EdgeRow {
+	cycle: usize,
+	from: String,
+	field: String,
+	to: String,
+}
+
+#[cfg(test)]
+mod tests;
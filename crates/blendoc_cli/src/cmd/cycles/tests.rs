@@ -0,0 +1,15 @@
+use crate::cmd::test_support::{TempBlend, run_blendoc, run_blendoc_json};
+
+#[test]
+fn acyclic_scene_reports_no_cycles() {
+	let target = TempBlend::scene("cycles");
+	let target_arg = target.arg();
+
+	let text = run_blendoc(&["cycles", target_arg]);
+	let json = run_blendoc_json(&["cycles", target_arg, "--json"]);
+
+	assert!(text.status.success(), "cycles failed: {}", String::from_utf8_lossy(&text.stderr));
+	assert!(String::from_utf8_lossy(&text.stdout).contains("cycles: 0 (4 IDs"));
+	assert_eq!(json["cycles"], serde_json::json!([]));
+	assert!(json["edges"].as_u64().is_some_and(|edges| edges > 0));
+}
@@ -0,0 +1,164 @@
+use std::io::Write;
+use std::path::PathBuf;
+
+use blendoc::blend::{BlendFile, PointerCounts, PointerStorage, Result, scan_dangling_pointers};
+
+use crate::cmd::output::OutputArgs;
+use crate::cmd::util::{ptr_hex, ptr_hex_opt, render_code};
+
+#[derive(clap::Args)]
+pub struct Args {
+	pub file: PathBuf,
+	#[arg(long)]
+	pub all: bool,
+	#[arg(long)]
+	pub json: bool,
+	#[command(flatten)]
+	pub output: OutputArgs,
+}
+
+/// Classify every non-null pointer field in the file as resolved, unresolved, or out of bounds.
+///
+/// Lists blocks and fields holding at least one dangling pointer; `--all`
+/// lists every block and field that holds pointers.
+pub fn run(args: Args) -> Result<()> {
+	let Args { file: path, all, json, output } = args;
+
+	let blend = BlendFile::open(&path)?;
+	let dna = blend.dna()?;
+	let report = scan_dangling_pointers(&blend, &dna)?;
+
+	let fields: Vec<FieldRow> = report
+		.fields
+		.iter()
+		.filter(|field| all || field.counts.dangling() > 0)
+		.map(|field| FieldRow {
+			type_name: field.type_name.to_string(),
+			field: field.field.to_string(),
+			resolved: field.counts.resolved,
+			unresolved: field.counts.unresolved,
+			out_of_bounds: field.counts.out_of_bounds,
+			first_dangling: ptr_hex_opt(field.first_dangling),
+		})
+		.collect();
+	let blocks: Vec<BlockRow> = report
+		.blocks
+		.iter()
+		.filter(|block| all || block.counts.dangling() > 0)
+		.map(|block| BlockRow {
+			block_offset: format!("0x{:x}", block.block_offset),
+			code: render_code(block.code),
+			ptr: ptr_hex(block.old_ptr),
+			type_name: block.type_name.to_string(),
+			nr: block.nr,
+			counts: block.counts.into(),
+		})
+		.collect();
+
+	let storage = match report.storage {
+		PointerStorage::AddressRanges => "address_ranges",
+		PointerStorage::StableIds => "stable_ids",
+	};
+
+	let mut out = output.open(json)?;
+	if !out.is_text() {
+		let payload = DanglingJson {
+			path: path.display().to_string(),
+			storage,
+			blocks_scanned: report.blocks_scanned,
+			totals: report.totals.into(),
+			fields: &fields,
+			blocks: &blocks,
+		};
+		out.structured(&payload, &fields)?;
+		return out.finish();
+	}
+
+	writeln!(out, "path: {}", path.display())?;
+	writeln!(out, "storage: {storage}")?;
+	writeln!(out, "blocks: {}", report.blocks_scanned)?;
+	let totals = report.totals;
+	writeln!(
+		out,
+		"pointers: {}  resolved: {}  unresolved: {}  out_of_bounds: {}",
+		totals.total(),
+		totals.resolved,
+		totals.unresolved,
+		totals.out_of_bounds
+	)?;
+	if !fields.is_empty() {
+		writeln!(out, "fields:")?;
+		for row in &fields {
+			let first = row.first_dangling.as_deref().map(|ptr| format!(" first={ptr}")).unwrap_or_default();
+			writeln!(
+				out,
+				"  {}.{}\tresolved={} unresolved={} out_of_bounds={}{first}",
+				row.type_name, row.field, row.resolved, row.unresolved, row.out_of_bounds
+			)?;
+		}
+	}
+	if !blocks.is_empty() {
+		writeln!(out, "blocks:")?;
+		for row in &blocks {
+			writeln!(
+				out,
+				"  {} {} {} {}[{}]\tresolved={} unresolved={} out_of_bounds={}",
+				row.block_offset, row.code, row.ptr, row.type_name, row.nr, row.counts.resolved, row.counts.unresolved, row.counts.out_of_bounds
+			)?;
+		}
+	}
+	out.finish()
+}
+
+#[derive(serde::Serialize)]
+struct CountsJson {
+	resolved: usize,
+	unresolved: usize,
+	out_of_bounds: usize,
+}
+
+impl From<PointerCounts> for CountsJson {
+	fn from(counts: PointerCounts) -> Self {
+		Self {
+			resolved: counts.resolved,
+			unresolved: counts.unresolved,
+			out_of_bounds: counts.out_of_bounds,
+		}
+	}
+}
+
+#[derive(serde::Serialize)]
+struct DanglingJson<'a> {
+	path: String,
+	storage: &'static str,
+	blocks_scanned: usize,
+	totals: CountsJson,
+	fields: &'a [FieldRow],
+	blocks: &'a [BlockRow],
+}
+
+/// Per-field counts; also the `--format csv` row.
+#[derive(serde::Serialize)]
+struct FieldRow {
+	#[serde(rename = "type")]
+	type_name: String,
+	field: String,
+	resolved: usize,
+	unresolved: usize,
+	out_of_bounds: usize,
+	first_dangling: Option<String>,
+}
+
+#[derive(serde::Serialize)]
+struct BlockRow {
+	block_offset: String,
+	code: String,
+	ptr: String,
+	#[serde(rename = "type")]
+	type_name: String,
+	nr: u64,
+	counts: CountsJson,
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,49 @@
+use blendoc_testkit::builder::{BlendBuilder, HeaderKind};
+
+use crate::cmd::test_support::{TempBlend, run_blendoc, run_blendoc_json};
+
+/// `OBCube` whose `parent` points nowhere.
+fn dangling_file() -> Vec<u8> {
+	let mut builder = BlendBuilder::new(HeaderKind::Legacy8, 404, false);
+	let sdna = builder.sdna_mut();
+	sdna.add_type("char", 1);
+	sdna.add_type("void", 0);
+	sdna.add_struct("ID", &[("void", "*next"), ("void", "*prev"), ("void", "*lib"), ("char", "name[24]")]);
+	let object = sdna.add_struct("Object", &[("ID", "id"), ("Object", "*parent")]);
+
+	let mut payload = builder.payload();
+	payload.ptr(0).ptr(0).ptr(0).name("OBCube", 24).ptr(0xdead_0000);
+	let cube = payload.finish();
+	builder.block(*b"OB\0\0", object, 0x1000, 1, cube);
+	builder.build()
+}
+
+#[test]
+fn dangling_lists_only_broken_fields_unless_all() {
+	let good = TempBlend::scene("dangling_ok");
+	let bad = TempBlend::new("dangling_bad", &dangling_file());
+	let good_arg = good.arg();
+	let bad_arg = bad.arg();
+
+	let clean = run_blendoc_json(&["dangling", good_arg, "--json"]);
+	let everything = run_blendoc_json(&["dangling", good_arg, "--all", "--json"]);
+	let broken = run_blendoc_json(&["dangling", bad_arg, "--json"]);
+	let text = run_blendoc(&["dangling", bad_arg]);
+
+	assert_eq!(clean["totals"]["unresolved"], 0);
+	assert!(clean["fields"].as_array().expect("fields array").is_empty());
+	assert!(!everything["fields"].as_array().expect("fields array").is_empty());
+	assert!(!everything["blocks"].as_array().expect("blocks array").is_empty());
+
+	assert_eq!(broken["totals"]["unresolved"], 1);
+	let fields = broken["fields"].as_array().expect("fields array");
+	assert_eq!(fields.len(), 1);
+	assert_eq!(fields[0]["type"], "Object");
+	assert_eq!(fields[0]["field"], "parent");
+	assert_eq!(fields[0]["first_dangling"], "0x00000000dead0000");
+	assert_eq!(broken["blocks"][0]["code"], "OB");
+
+	assert!(text.status.success());
+	let stdout = String::from_utf8_lossy(&text.stdout);
+	assert!(stdout.contains("Object.parent") && stdout.contains("unresolved=1"), "{stdout}");
+}
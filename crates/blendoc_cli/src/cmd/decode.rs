@@ -1,31 +1,57 @@
 use std::path::PathBuf;
 
-use blendoc::blend::{BlendError, BlendFile, DecodeOptions, decode_block_instances};
+use blendoc::blend::{BlendError, BlendFile, DecodeOptions, WarningCollector, decode_block_instances};
 
-use crate::cmd::print::{PrintOptions, print_value};
-use crate::cmd::util::{parse_block_code, render_code};
+use crate::cmd::invocation::Invocation;
+use crate::cmd::plugins::PluginArgs;
+use crate::cmd::print::{PrintCtx, PrintOptions, print_value};
+use crate::cmd::truncation::TruncationArgs;
+use crate::cmd::util::{parse_block_code, print_warnings, render_code};
 
 #[derive(clap::Args)]
 pub struct Args {
 	pub path: PathBuf,
 	#[arg(long)]
 	pub code: String,
+	#[arg(long = "pretty-math")]
+	pub pretty_math: bool,
+	#[command(flatten)]
+	pub truncation: TruncationArgs,
+	#[command(flatten)]
+	pub plugins: PluginArgs,
 }
 
 /// Decode and print the first block matching `code`.
 pub fn run(args: Args) -> blendoc::blend::Result<()> {
-	let Args { path, code } = args;
+	let Args {
+		path,
+		code,
+		pretty_math,
+		truncation,
+		plugins,
+	} = args;
 	let block_code = parse_block_code(&code)?;
-	run_with_code(path, block_code, DecodeOptions::default(), PrintOptions::default())
+	let mut print_options = PrintOptions {
+		pretty_math,
+		..PrintOptions::default()
+	};
+	truncation.apply(&mut print_options);
+	let decode_options = DecodeOptions {
+		plugins: plugins.load()?,
+		..DecodeOptions::default()
+	};
+	run_with_code(path, block_code, decode_options, print_options)
 }
 
 /// Decode and print the first block matching a binary block code.
-pub fn run_with_code(path: PathBuf, block_code: [u8; 4], decode_options: DecodeOptions, print_options: PrintOptions) -> blendoc::blend::Result<()> {
+pub fn run_with_code(path: PathBuf, block_code: [u8; 4], mut decode_options: DecodeOptions, print_options: PrintOptions) -> blendoc::blend::Result<()> {
 	let blend = BlendFile::open(&path)?;
 	let dna = blend.dna()?;
 	let block = blend
 		.find_first_block_by_code(block_code)?
 		.ok_or(BlendError::BlockNotFound { code: block_code })?;
+	let warnings = WarningCollector::new();
+	decode_options.warnings = Some(warnings.clone());
 	let value = decode_block_instances(&dna, &block, &decode_options)?;
 
 	println!("path: {}", path.display());
@@ -34,7 +60,10 @@ pub fn run_with_code(path: PathBuf, block_code: [u8; 4], decode_options: DecodeO
 	println!("nr: {}", block.head.nr);
 	println!("len: {}", block.head.len);
 	println!("decoded:");
-	print_value(&value, 0, 0, print_options, None, 0);
+	let invocation = Invocation::default();
+	let print_ctx = PrintCtx::new(None, false, Some(&decode_options), 0, &invocation);
+	print_value(&value, 0, 0, &print_options, Some(&print_ctx), 0);
+	print_warnings(&warnings.take());
 
 	Ok(())
 }
@@ -0,0 +1,163 @@
+use std::collections::{BTreeMap, HashMap};
+use std::path::PathBuf;
+
+use blendoc::blend::{BlendFile, ClosureResult, IdGraphNode, IdGraphOptions, IdIndex, dependency_closure, dependents_closure};
+
+use crate::cmd::graph_cache::GraphCacheArgs;
+use crate::cmd::util::{emit_json, ptr_hex, scan_ids};
+
+#[derive(clap::Args)]
+pub struct Args {
+	pub file: PathBuf,
+	#[arg(long = "id")]
+	pub id_name: String,
+	#[arg(long)]
+	pub dependents: bool,
+	#[arg(long)]
+	pub depth: Option<u32>,
+	#[arg(long = "refs-depth")]
+	pub refs_depth: Option<u32>,
+	#[arg(long = "max-edges")]
+	pub max_edges: Option<usize>,
+	#[arg(long)]
+	pub json: bool,
+	#[command(flatten)]
+	pub graph_cache: GraphCacheArgs,
+}
+
+/// Print the transitive dependencies (or dependents) of one ID with depth annotations.
+pub fn run(args: Args) -> blendoc::blend::Result<()> {
+	let Args {
+		file: path,
+		id_name,
+		dependents,
+		depth,
+		refs_depth,
+		max_edges,
+		json,
+		graph_cache,
+	} = args;
+
+	let blend = BlendFile::open(&path)?;
+	let dna = blend.dna()?;
+	let index = blend.pointer_index()?;
+	let ids = IdIndex::build(scan_ids(&blend, &dna)?);
+	let root = ids.resolve_selector(&id_name)?;
+
+	let mut options = IdGraphOptions::default();
+	if let Some(refs_depth) = refs_depth {
+		options.ref_scan.max_depth = refs_depth;
+	}
+	if let Some(max_edges) = max_edges {
+		options.max_edges = max_edges;
+	}
+	let graph = graph_cache.build(&path, &blend, &dna, &index, &ids, &options)?;
+
+	let closure = if dependents {
+		dependents_closure(&graph, root.old_ptr, depth)
+	} else {
+		dependency_closure(&graph, root.old_ptr, depth)
+	};
+
+	let nodes: HashMap<u64, &IdGraphNode> = graph.nodes.iter().map(|node| (node.canonical, node)).collect();
+	let by_type = count_by_type(&closure, &nodes);
+	let truncated = graph.truncated.is_some();
+
+	if json {
+		let payload = DepsJson {
+			path: path.display().to_string(),
+			root: root.id_name.to_string(),
+			root_ptr: ptr_hex(root.old_ptr),
+			direction: closure.direction.as_str(),
+			count: closure.entries.len(),
+			max_depth: closure.max_depth(),
+			depth_limited: closure.depth_limited,
+			graph_truncated: truncated,
+			by_type: by_type.iter().map(|(name, count)| ((*name).to_owned(), *count)).collect(),
+			entries: closure
+				.entries
+				.iter()
+				.map(|entry| DepsEntryJson {
+					id: id_label(&nodes, entry.canonical),
+					type_name: type_label(&nodes, entry.canonical),
+					ptr: ptr_hex(entry.canonical),
+					depth: entry.depth,
+					via: id_label(&nodes, entry.via),
+					field: entry.field.to_string(),
+					edges: entry.edge_count,
+				})
+				.collect(),
+		};
+		emit_json(&payload);
+		return Ok(());
+	}
+
+	println!("path: {}", path.display());
+	println!("root: {} ({})", root.id_name, root.type_name);
+	println!("direction: {}", closure.direction.as_str());
+	println!("count: {}", closure.entries.len());
+	println!("max_depth: {}", closure.max_depth());
+	println!("depth_limited: {}", closure.depth_limited);
+	println!("graph_truncated: {truncated}");
+	for (name, count) in &by_type {
+		println!("type.{name}: {count}");
+	}
+	println!("depth\tid\ttype\tvia\tfield\tedges");
+	for entry in &closure.entries {
+		println!(
+			"{}\t{}\t{}\t{}\t{}\t{}",
+			entry.depth,
+			id_label(&nodes, entry.canonical),
+			type_label(&nodes, entry.canonical),
+			id_label(&nodes, entry.via),
+			entry.field,
+			entry.edge_count
+		);
+	}
+
+	Ok(())
+}
+
+fn count_by_type<'n>(closure: &ClosureResult, nodes: &HashMap<u64, &'n IdGraphNode>) -> BTreeMap<&'n str, usize> {
+	let mut out = BTreeMap::new();
+	for entry in &closure.entries {
+		if let Some(node) = nodes.get(&entry.canonical) {
+			*out.entry(node.type_name.as_ref()).or_default() += 1;
+		}
+	}
+	out
+}
+
+fn id_label(nodes: &HashMap<u64, &IdGraphNode>, ptr: u64) -> String {
+	nodes.get(&ptr).map_or_else(|| ptr_hex(ptr), |node| node.id_name.to_string())
+}
+
+fn type_label(nodes: &HashMap<u64, &IdGraphNode>, ptr: u64) -> String {
+	nodes.get(&ptr).map_or_else(|| "-".to_owned(), |node| node.type_name.to_string())
+}
+
+#[derive(serde::Serialize)]
+struct DepsEntryJson {
+	id: String,
+	#[serde(rename = "type")]
+	type_name: String,
+	ptr: String,
+	depth: u32,
+	via: String,
+	field: String,
+	edges: usize,
+}
+
+#[derive(serde::Serialize)]
+struct DepsJson {
+	path: String,
+	root: String,
+	root_ptr: String,
+	direction: &'static str,
+	count: usize,
+	max_depth: u32,
+	depth_limited: bool,
+	graph_truncated: bool,
+	by_type: BTreeMap<String, usize>,
+	entries: Vec<DepsEntryJson>,
+}
@@ -0,0 +1,139 @@
+use std::path::PathBuf;
+
+use blendoc::blend::{BlendFile, IdDiffOptions, IdDiffResult, diff_id_blocks};
+
+use crate::cmd::show::value_to_json_value;
+use crate::cmd::util::{emit_json, ptr_hex_opt, render_value};
+
+#[derive(clap::Args)]
+pub struct Args {
+	pub left: PathBuf,
+	pub right: PathBuf,
+	#[arg(long)]
+	pub fields: bool,
+	#[arg(long = "max-field-diffs")]
+	pub max_field_diffs: Option<usize>,
+	#[arg(long = "type")]
+	pub type_name: Option<String>,
+	#[arg(long)]
+	pub json: bool,
+}
+
+/// Compare ID-root records between two files and print added/removed/changed IDs.
+pub fn run(args: Args) -> blendoc::blend::Result<()> {
+	let Args {
+		left,
+		right,
+		fields,
+		max_field_diffs,
+		type_name,
+		json,
+	} = args;
+
+	let mut options = IdDiffOptions {
+		fields,
+		type_name,
+		..IdDiffOptions::default()
+	};
+	if let Some(max_field_diffs) = max_field_diffs {
+		options.max_field_diffs = max_field_diffs;
+	}
+
+	let left_blend = BlendFile::open(&left)?;
+	let left_dna = left_blend.dna()?;
+	let right_blend = BlendFile::open(&right)?;
+	let right_dna = right_blend.dna()?;
+
+	let result = diff_id_blocks(&left_blend, &left_dna, &right_blend, &right_dna, &options)?;
+
+	if json {
+		print_json(&left, &right, &result);
+		return Ok(());
+	}
+
+	println!("left: {}", left.display());
+	println!("right: {}", right.display());
+	println!("left_ids: {}", result.left_ids);
+	println!("right_ids: {}", result.right_ids);
+	println!("unchanged: {}", result.unchanged);
+	println!("entries: {}", result.entries.len());
+	println!("change\ttype\tid_name");
+	for entry in &result.entries {
+		println!("{}\t{}\t{}", entry.change.as_str(), entry.type_name, entry.id_name);
+		for field in &entry.fields {
+			println!(
+				"\t{}\t{}\t{}",
+				field.path,
+				field.left.as_ref().map_or_else(|| "-".to_owned(), render_value),
+				field.right.as_ref().map_or_else(|| "-".to_owned(), render_value)
+			);
+		}
+		if entry.fields_truncated {
+			println!("\t...");
+		}
+	}
+
+	Ok(())
+}
+
+fn print_json(left: &std::path::Path, right: &std::path::Path, result: &IdDiffResult) {
+	let payload = DiffJson {
+		left: left.display().to_string(),
+		right: right.display().to_string(),
+		left_ids: result.left_ids,
+		right_ids: result.right_ids,
+		unchanged: result.unchanged,
+		entries: result
+			.entries
+			.iter()
+			.map(|entry| DiffEntryJson {
+				change: entry.change.as_str(),
+				type_name: entry.type_name.to_string(),
+				id_name: entry.id_name.to_string(),
+				left_ptr: ptr_hex_opt(entry.left_ptr),
+				right_ptr: ptr_hex_opt(entry.right_ptr),
+				fields: entry
+					.fields
+					.iter()
+					.map(|field| FieldDiffJson {
+						path: field.path.to_string(),
+						left: field.left.as_ref().map(value_to_json_value),
+						right: field.right.as_ref().map(value_to_json_value),
+					})
+					.collect(),
+				fields_truncated: entry.fields_truncated,
+			})
+			.collect(),
+	};
+
+	emit_json(&payload);
+}
+
+#[derive(serde::Serialize)]
+struct FieldDiffJson {
+	path: String,
+	left: Option<serde_json::Value>,
+	right: Option<serde_json::Value>,
+}
+
+#[derive(serde::Serialize)]
+struct DiffEntryJson {
+	change: &'static str,
+	#[serde(rename = "type")]
+	type_name: String,
+	id_name: String,
+	left_ptr: Option<String>,
+	right_ptr: Option<String>,
+	fields: Vec<FieldDiffJson>,
+	fields_truncated: bool,
+}
+
+#[derive(serde::Serialize)]
+struct DiffJson {
+	left: String,
+	right: String,
+	left_ids: usize,
+	right_ids: usize,
+	unchanged: usize,
+	entries: Vec<DiffEntryJson>,
+}
@@ -1,22 +1,44 @@
 use std::path::PathBuf;
 
-use blendoc::blend::{BlendError, BlendFile, DnaStruct, Result};
+use blendoc::blend::{BlendError, Dna, DnaStruct, Result, enrich_error, verify_dna_layout};
+
+use crate::cmd::input::{BlendInput, print_revision};
+use crate::cmd::stats::timed;
 
 #[derive(clap::Args)]
 pub struct Args {
-	pub path: PathBuf,
+	#[arg(required_unless_present = "git")]
+	pub path: Option<PathBuf>,
+	#[arg(long, conflicts_with = "path")]
+	pub git: Option<String>,
 	#[arg(long = "struct")]
 	pub struct_name: Option<String>,
+	#[arg(long, requires = "struct_name")]
+	pub layout: bool,
+	#[arg(long)]
+	pub verify: bool,
 }
 
 /// Print SDNA table counts and optional struct field listing.
+///
+/// `--layout` adds byte offsets and sizes per field and lists padding holes.
+/// `--verify` recomputes every struct size from its fields and exits 1 when
+/// any disagrees with `TLEN`.
 pub fn run(args: Args) -> Result<()> {
-	let Args { path, struct_name } = args;
+	let Args {
+		path,
+		git,
+		struct_name,
+		layout,
+		verify,
+	} = args;
 
-	let blend = BlendFile::open(&path)?;
-	let dna = blend.dna()?;
+	let input = BlendInput::from_args(path, git)?;
+	let (blend, revision) = input.open()?;
+	let dna = timed("dna", || blend.dna())?;
 
-	println!("path: {}", path.display());
+	println!("path: {}", input.label());
+	print_revision(revision.as_ref());
 	println!("compression: {}", blend.compression.as_str());
 	println!("version: {}", blend.header.version);
 	println!("dna_names: {}", dna.names.len());
@@ -24,19 +46,72 @@ pub fn run(args: Args) -> Result<()> {
 	println!("dna_structs: {}", dna.structs.len());
 
 	if let Some(name) = struct_name {
-		let (sdna_idx, item) = find_struct_by_name(&dna, &name).ok_or(BlendError::DnaStructNotFound { name })?;
+		let (sdna_idx, item) =
+			find_struct_by_name(&dna, &name).ok_or_else(|| enrich_error(BlendError::DnaStructNotFound { name }, &dna, blend.header.version))?;
 		let type_name = dna.type_name(item.type_idx);
 		println!("struct: {}", type_name);
 		println!("sdna_index: {}", sdna_idx);
 		println!("field_count: {}", item.fields.len());
-		for field in &item.fields {
-			println!("  {} {}", dna.type_name(field.type_idx), dna.field_name(field.name_idx));
+		if layout {
+			print_layout(&dna, sdna_idx as u32);
+		} else {
+			for field in &item.fields {
+				println!("  {} {}", dna.type_name(field.type_idx), dna.field_name(field.name_idx));
+			}
 		}
 	}
 
+	if verify && !print_verify(&dna) {
+		std::process::exit(1);
+	}
 	Ok(())
 }
 
-fn find_struct_by_name<'a>(dna: &'a blendoc::blend::Dna, name: &str) -> Option<(usize, &'a DnaStruct)> {
-	dna.structs.iter().enumerate().find(|(_, item)| dna.type_name(item.type_idx) == name)
+/// Print the `TLEN` consistency check; returns whether every struct passed.
+fn print_verify(dna: &Dna) -> bool {
+	let report = verify_dna_layout(dna);
+	println!("verify: {} structs checked, {} inconsistent", report.structs_checked, report.issues.len());
+	for fixup in &dna.tlen_fixups {
+		println!(
+			"  {}: tlen {} recomputed as {} for {}-byte pointers",
+			dna.type_name(fixup.type_idx),
+			fixup.written,
+			fixup.computed,
+			dna.pointer_size
+		);
+	}
+	for issue in &report.issues {
+		println!(
+			"  {}: tlen={} fields={} computed={} align={}",
+			issue.type_name, issue.tlen, issue.fields_size, issue.computed, issue.align
+		);
+		for field in &issue.misaligned {
+			println!("    {} at offset {} needs {}-byte alignment", field.name, field.offset, field.align);
+		}
+	}
+	report.is_consistent()
+}
+
+fn print_layout(dna: &Dna, sdna_nr: u32) {
+	let (Some(fields), Some(holes)) = (dna.struct_layout(sdna_nr), dna.padding_holes(sdna_nr)) else {
+		return;
+	};
+	if let Some(item) = dna.struct_by_sdna(sdna_nr) {
+		println!("size: {}", dna.tlen[item.type_idx as usize]);
+	}
+	println!("offset\tsize\ttype\tfield");
+	for field in &fields {
+		let pad = if field.is_padding { "\tpadding" } else { "" };
+		println!("{}\t{}\t{}\t{}{pad}", field.offset, field.size, field.type_name, field.decl);
+	}
+	let total: usize = holes.iter().map(|hole| hole.size).sum();
+	println!("padding: {total} bytes in {} holes", holes.len());
+	for hole in &holes {
+		let label = hole.field.as_deref().unwrap_or("past last field");
+		println!("  {}\t{}\t{label}", hole.offset, hole.size);
+	}
+}
+
+fn find_struct_by_name<'a>(dna: &'a Dna, name: &str) -> Option<(usize, &'a DnaStruct)> {
+	dna.struct_by_name(name).map(|(sdna_idx, item)| (sdna_idx as usize, item))
 }
@@ -0,0 +1,166 @@
+use std::path::PathBuf;
+
+use blendoc::blend::{BlendFile, DnaDiffResult, DnaFieldSide, diff_dna};
+
+use crate::cmd::util::emit_json;
+
+#[derive(clap::Args)]
+pub struct Args {
+	pub left: PathBuf,
+	pub right: PathBuf,
+	#[arg(long = "struct")]
+	pub struct_name: Option<String>,
+	#[arg(long)]
+	pub json: bool,
+}
+
+/// Compare SDNA struct definitions between two files and print schema changes.
+pub fn run(args: Args) -> blendoc::blend::Result<()> {
+	let Args {
+		left,
+		right,
+		struct_name,
+		json,
+	} = args;
+
+	let left_blend = BlendFile::open_mmap(&left)?;
+	let left_dna = left_blend.dna()?;
+	let right_blend = BlendFile::open_mmap(&right)?;
+	let right_dna = right_blend.dna()?;
+
+	let mut result = diff_dna(&left_dna, &right_dna);
+	if let Some(filter) = struct_name {
+		result.structs.retain(|item| item.name.as_ref() == filter.as_str());
+	}
+
+	if json {
+		print_json(&left, &right, &left_blend, &right_blend, &result);
+		return Ok(());
+	}
+
+	println!("left: {}", left.display());
+	println!("right: {}", right.display());
+	println!("left_version: {}", left_blend.header.version);
+	println!("right_version: {}", right_blend.header.version);
+	println!("left_structs: {}", result.left_structs);
+	println!("right_structs: {}", result.right_structs);
+	println!("unchanged: {}", result.unchanged);
+	println!("entries: {}", result.structs.len());
+	println!("change\tstruct\tleft_size\tright_size");
+	for item in &result.structs {
+		println!(
+			"{}\t{}\t{}\t{}",
+			item.change.as_str(),
+			item.name,
+			size_text(item.left_size),
+			size_text(item.right_size)
+		);
+		if item.reordered {
+			println!("\treordered");
+		}
+		for field in &item.fields {
+			println!(
+				"\t{}\t{}\t{}\t{}",
+				field.change.as_str(),
+				field.name,
+				side_text(field.left.as_ref()),
+				side_text(field.right.as_ref())
+			);
+		}
+	}
+
+	Ok(())
+}
+
+fn size_text(size: Option<u16>) -> String {
+	size.map_or_else(|| "-".to_owned(), |size| size.to_string())
+}
+
+fn side_text(side: Option<&DnaFieldSide>) -> String {
+	side.map_or_else(|| "-".to_owned(), |side| format!("{} {}", side.type_name, side.decl))
+}
+
+fn print_json(left: &std::path::Path, right: &std::path::Path, left_blend: &BlendFile, right_blend: &BlendFile, result: &DnaDiffResult) {
+	let payload = DnaDiffJson {
+		left: left.display().to_string(),
+		right: right.display().to_string(),
+		left_version: left_blend.header.version,
+		right_version: right_blend.header.version,
+		left_structs: result.left_structs,
+		right_structs: result.right_structs,
+		unchanged: result.unchanged,
+		structs: result
+			.structs
+			.iter()
+			.map(|item| DnaStructDiffJson {
+				change: item.change.as_str(),
+				name: item.name.to_string(),
+				left_size: item.left_size,
+				right_size: item.right_size,
+				reordered: item.reordered,
+				fields: item
+					.fields
+					.iter()
+					.map(|field| DnaFieldDiffJson {
+						change: field.change.as_str(),
+						name: field.name.to_string(),
+						left: field.left.as_ref().map(DnaFieldSideJson::from),
+						right: field.right.as_ref().map(DnaFieldSideJson::from),
+					})
+					.collect(),
+			})
+			.collect(),
+	};
+
+	emit_json(&payload);
+}
+
+#[derive(serde::Serialize)]
+struct DnaFieldSideJson {
+	#[serde(rename = "type")]
+	type_name: String,
+	decl: String,
+	offset: usize,
+	size: usize,
+}
+
+impl From<&DnaFieldSide> for DnaFieldSideJson {
+	fn from(side: &DnaFieldSide) -> Self {
+		Self {
+			type_name: side.type_name.to_string(),
+			decl: side.decl.to_string(),
+			offset: side.offset,
+			size: side.size,
+		}
+	}
+}
+
+#[derive(serde::Serialize)]
+struct DnaFieldDiffJson {
+	change: &'static str,
+	name: String,
+	left: Option<DnaFieldSideJson>,
+	right: Option<DnaFieldSideJson>,
+}
+
+#[derive(serde::Serialize)]
+struct DnaStructDiffJson {
+	change: &'static str,
+	name: String,
+	left_size: Option<u16>,
+	right_size: Option<u16>,
+	reordered: bool,
+	fields: Vec<DnaFieldDiffJson>,
+}
+
+#[derive(serde::Serialize)]
+struct DnaDiffJson {
+	left: String,
+	right: String,
+	left_version: u16,
+	right_version: u16,
+	left_structs: usize,
+	right_structs: usize,
+	unchanged: usize,
+	structs: Vec<DnaStructDiffJson>,
+}
@@ -0,0 +1,216 @@
+use std::path::Path;
+use std::process::Command;
+
+use blendoc::blend::{BlendFile, Compression, Result, codec_support, effective_threads};
+
+use crate::cmd::result_cache::default_cache_dir;
+use crate::cmd::selftest::{VARIANTS, run_variant};
+use crate::cmd::util::{emit_json, human_bytes};
+
+#[derive(clap::Args)]
+pub struct Args {
+	#[arg(long)]
+	pub json: bool,
+}
+
+/// Report build features, runtime environment, and cache state, then run a
+/// quick self-check on the bundled synthetic scene files.
+///
+/// The text form is the report to paste into bug reports. Exits with status 1
+/// when a self-check fails.
+pub fn run(args: Args) -> Result<()> {
+	let codecs = codec_support();
+	let features = FeaturesJson {
+		gzip: codecs.gzip,
+		zstd_decode: codecs.zstd_decode,
+		zstd_encode: codecs.zstd_encode,
+		mmap: BlendFile::mmap_supported(),
+		sqlite: cfg!(feature = "sqlite"),
+		trace: cfg!(feature = "trace"),
+		threads: effective_threads(0),
+	};
+	let environment = EnvironmentJson {
+		memory_available: std::fs::read_to_string("/proc/meminfo").ok().and_then(|text| mem_available(&text)),
+		graphviz: graphviz_version(),
+		pager: std::env::var("PAGER").ok().filter(|pager| !pager.is_empty()),
+	};
+	let cache = default_cache_dir().map(|dir| cache_status(&dir));
+	let self_check = self_check(codecs.zstd_encode.is_some());
+
+	let report = DoctorJson {
+		version: env!("CARGO_PKG_VERSION"),
+		os: std::env::consts::OS,
+		arch: std::env::consts::ARCH,
+		features,
+		environment,
+		cache,
+		self_check,
+	};
+	if args.json {
+		emit_json(&report);
+	} else {
+		print_report(&report);
+	}
+
+	if report.self_check.failed > 0 {
+		std::process::exit(1);
+	}
+	Ok(())
+}
+
+fn print_report(report: &DoctorJson) {
+	let features = &report.features;
+	let environment = &report.environment;
+	println!("blendoc doctor {}", report.version);
+	println!("target: {}-{}", report.os, report.arch);
+	println!("features:");
+	println!("  gzip: {}", features.gzip);
+	println!("  zstd_decode: {}", features.zstd_decode.unwrap_or("unavailable"));
+	println!("  zstd_encode: {}", features.zstd_encode.unwrap_or("unavailable"));
+	println!("  mmap: {}", yes_no(features.mmap));
+	println!("  sqlite: {}", yes_no(features.sqlite));
+	println!("  trace: {}", yes_no(features.trace));
+	println!("  threads: {}", features.threads);
+	println!("environment:");
+	match environment.memory_available {
+		Some(bytes) => println!("  memory_available: {}", human_bytes(bytes)),
+		None => println!("  memory_available: unknown"),
+	}
+	println!("  graphviz: {}", environment.graphviz.as_deref().unwrap_or("not found"));
+	println!("  pager: {}", environment.pager.as_deref().unwrap_or("-"));
+	match &report.cache {
+		Some(cache) if cache.exists => println!("cache: {} ({} entries, {})", cache.results_dir, cache.entries, human_bytes(cache.bytes)),
+		Some(cache) => println!("cache: {} (not created)", cache.results_dir),
+		None => println!("cache: - (neither XDG_CACHE_HOME nor HOME is set)"),
+	}
+	let check = &report.self_check;
+	println!(
+		"self_check: {}/{} passed ({} variants)",
+		check.passed,
+		check.passed + check.failed,
+		check.variants.len()
+	);
+	for failure in &check.failures {
+		println!("  FAIL {} {}: {}", failure.variant, failure.check, failure.message);
+	}
+}
+
+fn yes_no(flag: bool) -> &'static str {
+	if flag { "yes" } else { "no" }
+}
+
+/// `MemAvailable` from `/proc/meminfo` text, in bytes.
+fn mem_available(meminfo: &str) -> Option<u64> {
+	let line = meminfo.lines().find_map(|line| line.strip_prefix("MemAvailable:"))?;
+	let kib = line.trim().strip_suffix("kB")?.trim().parse::<u64>().ok()?;
+	Some(kib * 1024)
+}
+
+/// First line of `dot -V`, which Graphviz prints on stderr.
+fn graphviz_version() -> Option<String> {
+	let output = Command::new("dot").arg("-V").output().ok()?;
+	if !output.status.success() {
+		return None;
+	}
+	let text = if output.stderr.is_empty() { output.stdout } else { output.stderr };
+	let text = String::from_utf8_lossy(&text);
+	text.lines().next().map(|line| line.trim().to_owned())
+}
+
+fn cache_status(dir: &Path) -> CacheJson {
+	let mut cache = CacheJson {
+		results_dir: dir.display().to_string(),
+		exists: dir.is_dir(),
+		entries: 0,
+		bytes: 0,
+	};
+	for entry in std::fs::read_dir(dir).into_iter().flatten().flatten() {
+		if entry.path().extension().is_some_and(|ext| ext == "json") {
+			cache.entries += 1;
+			cache.bytes += entry.metadata().map_or(0, |meta| meta.len());
+		}
+	}
+	cache
+}
+
+/// Run every `selftest` variant this build can encode.
+fn self_check(zstd_encode: bool) -> SelfCheckJson {
+	let mut check = SelfCheckJson {
+		variants: Vec::new(),
+		passed: 0,
+		failed: 0,
+		failures: Vec::new(),
+	};
+	for variant in VARIANTS.iter().filter(|item| zstd_encode || item.compression != Compression::Zstd) {
+		let report = run_variant(variant);
+		for item in report.checks {
+			if item.ok {
+				check.passed += 1;
+			} else {
+				check.failed += 1;
+				check.failures.push(FailureJson {
+					variant: report.name,
+					check: item.feature,
+					message: item.message.unwrap_or_default(),
+				});
+			}
+		}
+		check.variants.push(report.name);
+	}
+	check
+}
+
+#[derive(serde::Serialize)]
+struct DoctorJson {
+	version: &'static str,
+	os: &'static str,
+	arch: &'static str,
+	features: FeaturesJson,
+	environment: EnvironmentJson,
+	cache: Option<CacheJson>,
+	self_check: SelfCheckJson,
+}
+
+#[derive(serde::Serialize)]
+struct FeaturesJson {
+	gzip: &'static str,
+	zstd_decode: Option<&'static str>,
+	zstd_encode: Option<&'static str>,
+	mmap: bool,
+	sqlite: bool,
+	trace: bool,
+	threads: usize,
+}
+
+#[derive(serde::Serialize)]
+struct EnvironmentJson {
+	memory_available: Option<u64>,
+	graphviz: Option<String>,
+	pager: Option<String>,
+}
+
+#[derive(serde::Serialize)]
+struct CacheJson {
+	results_dir: String,
+	exists: bool,
+	entries: usize,
+	bytes: u64,
+}
+
+#[derive(serde::Serialize)]
+struct SelfCheckJson {
+	variants: Vec<&'static str>,
+	passed: usize,
+	failed: usize,
+	failures: Vec<FailureJson>,
+}
+
+#[derive(serde::Serialize)]
+struct FailureJson {
+	variant: &'static str,
+	check: &'static str,
+	message: String,
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,24 @@
+use super::mem_available;
+use crate::cmd::test_support::{run_blendoc, run_blendoc_json};
+
+#[test]
+fn meminfo_available_line_converts_to_bytes() {
+	let meminfo = "MemTotal:       16318412 kB\nMemFree:         1208220 kB\nMemAvailable:    8159206 kB\n";
+	assert_eq!(mem_available(meminfo), Some(8_159_206 * 1024));
+	assert_eq!(mem_available("MemTotal: 1 kB\n"), None);
+}
+
+#[test]
+fn report_lists_features_and_passes_self_check() {
+	let text = run_blendoc(&["doctor"]);
+	assert!(text.status.success(), "doctor failed: {}", String::from_utf8_lossy(&text.stderr));
+	let stdout = String::from_utf8_lossy(&text.stdout);
+	for line in ["features:", "  gzip: flate2", "environment:", "self_check: "] {
+		assert!(stdout.contains(line), "missing {line:?} in:\n{stdout}");
+	}
+
+	let json = run_blendoc_json(&["doctor", "--json"]);
+	assert_eq!(json["self_check"]["failed"], 0);
+	assert!(json["features"]["mmap"].is_boolean());
+	assert!(!json["self_check"]["variants"].as_array().expect("variants array").is_empty());
+}
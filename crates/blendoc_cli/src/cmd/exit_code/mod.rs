@@ -0,0 +1,51 @@
+use blendoc::blend::BlendError;
+
+/// The command failed: a parse or decode error, or anything without a more specific code.
+pub(crate) const FAILURE: i32 = 1;
+/// A requested ID, struct, block, field, or pointer target is not in the file.
+pub(crate) const NOT_FOUND: i32 = 2;
+/// An argument was malformed, ambiguous, or rejected by the argument parser.
+pub(crate) const USAGE: i32 = 3;
+/// The input could not be read: a missing or unreadable file, or a failed `git` lookup.
+pub(crate) const IO: i32 = 4;
+
+/// Exit status for a command that returned `err`.
+///
+/// Commands that document their own codes (`has`, `validate`, `open-check`)
+/// exit before their errors reach `main` and keep those codes.
+pub(crate) fn for_error(err: &BlendError) -> i32 {
+	match err {
+		BlendError::Hinted { source, .. } => for_error(source),
+		BlendError::IdRecordNotFound { .. }
+		| BlendError::DnaStructNotFound { .. }
+		| BlendError::BlockNotFound { .. }
+		| BlendError::BlockOccurrenceOutOfRange { .. }
+		| BlendError::NodeTreeNotFound { .. }
+		| BlendError::WalkMissingNextField { .. }
+		| BlendError::ChaseMissingField { .. }
+		| BlendError::ChaseUnresolvedPtr { .. } => NOT_FOUND,
+		BlendError::InvalidBlockCode { .. }
+		| BlendError::InvalidChaseRoot
+		| BlendError::InvalidPointerLiteral { .. }
+		| BlendError::InvalidFieldPath { .. }
+		| BlendError::FieldPathFansOut { .. }
+		| BlendError::InvalidSearchPredicate { .. }
+		| BlendError::InvalidExpression { .. }
+		| BlendError::InvalidCompressionLevel { .. }
+		| BlendError::IdSelectorAmbiguous { .. } => USAGE,
+		BlendError::Io(_) | BlendError::GitInput { .. } => IO,
+		_ => FAILURE,
+	}
+}
+
+/// Print a command-line parse error (or `--help`/`--version`) and exit.
+///
+/// clap exits `2` on its own, which would read as [`NOT_FOUND`]; usage
+/// errors exit [`USAGE`] instead.
+pub(crate) fn exit_clap(err: clap::Error) -> ! {
+	let _ = err.print();
+	std::process::exit(if err.use_stderr() { USAGE } else { 0 })
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,41 @@
+use blendoc_testkit::builder::{HeaderKind, scene_file};
+
+use super::{FAILURE, IO, NOT_FOUND, USAGE};
+use crate::cmd::test_support::run_blendoc;
+
+#[test]
+fn errors_map_to_distinct_exit_codes_and_quiet_keeps_them() {
+	let dir = std::env::temp_dir().join(format!("blendoc_exit_code_{}", std::process::id()));
+	std::fs::create_dir_all(&dir).expect("temp dir created");
+	let scene = dir.join("scene.blend");
+	let broken = dir.join("broken.blend");
+	std::fs::write(&scene, scene_file(HeaderKind::Legacy8, false)).expect("synthetic file written");
+	std::fs::write(&broken, b"BLENDER-v300 truncated").expect("broken file written");
+	let scene = scene.to_string_lossy().into_owned();
+	let broken = broken.to_string_lossy().into_owned();
+	let missing = dir.join("missing.blend").to_string_lossy().into_owned();
+
+	let found = run_blendoc(&["id", &scene, "--id", "SCScene"]);
+	let not_found = run_blendoc(&["id", &scene, "--id", "OBNope"]);
+	let bad_code = run_blendoc(&["decode", &scene, "--code", "TOOLONG"]);
+	let bad_flag = run_blendoc(&["ids", &scene, "--no-such-flag"]);
+	let unreadable = run_blendoc(&["ids", &missing]);
+	let corrupt = run_blendoc(&["ids", &broken]);
+	let quiet_found = run_blendoc(&["id", &scene, "--id", "SCScene", "--quiet"]);
+	let quiet_missing = run_blendoc(&["--quiet", "id", &scene, "--id", "OBNope"]);
+	std::fs::remove_dir_all(&dir).expect("temp dir removed");
+
+	assert_eq!(found.status.code(), Some(0));
+	assert!(!found.stdout.is_empty());
+	assert_eq!(not_found.status.code(), Some(NOT_FOUND));
+	assert!(String::from_utf8_lossy(&not_found.stderr).contains("ID record not found"));
+	assert_eq!(bad_code.status.code(), Some(USAGE));
+	assert_eq!(bad_flag.status.code(), Some(USAGE));
+	assert_eq!(unreadable.status.code(), Some(IO));
+	assert_eq!(corrupt.status.code(), Some(FAILURE));
+
+	assert_eq!(quiet_found.status.code(), Some(0));
+	assert!(quiet_found.stdout.is_empty() && quiet_found.stderr.is_empty());
+	assert_eq!(quiet_missing.status.code(), Some(NOT_FOUND));
+	assert!(quiet_missing.stdout.is_empty() && quiet_missing.stderr.is_empty());
+}
@@ -0,0 +1,238 @@
+use std::collections::BTreeMap;
+use std::path::PathBuf;
+
+use blendoc::blend::{BlendFile, DecodeOptions, Dna, FieldValue, IdIndex, PointerIndex, Result, Value, codes, decode_block_instances, decode_ptr_instance};
+use serde_json::{Map, Value as JsonValue};
+
+use crate::cmd::output::{OutputArgs, OutputFormat};
+use crate::cmd::util::{ptr_hex, render_code, scan_ids};
+
+#[derive(clap::Args)]
+pub struct Args {
+	pub file: PathBuf,
+	#[arg(long, value_delimiter = ',', value_name = "TYPE,...")]
+	pub types: Vec<String>,
+	#[arg(long = "expand-depth", default_value_t = 1)]
+	pub expand_depth: u32,
+	#[arg(long = "expand-max-nodes", default_value_t = 4096)]
+	pub expand_max_nodes: usize,
+	#[arg(long, value_enum)]
+	pub format: Option<OutputFormat>,
+	#[arg(long, short = 'o', visible_alias = "output")]
+	pub out: Option<PathBuf>,
+}
+
+/// Decode every matching ID into one JSON document keyed by ID name.
+///
+/// Pointers to other IDs become `{ptr, id}` references. Pointers to owned
+/// data are replaced by `{ptr, data}` with the decoded target, down to
+/// `--expand-depth` hops; a pointer to the start of a `DATA` block decodes
+/// every element of the block.
+pub fn run(args: Args) -> Result<()> {
+	let Args {
+		file: path,
+		types,
+		expand_depth,
+		expand_max_nodes,
+		format,
+		out,
+	} = args;
+
+	let blend = BlendFile::open(&path)?;
+	let dna = blend.dna()?;
+	let index = PointerIndex::build(&blend)?;
+	let ids = IdIndex::build(scan_ids(&blend, &dna)?);
+	let decode = DecodeOptions::default();
+
+	let mut records: Vec<_> = ids
+		.records
+		.iter()
+		.filter(|record| types.is_empty() || types.iter().any(|name| name == record.type_name.as_ref()))
+		.collect();
+	records.sort_by_key(|record| record.old_ptr);
+
+	let mut exporter = Exporter {
+		dna: &dna,
+		index: &index,
+		ids: &ids,
+		decode: &decode,
+		max_nodes: expand_max_nodes,
+		expanded: 0,
+		truncated: false,
+		stack: Vec::new(),
+	};
+	let mut entries = BTreeMap::new();
+	let mut rows = Vec::with_capacity(records.len());
+	for record in records {
+		let mut key = record.id_name.to_string();
+		let mut duplicate = 0;
+		while entries.contains_key(&key) {
+			duplicate += 1;
+			key = format!("{} #{duplicate}", record.id_name);
+		}
+
+		let (fields, error) = match decode_ptr_instance(&dna, &index, record.old_ptr, &decode) {
+			Ok((canonical, value)) => {
+				exporter.stack.push(canonical);
+				let fields = exporter.fields(&value.fields, expand_depth);
+				exporter.stack.pop();
+				(Some(fields), None)
+			}
+			Err(err) => (None, Some(err.to_string())),
+		};
+		let lib = record.lib.filter(|lib| *lib != 0).map(|lib| exporter.id_label(lib));
+		rows.push(ExportRow {
+			key: key.clone(),
+			type_name: record.type_name.to_string(),
+			code: render_code(record.code),
+			ptr: ptr_hex(record.old_ptr),
+		});
+		entries.insert(
+			key,
+			ExportId {
+				type_name: record.type_name.to_string(),
+				code: render_code(record.code),
+				ptr: ptr_hex(record.old_ptr),
+				lib,
+				fields,
+				error,
+			},
+		);
+	}
+
+	let document = ExportJson {
+		path: path.display().to_string(),
+		version: blend.header.version,
+		pointer_size: blend.header.pointer_size,
+		expand_depth,
+		expanded: exporter.expanded,
+		truncated: exporter.truncated,
+		ids: entries,
+	};
+	let output = OutputArgs { output: out, format };
+	let mut out = output.open(true)?;
+	out.structured(&document, &rows)?;
+	out.finish()
+}
+
+/// Converts decoded values to JSON, expanding data pointers within a node budget.
+struct Exporter<'a, 'b> {
+	dna: &'a Dna,
+	index: &'a PointerIndex<'b>,
+	ids: &'a IdIndex,
+	decode: &'a DecodeOptions,
+	max_nodes: usize,
+	expanded: usize,
+	truncated: bool,
+	stack: Vec<u64>,
+}
+
+impl Exporter<'_, '_> {
+	fn fields(&mut self, fields: &[FieldValue], expand_left: u32) -> JsonValue {
+		let map: Map<String, JsonValue> = fields
+			.iter()
+			.map(|field| (field.name.to_string(), self.value(&field.value, expand_left)))
+			.collect();
+		JsonValue::Object(map)
+	}
+
+	fn value(&mut self, value: &Value, expand_left: u32) -> JsonValue {
+		match value {
+			Value::Null => JsonValue::Null,
+			Value::Bool(v) => serde_json::json!(v),
+			Value::I64(v) => serde_json::json!(v),
+			Value::U64(v) => serde_json::json!(v),
+			Value::F32(v) => serde_json::json!(v),
+			Value::F64(v) => serde_json::json!(v),
+			Value::Bytes(v) => JsonValue::Array(v.iter().map(|item| serde_json::json!(item)).collect()),
+			Value::String(v) => serde_json::json!(v),
+			Value::Ptr(ptr) => self.ptr(*ptr, expand_left),
+			Value::Array(items) => JsonValue::Array(items.iter().map(|item| self.value(item, expand_left)).collect()),
+			Value::Struct(item) => serde_json::json!({
+				"type": item.type_name.as_ref(),
+				"fields": self.fields(&item.fields, expand_left),
+			}),
+		}
+	}
+
+	fn ptr(&mut self, ptr: u64, expand_left: u32) -> JsonValue {
+		if ptr == 0 {
+			return JsonValue::Null;
+		}
+		if self.ids.get_by_ptr(ptr).is_some() {
+			return serde_json::json!({ "ptr": ptr_hex(ptr), "id": self.id_label(ptr) });
+		}
+		if expand_left == 0 {
+			return serde_json::json!(ptr_hex(ptr));
+		}
+		let Some((canonical, decoded)) = self.decode_target(ptr) else {
+			return serde_json::json!(ptr_hex(ptr));
+		};
+		if self.stack.contains(&canonical) {
+			return serde_json::json!(ptr_hex(ptr));
+		}
+		if self.expanded >= self.max_nodes {
+			self.truncated = true;
+			return serde_json::json!(ptr_hex(ptr));
+		}
+
+		self.expanded += 1;
+		self.stack.push(canonical);
+		let data = self.value(&decoded, expand_left - 1);
+		self.stack.pop();
+		serde_json::json!({ "ptr": ptr_hex(ptr), "data": data })
+	}
+
+	/// Whole `DATA` block when `ptr` is its start, otherwise the element it points into.
+	fn decode_target(&self, ptr: u64) -> Option<(u64, Value)> {
+		let resolved = self.index.resolve(ptr)?;
+		let block = resolved.entry.block;
+		if resolved.byte_offset == 0 && block.head.code == codes::DATA && block.head.nr > 1 {
+			let value = decode_block_instances(self.dna, &block, self.decode).ok()?;
+			return Some((resolved.entry.start_old, value));
+		}
+		let (canonical, value) = decode_ptr_instance(self.dna, self.index, ptr, self.decode).ok()?;
+		Some((canonical, Value::Struct(value)))
+	}
+
+	fn id_label(&self, ptr: u64) -> String {
+		self.ids.get_by_ptr(ptr).map_or_else(|| ptr_hex(ptr), |record| record.id_name.to_string())
+	}
+}
+
+#[derive(serde::Serialize)]
+struct ExportJson {
+	path: String,
+	version: u16,
+	pointer_size: usize,
+	expand_depth: u32,
+	expanded: usize,
+	truncated: bool,
+	ids: BTreeMap<String, ExportId>,
+}
+
+#[derive(serde::Serialize)]
+struct ExportId {
+	#[serde(rename = "type")]
+	type_name: String,
+	code: String,
+	ptr: String,
+	#[serde(skip_serializing_if = "Option::is_none")]
+	lib: Option<String>,
+	fields: Option<JsonValue>,
+	#[serde(skip_serializing_if = "Option::is_none")]
+	error: Option<String>,
+}
+
+/// One exported ID; the `--format csv` row.
+#[derive(serde::Serialize)]
+struct ExportRow {
+	key: String,
+	#[serde(rename = "type")]
+	type_name: String,
+	code: String,
+	ptr: String,
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,23 @@
+use blendoc_testkit::builder::SCENE_VERT_COUNT;
+
+use crate::cmd::test_support::{TempBlend, run_blendoc_json};
+
+#[test]
+fn export_keys_ids_by_name_and_expands_owned_data() {
+	let target = TempBlend::scene("export");
+	let target_arg = target.arg();
+
+	let json = run_blendoc_json(&["export", target_arg, "--types", "Object,Mesh"]);
+	let shallow = run_blendoc_json(&["export", target_arg, "--types", "Mesh", "--expand-depth", "0"]);
+
+	let ids = json["ids"].as_object().expect("ids object");
+	assert_eq!(ids.keys().collect::<Vec<_>>(), ["MECamera", "OBCamera"]);
+	assert_eq!(json["ids"]["OBCamera"]["type"], "Object");
+	assert_eq!(json["ids"]["OBCamera"]["fields"]["data"]["id"], "MECamera");
+	let verts = json["ids"]["MECamera"]["fields"]["mvert"]["data"].as_array().expect("expanded vertex block");
+	assert_eq!(verts.len(), SCENE_VERT_COUNT as usize);
+	assert_eq!(verts[0]["type"], "MVert");
+	assert_eq!(json["expanded"], 1);
+
+	assert!(shallow["ids"]["MECamera"]["fields"]["mvert"].is_string(), "{shallow}");
+}
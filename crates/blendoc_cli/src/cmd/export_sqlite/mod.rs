@@ -0,0 +1,176 @@
+use std::io;
+use std::path::PathBuf;
+
+use blendoc::blend::{BlendFile, IdGraphOptions, IdIndex, Result, build_id_graph, codes, scan_library_records};
+use rusqlite::{Connection, params};
+
+use crate::cmd::util::{render_code, scan_ids};
+
+/// Table definitions; pointers are stored as their 64-bit pattern in `INTEGER` columns.
+const SCHEMA: &str = "
+CREATE TABLE blocks (
+	idx INTEGER PRIMARY KEY,
+	code TEXT NOT NULL,
+	file_offset INTEGER NOT NULL,
+	old_ptr INTEGER NOT NULL,
+	sdna_nr INTEGER NOT NULL,
+	type TEXT,
+	nr INTEGER NOT NULL,
+	len INTEGER NOT NULL,
+	owner INTEGER
+);
+CREATE TABLE ids (
+	ptr INTEGER PRIMARY KEY,
+	code TEXT NOT NULL,
+	type TEXT NOT NULL,
+	name TEXT NOT NULL,
+	lib INTEGER
+);
+CREATE TABLE refs (
+	owner INTEGER NOT NULL,
+	field TEXT NOT NULL,
+	target INTEGER NOT NULL
+);
+CREATE TABLE libraries (
+	ptr INTEGER PRIMARY KEY,
+	name TEXT NOT NULL,
+	path TEXT NOT NULL,
+	relative INTEGER NOT NULL
+);
+CREATE INDEX blocks_owner ON blocks (owner);
+CREATE INDEX refs_owner ON refs (owner);
+CREATE INDEX refs_target ON refs (target);
+";
+
+#[derive(clap::Args)]
+pub struct Args {
+	pub file: PathBuf,
+	pub out: PathBuf,
+	#[arg(long = "refs-depth")]
+	pub refs_depth: Option<u32>,
+	#[arg(long = "max-edges")]
+	pub max_edges: Option<usize>,
+}
+
+/// Write blocks, IDs, ID references, and libraries into a fresh SQLite database.
+///
+/// `blocks.owner` is the ID a `DATA` block belongs to (the last ID block
+/// before it); `refs` holds the whole-file ID graph edges.
+pub fn run(args: Args) -> Result<()> {
+	let Args {
+		file: path,
+		out,
+		refs_depth,
+		max_edges,
+	} = args;
+
+	let blend = BlendFile::open(&path)?;
+	let dna = blend.dna()?;
+	let index = blend.pointer_index()?;
+	let ids = IdIndex::build(scan_ids(&blend, &dna)?);
+	let libraries = scan_library_records(&blend, &dna)?;
+
+	let mut options = IdGraphOptions::default();
+	if let Some(refs_depth) = refs_depth {
+		options.ref_scan.max_depth = refs_depth;
+	}
+	if let Some(max_edges) = max_edges {
+		options.max_edges = max_edges;
+	}
+	let graph = build_id_graph(&dna, &index, &ids, &options)?;
+
+	if out.exists() {
+		std::fs::remove_file(&out)?;
+	}
+	let mut db = Connection::open(&out).map_err(io::Error::other)?;
+	let tx = db.transaction().map_err(io::Error::other)?;
+	tx.execute_batch(SCHEMA).map_err(io::Error::other)?;
+
+	let mut counts = [0_usize; 4];
+	{
+		let mut insert = tx
+			.prepare("INSERT INTO blocks VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9)")
+			.map_err(io::Error::other)?;
+		let mut owner = None;
+		for (idx, block) in blend.blocks().enumerate() {
+			let block = block?;
+			if block.head.code != codes::DATA {
+				owner = ids
+					.get_by_ptr(block.head.old)
+					.filter(|record| record.sdna_nr == block.head.sdna_nr && record.code == block.head.code)
+					.map(|record| record.old_ptr);
+			}
+			let type_name = if codes::is_raw_code(block.head.code) {
+				None
+			} else {
+				dna.struct_by_sdna(block.head.sdna_nr).map(|item| dna.type_name(item.type_idx))
+			};
+			insert
+				.execute(params![
+					idx as i64,
+					render_code(block.head.code),
+					block.file_offset as i64,
+					sql_ptr(block.head.old),
+					block.head.sdna_nr,
+					type_name,
+					block.head.nr as i64,
+					block.head.len as i64,
+					owner.filter(|_| block.head.code == codes::DATA).map(sql_ptr),
+				])
+				.map_err(io::Error::other)?;
+			counts[0] += 1;
+		}
+
+		let mut insert = tx.prepare("INSERT INTO ids VALUES (?1, ?2, ?3, ?4, ?5)").map_err(io::Error::other)?;
+		for record in &ids.records {
+			insert
+				.execute(params![
+					sql_ptr(record.old_ptr),
+					render_code(record.code),
+					record.type_name.as_ref(),
+					record.id_name.as_ref(),
+					record.lib.filter(|lib| *lib != 0).map(sql_ptr),
+				])
+				.map_err(io::Error::other)?;
+			counts[1] += 1;
+		}
+
+		let mut insert = tx.prepare("INSERT INTO refs VALUES (?1, ?2, ?3)").map_err(io::Error::other)?;
+		for edge in &graph.edges {
+			insert
+				.execute(params![sql_ptr(edge.from), edge.field.as_ref(), sql_ptr(edge.to)])
+				.map_err(io::Error::other)?;
+			counts[2] += 1;
+		}
+
+		let mut insert = tx.prepare("INSERT INTO libraries VALUES (?1, ?2, ?3, ?4)").map_err(io::Error::other)?;
+		for library in &libraries {
+			insert
+				.execute(params![
+					sql_ptr(library.id_ptr),
+					library.id_name.as_ref(),
+					library.library_path.as_ref(),
+					library.is_relative,
+				])
+				.map_err(io::Error::other)?;
+			counts[3] += 1;
+		}
+	}
+	tx.commit().map_err(io::Error::other)?;
+
+	println!("path: {}", path.display());
+	println!("out: {}", out.display());
+	println!("blocks: {}", counts[0]);
+	println!("ids: {}", counts[1]);
+	println!("refs: {}{}", counts[2], if graph.truncated.is_some() { " (truncated)" } else { "" });
+	println!("libraries: {}", counts[3]);
+	Ok(())
+}
+
+/// Pointer bits as SQLite's signed 64-bit integer.
+fn sql_ptr(ptr: u64) -> i64 {
+	ptr as i64
+}
+
+#[cfg(test)]
+mod tests;
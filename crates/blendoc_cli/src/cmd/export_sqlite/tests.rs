@@ -0,0 +1,45 @@
+use blendoc_testkit::builder::{HeaderKind, SCENE_VERT_COUNT, scene_file};
+use rusqlite::Connection;
+
+use super::{Args, run};
+
+#[test]
+fn export_sqlite_writes_queryable_tables() {
+	let dir = std::env::temp_dir().join(format!("blendoc_export_sqlite_{}", std::process::id()));
+	std::fs::create_dir_all(&dir).expect("temp dir created");
+	let blend = dir.join("scene.blend");
+	let db = dir.join("scene.db");
+	std::fs::write(&blend, scene_file(HeaderKind::Legacy8, false)).expect("synthetic file written");
+
+	// The test harness binary is built without the `sqlite` feature, so run in process.
+	run(Args {
+		file: blend,
+		out: db.clone(),
+		refs_depth: None,
+		max_edges: None,
+	})
+	.expect("database written");
+
+	let conn = Connection::open(&db).expect("database opens");
+	let ids: i64 = conn.query_row("SELECT count(*) FROM ids", [], |row| row.get(0)).expect("ids counted");
+	let target: String = conn
+		.query_row(
+			"SELECT t.name FROM refs r JOIN ids o ON o.ptr = r.owner JOIN ids t ON t.ptr = r.target WHERE o.name = 'OBCamera' AND r.field = 'data'",
+			[],
+			|row| row.get(0),
+		)
+		.expect("object data edge");
+	let owned: i64 = conn
+		.query_row(
+			"SELECT b.nr FROM blocks b JOIN ids i ON i.ptr = b.owner WHERE i.name = 'MECamera' AND b.code = 'DATA'",
+			[],
+			|row| row.get(0),
+		)
+		.expect("owned vertex block");
+	drop(conn);
+	std::fs::remove_dir_all(&dir).expect("temp dir removed");
+
+	assert_eq!(ids, 4);
+	assert_eq!(target, "MECamera");
+	assert_eq!(owned, SCENE_VERT_COUNT as i64);
+}
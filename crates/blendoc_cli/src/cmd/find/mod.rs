@@ -0,0 +1,135 @@
+use std::io::Write;
+use std::path::PathBuf;
+
+use blendoc::blend::{BlendFile, NameEntry, NameIndex, NameIndexOptions, NameKind, Result};
+
+use crate::cmd::output::OutputArgs;
+use crate::cmd::stats::timed;
+use crate::cmd::util::{ptr_hex, render_code};
+
+/// Indexed string kind accepted by `--kind`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, clap::ValueEnum)]
+pub enum FindKind {
+	/// `ID.name` of any ID.
+	Id,
+	/// Linked library file path.
+	Library,
+	/// External file path (images, sounds, caches, ...).
+	File,
+}
+
+impl From<FindKind> for NameKind {
+	fn from(kind: FindKind) -> Self {
+		match kind {
+			FindKind::Id => Self::IdName,
+			FindKind::Library => Self::LibraryPath,
+			FindKind::File => Self::FilePath,
+		}
+	}
+}
+
+#[derive(clap::Args)]
+pub struct Args {
+	pub file: PathBuf,
+	pub query: String,
+	#[arg(long)]
+	pub prefix: bool,
+	#[arg(long, value_enum)]
+	pub kind: Option<FindKind>,
+	#[arg(long, default_value_t = 50)]
+	pub limit: usize,
+	#[arg(long, default_value_t = 64 << 20)]
+	pub max_index_bytes: usize,
+	#[arg(long)]
+	pub json: bool,
+	#[command(flatten)]
+	pub output: OutputArgs,
+}
+
+/// Look up ID names, library paths, and external file paths by substring or prefix.
+pub fn run(args: Args) -> Result<()> {
+	let Args {
+		file: path,
+		query,
+		prefix,
+		kind,
+		limit,
+		max_index_bytes,
+		json,
+		output,
+	} = args;
+
+	let blend = BlendFile::open(&path)?;
+	let dna = blend.dna()?;
+	let options = NameIndexOptions {
+		max_bytes: max_index_bytes,
+		..NameIndexOptions::default()
+	};
+	let index = timed("index", || NameIndex::build(&blend, &dna, &options))?;
+	let kind = kind.map(NameKind::from);
+	let hits = if prefix {
+		index.complete(&query, kind, limit)
+	} else {
+		index.find(&query, kind, limit)
+	};
+	let rows: Vec<FindRow> = hits.into_iter().map(find_row).collect();
+
+	let mut out = output.open(json)?;
+	if !out.is_text() {
+		let payload = FindJson {
+			path: path.display().to_string(),
+			query,
+			mode: if prefix { "prefix" } else { "substring" },
+			indexed: index.len(),
+			index_truncated: index.truncated(),
+			hits: &rows,
+		};
+		out.structured(&payload, &rows)?;
+		return out.finish();
+	}
+
+	writeln!(out, "path: {}", path.display())?;
+	writeln!(out, "indexed: {}{}", index.len(), if index.truncated() { " (truncated)" } else { "" })?;
+	writeln!(out, "hits: {}", rows.len())?;
+	for row in &rows {
+		if row.kind == NameKind::IdName.as_str() {
+			writeln!(out, "  {}\t{}\t{}", row.kind, row.text, row.ptr)?;
+		} else {
+			writeln!(out, "  {}\t{}\t{} ({})", row.kind, row.text, row.id, row.ptr)?;
+		}
+	}
+	out.finish()
+}
+
+fn find_row(entry: &NameEntry) -> FindRow {
+	FindRow {
+		kind: entry.kind.as_str(),
+		text: entry.text.to_string(),
+		id: entry.id_name.to_string(),
+		code: render_code(entry.code),
+		ptr: ptr_hex(entry.id_ptr),
+	}
+}
+
+#[derive(serde::Serialize)]
+struct FindJson<'a> {
+	path: String,
+	query: String,
+	mode: &'static str,
+	indexed: usize,
+	index_truncated: bool,
+	hits: &'a [FindRow],
+}
+
+/// One matched string; also the `--format csv` row.
+#[derive(serde::Serialize)]
+struct FindRow {
+	kind: &'static str,
+	text: String,
+	id: String,
+	code: String,
+	ptr: String,
+}
+
+#[cfg(test)]
+mod tests;
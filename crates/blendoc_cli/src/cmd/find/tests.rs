@@ -0,0 +1,28 @@
+use blendoc_testkit::builder::SCENE_ID_NAMES;
+
+use crate::cmd::test_support::{TempBlend, run_blendoc, run_blendoc_json};
+
+#[test]
+fn scene_names_match_by_substring_and_code_less_prefix() {
+	let target = TempBlend::scene("find");
+	let target_arg = target.arg();
+
+	let substring = run_blendoc_json(&["find", target_arg, "CAMERA", "--json"]);
+	let prefix = run_blendoc_json(&["find", target_arg, "wor", "--prefix", "--kind", "id", "--json"]);
+	let empty = run_blendoc(&["find", target_arg, "zzz"]);
+
+	let texts = |json: &serde_json::Value| -> Vec<String> {
+		json["hits"]
+			.as_array()
+			.expect("hits array")
+			.iter()
+			.map(|hit| hit["text"].as_str().expect("text").to_owned())
+			.collect()
+	};
+	assert_eq!(substring["indexed"], SCENE_ID_NAMES.len());
+	assert_eq!(texts(&substring), [SCENE_ID_NAMES[1], SCENE_ID_NAMES[2]]);
+	assert_eq!(texts(&prefix), [SCENE_ID_NAMES[3]]);
+	assert_eq!(prefix["mode"], "prefix");
+	assert!(empty.status.success());
+	assert!(String::from_utf8_lossy(&empty.stdout).contains("hits: 0"));
+}
@@ -1,9 +1,14 @@
 use std::collections::HashMap;
+use std::io::Write;
 use std::path::PathBuf;
 
-use blendoc::blend::{BlendError, BlendFile, GraphOptions, GraphResult, GraphTruncation, IdIndex, build_graph_from_ptr, scan_id_blocks};
+use blendoc::blend::{BlendFile, GraphExport, GraphFilter, GraphOptions, GraphResult, GraphTruncation, IdIndex, LibraryNamespaces, build_graph_from_ptr};
 
-use crate::cmd::util::{RootSelector, dot_escape, emit_json, parse_root_selector, ptr_hex, render_code};
+use crate::cmd::output::OutputArgs;
+use crate::cmd::util::{
+	WarningJson, dot_escape, library_namespaces, parse_block_code, parse_root_selector, ptr_hex, render_code, resolve_root, scan_ids, warnings_json,
+	write_warnings,
+};
 
 #[derive(clap::Args)]
 pub struct Args {
@@ -24,10 +29,18 @@ pub struct Args {
 	pub max_edges: Option<usize>,
 	#[arg(long = "id-only")]
 	pub id_only: bool,
+	#[command(flatten)]
+	pub filter: FilterArgs,
+	#[arg(long = "annotate-libs")]
+	pub annotate_libs: bool,
 	#[arg(long)]
 	pub dot: bool,
+	#[arg(long, conflicts_with_all = ["dot", "json", "format"])]
+	pub graphml: bool,
 	#[arg(long)]
 	pub json: bool,
+	#[command(flatten)]
+	pub output: OutputArgs,
 }
 
 /// Build and print a shallow pointer graph from one root selector.
@@ -42,8 +55,12 @@ pub fn run(args: Args) -> blendoc::blend::Result<()> {
 		max_nodes,
 		max_edges,
 		id_only,
+		filter,
+		annotate_libs,
 		dot,
+		graphml,
 		json,
+		output,
 	} = args;
 
 	let selector = parse_root_selector(code, ptr, id_name)?;
@@ -51,21 +68,9 @@ pub fn run(args: Args) -> blendoc::blend::Result<()> {
 	let blend = BlendFile::open(&path)?;
 	let dna = blend.dna()?;
 	let index = blend.pointer_index()?;
-	let ids = IdIndex::build(scan_id_blocks(&blend, &dna)?);
-
-	let (root_ptr, root_label) = match selector {
-		RootSelector::Code(block_code) => {
-			let block = blend
-				.find_first_block_by_code(block_code)?
-				.ok_or(BlendError::BlockNotFound { code: block_code })?;
-			(block.head.old, format!("code:{}", render_code(block_code)))
-		}
-		RootSelector::Ptr(ptr) => (ptr, format!("ptr:0x{ptr:016x}")),
-		RootSelector::Id(name) => {
-			let row = ids.get_by_name(&name).ok_or(BlendError::IdRecordNotFound { name: name.clone() })?;
-			(row.old_ptr, format!("id:{}", row.id_name))
-		}
-	};
+	let ids = IdIndex::build(scan_ids(&blend, &dna)?);
+
+	let (root_ptr, root_label) = resolve_root(&blend, &ids, selector)?;
 
 	let mut options = GraphOptions::default();
 	if let Some(depth) = depth {
@@ -81,56 +86,110 @@ pub fn run(args: Args) -> blendoc::blend::Result<()> {
 		options.max_edges = max_edges;
 	}
 	options.id_only = id_only;
+	options.filter = filter.filter()?;
+	options.cancel = crate::cmd::interrupt::token();
 
 	let graph = build_graph_from_ptr(&dna, &index, &ids, root_ptr, &options)?;
+	let namespaces = library_namespaces(&blend, &dna, annotate_libs)?;
 
-	if json {
-		print_json(&path, &root_label, root_ptr, &graph);
-		return Ok(());
-	}
-	if dot {
-		print_dot(&graph);
-		return Ok(());
+	let mut out = output.open(json)?;
+	if !out.is_text() {
+		out.structured(
+			&json_payload(&path, &root_label, root_ptr, &graph, &namespaces),
+			&edge_rows(&graph, &namespaces),
+		)?;
+	} else if graphml {
+		GraphExport::from_graph(&graph).write_graphml(&mut out)?;
+	} else if dot {
+		write_dot(&mut out, &graph, &namespaces)?;
+	} else {
+		write_text(&mut out, &path, &root_label, root_ptr, &graph, &namespaces)?;
 	}
+	out.finish()
+}
 
-	print_text(&path, &root_label, root_ptr, &graph);
-	Ok(())
+/// Node and edge filter flags; type and field values are globs.
+#[derive(clap::Args, Debug, Clone, Default)]
+pub struct FilterArgs {
+	#[arg(long = "include-type", value_name = "TYPE")]
+	pub include_type: Vec<String>,
+	#[arg(long = "exclude-type", value_name = "TYPE")]
+	pub exclude_type: Vec<String>,
+	#[arg(long = "include-code", value_name = "CODE")]
+	pub include_code: Vec<String>,
+	#[arg(long = "exclude-code", value_name = "CODE")]
+	pub exclude_code: Vec<String>,
+	#[arg(long = "include-field", value_name = "FIELD")]
+	pub include_field: Vec<String>,
+	#[arg(long = "exclude-field", value_name = "FIELD")]
+	pub exclude_field: Vec<String>,
 }
 
-fn print_text(path: &std::path::Path, root_label: &str, root_ptr: u64, graph: &GraphResult) {
-	println!("path: {}", path.display());
-	println!("root: {root_label}");
-	println!("root_ptr: 0x{root_ptr:016x}");
-	println!("nodes: {}", graph.nodes.len());
-	println!("edges: {}", graph.edges.len());
-	println!("truncated: {}", truncation_label(graph.truncated));
+impl FilterArgs {
+	/// Build the core filter, validating block codes.
+	pub(crate) fn filter(self) -> blendoc::blend::Result<GraphFilter> {
+		let codes = |values: Vec<String>| values.iter().map(|code| parse_block_code(code)).collect::<blendoc::blend::Result<Vec<_>>>();
+		Ok(GraphFilter {
+			include_types: self.include_type,
+			exclude_types: self.exclude_type,
+			include_codes: codes(self.include_code)?,
+			exclude_codes: codes(self.exclude_code)?,
+			include_fields: self.include_field,
+			exclude_fields: self.exclude_field,
+		})
+	}
+}
+
+fn write_text(
+	out: &mut impl Write,
+	path: &std::path::Path,
+	root_label: &str,
+	root_ptr: u64,
+	graph: &GraphResult,
+	namespaces: &LibraryNamespaces,
+) -> std::io::Result<()> {
+	writeln!(out, "path: {}", path.display())?;
+	writeln!(out, "root: {root_label}")?;
+	writeln!(out, "root_ptr: 0x{root_ptr:016x}")?;
+	writeln!(out, "nodes: {}", graph.nodes.len())?;
+	writeln!(out, "edges: {}", graph.edges.len())?;
+	writeln!(out, "truncated: {}", truncation_label(graph.truncated))?;
+	write_warnings(out, &graph.warnings)?;
 
 	let by_ptr: HashMap<u64, _> = graph.nodes.iter().map(|node| (node.canonical, node)).collect();
 	for edge in &graph.edges {
 		let from = by_ptr.get(&edge.from).copied();
 		let to = by_ptr.get(&edge.to).copied();
-		println!("{} -{}-> {}", node_label(from), edge.field, node_label(to));
+		writeln!(out, "{} -{}-> {}", node_label(from, namespaces), edge.field, node_label(to, namespaces))?;
 	}
+	Ok(())
 }
 
-fn print_dot(graph: &GraphResult) {
-	println!("digraph blendoc {{");
+fn write_dot(out: &mut impl Write, graph: &GraphResult, namespaces: &LibraryNamespaces) -> std::io::Result<()> {
+	writeln!(out, "digraph blendoc {{")?;
 	for node in &graph.nodes {
 		let label = if let Some(id_name) = &node.id_name {
-			format!("{}\\n{}", id_name, node.type_name)
+			format!("{}\\n{}", namespaces.annotate(node.canonical, id_name), node.type_name)
 		} else {
 			format!("{}\\n0x{:016x}", node.type_name, node.canonical)
 		};
-		println!("  \"0x{:016x}\" [label=\"{}\"]", node.canonical, dot_escape(&label));
+		writeln!(out, "  \"0x{:016x}\" [label=\"{}\"]", node.canonical, dot_escape(&label))?;
 	}
 	for edge in &graph.edges {
-		println!("  \"0x{:016x}\" -> \"0x{:016x}\" [label=\"{}\"]", edge.from, edge.to, dot_escape(&edge.field));
+		writeln!(
+			out,
+			"  \"0x{:016x}\" -> \"0x{:016x}\" [label=\"{}\"]",
+			edge.from,
+			edge.to,
+			dot_escape(&edge.field)
+		)?;
 	}
-	println!("}}");
+	writeln!(out, "}}")
 }
 
-fn print_json(path: &std::path::Path, root_label: &str, root_ptr: u64, graph: &GraphResult) {
-	let payload = GraphJson {
+/// `--json` payload for a graph built from one root.
+pub(crate) fn json_payload(path: &std::path::Path, root_label: &str, root_ptr: u64, graph: &GraphResult, namespaces: &LibraryNamespaces) -> GraphJson {
+	GraphJson {
 		path: path.display().to_string(),
 		root: root_label.to_owned(),
 		root_ptr: ptr_hex(root_ptr),
@@ -144,6 +203,7 @@ fn print_json(path: &std::path::Path, root_label: &str, root_ptr: u64, graph: &G
 				sdna_nr: node.sdna_nr,
 				type_name: node.type_name.to_string(),
 				id: node.id_name.as_deref().map(|item| item.to_string()),
+				library: namespaces.get(node.canonical).map(str::to_owned),
 			})
 			.collect(),
 		edges: graph
@@ -155,18 +215,32 @@ fn print_json(path: &std::path::Path, root_label: &str, root_ptr: u64, graph: &G
 				field: edge.field.to_string(),
 			})
 			.collect(),
-	};
+		warnings: warnings_json(&graph.warnings),
+	}
+}
 
-	emit_json(&payload);
+fn edge_rows(graph: &GraphResult, namespaces: &LibraryNamespaces) -> Vec<GraphEdgeRow> {
+	let by_ptr: HashMap<u64, _> = graph.nodes.iter().map(|node| (node.canonical, node)).collect();
+	graph
+		.edges
+		.iter()
+		.map(|edge| GraphEdgeRow {
+			from: ptr_hex(edge.from),
+			from_label: node_label(by_ptr.get(&edge.from).copied(), namespaces),
+			field: edge.field.to_string(),
+			to: ptr_hex(edge.to),
+			to_label: node_label(by_ptr.get(&edge.to).copied(), namespaces),
+		})
+		.collect()
 }
 
-fn node_label(node: Option<&blendoc::blend::GraphNode>) -> String {
+fn node_label(node: Option<&blendoc::blend::GraphNode>, namespaces: &LibraryNamespaces) -> String {
 	let Some(node) = node else {
 		return "<unknown>".to_owned();
 	};
 
 	if let Some(id_name) = &node.id_name {
-		format!("{}({})", id_name, node.type_name)
+		format!("{}({})", namespaces.annotate(node.canonical, id_name), node.type_name)
 	} else {
 		format!("{}@0x{:016x}", node.type_name, node.canonical)
 	}
@@ -177,6 +251,7 @@ fn truncation_label(value: Option<GraphTruncation>) -> &'static str {
 		Some(GraphTruncation::MaxDepth) => "max_depth",
 		Some(GraphTruncation::MaxNodes) => "max_nodes",
 		Some(GraphTruncation::MaxEdges) => "max_edges",
+		Some(GraphTruncation::Cancelled) => "cancelled",
 		None => "none",
 	}
 }
@@ -186,18 +261,20 @@ fn truncation_value(value: Option<GraphTruncation>) -> Option<&'static str> {
 		Some(GraphTruncation::MaxDepth) => Some("max_depth"),
 		Some(GraphTruncation::MaxNodes) => Some("max_nodes"),
 		Some(GraphTruncation::MaxEdges) => Some("max_edges"),
+		Some(GraphTruncation::Cancelled) => Some("cancelled"),
 		None => None,
 	}
 }
 
 #[derive(serde::Serialize)]
-struct GraphJson {
+pub(crate) struct GraphJson {
 	path: String,
 	root: String,
 	root_ptr: String,
 	truncated: Option<String>,
 	nodes: Vec<GraphNodeJson>,
 	edges: Vec<GraphEdgeJson>,
+	warnings: Vec<WarningJson>,
 }
 
 #[derive(serde::Serialize)]
@@ -208,6 +285,8 @@ struct GraphNodeJson {
 	#[serde(rename = "type")]
 	type_name: String,
 	id: Option<String>,
+	#[serde(skip_serializing_if = "Option::is_none")]
+	library: Option<String>,
 }
 
 #[derive(serde::Serialize)]
@@ -217,5 +296,15 @@ struct GraphEdgeJson {
 	field: String,
 }
 
+/// Flat `--format csv` edge row with endpoint labels.
+#[derive(serde::Serialize)]
+struct GraphEdgeRow {
+	from: String,
+	from_label: String,
+	field: String,
+	to: String,
+	to_label: String,
+}
+
 #[cfg(test)]
 mod tests;
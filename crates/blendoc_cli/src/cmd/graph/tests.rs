@@ -1,4 +1,4 @@
-use crate::cmd::test_support::{fixture_path, run_blendoc_json};
+use crate::cmd::test_support::{TempBlend, fixture_path, run_blendoc, run_blendoc_json};
 
 #[test]
 fn graph_json_output_contains_nodes_and_edges() {
@@ -10,3 +10,63 @@ fn graph_json_output_contains_nodes_and_edges() {
 	assert!(json["nodes"].as_array().is_some_and(|items| !items.is_empty()), "expected graph nodes");
 	assert!(json["edges"].as_array().is_some(), "expected graph edges array");
 }
+
+#[test]
+fn idgraph_yaml_output_matches_json_shape() {
+	let fixture = fixture_path("character.blend");
+	let fixture = fixture.to_string_lossy().into_owned();
+	let output = run_blendoc(&["idgraph", &fixture, "--format", "yaml"]);
+	assert!(output.status.success(), "idgraph failed: {}", String::from_utf8_lossy(&output.stderr));
+
+	let yaml = String::from_utf8(output.stdout).expect("yaml output is utf-8");
+	let json = run_blendoc_json(&["idgraph", &fixture, "--json"]);
+	assert!(yaml.starts_with("path: "));
+	assert!(yaml.contains("\nnodes:\n"));
+	assert_eq!(yaml.matches("\n- canonical: ").count(), json["nodes"].as_array().map_or(0, Vec::len));
+}
+
+#[test]
+fn graph_graphml_output_declares_typed_keys() {
+	let fixture = fixture_path("character.blend");
+	let fixture = fixture.to_string_lossy().into_owned();
+	let output = run_blendoc(&["graph", &fixture, "--id", "SCScene", "--depth", "1", "--graphml"]);
+	assert!(output.status.success(), "graph failed: {}", String::from_utf8_lossy(&output.stderr));
+
+	let xml = String::from_utf8(output.stdout).expect("graphml output is utf-8");
+	assert!(xml.contains(r#"<key id="field" for="edge" attr.name="field" attr.type="string"/>"#));
+	assert!(xml.contains("<data key=\"id_name\">SCScene</data>"));
+	assert!(xml.contains("<data key=\"field\">world</data>"));
+}
+
+#[test]
+fn graph_filters_drop_excluded_types_and_fields() {
+	let target = TempBlend::scene("graph_filter");
+	let target_arg = target.arg();
+
+	let full = run_blendoc_json(&["graph", target_arg, "--id", "SCScene", "--json"]);
+	let filtered = run_blendoc_json(&[
+		"graph",
+		target_arg,
+		"--id",
+		"SCScene",
+		"--exclude-type",
+		"World",
+		"--exclude-field",
+		"data",
+		"--json",
+	]);
+
+	let types = |json: &serde_json::Value| -> Vec<String> {
+		json["nodes"]
+			.as_array()
+			.expect("nodes array")
+			.iter()
+			.filter_map(|node| node["type"].as_str().map(str::to_owned))
+			.collect()
+	};
+	assert!(types(&full).iter().any(|name| name == "World"));
+	assert!(types(&full).iter().any(|name| name == "Mesh"));
+	let kept = types(&filtered);
+	assert!(kept.iter().any(|name| name == "Object"));
+	assert!(!kept.iter().any(|name| name == "World" || name == "Mesh"), "unexpected nodes: {kept:?}");
+}
@@ -0,0 +1,46 @@
+use std::path::PathBuf;
+
+use blendoc::blend::{BlendFile, Dna, IdGraphKey, IdGraphOptions, IdGraphResult, IdIndex, PointerIndex, Result, build_id_graph, load_or_build_id_graph};
+
+/// Shared `--graph-cache` flags persisting the whole-file ID graph between runs.
+#[derive(clap::Args, Debug, Clone, Default)]
+pub struct GraphCacheArgs {
+	#[arg(long = "graph-cache")]
+	pub graph_cache: bool,
+	#[arg(long = "graph-cache-dir", value_name = "DIR")]
+	pub graph_cache_dir: Option<PathBuf>,
+}
+
+impl GraphCacheArgs {
+	/// ID graph for `blend`, reloaded from its artifact when caching is on.
+	///
+	/// The artifact is `<file>.idgraph` beside the input, or
+	/// `DIR/<content-hash>.idgraph` under `--graph-cache-dir`.
+	pub(crate) fn build(
+		&self,
+		path: &std::path::Path,
+		blend: &BlendFile,
+		dna: &Dna,
+		index: &PointerIndex<'_>,
+		ids: &IdIndex,
+		options: &IdGraphOptions,
+	) -> Result<IdGraphResult> {
+		let mut options = options.clone();
+		options.progress = options.progress.or_else(crate::cmd::progress::sink);
+		options.cancel = options.cancel.or_else(crate::cmd::interrupt::token);
+		let options = &options;
+		let artifact = match &self.graph_cache_dir {
+			Some(dir) => {
+				std::fs::create_dir_all(dir)?;
+				dir.join(format!("{:016x}.idgraph", IdGraphKey::new(blend, options).content_hash))
+			}
+			None if self.graph_cache => {
+				let mut name = path.as_os_str().to_owned();
+				name.push(".idgraph");
+				PathBuf::from(name)
+			}
+			None => return build_id_graph(dna, index, ids, options),
+		};
+		Ok(load_or_build_id_graph(blend, dna, index, ids, options, &artifact)?.0)
+	}
+}
@@ -0,0 +1,98 @@
+use std::path::PathBuf;
+
+use blendoc::blend::{BlendFile, Result, find_id_block};
+
+use crate::cmd::util::{emit_json, parse_block_code, render_code};
+
+#[derive(clap::Args)]
+#[command(group(clap::ArgGroup::new("check").required(true).multiple(true).args(["id_name", "code", "struct_name"])))]
+pub struct Args {
+	pub file: PathBuf,
+	#[arg(long = "id")]
+	pub id_name: Option<String>,
+	#[arg(long)]
+	pub code: Option<String>,
+	#[arg(long = "struct")]
+	pub struct_name: Option<String>,
+	#[arg(long)]
+	pub json: bool,
+}
+
+/// Exit 0 when every requested ID/code/struct exists, 1 when any is absent, 2 on error.
+pub fn run(args: Args) -> Result<()> {
+	match check(args) {
+		Ok(true) => Ok(()),
+		Ok(false) => std::process::exit(1),
+		Err(err) => {
+			eprintln!("error: {err}");
+			std::process::exit(2);
+		}
+	}
+}
+
+fn check(args: Args) -> Result<bool> {
+	let Args {
+		file: path,
+		id_name,
+		code,
+		struct_name,
+		json,
+	} = args;
+
+	let code = code.as_deref().map(parse_block_code).transpose()?;
+	let blend = BlendFile::open_mmap(&path)?;
+	// Code checks need no schema; skip DNA parsing unless a name lookup was requested.
+	let dna = if struct_name.is_some() || id_name.is_some() {
+		Some(blend.dna()?)
+	} else {
+		None
+	};
+
+	let mut checks = Vec::new();
+	if let (Some(name), Some(dna)) = (&struct_name, &dna) {
+		checks.push(CheckJson {
+			kind: "struct",
+			value: name.clone(),
+			exists: dna.struct_index(name).is_some(),
+		});
+	}
+	if let Some(code) = code {
+		checks.push(CheckJson {
+			kind: "code",
+			value: render_code(code),
+			exists: blend.find_first_block_by_code(code)?.is_some(),
+		});
+	}
+	if let (Some(name), Some(dna)) = (&id_name, &dna) {
+		checks.push(CheckJson {
+			kind: "id",
+			value: name.clone(),
+			exists: find_id_block(&blend, dna, name)?.is_some(),
+		});
+	}
+
+	let exists = checks.iter().all(|item| item.exists);
+	if json {
+		emit_json(&HasJson {
+			path: path.display().to_string(),
+			exists,
+			checks,
+		});
+	}
+
+	Ok(exists)
+}
+
+#[derive(serde::Serialize)]
+struct CheckJson {
+	kind: &'static str,
+	value: String,
+	exists: bool,
+}
+
+#[derive(serde::Serialize)]
+struct HasJson {
+	path: String,
+	exists: bool,
+	checks: Vec<CheckJson>,
+}
@@ -0,0 +1,217 @@
+use std::io::Write;
+use std::path::PathBuf;
+
+use blendoc::blend::{BlendError, BlendFile, Block, IdIndex, PointerIndex, Result, layout_map};
+
+use crate::cmd::output::OutputArgs;
+use crate::cmd::util::{RootSelector, code_root_block, parse_root_selector, ptr_hex, render_code, scan_ids};
+
+/// Bytes per dump line.
+const LINE_BYTES: usize = 16;
+
+#[derive(clap::Args)]
+pub struct Args {
+	pub file: PathBuf,
+	#[arg(long = "id")]
+	pub id_name: Option<String>,
+	#[arg(long)]
+	pub ptr: Option<String>,
+	#[arg(long)]
+	pub code: Option<String>,
+	#[arg(long)]
+	pub annotate: bool,
+	#[arg(long = "max-bytes", default_value_t = 1024)]
+	pub max_bytes: usize,
+	#[arg(long)]
+	pub json: bool,
+	#[command(flatten)]
+	pub output: OutputArgs,
+}
+
+/// Dump the raw payload of the block holding an ID, pointer, or code root.
+///
+/// `--annotate` overlays SDNA field boundaries for every struct element in
+/// the dumped range, so a payload that does not match its struct layout can
+/// be inspected byte by byte.
+pub fn run(args: Args) -> Result<()> {
+	let Args {
+		file: path,
+		id_name,
+		ptr,
+		code,
+		annotate,
+		max_bytes,
+		json,
+		output,
+	} = args;
+
+	let blend = BlendFile::open(&path)?;
+	let dna = blend.dna()?;
+	let selector = parse_root_selector(code, ptr, id_name)?;
+	let index = PointerIndex::build(&blend)?;
+	let (block, target_offset): (Block<'_>, usize) = match selector {
+		RootSelector::Code(code, occurrence) => (code_root_block(&blend, code, occurrence)?, 0),
+		RootSelector::Ptr(ptr) => resolve(&index, ptr)?,
+		RootSelector::Id(name) => {
+			let ids = IdIndex::build(scan_ids(&blend, &dna)?);
+			resolve(&index, ids.resolve_selector(&name)?.old_ptr)?
+		}
+	};
+
+	let payload = block.payload;
+	let shown = &payload[..payload.len().min(max_bytes)];
+	let map = if block.head.sdna_nr == 0 {
+		None
+	} else {
+		layout_map(&dna, block.head.sdna_nr)
+	};
+	let struct_size = map.as_ref().map(|map| map.size);
+	let expected_len = struct_size.map(|size| (size as u64).saturating_mul(block.head.nr));
+
+	let lines: Vec<LineRow> = shown
+		.chunks(LINE_BYTES)
+		.enumerate()
+		.map(|(idx, bytes)| LineRow {
+			offset: idx * LINE_BYTES,
+			hex: bytes.iter().map(|byte| format!("{byte:02x}")).collect::<Vec<_>>().join(" "),
+			ascii: bytes
+				.iter()
+				.map(|byte| if byte.is_ascii_graphic() || *byte == b' ' { char::from(*byte) } else { '.' })
+				.collect(),
+		})
+		.collect();
+
+	let mut fields = Vec::new();
+	if annotate && let Some(map) = map.as_ref().filter(|map| map.size > 0) {
+		for element in 0..block.head.nr as usize {
+			let base = element * map.size;
+			if base >= shown.len() {
+				break;
+			}
+			for span in &map.spans {
+				let offset = base + span.offset;
+				if offset >= shown.len() {
+					break;
+				}
+				fields.push(FieldMark {
+					element,
+					field: span.path.to_string(),
+					type_name: if span.is_ptr {
+						format!("{}*", span.type_name)
+					} else {
+						span.type_name.to_string()
+					},
+					offset,
+					size: span.size,
+				});
+			}
+		}
+	}
+
+	let mut out = output.open(json)?;
+	if !out.is_text() {
+		let payload_json = HexdumpJson {
+			path: path.display().to_string(),
+			block_offset: format!("0x{:x}", block.file_offset),
+			code: render_code(block.head.code),
+			ptr: ptr_hex(block.head.old),
+			sdna_nr: block.head.sdna_nr,
+			type_name: map.as_ref().map(|map| map.type_name.to_string()),
+			nr: block.head.nr,
+			struct_size,
+			payload_len: payload.len(),
+			expected_len,
+			target_offset,
+			shown_bytes: shown.len(),
+			lines: &lines,
+			fields: &fields,
+		};
+		out.structured(&payload_json, &lines)?;
+		return out.finish();
+	}
+
+	let type_label = map
+		.as_ref()
+		.map_or_else(|| "raw".to_owned(), |map| format!("{} ({} bytes)", map.type_name, map.size));
+	writeln!(
+		out,
+		"block: {} {} at 0x{:x}  {type_label} x{}  payload {} bytes",
+		render_code(block.head.code),
+		ptr_hex(block.head.old),
+		block.file_offset,
+		block.head.nr,
+		payload.len()
+	)?;
+	if let Some(expected) = expected_len
+		&& expected != payload.len() as u64
+	{
+		writeln!(out, "layout mismatch: payload {} bytes, SDNA expects {expected}", payload.len())?;
+	}
+	if target_offset > 0 {
+		let element = struct_size.filter(|size| *size > 0).map(|size| format!(" (element {})", target_offset / size));
+		writeln!(out, "target: +0x{target_offset:x}{}", element.unwrap_or_default())?;
+	}
+
+	let mut marks = fields.iter().peekable();
+	for line in &lines {
+		writeln!(out, "{:08x}  {:<47}  |{}|", line.offset, line.hex, line.ascii)?;
+		while let Some(mark) = marks.next_if(|mark| mark.offset < line.offset + LINE_BYTES) {
+			let element = if block.head.nr > 1 { format!("[{}].", mark.element) } else { String::new() };
+			writeln!(
+				out,
+				"          +0x{:<6x}{:>6}  {:<12}{element}{}",
+				mark.offset, mark.size, mark.type_name, mark.field
+			)?;
+		}
+	}
+	if shown.len() < payload.len() {
+		writeln!(out, "... {} more bytes (raise --max-bytes)", payload.len() - shown.len())?;
+	}
+	out.finish()
+}
+
+fn resolve<'a>(index: &PointerIndex<'a>, ptr: u64) -> Result<(Block<'a>, usize)> {
+	let resolved = index.resolve(ptr).ok_or(BlendError::ChaseUnresolvedPtr { ptr })?;
+	Ok((resolved.entry.block, resolved.byte_offset))
+}
+
+#[derive(serde::Serialize)]
+struct HexdumpJson<'a> {
+	path: String,
+	block_offset: String,
+	code: String,
+	ptr: String,
+	sdna_nr: u32,
+	#[serde(rename = "type")]
+	type_name: Option<String>,
+	nr: u64,
+	struct_size: Option<usize>,
+	payload_len: usize,
+	expected_len: Option<u64>,
+	target_offset: usize,
+	shown_bytes: usize,
+	lines: &'a [LineRow],
+	fields: &'a [FieldMark],
+}
+
+/// Sixteen payload bytes; also the `--format csv` row.
+#[derive(serde::Serialize)]
+struct LineRow {
+	offset: usize,
+	hex: String,
+	ascii: String,
+}
+
+/// Start of one SDNA leaf field inside the dumped payload.
+#[derive(serde::Serialize)]
+struct FieldMark {
+	element: usize,
+	field: String,
+	#[serde(rename = "type")]
+	type_name: String,
+	offset: usize,
+	size: usize,
+}
+
+#[cfg(test)]
+mod tests;
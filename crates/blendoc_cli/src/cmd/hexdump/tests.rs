@@ -0,0 +1,31 @@
+use crate::cmd::test_support::{TempBlend, run_blendoc, run_blendoc_json};
+
+#[test]
+fn hexdump_annotates_struct_fields() {
+	let path = TempBlend::scene("hexdump");
+	let arg = path.arg();
+
+	let json = run_blendoc_json(&["hexdump", arg, "--id", "OBCamera", "--annotate", "--json"]);
+	let plain = run_blendoc_json(&["hexdump", arg, "--code", "ME", "--json"]);
+	let text = run_blendoc(&["hexdump", arg, "--id", "OBCamera", "--annotate"]);
+	let missing = run_blendoc(&["hexdump", arg, "--ptr", "0x1"]);
+
+	assert_eq!(json["code"], "OB");
+	assert_eq!(json["type"], "Object");
+	assert_eq!(json["payload_len"], json["expected_len"]);
+	assert_eq!(json["lines"][0]["offset"], 0);
+	let fields: Vec<(&str, u64)> = json["fields"]
+		.as_array()
+		.expect("fields array")
+		.iter()
+		.map(|mark| (mark["field"].as_str().expect("field"), mark["offset"].as_u64().expect("offset")))
+		.collect();
+	assert_eq!(fields[3], ("id.name", 24));
+	assert_eq!(fields[4], ("data", 48));
+	assert!(plain["fields"].as_array().expect("fields array").is_empty());
+
+	let stdout = String::from_utf8_lossy(&text.stdout);
+	assert!(stdout.contains("|........OBCamera|"), "{stdout}");
+	assert!(stdout.contains("id.name") && stdout.contains("void*"), "{stdout}");
+	assert!(!missing.status.success());
+}
@@ -0,0 +1,234 @@
+use std::collections::HashSet;
+use std::io::Write;
+use std::path::PathBuf;
+
+use blendoc::blend::{BlendFile, HierarchyCollection, HierarchyObject, HierarchyOptions, IdIndex, Result, SceneHierarchy, scene_hierarchies};
+
+use crate::cmd::output::OutputArgs;
+use crate::cmd::util::{ptr_hex, scan_ids};
+
+#[derive(clap::Args)]
+pub struct Args {
+	pub file: PathBuf,
+	#[arg(long)]
+	pub scene: Option<String>,
+	#[arg(long)]
+	pub depth: Option<u32>,
+	#[arg(long)]
+	pub json: bool,
+	#[command(flatten)]
+	pub output: OutputArgs,
+}
+
+/// Print the outliner-style collection/object tree of each scene.
+pub fn run(args: Args) -> Result<()> {
+	let Args {
+		file: path,
+		scene,
+		depth,
+		json,
+		output,
+	} = args;
+
+	let blend = BlendFile::open(&path)?;
+	let dna = blend.dna()?;
+	let index = blend.pointer_index()?;
+	let ids = IdIndex::build(scan_ids(&blend, &dna)?);
+
+	let mut options = HierarchyOptions::default();
+	if let Some(depth) = depth {
+		options.max_depth = depth;
+	}
+	let mut scenes = scene_hierarchies(&dna, &index, &ids, &options)?;
+	if let Some(name) = scene {
+		let wanted = ids.resolve_selector(&name)?.old_ptr;
+		scenes.retain(|item| item.scene_ptr == wanted);
+	}
+
+	let mut out = output.open(json)?;
+	if !out.is_text() {
+		let payload = HierarchyJson {
+			path: path.display().to_string(),
+			scenes: scenes.iter().map(scene_json).collect(),
+		};
+		out.structured(&payload, &object_rows(&scenes))?;
+		return out.finish();
+	}
+
+	writeln!(out, "path: {}", path.display())?;
+	writeln!(out, "scenes: {}", scenes.len())?;
+	for scene in &scenes {
+		let suffix = if scene.truncated { " (truncated)" } else { "" };
+		writeln!(out, "{}{suffix}", scene.scene_name)?;
+		if let Some(master) = &scene.master {
+			write_collection(&mut out, master, 1)?;
+		}
+		if !scene.legacy_bases.is_empty() {
+			writeln!(out, "  [bases]")?;
+			write_objects(&mut out, &scene.legacy_bases, 2)?;
+		}
+	}
+	out.finish()
+}
+
+fn write_collection(out: &mut impl Write, collection: &HierarchyCollection, depth: usize) -> std::io::Result<()> {
+	writeln!(out, "{}[{}]", "  ".repeat(depth), collection.name)?;
+	write_objects(out, &collection.objects, depth + 1)?;
+	for child in &collection.children {
+		write_collection(out, child, depth + 1)?;
+	}
+	Ok(())
+}
+
+/// Write objects nested under their parents; parents outside this list are shown as `parent=`.
+fn write_objects(out: &mut impl Write, objects: &[HierarchyObject], depth: usize) -> std::io::Result<()> {
+	let members: HashSet<u64> = objects.iter().map(|object| object.ptr).collect();
+	let mut seen = HashSet::new();
+	for object in objects {
+		if object.parent.is_none_or(|parent| !members.contains(&parent)) {
+			write_object_subtree(out, objects, &members, object, depth, &mut seen)?;
+		}
+	}
+	// Parent cycles have no root above; print what the walk above never reached.
+	for object in objects {
+		if !seen.contains(&object.ptr) {
+			write_object_subtree(out, objects, &members, object, depth, &mut seen)?;
+		}
+	}
+	Ok(())
+}
+
+fn write_object_subtree(
+	out: &mut impl Write,
+	objects: &[HierarchyObject],
+	members: &HashSet<u64>,
+	object: &HierarchyObject,
+	depth: usize,
+	seen: &mut HashSet<u64>,
+) -> std::io::Result<()> {
+	if !seen.insert(object.ptr) {
+		return Ok(());
+	}
+	let parent_note = match object.parent {
+		Some(parent) if !members.contains(&parent) => {
+			format!(" parent={}", object.parent_name.as_deref().map_or_else(|| ptr_hex(parent), str::to_owned))
+		}
+		_ => String::new(),
+	};
+	writeln!(out, "{}{} ({}){parent_note}", "  ".repeat(depth), object.name, object.type_label())?;
+	for child in objects.iter().filter(|item| item.parent == Some(object.ptr)) {
+		write_object_subtree(out, objects, members, child, depth + 1, seen)?;
+	}
+	Ok(())
+}
+
+fn scene_json(scene: &SceneHierarchy) -> SceneJson {
+	SceneJson {
+		scene: scene.scene_name.to_string(),
+		ptr: ptr_hex(scene.scene_ptr),
+		truncated: scene.truncated,
+		master_collection: scene.master.as_ref().map(collection_json),
+		legacy_bases: scene.legacy_bases.iter().map(object_json).collect(),
+	}
+}
+
+fn collection_json(collection: &HierarchyCollection) -> CollectionJson {
+	CollectionJson {
+		name: collection.name.to_string(),
+		ptr: ptr_hex(collection.ptr),
+		objects: collection.objects.iter().map(object_json).collect(),
+		children: collection.children.iter().map(collection_json).collect(),
+	}
+}
+
+fn object_json(object: &HierarchyObject) -> ObjectJson {
+	ObjectJson {
+		name: object.name.to_string(),
+		ptr: ptr_hex(object.ptr),
+		object_type: object.type_label(),
+		parent: object.parent.map(ptr_hex),
+		parent_name: object.parent_name.as_deref().map(str::to_owned),
+	}
+}
+
+fn object_rows(scenes: &[SceneHierarchy]) -> Vec<ObjectRowCsv> {
+	fn visit(rows: &mut Vec<ObjectRowCsv>, scene: &str, path: &str, collection: &HierarchyCollection) {
+		let path = if path.is_empty() {
+			collection.name.to_string()
+		} else {
+			format!("{path}/{}", collection.name)
+		};
+		rows.extend(collection.objects.iter().map(|object| ObjectRowCsv::new(scene, &path, object)));
+		for child in &collection.children {
+			visit(rows, scene, &path, child);
+		}
+	}
+
+	let mut rows = Vec::new();
+	for scene in scenes {
+		if let Some(master) = &scene.master {
+			visit(&mut rows, &scene.scene_name, "", master);
+		}
+		rows.extend(scene.legacy_bases.iter().map(|object| ObjectRowCsv::new(&scene.scene_name, "", object)));
+	}
+	rows
+}
+
+#[derive(serde::Serialize)]
+struct HierarchyJson {
+	path: String,
+	scenes: Vec<SceneJson>,
+}
+
+#[derive(serde::Serialize)]
+struct SceneJson {
+	scene: String,
+	ptr: String,
+	truncated: bool,
+	master_collection: Option<CollectionJson>,
+	legacy_bases: Vec<ObjectJson>,
+}
+
+#[derive(serde::Serialize)]
+struct CollectionJson {
+	name: String,
+	ptr: String,
+	objects: Vec<ObjectJson>,
+	children: Vec<CollectionJson>,
+}
+
+#[derive(serde::Serialize)]
+struct ObjectJson {
+	name: String,
+	ptr: String,
+	#[serde(rename = "type")]
+	object_type: String,
+	parent: Option<String>,
+	parent_name: Option<String>,
+}
+
+/// Flat `--format csv` row: one object membership per line with its collection path.
+#[derive(serde::Serialize)]
+struct ObjectRowCsv {
+	scene: String,
+	collection: String,
+	object: String,
+	#[serde(rename = "type")]
+	object_type: String,
+	parent: String,
+}
+
+impl ObjectRowCsv {
+	fn new(scene: &str, collection: &str, object: &HierarchyObject) -> Self {
+		Self {
+			scene: scene.to_owned(),
+			collection: collection.to_owned(),
+			object: object.name.to_string(),
+			object_type: object.type_label(),
+			parent: object.parent_name.as_deref().unwrap_or_default().to_owned(),
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests;
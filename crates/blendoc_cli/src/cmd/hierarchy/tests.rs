@@ -0,0 +1,29 @@
+use blendoc_testkit::builder::SCENE_ID_NAMES;
+
+use crate::cmd::test_support::{TempBlend, fixture_path, run_blendoc, run_blendoc_json};
+
+#[test]
+fn character_hierarchy_lists_objects_under_master_collection() {
+	let fixture = fixture_path("character.blend");
+	let fixture = fixture.to_string_lossy().into_owned();
+	let json = run_blendoc_json(&["hierarchy", &fixture, "--json"]);
+
+	let scenes = json["scenes"].as_array().expect("scenes array");
+	assert!(!scenes.is_empty());
+	let master = &scenes[0]["master_collection"];
+	let has_objects = |collection: &serde_json::Value| collection["objects"].as_array().is_some_and(|items| !items.is_empty());
+	assert!(has_objects(master) || master["children"].as_array().is_some_and(|items| items.iter().any(has_objects)));
+}
+
+#[test]
+fn scene_without_master_collection_prints_scene_only() {
+	let target = TempBlend::scene("hierarchy");
+	let target_arg = target.arg();
+
+	let output = run_blendoc(&["hierarchy", target_arg]);
+
+	assert!(output.status.success(), "hierarchy failed: {}", String::from_utf8_lossy(&output.stderr));
+	let stdout = String::from_utf8_lossy(&output.stdout);
+	assert!(stdout.contains("scenes: 1"));
+	assert!(stdout.lines().any(|line| line == SCENE_ID_NAMES[0]));
+}
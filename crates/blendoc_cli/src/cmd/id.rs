@@ -0,0 +1,255 @@
+use std::collections::BTreeSet;
+use std::path::PathBuf;
+
+use blendoc::blend::{
+	BlendFile, DecodeOptions, IdIndex, PreviewRect, RefCache, RefScanOptions, StructValue, Value, XrefOptions, chase_ptr_to_struct, decode_preview_rects,
+	decode_ptr_instance, find_inbound_refs_to_ptr, scan_id_footprints, scan_id_link_provenance,
+};
+
+use crate::cmd::libs::format_signal_summary;
+use crate::cmd::show::value_to_json_value;
+use crate::cmd::util::{emit_json, ptr_hex, ptr_hex_opt, render_code, render_value, scan_ids};
+
+#[derive(clap::Args)]
+pub struct Args {
+	pub file: PathBuf,
+	#[arg(long = "id")]
+	pub id_name: String,
+	#[arg(long)]
+	pub json: bool,
+}
+
+/// Print a one-stop summary of a single ID: header, links, references, footprint, and preview.
+pub fn run(args: Args) -> blendoc::blend::Result<()> {
+	let Args { file: path, id_name, json } = args;
+
+	let blend = BlendFile::open(&path)?;
+	let dna = blend.dna()?;
+	let index = blend.pointer_index()?;
+	let ids = IdIndex::build(scan_ids(&blend, &dna)?);
+	let row = ids.resolve_selector(&id_name)?;
+
+	let decode = DecodeOptions {
+		max_depth: 4,
+		..DecodeOptions::default()
+	};
+	let (_, root) = decode_ptr_instance(&dna, &index, row.old_ptr, &decode)?;
+	let header = header_fields(&root);
+	let asset_data = id_ptr_field(&root, "asset_data");
+	let override_library = id_ptr_field(&root, "override_library");
+
+	let link = scan_id_link_provenance(&blend, &dna)?.into_iter().find(|item| item.id_ptr == row.old_ptr);
+
+	let ref_cache = RefCache::new();
+	let outbound = ref_cache.scan(&dna, &index, &ids, row.old_ptr, &RefScanOptions::default())?;
+	let outbound_ids: BTreeSet<&str> = outbound
+		.iter()
+		.filter_map(|record| record.resolved.as_ref()?.id_name.as_deref())
+		.filter(|name| *name != row.id_name.as_ref())
+		.collect();
+	let xref = XrefOptions {
+		ref_cache: Some(ref_cache),
+		..XrefOptions::default()
+	};
+	let inbound = find_inbound_refs_to_ptr(&dna, &index, &ids, row.old_ptr, &xref)?;
+	let inbound_ids: BTreeSet<&str> = inbound
+		.iter()
+		.filter_map(|item| item.from_id.as_deref())
+		.filter(|name| *name != row.id_name.as_ref())
+		.collect();
+
+	let footprint = scan_id_footprints(&blend, &ids)?.into_iter().find(|item| item.id_ptr == row.old_ptr);
+
+	let preview_ptr = ptr_field(&root, "preview").filter(|ptr| *ptr != 0);
+	let preview_rects = match preview_ptr {
+		Some(ptr) => match chase_ptr_to_struct(&dna, &index, ptr, &decode) {
+			Ok(Some((_, preview))) => decode_preview_rects(&index, &preview),
+			_ => Vec::new(),
+		},
+		None => Vec::new(),
+	};
+
+	if json {
+		let payload = IdJson {
+			path: path.display().to_string(),
+			id: row.id_name.to_string(),
+			type_name: row.type_name.to_string(),
+			code: render_code(row.code),
+			ptr: ptr_hex(row.old_ptr),
+			sdna_nr: row.sdna_nr,
+			next: ptr_hex_opt(row.next),
+			prev: ptr_hex_opt(row.prev),
+			lib: ptr_hex_opt(row.lib),
+			header: header
+				.iter()
+				.map(|(name, value)| HeaderFieldJson {
+					name: name.clone(),
+					value: value_to_json_value(value),
+				})
+				.collect(),
+			linked: link.as_ref().is_some_and(|item| item.linked),
+			link_confidence: link.as_ref().map(|item| item.confidence.as_str()),
+			is_asset: asset_data.is_some_and(|ptr| ptr != 0),
+			asset_data: ptr_hex_opt(asset_data.filter(|ptr| *ptr != 0)),
+			is_override: override_library.is_some_and(|ptr| ptr != 0),
+			override_library: ptr_hex_opt(override_library.filter(|ptr| *ptr != 0)),
+			outbound_refs: outbound.len(),
+			outbound_resolved: outbound.iter().filter(|record| record.resolved.is_some()).count(),
+			outbound_ids: outbound_ids.iter().map(|name| (*name).to_owned()).collect(),
+			inbound_refs: inbound.len(),
+			inbound_ids: inbound_ids.iter().map(|name| (*name).to_owned()).collect(),
+			id_bytes: footprint.as_ref().map(|item| item.id_bytes),
+			data_blocks: footprint.as_ref().map(|item| item.data_blocks),
+			data_bytes: footprint.as_ref().map(|item| item.data_bytes),
+			preview_ptr: ptr_hex_opt(preview_ptr),
+			previews: preview_rects.iter().map(PreviewJson::from).collect(),
+		};
+		emit_json(&payload);
+		return Ok(());
+	}
+
+	println!("path: {}", path.display());
+	println!("id: {}", row.id_name);
+	println!("type: {}", row.type_name);
+	println!("code: {}", render_code(row.code));
+	println!("ptr: {}", ptr_hex(row.old_ptr));
+	println!("sdna_nr: {}", row.sdna_nr);
+	println!("next: {}", render_ptr(row.next));
+	println!("prev: {}", render_ptr(row.prev));
+	println!("lib: {}", render_ptr(row.lib));
+	for (name, value) in &header {
+		println!("id.{name}: {}", render_value(value));
+	}
+
+	match &link {
+		Some(item) => {
+			println!("linked: {}", item.linked);
+			println!("link_confidence: {}", item.confidence.as_str());
+			println!("link_signals: {}", format_signal_summary(&item.signals));
+		}
+		None => println!("linked: false"),
+	}
+	println!("asset_data: {}", render_ptr(asset_data));
+	println!("override_library: {}", render_ptr(override_library));
+
+	println!("outbound_refs: {}", outbound.len());
+	println!("outbound_resolved: {}", outbound.iter().filter(|record| record.resolved.is_some()).count());
+	println!("outbound_ids: {}", join_names(&outbound_ids));
+	println!("inbound_refs: {}", inbound.len());
+	println!("inbound_ids: {}", join_names(&inbound_ids));
+
+	match &footprint {
+		Some(item) => {
+			println!("id_bytes: {}", item.id_bytes);
+			println!("data_blocks: {}", item.data_blocks);
+			println!("data_bytes: {}", item.data_bytes);
+		}
+		None => println!("footprint: unknown"),
+	}
+
+	println!("preview: {}", render_ptr(preview_ptr));
+	for rect in &preview_rects {
+		println!("  {}: {}x{}", rect.size, rect.width, rect.height);
+	}
+
+	Ok(())
+}
+
+/// Scalar `ID` header fields, excluding the name, pointers, and padding.
+fn header_fields(root: &StructValue) -> Vec<(String, Value)> {
+	let Some(Value::Struct(id)) = field(root, "id") else {
+		return Vec::new();
+	};
+	id.fields
+		.iter()
+		.filter(|item| item.name.as_ref() != "name" && !item.name.starts_with("_pad"))
+		.filter(|item| !matches!(item.value, Value::Ptr(_) | Value::Struct(_)))
+		.map(|item| (item.name.to_string(), item.value.clone()))
+		.collect()
+}
+
+fn field<'v>(item: &'v StructValue, name: &str) -> Option<&'v Value> {
+	item.fields.iter().find(|field| field.name.as_ref() == name).map(|field| &field.value)
+}
+
+fn ptr_field(item: &StructValue, name: &str) -> Option<u64> {
+	match field(item, name)? {
+		Value::Ptr(ptr) => Some(*ptr),
+		_ => None,
+	}
+}
+
+fn id_ptr_field(root: &StructValue, name: &str) -> Option<u64> {
+	match field(root, "id")? {
+		Value::Struct(id) => ptr_field(id, name),
+		_ => None,
+	}
+}
+
+fn render_ptr(ptr: Option<u64>) -> String {
+	match ptr {
+		Some(0) | None => "null".to_owned(),
+		Some(ptr) => ptr_hex(ptr),
+	}
+}
+
+fn join_names(names: &BTreeSet<&str>) -> String {
+	if names.is_empty() {
+		return "-".to_owned();
+	}
+	names.iter().copied().collect::<Vec<_>>().join(", ")
+}
+
+#[derive(serde::Serialize)]
+struct HeaderFieldJson {
+	name: String,
+	value: serde_json::Value,
+}
+
+#[derive(serde::Serialize)]
+struct PreviewJson {
+	size: &'static str,
+	width: u32,
+	height: u32,
+}
+
+impl From<&PreviewRect> for PreviewJson {
+	fn from(rect: &PreviewRect) -> Self {
+		Self {
+			size: rect.size,
+			width: rect.width,
+			height: rect.height,
+		}
+	}
+}
+
+#[derive(serde::Serialize)]
+struct IdJson {
+	path: String,
+	id: String,
+	#[serde(rename = "type")]
+	type_name: String,
+	code: String,
+	ptr: String,
+	sdna_nr: u32,
+	next: Option<String>,
+	prev: Option<String>,
+	lib: Option<String>,
+	header: Vec<HeaderFieldJson>,
+	linked: bool,
+	link_confidence: Option<&'static str>,
+	is_asset: bool,
+	asset_data: Option<String>,
+	is_override: bool,
+	override_library: Option<String>,
+	outbound_refs: usize,
+	outbound_resolved: usize,
+	outbound_ids: Vec<String>,
+	inbound_refs: usize,
+	inbound_ids: Vec<String>,
+	id_bytes: Option<u64>,
+	data_blocks: Option<usize>,
+	data_bytes: Option<u64>,
+	preview_ptr: Option<String>,
+	previews: Vec<PreviewJson>,
+}
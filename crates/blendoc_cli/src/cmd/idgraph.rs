@@ -1,9 +1,12 @@
 use std::collections::{HashMap, HashSet};
+use std::io::Write;
 use std::path::PathBuf;
 
-use blendoc::blend::{BlendFile, IdGraphOptions, IdGraphResult, IdGraphTruncation, IdIndex, build_id_graph, scan_id_blocks};
+use blendoc::blend::{BlendFile, GraphExport, IdGraphOptions, IdGraphResult, IdGraphTruncation, IdIndex, LibraryNamespaces};
 
-use crate::cmd::util::{dot_escape, emit_json, ptr_hex, render_code};
+use crate::cmd::graph_cache::GraphCacheArgs;
+use crate::cmd::output::OutputArgs;
+use crate::cmd::util::{WarningJson, dot_escape, library_namespaces, ptr_hex, render_code, scan_ids, warnings_json, write_warnings};
 
 #[derive(clap::Args)]
 pub struct Args {
@@ -14,12 +17,20 @@ pub struct Args {
 	pub max_edges: Option<usize>,
 	#[arg(long)]
 	pub dot: bool,
+	#[arg(long, conflicts_with_all = ["dot", "json", "format"])]
+	pub graphml: bool,
 	#[arg(long)]
 	pub json: bool,
+	#[command(flatten)]
+	pub output: OutputArgs,
 	#[arg(long)]
 	pub prefix: Option<String>,
 	#[arg(long = "type")]
 	pub type_name: Option<String>,
+	#[arg(long = "annotate-libs")]
+	pub annotate_libs: bool,
+	#[command(flatten)]
+	pub graph_cache: GraphCacheArgs,
 }
 
 /// Build and print whole-file ID-to-ID graph.
@@ -29,15 +40,19 @@ pub fn run(args: Args) -> blendoc::blend::Result<()> {
 		refs_depth,
 		max_edges,
 		dot,
+		graphml,
 		json,
+		output,
 		prefix,
 		type_name,
+		annotate_libs,
+		graph_cache,
 	} = args;
 
 	let blend = BlendFile::open(&path)?;
 	let dna = blend.dna()?;
 	let index = blend.pointer_index()?;
-	let ids = IdIndex::build(scan_id_blocks(&blend, &dna)?);
+	let ids = IdIndex::build(scan_ids(&blend, &dna)?);
 
 	let mut options = IdGraphOptions::default();
 	if let Some(refs_depth) = refs_depth {
@@ -47,20 +62,21 @@ pub fn run(args: Args) -> blendoc::blend::Result<()> {
 		options.max_edges = max_edges;
 	}
 
-	let raw = build_id_graph(&dna, &index, &ids, &options)?;
+	let raw = graph_cache.build(&path, &blend, &dna, &index, &ids, &options)?;
 	let graph = apply_filters(raw, prefix.as_deref(), type_name.as_deref());
-
-	if json {
-		print_json(&path, &graph);
-		return Ok(());
-	}
-	if dot {
-		print_dot(&graph);
-		return Ok(());
+	let namespaces = library_namespaces(&blend, &dna, annotate_libs)?;
+
+	let mut out = output.open(json)?;
+	if !out.is_text() {
+		out.structured(&json_payload(&path, &graph, &namespaces), &edge_rows(&graph, &namespaces))?;
+	} else if graphml {
+		GraphExport::from_id_graph(&graph).write_graphml(&mut out)?;
+	} else if dot {
+		write_dot(&mut out, &graph, &namespaces)?;
+	} else {
+		write_text(&mut out, &path, &graph, &namespaces)?;
 	}
-
-	print_text(&path, &graph);
-	Ok(())
+	out.finish()
 }
 
 fn apply_filters(mut graph: IdGraphResult, prefix: Option<&str>, type_name: Option<&str>) -> IdGraphResult {
@@ -82,34 +98,42 @@ fn apply_filters(mut graph: IdGraphResult, prefix: Option<&str>, type_name: Opti
 	graph
 }
 
-fn print_text(path: &std::path::Path, graph: &IdGraphResult) {
-	println!("path: {}", path.display());
-	println!("nodes: {}", graph.nodes.len());
-	println!("edges: {}", graph.edges.len());
-	println!("truncated: {}", truncation_label(graph.truncated));
+fn write_text(out: &mut impl Write, path: &std::path::Path, graph: &IdGraphResult, namespaces: &LibraryNamespaces) -> std::io::Result<()> {
+	writeln!(out, "path: {}", path.display())?;
+	writeln!(out, "nodes: {}", graph.nodes.len())?;
+	writeln!(out, "edges: {}", graph.edges.len())?;
+	writeln!(out, "truncated: {}", truncation_label(graph.truncated))?;
+	write_warnings(out, &graph.warnings)?;
 
 	let by_ptr: HashMap<u64, _> = graph.nodes.iter().map(|node| (node.canonical, node)).collect();
 	for edge in &graph.edges {
 		let from = by_ptr.get(&edge.from).copied();
 		let to = by_ptr.get(&edge.to).copied();
-		println!("{} -{}-> {}", node_label(from), edge.field, node_label(to));
+		writeln!(out, "{} -{}-> {}", node_label(from, namespaces), edge.field, node_label(to, namespaces))?;
 	}
+	Ok(())
 }
 
-fn print_dot(graph: &IdGraphResult) {
-	println!("digraph blendoc_idgraph {{");
+fn write_dot(out: &mut impl Write, graph: &IdGraphResult, namespaces: &LibraryNamespaces) -> std::io::Result<()> {
+	writeln!(out, "digraph blendoc_idgraph {{")?;
 	for node in &graph.nodes {
-		let label = format!("{}\\n{}", node.id_name, node.type_name);
-		println!("  \"0x{:016x}\" [label=\"{}\"]", node.canonical, dot_escape(&label));
+		let label = format!("{}\\n{}", namespaces.annotate(node.canonical, &node.id_name), node.type_name);
+		writeln!(out, "  \"0x{:016x}\" [label=\"{}\"]", node.canonical, dot_escape(&label))?;
 	}
 	for edge in &graph.edges {
-		println!("  \"0x{:016x}\" -> \"0x{:016x}\" [label=\"{}\"]", edge.from, edge.to, dot_escape(&edge.field));
+		writeln!(
+			out,
+			"  \"0x{:016x}\" -> \"0x{:016x}\" [label=\"{}\"]",
+			edge.from,
+			edge.to,
+			dot_escape(&edge.field)
+		)?;
 	}
-	println!("}}");
+	writeln!(out, "}}")
 }
 
-fn print_json(path: &std::path::Path, graph: &IdGraphResult) {
-	let payload = IdGraphJson {
+fn json_payload(path: &std::path::Path, graph: &IdGraphResult, namespaces: &LibraryNamespaces) -> IdGraphJson {
+	IdGraphJson {
 		path: path.display().to_string(),
 		truncated: truncation_value(graph.truncated).map(str::to_owned),
 		nodes: graph
@@ -121,6 +145,7 @@ fn print_json(path: &std::path::Path, graph: &IdGraphResult) {
 				sdna_nr: node.sdna_nr,
 				type_name: node.type_name.to_string(),
 				id: node.id_name.to_string(),
+				library: namespaces.get(node.canonical).map(str::to_owned),
 			})
 			.collect(),
 		edges: graph
@@ -132,21 +157,36 @@ fn print_json(path: &std::path::Path, graph: &IdGraphResult) {
 				field: edge.field.to_string(),
 			})
 			.collect(),
-	};
+		warnings: warnings_json(&graph.warnings),
+	}
+}
 
-	emit_json(&payload);
+fn edge_rows(graph: &IdGraphResult, namespaces: &LibraryNamespaces) -> Vec<IdGraphEdgeRow> {
+	let by_ptr: HashMap<u64, _> = graph.nodes.iter().map(|node| (node.canonical, node)).collect();
+	graph
+		.edges
+		.iter()
+		.map(|edge| IdGraphEdgeRow {
+			from: ptr_hex(edge.from),
+			from_label: node_label(by_ptr.get(&edge.from).copied(), namespaces),
+			field: edge.field.to_string(),
+			to: ptr_hex(edge.to),
+			to_label: node_label(by_ptr.get(&edge.to).copied(), namespaces),
+		})
+		.collect()
 }
 
-fn node_label(node: Option<&blendoc::blend::IdGraphNode>) -> String {
+fn node_label(node: Option<&blendoc::blend::IdGraphNode>, namespaces: &LibraryNamespaces) -> String {
 	let Some(node) = node else {
 		return "<unknown>".to_owned();
 	};
-	format!("{}({})", node.id_name, node.type_name)
+	format!("{}({})", namespaces.annotate(node.canonical, &node.id_name), node.type_name)
 }
 
 fn truncation_label(value: Option<IdGraphTruncation>) -> &'static str {
 	match value {
 		Some(IdGraphTruncation::MaxEdges) => "max_edges",
+		Some(IdGraphTruncation::Cancelled) => "cancelled",
 		None => "none",
 	}
 }
@@ -154,6 +194,7 @@ fn truncation_label(value: Option<IdGraphTruncation>) -> &'static str {
 fn truncation_value(value: Option<IdGraphTruncation>) -> Option<&'static str> {
 	match value {
 		Some(IdGraphTruncation::MaxEdges) => Some("max_edges"),
+		Some(IdGraphTruncation::Cancelled) => Some("cancelled"),
 		None => None,
 	}
 }
@@ -164,6 +205,7 @@ struct IdGraphJson {
 	truncated: Option<String>,
 	nodes: Vec<IdGraphNodeJson>,
 	edges: Vec<IdGraphEdgeJson>,
+	warnings: Vec<WarningJson>,
 }
 
 #[derive(serde::Serialize)]
@@ -174,6 +216,8 @@ struct IdGraphNodeJson {
 	#[serde(rename = "type")]
 	type_name: String,
 	id: String,
+	#[serde(skip_serializing_if = "Option::is_none")]
+	library: Option<String>,
 }
 
 #[derive(serde::Serialize)]
@@ -182,3 +226,13 @@ struct IdGraphEdgeJson {
 	to: String,
 	field: String,
 }
+
+/// Flat `--format csv` edge row with endpoint labels.
+#[derive(serde::Serialize)]
+struct IdGraphEdgeRow {
+	from: String,
+	from_label: String,
+	field: String,
+	to: String,
+	to_label: String,
+}
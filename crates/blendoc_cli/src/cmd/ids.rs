@@ -1,113 +1,272 @@
 use std::collections::HashMap;
+use std::io::Write;
 use std::path::PathBuf;
 
-use blendoc::blend::{BlendFile, IdRecord, Result, scan_id_blocks, scan_id_link_provenance};
+use blendoc::blend::{
+	BlendFile, Dna, FileMeta, IdRecord, LibraryNamespaces, Result, format_unix_time, glob_match, parse_unix_time, read_file_meta, scan_id_link_provenance,
+};
 
-use crate::cmd::util::{emit_json, parse_block_code, ptr_hex, ptr_hex_opt, render_code};
+use crate::cmd::input::{BlendInput, GitRevisionJson, write_revision};
+use crate::cmd::output::{OutputArgs, OutputFormat};
+use crate::cmd::stats::timed;
+use crate::cmd::util::{library_namespaces, parse_block_code, ptr_hex, ptr_hex_opt, render_code, scan_ids};
 
 #[derive(clap::Args)]
 pub struct Args {
-	pub path: PathBuf,
+	#[arg(required_unless_present = "git")]
+	pub path: Option<PathBuf>,
+	#[arg(long, conflicts_with = "path")]
+	pub git: Option<String>,
 	#[arg(long)]
 	pub code: Option<String>,
 	#[arg(long = "type")]
 	pub type_name: Option<String>,
+	#[arg(long = "match")]
+	pub pattern: Option<String>,
+	#[arg(long = "ignore-case", short = 'i')]
+	pub ignore_case: bool,
 	#[arg(long)]
 	pub limit: Option<usize>,
 	#[arg(long = "verbose-links")]
 	pub verbose_links: bool,
+	#[arg(long = "annotate-libs")]
+	pub annotate_libs: bool,
+	#[arg(long = "file-meta")]
+	pub file_meta: bool,
+	#[arg(long = "modified-since", value_name = "DATE", value_parser = parse_date)]
+	pub modified_since: Option<i64>,
+	#[arg(long = "built-before", value_name = "DATE", value_parser = parse_date)]
+	pub built_before: Option<i64>,
 	#[arg(long)]
 	pub json: bool,
+	#[command(flatten)]
+	pub output: OutputArgs,
 }
 
 /// Scan and print ID-root block summaries.
 pub fn run(args: Args) -> Result<()> {
 	let Args {
 		path,
+		git,
 		code,
 		type_name,
+		pattern,
+		ignore_case,
 		limit,
 		verbose_links,
+		annotate_libs,
+		file_meta,
+		modified_since,
+		built_before,
 		json,
+		output,
 	} = args;
 
-	let blend = BlendFile::open(&path)?;
-	let dna = blend.dna()?;
+	let input = BlendInput::from_args(path, git)?;
+	let (blend, revision) = input.open()?;
+	let mut out = output.open(json)?;
+	let dna = timed("dna", || blend.dna())?;
 
-	let mut rows = scan_id_blocks(&blend, &dna)?;
+	let mut rows = timed("scan", || scan_ids(&blend, &dna))?;
 
-	if let Some(filter) = code {
-		let parsed = parse_block_code(&filter)?;
-		rows.retain(|row| row.code == parsed);
-	}
-
-	if let Some(filter) = type_name {
-		rows.retain(|row| row.type_name.as_ref() == filter.as_str());
+	let wants_meta = file_meta || modified_since.is_some() || built_before.is_some();
+	let meta = if wants_meta {
+		let meta = timed("file_meta", || read_file_meta(&blend, &dna))?;
+		Some(SaveInfo::new(&meta, input.modified(revision.as_ref())))
+	} else {
+		None
+	};
+	let excluded = meta.as_ref().and_then(|meta| meta.excluded_by(modified_since, built_before));
+	if excluded.is_some() {
+		rows.clear();
 	}
 
-	rows.sort_by_key(|row| row.old_ptr);
-
-	if let Some(max) = limit {
-		rows.truncate(max);
-	}
+	let filter = IdFilter {
+		code,
+		type_name,
+		pattern,
+		ignore_case,
+		limit,
+	};
+	filter.apply(&mut rows)?;
 
-	let link_by_ptr = if json || verbose_links {
-		let links = scan_id_link_provenance(&blend, &dna)?;
-		links
-			.into_iter()
-			.map(|item| (item.id_ptr, (item.linked, item.confidence.as_str().to_owned())))
-			.collect::<HashMap<_, _>>()
+	let link_by_ptr = if !out.is_text() || verbose_links {
+		timed("links", || link_confidences(&blend, &dna))?
 	} else {
 		HashMap::new()
 	};
 
-	if json {
-		print_json_rows(&rows, Some(&link_by_ptr));
-		return Ok(());
+	let namespaces = timed("libs", || library_namespaces(&blend, &dna, annotate_libs))?;
+
+	if !out.is_text() {
+		// CSV needs the same columns on every row, so unknown provenance reads as unlinked.
+		let mut rows = json_rows(&rows, &link_by_ptr, &namespaces, out.format() == OutputFormat::Csv);
+		if let Some(meta) = meta.as_ref().filter(|_| file_meta) {
+			for row in &mut rows {
+				row.saved_version = Some(meta.saved_version.clone());
+				row.build_date = meta.build_date.clone();
+				row.modified = meta.modified.clone();
+			}
+		}
+		match revision.as_ref() {
+			Some(revision) => {
+				let payload = IdsRevisionJson {
+					revision: GitRevisionJson::new(revision),
+					items: &rows,
+				};
+				out.structured(&payload, &rows)?;
+			}
+			None => out.structured(&rows, &rows)?,
+		}
+		return out.finish();
 	}
 
-	println!("path: {}", path.display());
-	println!("ids: {}", rows.len());
+	writeln!(out, "path: {}", input.label())?;
+	write_revision(&mut out, revision.as_ref())?;
+	if let Some(meta) = meta.as_ref().filter(|_| file_meta) {
+		writeln!(out, "saved_version: {}", meta.saved_version)?;
+		writeln!(out, "build_date: {}", meta.build_date.as_deref().unwrap_or("-"))?;
+		writeln!(out, "modified: {}", meta.modified.as_deref().unwrap_or("-"))?;
+	}
+	if let Some(reason) = &excluded {
+		writeln!(out, "excluded: {reason}")?;
+	}
+	writeln!(out, "ids: {}", rows.len())?;
 	if verbose_links {
-		println!("old_ptr\tcode\tsdna\ttype\tid_name\tnext\tprev\tlib\tlinked\tlink_confidence");
+		writeln!(out, "old_ptr\tcode\tsdna\ttype\tid_name\tnext\tprev\tlib\tlinked\tlink_confidence")?;
 	} else {
-		println!("old_ptr\tcode\tsdna\ttype\tid_name\tnext\tprev\tlib");
+		writeln!(out, "old_ptr\tcode\tsdna\ttype\tid_name\tnext\tprev\tlib")?;
 	}
 	for row in rows {
+		let id_name = namespaces.annotate(row.old_ptr, &row.id_name);
 		if verbose_links {
 			let (linked, confidence) = link_by_ptr
 				.get(&row.old_ptr)
 				.map(|(linked, confidence)| (*linked, confidence.as_str()))
 				.unwrap_or((false, "none"));
-			println!(
+			writeln!(
+				out,
 				"0x{:016x}\t{}\t{}\t{}\t{}\t{}\t{}\t{}\t{}\t{}",
 				row.old_ptr,
 				render_code(row.code),
 				row.sdna_nr,
 				row.type_name,
-				row.id_name,
+				id_name,
 				format_ptr(row.next),
 				format_ptr(row.prev),
 				format_ptr(row.lib),
 				linked,
 				confidence
-			);
+			)?;
 		} else {
-			println!(
+			writeln!(
+				out,
 				"0x{:016x}\t{}\t{}\t{}\t{}\t{}\t{}\t{}",
 				row.old_ptr,
 				render_code(row.code),
 				row.sdna_nr,
 				row.type_name,
-				row.id_name,
+				id_name,
 				format_ptr(row.next),
 				format_ptr(row.prev),
 				format_ptr(row.lib)
-			);
+			)?;
+		}
+	}
+
+	out.finish()
+}
+
+/// `--code`/`--type`/`--match`/`--limit` row filters.
+pub(crate) struct IdFilter {
+	pub code: Option<String>,
+	pub type_name: Option<String>,
+	pub pattern: Option<String>,
+	pub ignore_case: bool,
+	pub limit: Option<usize>,
+}
+
+impl IdFilter {
+	/// Keep matching rows, sorted by pointer and truncated to the limit.
+	pub(crate) fn apply(&self, rows: &mut Vec<IdRecord>) -> Result<()> {
+		if let Some(filter) = &self.code {
+			let parsed = parse_block_code(filter)?;
+			rows.retain(|row| row.code == parsed);
+		}
+
+		if let Some(filter) = &self.type_name {
+			rows.retain(|row| row.type_name.as_ref() == filter.as_str());
 		}
+
+		if let Some(pattern) = &self.pattern {
+			rows.retain(|row| glob_match(pattern, &row.id_name, self.ignore_case));
+		}
+
+		rows.sort_by_key(|row| row.old_ptr);
+
+		if let Some(max) = self.limit {
+			rows.truncate(max);
+		}
+		Ok(())
 	}
+}
+
+/// Link flag and confidence label per ID pointer.
+pub(crate) fn link_confidences(blend: &BlendFile, dna: &Dna) -> Result<HashMap<u64, (bool, String)>> {
+	Ok(scan_id_link_provenance(blend, dna)?
+		.into_iter()
+		.map(|item| (item.id_ptr, (item.linked, item.confidence.as_str().to_owned())))
+		.collect())
+}
 
-	Ok(())
+/// File-level save metadata shared by every row of one file.
+struct SaveInfo {
+	saved_version: String,
+	build_date: Option<String>,
+	build_timestamp: Option<i64>,
+	modified: Option<String>,
+	modified_timestamp: Option<i64>,
+}
+
+impl SaveInfo {
+	fn new(meta: &FileMeta, modified: Option<i64>) -> Self {
+		Self {
+			saved_version: meta.saved_version(),
+			build_date: meta.build_date(),
+			build_timestamp: meta.build_timestamp,
+			modified: modified.map(format_unix_time),
+			modified_timestamp: modified,
+		}
+	}
+
+	/// Why the file fails a date filter; unknown dates never pass.
+	fn excluded_by(&self, modified_since: Option<i64>, built_before: Option<i64>) -> Option<String> {
+		if let Some(since) = modified_since {
+			// Without a file or commit time, the build date is the best lower bound on the last save.
+			let modified = self.modified_timestamp.or(self.build_timestamp);
+			if modified.is_none_or(|modified| modified < since) {
+				return Some(format!(
+					"last modified {} is before {}",
+					modified.map_or_else(|| "unknown".to_owned(), format_unix_time),
+					format_unix_time(since)
+				));
+			}
+		}
+		if let Some(before) = built_before
+			&& self.build_timestamp.is_none_or(|built| built >= before)
+		{
+			return Some(format!(
+				"saving build date {} is not before {}",
+				self.build_date.as_deref().unwrap_or("unknown"),
+				format_unix_time(before)
+			));
+		}
+		None
+	}
+}
+
+fn parse_date(value: &str) -> std::result::Result<i64, String> {
+	parse_unix_time(value).ok_or_else(|| format!("expected YYYY-MM-DD[THH:MM[:SS]] or @<unix seconds>, got `{value}`"))
 }
 
 fn format_ptr(value: Option<u64>) -> String {
@@ -117,8 +276,15 @@ fn format_ptr(value: Option<u64>) -> String {
 	}
 }
 
+/// `--git` payload: the revision next to the rows, which otherwise go out as a bare list.
+#[derive(serde::Serialize)]
+struct IdsRevisionJson<'a> {
+	revision: GitRevisionJson,
+	items: &'a [IdRowJson],
+}
+
 #[derive(serde::Serialize)]
-struct IdRowJson {
+pub(crate) struct IdRowJson {
 	old_ptr: String,
 	code: String,
 	sdna_nr: u32,
@@ -126,30 +292,49 @@ struct IdRowJson {
 	type_name: String,
 	id_name: String,
 	#[serde(skip_serializing_if = "Option::is_none")]
+	library: Option<String>,
+	#[serde(skip_serializing_if = "Option::is_none")]
 	linked: Option<bool>,
 	#[serde(skip_serializing_if = "Option::is_none")]
 	link_confidence: Option<String>,
 	next: Option<String>,
 	prev: Option<String>,
 	lib: Option<String>,
+	#[serde(skip_serializing_if = "Option::is_none")]
+	saved_version: Option<String>,
+	#[serde(skip_serializing_if = "Option::is_none")]
+	build_date: Option<String>,
+	#[serde(skip_serializing_if = "Option::is_none")]
+	modified: Option<String>,
 }
 
-fn print_json_rows(rows: &[IdRecord], link_by_ptr: Option<&HashMap<u64, (bool, String)>>) {
-	let body: Vec<IdRowJson> = rows
-		.iter()
-		.map(|row| IdRowJson {
-			old_ptr: ptr_hex(row.old_ptr),
-			code: render_code(row.code),
-			sdna_nr: row.sdna_nr,
-			type_name: row.type_name.to_string(),
-			id_name: row.id_name.to_string(),
-			linked: link_by_ptr.and_then(|item| item.get(&row.old_ptr)).map(|(linked, _)| *linked),
-			link_confidence: link_by_ptr.and_then(|item| item.get(&row.old_ptr)).map(|(_, confidence)| confidence.clone()),
-			next: ptr_hex_opt(row.next),
-			prev: ptr_hex_opt(row.prev),
-			lib: ptr_hex_opt(row.lib),
+/// JSON rows for `rows`; `fill_links` reports unknown provenance as unlinked.
+pub(crate) fn json_rows(rows: &[IdRecord], link_by_ptr: &HashMap<u64, (bool, String)>, namespaces: &LibraryNamespaces, fill_links: bool) -> Vec<IdRowJson> {
+	rows.iter()
+		.map(|row| {
+			let link = link_by_ptr
+				.get(&row.old_ptr)
+				.cloned()
+				.or_else(|| fill_links.then(|| (false, "none".to_owned())));
+			IdRowJson {
+				old_ptr: ptr_hex(row.old_ptr),
+				code: render_code(row.code),
+				sdna_nr: row.sdna_nr,
+				type_name: row.type_name.to_string(),
+				id_name: row.id_name.to_string(),
+				library: namespaces
+					.get(row.old_ptr)
+					.map(str::to_owned)
+					.or_else(|| (fill_links && !namespaces.is_empty()).then(String::new)),
+				linked: link.as_ref().map(|(linked, _)| *linked),
+				link_confidence: link.map(|(_, confidence)| confidence),
+				next: ptr_hex_opt(row.next),
+				prev: ptr_hex_opt(row.prev),
+				lib: ptr_hex_opt(row.lib),
+				saved_version: None,
+				build_date: None,
+				modified: None,
+			}
 		})
-		.collect();
-
-	emit_json(&body);
+		.collect()
 }
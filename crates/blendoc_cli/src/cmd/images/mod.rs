@@ -0,0 +1,139 @@
+use std::io::Write;
+use std::path::{Path, PathBuf};
+
+use blendoc::blend::{BlendFile, ImageRecord, Result, scan_images};
+
+use crate::cmd::output::OutputArgs;
+use crate::cmd::util::{human_bytes, ptr_hex};
+
+/// Path tokens Blender expands per tile or frame; such paths are checked by directory.
+const PATH_TOKENS: &[&str] = &["<UDIM>", "<UVTILE>", "#"];
+
+#[derive(clap::Args)]
+pub struct Args {
+	pub file: PathBuf,
+	#[arg(long)]
+	pub missing: bool,
+	#[arg(long)]
+	pub json: bool,
+	#[command(flatten)]
+	pub output: OutputArgs,
+}
+
+/// List every `Image` with its path, source, packed state, resolution, and colorspace.
+///
+/// File-backed images that are neither packed nor linked are checked on disk,
+/// with `//` paths resolved against the blend file's directory; `--missing`
+/// keeps only the ones that do not exist.
+pub fn run(args: Args) -> Result<()> {
+	let Args {
+		file: path,
+		missing,
+		json,
+		output,
+	} = args;
+
+	let blend = BlendFile::open(&path)?;
+	let dna = blend.dna()?;
+	let blend_dir = path.parent().unwrap_or(Path::new("."));
+	let rows: Vec<ImageRow> = scan_images(&blend, &dna)?
+		.iter()
+		.map(|image| image_row(image, blend_dir))
+		.filter(|row| !missing || row.status == "missing")
+		.collect();
+
+	let mut out = output.open(json)?;
+	if !out.is_text() {
+		let payload = ImagesJson {
+			path: path.display().to_string(),
+			images: &rows,
+		};
+		out.structured(&payload, &rows)?;
+		return out.finish();
+	}
+
+	let packed = rows.iter().filter(|row| row.packed).count();
+	let missing = rows.iter().filter(|row| row.status == "missing").count();
+	writeln!(out, "path: {}", path.display())?;
+	writeln!(out, "images: {} ({packed} packed, {missing} missing)", rows.len())?;
+	for row in &rows {
+		let status = match row.status {
+			"packed" => format!("packed {}", human_bytes(row.packed_bytes)),
+			"missing" => "MISSING".to_owned(),
+			other => other.to_owned(),
+		};
+		writeln!(
+			out,
+			"{}\t{}\t{status}{}\t{}\t{}\t{}",
+			row.id,
+			row.source,
+			if row.absolute && !row.packed { " (absolute)" } else { "" },
+			row.resolution.as_deref().unwrap_or("-"),
+			row.colorspace.as_deref().unwrap_or("-"),
+			row.filepath.as_deref().unwrap_or("-")
+		)?;
+	}
+	out.finish()
+}
+
+fn image_row(image: &ImageRecord, blend_dir: &Path) -> ImageRow {
+	ImageRow {
+		id: image.id_name.to_string(),
+		ptr: ptr_hex(image.ptr),
+		source: image.source.as_str(),
+		filepath: image.filepath.as_deref().map(str::to_owned),
+		absolute: image.is_absolute(),
+		status: path_status(image, blend_dir),
+		packed: image.is_packed(),
+		packed_bytes: image.packed_bytes,
+		resolution: image.resolution.map(|(width, height)| format!("{width}x{height}")),
+		colorspace: image.colorspace.as_deref().map(str::to_owned),
+	}
+}
+
+/// `packed`, `linked`, `internal` (generated or viewer), `no-path`, `ok`, or `missing`.
+fn path_status(image: &ImageRecord, blend_dir: &Path) -> &'static str {
+	if image.is_packed() {
+		return "packed";
+	}
+	if image.lib.is_some() {
+		return "linked";
+	}
+	if !image.source.reads_file() {
+		return "internal";
+	}
+	let Some(resolved) = image.resolved_path(blend_dir) else {
+		return "no-path";
+	};
+	let templated = PATH_TOKENS.iter().any(|token| resolved.to_string_lossy().contains(token));
+	let target = if templated {
+		resolved.parent().map(Path::to_path_buf)
+	} else {
+		Some(resolved)
+	};
+	if target.is_some_and(|target| target.exists()) { "ok" } else { "missing" }
+}
+
+#[derive(serde::Serialize)]
+struct ImagesJson<'a> {
+	path: String,
+	images: &'a [ImageRow],
+}
+
+/// One image; also the `--format csv` row.
+#[derive(serde::Serialize)]
+struct ImageRow {
+	id: String,
+	ptr: String,
+	source: &'static str,
+	filepath: Option<String>,
+	absolute: bool,
+	status: &'static str,
+	packed: bool,
+	packed_bytes: u64,
+	resolution: Option<String>,
+	colorspace: Option<String>,
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,27 @@
+use crate::cmd::test_support::{TempBlend, fixture_path, run_blendoc, run_blendoc_json};
+
+#[test]
+fn character_images_report_source_and_status() {
+	let fixture = fixture_path("character.blend");
+	let fixture = fixture.to_string_lossy().into_owned();
+	let json = run_blendoc_json(&["images", &fixture, "--json"]);
+
+	let images = json["images"].as_array().expect("images array");
+	for image in images {
+		assert!(image["source"].is_string());
+		assert_eq!(image["packed"].as_bool(), Some(image["status"] == "packed"));
+	}
+}
+
+#[test]
+fn scene_without_images_reports_none() {
+	let target = TempBlend::scene("images");
+	let target_arg = target.arg();
+
+	let text = run_blendoc(&["images", target_arg]);
+	let missing = run_blendoc_json(&["images", target_arg, "--missing", "--json"]);
+
+	assert!(text.status.success(), "images failed: {}", String::from_utf8_lossy(&text.stderr));
+	assert!(String::from_utf8_lossy(&text.stdout).contains("images: 0 (0 packed, 0 missing)"));
+	assert_eq!(missing["images"], serde_json::json!([]));
+}
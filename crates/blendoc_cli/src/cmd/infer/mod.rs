@@ -0,0 +1,206 @@
+use std::io::Write;
+use std::path::PathBuf;
+
+use blendoc::blend::{BlendFile, BlockInference, IdIndex, InferOptions, InferOwner, Result, infer_raw_blocks};
+
+use crate::cmd::output::OutputArgs;
+use crate::cmd::util::{parse_ptr, ptr_hex, render_code, scan_ids};
+
+#[derive(clap::Args)]
+pub struct Args {
+	pub file: PathBuf,
+	#[arg(long)]
+	pub ptr: Option<String>,
+	#[arg(long, default_value_t = 50)]
+	pub limit: usize,
+	#[arg(long, default_value_t = 5)]
+	pub candidates: usize,
+	#[arg(long)]
+	pub json: bool,
+	#[command(flatten)]
+	pub output: OutputArgs,
+}
+
+/// Rank element-type guesses for raw `DATA` blocks.
+pub fn run(args: Args) -> Result<()> {
+	let Args {
+		file: path,
+		ptr,
+		limit,
+		candidates,
+		json,
+		output,
+	} = args;
+
+	let blend = BlendFile::open(&path)?;
+	let dna = blend.dna()?;
+	let index = blend.pointer_index()?;
+	let ids = IdIndex::build(scan_ids(&blend, &dna)?);
+
+	let options = InferOptions {
+		target: ptr.as_deref().map(parse_ptr).transpose()?,
+		max_blocks: limit,
+		max_candidates: candidates,
+		..InferOptions::default()
+	};
+	let report = infer_raw_blocks(&blend, &dna, &index, &ids, &options)?;
+
+	let mut out = output.open(json)?;
+	if !out.is_text() {
+		let payload = InferJson {
+			path: path.display().to_string(),
+			raw_blocks: report.raw_blocks,
+			blocks: report.blocks.iter().map(block_json).collect(),
+		};
+		out.structured(&payload, &candidate_rows(&report.blocks))?;
+		return out.finish();
+	}
+
+	writeln!(out, "path: {}", path.display())?;
+	writeln!(out, "raw_blocks: {}", report.raw_blocks)?;
+	writeln!(out, "shown: {}", report.blocks.len())?;
+	for block in &report.blocks {
+		writeln!(
+			out,
+			"{} {} len={} owner={} ptr={:.2} float={:.2} int={:.2}",
+			ptr_hex(block.old_ptr),
+			render_code(block.code),
+			block.len,
+			block.owner.as_ref().map_or_else(|| "-".to_owned(), owner_label),
+			block.pointer_density,
+			block.float_plausibility,
+			block.int_plausibility
+		)?;
+		for candidate in &block.candidates {
+			writeln!(
+				out,
+				"  {:.2} {} x{} ({})",
+				candidate.score,
+				candidate.label,
+				candidate.count,
+				candidate.source.as_str()
+			)?;
+		}
+	}
+	out.finish()
+}
+
+/// `MEMesh.vdata (void *vdata)`; non-ID owners show type and pointer.
+fn owner_label(owner: &InferOwner) -> String {
+	let base = match &owner.owner_id {
+		Some(id_name) => id_name.to_string(),
+		None => format!("{}@{}", owner.owner_type, ptr_hex(owner.owner_ptr)),
+	};
+	format!("{base}.{} ({} {})", owner.field, owner.field_type, owner.decl)
+}
+
+fn block_json(block: &BlockInference) -> BlockJson {
+	BlockJson {
+		ptr: ptr_hex(block.old_ptr),
+		code: render_code(block.code),
+		sdna_nr: block.sdna_nr,
+		len: block.len,
+		nr: block.nr,
+		owner: block.owner.as_ref().map(|owner| OwnerJson {
+			ptr: ptr_hex(owner.owner_ptr),
+			owner_type: owner.owner_type.to_string(),
+			id: owner.owner_id.as_deref().map(str::to_owned),
+			field: owner.field.to_string(),
+			decl: owner.decl.to_string(),
+			field_type: owner.field_type.to_string(),
+			refs: owner.refs,
+		}),
+		pointer_density: block.pointer_density,
+		float_plausibility: block.float_plausibility,
+		int_plausibility: block.int_plausibility,
+		candidates: block
+			.candidates
+			.iter()
+			.map(|candidate| CandidateJson {
+				label: candidate.label.to_string(),
+				source: candidate.source.as_str(),
+				element_size: candidate.element_size,
+				count: candidate.count,
+				score: candidate.score,
+			})
+			.collect(),
+	}
+}
+
+fn candidate_rows(blocks: &[BlockInference]) -> Vec<CandidateRowCsv> {
+	blocks
+		.iter()
+		.flat_map(|block| {
+			block.candidates.iter().enumerate().map(|(rank, candidate)| CandidateRowCsv {
+				ptr: ptr_hex(block.old_ptr),
+				len: block.len,
+				owner: block.owner.as_ref().map(owner_label).unwrap_or_default(),
+				rank: rank + 1,
+				label: candidate.label.to_string(),
+				source: candidate.source.as_str(),
+				element_size: candidate.element_size,
+				count: candidate.count,
+				score: candidate.score,
+			})
+		})
+		.collect()
+}
+
+#[derive(serde::Serialize)]
+struct InferJson {
+	path: String,
+	raw_blocks: usize,
+	blocks: Vec<BlockJson>,
+}
+
+#[derive(serde::Serialize)]
+struct BlockJson {
+	ptr: String,
+	code: String,
+	sdna_nr: u32,
+	len: u64,
+	nr: u64,
+	owner: Option<OwnerJson>,
+	pointer_density: f32,
+	float_plausibility: f32,
+	int_plausibility: f32,
+	candidates: Vec<CandidateJson>,
+}
+
+#[derive(serde::Serialize)]
+struct OwnerJson {
+	ptr: String,
+	#[serde(rename = "type")]
+	owner_type: String,
+	id: Option<String>,
+	field: String,
+	decl: String,
+	field_type: String,
+	refs: usize,
+}
+
+#[derive(serde::Serialize)]
+struct CandidateJson {
+	label: String,
+	source: &'static str,
+	element_size: usize,
+	count: u64,
+	score: f32,
+}
+
+/// Flat `--format csv` row: one ranked guess per line.
+#[derive(serde::Serialize)]
+struct CandidateRowCsv {
+	ptr: String,
+	len: u64,
+	owner: String,
+	rank: usize,
+	label: String,
+	source: &'static str,
+	element_size: usize,
+	count: u64,
+	score: f32,
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,21 @@
+use crate::cmd::test_support::{fixture_path, run_blendoc_json};
+
+#[test]
+fn character_raw_blocks_report_ranked_guesses() {
+	let fixture = fixture_path("character.blend");
+	let fixture = fixture.to_string_lossy().into_owned();
+	let json = run_blendoc_json(&["infer", &fixture, "--json", "--limit", "200"]);
+
+	assert!(json["raw_blocks"].as_u64().is_some_and(|count| count > 0));
+	let blocks = json["blocks"].as_array().expect("blocks array");
+	assert!(blocks.iter().any(|block| !block["owner"].is_null()));
+	for block in blocks {
+		let scores: Vec<f64> = block["candidates"]
+			.as_array()
+			.expect("candidates array")
+			.iter()
+			.map(|candidate| candidate["score"].as_f64().expect("score"))
+			.collect();
+		assert!(scores.windows(2).all(|pair| pair[0] >= pair[1]));
+	}
+}
@@ -0,0 +1,169 @@
+use blendoc::blend::{BlendHeader, Endianness};
+
+/// Container format accepted by `--assert-format`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, clap::ValueEnum)]
+pub enum FormatKind {
+	/// Pre-5.0 `BLENDER-v###` header with `BHead4`/`BHead8` blocks.
+	Legacy,
+	/// `BLENDER17-01` header with `LargeBHead8` blocks.
+	V1,
+}
+
+/// Byte order accepted by `--assert-endianness`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, clap::ValueEnum)]
+pub enum EndiannessKind {
+	Little,
+	Big,
+}
+
+/// Comparison used by `--assert-version`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum VersionOp {
+	Eq,
+	Ne,
+	Lt,
+	Le,
+	Gt,
+	Ge,
+}
+
+/// Parsed `--assert-version` expression such as `>=500`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct VersionRequirement {
+	pub op: VersionOp,
+	pub version: u16,
+}
+
+impl VersionRequirement {
+	fn matches(self, version: u16) -> bool {
+		match self.op {
+			VersionOp::Eq => version == self.version,
+			VersionOp::Ne => version != self.version,
+			VersionOp::Lt => version < self.version,
+			VersionOp::Le => version <= self.version,
+			VersionOp::Gt => version > self.version,
+			VersionOp::Ge => version >= self.version,
+		}
+	}
+
+	fn label(self) -> String {
+		let op = match self.op {
+			VersionOp::Eq => "==",
+			VersionOp::Ne => "!=",
+			VersionOp::Lt => "<",
+			VersionOp::Le => "<=",
+			VersionOp::Gt => ">",
+			VersionOp::Ge => ">=",
+		};
+		format!("{op}{}", self.version)
+	}
+}
+
+/// Parse `[op]VERSION` where `op` is one of `==`, `=`, `!=`, `<`, `<=`, `>`, `>=` (default `==`).
+pub(crate) fn parse_version_requirement(value: &str) -> Result<VersionRequirement, String> {
+	let value = value.trim();
+	let (op, rest) = [
+		(">=", VersionOp::Ge),
+		("<=", VersionOp::Le),
+		("==", VersionOp::Eq),
+		("!=", VersionOp::Ne),
+		(">", VersionOp::Gt),
+		("<", VersionOp::Lt),
+		("=", VersionOp::Eq),
+	]
+	.iter()
+	.find_map(|(token, op)| value.strip_prefix(token).map(|rest| (*op, rest)))
+	.unwrap_or((VersionOp::Eq, value));
+
+	let version = rest
+		.trim()
+		.parse::<u16>()
+		.map_err(|_| format!("expected [op]VERSION such as '>=500', got '{value}'"))?;
+	Ok(VersionRequirement { op, version })
+}
+
+fn parse_pointer_size(value: &str) -> Result<u8, String> {
+	match value {
+		"4" => Ok(4),
+		"8" => Ok(8),
+		_ => Err(format!("pointer size must be 4 or 8, got '{value}'")),
+	}
+}
+
+/// Header assertions for CI gates; any failure makes the command exit 1.
+#[derive(clap::Args, Debug, Clone, Default)]
+pub struct AssertArgs {
+	#[arg(long = "assert-format", value_enum)]
+	pub assert_format: Option<FormatKind>,
+	#[arg(long = "assert-version", value_parser = parse_version_requirement)]
+	pub assert_version: Option<VersionRequirement>,
+	#[arg(long = "assert-pointer-size", value_parser = parse_pointer_size)]
+	pub assert_pointer_size: Option<u8>,
+	#[arg(long = "assert-endianness", value_enum)]
+	pub assert_endianness: Option<EndiannessKind>,
+}
+
+/// Result of one header assertion.
+#[derive(Debug, Clone, serde::Serialize)]
+pub(crate) struct AssertionOutcome {
+	pub check: &'static str,
+	pub expected: String,
+	pub actual: String,
+	pub ok: bool,
+}
+
+impl AssertArgs {
+	/// Evaluate every requested assertion against a parsed header.
+	pub(crate) fn evaluate(&self, header: &BlendHeader) -> Vec<AssertionOutcome> {
+		let mut out = Vec::new();
+		if let Some(format) = self.assert_format {
+			let actual = if header.format_version == BlendHeader::LEGACY_FORMAT_VERSION {
+				FormatKind::Legacy
+			} else {
+				FormatKind::V1
+			};
+			out.push(AssertionOutcome {
+				check: "format",
+				expected: format_label(format).to_owned(),
+				actual: format_label(actual).to_owned(),
+				ok: actual == format,
+			});
+		}
+		if let Some(requirement) = self.assert_version {
+			out.push(AssertionOutcome {
+				check: "version",
+				expected: requirement.label(),
+				actual: header.version.to_string(),
+				ok: requirement.matches(header.version),
+			});
+		}
+		if let Some(pointer_size) = self.assert_pointer_size {
+			out.push(AssertionOutcome {
+				check: "pointer_size",
+				expected: pointer_size.to_string(),
+				actual: header.pointer_size.to_string(),
+				ok: usize::from(pointer_size) == header.pointer_size,
+			});
+		}
+		if let Some(endianness) = self.assert_endianness {
+			let expected = match endianness {
+				EndiannessKind::Little => Endianness::Little,
+				EndiannessKind::Big => Endianness::Big,
+			};
+			out.push(AssertionOutcome {
+				check: "endianness",
+				expected: expected.as_str().to_owned(),
+				actual: header.endianness.as_str().to_owned(),
+				ok: expected == header.endianness,
+			});
+		}
+		out
+	}
+}
+
+fn format_label(format: FormatKind) -> &'static str {
+	match format {
+		FormatKind::Legacy => "legacy",
+		FormatKind::V1 => "v1",
+	}
+}
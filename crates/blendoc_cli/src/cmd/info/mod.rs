@@ -1,91 +1,260 @@
+use std::io::Write;
 use std::path::PathBuf;
 
-use blendoc::blend::{BlendFile, PointerIndex, PointerStorage, Result};
+use blendoc::blend::{
+	BHeadCapabilities, BHeadLayout, BlendFile, BlockAlignment, BlockStats, FileMeta, PointerIndex, PointerStorage, Result, Warning, format_unix_time,
+	read_file_meta, scan_block_alignment,
+};
 
-use crate::cmd::util::{emit_json, ptr_hex};
+use self::assert::{AssertArgs, AssertionOutcome};
+use crate::cmd::input::{BlendInput, GitRevision, GitRevisionJson, write_revision};
+use crate::cmd::output::OutputArgs;
+use crate::cmd::stats::timed;
+use crate::cmd::util::{WarningJson, ptr_hex, render_code, warnings_json, write_warnings};
 
 #[derive(clap::Args)]
 pub struct Args {
-	pub path: PathBuf,
+	#[arg(required_unless_present = "git")]
+	pub path: Option<PathBuf>,
+	#[arg(long, conflicts_with = "path")]
+	pub git: Option<String>,
 	#[arg(long)]
 	pub json: bool,
+	#[command(flatten)]
+	pub output: OutputArgs,
+	#[command(flatten)]
+	pub assert: AssertArgs,
 }
 
 /// Print high-level file and block statistics.
 pub fn run(args: Args) -> Result<()> {
-	let Args { path, json } = args;
-
-	let blend = BlendFile::open(&path)?;
-	let stats = blend.scan_block_stats()?;
-	let pointer_index = blend.pointer_index()?;
-	let pointer_storage = pointer_index.storage();
-	let pointer_diag = analyze_pointer_index(&pointer_index);
-
-	let mut entries: Vec<_> = stats.codes.into_iter().collect();
-	entries.sort_by(|left, right| right.1.cmp(&left.1).then_with(|| left.0.cmp(&right.0)));
-
-	if json {
-		let payload = InfoJson {
-			path: path.display().to_string(),
-			compression: blend.compression.as_str().to_owned(),
-			header_size: blend.header.header_size,
-			format_version: blend.header.format_version,
-			version: blend.header.version,
-			bhead_layout: blend.header.bhead_layout_label(),
-			endianness: blend.header.endianness.as_str(),
-			pointer_size: blend.header.pointer_size as u8,
-			pointer_storage: pointer_storage_label(pointer_storage).to_owned(),
-			pointer_diagnostics: PointerDiagnosticsJson {
-				indexed_entries: pointer_diag.indexed_entries,
-				overlapping_ranges: pointer_diag.overlapping_ranges,
-				duplicate_starts: pointer_diag.duplicate_starts,
-				min_old: pointer_diag.min_old.map(ptr_hex),
-				max_old: pointer_diag.max_old.map(ptr_hex),
-				max_end: pointer_diag.max_end.map(ptr_hex),
-			},
-			block_count: stats.block_count,
-			has_dna1: stats.has_dna1,
-			has_endb: stats.has_endb,
-			last_code: code_label(stats.last_code),
-			top_codes: entries
-				.iter()
-				.take(12)
-				.map(|(code, count)| CodeCountJson {
-					code: code_label(*code),
-					count: *count,
-				})
-				.collect(),
+	let Args {
+		path,
+		git,
+		json,
+		output,
+		assert,
+	} = args;
+
+	let input = BlendInput::from_args(path, git)?;
+	let (blend, revision) = input.open()?;
+	let mut out = output.open(json)?;
+	let assertions = assert.evaluate(&blend.header);
+	let modified = input.modified(revision.as_ref());
+
+	if !out.is_text() {
+		let payload = json_payload(&blend, input.label(), revision.as_ref(), modified, assertions.clone())?;
+		let row = InfoRowCsv {
+			path: payload.path.clone(),
+			compression: payload.compression.clone(),
+			version: payload.version,
+			bhead_layout: payload.bhead_layout,
+			endianness: payload.endianness,
+			pointer_size: payload.pointer_size,
+			pointer_storage: payload.pointer_storage.clone(),
+			block_count: payload.block_count,
+			saved_version: payload.file_meta.saved_version.clone(),
+			build_date: payload.file_meta.build_date.clone(),
+			modified: payload.file_meta.modified.clone(),
+			warnings: payload.warnings.len(),
 		};
-		emit_json(&payload);
-		return Ok(());
+		out.structured(&payload, &[row])?;
+		out.finish()?;
+		return check_assertions(&assertions);
+	}
+
+	let Report {
+		bhead_layout,
+		stats,
+		alignment,
+		entries,
+		pointer_storage,
+		pointer_diag,
+		warnings,
+		file_meta,
+	} = Report::collect(&blend)?;
+	let bhead_caps = bhead_layout.capabilities();
+
+	writeln!(out, "path: {}", input.label())?;
+	write_revision(&mut out, revision.as_ref())?;
+	writeln!(out, "compression: {}", blend.compression.as_str())?;
+	writeln!(out, "header_size: {}", blend.header.header_size)?;
+	writeln!(out, "format_version: {}", blend.header.format_version)?;
+	writeln!(out, "version: {}", blend.header.version)?;
+	if let Some(meta) = &file_meta {
+		writeln!(out, "saved_version: {}", meta.saved_version())?;
+		writeln!(out, "min_version: {}", meta.min_version_label().unwrap_or_else(|| "-".to_owned()))?;
+		writeln!(out, "build_date: {}", meta.build_date().unwrap_or_else(|| "-".to_owned()))?;
+		writeln!(out, "build_hash: {}", meta.build_hash.as_deref().unwrap_or("-"))?;
+		writeln!(out, "saved_path: {}", meta.saved_path.as_deref().unwrap_or("-"))?;
+		match &meta.scene {
+			Some(scene) => writeln!(
+				out,
+				"current_scene: {} frame={} range={}..{}",
+				scene.name,
+				opt_label(scene.current),
+				opt_label(scene.start),
+				opt_label(scene.end)
+			)?,
+			None => writeln!(out, "current_scene: -")?,
+		}
 	}
+	writeln!(out, "modified: {}", modified.map_or_else(|| "-".to_owned(), format_unix_time))?;
+	writeln!(out, "bhead_layout: {}", blend.header.bhead_layout_label())?;
+	writeln!(out, "bhead_variant: {}", bhead_layout.as_str())?;
+	writeln!(out, "bhead_size: {}", bhead_caps.size)?;
+	writeln!(out, "bhead_field_order: {}", bhead_caps.field_order.as_str())?;
+	writeln!(out, "endianness: {}", blend.header.endianness.as_str())?;
+	writeln!(out, "pointer_size: {}", blend.header.pointer_size)?;
+	writeln!(out, "pointer_storage: {}", pointer_storage_label(pointer_storage))?;
+	writeln!(out, "pointer_blocks_indexed: {}", pointer_diag.indexed_entries)?;
+	writeln!(out, "pointer_overlapping_ranges: {}", pointer_diag.overlapping_ranges)?;
+	writeln!(out, "pointer_duplicate_starts: {}", pointer_diag.duplicate_starts)?;
+	writeln!(out, "pointer_min_old: {}", ptr_hex_opt(pointer_diag.min_old))?;
+	writeln!(out, "pointer_max_old: {}", ptr_hex_opt(pointer_diag.max_old))?;
+	writeln!(out, "pointer_max_end: {}", ptr_hex_opt(pointer_diag.max_end))?;
+	writeln!(out, "block_count: {}", stats.block_count)?;
+	writeln!(out, "has_dna1: {}", stats.has_dna1)?;
+	writeln!(out, "has_endb: {}", stats.has_endb)?;
+	writeln!(out, "last_code: {}", render_code(stats.last_code))?;
+	writeln!(out, "block_alignment: {}", alignment.convention())?;
+	writeln!(out, "block_header_alignment: {}", alignment.header_alignment)?;
+	writeln!(out, "block_payload_alignment: {}", alignment.payload_alignment)?;
+	writeln!(out, "block_padding: {} bytes in {} blocks", alignment.padding_bytes, alignment.padded_blocks)?;
+	writeln!(out, "trailing_bytes: {}", alignment.trailing_bytes)?;
 
-	println!("path: {}", path.display());
-	println!("compression: {}", blend.compression.as_str());
-	println!("header_size: {}", blend.header.header_size);
-	println!("format_version: {}", blend.header.format_version);
-	println!("version: {}", blend.header.version);
-	println!("bhead_layout: {}", blend.header.bhead_layout_label());
-	println!("endianness: {}", blend.header.endianness.as_str());
-	println!("pointer_size: {}", blend.header.pointer_size);
-	println!("pointer_storage: {}", pointer_storage_label(pointer_storage));
-	println!("pointer_blocks_indexed: {}", pointer_diag.indexed_entries);
-	println!("pointer_overlapping_ranges: {}", pointer_diag.overlapping_ranges);
-	println!("pointer_duplicate_starts: {}", pointer_diag.duplicate_starts);
-	println!("pointer_min_old: {}", ptr_hex_opt(pointer_diag.min_old));
-	println!("pointer_max_old: {}", ptr_hex_opt(pointer_diag.max_old));
-	println!("pointer_max_end: {}", ptr_hex_opt(pointer_diag.max_end));
-	println!("block_count: {}", stats.block_count);
-	println!("has_dna1: {}", stats.has_dna1);
-	println!("has_endb: {}", stats.has_endb);
-	println!("last_code: {}", code_label(stats.last_code));
-
-	println!("top_codes:");
+	writeln!(out, "top_codes:")?;
 	for (code, count) in entries.into_iter().take(12) {
-		println!("  {}: {}", code_label(code), count);
+		writeln!(out, "  {}: {}", render_code(code), count)?;
+	}
+	write_warnings(&mut out, &warnings)?;
+	if !assertions.is_empty() {
+		writeln!(out, "assertions:")?;
+		for item in &assertions {
+			let status = if item.ok { "ok" } else { "FAILED" };
+			writeln!(out, "  {}: expected={} actual={} {status}", item.check, item.expected, item.actual)?;
+		}
+	}
+
+	out.finish()?;
+	check_assertions(&assertions)
+}
+
+/// File-level facts shared by the text and structured reports.
+struct Report {
+	bhead_layout: BHeadLayout,
+	stats: BlockStats,
+	alignment: BlockAlignment,
+	/// Block codes by descending count, then code.
+	entries: Vec<([u8; 4], u32)>,
+	pointer_storage: PointerStorage,
+	pointer_diag: PointerDiagnostics,
+	warnings: Vec<Warning>,
+	file_meta: Option<FileMeta>,
+}
+
+impl Report {
+	fn collect(blend: &BlendFile) -> Result<Self> {
+		let bhead_layout = blend.header.bhead_layout()?;
+		let mut stats = timed("blocks", || blend.scan_block_stats())?;
+		let alignment = timed("alignment", || scan_block_alignment(blend))?;
+		let pointer_index = timed("pointer_index", || blend.pointer_index())?;
+		// Files without a readable `DNA1` still get the header-level report.
+		let file_meta = timed("file_meta", || blend.dna().and_then(|dna| read_file_meta(blend, &dna))).ok();
+
+		let mut entries: Vec<_> = std::mem::take(&mut stats.codes).into_iter().collect();
+		entries.sort_by(|left, right| right.1.cmp(&left.1).then_with(|| left.0.cmp(&right.0)));
+
+		Ok(Self {
+			bhead_layout,
+			stats,
+			alignment,
+			entries,
+			pointer_storage: pointer_index.storage(),
+			pointer_diag: analyze_pointer_index(&pointer_index),
+			warnings: pointer_index.overlap_warnings(),
+			file_meta,
+		})
 	}
+}
+
+/// `--json` payload for one opened file.
+pub(crate) fn json_payload(
+	blend: &BlendFile,
+	label: String,
+	revision: Option<&GitRevision>,
+	modified: Option<i64>,
+	assertions: Vec<AssertionOutcome>,
+) -> Result<InfoJson> {
+	let Report {
+		bhead_layout,
+		stats,
+		alignment,
+		entries,
+		pointer_storage,
+		pointer_diag,
+		warnings,
+		file_meta,
+	} = Report::collect(blend)?;
+	let bhead_caps = bhead_layout.capabilities();
+
+	Ok(InfoJson {
+		path: label,
+		revision: revision.map(GitRevisionJson::new),
+		compression: blend.compression.as_str().to_owned(),
+		header_size: blend.header.header_size,
+		format_version: blend.header.format_version,
+		version: blend.header.version,
+		bhead_layout: blend.header.bhead_layout_label(),
+		bhead: BHeadJson::new(bhead_layout.as_str(), bhead_caps),
+		endianness: blend.header.endianness.as_str(),
+		pointer_size: blend.header.pointer_size as u8,
+		pointer_storage: pointer_storage_label(pointer_storage).to_owned(),
+		pointer_diagnostics: PointerDiagnosticsJson {
+			indexed_entries: pointer_diag.indexed_entries,
+			overlapping_ranges: pointer_diag.overlapping_ranges,
+			duplicate_starts: pointer_diag.duplicate_starts,
+			min_old: pointer_diag.min_old.map(ptr_hex),
+			max_old: pointer_diag.max_old.map(ptr_hex),
+			max_end: pointer_diag.max_end.map(ptr_hex),
+		},
+		block_count: stats.block_count,
+		has_dna1: stats.has_dna1,
+		has_endb: stats.has_endb,
+		last_code: render_code(stats.last_code),
+		block_alignment: BlockAlignmentJson {
+			convention: alignment.convention(),
+			header_alignment: alignment.header_alignment,
+			payload_alignment: alignment.payload_alignment,
+			padded_blocks: alignment.padded_blocks,
+			padding_bytes: alignment.padding_bytes,
+			max_padding: alignment.max_padding,
+			trailing_bytes: alignment.trailing_bytes,
+		},
+		file_meta: FileMetaJson::new(file_meta.as_ref(), modified),
+		top_codes: entries
+			.iter()
+			.take(12)
+			.map(|(code, count)| CodeCountJson {
+				code: render_code(*code),
+				count: *count,
+			})
+			.collect(),
+		warnings: warnings_json(&warnings),
+		assertions,
+	})
+}
 
-	Ok(())
+/// Report failed assertions on stderr and exit 1 so CI gates need no JSON post-processing.
+fn check_assertions(assertions: &[AssertionOutcome]) -> Result<()> {
+	let failed: Vec<_> = assertions.iter().filter(|item| !item.ok).collect();
+	if failed.is_empty() {
+		return Ok(());
+	}
+	for item in failed {
+		eprintln!("assertion failed: {} expected {}, found {}", item.check, item.expected, item.actual);
+	}
+	std::process::exit(1);
 }
 
 #[derive(Debug, Clone, Copy)]
@@ -138,6 +307,10 @@ fn analyze_pointer_index(index: &PointerIndex<'_>) -> PointerDiagnostics {
 	}
 }
 
+fn opt_label(value: Option<i64>) -> String {
+	value.map_or_else(|| "-".to_owned(), |value| value.to_string())
+}
+
 fn ptr_hex_opt(value: Option<u64>) -> String {
 	value.map(ptr_hex).unwrap_or_else(|| "-".to_owned())
 }
@@ -152,6 +325,17 @@ struct PointerDiagnosticsJson {
 	max_end: Option<String>,
 }
 
+#[derive(serde::Serialize)]
+struct BlockAlignmentJson {
+	convention: &'static str,
+	header_alignment: usize,
+	payload_alignment: usize,
+	padded_blocks: u32,
+	padding_bytes: u64,
+	max_padding: usize,
+	trailing_bytes: usize,
+}
+
 #[derive(serde::Serialize)]
 struct CodeCountJson {
 	code: String,
@@ -159,13 +343,39 @@ struct CodeCountJson {
 }
 
 #[derive(serde::Serialize)]
-struct InfoJson {
+struct BHeadJson {
+	variant: &'static str,
+	size: usize,
+	old_width: usize,
+	count_width: usize,
+	field_order: &'static str,
+	stable_ids: bool,
+}
+
+impl BHeadJson {
+	fn new(variant: &'static str, caps: BHeadCapabilities) -> Self {
+		Self {
+			variant,
+			size: caps.size,
+			old_width: caps.old_width,
+			count_width: caps.count_width,
+			field_order: caps.field_order.as_str(),
+			stable_ids: caps.stable_ids,
+		}
+	}
+}
+
+#[derive(serde::Serialize)]
+pub(crate) struct InfoJson {
 	path: String,
+	#[serde(skip_serializing_if = "Option::is_none")]
+	revision: Option<GitRevisionJson>,
 	compression: String,
 	header_size: usize,
 	format_version: u16,
 	version: u16,
 	bhead_layout: &'static str,
+	bhead: BHeadJson,
 	endianness: &'static str,
 	pointer_size: u8,
 	pointer_storage: String,
@@ -174,22 +384,70 @@ struct InfoJson {
 	has_dna1: bool,
 	has_endb: bool,
 	last_code: String,
+	block_alignment: BlockAlignmentJson,
+	file_meta: FileMetaJson,
 	top_codes: Vec<CodeCountJson>,
+	warnings: Vec<WarningJson>,
+	#[serde(skip_serializing_if = "Vec::is_empty")]
+	assertions: Vec<AssertionOutcome>,
 }
 
-fn code_label(code: [u8; 4]) -> String {
-	let mut out = String::new();
-	for byte in code {
-		if byte == 0 {
-			continue;
-		}
-		if byte.is_ascii_graphic() || byte == b' ' {
-			out.push(char::from(byte));
-		} else {
-			out.push('.');
+/// Save-time metadata; everything but `modified` is `null` when the SDNA is unreadable.
+#[derive(serde::Serialize)]
+struct FileMetaJson {
+	saved_version: Option<String>,
+	min_version: Option<String>,
+	build_date: Option<String>,
+	build_timestamp: Option<i64>,
+	build_hash: Option<String>,
+	saved_path: Option<String>,
+	modified: Option<String>,
+	current_scene: Option<SceneFramesJson>,
+}
+
+impl FileMetaJson {
+	fn new(meta: Option<&FileMeta>, modified: Option<i64>) -> Self {
+		Self {
+			saved_version: meta.map(FileMeta::saved_version),
+			min_version: meta.and_then(FileMeta::min_version_label),
+			build_date: meta.and_then(FileMeta::build_date),
+			build_timestamp: meta.and_then(|meta| meta.build_timestamp),
+			build_hash: meta.and_then(|meta| meta.build_hash.as_deref().map(str::to_owned)),
+			saved_path: meta.and_then(|meta| meta.saved_path.as_deref().map(str::to_owned)),
+			modified: modified.map(format_unix_time),
+			current_scene: meta.and_then(|meta| meta.scene.as_ref()).map(|scene| SceneFramesJson {
+				name: scene.name.to_string(),
+				frame_current: scene.current,
+				frame_start: scene.start,
+				frame_end: scene.end,
+			}),
 		}
 	}
-	if out.is_empty() { "....".to_owned() } else { out }
+}
+
+#[derive(serde::Serialize)]
+struct SceneFramesJson {
+	name: String,
+	frame_current: Option<i64>,
+	frame_start: Option<i64>,
+	frame_end: Option<i64>,
+}
+
+/// One-line `--format csv` summary; nested diagnostics stay JSON/YAML-only.
+#[derive(serde::Serialize)]
+struct InfoRowCsv {
+	path: String,
+	compression: String,
+	version: u16,
+	bhead_layout: &'static str,
+	endianness: &'static str,
+	pointer_size: u8,
+	pointer_storage: String,
+	block_count: u32,
+	saved_version: Option<String>,
+	build_date: Option<String>,
+	modified: Option<String>,
+	warnings: usize,
 }
 
 fn pointer_storage_label(storage: PointerStorage) -> &'static str {
@@ -199,5 +457,7 @@ fn pointer_storage_label(storage: PointerStorage) -> &'static str {
 	}
 }
 
+mod assert;
+
 #[cfg(test)]
 mod tests;
@@ -1,4 +1,4 @@
-use crate::cmd::test_support::{fixture_path, run_blendoc_json};
+use crate::cmd::test_support::{TempBlend, fixture_path, run_blendoc, run_blendoc_json};
 
 #[test]
 fn info_json_includes_pointer_diagnostics() {
@@ -27,3 +27,149 @@ fn info_json_reports_supported_pointer_storage_value() {
 	let storage = json["pointer_storage"].as_str().expect("pointer storage label should be present");
 	assert!(matches!(storage, "address_ranges" | "stable_ids"));
 }
+
+#[test]
+fn info_json_includes_bhead_capabilities() {
+	let fixture = fixture_path("v5.1_character.blend");
+	let fixture = fixture.to_string_lossy().into_owned();
+	let json = run_blendoc_json(&["info", &fixture, "--json"]);
+
+	let bhead = &json["bhead"];
+	assert_eq!(bhead["variant"], "large_bhead8");
+	assert_eq!(bhead["size"], 32);
+	assert_eq!(bhead["field_order"], "code_sdna_old_len_nr");
+	assert_eq!(bhead["stable_ids"], true);
+}
+
+#[test]
+fn info_mem_stats_reports_phases_on_stderr() {
+	let fixture = fixture_path("character.blend");
+	let fixture = fixture.to_string_lossy().into_owned();
+	let output = run_blendoc(&["info", &fixture, "--json", "--mem-stats"]);
+	assert!(output.status.success(), "info failed: {}", String::from_utf8_lossy(&output.stderr));
+
+	serde_json::from_slice::<serde_json::Value>(&output.stdout).expect("stdout stays valid json");
+	let stderr = String::from_utf8_lossy(&output.stderr);
+	assert!(stderr.contains("mem_stats:"));
+	assert!(stderr.lines().any(|line| line.starts_with("command/pointer_index\t")));
+	assert!(stderr.contains("peak_live_bytes: "));
+}
+
+#[test]
+fn mem_stats_turns_on_heap_counting_from_argument_parsing() {
+	let target = TempBlend::scene("info_mem");
+	let target_arg = target.arg();
+	let counted = run_blendoc(&["info", target_arg, "--mem-stats"]);
+	let timed = run_blendoc(&["info", target_arg, "--timings"]);
+
+	let stderr = String::from_utf8_lossy(&counted.stderr);
+	let allocated = |label: &str| -> u64 {
+		let line = stderr.lines().find(|line| line.starts_with(&format!("{label}\t"))).expect("phase row");
+		line.split('\t').nth(2).and_then(|field| field.parse().ok()).expect("allocated bytes column")
+	};
+	assert!(allocated("args") > 0, "{stderr}");
+	assert!(allocated("command") > 0, "{stderr}");
+	let timed = String::from_utf8_lossy(&timed.stderr);
+	assert!(timed.contains("timings:") && !timed.contains("allocated_bytes"), "{timed}");
+}
+
+#[test]
+fn info_csv_output_writes_summary_row_to_file() {
+	let fixture = fixture_path("character.blend");
+	let fixture = fixture.to_string_lossy().into_owned();
+	let target = std::env::temp_dir().join(format!("blendoc_info_{}.csv", std::process::id()));
+	let target_arg = target.to_string_lossy().into_owned();
+
+	let output = run_blendoc(&["info", &fixture, "--format", "csv", "--output", &target_arg]);
+	assert!(output.status.success(), "info failed: {}", String::from_utf8_lossy(&output.stderr));
+	assert!(output.stdout.is_empty(), "--output should keep stdout empty");
+
+	let written = std::fs::read_to_string(&target).expect("csv output written");
+	std::fs::remove_file(&target).expect("csv output removed");
+
+	let mut lines = written.lines();
+	assert_eq!(
+		lines.next(),
+		Some("path,compression,version,bhead_layout,endianness,pointer_size,pointer_storage,block_count,saved_version,build_date,modified,warnings")
+	);
+	assert!(lines.next().is_some_and(|row| row.contains(",zstd,")), "expected one summary row");
+	assert_eq!(lines.next(), None);
+}
+
+#[test]
+fn version_requirement_parses_operators() {
+	use super::assert::{VersionOp, parse_version_requirement};
+
+	assert_eq!(parse_version_requirement(">=500").map(|item| (item.op, item.version)), Ok((VersionOp::Ge, 500)));
+	assert_eq!(parse_version_requirement("< 402").map(|item| (item.op, item.version)), Ok((VersionOp::Lt, 402)));
+	assert_eq!(parse_version_requirement("300").map(|item| (item.op, item.version)), Ok((VersionOp::Eq, 300)));
+	assert!(parse_version_requirement(">=five").is_err());
+}
+
+#[test]
+fn failed_header_assertions_exit_nonzero_with_structured_message() {
+	let target = TempBlend::scene("info_assert");
+	let target_arg = target.arg();
+
+	let passing = run_blendoc(&["info", target_arg, "--assert-format", "legacy", "--assert-pointer-size", "8"]);
+	let failing = run_blendoc(&[
+		"info",
+		target_arg,
+		"--json",
+		"--assert-format",
+		"v1",
+		"--assert-version",
+		">=500",
+		"--assert-endianness",
+		"little",
+	]);
+
+	assert!(
+		passing.status.success(),
+		"passing assertions failed: {}",
+		String::from_utf8_lossy(&passing.stderr)
+	);
+	assert_eq!(failing.status.code(), Some(1));
+	let stderr = String::from_utf8_lossy(&failing.stderr);
+	assert!(stderr.contains("assertion failed: format expected v1, found legacy"));
+	assert!(stderr.contains("assertion failed: version expected >=500, found 300"));
+
+	let json: serde_json::Value = serde_json::from_slice(&failing.stdout).expect("stdout stays valid json");
+	let outcomes: Vec<(&str, bool)> = json["assertions"]
+		.as_array()
+		.expect("assertions array")
+		.iter()
+		.map(|item| (item["check"].as_str().unwrap_or_default(), item["ok"].as_bool().unwrap_or_default()))
+		.collect();
+	assert_eq!(outcomes, [("format", false), ("version", false), ("endianness", true)]);
+}
+
+#[test]
+fn file_meta_reported_by_info_and_filters_ids() {
+	let target = TempBlend::scene("info_meta");
+	let target_arg = target.arg();
+
+	let info = run_blendoc_json(&["info", target_arg, "--json"]);
+	let recent = run_blendoc_json(&["ids", target_arg, "--modified-since", "2000-01-01", "--file-meta", "--json"]);
+	let future = run_blendoc(&["ids", target_arg, "--modified-since", "@99999999999"]);
+	let built = run_blendoc_json(&["ids", target_arg, "--built-before", "2100-01-01", "--json"]);
+
+	let meta = &info["file_meta"];
+	assert_eq!(meta["saved_version"], "3.0");
+	assert_eq!(meta["build_date"], serde_json::Value::Null);
+	assert_eq!(meta["current_scene"]["name"], "SCScene");
+	assert!(meta["modified"].as_str().is_some_and(|time| time.ends_with('Z')));
+	assert_eq!(info["block_alignment"]["convention"], "packed");
+	assert_eq!(info["block_alignment"]["trailing_bytes"], 0);
+
+	let items = recent["items"].as_array().expect("ids items");
+	assert_eq!(items.len(), 4);
+	assert_eq!(items[0]["saved_version"], "3.0");
+	assert_eq!(items[0]["modified"], meta["modified"]);
+
+	let future = String::from_utf8_lossy(&future.stdout);
+	assert!(future.contains("excluded: last modified"), "{future}");
+	assert!(future.contains("ids: 0"), "{future}");
+	// Without a `GLOB` build date the file cannot be shown to predate the cutoff.
+	assert_eq!(built["items"].as_array().map(Vec::len), Some(0));
+}
@@ -0,0 +1,141 @@
+use std::io::Write;
+use std::path::PathBuf;
+use std::process::{Command, Stdio};
+
+use blendoc::blend::{BlendError, BlendFile, Result};
+
+use crate::cmd::stats;
+
+/// First line of a git-lfs pointer file stored in place of the real blob.
+const LFS_POINTER_PREFIX: &[u8] = b"version https://git-lfs.github.com/spec/v1";
+
+/// Source of `.blend` bytes for commands accepting `<path>`, `-` (stdin), or `--git REV:PATH`.
+pub(crate) enum BlendInput {
+	Path(PathBuf),
+	Stdin,
+	Git(String),
+}
+
+/// Revision a `--git` input was read from.
+pub(crate) struct GitRevision {
+	pub spec: String,
+	pub commit: String,
+}
+
+impl BlendInput {
+	/// Build from the mutually exclusive positional path and `--git` spec.
+	pub(crate) fn from_args(path: Option<PathBuf>, git: Option<String>) -> Result<Self> {
+		match (path, git) {
+			(_, Some(spec)) => Ok(Self::Git(spec)),
+			(Some(path), None) if path.as_os_str() == "-" => Ok(Self::Stdin),
+			(Some(path), None) => Ok(Self::Path(path)),
+			(None, None) => Err(BlendError::Io(std::io::Error::other("missing input path or --git REV:PATH"))),
+		}
+	}
+
+	/// Label printed as `path:` in command output.
+	pub(crate) fn label(&self) -> String {
+		match self {
+			Self::Path(path) => path.display().to_string(),
+			Self::Stdin => "<stdin>".to_owned(),
+			Self::Git(spec) => format!("git:{spec}"),
+		}
+	}
+
+	/// Open the input; filesystem paths are memory-mapped.
+	pub(crate) fn open(&self) -> Result<(BlendFile, Option<GitRevision>)> {
+		let _phase = stats::phase("open");
+		match self {
+			Self::Path(path) => Ok((BlendFile::open_mmap(path)?, None)),
+			Self::Stdin => Ok((BlendFile::from_reader(std::io::stdin().lock())?, None)),
+			Self::Git(spec) => {
+				let (rev, file) = spec.split_once(':').ok_or_else(|| git_error(spec, "expected REV:PATH"))?;
+				let commit = run_git(spec, &["rev-parse", "--verify", &format!("{rev}^{{commit}}")], None)?;
+				let mut bytes = run_git(spec, &["cat-file", "blob", spec], None)?;
+				if bytes.starts_with(LFS_POINTER_PREFIX) {
+					bytes = run_git(spec, &["lfs", "smudge", "--", file], Some(&bytes))?;
+				}
+
+				let revision = GitRevision {
+					spec: spec.clone(),
+					commit: String::from_utf8_lossy(&commit).trim().to_owned(),
+				};
+				Ok((BlendFile::from_bytes(bytes)?, Some(revision)))
+			}
+		}
+	}
+
+	/// Last-modified time in Unix seconds: file mtime, or the commit time for git inputs.
+	pub(crate) fn modified(&self, revision: Option<&GitRevision>) -> Option<i64> {
+		match self {
+			Self::Path(path) => {
+				let modified = std::fs::metadata(path).ok()?.modified().ok()?;
+				let seconds = modified.duration_since(std::time::UNIX_EPOCH).ok()?.as_secs();
+				i64::try_from(seconds).ok()
+			}
+			Self::Stdin => None,
+			Self::Git(spec) => {
+				let commit = &revision?.commit;
+				let time = run_git(spec, &["show", "-s", "--format=%ct", commit], None).ok()?;
+				String::from_utf8_lossy(&time).trim().parse().ok()
+			}
+		}
+	}
+}
+
+/// Print `revision:`/`commit:` lines for git inputs.
+pub(crate) fn print_revision(revision: Option<&GitRevision>) {
+	write_revision(&mut crate::cmd::quiet::stdout(), revision).expect("stdout write should succeed");
+}
+
+/// Write the [`print_revision`] lines to `out`.
+pub(crate) fn write_revision(out: &mut impl Write, revision: Option<&GitRevision>) -> std::io::Result<()> {
+	if let Some(revision) = revision {
+		writeln!(out, "revision: {}", revision.spec)?;
+		writeln!(out, "commit: {}", revision.commit)?;
+	}
+	Ok(())
+}
+
+/// JSON form of a git input revision.
+#[derive(serde::Serialize)]
+pub(crate) struct GitRevisionJson {
+	spec: String,
+	commit: String,
+}
+
+impl GitRevisionJson {
+	pub(crate) fn new(revision: &GitRevision) -> Self {
+		Self {
+			spec: revision.spec.clone(),
+			commit: revision.commit.clone(),
+		}
+	}
+}
+
+fn run_git(spec: &str, args: &[&str], stdin: Option<&[u8]>) -> Result<Vec<u8>> {
+	let mut child = Command::new("git")
+		.args(args)
+		.stdin(if stdin.is_some() { Stdio::piped() } else { Stdio::null() })
+		.stdout(Stdio::piped())
+		.stderr(Stdio::piped())
+		.spawn()
+		.map_err(|err| git_error(spec, &format!("failed to spawn git: {err}")))?;
+
+	if let (Some(input), Some(mut pipe)) = (stdin, child.stdin.take()) {
+		pipe.write_all(input)?;
+	}
+
+	let output = child.wait_with_output()?;
+	if !output.status.success() {
+		return Err(git_error(spec, String::from_utf8_lossy(&output.stderr).trim()));
+	}
+	Ok(output.stdout)
+}
+
+fn git_error(spec: &str, message: &str) -> BlendError {
+	BlendError::GitInput {
+		spec: spec.to_owned(),
+		message: message.to_owned(),
+	}
+}
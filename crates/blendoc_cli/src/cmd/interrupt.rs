@@ -0,0 +1,48 @@
+use std::sync::OnceLock;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+use blendoc::blend::CancelToken;
+
+/// Exit status for a run stopped by Ctrl-C (128 + SIGINT).
+pub(crate) const EXIT_INTERRUPTED: i32 = 130;
+
+static TOKEN: OnceLock<CancelToken> = OnceLock::new();
+static ARMED: AtomicBool = AtomicBool::new(false);
+
+/// Route Ctrl-C through the shared cancel token.
+///
+/// While a cancellable traversal is running, the first Ctrl-C cancels it so
+/// the command prints partial results; otherwise, or on a second Ctrl-C, the
+/// process exits immediately as before.
+pub(crate) fn install() {
+	let token = TOKEN.get_or_init(CancelToken::new).clone();
+	let _ = ctrlc::set_handler(move || on_interrupt(&token));
+}
+
+fn on_interrupt(token: &CancelToken) {
+	if !ARMED.load(Ordering::Relaxed) || token.is_cancelled() {
+		std::process::exit(EXIT_INTERRUPTED);
+	}
+	token.cancel();
+	eprintln!("\ninterrupted; stopping with partial results (Ctrl-C again to abort)");
+}
+
+/// Token for traversal options; arms the Ctrl-C handler to cancel instead of exit.
+pub(crate) fn token() -> Option<CancelToken> {
+	let token = TOKEN.get()?.clone();
+	ARMED.store(true, Ordering::Relaxed);
+	Some(token)
+}
+
+/// Whether Ctrl-C cancelled a traversal in this run.
+pub(crate) fn was_interrupted() -> bool {
+	TOKEN.get().is_some_and(CancelToken::is_cancelled)
+}
+
+/// Disarm and clear the token between REPL commands.
+pub(crate) fn reset() {
+	ARMED.store(false, Ordering::Relaxed);
+	if let Some(token) = TOKEN.get() {
+		token.reset();
+	}
+}
@@ -0,0 +1,48 @@
+use std::cell::RefCell;
+use std::collections::HashMap;
+
+use blendoc::blend::{DecodeCache, StructValue};
+
+/// Pointer resolution caches shared by every output of one command invocation.
+///
+/// Commands create one up front and hand it to each [`PrintCtx`] they build, so
+/// printing text and writing files (or answering several REPL queries) resolves
+/// each pointer once. Entries are keyed by pointer alone: an instance must not
+/// outlive the file and `DecodeOptions` it was filled from.
+///
+/// [`PrintCtx`]: crate::cmd::print::PrintCtx
+#[derive(Default)]
+pub struct Invocation {
+	annotations: RefCell<HashMap<u64, String>>,
+	decoded: DecodeCache,
+}
+
+impl Invocation {
+	/// Rendered annotation for raw pointer `ptr`, computing it on first use.
+	pub(crate) fn annotation(&self, ptr: u64, render: impl FnOnce() -> String) -> String {
+		if let Some(cached) = self.annotations.borrow().get(&ptr) {
+			return cached.clone();
+		}
+		let rendered = render();
+		self.annotations.borrow_mut().insert(ptr, rendered.clone());
+		rendered
+	}
+
+	/// Decoded struct at `canonical`, decoding it on first use.
+	///
+	/// `decode` returns the canonical pointer it actually resolved alongside the
+	/// value; `None` (unresolvable) is not cached.
+	pub(crate) fn decoded(&self, canonical: u64, decode: impl FnOnce() -> Option<(u64, StructValue)>) -> Option<StructValue> {
+		if let Some(cached) = self.decoded.get(canonical) {
+			return Some(cached);
+		}
+		let (resolved, value) = decode()?;
+		self.decoded.insert(resolved, value.clone());
+		Some(value)
+	}
+
+	/// Handle to the decoded-struct cache, for chases run in this invocation.
+	pub(crate) fn decode_cache(&self) -> DecodeCache {
+		self.decoded.clone()
+	}
+}
@@ -1,8 +1,10 @@
+use std::io::Write;
 use std::path::PathBuf;
 
 use blendoc::blend::{BlendFile, LinkSignal, scan_id_link_provenance, scan_library_records};
 
-use crate::cmd::util::{emit_json, ptr_hex};
+use crate::cmd::output::OutputArgs;
+use crate::cmd::util::ptr_hex;
 
 #[derive(clap::Args)]
 pub struct Args {
@@ -13,6 +15,8 @@ pub struct Args {
 	pub limit: Option<usize>,
 	#[arg(long)]
 	pub json: bool,
+	#[command(flatten)]
+	pub output: OutputArgs,
 }
 
 /// Scan and print linked-library declarations and per-ID link provenance.
@@ -22,6 +26,7 @@ pub fn run(args: Args) -> blendoc::blend::Result<()> {
 		linked_only,
 		limit,
 		json,
+		output,
 	} = args;
 
 	let blend = BlendFile::open(&path)?;
@@ -36,7 +41,8 @@ pub fn run(args: Args) -> blendoc::blend::Result<()> {
 		ids.truncate(limit);
 	}
 
-	if json {
+	let mut out = output.open(json)?;
+	if !out.is_text() {
 		let payload = LibsJson {
 			path: path.display().to_string(),
 			libraries: libraries
@@ -60,25 +66,37 @@ pub fn run(args: Args) -> blendoc::blend::Result<()> {
 				})
 				.collect(),
 		};
-		emit_json(&payload);
-		return Ok(());
+		let rows: Vec<IdProvenanceRow> = ids
+			.iter()
+			.map(|item| IdProvenanceRow {
+				id_name: item.id_name.to_string(),
+				type_name: item.type_name.to_string(),
+				id_ptr: ptr_hex(item.id_ptr),
+				linked: item.linked,
+				confidence: item.confidence.as_str(),
+				signals: format_signal_summary(&item.signals),
+			})
+			.collect();
+		out.structured(&payload, &rows)?;
+		return out.finish();
 	}
 
-	println!("path: {}", path.display());
-	println!("libraries: {}", libraries.len());
-	println!("ids: {}", ids.len());
-	println!();
-	println!("libraries:");
-	println!("id_name\tid_ptr\tpath\trelative");
+	writeln!(out, "path: {}", path.display())?;
+	writeln!(out, "libraries: {}", libraries.len())?;
+	writeln!(out, "ids: {}", ids.len())?;
+	writeln!(out)?;
+	writeln!(out, "libraries:")?;
+	writeln!(out, "id_name\tid_ptr\tpath\trelative")?;
 	for item in &libraries {
-		println!("{}\t{}\t{}\t{}", item.id_name, ptr_hex(item.id_ptr), item.library_path, item.is_relative);
+		writeln!(out, "{}\t{}\t{}\t{}", item.id_name, ptr_hex(item.id_ptr), item.library_path, item.is_relative)?;
 	}
-	println!();
-	println!("id_provenance:");
-	println!("id_name\ttype\tid_ptr\tlinked\tconfidence\tsignals");
+	writeln!(out)?;
+	writeln!(out, "id_provenance:")?;
+	writeln!(out, "id_name\ttype\tid_ptr\tlinked\tconfidence\tsignals")?;
 	for item in &ids {
 		let signals = format_signal_summary(&item.signals);
-		println!(
+		writeln!(
+			out,
 			"{}\t{}\t{}\t{}\t{}\t{}",
 			item.id_name,
 			item.type_name,
@@ -86,13 +104,13 @@ pub fn run(args: Args) -> blendoc::blend::Result<()> {
 			item.linked,
 			item.confidence.as_str(),
 			signals
-		);
+		)?;
 	}
 
-	Ok(())
+	out.finish()
 }
 
-fn format_signal_summary(signals: &[LinkSignal]) -> String {
+pub(crate) fn format_signal_summary(signals: &[LinkSignal]) -> String {
 	if signals.is_empty() {
 		return "-".to_owned();
 	}
@@ -164,6 +182,18 @@ struct IdProvenanceJson {
 	signals: Vec<SignalJson>,
 }
 
+/// Flat `--format csv` provenance row; signals use the text summary form.
+#[derive(serde::Serialize)]
+struct IdProvenanceRow {
+	id_name: String,
+	#[serde(rename = "type")]
+	type_name: String,
+	id_ptr: String,
+	linked: bool,
+	confidence: &'static str,
+	signals: String,
+}
+
 #[derive(serde::Serialize)]
 struct SignalJson {
 	kind: String,
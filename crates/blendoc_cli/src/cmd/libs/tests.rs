@@ -46,7 +46,7 @@ fn ids_refs_and_show_json_include_link_metadata() {
 	let fixture = fixture.to_string_lossy().into_owned();
 
 	let ids_json = run_blendoc_json(&["ids", &fixture, "--json"]);
-	let sword = ids_json
+	let sword = ids_json["items"]
 		.as_array()
 		.expect("ids json should be array")
 		.iter()
@@ -63,3 +63,23 @@ fn ids_refs_and_show_json_include_link_metadata() {
 	assert_eq!(show_json["root_linked"], true);
 	assert!(matches!(show_json["root_link_confidence"].as_str(), Some("medium") | Some("high")));
 }
+
+#[test]
+fn annotate_libs_labels_linked_ids_with_source_library() {
+	let fixture = fixture_path("character.blend");
+	let fixture = fixture.to_string_lossy().into_owned();
+
+	let ids = run_blendoc_json(&["ids", &fixture, "--annotate-libs", "--json"]);
+	let rows = ids["items"].as_array().expect("ids should be array");
+	assert!(rows.iter().any(|row| row["library"] == "lib://sword.blend"));
+	assert!(rows.iter().any(|row| row["id_name"] == "OBcharacter_model" && row.get("library").is_none()));
+
+	let graph = run_blendoc_json(&["idgraph", &fixture, "--annotate-libs", "--json"]);
+	assert!(
+		graph["nodes"]
+			.as_array()
+			.expect("nodes should be array")
+			.iter()
+			.any(|node| node["library"] == "lib://sword.blend")
+	);
+}
@@ -0,0 +1,145 @@
+use std::io::Write;
+use std::path::PathBuf;
+
+use blendoc::blend::{LibraryNode, LibraryStatus, LibraryTreeOptions, Result, build_library_tree};
+
+use crate::cmd::output::OutputArgs;
+
+#[derive(clap::Args)]
+pub struct Args {
+	pub file: PathBuf,
+	#[arg(long)]
+	pub follow: bool,
+	#[arg(long = "search-root")]
+	pub search_root: Option<PathBuf>,
+	#[arg(long = "max-depth", default_value_t = 16)]
+	pub max_depth: usize,
+	#[arg(long)]
+	pub json: bool,
+	#[command(flatten)]
+	pub output: OutputArgs,
+}
+
+/// Print the library dependency tree of a blend file.
+///
+/// Without `--follow` only the file's own libraries are resolved; with it
+/// each found library is opened and its libraries are resolved in turn.
+pub fn run(args: Args) -> Result<()> {
+	let Args {
+		file: path,
+		follow,
+		search_root,
+		max_depth,
+		json,
+		output,
+	} = args;
+
+	let options = LibraryTreeOptions {
+		search_root,
+		max_depth: if follow { max_depth } else { 0 },
+		..LibraryTreeOptions::default()
+	};
+	let tree = build_library_tree(&path, &options)?;
+
+	let mut rows = Vec::new();
+	tree.root.walk(&mut |node, depth| {
+		if depth > 0 {
+			rows.push(node_row(node, depth));
+		}
+	});
+	let problems = tree.problems().len();
+
+	let mut out = output.open(json)?;
+	if !out.is_text() {
+		let payload = LibTreeJson {
+			path: path.display().to_string(),
+			files_opened: tree.files_opened,
+			problems,
+			libraries: tree.root.children.iter().map(node_json).collect(),
+		};
+		out.structured(&payload, &rows)?;
+		return out.finish();
+	}
+
+	writeln!(out, "{}", path.display())?;
+	for row in &rows {
+		let indent = "  ".repeat(row.depth);
+		let resolved = match &row.resolved_path {
+			Some(resolved) if row.via_search_root => format!(" -> {resolved} (search root)"),
+			Some(resolved) if resolved != &row.declared_path => format!(" -> {resolved}"),
+			_ => String::new(),
+		};
+		let error = row.error.as_deref().map(|error| format!(": {error}")).unwrap_or_default();
+		writeln!(out, "{indent}{} {}{resolved} [{}{error}]", row.library_id, row.declared_path, row.status)?;
+	}
+	writeln!(out)?;
+	writeln!(out, "files_opened: {}", tree.files_opened)?;
+	writeln!(out, "missing_or_unreadable: {problems}")?;
+	out.finish()
+}
+
+fn node_row(node: &LibraryNode, depth: usize) -> LibraryRow {
+	LibraryRow {
+		depth,
+		library_id: node.library_id.as_deref().unwrap_or("").to_owned(),
+		declared_path: node.declared_path.to_string(),
+		resolved_path: node.resolved_path.as_ref().map(|path| path.display().to_string()),
+		via_search_root: node.via_search_root,
+		status: node.status.as_str(),
+		error: error_of(&node.status),
+	}
+}
+
+fn node_json(node: &LibraryNode) -> LibraryJson {
+	LibraryJson {
+		library_id: node.library_id.as_deref().unwrap_or("").to_owned(),
+		declared_path: node.declared_path.to_string(),
+		resolved_path: node.resolved_path.as_ref().map(|path| path.display().to_string()),
+		via_search_root: node.via_search_root,
+		status: node.status.as_str(),
+		error: error_of(&node.status),
+		children: node.children.iter().map(node_json).collect(),
+	}
+}
+
+fn error_of(status: &LibraryStatus) -> Option<String> {
+	match status {
+		LibraryStatus::Unreadable { error } => Some(error.to_string()),
+		_ => None,
+	}
+}
+
+#[derive(serde::Serialize)]
+struct LibTreeJson {
+	path: String,
+	files_opened: usize,
+	problems: usize,
+	libraries: Vec<LibraryJson>,
+}
+
+#[derive(serde::Serialize)]
+struct LibraryJson {
+	library_id: String,
+	declared_path: String,
+	resolved_path: Option<String>,
+	via_search_root: bool,
+	status: &'static str,
+	#[serde(skip_serializing_if = "Option::is_none")]
+	error: Option<String>,
+	children: Vec<LibraryJson>,
+}
+
+/// One library link in depth-first order; also the `--format csv` row.
+#[derive(serde::Serialize)]
+struct LibraryRow {
+	depth: usize,
+	library_id: String,
+	declared_path: String,
+	resolved_path: Option<String>,
+	via_search_root: bool,
+	status: &'static str,
+	error: Option<String>,
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,63 @@
+use std::path::Path;
+
+use blendoc_testkit::builder::{BlendBuilder, HeaderKind};
+
+use crate::cmd::test_support::{run_blendoc, run_blendoc_json};
+
+/// File declaring one `Library` ID per `(id name, path)` pair.
+fn library_file(libraries: &[(&str, &str)]) -> Vec<u8> {
+	let mut builder = BlendBuilder::new(HeaderKind::Legacy8, 404, false);
+	let sdna = builder.sdna_mut();
+	sdna.add_struct("Link", &[("Link", "*next"), ("Link", "*prev")]);
+	sdna.add_type("char", 1);
+	sdna.add_type("void", 0);
+	sdna.add_struct("ID", &[("void", "*next"), ("void", "*prev"), ("void", "*lib"), ("char", "name[24]")]);
+	let library = sdna.add_struct("Library", &[("ID", "id"), ("char", "name[64]")]);
+
+	for (idx, (id_name, path)) in libraries.iter().enumerate() {
+		let mut payload = builder.payload();
+		payload.ptr(0).ptr(0).ptr(0).name(id_name, 24).name(path, 64);
+		let library_payload = payload.finish();
+		builder.block(*b"LI\0\0", library, 0x1000 + idx as u64 * 0x100, 1, library_payload);
+	}
+	builder.build()
+}
+
+fn write_tree(dir: &Path) {
+	let _ = std::fs::remove_dir_all(dir);
+	std::fs::create_dir_all(dir.join("lib")).expect("dir created");
+	std::fs::write(
+		dir.join("root.blend"),
+		library_file(&[("LIa.blend", "//lib/a.blend"), ("LIgone.blend", "//gone.blend")]),
+	)
+	.expect("root written");
+	std::fs::write(dir.join("lib").join("a.blend"), library_file(&[("LIb.blend", "//b.blend")])).expect("a written");
+	std::fs::write(dir.join("lib").join("b.blend"), library_file(&[])).expect("b written");
+}
+
+#[test]
+fn follow_opens_nested_libraries_and_reports_missing() {
+	let dir = std::env::temp_dir().join(format!("blendoc_cli_libtree_{}", std::process::id()));
+	write_tree(&dir);
+	let root = dir.join("root.blend").to_string_lossy().into_owned();
+
+	let shallow = run_blendoc_json(&["libtree", &root, "--json"]);
+	let follow = run_blendoc_json(&["libtree", &root, "--follow", "--json"]);
+	let text = run_blendoc(&["libtree", &root, "--follow"]);
+	let _ = std::fs::remove_dir_all(&dir);
+
+	assert_eq!(shallow["files_opened"], 1);
+	assert_eq!(shallow["libraries"][0]["status"], "found");
+	assert_eq!(shallow["libraries"][1]["status"], "missing");
+
+	assert_eq!(follow["files_opened"], 3);
+	assert_eq!(follow["problems"], 1);
+	assert_eq!(follow["libraries"][0]["status"], "opened");
+	assert_eq!(follow["libraries"][0]["children"][0]["library_id"], "LIb.blend");
+	assert_eq!(follow["libraries"][0]["children"][0]["status"], "opened");
+
+	let text = String::from_utf8_lossy(&text.stdout);
+	assert!(text.contains("    LIb.blend //b.blend"), "{text}");
+	assert!(text.contains("LIgone.blend //gone.blend"), "{text}");
+	assert!(text.contains("missing_or_unreadable: 1"), "{text}");
+}
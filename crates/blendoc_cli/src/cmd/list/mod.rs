@@ -0,0 +1,175 @@
+use std::io::Write;
+use std::path::PathBuf;
+
+use blendoc::blend::{BlendFile, DecodeOptions, Dna, IdIndex, PointerIndex, Result, Value, WalkOptions, decode_ptr_instance, iter_listbase};
+
+use crate::cmd::output::OutputArgs;
+use crate::cmd::plugins::PluginArgs;
+use crate::cmd::util::{RootSelector, code_root_block, code_selector_label, parse_root_selector, ptr_hex, render_code, scan_ids};
+
+#[derive(clap::Args)]
+pub struct Args {
+	pub file: PathBuf,
+	#[arg(long = "id")]
+	pub id_name: Option<String>,
+	#[arg(long)]
+	pub ptr: Option<String>,
+	#[arg(long)]
+	pub code: Option<String>,
+	#[arg(long = "path")]
+	pub path_expr: String,
+	#[arg(long = "next", default_value = "next")]
+	pub next_field: String,
+	#[arg(long, default_value_t = 256)]
+	pub limit: usize,
+	#[arg(long)]
+	pub json: bool,
+	#[command(flatten)]
+	pub output: OutputArgs,
+	#[command(flatten)]
+	pub plugins: PluginArgs,
+}
+
+/// Walk the `ListBase` at `--path` on an ID/pointer/code root.
+///
+/// Replaces chasing `<path>.first` and then running `walk` by hand.
+pub fn run(args: Args) -> Result<()> {
+	let Args {
+		file: path,
+		id_name,
+		ptr,
+		code,
+		path_expr,
+		next_field,
+		limit,
+		json,
+		output,
+		plugins,
+	} = args;
+
+	let selector = parse_root_selector(code, ptr, id_name)?;
+
+	let blend = BlendFile::open(&path)?;
+	let dna = blend.dna()?;
+	let index = blend.pointer_index()?;
+	let ids = IdIndex::build(scan_ids(&blend, &dna)?);
+
+	let (root_ptr, root_label) = match selector {
+		RootSelector::Id(name) => {
+			let row = ids.resolve_selector(&name)?;
+			(row.old_ptr, format!("id:{}", row.id_name))
+		}
+		RootSelector::Ptr(ptr) => (ptr, format!("ptr:0x{ptr:016x}")),
+		RootSelector::Code(code, occurrence) => {
+			let block = code_root_block(&blend, code, occurrence)?;
+			(block.head.old, code_selector_label(code, occurrence))
+		}
+	};
+
+	let options = WalkOptions {
+		next_field: next_field.as_str().into(),
+		max_steps: limit,
+		..WalkOptions::default()
+	};
+	let walk = iter_listbase(&dna, &index, &ids, root_ptr, &path_expr, &options)?;
+	let last_matches = walk.last_matches(&dna, &index);
+
+	let decode = DecodeOptions {
+		max_depth: 1,
+		plugins: plugins.load()?,
+		..DecodeOptions::for_scene_inspect()
+	};
+	let rows: Vec<ListItemRow> = walk
+		.items
+		.iter()
+		.map(|item| ListItemRow {
+			index: item.index,
+			canonical: ptr_hex(item.canonical),
+			code: render_code(item.code),
+			type_name: item.type_name.to_string(),
+			id: item.id_name.as_deref().map(str::to_owned),
+			name: item_name(&dna, &index, item.canonical, &decode),
+		})
+		.collect();
+
+	let mut out = output.open(json)?;
+	if !out.is_text() {
+		let payload = ListJson {
+			path: path.display().to_string(),
+			root: root_label,
+			list: path_expr,
+			first: ptr_hex(walk.first),
+			last: ptr_hex(walk.last),
+			complete: walk.is_complete(),
+			last_matches,
+			items: &rows,
+		};
+		out.structured(&payload, &rows)?;
+		return out.finish();
+	}
+
+	writeln!(out, "path: {}", path.display())?;
+	writeln!(out, "root: {root_label}")?;
+	writeln!(out, "list: {path_expr}")?;
+	writeln!(out, "first: {}", ptr_hex(walk.first))?;
+	writeln!(out, "last: {}", ptr_hex(walk.last))?;
+	writeln!(out, "items: {}{}", rows.len(), if walk.is_complete() { "" } else { " (truncated)" })?;
+	if walk.is_complete() && !last_matches {
+		writeln!(out, "warning: ListBase.last does not point at the final item")?;
+	}
+	writeln!(out, "idx\tcanonical\tcode\ttype\tname")?;
+	for row in &rows {
+		writeln!(
+			out,
+			"{}\t{}\t{}\t{}\t{}",
+			row.index,
+			row.canonical,
+			row.code,
+			row.type_name,
+			row.id.as_deref().or(row.name.as_deref()).unwrap_or("-")
+		)?;
+	}
+	out.finish()
+}
+
+/// Plugin summary or `name` string of a list element, when available.
+fn item_name(dna: &Dna, index: &PointerIndex<'_>, ptr: u64, decode: &DecodeOptions) -> Option<String> {
+	let (_, item) = decode_ptr_instance(dna, index, ptr, decode).ok()?;
+	if let Some(summary) = decode.plugins.as_ref().and_then(|plugins| plugins.summary(&item)) {
+		return Some(summary);
+	}
+	item.fields
+		.iter()
+		.find(|field| field.name.as_ref() == "name")
+		.and_then(|field| match &field.value {
+			Value::String(name) => Some(name.to_string()),
+			_ => None,
+		})
+}
+
+#[derive(serde::Serialize)]
+struct ListJson<'a> {
+	path: String,
+	root: String,
+	list: String,
+	first: String,
+	last: String,
+	complete: bool,
+	last_matches: bool,
+	items: &'a [ListItemRow],
+}
+
+/// One list element; also the `--format csv` row.
+#[derive(serde::Serialize)]
+struct ListItemRow {
+	index: usize,
+	canonical: String,
+	code: String,
+	#[serde(rename = "type")]
+	type_name: String,
+	id: Option<String>,
+	name: Option<String>,
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,52 @@
+use blendoc_testkit::builder::{BlendBuilder, HeaderKind};
+
+use crate::cmd::test_support::{TempBlend, run_blendoc, run_blendoc_json};
+
+/// `SCScene` with two view layers in its `view_layers` list.
+fn scene_with_layers() -> Vec<u8> {
+	let mut builder = BlendBuilder::new(HeaderKind::Legacy8, 404, false);
+	let sdna = builder.sdna_mut();
+	sdna.add_struct("Link", &[("Link", "*next"), ("Link", "*prev")]);
+	sdna.add_type("char", 1);
+	sdna.add_type("void", 0);
+	sdna.add_struct("ID", &[("void", "*next"), ("void", "*prev"), ("void", "*lib"), ("char", "name[24]")]);
+	sdna.add_struct("ListBase", &[("void", "*first"), ("void", "*last")]);
+	let scene = sdna.add_struct("Scene", &[("ID", "id"), ("ListBase", "view_layers")]);
+	let layer = sdna.add_struct("ViewLayer", &[("ViewLayer", "*next"), ("ViewLayer", "*prev"), ("char", "name[16]")]);
+
+	let mut payload = builder.payload();
+	payload.ptr(0).ptr(0).ptr(0).name("SCScene", 24).ptr(0x2000).ptr(0x2100);
+	let scene_payload = payload.finish();
+	let mut payload = builder.payload();
+	payload.ptr(0x2100).ptr(0).name("ViewLayer", 16);
+	let first_payload = payload.finish();
+	let mut payload = builder.payload();
+	payload.ptr(0).ptr(0x2000).name("Shadows", 16);
+	let second_payload = payload.finish();
+	builder
+		.block(*b"SC\0\0", scene, 0x1000, 1, scene_payload)
+		.block(*b"DATA", layer, 0x2000, 1, first_payload)
+		.block(*b"DATA", layer, 0x2100, 1, second_payload);
+	builder.build()
+}
+
+#[test]
+fn scene_view_layers_list_without_manual_first_chase() {
+	let target = TempBlend::new("list", &scene_with_layers());
+	let target_arg = target.arg();
+
+	let json = run_blendoc_json(&["list", target_arg, "--id", "SCScene", "--path", "view_layers", "--json"]);
+	let not_list = run_blendoc(&["list", target_arg, "--id", "SCScene", "--path", "id"]);
+
+	let names: Vec<&str> = json["items"]
+		.as_array()
+		.expect("items array")
+		.iter()
+		.map(|item| item["name"].as_str().expect("layer name"))
+		.collect();
+	assert_eq!(names, ["ViewLayer", "Shadows"]);
+	assert_eq!(json["complete"], true);
+	assert_eq!(json["last_matches"], true);
+	assert!(!not_list.status.success());
+	assert!(String::from_utf8_lossy(&not_list.stderr).contains("is not a ListBase"));
+}
@@ -0,0 +1,178 @@
+use std::io::Write;
+use std::path::PathBuf;
+
+use blendoc::blend::{BlendFile, IdIndex, MeshSummary, Result, codes, read_mesh_summary};
+
+use crate::cmd::output::OutputArgs;
+use crate::cmd::util::{IdOrPtrSelector, parse_id_or_ptr_selector, ptr_hex, scan_ids};
+
+#[derive(clap::Args)]
+pub struct Args {
+	pub file: PathBuf,
+	#[arg(long = "id")]
+	pub id_name: Option<String>,
+	#[arg(long, conflicts_with = "id_name")]
+	pub ptr: Option<String>,
+	#[arg(long)]
+	pub json: bool,
+	#[command(flatten)]
+	pub output: OutputArgs,
+}
+
+/// Summarize mesh element counts and custom-data layers; all meshes without a selector.
+pub fn run(args: Args) -> Result<()> {
+	let Args {
+		file: path,
+		id_name,
+		ptr,
+		json,
+		output,
+	} = args;
+
+	let blend = BlendFile::open(&path)?;
+	let dna = blend.dna()?;
+	let index = blend.pointer_index()?;
+	let ids = IdIndex::build(scan_ids(&blend, &dna)?);
+
+	let targets: Vec<u64> = if id_name.is_none() && ptr.is_none() {
+		ids.records
+			.iter()
+			.filter(|record| record.code == codes::ID_ME)
+			.map(|record| record.old_ptr)
+			.collect()
+	} else {
+		match parse_id_or_ptr_selector(id_name, ptr)? {
+			IdOrPtrSelector::Id(name) => vec![ids.resolve_selector(&name)?.old_ptr],
+			IdOrPtrSelector::Ptr(ptr) => vec![ptr],
+		}
+	};
+	let meshes = targets
+		.into_iter()
+		.map(|ptr| read_mesh_summary(&dna, &index, &ids, ptr))
+		.collect::<Result<Vec<_>>>()?;
+
+	let mut out = output.open(json)?;
+	if !out.is_text() {
+		let payload = MeshesJson {
+			path: path.display().to_string(),
+			meshes: meshes.iter().map(mesh_json).collect(),
+		};
+		out.structured(&payload, &layer_rows(&meshes))?;
+		return out.finish();
+	}
+
+	writeln!(out, "path: {}", path.display())?;
+	writeln!(out, "meshes: {}", meshes.len())?;
+	for mesh in &meshes {
+		writeln!(out)?;
+		writeln!(out, "mesh: {} ({})", mesh.id_name.as_deref().unwrap_or("-"), ptr_hex(mesh.ptr))?;
+		for (label, value) in [
+			("verts", mesh.verts),
+			("edges", mesh.edges),
+			("corners", mesh.corners),
+			("faces", mesh.faces),
+			("tess_faces", mesh.tess_faces),
+			("materials", mesh.materials),
+		] {
+			writeln!(out, "{label}: {}", value.map_or_else(|| "-".to_owned(), |value| value.to_string()))?;
+		}
+		writeln!(out, "layers: {} ({} bytes)", mesh.layers.len(), mesh.layer_bytes())?;
+		for layer in &mesh.layers {
+			writeln!(
+				out,
+				"  {}\t{}\t{}\t{}",
+				layer.domain.as_str(),
+				layer.type_label(),
+				if layer.name.is_empty() { "-" } else { &layer.name },
+				layer.data_bytes.map_or_else(|| "-".to_owned(), |bytes| bytes.to_string())
+			)?;
+		}
+	}
+	out.finish()
+}
+
+fn mesh_json(mesh: &MeshSummary) -> MeshJson {
+	MeshJson {
+		ptr: ptr_hex(mesh.ptr),
+		id: mesh.id_name.as_deref().map(str::to_owned),
+		verts: mesh.verts,
+		edges: mesh.edges,
+		corners: mesh.corners,
+		faces: mesh.faces,
+		tess_faces: mesh.tess_faces,
+		materials: mesh.materials,
+		layer_bytes: mesh.layer_bytes(),
+		layers: mesh
+			.layers
+			.iter()
+			.map(|layer| LayerJson {
+				domain: layer.domain.as_str(),
+				layer_type: layer.type_label(),
+				type_code: layer.layer_type,
+				name: layer.name.to_string(),
+				data: ptr_hex(layer.data_ptr),
+				data_bytes: layer.data_bytes,
+			})
+			.collect(),
+	}
+}
+
+fn layer_rows(meshes: &[MeshSummary]) -> Vec<LayerRowCsv> {
+	meshes
+		.iter()
+		.flat_map(|mesh| {
+			mesh.layers.iter().map(|layer| LayerRowCsv {
+				mesh: mesh.id_name.as_deref().unwrap_or_default().to_owned(),
+				domain: layer.domain.as_str(),
+				layer_type: layer.type_label(),
+				name: layer.name.to_string(),
+				data_bytes: layer.data_bytes,
+			})
+		})
+		.collect()
+}
+
+#[derive(serde::Serialize)]
+struct MeshesJson {
+	path: String,
+	meshes: Vec<MeshJson>,
+}
+
+#[derive(serde::Serialize)]
+struct MeshJson {
+	ptr: String,
+	id: Option<String>,
+	verts: Option<i64>,
+	edges: Option<i64>,
+	corners: Option<i64>,
+	faces: Option<i64>,
+	tess_faces: Option<i64>,
+	materials: Option<i64>,
+	layer_bytes: u64,
+	layers: Vec<LayerJson>,
+}
+
+#[derive(serde::Serialize)]
+struct LayerJson {
+	domain: &'static str,
+	#[serde(rename = "type")]
+	layer_type: String,
+	type_code: i64,
+	name: String,
+	data: String,
+	data_bytes: Option<u64>,
+}
+
+/// Flat `--format csv` row: one custom-data layer per line.
+#[derive(serde::Serialize)]
+struct LayerRowCsv {
+	mesh: String,
+	domain: &'static str,
+	#[serde(rename = "type")]
+	layer_type: String,
+	name: String,
+	data_bytes: Option<u64>,
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,30 @@
+use blendoc_testkit::builder::{SCENE_ID_NAMES, SCENE_VERT_COUNT};
+
+use crate::cmd::test_support::{TempBlend, fixture_path, run_blendoc, run_blendoc_json};
+
+#[test]
+fn character_meshes_report_vertex_counts_and_layers() {
+	let fixture = fixture_path("character.blend");
+	let fixture = fixture.to_string_lossy().into_owned();
+	let json = run_blendoc_json(&["mesh", &fixture, "--json"]);
+
+	let meshes = json["meshes"].as_array().expect("meshes array");
+	assert!(!meshes.is_empty());
+	assert!(meshes.iter().all(|mesh| mesh["verts"].as_i64().is_some_and(|count| count >= 0)));
+	assert!(meshes.iter().any(|mesh| mesh["layers"].as_array().is_some_and(|layers| !layers.is_empty())));
+}
+
+#[test]
+fn legacy_scene_mesh_reports_totvert_and_rejects_non_mesh() {
+	let target = TempBlend::scene("mesh");
+	let target_arg = target.arg();
+
+	let mesh = run_blendoc(&["mesh", target_arg, "--id", SCENE_ID_NAMES[2], "--json"]);
+	let object = run_blendoc(&["mesh", target_arg, "--id", SCENE_ID_NAMES[1]]);
+
+	assert!(mesh.status.success(), "mesh failed: {}", String::from_utf8_lossy(&mesh.stderr));
+	let json: serde_json::Value = serde_json::from_slice(&mesh.stdout).expect("valid json");
+	assert_eq!(json["meshes"][0]["verts"], SCENE_VERT_COUNT as i64);
+	assert!(!object.status.success());
+	assert!(String::from_utf8_lossy(&object.stderr).contains("expected Mesh"));
+}
@@ -1,36 +1,141 @@
+/// Action F-Curve and driver inspection command.
+pub mod anim;
+/// Raw block table listing command.
+pub mod blocks;
 /// Camera pointer chase command.
 pub mod camera;
 /// Generic pointer/path chase command.
 pub mod chase;
+/// Known block code table command.
+pub mod codes;
+/// Block-by-block verified file copy command.
+pub mod copy;
+/// ID reference cycle listing command.
+pub mod cycles;
+/// Whole-file dangling pointer report command.
+pub mod dangling;
 /// Generic block decode command.
 pub mod decode;
+/// Transitive ID dependency/dependent closure command.
+pub mod deps;
+/// ID-level file comparison command.
+pub mod diff;
 /// SDNA inspection command.
 pub mod dna;
+/// SDNA schema comparison command.
+pub mod dna_diff;
+/// Build feature, environment, and self-check report command.
+pub mod doctor;
+/// Decoded ID snapshot export command.
+pub mod export;
+/// SQLite database export command.
+#[cfg(feature = "sqlite")]
+pub mod export_sqlite;
+/// Name and path lookup command backed by the in-memory name index.
+pub mod find;
 /// Graph extraction command.
 pub mod graph;
+/// Exit-status existence check command.
+pub mod has;
+/// Raw block payload dump with SDNA field overlay.
+pub mod hexdump;
+/// Scene collection/object hierarchy command.
+pub mod hierarchy;
+/// Single-ID detail view command.
+pub mod id;
 /// Whole-file ID graph command.
 pub mod idgraph;
 /// ID-root block listing command.
 pub mod ids;
+/// Image datablock path, packing, and colorspace report command.
+pub mod images;
+/// Raw data block type inference command.
+pub mod infer;
 /// File-level information command.
 pub mod info;
 /// Linked-library provenance command.
 pub mod libs;
+/// Cross-file library dependency tree command.
+pub mod libtree;
+/// `ListBase` item listing command.
+pub mod list;
+/// Mesh count and custom-data layer summary command.
+pub mod mesh;
+/// Material/world node-tree inspection command.
+pub mod nodetree;
+/// Block-header-only fast open probe command.
+pub mod open_check;
+/// Unreferenced (orphan) ID listing command.
+pub mod orphans;
+/// Packed file listing and extraction command.
+pub mod packed;
+/// Per-ID preview image extraction command.
+pub mod previews;
 /// Shared decoded-value printer and pointer annotation helpers.
 pub mod print;
+/// Project-wide external file and library reference audit command.
+pub mod project_audit;
+/// ID custom property (`IDProperty`) listing command.
+pub mod props;
+/// Planned ID removal command.
+pub mod prune;
+/// Compression rewrite command.
+pub mod recompress;
 /// Pointer reference scanning command.
 pub mod refs;
+/// ID rename impact analysis command.
+pub mod rename;
+/// Interactive inspection session over one loaded file.
+pub mod repl;
+/// Recorded session replay command and `--record` logging.
+pub mod replay;
+/// Multi-file summary report command.
+pub mod report;
 /// Shortest route query command.
 pub mod route;
 /// Scene convenience decode command.
 pub mod scene;
+/// JSON schema versioning for structured output and the `schema` command.
+pub mod schema;
+/// Whole-file decoded value search command.
+pub mod search;
+/// Synthetic-file conformance self-test command.
+pub mod selftest;
+/// JSON-RPC server answering queries against cached parsed files.
+pub mod serve;
 /// Decode/show command by pointer or ID.
 pub mod show;
+/// Per-type payload size accounting command.
+pub mod size_stats;
+/// Embedded file thumbnail extraction command.
+pub mod thumb;
+/// Canonical text form and text-form diff command.
+pub mod to_text;
+/// Structural integrity check command.
+pub mod validate;
 /// Linked-list walk command.
 pub mod walk;
+/// File-change watch command re-running a subcommand.
+pub mod watch;
+/// Ranked "why is this file big" size findings command.
+pub mod why_big;
 /// Inbound reference query command.
 pub mod xref;
 
+pub(crate) mod exit_code;
+pub(crate) mod graph_cache;
+pub(crate) mod input;
+pub(crate) mod interrupt;
+pub(crate) mod invocation;
+pub(crate) mod output;
+pub(crate) mod plugins;
+pub(crate) mod progress;
+pub(crate) mod quiet;
+pub(crate) mod result_cache;
+pub(crate) mod stats;
 #[cfg(test)]
 pub(crate) mod test_support;
+#[cfg(feature = "trace")]
+pub(crate) mod trace_timing;
+pub(crate) mod truncation;
 pub(crate) mod util;
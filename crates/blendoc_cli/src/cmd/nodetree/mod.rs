@@ -0,0 +1,218 @@
+use std::path::PathBuf;
+
+use blendoc::blend::{BlendFile, IdIndex, NodeSocket, NodeTree, NodeTreeOptions, read_node_tree};
+
+use crate::cmd::util::{IdOrPtrSelector, dot_escape, emit_json, parse_id_or_ptr_selector, ptr_hex, scan_ids};
+
+#[derive(clap::Args)]
+pub struct Args {
+	pub file: PathBuf,
+	#[arg(long = "id")]
+	pub id_name: Option<String>,
+	#[arg(long)]
+	pub ptr: Option<String>,
+	#[arg(long = "max-nodes")]
+	pub max_nodes: Option<usize>,
+	#[arg(long, conflicts_with = "json")]
+	pub dot: bool,
+	#[arg(long)]
+	pub json: bool,
+}
+
+/// Print the nodes, sockets, and links of a material/world/node-group tree.
+pub fn run(args: Args) -> blendoc::blend::Result<()> {
+	let Args {
+		file: path,
+		id_name,
+		ptr,
+		max_nodes,
+		dot,
+		json,
+	} = args;
+
+	let selector = parse_id_or_ptr_selector(id_name, ptr)?;
+
+	let blend = BlendFile::open(&path)?;
+	let dna = blend.dna()?;
+	let index = blend.pointer_index()?;
+	let ids = IdIndex::build(scan_ids(&blend, &dna)?);
+
+	let (owner_ptr, owner_label) = match selector {
+		IdOrPtrSelector::Id(name) => {
+			let row = ids.resolve_selector(&name)?;
+			(row.old_ptr, format!("id:{}", row.id_name))
+		}
+		IdOrPtrSelector::Ptr(ptr) => (ptr, format!("ptr:0x{ptr:016x}")),
+	};
+
+	let mut options = NodeTreeOptions::default();
+	if let Some(max_nodes) = max_nodes {
+		options.max_nodes = max_nodes;
+	}
+	let tree = read_node_tree(&dna, &index, &ids, owner_ptr, &options)?;
+
+	if json {
+		emit_json(&json_payload(&path, &owner_label, &tree));
+		return Ok(());
+	}
+	if dot {
+		print_dot(&tree);
+		return Ok(());
+	}
+
+	println!("path: {}", path.display());
+	println!("owner: {owner_label}");
+	println!("tree: {}", ptr_hex(tree.ptr));
+	println!("tree_type: {}", tree.idname.as_deref().unwrap_or("-"));
+	if let Some(id_name) = &tree.id_name {
+		println!("tree_id: {id_name}");
+	}
+	println!("truncated: {}", tree.truncated);
+
+	println!("nodes: {}", tree.nodes.len());
+	for node in &tree.nodes {
+		println!("  {} [{}] {}", node.name, node.type_label(), ptr_hex(node.ptr));
+		for socket in &node.inputs {
+			println!("    in  {}{}", socket.name, socket_type_suffix(socket));
+		}
+		for socket in &node.outputs {
+			println!("    out {}{}", socket.name, socket_type_suffix(socket));
+		}
+	}
+
+	println!("links: {}", tree.links.len());
+	for link in &tree.links {
+		println!(
+			"  {}.{} -> {}.{}",
+			node_label(&tree, link.from_node),
+			socket_label(&tree, link.from_socket),
+			node_label(&tree, link.to_node),
+			socket_label(&tree, link.to_socket)
+		);
+	}
+
+	Ok(())
+}
+
+fn socket_type_suffix(socket: &NodeSocket) -> String {
+	socket.idname.as_deref().map(|idname| format!(" ({idname})")).unwrap_or_default()
+}
+
+fn node_label(tree: &NodeTree, ptr: u64) -> String {
+	tree.node(ptr).map_or_else(|| ptr_hex(ptr), |node| node.name.to_string())
+}
+
+fn socket_label(tree: &NodeTree, ptr: u64) -> String {
+	tree.socket(ptr).map_or_else(|| ptr_hex(ptr), |socket| socket.name.to_string())
+}
+
+fn print_dot(tree: &NodeTree) {
+	println!("digraph blendoc_nodetree {{");
+	println!("  rankdir=LR");
+	for node in &tree.nodes {
+		println!(
+			"  \"0x{:016x}\" [shape=box,label=\"{}\\n{}\"]",
+			node.ptr,
+			dot_escape(&node.name),
+			dot_escape(&node.type_label())
+		);
+	}
+	for link in &tree.links {
+		let label = format!("{} -> {}", socket_label(tree, link.from_socket), socket_label(tree, link.to_socket));
+		println!(
+			"  \"0x{:016x}\" -> \"0x{:016x}\" [label=\"{}\"]",
+			link.from_node,
+			link.to_node,
+			dot_escape(&label)
+		);
+	}
+	println!("}}");
+}
+
+fn json_payload(path: &std::path::Path, owner: &str, tree: &NodeTree) -> NodeTreeJson {
+	let socket_json = |socket: &NodeSocket| NodeSocketJson {
+		ptr: ptr_hex(socket.ptr),
+		name: socket.name.to_string(),
+		identifier: socket.identifier.as_deref().map(str::to_owned),
+		idname: socket.idname.as_deref().map(str::to_owned),
+	};
+	NodeTreeJson {
+		path: path.display().to_string(),
+		owner: owner.to_owned(),
+		tree: ptr_hex(tree.ptr),
+		tree_type: tree.idname.as_deref().map(str::to_owned),
+		tree_id: tree.id_name.as_deref().map(str::to_owned),
+		truncated: tree.truncated,
+		nodes: tree
+			.nodes
+			.iter()
+			.map(|node| NodeJson {
+				ptr: ptr_hex(node.ptr),
+				name: node.name.to_string(),
+				node_type: node.type_label(),
+				inputs: node.inputs.iter().map(socket_json).collect(),
+				outputs: node.outputs.iter().map(socket_json).collect(),
+			})
+			.collect(),
+		links: tree
+			.links
+			.iter()
+			.map(|link| NodeLinkJson {
+				ptr: ptr_hex(link.ptr),
+				from_node: ptr_hex(link.from_node),
+				from_node_name: tree.node(link.from_node).map(|node| node.name.to_string()),
+				from_socket: ptr_hex(link.from_socket),
+				from_socket_name: tree.socket(link.from_socket).map(|socket| socket.name.to_string()),
+				to_node: ptr_hex(link.to_node),
+				to_node_name: tree.node(link.to_node).map(|node| node.name.to_string()),
+				to_socket: ptr_hex(link.to_socket),
+				to_socket_name: tree.socket(link.to_socket).map(|socket| socket.name.to_string()),
+			})
+			.collect(),
+	}
+}
+
+#[derive(serde::Serialize)]
+struct NodeTreeJson {
+	path: String,
+	owner: String,
+	tree: String,
+	tree_type: Option<String>,
+	tree_id: Option<String>,
+	truncated: bool,
+	nodes: Vec<NodeJson>,
+	links: Vec<NodeLinkJson>,
+}
+
+#[derive(serde::Serialize)]
+struct NodeJson {
+	ptr: String,
+	name: String,
+	node_type: String,
+	inputs: Vec<NodeSocketJson>,
+	outputs: Vec<NodeSocketJson>,
+}
+
+#[derive(serde::Serialize)]
+struct NodeSocketJson {
+	ptr: String,
+	name: String,
+	identifier: Option<String>,
+	idname: Option<String>,
+}
+
+#[derive(serde::Serialize)]
+struct NodeLinkJson {
+	ptr: String,
+	from_node: String,
+	from_node_name: Option<String>,
+	from_socket: String,
+	from_socket_name: Option<String>,
+	to_node: String,
+	to_node_name: Option<String>,
+	to_socket: String,
+	to_socket_name: Option<String>,
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,34 @@
+use crate::cmd::test_support::{TempBlend, fixture_path, run_blendoc, run_blendoc_json};
+
+#[test]
+fn character_material_tree_links_reference_listed_nodes() {
+	let fixture = fixture_path("character.blend");
+	let fixture = fixture.to_string_lossy().into_owned();
+	let ids = run_blendoc_json(&["ids", &fixture, "--code", "MA", "--json"]);
+	let material = ids["items"]
+		.as_array()
+		.and_then(|rows| rows.first())
+		.and_then(|row| row["id_name"].as_str())
+		.expect("fixture has a material")
+		.to_owned();
+
+	let json = run_blendoc_json(&["nodetree", &fixture, "--id", &material, "--json"]);
+	let nodes = json["nodes"].as_array().expect("nodes array");
+	assert!(!nodes.is_empty());
+	let node_ptrs: Vec<&str> = nodes.iter().filter_map(|node| node["ptr"].as_str()).collect();
+	for link in json["links"].as_array().expect("links array") {
+		assert!(node_ptrs.contains(&link["from_node"].as_str().unwrap_or_default()));
+		assert!(node_ptrs.contains(&link["to_node"].as_str().unwrap_or_default()));
+	}
+}
+
+#[test]
+fn id_without_node_tree_reports_error() {
+	let target = TempBlend::scene("nodetree");
+	let target_arg = target.arg();
+
+	let output = run_blendoc(&["nodetree", target_arg, "--id", "WOWorld"]);
+
+	assert!(!output.status.success());
+	assert!(String::from_utf8_lossy(&output.stderr).contains("no node tree on 0x"));
+}
@@ -0,0 +1,98 @@
+use std::path::PathBuf;
+
+use blendoc::blend::{OpenProbe, Result, probe_file};
+
+use crate::cmd::util::emit_json;
+
+#[derive(clap::Args)]
+pub struct Args {
+	pub file: PathBuf,
+	#[arg(long)]
+	pub json: bool,
+}
+
+/// Probe block headers only; exit 0 when `DNA1` and `ENDB` are present, 1 when either is missing, 2 on error.
+pub fn run(args: Args) -> Result<()> {
+	match check(args) {
+		Ok(true) => Ok(()),
+		Ok(false) => std::process::exit(1),
+		Err(err) => {
+			eprintln!("error: {err}");
+			std::process::exit(2);
+		}
+	}
+}
+
+fn check(args: Args) -> Result<bool> {
+	let Args { file: path, json } = args;
+	let probe = probe_file(&path)?;
+
+	if json {
+		emit_json(&OpenCheckJson::new(&path, &probe));
+	} else {
+		let offset = |value: Option<u64>| value.map_or_else(|| "missing".to_owned(), |offset| format!("0x{offset:x}"));
+		println!("path: {}", path.display());
+		println!("version: {}", probe.header.version);
+		println!("pointer_size: {}", probe.header.pointer_size);
+		println!("endianness: {}", probe.header.endianness.as_str());
+		println!("bhead_layout: {}", probe.bhead_layout.as_str());
+		println!("compression: {}", probe.compression.as_str());
+		if probe.seek_frames > 0 {
+			println!("seek_frames: {} ({} decoded)", probe.seek_frames, probe.frames_decoded);
+		}
+		println!("file_size: {}", probe.file_size);
+		println!("decoded_size: {}", probe.decoded_size);
+		println!("blocks: {} ({} codes)", probe.block_count, probe.code_count);
+		println!("payload_bytes: {}", probe.payload_bytes);
+		println!("dna1: {}", offset(probe.dna_offset));
+		println!("endb: {}", offset(probe.endb_offset));
+	}
+
+	Ok(probe.is_complete())
+}
+
+#[derive(serde::Serialize)]
+struct OpenCheckJson {
+	path: String,
+	ok: bool,
+	version: u16,
+	pointer_size: usize,
+	endianness: &'static str,
+	bhead_layout: &'static str,
+	compression: &'static str,
+	file_size: u64,
+	decoded_size: u64,
+	block_count: u32,
+	code_count: usize,
+	payload_bytes: u64,
+	dna_offset: Option<u64>,
+	endb_offset: Option<u64>,
+	seek_frames: usize,
+	frames_decoded: usize,
+}
+
+impl OpenCheckJson {
+	fn new(path: &std::path::Path, probe: &OpenProbe) -> Self {
+		Self {
+			path: path.display().to_string(),
+			ok: probe.is_complete(),
+			version: probe.header.version,
+			pointer_size: probe.header.pointer_size,
+			endianness: probe.header.endianness.as_str(),
+			bhead_layout: probe.bhead_layout.as_str(),
+			compression: probe.compression.as_str(),
+			file_size: probe.file_size,
+			decoded_size: probe.decoded_size,
+			block_count: probe.block_count,
+			code_count: probe.code_count,
+			payload_bytes: probe.payload_bytes,
+			dna_offset: probe.dna_offset,
+			endb_offset: probe.endb_offset,
+			seek_frames: probe.seek_frames,
+			frames_decoded: probe.frames_decoded,
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,30 @@
+use blendoc_testkit::builder::{HeaderKind, scene_file};
+
+use crate::cmd::test_support::{TempBlend, run_blendoc, run_blendoc_json};
+
+#[test]
+fn open_check_exit_status_tracks_dna_and_endb() {
+	let bytes = scene_file(HeaderKind::Legacy8, false);
+	let complete = TempBlend::new("open_check", &bytes);
+	// Drop the trailing 24-byte `ENDB` header.
+	let open_ended = TempBlend::new("open_check_no_endb", &bytes[..bytes.len() - 24]);
+	let complete_arg = complete.arg();
+	let open_ended_arg = open_ended.arg();
+
+	let json = run_blendoc_json(&["open-check", complete_arg, "--json"]);
+	let text = run_blendoc(&["open-check", complete_arg]);
+	let missing = run_blendoc(&["open-check", open_ended_arg]);
+	let not_blend = run_blendoc(&["open-check", "Cargo.toml"]);
+
+	assert_eq!(json["ok"], true);
+	assert_eq!(json["compression"], "none");
+	assert_eq!(json["block_count"], 7);
+	assert_eq!(json["decoded_size"], bytes.len());
+	assert!(json["dna_offset"].is_u64() && json["endb_offset"].is_u64());
+
+	assert_eq!(text.status.code(), Some(0));
+	assert!(String::from_utf8_lossy(&text.stdout).contains("bhead_layout: bhead8"));
+	assert_eq!(missing.status.code(), Some(1));
+	assert!(String::from_utf8_lossy(&missing.stdout).contains("endb: missing"));
+	assert_eq!(not_blend.status.code(), Some(2));
+}
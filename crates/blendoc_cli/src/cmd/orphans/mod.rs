@@ -0,0 +1,115 @@
+use std::io::Write;
+use std::path::PathBuf;
+
+use blendoc::blend::{BlendFile, IdIndex, OrphanOptions, Result, find_orphan_ids_in_graph};
+
+use crate::cmd::graph_cache::GraphCacheArgs;
+use crate::cmd::output::OutputArgs;
+use crate::cmd::util::{parse_block_code, ptr_hex, render_code, scan_ids};
+
+#[derive(clap::Args)]
+pub struct Args {
+	pub file: PathBuf,
+	#[arg(long)]
+	pub code: Option<String>,
+	#[arg(long = "type")]
+	pub type_name: Option<String>,
+	#[arg(long = "no-fake-users")]
+	pub no_fake_users: bool,
+	#[arg(long)]
+	pub json: bool,
+	#[command(flatten)]
+	pub output: OutputArgs,
+	#[command(flatten)]
+	pub graph_cache: GraphCacheArgs,
+}
+
+/// List IDs that no other ID references, with their stored user counts.
+pub fn run(args: Args) -> Result<()> {
+	let Args {
+		file: path,
+		code,
+		type_name,
+		no_fake_users,
+		json,
+		output,
+		graph_cache,
+	} = args;
+
+	let blend = BlendFile::open(&path)?;
+	let dna = blend.dna()?;
+	let index = blend.pointer_index()?;
+	let ids = IdIndex::build(scan_ids(&blend, &dna)?);
+	let options = OrphanOptions {
+		code: code.as_deref().map(parse_block_code).transpose()?,
+		type_name,
+		include_fake_users: !no_fake_users,
+		..OrphanOptions::default()
+	};
+	let graph = graph_cache.build(&path, &blend, &dna, &index, &ids, &options.graph)?;
+	let report = find_orphan_ids_in_graph(&dna, &index, &ids, &graph, &options);
+
+	let rows: Vec<OrphanRow> = report
+		.orphans
+		.iter()
+		.map(|item| OrphanRow {
+			id_ptr: ptr_hex(item.id_ptr),
+			code: render_code(item.code),
+			type_name: item.type_name.to_string(),
+			id_name: item.id_name.to_string(),
+			users: item.users,
+			fake_user: item.fake_user,
+		})
+		.collect();
+
+	let mut out = output.open(json)?;
+	if !out.is_text() {
+		let payload = OrphansJson {
+			path: path.display().to_string(),
+			ids_checked: report.ids_checked,
+			truncated: report.truncated,
+			orphans: &rows,
+		};
+		out.structured(&payload, &rows)?;
+		return out.finish();
+	}
+
+	writeln!(out, "path: {}", path.display())?;
+	writeln!(out, "orphans: {} of {} IDs", rows.len(), report.ids_checked)?;
+	if report.truncated {
+		writeln!(out, "warning: ID graph edge budget reached; some orphans may be referenced")?;
+	}
+	writeln!(out, "id_ptr\tcode\ttype\tid_name\tusers\tfake_user")?;
+	for row in &rows {
+		let users = row.users.map(|users| users.to_string()).unwrap_or_else(|| "-".to_owned());
+		writeln!(
+			out,
+			"{}\t{}\t{}\t{}\t{}\t{}",
+			row.id_ptr, row.code, row.type_name, row.id_name, users, row.fake_user
+		)?;
+	}
+	out.finish()
+}
+
+#[derive(serde::Serialize)]
+struct OrphansJson<'a> {
+	path: String,
+	ids_checked: usize,
+	truncated: bool,
+	orphans: &'a [OrphanRow],
+}
+
+/// One orphan ID; also the `--format csv` row.
+#[derive(serde::Serialize)]
+struct OrphanRow {
+	id_ptr: String,
+	code: String,
+	#[serde(rename = "type")]
+	type_name: String,
+	id_name: String,
+	users: Option<i64>,
+	fake_user: bool,
+}
+
+#[cfg(test)]
+mod tests;
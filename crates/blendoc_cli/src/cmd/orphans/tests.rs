@@ -0,0 +1,92 @@
+use blendoc_testkit::builder::{BlendBuilder, HeaderKind};
+
+use crate::cmd::test_support::{TempBlend, run_blendoc, run_blendoc_json};
+
+/// `SCScene` -> `MEUsed`, plus unused `MEOrphan` and fake-user `MEFake`.
+fn orphan_file() -> Vec<u8> {
+	let mut builder = BlendBuilder::new(HeaderKind::Legacy8, 404, false);
+	let sdna = builder.sdna_mut();
+	sdna.add_struct("Link", &[("Link", "*next"), ("Link", "*prev")]);
+	sdna.add_type("char", 1);
+	sdna.add_type("int", 4);
+	sdna.add_type("void", 0);
+	sdna.add_struct(
+		"ID",
+		&[
+			("void", "*next"),
+			("void", "*prev"),
+			("void", "*lib"),
+			("char", "name[24]"),
+			("int", "us"),
+			("int", "flag"),
+		],
+	);
+	let mesh = sdna.add_struct("Mesh", &[("ID", "id"), ("void", "*verts")]);
+	let scene = sdna.add_struct("Scene", &[("ID", "id"), ("Mesh", "*mesh")]);
+
+	let id = |builder: &BlendBuilder, name: &str, users: i32, flag: i32, ptr: u64| {
+		let mut payload = builder.payload();
+		payload.ptr(0).ptr(0).ptr(0).name(name, 24).i32(users).i32(flag).ptr(ptr);
+		payload.finish()
+	};
+	let scene_payload = id(&builder, "SCScene", 0, 0, 0x2000);
+	let used_payload = id(&builder, "MEUsed", 1, 0, 0);
+	let orphan_payload = id(&builder, "MEOrphan", 0, 0, 0);
+	let fake_payload = id(&builder, "MEFake", 1, 1 << 9, 0);
+	builder
+		.block(*b"SC\0\0", scene, 0x1000, 1, scene_payload)
+		.block(*b"ME\0\0", mesh, 0x2000, 1, used_payload)
+		.block(*b"ME\0\0", mesh, 0x3000, 1, orphan_payload)
+		.block(*b"ME\0\0", mesh, 0x4000, 1, fake_payload);
+	builder.build()
+}
+
+#[test]
+fn orphans_lists_unreferenced_ids_with_users() {
+	let target = TempBlend::new("orphans", &orphan_file());
+	let target_arg = target.arg();
+
+	let json = run_blendoc_json(&["orphans", target_arg, "--type", "Mesh", "--json"]);
+	let strict = run_blendoc_json(&["orphans", target_arg, "--no-fake-users", "--json"]);
+	let text = run_blendoc(&["orphans", target_arg]);
+
+	assert_eq!(json["ids_checked"], 3);
+	let orphans = json["orphans"].as_array().expect("orphans array");
+	let names: Vec<&str> = orphans.iter().filter_map(|item| item["id_name"].as_str()).collect();
+	assert_eq!(names, ["MEFake", "MEOrphan"]);
+	assert_eq!(orphans[0]["fake_user"], true);
+	assert_eq!(orphans[0]["users"], 1);
+	assert_eq!(orphans[1]["users"], 0);
+
+	let strict = strict["orphans"].as_array().expect("orphans array");
+	assert_eq!(strict.len(), 1);
+	assert_eq!(strict[0]["id_name"], "MEOrphan");
+
+	let text = String::from_utf8_lossy(&text.stdout);
+	assert!(text.contains("orphans: 2 of 4 IDs"), "{text}");
+	assert!(text.contains("0x0000000000003000\tME\tMesh\tMEOrphan\t0\tfalse"), "{text}");
+}
+
+#[test]
+fn graph_cache_reuses_the_saved_id_graph() {
+	let target = TempBlend::new("orphans_cache", &orphan_file());
+	let cache_dir = std::env::temp_dir().join(format!("blendoc_orphans_cache_dir_{}", std::process::id()));
+	let target_arg = target.arg();
+	let cache_dir_arg = cache_dir.to_string_lossy().into_owned();
+	let artifact = std::path::PathBuf::from(format!("{target_arg}.idgraph"));
+
+	let plain = run_blendoc_json(&["orphans", target_arg, "--json"]);
+	let first = run_blendoc_json(&["orphans", target_arg, "--json", "--graph-cache"]);
+	let saved = artifact.exists();
+	let second = run_blendoc_json(&["orphans", target_arg, "--json", "--graph-cache"]);
+	let in_dir = run_blendoc_json(&["orphans", target_arg, "--json", "--graph-cache-dir", &cache_dir_arg]);
+	let dir_entries = std::fs::read_dir(&cache_dir).map(Iterator::count).unwrap_or(0);
+	let _ = std::fs::remove_file(&artifact);
+	let _ = std::fs::remove_dir_all(&cache_dir);
+
+	assert!(saved, "artifact written beside the file");
+	assert_eq!(dir_entries, 1, "artifact written into --graph-cache-dir");
+	assert_eq!(first, plain);
+	assert_eq!(second, plain, "reloaded graph gives the same orphans");
+	assert_eq!(in_dir, plain);
+}
@@ -0,0 +1,97 @@
+use std::fs::File;
+use std::io::{self, BufWriter, Write};
+use std::path::PathBuf;
+
+use blendoc::blend::Result;
+
+use crate::cmd::{quiet, schema};
+
+/// Result encoding selected by `--format`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, clap::ValueEnum)]
+pub enum OutputFormat {
+	Text,
+	Json,
+	Yaml,
+	Csv,
+}
+
+/// Shared `--output`/`--format` flags for commands with structured results.
+#[derive(clap::Args, Debug, Clone, Default)]
+pub struct OutputArgs {
+	#[arg(long, short = 'o')]
+	pub output: Option<PathBuf>,
+	#[arg(long, value_enum, conflicts_with = "json")]
+	pub format: Option<OutputFormat>,
+}
+
+impl OutputArgs {
+	/// Open the destination; a bare `--json` flag selects JSON when `--format` is absent.
+	pub(crate) fn open(&self, json: bool) -> Result<Output> {
+		let format = self.format.unwrap_or(if json { OutputFormat::Json } else { OutputFormat::Text });
+		let writer: Box<dyn Write> = match &self.output {
+			Some(path) if path.as_os_str() != "-" => Box::new(BufWriter::new(File::create(path)?)),
+			_ => Box::new(BufWriter::new(quiet::stdout())),
+		};
+		Ok(Output { format, writer })
+	}
+}
+
+/// Command output sink: stdout or a file, plus the selected format.
+///
+/// Text renderers write through the `Write` impl; structured formats go
+/// through [`Output::structured`].
+pub(crate) struct Output {
+	format: OutputFormat,
+	writer: Box<dyn Write>,
+}
+
+impl Output {
+	/// Selected format.
+	pub(crate) fn format(&self) -> OutputFormat {
+		self.format
+	}
+
+	/// Whether the caller should render its plain-text view.
+	pub(crate) fn is_text(&self) -> bool {
+		self.format == OutputFormat::Text
+	}
+
+	/// Write `document` as JSON or YAML stamped with the selected schema version,
+	/// or `rows` as CSV with a header line.
+	pub(crate) fn structured<D: serde::Serialize, R: serde::Serialize>(&mut self, document: &D, rows: &[R]) -> Result<()> {
+		match self.format {
+			OutputFormat::Text | OutputFormat::Json => {
+				let rendered = schema::to_json(document).map_err(io::Error::other)?;
+				writeln!(self.writer, "{rendered}")?;
+			}
+			OutputFormat::Yaml => {
+				let rendered = schema::to_yaml(document).map_err(io::Error::other)?;
+				self.writer.write_all(rendered.as_bytes())?;
+			}
+			OutputFormat::Csv => {
+				let mut csv = csv::Writer::from_writer(&mut self.writer);
+				for row in rows {
+					csv.serialize(row).map_err(io::Error::other)?;
+				}
+				csv.flush()?;
+			}
+		}
+		Ok(())
+	}
+
+	/// Flush buffered output, surfacing write errors.
+	pub(crate) fn finish(mut self) -> Result<()> {
+		self.writer.flush()?;
+		Ok(())
+	}
+}
+
+impl Write for Output {
+	fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+		self.writer.write(buf)
+	}
+
+	fn flush(&mut self) -> io::Result<()> {
+		self.writer.flush()
+	}
+}
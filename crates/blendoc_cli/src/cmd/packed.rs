@@ -0,0 +1,169 @@
+use std::collections::HashSet;
+use std::fs;
+use std::io::{BufWriter, Write};
+use std::path::{Path, PathBuf};
+
+use blendoc::blend::{BlendFile, PackedFileRecord, PayloadChunks, Result, packed_file_bytes, scan_packed_files};
+
+use crate::cmd::util::{emit_json, ptr_hex, ptr_hex_opt};
+
+/// Bytes copied per write when extracting packed payloads.
+const EXTRACT_CHUNK_SIZE: usize = 1 << 20;
+
+#[derive(clap::Args)]
+pub struct Args {
+	pub file: PathBuf,
+	#[arg(long)]
+	pub extract: Option<PathBuf>,
+	#[arg(long = "id")]
+	pub id_name: Option<String>,
+	#[arg(long)]
+	pub json: bool,
+}
+
+/// List packed files and optionally extract their raw bytes to a directory.
+pub fn run(args: Args) -> Result<()> {
+	let Args {
+		file: path,
+		extract,
+		id_name,
+		json,
+	} = args;
+
+	let blend = BlendFile::open_mmap(&path)?;
+	let dna = blend.dna()?;
+	let index = blend.pointer_index()?;
+	let mut records = scan_packed_files(&blend, &dna)?;
+	if let Some(filter) = id_name {
+		records.retain(|item| item.owner_id_name.as_deref() == Some(filter.as_str()));
+	}
+
+	let mut rows = Vec::new();
+	let mut used_names = HashSet::new();
+	if let Some(dir) = &extract {
+		fs::create_dir_all(dir)?;
+	}
+	for record in &records {
+		let bytes = packed_file_bytes(&index, record);
+		let written = match (&extract, bytes) {
+			(Some(dir), Some(bytes)) => {
+				let target = dir.join(unique_name(&mut used_names, output_name(record)));
+				write_chunked(&target, bytes)?;
+				Some(target)
+			}
+			_ => None,
+		};
+		rows.push((record, bytes.is_some(), written));
+	}
+
+	if json {
+		let payload = PackedJson {
+			path: path.display().to_string(),
+			extract: extract.as_ref().map(|dir| dir.display().to_string()),
+			packed: rows
+				.iter()
+				.map(|(record, readable, written)| PackedFileJson {
+					packed_ptr: ptr_hex(record.packed_ptr),
+					data_ptr: ptr_hex(record.data_ptr),
+					size: record.size,
+					readable: *readable,
+					owner_ptr: ptr_hex_opt(record.owner_ptr),
+					owner_id_name: record.owner_id_name.as_deref().map(str::to_owned),
+					owner_type: record.owner_type.as_deref().map(str::to_owned),
+					filepath: record.filepath.as_deref().map(str::to_owned),
+					file: written.as_ref().map(|target| target.display().to_string()),
+				})
+				.collect(),
+		};
+		emit_json(&payload);
+		return Ok(());
+	}
+
+	println!("path: {}", path.display());
+	if let Some(dir) = &extract {
+		println!("extract: {}", dir.display());
+	}
+	println!("packed: {}", rows.len());
+	println!("owner\ttype\tsize\treadable\tfilepath\tfile");
+	for (record, readable, written) in &rows {
+		println!(
+			"{}\t{}\t{}\t{}\t{}\t{}",
+			record.owner_id_name.as_deref().unwrap_or("-"),
+			record.owner_type.as_deref().unwrap_or("-"),
+			record.size,
+			readable,
+			record.filepath.as_deref().unwrap_or("-"),
+			written.as_ref().map_or_else(|| "-".to_owned(), |target| target.display().to_string())
+		);
+	}
+
+	Ok(())
+}
+
+/// File name from the recorded path's last component, else the owner ID name.
+/// Copy a payload out in bounded chunks so mapped pages are touched incrementally.
+fn write_chunked(target: &Path, bytes: &[u8]) -> Result<()> {
+	let mut writer = BufWriter::new(fs::File::create(target)?);
+	for chunk in PayloadChunks::new(bytes, EXTRACT_CHUNK_SIZE) {
+		writer.write_all(chunk)?;
+	}
+	writer.flush()?;
+	Ok(())
+}
+
+fn output_name(record: &PackedFileRecord) -> String {
+	let from_path = record
+		.filepath
+		.as_deref()
+		.and_then(|path| path.rsplit(['/', '\\']).next())
+		.filter(|name| !name.is_empty());
+	let stem = match (from_path, record.owner_id_name.as_deref()) {
+		(Some(name), _) => name.to_owned(),
+		(None, Some(id_name)) => format!("{id_name}.bin"),
+		(None, None) => format!("packed_{:016x}.bin", record.packed_ptr),
+	};
+	stem.chars()
+		.map(|ch| {
+			if ch.is_ascii_alphanumeric() || matches!(ch, '-' | '_' | '.') {
+				ch
+			} else {
+				'_'
+			}
+		})
+		.collect()
+}
+
+/// Prefix a counter when several packed files share a name.
+fn unique_name(used: &mut HashSet<String>, name: String) -> String {
+	if used.insert(name.clone()) {
+		return name;
+	}
+	let mut counter = 1_usize;
+	loop {
+		let candidate = format!("{counter}_{name}");
+		if used.insert(candidate.clone()) {
+			return candidate;
+		}
+		counter += 1;
+	}
+}
+
+#[derive(serde::Serialize)]
+struct PackedFileJson {
+	packed_ptr: String,
+	data_ptr: String,
+	size: usize,
+	readable: bool,
+	owner_ptr: Option<String>,
+	owner_id_name: Option<String>,
+	owner_type: Option<String>,
+	filepath: Option<String>,
+	file: Option<String>,
+}
+
+#[derive(serde::Serialize)]
+struct PackedJson {
+	path: String,
+	extract: Option<String>,
+	packed: Vec<PackedFileJson>,
+}
@@ -0,0 +1,24 @@
+use std::path::PathBuf;
+
+use blendoc::blend::{DecodePlugins, Result};
+
+/// Shared `--plugins` flag loading TOML struct descriptors.
+#[derive(clap::Args, Debug, Clone, Default)]
+pub struct PluginArgs {
+	#[arg(long = "plugins", value_name = "TOML")]
+	pub plugins: Vec<PathBuf>,
+}
+
+impl PluginArgs {
+	/// Registry built from every descriptor file, or `None` when none were given.
+	pub(crate) fn load(&self) -> Result<Option<DecodePlugins>> {
+		if self.plugins.is_empty() {
+			return Ok(None);
+		}
+		let mut plugins = DecodePlugins::new();
+		for path in &self.plugins {
+			plugins.load_descriptors(&std::fs::read_to_string(path)?)?;
+		}
+		Ok(Some(plugins))
+	}
+}
@@ -0,0 +1,164 @@
+use std::collections::HashSet;
+use std::fs;
+use std::path::PathBuf;
+
+use blendoc::blend::{BlendError, BlendFile, IdPreview, PreviewRect, Result, scan_id_previews};
+
+use crate::cmd::util::{emit_json, ptr_hex, render_code, write_png_rgba};
+
+#[derive(clap::Args)]
+pub struct Args {
+	pub file: PathBuf,
+	#[arg(long = "out-dir")]
+	pub out_dir: Option<PathBuf>,
+	#[arg(long = "id")]
+	pub id_name: Option<String>,
+	#[arg(long = "type")]
+	pub type_name: Option<String>,
+	#[arg(long)]
+	pub json: bool,
+}
+
+/// List per-ID stored previews and optionally write them as PNG files.
+pub fn run(args: Args) -> Result<()> {
+	let Args {
+		file: path,
+		out_dir,
+		id_name,
+		type_name,
+		json,
+	} = args;
+
+	let blend = BlendFile::open(&path)?;
+	let dna = blend.dna()?;
+	let mut previews = scan_id_previews(&blend, &dna)?;
+	if let Some(filter) = id_name {
+		previews.retain(|item| item.id_name.as_ref() == filter.as_str());
+	}
+	if let Some(filter) = type_name {
+		previews.retain(|item| item.type_name.as_ref() == filter.as_str());
+	}
+
+	let mut rows = Vec::new();
+	if let Some(dir) = &out_dir {
+		fs::create_dir_all(dir)?;
+	}
+	// Lowercased so names stay distinct on case-insensitive filesystems too.
+	let mut taken = HashSet::new();
+	for preview in &previews {
+		for rect in &preview.rects {
+			let written = match &out_dir {
+				Some(dir) => {
+					let mut name = png_file_name(preview, rect, false);
+					if taken.contains(&name.to_ascii_lowercase()) {
+						name = png_file_name(preview, rect, true);
+					}
+					if !taken.insert(name.to_ascii_lowercase()) {
+						return Err(BlendError::Io(std::io::Error::new(
+							std::io::ErrorKind::AlreadyExists,
+							format!("refusing to overwrite {name}, already written for another preview"),
+						)));
+					}
+					let target = dir.join(name);
+					write_png_rgba(&target, rect.width, rect.height, &rect.rgba)?;
+					Some(target)
+				}
+				None => None,
+			};
+			rows.push((preview, rect, written));
+		}
+	}
+
+	if json {
+		let payload = PreviewsJson {
+			path: path.display().to_string(),
+			out_dir: out_dir.as_ref().map(|dir| dir.display().to_string()),
+			previews: rows
+				.iter()
+				.map(|(preview, rect, written)| PreviewJson {
+					id_ptr: ptr_hex(preview.id_ptr),
+					code: render_code(preview.code),
+					type_name: preview.type_name.to_string(),
+					id_name: preview.id_name.to_string(),
+					preview_ptr: ptr_hex(preview.preview_ptr),
+					size: rect.size,
+					width: rect.width,
+					height: rect.height,
+					file: written.as_ref().map(|target| target.display().to_string()),
+				})
+				.collect(),
+		};
+		emit_json(&payload);
+		return Ok(());
+	}
+
+	println!("path: {}", path.display());
+	if let Some(dir) = &out_dir {
+		println!("out_dir: {}", dir.display());
+	}
+	println!("previews: {}", rows.len());
+	println!("id_ptr\tcode\ttype\tid_name\tsize\twidth\theight\tfile");
+	for (preview, rect, written) in &rows {
+		println!(
+			"{}\t{}\t{}\t{}\t{}\t{}\t{}\t{}",
+			ptr_hex(preview.id_ptr),
+			render_code(preview.code),
+			preview.type_name,
+			preview.id_name,
+			rect.size,
+			rect.width,
+			rect.height,
+			written.as_ref().map_or_else(|| "-".to_owned(), |target| target.display().to_string())
+		);
+	}
+
+	Ok(())
+}
+
+/// Build a filesystem-safe file name from the ID name and size slot.
+///
+/// IDs whose sanitized names collide are told apart by `with_ptr`, which
+/// adds the ID block pointer.
+fn png_file_name(preview: &IdPreview, rect: &PreviewRect, with_ptr: bool) -> String {
+	let stem: String = preview
+		.id_name
+		.chars()
+		.map(|ch| {
+			if ch.is_ascii_alphanumeric() || matches!(ch, '-' | '_' | '.') {
+				ch
+			} else {
+				'_'
+			}
+		})
+		.collect();
+	let stem = if with_ptr { format!("{stem}.{:x}", preview.id_ptr) } else { stem };
+	if rect.size == "preview" {
+		format!("{stem}.png")
+	} else {
+		format!("{stem}.{}.png", rect.size)
+	}
+}
+
+#[derive(serde::Serialize)]
+struct PreviewJson {
+	id_ptr: String,
+	code: String,
+	#[serde(rename = "type")]
+	type_name: String,
+	id_name: String,
+	preview_ptr: String,
+	size: &'static str,
+	width: u32,
+	height: u32,
+	file: Option<String>,
+}
+
+#[derive(serde::Serialize)]
+struct PreviewsJson {
+	path: String,
+	out_dir: Option<String>,
+	previews: Vec<PreviewJson>,
+}
+
+#[cfg(test)]
+mod tests;
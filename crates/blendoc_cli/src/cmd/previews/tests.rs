@@ -0,0 +1,56 @@
+use blendoc_testkit::builder::{BlendBuilder, HeaderKind};
+
+use crate::cmd::test_support::{TempBlend, run_blendoc_json};
+
+/// Two objects, `OBa b` and `OBa_b`, each with a 1x1 preview; their names
+/// sanitize to the same file stem.
+fn colliding_previews() -> Vec<u8> {
+	let mut builder = BlendBuilder::new(HeaderKind::Legacy8, 404, false);
+	let sdna = builder.sdna_mut();
+	sdna.add_type("char", 1);
+	sdna.add_type("int", 4);
+	sdna.add_type("void", 0);
+	sdna.add_struct("ID", &[("void", "*next"), ("void", "*prev"), ("void", "*lib"), ("char", "name[24]")]);
+	let preview = sdna.add_struct("PreviewImage", &[("int", "w[2]"), ("int", "h[2]"), ("int", "*rect[2]")]);
+	let object = sdna.add_struct("Object", &[("ID", "id"), ("PreviewImage", "*preview")]);
+
+	for (name, base) in [("OBa b", 0x1000), ("OBa_b", 0x2000)] {
+		let mut payload = builder.payload();
+		payload.ptr(0).ptr(0).ptr(0).name(name, 24).ptr(base + 0x100);
+		let id = payload.finish();
+		let mut payload = builder.payload();
+		payload.i32(0).i32(1).i32(0).i32(1).ptr(0).ptr(base + 0x200);
+		let image = payload.finish();
+		builder
+			.block(*b"OB\0\0", object, base, 1, id)
+			.block(*b"DATA", preview, base + 0x100, 1, image)
+			.block(*b"DATA", 0, base + 0x200, 1, vec![255, 0, 0, 255]);
+	}
+	builder.build()
+}
+
+#[test]
+fn colliding_id_names_get_distinct_png_files() {
+	let target = TempBlend::new("previews", &colliding_previews());
+	let dir = std::env::temp_dir().join(format!("blendoc_previews_{}", std::process::id()));
+	let target_arg = target.arg();
+	let dir_arg = dir.to_string_lossy().into_owned();
+
+	let json = run_blendoc_json(&["previews", target_arg, "--out-dir", &dir_arg, "--json"]);
+	let mut written: Vec<String> = std::fs::read_dir(&dir)
+		.expect("out dir listed")
+		.map(|entry| entry.expect("dir entry").file_name().to_string_lossy().into_owned())
+		.collect();
+	written.sort();
+	std::fs::remove_dir_all(&dir).expect("out dir removed");
+
+	assert_eq!(written, ["OBa_b.2000.png", "OBa_b.png"]);
+	let files: Vec<&str> = json["previews"]
+		.as_array()
+		.expect("previews")
+		.iter()
+		.filter_map(|row| row["file"].as_str())
+		.collect();
+	assert_eq!(files.len(), 2);
+	assert!(files[0].ends_with("OBa_b.png") && files[1].ends_with("OBa_b.2000.png"), "{files:?}");
+}
@@ -0,0 +1,126 @@
+use blendoc::blend::Value;
+
+/// Field names treated as 3-/2-/4-component vectors when their element count fits.
+const VECTOR_NAMES: &[&str] = &[
+	"loc", "dloc", "size", "dscale", "scale", "co", "no", "ofs", "location", "axis", "center", "cent", "min", "max", "vec", "velocity",
+];
+
+/// Recognized math shape for a flat float array field.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(super) enum MathShape {
+	/// Square matrix with the given row count, stored as `float[n][n]`.
+	Mat(usize),
+	/// Plain 2-, 3-, or 4-component vector.
+	Vec,
+	/// Euler rotation in radians.
+	Euler,
+	/// Quaternion stored as `w, x, y, z`.
+	Quat,
+}
+
+/// `--pretty-math` rendering: a head written after `name = ` plus optional indented rows.
+#[derive(Debug, Clone, PartialEq)]
+pub(super) struct MathRender {
+	pub head: String,
+	pub rows: Vec<String>,
+}
+
+/// Classify a field by its name and element count.
+pub(super) fn math_shape(name: &str, len: usize) -> Option<MathShape> {
+	let lower = name.to_ascii_lowercase();
+	match len {
+		16 if is_matrix_name(&lower) => Some(MathShape::Mat(4)),
+		9 if is_matrix_name(&lower) => Some(MathShape::Mat(3)),
+		4 if lower.contains("quat") => Some(MathShape::Quat),
+		3 if lower.ends_with("rot") || lower.contains("euler") => Some(MathShape::Euler),
+		2..=4 if VECTOR_NAMES.contains(&lower.as_str()) => Some(MathShape::Vec),
+		_ => None,
+	}
+}
+
+/// Render a float array field as a matrix/vector, or `None` when it does not look like one.
+pub(super) fn render_math(name: &str, value: &Value) -> Option<MathRender> {
+	let Value::Array(items) = value else {
+		return None;
+	};
+	let shape = math_shape(name, items.len())?;
+	let floats = items
+		.iter()
+		.map(|item| match item {
+			Value::F32(v) => Some(f64::from(*v)),
+			Value::F64(v) => Some(*v),
+			_ => None,
+		})
+		.collect::<Option<Vec<_>>>()?;
+
+	let render = match shape {
+		MathShape::Mat(n) => {
+			let cells: Vec<String> = floats.iter().map(|v| format_float(*v)).collect();
+			let width = cells.iter().map(String::len).max().unwrap_or(0);
+			let rows = cells
+				.chunks(n)
+				.map(|row| {
+					let row: Vec<String> = row.iter().map(|cell| format!("{cell:>width$}")).collect();
+					format!("[ {} ]", row.join(", "))
+				})
+				.collect();
+			MathRender { head: format!("mat{n}"), rows }
+		}
+		MathShape::Vec => MathRender {
+			head: format!("vec{}({})", floats.len(), join_floats(&floats)),
+			rows: Vec::new(),
+		},
+		MathShape::Euler => MathRender {
+			head: format!("euler({}) deg({})", join_floats(&floats), join_degrees(&floats)),
+			rows: Vec::new(),
+		},
+		MathShape::Quat => MathRender {
+			head: format!(
+				"quat(w={}, x={}, y={}, z={}) euler_deg({})",
+				format_float(floats[0]),
+				format_float(floats[1]),
+				format_float(floats[2]),
+				format_float(floats[3]),
+				join_degrees(&quat_to_euler(floats[0], floats[1], floats[2], floats[3]))
+			),
+			rows: Vec::new(),
+		},
+	};
+	Some(render)
+}
+
+fn is_matrix_name(lower: &str) -> bool {
+	lower.contains("mat") || lower.ends_with("inv")
+}
+
+/// XYZ Euler angles (radians) for a unit quaternion, matching Blender's default rotation mode.
+fn quat_to_euler(w: f64, x: f64, y: f64, z: f64) -> [f64; 3] {
+	let roll = (2.0 * (w * x + y * z)).atan2(1.0 - 2.0 * (x * x + y * y));
+	let pitch = (2.0 * (w * y - z * x)).clamp(-1.0, 1.0).asin();
+	let yaw = (2.0 * (w * z + x * y)).atan2(1.0 - 2.0 * (y * y + z * z));
+	[roll, pitch, yaw]
+}
+
+fn format_float(value: f64) -> String {
+	// Avoid printing `-0.0000` for tiny negative noise.
+	let rounded = format!("{value:.4}");
+	if rounded.trim_start_matches('-').chars().all(|ch| ch == '0' || ch == '.') {
+		return rounded.trim_start_matches('-').to_owned();
+	}
+	rounded
+}
+
+fn join_floats(values: &[f64]) -> String {
+	values.iter().map(|v| format_float(*v)).collect::<Vec<_>>().join(", ")
+}
+
+fn join_degrees(values: &[f64]) -> String {
+	values
+		.iter()
+		.map(|v| {
+			let deg = format!("{:.2}", v.to_degrees());
+			if deg == "-0.00" { "0.00".to_owned() } else { deg }
+		})
+		.collect::<Vec<_>>()
+		.join(", ")
+}
@@ -1,21 +1,32 @@
 use std::cell::{Cell, RefCell};
-use std::collections::HashMap;
 
-use blendoc::blend::{DecodeOptions, Dna, IdIndex, PointerIndex, StructValue, Value, decode_ptr_instance};
+use blendoc::blend::{DecodeOptions, Dna, IdIndex, PointerIndex, Value, decode_ptr_instance};
 
+use crate::cmd::invocation::Invocation;
 use crate::cmd::util::render_code;
 
+mod math;
+mod truncate;
+
+pub use self::truncate::{FieldTruncation, TruncateMode};
+
 /// Output truncation and formatting limits for decoded values.
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone)]
 pub struct PrintOptions {
 	/// Maximum number of fields printed for a single struct.
 	pub max_fields_per_struct: usize,
-	/// Maximum number of Unicode scalar values printed for strings.
+	/// Maximum string length, in the units of the active [`TruncateMode`].
 	pub max_string_len: usize,
+	/// Strategy for strings longer than `max_string_len`.
+	pub truncate: TruncateMode,
+	/// Per-field strategies, checked before `truncate`; later entries win.
+	pub field_truncation: Vec<FieldTruncation>,
 	/// Maximum number of elements printed for arrays.
 	pub max_array_items: usize,
 	/// Maximum recursive print depth for nested arrays/structs.
 	pub max_print_depth: u32,
+	/// Render matrix/vector/rotation float arrays as aligned math values.
+	pub pretty_math: bool,
 }
 
 impl Default for PrintOptions {
@@ -23,8 +34,11 @@ impl Default for PrintOptions {
 		Self {
 			max_fields_per_struct: 80,
 			max_string_len: 200,
+			truncate: TruncateMode::Graphemes,
+			field_truncation: FieldTruncation::path_defaults(),
 			max_array_items: 16,
 			max_print_depth: 6,
+			pretty_math: false,
 		}
 	}
 }
@@ -35,8 +49,20 @@ impl PrintOptions {
 		Self {
 			max_fields_per_struct: 40,
 			max_string_len: 160,
+			truncate: TruncateMode::Graphemes,
+			field_truncation: FieldTruncation::path_defaults(),
 			max_array_items: 8,
 			max_print_depth: 4,
+			pretty_math: false,
+		}
+	}
+
+	/// Render a string value, applying the override for `field` when one exists.
+	pub fn render_string(&self, field: Option<&str>, text: &str) -> String {
+		let rule = field.and_then(|field| self.field_truncation.iter().rev().find(|rule| rule.field == field));
+		match rule {
+			Some(rule) => truncate::truncate(text, rule.mode, rule.max_len.unwrap_or(self.max_string_len)),
+			None => truncate::truncate(text, self.truncate, self.max_string_len),
 		}
 	}
 }
@@ -61,30 +87,35 @@ pub struct PrintCtx<'a> {
 	pub decode: Option<&'a DecodeOptions>,
 	/// Maximum expanded pointer nodes per print call.
 	pub expand_max_nodes: usize,
-	cache: RefCell<HashMap<u64, String>>,
+	/// Annotation and decode caches shared with the rest of the invocation.
+	pub invocation: &'a Invocation,
 	expand_stack: RefCell<Vec<u64>>,
 	expand_count: Cell<usize>,
-	decoded_cache: RefCell<HashMap<u64, StructValue>>,
 }
 
 impl<'a> PrintCtx<'a> {
-	/// Create a print context.
-	pub fn new(ptr_annot: Option<PtrAnnotCtx<'a>>, annotate_ptrs: bool, decode: Option<&'a DecodeOptions>, expand_max_nodes: usize) -> Self {
+	/// Create a print context resolving pointers through `invocation`'s caches.
+	pub fn new(
+		ptr_annot: Option<PtrAnnotCtx<'a>>,
+		annotate_ptrs: bool,
+		decode: Option<&'a DecodeOptions>,
+		expand_max_nodes: usize,
+		invocation: &'a Invocation,
+	) -> Self {
 		Self {
 			ptr_annot,
 			annotate_ptrs,
 			decode,
 			expand_max_nodes,
-			cache: RefCell::new(HashMap::new()),
+			invocation,
 			expand_stack: RefCell::new(Vec::new()),
 			expand_count: Cell::new(0),
-			decoded_cache: RefCell::new(HashMap::new()),
 		}
 	}
 }
 
 /// Print one decoded runtime value tree.
-pub fn print_value(value: &Value, indent: usize, depth: u32, options: PrintOptions, ctx: Option<&PrintCtx<'_>>, expand_left: u32) {
+pub fn print_value(value: &Value, indent: usize, depth: u32, options: &PrintOptions, ctx: Option<&PrintCtx<'_>>, expand_left: u32) {
 	let pad = " ".repeat(indent);
 	match value {
 		Value::Null => println!("{}null", pad),
@@ -94,7 +125,7 @@ pub fn print_value(value: &Value, indent: usize, depth: u32, options: PrintOptio
 		Value::F32(v) => println!("{}{v}", pad),
 		Value::F64(v) => println!("{}{v}", pad),
 		Value::Bytes(v) => println!("{}bytes[{}]", pad, v.len()),
-		Value::String(v) => println!("{}\"{}\"", pad, truncate(v, options.max_string_len)),
+		Value::String(v) => println!("{}\"{}\"", pad, options.render_string(None, v)),
 		Value::Ptr(v) => {
 			println!("{}{}", pad, format_ptr(*v, ctx));
 			print_ptr_expansion(*v, indent, depth, options, ctx, expand_left);
@@ -114,14 +145,25 @@ pub fn print_value(value: &Value, indent: usize, depth: u32, options: PrintOptio
 			println!("{}]", pad);
 		}
 		Value::Struct(item) => {
+			let label = match ctx.and_then(|ctx| ctx.decode?.plugins.as_ref()?.summary(item)) {
+				Some(summary) => format!("{} ({summary})", item.type_name),
+				None => item.type_name.to_string(),
+			};
 			if depth >= options.max_print_depth {
-				println!("{}{} {{ ... }}", pad, item.type_name);
+				println!("{}{} {{ ... }}", pad, label);
 				return;
 			}
-			println!("{}{} {{", pad, item.type_name);
+			println!("{}{} {{", pad, label);
 			for field in item.fields.iter().take(options.max_fields_per_struct) {
 				print!("{}  {} = ", pad, field.name);
-				if matches!(field.value, Value::Struct(_) | Value::Array(_)) {
+				if let Some(render) = options.pretty_math.then(|| math::render_math(&field.name, &field.value)).flatten() {
+					println!("{}", render.head);
+					for row in &render.rows {
+						println!("{}    {}", pad, row);
+					}
+				} else if let Value::String(text) = &field.value {
+					println!("\"{}\"", options.render_string(Some(&field.name), text));
+				} else if matches!(field.value, Value::Struct(_) | Value::Array(_)) {
 					println!();
 					print_value(&field.value, indent + 4, depth + 1, options, ctx, expand_left);
 				} else {
@@ -136,7 +178,7 @@ pub fn print_value(value: &Value, indent: usize, depth: u32, options: PrintOptio
 	}
 }
 
-fn print_ptr_expansion(ptr: u64, indent: usize, depth: u32, options: PrintOptions, ctx: Option<&PrintCtx<'_>>, expand_left: u32) {
+fn print_ptr_expansion(ptr: u64, indent: usize, depth: u32, options: &PrintOptions, ctx: Option<&PrintCtx<'_>>, expand_left: u32) {
 	if ptr == 0 || expand_left == 0 {
 		return;
 	}
@@ -164,15 +206,12 @@ fn print_ptr_expansion(ptr: u64, indent: usize, depth: u32, options: PrintOption
 		return;
 	}
 
-	let decoded = if let Some(cached) = ctx.decoded_cache.borrow().get(&canonical) {
-		cached.clone()
-	} else {
-		let Ok((resolved_canonical, struct_value)) = decode_ptr_instance(annot.dna, annot.index, ptr, decode) else {
-			println!("{}... (unresolved)", " ".repeat(indent + 2));
-			return;
-		};
-		ctx.decoded_cache.borrow_mut().insert(resolved_canonical, struct_value.clone());
-		struct_value
+	let Some(decoded) = ctx
+		.invocation
+		.decoded(canonical, || decode_ptr_instance(annot.dna, annot.index, ptr, decode).ok())
+	else {
+		println!("{}... (unresolved)", " ".repeat(indent + 2));
+		return;
 	};
 
 	ctx.expand_stack.borrow_mut().push(canonical);
@@ -197,11 +236,7 @@ fn format_ptr(ptr: u64, ctx: Option<&PrintCtx<'_>>) -> String {
 		return raw;
 	};
 
-	if let Some(cached) = ctx.cache.borrow().get(&ptr) {
-		return cached.clone();
-	}
-
-	let rendered = match annot.index.resolve_typed(annot.dna, ptr) {
+	ctx.invocation.annotation(ptr, || match annot.index.resolve_typed(annot.dna, ptr) {
 		Some(typed) if typed.element_index.is_some() => {
 			if let Some(canonical) = annot.index.canonical_ptr(annot.dna, ptr) {
 				let type_name = annot
@@ -223,18 +258,7 @@ fn format_ptr(ptr: u64, ctx: Option<&PrintCtx<'_>>) -> String {
 			}
 		}
 		_ => format!("{raw} (unresolved)"),
-	};
-
-	ctx.cache.borrow_mut().insert(ptr, rendered.clone());
-	rendered
-}
-
-fn truncate(input: &str, max_len: usize) -> String {
-	if input.chars().count() <= max_len {
-		return input.to_owned();
-	}
-	let out: String = input.chars().take(max_len).collect();
-	format!("{out}...")
+	})
 }
 
 #[cfg(test)]
@@ -1,21 +1,23 @@
 use blendoc::blend::{BHead, Block, Dna, DnaField, DnaStruct, IdIndex, IdRecord, PointerIndex, PtrEntry};
+use blendoc_testkit::builder::SCENE_ID_NAMES;
 
-use super::{PrintCtx, PtrAnnotCtx, format_ptr};
-use crate::cmd::test_support::{fixture_path, run_blendoc};
+use super::{FieldTruncation, PrintCtx, PrintOptions, PtrAnnotCtx, TruncateMode, format_ptr};
+use crate::cmd::invocation::Invocation;
+use crate::cmd::test_support::{TempBlend, fixture_path, run_blendoc};
 
 fn test_dna() -> Dna {
-	Dna {
-		endianness: blendoc::blend::Endianness::Little,
-		pointer_size: 8,
-		names: vec!["*next".into()],
-		types: vec!["Node".into()],
-		tlen: vec![8],
-		structs: vec![DnaStruct {
+	Dna::from_tables(
+		blendoc::blend::Endianness::Little,
+		8,
+		vec!["*next".into()],
+		vec!["Node".into()],
+		vec![8],
+		vec![DnaStruct {
 			type_idx: 0,
 			fields: vec![DnaField { type_idx: 0, name_idx: 0 }],
 		}],
-		struct_for_type: vec![Some(0)],
-	}
+	)
+	.expect("valid dna tables")
 }
 
 fn make_index<'a>(payload: &'a [u8], start_old: u64, code: [u8; 4]) -> PointerIndex<'a> {
@@ -53,6 +55,7 @@ fn ptr_annotation_uses_id_label_when_available() {
 		lib: None,
 	}]);
 
+	let invocation = Invocation::default();
 	let ctx = PrintCtx::new(
 		Some(PtrAnnotCtx {
 			dna: &dna,
@@ -62,6 +65,7 @@ fn ptr_annotation_uses_id_label_when_available() {
 		true,
 		None,
 		64,
+		&invocation,
 	);
 
 	let rendered = format_ptr(0x2000, Some(&ctx));
@@ -75,6 +79,7 @@ fn ptr_annotation_uses_type_for_non_id_target() {
 	let dna = test_dna();
 	let ids = IdIndex::build(Vec::new());
 
+	let invocation = Invocation::default();
 	let ctx = PrintCtx::new(
 		Some(PtrAnnotCtx {
 			dna: &dna,
@@ -84,6 +89,7 @@ fn ptr_annotation_uses_type_for_non_id_target() {
 		true,
 		None,
 		64,
+		&invocation,
 	);
 
 	let rendered = format_ptr(0x3000, Some(&ctx));
@@ -97,6 +103,7 @@ fn ptr_annotation_marks_unresolved_targets() {
 	let dna = test_dna();
 	let ids = IdIndex::build(Vec::new());
 
+	let invocation = Invocation::default();
 	let ctx = PrintCtx::new(
 		Some(PtrAnnotCtx {
 			dna: &dna,
@@ -106,12 +113,36 @@ fn ptr_annotation_marks_unresolved_targets() {
 		true,
 		None,
 		64,
+		&invocation,
 	);
 
 	let rendered = format_ptr(0x9999, Some(&ctx));
 	assert!(rendered.ends_with("(unresolved)"));
 }
 
+#[test]
+fn print_contexts_share_the_invocation_cache() {
+	let payload = 0_u64.to_le_bytes();
+	let index = make_index(&payload, 0x5000, *b"DATA");
+	let empty_index = make_index(&payload, 0x9000, *b"DATA");
+	let dna = test_dna();
+	let ids = IdIndex::build(Vec::new());
+	let annot = |index| PtrAnnotCtx { dna: &dna, index, ids: &ids };
+	let invocation = Invocation::default();
+
+	let text = PrintCtx::new(Some(annot(&index)), true, None, 64, &invocation);
+	let first = format_ptr(0x5000, Some(&text));
+	// The second output cannot resolve 0x5000 itself, so a match proves reuse.
+	let file = PrintCtx::new(Some(annot(&empty_index)), true, None, 64, &invocation);
+	let second = format_ptr(0x5000, Some(&file));
+	assert!(first.contains("-> Node@0x0000000000005000"));
+	assert_eq!(second, first, "second output reuses the first resolution");
+
+	let fresh = Invocation::default();
+	let uncached = PrintCtx::new(Some(annot(&empty_index)), true, None, 64, &fresh);
+	assert!(format_ptr(0x5000, Some(&uncached)).ends_with("(unresolved)"));
+}
+
 #[test]
 fn show_prints_pointer_annotations() {
 	let fixture = fixture_path("character.blend");
@@ -136,3 +167,108 @@ fn show_expands_pointer_targets_when_enabled() {
 	assert!(stdout.contains("-> NTShader Nodetree"), "expected pointer annotation");
 	assert!(stdout.contains("bNodeTree {"), "expected expanded nested struct output");
 }
+
+mod pretty_math {
+	use blendoc::blend::Value;
+
+	use super::super::math::{MathShape, math_shape, render_math};
+
+	fn floats(values: &[f32]) -> Value {
+		Value::Array(values.iter().copied().map(Value::F32).collect())
+	}
+
+	#[test]
+	fn shapes_follow_field_name_and_element_count() {
+		assert_eq!(math_shape("obmat", 16), Some(MathShape::Mat(4)));
+		assert_eq!(math_shape("parentinv", 16), Some(MathShape::Mat(4)));
+		assert_eq!(math_shape("mat", 9), Some(MathShape::Mat(3)));
+		assert_eq!(math_shape("quat", 4), Some(MathShape::Quat));
+		assert_eq!(math_shape("drot", 3), Some(MathShape::Euler));
+		assert_eq!(math_shape("loc", 3), Some(MathShape::Vec));
+		assert_eq!(math_shape("obmat", 12), None);
+		assert_eq!(math_shape("color", 3), None);
+	}
+
+	#[test]
+	fn matrix_renders_aligned_rows() {
+		let mut values = [0.0_f32; 16];
+		for idx in 0..4 {
+			values[idx * 4 + idx] = 1.0;
+		}
+		values[12] = -12.5;
+		let render = render_math("obmat", &floats(&values)).expect("matrix shape");
+		assert_eq!(render.head, "mat4");
+		assert_eq!(render.rows.len(), 4);
+		assert_eq!(render.rows[0], "[   1.0000,   0.0000,   0.0000,   0.0000 ]");
+		assert_eq!(render.rows[3], "[ -12.5000,   0.0000,   0.0000,   1.0000 ]");
+	}
+
+	#[test]
+	fn rotations_include_degree_interpretation() {
+		let euler = render_math("rot", &floats(&[std::f32::consts::FRAC_PI_2, 0.0, 0.0])).expect("euler shape");
+		assert_eq!(euler.head, "euler(1.5708, 0.0000, 0.0000) deg(90.00, 0.00, 0.00)");
+
+		let half = std::f32::consts::FRAC_1_SQRT_2;
+		let quat = render_math("quat", &floats(&[half, 0.0, 0.0, half])).expect("quat shape");
+		assert_eq!(quat.head, "quat(w=0.7071, x=0.0000, y=0.0000, z=0.7071) euler_deg(0.00, 0.00, 90.00)");
+	}
+
+	#[test]
+	fn non_float_arrays_are_left_alone() {
+		let ints = Value::Array(vec![Value::I64(1), Value::I64(2), Value::I64(3)]);
+		assert!(render_math("loc", &ints).is_none());
+	}
+}
+
+#[test]
+fn truncation_strategies_keep_the_informative_parts() {
+	let mut options = PrintOptions {
+		max_string_len: 12,
+		..PrintOptions::default()
+	};
+	let path = "//textures/brick/diffuse_4k.png";
+	// "e" + combining acute accent is one grapheme but two chars.
+	let accented = "caf\u{0065}\u{0301}s and more";
+
+	assert_eq!(options.render_string(Some("filepath"), path), "//te...e_4k.png");
+	assert_eq!(options.render_string(None, path), "//textures/b...");
+	assert_eq!(options.render_string(None, "short"), "short");
+
+	options.max_string_len = 4;
+	assert_eq!(options.render_string(None, accented), "cafe\u{0301}...");
+	options.truncate = TruncateMode::Chars;
+	assert_eq!(options.render_string(None, accented), "cafe...");
+	options.truncate = TruncateMode::None;
+	assert_eq!(options.render_string(None, accented), accented);
+
+	options.field_truncation.push("filepath=none".parse().expect("override parses"));
+	assert_eq!(options.render_string(Some("filepath"), path), path);
+	let rule: FieldTruncation = "name=middle:8".parse().expect("override parses");
+	assert_eq!((rule.field.as_str(), rule.mode, rule.max_len), ("name", TruncateMode::Middle, Some(8)));
+	for bad in ["=middle", "name", "name=squash", "name=middle:x"] {
+		assert!(bad.parse::<FieldTruncation>().is_err(), "{bad} should be rejected");
+	}
+}
+
+#[test]
+fn truncation_flags_reach_show_output() {
+	let target = TempBlend::scene("print_truncate");
+	let target_arg = target.arg();
+
+	let cut = run_blendoc(&["show", target_arg, "--id", SCENE_ID_NAMES[1], "--max-string", "3"]);
+	let middle = run_blendoc(&[
+		"show",
+		target_arg,
+		"--id",
+		SCENE_ID_NAMES[1],
+		"--max-string",
+		"3",
+		"--truncate-field",
+		"name=middle:5",
+	]);
+
+	let cut = String::from_utf8_lossy(&cut.stdout);
+	assert!(cut.contains("name = \"OBC...\""), "{cut}");
+	let middle = String::from_utf8_lossy(&middle.stdout);
+	assert!(middle.contains("name = \"O...mera\""), "{middle}");
+}
@@ -0,0 +1,95 @@
+use std::str::FromStr;
+
+use unicode_segmentation::UnicodeSegmentation;
+
+/// Field names whose strings are paths and keep their file name when shortened.
+pub(crate) const PATH_FIELDS: &[&str] = &["filepath", "filepath_abs", "pic"];
+
+/// How printed strings longer than the limit are shortened.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, clap::ValueEnum)]
+pub enum TruncateMode {
+	/// Keep the first N Unicode scalar values.
+	Chars,
+	/// Keep the first N grapheme clusters, never splitting a combined character.
+	Graphemes,
+	/// Keep the start and the (longer) end around a `...`, for paths.
+	Middle,
+	/// Print the whole string.
+	None,
+}
+
+/// Truncation override for one field name.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FieldTruncation {
+	/// Struct field name the override applies to.
+	pub field: String,
+	/// Strategy used for this field.
+	pub mode: TruncateMode,
+	/// Length limit for this field; `None` keeps the global limit.
+	pub max_len: Option<usize>,
+}
+
+impl FieldTruncation {
+	/// Middle-ellipsis overrides for the fields in [`PATH_FIELDS`].
+	pub fn path_defaults() -> Vec<Self> {
+		PATH_FIELDS
+			.iter()
+			.map(|field| Self {
+				field: (*field).to_owned(),
+				mode: TruncateMode::Middle,
+				max_len: None,
+			})
+			.collect()
+	}
+}
+
+impl FromStr for FieldTruncation {
+	type Err = String;
+
+	/// Parse `FIELD=MODE` or `FIELD=MODE:LEN`, e.g. `filepath=middle:120`.
+	fn from_str(value: &str) -> Result<Self, Self::Err> {
+		let usage = || format!("expected FIELD=MODE[:LEN] with MODE one of chars, graphemes, middle, none; got `{value}`");
+		let (field, spec) = value.split_once('=').filter(|(field, _)| !field.is_empty()).ok_or_else(usage)?;
+		let (mode, max_len) = match spec.split_once(':') {
+			Some((mode, len)) => (mode, Some(len.parse::<usize>().map_err(|_| usage())?)),
+			None => (spec, None),
+		};
+		let mode = <TruncateMode as clap::ValueEnum>::from_str(mode, true).map_err(|_| usage())?;
+		Ok(Self {
+			field: field.to_owned(),
+			mode,
+			max_len,
+		})
+	}
+}
+
+/// Shorten `input` to `max_len` units under `mode`, marking cuts with `...`.
+pub(crate) fn truncate(input: &str, mode: TruncateMode, max_len: usize) -> String {
+	match mode {
+		TruncateMode::None => input.to_owned(),
+		TruncateMode::Chars => {
+			if input.chars().count() <= max_len {
+				return input.to_owned();
+			}
+			let out: String = input.chars().take(max_len).collect();
+			format!("{out}...")
+		}
+		TruncateMode::Graphemes => {
+			let graphemes: Vec<&str> = input.graphemes(true).collect();
+			if graphemes.len() <= max_len {
+				return input.to_owned();
+			}
+			format!("{}...", graphemes[..max_len].concat())
+		}
+		TruncateMode::Middle => {
+			let graphemes: Vec<&str> = input.graphemes(true).collect();
+			if graphemes.len() <= max_len {
+				return input.to_owned();
+			}
+			// The end of a path (file name, extension) says more than its root.
+			let head = max_len / 3;
+			let tail = max_len - head;
+			format!("{}...{}", graphemes[..head].concat(), graphemes[graphemes.len() - tail..].concat())
+		}
+	}
+}
@@ -0,0 +1,83 @@
+use std::io::Write;
+use std::sync::Mutex;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::{Duration, Instant};
+
+use blendoc::blend::{Progress, ProgressSink, ProgressStage};
+
+static ENABLED: AtomicBool = AtomicBool::new(false);
+
+/// Minimum time between redraws of the same stage.
+const REDRAW_INTERVAL: Duration = Duration::from_millis(100);
+/// Width of the bar between the brackets.
+const BAR_WIDTH: usize = 30;
+
+/// Turn on stderr progress bars for core scans (`--progress`).
+pub(crate) fn set_enabled(enabled: bool) {
+	ENABLED.store(enabled, Ordering::Relaxed);
+}
+
+/// Progress handle for scan options; `None` unless `--progress` is set.
+pub(crate) fn sink() -> Option<Progress> {
+	ENABLED.load(Ordering::Relaxed).then(|| Progress::new(StderrBar::default()))
+}
+
+/// Single-line stderr progress bar, redrawn in place with `\r`.
+#[derive(Default)]
+struct StderrBar {
+	state: Mutex<BarState>,
+}
+
+#[derive(Default)]
+struct BarState {
+	drawn_at: Option<Instant>,
+	latest: Option<(ProgressStage, u64, Option<u64>)>,
+}
+
+impl ProgressSink for StderrBar {
+	fn update(&self, stage: ProgressStage, done: u64, total: Option<u64>) {
+		let mut state = self.state.lock().expect("progress lock");
+		state.latest = Some((stage, done, total));
+		if state.drawn_at.is_some_and(|at| at.elapsed() < REDRAW_INTERVAL) {
+			return;
+		}
+		state.drawn_at = Some(Instant::now());
+		draw(stage, done, total, false);
+	}
+
+	fn finish(&self, _stage: ProgressStage) {
+		let mut state = self.state.lock().expect("progress lock");
+		if let Some((stage, done, total)) = state.latest.take() {
+			draw(stage, done, total, true);
+		}
+		state.drawn_at = None;
+	}
+}
+
+fn draw(stage: ProgressStage, done: u64, total: Option<u64>, last: bool) {
+	let mut stderr = crate::cmd::quiet::stderr();
+	let _ = write!(stderr, "\r{}", render_line(stage, done, total));
+	if last {
+		let _ = writeln!(stderr);
+	}
+	let _ = stderr.flush();
+}
+
+/// `ids [#########---------] 150/300 50%`, or `blocks 1200` without a total.
+fn render_line(stage: ProgressStage, done: u64, total: Option<u64>) -> String {
+	let Some(total) = total.filter(|total| *total > 0) else {
+		return format!("{} {done}", stage.as_str());
+	};
+	let done = done.min(total);
+	let filled = (done as u128 * BAR_WIDTH as u128 / total as u128) as usize;
+	format!(
+		"{} [{}{}] {done}/{total} {}%",
+		stage.as_str(),
+		"#".repeat(filled),
+		"-".repeat(BAR_WIDTH - filled),
+		done * 100 / total
+	)
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,13 @@
+use blendoc::blend::ProgressStage;
+
+use super::render_line;
+
+#[test]
+fn bar_fills_in_proportion_and_counts_without_total() {
+	assert_eq!(
+		render_line(ProgressStage::Ids, 15, Some(30)),
+		format!("ids [{}{}] 15/30 50%", "#".repeat(15), "-".repeat(15))
+	);
+	assert_eq!(render_line(ProgressStage::Ids, 40, Some(30)), format!("ids [{}] 30/30 100%", "#".repeat(30)));
+	assert_eq!(render_line(ProgressStage::Blocks, 1200, None), "blocks 1200");
+}
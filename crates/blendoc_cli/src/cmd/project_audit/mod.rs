@@ -0,0 +1,184 @@
+use std::io::Write;
+use std::path::PathBuf;
+
+use blendoc::blend::{ProjectAudit, ProjectAuditOptions, ProjectTarget, Result, audit_project};
+
+use crate::cmd::output::OutputArgs;
+
+#[derive(clap::Args)]
+pub struct Args {
+	pub dir: PathBuf,
+	#[arg(long = "min-users", default_value_t = 2)]
+	pub min_users: usize,
+	#[arg(long = "asset-ext", value_name = "EXT")]
+	pub asset_ext: Vec<String>,
+	#[arg(long)]
+	pub json: bool,
+	#[command(flatten)]
+	pub output: OutputArgs,
+}
+
+/// Cross-reference external files and library links across every blend in a directory.
+///
+/// Reports targets used by at least `--min-users` blends, paths leaving the
+/// project root, missing targets, and asset files no blend references.
+pub fn run(args: Args) -> Result<()> {
+	let Args {
+		dir,
+		min_users,
+		asset_ext,
+		json,
+		output,
+	} = args;
+
+	let mut options = ProjectAuditOptions::default();
+	if !asset_ext.is_empty() {
+		options.asset_extensions = asset_ext.iter().map(|ext| ext.trim_start_matches('.').to_ascii_lowercase()).collect();
+	}
+	let audit = audit_project(&dir, &options)?;
+	let rows: Vec<TargetJson> = audit.targets.iter().map(|target| target_json(&audit, target)).collect();
+
+	let mut out = output.open(json)?;
+	if !out.is_text() {
+		let payload = ProjectAuditJson {
+			root: audit.root.display().to_string(),
+			min_users,
+			blends: audit.blends.iter().map(|path| display(&audit, path)).collect(),
+			unreadable: audit
+				.unreadable
+				.iter()
+				.map(|(path, error)| UnreadableJson {
+					path: display(&audit, path),
+					error: error.to_string(),
+				})
+				.collect(),
+			shared: audit.shared(min_users).iter().map(|target| display(&audit, &target.path)).collect(),
+			outside_root: audit.outside_root().iter().map(|target| display(&audit, &target.path)).collect(),
+			missing: audit.missing().iter().map(|target| display(&audit, &target.path)).collect(),
+			unreferenced: audit.unreferenced.iter().map(|path| display(&audit, path)).collect(),
+			targets: &rows,
+		};
+		let csv_rows: Vec<TargetRow> = rows.iter().map(TargetRow::from).collect();
+		out.structured(&payload, &csv_rows)?;
+		return out.finish();
+	}
+
+	writeln!(out, "root: {}", audit.root.display())?;
+	writeln!(out, "blends: {}", audit.blends.len())?;
+	writeln!(out, "targets: {}", audit.targets.len())?;
+	writeln!(out, "unreadable: {}", audit.unreadable.len())?;
+	for (path, error) in &audit.unreadable {
+		writeln!(out, "  {}: {error}", display(&audit, path))?;
+	}
+	write_targets(&mut out, &audit, &format!("shared (>= {min_users} blends)"), &audit.shared(min_users))?;
+	write_targets(&mut out, &audit, "outside_root", &audit.outside_root())?;
+	write_targets(&mut out, &audit, "missing", &audit.missing())?;
+	writeln!(out, "unreferenced: {}", audit.unreferenced.len())?;
+	for path in &audit.unreferenced {
+		writeln!(out, "  {}", display(&audit, path))?;
+	}
+	out.finish()
+}
+
+fn write_targets(out: &mut impl Write, audit: &ProjectAudit, label: &str, targets: &[&ProjectTarget]) -> Result<()> {
+	writeln!(out, "{label}: {}", targets.len())?;
+	for target in targets {
+		writeln!(
+			out,
+			"  {} [{}] blends={}",
+			display(audit, &target.path),
+			target.kind.as_str(),
+			target.blend_count()
+		)?;
+		for user in &target.users {
+			writeln!(out, "    {} {} ({})", display(audit, &user.blend), user.id_name, user.declared_path)?;
+		}
+	}
+	Ok(())
+}
+
+fn display(audit: &ProjectAudit, path: &std::path::Path) -> String {
+	audit.display_path(path).display().to_string()
+}
+
+fn target_json(audit: &ProjectAudit, target: &ProjectTarget) -> TargetJson {
+	TargetJson {
+		path: display(audit, &target.path),
+		kind: target.kind.as_str(),
+		exists: target.exists,
+		outside_root: target.outside_root,
+		blends: target.blend_count(),
+		users: target
+			.users
+			.iter()
+			.map(|user| UserJson {
+				blend: display(audit, &user.blend),
+				id: user.id_name.to_string(),
+				declared_path: user.declared_path.to_string(),
+			})
+			.collect(),
+	}
+}
+
+#[derive(serde::Serialize)]
+struct ProjectAuditJson<'a> {
+	root: String,
+	min_users: usize,
+	blends: Vec<String>,
+	unreadable: Vec<UnreadableJson>,
+	shared: Vec<String>,
+	outside_root: Vec<String>,
+	missing: Vec<String>,
+	unreferenced: Vec<String>,
+	targets: &'a [TargetJson],
+}
+
+#[derive(serde::Serialize)]
+struct UnreadableJson {
+	path: String,
+	error: String,
+}
+
+#[derive(serde::Serialize)]
+struct TargetJson {
+	path: String,
+	kind: &'static str,
+	exists: bool,
+	outside_root: bool,
+	blends: usize,
+	users: Vec<UserJson>,
+}
+
+#[derive(serde::Serialize)]
+struct UserJson {
+	blend: String,
+	id: String,
+	declared_path: String,
+}
+
+/// One referenced file; the `--format csv` row.
+#[derive(serde::Serialize)]
+struct TargetRow {
+	path: String,
+	kind: &'static str,
+	exists: bool,
+	outside_root: bool,
+	blends: usize,
+	references: usize,
+}
+
+impl From<&TargetJson> for TargetRow {
+	fn from(target: &TargetJson) -> Self {
+		Self {
+			path: target.path.clone(),
+			kind: target.kind,
+			exists: target.exists,
+			outside_root: target.outside_root,
+			blends: target.blends,
+			references: target.users.len(),
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests;
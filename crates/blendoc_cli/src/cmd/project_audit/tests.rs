@@ -0,0 +1,61 @@
+use std::path::Path;
+
+use blendoc_testkit::builder::{BlendBuilder, HeaderKind};
+
+use crate::cmd::test_support::{run_blendoc, run_blendoc_json};
+
+/// File with one `Image` ID per stored file path.
+fn image_file(images: &[&str]) -> Vec<u8> {
+	let mut builder = BlendBuilder::new(HeaderKind::Legacy8, 404, false);
+	let sdna = builder.sdna_mut();
+	sdna.add_type("char", 1);
+	sdna.add_type("void", 0);
+	sdna.add_struct("ID", &[("void", "*next"), ("void", "*prev"), ("void", "*lib"), ("char", "name[24]")]);
+	let image = sdna.add_struct("Image", &[("ID", "id"), ("char", "filepath[64]")]);
+
+	for (idx, path) in images.iter().enumerate() {
+		let mut payload = builder.payload();
+		payload.ptr(0).ptr(0).ptr(0).name(&format!("IMimage{idx}"), 24).name(path, 64);
+		let image_payload = payload.finish();
+		builder.block(*b"IM\0\0", image, 0x1000 + idx as u64 * 0x100, 1, image_payload);
+	}
+	builder.build()
+}
+
+fn write_project(dir: &Path) {
+	let _ = std::fs::remove_dir_all(dir);
+	std::fs::create_dir_all(dir.join("shots")).expect("dir created");
+	std::fs::create_dir_all(dir.join("tex")).expect("dir created");
+	std::fs::write(dir.join("a.blend"), image_file(&["//tex/wood.png", "/elsewhere/sky.exr"])).expect("a written");
+	std::fs::write(dir.join("shots").join("b.blend"), image_file(&["//../tex/wood.png"])).expect("b written");
+	std::fs::write(dir.join("tex").join("wood.png"), b"png").expect("wood written");
+	std::fs::write(dir.join("tex").join("old.png"), b"png").expect("old written");
+}
+
+#[test]
+fn project_audit_reports_shared_outside_and_unreferenced_assets() {
+	let dir = std::env::temp_dir().join(format!("blendoc_cli_project_{}", std::process::id()));
+	write_project(&dir);
+	let dir_arg = dir.to_string_lossy().into_owned();
+
+	let json = run_blendoc_json(&["project-audit", &dir_arg, "--json"]);
+	let text = run_blendoc(&["project-audit", &dir_arg, "--min-users", "3"]);
+	let _ = std::fs::remove_dir_all(&dir);
+
+	assert_eq!(json["blends"], serde_json::json!(["a.blend", "shots/b.blend"]));
+	assert_eq!(json["shared"], serde_json::json!(["tex/wood.png"]));
+	assert_eq!(json["outside_root"], serde_json::json!(["/elsewhere/sky.exr"]));
+	assert_eq!(json["missing"], serde_json::json!(["/elsewhere/sky.exr"]));
+	assert_eq!(json["unreferenced"], serde_json::json!(["tex/old.png"]));
+	let wood = json["targets"]
+		.as_array()
+		.and_then(|targets| targets.iter().find(|target| target["path"] == "tex/wood.png"))
+		.expect("wood target");
+	assert_eq!(wood["blends"], 2);
+	assert_eq!(wood["users"][1]["declared_path"], "//../tex/wood.png");
+
+	assert!(text.status.success(), "project-audit failed: {}", String::from_utf8_lossy(&text.stderr));
+	let text = String::from_utf8_lossy(&text.stdout);
+	assert!(text.contains("shared (>= 3 blends): 0"), "{text}");
+	assert!(text.contains("unreferenced: 1\n  tex/old.png"), "{text}");
+}
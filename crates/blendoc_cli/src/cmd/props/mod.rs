@@ -0,0 +1,208 @@
+use std::io::Write;
+use std::path::PathBuf;
+
+use blendoc::blend::{BlendFile, IdIndex, IdPropOptions, IdPropValue, IdPropertiesInfo, IdProperty, Result, read_id_properties};
+use serde_json::{Map, Value as JsonValue};
+
+use crate::cmd::output::OutputArgs;
+use crate::cmd::util::{IdOrPtrSelector, parse_id_or_ptr_selector, ptr_hex, scan_ids};
+
+#[derive(clap::Args)]
+pub struct Args {
+	pub file: PathBuf,
+	#[arg(long = "id")]
+	pub id_name: Option<String>,
+	#[arg(long, conflicts_with = "id_name")]
+	pub ptr: Option<String>,
+	#[arg(long = "max-depth", default_value_t = 32)]
+	pub max_depth: u32,
+	#[arg(long)]
+	pub json: bool,
+	#[command(flatten)]
+	pub output: OutputArgs,
+}
+
+/// Print an ID's custom properties; every ID that has some without a selector.
+///
+/// Groups are flattened to dotted paths, one typed leaf per line.
+pub fn run(args: Args) -> Result<()> {
+	let Args {
+		file: path,
+		id_name,
+		ptr,
+		max_depth,
+		json,
+		output,
+	} = args;
+
+	let blend = BlendFile::open(&path)?;
+	let dna = blend.dna()?;
+	let index = blend.pointer_index()?;
+	let ids = IdIndex::build(scan_ids(&blend, &dna)?);
+	let options = IdPropOptions {
+		max_depth,
+		..IdPropOptions::default()
+	};
+
+	let owners = if id_name.is_none() && ptr.is_none() {
+		let mut owners = Vec::new();
+		for record in &ids.records {
+			let info = read_id_properties(&dna, &index, &ids, record.old_ptr, &options)?;
+			if !info.is_empty() {
+				owners.push(info);
+			}
+		}
+		owners.sort_by_key(|info| info.owner_ptr);
+		owners
+	} else {
+		let target = match parse_id_or_ptr_selector(id_name, ptr)? {
+			IdOrPtrSelector::Id(name) => ids.resolve_selector(&name)?.old_ptr,
+			IdOrPtrSelector::Ptr(ptr) => index.canonicalize_ptr(&dna, ptr)?,
+		};
+		vec![read_id_properties(&dna, &index, &ids, target, &options)?]
+	};
+
+	let mut out = output.open(json)?;
+	if !out.is_text() {
+		let rows: Vec<PropRow> = owners.iter().flat_map(owner_rows).collect();
+		let payload = PropsJson {
+			path: path.display().to_string(),
+			ids: owners.iter().map(owner_json).collect(),
+		};
+		out.structured(&payload, &rows)?;
+		return out.finish();
+	}
+
+	writeln!(out, "path: {}", path.display())?;
+	writeln!(out, "ids: {}", owners.len())?;
+	for owner in &owners {
+		writeln!(
+			out,
+			"\nid: {} ({}){}",
+			owner.owner_id.as_deref().unwrap_or("-"),
+			ptr_hex(owner.owner_ptr),
+			if owner.truncated { " (truncated)" } else { "" }
+		)?;
+		if owner.is_empty() {
+			writeln!(out, "  properties: -")?;
+		}
+		for row in owner_rows(owner) {
+			let prefix = if row.system { "system:" } else { "" };
+			writeln!(out, "  {prefix}{}\t{}\t{}", row.path, row.type_name, row.value)?;
+		}
+	}
+	out.finish()
+}
+
+fn owner_rows(owner: &IdPropertiesInfo) -> Vec<PropRow> {
+	let id = owner.owner_id.as_deref().unwrap_or_default();
+	let mut rows = Vec::new();
+	for prop in &owner.properties {
+		push_rows(&mut rows, id, false, &prop.name, &prop.value);
+	}
+	for prop in &owner.system_properties {
+		push_rows(&mut rows, id, true, &prop.name, &prop.value);
+	}
+	rows
+}
+
+/// One row per leaf; groups recurse with dotted paths and group arrays with `[i]`.
+fn push_rows(rows: &mut Vec<PropRow>, id: &str, system: bool, path: &str, value: &IdPropValue) {
+	match value {
+		IdPropValue::Group(children) if !children.is_empty() => {
+			for child in children {
+				push_rows(rows, id, system, &format!("{path}.{}", child.name), &child.value);
+			}
+		}
+		IdPropValue::Array(items) if items.iter().any(|item| matches!(item, IdPropValue::Group(_) | IdPropValue::Array(_))) => {
+			for (idx, item) in items.iter().enumerate() {
+				push_rows(rows, id, system, &format!("{path}[{idx}]"), item);
+			}
+		}
+		leaf => rows.push(PropRow {
+			id: id.to_owned(),
+			system,
+			path: path.to_owned(),
+			type_name: leaf.type_label(),
+			value: value_label(leaf),
+		}),
+	}
+}
+
+fn value_label(value: &IdPropValue) -> String {
+	match value {
+		IdPropValue::String(text) => format!("{:?}", text.as_ref()),
+		IdPropValue::Bytes(bytes) => format!("bytes[{}]", bytes.len()),
+		IdPropValue::Int(number) => number.to_string(),
+		IdPropValue::Float(number) => number.to_string(),
+		IdPropValue::Double(number) => number.to_string(),
+		IdPropValue::Bool(flag) => flag.to_string(),
+		IdPropValue::Array(items) => format!("[{}]", items.iter().map(value_label).collect::<Vec<_>>().join(", ")),
+		IdPropValue::Group(_) => "{}".to_owned(),
+		IdPropValue::Id { ptr, name } => match name {
+			Some(name) => format!("-> {name}"),
+			None if *ptr == 0 => "null".to_owned(),
+			None => format!("-> {}", ptr_hex(*ptr)),
+		},
+		IdPropValue::Unsupported { .. } => "?".to_owned(),
+	}
+}
+
+fn owner_json(owner: &IdPropertiesInfo) -> OwnerJson {
+	OwnerJson {
+		ptr: ptr_hex(owner.owner_ptr),
+		id: owner.owner_id.as_deref().map(str::to_owned),
+		truncated: owner.truncated,
+		properties: group_json(&owner.properties),
+		system_properties: group_json(&owner.system_properties),
+	}
+}
+
+fn group_json(children: &[IdProperty]) -> JsonValue {
+	let map: Map<String, JsonValue> = children.iter().map(|prop| (prop.name.to_string(), value_json(&prop.value))).collect();
+	JsonValue::Object(map)
+}
+
+fn value_json(value: &IdPropValue) -> JsonValue {
+	match value {
+		IdPropValue::String(text) => serde_json::json!(text.as_ref()),
+		IdPropValue::Bytes(bytes) => serde_json::json!(bytes),
+		IdPropValue::Int(number) => serde_json::json!(number),
+		IdPropValue::Float(number) => serde_json::json!(number),
+		IdPropValue::Double(number) => serde_json::json!(number),
+		IdPropValue::Bool(flag) => serde_json::json!(flag),
+		IdPropValue::Array(items) => JsonValue::Array(items.iter().map(value_json).collect()),
+		IdPropValue::Group(children) => group_json(children),
+		IdPropValue::Id { ptr, name } => serde_json::json!({ "id": name.as_deref(), "ptr": ptr_hex(*ptr) }),
+		IdPropValue::Unsupported { type_code } => serde_json::json!({ "unsupported_type": type_code }),
+	}
+}
+
+#[derive(serde::Serialize)]
+struct PropsJson {
+	path: String,
+	ids: Vec<OwnerJson>,
+}
+
+#[derive(serde::Serialize)]
+struct OwnerJson {
+	ptr: String,
+	id: Option<String>,
+	truncated: bool,
+	properties: JsonValue,
+	system_properties: JsonValue,
+}
+
+/// One leaf property; also the `--format csv` row.
+#[derive(serde::Serialize)]
+struct PropRow {
+	id: String,
+	system: bool,
+	path: String,
+	#[serde(rename = "type")]
+	type_name: String,
+	value: String,
+}
+
+#[cfg(test)]
+mod tests;
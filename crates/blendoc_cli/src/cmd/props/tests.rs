@@ -0,0 +1,20 @@
+use blendoc_testkit::builder::SCENE_ID_NAMES;
+
+use crate::cmd::test_support::{TempBlend, run_blendoc, run_blendoc_json};
+
+#[test]
+fn scene_without_properties_lists_no_ids_and_reports_empty_selection() {
+	let target = TempBlend::scene("props");
+	let target_arg = target.arg();
+
+	let all = run_blendoc(&["props", target_arg]);
+	let object = run_blendoc_json(&["props", target_arg, "--id", SCENE_ID_NAMES[1], "--json"]);
+
+	assert!(all.status.success(), "props failed: {}", String::from_utf8_lossy(&all.stderr));
+	assert!(String::from_utf8_lossy(&all.stdout).contains("ids: 0"));
+	let ids = object["ids"].as_array().expect("ids array");
+	assert_eq!(ids.len(), 1);
+	assert_eq!(ids[0]["id"], SCENE_ID_NAMES[1]);
+	assert_eq!(ids[0]["properties"], serde_json::json!({}));
+	assert_eq!(ids[0]["truncated"], false);
+}
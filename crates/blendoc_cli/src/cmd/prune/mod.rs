@@ -0,0 +1,209 @@
+use std::fs;
+use std::io::{self, Write};
+use std::path::PathBuf;
+
+use blendoc::blend::{BlendError, BlendFile, IdIndex, PrunePlan, PrunedBlock, Result, encode_bytes, plan_prune, write_pruned};
+
+use crate::cmd::output::OutputArgs;
+use crate::cmd::replay::digest_reader;
+use crate::cmd::util::{parse_block_code, parse_ptr, ptr_hex, render_code, scan_ids};
+
+#[derive(clap::Args)]
+pub struct Args {
+	pub file: PathBuf,
+	#[arg(long, value_name = "PATH")]
+	pub plan: PathBuf,
+	#[arg(long = "dry-run", conflicts_with = "write")]
+	pub dry_run: bool,
+	#[arg(long, value_name = "FILE", required_unless_present = "dry_run")]
+	pub write: Option<PathBuf>,
+	#[arg(long)]
+	pub json: bool,
+	#[command(flatten)]
+	pub output: OutputArgs,
+}
+
+/// Remove IDs and their owned data blocks, or preview the removal as a plan.
+///
+/// `--plan` is either a list of ID selectors, one per line (`#` starts a
+/// comment), or a JSON plan printed by an earlier `--dry-run --json`. A JSON
+/// plan is executed verbatim: the file must still hash to the plan's
+/// `input_digest` and hold every planned block at its recorded offset.
+pub fn run(args: Args) -> Result<()> {
+	let Args {
+		file: path,
+		plan: plan_path,
+		dry_run: _,
+		write,
+		json,
+		output,
+	} = args;
+
+	let blend = BlendFile::open(&path)?;
+	let (_, input_digest) = digest_reader(blend.bytes(), None)?;
+	let text = fs::read_to_string(&plan_path)?;
+
+	let mut plan = if text.trim_start().starts_with('{') {
+		let plan: PlanJson = serde_json::from_str(&text).map_err(|err| usage(format!("{}: not a prune plan: {err}", plan_path.display())))?;
+		if plan.input_digest != input_digest {
+			return Err(BlendError::PrunePlanMismatch {
+				detail: format!("plan was made for {} ({}), file hashes to {input_digest}", plan.path, plan.input_digest),
+			});
+		}
+		plan
+	} else {
+		let dna = blend.dna()?;
+		let ids = IdIndex::build(scan_ids(&blend, &dna)?);
+		let mut remove = Vec::new();
+		for selector in text
+			.lines()
+			.map(|line| line.split('#').next().unwrap_or_default().trim())
+			.filter(|line| !line.is_empty())
+		{
+			let ptr = ids.resolve_selector(selector)?.old_ptr;
+			if !remove.contains(&ptr) {
+				remove.push(ptr);
+			}
+		}
+		let computed = plan_prune(&blend, &dna, &ids, &remove)?;
+		plan_json(&path, input_digest, &ids, &computed)
+	};
+
+	if let Some(target) = &write {
+		let blocks = plan.blocks.iter().map(pruned_block).collect::<Result<Vec<_>>>()?;
+		let mut decoded = Vec::with_capacity(blend.bytes().len());
+		write_pruned(&blend, &blocks, &mut decoded)?;
+		BlendFile::from_bytes(decoded.clone())?;
+		fs::write(target, encode_bytes(&decoded, blend.compression, None)?)?;
+		plan.written = Some(target.display().to_string());
+	}
+
+	let mut out = output.open(json)?;
+	if !out.is_text() {
+		out.structured(&plan, &plan.blocks)?;
+		return out.finish();
+	}
+
+	writeln!(out, "path: {}", plan.path)?;
+	writeln!(out, "input_digest: {}", plan.input_digest)?;
+	for id in &plan.ids {
+		writeln!(out, "remove: {} {} {}", id.code, id.ptr, id.name)?;
+	}
+	writeln!(out, "blocks: {}", plan.blocks.len())?;
+	for block in &plan.blocks {
+		writeln!(out, "  {} {} {}\t{} bytes", block.block_offset, block.code, block.ptr, block.bytes)?;
+	}
+	writeln!(out, "broken_refs: {}", plan.broken_refs.len())?;
+	for broken in &plan.broken_refs {
+		writeln!(out, "  {}.{} -> {} ({})", broken.owner, broken.field, broken.ptr, broken.target)?;
+	}
+	writeln!(out, "bytes: {} -> {} (saves {})", plan.input_bytes, plan.output_bytes, plan.removed_bytes)?;
+	match &plan.written {
+		Some(target) => writeln!(out, "written: {target}")?,
+		None => writeln!(out, "dry run: nothing written")?,
+	}
+	out.finish()
+}
+
+fn plan_json(path: &std::path::Path, input_digest: String, ids: &IdIndex, plan: &PrunePlan) -> PlanJson {
+	let name = |ptr: u64| ids.get_by_ptr(ptr).map_or_else(|| ptr_hex(ptr), |record| record.id_name.to_string());
+	PlanJson {
+		path: path.display().to_string(),
+		input_digest,
+		input_bytes: plan.input_bytes,
+		removed_bytes: plan.removed_bytes,
+		output_bytes: plan.output_bytes(),
+		ids: plan
+			.ids
+			.iter()
+			.filter_map(|ptr| ids.get_by_ptr(*ptr))
+			.map(|record| PlanId {
+				name: record.id_name.to_string(),
+				code: render_code(record.code),
+				ptr: ptr_hex(record.old_ptr),
+			})
+			.collect(),
+		blocks: plan
+			.blocks
+			.iter()
+			.map(|block| PlanBlock {
+				block_offset: format!("0x{:x}", block.block_offset),
+				code: render_code(block.code),
+				ptr: ptr_hex(block.old_ptr),
+				bytes: block.bytes,
+				owner: name(block.owner),
+			})
+			.collect(),
+		broken_refs: plan
+			.broken_refs
+			.iter()
+			.map(|broken| PlanBrokenRef {
+				owner: broken.owner_name.to_string(),
+				owner_ptr: ptr_hex(broken.owner),
+				field: broken.field.to_string(),
+				ptr: ptr_hex(broken.ptr),
+				target: name(broken.target),
+			})
+			.collect(),
+		written: None,
+	}
+}
+
+fn pruned_block(block: &PlanBlock) -> Result<PrunedBlock> {
+	let block_offset = parse_ptr(&block.block_offset)?;
+	Ok(PrunedBlock {
+		block_offset: usize::try_from(block_offset).map_err(|_| usage(format!("block offset {} is out of range", block.block_offset)))?,
+		code: parse_block_code(&block.code)?,
+		old_ptr: parse_ptr(&block.ptr)?,
+		bytes: block.bytes,
+		owner: 0,
+	})
+}
+
+fn usage(message: String) -> BlendError {
+	BlendError::Io(io::Error::other(message))
+}
+
+/// Reviewable prune plan; `--plan` reads it back to execute it unchanged.
+#[derive(serde::Serialize, serde::Deserialize)]
+struct PlanJson {
+	path: String,
+	input_digest: String,
+	input_bytes: u64,
+	removed_bytes: u64,
+	output_bytes: u64,
+	ids: Vec<PlanId>,
+	blocks: Vec<PlanBlock>,
+	broken_refs: Vec<PlanBrokenRef>,
+	#[serde(default)]
+	written: Option<String>,
+}
+
+#[derive(serde::Serialize, serde::Deserialize)]
+struct PlanId {
+	name: String,
+	code: String,
+	ptr: String,
+}
+
+/// One removed block; also the `--format csv` row.
+#[derive(serde::Serialize, serde::Deserialize)]
+struct PlanBlock {
+	block_offset: String,
+	code: String,
+	ptr: String,
+	bytes: u64,
+	owner: String,
+}
+
+#[derive(serde::Serialize, serde::Deserialize)]
+struct PlanBrokenRef {
+	owner: String,
+	owner_ptr: String,
+	field: String,
+	ptr: String,
+	target: String,
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,57 @@
+use blendoc_testkit::builder::{HeaderKind, scene_file};
+
+use crate::cmd::test_support::{run_blendoc, run_blendoc_json};
+
+#[test]
+fn dry_run_plan_executes_verbatim() {
+	let base = std::env::temp_dir().join(format!("blendoc_prune_{}", std::process::id()));
+	let input = base.with_extension("blend");
+	let ids = base.with_extension("ids");
+	let plan = base.with_extension("json");
+	let pruned = base.with_extension("pruned.blend");
+	std::fs::write(&input, scene_file(HeaderKind::Legacy8, false)).expect("synthetic file written");
+	std::fs::write(&ids, "# drop the camera mesh\nMECamera\n").expect("id list written");
+	let input_arg = input.to_string_lossy().into_owned();
+	let ids_arg = ids.to_string_lossy().into_owned();
+	let plan_arg = plan.to_string_lossy().into_owned();
+	let pruned_arg = pruned.to_string_lossy().into_owned();
+
+	let dry = run_blendoc(&["prune", &input_arg, "--plan", &ids_arg, "--dry-run", "--json"]);
+	std::fs::write(&plan, &dry.stdout).expect("plan written");
+	let applied = run_blendoc_json(&["prune", &input_arg, "--plan", &plan_arg, "--write", &pruned_arg, "--json"]);
+	let remaining = run_blendoc_json(&["ids", &pruned_arg, "--json"]);
+	std::fs::write(&input, scene_file(HeaderKind::Legacy4, false)).expect("synthetic file rewritten");
+	let stale = run_blendoc(&["prune", &input_arg, "--plan", &plan_arg, "--write", &pruned_arg]);
+	let missing_write = run_blendoc(&["prune", &input_arg, "--plan", &ids_arg]);
+	for path in [&input, &ids, &plan, &pruned] {
+		std::fs::remove_file(path).expect("temp file removed");
+	}
+
+	assert!(dry.status.success());
+	let dry: serde_json::Value = serde_json::from_slice(&dry.stdout).expect("json plan");
+	assert_eq!(dry["ids"][0]["name"], "MECamera");
+	let codes: Vec<&str> = dry["blocks"]
+		.as_array()
+		.expect("blocks array")
+		.iter()
+		.map(|block| block["code"].as_str().expect("code"))
+		.collect();
+	assert_eq!(codes, ["ME", "DATA"]);
+	assert_eq!(dry["broken_refs"][0]["owner"], "OBCamera");
+	assert_eq!(dry["broken_refs"][0]["field"], "data");
+	assert_eq!(dry["written"], serde_json::Value::Null);
+
+	assert_eq!(applied["blocks"], dry["blocks"]);
+	assert_eq!(applied["written"], pruned_arg.as_str());
+	let names: Vec<&str> = remaining["items"]
+		.as_array()
+		.expect("ids array")
+		.iter()
+		.map(|row| row["id_name"].as_str().expect("name"))
+		.collect();
+	assert!(!names.contains(&"MECamera") && names.contains(&"OBCamera"), "{names:?}");
+
+	assert!(!stale.status.success());
+	assert!(String::from_utf8_lossy(&stale.stderr).contains("prune plan does not match"));
+	assert!(!missing_write.status.success());
+}
@@ -0,0 +1,25 @@
+use std::io::{self, Write};
+use std::sync::atomic::{AtomicBool, Ordering};
+
+static QUIET: AtomicBool = AtomicBool::new(false);
+
+/// Enable or disable `--quiet` output suppression for this process.
+pub(crate) fn set_enabled(enabled: bool) {
+	QUIET.store(enabled, Ordering::Relaxed);
+}
+
+/// Whether `--quiet` is in effect; checked by the crate's `print!` family and
+/// by [`stdout`]/[`stderr`].
+pub(crate) fn enabled() -> bool {
+	QUIET.load(Ordering::Relaxed)
+}
+
+/// Locked stdout, or a sink under `--quiet`.
+pub(crate) fn stdout() -> Box<dyn Write> {
+	if enabled() { Box::new(io::sink()) } else { Box::new(io::stdout().lock()) }
+}
+
+/// Locked stderr, or a sink under `--quiet`.
+pub(crate) fn stderr() -> Box<dyn Write> {
+	if enabled() { Box::new(io::sink()) } else { Box::new(io::stderr().lock()) }
+}
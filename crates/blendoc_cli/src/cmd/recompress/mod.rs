@@ -0,0 +1,83 @@
+use std::fs;
+use std::path::PathBuf;
+
+use blendoc::blend::{BlendError, BlendFile, Compression, decode_bytes, encode_bytes};
+
+use crate::cmd::util::emit_json;
+
+#[derive(clap::Args)]
+pub struct Args {
+	pub input: PathBuf,
+	pub output: PathBuf,
+	#[arg(long, default_value = "zstd", value_parser = ["zstd", "gzip", "none"])]
+	pub to: String,
+	#[arg(long)]
+	pub level: Option<i32>,
+	#[arg(long)]
+	pub json: bool,
+}
+
+/// Re-emit a blend file's decoded block stream with different compression.
+pub fn run(args: Args) -> blendoc::blend::Result<()> {
+	let Args {
+		input,
+		output,
+		to,
+		level,
+		json,
+	} = args;
+
+	let target = match to.as_str() {
+		"gzip" => Compression::Gzip,
+		"none" => Compression::None,
+		_ => Compression::Zstd,
+	};
+
+	let blend = BlendFile::open(&input)?;
+	let encoded = encode_bytes(blend.bytes(), target, level)?;
+
+	// Round-trip before writing so a bad encode never replaces a good file.
+	let (_, roundtrip) = decode_bytes(encoded.clone())?;
+	if roundtrip != blend.bytes() {
+		return Err(BlendError::Io(std::io::Error::other("recompressed stream did not round-trip")));
+	}
+	fs::write(&output, &encoded)?;
+
+	let input_size = fs::metadata(&input)?.len();
+	if json {
+		emit_json(&RecompressJson {
+			input: input.display().to_string(),
+			output: output.display().to_string(),
+			from: blend.compression.as_str(),
+			to: target.as_str(),
+			decoded_bytes: blend.bytes().len(),
+			input_bytes: input_size,
+			output_bytes: encoded.len(),
+		});
+		return Ok(());
+	}
+
+	println!("input: {}", input.display());
+	println!("output: {}", output.display());
+	println!("from: {}", blend.compression.as_str());
+	println!("to: {}", target.as_str());
+	println!("decoded_bytes: {}", blend.bytes().len());
+	println!("input_bytes: {input_size}");
+	println!("output_bytes: {}", encoded.len());
+
+	Ok(())
+}
+
+#[derive(serde::Serialize)]
+struct RecompressJson {
+	input: String,
+	output: String,
+	from: &'static str,
+	to: &'static str,
+	decoded_bytes: usize,
+	input_bytes: u64,
+	output_bytes: usize,
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,27 @@
+use crate::cmd::test_support::{TempBlend, run_blendoc, run_blendoc_json};
+
+#[test]
+fn recompress_writes_seekable_zstd_and_rejects_bad_levels() {
+	let input = TempBlend::scene("recompress_in");
+	let output = std::env::temp_dir().join(format!("blendoc_recompress_out_{}.blend", std::process::id()));
+	let input_arg = input.arg();
+	let output_arg = output.to_string_lossy().into_owned();
+
+	let json = run_blendoc_json(&["recompress", input_arg, &output_arg, "--to", "zstd", "--level", "19", "--json"]);
+	let encoded = std::fs::read(&output).expect("output written");
+	let info = run_blendoc_json(&["info", &output_arg, "--json"]);
+	let bad_level = run_blendoc(&["recompress", input_arg, &output_arg, "--to", "gzip", "--level", "12"]);
+	let reencoded = std::fs::read(&output).expect("output kept");
+	std::fs::remove_file(&output).expect("output removed");
+
+	assert_eq!(json["to"], "zstd");
+	assert_eq!(json["output_bytes"].as_u64(), Some(encoded.len() as u64));
+	assert!(encoded.starts_with(&[0x28, 0xB5, 0x2F, 0xFD]));
+	assert!(encoded.ends_with(&0x8F92_EAB1_u32.to_le_bytes()), "seek table footer");
+	assert_eq!(info["compression"], "zstd");
+
+	assert_eq!(bad_level.status.code(), Some(3));
+	let stderr = String::from_utf8_lossy(&bad_level.stderr);
+	assert!(stderr.contains("invalid gzip compression level 12 (expected 0..=9)"), "{stderr}");
+	assert_eq!(reencoded, encoded, "a rejected level leaves the output untouched");
+}
@@ -1,8 +1,10 @@
 use std::path::PathBuf;
 
-use blendoc::blend::{BlendError, BlendFile, IdIndex, RefRecord, RefScanOptions, scan_id_blocks, scan_id_link_provenance, scan_refs_from_ptr};
+use blendoc::blend::{BlendFile, IdIndex, LibraryNamespaces, RefRecord, RefScanOptions, scan_id_link_provenance, scan_refs_from_ptr};
 
-use crate::cmd::util::{RootSelector, emit_json, parse_root_selector, ptr_hex, render_code};
+use crate::cmd::util::{
+	RootSelector, code_root_block, code_selector_label, emit_json, library_namespaces, parse_root_selector, ptr_hex, render_code, scan_ids,
+};
 
 #[derive(clap::Args)]
 pub struct Args {
@@ -17,6 +19,8 @@ pub struct Args {
 	pub depth: Option<u32>,
 	#[arg(long)]
 	pub limit: Option<usize>,
+	#[arg(long = "annotate-libs")]
+	pub annotate_libs: bool,
 	#[arg(long)]
 	pub json: bool,
 }
@@ -30,6 +34,7 @@ pub fn run(args: Args) -> blendoc::blend::Result<()> {
 		id_name,
 		depth,
 		limit,
+		annotate_libs,
 		json,
 	} = args;
 
@@ -38,18 +43,16 @@ pub fn run(args: Args) -> blendoc::blend::Result<()> {
 	let blend = BlendFile::open(&path)?;
 	let dna = blend.dna()?;
 	let index = blend.pointer_index()?;
-	let ids = IdIndex::build(scan_id_blocks(&blend, &dna)?);
+	let ids = IdIndex::build(scan_ids(&blend, &dna)?);
 
 	let (root_ptr, root_label) = match selector {
-		RootSelector::Code(block_code) => {
-			let block = blend
-				.find_first_block_by_code(block_code)?
-				.ok_or(BlendError::BlockNotFound { code: block_code })?;
-			(block.head.old, format!("code:{}", render_code(block_code)))
+		RootSelector::Code(block_code, occurrence) => {
+			let block = code_root_block(&blend, block_code, occurrence)?;
+			(block.head.old, code_selector_label(block_code, occurrence))
 		}
 		RootSelector::Ptr(ptr) => (ptr, format!("ptr:0x{ptr:016x}")),
 		RootSelector::Id(id_name) => {
-			let row = ids.get_by_name(&id_name).ok_or(BlendError::IdRecordNotFound { name: id_name.clone() })?;
+			let row = ids.resolve_selector(&id_name)?;
 			(row.old_ptr, format!("id:{}", row.id_name))
 		}
 	};
@@ -75,8 +78,10 @@ pub fn run(args: Args) -> blendoc::blend::Result<()> {
 		None
 	};
 
+	let namespaces = library_namespaces(&blend, &dna, annotate_libs)?;
+
 	if json {
-		print_json(&path, &root_label, root_ptr, &refs, root_link);
+		print_json(&path, &root_label, root_ptr, &refs, root_link, &namespaces);
 		return Ok(());
 	}
 
@@ -95,7 +100,10 @@ pub fn run(args: Args) -> blendoc::blend::Result<()> {
 				render_code(target.code),
 				target.sdna_nr,
 				target.type_name,
-				target.id_name.as_deref().unwrap_or("-")
+				target
+					.id_name
+					.as_deref()
+					.map_or_else(|| "-".to_owned(), |id| namespaces.annotate(target.canonical, id))
 			);
 		} else {
 			println!("{}\t0x{:016x}\t-\t-\t-\t-\t-", record.field, record.ptr);
@@ -105,7 +113,7 @@ pub fn run(args: Args) -> blendoc::blend::Result<()> {
 	Ok(())
 }
 
-fn print_json(path: &std::path::Path, root_label: &str, root_ptr: u64, refs: &[RefRecord], root_link: Option<(bool, &str)>) {
+fn print_json(path: &std::path::Path, root_label: &str, root_ptr: u64, refs: &[RefRecord], root_link: Option<(bool, &str)>, namespaces: &LibraryNamespaces) {
 	let payload = RefsJson {
 		path: path.display().to_string(),
 		root: root_label.to_owned(),
@@ -124,6 +132,7 @@ fn print_json(path: &std::path::Path, root_label: &str, root_ptr: u64, refs: &[R
 						sdna_nr: Some(target.sdna_nr),
 						type_name: Some(target.type_name.to_string()),
 						id: target.id_name.as_deref().map(|item| item.to_string()),
+						library: namespaces.get(target.canonical).map(str::to_owned),
 					}
 				} else {
 					RefJson {
@@ -134,6 +143,7 @@ fn print_json(path: &std::path::Path, root_label: &str, root_ptr: u64, refs: &[R
 						sdna_nr: None,
 						type_name: None,
 						id: None,
+						library: None,
 					}
 				}
 			})
@@ -165,4 +175,6 @@ struct RefJson {
 	#[serde(rename = "type")]
 	type_name: Option<String>,
 	id: Option<String>,
+	#[serde(skip_serializing_if = "Option::is_none")]
+	library: Option<String>,
 }
@@ -0,0 +1,158 @@
+use std::path::PathBuf;
+
+use blendoc::blend::{BlendFile, IdIndex, InboundRef, StringHit, XrefOptions, find_inbound_refs_to_ptr, find_string_occurrences};
+
+use crate::cmd::util::{emit_json, ptr_hex, render_code, scan_ids};
+
+#[derive(clap::Args)]
+pub struct Args {
+	pub file: PathBuf,
+	#[arg(long = "id")]
+	pub id_name: String,
+	#[arg(long = "dry-run", required = true)]
+	pub dry_run: bool,
+	#[arg(long = "refs-depth")]
+	pub refs_depth: Option<u32>,
+	#[arg(long)]
+	pub limit: Option<usize>,
+	#[arg(long)]
+	pub partial: bool,
+	#[arg(long)]
+	pub json: bool,
+}
+
+/// Report pointer and textual references affected by renaming one ID.
+pub fn run(args: Args) -> blendoc::blend::Result<()> {
+	let Args {
+		file: path,
+		id_name,
+		dry_run: _,
+		refs_depth,
+		limit,
+		partial,
+		json,
+	} = args;
+
+	let blend = BlendFile::open(&path)?;
+	let dna = blend.dna()?;
+	let index = blend.pointer_index()?;
+	let ids = IdIndex::build(scan_ids(&blend, &dna)?);
+
+	let row = ids.resolve_selector(&id_name)?;
+	let bare_name = row.id_name.get(2..).unwrap_or_default();
+
+	let mut options = XrefOptions::default();
+	if let Some(refs_depth) = refs_depth {
+		options.ref_scan.max_depth = refs_depth;
+	}
+	if let Some(limit) = limit {
+		options.max_results = limit;
+	}
+
+	let pointer_refs = find_inbound_refs_to_ptr(&dna, &index, &ids, row.old_ptr, &options)?;
+
+	let mut text_refs = find_string_occurrences(&blend, &dna, bare_name.as_bytes())?;
+	text_refs.retain(|hit| (partial || hit.whole_token) && !(hit.block_old == row.old_ptr && hit.field.as_deref() == Some("id.name")));
+	text_refs.truncate(options.max_results);
+
+	if json {
+		print_json(&path, &row.id_name, bare_name, &pointer_refs, &text_refs);
+		return Ok(());
+	}
+
+	println!("path: {}", path.display());
+	println!("id: {}", row.id_name);
+	println!("name: {bare_name}");
+	println!("pointer_refs: {}", pointer_refs.len());
+	for inbound in &pointer_refs {
+		println!(
+			"{}({}) -{}-> {}",
+			inbound.from_id.as_deref().unwrap_or("-"),
+			inbound.from_type,
+			inbound.field,
+			row.id_name
+		);
+	}
+
+	println!("text_refs: {}", text_refs.len());
+	println!("code\tblock\ttype\telement\tfield\twhole_token\ttext");
+	for hit in &text_refs {
+		println!(
+			"{}\t0x{:016x}\t{}\t{}\t{}\t{}\t{}",
+			render_code(hit.code),
+			hit.block_old,
+			hit.type_name.as_deref().unwrap_or("-"),
+			hit.element_index.map(|item| item.to_string()).unwrap_or_else(|| "-".to_owned()),
+			hit.field.as_deref().unwrap_or("-"),
+			hit.whole_token,
+			hit.text.escape_debug()
+		);
+	}
+
+	Ok(())
+}
+
+fn print_json(path: &std::path::Path, id_name: &str, bare_name: &str, pointer_refs: &[InboundRef], text_refs: &[StringHit]) {
+	let payload = RenameJson {
+		path: path.display().to_string(),
+		id: id_name.to_owned(),
+		name: bare_name.to_owned(),
+		pointer_refs: pointer_refs
+			.iter()
+			.map(|inbound| PointerRefJson {
+				from: ptr_hex(inbound.from),
+				from_type: inbound.from_type.to_string(),
+				from_id: inbound.from_id.as_deref().map(str::to_owned),
+				field: inbound.field.to_string(),
+			})
+			.collect(),
+		text_refs: text_refs
+			.iter()
+			.map(|hit| TextRefJson {
+				code: render_code(hit.code),
+				block: ptr_hex(hit.block_old),
+				type_name: hit.type_name.as_deref().map(str::to_owned),
+				file_offset: hit.file_offset,
+				element_index: hit.element_index,
+				field: hit.field.as_deref().map(str::to_owned),
+				whole_token: hit.whole_token,
+				text: hit.text.to_string(),
+			})
+			.collect(),
+	};
+
+	emit_json(&payload);
+}
+
+#[derive(serde::Serialize)]
+struct PointerRefJson {
+	from: String,
+	from_type: String,
+	from_id: Option<String>,
+	field: String,
+}
+
+#[derive(serde::Serialize)]
+struct TextRefJson {
+	code: String,
+	block: String,
+	#[serde(rename = "type")]
+	type_name: Option<String>,
+	file_offset: usize,
+	element_index: Option<usize>,
+	field: Option<String>,
+	whole_token: bool,
+	text: String,
+}
+
+#[derive(serde::Serialize)]
+struct RenameJson {
+	path: String,
+	id: String,
+	name: String,
+	pointer_refs: Vec<PointerRefJson>,
+	text_refs: Vec<TextRefJson>,
+}
+
+#[cfg(test)]
+mod tests;
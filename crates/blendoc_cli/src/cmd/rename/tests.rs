@@ -0,0 +1,38 @@
+use crate::cmd::test_support::{TempBlend, run_blendoc, run_blendoc_json};
+
+#[test]
+fn id_accepts_selectors_and_suggests_on_miss() {
+	let target = TempBlend::scene("rename");
+	let target_arg = target.arg();
+
+	let glob = run_blendoc_json(&["rename", target_arg, "--id", "OBCam*", "--dry-run", "--json"]);
+	let local = run_blendoc_json(&["rename", target_arg, "--id", "obcamera@", "--dry-run", "--json"]);
+	let missing = run_blendoc(&["rename", target_arg, "--id", "OBCamra", "--dry-run"]);
+
+	assert_eq!(glob["id"], "OBCamera");
+	assert_eq!(glob["pointer_refs"][0]["from_id"], "SCScene");
+	assert_eq!(local["id"], "OBCamera");
+	let stderr = String::from_utf8_lossy(&missing.stderr);
+	assert!(stderr.contains("(did you mean `OBCamera`"), "{stderr}");
+	assert_eq!(missing.status.code(), Some(2), "{stderr}");
+}
+
+#[test]
+fn dry_run_lists_partial_text_hits_only_with_partial() {
+	let target = TempBlend::scene("rename_partial");
+	let target_arg = target.arg();
+
+	let whole = run_blendoc_json(&["rename", target_arg, "--id", "OBCamera", "--dry-run", "--json"]);
+	let partial = run_blendoc_json(&["rename", target_arg, "--id", "OBCamera", "--dry-run", "--partial", "--json"]);
+
+	let hits = |json: &serde_json::Value| -> Vec<(String, bool)> {
+		json["text_refs"]
+			.as_array()
+			.expect("text refs")
+			.iter()
+			.map(|hit| (hit["text"].as_str().expect("text").to_owned(), hit["whole_token"].as_bool().expect("flag")))
+			.collect()
+	};
+	assert!(hits(&whole).iter().all(|(_, whole_token)| *whole_token), "{:?}", hits(&whole));
+	assert!(hits(&partial).contains(&("MECamera".to_owned(), false)), "{:?}", hits(&partial));
+}
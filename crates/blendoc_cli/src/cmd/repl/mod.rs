@@ -0,0 +1,312 @@
+use std::path::{Path, PathBuf};
+use std::time::Instant;
+
+use blendoc::blend::{
+	BlendError, BlendFile, ChasePolicy, DecodeOptions, Dna, FieldPath, IdIndex, PointerIndex, RefCache, RefScanOptions, Result, RouteOptions, RouteTruncation,
+	Value, XrefOptions, chase_from_ptr, find_inbound_refs_to_ptr, find_route_between_ptrs,
+};
+use rustyline::DefaultEditor;
+use rustyline::error::ReadlineError;
+
+use crate::cmd::invocation::Invocation;
+use crate::cmd::plugins::PluginArgs;
+use crate::cmd::print::{PrintCtx, PrintOptions, PtrAnnotCtx, print_value};
+use crate::cmd::util::{parse_ptr, ptr_hex, render_code, scan_ids};
+
+#[derive(clap::Args)]
+pub struct Args {
+	pub file: PathBuf,
+	#[arg(long, conflicts_with = "no_history")]
+	pub history: Option<PathBuf>,
+	#[arg(long = "no-history")]
+	pub no_history: bool,
+	#[command(flatten)]
+	pub plugins: PluginArgs,
+}
+
+const HELP: &str = "\
+commands:
+  ids [PATTERN]              list IDs, optionally filtered by a name glob
+  show <SEL> [FIELD.PATH]    decode a struct, or the value at a chased path
+  refs <SEL>                 outbound pointer references
+  xref <SEL>                 inbound references from ID roots
+  route <FROM> <TO>          shortest pointer route between two roots
+  help                       this list
+  quit | exit                leave (also Ctrl-D)
+SEL is an ID name or glob (`OBCube`, `OB*`) or a `0x` pointer; quote names with spaces.";
+
+/// Open a file once and answer interactive queries against its indexes.
+pub fn run(args: Args) -> Result<()> {
+	let Args {
+		file: path,
+		history,
+		no_history,
+		plugins,
+	} = args;
+
+	let started = Instant::now();
+	let blend = BlendFile::open(&path)?;
+	let dna = blend.dna()?;
+	let index = blend.pointer_index()?;
+	let ids = IdIndex::build(scan_ids(&blend, &dna)?);
+	let session = Session {
+		dna: &dna,
+		index: &index,
+		ids: &ids,
+		decode: DecodeOptions {
+			plugins: plugins.load()?,
+			..DecodeOptions::default()
+		},
+		ref_cache: RefCache::new(),
+		invocation: Invocation::default(),
+	};
+	println!(
+		"loaded {}: {} IDs in {} ms; type `help` for commands",
+		path.display(),
+		ids.records.len(),
+		started.elapsed().as_millis()
+	);
+
+	let history = if no_history { None } else { history.or_else(default_history_path) };
+	let mut editor = DefaultEditor::new().map_err(readline_error)?;
+	if let Some(history) = &history {
+		let _ = editor.load_history(history);
+	}
+
+	loop {
+		let line = match editor.readline("blendoc> ") {
+			Ok(line) => line,
+			Err(ReadlineError::Interrupted) => continue,
+			Err(ReadlineError::Eof) => break,
+			Err(err) => return Err(readline_error(err)),
+		};
+		let line = line.trim();
+		if line.is_empty() {
+			continue;
+		}
+		let _ = editor.add_history_entry(line);
+		crate::cmd::interrupt::reset();
+		match session.eval(line) {
+			Ok(Flow::Continue) => {}
+			Ok(Flow::Quit) => break,
+			Err(err) => println!("error: {err}"),
+		}
+	}
+
+	if let Some(history) = &history {
+		save_history(&mut editor, history);
+	}
+	crate::cmd::interrupt::reset();
+	Ok(())
+}
+
+/// Whether the loop keeps reading after a command.
+enum Flow {
+	Continue,
+	Quit,
+}
+
+/// Indexes built once per REPL run and shared by every command.
+struct Session<'a> {
+	dna: &'a Dna,
+	index: &'a PointerIndex<'a>,
+	ids: &'a IdIndex,
+	decode: DecodeOptions,
+	ref_cache: RefCache,
+	/// Pointer annotations and expansions reused by every `show`.
+	invocation: Invocation,
+}
+
+impl Session<'_> {
+	fn eval(&self, line: &str) -> Result<Flow> {
+		let words = split_words(line);
+		let Some((command, rest)) = words.split_first() else {
+			return Ok(Flow::Continue);
+		};
+		match (command.as_str(), rest) {
+			("quit" | "exit", []) => return Ok(Flow::Quit),
+			("help" | "?", []) => println!("{HELP}"),
+			("ids", []) => self.ids(None),
+			("ids", [pattern]) => self.ids(Some(pattern)),
+			("show", [root]) => self.show(root, None)?,
+			("show", [root, path]) => self.show(root, Some(path))?,
+			("refs", [root]) => self.refs(root)?,
+			("xref", [root]) => self.xref(root)?,
+			("route", [from, to]) => self.route(from, to)?,
+			_ => println!("cannot parse `{line}`; type `help` for commands"),
+		}
+		Ok(Flow::Continue)
+	}
+
+	fn ids(&self, pattern: Option<&str>) {
+		let rows = match pattern {
+			Some(pattern) => self.ids.find_by_pattern(pattern, true),
+			None => self.ids.records.iter().collect(),
+		};
+		for row in &rows {
+			println!("{}\t{}\t{}\t{}", render_code(row.code), ptr_hex(row.old_ptr), row.type_name, row.id_name);
+		}
+		println!("({} IDs)", rows.len());
+	}
+
+	fn show(&self, root: &str, path: Option<&str>) -> Result<()> {
+		let ptr = self.resolve(root)?;
+		let value = match path {
+			Some(path) => {
+				let policy = ChasePolicy {
+					decode_cache: Some(self.invocation.decode_cache()),
+					..ChasePolicy::default()
+				};
+				let result = chase_from_ptr(self.dna, self.index, ptr, &FieldPath::parse(path)?, &self.decode, &policy)?;
+				if let Some(stop) = &result.stop {
+					println!("stopped: {:?}", stop.reason);
+				}
+				result.value
+			}
+			None => Value::Struct(self.invocation.decode_cache().decode_ptr(self.dna, self.index, ptr, &self.decode)?.1),
+		};
+		let ctx = PrintCtx::new(
+			Some(PtrAnnotCtx {
+				dna: self.dna,
+				index: self.index,
+				ids: self.ids,
+			}),
+			true,
+			Some(&self.decode),
+			0,
+			&self.invocation,
+		);
+		print_value(&value, 0, 0, &PrintOptions::default(), Some(&ctx), 0);
+		Ok(())
+	}
+
+	fn refs(&self, root: &str) -> Result<()> {
+		let ptr = self.resolve(root)?;
+		let refs = self.ref_cache.scan(self.dna, self.index, self.ids, ptr, &RefScanOptions::default())?;
+		for record in refs.iter() {
+			match &record.resolved {
+				Some(target) => println!(
+					"{}\t{}\t{}\t{}",
+					record.field,
+					ptr_hex(target.canonical),
+					target.type_name,
+					target.id_name.as_deref().unwrap_or("-")
+				),
+				None => println!("{}\t{}\t-\t-", record.field, ptr_hex(record.ptr)),
+			}
+		}
+		println!("({} refs)", refs.len());
+		Ok(())
+	}
+
+	fn xref(&self, root: &str) -> Result<()> {
+		let ptr = self.resolve(root)?;
+		let options = XrefOptions {
+			ref_cache: Some(self.ref_cache.clone()),
+			cancel: crate::cmd::interrupt::token(),
+			..XrefOptions::default()
+		};
+		let inbound = find_inbound_refs_to_ptr(self.dna, self.index, self.ids, ptr, &options)?;
+		for item in &inbound {
+			println!(
+				"{}\t{}\t{}\t{}",
+				ptr_hex(item.from),
+				item.from_type,
+				item.from_id.as_deref().unwrap_or("-"),
+				item.field
+			);
+		}
+		let cancelled = options.cancel.as_ref().is_some_and(|token| token.is_cancelled());
+		println!("({} inbound{})", inbound.len(), if cancelled { ", cancelled" } else { "" });
+		Ok(())
+	}
+
+	fn route(&self, from: &str, to: &str) -> Result<()> {
+		let from = self.resolve(from)?;
+		let to = self.resolve(to)?;
+		let options = RouteOptions {
+			ref_cache: Some(self.ref_cache.clone()),
+			cancel: crate::cmd::interrupt::token(),
+			..RouteOptions::default()
+		};
+		let result = find_route_between_ptrs(self.dna, self.index, self.ids, from, to, &options)?;
+		let Some(edges) = result.path else {
+			let outcome = if matches!(result.truncated, Some(RouteTruncation::Cancelled)) {
+				"cancelled"
+			} else {
+				"no route"
+			};
+			println!("{outcome} ({} nodes visited)", result.visited_nodes);
+			return Ok(());
+		};
+		for edge in &edges {
+			println!("{} -{}-> {}", self.label(edge.from), edge.field, self.label(edge.to));
+		}
+		println!("({} hops)", edges.len());
+		Ok(())
+	}
+
+	/// `0x` pointer or ID selector to a pointer.
+	fn resolve(&self, root: &str) -> Result<u64> {
+		if root.starts_with("0x") || root.starts_with("0X") {
+			return parse_ptr(root);
+		}
+		Ok(self.ids.resolve_selector(root)?.old_ptr)
+	}
+
+	fn label(&self, ptr: u64) -> String {
+		match self.ids.get_by_ptr(ptr) {
+			Some(row) => row.id_name.to_string(),
+			None => ptr_hex(ptr),
+		}
+	}
+}
+
+/// Split on whitespace, keeping `"double quoted"` words together.
+fn split_words(line: &str) -> Vec<String> {
+	let mut words = Vec::new();
+	let mut chars = line.chars().peekable();
+	while let Some(&ch) = chars.peek() {
+		if ch.is_whitespace() {
+			chars.next();
+			continue;
+		}
+		let mut word = String::new();
+		if ch == '"' {
+			chars.next();
+			for ch in chars.by_ref() {
+				if ch == '"' {
+					break;
+				}
+				word.push(ch);
+			}
+		} else {
+			while let Some(&ch) = chars.peek() {
+				if ch.is_whitespace() {
+					break;
+				}
+				word.push(ch);
+				chars.next();
+			}
+		}
+		words.push(word);
+	}
+	words
+}
+
+fn readline_error(err: ReadlineError) -> BlendError {
+	BlendError::Io(std::io::Error::other(err.to_string()))
+}
+
+fn default_history_path() -> Option<PathBuf> {
+	std::env::var_os("HOME").map(|home| Path::new(&home).join(".blendoc_history"))
+}
+
+fn save_history(editor: &mut DefaultEditor, path: &Path) {
+	if let Err(err) = editor.save_history(path) {
+		eprintln!("warning: could not save history to {}: {err}", path.display());
+	}
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,27 @@
+use blendoc_testkit::builder::SCENE_ID_NAMES;
+
+use crate::cmd::test_support::{TempBlend, run_blendoc_with_stdin};
+
+#[test]
+fn repl_answers_queries_against_one_load() {
+	let target = TempBlend::scene("repl");
+	let target_arg = target.arg();
+
+	let script = format!(
+		"ids\nshow {camera} id.name\nrefs {scene}\nxref {camera}\nroute {scene} {world}\nbogus words\nshow OBMissing\nquit\nids\n",
+		scene = SCENE_ID_NAMES[0],
+		camera = SCENE_ID_NAMES[1],
+		world = SCENE_ID_NAMES[3],
+	);
+	let out = run_blendoc_with_stdin(&["repl", target_arg, "--no-history"], script.as_bytes());
+
+	assert!(out.status.success(), "repl failed: {}", String::from_utf8_lossy(&out.stderr));
+	let text = String::from_utf8_lossy(&out.stdout);
+	assert!(text.contains("(4 IDs)"), "{text}");
+	assert!(text.contains(&format!("\"{}\"", SCENE_ID_NAMES[1])), "{text}");
+	assert!(text.contains(&format!("camera\t0x0000000000002000\tObject\t{}", SCENE_ID_NAMES[1])), "{text}");
+	assert!(text.contains(&format!("{} -world-> {}", SCENE_ID_NAMES[0], SCENE_ID_NAMES[3])), "{text}");
+	assert!(text.contains("cannot parse `bogus words`"), "{text}");
+	assert!(text.contains("error: ID record not found: OBMissing"), "{text}");
+	assert_eq!(text.matches("IDs)").count(), 1, "commands after quit must not run: {text}");
+}
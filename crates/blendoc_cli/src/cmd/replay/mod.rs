@@ -0,0 +1,303 @@
+use std::ffi::OsString;
+use std::fs::{File, OpenOptions};
+use std::io::{self, BufRead, BufReader, Read, Write};
+use std::path::{Path, PathBuf};
+use std::process::{Command, ExitStatus, Stdio};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use blendoc::blend::{BlendError, Result};
+
+use crate::cmd::output::OutputArgs;
+
+#[derive(clap::Args)]
+pub struct Args {
+	pub session: PathBuf,
+	#[arg(long)]
+	pub file: Option<PathBuf>,
+	#[arg(long)]
+	pub json: bool,
+	#[command(flatten)]
+	pub output: OutputArgs,
+}
+
+/// One `--record` log line.
+#[derive(serde::Serialize, serde::Deserialize)]
+struct SessionEntry {
+	/// Command-line arguments after the program name, without `--record`.
+	args: Vec<String>,
+	/// Working directory the command ran in.
+	cwd: String,
+	/// Existing files named on the command line, in argument order.
+	files: Vec<FileDigest>,
+	exit_code: i32,
+	stdout_bytes: u64,
+	stdout_digest: String,
+	blendoc_version: String,
+	recorded_at: u64,
+}
+
+#[derive(serde::Serialize, serde::Deserialize)]
+pub(crate) struct FileDigest {
+	path: String,
+	bytes: u64,
+	pub(crate) digest: String,
+}
+
+/// Run the current command line again as a child without `--record`, tee its
+/// stdout, and append a [`SessionEntry`] to `session`.
+///
+/// Returns the child's exit code so `main` can exit with it.
+pub(crate) fn record(session: &Path) -> Result<i32> {
+	let args = strip_record_flag(std::env::args_os().skip(1));
+	let cwd = std::env::current_dir()?;
+	let mut child = Command::new(std::env::current_exe()?)
+		.args(&args)
+		.stdout(Stdio::piped())
+		.stderr(Stdio::inherit())
+		.spawn()?;
+
+	let mut stdout = crate::cmd::quiet::stdout();
+	let (stdout_bytes, stdout_digest) = digest_reader(child.stdout.take().expect("stdout is piped"), Some(&mut stdout))?;
+	stdout.flush()?;
+	let exit_code = exit_code(child.wait()?);
+
+	let args: Vec<String> = args.iter().map(|arg| arg.to_string_lossy().into_owned()).collect();
+	let entry = SessionEntry {
+		files: input_files(&cwd, &args)?,
+		args,
+		cwd: cwd.display().to_string(),
+		exit_code,
+		stdout_bytes,
+		stdout_digest,
+		blendoc_version: env!("CARGO_PKG_VERSION").to_owned(),
+		recorded_at: SystemTime::now().duration_since(UNIX_EPOCH).map_or(0, |since| since.as_secs()),
+	};
+	let mut log = OpenOptions::new().create(true).append(true).open(session)?;
+	serde_json::to_writer(&mut log, &entry).map_err(io::Error::other)?;
+	writeln!(log)?;
+	Ok(exit_code)
+}
+
+/// Re-run every recorded command and report result drift.
+///
+/// Exits 1 when any command's stdout digest or exit code differs from the
+/// recording; changed input files are reported but are not drift on their own.
+pub fn run(args: Args) -> Result<()> {
+	let Args { session, file, json, output } = args;
+
+	let entries = read_session(&session)?;
+	let exe = std::env::current_exe()?;
+	let mut rows = Vec::with_capacity(entries.len());
+	for (idx, entry) in entries.iter().enumerate() {
+		rows.push(replay_entry(&exe, idx + 1, entry, file.as_deref())?);
+	}
+	let drifted = rows.iter().filter(|row| row.drift).count();
+
+	let mut out = output.open(json)?;
+	if !out.is_text() {
+		let payload = ReplayJson {
+			session: session.display().to_string(),
+			entries: rows.len(),
+			drifted,
+			results: &rows,
+		};
+		out.structured(&payload, &rows)?;
+	} else {
+		writeln!(out, "session: {}", session.display())?;
+		writeln!(out, "entries: {}", rows.len())?;
+		writeln!(out, "drifted: {drifted}")?;
+		for row in &rows {
+			writeln!(
+				out,
+				"  #{} {}\t{}\tinput={}\texit={}->{}\tstdout={}",
+				row.entry,
+				if row.drift { "DRIFT" } else { "ok" },
+				row.command,
+				row.input,
+				row.recorded_exit,
+				row.exit_code,
+				if row.stdout_digest == row.recorded_digest { "same" } else { "changed" }
+			)?;
+		}
+	}
+	out.finish()?;
+
+	if drifted > 0 {
+		eprintln!("replay drift: {drifted} of {} commands changed", rows.len());
+		std::process::exit(1);
+	}
+	Ok(())
+}
+
+fn replay_entry(exe: &Path, entry_no: usize, entry: &SessionEntry, file: Option<&Path>) -> Result<ReplayRow> {
+	let mut args = entry.args.clone();
+	let primary = entry.files.first();
+	let substitution = file.zip(primary).map(|(file, primary)| (file.display().to_string(), primary.path.as_str()));
+	if let Some((replacement, original)) = &substitution {
+		for arg in args.iter_mut().filter(|arg| *arg == original) {
+			arg.clone_from(replacement);
+		}
+	}
+
+	let cwd = Path::new(&entry.cwd);
+	let mut command = Command::new(exe);
+	command.args(&args).stdout(Stdio::piped()).stderr(Stdio::null());
+	if cwd.is_dir() {
+		command.current_dir(cwd);
+	}
+	let mut child = command.spawn()?;
+	let mut stdout = Vec::new();
+	child.stdout.take().expect("stdout is piped").read_to_end(&mut stdout)?;
+	let exit_code = exit_code(child.wait()?);
+	// Echoed input paths would otherwise make every `--file` replay drift.
+	if let Some((replacement, original)) = &substitution {
+		stdout = replace_bytes(&stdout, replacement.as_bytes(), original.as_bytes());
+	}
+	let (_, stdout_digest) = digest_reader(stdout.as_slice(), None)?;
+
+	let base = if cwd.is_dir() { cwd.to_path_buf() } else { std::env::current_dir()? };
+	let input = match primary {
+		None => "-",
+		Some(recorded) => {
+			let path = file.map_or_else(|| base.join(&recorded.path), Path::to_path_buf);
+			match File::open(&path) {
+				Ok(handle) => {
+					if digest_reader(handle, None)?.1 == recorded.digest {
+						"same"
+					} else {
+						"changed"
+					}
+				}
+				Err(_) => "missing",
+			}
+		}
+	};
+
+	Ok(ReplayRow {
+		entry: entry_no,
+		command: args.join(" "),
+		input,
+		drift: exit_code != entry.exit_code || stdout_digest != entry.stdout_digest,
+		recorded_exit: entry.exit_code,
+		exit_code,
+		recorded_digest: entry.stdout_digest.clone(),
+		stdout_digest,
+	})
+}
+
+fn read_session(path: &Path) -> Result<Vec<SessionEntry>> {
+	let reader = BufReader::new(File::open(path)?);
+	let mut entries = Vec::new();
+	for (line_no, line) in reader.lines().enumerate() {
+		let line = line?;
+		if line.trim().is_empty() {
+			continue;
+		}
+		let entry = serde_json::from_str(&line)
+			.map_err(|err| BlendError::Io(io::Error::other(format!("{}:{}: invalid session entry: {err}", path.display(), line_no + 1))))?;
+		entries.push(entry);
+	}
+	Ok(entries)
+}
+
+/// Drop `--record <PATH>` / `--record=<PATH>` from a raw argument list.
+fn strip_record_flag(args: impl IntoIterator<Item = OsString>) -> Vec<OsString> {
+	let mut out = Vec::new();
+	let mut args = args.into_iter();
+	while let Some(arg) = args.next() {
+		if arg == "--record" {
+			args.next();
+		} else if !arg.to_string_lossy().starts_with("--record=") {
+			out.push(arg);
+		}
+	}
+	out
+}
+
+/// Digest every argument that names an existing file, resolved against `cwd`.
+pub(crate) fn input_files(cwd: &Path, args: &[String]) -> Result<Vec<FileDigest>> {
+	let mut out = Vec::new();
+	for arg in args.iter().filter(|arg| !arg.starts_with('-')) {
+		let path = cwd.join(arg);
+		if !path.is_file() || out.iter().any(|file: &FileDigest| file.path == *arg) {
+			continue;
+		}
+		let (bytes, digest) = digest_reader(File::open(&path)?, None)?;
+		out.push(FileDigest {
+			path: arg.clone(),
+			bytes,
+			digest,
+		});
+	}
+	Ok(out)
+}
+
+/// FNV-1a 64 over a stream, optionally copying it to `tee`.
+///
+/// A change detector for replay, not a cryptographic hash.
+pub(crate) fn digest_reader(mut reader: impl Read, mut tee: Option<&mut dyn Write>) -> io::Result<(u64, String)> {
+	let mut hash = 0xcbf2_9ce4_8422_2325_u64;
+	let mut total = 0_u64;
+	let mut buf = [0_u8; 64 * 1024];
+	loop {
+		let read = reader.read(&mut buf)?;
+		if read == 0 {
+			break;
+		}
+		for byte in &buf[..read] {
+			hash = (hash ^ u64::from(*byte)).wrapping_mul(0x0000_0100_0000_01b3);
+		}
+		if let Some(tee) = tee.as_deref_mut() {
+			tee.write_all(&buf[..read])?;
+		}
+		total += read as u64;
+	}
+	Ok((total, format!("fnv1a64:{hash:016x}")))
+}
+
+fn replace_bytes(haystack: &[u8], from: &[u8], to: &[u8]) -> Vec<u8> {
+	if from.is_empty() {
+		return haystack.to_vec();
+	}
+	let mut out = Vec::with_capacity(haystack.len());
+	let mut rest = haystack;
+	while !rest.is_empty() {
+		if rest.starts_with(from) {
+			out.extend_from_slice(to);
+			rest = &rest[from.len()..];
+		} else {
+			out.push(rest[0]);
+			rest = &rest[1..];
+		}
+	}
+	out
+}
+
+pub(crate) fn exit_code(status: ExitStatus) -> i32 {
+	status.code().unwrap_or(-1)
+}
+
+#[derive(serde::Serialize)]
+struct ReplayJson<'a> {
+	session: String,
+	entries: usize,
+	drifted: usize,
+	results: &'a [ReplayRow],
+}
+
+/// Replay outcome for one session entry; also the `--format csv` row.
+#[derive(serde::Serialize)]
+struct ReplayRow {
+	entry: usize,
+	command: String,
+	/// `same`, `changed`, or `missing` for the primary input file; `-` without one.
+	input: &'static str,
+	drift: bool,
+	recorded_exit: i32,
+	exit_code: i32,
+	recorded_digest: String,
+	stdout_digest: String,
+}
+
+#[cfg(test)]
+mod tests;
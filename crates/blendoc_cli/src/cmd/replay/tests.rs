@@ -0,0 +1,45 @@
+use blendoc_testkit::builder::{HeaderKind, scene_file};
+
+use crate::cmd::test_support::run_blendoc;
+
+#[test]
+fn recorded_session_replays_clean_and_reports_drift_on_new_file() {
+	let dir = std::env::temp_dir().join(format!("blendoc_replay_{}", std::process::id()));
+	std::fs::create_dir_all(&dir).expect("temp dir created");
+	let little = dir.join("little.blend");
+	let big = dir.join("big.blend");
+	let session = dir.join("session.jsonl");
+	std::fs::write(&little, scene_file(HeaderKind::Legacy8, false)).expect("little-endian file written");
+	std::fs::write(&big, scene_file(HeaderKind::Legacy8, true)).expect("big-endian file written");
+	let little_arg = little.to_string_lossy().into_owned();
+	let big_arg = big.to_string_lossy().into_owned();
+	let session_arg = session.to_string_lossy().into_owned();
+
+	let info = run_blendoc(&["info", &little_arg, "--record", &session_arg]);
+	let ids = run_blendoc(&["--record", &session_arg, "ids", &little_arg, "--json"]);
+	let same = run_blendoc(&["replay", &session_arg, "--json"]);
+	let drift = run_blendoc(&["replay", &session_arg, "--file", &big_arg]);
+	let log = std::fs::read_to_string(&session).expect("session log readable");
+	std::fs::remove_dir_all(&dir).expect("temp dir removed");
+
+	assert!(info.status.success() && ids.status.success());
+	assert!(
+		String::from_utf8_lossy(&info.stdout).contains("endianness: little"),
+		"recorded command output is passed through"
+	);
+	assert_eq!(log.lines().count(), 2);
+	let first: serde_json::Value = serde_json::from_str(log.lines().next().expect("first entry")).expect("entry is json");
+	assert_eq!(first["args"][0], "info");
+	assert!(first["args"].as_array().is_some_and(|args| !args.iter().any(|arg| arg == "--record")));
+	assert_eq!(first["files"][0]["path"], little_arg.as_str());
+
+	assert!(same.status.success(), "replay failed: {}", String::from_utf8_lossy(&same.stderr));
+	let json: serde_json::Value = serde_json::from_slice(&same.stdout).expect("valid json");
+	assert_eq!((json["entries"].as_u64(), json["drifted"].as_u64()), (Some(2), Some(0)));
+	assert_eq!(json["results"][0]["input"], "same");
+
+	assert!(!drift.status.success());
+	let text = String::from_utf8_lossy(&drift.stdout);
+	assert!(text.contains("#1 DRIFT") && text.contains("input=changed"), "unexpected replay output: {text}");
+	assert!(text.contains("#2 ok"), "ids output does not depend on endianness: {text}");
+}
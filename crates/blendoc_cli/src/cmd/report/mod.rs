@@ -0,0 +1,294 @@
+use std::collections::BTreeMap;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+
+use blendoc::blend::{BlendFile, IdIndex, Result, blend_files_below, scan_library_records, scan_size_stats};
+
+use crate::cmd::output::OutputArgs;
+use crate::cmd::util::{render_code, scan_ids};
+
+#[derive(clap::Args)]
+pub struct Args {
+	pub dir: PathBuf,
+	#[arg(long, short = 'r')]
+	pub recursive: bool,
+	#[arg(long = "top-types", default_value_t = 5)]
+	pub top_types: usize,
+	#[arg(long)]
+	pub json: bool,
+	#[command(flatten)]
+	pub output: OutputArgs,
+}
+
+/// Summarize every blend in a directory and aggregate the totals.
+///
+/// Each file contributes its version, block counts per code, linked
+/// libraries, and largest SDNA types; unreadable files are listed with
+/// their error and left out of the totals.
+pub fn run(args: Args) -> Result<()> {
+	let Args {
+		dir,
+		recursive,
+		top_types,
+		json,
+		output,
+	} = args;
+
+	let paths = blend_files_below(&dir, recursive)?;
+	let files: Vec<FileReport> = paths
+		.iter()
+		.map(|path| {
+			let relative = path.strip_prefix(&dir).unwrap_or(path).display().to_string();
+			summarize(path, &relative, top_types).unwrap_or_else(|err| FileReport::unreadable(relative, err.to_string()))
+		})
+		.collect();
+	let totals = Totals::collect(&files, top_types);
+
+	let mut out = output.open(json)?;
+	if !out.is_text() {
+		let payload = ReportJson {
+			root: dir.display().to_string(),
+			recursive,
+			totals: &totals,
+			files: &files,
+		};
+		let rows: Vec<FileRow> = files.iter().map(FileRow::from).collect();
+		out.structured(&payload, &rows)?;
+		return out.finish();
+	}
+
+	writeln!(out, "root: {}", dir.display())?;
+	writeln!(out, "files: {}", totals.files)?;
+	writeln!(out, "unreadable: {}", totals.unreadable)?;
+	writeln!(out, "blocks: {}", totals.blocks)?;
+	writeln!(out, "payload_bytes: {}", totals.payload_bytes)?;
+	writeln!(out, "ids: {}", totals.ids)?;
+	writeln!(out, "versions:")?;
+	for (version, count) in &totals.versions {
+		writeln!(out, "  {version}: {count}")?;
+	}
+	writeln!(out, "codes:")?;
+	for (code, count) in &totals.codes {
+		writeln!(out, "  {code}: {count}")?;
+	}
+	writeln!(out, "libraries: {}", totals.libraries.len())?;
+	for (library, count) in &totals.libraries {
+		writeln!(out, "  {library} files={count}")?;
+	}
+	writeln!(out, "top_types:")?;
+	for item in &totals.top_types {
+		writeln!(out, "  {} {}", item.payload_bytes, item.type_name)?;
+	}
+
+	for file in &files {
+		writeln!(out, "\nfile: {}", file.path)?;
+		if let Some(error) = &file.error {
+			writeln!(out, "  error: {error}")?;
+			continue;
+		}
+		writeln!(
+			out,
+			"  version={} pointer_size={} endianness={} compression={}",
+			file.version.unwrap_or_default(),
+			file.pointer_size.unwrap_or_default(),
+			file.endianness.unwrap_or_default(),
+			file.compression.as_deref().unwrap_or_default()
+		)?;
+		writeln!(out, "  blocks={} payload_bytes={} ids={}", file.blocks, file.payload_bytes, file.ids)?;
+		if !file.libraries.is_empty() {
+			writeln!(out, "  libraries: {}", file.libraries.join(", "))?;
+		}
+		for item in &file.top_types {
+			writeln!(out, "  {} {}", item.payload_bytes, item.type_name)?;
+		}
+	}
+	out.finish()
+}
+
+/// Sorted `.blend` files directly in `dir`, or below it with `recursive`,
+/// skipping hidden directories.
+fn summarize(path: &Path, relative: &str, top_types: usize) -> Result<FileReport> {
+	let blend = BlendFile::open(path)?;
+	let dna = blend.dna()?;
+	let ids = IdIndex::build(scan_ids(&blend, &dna)?);
+	let stats = scan_size_stats(&blend, &dna, &ids)?;
+	let libraries = scan_library_records(&blend, &dna)?;
+
+	let mut codes = BTreeMap::new();
+	for block in blend.blocks() {
+		*codes.entry(render_code(block?.head.code)).or_default() += 1;
+	}
+
+	Ok(FileReport {
+		path: relative.to_owned(),
+		version: Some(blend.header.version),
+		pointer_size: Some(blend.header.pointer_size),
+		endianness: Some(blend.header.endianness.as_str()),
+		compression: Some(blend.compression.as_str().to_owned()),
+		blocks: stats.block_count,
+		payload_bytes: stats.payload_bytes,
+		ids: ids.records.len(),
+		codes,
+		libraries: libraries.iter().map(|library| library.library_path.to_string()).collect(),
+		top_types: stats
+			.types
+			.iter()
+			.take(top_types)
+			.map(|item| TypeBytes {
+				type_name: item.type_name.to_string(),
+				payload_bytes: item.payload_bytes,
+			})
+			.collect(),
+		error: None,
+	})
+}
+
+#[derive(serde::Serialize)]
+struct ReportJson<'a> {
+	root: String,
+	recursive: bool,
+	totals: &'a Totals,
+	files: &'a [FileReport],
+}
+
+/// Sums over every readable file; `libraries` counts the files linking each declared path.
+#[derive(serde::Serialize)]
+struct Totals {
+	files: usize,
+	unreadable: usize,
+	blocks: usize,
+	payload_bytes: u64,
+	ids: usize,
+	versions: BTreeMap<u16, usize>,
+	codes: BTreeMap<String, usize>,
+	libraries: BTreeMap<String, usize>,
+	top_types: Vec<TypeBytes>,
+}
+
+impl Totals {
+	fn collect(files: &[FileReport], top_types: usize) -> Self {
+		let mut totals = Self {
+			files: files.len(),
+			unreadable: 0,
+			blocks: 0,
+			payload_bytes: 0,
+			ids: 0,
+			versions: BTreeMap::new(),
+			codes: BTreeMap::new(),
+			libraries: BTreeMap::new(),
+			top_types: Vec::new(),
+		};
+		let mut type_bytes: BTreeMap<&str, u64> = BTreeMap::new();
+		for file in files {
+			let Some(version) = file.version else {
+				totals.unreadable += 1;
+				continue;
+			};
+			totals.blocks += file.blocks;
+			totals.payload_bytes += file.payload_bytes;
+			totals.ids += file.ids;
+			*totals.versions.entry(version).or_default() += 1;
+			for (code, count) in &file.codes {
+				*totals.codes.entry(code.clone()).or_default() += count;
+			}
+			for library in &file.libraries {
+				*totals.libraries.entry(library.clone()).or_default() += 1;
+			}
+			for item in &file.top_types {
+				*type_bytes.entry(&item.type_name).or_default() += item.payload_bytes;
+			}
+		}
+		let mut type_bytes: Vec<(&str, u64)> = type_bytes.into_iter().collect();
+		type_bytes.sort_by(|left, right| right.1.cmp(&left.1).then(left.0.cmp(right.0)));
+		totals.top_types = type_bytes
+			.into_iter()
+			.take(top_types)
+			.map(|(type_name, payload_bytes)| TypeBytes {
+				type_name: type_name.to_owned(),
+				payload_bytes,
+			})
+			.collect();
+		totals
+	}
+}
+
+/// One scanned file; `error` is set and the other fields empty when it could not be read.
+#[derive(serde::Serialize)]
+struct FileReport {
+	path: String,
+	version: Option<u16>,
+	pointer_size: Option<usize>,
+	endianness: Option<&'static str>,
+	compression: Option<String>,
+	blocks: usize,
+	payload_bytes: u64,
+	ids: usize,
+	codes: BTreeMap<String, usize>,
+	libraries: Vec<String>,
+	top_types: Vec<TypeBytes>,
+	#[serde(skip_serializing_if = "Option::is_none")]
+	error: Option<String>,
+}
+
+impl FileReport {
+	fn unreadable(path: String, error: String) -> Self {
+		Self {
+			path,
+			version: None,
+			pointer_size: None,
+			endianness: None,
+			compression: None,
+			blocks: 0,
+			payload_bytes: 0,
+			ids: 0,
+			codes: BTreeMap::new(),
+			libraries: Vec::new(),
+			top_types: Vec::new(),
+			error: Some(error),
+		}
+	}
+}
+
+#[derive(serde::Serialize)]
+struct TypeBytes {
+	#[serde(rename = "type")]
+	type_name: String,
+	payload_bytes: u64,
+}
+
+/// One file; the `--format csv` row.
+#[derive(serde::Serialize)]
+struct FileRow {
+	path: String,
+	version: Option<u16>,
+	pointer_size: Option<usize>,
+	endianness: Option<&'static str>,
+	compression: Option<String>,
+	blocks: usize,
+	payload_bytes: u64,
+	ids: usize,
+	libraries: usize,
+	largest_type: Option<String>,
+	error: Option<String>,
+}
+
+impl From<&FileReport> for FileRow {
+	fn from(file: &FileReport) -> Self {
+		Self {
+			path: file.path.clone(),
+			version: file.version,
+			pointer_size: file.pointer_size,
+			endianness: file.endianness,
+			compression: file.compression.clone(),
+			blocks: file.blocks,
+			payload_bytes: file.payload_bytes,
+			ids: file.ids,
+			libraries: file.libraries.len(),
+			largest_type: file.top_types.first().map(|item| item.type_name.clone()),
+			error: file.error.clone(),
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests;
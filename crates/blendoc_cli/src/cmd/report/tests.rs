@@ -0,0 +1,53 @@
+use blendoc_testkit::builder::{HeaderKind, scene_file};
+
+use crate::cmd::test_support::{run_blendoc, run_blendoc_json};
+
+#[test]
+fn report_aggregates_blends_in_a_directory() {
+	let dir = std::env::temp_dir().join(format!("blendoc_cli_report_{}", std::process::id()));
+	let _ = std::fs::remove_dir_all(&dir);
+	std::fs::create_dir_all(dir.join("shots")).expect("dir created");
+	std::fs::create_dir_all(dir.join(".hidden")).expect("dir created");
+	std::fs::write(dir.join("a.blend"), scene_file(HeaderKind::Legacy8, false)).expect("a written");
+	std::fs::write(dir.join("shots").join("b.blend"), scene_file(HeaderKind::Legacy4, true)).expect("b written");
+	std::fs::write(dir.join(".hidden").join("c.blend"), scene_file(HeaderKind::Legacy8, false)).expect("c written");
+	std::fs::write(dir.join("broken.blend"), b"not a blend").expect("broken written");
+	let dir_arg = dir.to_string_lossy().into_owned();
+
+	let flat = run_blendoc_json(&["report", &dir_arg, "--json"]);
+	let json = run_blendoc_json(&["report", &dir_arg, "--recursive", "--top-types", "2", "--json"]);
+	let csv = run_blendoc(&["report", &dir_arg, "-r", "--format", "csv"]);
+	let text = run_blendoc(&["report", &dir_arg, "-r"]);
+	let _ = std::fs::remove_dir_all(&dir);
+
+	assert_eq!(flat["totals"]["files"], 2);
+	let paths: Vec<&str> = json["files"]
+		.as_array()
+		.expect("files")
+		.iter()
+		.filter_map(|file| file["path"].as_str())
+		.collect();
+	assert_eq!(paths, ["a.blend", "broken.blend", "shots/b.blend"]);
+
+	let totals = &json["totals"];
+	assert_eq!(totals["files"], 3);
+	assert_eq!(totals["unreadable"], 1);
+	assert_eq!(totals["ids"], 8);
+	assert_eq!(totals["codes"]["OB"], 2);
+	assert_eq!(totals["codes"]["ENDB"], 2);
+	assert_eq!(totals["top_types"].as_array().map(Vec::len), Some(2));
+	assert_eq!(json["files"][0]["pointer_size"], 8);
+	assert_eq!(json["files"][2]["pointer_size"], 4);
+	assert_eq!(json["files"][2]["endianness"], "big");
+	assert!(json["files"][1]["error"].is_string());
+	assert!(json["files"][1]["version"].is_null());
+
+	assert!(csv.status.success(), "report csv failed: {}", String::from_utf8_lossy(&csv.stderr));
+	let csv = String::from_utf8_lossy(&csv.stdout);
+	assert_eq!(csv.lines().count(), 4, "{csv}");
+	assert!(csv.starts_with("path,version,pointer_size,endianness,compression,blocks,"), "{csv}");
+
+	let text = String::from_utf8_lossy(&text.stdout);
+	assert!(text.contains("files: 3\nunreadable: 1\n"), "{text}");
+	assert!(text.contains("\nfile: broken.blend\n  error: "), "{text}");
+}
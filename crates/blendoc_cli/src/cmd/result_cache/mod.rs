@@ -0,0 +1,153 @@
+use std::ffi::OsString;
+use std::fs;
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+use blendoc::blend::{BlendError, Result};
+
+use crate::cmd::replay::{FileDigest, digest_reader, exit_code, input_files};
+
+/// Commands whose results depend on more than their arguments and input files.
+const UNCACHEABLE_COMMANDS: &[&str] = &["doctor", "export-sqlite", "repl", "replay", "serve"];
+/// Flags that send results outside stdout or read repository state.
+const UNCACHEABLE_FLAGS: &[&str] = &["--output", "-o", "--out", "--write", "--git"];
+
+/// One stored `--cached` result.
+#[derive(serde::Serialize, serde::Deserialize)]
+struct CacheEntry {
+	/// Command-line arguments after the program name, without cache flags.
+	args: Vec<String>,
+	/// Existing files named on the command line, in argument order.
+	files: Vec<FileDigest>,
+	blendoc_version: String,
+	stdout: String,
+}
+
+/// Answer the current command line from the result cache, or run it as a
+/// child without cache flags and store its stdout on success.
+///
+/// Entries are keyed by the working directory, the arguments, the content
+/// digest of every file they name, and the blendoc version, so editing an
+/// input file or upgrading misses the cache. Returns the exit code for `main`.
+pub(crate) fn run_cached(cache_dir: Option<&Path>) -> Result<i32> {
+	let args: Vec<String> = strip_cache_flags(std::env::args_os().skip(1))
+		.iter()
+		.map(|arg| arg.to_string_lossy().into_owned())
+		.collect();
+	check_cacheable(&args)?;
+
+	let cwd = std::env::current_dir()?;
+	let dir = match cache_dir {
+		Some(dir) => dir.to_path_buf(),
+		None => default_cache_dir().ok_or_else(|| io::Error::other("--cached needs --cache-dir when neither XDG_CACHE_HOME nor HOME is set"))?,
+	};
+	let files = input_files(&cwd, &args)?;
+	let entry_path = dir.join(format!("{}.json", cache_key(&cwd, &args, &files)?));
+
+	if let Some(entry) = load_entry(&entry_path).filter(|entry| entry.args == args && entry.blendoc_version == env!("CARGO_PKG_VERSION")) {
+		// The hit is reported on stderr so stdout stays byte-identical to a fresh run.
+		let mut stdout = crate::cmd::quiet::stdout();
+		stdout.write_all(entry.stdout.as_bytes())?;
+		stdout.flush()?;
+		eprintln!("cached: true");
+		return Ok(0);
+	}
+
+	let output = Command::new(std::env::current_exe()?)
+		.args(&args)
+		.stderr(std::process::Stdio::inherit())
+		.output()?;
+	let mut stdout = crate::cmd::quiet::stdout();
+	stdout.write_all(&output.stdout)?;
+	stdout.flush()?;
+
+	let code = exit_code(output.status);
+	// Failed runs and non-UTF-8 output are passed through but never stored.
+	if code == 0
+		&& let Ok(text) = String::from_utf8(output.stdout)
+	{
+		let entry = CacheEntry {
+			args,
+			files,
+			blendoc_version: env!("CARGO_PKG_VERSION").to_owned(),
+			stdout: text,
+		};
+		store_entry(&dir, &entry_path, &entry)?;
+	}
+	Ok(code)
+}
+
+/// Remove `--cached`, `--no-cached`, and `--cache-dir [DIR]` from the arguments.
+fn strip_cache_flags(args: impl IntoIterator<Item = OsString>) -> Vec<OsString> {
+	let mut out = Vec::new();
+	let mut args = args.into_iter();
+	while let Some(arg) = args.next() {
+		if arg == "--cache-dir" {
+			args.next();
+		} else if arg != "--cached" && arg != "--no-cached" && !arg.to_string_lossy().starts_with("--cache-dir=") {
+			out.push(arg);
+		}
+	}
+	out
+}
+
+fn check_cacheable(args: &[String]) -> Result<()> {
+	let command = args.iter().find(|arg| !arg.starts_with('-'));
+	if let Some(command) = command.filter(|command| UNCACHEABLE_COMMANDS.contains(&command.as_str())) {
+		return Err(BlendError::Io(io::Error::other(format!("--cached cannot be used with `{command}`"))));
+	}
+	let flag = args
+		.iter()
+		.find(|arg| UNCACHEABLE_FLAGS.iter().any(|flag| *arg == flag || arg.starts_with(&format!("{flag}="))));
+	if let Some(flag) = flag {
+		return Err(BlendError::Io(io::Error::other(format!(
+			"--cached caches stdout results and cannot be used with `{flag}`"
+		))));
+	}
+	Ok(())
+}
+
+/// `$XDG_CACHE_HOME/blendoc/results`, falling back to `~/.cache/blendoc/results`.
+pub(crate) fn default_cache_dir() -> Option<PathBuf> {
+	let base = std::env::var_os("XDG_CACHE_HOME")
+		.filter(|dir| !dir.is_empty())
+		.map(PathBuf::from)
+		.or_else(|| std::env::var_os("HOME").map(|home| Path::new(&home).join(".cache")))?;
+	Some(base.join("blendoc").join("results"))
+}
+
+/// Hex digest over the version, working directory, arguments, and input digests.
+fn cache_key(cwd: &Path, args: &[String], files: &[FileDigest]) -> Result<String> {
+	let mut material = Vec::new();
+	for part in [env!("CARGO_PKG_VERSION"), &cwd.to_string_lossy()]
+		.into_iter()
+		.chain(args.iter().map(String::as_str))
+	{
+		material.extend_from_slice(part.as_bytes());
+		material.push(0);
+	}
+	for file in files {
+		material.extend_from_slice(file.digest.as_bytes());
+		material.push(0);
+	}
+	let (_, digest) = digest_reader(material.as_slice(), None)?;
+	Ok(digest.trim_start_matches("fnv1a64:").to_owned())
+}
+
+fn load_entry(path: &Path) -> Option<CacheEntry> {
+	let bytes = fs::read(path).ok()?;
+	serde_json::from_slice(&bytes).ok()
+}
+
+/// Write through a temporary file so concurrent readers never see a partial entry.
+fn store_entry(dir: &Path, path: &Path, entry: &CacheEntry) -> Result<()> {
+	fs::create_dir_all(dir)?;
+	let staging = path.with_extension(format!("json.{}.tmp", std::process::id()));
+	fs::write(&staging, serde_json::to_vec(entry).map_err(io::Error::other)?)?;
+	fs::rename(&staging, path)?;
+	Ok(())
+}
+
+#[cfg(test)]
+mod tests;
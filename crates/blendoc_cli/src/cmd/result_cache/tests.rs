@@ -0,0 +1,60 @@
+use blendoc_testkit::builder::{HeaderKind, scene_file};
+
+use super::strip_cache_flags;
+use crate::cmd::test_support::{TempBlend, run_blendoc};
+
+#[test]
+fn cache_flags_are_stripped_from_child_arguments() {
+	let args = [
+		"--cached",
+		"info",
+		"a.blend",
+		"--cache-dir",
+		"/tmp/c",
+		"--json",
+		"--cache-dir=/x",
+		"--no-cached",
+	]
+	.map(std::ffi::OsString::from);
+	let stripped = strip_cache_flags(args);
+	assert_eq!(stripped, ["info", "a.blend", "--json"].map(std::ffi::OsString::from));
+}
+
+#[test]
+fn repeated_queries_hit_until_the_file_changes() {
+	let dir = std::env::temp_dir().join(format!("blendoc_result_cache_{}", std::process::id()));
+	let target = TempBlend::scene("result_cache");
+	let target_arg = target.arg();
+	let dir_arg = dir.to_string_lossy().into_owned();
+	let query = ["info", target_arg, "--json", "--cached", "--cache-dir", dir_arg.as_str()];
+	let run = |args: &[&str]| -> (String, bool) {
+		let output = run_blendoc(args);
+		assert!(output.status.success(), "info failed: {}", String::from_utf8_lossy(&output.stderr));
+		let cached = String::from_utf8_lossy(&output.stderr).contains("cached: true");
+		(String::from_utf8(output.stdout).expect("utf-8 output"), cached)
+	};
+
+	let miss = run(&query);
+	let hit = run(&query);
+	let bypass = run(&[&query[..], &["--no-cached"]].concat());
+	std::fs::write(&target, scene_file(HeaderKind::Legacy4, false)).expect("synthetic file rewritten");
+	let changed = run(&query);
+	std::fs::remove_dir_all(&dir).expect("cache dir removed");
+
+	assert!(!miss.1 && hit.1 && !bypass.1 && !changed.1);
+	assert_eq!(hit.0, miss.0, "hits replay stdout byte for byte");
+	assert_eq!(bypass.0, miss.0);
+	let changed: serde_json::Value = serde_json::from_str(&changed.0).expect("json output");
+	assert_eq!(changed["pointer_size"], 4);
+}
+
+#[test]
+fn interactive_and_file_output_commands_are_rejected() {
+	let output = run_blendoc(&["--cached", "info", "missing.blend", "--json", "-o", "out.json"]);
+	assert!(!output.status.success());
+	assert!(String::from_utf8_lossy(&output.stderr).contains("cannot be used with `-o`"));
+
+	let output = run_blendoc(&["--cached", "repl", "missing.blend"]);
+	assert!(!output.status.success());
+	assert!(String::from_utf8_lossy(&output.stderr).contains("--cached cannot be used with `repl`"));
+}
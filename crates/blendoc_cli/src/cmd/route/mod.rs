@@ -1,9 +1,16 @@
 use std::collections::HashMap;
+use std::collections::hash_map::Entry;
 use std::path::PathBuf;
 
-use blendoc::blend::{BlendError, BlendFile, IdIndex, RouteOptions, RouteResult, RouteTruncation, find_route_between_ptrs, scan_id_blocks};
+use blendoc::blend::{
+	BlendError, BlendFile, Dna, IdIndex, PointerIndex, Result, RouteEdge, RouteOptions, RouteResult, RouteTruncation, RoutesResult, find_route_between_ptrs,
+	find_route_to_code, find_routes_between_ptrs, find_routes_to_code,
+};
 
-use crate::cmd::util::{IdOrPtrSelector, RootSelector, emit_json, parse_id_or_ptr_selector, parse_root_selector, ptr_hex, render_code};
+use crate::cmd::util::{
+	IdOrPtrSelector, RootSelector, code_selector_label, emit_json, parse_block_code_occurrence, parse_id_or_ptr_selector, parse_root_selector, ptr_hex,
+	render_code, resolve_root, scan_ids,
+};
 
 #[derive(clap::Args)]
 pub struct Args {
@@ -18,6 +25,8 @@ pub struct Args {
 	pub to_id: Option<String>,
 	#[arg(long = "to-ptr")]
 	pub to_ptr: Option<String>,
+	#[arg(long = "to-code")]
+	pub to_code: Option<String>,
 	#[arg(long)]
 	pub depth: Option<u32>,
 	#[arg(long = "refs-depth")]
@@ -26,11 +35,13 @@ pub struct Args {
 	pub max_nodes: Option<usize>,
 	#[arg(long = "max-edges")]
 	pub max_edges: Option<usize>,
+	#[arg(long = "max-routes", value_name = "N")]
+	pub max_routes: Option<usize>,
 	#[arg(long)]
 	pub json: bool,
 }
 
-/// Find and print a shortest pointer route between two endpoints.
+/// Find and print a shortest pointer route, or up to `--max-routes` routes, between two endpoints.
 pub fn run(args: Args) -> blendoc::blend::Result<()> {
 	let Args {
 		file: path,
@@ -39,41 +50,98 @@ pub fn run(args: Args) -> blendoc::blend::Result<()> {
 		from_code,
 		to_id,
 		to_ptr,
+		to_code,
 		depth,
 		refs_depth,
 		max_nodes,
 		max_edges,
+		max_routes,
 		json,
 	} = args;
 
 	let from_selector = parse_root_selector(from_code, from_ptr, from_id)?;
-	let to_selector = parse_id_or_ptr_selector(to_id, to_ptr)?;
+	let to_selector = parse_route_target(to_id, to_ptr, to_code)?;
 
 	let blend = BlendFile::open(&path)?;
 	let dna = blend.dna()?;
 	let index = blend.pointer_index()?;
-	let ids = IdIndex::build(scan_id_blocks(&blend, &dna)?);
+	let ids = IdIndex::build(scan_ids(&blend, &dna)?);
 
-	let (from_ptr, from_label) = match from_selector {
-		RootSelector::Id(name) => {
-			let row = ids.get_by_name(&name).ok_or(BlendError::IdRecordNotFound { name: name.clone() })?;
-			(row.old_ptr, format!("id:{}", row.id_name))
-		}
-		RootSelector::Ptr(ptr) => (ptr, format!("ptr:0x{ptr:016x}")),
-		RootSelector::Code(code) => {
-			let block = blend.find_first_block_by_code(code)?.ok_or(BlendError::BlockNotFound { code })?;
-			(block.head.old, format!("code:{}", render_code(code)))
+	let mut options = route_options(depth, refs_depth, max_nodes, max_edges, max_routes);
+	options.cancel = crate::cmd::interrupt::token();
+	let RouteQuery {
+		from_label,
+		to_label,
+		from_meta,
+		to_meta,
+		result,
+		routes,
+	} = find_route(&blend, &dna, &index, &ids, from_selector, to_selector, &options)?;
+
+	if json {
+		emit_json(&json_payload(&path, &from_label, &to_label, &from_meta, to_meta.as_ref(), &result, &routes));
+		return Ok(());
+	}
+
+	println!("path: {}", path.display());
+	println!("from: {from_label}");
+	println!("to: {to_label}");
+	println!("from_canonical: 0x{:016x}", from_meta.canonical);
+	match &to_meta {
+		Some(to_meta) => println!("to_canonical: 0x{:016x}", to_meta.canonical),
+		None => println!("to_canonical: none"),
+	}
+	println!("visited_nodes: {}", result.visited_nodes);
+	println!("visited_edges: {}", result.visited_edges);
+	println!("truncated: {}", truncation_label_opt(result.truncated));
+
+	let mut labels = HashMap::new();
+	labels.insert(from_meta.canonical, from_meta.clone());
+	if let Some(to_meta) = &to_meta {
+		labels.insert(to_meta.canonical, to_meta.clone());
+	}
+
+	if options.max_routes > 1 {
+		println!("routes: {}", routes.len());
+		for (idx, route) in routes.iter().enumerate() {
+			println!("route {}: len={}", idx + 1, route.len());
+			print_edges(&dna, &index, &ids, &mut labels, route, "  ")?;
 		}
-	};
+	} else if let Some(path_edges) = &result.path {
+		println!("route_len: {}", path_edges.len());
+		print_edges(&dna, &index, &ids, &mut labels, path_edges, "")?;
+	} else {
+		println!("route_len: not_found");
+	}
+
+	Ok(())
+}
 
-	let (to_ptr, to_label) = match to_selector {
-		IdOrPtrSelector::Id(name) => {
-			let row = ids.get_by_name(&name).ok_or(BlendError::IdRecordNotFound { name: name.clone() })?;
-			(row.old_ptr, format!("id:{}", row.id_name))
+fn print_edges<'a>(dna: &Dna, index: &PointerIndex<'a>, ids: &IdIndex, labels: &mut HashMap<u64, NodeMeta>, edges: &[RouteEdge], indent: &str) -> Result<()> {
+	for edge in edges {
+		for ptr in [edge.from, edge.to] {
+			if let Entry::Vacant(slot) = labels.entry(ptr) {
+				slot.insert(resolve_node_meta(dna, index, ids, ptr)?);
+			}
 		}
-		IdOrPtrSelector::Ptr(ptr) => (ptr, format!("ptr:0x{ptr:016x}")),
-	};
+		println!(
+			"{indent}{} -{}-> {}",
+			node_label(&labels[&edge.from]),
+			edge.field,
+			node_label(&labels[&edge.to])
+		);
+	}
+	Ok(())
+}
 
+/// Route limits from the `--depth`/`--refs-depth`/`--max-*` flags; `None` keeps the default.
+pub(crate) fn route_options(
+	depth: Option<u32>,
+	refs_depth: Option<u32>,
+	max_nodes: Option<usize>,
+	max_edges: Option<usize>,
+	max_routes: Option<usize>,
+) -> RouteOptions {
 	let mut options = RouteOptions::default();
 	if let Some(depth) = depth {
 		options.max_depth = depth;
@@ -87,58 +155,115 @@ pub fn run(args: Args) -> blendoc::blend::Result<()> {
 	if let Some(max_edges) = max_edges {
 		options.max_edges = max_edges;
 	}
+	if let Some(max_routes) = max_routes {
+		options.max_routes = max_routes;
+	}
+	options
+}
 
-	let result = find_route_between_ptrs(&dna, &index, &ids, from_ptr, to_ptr, &options)?;
+/// Resolved endpoints and search result of one route query.
+pub(crate) struct RouteQuery {
+	pub from_label: String,
+	pub to_label: String,
+	pub from_meta: NodeMeta,
+	pub to_meta: Option<NodeMeta>,
+	pub result: RouteResult,
+	/// Every route found when `max_routes` is above one, shortest first; `result.path` is the first.
+	pub routes: Vec<Vec<RouteEdge>>,
+}
 
-	let from_meta = resolve_node_meta(&dna, &index, &ids, from_ptr)?;
-	let to_meta = resolve_node_meta(&dna, &index, &ids, to_ptr)?;
+/// Resolve both endpoints and search for a shortest route, or up to `max_routes` routes, between them.
+pub(crate) fn find_route<'a>(
+	blend: &'a BlendFile,
+	dna: &Dna,
+	index: &PointerIndex<'a>,
+	ids: &IdIndex,
+	from: RootSelector,
+	to: RouteTargetSelector,
+	options: &RouteOptions,
+) -> Result<RouteQuery> {
+	let (from_ptr, from_label) = resolve_root(blend, ids, from)?;
 
-	if json {
-		print_json(&path, &from_label, &to_label, &from_meta, &to_meta, &result);
-		return Ok(());
+	let (to_target, to_label) = match to {
+		RouteTargetSelector::Single(IdOrPtrSelector::Id(name)) => {
+			let row = ids.resolve_selector(&name)?;
+			(RouteTarget::Ptr(row.old_ptr), format!("id:{}", row.id_name))
+		}
+		RouteTargetSelector::Single(IdOrPtrSelector::Ptr(ptr)) => (RouteTarget::Ptr(ptr), format!("ptr:0x{ptr:016x}")),
+		RouteTargetSelector::Code(code, Some(occurrence)) => {
+			let block = blend.find_nth_block_by_code(code, occurrence)?;
+			(RouteTarget::Ptr(block.head.old), code_selector_label(code, Some(occurrence)))
+		}
+		RouteTargetSelector::Code(code, None) => (RouteTarget::AnyCode(code), format!("code:{}", render_code(code))),
+	};
+
+	let (result, routes) = if options.max_routes > 1 {
+		let found = match to_target {
+			RouteTarget::Ptr(to_ptr) => find_routes_between_ptrs(dna, index, ids, from_ptr, to_ptr, options)?,
+			RouteTarget::AnyCode(code) => find_routes_to_code(dna, index, ids, from_ptr, code, options)?,
+		};
+		let from_canonical = index.canonicalize_ptr(dna, from_ptr)?;
+		(first_route(&found, from_canonical), found.routes)
+	} else {
+		let result = match to_target {
+			RouteTarget::Ptr(to_ptr) => find_route_between_ptrs(dna, index, ids, from_ptr, to_ptr, options)?,
+			RouteTarget::AnyCode(code) => find_route_to_code(dna, index, ids, from_ptr, code, options)?,
+		};
+		(result, Vec::new())
+	};
+
+	let from_meta = resolve_node_meta(dna, index, ids, from_ptr)?;
+	let to_meta = match (to_target, result.reached) {
+		(RouteTarget::Ptr(to_ptr), _) => Some(resolve_node_meta(dna, index, ids, to_ptr)?),
+		(RouteTarget::AnyCode(_), Some(reached)) => Some(resolve_node_meta(dna, index, ids, reached)?),
+		(RouteTarget::AnyCode(_), None) => None,
+	};
+
+	Ok(RouteQuery {
+		from_label,
+		to_label,
+		from_meta,
+		to_meta,
+		result,
+		routes,
+	})
+}
+
+/// Single-route view of a multi-route search, so the summary fields stay the same.
+fn first_route(found: &RoutesResult, from: u64) -> RouteResult {
+	RouteResult {
+		path: found.routes.first().cloned(),
+		visited_nodes: found.visited_nodes,
+		visited_edges: found.visited_edges,
+		truncated: found.truncated,
+		reached: found.reached(0, from),
 	}
+}
 
-	println!("path: {}", path.display());
-	println!("from: {from_label}");
-	println!("to: {to_label}");
-	println!("from_canonical: 0x{:016x}", from_meta.canonical);
-	println!("to_canonical: 0x{:016x}", to_meta.canonical);
-	println!("visited_nodes: {}", result.visited_nodes);
-	println!("visited_edges: {}", result.visited_edges);
-	println!("truncated: {}", truncation_label_opt(result.truncated));
+pub(crate) enum RouteTargetSelector {
+	Single(IdOrPtrSelector),
+	Code([u8; 4], Option<usize>),
+}
 
-	let mut labels = HashMap::new();
-	labels.insert(from_meta.canonical, from_meta.clone());
-	labels.insert(to_meta.canonical, to_meta.clone());
+#[derive(Clone, Copy)]
+enum RouteTarget {
+	Ptr(u64),
+	AnyCode([u8; 4]),
+}
 
-	if let Some(path_edges) = &result.path {
-		println!("route_len: {}", path_edges.len());
-		for edge in path_edges {
-			let from = if let Some(existing) = labels.get(&edge.from) {
-				existing.clone()
-			} else {
-				let resolved = resolve_node_meta(&dna, &index, &ids, edge.from)?;
-				labels.insert(edge.from, resolved.clone());
-				resolved
-			};
-			let to = if let Some(existing) = labels.get(&edge.to) {
-				existing.clone()
-			} else {
-				let resolved = resolve_node_meta(&dna, &index, &ids, edge.to)?;
-				labels.insert(edge.to, resolved.clone());
-				resolved
-			};
-			println!("{} -{}-> {}", node_label(&from), edge.field, node_label(&to));
+pub(crate) fn parse_route_target(to_id: Option<String>, to_ptr: Option<String>, to_code: Option<String>) -> blendoc::blend::Result<RouteTargetSelector> {
+	match to_code {
+		Some(_) if to_id.is_some() || to_ptr.is_some() => Err(BlendError::InvalidChaseRoot),
+		Some(to_code) => {
+			let (code, occurrence) = parse_block_code_occurrence(&to_code)?;
+			Ok(RouteTargetSelector::Code(code, occurrence))
 		}
-	} else {
-		println!("route_len: not_found");
+		None => Ok(RouteTargetSelector::Single(parse_id_or_ptr_selector(to_id, to_ptr)?)),
 	}
-
-	Ok(())
 }
 
 #[derive(Debug, Clone)]
-struct NodeMeta {
+pub(crate) struct NodeMeta {
 	canonical: u64,
 	type_name: String,
 	id_name: Option<String>,
@@ -173,6 +298,7 @@ fn truncation_label_opt(value: Option<RouteTruncation>) -> &'static str {
 		Some(RouteTruncation::MaxDepth) => "max_depth",
 		Some(RouteTruncation::MaxNodes) => "max_nodes",
 		Some(RouteTruncation::MaxEdges) => "max_edges",
+		Some(RouteTruncation::Cancelled) => "cancelled",
 		None => "none",
 	}
 }
@@ -182,49 +308,59 @@ fn truncation_label(value: RouteTruncation) -> &'static str {
 		RouteTruncation::MaxDepth => "max_depth",
 		RouteTruncation::MaxNodes => "max_nodes",
 		RouteTruncation::MaxEdges => "max_edges",
+		RouteTruncation::Cancelled => "cancelled",
 	}
 }
 
-fn print_json(path: &std::path::Path, from_label: &str, to_label: &str, from: &NodeMeta, to: &NodeMeta, result: &RouteResult) {
-	let payload = RouteJson {
+/// `--json` payload for a route query.
+pub(crate) fn json_payload(
+	path: &std::path::Path,
+	from_label: &str,
+	to_label: &str,
+	from: &NodeMeta,
+	to: Option<&NodeMeta>,
+	result: &RouteResult,
+	routes: &[Vec<RouteEdge>],
+) -> RouteJson {
+	RouteJson {
 		path: path.display().to_string(),
 		from: EndpointJson {
 			selector: from_label.to_owned(),
-			canonical: ptr_hex(from.canonical),
-			type_name: from.type_name.clone(),
+			canonical: Some(ptr_hex(from.canonical)),
+			type_name: Some(from.type_name.clone()),
 			id: from.id_name.clone(),
 		},
 		to: EndpointJson {
 			selector: to_label.to_owned(),
-			canonical: ptr_hex(to.canonical),
-			type_name: to.type_name.clone(),
-			id: to.id_name.clone(),
+			canonical: to.map(|to| ptr_hex(to.canonical)),
+			type_name: to.map(|to| to.type_name.clone()),
+			id: to.and_then(|to| to.id_name.clone()),
 		},
 		visited_nodes: result.visited_nodes,
 		visited_edges: result.visited_edges,
 		truncated: result.truncated.map(truncation_label).map(str::to_owned),
-		path_edges: result
-			.path
-			.as_deref()
-			.unwrap_or(&[])
-			.iter()
-			.map(|edge| RouteEdgeJson {
-				from: ptr_hex(edge.from),
-				to: ptr_hex(edge.to),
-				field: edge.field.to_string(),
-			})
-			.collect(),
-	};
+		path_edges: edges_json(result.path.as_deref().unwrap_or(&[])),
+		routes: routes.iter().map(|route| edges_json(route)).collect(),
+	}
+}
 
-	emit_json(&payload);
+fn edges_json(edges: &[RouteEdge]) -> Vec<RouteEdgeJson> {
+	edges
+		.iter()
+		.map(|edge| RouteEdgeJson {
+			from: ptr_hex(edge.from),
+			to: ptr_hex(edge.to),
+			field: edge.field.to_string(),
+		})
+		.collect()
 }
 
 #[derive(serde::Serialize)]
 struct EndpointJson {
 	selector: String,
-	canonical: String,
+	canonical: Option<String>,
 	#[serde(rename = "type")]
-	type_name: String,
+	type_name: Option<String>,
 	id: Option<String>,
 }
 
@@ -236,7 +372,7 @@ struct RouteEdgeJson {
 }
 
 #[derive(serde::Serialize)]
-struct RouteJson {
+pub(crate) struct RouteJson {
 	path: String,
 	from: EndpointJson,
 	to: EndpointJson,
@@ -244,6 +380,8 @@ struct RouteJson {
 	visited_edges: usize,
 	truncated: Option<String>,
 	path_edges: Vec<RouteEdgeJson>,
+	#[serde(skip_serializing_if = "Vec::is_empty")]
+	routes: Vec<Vec<RouteEdgeJson>>,
 }
 
 #[cfg(test)]
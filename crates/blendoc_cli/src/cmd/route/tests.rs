@@ -1,4 +1,4 @@
-use crate::cmd::test_support::{fixture_path, run_blendoc_json};
+use crate::cmd::test_support::{TempBlend, fixture_path, run_blendoc_json};
 
 #[test]
 fn route_json_output_includes_path_edges_array() {
@@ -10,3 +10,41 @@ fn route_json_output_includes_path_edges_array() {
 	assert_eq!(json["to"]["selector"], "id:WOWorld");
 	assert!(json["path_edges"].is_array(), "expected path_edges array");
 }
+
+#[test]
+fn route_to_code_reports_reached_target() {
+	let fixture = fixture_path("character.blend");
+	let fixture = fixture.to_string_lossy().into_owned();
+	let json = run_blendoc_json(&["route", &fixture, "--from-id", "SCScene", "--to-code", "WO", "--json"]);
+
+	assert_eq!(json["to"]["selector"], "code:WO");
+	assert_eq!(json["to"]["id"], "WOWorld");
+	assert_eq!(json["path_edges"].as_array().map(Vec::len), Some(1));
+}
+
+#[test]
+fn max_routes_lists_alternatives_shortest_first() {
+	let target = TempBlend::scene("route_routes");
+	let target_arg = target.arg();
+
+	let json = run_blendoc_json(&[
+		"route",
+		target_arg,
+		"--from-id",
+		"SCScene",
+		"--to-id",
+		"MECamera",
+		"--max-routes",
+		"3",
+		"--json",
+	]);
+	let single = run_blendoc_json(&["route", target_arg, "--from-id", "SCScene", "--to-id", "MECamera", "--json"]);
+
+	let routes = json["routes"].as_array().expect("routes array");
+	assert!(!routes.is_empty() && routes.len() <= 3);
+	assert_eq!(routes[0], json["path_edges"]);
+	assert_eq!(json["path_edges"], single["path_edges"]);
+	let lens: Vec<usize> = routes.iter().map(|route| route.as_array().map_or(0, Vec::len)).collect();
+	assert!(lens.windows(2).all(|pair| pair[0] <= pair[1]), "routes not shortest first: {lens:?}");
+	assert_eq!(single.get("routes"), None);
+}
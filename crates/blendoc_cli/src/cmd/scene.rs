@@ -1,17 +1,27 @@
 use std::path::PathBuf;
 
-use blendoc::blend::DecodeOptions;
+use blendoc::blend::{DecodeOptions, codes};
 
 use crate::cmd::decode::run_with_code;
 use crate::cmd::print::PrintOptions;
+use crate::cmd::truncation::TruncationArgs;
 
 #[derive(clap::Args)]
 pub struct Args {
 	pub path: PathBuf,
+	#[arg(long = "pretty-math")]
+	pub pretty_math: bool,
+	#[command(flatten)]
+	pub truncation: TruncationArgs,
 }
 
 /// Decode and print the first `SC\0\0` scene block.
 pub fn run(args: Args) -> blendoc::blend::Result<()> {
-	let Args { path } = args;
-	run_with_code(path, [b'S', b'C', 0, 0], DecodeOptions::for_scene_inspect(), PrintOptions::for_scene_inspect())
+	let Args { path, pretty_math, truncation } = args;
+	let mut print_options = PrintOptions {
+		pretty_math,
+		..PrintOptions::for_scene_inspect()
+	};
+	truncation.apply(&mut print_options);
+	run_with_code(path, codes::ID_SCE, DecodeOptions::for_scene_inspect(), print_options)
 }
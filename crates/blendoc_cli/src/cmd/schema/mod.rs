@@ -0,0 +1,396 @@
+use std::sync::OnceLock;
+
+use blendoc::blend::Result;
+
+/// Newest JSON schema major version; payloads carry it as `schema_version`.
+pub(crate) const CURRENT: u32 = 2;
+/// Oldest major version still selectable with `--schema-version`.
+///
+/// Version 1 is the unversioned format: no `schema_version` field and
+/// list-shaped payloads emitted as bare arrays.
+pub(crate) const OLDEST: u32 = 1;
+
+/// One subcommand with structured output.
+pub(crate) struct Payload {
+	/// Subcommand name.
+	pub(crate) command: &'static str,
+	/// Schema major in which the payload shape last changed.
+	pub(crate) since: u32,
+	/// JSON Schema of the current payload, from `crates/blendoc_cli/schemas`.
+	pub(crate) schema: &'static str,
+}
+
+/// Registry of subcommands with structured output. Only registered commands
+/// are stamped.
+///
+/// Changing a payload's shape means bumping [`CURRENT`], setting the
+/// command's `since` to it, updating its schema file, and keeping the
+/// previous shape available to `--schema-version` until [`OLDEST`] moves
+/// past it.
+pub(crate) const PAYLOADS: &[Payload] = &[
+	Payload {
+		command: "anim",
+		since: 2,
+		schema: include_str!("../../../schemas/anim.json"),
+	},
+	Payload {
+		command: "blocks",
+		since: 2,
+		schema: include_str!("../../../schemas/blocks.json"),
+	},
+	Payload {
+		command: "chase",
+		since: 2,
+		schema: include_str!("../../../schemas/chase.json"),
+	},
+	Payload {
+		command: "codes",
+		since: 2,
+		schema: include_str!("../../../schemas/codes.json"),
+	},
+	Payload {
+		command: "copy",
+		since: 2,
+		schema: include_str!("../../../schemas/copy.json"),
+	},
+	Payload {
+		command: "cycles",
+		since: 2,
+		schema: include_str!("../../../schemas/cycles.json"),
+	},
+	Payload {
+		command: "dangling",
+		since: 2,
+		schema: include_str!("../../../schemas/dangling.json"),
+	},
+	Payload {
+		command: "deps",
+		since: 2,
+		schema: include_str!("../../../schemas/deps.json"),
+	},
+	Payload {
+		command: "diff",
+		since: 2,
+		schema: include_str!("../../../schemas/diff.json"),
+	},
+	Payload {
+		command: "dna-diff",
+		since: 2,
+		schema: include_str!("../../../schemas/dna-diff.json"),
+	},
+	Payload {
+		command: "doctor",
+		since: 2,
+		schema: include_str!("../../../schemas/doctor.json"),
+	},
+	Payload {
+		command: "export",
+		since: 2,
+		schema: include_str!("../../../schemas/export.json"),
+	},
+	Payload {
+		command: "find",
+		since: 2,
+		schema: include_str!("../../../schemas/find.json"),
+	},
+	Payload {
+		command: "graph",
+		since: 2,
+		schema: include_str!("../../../schemas/graph.json"),
+	},
+	Payload {
+		command: "has",
+		since: 2,
+		schema: include_str!("../../../schemas/has.json"),
+	},
+	Payload {
+		command: "hexdump",
+		since: 2,
+		schema: include_str!("../../../schemas/hexdump.json"),
+	},
+	Payload {
+		command: "hierarchy",
+		since: 2,
+		schema: include_str!("../../../schemas/hierarchy.json"),
+	},
+	Payload {
+		command: "id",
+		since: 2,
+		schema: include_str!("../../../schemas/id.json"),
+	},
+	Payload {
+		command: "idgraph",
+		since: 2,
+		schema: include_str!("../../../schemas/idgraph.json"),
+	},
+	Payload {
+		command: "ids",
+		since: 2,
+		schema: include_str!("../../../schemas/ids.json"),
+	},
+	Payload {
+		command: "images",
+		since: 2,
+		schema: include_str!("../../../schemas/images.json"),
+	},
+	Payload {
+		command: "infer",
+		since: 2,
+		schema: include_str!("../../../schemas/infer.json"),
+	},
+	Payload {
+		command: "info",
+		since: 2,
+		schema: include_str!("../../../schemas/info.json"),
+	},
+	Payload {
+		command: "libs",
+		since: 2,
+		schema: include_str!("../../../schemas/libs.json"),
+	},
+	Payload {
+		command: "libtree",
+		since: 2,
+		schema: include_str!("../../../schemas/libtree.json"),
+	},
+	Payload {
+		command: "list",
+		since: 2,
+		schema: include_str!("../../../schemas/list.json"),
+	},
+	Payload {
+		command: "mesh",
+		since: 2,
+		schema: include_str!("../../../schemas/mesh.json"),
+	},
+	Payload {
+		command: "nodetree",
+		since: 2,
+		schema: include_str!("../../../schemas/nodetree.json"),
+	},
+	Payload {
+		command: "open-check",
+		since: 2,
+		schema: include_str!("../../../schemas/open-check.json"),
+	},
+	Payload {
+		command: "orphans",
+		since: 2,
+		schema: include_str!("../../../schemas/orphans.json"),
+	},
+	Payload {
+		command: "packed",
+		since: 2,
+		schema: include_str!("../../../schemas/packed.json"),
+	},
+	Payload {
+		command: "previews",
+		since: 2,
+		schema: include_str!("../../../schemas/previews.json"),
+	},
+	Payload {
+		command: "project-audit",
+		since: 2,
+		schema: include_str!("../../../schemas/project-audit.json"),
+	},
+	Payload {
+		command: "props",
+		since: 2,
+		schema: include_str!("../../../schemas/props.json"),
+	},
+	Payload {
+		command: "prune",
+		since: 2,
+		schema: include_str!("../../../schemas/prune.json"),
+	},
+	Payload {
+		command: "recompress",
+		since: 2,
+		schema: include_str!("../../../schemas/recompress.json"),
+	},
+	Payload {
+		command: "refs",
+		since: 2,
+		schema: include_str!("../../../schemas/refs.json"),
+	},
+	Payload {
+		command: "rename",
+		since: 2,
+		schema: include_str!("../../../schemas/rename.json"),
+	},
+	Payload {
+		command: "replay",
+		since: 2,
+		schema: include_str!("../../../schemas/replay.json"),
+	},
+	Payload {
+		command: "report",
+		since: 2,
+		schema: include_str!("../../../schemas/report.json"),
+	},
+	Payload {
+		command: "route",
+		since: 2,
+		schema: include_str!("../../../schemas/route.json"),
+	},
+	Payload {
+		command: "search",
+		since: 2,
+		schema: include_str!("../../../schemas/search.json"),
+	},
+	Payload {
+		command: "selftest",
+		since: 2,
+		schema: include_str!("../../../schemas/selftest.json"),
+	},
+	Payload {
+		command: "show",
+		since: 2,
+		schema: include_str!("../../../schemas/show.json"),
+	},
+	Payload {
+		command: "stats",
+		since: 2,
+		schema: include_str!("../../../schemas/stats.json"),
+	},
+	Payload {
+		command: "thumb",
+		since: 2,
+		schema: include_str!("../../../schemas/thumb.json"),
+	},
+	Payload {
+		command: "validate",
+		since: 2,
+		schema: include_str!("../../../schemas/validate.json"),
+	},
+	Payload {
+		command: "walk",
+		since: 2,
+		schema: include_str!("../../../schemas/walk.json"),
+	},
+	Payload {
+		command: "why-big",
+		since: 2,
+		schema: include_str!("../../../schemas/why-big.json"),
+	},
+	Payload {
+		command: "xref",
+		since: 2,
+		schema: include_str!("../../../schemas/xref.json"),
+	},
+];
+
+/// Schema version and stamping decision for this process.
+#[derive(Clone, Copy)]
+struct Selection {
+	version: u32,
+	stamp: bool,
+}
+
+static SELECTED: OnceLock<Selection> = OnceLock::new();
+
+/// Record the running subcommand and its `--schema-version` (default [`CURRENT`]).
+pub(crate) fn select(command: Option<&str>, version: Option<u32>) {
+	let version = version.unwrap_or(CURRENT);
+	let registered = command.is_some_and(|command| PAYLOADS.iter().any(|payload| payload.command == command));
+	let _ = SELECTED.set(Selection {
+		version,
+		stamp: registered && version >= CURRENT,
+	});
+}
+
+/// Version to stamp on payloads, if any.
+fn stamp() -> Option<u32> {
+	SELECTED.get().filter(|selection| selection.stamp).map(|selection| selection.version)
+}
+
+/// Object payload with `schema_version` as its first key.
+#[derive(serde::Serialize)]
+struct Stamped<'a, T: ?Sized> {
+	schema_version: u32,
+	#[serde(flatten)]
+	payload: &'a T,
+}
+
+/// List payload wrapped so it can carry `schema_version`.
+#[derive(serde::Serialize)]
+struct StampedItems<'a, T: ?Sized> {
+	schema_version: u32,
+	items: &'a T,
+}
+
+/// Pretty JSON for `payload` in the selected schema version.
+pub(crate) fn to_json<T: serde::Serialize + ?Sized>(payload: &T) -> serde_json::Result<String> {
+	let Some(version) = stamp() else {
+		return serde_json::to_string_pretty(payload);
+	};
+	// Only maps can be flattened; lists fail before any item is written and
+	// fall back to an `items` wrapper.
+	serde_json::to_string_pretty(&Stamped {
+		schema_version: version,
+		payload,
+	})
+	.or_else(|_| {
+		serde_json::to_string_pretty(&StampedItems {
+			schema_version: version,
+			items: payload,
+		})
+	})
+}
+
+/// `payload` as a JSON value stamped with [`CURRENT`], matching default `--json` output.
+pub(crate) fn to_current_value<T: serde::Serialize + ?Sized>(payload: &T) -> serde_json::Result<serde_json::Value> {
+	serde_json::to_value(Stamped {
+		schema_version: CURRENT,
+		payload,
+	})
+	.or_else(|_| {
+		serde_json::to_value(StampedItems {
+			schema_version: CURRENT,
+			items: payload,
+		})
+	})
+}
+
+/// YAML for `payload` in the selected schema version; see [`to_json`].
+pub(crate) fn to_yaml<T: serde::Serialize + ?Sized>(payload: &T) -> serde_yaml::Result<String> {
+	let Some(version) = stamp() else {
+		return serde_yaml::to_string(payload);
+	};
+	serde_yaml::to_string(&Stamped {
+		schema_version: version,
+		payload,
+	})
+	.or_else(|_| {
+		serde_yaml::to_string(&StampedItems {
+			schema_version: version,
+			items: payload,
+		})
+	})
+}
+
+#[derive(clap::Args)]
+pub struct Args {
+	#[arg(value_parser = clap::builder::PossibleValuesParser::new(PAYLOADS.iter().map(|payload| payload.command)))]
+	pub command: Option<String>,
+}
+
+/// Print the JSON Schema of one command's `--json` payload, or list the
+/// registered commands with the schema version their payload last changed in.
+pub fn run(args: Args) -> Result<()> {
+	let Some(command) = args.command else {
+		println!("schema_version: {CURRENT} (oldest selectable: {OLDEST})");
+		println!("command\tsince");
+		for payload in PAYLOADS {
+			println!("{}\t{}", payload.command, payload.since);
+		}
+		return Ok(());
+	};
+	// The value parser only admits registered commands.
+	let payload = PAYLOADS.iter().find(|payload| payload.command == command).expect("registered command");
+	print!("{}", payload.schema);
+	Ok(())
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,339 @@
+use std::process::Command;
+
+use blendoc_testkit::blendoc_bin;
+use blendoc_testkit::builder::{HeaderKind, scene_file};
+use clap::CommandFactory;
+use serde_json::Value;
+
+use super::{CURRENT, OLDEST, PAYLOADS};
+use crate::cmd::test_support::{run_blendoc, run_blendoc_json};
+
+/// Invocations on the synthetic scene file whose output is checked against
+/// the command's schema; `{file}` is replaced with its path, `{dir}` with
+/// the directory holding only that file, and `{ids}` with a file listing
+/// `OBCamera`.
+const SAMPLES: &[&[&str]] = &[
+	&["info", "{file}", "--json"],
+	&["ids", "{file}", "--json"],
+	&["id", "{file}", "--id", "SCScene", "--json"],
+	&["has", "{file}", "--id", "SCScene", "--json"],
+	&["refs", "{file}", "--id", "SCScene", "--json"],
+	&["xref", "{file}", "--id", "OBCamera", "--json"],
+	&["graph", "{file}", "--id", "SCScene", "--json"],
+	&["idgraph", "{file}", "--json"],
+	&["cycles", "{file}", "--json"],
+	&["deps", "{file}", "--id", "SCScene", "--json"],
+	&["route", "{file}", "--from-id", "SCScene", "--to-id", "MECamera", "--json"],
+	&["route", "{file}", "--from-id", "SCScene", "--to-id", "MECamera", "--max-routes", "3", "--json"],
+	&["project-audit", "{dir}", "--json"],
+	&["report", "{dir}", "--json"],
+	&["prune", "{file}", "--plan", "{ids}", "--dry-run", "--json"],
+	&["chase", "{file}", "--id", "SCScene", "--path", "camera.data", "--json"],
+	&["chase", "{file}", "--id", "SCScene", "--path", "*.id", "--json"],
+	&["walk", "{file}", "--id", "SCScene", "--json"],
+	&["show", "{file}", "--id", "SCScene", "--json"],
+	&["show", "{file}", "--id", "SCScene", "--path", "camera", "--json"],
+	&["show", "{file}", "--id", "OBCamera", "--path", "loc[*]", "--trace", "--json"],
+	&["show", "{file}", "--id", "SCScene", "--explain-decode", "--json"],
+	&["find", "{file}", "SCScene", "--json"],
+	&["export", "{file}", "--expand-depth", "2"],
+	&["diff", "{file}", "{file}", "--json"],
+	&["dna-diff", "{file}", "{file}", "--json"],
+	&["rename", "{file}", "--id", "OBCamera", "--dry-run", "--json"],
+	&["stats", "{file}", "--json"],
+	&["validate", "{file}", "--json"],
+	&["dangling", "{file}", "--all", "--json"],
+	&["codes", "--json"],
+	&["blocks", "{file}", "--json"],
+	&["why-big", "{file}", "--min-bytes", "0", "--json"],
+	&["open-check", "{file}", "--json"],
+	&["orphans", "{file}", "--json"],
+	&["libs", "{file}", "--json"],
+	&["libtree", "{file}", "--json"],
+	&["infer", "{file}", "--json"],
+	&["hierarchy", "{file}", "--json"],
+	&["hexdump", "{file}", "--id", "OBCamera", "--annotate", "--json"],
+	&["hexdump", "{file}", "--code", "DNA1", "--max-bytes", "20", "--json"],
+	&["mesh", "{file}", "--json"],
+	&["anim", "{file}", "--json"],
+	&["props", "{file}", "--id", "OBCamera", "--json"],
+	&["images", "{file}", "--json"],
+	&["packed", "{file}", "--json"],
+	&["previews", "{file}", "--json"],
+	&["thumb", "{file}", "--json"],
+	&["selftest", "--json"],
+	&["doctor", "--json"],
+];
+
+#[test]
+fn registry_covers_every_structured_command() {
+	let cli = crate::Cli::command();
+	let structured: Vec<&str> = cli
+		.get_subcommands()
+		.filter(|command| command.get_arguments().any(|arg| matches!(arg.get_id().as_str(), "json" | "format")))
+		.map(|command| command.get_name())
+		.collect();
+
+	for name in &structured {
+		assert!(
+			PAYLOADS.iter().any(|payload| payload.command == *name),
+			"`{name}` emits JSON but has no PAYLOADS entry"
+		);
+	}
+	for payload in PAYLOADS {
+		let name = payload.command;
+		assert!(structured.contains(&name), "PAYLOADS entry `{name}` is not a structured subcommand");
+		assert!(
+			(OLDEST..=CURRENT).contains(&payload.since),
+			"`{name}` is registered at unsupported version {}",
+			payload.since
+		);
+
+		let schema: Value = serde_json::from_str(payload.schema).unwrap_or_else(|err| panic!("`{name}` schema is not JSON: {err}"));
+		assert_eq!(schema["title"], format!("blendoc {name} --json"));
+		let variants = schema["oneOf"].as_array().cloned().unwrap_or_else(|| vec![schema.clone()]);
+		for variant in variants {
+			assert_eq!(
+				variant["properties"]["schema_version"]["const"], CURRENT,
+				"`{name}` schema pins another version"
+			);
+		}
+	}
+	assert!(
+		PAYLOADS.windows(2).all(|pair| pair[0].command < pair[1].command),
+		"PAYLOADS must stay sorted by command"
+	);
+}
+
+#[test]
+fn payloads_match_their_schemas() {
+	let dir = std::env::temp_dir().join(format!("blendoc_schema_{}", std::process::id()));
+	std::fs::create_dir_all(&dir).expect("sample dir created");
+	let target = dir.join("scene.blend");
+	std::fs::write(&target, scene_file(HeaderKind::Legacy8, false)).expect("synthetic file written");
+	let target_arg = target.to_string_lossy().into_owned();
+	let dir_arg = dir.to_string_lossy().into_owned();
+	let ids = std::env::temp_dir().join(format!("blendoc_schema_{}.ids", std::process::id()));
+	std::fs::write(&ids, "OBCamera\n").expect("id list written");
+	let ids_arg = ids.to_string_lossy().into_owned();
+
+	let outputs: Vec<(&str, Value)> = SAMPLES
+		.iter()
+		.map(|sample| {
+			let args: Vec<&str> = sample
+				.iter()
+				.map(|arg| match *arg {
+					"{file}" => target_arg.as_str(),
+					"{dir}" => dir_arg.as_str(),
+					"{ids}" => ids_arg.as_str(),
+					_ => arg,
+				})
+				.collect();
+			(sample[0], run_blendoc_json(&args))
+		})
+		.collect();
+	let legacy_ids = run_blendoc_json(&["ids", &target_arg, "--json", "--schema-version", "1"]);
+	let legacy_info = run_blendoc_json(&["info", &target_arg, "--json", "--schema-version", "1"]);
+	let rejected = run_blendoc(&["info", &target_arg, "--json", "--schema-version", "99"]);
+	std::fs::remove_dir_all(&dir).expect("sample dir removed");
+	std::fs::remove_file(&ids).expect("id list removed");
+
+	for (command, json) in &outputs {
+		let payload = PAYLOADS.iter().find(|payload| payload.command == *command).expect("registered command");
+		let schema: Value = serde_json::from_str(payload.schema).expect("schema parses");
+		let mut errors = Vec::new();
+		check(&schema, &schema, json, "$", &mut errors);
+		assert!(
+			errors.is_empty(),
+			"`{command}` output no longer matches schemas/{command}.json; bump its schema version:\n{}",
+			errors.join("\n")
+		);
+	}
+
+	assert!(legacy_ids.as_array().is_some_and(|rows| !rows.is_empty()), "version 1 keeps bare arrays");
+	assert!(legacy_info.get("schema_version").is_none(), "version 1 has no schema_version field");
+	assert!(!rejected.status.success());
+}
+
+#[test]
+fn cached_hits_keep_payloads_on_schema() {
+	let dir = std::env::temp_dir().join(format!("blendoc_schema_cached_{}", std::process::id()));
+	std::fs::create_dir_all(&dir).expect("sample dir created");
+	let target = dir.join("scene.blend");
+	std::fs::write(&target, scene_file(HeaderKind::Legacy8, false)).expect("synthetic file written");
+	let target_arg = target.to_string_lossy().into_owned();
+	let cache_arg = dir.join("cache").to_string_lossy().into_owned();
+
+	let mut hits = Vec::new();
+	for command in ["info", "ids"] {
+		let args = ["--cached", "--cache-dir", cache_arg.as_str(), command, target_arg.as_str(), "--json"];
+		let miss = run_blendoc(&args);
+		let hit = run_blendoc(&args);
+		assert!(miss.status.success() && hit.status.success());
+		assert!(String::from_utf8_lossy(&hit.stderr).contains("cached: true"));
+		hits.push((command, String::from_utf8(hit.stdout).expect("utf-8 json")));
+	}
+	std::fs::remove_dir_all(&dir).expect("sample dir removed");
+
+	for (command, raw) in hits {
+		assert!(
+			raw.starts_with("{\n  \"schema_version\""),
+			"`{command}` hit must lead with schema_version:\n{raw}"
+		);
+		let json: Value = serde_json::from_str(&raw).expect("hit is json");
+		let payload = PAYLOADS.iter().find(|payload| payload.command == command).expect("registered command");
+		let schema: Value = serde_json::from_str(payload.schema).expect("schema parses");
+		let mut errors = Vec::new();
+		check(&schema, &schema, &json, "$", &mut errors);
+		assert!(errors.is_empty(), "cached `{command}` output does not match its schema:\n{}", errors.join("\n"));
+	}
+}
+
+#[test]
+fn git_inputs_carry_their_revision_in_structured_output() {
+	let repo = std::env::temp_dir().join(format!("blendoc_schema_git_{}", std::process::id()));
+	let _ = std::fs::remove_dir_all(&repo);
+	std::fs::create_dir_all(&repo).expect("repo dir created");
+	std::fs::write(repo.join("scene.blend"), scene_file(HeaderKind::Legacy8, false)).expect("synthetic file written");
+	let git = |args: &[&str]| {
+		let output = Command::new("git")
+			.args(["-c", "user.name=blendoc", "-c", "user.email=blendoc@example.invalid"])
+			.args(args)
+			.current_dir(&repo)
+			.output()
+			.expect("git runs");
+		assert!(output.status.success(), "git {args:?}: {}", String::from_utf8_lossy(&output.stderr));
+		String::from_utf8_lossy(&output.stdout).trim().to_owned()
+	};
+	git(&["init", "-q"]);
+	git(&["add", "scene.blend"]);
+	git(&["commit", "-q", "-m", "scene"]);
+	let commit = git(&["rev-parse", "HEAD"]);
+	let blendoc = |args: &[&str]| {
+		let output = Command::new(blendoc_bin()).args(args).current_dir(&repo).output().expect("blendoc runs");
+		assert!(output.status.success(), "{args:?}: {}", String::from_utf8_lossy(&output.stderr));
+		output.stdout
+	};
+	let ids_raw = String::from_utf8(blendoc(&["ids", "--git", "HEAD:scene.blend", "--json"])).expect("utf-8 json");
+	let ids: Value = serde_json::from_str(&ids_raw).expect("ids json");
+	let info: Value = serde_json::from_slice(&blendoc(&["info", "--git", "HEAD:scene.blend", "--json"])).expect("info json");
+	let text = String::from_utf8(blendoc(&["ids", "--git", "HEAD:scene.blend"])).expect("utf-8 text");
+	std::fs::remove_dir_all(&repo).expect("repo removed");
+
+	for (command, json) in [("ids", &ids), ("info", &info)] {
+		let payload = PAYLOADS.iter().find(|payload| payload.command == command).expect("registered command");
+		let schema: Value = serde_json::from_str(payload.schema).expect("schema parses");
+		let mut errors = Vec::new();
+		check(&schema, &schema, json, "$", &mut errors);
+		assert!(errors.is_empty(), "`{command} --git` output does not match its schema:\n{}", errors.join("\n"));
+		assert_eq!(json["revision"]["spec"], "HEAD:scene.blend");
+		assert_eq!(json["revision"]["commit"], commit.as_str());
+	}
+	assert!(ids_raw.starts_with("{\n  \"schema_version\""), "{ids_raw}");
+	assert!(ids["items"].as_array().is_some_and(|rows| !rows.is_empty()));
+	assert!(text.contains(&format!("commit: {commit}")), "{text}");
+}
+
+#[test]
+fn schema_command_prints_registered_schemas() {
+	let schema = run_blendoc_json(&["schema", "orphans"]);
+	assert_eq!(schema["title"], "blendoc orphans --json");
+	assert!(schema.get("schema_version").is_none(), "schemas are printed unstamped");
+
+	let listing = run_blendoc(&["schema"]);
+	let listing = String::from_utf8_lossy(&listing.stdout);
+	assert!(listing.contains("why-big\t2"), "{listing}");
+	assert!(!run_blendoc(&["schema", "nope"]).status.success());
+}
+
+/// Validate `value` against the JSON Schema subset the bundled schemas use:
+/// `$ref` into `$defs`, `anyOf`, `oneOf`, `type`, `const`, `minimum`,
+/// object `properties`/`required`/`additionalProperties`, and array `items`.
+fn check(schema: &Value, root: &Value, value: &Value, path: &str, errors: &mut Vec<String>) {
+	if let Some(target) = schema["$ref"].as_str() {
+		let name = target.strip_prefix("#/$defs/").expect("local $ref");
+		check(&root["$defs"][name], root, value, path, errors);
+		return;
+	}
+	if let Some(options) = schema["anyOf"].as_array() {
+		if !options.iter().any(|option| passes(option, root, value)) {
+			errors.push(format!("{path}: matches no anyOf branch"));
+		}
+		return;
+	}
+	if let Some(options) = schema["oneOf"].as_array() {
+		let matched = options.iter().filter(|option| passes(option, root, value)).count();
+		if matched != 1 {
+			errors.push(format!("{path}: matches {matched} oneOf branches"));
+			for option in options {
+				check(option, root, value, path, errors);
+			}
+		}
+		return;
+	}
+
+	if let Some(expected) = schema.get("type") {
+		let allowed: Vec<&str> = match expected {
+			Value::String(name) => vec![name.as_str()],
+			Value::Array(names) => names.iter().filter_map(Value::as_str).collect(),
+			_ => Vec::new(),
+		};
+		if !allowed.iter().any(|name| is_type(value, name)) {
+			errors.push(format!("{path}: expected {allowed:?}, got {value}"));
+			return;
+		}
+	}
+	if let Some(expected) = schema.get("const")
+		&& value != expected
+	{
+		errors.push(format!("{path}: expected const {expected}, got {value}"));
+	}
+	if let (Some(minimum), Some(number)) = (schema["minimum"].as_f64(), value.as_f64())
+		&& number < minimum
+	{
+		errors.push(format!("{path}: {number} is below {minimum}"));
+	}
+
+	if let Some(object) = value.as_object() {
+		let properties = schema["properties"].as_object();
+		for required in schema["required"].as_array().into_iter().flatten().filter_map(Value::as_str) {
+			if !object.contains_key(required) {
+				errors.push(format!("{path}: missing `{required}`"));
+			}
+		}
+		for (key, item) in object {
+			match properties.and_then(|properties| properties.get(key)) {
+				Some(property) => check(property, root, item, &format!("{path}.{key}"), errors),
+				None => match schema.get("additionalProperties") {
+					Some(Value::Bool(false)) => errors.push(format!("{path}: unexpected `{key}`")),
+					Some(extra @ Value::Object(_)) => check(extra, root, item, &format!("{path}.{key}"), errors),
+					_ => {}
+				},
+			}
+		}
+	}
+	if let (Some(items), Some(array)) = (schema.get("items"), value.as_array()) {
+		for (idx, item) in array.iter().enumerate() {
+			check(items, root, item, &format!("{path}[{idx}]"), errors);
+		}
+	}
+}
+
+fn passes(schema: &Value, root: &Value, value: &Value) -> bool {
+	let mut errors = Vec::new();
+	check(schema, root, value, "", &mut errors);
+	errors.is_empty()
+}
+
+fn is_type(value: &Value, name: &str) -> bool {
+	match name {
+		"object" => value.is_object(),
+		"array" => value.is_array(),
+		"string" => value.is_string(),
+		"integer" => value.is_i64() || value.is_u64(),
+		"number" => value.is_number(),
+		"boolean" => value.is_boolean(),
+		"null" => value.is_null(),
+		_ => false,
+	}
+}
@@ -0,0 +1,190 @@
+use std::path::PathBuf;
+
+use blendoc::blend::{BlendError, BlendFile, ExprColumn, FieldPredicate, SearchOp, SearchOptions, SearchResult, search_blocks};
+
+use crate::cmd::show::value_to_json_value;
+use crate::cmd::util::{emit_json, parse_block_code, ptr_hex, render_code, render_value};
+
+#[derive(clap::Args)]
+pub struct Args {
+	pub file: PathBuf,
+	#[arg(long = "type")]
+	pub type_name: Option<String>,
+	#[arg(long)]
+	pub code: Option<String>,
+	#[arg(long)]
+	pub field: Option<String>,
+	#[arg(long, requires = "field")]
+	pub contains: Option<String>,
+	#[arg(long, requires = "field", conflicts_with = "contains")]
+	pub equals: Option<String>,
+	#[arg(long = "where")]
+	pub where_exprs: Vec<String>,
+	#[arg(long = "col", value_name = "NAME=EXPR")]
+	pub columns: Vec<String>,
+	#[arg(long)]
+	pub limit: Option<usize>,
+	#[arg(long, default_value_t = 1)]
+	pub threads: usize,
+	#[arg(long)]
+	pub json: bool,
+}
+
+/// Decode all typed blocks and print elements matching field predicates.
+///
+/// `--where` takes `field<op>value` predicates or full expressions
+/// (`len(name) > 60`); `--col` adds computed columns (`area=dimx*dimy`).
+pub fn run(args: Args) -> blendoc::blend::Result<()> {
+	let Args {
+		file: path,
+		type_name,
+		code,
+		field,
+		contains,
+		equals,
+		where_exprs,
+		columns,
+		limit,
+		threads,
+		json,
+	} = args;
+
+	let mut options = SearchOptions {
+		type_name,
+		code: code.as_deref().map(parse_block_code).transpose()?,
+		threads,
+		..SearchOptions::default()
+	};
+	if let Some(field) = field {
+		let (op, value) = match (contains, equals) {
+			(Some(value), _) => (SearchOp::Contains, value),
+			(None, Some(value)) => (SearchOp::Eq, value),
+			(None, None) => return Err(BlendError::InvalidSearchPredicate { expr: field }),
+		};
+		options.predicates.push(FieldPredicate { field, op, value });
+	}
+	let mut filters = Vec::new();
+	for expr in &where_exprs {
+		let before = options.filters.len();
+		options.add_where(expr)?;
+		if options.filters.len() > before {
+			filters.push(expr.as_str());
+		}
+	}
+	for column in &columns {
+		options.columns.push(ExprColumn::parse(column)?);
+	}
+	if let Some(limit) = limit {
+		options.max_results = limit;
+	}
+
+	let blend = BlendFile::open(&path)?;
+	let dna = blend.dna()?;
+	let result = search_blocks(&blend, &dna, &options)?;
+
+	if json {
+		print_json(&path, &options, &filters, &result);
+		return Ok(());
+	}
+
+	println!("path: {}", path.display());
+	for predicate in &options.predicates {
+		println!("predicate: {}{}{}", predicate.field, predicate.op.as_str(), predicate.value);
+	}
+	for filter in &filters {
+		println!("predicate: {filter}");
+	}
+	println!("scanned_blocks: {}", result.scanned_blocks);
+	println!("decode_failures: {}", result.decode_failures);
+	println!("hits: {}", result.hits.len());
+	println!("truncated: {}", result.truncated);
+	let column_header: String = options.columns.iter().map(|column| format!("{}\t", column.name)).collect();
+	println!("canonical\tcode\ttype\telement\t{column_header}matches");
+	for hit in &result.hits {
+		let column_values: String = hit.columns.iter().map(|value| format!("{}\t", render_value(value))).collect();
+		let matches = hit
+			.fields
+			.iter()
+			.map(|field| format!("{}={}", field.path, render_value(&field.value)))
+			.collect::<Vec<_>>()
+			.join(", ");
+		println!(
+			"0x{:016x}\t{}\t{}\t{}\t{column_values}{}",
+			hit.canonical,
+			render_code(hit.code),
+			hit.type_name,
+			hit.element_index,
+			matches
+		);
+	}
+
+	Ok(())
+}
+
+fn print_json(path: &std::path::Path, options: &SearchOptions, filters: &[&str], result: &SearchResult) {
+	let payload = SearchJson {
+		path: path.display().to_string(),
+		predicates: options
+			.predicates
+			.iter()
+			.map(|predicate| format!("{}{}{}", predicate.field, predicate.op.as_str(), predicate.value))
+			.chain(filters.iter().map(|filter| (*filter).to_owned()))
+			.collect(),
+		scanned_blocks: result.scanned_blocks,
+		decode_failures: result.decode_failures,
+		truncated: result.truncated,
+		hits: result
+			.hits
+			.iter()
+			.map(|hit| SearchHitJson {
+				canonical: ptr_hex(hit.canonical),
+				code: render_code(hit.code),
+				type_name: hit.type_name.to_string(),
+				element: hit.element_index,
+				matches: hit
+					.fields
+					.iter()
+					.map(|field| SearchFieldJson {
+						path: field.path.to_string(),
+						value: value_to_json_value(&field.value),
+					})
+					.collect(),
+				columns: options
+					.columns
+					.iter()
+					.zip(&hit.columns)
+					.map(|(column, value)| (column.name.to_string(), value_to_json_value(value)))
+					.collect(),
+			})
+			.collect(),
+	};
+
+	emit_json(&payload);
+}
+
+#[derive(serde::Serialize)]
+struct SearchFieldJson {
+	path: String,
+	value: serde_json::Value,
+}
+
+#[derive(serde::Serialize)]
+struct SearchHitJson {
+	canonical: String,
+	code: String,
+	#[serde(rename = "type")]
+	type_name: String,
+	element: usize,
+	matches: Vec<SearchFieldJson>,
+	columns: serde_json::Map<String, serde_json::Value>,
+}
+
+#[derive(serde::Serialize)]
+struct SearchJson {
+	path: String,
+	predicates: Vec<String>,
+	scanned_blocks: usize,
+	decode_failures: usize,
+	truncated: bool,
+	hits: Vec<SearchHitJson>,
+}
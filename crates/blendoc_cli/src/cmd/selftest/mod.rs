@@ -0,0 +1,368 @@
+use blendoc::blend::{
+	BlendFile, ChasePolicy, Compression, DecodeOptions, Dna, Endianness, FieldPath, FieldPredicate, IdGraphOptions, IdIndex, PointerStorage, RefScanOptions,
+	RouteOptions, SearchOptions, Value, XrefOptions, build_id_graph, chase_from_ptr, codes, decode_bytes, decode_ptr_instance, encode_bytes,
+	find_inbound_refs_to_ptr, find_route_between_ptrs, scan_id_blocks, scan_refs_from_ptr, scan_size_stats, search_blocks,
+};
+use blendoc_testkit::builder::{HeaderKind, SCENE_ID_NAMES, SCENE_OBJECT_LOC, SCENE_VERT_COUNT, scene_file, scene_ptrs, scene_version};
+
+use crate::cmd::util::emit_json;
+
+#[derive(clap::Args)]
+pub struct Args {
+	#[arg(long)]
+	pub json: bool,
+	#[arg(long)]
+	pub variant: Option<String>,
+}
+
+/// One synthetic file shape exercised by the self-test.
+pub(crate) struct Variant {
+	name: &'static str,
+	kind: HeaderKind,
+	big_endian: bool,
+	pub(crate) compression: Compression,
+}
+
+pub(crate) const VARIANTS: &[Variant] = &[
+	Variant {
+		name: "legacy-bhead4",
+		kind: HeaderKind::Legacy4,
+		big_endian: false,
+		compression: Compression::None,
+	},
+	Variant {
+		name: "legacy-bhead8",
+		kind: HeaderKind::Legacy8,
+		big_endian: false,
+		compression: Compression::None,
+	},
+	Variant {
+		name: "legacy-bhead8-gzip",
+		kind: HeaderKind::Legacy8,
+		big_endian: false,
+		compression: Compression::Gzip,
+	},
+	Variant {
+		name: "legacy-bhead8-zstd",
+		kind: HeaderKind::Legacy8,
+		big_endian: false,
+		compression: Compression::Zstd,
+	},
+	Variant {
+		name: "legacy-bhead4-big-endian",
+		kind: HeaderKind::Legacy4,
+		big_endian: true,
+		compression: Compression::None,
+	},
+	Variant {
+		name: "legacy-bhead8-big-endian",
+		kind: HeaderKind::Legacy8,
+		big_endian: true,
+		compression: Compression::None,
+	},
+	Variant {
+		name: "stable-ids",
+		kind: HeaderKind::Large,
+		big_endian: false,
+		compression: Compression::None,
+	},
+	Variant {
+		name: "stable-ids-zstd",
+		kind: HeaderKind::Large,
+		big_endian: false,
+		compression: Compression::Zstd,
+	},
+];
+
+type Check = std::result::Result<(), String>;
+type CheckFn = fn(&Variant, &Env) -> Check;
+
+/// Opened synthetic file shared by the per-feature checks of one variant.
+struct Env {
+	blend: BlendFile,
+	dna: Dna,
+	ids: IdIndex,
+}
+
+const CHECKS: &[(&str, CheckFn)] = &[
+	("header", check_header),
+	("blocks", check_blocks),
+	("pointer_index", check_pointer_index),
+	("ids", check_ids),
+	("decode", check_decode),
+	("refs", check_refs),
+	("chase", check_chase),
+	("xref", check_xref),
+	("route", check_route),
+	("idgraph", check_idgraph),
+	("search", check_search),
+	("stats", check_stats),
+	("recompress", check_recompress),
+];
+
+/// Build synthetic files for each container variant, exercise the library surface, and report per-feature results.
+///
+/// Exits with status 1 when any check fails.
+pub fn run(args: Args) -> blendoc::blend::Result<()> {
+	let Args { json, variant } = args;
+
+	let mut reports = Vec::new();
+	for item in VARIANTS.iter().filter(|item| variant.as_deref().is_none_or(|name| name == item.name)) {
+		reports.push(run_variant(item));
+	}
+	if reports.is_empty() {
+		let known: Vec<_> = VARIANTS.iter().map(|item| item.name).collect();
+		return Err(blendoc::blend::BlendError::Io(std::io::Error::other(format!(
+			"unknown selftest variant; expected one of: {}",
+			known.join(", ")
+		))));
+	}
+
+	let total: usize = reports.iter().map(|item| item.checks.len()).sum();
+	let failed: usize = reports.iter().map(|item| item.checks.iter().filter(|check| !check.ok).count()).sum();
+
+	if json {
+		emit_json(&SelftestJson {
+			version: env!("CARGO_PKG_VERSION"),
+			passed: total - failed,
+			failed,
+			variants: reports,
+		});
+	} else {
+		println!("blendoc selftest {}", env!("CARGO_PKG_VERSION"));
+		for report in &reports {
+			let passed = report.checks.iter().filter(|check| check.ok).count();
+			println!("variant {}: {passed}/{} passed", report.name, report.checks.len());
+			for check in &report.checks {
+				match &check.message {
+					Some(message) => println!("  FAIL {}: {message}", check.feature),
+					None => println!("  ok   {}", check.feature),
+				}
+			}
+		}
+		println!("summary: {}/{total} passed", total - failed);
+	}
+
+	if failed > 0 {
+		std::process::exit(1);
+	}
+	Ok(())
+}
+
+pub(crate) fn run_variant(variant: &Variant) -> VariantJson {
+	let mut checks = Vec::new();
+	let env = open_variant(variant);
+	checks.push(CheckJson::from_result("open", env.as_ref().map(|_| ()).map_err(Clone::clone)));
+
+	for (feature, check) in CHECKS {
+		let result = match &env {
+			Ok(env) => check(variant, env),
+			Err(_) => Err("skipped: open failed".to_owned()),
+		};
+		checks.push(CheckJson::from_result(feature, result));
+	}
+
+	VariantJson {
+		name: variant.name,
+		compression: variant.compression.as_str(),
+		endianness: if variant.big_endian { "big" } else { "little" },
+		pointer_size: variant.kind.pointer_size(),
+		checks,
+	}
+}
+
+fn open_variant(variant: &Variant) -> std::result::Result<Env, String> {
+	let raw = scene_file(variant.kind, variant.big_endian);
+	let bytes = encode_bytes(&raw, variant.compression, None).map_err(|err| err.to_string())?;
+	let blend = BlendFile::from_bytes(bytes).map_err(|err| err.to_string())?;
+	ensure(blend.compression == variant.compression, || {
+		format!("compression detected as {}", blend.compression.as_str())
+	})?;
+	let dna = blend.dna().map_err(|err| format!("dna: {err}"))?;
+	let ids = IdIndex::build(scan_id_blocks(&blend, &dna).map_err(|err| format!("id scan: {err}"))?);
+	Ok(Env { blend, dna, ids })
+}
+
+fn check_header(variant: &Variant, env: &Env) -> Check {
+	let header = env.blend.header;
+	ensure(header.version == scene_version(variant.kind), || format!("version {}", header.version))?;
+	ensure(header.pointer_size == variant.kind.pointer_size(), || {
+		format!("pointer_size {}", header.pointer_size)
+	})?;
+	let endianness = if variant.big_endian { Endianness::Big } else { Endianness::Little };
+	ensure(header.endianness == endianness, || format!("endianness {}", header.endianness.as_str()))
+}
+
+fn check_blocks(_variant: &Variant, env: &Env) -> Check {
+	let stats = env.blend.scan_block_stats().map_err(|err| err.to_string())?;
+	ensure(stats.block_count == 7, || format!("block_count {}", stats.block_count))?;
+	ensure(stats.has_dna1 && stats.has_endb, || "missing DNA1 or ENDB".to_owned())
+}
+
+fn check_pointer_index(variant: &Variant, env: &Env) -> Check {
+	let index = env.blend.pointer_index().map_err(|err| err.to_string())?;
+	let expected = match variant.kind {
+		HeaderKind::Large => PointerStorage::StableIds,
+		HeaderKind::Legacy4 | HeaderKind::Legacy8 => PointerStorage::AddressRanges,
+	};
+	ensure(index.storage() == expected, || "unexpected pointer storage".to_owned())?;
+	for ptr in scene_ptrs(variant.kind) {
+		ensure(index.canonical_ptr(&env.dna, ptr) == Some(ptr), || format!("0x{ptr:x} does not resolve"))?;
+	}
+	Ok(())
+}
+
+fn check_ids(_variant: &Variant, env: &Env) -> Check {
+	let names: Vec<&str> = env.ids.records.iter().map(|item| item.id_name.as_ref()).collect();
+	ensure(names == SCENE_ID_NAMES, || format!("ids {names:?}"))
+}
+
+fn check_decode(variant: &Variant, env: &Env) -> Check {
+	let index = env.blend.pointer_index().map_err(|err| err.to_string())?;
+	let (_, object) = decode_ptr_instance(&env.dna, &index, scene_ptrs(variant.kind)[1], &DecodeOptions::default()).map_err(|err| err.to_string())?;
+	let loc = object.fields.iter().find(|field| field.name.as_ref() == "loc").map(|field| &field.value);
+	let Some(Value::Array(items)) = loc else {
+		return Err("Object.loc missing".to_owned());
+	};
+	let values: Vec<f32> = items
+		.iter()
+		.filter_map(|item| match item {
+			Value::F32(value) => Some(*value),
+			_ => None,
+		})
+		.collect();
+	ensure(values == SCENE_OBJECT_LOC, || format!("Object.loc {values:?}"))
+}
+
+fn check_refs(variant: &Variant, env: &Env) -> Check {
+	let index = env.blend.pointer_index().map_err(|err| err.to_string())?;
+	let ptrs = scene_ptrs(variant.kind);
+	let refs = scan_refs_from_ptr(&env.dna, &index, &env.ids, ptrs[0], &RefScanOptions::default()).map_err(|err| err.to_string())?;
+	for (field, target) in [("camera", ptrs[1]), ("world", ptrs[4])] {
+		ensure(
+			refs.iter()
+				.any(|item| item.field.as_ref() == field && item.resolved.as_ref().is_some_and(|resolved| resolved.canonical == target)),
+			|| format!("Scene.{field} not resolved"),
+		)?;
+	}
+	Ok(())
+}
+
+fn check_chase(variant: &Variant, env: &Env) -> Check {
+	let index = env.blend.pointer_index().map_err(|err| err.to_string())?;
+	let path = FieldPath::parse("camera.data.mvert").map_err(|err| err.to_string())?;
+	let result = chase_from_ptr(
+		&env.dna,
+		&index,
+		scene_ptrs(variant.kind)[0],
+		&path,
+		&DecodeOptions::default(),
+		&ChasePolicy::default(),
+	)
+	.map_err(|err| err.to_string())?;
+	ensure(result.stop.is_none(), || {
+		format!("stopped: {:?}", result.stop.as_ref().map(|stop| &stop.reason))
+	})?;
+	ensure(result.hops.len() == 4, || format!("{} hops", result.hops.len()))?;
+	ensure(matches!(&result.value, Value::Struct(item) if item.type_name.as_ref() == "MVert"), || {
+		"camera.data.mvert did not reach an MVert".to_owned()
+	})
+}
+
+fn check_xref(variant: &Variant, env: &Env) -> Check {
+	let index = env.blend.pointer_index().map_err(|err| err.to_string())?;
+	let inbound = find_inbound_refs_to_ptr(&env.dna, &index, &env.ids, scene_ptrs(variant.kind)[4], &XrefOptions::default()).map_err(|err| err.to_string())?;
+	ensure(
+		inbound
+			.iter()
+			.any(|item| item.field.as_ref() == "world" && item.from_id.as_deref() == Some(SCENE_ID_NAMES[0])),
+		|| format!("{} inbound refs, none from Scene.world", inbound.len()),
+	)
+}
+
+fn check_route(variant: &Variant, env: &Env) -> Check {
+	let index = env.blend.pointer_index().map_err(|err| err.to_string())?;
+	let ptrs = scene_ptrs(variant.kind);
+	let result = find_route_between_ptrs(&env.dna, &index, &env.ids, ptrs[0], ptrs[2], &RouteOptions::default()).map_err(|err| err.to_string())?;
+	let fields: Vec<&str> = result.path.iter().flatten().map(|edge| edge.field.as_ref()).collect();
+	ensure(fields == ["camera", "data"], || format!("route {fields:?}"))
+}
+
+fn check_idgraph(_variant: &Variant, env: &Env) -> Check {
+	let index = env.blend.pointer_index().map_err(|err| err.to_string())?;
+	let graph = build_id_graph(&env.dna, &index, &env.ids, &IdGraphOptions::default()).map_err(|err| err.to_string())?;
+	ensure(graph.nodes.len() == 4 && graph.edges.len() == 3, || {
+		format!("{} nodes, {} edges", graph.nodes.len(), graph.edges.len())
+	})
+}
+
+fn check_search(_variant: &Variant, env: &Env) -> Check {
+	let options = SearchOptions {
+		predicates: vec![FieldPredicate::parse("name~=Camera").map_err(|err| err.to_string())?],
+		..SearchOptions::default()
+	};
+	let result = search_blocks(&env.blend, &env.dna, &options).map_err(|err| err.to_string())?;
+	ensure(result.hits.len() == 2, || format!("{} hits for name~=Camera", result.hits.len()))
+}
+
+fn check_stats(_variant: &Variant, env: &Env) -> Check {
+	let stats = scan_size_stats(&env.blend, &env.dna, &env.ids).map_err(|err| err.to_string())?;
+	let mesh = stats.id_types.iter().find(|item| item.code == codes::ID_ME);
+	ensure(mesh.is_some_and(|item| item.data_blocks == 1), || "mesh DATA block not attributed".to_owned())?;
+	let verts = stats.types.iter().find(|item| item.type_name.as_ref() == "MVert");
+	ensure(verts.is_some_and(|item| item.elements == SCENE_VERT_COUNT), || {
+		"MVert elements miscounted".to_owned()
+	})
+}
+
+fn check_recompress(variant: &Variant, _env: &Env) -> Check {
+	let raw = scene_file(variant.kind, variant.big_endian);
+	for target in [Compression::None, Compression::Gzip, Compression::Zstd] {
+		let encoded = encode_bytes(&raw, target, None).map_err(|err| err.to_string())?;
+		let (mode, roundtrip) = decode_bytes(encoded).map_err(|err| err.to_string())?;
+		ensure(mode == target && roundtrip == raw, || format!("{} roundtrip differs", target.as_str()))?;
+	}
+	Ok(())
+}
+
+fn ensure(condition: bool, message: impl FnOnce() -> String) -> Check {
+	if condition { Ok(()) } else { Err(message()) }
+}
+
+#[derive(serde::Serialize)]
+struct SelftestJson {
+	version: &'static str,
+	passed: usize,
+	failed: usize,
+	variants: Vec<VariantJson>,
+}
+
+#[derive(serde::Serialize)]
+pub(crate) struct VariantJson {
+	pub(crate) name: &'static str,
+	compression: &'static str,
+	endianness: &'static str,
+	pointer_size: usize,
+	pub(crate) checks: Vec<CheckJson>,
+}
+
+#[derive(serde::Serialize)]
+pub(crate) struct CheckJson {
+	pub(crate) feature: &'static str,
+	pub(crate) ok: bool,
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub(crate) message: Option<String>,
+}
+
+impl CheckJson {
+	fn from_result(feature: &'static str, result: Check) -> Self {
+		Self {
+			feature,
+			ok: result.is_ok(),
+			message: result.err(),
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,11 @@
+use super::{VARIANTS, run_variant};
+
+#[test]
+fn every_variant_passes_every_check() {
+	for variant in VARIANTS {
+		let report = run_variant(variant);
+		for check in &report.checks {
+			assert!(check.ok, "{} {}: {:?}", report.name, check.feature, check.message);
+		}
+	}
+}
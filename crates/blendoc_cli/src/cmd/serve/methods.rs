@@ -0,0 +1,373 @@
+use std::path::PathBuf;
+
+use blendoc::blend::{
+	BlendError, CachedFile, ChasePolicy, DecodeOptions, FieldPath, FileCache, GraphOptions, Value, WarningCollector, build_graph_from_ptr, chase_from_ptr,
+	decode_ptr_instance,
+};
+use serde::Deserialize;
+use serde::de::DeserializeOwned;
+
+use crate::cmd::ids::{IdFilter, json_rows, link_confidences};
+use crate::cmd::input::BlendInput;
+use crate::cmd::route::{RouteQuery, find_route, json_payload as route_json, parse_route_target, route_options};
+use crate::cmd::schema::to_current_value;
+use crate::cmd::show::{JsonRootMeta, path_json, struct_json};
+use crate::cmd::util::{library_namespaces, parse_root_selector, resolve_root, stop_hint};
+use crate::cmd::{graph, info};
+
+/// JSON-RPC error object.
+pub(super) struct RpcError {
+	pub code: i64,
+	pub message: String,
+}
+
+impl RpcError {
+	pub(super) fn parse(err: serde_json::Error) -> Self {
+		Self {
+			code: -32700,
+			message: format!("parse error: {err}"),
+		}
+	}
+
+	pub(super) fn invalid_request(message: &str) -> Self {
+		Self {
+			code: -32600,
+			message: format!("invalid request: {message}"),
+		}
+	}
+
+	fn method_not_found(method: &str) -> Self {
+		Self {
+			code: -32601,
+			message: format!("unknown method `{method}`"),
+		}
+	}
+
+	fn invalid_params(err: serde_json::Error) -> Self {
+		Self {
+			code: -32602,
+			message: format!("invalid params: {err}"),
+		}
+	}
+}
+
+impl From<BlendError> for RpcError {
+	fn from(err: BlendError) -> Self {
+		Self {
+			code: -32000,
+			message: err.to_string(),
+		}
+	}
+}
+
+type RpcResult = std::result::Result<serde_json::Value, RpcError>;
+
+/// Dispatch one method call; results mirror the command's `--json` payload.
+pub(super) fn call(cache: &FileCache, method: &str, params: serde_json::Value) -> RpcResult {
+	match method {
+		"info" => info_method(cache, parse_params(params)?),
+		"ids" => ids_method(cache, parse_params(params)?),
+		"show" => show_method(cache, parse_params(params)?),
+		"graph" => graph_method(cache, parse_params(params)?),
+		"route" => route_method(cache, parse_params(params)?),
+		"files" => Ok(files_method(cache)),
+		"evict" => {
+			let FileParams { file } = parse_params(params)?;
+			Ok(serde_json::json!({ "evicted": cache.evict(file) }))
+		}
+		_ => Err(RpcError::method_not_found(method)),
+	}
+}
+
+fn parse_params<T: DeserializeOwned>(params: serde_json::Value) -> std::result::Result<T, RpcError> {
+	// Omitted params read as an empty object so all-optional methods need none.
+	let params = if params.is_null() { serde_json::json!({}) } else { params };
+	serde_json::from_value(params).map_err(RpcError::invalid_params)
+}
+
+fn to_result<T: serde::Serialize>(payload: &T) -> RpcResult {
+	to_current_value(payload).map_err(|err| BlendError::Io(std::io::Error::other(err)).into())
+}
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct FileParams {
+	file: PathBuf,
+}
+
+fn info_method(cache: &FileCache, FileParams { file }: FileParams) -> RpcResult {
+	let loaded = cache.get(&file)?;
+	let input = BlendInput::Path(file);
+	let payload = info::json_payload(&loaded.blend, input.label(), None, input.modified(None), Vec::new())?;
+	to_result(&payload)
+}
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct IdsParams {
+	file: PathBuf,
+	code: Option<String>,
+	#[serde(rename = "type")]
+	type_name: Option<String>,
+	#[serde(rename = "match")]
+	pattern: Option<String>,
+	#[serde(default)]
+	ignore_case: bool,
+	limit: Option<usize>,
+	#[serde(default)]
+	annotate_libs: bool,
+}
+
+fn ids_method(cache: &FileCache, params: IdsParams) -> RpcResult {
+	let IdsParams {
+		file,
+		code,
+		type_name,
+		pattern,
+		ignore_case,
+		limit,
+		annotate_libs,
+	} = params;
+	let loaded = cache.get(&file)?;
+	let mut rows = loaded.ids.records.clone();
+	IdFilter {
+		code,
+		type_name,
+		pattern,
+		ignore_case,
+		limit,
+	}
+	.apply(&mut rows)?;
+	let links = link_confidences(&loaded.blend, &loaded.dna)?;
+	let namespaces = library_namespaces(&loaded.blend, &loaded.dna, annotate_libs)?;
+	to_result(&json_rows(&rows, &links, &namespaces, false))
+}
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct ShowParams {
+	file: PathBuf,
+	id: Option<String>,
+	ptr: Option<String>,
+	code: Option<String>,
+	path: Option<String>,
+	#[serde(default)]
+	trace: bool,
+	max_depth: Option<u32>,
+	max_array: Option<usize>,
+}
+
+fn show_method(cache: &FileCache, params: ShowParams) -> RpcResult {
+	let ShowParams {
+		file,
+		id,
+		ptr,
+		code,
+		path,
+		trace,
+		max_depth,
+		max_array,
+	} = params;
+	let selector = parse_root_selector(code, ptr, id)?;
+	let loaded = cache.get(&file)?;
+	let CachedFile { blend, dna, ids, .. } = loaded.as_ref();
+	let index = loaded.pointer_index()?;
+	let (root_ptr, root_label) = resolve_root(blend, ids, selector)?;
+
+	let warnings = WarningCollector::new();
+	let mut decode = DecodeOptions {
+		warnings: Some(warnings.clone()),
+		..DecodeOptions::default()
+	};
+	if let Some(max_depth) = max_depth {
+		decode.max_depth = max_depth;
+	}
+	if let Some(max_array) = max_array {
+		decode.max_array_elems = max_array;
+	}
+
+	let root_canonical = index.canonical_ptr(dna, root_ptr).unwrap_or(root_ptr);
+	let root_link = link_confidences(blend, dna)?.remove(&root_canonical);
+
+	if let Some(path_expr) = path {
+		let field_path = FieldPath::parse(&path_expr)?;
+		let result = chase_from_ptr(dna, &index, root_ptr, &field_path, &decode, &ChasePolicy::default())?;
+		let hint = result.stop.as_ref().and_then(|stop| stop_hint(dna, blend.header.version, &stop.reason));
+		let decode_warnings = warnings.take();
+		let root = JsonRootMeta {
+			path: &file,
+			root_label: &root_label,
+			root_ptr,
+			root_link: root_link.as_ref(),
+			warnings: &decode_warnings,
+		};
+		let stop = result.stop.as_ref();
+		return to_result(&path_json(
+			&root,
+			&path_expr,
+			&result.value,
+			stop,
+			hint.as_deref(),
+			trace.then_some(&result.hops),
+		));
+	}
+
+	let (canonical, value) = decode_ptr_instance(dna, &index, root_ptr, &decode)?;
+	let decode_warnings = warnings.take();
+	let root = JsonRootMeta {
+		path: &file,
+		root_label: &root_label,
+		root_ptr,
+		root_link: root_link.as_ref().filter(|_| canonical == root_canonical),
+		warnings: &decode_warnings,
+	};
+	let id_name = ids.get_by_ptr(canonical).map(|item| item.id_name.as_ref());
+	to_result(&struct_json(&root, canonical, id_name, &Value::Struct(value), None))
+}
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct GraphParams {
+	file: PathBuf,
+	id: Option<String>,
+	ptr: Option<String>,
+	code: Option<String>,
+	depth: Option<u32>,
+	refs_depth: Option<u32>,
+	max_nodes: Option<usize>,
+	max_edges: Option<usize>,
+	#[serde(default)]
+	id_only: bool,
+	#[serde(default)]
+	include_type: Vec<String>,
+	#[serde(default)]
+	exclude_type: Vec<String>,
+	#[serde(default)]
+	include_code: Vec<String>,
+	#[serde(default)]
+	exclude_code: Vec<String>,
+	#[serde(default)]
+	include_field: Vec<String>,
+	#[serde(default)]
+	exclude_field: Vec<String>,
+	#[serde(default)]
+	annotate_libs: bool,
+}
+
+fn graph_method(cache: &FileCache, params: GraphParams) -> RpcResult {
+	let GraphParams {
+		file,
+		id,
+		ptr,
+		code,
+		depth,
+		refs_depth,
+		max_nodes,
+		max_edges,
+		id_only,
+		include_type,
+		exclude_type,
+		include_code,
+		exclude_code,
+		include_field,
+		exclude_field,
+		annotate_libs,
+	} = params;
+	let selector = parse_root_selector(code, ptr, id)?;
+	let loaded = cache.get(&file)?;
+	let index = loaded.pointer_index()?;
+	let (root_ptr, root_label) = resolve_root(&loaded.blend, &loaded.ids, selector)?;
+
+	let filter = graph::FilterArgs {
+		include_type,
+		exclude_type,
+		include_code,
+		exclude_code,
+		include_field,
+		exclude_field,
+	};
+	let mut options = GraphOptions {
+		id_only,
+		ref_cache: Some(loaded.ref_cache.clone()),
+		filter: filter.filter()?,
+		..GraphOptions::default()
+	};
+	if let Some(depth) = depth {
+		options.max_depth = depth;
+	}
+	if let Some(refs_depth) = refs_depth {
+		options.ref_scan.max_depth = refs_depth;
+	}
+	if let Some(max_nodes) = max_nodes {
+		options.max_nodes = max_nodes;
+	}
+	if let Some(max_edges) = max_edges {
+		options.max_edges = max_edges;
+	}
+
+	let result = build_graph_from_ptr(&loaded.dna, &index, &loaded.ids, root_ptr, &options)?;
+	let namespaces = library_namespaces(&loaded.blend, &loaded.dna, annotate_libs)?;
+	to_result(&graph::json_payload(&file, &root_label, root_ptr, &result, &namespaces))
+}
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct RouteParams {
+	file: PathBuf,
+	from_id: Option<String>,
+	from_ptr: Option<String>,
+	from_code: Option<String>,
+	to_id: Option<String>,
+	to_ptr: Option<String>,
+	to_code: Option<String>,
+	depth: Option<u32>,
+	refs_depth: Option<u32>,
+	max_nodes: Option<usize>,
+	max_edges: Option<usize>,
+	max_routes: Option<usize>,
+}
+
+fn route_method(cache: &FileCache, params: RouteParams) -> RpcResult {
+	let RouteParams {
+		file,
+		from_id,
+		from_ptr,
+		from_code,
+		to_id,
+		to_ptr,
+		to_code,
+		depth,
+		refs_depth,
+		max_nodes,
+		max_edges,
+		max_routes,
+	} = params;
+	let from = parse_root_selector(from_code, from_ptr, from_id)?;
+	let to = parse_route_target(to_id, to_ptr, to_code)?;
+	let loaded = cache.get(&file)?;
+	let index = loaded.pointer_index()?;
+
+	let mut options = route_options(depth, refs_depth, max_nodes, max_edges, max_routes);
+	options.ref_cache = Some(loaded.ref_cache.clone());
+	let RouteQuery {
+		from_label,
+		to_label,
+		from_meta,
+		to_meta,
+		result,
+		routes,
+	} = find_route(&loaded.blend, &loaded.dna, &index, &loaded.ids, from, to, &options)?;
+	to_result(&route_json(&file, &from_label, &to_label, &from_meta, to_meta.as_ref(), &result, &routes))
+}
+
+fn files_method(cache: &FileCache) -> serde_json::Value {
+	let stats = cache.stats();
+	let files: Vec<String> = cache.paths().iter().map(|path| path.display().to_string()).collect();
+	serde_json::json!({
+		"files": files,
+		"hits": stats.hits,
+		"misses": stats.misses,
+		"reloads": stats.reloads,
+		"evictions": stats.evictions,
+	})
+}
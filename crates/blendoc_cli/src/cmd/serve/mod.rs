@@ -0,0 +1,158 @@
+use std::io::{BufRead, BufReader, Write};
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+use blendoc::blend::{BlendError, FileCache, Result};
+
+use self::methods::{RpcError, call};
+
+#[derive(clap::Args)]
+pub struct Args {
+	#[arg(long, required_unless_present = "stdio", conflicts_with = "stdio")]
+	pub socket: Option<PathBuf>,
+	#[arg(long)]
+	pub stdio: bool,
+	#[arg(long = "max-files", default_value_t = 8)]
+	pub max_files: usize,
+}
+
+/// Answer newline-delimited JSON-RPC 2.0 requests against cached parsed files.
+pub fn run(args: Args) -> Result<()> {
+	let Args { socket, stdio, max_files } = args;
+	let server = Arc::new(Server {
+		cache: FileCache::new(max_files),
+		shutdown: AtomicBool::new(false),
+	});
+
+	match socket {
+		Some(socket) if !stdio => serve_socket(&server, &socket),
+		_ => server.serve_lines(std::io::stdin().lock(), crate::cmd::quiet::stdout()),
+	}
+}
+
+/// State shared by every connection.
+struct Server {
+	cache: FileCache,
+	shutdown: AtomicBool,
+}
+
+impl Server {
+	/// Answer one request per line until EOF or a `shutdown` request.
+	fn serve_lines(&self, input: impl BufRead, mut output: impl Write) -> Result<()> {
+		for line in input.lines() {
+			let line = line?;
+			if line.trim().is_empty() {
+				continue;
+			}
+			if let Some(response) = self.handle_line(&line) {
+				writeln!(output, "{response}")?;
+				output.flush()?;
+			}
+			if self.shutdown.load(Ordering::Relaxed) {
+				break;
+			}
+		}
+		Ok(())
+	}
+
+	/// Response line for one request line; `None` for notifications.
+	fn handle_line(&self, line: &str) -> Option<serde_json::Value> {
+		let request: serde_json::Value = match serde_json::from_str(line) {
+			Ok(request) => request,
+			Err(err) => return Some(error_response(serde_json::Value::Null, RpcError::parse(err))),
+		};
+		let id = request.get("id").cloned();
+		let response_id = id.clone().unwrap_or(serde_json::Value::Null);
+
+		let Some(method) = request.get("method").and_then(serde_json::Value::as_str) else {
+			return Some(error_response(response_id, RpcError::invalid_request("missing `method`")));
+		};
+		if request.get("jsonrpc").is_some_and(|version| version != "2.0") {
+			return Some(error_response(response_id, RpcError::invalid_request("`jsonrpc` must be \"2.0\"")));
+		}
+		let params = request.get("params").cloned().unwrap_or(serde_json::Value::Null);
+
+		let outcome = if method == "shutdown" {
+			self.shutdown.store(true, Ordering::Relaxed);
+			Ok(serde_json::Value::Null)
+		} else {
+			call(&self.cache, method, params)
+		};
+
+		id.map(|id| match outcome {
+			Ok(result) => serde_json::json!({ "jsonrpc": "2.0", "id": id, "result": result }),
+			Err(err) => error_response(id, err),
+		})
+	}
+}
+
+fn error_response(id: serde_json::Value, err: RpcError) -> serde_json::Value {
+	serde_json::json!({
+		"jsonrpc": "2.0",
+		"id": id,
+		"error": { "code": err.code, "message": err.message },
+	})
+}
+
+#[cfg(unix)]
+fn serve_socket(server: &Arc<Server>, socket: &std::path::Path) -> Result<()> {
+	use std::os::unix::net::{UnixListener, UnixStream};
+
+	if socket.exists() {
+		if UnixStream::connect(socket).is_ok() {
+			return Err(BlendError::Io(std::io::Error::new(
+				std::io::ErrorKind::AddrInUse,
+				format!("{} is already being served", socket.display()),
+			)));
+		}
+		// Left behind by a server that did not exit cleanly.
+		std::fs::remove_file(socket)?;
+	}
+
+	let listener = UnixListener::bind(socket)?;
+	eprintln!("serving on {}", socket.display());
+	for stream in listener.incoming() {
+		if server.shutdown.load(Ordering::Relaxed) {
+			break;
+		}
+		let stream = match stream {
+			Ok(stream) => stream,
+			Err(err) => {
+				eprintln!("warning: accept failed: {err}");
+				continue;
+			}
+		};
+		let server = server.clone();
+		let socket = socket.to_path_buf();
+		std::thread::spawn(move || {
+			let reader = match stream.try_clone() {
+				Ok(reader) => BufReader::new(reader),
+				Err(err) => return eprintln!("warning: connection setup failed: {err}"),
+			};
+			if let Err(err) = server.serve_lines(reader, &stream) {
+				eprintln!("warning: connection closed: {err}");
+			}
+			if server.shutdown.load(Ordering::Relaxed) {
+				// Wake the accept loop so it sees the flag.
+				let _ = UnixStream::connect(&socket);
+			}
+		});
+	}
+
+	std::fs::remove_file(socket)?;
+	Ok(())
+}
+
+#[cfg(not(unix))]
+fn serve_socket(_server: &Arc<Server>, _socket: &std::path::Path) -> Result<()> {
+	Err(BlendError::Io(std::io::Error::new(
+		std::io::ErrorKind::Unsupported,
+		"--socket needs Unix domain sockets; use --stdio",
+	)))
+}
+
+mod methods;
+
+#[cfg(test)]
+mod tests;
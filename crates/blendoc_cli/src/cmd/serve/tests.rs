@@ -0,0 +1,90 @@
+use blendoc_testkit::builder::SCENE_ID_NAMES;
+
+use crate::cmd::test_support::{TempBlend, run_blendoc, run_blendoc_with_stdin};
+
+fn responses(stdout: &[u8]) -> Vec<serde_json::Value> {
+	String::from_utf8_lossy(stdout)
+		.lines()
+		.map(|line| serde_json::from_str(line).expect("each response line is json"))
+		.collect()
+}
+
+#[test]
+fn stdio_requests_mirror_command_json() {
+	let target = TempBlend::scene("serve_stdio");
+	let file = target.arg();
+
+	let requests = [
+		serde_json::json!({ "jsonrpc": "2.0", "id": 1, "method": "ids", "params": { "file": file } }),
+		serde_json::json!({ "jsonrpc": "2.0", "id": 2, "method": "show", "params": { "file": file, "id": SCENE_ID_NAMES[1], "path": "data" } }),
+		serde_json::json!({ "jsonrpc": "2.0", "id": 3, "method": "route", "params": { "file": file, "from_id": SCENE_ID_NAMES[0], "to_id": SCENE_ID_NAMES[2] } }),
+		serde_json::json!({ "jsonrpc": "2.0", "method": "files" }),
+		serde_json::json!({ "jsonrpc": "2.0", "id": 4, "method": "files" }),
+		serde_json::json!({ "jsonrpc": "2.0", "id": 5, "method": "show", "params": { "file": file, "bogus": true } }),
+		serde_json::json!({ "jsonrpc": "2.0", "id": 6, "method": "nope" }),
+		serde_json::json!({ "jsonrpc": "2.0", "id": 7, "method": "shutdown" }),
+		serde_json::json!({ "jsonrpc": "2.0", "id": 8, "method": "files" }),
+	];
+	let stdin: String = requests.iter().map(|request| format!("{request}\n")).collect();
+	let served = run_blendoc_with_stdin(&["serve", "--stdio"], format!("not json\n{stdin}").as_bytes());
+	let cli = run_blendoc(&["ids", file, "--json"]);
+
+	assert!(served.status.success(), "serve failed: {}", String::from_utf8_lossy(&served.stderr));
+	let responses = responses(&served.stdout);
+	assert_eq!(responses.len(), 8, "notifications get no response and shutdown stops reading");
+
+	assert_eq!(responses[0]["error"]["code"], -32700);
+	let cli: serde_json::Value = serde_json::from_slice(&cli.stdout).expect("ids json");
+	assert_eq!(responses[1]["id"], 1);
+	assert_eq!(responses[1]["result"], cli, "ids result matches `blendoc ids --json`");
+	assert_eq!(responses[2]["result"]["path_expr"], "data");
+	assert!(responses[2]["result"]["value"]["fields"]["id"]["fields"]["name"] == SCENE_ID_NAMES[2]);
+	assert!(responses[3]["result"]["path_edges"].as_array().is_some_and(|edges| !edges.is_empty()));
+	assert_eq!(responses[4]["result"]["files"].as_array().map(Vec::len), Some(1));
+	assert_eq!(responses[4]["result"]["misses"], 1, "the file was parsed once");
+	assert_eq!(responses[5]["error"]["code"], -32602);
+	assert_eq!(responses[6]["error"]["code"], -32601);
+	assert_eq!(responses[7]["id"], 7);
+}
+
+#[cfg(unix)]
+#[test]
+fn socket_connections_share_the_cache() {
+	use std::io::{BufRead, BufReader, Write};
+	use std::os::unix::net::UnixStream;
+
+	let target = TempBlend::scene("serve_socket");
+	let socket = std::env::temp_dir().join(format!("blendoc_serve_{}.sock", std::process::id()));
+	let file = target.arg();
+
+	let mut server = std::process::Command::new(blendoc_testkit::blendoc_bin())
+		.args(["serve", "--socket", &socket.to_string_lossy()])
+		.stderr(std::process::Stdio::null())
+		.spawn()
+		.expect("server starts");
+	let started = std::time::Instant::now();
+	while !socket.exists() && started.elapsed() < std::time::Duration::from_secs(10) {
+		std::thread::sleep(std::time::Duration::from_millis(20));
+	}
+
+	let ask = |request: serde_json::Value| {
+		let mut stream = UnixStream::connect(&socket).expect("socket accepts");
+		writeln!(stream, "{request}").expect("request written");
+		let mut line = String::new();
+		BufReader::new(stream).read_line(&mut line).expect("response read");
+		serde_json::from_str::<serde_json::Value>(&line).expect("response is json")
+	};
+	let info = ask(serde_json::json!({ "jsonrpc": "2.0", "id": 1, "method": "info", "params": { "file": file } }));
+	let graph = ask(serde_json::json!({ "jsonrpc": "2.0", "id": 2, "method": "graph", "params": { "file": file, "code": "SC" } }));
+	let files = ask(serde_json::json!({ "jsonrpc": "2.0", "id": 3, "method": "files" }));
+	let shutdown = ask(serde_json::json!({ "jsonrpc": "2.0", "id": 4, "method": "shutdown" }));
+	let status = server.wait().expect("server exits");
+
+	assert_eq!(info["result"]["path"], file);
+	assert_eq!(info["result"]["schema_version"], 2);
+	assert!(graph["result"]["nodes"].as_array().is_some_and(|nodes| nodes.len() > 1));
+	assert_eq!((files["result"]["hits"].as_u64(), files["result"]["misses"].as_u64()), (Some(1), Some(1)));
+	assert!(shutdown["result"].is_null());
+	assert!(status.success());
+	assert!(!socket.exists(), "socket file removed on shutdown");
+}
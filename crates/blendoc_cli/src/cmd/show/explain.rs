@@ -0,0 +1,89 @@
+use std::path::Path;
+
+use blendoc::blend::DecodeExplanation;
+
+use crate::cmd::show::value_to_json_value;
+use crate::cmd::util::{emit_json, ptr_hex, render_value};
+
+/// Print variant outcomes, then one row per divergent leaf with a column per variant.
+pub(crate) fn print_explanation(path: &Path, root_label: &str, root_ptr: u64, explanation: &DecodeExplanation) {
+	println!("path: {}", path.display());
+	println!("root: {root_label}");
+	println!("root_ptr: {}", ptr_hex(root_ptr));
+	println!("variant\tstatus\tleaves\twarnings");
+	for variant in &explanation.variants {
+		let status = match &variant.error {
+			Some(err) => format!("error: {err}"),
+			None => "ok".to_owned(),
+		};
+		println!("{}\t{}\t{}\t{}", variant.label, status, variant.leaves, variant.warnings);
+	}
+	println!("agreed_fields: {}", explanation.agreed);
+	println!("divergent_fields: {}", explanation.divergences.len());
+	if explanation.divergences.is_empty() {
+		return;
+	}
+
+	let header: Vec<&str> = explanation.variants.iter().map(|variant| variant.label.as_ref()).collect();
+	println!("field\t{}", header.join("\t"));
+	for divergence in &explanation.divergences {
+		let cells: Vec<String> = divergence
+			.values
+			.iter()
+			.map(|value| value.as_ref().map_or_else(|| "-".to_owned(), render_value))
+			.collect();
+		println!("{}\t{}", divergence.path, cells.join("\t"));
+	}
+}
+
+pub(crate) fn print_explanation_json(path: &Path, root_label: &str, root_ptr: u64, explanation: &DecodeExplanation) {
+	let payload = ExplainJson {
+		path: path.display().to_string(),
+		root: root_label.to_owned(),
+		root_ptr: ptr_hex(root_ptr),
+		variants: explanation
+			.variants
+			.iter()
+			.map(|variant| VariantJson {
+				label: variant.label.to_string(),
+				error: variant.error.clone(),
+				leaves: variant.leaves,
+				warnings: variant.warnings,
+			})
+			.collect(),
+		agreed_fields: explanation.agreed,
+		divergences: explanation
+			.divergences
+			.iter()
+			.map(|divergence| DivergenceJson {
+				field: divergence.path.to_string(),
+				values: divergence.values.iter().map(|value| value.as_ref().map(value_to_json_value)).collect(),
+			})
+			.collect(),
+	};
+	emit_json(&payload);
+}
+
+#[derive(serde::Serialize)]
+struct VariantJson {
+	label: String,
+	error: Option<String>,
+	leaves: usize,
+	warnings: usize,
+}
+
+#[derive(serde::Serialize)]
+struct DivergenceJson {
+	field: String,
+	values: Vec<Option<serde_json::Value>>,
+}
+
+#[derive(serde::Serialize)]
+struct ExplainJson {
+	path: String,
+	root: String,
+	root_ptr: String,
+	variants: Vec<VariantJson>,
+	agreed_fields: usize,
+	divergences: Vec<DivergenceJson>,
+}
@@ -0,0 +1,119 @@
+use blendoc::blend::{Dna, PointerIndex, Result, StructValue, Value};
+
+use crate::cmd::show::value_to_json_value;
+use crate::cmd::util::render_value;
+
+/// Hex bytes shown per row before eliding the rest.
+const MAX_HEX_BYTES: usize = 32;
+
+/// One leaf field of the layout overlay.
+pub(crate) struct LayoutRow {
+	pub path: String,
+	pub offset: usize,
+	pub size: usize,
+	pub type_name: String,
+	pub hex: String,
+	pub value: Option<Value>,
+}
+
+/// Build overlay rows for the struct element at `canonical`, descending into embedded structs.
+///
+/// `value` must be decoded with padding fields kept so it lines up with SDNA fields.
+pub(crate) fn layout_rows(dna: &Dna, index: &PointerIndex<'_>, canonical: u64, value: &StructValue) -> Result<Vec<LayoutRow>> {
+	let (_, typed) = index.resolve_canonical_typed(dna, canonical)?;
+	let element_index = typed.element_index.unwrap_or(0);
+	let start = element_index * typed.struct_size;
+	let bytes = typed.base.payload().get(start..start + typed.struct_size).unwrap_or(&[]);
+
+	let mut out = Vec::new();
+	collect_rows(dna, typed.base.entry.block.head.sdna_nr, bytes, 0, "", Some(value), &mut out);
+	Ok(out)
+}
+
+fn collect_rows(dna: &Dna, sdna_nr: u32, bytes: &[u8], base: usize, prefix: &str, value: Option<&StructValue>, out: &mut Vec<LayoutRow>) {
+	let Some(layout) = dna.struct_layout(sdna_nr) else {
+		return;
+	};
+
+	for (idx, field) in layout.iter().enumerate() {
+		let path = format!("{prefix}{}", field.name);
+		let field_value = value
+			.and_then(|item| item.fields.get(idx))
+			.filter(|item| item.name == field.name)
+			.map(|item| &item.value);
+		let offset = base + field.offset;
+
+		if let (Some(nested_sdna), 1) = (field.struct_sdna, field.count) {
+			let nested = match field_value {
+				Some(Value::Struct(nested)) => Some(nested),
+				_ => None,
+			};
+			collect_rows(dna, nested_sdna, bytes, offset, &format!("{path}."), nested, out);
+			continue;
+		}
+
+		out.push(LayoutRow {
+			path,
+			offset,
+			size: field.size,
+			type_name: if field.is_ptr {
+				format!("{}*", field.type_name)
+			} else {
+				field.type_name.to_string()
+			},
+			hex: hex_bytes(bytes.get(offset..offset + field.size).unwrap_or(&[])),
+			value: field_value.cloned(),
+		});
+	}
+}
+
+fn hex_bytes(bytes: &[u8]) -> String {
+	let mut out = bytes.iter().take(MAX_HEX_BYTES).map(|byte| format!("{byte:02x}")).collect::<Vec<_>>().join(" ");
+	if bytes.len() > MAX_HEX_BYTES {
+		out.push_str(" ..");
+	}
+	out
+}
+
+/// Print overlay rows as a tab-separated table.
+pub(crate) fn print_layout_rows(rows: &[LayoutRow]) {
+	println!("layout: {}", rows.len());
+	println!("offset\tsize\ttype\tfield\thex\tvalue");
+	for row in rows {
+		println!(
+			"{}\t{}\t{}\t{}\t{}\t{}",
+			row.offset,
+			row.size,
+			row.type_name,
+			row.path,
+			row.hex,
+			row.value.as_ref().map_or_else(|| "-".to_owned(), render_value)
+		);
+	}
+}
+
+/// JSON row for one layout overlay field.
+#[derive(serde::Serialize)]
+pub(crate) struct LayoutRowJson {
+	field: String,
+	offset: usize,
+	size: usize,
+	#[serde(rename = "type")]
+	type_name: String,
+	hex: String,
+	value: Option<serde_json::Value>,
+}
+
+/// Convert overlay rows into JSON rows.
+pub(crate) fn layout_rows_json(rows: &[LayoutRow]) -> Vec<LayoutRowJson> {
+	rows.iter()
+		.map(|row| LayoutRowJson {
+			field: row.path.clone(),
+			offset: row.offset,
+			size: row.size,
+			type_name: row.type_name.clone(),
+			hex: row.hex.clone(),
+			value: row.value.as_ref().map(value_to_json_value),
+		})
+		.collect()
+}
@@ -1,11 +1,17 @@
 use std::path::PathBuf;
 
 use blendoc::blend::{
-	BlendError, BlendFile, ChasePolicy, DecodeOptions, FieldPath, IdIndex, Value, chase_from_ptr, decode_ptr_instance, scan_id_blocks, scan_id_link_provenance,
+	BlendFile, ChaseMatch, ChaseMeta, ChasePolicy, DecodeOptions, FieldPath, IdIndex, Value, Warning, WarningCollector, chase_all_from_ptr, chase_from_ptr,
+	explain_decode_ptr, scan_id_link_provenance, standard_decode_variants,
 };
 
+use crate::cmd::invocation::Invocation;
+use crate::cmd::plugins::PluginArgs;
 use crate::cmd::print::{PrintCtx, PrintOptions, PtrAnnotCtx, print_value};
-use crate::cmd::util::{RootSelector, emit_json, parse_root_selector, ptr_hex, render_code};
+use crate::cmd::show::explain::{print_explanation, print_explanation_json};
+use crate::cmd::show::layout::{LayoutRow, LayoutRowJson, layout_rows, layout_rows_json, print_layout_rows};
+use crate::cmd::truncation::TruncationArgs;
+use crate::cmd::util::{WarningJson, emit_json, parse_root_selector, print_warnings, ptr_hex, render_code, resolve_root, scan_ids, stop_hint, warnings_json};
 
 #[derive(clap::Args)]
 pub struct Args {
@@ -38,6 +44,16 @@ pub struct Args {
 	pub expand_depth: u32,
 	#[arg(long = "expand-max-nodes", default_value_t = 64)]
 	pub expand_max_nodes: usize,
+	#[arg(long, conflicts_with = "path_expr")]
+	pub layout: bool,
+	#[arg(long = "explain-decode", conflicts_with_all = ["path_expr", "layout"])]
+	pub explain_decode: bool,
+	#[arg(long = "pretty-math")]
+	pub pretty_math: bool,
+	#[command(flatten)]
+	pub truncation: TruncationArgs,
+	#[command(flatten)]
+	pub plugins: PluginArgs,
 }
 
 /// Decode and print a struct/value from ID, pointer, or block code roots.
@@ -58,6 +74,11 @@ pub fn run(args: Args) -> blendoc::blend::Result<()> {
 		raw_ptrs,
 		expand_depth,
 		expand_max_nodes,
+		layout,
+		explain_decode,
+		pretty_math,
+		truncation,
+		plugins,
 	} = args;
 
 	let selector = parse_root_selector(code, ptr, id_name)?;
@@ -65,19 +86,9 @@ pub fn run(args: Args) -> blendoc::blend::Result<()> {
 	let blend = BlendFile::open(&path)?;
 	let dna = blend.dna()?;
 	let index = blend.pointer_index()?;
-	let ids = IdIndex::build(scan_id_blocks(&blend, &dna)?);
+	let ids = IdIndex::build(scan_ids(&blend, &dna)?);
 
-	let (root_ptr, root_label) = match selector {
-		RootSelector::Id(name) => {
-			let row = ids.get_by_name(&name).ok_or(BlendError::IdRecordNotFound { name: name.clone() })?;
-			(row.old_ptr, format!("id:{}", row.id_name))
-		}
-		RootSelector::Ptr(ptr) => (ptr, format!("ptr:0x{ptr:016x}")),
-		RootSelector::Code(code) => {
-			let block = blend.find_first_block_by_code(code)?.ok_or(BlendError::BlockNotFound { code })?;
-			(block.head.old, format!("code:{}", render_code(code)))
-		}
-	};
+	let (root_ptr, root_label) = resolve_root(&blend, &ids, selector)?;
 
 	let mut decode = DecodeOptions::default();
 	if let Some(max_depth) = max_depth {
@@ -86,8 +97,22 @@ pub fn run(args: Args) -> blendoc::blend::Result<()> {
 	if let Some(max_array) = max_array {
 		decode.max_array_elems = max_array;
 	}
-	decode.include_padding = include_padding;
+	decode.include_padding = include_padding || layout;
 	decode.strict_layout = strict_layout;
+	decode.plugins = plugins.load()?;
+
+	if explain_decode {
+		let explanation = explain_decode_ptr(&dna, &index, root_ptr, &standard_decode_variants(&decode));
+		if json {
+			print_explanation_json(&path, &root_label, root_ptr, &explanation);
+		} else {
+			print_explanation(&path, &root_label, root_ptr, &explanation);
+		}
+		return Ok(());
+	}
+
+	let warnings = WarningCollector::new();
+	decode.warnings = Some(warnings.clone());
 
 	let mut print = PrintOptions::default();
 	if let Some(max_depth) = max_depth {
@@ -96,8 +121,11 @@ pub fn run(args: Args) -> blendoc::blend::Result<()> {
 	if let Some(max_array) = max_array {
 		print.max_array_items = max_array;
 	}
+	print.pretty_math = pretty_math;
+	truncation.apply(&mut print);
 
 	let effective_expand_depth = if raw_ptrs { 0 } else { expand_depth };
+	let invocation = Invocation::default();
 	let print_ctx = PrintCtx::new(
 		Some(PtrAnnotCtx {
 			dna: &dna,
@@ -107,7 +135,12 @@ pub fn run(args: Args) -> blendoc::blend::Result<()> {
 		annotate_ptrs && !raw_ptrs,
 		Some(&decode),
 		expand_max_nodes,
+		&invocation,
 	);
+	let chase_policy = ChasePolicy {
+		decode_cache: Some(invocation.decode_cache()),
+		..ChasePolicy::default()
+	};
 
 	let root_link = if json {
 		let links = scan_id_link_provenance(&blend, &dna)?;
@@ -122,16 +155,62 @@ pub fn run(args: Args) -> blendoc::blend::Result<()> {
 
 	if let Some(path_expr) = path_expr {
 		let field_path = FieldPath::parse(&path_expr)?;
-		let result = chase_from_ptr(&dna, &index, root_ptr, &field_path, &decode, &ChasePolicy::default())?;
+		if field_path.fans_out() {
+			let matches = chase_all_from_ptr(&dna, &index, root_ptr, &field_path, &decode, &chase_policy)?;
+			let decode_warnings = warnings.take();
+			if json {
+				let json_root = JsonRootMeta {
+					path: &path,
+					root_label: &root_label,
+					root_ptr,
+					root_link: root_link.as_ref(),
+					warnings: &decode_warnings,
+				};
+				emit_json(&matches_json(&json_root, &path_expr, &matches, trace));
+				return Ok(());
+			}
+
+			println!("path: {}", path.display());
+			println!("root: {root_label}");
+			println!("root_ptr: 0x{root_ptr:016x}");
+			println!("path_expr: {path_expr}");
+			println!("matches: {}", matches.len());
+			for found in &matches {
+				println!("match: {}", found.path);
+				println!("  value:");
+				print_value(&found.result.value, 4, 0, &print, Some(&print_ctx), effective_expand_depth);
+				if trace {
+					print_hops(&found.result.hops, "  ");
+				}
+				if let Some(stop) = &found.result.stop {
+					println!("  stop_step: {}", stop.step_index);
+					println!("  stop_reason: {:?}", stop.reason);
+				}
+			}
+			print_warnings(&decode_warnings);
+			return Ok(());
+		}
+
+		let result = chase_from_ptr(&dna, &index, root_ptr, &field_path, &decode, &chase_policy)?;
+		let hint = result.stop.as_ref().and_then(|stop| stop_hint(&dna, blend.header.version, &stop.reason));
+		let decode_warnings = warnings.take();
 		let json_root = JsonRootMeta {
 			path: &path,
 			root_label: &root_label,
 			root_ptr,
 			root_link: root_link.as_ref(),
+			warnings: &decode_warnings,
 		};
 
 		if json {
-			print_json_path(&json_root, &path_expr, &result.value, result.stop.as_ref(), trace.then_some(&result.hops));
+			emit_json(&path_json(
+				&json_root,
+				&path_expr,
+				&result.value,
+				result.stop.as_ref(),
+				hint.as_deref(),
+				trace.then_some(&result.hops),
+			));
 			return Ok(());
 		}
 
@@ -140,45 +219,48 @@ pub fn run(args: Args) -> blendoc::blend::Result<()> {
 		println!("root_ptr: 0x{root_ptr:016x}");
 		println!("path_expr: {path_expr}");
 		println!("value:");
-		print_value(&result.value, 2, 0, print, Some(&print_ctx), effective_expand_depth);
+		print_value(&result.value, 2, 0, &print, Some(&print_ctx), effective_expand_depth);
 
 		if trace {
-			println!("hops: {}", result.hops.len());
-			for (idx, hop) in result.hops.iter().enumerate() {
-				println!(
-					"  {idx}: ptr=0x{:016x} code={} sdna={} element={} offset={}",
-					hop.ptr,
-					render_code(hop.resolved_block_code),
-					hop.sdna_nr,
-					hop.element_index,
-					hop.element_offset
-				);
-			}
+			print_hops(&result.hops, "");
 		}
 
 		if let Some(stop) = result.stop {
 			println!("stop_step: {}", stop.step_index);
 			println!("stop_reason: {:?}", stop.reason);
+			if let Some(hint) = &hint {
+				println!("stop_hint: {hint}");
+			}
 		}
+		let mut decode_warnings = decode_warnings;
+		decode_warnings.extend(warnings.take());
+		print_warnings(&decode_warnings);
 
 		return Ok(());
 	}
 
-	let (canonical, struct_value) = decode_ptr_instance(&dna, &index, root_ptr, &decode)?;
+	let (canonical, struct_value) = invocation.decode_cache().decode_ptr(&dna, &index, root_ptr, &decode)?;
 	let node_id = ids.get_by_ptr(canonical).map(|item| item.id_name.as_ref());
+	let layout_rows = if layout {
+		Some(layout_rows(&dna, &index, canonical, &struct_value)?)
+	} else {
+		None
+	};
 
 	if json {
 		let value = Value::Struct(struct_value);
 		let canonical_link = root_link
 			.as_ref()
 			.filter(|_| canonical == index.canonical_ptr(&dna, root_ptr).unwrap_or(root_ptr));
+		let decode_warnings = warnings.take();
 		let json_root = JsonRootMeta {
 			path: &path,
 			root_label: &root_label,
 			root_ptr,
 			root_link: canonical_link,
+			warnings: &decode_warnings,
 		};
-		print_json_struct(&json_root, canonical, node_id, &value);
+		emit_json(&struct_json(&json_root, canonical, node_id, &value, layout_rows.as_deref()));
 		return Ok(());
 	}
 
@@ -187,14 +269,20 @@ pub fn run(args: Args) -> blendoc::blend::Result<()> {
 	println!("root_ptr: 0x{root_ptr:016x}");
 	println!("canonical: 0x{canonical:016x}");
 	println!("id_name: {}", node_id.unwrap_or("-"));
-	println!("value:");
-	print_value(&Value::Struct(struct_value), 2, 0, print, Some(&print_ctx), effective_expand_depth);
+	if let Some(rows) = &layout_rows {
+		print_layout_rows(rows);
+	} else {
+		println!("value:");
+		print_value(&Value::Struct(struct_value), 2, 0, &print, Some(&print_ctx), effective_expand_depth);
+	}
+	print_warnings(&warnings.take());
 
 	Ok(())
 }
 
-fn print_json_struct(root: &JsonRootMeta<'_>, canonical: u64, id_name: Option<&str>, value: &Value) {
-	let payload = ShowStructJson {
+/// `--json` payload for a decoded root struct.
+pub(crate) fn struct_json(root: &JsonRootMeta<'_>, canonical: u64, id_name: Option<&str>, value: &Value, layout: Option<&[LayoutRow]>) -> ShowStructJson {
+	ShowStructJson {
 		path: root.path.display().to_string(),
 		root: root.root_label.to_owned(),
 		root_ptr: ptr_hex(root.root_ptr),
@@ -203,19 +291,35 @@ fn print_json_struct(root: &JsonRootMeta<'_>, canonical: u64, id_name: Option<&s
 		root_linked: root.root_link.map(|item| item.0),
 		root_link_confidence: root.root_link.map(|item| item.1.clone()),
 		value: value_to_json_value(value),
-	};
+		layout: layout.map(layout_rows_json),
+		warnings: warnings_json(root.warnings),
+	}
+}
 
-	emit_json(&payload);
+fn print_hops(hops: &[ChaseMeta], indent: &str) {
+	println!("{indent}hops: {}", hops.len());
+	for (idx, hop) in hops.iter().enumerate() {
+		println!(
+			"{indent}  {idx}: ptr=0x{:016x} code={} sdna={} element={} offset={}",
+			hop.ptr,
+			render_code(hop.resolved_block_code),
+			hop.sdna_nr,
+			hop.element_index,
+			hop.element_offset
+		);
+	}
 }
 
-fn print_json_path(
+/// `--json` payload for a `--path` chase.
+pub(crate) fn path_json(
 	root: &JsonRootMeta<'_>,
 	path_expr: &str,
 	value: &Value,
 	stop: Option<&blendoc::blend::ChaseStop>,
+	hint: Option<&str>,
 	hops: Option<&Vec<blendoc::blend::ChaseMeta>>,
-) {
-	let payload = ShowPathJson {
+) -> ShowPathJson {
+	ShowPathJson {
 		path: root.path.display().to_string(),
 		root: root.root_label.to_owned(),
 		root_ptr: ptr_hex(root.root_ptr),
@@ -223,35 +327,71 @@ fn print_json_path(
 		root_linked: root.root_link.map(|item| item.0),
 		root_link_confidence: root.root_link.map(|item| item.1.clone()),
 		value: value_to_json_value(value),
-		stop: stop.map(|stop| ShowStopJson {
-			step: stop.step_index,
-			reason: format!("{:?}", stop.reason),
-		}),
-		hops: hops.map(|items| {
-			items
+		stop: stop_json(stop, hint),
+		hops: hops.map(|items| hops_json(items)),
+		warnings: warnings_json(root.warnings),
+		matches: None,
+	}
+}
+
+/// `--json` payload for a `--path` that fans out: `value` lists every match
+/// value in order and `matches` pairs each with its concrete path.
+pub(crate) fn matches_json(root: &JsonRootMeta<'_>, path_expr: &str, matches: &[ChaseMatch], trace: bool) -> ShowPathJson {
+	ShowPathJson {
+		path: root.path.display().to_string(),
+		root: root.root_label.to_owned(),
+		root_ptr: ptr_hex(root.root_ptr),
+		path_expr: path_expr.to_owned(),
+		root_linked: root.root_link.map(|item| item.0),
+		root_link_confidence: root.root_link.map(|item| item.1.clone()),
+		value: serde_json::Value::Array(matches.iter().map(|found| value_to_json_value(&found.result.value)).collect()),
+		stop: None,
+		hops: None,
+		warnings: warnings_json(root.warnings),
+		matches: Some(
+			matches
 				.iter()
-				.map(|hop| ShowHopJson {
-					ptr: ptr_hex(hop.ptr),
-					code: render_code(hop.resolved_block_code),
-					sdna: hop.sdna_nr,
-					element: hop.element_index,
-					offset: hop.element_offset,
+				.map(|found| ShowMatchJson {
+					path: found.path.to_string(),
+					value: value_to_json_value(&found.result.value),
+					stop: stop_json(found.result.stop.as_ref(), None),
+					hops: trace.then(|| hops_json(&found.result.hops)),
 				})
-				.collect()
-		}),
-	};
+				.collect(),
+		),
+	}
+}
+
+fn stop_json(stop: Option<&blendoc::blend::ChaseStop>, hint: Option<&str>) -> Option<ShowStopJson> {
+	stop.map(|stop| ShowStopJson {
+		step: stop.step_index,
+		reason: format!("{:?}", stop.reason),
+		hint: hint.map(str::to_owned),
+	})
+}
 
-	emit_json(&payload);
+fn hops_json(hops: &[ChaseMeta]) -> Vec<ShowHopJson> {
+	hops.iter()
+		.map(|hop| ShowHopJson {
+			ptr: ptr_hex(hop.ptr),
+			code: render_code(hop.resolved_block_code),
+			sdna: hop.sdna_nr,
+			element: hop.element_index,
+			offset: hop.element_offset,
+		})
+		.collect()
 }
 
-struct JsonRootMeta<'a> {
-	path: &'a std::path::Path,
-	root_label: &'a str,
-	root_ptr: u64,
-	root_link: Option<&'a (bool, String)>,
+/// Root fields shared by both `--json` payloads.
+pub(crate) struct JsonRootMeta<'a> {
+	pub path: &'a std::path::Path,
+	pub root_label: &'a str,
+	pub root_ptr: u64,
+	pub root_link: Option<&'a (bool, String)>,
+	pub warnings: &'a [Warning],
 }
 
-fn value_to_json_value(value: &Value) -> serde_json::Value {
+pub(crate) fn value_to_json_value(value: &Value) -> serde_json::Value {
 	use serde_json::{Map, Value as JsonValue};
 
 	match value {
@@ -287,7 +427,7 @@ fn value_to_json_value(value: &Value) -> serde_json::Value {
 }
 
 #[derive(serde::Serialize)]
-struct ShowStructJson {
+pub(crate) struct ShowStructJson {
 	path: String,
 	root: String,
 	root_ptr: String,
@@ -298,12 +438,16 @@ struct ShowStructJson {
 	#[serde(skip_serializing_if = "Option::is_none")]
 	root_link_confidence: Option<String>,
 	value: serde_json::Value,
+	#[serde(skip_serializing_if = "Option::is_none")]
+	layout: Option<Vec<LayoutRowJson>>,
+	warnings: Vec<WarningJson>,
 }
 
 #[derive(serde::Serialize)]
 struct ShowStopJson {
 	step: usize,
 	reason: String,
+	hint: Option<String>,
 }
 
 #[derive(serde::Serialize)]
@@ -316,7 +460,7 @@ struct ShowHopJson {
 }
 
 #[derive(serde::Serialize)]
-struct ShowPathJson {
+pub(crate) struct ShowPathJson {
 	path: String,
 	root: String,
 	root_ptr: String,
@@ -328,7 +472,21 @@ struct ShowPathJson {
 	value: serde_json::Value,
 	stop: Option<ShowStopJson>,
 	hops: Option<Vec<ShowHopJson>>,
+	warnings: Vec<WarningJson>,
+	#[serde(skip_serializing_if = "Option::is_none")]
+	matches: Option<Vec<ShowMatchJson>>,
 }
 
+#[derive(serde::Serialize)]
+struct ShowMatchJson {
+	path: String,
+	value: serde_json::Value,
+	stop: Option<ShowStopJson>,
+	hops: Option<Vec<ShowHopJson>>,
+}
+
+mod explain;
+mod layout;
+
 #[cfg(test)]
 mod tests;
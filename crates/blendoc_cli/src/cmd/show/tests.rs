@@ -1,4 +1,6 @@
-use crate::cmd::test_support::{fixture_path, run_blendoc_json};
+use blendoc_testkit::builder::{BlendBuilder, HeaderKind, SCENE_ID_NAMES};
+
+use crate::cmd::test_support::{TempBlend, fixture_path, run_blendoc, run_blendoc_json};
 
 #[test]
 fn show_json_output_is_valid_and_structured() {
@@ -10,3 +12,163 @@ fn show_json_output_is_valid_and_structured() {
 	assert!(json["canonical"].as_str().is_some_and(|item| item.starts_with("0x")));
 	assert!(json["value"].is_object(), "expected top-level value object");
 }
+
+#[test]
+fn code_selector_accepts_occurrence_index() {
+	let target = TempBlend::scene("show_nth");
+	let target_arg = target.arg();
+
+	let first = run_blendoc(&["show", target_arg, "--code", "OB:0", "--json"]);
+	let bracket = run_blendoc(&["graph", target_arg, "--code", "OB[0]", "--json"]);
+	let past_end = run_blendoc(&["show", target_arg, "--code", "OB:1"]);
+
+	assert!(first.status.success(), "show failed: {}", String::from_utf8_lossy(&first.stderr));
+	let json: serde_json::Value = serde_json::from_slice(&first.stdout).expect("valid json");
+	assert_eq!(json["root"], "code:OB[0]");
+	assert!(
+		json["value"]["fields"]["id"]["fields"]["name"]
+			.as_str()
+			.is_some_and(|name| name == SCENE_ID_NAMES[1])
+	);
+	assert!(bracket.status.success(), "graph failed: {}", String::from_utf8_lossy(&bracket.stderr));
+	assert!(!past_end.status.success());
+	assert!(String::from_utf8_lossy(&past_end.stderr).contains("block occurrence out of range"));
+}
+
+#[test]
+fn plugin_descriptors_change_show_and_decode_output() {
+	let target = TempBlend::scene("show_plugins");
+	let descriptor = std::env::temp_dir().join(format!("blendoc_show_plugins_{}.toml", std::process::id()));
+	std::fs::write(
+		&descriptor,
+		"[[struct]]\ntype = \"Object\"\nsummary = \"{id.name} at {data}\"\nhide = [\"loc\"]\n",
+	)
+	.expect("descriptor written");
+	let target_arg = target.arg();
+	let descriptor_arg = descriptor.to_string_lossy().into_owned();
+
+	let json = run_blendoc_json(&["show", target_arg, "--code", "OB", "--plugins", &descriptor_arg, "--json"]);
+	let text = run_blendoc(&["decode", target_arg, "--code", "OB", "--plugins", &descriptor_arg]);
+	let plain = run_blendoc(&["decode", target_arg, "--code", "OB"]);
+	std::fs::remove_file(&descriptor).expect("descriptor removed");
+
+	assert!(json["value"]["fields"].get("loc").is_none());
+	assert!(json["value"]["fields"]["data"].is_string());
+	let text = String::from_utf8_lossy(&text.stdout);
+	assert!(text.contains(&format!("Object ({} at 0x", SCENE_ID_NAMES[1])), "{text}");
+	assert!(!text.contains("loc ="), "{text}");
+	assert!(String::from_utf8_lossy(&plain.stdout).contains("loc ="));
+}
+
+#[test]
+fn undecodable_id_is_skipped_unless_strict() {
+	let mut builder = BlendBuilder::new(HeaderKind::Legacy8, 404, false);
+	let sdna = builder.sdna_mut();
+	sdna.add_struct("Link", &[("Link", "*next"), ("Link", "*prev")]);
+	sdna.add_type("char", 1);
+	sdna.add_type("void", 0);
+	sdna.add_struct("ID", &[("void", "*next"), ("void", "*prev"), ("void", "*lib"), ("char", "name[24]")]);
+	let object = sdna.add_struct("Object", &[("ID", "id"), ("void", "*data")]);
+	let mut payload = builder.payload();
+	payload.ptr(0).ptr(0).ptr(0).name("OBAfter", 24).ptr(0);
+	let after = payload.finish();
+	builder
+		.block(*b"OB\0\0", object, 0x1000, 1, vec![0; 8])
+		.block(*b"OB\0\0", object, 0x2000, 1, after);
+
+	let target = TempBlend::new("show_skipped_id", &builder.build());
+	let target_arg = target.arg();
+
+	let lenient = run_blendoc(&["show", target_arg, "--id", "OBAfter", "--json"]);
+	let strict = run_blendoc(&["show", target_arg, "--id", "OBAfter", "--strict-ids"]);
+
+	assert!(lenient.status.success(), "show failed: {}", String::from_utf8_lossy(&lenient.stderr));
+	let json: serde_json::Value = serde_json::from_slice(&lenient.stdout).expect("warnings stay off stdout");
+	assert_eq!(json["root"], "id:OBAfter");
+	let stderr = String::from_utf8_lossy(&lenient.stderr);
+	assert!(stderr.contains("warning\tskipped_id\tOB block at offset"), "{stderr}");
+	assert!(!strict.status.success());
+}
+
+#[test]
+fn fanned_out_paths_list_every_concrete_match() {
+	let target = TempBlend::scene("show_fan_out");
+	let target_arg = target.arg();
+
+	let shown = run_blendoc_json(&["show", target_arg, "--id", SCENE_ID_NAMES[1], "--path", "loc[1..]", "--json"]);
+	let last = run_blendoc_json(&["show", target_arg, "--id", SCENE_ID_NAMES[1], "--path", "loc[-1]", "--json"]);
+	let chased = run_blendoc_json(&["chase", target_arg, "--id", SCENE_ID_NAMES[0], "--path", "*.id", "--json"]);
+	let text = run_blendoc(&["chase", target_arg, "--id", SCENE_ID_NAMES[0], "--path", "*.id"]);
+
+	let paths: Vec<&str> = shown["matches"]
+		.as_array()
+		.expect("matches")
+		.iter()
+		.filter_map(|item| item["path"].as_str())
+		.collect();
+	assert_eq!(paths, ["loc[1]", "loc[2]"]);
+	assert_eq!(shown["value"], serde_json::json!([2.0, 3.0]));
+	assert_eq!(last["value"], 3.0);
+	assert!(last.get("matches").is_none());
+
+	assert_eq!(chased["result"]["kind"], "Matches");
+	let paths: Vec<&str> = chased["matches"]
+		.as_array()
+		.expect("matches")
+		.iter()
+		.filter_map(|item| item["path"].as_str())
+		.collect();
+	assert_eq!(paths, ["id.id", "camera.id", "world.id"]);
+	assert!(chased["matches"][0]["stop"].is_object());
+	assert_eq!(chased["matches"][1]["result"]["type"], "ID");
+	let text = String::from_utf8_lossy(&text.stdout);
+	assert!(text.contains("matches: 3") && text.contains("match: world.id"), "{text}");
+}
+
+#[test]
+fn missing_id_errors_suggest_close_names() {
+	let target = TempBlend::scene("show_suggest");
+	let target_arg = target.arg();
+
+	let outputs = [
+		run_blendoc(&["show", target_arg, "--id", "OBCamra"]),
+		run_blendoc(&["chase", target_arg, "--id", "OBCamra", "--path", "data"]),
+		run_blendoc(&["graph", target_arg, "--id", "OBCamra"]),
+		run_blendoc(&["route", target_arg, "--from-id", "SCScene", "--to-id", "Camra"]),
+		run_blendoc(&["xref", target_arg, "--id", "OBCamra"]),
+	];
+	let unrelated = run_blendoc(&["show", target_arg, "--id", "OBZebraStripes"]);
+
+	for output in &outputs {
+		let stderr = String::from_utf8_lossy(&output.stderr);
+		assert!(stderr.contains("(did you mean `OBCamera`"), "{stderr}");
+		assert_eq!(output.status.code(), Some(2), "{stderr}");
+	}
+	let stderr = String::from_utf8_lossy(&unrelated.stderr);
+	assert!(stderr.contains("ID record not found: OBZebraStripes\n"), "{stderr}");
+}
+
+#[test]
+fn path_text_output_reports_chase_warnings() {
+	let mut builder = BlendBuilder::new(HeaderKind::Legacy8, 404, false);
+	let sdna = builder.sdna_mut();
+	sdna.add_type("char", 1);
+	sdna.add_type("void", 0);
+	sdna.add_struct("ID", &[("void", "*next"), ("void", "*prev"), ("void", "*lib"), ("char", "name[24]")]);
+	let object = sdna.add_struct("Object", &[("ID", "id"), ("char", "label[8]")]);
+	let mut payload = builder.payload();
+	payload.ptr(0).ptr(0).ptr(0).name("OBLabel", 24).raw(&[b'a', 0xff, b'b', 0, 0, 0, 0, 0]);
+	let object_payload = payload.finish();
+	builder.block(*b"OB\0\0", object, 0x1000, 1, object_payload);
+
+	let target = TempBlend::new("show_path_warnings", &builder.build());
+	let target_arg = target.arg();
+
+	let text = run_blendoc(&["show", target_arg, "--id", "OBLabel", "--path", "label"]);
+	let json = run_blendoc_json(&["show", target_arg, "--id", "OBLabel", "--path", "label", "--json"]);
+
+	assert!(text.status.success(), "show failed: {}", String::from_utf8_lossy(&text.stderr));
+	let text = String::from_utf8_lossy(&text.stdout);
+	assert!(text.contains("warnings: 1\nwarning\tlossy_string\t"), "{text}");
+	assert_eq!(json["warnings"].as_array().map(Vec::len), Some(1));
+}
@@ -0,0 +1,222 @@
+use std::io::Write;
+use std::path::PathBuf;
+
+use blendoc::blend::{BlendFile, IdIndex, SizeStats, scan_size_stats};
+
+use crate::cmd::output::OutputArgs;
+use crate::cmd::util::{render_code, scan_ids};
+
+#[derive(clap::Args)]
+pub struct Args {
+	pub file: PathBuf,
+	#[arg(long, default_value_t = 20)]
+	pub limit: usize,
+	#[arg(long)]
+	pub json: bool,
+	#[command(flatten)]
+	pub output: OutputArgs,
+}
+
+/// Print payload bytes per SDNA type and per ID type, largest first.
+pub fn run(args: Args) -> blendoc::blend::Result<()> {
+	let Args {
+		file: path,
+		limit,
+		json,
+		output,
+	} = args;
+
+	let blend = BlendFile::open(&path)?;
+	let dna = blend.dna()?;
+	let ids = IdIndex::build(scan_ids(&blend, &dna)?);
+	let stats = scan_size_stats(&blend, &dna, &ids)?;
+
+	let mut out = output.open(json)?;
+	if !out.is_text() {
+		let payload = stats_json(&path, &stats, limit);
+		let rows = stats_rows(&stats, limit);
+		out.structured(&payload, &rows)?;
+		return out.finish();
+	}
+
+	writeln!(out, "path: {}", path.display())?;
+	writeln!(out, "blocks: {}", stats.block_count)?;
+	writeln!(out, "payload_bytes: {}", stats.payload_bytes)?;
+
+	writeln!(out, "types: {}", stats.types.len())?;
+	for item in stats.types.iter().take(limit) {
+		writeln!(
+			out,
+			"  {} {} ({}) blocks={} elements={} struct_size={} avg_block_bytes={}",
+			item.payload_bytes,
+			item.type_name,
+			share(item.payload_bytes, stats.payload_bytes),
+			item.blocks,
+			item.elements,
+			item.struct_size,
+			item.avg_block_bytes()
+		)?;
+	}
+	if stats.types.len() > limit {
+		writeln!(out, "  ... {} more", stats.types.len() - limit)?;
+	}
+
+	writeln!(out, "id_types: {}", stats.id_types.len())?;
+	for item in stats.id_types.iter().take(limit) {
+		writeln!(
+			out,
+			"  {} {}({}) ({}) ids={} id_bytes={} data_blocks={} data_bytes={}",
+			item.total_bytes(),
+			render_code(item.code),
+			item.type_name,
+			share(item.total_bytes(), stats.payload_bytes),
+			item.ids,
+			item.id_bytes,
+			item.data_blocks,
+			item.data_bytes
+		)?;
+	}
+	if stats.id_types.len() > limit {
+		writeln!(out, "  ... {} more", stats.id_types.len() - limit)?;
+	}
+
+	writeln!(out, "raw:")?;
+	for item in &stats.raw {
+		writeln!(out, "  {} {} blocks={}", item.payload_bytes, render_code(item.code), item.blocks)?;
+	}
+	writeln!(out, "unowned_data: blocks={} bytes={}", stats.unowned_data_blocks, stats.unowned_data_bytes)?;
+
+	out.finish()
+}
+
+fn share(bytes: u64, total: u64) -> String {
+	if total == 0 {
+		return "0.0%".to_owned();
+	}
+	format!("{:.1}%", bytes as f64 * 100.0 / total as f64)
+}
+
+fn stats_json(path: &std::path::Path, stats: &SizeStats, limit: usize) -> StatsJson {
+	StatsJson {
+		path: path.display().to_string(),
+		block_count: stats.block_count,
+		payload_bytes: stats.payload_bytes,
+		type_count: stats.types.len(),
+		types: stats
+			.types
+			.iter()
+			.take(limit)
+			.map(|item| TypeSizeJson {
+				sdna_nr: item.sdna_nr,
+				type_name: item.type_name.to_string(),
+				struct_size: item.struct_size,
+				blocks: item.blocks,
+				elements: item.elements,
+				payload_bytes: item.payload_bytes,
+				avg_block_bytes: item.avg_block_bytes(),
+			})
+			.collect(),
+		id_type_count: stats.id_types.len(),
+		id_types: stats
+			.id_types
+			.iter()
+			.take(limit)
+			.map(|item| IdTypeSizeJson {
+				code: render_code(item.code),
+				type_name: item.type_name.to_string(),
+				ids: item.ids,
+				id_bytes: item.id_bytes,
+				data_blocks: item.data_blocks,
+				data_bytes: item.data_bytes,
+				total_bytes: item.total_bytes(),
+			})
+			.collect(),
+		raw: stats
+			.raw
+			.iter()
+			.map(|item| RawSizeJson {
+				code: render_code(item.code),
+				blocks: item.blocks,
+				payload_bytes: item.payload_bytes,
+			})
+			.collect(),
+		unowned_data_blocks: stats.unowned_data_blocks,
+		unowned_data_bytes: stats.unowned_data_bytes,
+	}
+}
+
+/// `--format csv` rows: SDNA types then ID types, tagged by `section`.
+///
+/// `count` is the element count for types and the ID count for ID types.
+fn stats_rows(stats: &SizeStats, limit: usize) -> Vec<SizeRowCsv> {
+	let types = stats.types.iter().take(limit).map(|item| SizeRowCsv {
+		section: "type",
+		name: item.type_name.to_string(),
+		blocks: item.blocks,
+		count: item.elements,
+		payload_bytes: item.payload_bytes,
+	});
+	let id_types = stats.id_types.iter().take(limit).map(|item| SizeRowCsv {
+		section: "id_type",
+		name: render_code(item.code),
+		blocks: item.ids + item.data_blocks,
+		count: item.ids as u64,
+		payload_bytes: item.total_bytes(),
+	});
+	types.chain(id_types).collect()
+}
+
+#[derive(serde::Serialize)]
+struct StatsJson {
+	path: String,
+	block_count: usize,
+	payload_bytes: u64,
+	type_count: usize,
+	types: Vec<TypeSizeJson>,
+	id_type_count: usize,
+	id_types: Vec<IdTypeSizeJson>,
+	raw: Vec<RawSizeJson>,
+	unowned_data_blocks: usize,
+	unowned_data_bytes: u64,
+}
+
+#[derive(serde::Serialize)]
+struct TypeSizeJson {
+	sdna_nr: u32,
+	type_name: String,
+	struct_size: u64,
+	blocks: usize,
+	elements: u64,
+	payload_bytes: u64,
+	avg_block_bytes: u64,
+}
+
+#[derive(serde::Serialize)]
+struct IdTypeSizeJson {
+	code: String,
+	type_name: String,
+	ids: usize,
+	id_bytes: u64,
+	data_blocks: usize,
+	data_bytes: u64,
+	total_bytes: u64,
+}
+
+#[derive(serde::Serialize)]
+struct RawSizeJson {
+	code: String,
+	blocks: usize,
+	payload_bytes: u64,
+}
+
+#[derive(serde::Serialize)]
+struct SizeRowCsv {
+	section: &'static str,
+	name: String,
+	blocks: usize,
+	count: u64,
+	payload_bytes: u64,
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,18 @@
+use crate::cmd::test_support::{fixture_path, run_blendoc_json};
+
+#[test]
+fn stats_json_sorts_types_by_payload_bytes() {
+	let fixture = fixture_path("character.blend");
+	let fixture = fixture.to_string_lossy().into_owned();
+	let json = run_blendoc_json(&["stats", &fixture, "--json", "--limit", "50"]);
+
+	let types = json["types"].as_array().expect("types array");
+	assert!(!types.is_empty());
+	let sizes: Vec<u64> = types.iter().map(|item| item["payload_bytes"].as_u64().expect("payload bytes")).collect();
+	assert!(sizes.windows(2).all(|pair| pair[0] >= pair[1]));
+
+	let id_types = json["id_types"].as_array().expect("id_types array");
+	assert!(id_types.iter().any(|item| item["code"] == "OB"));
+
+	assert!(sizes.iter().sum::<u64>() <= json["payload_bytes"].as_u64().expect("total bytes"));
+}
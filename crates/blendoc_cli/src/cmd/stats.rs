@@ -0,0 +1,194 @@
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::cell::RefCell;
+use std::sync::Mutex;
+use std::sync::atomic::{AtomicBool, AtomicU64, AtomicUsize, Ordering};
+use std::time::{Duration, Instant};
+
+static COUNTING: AtomicBool = AtomicBool::new(false);
+static LIVE: AtomicUsize = AtomicUsize::new(0);
+static PEAK: AtomicUsize = AtomicUsize::new(0);
+static ALLOCATED: AtomicU64 = AtomicU64::new(0);
+static ALLOCATIONS: AtomicU64 = AtomicU64::new(0);
+
+static PHASES: Mutex<Vec<PhaseRecord>> = Mutex::new(Vec::new());
+
+thread_local! {
+	static STACK: RefCell<Vec<&'static str>> = const { RefCell::new(Vec::new()) };
+}
+
+/// System allocator wrapper counting live, peak, and cumulative heap bytes.
+///
+/// Counting is off until [`set_counting`] enables it for `--mem-stats`; until
+/// then each call only adds one relaxed load to the system allocator.
+pub(crate) struct CountingAlloc;
+
+// SAFETY: every call is forwarded unchanged to `System`; only counters are updated.
+unsafe impl GlobalAlloc for CountingAlloc {
+	unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+		// SAFETY: caller upholds `GlobalAlloc::alloc` requirements.
+		let ptr = unsafe { System.alloc(layout) };
+		if !ptr.is_null() && counting() {
+			record_alloc(layout.size());
+		}
+		ptr
+	}
+
+	unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
+		// SAFETY: caller upholds `GlobalAlloc::alloc_zeroed` requirements.
+		let ptr = unsafe { System.alloc_zeroed(layout) };
+		if !ptr.is_null() && counting() {
+			record_alloc(layout.size());
+		}
+		ptr
+	}
+
+	unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+		// SAFETY: caller upholds `GlobalAlloc::dealloc` requirements.
+		unsafe { System.dealloc(ptr, layout) };
+		if counting() {
+			record_free(layout.size());
+		}
+	}
+
+	unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+		// SAFETY: caller upholds `GlobalAlloc::realloc` requirements.
+		let new_ptr = unsafe { System.realloc(ptr, layout, new_size) };
+		if !new_ptr.is_null() && counting() {
+			record_free(layout.size());
+			record_alloc(new_size);
+		}
+		new_ptr
+	}
+}
+
+/// Turn heap counting on or off; `main` enables it only for `--mem-stats`.
+pub(crate) fn set_counting(enabled: bool) {
+	COUNTING.store(enabled, Ordering::Relaxed);
+}
+
+fn counting() -> bool {
+	COUNTING.load(Ordering::Relaxed)
+}
+
+/// Blocks allocated before counting started are freed uncounted, so live bytes saturate at zero.
+fn record_free(size: usize) {
+	let _ = LIVE.fetch_update(Ordering::Relaxed, Ordering::Relaxed, |live| Some(live.saturating_sub(size)));
+}
+
+fn record_alloc(size: usize) {
+	let live = LIVE.fetch_add(size, Ordering::Relaxed) + size;
+	PEAK.fetch_max(live, Ordering::Relaxed);
+	ALLOCATED.fetch_add(size as u64, Ordering::Relaxed);
+	ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+}
+
+/// Timing and allocation totals for one finished phase.
+struct PhaseRecord {
+	label: String,
+	elapsed: Duration,
+	allocated: u64,
+	allocations: u64,
+	peak: usize,
+	live_end: usize,
+}
+
+/// Open phase; the record is written when the guard drops.
+pub(crate) struct Phase {
+	start: Instant,
+	allocated: u64,
+	allocations: u64,
+	outer_peak: usize,
+}
+
+/// Start a named phase. Phases nest; nested labels are reported as `outer/inner`.
+pub(crate) fn phase(label: &'static str) -> Phase {
+	STACK.with(|stack| stack.borrow_mut().push(label));
+	// The phase peak starts from current live bytes; the enclosing peak is restored on drop.
+	let outer_peak = PEAK.swap(LIVE.load(Ordering::Relaxed), Ordering::Relaxed);
+	Phase {
+		start: Instant::now(),
+		allocated: ALLOCATED.load(Ordering::Relaxed),
+		allocations: ALLOCATIONS.load(Ordering::Relaxed),
+		outer_peak,
+	}
+}
+
+/// Run `body` inside a named phase.
+pub(crate) fn timed<T>(label: &'static str, body: impl FnOnce() -> T) -> T {
+	let _phase = phase(label);
+	body()
+}
+
+impl Drop for Phase {
+	fn drop(&mut self) {
+		let elapsed = self.start.elapsed();
+		let peak = PEAK.fetch_max(self.outer_peak, Ordering::Relaxed);
+		let label = STACK.with(|stack| {
+			let mut stack = stack.borrow_mut();
+			let label = stack.join("/");
+			stack.pop();
+			label
+		});
+		let record = PhaseRecord {
+			label,
+			elapsed,
+			allocated: ALLOCATED.load(Ordering::Relaxed) - self.allocated,
+			allocations: ALLOCATIONS.load(Ordering::Relaxed) - self.allocations,
+			peak,
+			live_end: LIVE.load(Ordering::Relaxed),
+		};
+		if let Ok(mut phases) = PHASES.lock() {
+			phases.push(record);
+		}
+	}
+}
+
+/// Print recorded phases to stderr so stdout (including `--json`) stays clean.
+pub(crate) fn report(timings: bool, mem_stats: bool) {
+	if !timings && !mem_stats {
+		return;
+	}
+	let Ok(phases) = PHASES.lock() else {
+		return;
+	};
+
+	if mem_stats {
+		eprintln!("mem_stats:");
+		eprintln!("phase\telapsed_ms\tallocated_bytes\tallocations\tpeak_live_bytes\tlive_bytes_end");
+		for phase in phases.iter() {
+			eprintln!(
+				"{}\t{:.3}\t{}\t{}\t{}\t{}",
+				phase.label,
+				millis(phase.elapsed),
+				phase.allocated,
+				phase.allocations,
+				phase.peak,
+				phase.live_end
+			);
+		}
+		eprintln!("allocated_bytes: {}", ALLOCATED.load(Ordering::Relaxed));
+		eprintln!("peak_live_bytes: {}", PEAK.load(Ordering::Relaxed));
+		match peak_rss_bytes() {
+			Some(bytes) => eprintln!("peak_rss_bytes: {bytes}"),
+			None => eprintln!("peak_rss_bytes: unknown"),
+		}
+	} else {
+		eprintln!("timings:");
+		eprintln!("phase\telapsed_ms");
+		for phase in phases.iter() {
+			eprintln!("{}\t{:.3}", phase.label, millis(phase.elapsed));
+		}
+	}
+}
+
+fn millis(elapsed: Duration) -> f64 {
+	elapsed.as_secs_f64() * 1000.0
+}
+
+/// Process high-water resident set size (`VmHWM`), where the platform exposes it.
+fn peak_rss_bytes() -> Option<u64> {
+	let status = std::fs::read_to_string("/proc/self/status").ok()?;
+	let line = status.lines().find(|line| line.starts_with("VmHWM:"))?;
+	let kib: u64 = line.trim_start_matches("VmHWM:").trim().trim_end_matches("kB").trim().parse().ok()?;
+	Some(kib * 1024)
+}
@@ -1,7 +1,11 @@
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use std::process::Output;
 
-use blendoc_testkit::{fixture_path as shared_fixture_path, run_blendoc as shared_run_blendoc, run_blendoc_json as shared_run_blendoc_json};
+use blendoc_testkit::builder::{HeaderKind, scene_file};
+use blendoc_testkit::{
+	fixture_path as shared_fixture_path, run_blendoc as shared_run_blendoc, run_blendoc_json as shared_run_blendoc_json,
+	run_blendoc_with_stdin as shared_run_blendoc_with_stdin,
+};
 
 pub(crate) fn fixture_path(name: &str) -> PathBuf {
 	shared_fixture_path(name)
@@ -11,6 +15,48 @@ pub(crate) fn run_blendoc(args: &[&str]) -> Output {
 	shared_run_blendoc(args)
 }
 
+pub(crate) fn run_blendoc_with_stdin(args: &[&str], stdin: &[u8]) -> Output {
+	shared_run_blendoc_with_stdin(args, stdin)
+}
+
 pub(crate) fn run_blendoc_json(args: &[&str]) -> serde_json::Value {
 	shared_run_blendoc_json(args)
 }
+
+/// A `.blend` written to the temp dir for one test, removed again on drop.
+pub(crate) struct TempBlend {
+	path: PathBuf,
+	arg: String,
+}
+
+impl TempBlend {
+	/// Write `bytes` to `blendoc_<label>_<pid>.blend`; `label` must be unique per test.
+	pub(crate) fn new(label: &str, bytes: &[u8]) -> Self {
+		let path = std::env::temp_dir().join(format!("blendoc_{label}_{}.blend", std::process::id()));
+		std::fs::write(&path, bytes).expect("synthetic file written");
+		let arg = path.to_string_lossy().into_owned();
+		Self { path, arg }
+	}
+
+	/// Write the little-endian legacy 8-byte synthetic scene.
+	pub(crate) fn scene(label: &str) -> Self {
+		Self::new(label, &scene_file(HeaderKind::Legacy8, false))
+	}
+
+	/// Path as a command-line argument.
+	pub(crate) fn arg(&self) -> &str {
+		&self.arg
+	}
+}
+
+impl AsRef<Path> for TempBlend {
+	fn as_ref(&self) -> &Path {
+		&self.path
+	}
+}
+
+impl Drop for TempBlend {
+	fn drop(&mut self) {
+		let _ = std::fs::remove_file(&self.path);
+	}
+}
@@ -0,0 +1,71 @@
+use std::path::PathBuf;
+
+use blendoc::blend::{BlendFile, Result, extract_thumbnail};
+
+use crate::cmd::util::{emit_json, write_png_rgba};
+
+#[derive(clap::Args)]
+pub struct Args {
+	pub file: PathBuf,
+	#[arg(long)]
+	pub out: Option<PathBuf>,
+	#[arg(long, requires = "out")]
+	pub raw: bool,
+	#[arg(long)]
+	pub json: bool,
+}
+
+/// Decode the embedded `TEST` thumbnail and optionally write it as PNG or raw RGBA.
+pub fn run(args: Args) -> Result<()> {
+	let Args { file: path, out, raw, json } = args;
+
+	let blend = BlendFile::open(&path)?;
+	let thumbnail = extract_thumbnail(&blend)?;
+
+	if let (Some(thumb), Some(target)) = (&thumbnail, &out) {
+		if raw {
+			std::fs::write(target, &thumb.rgba)?;
+		} else {
+			write_png_rgba(target, thumb.width, thumb.height, &thumb.rgba)?;
+		}
+	}
+	let written = out.as_ref().filter(|_| thumbnail.is_some());
+	let format = if raw { "rgba" } else { "png" };
+
+	if json {
+		let payload = ThumbJson {
+			path: path.display().to_string(),
+			present: thumbnail.is_some(),
+			width: thumbnail.as_ref().map(|thumb| thumb.width),
+			height: thumbnail.as_ref().map(|thumb| thumb.height),
+			out: written.map(|target| target.display().to_string()),
+			format: written.map(|_| format),
+		};
+		emit_json(&payload);
+		return Ok(());
+	}
+
+	println!("path: {}", path.display());
+	let Some(thumb) = &thumbnail else {
+		println!("thumbnail: none");
+		return Ok(());
+	};
+	println!("width: {}", thumb.width);
+	println!("height: {}", thumb.height);
+	if let Some(target) = written {
+		println!("out: {}", target.display());
+		println!("format: {format}");
+	}
+
+	Ok(())
+}
+
+#[derive(serde::Serialize)]
+struct ThumbJson {
+	path: String,
+	present: bool,
+	width: Option<u32>,
+	height: Option<u32>,
+	out: Option<String>,
+	format: Option<&'static str>,
+}
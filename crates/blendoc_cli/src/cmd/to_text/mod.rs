@@ -0,0 +1,69 @@
+use std::fs;
+use std::io::{self, Write};
+use std::path::PathBuf;
+
+use blendoc::blend::{BlendFile, Result, TextFormOptions, diff_text_forms, text_form};
+
+#[derive(clap::Args)]
+pub struct Args {
+	pub file: PathBuf,
+	#[arg(long, value_name = "TEXT")]
+	pub from: Option<PathBuf>,
+	#[arg(long = "max-array", default_value_t = 16)]
+	pub max_array: usize,
+	#[arg(long, short = 'o')]
+	pub output: Option<PathBuf>,
+}
+
+/// Print the canonical text form of a file, or diff it against a stored one.
+///
+/// `--from` reads a text form written earlier (for example the copy kept in
+/// version control) and prints only the lines that changed, grouped by ID.
+pub fn run(args: Args) -> Result<()> {
+	let Args {
+		file: path,
+		from,
+		max_array,
+		output,
+	} = args;
+
+	let blend = BlendFile::open(&path)?;
+	let dna = blend.dna()?;
+	let options = TextFormOptions {
+		max_inline_array: max_array,
+		..TextFormOptions::default()
+	};
+	let text = text_form(&blend, &dna, &options)?;
+
+	let mut out: Box<dyn Write> = match &output {
+		Some(target) if target.as_os_str() != "-" => Box::new(io::BufWriter::new(fs::File::create(target)?)),
+		_ => Box::new(io::BufWriter::new(crate::cmd::quiet::stdout())),
+	};
+	let Some(from) = from else {
+		out.write_all(text.as_bytes())?;
+		out.flush()?;
+		return Ok(());
+	};
+
+	let before = fs::read_to_string(&from)?;
+	let changes = diff_text_forms(&before, &text);
+	let mut section = None;
+	for change in &changes {
+		if section != Some(&change.section) {
+			section = Some(&change.section);
+			writeln!(out, "{}", if change.section.is_empty() { "(header)" } else { &change.section })?;
+		}
+		if let Some(line) = &change.before {
+			writeln!(out, "- {line}")?;
+		}
+		if let Some(line) = &change.after {
+			writeln!(out, "+ {line}")?;
+		}
+	}
+	writeln!(out, "changes: {}", changes.len())?;
+	out.flush()?;
+	Ok(())
+}
+
+#[cfg(test)]
+mod tests;
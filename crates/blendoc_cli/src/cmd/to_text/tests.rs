@@ -0,0 +1,30 @@
+use blendoc_testkit::builder::{HeaderKind, scene_file};
+
+use crate::cmd::test_support::run_blendoc;
+
+#[test]
+fn to_text_round_trips_through_from() {
+	let dir = std::env::temp_dir().join(format!("blendoc_to_text_{}", std::process::id()));
+	std::fs::create_dir_all(&dir).expect("temp dir created");
+	let blend = dir.join("scene.blend");
+	let text = dir.join("scene.txt");
+	let edited = dir.join("edited.txt");
+	std::fs::write(&blend, scene_file(HeaderKind::Legacy8, false)).expect("synthetic file written");
+	let blend_arg = blend.to_string_lossy().into_owned();
+	let text_arg = text.to_string_lossy().into_owned();
+	let edited_arg = edited.to_string_lossy().into_owned();
+
+	let written = run_blendoc(&["to-text", &blend_arg, "-o", &text_arg]);
+	assert!(written.status.success());
+	let form = std::fs::read_to_string(&text).expect("text form written");
+	std::fs::write(&edited, form.replace("loc = [1.0, 2.0, 3.0]", "loc = [1.0, 2.0, 4.0]")).expect("edited form written");
+
+	let same = run_blendoc(&["to-text", &blend_arg, "--from", &text_arg]);
+	let changed = run_blendoc(&["to-text", &blend_arg, "--from", &edited_arg]);
+	std::fs::remove_dir_all(&dir).expect("temp dir removed");
+
+	assert!(form.contains("[Object \"OBCamera\"]"), "{form}");
+	assert_eq!(String::from_utf8_lossy(&same.stdout), "changes: 0\n");
+	let stdout = String::from_utf8_lossy(&changed.stdout);
+	assert_eq!(stdout, "[Object \"OBCamera\"]\n- loc = [1.0, 2.0, 4.0]\n+ loc = [1.0, 2.0, 3.0]\nchanges: 1\n");
+}
@@ -0,0 +1,111 @@
+use std::collections::BTreeMap;
+use std::sync::{Arc, Mutex, OnceLock};
+use std::time::{Duration, Instant};
+
+use tracing::span::{Attributes, Id};
+use tracing::subscriber::Subscriber;
+use tracing_subscriber::Layer;
+use tracing_subscriber::layer::{Context, SubscriberExt};
+use tracing_subscriber::registry::LookupSpan;
+
+/// Busy time per span name, shared between the layer and the report.
+type Totals = Arc<Mutex<BTreeMap<&'static str, SpanTotals>>>;
+
+static TOTALS: OnceLock<Totals> = OnceLock::new();
+
+/// Closed spans and summed busy time for one span name.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+struct SpanTotals {
+	calls: u64,
+	busy: Duration,
+}
+
+/// Per-span state kept in registry extensions while the span is open.
+#[derive(Default)]
+struct SpanClock {
+	entered: Option<Instant>,
+	busy: Duration,
+}
+
+/// Layer summing the time each core span is entered (`--trace-timing`).
+///
+/// Busy time is inclusive: a `blocks` span entered inside
+/// `pointer_index.build` counts toward both.
+struct TimingLayer {
+	totals: Totals,
+}
+
+impl<S> Layer<S> for TimingLayer
+where
+	S: Subscriber + for<'a> LookupSpan<'a>,
+{
+	fn on_new_span(&self, _attrs: &Attributes<'_>, id: &Id, ctx: Context<'_, S>) {
+		if let Some(span) = ctx.span(id) {
+			span.extensions_mut().insert(SpanClock::default());
+		}
+	}
+
+	fn on_enter(&self, id: &Id, ctx: Context<'_, S>) {
+		if let Some(span) = ctx.span(id)
+			&& let Some(clock) = span.extensions_mut().get_mut::<SpanClock>()
+		{
+			clock.entered = Some(Instant::now());
+		}
+	}
+
+	fn on_exit(&self, id: &Id, ctx: Context<'_, S>) {
+		if let Some(span) = ctx.span(id)
+			&& let Some(clock) = span.extensions_mut().get_mut::<SpanClock>()
+			&& let Some(entered) = clock.entered.take()
+		{
+			clock.busy += entered.elapsed();
+		}
+	}
+
+	fn on_close(&self, id: Id, ctx: Context<'_, S>) {
+		let Some(span) = ctx.span(&id) else {
+			return;
+		};
+		let busy = span.extensions().get::<SpanClock>().map_or(Duration::ZERO, |clock| clock.busy);
+		if let Ok(mut totals) = self.totals.lock() {
+			let entry = totals.entry(span.name()).or_default();
+			entry.calls += 1;
+			entry.busy += busy;
+		}
+	}
+}
+
+/// Install the timing layer as the global `tracing` subscriber.
+pub(crate) fn install() {
+	let totals = TOTALS.get_or_init(Totals::default).clone();
+	let subscriber = tracing_subscriber::registry().with(TimingLayer { totals });
+	let _ = tracing::subscriber::set_global_default(subscriber);
+}
+
+/// Print per-span totals to stderr, slowest first; nothing unless installed.
+pub(crate) fn report() {
+	let Some(totals) = TOTALS.get() else {
+		return;
+	};
+	let Ok(totals) = totals.lock() else {
+		return;
+	};
+	eprintln!("trace_timing:");
+	for line in render(&totals) {
+		eprintln!("{line}");
+	}
+}
+
+fn render(totals: &BTreeMap<&'static str, SpanTotals>) -> Vec<String> {
+	let mut rows: Vec<_> = totals.iter().collect();
+	rows.sort_by(|left, right| right.1.busy.cmp(&left.1.busy).then_with(|| left.0.cmp(right.0)));
+	let mut lines = vec!["span\tcalls\tbusy_ms".to_owned()];
+	lines.extend(
+		rows.into_iter()
+			.map(|(name, item)| format!("{name}\t{}\t{:.3}", item.calls, item.busy.as_secs_f64() * 1000.0)),
+	);
+	lines
+}
+
+#[cfg(test)]
+mod tests;
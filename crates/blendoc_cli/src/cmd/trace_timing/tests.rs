@@ -0,0 +1,29 @@
+use blendoc::blend::{BlendFile, IdIndex, scan_id_blocks};
+use blendoc_testkit::builder::{HeaderKind, scene_file};
+use tracing_subscriber::layer::SubscriberExt;
+
+use super::{TimingLayer, Totals, render};
+
+#[test]
+fn core_spans_are_counted_per_name() {
+	let totals = Totals::default();
+	let subscriber = tracing_subscriber::registry().with(TimingLayer { totals: totals.clone() });
+	tracing::subscriber::with_default(subscriber, || {
+		let blend = BlendFile::from_bytes(scene_file(HeaderKind::Legacy8, false)).expect("synthetic file opens");
+		let dna = blend.dna().expect("dna parses");
+		let index = blend.pointer_index().expect("pointer index builds");
+		let ids = IdIndex::build(scan_id_blocks(&blend, &dna).expect("ids scan"));
+		assert_eq!(ids.records.len(), 4);
+		drop(index);
+	});
+
+	let totals = totals.lock().expect("totals lock");
+	for name in ["decode_bytes", "dna.parse", "pointer_index.build", "ids.scan"] {
+		assert_eq!(totals.get(name).map(|item| item.calls), Some(1), "{name}");
+	}
+	assert!(totals["blocks"].calls >= 3, "dna lookup, pointer index, and id scan each iterate blocks");
+
+	let lines = render(&totals);
+	assert_eq!(lines[0], "span\tcalls\tbusy_ms");
+	assert_eq!(lines.len(), totals.len() + 1);
+}
@@ -0,0 +1,25 @@
+use crate::cmd::print::{FieldTruncation, PrintOptions, TruncateMode};
+
+/// Shared string truncation flags for commands that print decoded values.
+#[derive(clap::Args, Debug, Clone, Default)]
+pub struct TruncationArgs {
+	#[arg(long, value_enum, value_name = "MODE")]
+	pub truncate: Option<TruncateMode>,
+	#[arg(long = "max-string", value_name = "N")]
+	pub max_string: Option<usize>,
+	#[arg(long = "truncate-field", value_name = "FIELD=MODE[:LEN]")]
+	pub truncate_field: Vec<FieldTruncation>,
+}
+
+impl TruncationArgs {
+	/// Overlay the flags on `options`; `--truncate-field` rules win over the path defaults.
+	pub(crate) fn apply(&self, options: &mut PrintOptions) {
+		if let Some(mode) = self.truncate {
+			options.truncate = mode;
+		}
+		if let Some(max_string) = self.max_string {
+			options.max_string_len = max_string;
+		}
+		options.field_truncation.extend(self.truncate_field.iter().cloned());
+	}
+}
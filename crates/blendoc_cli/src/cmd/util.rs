@@ -1,8 +1,16 @@
-use blendoc::blend::{BlendError, Result};
+use std::sync::atomic::{AtomicBool, Ordering};
+
+use blendoc::blend::{
+	BlendError, BlendFile, Block, ChaseStopReason, Dna, IdIndex, IdRecord, IdScanOptions, LibraryNamespaces, Result, Value, Warning, WarningCollector, codes,
+	field_name_hint, scan_id_blocks_with, scan_library_namespaces,
+};
+
+use crate::cmd::show::value_to_json_value;
 
 /// Common selector form for roots that accept `--code`, `--ptr`, or `--id`.
 pub(crate) enum RootSelector {
-	Code([u8; 4]),
+	/// Block code with an optional zero-based occurrence (`OB[2]`); `None` picks the first block.
+	Code([u8; 4], Option<usize>),
 	Ptr(u64),
 	Id(String),
 }
@@ -15,13 +23,25 @@ pub(crate) enum IdOrPtrSelector {
 
 /// Parse up-to-4 ASCII block code into padded `[u8; 4]`.
 pub(crate) fn parse_block_code(code: &str) -> Result<[u8; 4]> {
-	if code.is_empty() || code.len() > 4 || !code.is_ascii() {
-		return Err(BlendError::InvalidBlockCode { code: code.to_owned() });
+	codes::str_to_code(code)
+}
+
+/// Parse block code with optional zero-based occurrence index (`IM`, `IM[3]`, or `IM:3`).
+pub(crate) fn parse_block_code_occurrence(value: &str) -> Result<([u8; 4], Option<usize>)> {
+	if let Some((code, index)) = value.split_once(':') {
+		let index = index.parse::<usize>().map_err(|_| BlendError::InvalidBlockCode { code: value.to_owned() })?;
+		return Ok((parse_block_code(code)?, Some(index)));
 	}
+	let Some(open) = value.find('[') else {
+		return Ok((parse_block_code(value)?, None));
+	};
+
+	let index = value[open + 1..]
+		.strip_suffix(']')
+		.and_then(|digits| digits.parse::<usize>().ok())
+		.ok_or(BlendError::InvalidBlockCode { code: value.to_owned() })?;
 
-	let mut out = [0_u8; 4];
-	out[..code.len()].copy_from_slice(code.as_bytes());
-	Ok(out)
+	Ok((parse_block_code(&value[..open])?, Some(index)))
 }
 
 /// Parse decimal or `0x`-prefixed hex pointer literal.
@@ -43,7 +63,8 @@ pub(crate) fn parse_root_selector(code: Option<String>, ptr: Option<String>, id_
 	}
 
 	if let Some(code) = code {
-		return Ok(RootSelector::Code(parse_block_code(&code)?));
+		let (code, occurrence) = parse_block_code_occurrence(&code)?;
+		return Ok(RootSelector::Code(code, occurrence));
 	}
 	if let Some(ptr) = ptr {
 		return Ok(RootSelector::Ptr(parse_ptr(&ptr)?));
@@ -72,20 +93,68 @@ pub(crate) fn parse_id_or_ptr_selector(id_name: Option<String>, ptr: Option<Stri
 	Err(BlendError::InvalidChaseRoot)
 }
 
-/// Render block code bytes as printable label.
-pub(crate) fn render_code(code: [u8; 4]) -> String {
-	let mut out = String::new();
-	for byte in code {
-		if byte == 0 {
-			continue;
+/// Resolve a root selector to its pointer and `id:`/`ptr:`/`code:` label.
+pub(crate) fn resolve_root(blend: &BlendFile, ids: &IdIndex, selector: RootSelector) -> Result<(u64, String)> {
+	match selector {
+		RootSelector::Id(name) => {
+			let row = ids.resolve_selector(&name)?;
+			Ok((row.old_ptr, format!("id:{}", row.id_name)))
 		}
-		if byte.is_ascii_graphic() || byte == b' ' {
-			out.push(char::from(byte));
-		} else {
-			out.push('.');
+		RootSelector::Ptr(ptr) => Ok((ptr, format!("ptr:0x{ptr:016x}"))),
+		RootSelector::Code(code, occurrence) => {
+			let block = code_root_block(blend, code, occurrence)?;
+			Ok((block.head.old, code_selector_label(code, occurrence)))
 		}
 	}
-	if out.is_empty() { "....".to_owned() } else { out }
+}
+
+/// Resolve a `--code` root: the first block with the code, or its `occurrence`-th block.
+pub(crate) fn code_root_block(blend: &BlendFile, code: [u8; 4], occurrence: Option<usize>) -> Result<Block<'_>> {
+	blend.find_nth_block_by_code(code, occurrence.unwrap_or(0))
+}
+
+/// Selector label for a `--code` root: `code:OB`, or `code:OB[2]` for an explicit occurrence.
+pub(crate) fn code_selector_label(code: [u8; 4], occurrence: Option<usize>) -> String {
+	match occurrence {
+		Some(index) => format!("code:{}[{index}]", render_code(code)),
+		None => format!("code:{}", render_code(code)),
+	}
+}
+
+/// Render block code bytes as printable label.
+pub(crate) fn render_code(code: [u8; 4]) -> String {
+	codes::code_to_str(code)
+}
+
+static STRICT_IDS: AtomicBool = AtomicBool::new(false);
+
+/// Make [`scan_ids`] abort on the first undecodable ID block (`--strict-ids`).
+pub(crate) fn set_strict_ids(strict: bool) {
+	STRICT_IDS.store(strict, Ordering::Relaxed);
+}
+
+/// Scan ID-root blocks, reporting each skipped undecodable ID on stderr unless `--strict-ids` is set.
+pub(crate) fn scan_ids(blend: &BlendFile, dna: &Dna) -> Result<Vec<IdRecord>> {
+	let warnings = WarningCollector::new();
+	let options = IdScanOptions {
+		strict: STRICT_IDS.load(Ordering::Relaxed),
+		warnings: Some(warnings.clone()),
+		progress: crate::cmd::progress::sink(),
+	};
+	let ids = scan_id_blocks_with(blend, dna, &options)?;
+	for warning in warnings.take() {
+		eprintln!("warning\t{}\t{}", warning.kind.as_str(), warning.message);
+	}
+	Ok(ids)
+}
+
+/// Source-library labels for `--annotate-libs`; empty (annotating nothing) when the flag is off.
+pub(crate) fn library_namespaces(blend: &BlendFile, dna: &Dna, annotate_libs: bool) -> Result<LibraryNamespaces> {
+	if annotate_libs {
+		scan_library_namespaces(blend, dna)
+	} else {
+		Ok(LibraryNamespaces::default())
+	}
 }
 
 /// Escape text for Graphviz DOT label values.
@@ -98,13 +167,92 @@ pub(crate) fn ptr_hex(value: u64) -> String {
 	format!("0x{value:016x}")
 }
 
+/// Render a byte count with a binary unit (`512 B`, `1.5 MiB`).
+pub(crate) fn human_bytes(bytes: u64) -> String {
+	const UNITS: [&str; 4] = ["KiB", "MiB", "GiB", "TiB"];
+	if bytes < 1024 {
+		return format!("{bytes} B");
+	}
+	let mut value = bytes as f64 / 1024.0;
+	let mut unit = 0;
+	while value >= 1024.0 && unit + 1 < UNITS.len() {
+		value /= 1024.0;
+		unit += 1;
+	}
+	format!("{value:.1} {}", UNITS[unit])
+}
+
 /// Render optional pointer as optional fixed-width hex string.
 pub(crate) fn ptr_hex_opt(value: Option<u64>) -> Option<String> {
 	value.map(ptr_hex)
 }
 
-/// Pretty-print any serializable value as JSON.
+/// Render a decoded leaf value on one line (quoted strings, hex pointers, JSON otherwise).
+pub(crate) fn render_value(value: &Value) -> String {
+	match value {
+		Value::String(text) => format!("{text:?}"),
+		Value::Ptr(ptr) => ptr_hex(*ptr),
+		other => value_to_json_value(other).to_string(),
+	}
+}
+
+/// Pretty-print any serializable value as JSON stamped with the selected schema version.
 pub(crate) fn emit_json<T: serde::Serialize>(value: &T) {
-	let rendered = serde_json::to_string_pretty(value).expect("json serialization should succeed");
+	let rendered = crate::cmd::schema::to_json(value).expect("json serialization should succeed");
 	println!("{rendered}");
 }
+
+/// Print a `warnings:` count line followed by one tab-separated row per warning.
+pub(crate) fn print_warnings(warnings: &[Warning]) {
+	write_warnings(&mut crate::cmd::quiet::stdout(), warnings).expect("stdout write should succeed");
+}
+
+/// Write the [`print_warnings`] lines to `out`.
+pub(crate) fn write_warnings(out: &mut impl std::io::Write, warnings: &[Warning]) -> std::io::Result<()> {
+	writeln!(out, "warnings: {}", warnings.len())?;
+	for warning in warnings {
+		writeln!(out, "warning\t{}\t{}", warning.kind.as_str(), warning.message)?;
+	}
+	Ok(())
+}
+
+/// JSON row for one non-fatal warning.
+#[derive(serde::Serialize)]
+pub(crate) struct WarningJson {
+	kind: &'static str,
+	ptr: Option<String>,
+	message: String,
+}
+
+/// Convert warnings into JSON rows.
+pub(crate) fn warnings_json(warnings: &[Warning]) -> Vec<WarningJson> {
+	warnings
+		.iter()
+		.map(|warning| WarningJson {
+			kind: warning.kind.as_str(),
+			ptr: ptr_hex_opt(warning.ptr),
+			message: warning.message.to_string(),
+		})
+		.collect()
+}
+
+/// Render a "did you mean" hint for a missing-field chase stop.
+pub(crate) fn stop_hint(dna: &Dna, file_version: u16, reason: &ChaseStopReason) -> Option<String> {
+	let ChaseStopReason::MissingField { struct_name, field } = reason else {
+		return None;
+	};
+	let hint = field_name_hint(dna, file_version, struct_name, field);
+	(!hint.is_empty()).then(|| hint.to_string())
+}
+
+/// Write top-down RGBA8 pixels as a PNG file.
+pub(crate) fn write_png_rgba(target: &std::path::Path, width: u32, height: u32, rgba: &[u8]) -> Result<()> {
+	let file = std::fs::File::create(target)?;
+	let mut encoder = png::Encoder::new(std::io::BufWriter::new(file), width, height);
+	encoder.set_color(png::ColorType::Rgba);
+	encoder.set_depth(png::BitDepth::Eight);
+	let mut writer = encoder.write_header().map_err(std::io::Error::other)?;
+	writer.write_image_data(rgba).map_err(std::io::Error::other)?;
+	writer.finish().map_err(std::io::Error::other)?;
+	Ok(())
+}
@@ -0,0 +1,170 @@
+use std::io::Write;
+use std::path::PathBuf;
+
+use blendoc::blend::{BlendFile, Result, Severity, ValidateOptions, ValidationReport, validate_file};
+
+use crate::cmd::output::OutputArgs;
+use crate::cmd::util::ptr_hex_opt;
+
+/// Lowest severity printed by `--min-severity`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, clap::ValueEnum)]
+pub enum SeverityArg {
+	/// Every finding.
+	Info,
+	/// Warnings and errors.
+	Warning,
+	/// Errors only.
+	Error,
+}
+
+impl From<SeverityArg> for Severity {
+	fn from(value: SeverityArg) -> Self {
+		match value {
+			SeverityArg::Info => Self::Info,
+			SeverityArg::Warning => Self::Warning,
+			SeverityArg::Error => Self::Error,
+		}
+	}
+}
+
+#[derive(clap::Args)]
+pub struct Args {
+	pub file: PathBuf,
+	#[arg(long = "no-pointers")]
+	pub no_pointers: bool,
+	#[arg(long = "max-issues", default_value_t = 100)]
+	pub max_issues: usize,
+	#[arg(long = "min-severity", value_enum, default_value = "info")]
+	pub min_severity: SeverityArg,
+	#[arg(long, default_value_t = 1)]
+	pub threads: usize,
+	#[arg(long)]
+	pub json: bool,
+	#[command(flatten)]
+	pub output: OutputArgs,
+}
+
+/// Exit 0 when no error-level finding was reported, 1 when one was, 2 on failure.
+pub fn run(args: Args) -> Result<()> {
+	match check(args) {
+		Ok(true) => Ok(()),
+		Ok(false) => std::process::exit(1),
+		Err(err) => {
+			eprintln!("error: {err}");
+			std::process::exit(2);
+		}
+	}
+}
+
+fn check(args: Args) -> Result<bool> {
+	let Args {
+		file: path,
+		no_pointers,
+		max_issues,
+		min_severity,
+		threads,
+		json,
+		output,
+	} = args;
+
+	let blend = BlendFile::open(&path)?;
+	let options = ValidateOptions {
+		check_pointers: !no_pointers,
+		max_issues_per_check: max_issues,
+		threads,
+	};
+	let report = validate_file(&blend, &options)?;
+	let valid = report.is_valid();
+
+	let min_severity = Severity::from(min_severity);
+	let rows: Vec<IssueRow> = report
+		.issues
+		.iter()
+		.filter(|issue| issue.severity >= min_severity)
+		.map(|issue| IssueRow {
+			severity: issue.severity.as_str(),
+			check: issue.check.as_str(),
+			block_offset: issue.block_offset.map(|offset| format!("0x{offset:x}")),
+			ptr: ptr_hex_opt(issue.ptr),
+			message: issue.message.to_string(),
+		})
+		.collect();
+
+	let mut out = output.open(json)?;
+	if !out.is_text() {
+		let payload = ValidateJson {
+			path: path.display().to_string(),
+			valid,
+			counts: counts(&report),
+			suppressed: report.suppressed,
+			blocks_checked: report.blocks_checked,
+			pointers_checked: report.pointers_checked,
+			issues: &rows,
+		};
+		out.structured(&payload, &rows)?;
+		out.finish()?;
+		return Ok(valid);
+	}
+
+	writeln!(out, "path: {}", path.display())?;
+	writeln!(out, "blocks: {}", report.blocks_checked)?;
+	if !no_pointers {
+		writeln!(out, "pointers: {}", report.pointers_checked)?;
+	}
+	let counts = counts(&report);
+	writeln!(out, "errors: {}  warnings: {}  info: {}", counts.error, counts.warning, counts.info)?;
+	if report.suppressed > 0 {
+		writeln!(out, "suppressed: {} (raise --max-issues to see them)", report.suppressed)?;
+	}
+	for row in &rows {
+		let location = match (&row.block_offset, &row.ptr) {
+			(Some(offset), Some(ptr)) => format!(" [block {offset} ptr {ptr}]"),
+			(Some(offset), None) => format!(" [block {offset}]"),
+			(None, Some(ptr)) => format!(" [ptr {ptr}]"),
+			(None, None) => String::new(),
+		};
+		writeln!(out, "{}\t{}\t{}{location}", row.severity, row.check, row.message)?;
+	}
+	writeln!(out, "result: {}", if valid { "ok" } else { "invalid" })?;
+	out.finish()?;
+	Ok(valid)
+}
+
+fn counts(report: &ValidationReport) -> SeverityCounts {
+	SeverityCounts {
+		error: report.count(Severity::Error),
+		warning: report.count(Severity::Warning),
+		info: report.count(Severity::Info),
+	}
+}
+
+#[derive(serde::Serialize)]
+struct SeverityCounts {
+	error: usize,
+	warning: usize,
+	info: usize,
+}
+
+#[derive(serde::Serialize)]
+struct ValidateJson<'a> {
+	path: String,
+	valid: bool,
+	counts: SeverityCounts,
+	suppressed: usize,
+	blocks_checked: usize,
+	pointers_checked: usize,
+	issues: &'a [IssueRow],
+}
+
+/// One finding; also the `--format csv` row.
+#[derive(serde::Serialize)]
+struct IssueRow {
+	severity: &'static str,
+	check: &'static str,
+	block_offset: Option<String>,
+	ptr: Option<String>,
+	message: String,
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,55 @@
+use blendoc_testkit::builder::{BlendBuilder, HeaderKind};
+
+use crate::cmd::test_support::{TempBlend, run_blendoc, run_blendoc_json};
+
+/// `OBCube` whose `parent` points nowhere, plus a block past the struct table.
+fn broken_file() -> Vec<u8> {
+	let mut builder = BlendBuilder::new(HeaderKind::Legacy8, 404, false);
+	let sdna = builder.sdna_mut();
+	sdna.add_struct("Link", &[("Link", "*next"), ("Link", "*prev")]);
+	sdna.add_type("char", 1);
+	sdna.add_type("void", 0);
+	sdna.add_struct("ID", &[("void", "*next"), ("void", "*prev"), ("void", "*lib"), ("char", "name[24]")]);
+	let object = sdna.add_struct("Object", &[("ID", "id"), ("Object", "*parent")]);
+
+	let mut payload = builder.payload();
+	payload.ptr(0).ptr(0).ptr(0).name("OBCube", 24).ptr(0xdead_0000);
+	let cube_payload = payload.finish();
+	builder
+		.block(*b"OB\0\0", object, 0x1000, 1, cube_payload)
+		.block(*b"DATA", 42, 0x2000, 1, vec![0; 4]);
+	builder.build()
+}
+
+#[test]
+fn validate_reports_findings_and_exit_code() {
+	let good = TempBlend::scene("validate_ok");
+	let bad = TempBlend::new("validate_bad", &broken_file());
+	let good_arg = good.arg();
+	let bad_arg = bad.arg();
+
+	let ok = run_blendoc(&["validate", good_arg]);
+	let json = run_blendoc_json(&["validate", good_arg, "--json"]);
+	let failing = run_blendoc(&["validate", bad_arg, "--json"]);
+	let errors_only = run_blendoc(&["validate", bad_arg, "--min-severity", "error"]);
+
+	assert!(ok.status.success());
+	assert!(String::from_utf8_lossy(&ok.stdout).contains("result: ok"));
+	assert_eq!(json["valid"], true);
+	assert_eq!(json["issues"].as_array().expect("issues array").len(), 0);
+
+	assert_eq!(failing.status.code(), Some(1));
+	let report: serde_json::Value = serde_json::from_slice(&failing.stdout).expect("json report");
+	let checks: Vec<&str> = report["issues"]
+		.as_array()
+		.expect("issues array")
+		.iter()
+		.map(|issue| issue["check"].as_str().expect("check label"))
+		.collect();
+	assert!(checks.contains(&"sdna_out_of_range"), "{checks:?}");
+	assert!(checks.contains(&"dangling_ptr"), "{checks:?}");
+	assert_eq!(report["counts"]["error"], 1);
+
+	let text = String::from_utf8_lossy(&errors_only.stdout);
+	assert!(text.contains("sdna_out_of_range") && !text.contains("dangling_ptr"), "{text}");
+}
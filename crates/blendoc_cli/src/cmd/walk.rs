@@ -1,11 +1,10 @@
 use std::sync::Arc;
 
 use blendoc::blend::{
-	BlendError, BlendFile, ChasePolicy, DecodeOptions, FieldPath, IdIndex, StopMode, Value, WalkOptions, WalkStopReason, chase_from_ptr, scan_id_blocks,
-	walk_ptr_chain,
+	BlendError, BlendFile, ChasePolicy, DecodeOptions, FieldPath, IdIndex, StopMode, Value, WalkOptions, WalkStopReason, chase_from_ptr, walk_ptr_chain,
 };
 
-use crate::cmd::util::{RootSelector, emit_json, parse_root_selector, ptr_hex, render_code};
+use crate::cmd::util::{RootSelector, code_root_block, code_selector_label, emit_json, parse_root_selector, ptr_hex, render_code, scan_ids};
 
 #[derive(clap::Args)]
 pub struct Args {
@@ -47,17 +46,17 @@ pub fn run(args: Args) -> blendoc::blend::Result<()> {
 	let blend = BlendFile::open(&path)?;
 	let dna = blend.dna()?;
 	let index = blend.pointer_index()?;
-	let ids = IdIndex::build(scan_id_blocks(&blend, &dna)?);
+	let ids = IdIndex::build(scan_ids(&blend, &dna)?);
 
 	let (root_ptr, root_label) = match selector {
 		RootSelector::Id(name) => {
-			let row = ids.get_by_name(&name).ok_or(BlendError::IdRecordNotFound { name: name.clone() })?;
+			let row = ids.resolve_selector(&name)?;
 			(row.old_ptr, format!("id:{}", row.id_name))
 		}
 		RootSelector::Ptr(ptr) => (ptr, format!("ptr:0x{ptr:016x}")),
-		RootSelector::Code(code) => {
-			let block = blend.find_first_block_by_code(code)?.ok_or(BlendError::BlockNotFound { code })?;
-			(block.head.old, format!("code:{}", render_code(code)))
+		RootSelector::Code(code, occurrence) => {
+			let block = code_root_block(&blend, code, occurrence)?;
+			(block.head.old, code_selector_label(code, occurrence))
 		}
 	};
 
@@ -0,0 +1,105 @@
+use std::ffi::CString;
+use std::io;
+use std::os::fd::{AsRawFd, FromRawFd, OwnedFd};
+use std::os::unix::ffi::OsStrExt;
+use std::path::Path;
+use std::time::Duration;
+
+/// Events that mean the file now holds a finished save.
+const EVENTS: u32 = libc::IN_CLOSE_WRITE | libc::IN_MOVED_TO;
+/// Fixed part of `struct inotify_event` before the name.
+const EVENT_HEADER: usize = 16;
+
+/// Inotify watch on a file's directory, filtered to events naming the file.
+///
+/// Watching the directory rather than the file catches saves that write a
+/// temporary file and rename it over the original, as Blender does.
+pub(super) struct DirWatch {
+	fd: OwnedFd,
+	name: Vec<u8>,
+}
+
+impl DirWatch {
+	pub(super) fn new(file: &Path) -> io::Result<Self> {
+		let name = file
+			.file_name()
+			.ok_or_else(|| io::Error::other("watched path has no file name"))?
+			.as_bytes()
+			.to_vec();
+		let dir = match file.parent() {
+			Some(parent) if !parent.as_os_str().is_empty() => parent,
+			_ => Path::new("."),
+		};
+		let dir = CString::new(dir.as_os_str().as_bytes())?;
+
+		// SAFETY: plain syscall; the returned descriptor is owned below.
+		let raw = unsafe { libc::inotify_init1(libc::IN_CLOEXEC) };
+		if raw < 0 {
+			return Err(io::Error::last_os_error());
+		}
+		// SAFETY: `raw` is a fresh descriptor nothing else owns.
+		let fd = unsafe { OwnedFd::from_raw_fd(raw) };
+		// SAFETY: `dir` is a NUL-terminated path that outlives the call.
+		if unsafe { libc::inotify_add_watch(fd.as_raw_fd(), dir.as_ptr(), EVENTS) } < 0 {
+			return Err(io::Error::last_os_error());
+		}
+		Ok(Self { fd, name })
+	}
+
+	/// Block until the file is written or renamed into place, then swallow
+	/// further events until none arrive for `settle`.
+	pub(super) fn wait(&self, settle: Duration) -> io::Result<()> {
+		while self.next_batch(None)? != Some(true) {}
+		while self.next_batch(Some(settle))?.is_some() {}
+		Ok(())
+	}
+
+	/// Read one batch of events; `None` when `timeout` passed without any,
+	/// otherwise whether a batch event names the watched file.
+	fn next_batch(&self, timeout: Option<Duration>) -> io::Result<Option<bool>> {
+		let timeout = timeout.map_or(-1, |timeout| i32::try_from(timeout.as_millis()).unwrap_or(i32::MAX));
+		let mut poll = libc::pollfd {
+			fd: self.fd.as_raw_fd(),
+			events: libc::POLLIN,
+			revents: 0,
+		};
+		// SAFETY: `poll` is one valid `pollfd` for the duration of the call.
+		match unsafe { libc::poll(&mut poll, 1, timeout) } {
+			0 => return Ok(None),
+			count if count < 0 => {
+				let err = io::Error::last_os_error();
+				return if err.kind() == io::ErrorKind::Interrupted {
+					Ok(Some(false))
+				} else {
+					Err(err)
+				};
+			}
+			_ => {}
+		}
+
+		let mut buf = [0_u8; 4096];
+		// SAFETY: `buf` is writable for its full length.
+		let read = unsafe { libc::read(self.fd.as_raw_fd(), buf.as_mut_ptr().cast(), buf.len()) };
+		if read < 0 {
+			let err = io::Error::last_os_error();
+			return if err.kind() == io::ErrorKind::Interrupted {
+				Ok(Some(false))
+			} else {
+				Err(err)
+			};
+		}
+
+		let mut events = &buf[..read as usize];
+		let mut matched = false;
+		while events.len() >= EVENT_HEADER {
+			let word = |at: usize| u32::from_ne_bytes(events[at..at + 4].try_into().expect("4 bytes"));
+			let mask = word(4);
+			let len = word(12) as usize;
+			let name = events.get(EVENT_HEADER..EVENT_HEADER + len).unwrap_or_default();
+			let name = name.split(|byte| *byte == 0).next().unwrap_or_default();
+			matched |= mask & libc::IN_Q_OVERFLOW != 0 || (mask & EVENTS != 0 && name == self.name.as_slice());
+			events = events.get(EVENT_HEADER + len..).unwrap_or_default();
+		}
+		Ok(Some(matched))
+	}
+}
@@ -0,0 +1,204 @@
+use std::ffi::OsString;
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use std::time::{Duration, SystemTime};
+use std::{fs, thread};
+
+use blendoc::blend::Result;
+
+use crate::cmd::quiet;
+use crate::cmd::replay::exit_code;
+
+#[cfg(target_os = "linux")]
+mod inotify;
+
+/// Largest LCS table `--diff` builds after trimming the common prefix and
+/// suffix; bigger changes print the full output instead.
+const MAX_DIFF_CELLS: usize = 1 << 22;
+
+#[derive(clap::Args)]
+pub struct Args {
+	pub file: PathBuf,
+	#[arg(long = "interval-ms", default_value_t = 500)]
+	pub interval_ms: u64,
+	#[arg(long)]
+	pub diff: bool,
+	#[arg(long = "max-runs")]
+	pub max_runs: Option<usize>,
+	#[arg(last = true, required = true, value_name = "COMMAND")]
+	pub command: Vec<OsString>,
+}
+
+/// Re-run a subcommand on `file` each time the file is saved.
+///
+/// On Linux, inotify on the file's directory reports finished writes and
+/// renames onto the file; elsewhere the file is polled every `--interval-ms`
+/// for a new modification time or size. Either way the command re-runs once
+/// the file has been quiet for one more interval, so a save in progress is
+/// not read half-written. With `--diff`, runs after the first print only the
+/// lines that changed.
+pub fn run(args: Args) -> Result<()> {
+	let Args {
+		file,
+		interval_ms,
+		diff,
+		max_runs,
+		command,
+	} = args;
+
+	let command = command_args(&file, command);
+	let exe = std::env::current_exe()?;
+	let interval = Duration::from_millis(interval_ms.max(1));
+
+	let mut changes = Changes::new(&file);
+	let mut previous = None;
+	let mut runs = 0;
+	loop {
+		runs += 1;
+		let output = Command::new(&exe).args(&command).output()?;
+		let stdout = String::from_utf8_lossy(&output.stdout).into_owned();
+		let mut out = quiet::stdout();
+		writeln!(out, "--- run {runs}: {} (exit {}) ---", file.display(), exit_code(output.status))?;
+		match previous.as_deref() {
+			Some(previous) if diff => write_line_diff(&mut out, previous, &stdout)?,
+			_ => out.write_all(stdout.as_bytes())?,
+		}
+		out.flush()?;
+		quiet::stderr().write_all(&output.stderr)?;
+		previous = Some(stdout);
+
+		if max_runs.is_some_and(|max_runs| runs >= max_runs) {
+			return Ok(());
+		}
+		changes.wait(&file, interval)?;
+	}
+}
+
+/// Source of change notifications for the watched file.
+enum Changes {
+	#[cfg(target_os = "linux")]
+	Notify(inotify::DirWatch),
+	/// Last seen modification time and size.
+	Poll(Option<(SystemTime, u64)>),
+}
+
+impl Changes {
+	/// Register before the first run, so a save during it is not missed.
+	fn new(file: &Path) -> Self {
+		Self::notify(file).unwrap_or_else(|| Self::Poll(stamp(file)))
+	}
+
+	#[cfg(target_os = "linux")]
+	fn notify(file: &Path) -> Option<Self> {
+		inotify::DirWatch::new(file).ok().map(Self::Notify)
+	}
+
+	#[cfg(not(target_os = "linux"))]
+	fn notify(_file: &Path) -> Option<Self> {
+		None
+	}
+
+	/// Block until the file changed and has been quiet for `interval`.
+	fn wait(&mut self, file: &Path, interval: Duration) -> Result<()> {
+		match self {
+			#[cfg(target_os = "linux")]
+			Self::Notify(watch) => Ok(watch.wait(interval)?),
+			Self::Poll(last) => loop {
+				thread::sleep(interval);
+				let current = stamp(file);
+				if current.is_none() || current == *last {
+					continue;
+				}
+				thread::sleep(interval);
+				if stamp(file) == current {
+					*last = current;
+					return Ok(());
+				}
+			},
+		}
+	}
+}
+
+/// Subcommand arguments with `{}` replaced by `file`, or `file` inserted
+/// after the subcommand name when there is no `{}`.
+fn command_args(file: &Path, mut command: Vec<OsString>) -> Vec<OsString> {
+	if command.iter().any(|arg| arg == "{}") {
+		for arg in &mut command {
+			if arg == "{}" {
+				*arg = file.as_os_str().to_owned();
+			}
+		}
+	} else {
+		command.insert(1.min(command.len()), file.as_os_str().to_owned());
+	}
+	command
+}
+
+/// Modification time and size; `None` while the file is missing, as during
+/// a save that replaces it.
+fn stamp(path: &Path) -> Option<(SystemTime, u64)> {
+	let meta = fs::metadata(path).ok()?;
+	Some((meta.modified().ok()?, meta.len()))
+}
+
+/// Lines removed from `old` as `-line` and added in `new` as `+line`.
+///
+/// The common prefix and suffix are skipped; the lines between are aligned
+/// by longest common subsequence, or printed in full when that table would
+/// exceed [`MAX_DIFF_CELLS`].
+fn write_line_diff(out: &mut impl Write, old: &str, new: &str) -> io::Result<()> {
+	let old: Vec<&str> = old.lines().collect();
+	let new: Vec<&str> = new.lines().collect();
+	if old == new {
+		return writeln!(out, "(no change)");
+	}
+
+	let prefix = old.iter().zip(&new).take_while(|(a, b)| a == b).count();
+	let suffix = old[prefix..].iter().rev().zip(new[prefix..].iter().rev()).take_while(|(a, b)| a == b).count();
+	let changed_old = &old[prefix..old.len() - suffix];
+	let changed_new = &new[prefix..new.len() - suffix];
+	if (changed_old.len() + 1).saturating_mul(changed_new.len() + 1) > MAX_DIFF_CELLS {
+		writeln!(
+			out,
+			"(diff skipped: {} old and {} new changed lines; full output follows)",
+			changed_old.len(),
+			changed_new.len()
+		)?;
+		for line in &new {
+			writeln!(out, "{line}")?;
+		}
+		return Ok(());
+	}
+	let (old, new) = (changed_old, changed_new);
+
+	// common[i][j]: LCS length of old[i..] and new[j..].
+	let mut common = vec![vec![0_u32; new.len() + 1]; old.len() + 1];
+	for i in (0..old.len()).rev() {
+		for j in (0..new.len()).rev() {
+			common[i][j] = if old[i] == new[j] {
+				common[i + 1][j + 1] + 1
+			} else {
+				common[i + 1][j].max(common[i][j + 1])
+			};
+		}
+	}
+
+	let (mut i, mut j) = (0, 0);
+	while i < old.len() || j < new.len() {
+		if i < old.len() && j < new.len() && old[i] == new[j] {
+			i += 1;
+			j += 1;
+		} else if i < old.len() && (j == new.len() || common[i + 1][j] >= common[i][j + 1]) {
+			writeln!(out, "-{}", old[i])?;
+			i += 1;
+		} else {
+			writeln!(out, "+{}", new[j])?;
+			j += 1;
+		}
+	}
+	Ok(())
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,85 @@
+use std::io::{BufRead, BufReader, Read};
+use std::process::{Command, Stdio};
+use std::time::{Duration, Instant};
+
+use blendoc_testkit::blendoc_bin;
+use blendoc_testkit::builder::{HeaderKind, scene_file};
+
+use super::write_line_diff;
+use crate::cmd::test_support::{TempBlend, run_blendoc};
+
+fn line_diff(old: &str, new: &str) -> String {
+	let mut out = Vec::new();
+	write_line_diff(&mut out, old, new).expect("diff written");
+	String::from_utf8(out).expect("utf8 diff")
+}
+
+#[test]
+fn line_diff_lists_removed_and_added_lines() {
+	assert_eq!(line_diff("a\nb\nc\n", "a\nb\nc\n"), "(no change)\n");
+	assert_eq!(line_diff("a\nb\nc\n", "a\nB\nc\nd\n"), "-b\n+B\n+d\n");
+	assert_eq!(line_diff("", "x\n"), "+x\n");
+	assert_eq!(line_diff("a\nb\na\n", "a\na\n"), "-b\n");
+}
+
+#[test]
+fn line_diff_prints_full_output_past_the_table_limit() {
+	let old: String = (0..3000).map(|idx| format!("old {idx}\n")).collect();
+	let new: String = (0..3000).map(|idx| format!("new {idx}\n")).collect();
+	let same_ends = format!("head\n{old}tail\n");
+
+	let diff = line_diff(&same_ends, &format!("head\n{new}tail\n"));
+	assert!(diff.starts_with("(diff skipped: 3000 old and 3000 new changed lines; full output follows)\nhead\nnew 0\n"));
+	assert!(diff.ends_with("new 2999\ntail\n"));
+
+	let prefixed: String = (0..100_000).map(|idx| format!("line {idx}\n")).collect();
+	assert_eq!(line_diff(&format!("{prefixed}x\n"), &format!("{prefixed}y\n")), "-x\n+y\n");
+}
+
+#[test]
+fn watch_reruns_command_after_the_file_changes() {
+	let target = TempBlend::scene("watch");
+	let target_arg = target.arg();
+
+	let once = run_blendoc(&["watch", target_arg, "--max-runs", "1", "--", "info"]);
+	assert!(once.status.success(), "watch failed: {}", String::from_utf8_lossy(&once.stderr));
+	let once = String::from_utf8_lossy(&once.stdout);
+	assert!(
+		once.starts_with(&format!("--- run 1: {target_arg} (exit 0) ---\npath: {target_arg}\n")),
+		"{once}"
+	);
+
+	let mut child = Command::new(blendoc_bin())
+		.args(["watch", target_arg, "--max-runs", "2", "--interval-ms", "20", "--diff", "--", "info", "{}"])
+		.stdout(Stdio::piped())
+		.stderr(Stdio::null())
+		.spawn()
+		.expect("watch spawns");
+	let mut stdout = BufReader::new(child.stdout.take().expect("stdout piped"));
+
+	// The watch is registered before the first run, so a save after its header is always seen.
+	let mut header = String::new();
+	stdout.read_line(&mut header).expect("first header read");
+	assert!(header.starts_with("--- run 1:"), "{header}");
+	std::fs::write(&target, scene_file(HeaderKind::Legacy4, false)).expect("synthetic file rewritten");
+
+	let deadline = Instant::now() + Duration::from_secs(60);
+	let status = loop {
+		if let Some(status) = child.try_wait().expect("watch polled") {
+			break status;
+		}
+		if Instant::now() > deadline {
+			child.kill().expect("watch killed");
+			panic!("watch did not re-run after the file changed");
+		}
+		std::thread::sleep(Duration::from_millis(20));
+	};
+	let mut rest = String::new();
+	stdout.read_to_string(&mut rest).expect("output read");
+
+	assert!(status.success());
+	let (_, second) = rest.split_once("--- run 2:").expect("second run");
+	assert!(second.contains("\n-pointer_size: 8\n"), "{second}");
+	assert!(second.contains("\n+pointer_size: 4\n"), "{second}");
+	assert!(!second.contains(&format!("path: {target_arg}")), "{second}");
+}
@@ -0,0 +1,139 @@
+use std::io::Write;
+use std::path::{Path, PathBuf};
+
+use blendoc::blend::{BlendFile, BloatFinding, BloatKind, BloatOptions, Result, analyze_bloat};
+
+use crate::cmd::output::OutputArgs;
+use crate::cmd::util::human_bytes;
+
+#[derive(clap::Args)]
+pub struct Args {
+	pub file: PathBuf,
+	#[arg(long = "min-bytes", default_value_t = 1 << 20)]
+	pub min_bytes: u64,
+	#[arg(long = "min-duplicate-block", default_value_t = 4096)]
+	pub min_duplicate_block: u64,
+	#[arg(long = "top-ids", default_value_t = 5)]
+	pub top_ids: usize,
+	#[arg(long)]
+	pub json: bool,
+	#[command(flatten)]
+	pub output: OutputArgs,
+}
+
+/// Rank packed files, orphan IDs, duplicated data, and the largest IDs by size.
+///
+/// Each finding comes with the command that drills into it.
+pub fn run(args: Args) -> Result<()> {
+	let Args {
+		file: path,
+		min_bytes,
+		min_duplicate_block,
+		top_ids,
+		json,
+		output,
+	} = args;
+
+	let blend = BlendFile::open(&path)?;
+	let dna = blend.dna()?;
+	let options = BloatOptions {
+		min_bytes,
+		min_duplicate_block,
+		max_large_ids: top_ids,
+		..BloatOptions::default()
+	};
+	let report = analyze_bloat(&blend, &dna, &options)?;
+
+	let rows: Vec<FindingRow> = report
+		.findings
+		.iter()
+		.enumerate()
+		.map(|(rank, item)| FindingRow {
+			rank: rank + 1,
+			kind: item.kind.as_str(),
+			bytes: item.bytes,
+			share: share(item.bytes, report.file_bytes),
+			count: item.count,
+			summary: summary(item),
+			examples: item.examples.iter().map(|name| name.to_string()).collect::<Vec<_>>().join(", "),
+			investigate: investigate(&path, item),
+		})
+		.collect();
+
+	let mut out = output.open(json)?;
+	if !out.is_text() {
+		let payload = WhyBigJson {
+			path: path.display().to_string(),
+			file_bytes: report.file_bytes,
+			findings: &rows,
+		};
+		out.structured(&payload, &rows)?;
+		return out.finish();
+	}
+
+	writeln!(out, "path: {}", path.display())?;
+	writeln!(out, "file_bytes: {} ({})", report.file_bytes, human_bytes(report.file_bytes))?;
+	if rows.is_empty() {
+		writeln!(out, "no findings of at least {}", human_bytes(min_bytes))?;
+	}
+	for row in &rows {
+		writeln!(out, "{}. {}: {} ({})", row.rank, row.summary, human_bytes(row.bytes), row.share)?;
+		if !row.examples.is_empty() {
+			writeln!(out, "   largest: {}", row.examples)?;
+		}
+		writeln!(out, "   investigate: {}", row.investigate)?;
+	}
+	out.finish()
+}
+
+fn summary(item: &BloatFinding) -> String {
+	let plural = if item.count == 1 { "" } else { "s" };
+	match item.kind {
+		BloatKind::PackedFiles => format!("{} packed file{plural}", item.count),
+		BloatKind::OrphanIds => format!("{} orphan {} ID{plural}", item.count, item.subject),
+		BloatKind::DuplicateData => format!("{} duplicated DATA block{plural}", item.count),
+		BloatKind::LargeId => format!("{} and its data ({} block{plural})", item.subject, item.count),
+	}
+}
+
+fn investigate(path: &Path, item: &BloatFinding) -> String {
+	let path = path.display();
+	let first = item.examples.first().map(|name| name.as_ref());
+	match (item.kind, first) {
+		(BloatKind::PackedFiles, _) => format!("blendoc packed {path}"),
+		(BloatKind::OrphanIds, Some(name)) => format!("blendoc xref {path} --id {name}"),
+		(BloatKind::DuplicateData, Some(name)) => format!("blendoc id {path} --id {name}"),
+		(BloatKind::LargeId, _) => format!("blendoc id {path} --id {}", item.subject),
+		_ => format!("blendoc stats {path}"),
+	}
+}
+
+fn share(bytes: u64, total: u64) -> String {
+	if total == 0 {
+		return "0.0%".to_owned();
+	}
+	format!("{:.1}%", bytes as f64 * 100.0 / total as f64)
+}
+
+#[derive(serde::Serialize)]
+struct WhyBigJson<'a> {
+	path: String,
+	file_bytes: u64,
+	findings: &'a [FindingRow],
+}
+
+/// One ranked finding; also the `--format csv` row.
+#[derive(serde::Serialize)]
+struct FindingRow {
+	rank: usize,
+	kind: &'static str,
+	bytes: u64,
+	share: String,
+	count: usize,
+	summary: String,
+	examples: String,
+	investigate: String,
+}
+
+#[cfg(test)]
+mod tests;
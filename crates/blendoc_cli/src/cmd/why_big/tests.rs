@@ -0,0 +1,60 @@
+use blendoc_testkit::builder::{BlendBuilder, HeaderKind};
+
+use crate::cmd::test_support::{TempBlend, run_blendoc, run_blendoc_json};
+
+/// `SCScene` using `MEUsed`, plus an unused `MEOrphan` with 8 KiB of vertex data.
+fn orphan_file() -> Vec<u8> {
+	let mut builder = BlendBuilder::new(HeaderKind::Legacy8, 404, false);
+	let sdna = builder.sdna_mut();
+	sdna.add_struct("Link", &[("Link", "*next"), ("Link", "*prev")]);
+	sdna.add_type("char", 1);
+	sdna.add_type("int", 4);
+	sdna.add_type("void", 0);
+	sdna.add_struct(
+		"ID",
+		&[
+			("void", "*next"),
+			("void", "*prev"),
+			("void", "*lib"),
+			("char", "name[24]"),
+			("int", "flag"),
+			("int", "pad"),
+		],
+	);
+	let mesh = sdna.add_struct("Mesh", &[("ID", "id"), ("void", "*verts")]);
+	let scene = sdna.add_struct("Scene", &[("ID", "id"), ("Mesh", "*mesh")]);
+
+	let id = |builder: &BlendBuilder, name: &str, ptr: u64| {
+		let mut payload = builder.payload();
+		payload.ptr(0).ptr(0).ptr(0).name(name, 24).i32(0).i32(0).ptr(ptr);
+		payload.finish()
+	};
+	let scene_payload = id(&builder, "SCScene", 0x2000);
+	let used_payload = id(&builder, "MEUsed", 0);
+	let orphan_payload = id(&builder, "MEOrphan", 0x3100);
+	builder
+		.block(*b"SC\0\0", scene, 0x1000, 1, scene_payload)
+		.block(*b"ME\0\0", mesh, 0x2000, 1, used_payload)
+		.block(*b"ME\0\0", mesh, 0x3000, 1, orphan_payload)
+		.block(*b"DATA", 0, 0x3100, 1, vec![3; 8192]);
+	builder.build()
+}
+
+#[test]
+fn why_big_ranks_orphans_with_investigate_commands() {
+	let target = TempBlend::new("why_big", &orphan_file());
+	let target_arg = target.arg();
+
+	let json = run_blendoc_json(&["why-big", target_arg, "--min-bytes", "4096", "--json"]);
+	let text = run_blendoc(&["why-big", target_arg, "--min-bytes", "4096"]);
+
+	let findings = json["findings"].as_array().expect("findings array");
+	let orphan = findings.iter().find(|item| item["kind"] == "orphan_ids").expect("orphan finding");
+	assert_eq!(orphan["count"], 1);
+	assert_eq!(orphan["examples"], "MEOrphan");
+	assert!(orphan["investigate"].as_str().expect("command").ends_with("--id MEOrphan"));
+	assert!(findings.iter().all(|item| item["bytes"].as_u64().expect("bytes") >= 4096));
+
+	let text = String::from_utf8_lossy(&text.stdout);
+	assert!(text.contains("1 orphan Mesh ID: 8.1 KiB"), "{text}");
+}
@@ -1,8 +1,8 @@
 use std::path::PathBuf;
 
-use blendoc::blend::{BlendError, BlendFile, IdIndex, XrefOptions, find_inbound_refs_to_ptr, scan_id_blocks};
+use blendoc::blend::{BlendFile, IdIndex, InboundRef, InboundRefLocation, LibraryNamespaces, XrefOptions, find_inbound_refs_to_ptr};
 
-use crate::cmd::util::{IdOrPtrSelector, emit_json, parse_id_or_ptr_selector, ptr_hex};
+use crate::cmd::util::{IdOrPtrSelector, emit_json, library_namespaces, parse_id_or_ptr_selector, ptr_hex, scan_ids};
 
 #[derive(clap::Args)]
 pub struct Args {
@@ -15,6 +15,10 @@ pub struct Args {
 	pub refs_depth: Option<u32>,
 	#[arg(long)]
 	pub limit: Option<usize>,
+	#[arg(long = "annotate-libs")]
+	pub annotate_libs: bool,
+	#[arg(long)]
+	pub verbose: bool,
 	#[arg(long)]
 	pub json: bool,
 }
@@ -27,6 +31,8 @@ pub fn run(args: Args) -> blendoc::blend::Result<()> {
 		ptr,
 		refs_depth,
 		limit,
+		annotate_libs,
+		verbose,
 		json,
 	} = args;
 
@@ -35,11 +41,11 @@ pub fn run(args: Args) -> blendoc::blend::Result<()> {
 	let blend = BlendFile::open(&path)?;
 	let dna = blend.dna()?;
 	let index = blend.pointer_index()?;
-	let ids = IdIndex::build(scan_id_blocks(&blend, &dna)?);
+	let ids = IdIndex::build(scan_ids(&blend, &dna)?);
 
 	let (target_ptr, target_label) = match selector {
 		IdOrPtrSelector::Id(name) => {
-			let row = ids.get_by_name(&name).ok_or(BlendError::IdRecordNotFound { name: name.clone() })?;
+			let row = ids.resolve_selector(&name)?;
 			(row.old_ptr, format!("id:{}", row.id_name))
 		}
 		IdOrPtrSelector::Ptr(ptr) => (ptr, format!("ptr:0x{ptr:016x}")),
@@ -59,41 +65,68 @@ pub fn run(args: Args) -> blendoc::blend::Result<()> {
 	if let Some(limit) = limit {
 		options.max_results = limit;
 	}
+	options.include_locations = verbose;
+	options.progress = crate::cmd::progress::sink();
+	options.cancel = crate::cmd::interrupt::token();
+	let bhead_size = blend.header.bhead_layout()?.capabilities().size;
 
 	let refs = find_inbound_refs_to_ptr(&dna, &index, &ids, target_ptr, &options)?;
+	let namespaces = library_namespaces(&blend, &dna, annotate_libs)?;
 
 	if json {
-		print_json(&path, &target_label, target_canonical, target_type, target_id, &refs);
+		let inbound = inbound_json(&refs, &namespaces, bhead_size);
+		print_json(&path, &target_label, target_canonical, target_type, target_id, inbound, &namespaces);
 		return Ok(());
 	}
 
+	let target_display = target_id.map_or_else(|| "-".to_owned(), |id| namespaces.annotate(target_canonical, id));
+
 	println!("path: {}", path.display());
 	println!("target: {target_label}");
 	println!("target_canonical: 0x{target_canonical:016x}");
 	println!("target_type: {target_type}");
-	println!("target_id: {}", target_id.unwrap_or("-"));
+	println!("target_id: {target_display}");
 	println!("inbound: {}", refs.len());
 	for inbound in refs {
 		println!(
 			"{}({}) -{}-> {}({})",
-			inbound.from_id.as_deref().unwrap_or("-"),
+			inbound
+				.from_id
+				.as_deref()
+				.map_or_else(|| "-".to_owned(), |id| namespaces.annotate(inbound.from, id)),
 			inbound.from_type,
 			inbound.field,
-			target_id.unwrap_or("-"),
+			target_display,
 			target_type
 		);
+		if let Some(location) = inbound.location {
+			println!(
+				"  at file=0x{:x} block=0x{:x} payload+0x{:x} field+0x{:x} target_element={}",
+				location_file_offset(location, bhead_size),
+				location.block_offset,
+				location.payload_offset,
+				location.field_offset,
+				location.target_element
+			);
+		}
 	}
 
 	Ok(())
 }
 
+/// Absolute file offset of the pointer word: block header start + header size + payload offset.
+fn location_file_offset(location: InboundRefLocation, bhead_size: usize) -> usize {
+	location.block_offset + bhead_size + location.payload_offset
+}
+
 fn print_json(
 	path: &std::path::Path,
 	target_label: &str,
 	target_canonical: u64,
 	target_type: &str,
 	target_id: Option<&str>,
-	refs: &[blendoc::blend::InboundRef],
+	inbound: Vec<InboundJson>,
+	namespaces: &LibraryNamespaces,
 ) {
 	let payload = XrefJson {
 		path: path.display().to_string(),
@@ -101,26 +134,51 @@ fn print_json(
 		target_canonical: ptr_hex(target_canonical),
 		target_type: target_type.to_owned(),
 		target_id: target_id.map(str::to_owned),
-		inbound: refs
-			.iter()
-			.map(|inbound| InboundJson {
-				from: ptr_hex(inbound.from),
-				from_type: inbound.from_type.to_string(),
-				from_id: inbound.from_id.as_deref().map(|item| item.to_string()),
-				field: inbound.field.to_string(),
-			})
-			.collect(),
+		target_library: namespaces.get(target_canonical).map(str::to_owned),
+		inbound,
 	};
 
 	emit_json(&payload);
 }
 
+fn inbound_json(refs: &[InboundRef], namespaces: &LibraryNamespaces, bhead_size: usize) -> Vec<InboundJson> {
+	refs.iter()
+		.map(|inbound| InboundJson {
+			from: ptr_hex(inbound.from),
+			from_type: inbound.from_type.to_string(),
+			from_id: inbound.from_id.as_deref().map(|item| item.to_string()),
+			from_library: namespaces.get(inbound.from).map(str::to_owned),
+			field: inbound.field.to_string(),
+			location: inbound.location.map(|location| LocationJson {
+				file_offset: location_file_offset(location, bhead_size),
+				block_offset: location.block_offset,
+				payload_offset: location.payload_offset,
+				field_offset: location.field_offset,
+				target_element: location.target_element,
+			}),
+		})
+		.collect()
+}
+
 #[derive(serde::Serialize)]
 struct InboundJson {
 	from: String,
 	from_type: String,
 	from_id: Option<String>,
+	#[serde(skip_serializing_if = "Option::is_none")]
+	from_library: Option<String>,
 	field: String,
+	#[serde(skip_serializing_if = "Option::is_none")]
+	location: Option<LocationJson>,
+}
+
+#[derive(serde::Serialize)]
+struct LocationJson {
+	file_offset: usize,
+	block_offset: usize,
+	payload_offset: usize,
+	field_offset: usize,
+	target_element: usize,
 }
 
 #[derive(serde::Serialize)]
@@ -130,5 +188,7 @@ struct XrefJson {
 	target_canonical: String,
 	target_type: String,
 	target_id: Option<String>,
+	#[serde(skip_serializing_if = "Option::is_none")]
+	target_library: Option<String>,
 	inbound: Vec<InboundJson>,
 }
@@ -1,60 +1,257 @@
 #![allow(missing_docs)]
 
-use clap::{Parser, Subcommand};
+use clap::{CommandFactory, FromArgMatches, Parser, Subcommand};
+
+// Shadow the std print macros for the whole crate so `--quiet` is honoured at
+// every print site without threading a flag through each command.
+macro_rules! print {
+	($($arg:tt)*) => {{
+		if !$crate::cmd::quiet::enabled() {
+			::std::print!($($arg)*);
+		}
+	}};
+}
+
+macro_rules! println {
+	($($arg:tt)*) => {{
+		if !$crate::cmd::quiet::enabled() {
+			::std::println!($($arg)*);
+		}
+	}};
+}
+
+macro_rules! eprintln {
+	($($arg:tt)*) => {{
+		if !$crate::cmd::quiet::enabled() {
+			::std::eprintln!($($arg)*);
+		}
+	}};
+}
 
 mod cmd;
 
+#[global_allocator]
+static ALLOC: cmd::stats::CountingAlloc = cmd::stats::CountingAlloc;
+
 #[derive(Parser)]
 #[command(name = "blendoc", about = "Blender .blend inspection tools")]
 struct Cli {
 	#[command(subcommand)]
 	command: Commands,
+	#[arg(long, global = true)]
+	timings: bool,
+	#[arg(long = "mem-stats", global = true)]
+	mem_stats: bool,
+	#[arg(long, global = true, value_name = "SESSION")]
+	record: Option<std::path::PathBuf>,
+	#[arg(long, global = true, overrides_with = "no_cached")]
+	cached: bool,
+	#[arg(long = "no-cached", global = true, overrides_with = "cached")]
+	no_cached: bool,
+	#[arg(long = "cache-dir", global = true, value_name = "DIR")]
+	cache_dir: Option<std::path::PathBuf>,
+	#[arg(
+		long = "schema-version",
+		global = true,
+		value_name = "N",
+		value_parser = clap::value_parser!(u32).range(i64::from(cmd::schema::OLDEST)..=i64::from(cmd::schema::CURRENT)),
+	)]
+	schema_version: Option<u32>,
+	#[arg(long = "strict-ids", global = true)]
+	strict_ids: bool,
+	#[arg(long, global = true)]
+	progress: bool,
+	#[arg(long, global = true)]
+	quiet: bool,
+	#[cfg(feature = "trace")]
+	#[arg(long = "trace-timing", global = true)]
+	trace_timing: bool,
 }
 
 #[derive(Subcommand)]
 enum Commands {
 	Info(cmd::info::Args),
+	Has(cmd::has::Args),
+	Id(cmd::id::Args),
 	Ids(cmd::ids::Args),
+	Images(cmd::images::Args),
+	Blocks(cmd::blocks::Args),
+	Codes(cmd::codes::Args),
+	Copy(cmd::copy::Args),
+	Cycles(cmd::cycles::Args),
 	Dna(cmd::dna::Args),
+	DnaDiff(cmd::dna_diff::Args),
+	Dangling(cmd::dangling::Args),
 	Decode(cmd::decode::Args),
+	Deps(cmd::deps::Args),
+	Diff(cmd::diff::Args),
+	Doctor(cmd::doctor::Args),
+	Export(cmd::export::Args),
+	#[cfg(feature = "sqlite")]
+	ExportSqlite(cmd::export_sqlite::Args),
+	Find(cmd::find::Args),
 	Chase(cmd::chase::Args),
+	Recompress(cmd::recompress::Args),
+	Replay(cmd::replay::Args),
+	Report(cmd::report::Args),
+	Repl(cmd::repl::Args),
 	Refs(cmd::refs::Args),
 	Graph(cmd::graph::Args),
 	Idgraph(cmd::idgraph::Args),
+	Hexdump(cmd::hexdump::Args),
+	Hierarchy(cmd::hierarchy::Args),
+	Anim(cmd::anim::Args),
+	Infer(cmd::infer::Args),
 	Libs(cmd::libs::Args),
+	Libtree(cmd::libtree::Args),
+	List(cmd::list::Args),
+	Mesh(cmd::mesh::Args),
+	Nodetree(cmd::nodetree::Args),
+	OpenCheck(cmd::open_check::Args),
+	Orphans(cmd::orphans::Args),
+	Packed(cmd::packed::Args),
+	Previews(cmd::previews::Args),
+	ProjectAudit(cmd::project_audit::Args),
+	Props(cmd::props::Args),
+	Prune(cmd::prune::Args),
+	Thumb(cmd::thumb::Args),
+	ToText(cmd::to_text::Args),
 	Xref(cmd::xref::Args),
+	Rename(cmd::rename::Args),
 	Route(cmd::route::Args),
+	Selftest(cmd::selftest::Args),
+	Serve(cmd::serve::Args),
 	Show(cmd::show::Args),
+	Search(cmd::search::Args),
+	Stats(cmd::size_stats::Args),
+	Validate(cmd::validate::Args),
 	Walk(cmd::walk::Args),
+	Watch(cmd::watch::Args),
+	WhyBig(cmd::why_big::Args),
 	Scene(cmd::scene::Args),
+	Schema(cmd::schema::Args),
 	Camera(cmd::camera::Args),
 }
 
 fn main() {
-	if let Err(err) = run() {
+	// Count from the start when `--mem-stats` is likely present so the `args`
+	// phase is measured; the parsed flag below has the final say.
+	cmd::stats::set_counting(std::env::args_os().take_while(|arg| arg != "--").any(|arg| arg == "--mem-stats"));
+	let args = cmd::stats::phase("args");
+	let matches = Cli::command().try_get_matches().unwrap_or_else(|err| cmd::exit_code::exit_clap(err));
+	let cli = Cli::from_arg_matches(&matches).unwrap_or_else(|err| cmd::exit_code::exit_clap(err));
+	drop(args);
+	cmd::stats::set_counting(cli.mem_stats);
+	cmd::schema::select(matches.subcommand_name(), cli.schema_version);
+	cmd::util::set_strict_ids(cli.strict_ids);
+	cmd::progress::set_enabled(cli.progress);
+	cmd::quiet::set_enabled(cli.quiet);
+	cmd::interrupt::install();
+	#[cfg(feature = "trace")]
+	if cli.trace_timing {
+		cmd::trace_timing::install();
+	}
+
+	if let Some(session) = &cli.record {
+		match cmd::replay::record(session) {
+			Ok(code) => std::process::exit(code),
+			Err(err) => {
+				eprintln!("error: {err}");
+				std::process::exit(cmd::exit_code::for_error(&err));
+			}
+		}
+	}
+
+	if cli.cached {
+		match cmd::result_cache::run_cached(cli.cache_dir.as_deref()) {
+			Ok(code) => std::process::exit(code),
+			Err(err) => {
+				eprintln!("error: {err}");
+				std::process::exit(cmd::exit_code::for_error(&err));
+			}
+		}
+	}
+
+	let (timings, mem_stats) = (cli.timings, cli.mem_stats);
+	let result = {
+		let _command = cmd::stats::phase("command");
+		run(cli.command)
+	};
+	cmd::stats::report(timings, mem_stats);
+	#[cfg(feature = "trace")]
+	cmd::trace_timing::report();
+
+	if let Err(err) = result {
 		eprintln!("error: {err}");
-		std::process::exit(1);
+		std::process::exit(cmd::exit_code::for_error(&err));
+	}
+	if cmd::interrupt::was_interrupted() {
+		std::process::exit(cmd::interrupt::EXIT_INTERRUPTED);
 	}
 }
 
-fn run() -> blendoc::blend::Result<()> {
-	let cli = Cli::parse();
-
-	match cli.command {
+fn run(command: Commands) -> blendoc::blend::Result<()> {
+	match command {
 		Commands::Info(args) => cmd::info::run(args),
+		Commands::Has(args) => cmd::has::run(args),
+		Commands::Id(args) => cmd::id::run(args),
 		Commands::Ids(args) => cmd::ids::run(args),
+		Commands::Images(args) => cmd::images::run(args),
+		Commands::Blocks(args) => cmd::blocks::run(args),
+		Commands::Codes(args) => cmd::codes::run(args),
+		Commands::Copy(args) => cmd::copy::run(args),
+		Commands::Cycles(args) => cmd::cycles::run(args),
 		Commands::Dna(args) => cmd::dna::run(args),
+		Commands::DnaDiff(args) => cmd::dna_diff::run(args),
+		Commands::Dangling(args) => cmd::dangling::run(args),
 		Commands::Decode(args) => cmd::decode::run(args),
+		Commands::Deps(args) => cmd::deps::run(args),
+		Commands::Diff(args) => cmd::diff::run(args),
+		Commands::Doctor(args) => cmd::doctor::run(args),
+		Commands::Export(args) => cmd::export::run(args),
+		#[cfg(feature = "sqlite")]
+		Commands::ExportSqlite(args) => cmd::export_sqlite::run(args),
+		Commands::Find(args) => cmd::find::run(args),
 		Commands::Chase(args) => cmd::chase::run(args),
+		Commands::Recompress(args) => cmd::recompress::run(args),
+		Commands::Replay(args) => cmd::replay::run(args),
+		Commands::Report(args) => cmd::report::run(args),
+		Commands::Repl(args) => cmd::repl::run(args),
 		Commands::Refs(args) => cmd::refs::run(args),
 		Commands::Graph(args) => cmd::graph::run(args),
 		Commands::Idgraph(args) => cmd::idgraph::run(args),
+		Commands::Hexdump(args) => cmd::hexdump::run(args),
+		Commands::Hierarchy(args) => cmd::hierarchy::run(args),
+		Commands::Anim(args) => cmd::anim::run(args),
+		Commands::Infer(args) => cmd::infer::run(args),
 		Commands::Libs(args) => cmd::libs::run(args),
+		Commands::Libtree(args) => cmd::libtree::run(args),
+		Commands::List(args) => cmd::list::run(args),
+		Commands::Mesh(args) => cmd::mesh::run(args),
+		Commands::Nodetree(args) => cmd::nodetree::run(args),
+		Commands::OpenCheck(args) => cmd::open_check::run(args),
+		Commands::Orphans(args) => cmd::orphans::run(args),
+		Commands::Packed(args) => cmd::packed::run(args),
+		Commands::Previews(args) => cmd::previews::run(args),
+		Commands::ProjectAudit(args) => cmd::project_audit::run(args),
+		Commands::Props(args) => cmd::props::run(args),
+		Commands::Prune(args) => cmd::prune::run(args),
+		Commands::Thumb(args) => cmd::thumb::run(args),
+		Commands::ToText(args) => cmd::to_text::run(args),
 		Commands::Xref(args) => cmd::xref::run(args),
+		Commands::Rename(args) => cmd::rename::run(args),
 		Commands::Route(args) => cmd::route::run(args),
+		Commands::Selftest(args) => cmd::selftest::run(args),
+		Commands::Serve(args) => cmd::serve::run(args),
 		Commands::Show(args) => cmd::show::run(args),
+		Commands::Search(args) => cmd::search::run(args),
+		Commands::Stats(args) => cmd::size_stats::run(args),
+		Commands::Validate(args) => cmd::validate::run(args),
 		Commands::Walk(args) => cmd::walk::run(args),
+		Commands::Watch(args) => cmd::watch::run(args),
+		Commands::WhyBig(args) => cmd::why_big::run(args),
 		Commands::Scene(args) => cmd::scene::run(args),
+		Commands::Schema(args) => cmd::schema::run(args),
 		Commands::Camera(args) => cmd::camera::run(args),
 	}
 }
@@ -0,0 +1,51 @@
+//! Pointer index build and lookup throughput on synthetic many-block files.
+//!
+//! Run with `cargo bench -p blendoc --bench pointer_index`.
+
+use std::hint::black_box;
+
+use blendoc::blend::BlendFile;
+use blendoc_testkit::builder::{BlendBuilder, HeaderKind};
+use criterion::{BenchmarkId, Criterion, criterion_group, criterion_main};
+
+/// Payload bytes per block; stored starts are spaced twice as far apart.
+const CHUNK: u64 = 32;
+const BASE: u64 = 0x10_0000;
+
+/// Uncompressed file with `blocks` `DATA` blocks at evenly spaced old addresses.
+fn many_blocks_file(blocks: u64) -> Vec<u8> {
+	let mut builder = BlendBuilder::new(HeaderKind::Legacy8, 300, false);
+	let sdna = builder.sdna_mut();
+	sdna.add_type("char", 1);
+	let chunk = sdna.add_struct("Chunk", &[("char", "data[32]")]);
+	for idx in 0..blocks {
+		builder.block(*b"DATA", chunk, BASE + idx * CHUNK * 2, 1, vec![0; CHUNK as usize]);
+	}
+	builder.build()
+}
+
+fn pointer_index(c: &mut Criterion) {
+	let mut group = c.benchmark_group("pointer_index");
+	for blocks in [1_000_u64, 100_000] {
+		let blend = BlendFile::from_bytes(many_blocks_file(blocks)).expect("synthetic file opens");
+		let index = blend.pointer_index().expect("pointer index builds");
+		let ptrs: Vec<u64> = (0..blocks).map(|idx| BASE + idx * CHUNK * 2).collect();
+
+		group.bench_with_input(BenchmarkId::new("build", blocks), &blend, |b, blend| {
+			b.iter(|| blend.pointer_index().expect("pointer index builds").len());
+		});
+		group.bench_with_input(BenchmarkId::new("resolve_exact", blocks), &ptrs, |b, ptrs| {
+			b.iter(|| ptrs.iter().filter(|ptr| index.resolve(black_box(**ptr)).is_some()).count());
+		});
+		group.bench_with_input(BenchmarkId::new("resolve_inside", blocks), &ptrs, |b, ptrs| {
+			b.iter(|| ptrs.iter().filter(|ptr| index.resolve(black_box(**ptr + CHUNK / 2)).is_some()).count());
+		});
+		group.bench_with_input(BenchmarkId::new("resolve_miss", blocks), &ptrs, |b, ptrs| {
+			b.iter(|| ptrs.iter().filter(|ptr| index.resolve(black_box(**ptr + CHUNK)).is_some()).count());
+		});
+	}
+	group.finish();
+}
+
+criterion_group!(benches, pointer_index);
+criterion_main!(benches);
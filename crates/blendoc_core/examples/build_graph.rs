@@ -0,0 +1,43 @@
+//! Build a shallow pointer graph rooted at the first scene and print its edges.
+//!
+//! ```text
+//! cargo run -p blendoc --example build_graph -- fixtures/character.blend
+//! ```
+
+use std::path::PathBuf;
+
+use blendoc::blend::{BlendError, BlendFile, GraphOptions, IdIndex, Result, build_graph_from_ptr, codes, scan_id_blocks};
+
+fn main() -> Result<()> {
+	let path = std::env::args_os()
+		.nth(1)
+		.map(PathBuf::from)
+		.unwrap_or_else(|| "fixtures/character.blend".into());
+
+	let file = BlendFile::open(&path)?;
+	let dna = file.dna()?;
+	let index = file.pointer_index()?;
+	let ids = IdIndex::build(scan_id_blocks(&file, &dna)?);
+
+	let scene = file
+		.find_first_block_by_code(codes::ID_SCE)?
+		.ok_or(BlendError::BlockNotFound { code: codes::ID_SCE })?;
+
+	let options = GraphOptions {
+		max_depth: 1,
+		..GraphOptions::default()
+	};
+	let graph = build_graph_from_ptr(&dna, &index, &ids, scene.head.old, &options)?;
+
+	println!("nodes: {} edges: {}", graph.nodes.len(), graph.edges.len());
+	for edge in &graph.edges {
+		let target = graph.nodes.iter().find(|node| node.canonical == edge.to);
+		let label = target.map(|node| node.id_name.as_deref().unwrap_or(&node.type_name)).unwrap_or("?");
+		println!("{} -> {label}", edge.field);
+	}
+	for warning in &graph.warnings {
+		eprintln!("warning: {}", warning.message);
+	}
+
+	Ok(())
+}
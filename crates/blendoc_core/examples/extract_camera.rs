@@ -0,0 +1,43 @@
+//! Follow `Scene.camera` to the camera object and print its name.
+//!
+//! ```text
+//! cargo run -p blendoc --example extract_camera -- fixtures/character.blend
+//! ```
+
+use std::path::PathBuf;
+
+use blendoc::blend::{BlendFile, DecodeOptions, Result, Value, chase_scene_camera};
+
+fn main() -> Result<()> {
+	let path = std::env::args_os()
+		.nth(1)
+		.map(PathBuf::from)
+		.unwrap_or_else(|| "fixtures/character.blend".into());
+
+	let file = BlendFile::open(&path)?;
+	let dna = file.dna()?;
+	let index = file.pointer_index()?;
+
+	let decode = DecodeOptions::for_scene_inspect();
+	let Some((meta, object)) = chase_scene_camera(&file, &dna, &index, &decode, &decode)? else {
+		println!("scene has no camera");
+		return Ok(());
+	};
+
+	let name = object
+		.fields
+		.iter()
+		.find(|field| field.name.as_ref() == "id")
+		.and_then(|field| match &field.value {
+			Value::Struct(id) => id.fields.iter().find(|item| item.name.as_ref() == "name"),
+			_ => None,
+		})
+		.and_then(|field| match &field.value {
+			Value::String(name) => Some(name.as_ref()),
+			_ => None,
+		})
+		.unwrap_or("<unnamed>");
+
+	println!("camera: {name} ({}) at 0x{:016x}", object.type_name, meta.ptr);
+	Ok(())
+}
@@ -0,0 +1,27 @@
+//! List every ID-root record in a `.blend` file.
+//!
+//! ```text
+//! cargo run -p blendoc --example list_ids -- fixtures/character.blend
+//! ```
+
+use std::path::PathBuf;
+
+use blendoc::blend::{BlendFile, IdIndex, Result, scan_id_blocks};
+
+fn main() -> Result<()> {
+	let path = std::env::args_os()
+		.nth(1)
+		.map(PathBuf::from)
+		.unwrap_or_else(|| "fixtures/character.blend".into());
+
+	let file = BlendFile::open(&path)?;
+	let dna = file.dna()?;
+	let ids = IdIndex::build(scan_id_blocks(&file, &dna)?);
+
+	println!("{} ids in {}", ids.records.len(), path.display());
+	for record in &ids.records {
+		println!("0x{:016x}\t{}\t{}", record.old_ptr, record.type_name, record.id_name);
+	}
+
+	Ok(())
+}
@@ -0,0 +1,85 @@
+use crate::blend::{BlendFile, MAX_BLOCK_ALIGNMENT, Result};
+
+/// Inter-block padding and alignment conventions observed in one file.
+///
+/// Offsets are measured from the first block header, since file headers
+/// (12 or 17 bytes) are not themselves aligned.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BlockAlignment {
+	/// Number of blocks visited, including `ENDB`.
+	pub block_count: u32,
+	/// Largest power of two, capped at [`MAX_BLOCK_ALIGNMENT`], dividing every block header offset.
+	pub header_alignment: usize,
+	/// Largest power of two, capped at [`MAX_BLOCK_ALIGNMENT`], dividing every payload length.
+	pub payload_alignment: usize,
+	/// Blocks preceded by padding bytes.
+	pub padded_blocks: u32,
+	/// Total padding bytes skipped between blocks.
+	pub padding_bytes: u64,
+	/// Largest single inter-block gap.
+	pub max_padding: usize,
+	/// Bytes left after the last block (after `ENDB`, or after a file without one).
+	pub trailing_bytes: usize,
+}
+
+impl BlockAlignment {
+	/// Return whether blocks are stored back to back with no padding.
+	pub fn is_packed(&self) -> bool {
+		self.padding_bytes == 0
+	}
+
+	/// Short label for the convention: `packed`, `padded`, or `padded+trailing`.
+	pub fn convention(&self) -> &'static str {
+		match (self.is_packed(), self.trailing_bytes) {
+			(true, 0) => "packed",
+			(true, _) => "packed+trailing",
+			(false, 0) => "padded",
+			(false, _) => "padded+trailing",
+		}
+	}
+}
+
+/// Walk every block header and report padding and alignment between blocks.
+pub fn scan_block_alignment(blend: &BlendFile) -> Result<BlockAlignment> {
+	let head_size = blend.header.bhead_layout()?.capabilities().size;
+	let base = blend.header.header_size;
+	let mut report = BlockAlignment {
+		block_count: 0,
+		header_alignment: MAX_BLOCK_ALIGNMENT,
+		payload_alignment: MAX_BLOCK_ALIGNMENT,
+		padded_blocks: 0,
+		padding_bytes: 0,
+		max_padding: 0,
+		trailing_bytes: 0,
+	};
+	let mut end = base;
+
+	let mut blocks = blend.blocks();
+	while let Some(block) = blocks.next() {
+		let block = block?;
+		let padding = blocks.last_padding();
+		report.block_count += 1;
+		if padding > 0 {
+			report.padded_blocks += 1;
+			report.padding_bytes += padding as u64;
+			report.max_padding = report.max_padding.max(padding);
+		}
+		report.header_alignment = report.header_alignment.min(alignment_of(block.file_offset - base));
+		report.payload_alignment = report.payload_alignment.min(alignment_of(block.payload.len()));
+		end = block.file_offset + head_size + block.payload.len();
+	}
+
+	report.trailing_bytes = blend.bytes().len().saturating_sub(end);
+	Ok(report)
+}
+
+/// Largest power of two up to [`MAX_BLOCK_ALIGNMENT`] dividing `value`.
+fn alignment_of(value: usize) -> usize {
+	if value == 0 {
+		return MAX_BLOCK_ALIGNMENT;
+	}
+	(1_usize << value.trailing_zeros()).min(MAX_BLOCK_ALIGNMENT)
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,54 @@
+use blendoc_testkit::builder::{HeaderKind, SCENE_ID_NAMES, scene_file};
+
+use crate::blend::{BlendFile, IdIndex, MAX_BLOCK_ALIGNMENT, scan_block_alignment, scan_id_blocks};
+
+/// Re-encode `raw` with zero padding so every block header is 8-byte aligned.
+fn pad_blocks(raw: &[u8]) -> Vec<u8> {
+	let blend = BlendFile::from_bytes(raw.to_vec()).expect("synthetic file parses");
+	let head_size = blend.header.bhead_layout().expect("known layout").capabilities().size;
+	let base = blend.header.header_size;
+	let mut out = raw[..base].to_vec();
+	for block in blend.blocks() {
+		let block = block.expect("block parses");
+		while !(out.len() - base).is_multiple_of(MAX_BLOCK_ALIGNMENT) {
+			out.push(0);
+		}
+		out.extend_from_slice(&raw[block.file_offset..block.file_offset + head_size + block.payload.len()]);
+	}
+	out
+}
+
+#[test]
+fn synthetic_files_are_packed() {
+	for kind in [HeaderKind::Legacy4, HeaderKind::Legacy8, HeaderKind::Large] {
+		let blend = BlendFile::from_bytes(scene_file(kind, false)).expect("synthetic file parses");
+		let report = scan_block_alignment(&blend).expect("alignment scans");
+		assert!(report.is_packed(), "{kind:?}: {report:?}");
+		assert_eq!(report.trailing_bytes, 0);
+		assert_eq!(report.convention(), "packed");
+	}
+}
+
+#[test]
+fn padded_blocks_iterate_and_are_reported() {
+	let raw = scene_file(HeaderKind::Legacy8, false);
+	let packed = BlendFile::from_bytes(raw.clone()).expect("packed file parses");
+	let packed_codes: Vec<[u8; 4]> = packed.blocks().map(|block| block.expect("block parses").head.code).collect();
+
+	let padded_raw = pad_blocks(&raw);
+	assert!(padded_raw.len() > raw.len(), "scene file should need padding");
+	let padded = BlendFile::from_bytes(padded_raw).expect("padded file parses");
+	let padded_codes: Vec<[u8; 4]> = padded.blocks().map(|block| block.expect("block parses").head.code).collect();
+	assert_eq!(padded_codes, packed_codes);
+
+	let report = scan_block_alignment(&padded).expect("alignment scans");
+	assert!(!report.is_packed());
+	assert_eq!(report.header_alignment, MAX_BLOCK_ALIGNMENT);
+	assert!(report.max_padding < MAX_BLOCK_ALIGNMENT);
+	assert_eq!(report.convention(), "padded");
+
+	let dna = padded.dna().expect("padded DNA1 parses");
+	let ids = IdIndex::build(scan_id_blocks(&padded, &dna).expect("ids scan"));
+	let names: Vec<&str> = ids.records.iter().map(|record| record.id_name.as_ref()).collect();
+	assert_eq!(names, SCENE_ID_NAMES);
+}
@@ -0,0 +1,499 @@
+use std::sync::Arc;
+
+use crate::blend::{
+	BlendError, DecodeOptions, Dna, Endianness, IdIndex, PointerIndex, Result, StructValue, Value, WalkOptions, decode_ptr_instance, decode_struct_instance,
+	walk_listbase,
+};
+
+/// Traversal limits for [`read_action`] and [`read_anim_data`].
+#[derive(Debug, Clone)]
+pub struct AnimOptions {
+	/// Maximum F-Curves read per action or driver list.
+	pub max_fcurves: usize,
+	/// Maximum keyframes scanned per F-Curve when computing its frame range.
+	pub max_keys: usize,
+}
+
+impl Default for AnimOptions {
+	fn default() -> Self {
+		Self {
+			max_fcurves: 10_000,
+			max_keys: 1_000_000,
+		}
+	}
+}
+
+/// One `DriverTarget` of a driver variable.
+#[derive(Debug, Clone)]
+pub struct DriverTarget {
+	/// Stored `DriverTarget.id` pointer (0 when unset).
+	pub id_ptr: u64,
+	/// Target `ID.name`, when the pointer resolves to an ID.
+	pub id_name: Option<Arc<str>>,
+	/// Target RNA path, when set.
+	pub rna_path: Option<Arc<str>>,
+}
+
+/// One `DriverVar` of a channel driver.
+#[derive(Debug, Clone)]
+pub struct DriverVariable {
+	/// Variable name used in the driver expression.
+	pub name: Arc<str>,
+	/// Raw `DriverVar.type` value.
+	pub var_type: i64,
+	/// Used targets (`num_targets` of the inline `targets` array).
+	pub targets: Vec<DriverTarget>,
+}
+
+impl DriverVariable {
+	/// Driver variable type label (`SINGLE_PROP`, `TRANSFORMS`, ...), or `type:<n>` for unknown values.
+	pub fn type_label(&self) -> String {
+		let label = match self.var_type {
+			0 => "SINGLE_PROP",
+			1 => "ROTATION_DIFF",
+			2 => "LOC_DIFF",
+			3 => "TRANSFORMS",
+			4 => "CONTEXT_PROP",
+			value => return format!("type:{value}"),
+		};
+		label.to_owned()
+	}
+}
+
+/// Decoded `ChannelDriver` attached to a driver F-Curve.
+#[derive(Debug, Clone)]
+pub struct DriverInfo {
+	/// Raw `ChannelDriver.type` value.
+	pub driver_type: i64,
+	/// Scripted expression; empty for non-scripted drivers.
+	pub expression: Arc<str>,
+	/// Driver variables, in list order.
+	pub variables: Vec<DriverVariable>,
+}
+
+impl DriverInfo {
+	/// Driver type label (`AVERAGE`, `SCRIPTED`, ...), or `type:<n>` for unknown values.
+	pub fn type_label(&self) -> String {
+		let label = match self.driver_type {
+			0 => "AVERAGE",
+			1 => "SCRIPTED",
+			2 => "SUM",
+			3 => "MIN",
+			4 => "MAX",
+			value => return format!("type:{value}"),
+		};
+		label.to_owned()
+	}
+}
+
+/// One decoded `FCurve`.
+#[derive(Debug, Clone)]
+pub struct FCurveInfo {
+	/// Canonical `FCurve` pointer.
+	pub ptr: u64,
+	/// Animated RNA path (`location`, `nodes["Mix"].inputs[0].default_value`, ...).
+	pub rna_path: Option<Arc<str>>,
+	/// `FCurve.array_index` into the animated property.
+	pub array_index: i64,
+	/// Name of the `bActionGroup` the curve belongs to.
+	pub group: Option<Arc<str>>,
+	/// Stored keyframe or sample count (`totvert`).
+	pub keyframes: u64,
+	/// Whether the curve stores baked samples (`fpt`) instead of keyframes.
+	pub sampled: bool,
+	/// Lowest and highest key frame, when keys resolve.
+	pub frame_range: Option<(f32, f32)>,
+	/// Attached driver, for curves read from `AnimData.drivers`.
+	pub driver: Option<DriverInfo>,
+}
+
+impl FCurveInfo {
+	/// `rna_path[array_index]` label used by text output.
+	pub fn channel_label(&self) -> String {
+		format!("{}[{}]", self.rna_path.as_deref().unwrap_or("-"), self.array_index)
+	}
+}
+
+/// Decoded `bAction` with its F-Curves.
+#[derive(Debug, Clone)]
+pub struct ActionInfo {
+	/// Canonical `bAction` pointer.
+	pub ptr: u64,
+	/// Action `ID.name`.
+	pub id_name: Option<Arc<str>>,
+	/// Whether curves came from the 4.4+ layered channelbags rather than `bAction.curves`.
+	pub layered: bool,
+	/// F-Curves, in storage order.
+	pub fcurves: Vec<FCurveInfo>,
+	/// Whether a limit or a broken list cut the curve list short.
+	pub truncated: bool,
+}
+
+impl ActionInfo {
+	/// Union of all curve frame ranges.
+	pub fn frame_range(&self) -> Option<(f32, f32)> {
+		union_ranges(self.fcurves.iter().filter_map(|curve| curve.frame_range))
+	}
+
+	/// Sum of curve keyframe counts.
+	pub fn keyframes(&self) -> u64 {
+		self.fcurves.iter().map(|curve| curve.keyframes).sum()
+	}
+}
+
+/// Animation attached to one ID through `AnimData`.
+#[derive(Debug, Clone)]
+pub struct AnimDataInfo {
+	/// Canonical owner ID pointer.
+	pub owner_ptr: u64,
+	/// Owner `ID.name`.
+	pub owner_id: Option<Arc<str>>,
+	/// Stored `AnimData` pointer; 0 when the ID is not animated.
+	pub adt_ptr: u64,
+	/// Assigned action, when set.
+	pub action: Option<ActionInfo>,
+	/// Driver F-Curves from `AnimData.drivers`.
+	pub drivers: Vec<FCurveInfo>,
+	/// Whether a limit or a broken list cut the driver list short.
+	pub truncated: bool,
+}
+
+/// Decode a `bAction` and its F-Curves.
+///
+/// Legacy actions store curves in the `curves` ListBase. Blender 4.4+ layered
+/// actions keep them in `strip_keyframe_data_array` channelbags; these are read
+/// when the ListBase is empty, across all slots.
+pub fn read_action<'a>(dna: &Dna, index: &PointerIndex<'a>, ids: &IdIndex, action_ptr: u64, options: &AnimOptions) -> Result<ActionInfo> {
+	let mut reader = AnimReader::new(dna, index, ids, options);
+	let (ptr, action) = decode_ptr_instance(dna, index, action_ptr, &reader.decode)?;
+	if action.type_name.as_ref() != "bAction" {
+		return Err(BlendError::UnexpectedStructType {
+			ptr,
+			expected: "bAction",
+			got: action.type_name.to_string(),
+		});
+	}
+
+	let mut curve_ptrs = reader.list_items(&action, "curves")?;
+	let layered = curve_ptrs.is_empty() && field(&action, "strip_keyframe_data_array").is_some();
+	if layered {
+		curve_ptrs = reader.layered_curves(&action);
+	}
+	let fcurves = reader.fcurves(curve_ptrs)?;
+
+	Ok(ActionInfo {
+		ptr,
+		id_name: ids.get_by_ptr(ptr).map(|record| Arc::from(record.id_name.as_ref())),
+		layered,
+		fcurves,
+		truncated: reader.truncated,
+	})
+}
+
+/// Decode the `AnimData` of an animatable ID: its action and its drivers.
+///
+/// IDs whose struct has no `adt` field fail with
+/// [`BlendError::UnexpectedStructType`]; animatable IDs without animation
+/// return an empty [`AnimDataInfo`].
+pub fn read_anim_data<'a>(dna: &Dna, index: &PointerIndex<'a>, ids: &IdIndex, id_ptr: u64, options: &AnimOptions) -> Result<AnimDataInfo> {
+	let mut reader = AnimReader::new(dna, index, ids, options);
+	let (owner_ptr, owner) = decode_ptr_instance(dna, index, id_ptr, &reader.decode)?;
+	let Some(adt_ptr) = ptr_field(&owner, "adt") else {
+		return Err(BlendError::UnexpectedStructType {
+			ptr: owner_ptr,
+			expected: "ID with AnimData",
+			got: owner.type_name.to_string(),
+		});
+	};
+
+	let mut info = AnimDataInfo {
+		owner_ptr,
+		owner_id: ids.get_by_ptr(owner_ptr).map(|record| Arc::from(record.id_name.as_ref())),
+		adt_ptr,
+		action: None,
+		drivers: Vec::new(),
+		truncated: false,
+	};
+	if adt_ptr == 0 {
+		return Ok(info);
+	}
+
+	let (_, adt) = decode_ptr_instance(dna, index, adt_ptr, &reader.decode)?;
+	let action_ptr = ptr_field(&adt, "action").unwrap_or(0);
+	if action_ptr != 0 {
+		info.action = Some(read_action(dna, index, ids, action_ptr, options)?);
+	}
+	let driver_ptrs = reader.list_items(&adt, "drivers")?;
+	info.drivers = reader.fcurves(driver_ptrs)?;
+	info.truncated = reader.truncated;
+	Ok(info)
+}
+
+struct AnimReader<'r, 'a> {
+	dna: &'r Dna,
+	index: &'r PointerIndex<'a>,
+	ids: &'r IdIndex,
+	options: &'r AnimOptions,
+	decode: DecodeOptions,
+	truncated: bool,
+}
+
+impl<'r, 'a> AnimReader<'r, 'a> {
+	fn new(dna: &'r Dna, index: &'r PointerIndex<'a>, ids: &'r IdIndex, options: &'r AnimOptions) -> Self {
+		Self {
+			dna,
+			index,
+			ids,
+			options,
+			decode: DecodeOptions::default(),
+			truncated: false,
+		}
+	}
+
+	fn fcurves(&mut self, ptrs: Vec<u64>) -> Result<Vec<FCurveInfo>> {
+		let mut out = Vec::with_capacity(ptrs.len());
+		for ptr in ptrs {
+			if out.len() >= self.options.max_fcurves {
+				self.truncated = true;
+				break;
+			}
+			let (ptr, curve) = decode_ptr_instance(self.dna, self.index, ptr, &self.decode)?;
+			out.push(self.fcurve(ptr, &curve)?);
+		}
+		Ok(out)
+	}
+
+	fn fcurve(&self, ptr: u64, curve: &StructValue) -> Result<FCurveInfo> {
+		let keyframes = int_field(curve, "totvert").unwrap_or(0).max(0) as u64;
+		let bezt = ptr_field(curve, "bezt").unwrap_or(0);
+		let fpt = ptr_field(curve, "fpt").unwrap_or(0);
+		let frame_range = if bezt != 0 {
+			// `BezTriple.vec[1][0]` is the key frame: flattened index 3 of `vec[3][3]`.
+			self.key_frames(bezt, keyframes, 3)?
+		} else if fpt != 0 {
+			self.key_frames(fpt, keyframes, 0)?
+		} else {
+			None
+		};
+
+		let group = match ptr_field(curve, "grp").unwrap_or(0) {
+			0 => None,
+			grp => decode_ptr_instance(self.dna, self.index, grp, &self.decode)
+				.ok()
+				.and_then(|(_, group)| string_field(&group, "name")),
+		};
+		let driver = match ptr_field(curve, "driver").unwrap_or(0) {
+			0 => None,
+			driver => Some(self.driver(driver)?),
+		};
+
+		Ok(FCurveInfo {
+			ptr,
+			rna_path: self.c_string(ptr_field(curve, "rna_path").unwrap_or(0)),
+			array_index: int_field(curve, "array_index").unwrap_or(0),
+			group,
+			keyframes,
+			sampled: bezt == 0 && fpt != 0,
+			frame_range,
+			driver,
+		})
+	}
+
+	/// Min/max frame over `count` key elements, reading `vec[frame_slot]` of each.
+	fn key_frames(&self, keys_ptr: u64, count: u64, frame_slot: usize) -> Result<Option<(f32, f32)>> {
+		// Keys are read from the block bytes: stable-ID pointers do not support element arithmetic.
+		let Some(resolved) = self.index.resolve_typed(self.dna, keys_ptr) else {
+			return Ok(None);
+		};
+		let sdna_nr = resolved.base.entry.block.head.sdna_nr;
+		let stride = resolved.struct_size.max(1);
+		let payload = resolved.base.payload();
+		let mut frames = Vec::new();
+		for key_idx in 0..(count as usize).min(self.options.max_keys) {
+			let start = resolved.base.byte_offset + key_idx * stride;
+			let Some(bytes) = payload.get(start..start + stride) else {
+				break;
+			};
+			let key = decode_struct_instance(self.dna, sdna_nr, bytes, &self.decode)?;
+			if let Some(Value::Array(values)) = field(&key, "vec")
+				&& let Some(Value::F32(frame)) = values.get(frame_slot)
+			{
+				frames.push((*frame, *frame));
+			}
+		}
+		Ok(union_ranges(frames))
+	}
+
+	fn driver(&self, driver_ptr: u64) -> Result<DriverInfo> {
+		let (_, driver) = decode_ptr_instance(self.dna, self.index, driver_ptr, &self.decode)?;
+		let mut variables = Vec::new();
+		for var_ptr in self.list_items_untracked(&driver, "variables")? {
+			let (_, var) = decode_ptr_instance(self.dna, self.index, var_ptr, &self.decode)?;
+			let used = int_field(&var, "num_targets").unwrap_or(1).max(0) as usize;
+			let targets = match field(&var, "targets") {
+				Some(Value::Array(items)) => items.iter().take(used).filter_map(|item| self.target(item)).collect(),
+				Some(item) => self.target(item).into_iter().collect(),
+				None => Vec::new(),
+			};
+			variables.push(DriverVariable {
+				name: string_field(&var, "name").unwrap_or_else(|| Arc::from("")),
+				var_type: int_field(&var, "type").unwrap_or(0),
+				targets,
+			});
+		}
+
+		Ok(DriverInfo {
+			driver_type: int_field(&driver, "type").unwrap_or(0),
+			expression: string_field(&driver, "expression").unwrap_or_else(|| Arc::from("")),
+			variables,
+		})
+	}
+
+	fn target(&self, item: &Value) -> Option<DriverTarget> {
+		let Value::Struct(target) = item else {
+			return None;
+		};
+		let id_ptr = ptr_field(target, "id").unwrap_or(0);
+		Some(DriverTarget {
+			id_ptr,
+			id_name: (id_ptr != 0)
+				.then(|| self.index.canonical_ptr(self.dna, id_ptr))
+				.flatten()
+				.and_then(|ptr| self.ids.get_by_ptr(ptr))
+				.map(|record| Arc::from(record.id_name.as_ref())),
+			rna_path: self.c_string(ptr_field(target, "rna_path").unwrap_or(0)),
+		})
+	}
+
+	/// Follow `strip_keyframe_data_array` → `channelbag_array` → `fcurve_array`.
+	fn layered_curves(&mut self, action: &StructValue) -> Vec<u64> {
+		let mut out = Vec::new();
+		for data_ptr in self.ptr_array(action, "strip_keyframe_data_array", "strip_keyframe_data_array_num") {
+			let Ok((_, data)) = decode_ptr_instance(self.dna, self.index, data_ptr, &self.decode) else {
+				self.truncated = true;
+				continue;
+			};
+			for bag_ptr in self.ptr_array(&data, "channelbag_array", "channelbag_array_num") {
+				let Ok((_, bag)) = decode_ptr_instance(self.dna, self.index, bag_ptr, &self.decode) else {
+					self.truncated = true;
+					continue;
+				};
+				out.extend(self.ptr_array(&bag, "fcurve_array", "fcurve_array_num"));
+			}
+		}
+		out
+	}
+
+	/// Read `count` non-null pointers from the array block an `owner.array` (`T **`) field points at.
+	fn ptr_array(&mut self, owner: &StructValue, array: &str, count: &str) -> Vec<u64> {
+		let count = int_field(owner, count).unwrap_or(0).max(0) as usize;
+		let array_ptr = ptr_field(owner, array).unwrap_or(0);
+		if count == 0 || array_ptr == 0 {
+			return Vec::new();
+		}
+		let Some(resolved) = self.index.resolve(array_ptr) else {
+			self.truncated = true;
+			return Vec::new();
+		};
+		let width = self.dna.pointer_size;
+		let payload = resolved.payload();
+		let mut out = Vec::with_capacity(count);
+		for slot in 0..count {
+			let Some(ptr) = read_word(payload, resolved.byte_offset + slot * width, width, self.dna.endianness) else {
+				self.truncated = true;
+				break;
+			};
+			if ptr != 0 {
+				out.push(ptr);
+			}
+		}
+		out
+	}
+
+	fn list_items(&mut self, owner: &StructValue, list: &str) -> Result<Vec<u64>> {
+		let (items, complete) = self.walk_list(owner, list)?;
+		self.truncated |= !complete;
+		Ok(items)
+	}
+
+	/// Walk a nested list (driver variables) whose truncation is not reported.
+	fn list_items_untracked(&self, owner: &StructValue, list: &str) -> Result<Vec<u64>> {
+		Ok(self.walk_list(owner, list)?.0)
+	}
+
+	fn walk_list(&self, owner: &StructValue, list: &str) -> Result<(Vec<u64>, bool)> {
+		let Some(Value::Struct(list_base)) = field(owner, list) else {
+			return Ok((Vec::new(), true));
+		};
+		let walk = walk_listbase(
+			self.dna,
+			self.index,
+			self.ids,
+			list_base,
+			&WalkOptions {
+				max_steps: self.options.max_fcurves,
+				..WalkOptions::default()
+			},
+		)?;
+		Ok((walk.canonical_ptrs(), walk.is_complete()))
+	}
+
+	/// NUL-terminated string stored in the block `ptr` points into.
+	fn c_string(&self, ptr: u64) -> Option<Arc<str>> {
+		if ptr == 0 {
+			return None;
+		}
+		let resolved = self.index.resolve(ptr)?;
+		let bytes = resolved.payload().get(resolved.byte_offset..)?;
+		let end = bytes.iter().position(|byte| *byte == 0).unwrap_or(bytes.len());
+		Some(Arc::from(String::from_utf8_lossy(&bytes[..end]).as_ref()))
+	}
+}
+
+fn union_ranges(ranges: impl IntoIterator<Item = (f32, f32)>) -> Option<(f32, f32)> {
+	ranges.into_iter().reduce(|(low, high), (start, end)| (low.min(start), high.max(end)))
+}
+
+fn field<'v>(item: &'v StructValue, name: &str) -> Option<&'v Value> {
+	item.fields.iter().find(|field| field.name.as_ref() == name).map(|field| &field.value)
+}
+
+fn ptr_field(item: &StructValue, name: &str) -> Option<u64> {
+	match field(item, name)? {
+		Value::Ptr(ptr) => Some(*ptr),
+		_ => None,
+	}
+}
+
+fn string_field(item: &StructValue, name: &str) -> Option<Arc<str>> {
+	match field(item, name)? {
+		Value::String(value) => Some(Arc::from(value.as_ref())),
+		_ => None,
+	}
+}
+
+fn int_field(item: &StructValue, name: &str) -> Option<i64> {
+	match field(item, name)? {
+		Value::I64(value) => Some(*value),
+		Value::U64(value) => i64::try_from(*value).ok(),
+		_ => None,
+	}
+}
+
+fn read_word(bytes: &[u8], start: usize, width: usize, endianness: Endianness) -> Option<u64> {
+	let raw = bytes.get(start..start.checked_add(width)?)?;
+	let mut buf = [0_u8; 8];
+	match endianness {
+		Endianness::Little => {
+			buf[..width].copy_from_slice(raw);
+			Some(u64::from_le_bytes(buf))
+		}
+		Endianness::Big => {
+			buf[8 - width..].copy_from_slice(raw);
+			Some(u64::from_be_bytes(buf))
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,255 @@
+mod synthetic_anim {
+	use blendoc_testkit::builder::{BlendBuilder, HeaderKind};
+
+	use crate::blend::{AnimOptions, BlendError, BlendFile, IdIndex, read_action, read_anim_data, scan_id_blocks};
+
+	const ACTION: u64 = 0x1000;
+	const CURVE_LOC: u64 = 0x1100;
+	const CURVE_ROT: u64 = 0x1200;
+	const KEYS: u64 = 0x1300;
+	const SAMPLES: u64 = 0x1380;
+	const PATH_LOC: u64 = 0x1400;
+	const PATH_ROT: u64 = 0x1480;
+	const GROUP: u64 = 0x1500;
+	const LAYERED: u64 = 0x2000;
+	const STRIP_DATA_ARRAY: u64 = 0x2100;
+	const STRIP_DATA: u64 = 0x2200;
+	const BAG_ARRAY: u64 = 0x2300;
+	const BAG: u64 = 0x2400;
+	const CURVE_ARRAY: u64 = 0x2500;
+	const LAYERED_CURVE: u64 = 0x2600;
+	const OBJECT: u64 = 0x3000;
+	const ADT: u64 = 0x3100;
+	const DRIVER_CURVE: u64 = 0x3200;
+	const DRIVER: u64 = 0x3300;
+	const VAR: u64 = 0x3400;
+	const PATH_VAR: u64 = 0x3500;
+	const MESH: u64 = 0x4000;
+
+	/// `ACLegacy` with a grouped `location[1]` curve keyed at frames 1, 40, 12
+	/// and a sampled `rotation_euler[2]` curve; `ACLayered` with one curve in a
+	/// 4.4-style channelbag; `OBCube` using `ACLegacy` plus one scripted driver.
+	fn anim_file() -> Vec<u8> {
+		let mut builder = BlendBuilder::new(HeaderKind::Legacy8, 404, false);
+		let sdna = builder.sdna_mut();
+		sdna.add_type("char", 1);
+		sdna.add_type("int", 4);
+		sdna.add_type("float", 4);
+		sdna.add_type("void", 0);
+		sdna.add_struct("Link", &[("Link", "*next"), ("Link", "*prev")]);
+		sdna.add_struct("ID", &[("void", "*next"), ("void", "*prev"), ("void", "*lib"), ("char", "name[24]")]);
+		sdna.add_struct("ListBase", &[("void", "*first"), ("void", "*last")]);
+		let bezt = sdna.add_struct("BezTriple", &[("float", "vec[3][3]")]);
+		let fpoint = sdna.add_struct("FPoint", &[("float", "vec[2]")]);
+		let group = sdna.add_struct("bActionGroup", &[("bActionGroup", "*next"), ("bActionGroup", "*prev"), ("char", "name[24]")]);
+		sdna.add_struct("DriverTarget", &[("ID", "*id"), ("char", "*rna_path")]);
+		let var = sdna.add_struct(
+			"DriverVar",
+			&[
+				("DriverVar", "*next"),
+				("DriverVar", "*prev"),
+				("char", "name[16]"),
+				("DriverTarget", "targets[2]"),
+				("int", "num_targets"),
+				("int", "type"),
+			],
+		);
+		let driver = sdna.add_struct("ChannelDriver", &[("ListBase", "variables"), ("char", "expression[32]"), ("int", "type")]);
+		let fcurve = sdna.add_struct(
+			"FCurve",
+			&[
+				("FCurve", "*next"),
+				("FCurve", "*prev"),
+				("bActionGroup", "*grp"),
+				("ChannelDriver", "*driver"),
+				("BezTriple", "*bezt"),
+				("FPoint", "*fpt"),
+				("int", "totvert"),
+				("char", "*rna_path"),
+				("int", "array_index"),
+			],
+		);
+		let bag = sdna.add_struct(
+			"ActionChannelbag",
+			&[("int", "slot_handle"), ("FCurve", "**fcurve_array"), ("int", "fcurve_array_num")],
+		);
+		let strip_data = sdna.add_struct(
+			"ActionStripKeyframeData",
+			&[("ActionChannelbag", "**channelbag_array"), ("int", "channelbag_array_num")],
+		);
+		let action = sdna.add_struct(
+			"bAction",
+			&[
+				("ID", "id"),
+				("ListBase", "curves"),
+				("ActionStripKeyframeData", "**strip_keyframe_data_array"),
+				("int", "strip_keyframe_data_array_num"),
+			],
+		);
+		let adt = sdna.add_struct("AnimData", &[("bAction", "*action"), ("ListBase", "drivers")]);
+		let object = sdna.add_struct("Object", &[("ID", "id"), ("AnimData", "*adt")]);
+		let mesh = sdna.add_struct("Mesh", &[("ID", "id")]);
+
+		let id = |name: &str| {
+			let mut payload = builder.payload();
+			payload.ptr(0).ptr(0).ptr(0).name(name, 24);
+			payload.finish()
+		};
+		let text = |value: &str| {
+			let mut payload = builder.payload();
+			payload.name(value, value.len() + 1);
+			payload.finish()
+		};
+		let curve = |next: u64, prev: u64, grp: u64, driver: u64, bezt: u64, fpt: u64, count: i32, path: u64, array_index: i32| {
+			let mut payload = builder.payload();
+			payload
+				.ptr(next)
+				.ptr(prev)
+				.ptr(grp)
+				.ptr(driver)
+				.ptr(bezt)
+				.ptr(fpt)
+				.i32(count)
+				.ptr(path)
+				.i32(array_index);
+			payload.finish()
+		};
+
+		let mut payload = builder.payload();
+		payload.raw(&id("ACLegacy")).ptr(CURVE_LOC).ptr(CURVE_ROT).ptr(0).i32(0);
+		let legacy_payload = payload.finish();
+		let mut payload = builder.payload();
+		payload.raw(&id("ACLayered")).ptr(0).ptr(0).ptr(STRIP_DATA_ARRAY).i32(1);
+		let layered_payload = payload.finish();
+
+		let mut payload = builder.payload();
+		for frame in [1.0, 40.0, 12.0] {
+			payload.f32(0.0).f32(0.0).f32(0.0).f32(frame).f32(0.5).f32(0.0).f32(0.0).f32(0.0).f32(0.0);
+		}
+		let keys_payload = payload.finish();
+		let mut payload = builder.payload();
+		payload.f32(5.0).f32(0.0).f32(6.0).f32(0.0);
+		let samples_payload = payload.finish();
+		let mut payload = builder.payload();
+		payload.ptr(0).ptr(0).name("Object Transforms", 24);
+		let group_payload = payload.finish();
+
+		let mut payload = builder.payload();
+		payload.ptr(STRIP_DATA);
+		let strip_array_payload = payload.finish();
+		let mut payload = builder.payload();
+		payload.ptr(BAG_ARRAY).i32(1);
+		let strip_data_payload = payload.finish();
+		let mut payload = builder.payload();
+		payload.ptr(BAG);
+		let bag_array_payload = payload.finish();
+		let mut payload = builder.payload();
+		payload.i32(1).ptr(CURVE_ARRAY).i32(1);
+		let bag_payload = payload.finish();
+		let mut payload = builder.payload();
+		payload.ptr(LAYERED_CURVE);
+		let curve_array_payload = payload.finish();
+
+		let mut payload = builder.payload();
+		payload.raw(&id("OBCube")).ptr(ADT);
+		let object_payload = payload.finish();
+		let mut payload = builder.payload();
+		payload.ptr(ACTION).ptr(DRIVER_CURVE).ptr(DRIVER_CURVE);
+		let adt_payload = payload.finish();
+		let mut payload = builder.payload();
+		payload.ptr(VAR).ptr(VAR).name("var * 2", 32).i32(1);
+		let driver_payload = payload.finish();
+		let mut payload = builder.payload();
+		payload.ptr(0).ptr(0).name("var", 16).ptr(OBJECT).ptr(PATH_VAR).ptr(0).ptr(0).i32(1).i32(0);
+		let var_payload = payload.finish();
+
+		let blocks = [
+			(*b"AC\0\0", action, ACTION, legacy_payload),
+			(*b"DATA", fcurve, CURVE_LOC, curve(CURVE_ROT, 0, GROUP, 0, KEYS, 0, 3, PATH_LOC, 1)),
+			(*b"DATA", fcurve, CURVE_ROT, curve(0, CURVE_LOC, 0, 0, 0, SAMPLES, 2, PATH_ROT, 2)),
+			(*b"DATA", bezt, KEYS, keys_payload),
+			(*b"DATA", fpoint, SAMPLES, samples_payload),
+			(*b"DATA", 0, PATH_LOC, text("location")),
+			(*b"DATA", 0, PATH_ROT, text("rotation_euler")),
+			(*b"DATA", group, GROUP, group_payload),
+			(*b"AC\0\0", action, LAYERED, layered_payload),
+			(*b"DATA", 0, STRIP_DATA_ARRAY, strip_array_payload),
+			(*b"DATA", strip_data, STRIP_DATA, strip_data_payload),
+			(*b"DATA", 0, BAG_ARRAY, bag_array_payload),
+			(*b"DATA", bag, BAG, bag_payload),
+			(*b"DATA", 0, CURVE_ARRAY, curve_array_payload),
+			(*b"DATA", fcurve, LAYERED_CURVE, curve(0, 0, 0, 0, 0, 0, 0, PATH_LOC, 0)),
+			(*b"OB\0\0", object, OBJECT, object_payload),
+			(*b"DATA", adt, ADT, adt_payload),
+			(*b"DATA", fcurve, DRIVER_CURVE, curve(0, 0, 0, DRIVER, 0, 0, 0, PATH_ROT, 0)),
+			(*b"DATA", driver, DRIVER, driver_payload),
+			(*b"DATA", var, VAR, var_payload),
+			(*b"DATA", 0, PATH_VAR, text("location.z")),
+			(*b"ME\0\0", mesh, MESH, id("MECube")),
+		];
+		for (code, sdna_nr, old, payload) in blocks {
+			builder.block(code, sdna_nr, old, 1, payload);
+		}
+		builder.build()
+	}
+
+	#[test]
+	fn legacy_and_layered_actions_list_curves() {
+		let blend = BlendFile::from_bytes(anim_file()).expect("synthetic file opens");
+		let dna = blend.dna().expect("dna parses");
+		let index = blend.pointer_index().expect("pointer index builds");
+		let ids = IdIndex::build(scan_id_blocks(&blend, &dna).expect("id scan succeeds"));
+		let options = AnimOptions::default();
+
+		let legacy = read_action(&dna, &index, &ids, ACTION, &options).expect("legacy action decodes");
+		assert_eq!(legacy.id_name.as_deref(), Some("ACLegacy"));
+		assert!(!legacy.layered && !legacy.truncated);
+		let curves: Vec<_> = legacy
+			.fcurves
+			.iter()
+			.map(|curve| (curve.channel_label(), curve.keyframes, curve.sampled, curve.frame_range, curve.group.as_deref()))
+			.collect();
+		assert_eq!(
+			curves,
+			[
+				("location[1]".to_owned(), 3, false, Some((1.0, 40.0)), Some("Object Transforms")),
+				("rotation_euler[2]".to_owned(), 2, true, Some((5.0, 6.0)), None),
+			]
+		);
+		assert_eq!((legacy.keyframes(), legacy.frame_range()), (5, Some((1.0, 40.0))));
+
+		let layered = read_action(&dna, &index, &ids, LAYERED, &options).expect("layered action decodes");
+		assert!(layered.layered);
+		assert_eq!(layered.fcurves.len(), 1);
+		assert_eq!(layered.fcurves[0].channel_label(), "location[0]");
+
+		let err = read_action(&dna, &index, &ids, OBJECT, &options).expect_err("object is not an action");
+		assert!(matches!(err, BlendError::UnexpectedStructType { expected: "bAction", .. }));
+	}
+
+	#[test]
+	fn anim_data_reports_action_and_drivers() {
+		let blend = BlendFile::from_bytes(anim_file()).expect("synthetic file opens");
+		let dna = blend.dna().expect("dna parses");
+		let index = blend.pointer_index().expect("pointer index builds");
+		let ids = IdIndex::build(scan_id_blocks(&blend, &dna).expect("id scan succeeds"));
+		let options = AnimOptions::default();
+
+		let anim = read_anim_data(&dna, &index, &ids, OBJECT, &options).expect("anim data decodes");
+		assert_eq!(anim.owner_id.as_deref(), Some("OBCube"));
+		assert_eq!(anim.action.as_ref().and_then(|action| action.id_name.as_deref()), Some("ACLegacy"));
+		assert_eq!(anim.drivers.len(), 1);
+
+		let driver = anim.drivers[0].driver.as_ref().expect("driver decodes");
+		assert_eq!((driver.type_label().as_str(), driver.expression.as_ref()), ("SCRIPTED", "var * 2"));
+		assert_eq!(driver.variables.len(), 1);
+		let variable = &driver.variables[0];
+		assert_eq!((variable.name.as_ref(), variable.type_label().as_str()), ("var", "SINGLE_PROP"));
+		assert_eq!(variable.targets.len(), 1);
+		assert_eq!(variable.targets[0].id_name.as_deref(), Some("OBCube"));
+		assert_eq!(variable.targets[0].rna_path.as_deref(), Some("location.z"));
+
+		let err = read_anim_data(&dna, &index, &ids, MESH, &options).expect_err("test mesh has no adt field");
+		assert!(matches!(err, BlendError::UnexpectedStructType { .. }));
+	}
+}
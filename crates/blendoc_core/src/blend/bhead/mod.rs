@@ -1,7 +1,9 @@
-use crate::blend::bytes::Cursor;
-use crate::blend::{BlendError, BlendHeader, Result};
+use std::io::Write;
 
-/// Parsed `LargeBHead8` block header.
+use crate::blend::bytes::{ByteWriter, Cursor};
+use crate::blend::{BlendError, BlendHeader, Endianness, Result, codes};
+
+/// Parsed block header, normalized across on-disk layouts.
 #[derive(Debug, Clone, Copy)]
 pub struct BHead {
 	/// Four-byte block code.
@@ -18,54 +20,134 @@ pub struct BHead {
 	pub nr: u64,
 }
 
-impl BHead {
-	/// Parse a block header from cursor position.
-	pub fn parse(cursor: &mut Cursor<'_>, header: BlendHeader) -> Result<Self> {
-		if header.format_version == BlendHeader::LEGACY_FORMAT_VERSION {
-			return Self::parse_legacy(cursor, header);
+/// Field ordering of a block-header record.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BHeadFieldOrder {
+	/// `code, len, old, sdna_nr, nr` (legacy `BHead`).
+	CodeLenOldSdnaNr,
+	/// `code, sdna_nr, old, len, nr` (`LargeBHead8`).
+	CodeSdnaOldLenNr,
+}
+
+impl BHeadFieldOrder {
+	/// Stable snake-case label.
+	pub fn as_str(self) -> &'static str {
+		match self {
+			Self::CodeLenOldSdnaNr => "code_len_old_sdna_nr",
+			Self::CodeSdnaOldLenNr => "code_sdna_old_len_nr",
 		}
-		Self::parse_v1(cursor, header)
 	}
+}
 
-	fn parse_v1(cursor: &mut Cursor<'_>, header: BlendHeader) -> Result<Self> {
-		let code = cursor.read_code4()?;
-		let sdna_nr = cursor.read_u32(header.endianness)?;
-		let old = cursor.read_u64(header.endianness)?;
+/// Capability flags describing one block-header layout.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BHeadCapabilities {
+	/// Total encoded header size in bytes.
+	pub size: usize,
+	/// Width of the stored `old` identifier in bytes.
+	pub old_width: usize,
+	/// Width of the `len` and `nr` fields in bytes.
+	pub count_width: usize,
+	/// Field order on disk.
+	pub field_order: BHeadFieldOrder,
+	/// Whether `old` may hold stable opaque IDs rather than runtime addresses.
+	pub stable_ids: bool,
+}
 
-		let len = cursor.read_i64(header.endianness)?;
-		if len < 0 {
-			return Err(BlendError::NegativeBlockLength { len });
+/// Block-header layout negotiated from the file header.
+///
+/// New container formats add a variant here plus a branch in
+/// [`BHeadLayout::from_header`]; block iteration stays unchanged.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BHeadLayout {
+	/// Legacy `BHead4` with 4-byte pointers.
+	Legacy4,
+	/// Legacy `BHead8` with 8-byte pointers.
+	Legacy8,
+	/// Modern `LargeBHead8` with 64-bit lengths and counts.
+	LargeBHead8,
+}
+
+impl BHeadLayout {
+	/// Select the block-header layout for a parsed file header.
+	pub fn from_header(header: BlendHeader) -> Result<Self> {
+		match (header.format_version, header.pointer_size) {
+			(BlendHeader::LEGACY_FORMAT_VERSION, 4) => Ok(Self::Legacy4),
+			(BlendHeader::LEGACY_FORMAT_VERSION, 8) => Ok(Self::Legacy8),
+			(BlendHeader::LEGACY_FORMAT_VERSION, _) => Err(BlendError::UnsupportedPointerSize {
+				header_size: header.pointer_size,
+			}),
+			(BlendHeader::V1_FORMAT_VERSION, _) => Ok(Self::LargeBHead8),
+			(version, _) => Err(BlendError::UnsupportedFormatVersion { version }),
 		}
+	}
 
-		let nr = cursor.read_i64(header.endianness)?;
-		if nr < 0 {
-			return Err(BlendError::NegativeBlockCount { nr });
+	/// Stable snake-case label.
+	pub fn as_str(self) -> &'static str {
+		match self {
+			Self::Legacy4 => "bhead4",
+			Self::Legacy8 => "bhead8",
+			Self::LargeBHead8 => "large_bhead8",
 		}
+	}
 
-		Ok(Self {
-			code,
-			sdna_nr,
-			old,
-			len: len as u64,
-			nr: nr as u64,
-		})
+	/// Return capability flags for this layout.
+	pub fn capabilities(self) -> BHeadCapabilities {
+		match self {
+			Self::Legacy4 => BHeadCapabilities {
+				size: 20,
+				old_width: 4,
+				count_width: 4,
+				field_order: BHeadFieldOrder::CodeLenOldSdnaNr,
+				stable_ids: false,
+			},
+			Self::Legacy8 => BHeadCapabilities {
+				size: 24,
+				old_width: 8,
+				count_width: 4,
+				field_order: BHeadFieldOrder::CodeLenOldSdnaNr,
+				stable_ids: false,
+			},
+			Self::LargeBHead8 => BHeadCapabilities {
+				size: 32,
+				old_width: 8,
+				count_width: 8,
+				field_order: BHeadFieldOrder::CodeSdnaOldLenNr,
+				stable_ids: true,
+			},
+		}
 	}
 
-	fn parse_legacy(cursor: &mut Cursor<'_>, header: BlendHeader) -> Result<Self> {
+	/// Parse one block header in this layout from cursor position.
+	pub fn parse(self, cursor: &mut Cursor<'_>, endianness: Endianness) -> Result<BHead> {
+		let caps = self.capabilities();
 		let code = cursor.read_code4()?;
-		let len = i64::from(cursor.read_i32(header.endianness)?);
+
+		let (sdna_nr, old, len, nr) = match caps.field_order {
+			BHeadFieldOrder::CodeLenOldSdnaNr => {
+				let len = read_count(cursor, caps.count_width, endianness)?;
+				let old = cursor.read_ptr(caps.old_width, endianness)?;
+				let sdna_nr = cursor.read_u32(endianness)?;
+				let nr = read_count(cursor, caps.count_width, endianness)?;
+				(sdna_nr, old, len, nr)
+			}
+			BHeadFieldOrder::CodeSdnaOldLenNr => {
+				let sdna_nr = cursor.read_u32(endianness)?;
+				let old = cursor.read_ptr(caps.old_width, endianness)?;
+				let len = read_count(cursor, caps.count_width, endianness)?;
+				let nr = read_count(cursor, caps.count_width, endianness)?;
+				(sdna_nr, old, len, nr)
+			}
+		};
+
 		if len < 0 {
 			return Err(BlendError::NegativeBlockLength { len });
 		}
-
-		let old = cursor.read_ptr(header.pointer_size, header.endianness)?;
-		let sdna_nr = cursor.read_u32(header.endianness)?;
-		let nr = i64::from(cursor.read_i32(header.endianness)?);
 		if nr < 0 {
 			return Err(BlendError::NegativeBlockCount { nr });
 		}
 
-		Ok(Self {
+		Ok(BHead {
 			code,
 			sdna_nr,
 			old,
@@ -74,9 +156,73 @@ impl BHead {
 		})
 	}
 
+	/// Encode `head` in this layout, the inverse of [`BHeadLayout::parse`].
+	///
+	/// Fails with [`BlendError::EncodeOutOfRange`] when `old`, `len`, or `nr`
+	/// do not fit this layout's field widths.
+	pub fn write(self, head: &BHead, out: &mut ByteWriter, endianness: Endianness) -> Result<()> {
+		let caps = self.capabilities();
+		out.write_bytes(&head.code);
+
+		match caps.field_order {
+			BHeadFieldOrder::CodeLenOldSdnaNr => {
+				write_count(out, "len", head.len, caps.count_width, endianness)?;
+				out.write_ptr(head.old, caps.old_width, endianness)?;
+				out.write_u32(head.sdna_nr, endianness);
+				write_count(out, "nr", head.nr, caps.count_width, endianness)?;
+			}
+			BHeadFieldOrder::CodeSdnaOldLenNr => {
+				out.write_u32(head.sdna_nr, endianness);
+				out.write_ptr(head.old, caps.old_width, endianness)?;
+				write_count(out, "len", head.len, caps.count_width, endianness)?;
+				write_count(out, "nr", head.nr, caps.count_width, endianness)?;
+			}
+		}
+		Ok(())
+	}
+}
+
+fn write_count(out: &mut ByteWriter, field: &'static str, value: u64, width: usize, endianness: Endianness) -> Result<()> {
+	// Counts are signed on disk, so only the non-negative half is encodable.
+	let max = if width == 4 { i32::MAX as u64 } else { i64::MAX as u64 };
+	if value > max {
+		return Err(BlendError::EncodeOutOfRange { field, value, max });
+	}
+	if width == 4 {
+		out.write_u32(value as u32, endianness);
+	} else {
+		out.write_u64(value, endianness);
+	}
+	Ok(())
+}
+
+fn read_count(cursor: &mut Cursor<'_>, width: usize, endianness: Endianness) -> Result<i64> {
+	if width == 4 {
+		Ok(i64::from(cursor.read_i32(endianness)?))
+	} else {
+		cursor.read_i64(endianness)
+	}
+}
+
+impl BHead {
+	/// Parse a block header from cursor position.
+	pub fn parse(cursor: &mut Cursor<'_>, header: BlendHeader) -> Result<Self> {
+		BHeadLayout::from_header(header)?.parse(cursor, header.endianness)
+	}
+
+	/// Write this block header in the layout and byte order selected by `header`.
+	///
+	/// Parsing the written bytes with the same header yields an equal `BHead`.
+	pub fn write(&self, out: &mut impl Write, header: BlendHeader) -> Result<()> {
+		let mut bytes = ByteWriter::new();
+		BHeadLayout::from_header(header)?.write(self, &mut bytes, header.endianness)?;
+		out.write_all(&bytes.into_bytes())?;
+		Ok(())
+	}
+
 	/// Return `true` when this is the terminal `ENDB` block.
 	pub fn is_endb(&self) -> bool {
-		self.code == *b"ENDB"
+		self.code == codes::ENDB
 	}
 }
 
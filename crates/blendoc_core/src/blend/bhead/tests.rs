@@ -1,5 +1,5 @@
 use crate::blend::bytes::Cursor;
-use crate::blend::{BHead, BlendHeader};
+use crate::blend::{BHead, BHeadFieldOrder, BHeadLayout, BlendError, BlendHeader};
 
 #[test]
 fn parses_v1_little_endian_bhead() {
@@ -38,3 +38,110 @@ fn parses_legacy_big_endian_bhead() {
 	assert_eq!(head.len, 12);
 	assert_eq!(head.nr, 1);
 }
+
+#[test]
+fn parses_legacy_little_endian_bhead8() {
+	let header = BlendHeader::parse(b"BLENDER-v279").expect("header parses");
+	let layout = BHeadLayout::from_header(header).expect("layout negotiates");
+	assert_eq!(layout, BHeadLayout::Legacy8);
+
+	let mut bytes = Vec::new();
+	bytes.extend_from_slice(b"DATA");
+	bytes.extend_from_slice(&40_i32.to_le_bytes());
+	bytes.extend_from_slice(&0x0000_7f00_1234_5678_u64.to_le_bytes());
+	bytes.extend_from_slice(&5_u32.to_le_bytes());
+	bytes.extend_from_slice(&4_i32.to_le_bytes());
+	assert_eq!(bytes.len(), layout.capabilities().size);
+
+	let mut cursor = Cursor::new(&bytes);
+	let head = BHead::parse(&mut cursor, header).expect("legacy bhead8 parses");
+	assert_eq!(head.code, *b"DATA");
+	assert_eq!(head.sdna_nr, 5);
+	assert_eq!(head.old, 0x0000_7f00_1234_5678);
+	assert_eq!(head.len, 40);
+	assert_eq!(head.nr, 4);
+	assert_eq!(cursor.remaining(), 0);
+}
+
+#[test]
+fn layout_negotiation_reports_capabilities_per_variant() {
+	let cases = [
+		(&b"BLENDER_V248"[..], BHeadLayout::Legacy4, 20, BHeadFieldOrder::CodeLenOldSdnaNr, false),
+		(&b"BLENDER-v302"[..], BHeadLayout::Legacy8, 24, BHeadFieldOrder::CodeLenOldSdnaNr, false),
+		(&b"BLENDER17-01v0500"[..], BHeadLayout::LargeBHead8, 32, BHeadFieldOrder::CodeSdnaOldLenNr, true),
+	];
+
+	for (raw, expected, size, order, stable_ids) in cases {
+		let header = BlendHeader::parse(raw).expect("header parses");
+		let layout = header.bhead_layout().expect("layout negotiates");
+		let caps = layout.capabilities();
+		assert_eq!(layout, expected);
+		assert_eq!(caps.size, size);
+		assert_eq!(caps.size, 4 + 4 + caps.old_width + 2 * caps.count_width);
+		assert_eq!(caps.field_order, order);
+		assert_eq!(caps.stable_ids, stable_ids);
+	}
+}
+
+#[test]
+fn unknown_format_version_is_rejected() {
+	let mut header = BlendHeader::parse(b"BLENDER17-01v0500").expect("header parses");
+	header.format_version = 2;
+	let err = BHeadLayout::from_header(header).expect_err("future format is not negotiated");
+	assert!(matches!(err, BlendError::UnsupportedFormatVersion { version: 2 }));
+}
+
+#[test]
+fn negative_large_bhead_length_is_rejected() {
+	let header = BlendHeader::parse(b"BLENDER17-01v0500").expect("header parses");
+	let mut bytes = Vec::new();
+	bytes.extend_from_slice(b"TEST");
+	bytes.extend_from_slice(&0_u32.to_le_bytes());
+	bytes.extend_from_slice(&0_u64.to_le_bytes());
+	bytes.extend_from_slice(&(-1_i64).to_le_bytes());
+	bytes.extend_from_slice(&1_i64.to_le_bytes());
+
+	let err = BHead::parse(&mut Cursor::new(&bytes), header).expect_err("negative length fails");
+	assert!(matches!(err, BlendError::NegativeBlockLength { len: -1 }));
+}
+
+#[test]
+fn write_round_trips_every_layout() {
+	let head = BHead {
+		code: *b"DATA",
+		sdna_nr: 5,
+		old: 0x99AA_BBCC,
+		len: 40,
+		nr: 4,
+	};
+	for raw in [b"BLENDER_v279".as_slice(), b"BLENDER-V279", b"BLENDER17-01v0500", b"BLENDER17-01V0500"] {
+		let header = BlendHeader::parse(raw).expect("header parses");
+		let mut bytes = Vec::new();
+		head.write(&mut bytes, header).expect("bhead writes");
+		assert_eq!(bytes.len(), header.bhead_layout().expect("layout").capabilities().size);
+
+		let parsed = BHead::parse(&mut Cursor::new(&bytes), header).expect("written bhead parses");
+		assert_eq!(
+			(parsed.code, parsed.sdna_nr, parsed.old, parsed.len, parsed.nr),
+			(head.code, head.sdna_nr, head.old, head.len, head.nr)
+		);
+	}
+}
+
+#[test]
+fn write_rejects_values_wider_than_layout() {
+	let header = BlendHeader::parse(b"BLENDER_v279").expect("header parses");
+	let head = BHead {
+		code: *b"DATA",
+		sdna_nr: 0,
+		old: 0x1_0000_0000,
+		len: 0,
+		nr: 1,
+	};
+	let err = head.write(&mut Vec::new(), header).expect_err("64-bit old does not fit BHead4");
+	assert!(matches!(err, BlendError::EncodeOutOfRange { field: "pointer", .. }));
+
+	let head = BHead { old: 0, len: 1 << 31, ..head };
+	let err = head.write(&mut Vec::new(), header).expect_err("len exceeds i32");
+	assert!(matches!(err, BlendError::EncodeOutOfRange { field: "len", .. }));
+}
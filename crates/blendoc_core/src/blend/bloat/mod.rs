@@ -0,0 +1,244 @@
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use crate::blend::{
+	BlendFile, Dna, IdFootprint, IdIndex, OrphanOptions, PointerIndex, Result, codes, find_orphan_ids, scan_id_blocks, scan_id_footprints, scan_packed_files,
+};
+
+/// `DATA` payloads with their owning ID pointer, keyed by `(len, hash)`.
+type DataGroups<'a> = HashMap<(u64, u64), Vec<(&'a [u8], Option<u64>)>>;
+
+/// What a [`BloatFinding`] is about.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum BloatKind {
+	/// Packed files embedded in the blend file.
+	PackedFiles,
+	/// IDs of one type with no users and no fake user; Blender drops them on reload.
+	OrphanIds,
+	/// `DATA` payloads stored more than once with identical bytes.
+	DuplicateData,
+	/// One ID whose own block plus owned data takes a large share of the file.
+	LargeId,
+}
+
+impl BloatKind {
+	/// Stable snake-case label.
+	pub fn as_str(self) -> &'static str {
+		match self {
+			Self::PackedFiles => "packed_files",
+			Self::OrphanIds => "orphan_ids",
+			Self::DuplicateData => "duplicate_data",
+			Self::LargeId => "large_id",
+		}
+	}
+}
+
+/// One ranked, actionable size finding.
+#[derive(Debug, Clone)]
+pub struct BloatFinding {
+	/// Finding category.
+	pub kind: BloatKind,
+	/// Bytes that acting on the finding would save or that the subject occupies.
+	pub bytes: u64,
+	/// Number of items (packed files, IDs, or redundant copies) behind `bytes`.
+	pub count: usize,
+	/// Subject: ID type name for orphans, ID name for large IDs, empty otherwise.
+	pub subject: Arc<str>,
+	/// Largest contributing ID names or file paths, largest first.
+	pub examples: Vec<Arc<str>>,
+}
+
+/// Thresholds for [`analyze_bloat`].
+#[derive(Debug, Clone)]
+pub struct BloatOptions {
+	/// Findings smaller than this are dropped.
+	pub min_bytes: u64,
+	/// `DATA` blocks smaller than this are not checked for duplicates.
+	pub min_duplicate_block: u64,
+	/// Maximum [`BloatKind::LargeId`] findings.
+	pub max_large_ids: usize,
+	/// Maximum examples kept per finding.
+	pub max_examples: usize,
+}
+
+impl Default for BloatOptions {
+	fn default() -> Self {
+		Self {
+			min_bytes: 1 << 20,
+			min_duplicate_block: 4096,
+			max_large_ids: 5,
+			max_examples: 5,
+		}
+	}
+}
+
+/// Ranked findings of one [`analyze_bloat`] run.
+#[derive(Debug, Clone)]
+pub struct BloatReport {
+	/// Decoded (uncompressed) file size.
+	pub file_bytes: u64,
+	/// Findings, largest first.
+	pub findings: Vec<BloatFinding>,
+}
+
+/// Explain where the bytes of `file` go, as a ranked list of findings.
+///
+/// Combines packed-file sizes, orphan IDs (see [`find_orphan_ids`]; fake-user
+/// IDs are skipped because they survive a reload), identical `DATA` payloads, and
+/// the largest per-ID footprints. The lenses overlap: a packed image that is
+/// also an orphan counts toward both findings.
+pub fn analyze_bloat(file: &BlendFile, dna: &Dna, options: &BloatOptions) -> Result<BloatReport> {
+	let index = file.pointer_index()?;
+	let ids = IdIndex::build(scan_id_blocks(file, dna)?);
+	let footprints = scan_id_footprints(file, &ids)?;
+	let sizes: HashMap<u64, u64> = footprints.iter().map(|item| (item.id_ptr, item.total_bytes())).collect();
+
+	let mut findings = Vec::new();
+	findings.extend(packed_finding(file, dna, options)?);
+	findings.extend(orphan_findings(dna, &index, &ids, &sizes, options)?);
+	findings.extend(duplicate_finding(file, &ids, options)?);
+	findings.extend(large_id_findings(&ids, &footprints, options));
+
+	findings.retain(|item| item.bytes >= options.min_bytes && item.bytes > 0);
+	findings.sort_by(|left, right| right.bytes.cmp(&left.bytes).then_with(|| left.subject.cmp(&right.subject)));
+
+	Ok(BloatReport {
+		file_bytes: file.bytes().len() as u64,
+		findings,
+	})
+}
+
+fn packed_finding(file: &BlendFile, dna: &Dna, options: &BloatOptions) -> Result<Option<BloatFinding>> {
+	let mut packed = scan_packed_files(file, dna)?;
+	if packed.is_empty() {
+		return Ok(None);
+	}
+	packed.sort_by_key(|item| std::cmp::Reverse(item.size));
+	let examples = packed
+		.iter()
+		.take(options.max_examples)
+		.map(|item| {
+			let label = item.filepath.as_deref().or(item.owner_id_name.as_deref()).unwrap_or("?");
+			Arc::from(label)
+		})
+		.collect();
+	Ok(Some(BloatFinding {
+		kind: BloatKind::PackedFiles,
+		bytes: packed.iter().map(|item| item.size as u64).sum(),
+		count: packed.len(),
+		subject: Arc::from(""),
+		examples,
+	}))
+}
+
+fn orphan_findings(dna: &Dna, index: &PointerIndex<'_>, ids: &IdIndex, sizes: &HashMap<u64, u64>, options: &BloatOptions) -> Result<Vec<BloatFinding>> {
+	let orphan_options = OrphanOptions {
+		include_fake_users: false,
+		..OrphanOptions::default()
+	};
+	let mut by_type: HashMap<Arc<str>, Vec<(u64, Arc<str>)>> = HashMap::new();
+	for orphan in find_orphan_ids(dna, index, ids, &orphan_options)?.orphans {
+		by_type
+			.entry(orphan.type_name)
+			.or_default()
+			.push((sizes.get(&orphan.id_ptr).copied().unwrap_or(0), orphan.id_name));
+	}
+
+	Ok(by_type
+		.into_iter()
+		.map(|(type_name, mut items)| {
+			items.sort_by(|left, right| right.0.cmp(&left.0).then_with(|| left.1.cmp(&right.1)));
+			BloatFinding {
+				kind: BloatKind::OrphanIds,
+				bytes: items.iter().map(|(bytes, _)| bytes).sum(),
+				count: items.len(),
+				subject: type_name,
+				examples: items.into_iter().take(options.max_examples).map(|(_, name)| name).collect(),
+			}
+		})
+		.collect())
+}
+
+fn duplicate_finding(file: &BlendFile, ids: &IdIndex, options: &BloatOptions) -> Result<Option<BloatFinding>> {
+	// Group by length and content hash, then confirm with a byte compare.
+	let mut groups: DataGroups<'_> = HashMap::new();
+	let mut owner = None;
+	for block in file.blocks() {
+		let block = block?;
+		if block.head.code != codes::DATA {
+			owner = ids.get_by_ptr(block.head.old).map(|record| record.old_ptr);
+			continue;
+		}
+		if block.head.len < options.min_duplicate_block.max(1) {
+			continue;
+		}
+		groups.entry((block.head.len, fnv1a(block.payload))).or_default().push((block.payload, owner));
+	}
+
+	let mut redundant = 0_u64;
+	let mut copies = 0_usize;
+	let mut owners: HashMap<u64, u64> = HashMap::new();
+	for items in groups.values() {
+		let (first, _) = items[0];
+		for (payload, owner) in &items[1..] {
+			if *payload != first {
+				continue;
+			}
+			redundant += payload.len() as u64;
+			copies += 1;
+			if let Some(owner) = owner {
+				*owners.entry(*owner).or_default() += payload.len() as u64;
+			}
+		}
+	}
+	if copies == 0 {
+		return Ok(None);
+	}
+
+	let mut owners: Vec<(u64, u64)> = owners.into_iter().collect();
+	owners.sort_by(|left, right| right.1.cmp(&left.1).then_with(|| left.0.cmp(&right.0)));
+	let examples = owners
+		.iter()
+		.filter_map(|(ptr, _)| ids.get_by_ptr(*ptr))
+		.take(options.max_examples)
+		.map(|record| Arc::from(record.id_name.as_ref()))
+		.collect();
+	Ok(Some(BloatFinding {
+		kind: BloatKind::DuplicateData,
+		bytes: redundant,
+		count: copies,
+		subject: Arc::from(""),
+		examples,
+	}))
+}
+
+fn large_id_findings(ids: &IdIndex, footprints: &[IdFootprint], options: &BloatOptions) -> Vec<BloatFinding> {
+	let mut ranked: Vec<&IdFootprint> = footprints.iter().collect();
+	ranked.sort_by(|left, right| right.total_bytes().cmp(&left.total_bytes()).then_with(|| left.id_ptr.cmp(&right.id_ptr)));
+	ranked
+		.into_iter()
+		.take(options.max_large_ids)
+		.filter_map(|item| {
+			let record = ids.get_by_ptr(item.id_ptr)?;
+			Some(BloatFinding {
+				kind: BloatKind::LargeId,
+				bytes: item.total_bytes(),
+				count: 1 + item.data_blocks,
+				subject: Arc::from(record.id_name.as_ref()),
+				examples: Vec::new(),
+			})
+		})
+		.collect()
+}
+
+fn fnv1a(bytes: &[u8]) -> u64 {
+	let mut hash = 0xcbf2_9ce4_8422_2325_u64;
+	for byte in bytes {
+		hash ^= u64::from(*byte);
+		hash = hash.wrapping_mul(0x0000_0100_0000_01b3);
+	}
+	hash
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,118 @@
+mod synthetic_bloat {
+	use blendoc_testkit::builder::{BlendBuilder, HeaderKind};
+
+	use crate::blend::{BlendFile, BloatKind, BloatOptions, analyze_bloat};
+
+	/// `SCScene` -> `OBCube` -> `MECube`, an unused `MEOrphan` whose vertex
+	/// `DATA` duplicates `MECube`'s, an unused packed `IMPacked`, and an
+	/// unused `IMFake` kept by its fake user.
+	fn bloated_file() -> Vec<u8> {
+		let mut builder = BlendBuilder::new(HeaderKind::Legacy8, 404, false);
+		let sdna = builder.sdna_mut();
+		sdna.add_struct("Link", &[("Link", "*next"), ("Link", "*prev")]);
+		sdna.add_type("char", 1);
+		sdna.add_type("int", 4);
+		sdna.add_type("void", 0);
+		sdna.add_struct(
+			"ID",
+			&[
+				("void", "*next"),
+				("void", "*prev"),
+				("void", "*lib"),
+				("char", "name[24]"),
+				("int", "flag"),
+				("int", "pad"),
+			],
+		);
+		let packed = sdna.add_struct("PackedFile", &[("int", "size"), ("int", "seek"), ("void", "*data")]);
+		let image = sdna.add_struct("Image", &[("ID", "id"), ("PackedFile", "*packedfile")]);
+		let mesh = sdna.add_struct("Mesh", &[("ID", "id"), ("void", "*verts")]);
+		let object = sdna.add_struct("Object", &[("ID", "id"), ("void", "*data")]);
+		let scene = sdna.add_struct("Scene", &[("ID", "id"), ("Object", "*camera")]);
+
+		let id = |builder: &BlendBuilder, name: &str, flag: i32, ptr: u64| {
+			let mut payload = builder.payload();
+			payload.ptr(0).ptr(0).ptr(0).name(name, 24).i32(flag).i32(0).ptr(ptr);
+			payload.finish()
+		};
+		let verts = vec![7_u8; 64];
+		let mut payload = builder.payload();
+		payload.i32(32).i32(0).ptr(0x5200);
+		let packed_payload = payload.finish();
+
+		let scene_payload = id(&builder, "SCScene", 0, 0x2000);
+		let object_payload = id(&builder, "OBCube", 0, 0x3000);
+		let mesh_payload = id(&builder, "MECube", 0, 0x3100);
+		let orphan_payload = id(&builder, "MEOrphan", 0, 0x4100);
+		let image_payload = id(&builder, "IMPacked", 0, 0x5100);
+		let fake_payload = id(&builder, "IMFake", 1 << 9, 0);
+		builder
+			.block(*b"SC\0\0", scene, 0x1000, 1, scene_payload)
+			.block(*b"OB\0\0", object, 0x2000, 1, object_payload)
+			.block(*b"ME\0\0", mesh, 0x3000, 1, mesh_payload)
+			.block(*b"DATA", 0, 0x3100, 1, verts.clone())
+			.block(*b"ME\0\0", mesh, 0x4000, 1, orphan_payload)
+			.block(*b"DATA", 0, 0x4100, 1, verts)
+			.block(*b"IM\0\0", image, 0x5000, 1, image_payload)
+			.block(*b"DATA", packed, 0x5100, 1, packed_payload)
+			.block(*b"DATA", 0, 0x5200, 1, vec![1; 32])
+			.block(*b"IM\0\0", image, 0x6000, 1, fake_payload);
+		builder.build()
+	}
+
+	fn options() -> BloatOptions {
+		BloatOptions {
+			min_bytes: 0,
+			min_duplicate_block: 16,
+			max_large_ids: 1,
+			..BloatOptions::default()
+		}
+	}
+
+	#[test]
+	fn findings_cover_packed_orphans_duplicates_and_large_ids() {
+		let blend = BlendFile::from_bytes(bloated_file()).expect("synthetic file parses");
+		let dna = blend.dna().expect("dna");
+		let report = analyze_bloat(&blend, &dna, &options()).expect("analysis");
+
+		let find = |kind: BloatKind, subject: &str| {
+			report
+				.findings
+				.iter()
+				.find(|item| item.kind == kind && item.subject.as_ref() == subject)
+				.unwrap_or_else(|| panic!("{kind:?} {subject}: {:?}", report.findings))
+		};
+
+		let packed = find(BloatKind::PackedFiles, "");
+		assert_eq!((packed.bytes, packed.count), (32, 1));
+
+		let meshes = find(BloatKind::OrphanIds, "Mesh");
+		assert_eq!(meshes.count, 1);
+		assert_eq!(meshes.examples[0].as_ref(), "MEOrphan");
+		let images = find(BloatKind::OrphanIds, "Image");
+		assert_eq!(images.count, 1, "fake-user images are kept");
+		assert_eq!(images.examples[0].as_ref(), "IMPacked");
+		assert!(
+			report
+				.findings
+				.iter()
+				.all(|item| item.subject.as_ref() != "Scene" && item.subject.as_ref() != "Object")
+		);
+
+		let duplicate = find(BloatKind::DuplicateData, "");
+		assert_eq!((duplicate.bytes, duplicate.count), (64, 1));
+		assert_eq!(duplicate.examples[0].as_ref(), "MEOrphan");
+
+		assert_eq!(report.findings.iter().filter(|item| item.kind == BloatKind::LargeId).count(), 1);
+		assert!(report.findings.windows(2).all(|pair| pair[0].bytes >= pair[1].bytes));
+	}
+
+	#[test]
+	fn min_bytes_drops_small_findings() {
+		let blend = BlendFile::from_bytes(bloated_file()).expect("synthetic file parses");
+		let dna = blend.dna().expect("dna");
+		let report = analyze_bloat(&blend, &dna, &BloatOptions::default()).expect("analysis");
+		assert!(report.findings.is_empty());
+		assert_eq!(report.file_bytes, blend.bytes().len() as u64);
+	}
+}
@@ -12,12 +12,86 @@ pub struct Block<'a> {
 	pub file_offset: usize,
 }
 
-/// Iterator over contiguous block records.
+impl<'a> Block<'a> {
+	/// Iterate the payload in `chunk_size`-byte slices; the last one may be shorter.
+	///
+	/// Slices borrow the file backing directly, so under [`BlendFile::open_mmap`]
+	/// only the pages of the chunk being read are faulted in.
+	///
+	/// [`BlendFile::open_mmap`]: crate::blend::BlendFile::open_mmap
+	pub fn payload_chunks(&self, chunk_size: usize) -> PayloadChunks<'a> {
+		PayloadChunks::new(self.payload, chunk_size)
+	}
+}
+
+/// Bounded-size slices over a block payload or any borrowed byte range.
+#[derive(Debug, Clone)]
+pub struct PayloadChunks<'a> {
+	rest: &'a [u8],
+	chunk_size: usize,
+	offset: usize,
+}
+
+impl<'a> PayloadChunks<'a> {
+	/// Split `bytes` into `chunk_size`-byte slices; a zero size is treated as one.
+	pub fn new(bytes: &'a [u8], chunk_size: usize) -> Self {
+		Self {
+			rest: bytes,
+			chunk_size: chunk_size.max(1),
+			offset: 0,
+		}
+	}
+
+	/// Byte offset of the next chunk within the payload.
+	pub fn offset(&self) -> usize {
+		self.offset
+	}
+}
+
+impl<'a> Iterator for PayloadChunks<'a> {
+	type Item = &'a [u8];
+
+	fn next(&mut self) -> Option<Self::Item> {
+		if self.rest.is_empty() {
+			return None;
+		}
+
+		let (chunk, rest) = self.rest.split_at(self.chunk_size.min(self.rest.len()));
+		self.rest = rest;
+		self.offset += chunk.len();
+		Some(chunk)
+	}
+
+	fn size_hint(&self) -> (usize, Option<usize>) {
+		let remaining = self.rest.len().div_ceil(self.chunk_size);
+		(remaining, Some(remaining))
+	}
+}
+
+impl ExactSizeIterator for PayloadChunks<'_> {}
+
+impl std::iter::FusedIterator for PayloadChunks<'_> {}
+
+/// Largest block alignment [`BlockIter`] recognizes when skipping inter-block padding.
+pub const MAX_BLOCK_ALIGNMENT: usize = 8;
+
+/// Iterator over block records, tolerating zero padding between them.
+///
+/// Blocks are normally packed back to back. When NUL bytes sit where a block
+/// code should start and the position is not yet [`MAX_BLOCK_ALIGNMENT`]-aligned
+/// (relative to the first block), they are treated as alignment padding and
+/// skipped; [`BlockIter::last_padding`] and [`BlockIter::total_padding`] report
+/// what was skipped. Block codes never start with NUL, so packed files are
+/// unaffected.
 pub struct BlockIter<'a> {
 	cursor: Cursor<'a>,
 	offset_base: usize,
 	header: BlendHeader,
 	done: bool,
+	last_padding: usize,
+	total_padding: u64,
+	#[cfg(feature = "tracing")]
+	span: tracing::Span,
 }
 
 impl<'a> BlockIter<'a> {
@@ -29,14 +103,46 @@ impl<'a> BlockIter<'a> {
 			offset_base: offset,
 			header,
 			done: false,
+			last_padding: 0,
+			total_padding: 0,
+			#[cfg(feature = "tracing")]
+			span: tracing::info_span!("blocks"),
 		}
 	}
+
+	/// Padding bytes skipped before the most recently yielded block header.
+	pub fn last_padding(&self) -> usize {
+		self.last_padding
+	}
+
+	/// Padding bytes skipped before all block headers yielded so far.
+	pub fn total_padding(&self) -> u64 {
+		self.total_padding
+	}
+
+	/// Skip NUL bytes up to the next aligned position and return how many were skipped.
+	fn skip_padding(&mut self) -> usize {
+		let misalignment = self.cursor.pos() % MAX_BLOCK_ALIGNMENT;
+		if misalignment == 0 {
+			return 0;
+		}
+
+		let gap = (MAX_BLOCK_ALIGNMENT - misalignment).min(self.cursor.remaining());
+		let zeros = self.cursor.peek(gap).iter().take_while(|byte| **byte == 0).count();
+		if zeros == 0 {
+			return 0;
+		}
+		self.cursor.skip(zeros);
+		zeros
+	}
 }
 
 impl<'a> Iterator for BlockIter<'a> {
 	type Item = Result<Block<'a>>;
 
 	fn next(&mut self) -> Option<Self::Item> {
+		#[cfg(feature = "tracing")]
+		let _enter = self.span.clone().entered();
 		if self.done {
 			return None;
 		}
@@ -46,6 +152,13 @@ impl<'a> Iterator for BlockIter<'a> {
 			return None;
 		}
 
+		self.last_padding = self.skip_padding();
+		self.total_padding += self.last_padding as u64;
+		if self.cursor.remaining() == 0 {
+			self.done = true;
+			return None;
+		}
+
 		let file_offset = self.offset_base + self.cursor.pos();
 		let head = match BHead::parse(&mut self.cursor, self.header) {
 			Ok(value) => value,
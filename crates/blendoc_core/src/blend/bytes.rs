@@ -22,6 +22,16 @@ impl<'a> Cursor<'a> {
 		self.bytes.len().saturating_sub(self.pos)
 	}
 
+	/// Return up to `n` upcoming bytes without advancing.
+	pub fn peek(&self, n: usize) -> &'a [u8] {
+		&self.bytes[self.pos..self.pos + n.min(self.remaining())]
+	}
+
+	/// Advance past up to `n` bytes.
+	pub fn skip(&mut self, n: usize) {
+		self.pos += n.min(self.remaining());
+	}
+
 	/// Read exactly `n` bytes and advance cursor.
 	pub fn read_exact(&mut self, n: usize) -> Result<&'a [u8]> {
 		if n > self.remaining() {
@@ -154,3 +164,87 @@ impl<'a> Cursor<'a> {
 		Ok(&self.bytes[start..end])
 	}
 }
+
+/// Growable byte buffer mirroring the [`Cursor`] reads, for encoding.
+#[derive(Default)]
+pub struct ByteWriter {
+	bytes: Vec<u8>,
+}
+
+impl ByteWriter {
+	/// Create an empty writer.
+	pub fn new() -> Self {
+		Self::default()
+	}
+
+	/// Return current byte offset.
+	pub fn pos(&self) -> usize {
+		self.bytes.len()
+	}
+
+	/// Return the written bytes.
+	pub fn into_bytes(self) -> Vec<u8> {
+		self.bytes
+	}
+
+	/// Append raw bytes.
+	pub fn write_bytes(&mut self, bytes: &[u8]) {
+		self.bytes.extend_from_slice(bytes);
+	}
+
+	/// Append a `u16` using the selected endianness.
+	pub fn write_u16(&mut self, value: u16, endianness: Endianness) {
+		match endianness {
+			Endianness::Little => self.write_bytes(&value.to_le_bytes()),
+			Endianness::Big => self.write_bytes(&value.to_be_bytes()),
+		}
+	}
+
+	/// Append a `u32` using the selected endianness.
+	pub fn write_u32(&mut self, value: u32, endianness: Endianness) {
+		match endianness {
+			Endianness::Little => self.write_bytes(&value.to_le_bytes()),
+			Endianness::Big => self.write_bytes(&value.to_be_bytes()),
+		}
+	}
+
+	/// Append a `u64` using the selected endianness.
+	pub fn write_u64(&mut self, value: u64, endianness: Endianness) {
+		match endianness {
+			Endianness::Little => self.write_bytes(&value.to_le_bytes()),
+			Endianness::Big => self.write_bytes(&value.to_be_bytes()),
+		}
+	}
+
+	/// Append a pointer-sized unsigned integer, failing when `value` does not fit.
+	pub fn write_ptr(&mut self, value: u64, pointer_size: usize, endianness: Endianness) -> Result<()> {
+		match pointer_size {
+			4 => {
+				let narrow = u32::try_from(value).map_err(|_| BlendError::EncodeOutOfRange {
+					field: "pointer",
+					value,
+					max: u64::from(u32::MAX),
+				})?;
+				self.write_u32(narrow, endianness);
+				Ok(())
+			}
+			8 => {
+				self.write_u64(value, endianness);
+				Ok(())
+			}
+			_ => Err(BlendError::UnsupportedPointerSize { header_size: pointer_size }),
+		}
+	}
+
+	/// Zero-pad to the next 4-byte aligned position.
+	pub fn align4(&mut self) {
+		let aligned = (self.pos() + 3) & !3;
+		self.bytes.resize(aligned, 0);
+	}
+
+	/// Append a byte string followed by a zero terminator.
+	pub fn write_cstring_bytes(&mut self, bytes: &[u8]) {
+		self.write_bytes(bytes);
+		self.bytes.push(0);
+	}
+}
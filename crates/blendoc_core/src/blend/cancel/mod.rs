@@ -0,0 +1,43 @@
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+/// Cooperative cancellation flag for long-running traversals.
+///
+/// Traversals check the token once per expanded node or owner and stop
+/// early with partial results and a `Cancelled` truncation reason. Clones
+/// share the same flag, so one handle can be kept by a signal handler or
+/// request loop while another is stored in traversal options.
+#[derive(Debug, Clone, Default)]
+pub struct CancelToken {
+	flag: Arc<AtomicBool>,
+}
+
+impl CancelToken {
+	/// Fresh, uncancelled token.
+	pub fn new() -> Self {
+		Self::default()
+	}
+
+	/// Request cancellation; every clone observes it.
+	pub fn cancel(&self) {
+		self.flag.store(true, Ordering::Relaxed);
+	}
+
+	/// Clear a previous request so the token can be reused for the next job.
+	pub fn reset(&self) {
+		self.flag.store(false, Ordering::Relaxed);
+	}
+
+	/// Whether cancellation has been requested.
+	pub fn is_cancelled(&self) -> bool {
+		self.flag.load(Ordering::Relaxed)
+	}
+}
+
+/// Whether an optional token has been cancelled.
+pub(crate) fn is_cancelled(token: Option<&CancelToken>) -> bool {
+	token.is_some_and(CancelToken::is_cancelled)
+}
+
+#[cfg(test)]
+mod tests;
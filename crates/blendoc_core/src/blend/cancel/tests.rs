@@ -0,0 +1,65 @@
+use blendoc_testkit::builder::{HeaderKind, scene_file, scene_ptrs};
+
+use crate::blend::{
+	BlendFile, CancelToken, GraphOptions, GraphTruncation, IdGraphOptions, IdGraphTruncation, IdIndex, RouteOptions, RouteTruncation, XrefOptions,
+	build_graph_from_ptr, build_id_graph, find_inbound_refs_to_ptr, find_route_between_ptrs, scan_id_blocks,
+};
+
+#[test]
+fn clones_share_cancellation_and_reset() {
+	let token = CancelToken::new();
+	let held = token.clone();
+	assert!(!held.is_cancelled());
+	token.cancel();
+	assert!(held.is_cancelled());
+	held.reset();
+	assert!(!token.is_cancelled());
+}
+
+#[test]
+fn cancelled_traversals_stop_with_partial_results() {
+	let blend = BlendFile::from_bytes(scene_file(HeaderKind::Legacy8, false)).expect("synthetic file opens");
+	let dna = blend.dna().expect("dna parses");
+	let index = blend.pointer_index().expect("pointer index builds");
+	let ids = IdIndex::build(scan_id_blocks(&blend, &dna).expect("ids scan"));
+	let [scene, _, _, _, world] = scene_ptrs(HeaderKind::Legacy8);
+
+	for cancelled in [false, true] {
+		let token = CancelToken::new();
+		if cancelled {
+			token.cancel();
+		}
+
+		let graph_options = GraphOptions {
+			cancel: Some(token.clone()),
+			..GraphOptions::default()
+		};
+		let graph = build_graph_from_ptr(&dna, &index, &ids, scene, &graph_options).expect("graph builds");
+		assert_eq!(matches!(graph.truncated, Some(GraphTruncation::Cancelled)), cancelled);
+		assert_eq!(graph.nodes.len() == 1, cancelled, "a cancelled graph keeps only its root");
+
+		let route_options = RouteOptions {
+			cancel: Some(token.clone()),
+			..RouteOptions::default()
+		};
+		let route = find_route_between_ptrs(&dna, &index, &ids, scene, world, &route_options).expect("route search runs");
+		assert_eq!(matches!(route.truncated, Some(RouteTruncation::Cancelled)), cancelled);
+		assert_eq!(route.path.is_none(), cancelled);
+
+		let id_graph_options = IdGraphOptions {
+			cancel: Some(token.clone()),
+			..IdGraphOptions::default()
+		};
+		let id_graph = build_id_graph(&dna, &index, &ids, &id_graph_options).expect("id graph builds");
+		assert_eq!(matches!(id_graph.truncated, Some(IdGraphTruncation::Cancelled)), cancelled);
+		assert_eq!(id_graph.edges.is_empty(), cancelled);
+		assert_eq!(id_graph.nodes.len(), ids.records.len(), "nodes are listed before the owner scan");
+
+		let xref_options = XrefOptions {
+			cancel: Some(token),
+			..XrefOptions::default()
+		};
+		let inbound = find_inbound_refs_to_ptr(&dna, &index, &ids, world, &xref_options).expect("xref runs");
+		assert_eq!(inbound.is_empty(), cancelled);
+	}
+}
@@ -0,0 +1,322 @@
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use crate::blend::{BlendFile, DecodeOptions, Dna, IdChange, IdIndex, Result, StructValue, Value, codes, decode_block_instances, glob_match, scan_id_blocks};
+
+/// Content hash of one ID as of one load.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct IdSnapshotEntry {
+	/// ID block code.
+	pub code: [u8; 4],
+	/// ID-root struct type name.
+	pub type_name: Arc<str>,
+	/// `ID.name`.
+	pub id_name: Arc<str>,
+	/// ID block pointer in this load.
+	pub old_ptr: u64,
+	/// FNV-1a 64 over the ID root and its owned `DATA` blocks.
+	pub hash: u64,
+	/// Payload bytes covered by `hash`.
+	pub bytes: u64,
+}
+
+/// Per-ID content hashes of one file load, keyed by type and `ID.name`.
+///
+/// Hashes ignore pointer addresses in typed structs (only nullness counts), so
+/// resaving an unchanged file does not report every ID as changed. Untyped
+/// `DATA` payloads (attribute arrays, pointer arrays) are hashed as raw bytes.
+#[derive(Debug, Clone, Default)]
+pub struct IdSnapshot {
+	entries: HashMap<SnapshotKey, IdSnapshotEntry>,
+}
+
+/// Match key: type name, ID name, and ordinal among same-named IDs.
+type SnapshotKey = (Arc<str>, Arc<str>, usize);
+
+impl IdSnapshot {
+	/// Hash every ID-root block and the `DATA` blocks written after it.
+	///
+	/// Ownership follows file order as in [`crate::blend::scan_id_footprints`].
+	pub fn capture(file: &BlendFile, dna: &Dna) -> Result<Self> {
+		let ids = IdIndex::build(scan_id_blocks(file, dna)?);
+		let decode = DecodeOptions::default();
+		let mut hashed: Vec<IdSnapshotEntry> = Vec::with_capacity(ids.records.len());
+		let mut open = false;
+
+		for block in file.blocks() {
+			let block = block?;
+			if block.head.code == codes::DATA {
+				if open && let Some(current) = hashed.last_mut() {
+					let mut hasher = Fnv::seeded(current.hash);
+					hash_block(dna, &block, &decode, &mut hasher);
+					current.hash = hasher.finish();
+					current.bytes = current.bytes.saturating_add(block.head.len);
+				}
+				continue;
+			}
+
+			let record = ids
+				.get_by_ptr(block.head.old)
+				.filter(|record| record.sdna_nr == block.head.sdna_nr && record.code == block.head.code);
+			open = record.is_some();
+			if let Some(record) = record {
+				let mut hasher = Fnv::new();
+				hash_block(dna, &block, &decode, &mut hasher);
+				hashed.push(IdSnapshotEntry {
+					code: record.code,
+					type_name: Arc::from(record.type_name.as_ref()),
+					id_name: Arc::from(record.id_name.as_ref()),
+					old_ptr: record.old_ptr,
+					hash: hasher.finish(),
+					bytes: block.head.len,
+				});
+			}
+		}
+
+		let mut seen: HashMap<(Arc<str>, Arc<str>), usize> = HashMap::new();
+		let mut entries = HashMap::with_capacity(hashed.len());
+		for entry in hashed {
+			let ordinal = seen.entry((entry.type_name.clone(), entry.id_name.clone())).or_default();
+			entries.insert((entry.type_name.clone(), entry.id_name.clone(), *ordinal), entry);
+			*ordinal += 1;
+		}
+		Ok(Self { entries })
+	}
+
+	/// Number of IDs in the snapshot.
+	pub fn len(&self) -> usize {
+		self.entries.len()
+	}
+
+	/// Whether the snapshot holds no IDs.
+	pub fn is_empty(&self) -> bool {
+		self.entries.is_empty()
+	}
+
+	/// Look up the first ID with this `ID.name`.
+	pub fn get(&self, id_name: &str) -> Option<&IdSnapshotEntry> {
+		self.entries
+			.iter()
+			.filter(|(key, _)| key.1.as_ref() == id_name)
+			.min_by_key(|(key, _)| key.2)
+			.map(|(_, entry)| entry)
+	}
+
+	/// Entries ordered by type, name, then file order.
+	pub fn entries(&self) -> Vec<&IdSnapshotEntry> {
+		let mut keys: Vec<_> = self.entries.keys().collect();
+		keys.sort();
+		keys.into_iter().map(|key| &self.entries[key]).collect()
+	}
+}
+
+/// One ID that was added, removed, or changed between two snapshots.
+#[derive(Debug, Clone)]
+pub struct IdChangeEvent {
+	/// Kind of change.
+	pub change: IdChange,
+	/// ID block code.
+	pub code: [u8; 4],
+	/// ID-root struct type name.
+	pub type_name: Arc<str>,
+	/// `ID.name`.
+	pub id_name: Arc<str>,
+	/// Content hash in the earlier snapshot.
+	pub before: Option<u64>,
+	/// Content hash in the later snapshot.
+	pub after: Option<u64>,
+}
+
+/// Compare two snapshots; events are ordered by type, name, then change kind.
+pub fn diff_snapshots(before: &IdSnapshot, after: &IdSnapshot) -> Vec<IdChangeEvent> {
+	let mut out = Vec::new();
+	for (key, old) in &before.entries {
+		match after.entries.get(key) {
+			None => out.push(event(IdChange::Removed, old, Some(old.hash), None)),
+			Some(new) if new.hash != old.hash => out.push(event(IdChange::Changed, new, Some(old.hash), Some(new.hash))),
+			Some(_) => {}
+		}
+	}
+	for (key, new) in &after.entries {
+		if !before.entries.contains_key(key) {
+			out.push(event(IdChange::Added, new, None, Some(new.hash)));
+		}
+	}
+	out.sort_by(|a, b| (&a.type_name, &a.id_name, a.change.as_str()).cmp(&(&b.type_name, &b.id_name, b.change.as_str())));
+	out
+}
+
+fn event(change: IdChange, entry: &IdSnapshotEntry, before: Option<u64>, after: Option<u64>) -> IdChangeEvent {
+	IdChangeEvent {
+		change,
+		code: entry.code,
+		type_name: entry.type_name.clone(),
+		id_name: entry.id_name.clone(),
+		before,
+		after,
+	}
+}
+
+/// Which change events a subscriber receives; unset fields match anything.
+#[derive(Debug, Clone, Default)]
+pub struct IdChangeFilter {
+	/// Only IDs with this block code (`MA`, `OB`, ...).
+	pub code: Option<[u8; 4]>,
+	/// Only IDs of this struct type (`Material`, ...).
+	pub type_name: Option<Arc<str>>,
+	/// Only IDs whose `ID.name` matches this glob (`MA*`, `OBCube`).
+	pub name: Option<Arc<str>>,
+	/// Only this kind of change.
+	pub change: Option<IdChange>,
+}
+
+impl IdChangeFilter {
+	/// Whether `event` passes every set criterion.
+	pub fn matches(&self, event: &IdChangeEvent) -> bool {
+		self.code.is_none_or(|code| code == event.code)
+			&& self.type_name.as_deref().is_none_or(|type_name| type_name == event.type_name.as_ref())
+			&& self.name.as_deref().is_none_or(|pattern| glob_match(pattern, &event.id_name, false))
+			&& self.change.is_none_or(|change| change == event.change)
+	}
+}
+
+/// Handle returned by [`IdChangeFeed::subscribe`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct SubscriptionId(u64);
+
+type Callback = Box<dyn FnMut(&IdChangeEvent) + Send>;
+
+/// Subscription hub for per-ID change events across successive loads of one file.
+///
+/// Feed each reload to [`IdChangeFeed::update`]; it diffs against the previous
+/// snapshot and calls every subscriber whose filter matches an event. The
+/// first update only records a baseline.
+#[derive(Default)]
+pub struct IdChangeFeed {
+	last: Option<IdSnapshot>,
+	subscribers: Vec<(SubscriptionId, IdChangeFilter, Callback)>,
+	next_id: u64,
+}
+
+impl IdChangeFeed {
+	/// Create a feed with no baseline and no subscribers.
+	pub fn new() -> Self {
+		Self::default()
+	}
+
+	/// Register `callback` for events matching `filter`.
+	pub fn subscribe(&mut self, filter: IdChangeFilter, callback: impl FnMut(&IdChangeEvent) + Send + 'static) -> SubscriptionId {
+		let id = SubscriptionId(self.next_id);
+		self.next_id += 1;
+		self.subscribers.push((id, filter, Box::new(callback)));
+		id
+	}
+
+	/// Remove a subscriber; returns whether it was registered.
+	pub fn unsubscribe(&mut self, id: SubscriptionId) -> bool {
+		let before = self.subscribers.len();
+		self.subscribers.retain(|(item, _, _)| *item != id);
+		self.subscribers.len() != before
+	}
+
+	/// Snapshot recorded by the last update.
+	pub fn last_snapshot(&self) -> Option<&IdSnapshot> {
+		self.last.as_ref()
+	}
+
+	/// Diff `snapshot` against the previous one, notify subscribers, and return all events.
+	pub fn update(&mut self, snapshot: IdSnapshot) -> Vec<IdChangeEvent> {
+		let events = match &self.last {
+			Some(previous) => diff_snapshots(previous, &snapshot),
+			None => Vec::new(),
+		};
+		self.last = Some(snapshot);
+		for event in &events {
+			for (_, filter, callback) in &mut self.subscribers {
+				if filter.matches(event) {
+					callback(event);
+				}
+			}
+		}
+		events
+	}
+
+	/// Capture a snapshot of `file` and [`update`](Self::update) with it.
+	pub fn load(&mut self, file: &BlendFile, dna: &Dna) -> Result<Vec<IdChangeEvent>> {
+		Ok(self.update(IdSnapshot::capture(file, dna)?))
+	}
+}
+
+/// Hash typed blocks by decoded value and untyped or undecodable ones by raw payload.
+pub(crate) fn hash_block(dna: &Dna, block: &crate::blend::Block<'_>, decode: &DecodeOptions, hasher: &mut Fnv) {
+	if block.head.sdna_nr != 0
+		&& let Ok(value) = decode_block_instances(dna, block, decode)
+	{
+		hash_value(&value, hasher);
+		return;
+	}
+	hasher.write(&[0xff]);
+	hasher.write(block.payload);
+}
+
+pub(crate) fn hash_value(value: &Value, hasher: &mut Fnv) {
+	match value {
+		Value::Null => hasher.write(&[0]),
+		Value::Bool(value) => hasher.write(&[1, u8::from(*value)]),
+		Value::I64(value) => hash_tagged(hasher, 2, &value.to_le_bytes()),
+		Value::U64(value) => hash_tagged(hasher, 3, &value.to_le_bytes()),
+		Value::F32(value) => hash_tagged(hasher, 4, &value.to_bits().to_le_bytes()),
+		Value::F64(value) => hash_tagged(hasher, 5, &value.to_bits().to_le_bytes()),
+		Value::Bytes(bytes) => hash_tagged(hasher, 6, bytes),
+		Value::String(text) => hash_tagged(hasher, 7, text.as_bytes()),
+		// Stored addresses are session-specific; only nullness is content.
+		Value::Ptr(ptr) => hasher.write(&[8, u8::from(*ptr != 0)]),
+		Value::Array(items) => {
+			hash_tagged(hasher, 9, &(items.len() as u64).to_le_bytes());
+			for item in items {
+				hash_value(item, hasher);
+			}
+		}
+		Value::Struct(item) => hash_struct(item, hasher),
+	}
+}
+
+fn hash_struct(item: &StructValue, hasher: &mut Fnv) {
+	hash_tagged(hasher, 10, item.type_name.as_bytes());
+	for field in &item.fields {
+		hasher.write(field.name.as_bytes());
+		hash_value(&field.value, hasher);
+	}
+}
+
+fn hash_tagged(hasher: &mut Fnv, tag: u8, bytes: &[u8]) {
+	hasher.write(&[tag]);
+	hasher.write(&(bytes.len() as u64).to_le_bytes());
+	hasher.write(bytes);
+}
+
+/// FNV-1a 64: stable across processes and Rust versions, unlike `DefaultHasher`.
+pub(crate) struct Fnv(u64);
+
+impl Fnv {
+	pub(crate) fn new() -> Self {
+		Self(0xcbf2_9ce4_8422_2325)
+	}
+
+	pub(crate) fn seeded(state: u64) -> Self {
+		Self(state)
+	}
+
+	pub(crate) fn write(&mut self, bytes: &[u8]) {
+		for byte in bytes {
+			self.0 = (self.0 ^ u64::from(*byte)).wrapping_mul(0x0000_0100_0000_01b3);
+		}
+	}
+
+	pub(crate) fn finish(&self) -> u64 {
+		self.0
+	}
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,122 @@
+mod synthetic_changes {
+	use std::sync::{Arc, Mutex};
+
+	use blendoc_testkit::builder::{BlendBuilder, HeaderKind};
+
+	use crate::blend::{BlendFile, IdChange, IdChangeFeed, IdChangeFilter, IdSnapshot, diff_snapshots};
+
+	/// `MAMetal` (with `r` and a trailing raw `DATA` block) and `OBCube`
+	/// pointing at it; `base` shifts every stored address, `extra` appends `OBLight`.
+	fn file(base: u64, roughness: f32, tail: u8, extra: bool) -> Vec<u8> {
+		let mut builder = BlendBuilder::new(HeaderKind::Legacy8, 404, false);
+		let sdna = builder.sdna_mut();
+		sdna.add_struct("Link", &[("Link", "*next"), ("Link", "*prev")]);
+		sdna.add_type("char", 1);
+		sdna.add_type("float", 4);
+		sdna.add_type("void", 0);
+		sdna.add_struct("ID", &[("void", "*next"), ("void", "*prev"), ("void", "*lib"), ("char", "name[24]")]);
+		let material = sdna.add_struct("Material", &[("ID", "id"), ("float", "r")]);
+		let object = sdna.add_struct("Object", &[("ID", "id"), ("void", "*data")]);
+
+		let material_ptr = base + 0x100;
+		let id = |builder: &BlendBuilder, name: &str| {
+			let mut payload = builder.payload();
+			payload.ptr(0).ptr(0).ptr(0).name(name, 24);
+			payload
+		};
+		let mut payload = id(&builder, "MAMetal");
+		payload.f32(roughness);
+		let material_payload = payload.finish();
+		let mut payload = id(&builder, "OBCube");
+		payload.ptr(material_ptr);
+		let object_payload = payload.finish();
+
+		builder
+			.block(*b"MA\0\0", material, material_ptr, 1, material_payload)
+			.block(*b"DATA", 0, base + 0x180, 1, vec![1, 2, 3, tail])
+			.block(*b"OB\0\0", object, base + 0x200, 1, object_payload);
+		if extra {
+			let mut payload = id(&builder, "OBLight");
+			payload.ptr(0);
+			let light_payload = payload.finish();
+			builder.block(*b"OB\0\0", object, base + 0x300, 1, light_payload);
+		}
+		builder.build()
+	}
+
+	fn snapshot(bytes: Vec<u8>) -> IdSnapshot {
+		let blend = BlendFile::from_bytes(bytes).expect("synthetic file parses");
+		let dna = blend.dna().expect("dna");
+		IdSnapshot::capture(&blend, &dna).expect("snapshot")
+	}
+
+	#[test]
+	fn rebased_pointers_are_not_changes() {
+		let before = snapshot(file(0x1000, 0.5, 4, false));
+		let after = snapshot(file(0x8000, 0.5, 4, false));
+
+		assert_eq!(before.len(), 2);
+		assert_eq!(before.get("MAMetal").expect("material").bytes, after.get("MAMetal").expect("material").bytes);
+		assert!(diff_snapshots(&before, &after).is_empty());
+	}
+
+	#[test]
+	fn field_owned_data_and_new_ids_are_reported() {
+		let base = snapshot(file(0x1000, 0.5, 4, false));
+
+		let changed = diff_snapshots(&base, &snapshot(file(0x1000, 0.25, 4, false)));
+		assert_eq!(changed.len(), 1);
+		assert_eq!((changed[0].change, changed[0].id_name.as_ref()), (IdChange::Changed, "MAMetal"));
+		assert_ne!(changed[0].before, changed[0].after);
+
+		let data_changed = diff_snapshots(&base, &snapshot(file(0x1000, 0.5, 9, false)));
+		assert_eq!(data_changed.len(), 1);
+		assert_eq!(data_changed[0].id_name.as_ref(), "MAMetal");
+
+		let added = diff_snapshots(&base, &snapshot(file(0x1000, 0.5, 4, true)));
+		assert_eq!(added.len(), 1);
+		assert_eq!(
+			(added[0].change, added[0].id_name.as_ref(), added[0].before),
+			(IdChange::Added, "OBLight", None)
+		);
+
+		let removed = diff_snapshots(&snapshot(file(0x1000, 0.5, 4, true)), &base);
+		assert_eq!((removed[0].change, removed[0].id_name.as_ref()), (IdChange::Removed, "OBLight"));
+	}
+
+	#[test]
+	fn feed_dispatches_filtered_events() {
+		let mut feed = IdChangeFeed::new();
+		let materials = Arc::new(Mutex::new(Vec::new()));
+		let sink = materials.clone();
+		feed.subscribe(
+			IdChangeFilter {
+				code: Some(*b"MA\0\0"),
+				..IdChangeFilter::default()
+			},
+			move |event| sink.lock().expect("sink").push(event.id_name.to_string()),
+		);
+		let added = Arc::new(Mutex::new(0_usize));
+		let counter = added.clone();
+		let objects = feed.subscribe(
+			IdChangeFilter {
+				name: Some("OB*".into()),
+				change: Some(IdChange::Added),
+				..IdChangeFilter::default()
+			},
+			move |_| *counter.lock().expect("counter") += 1,
+		);
+
+		assert!(feed.update(snapshot(file(0x1000, 0.5, 4, false))).is_empty());
+		let events = feed.update(snapshot(file(0x2000, 0.25, 4, true)));
+		assert_eq!(events.len(), 2);
+		assert_eq!(*materials.lock().expect("sink"), vec!["MAMetal".to_owned()]);
+		assert_eq!(*added.lock().expect("counter"), 1);
+
+		assert!(feed.unsubscribe(objects));
+		assert!(!feed.unsubscribe(objects));
+		feed.update(snapshot(file(0x1000, 0.5, 4, false)));
+		assert_eq!(materials.lock().expect("sink").len(), 2);
+		assert_eq!(*added.lock().expect("counter"), 1);
+	}
+}
@@ -1,4 +1,6 @@
-use crate::blend::{BlendError, BlendFile, DecodeOptions, Dna, PointerIndex, Result, StructValue, Value, decode_block_instances, decode_struct_instance};
+use crate::blend::{
+	BlendError, BlendFile, DecodeOptions, Dna, PointerIndex, Result, StructValue, Value, codes, decode_block_instances, decode_struct_instance,
+};
 
 /// Metadata captured for one pointer dereference step.
 #[derive(Debug, Clone, Copy)]
@@ -67,7 +69,7 @@ pub fn chase_scene_camera<'a>(
 	scene_decode: &DecodeOptions,
 	object_decode: &DecodeOptions,
 ) -> Result<Option<(ChaseMeta, StructValue)>> {
-	let Some(scene_block) = file.find_first_block_by_code([b'S', b'C', 0, 0])? else {
+	let Some(scene_block) = file.find_first_block_by_code(codes::ID_SCE)? else {
 		return Ok(None);
 	};
 
@@ -2,7 +2,7 @@ mod fixtures_day4_chase_camera {
 
 	use blendoc_testkit::fixture_path;
 
-	use crate::blend::{BlendFile, DecodeOptions, Value, chase_ptr_to_struct, chase_scene_camera, decode_block_instances};
+	use crate::blend::{BlendFile, DecodeOptions, Value, chase_ptr_to_struct, chase_scene_camera, codes, decode_block_instances};
 
 	#[test]
 	fn character_chase_camera_smoke() {
@@ -43,7 +43,7 @@ mod fixtures_day4_chase_camera {
 		};
 
 		let scene_block = blend
-			.find_first_block_by_code([b'S', b'C', 0, 0])
+			.find_first_block_by_code(codes::ID_SCE)
 			.expect("scene block lookup")
 			.expect("scene block exists");
 		let scene_value = decode_block_instances(&dna, &scene_block, &scene_decode).expect("scene decode succeeds");
@@ -80,7 +80,7 @@ mod fixtures_day4_chase_camera {
 		};
 
 		let scene_block = blend
-			.find_first_block_by_code([b'S', b'C', 0, 0])
+			.find_first_block_by_code(codes::ID_SCE)
 			.expect("scene block lookup")
 			.expect("scene block exists");
 		let scene_value = decode_block_instances(&dna, &scene_block, &scene_decode).expect("scene decode succeeds");
@@ -1,8 +1,8 @@
-use std::collections::{HashMap, HashSet};
+use std::collections::HashSet;
 
 use crate::blend::{
-	BlendError, BlendFile, ChaseMeta, DecodeOptions, Dna, FieldPath, PathStep, PointerIndex, Result, StructValue, Value, decode_block_instances,
-	decode_struct_instance,
+	BlendError, BlendFile, Block, ChaseMeta, DecodeCache, DecodeOptions, Dna, FieldPath, PathStep, PointerIndex, Result, StructValue, Value,
+	decode_block_instances, decode_struct_instance,
 };
 
 /// Behavior when a traversal stop condition is encountered.
@@ -29,6 +29,10 @@ pub struct ChasePolicy {
 	pub on_unresolved_ptr: StopMode,
 	/// Action when cycle or visited-limit condition is encountered.
 	pub on_cycle: StopMode,
+	/// Optional shared memo of dereferenced structs, reused across chases.
+	///
+	/// Must only be shared between chases using the same file and [`DecodeOptions`].
+	pub decode_cache: Option<DecodeCache>,
 }
 
 impl Default for ChasePolicy {
@@ -40,6 +44,7 @@ impl Default for ChasePolicy {
 			on_null_ptr: StopMode::Stop,
 			on_unresolved_ptr: StopMode::Stop,
 			on_cycle: StopMode::Error,
+			decode_cache: None,
 		}
 	}
 }
@@ -99,6 +104,15 @@ pub struct ChaseResult {
 	pub stop: Option<ChaseStop>,
 }
 
+/// One concrete match of a fanned-out field path.
+#[derive(Debug, Clone)]
+pub struct ChaseMatch {
+	/// Path with every slice, wildcard, and negative index resolved.
+	pub path: FieldPath,
+	/// Traversal result for this match; `hops` include the shared prefix.
+	pub result: ChaseResult,
+}
+
 /// Start from the first block code match and chase a parsed field path.
 pub fn chase_from_block_code<'a>(
 	file: &'a BlendFile,
@@ -110,10 +124,52 @@ pub fn chase_from_block_code<'a>(
 	policy: &ChasePolicy,
 ) -> Result<ChaseResult> {
 	let block = file.find_first_block_by_code(root_code)?.ok_or(BlendError::BlockNotFound { code: root_code })?;
-	let root = decode_block_instances(dna, &block, decode)?;
+	chase_from_block(dna, index, &block, path, decode, policy)
+}
+
+/// Decode every element of `block` and chase a parsed field path from it.
+///
+/// Pair with [`BlendFile::find_nth_block_by_code`] to start from a later block of a code.
+pub fn chase_from_block<'a>(
+	dna: &Dna,
+	index: &PointerIndex<'a>,
+	block: &Block<'_>,
+	path: &FieldPath,
+	decode: &DecodeOptions,
+	policy: &ChasePolicy,
+) -> Result<ChaseResult> {
+	let root = decode_block_instances(dna, block, decode)?;
 	chase_value(root, dna, index, path, decode, policy)
 }
 
+/// Decode every element of `block` and chase a path that may fan out.
+pub fn chase_all_from_block<'a>(
+	dna: &Dna,
+	index: &PointerIndex<'a>,
+	block: &Block<'_>,
+	path: &FieldPath,
+	decode: &DecodeOptions,
+	policy: &ChasePolicy,
+) -> Result<Vec<ChaseMatch>> {
+	let root = decode_block_instances(dna, block, decode)?;
+	chase_all_value(root, dna, index, &path.steps, decode, policy)
+}
+
+/// Start from a raw pointer and chase a path that may fan out.
+///
+/// Slice and wildcard steps branch into one [`ChaseMatch`] per selected
+/// element or field, in order; a branch that stops early is still reported.
+pub fn chase_all_from_ptr<'a>(
+	dna: &Dna,
+	index: &PointerIndex<'a>,
+	root_ptr: u64,
+	path: &FieldPath,
+	decode: &DecodeOptions,
+	policy: &ChasePolicy,
+) -> Result<Vec<ChaseMatch>> {
+	chase_all_value(Value::Ptr(root_ptr), dna, index, &path.steps, decode, policy)
+}
+
 /// Start from a raw pointer and chase a parsed field path.
 pub fn chase_from_ptr<'a>(
 	dna: &Dna,
@@ -126,6 +182,96 @@ pub fn chase_from_ptr<'a>(
 	chase_value(Value::Ptr(root_ptr), dna, index, path, decode, policy)
 }
 
+fn chase_all_value<'a>(
+	root: Value,
+	dna: &Dna,
+	index: &PointerIndex<'a>,
+	steps: &[PathStep],
+	decode: &DecodeOptions,
+	policy: &ChasePolicy,
+) -> Result<Vec<ChaseMatch>> {
+	let Some(split) = steps.iter().position(|step| step.fans_out() || matches!(step, PathStep::IndexFromEnd(_))) else {
+		let path = FieldPath { steps: steps.to_vec() };
+		let result = chase_value(root, dna, index, &path, decode, policy)?;
+		return Ok(vec![ChaseMatch { path, result }]);
+	};
+
+	let prefix_path = FieldPath {
+		steps: steps[..split].to_vec(),
+	};
+	let prefix = chase_value(root, dna, index, &prefix_path, decode, policy)?;
+	if prefix.stop.is_some() {
+		return Ok(vec![ChaseMatch {
+			path: prefix_path,
+			result: prefix,
+		}]);
+	}
+
+	let children = match expand_step(&steps[split], &prefix.value) {
+		Ok(children) => children,
+		Err(reason) => {
+			return Ok(vec![ChaseMatch {
+				path: prefix_path,
+				result: ChaseResult {
+					stop: Some(ChaseStop { step_index: split, reason }),
+					..prefix
+				},
+			}]);
+		}
+	};
+
+	let mut matches = Vec::new();
+	for child in children {
+		let mut branch = vec![child];
+		branch.extend_from_slice(&steps[split + 1..]);
+		for found in chase_all_value(prefix.value.clone(), dna, index, &branch, decode, policy)? {
+			let mut path = prefix_path.clone();
+			path.steps.extend(found.path.steps);
+			let mut hops = prefix.hops.clone();
+			hops.extend(found.result.hops);
+			let stop = found.result.stop.map(|stop| ChaseStop {
+				step_index: stop.step_index + split,
+				reason: stop.reason,
+			});
+			matches.push(ChaseMatch {
+				path,
+				result: ChaseResult {
+					value: found.result.value,
+					hops,
+					stop,
+				},
+			});
+		}
+	}
+
+	Ok(matches)
+}
+
+/// Resolve a slice, wildcard, or negative index into concrete steps over `value`.
+fn expand_step(step: &PathStep, value: &Value) -> std::result::Result<Vec<PathStep>, ChaseStopReason> {
+	match (step, value) {
+		(PathStep::Wildcard, Value::Struct(item)) => Ok(item.fields.iter().map(|field| PathStep::Field(field.name.to_string())).collect()),
+		(PathStep::Wildcard, Value::Array(items)) => Ok((0..items.len()).map(PathStep::Index).collect()),
+		(PathStep::Wildcard, other) => Err(ChaseStopReason::ExpectedStruct {
+			got: value_kind(other).to_owned(),
+		}),
+		(PathStep::Slice { start, end }, Value::Array(items)) => {
+			let end = end.unwrap_or(items.len()).min(items.len());
+			Ok((*start..end).map(PathStep::Index).collect())
+		}
+		(PathStep::IndexFromEnd(back), Value::Array(items)) => match items.len().checked_sub(*back) {
+			Some(index) => Ok(vec![PathStep::Index(index)]),
+			None => Err(ChaseStopReason::IndexOob {
+				index: *back,
+				len: items.len(),
+			}),
+		},
+		(_, other) => Err(ChaseStopReason::ExpectedArray {
+			got: value_kind(other).to_owned(),
+		}),
+	}
+}
+
 fn chase_value<'a>(
 	mut current: Value,
 	dna: &Dna,
@@ -134,10 +280,13 @@ fn chase_value<'a>(
 	decode: &DecodeOptions,
 	policy: &ChasePolicy,
 ) -> Result<ChaseResult> {
+	if path.fans_out() {
+		return Err(BlendError::FieldPathFansOut { path: path.to_string() });
+	}
+
 	let config = DerefConfig { decode, policy };
 	let mut hops = Vec::new();
 	let mut visited = HashSet::new();
-	let mut decoded_cache: HashMap<u64, StructValue> = HashMap::new();
 
 	for (step_index, step) in path.steps.iter().enumerate() {
 		loop {
@@ -188,7 +337,7 @@ fn chase_value<'a>(
 					current = items[default_index].clone();
 					continue;
 				}
-				(PathStep::Field(_), Value::Ptr(ptr)) => match deref_pointer(dna, index, ptr, &config, &mut hops, &mut visited, &mut decoded_cache)? {
+				(PathStep::Field(_), Value::Ptr(ptr)) => match deref_pointer(dna, index, ptr, &config, &mut hops, &mut visited)? {
 					DerefOutcome::Struct(item) => {
 						current = Value::Struct(item);
 						continue;
@@ -232,7 +381,25 @@ fn chase_value<'a>(
 					current = items[*index_value].clone();
 					break;
 				}
-				(PathStep::Index(_), Value::Ptr(ptr)) => match deref_pointer(dna, index, ptr, &config, &mut hops, &mut visited, &mut decoded_cache)? {
+				(PathStep::IndexFromEnd(back), Value::Array(items)) => {
+					let Some(resolved) = items.len().checked_sub(*back) else {
+						return Ok(ChaseResult {
+							value: current,
+							hops,
+							stop: Some(ChaseStop {
+								step_index,
+								reason: ChaseStopReason::IndexOob {
+									index: *back,
+									len: items.len(),
+								},
+							}),
+						});
+					};
+
+					current = items[resolved].clone();
+					break;
+				}
+				(PathStep::Index(_) | PathStep::IndexFromEnd(_), Value::Ptr(ptr)) => match deref_pointer(dna, index, ptr, &config, &mut hops, &mut visited)? {
 					DerefOutcome::Struct(item) => {
 						current = Value::Struct(item);
 						continue;
@@ -245,7 +412,10 @@ fn chase_value<'a>(
 						});
 					}
 				},
-				(PathStep::Index(_), other) => {
+				(PathStep::Slice { .. } | PathStep::Wildcard, _) => {
+					return Err(BlendError::FieldPathFansOut { path: path.to_string() });
+				}
+				(PathStep::Index(_) | PathStep::IndexFromEnd(_), other) => {
 					return Ok(ChaseResult {
 						value: current,
 						hops,
@@ -262,12 +432,8 @@ fn chase_value<'a>(
 	}
 
 	let final_step = path.steps.len();
-	loop {
-		let Value::Ptr(ptr) = current.clone() else {
-			break;
-		};
-
-		match deref_pointer(dna, index, ptr, &config, &mut hops, &mut visited, &mut decoded_cache)? {
+	while let Value::Ptr(ptr) = current.clone() {
+		match deref_pointer(dna, index, ptr, &config, &mut hops, &mut visited)? {
 			DerefOutcome::Struct(item) => {
 				current = Value::Struct(item);
 			}
@@ -308,7 +474,6 @@ fn deref_pointer<'a>(
 	config: &DerefConfig<'_>,
 	hops: &mut Vec<ChaseMeta>,
 	visited: &mut HashSet<u64>,
-	decoded_cache: &mut HashMap<u64, StructValue>,
 ) -> Result<DerefOutcome> {
 	if hops.len() >= config.policy.max_hops {
 		return Err(BlendError::ChaseHopLimitExceeded {
@@ -361,33 +526,29 @@ fn deref_pointer<'a>(
 	}
 	visited.insert(canonical);
 
-	if let Some(cached) = decoded_cache.get(&canonical) {
-		hops.push(ChaseMeta {
-			ptr,
-			resolved_block_code: typed.base.entry.block.head.code,
-			sdna_nr: typed.base.entry.block.head.sdna_nr,
-			element_index,
-			element_offset: typed.element_offset,
-			struct_size: typed.struct_size,
-			block_old: typed.base.entry.start_old,
-		});
-		return Ok(DerefOutcome::Struct(cached.clone()));
-	}
-
-	let start = offset_bytes;
-	let end = start.checked_add(typed.struct_size).ok_or(BlendError::ChaseSliceOob {
-		start,
-		size: typed.struct_size,
-		payload: typed.base.payload().len(),
-	})?;
-	let bytes = typed.base.payload().get(start..end).ok_or(BlendError::ChaseSliceOob {
-		start,
-		size: typed.struct_size,
-		payload: typed.base.payload().len(),
-	})?;
-
-	let value = decode_struct_instance(dna, typed.base.entry.block.head.sdna_nr, bytes, config.decode)?;
-	decoded_cache.insert(canonical, value.clone());
+	let cache = config.policy.decode_cache.as_ref();
+	let value = match cache.and_then(|cache| cache.get(canonical)) {
+		Some(value) => value,
+		None => {
+			let start = offset_bytes;
+			let end = start.checked_add(typed.struct_size).ok_or(BlendError::ChaseSliceOob {
+				start,
+				size: typed.struct_size,
+				payload: typed.base.payload().len(),
+			})?;
+			let bytes = typed.base.payload().get(start..end).ok_or(BlendError::ChaseSliceOob {
+				start,
+				size: typed.struct_size,
+				payload: typed.base.payload().len(),
+			})?;
+
+			let value = decode_struct_instance(dna, typed.base.entry.block.head.sdna_nr, bytes, config.decode)?;
+			if let Some(cache) = cache {
+				cache.insert(canonical, value.clone());
+			}
+			value
+		}
+	};
 	hops.push(ChaseMeta {
 		ptr,
 		resolved_block_code: typed.base.entry.block.head.code,
@@ -2,7 +2,7 @@ mod fixtures_day4_chase_path {
 
 	use blendoc_testkit::fixture_path;
 
-	use crate::blend::{BlendFile, ChasePolicy, ChaseStopReason, DecodeOptions, FieldPath, Value, chase_from_block_code};
+	use crate::blend::{BlendFile, ChasePolicy, ChaseStopReason, DecodeOptions, FieldPath, Value, chase_from_block_code, codes};
 
 	#[test]
 	fn character_world_path_chase() {
@@ -34,7 +34,7 @@ mod fixtures_day4_chase_path {
 		decode.strict_layout = true;
 
 		let path = FieldPath::parse("world").expect("path parses");
-		let result = chase_from_block_code(&blend, &dna, &index, [b'S', b'C', 0, 0], &path, &decode, &ChasePolicy::default()).expect("chase succeeds");
+		let result = chase_from_block_code(&blend, &dna, &index, codes::ID_SCE, &path, &decode, &ChasePolicy::default()).expect("chase succeeds");
 
 		assert!(result.stop.is_none(), "expected world path to resolve");
 		let Value::Struct(item) = result.value else {
@@ -53,7 +53,7 @@ mod fixtures_day4_chase_path {
 		decode.strict_layout = true;
 
 		let path = FieldPath::parse("view_layers.first").expect("path parses");
-		let result = chase_from_block_code(&blend, &dna, &index, [b'S', b'C', 0, 0], &path, &decode, &ChasePolicy::default()).expect("chase succeeds");
+		let result = chase_from_block_code(&blend, &dna, &index, codes::ID_SCE, &path, &decode, &ChasePolicy::default()).expect("chase succeeds");
 
 		if let Some(stop) = result.stop {
 			match stop.reason {
@@ -76,7 +76,7 @@ mod fixtures_day6_chase_ids {
 
 	use blendoc_testkit::fixture_path;
 
-	use crate::blend::{BlendFile, ChaseMeta, ChasePolicy, DecodeOptions, FieldPath, Value, chase_from_ptr, scan_id_blocks};
+	use crate::blend::{BlendFile, ChaseMeta, ChasePolicy, DecodeOptions, FieldPath, Value, chase_from_ptr, codes, scan_id_blocks};
 
 	#[test]
 	fn character_scene_world_chase_has_id_annotations() {
@@ -94,7 +94,7 @@ mod fixtures_day6_chase_ids {
 		let index = blend.pointer_index().expect("pointer index builds");
 		let ids = scan_id_blocks(&blend, &dna).expect("id scan succeeds");
 
-		let scene = ids.iter().find(|item| item.code == [b'S', b'C', 0, 0]).expect("scene ID record exists");
+		let scene = ids.iter().find(|item| item.code == codes::ID_SCE).expect("scene ID record exists");
 
 		let id_by_ptr: HashMap<u64, &str> = ids.iter().map(|item| (item.old_ptr, item.id_name.as_ref())).collect();
 
@@ -175,18 +175,18 @@ mod unit_chase_cycle {
 			},
 		]);
 
-		let dna = Dna {
-			endianness: crate::blend::Endianness::Little,
-			pointer_size: 8,
-			names: vec!["*next".into()],
-			types: vec!["Node".into()],
-			tlen: vec![8],
-			structs: vec![DnaStruct {
+		let dna = Dna::from_tables(
+			crate::blend::Endianness::Little,
+			8,
+			vec!["*next".into()],
+			vec!["Node".into()],
+			vec![8],
+			vec![DnaStruct {
 				type_idx: 0,
 				fields: vec![DnaField { type_idx: 0, name_idx: 0 }],
 			}],
-			struct_for_type: vec![Some(0)],
-		};
+		)
+		.expect("valid dna tables");
 
 		let path = FieldPath::parse("next.next.next").expect("path parses");
 		let policy = ChasePolicy {
@@ -201,3 +201,72 @@ mod unit_chase_cycle {
 		assert_eq!(result.hops.len(), 2);
 	}
 }
+
+mod synthetic_fan_out {
+	use blendoc_testkit::builder::{HeaderKind, SCENE_ID_NAMES, SCENE_OBJECT_LOC, scene_file};
+
+	use crate::blend::{
+		BlendError, BlendFile, ChasePolicy, ChaseStopReason, DecodeOptions, FieldPath, IdIndex, PathStep, Value, chase_all_from_ptr, chase_from_ptr,
+		scan_id_blocks,
+	};
+
+	#[test]
+	fn path_grammar_round_trips() {
+		let path = FieldPath::parse("mat[-1].*[2..4][..3][1..][*]").expect("path parses");
+		assert_eq!(
+			path.steps,
+			vec![
+				PathStep::Field("mat".to_owned()),
+				PathStep::IndexFromEnd(1),
+				PathStep::Wildcard,
+				PathStep::Slice { start: 2, end: Some(4) },
+				PathStep::Slice { start: 0, end: Some(3) },
+				PathStep::Slice { start: 1, end: None },
+				PathStep::Wildcard,
+			]
+		);
+		assert_eq!(path.to_string(), "mat[-1].*[2..4][0..3][1..].*");
+		assert!(path.fans_out());
+		assert!(!FieldPath::parse("loc[-2]").expect("path parses").fans_out());
+
+		for bad in ["a[-0]", "a[1..x]", "a[", "a.", "a[-]", "*x"] {
+			assert!(FieldPath::parse(bad).is_err(), "{bad} should be rejected");
+		}
+	}
+
+	#[test]
+	fn negative_index_slice_and_wildcard_resolve_concrete_paths() {
+		let blend = BlendFile::from_bytes(scene_file(HeaderKind::Legacy8, false)).expect("synthetic file parses");
+		let dna = blend.dna().expect("dna parses");
+		let index = blend.pointer_index().expect("pointer index builds");
+		let ids = IdIndex::build(scan_id_blocks(&blend, &dna).expect("id scan succeeds"));
+		let object = ids.resolve_selector(SCENE_ID_NAMES[1]).expect("object id").old_ptr;
+		let scene = ids.resolve_selector(SCENE_ID_NAMES[0]).expect("scene id").old_ptr;
+		let decode = DecodeOptions::default();
+		let policy = ChasePolicy::default();
+
+		let last = chase_from_ptr(&dna, &index, object, &FieldPath::parse("loc[-1]").expect("path"), &decode, &policy).expect("chase");
+		assert!(matches!(last.value, Value::F32(value) if value == SCENE_OBJECT_LOC[2]));
+
+		let slice = chase_all_from_ptr(&dna, &index, object, &FieldPath::parse("loc[1..]").expect("path"), &decode, &policy).expect("chase");
+		let paths: Vec<String> = slice.iter().map(|found| found.path.to_string()).collect();
+		assert_eq!(paths, ["loc[1]", "loc[2]"]);
+
+		let fields = chase_all_from_ptr(&dna, &index, scene, &FieldPath::parse("*.id.name").expect("path"), &decode, &policy).expect("chase");
+		let paths: Vec<String> = fields.iter().map(|found| found.path.to_string()).collect();
+		assert_eq!(paths, ["id.id.name", "camera.id.name", "world.id.name"]);
+		assert!(fields[0].result.stop.is_some(), "ID has no nested id field");
+		let names: Vec<String> = fields[1..].iter().map(|found| format!("{:?}", found.result.value)).collect();
+		assert!(names[0].contains(SCENE_ID_NAMES[1]) && names[1].contains(SCENE_ID_NAMES[3]));
+		assert_eq!(fields[1].result.hops.len(), 2, "scene root plus camera deref");
+
+		let oob = chase_all_from_ptr(&dna, &index, object, &FieldPath::parse("loc[-4]").expect("path"), &decode, &policy).expect("chase");
+		assert_eq!(oob.len(), 1);
+		let stop = oob[0].result.stop.as_ref().expect("out of range stops");
+		assert_eq!(stop.step_index, 1);
+		assert!(matches!(stop.reason, ChaseStopReason::IndexOob { index: 4, len: 3 }));
+
+		let err = chase_from_ptr(&dna, &index, object, &FieldPath::parse("loc[*]").expect("path"), &decode, &policy).expect_err("single chase rejects fan-out");
+		assert!(matches!(err, BlendError::FieldPathFansOut { .. }));
+	}
+}
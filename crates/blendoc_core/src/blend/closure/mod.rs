@@ -0,0 +1,127 @@
+use std::collections::{HashMap, VecDeque};
+use std::sync::Arc;
+
+use crate::blend::IdGraphResult;
+
+/// Edge direction followed by a closure walk.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ClosureDirection {
+	/// Follow outgoing edges: what the root depends on.
+	Dependencies,
+	/// Follow incoming edges: what depends on the root.
+	Dependents,
+}
+
+impl ClosureDirection {
+	/// Stable lowercase label.
+	pub fn as_str(self) -> &'static str {
+		match self {
+			Self::Dependencies => "dependencies",
+			Self::Dependents => "dependents",
+		}
+	}
+}
+
+/// One ID reached from the closure root.
+#[derive(Debug, Clone)]
+pub struct ClosureEntry {
+	/// Reached ID canonical pointer.
+	pub canonical: u64,
+	/// Shortest hop count from the root.
+	pub depth: u32,
+	/// Neighbor through which the ID was first reached.
+	pub via: u64,
+	/// Field of the edge that first reached the ID.
+	pub field: Arc<str>,
+	/// Edges reaching this ID from the root or other closure members.
+	pub edge_count: usize,
+}
+
+/// Transitive closure of one ID over an ID graph.
+#[derive(Debug, Clone)]
+pub struct ClosureResult {
+	/// Closure root canonical pointer.
+	pub root: u64,
+	/// Walk direction.
+	pub direction: ClosureDirection,
+	/// Reached IDs ordered by depth, then pointer; excludes the root.
+	pub entries: Vec<ClosureEntry>,
+	/// Whether `max_depth` cut the walk short.
+	pub depth_limited: bool,
+}
+
+impl ClosureResult {
+	/// Deepest hop count reached.
+	pub fn max_depth(&self) -> u32 {
+		self.entries.iter().map(|entry| entry.depth).max().unwrap_or(0)
+	}
+}
+
+/// Transitive set of IDs the root points at, directly or indirectly.
+pub fn dependency_closure(graph: &IdGraphResult, root: u64, max_depth: Option<u32>) -> ClosureResult {
+	closure(graph, root, ClosureDirection::Dependencies, max_depth)
+}
+
+/// Transitive set of IDs that point at the root, directly or indirectly.
+pub fn dependents_closure(graph: &IdGraphResult, root: u64, max_depth: Option<u32>) -> ClosureResult {
+	closure(graph, root, ClosureDirection::Dependents, max_depth)
+}
+
+fn closure(graph: &IdGraphResult, root: u64, direction: ClosureDirection, max_depth: Option<u32>) -> ClosureResult {
+	let mut adjacency: HashMap<u64, Vec<(u64, &Arc<str>)>> = HashMap::new();
+	for edge in &graph.edges {
+		let (from, to) = match direction {
+			ClosureDirection::Dependencies => (edge.from, edge.to),
+			ClosureDirection::Dependents => (edge.to, edge.from),
+		};
+		adjacency.entry(from).or_default().push((to, &edge.field));
+	}
+
+	let mut reached: HashMap<u64, ClosureEntry> = HashMap::new();
+	let mut queue = VecDeque::from([(root, 0_u32)]);
+	let mut depth_limited = false;
+
+	while let Some((current, depth)) = queue.pop_front() {
+		let Some(neighbors) = adjacency.get(&current) else {
+			continue;
+		};
+		if max_depth.is_some_and(|max| depth >= max) {
+			depth_limited |= neighbors.iter().any(|(next, _)| *next != root && !reached.contains_key(next));
+			continue;
+		}
+
+		for (next, field) in neighbors {
+			if *next == root {
+				continue;
+			}
+			if let Some(entry) = reached.get_mut(next) {
+				entry.edge_count += 1;
+				continue;
+			}
+			reached.insert(
+				*next,
+				ClosureEntry {
+					canonical: *next,
+					depth: depth + 1,
+					via: current,
+					field: Arc::clone(field),
+					edge_count: 1,
+				},
+			);
+			queue.push_back((*next, depth + 1));
+		}
+	}
+
+	let mut entries: Vec<ClosureEntry> = reached.into_values().collect();
+	entries.sort_by_key(|entry| (entry.depth, entry.canonical));
+
+	ClosureResult {
+		root,
+		direction,
+		entries,
+		depth_limited,
+	}
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,63 @@
+mod synthetic_closure {
+	use crate::blend::{ClosureDirection, IdGraphEdge, IdGraphResult, dependency_closure, dependents_closure};
+
+	fn edge(from: u64, to: u64, field: &str) -> IdGraphEdge {
+		IdGraphEdge { from, to, field: field.into() }
+	}
+
+	/// Scene(1) -> Object(2) -> Mesh(3) -> Material(4); Object(5) -> Mesh(3); Object(2) -> Material(4).
+	fn graph() -> IdGraphResult {
+		IdGraphResult {
+			nodes: Vec::new(),
+			edges: vec![
+				edge(1, 2, "master_collection"),
+				edge(2, 3, "data"),
+				edge(2, 4, "mat[0]"),
+				edge(3, 4, "mat[0]"),
+				edge(5, 3, "data"),
+			],
+			truncated: None,
+			warnings: Vec::new(),
+			cycles: Vec::new(),
+		}
+	}
+
+	fn summary(result: &crate::blend::ClosureResult) -> Vec<(u64, u32, u64, usize)> {
+		result
+			.entries
+			.iter()
+			.map(|entry| (entry.canonical, entry.depth, entry.via, entry.edge_count))
+			.collect()
+	}
+
+	#[test]
+	fn dependency_closure_records_depth_and_edge_counts() {
+		let result = dependency_closure(&graph(), 1, None);
+
+		assert_eq!(result.direction, ClosureDirection::Dependencies);
+		assert_eq!(summary(&result), vec![(2, 1, 1, 1), (3, 2, 2, 1), (4, 2, 2, 2)]);
+		assert_eq!(result.entries[0].field.as_ref(), "master_collection");
+		assert_eq!(result.max_depth(), 2);
+		assert!(!result.depth_limited);
+	}
+
+	#[test]
+	fn dependents_closure_walks_incoming_edges() {
+		let result = dependents_closure(&graph(), 4, None);
+
+		assert_eq!(summary(&result), vec![(2, 1, 4, 2), (3, 1, 4, 1), (1, 2, 2, 1), (5, 2, 3, 1)]);
+	}
+
+	#[test]
+	fn max_depth_truncates_and_cycles_terminate() {
+		let limited = dependency_closure(&graph(), 1, Some(1));
+		assert_eq!(summary(&limited), vec![(2, 1, 1, 1)]);
+		assert!(limited.depth_limited);
+
+		let mut cyclic = graph();
+		cyclic.edges.push(edge(4, 1, "parent"));
+		let result = dependency_closure(&cyclic, 1, None);
+		assert_eq!(result.entries.len(), 3);
+		assert!(result.entries.iter().all(|entry| entry.canonical != 1));
+	}
+}
@@ -0,0 +1,241 @@
+//! Named block codes and the table describing them.
+//!
+//! Constants follow Blender's own names (`ID_OB`, `ID_SCE`, ...). [`CODES`]
+//! is sorted by code bytes, so listings never depend on locale collation.
+
+use crate::blend::{BlendError, Result};
+
+/// `DNA1`: the SDNA schema block.
+pub const DNA1: [u8; 4] = *b"DNA1";
+/// `ENDB`: the terminating block.
+pub const ENDB: [u8; 4] = *b"ENDB";
+/// `GLOB`: the `FileGlobal` block.
+pub const GLOB: [u8; 4] = *b"GLOB";
+/// `TEST`: the embedded thumbnail.
+pub const TEST: [u8; 4] = *b"TEST";
+/// `REND`: render-result metadata for file browsers.
+pub const REND: [u8; 4] = *b"REND";
+/// `USER`: user preferences, only in `userpref.blend`.
+pub const USER: [u8; 4] = *b"USER";
+/// `DATA`: data owned by the preceding ID block.
+pub const DATA: [u8; 4] = *b"DATA";
+
+/// Action.
+pub const ID_AC: [u8; 4] = *b"AC\0\0";
+/// Armature.
+pub const ID_AR: [u8; 4] = *b"AR\0\0";
+/// Brush.
+pub const ID_BR: [u8; 4] = *b"BR\0\0";
+/// Camera.
+pub const ID_CA: [u8; 4] = *b"CA\0\0";
+/// Cache file.
+pub const ID_CF: [u8; 4] = *b"CF\0\0";
+/// Legacy curve, surface, or text object data.
+pub const ID_CU_LEGACY: [u8; 4] = *b"CU\0\0";
+/// Curves (hair) object data.
+pub const ID_CV: [u8; 4] = *b"CV\0\0";
+/// Legacy grease pencil.
+pub const ID_GD_LEGACY: [u8; 4] = *b"GD\0\0";
+/// Grease pencil.
+pub const ID_GP: [u8; 4] = *b"GP\0\0";
+/// Collection (group in older files).
+pub const ID_GR: [u8; 4] = *b"GR\0\0";
+/// Image.
+pub const ID_IM: [u8; 4] = *b"IM\0\0";
+/// Legacy IPO curves.
+pub const ID_IP: [u8; 4] = *b"IP\0\0";
+/// Shape key.
+pub const ID_KE: [u8; 4] = *b"KE\0\0";
+/// Light (lamp in older files).
+pub const ID_LA: [u8; 4] = *b"LA\0\0";
+/// Library.
+pub const ID_LI: [u8; 4] = *b"LI\0\0";
+/// Light probe.
+pub const ID_LP: [u8; 4] = *b"LP\0\0";
+/// Freestyle line style.
+pub const ID_LS: [u8; 4] = *b"LS\0\0";
+/// Lattice.
+pub const ID_LT: [u8; 4] = *b"LT\0\0";
+/// Material.
+pub const ID_MA: [u8; 4] = *b"MA\0\0";
+/// Metaball.
+pub const ID_MB: [u8; 4] = *b"MB\0\0";
+/// Movie clip.
+pub const ID_MC: [u8; 4] = *b"MC\0\0";
+/// Mesh.
+pub const ID_ME: [u8; 4] = *b"ME\0\0";
+/// Mask.
+pub const ID_MSK: [u8; 4] = *b"MS\0\0";
+/// Node tree.
+pub const ID_NT: [u8; 4] = *b"NT\0\0";
+/// Object.
+pub const ID_OB: [u8; 4] = *b"OB\0\0";
+/// Particle settings.
+pub const ID_PA: [u8; 4] = *b"PA\0\0";
+/// Paint curve.
+pub const ID_PC: [u8; 4] = *b"PC\0\0";
+/// Palette.
+pub const ID_PAL: [u8; 4] = *b"PL\0\0";
+/// Point cloud.
+pub const ID_PT: [u8; 4] = *b"PT\0\0";
+/// Scene.
+pub const ID_SCE: [u8; 4] = *b"SC\0\0";
+/// Legacy screen, from files before 2.80.
+pub const ID_SCRN: [u8; 4] = *b"SN\0\0";
+/// Sound.
+pub const ID_SO: [u8; 4] = *b"SO\0\0";
+/// Speaker.
+pub const ID_SPK: [u8; 4] = *b"SP\0\0";
+/// Screen.
+pub const ID_SCR: [u8; 4] = *b"SR\0\0";
+/// Texture.
+pub const ID_TE: [u8; 4] = *b"TE\0\0";
+/// Text.
+pub const ID_TXT: [u8; 4] = *b"TX\0\0";
+/// Vector font.
+pub const ID_VF: [u8; 4] = *b"VF\0\0";
+/// Volume.
+pub const ID_VO: [u8; 4] = *b"VO\0\0";
+/// Window manager.
+pub const ID_WM: [u8; 4] = *b"WM\0\0";
+/// World.
+pub const ID_WO: [u8; 4] = *b"WO\0\0";
+/// Workspace.
+pub const ID_WS: [u8; 4] = *b"WS\0\0";
+
+/// What a block code marks.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum CodeKind {
+	/// An ID-root block; the payload starts with an `ID` struct.
+	Id,
+	/// Data owned by the preceding ID block.
+	Data,
+	/// A file-level SDNA struct outside any ID (`GLOB`, `USER`).
+	Global,
+	/// A payload that is not an SDNA struct array (`DNA1`, `TEST`, `REND`, `ENDB`).
+	Raw,
+}
+
+impl CodeKind {
+	/// Stable lowercase label.
+	pub fn as_str(self) -> &'static str {
+		match self {
+			Self::Id => "id",
+			Self::Data => "data",
+			Self::Global => "global",
+			Self::Raw => "raw",
+		}
+	}
+}
+
+/// One row of [`CODES`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CodeInfo {
+	/// Block code bytes, NUL-padded.
+	pub code: [u8; 4],
+	/// Name of the constant in this module.
+	pub constant: &'static str,
+	/// What the code marks.
+	pub kind: CodeKind,
+	/// What the block holds, e.g. `Object`.
+	pub description: &'static str,
+}
+
+const fn row(code: [u8; 4], constant: &'static str, kind: CodeKind, description: &'static str) -> CodeInfo {
+	CodeInfo {
+		code,
+		constant,
+		kind,
+		description,
+	}
+}
+
+/// Every block code blendoc knows, sorted by code bytes.
+pub const CODES: &[CodeInfo] = &[
+	row(ID_AC, "ID_AC", CodeKind::Id, "Action"),
+	row(ID_AR, "ID_AR", CodeKind::Id, "Armature"),
+	row(ID_BR, "ID_BR", CodeKind::Id, "Brush"),
+	row(ID_CA, "ID_CA", CodeKind::Id, "Camera"),
+	row(ID_CF, "ID_CF", CodeKind::Id, "Cache file"),
+	row(ID_CU_LEGACY, "ID_CU_LEGACY", CodeKind::Id, "Curve (legacy)"),
+	row(ID_CV, "ID_CV", CodeKind::Id, "Curves"),
+	row(DATA, "DATA", CodeKind::Data, "ID-owned data"),
+	row(DNA1, "DNA1", CodeKind::Raw, "SDNA schema"),
+	row(ENDB, "ENDB", CodeKind::Raw, "End of file"),
+	row(ID_GD_LEGACY, "ID_GD_LEGACY", CodeKind::Id, "Grease pencil (legacy)"),
+	row(GLOB, "GLOB", CodeKind::Global, "File globals"),
+	row(ID_GP, "ID_GP", CodeKind::Id, "Grease pencil"),
+	row(ID_GR, "ID_GR", CodeKind::Id, "Collection"),
+	row(ID_IM, "ID_IM", CodeKind::Id, "Image"),
+	row(ID_IP, "ID_IP", CodeKind::Id, "IPO curves (legacy)"),
+	row(ID_KE, "ID_KE", CodeKind::Id, "Shape key"),
+	row(ID_LA, "ID_LA", CodeKind::Id, "Light"),
+	row(ID_LI, "ID_LI", CodeKind::Id, "Library"),
+	row(ID_LP, "ID_LP", CodeKind::Id, "Light probe"),
+	row(ID_LS, "ID_LS", CodeKind::Id, "Line style"),
+	row(ID_LT, "ID_LT", CodeKind::Id, "Lattice"),
+	row(ID_MA, "ID_MA", CodeKind::Id, "Material"),
+	row(ID_MB, "ID_MB", CodeKind::Id, "Metaball"),
+	row(ID_MC, "ID_MC", CodeKind::Id, "Movie clip"),
+	row(ID_ME, "ID_ME", CodeKind::Id, "Mesh"),
+	row(ID_MSK, "ID_MSK", CodeKind::Id, "Mask"),
+	row(ID_NT, "ID_NT", CodeKind::Id, "Node tree"),
+	row(ID_OB, "ID_OB", CodeKind::Id, "Object"),
+	row(ID_PA, "ID_PA", CodeKind::Id, "Particle settings"),
+	row(ID_PC, "ID_PC", CodeKind::Id, "Paint curve"),
+	row(ID_PAL, "ID_PAL", CodeKind::Id, "Palette"),
+	row(ID_PT, "ID_PT", CodeKind::Id, "Point cloud"),
+	row(REND, "REND", CodeKind::Raw, "Render info"),
+	row(ID_SCE, "ID_SCE", CodeKind::Id, "Scene"),
+	row(ID_SCRN, "ID_SCRN", CodeKind::Id, "Screen (legacy)"),
+	row(ID_SO, "ID_SO", CodeKind::Id, "Sound"),
+	row(ID_SPK, "ID_SPK", CodeKind::Id, "Speaker"),
+	row(ID_SCR, "ID_SCR", CodeKind::Id, "Screen"),
+	row(ID_TE, "ID_TE", CodeKind::Id, "Texture"),
+	row(TEST, "TEST", CodeKind::Raw, "Thumbnail"),
+	row(ID_TXT, "ID_TXT", CodeKind::Id, "Text"),
+	row(USER, "USER", CodeKind::Global, "User preferences"),
+	row(ID_VF, "ID_VF", CodeKind::Id, "Vector font"),
+	row(ID_VO, "ID_VO", CodeKind::Id, "Volume"),
+	row(ID_WM, "ID_WM", CodeKind::Id, "Window manager"),
+	row(ID_WO, "ID_WO", CodeKind::Id, "World"),
+	row(ID_WS, "ID_WS", CodeKind::Id, "Workspace"),
+];
+
+/// Table row for `code`, if blendoc knows it.
+pub fn lookup(code: [u8; 4]) -> Option<&'static CodeInfo> {
+	CODES.binary_search_by(|info| info.code.cmp(&code)).ok().map(|idx| &CODES[idx])
+}
+
+/// Whether `code` is a known ID-root code.
+pub fn is_id_code(code: [u8; 4]) -> bool {
+	lookup(code).is_some_and(|info| info.kind == CodeKind::Id)
+}
+
+/// Whether `code` marks a payload that is not an SDNA struct array.
+pub fn is_raw_code(code: [u8; 4]) -> bool {
+	lookup(code).is_some_and(|info| info.kind == CodeKind::Raw)
+}
+
+/// Printable label: NUL padding dropped, other non-printable bytes as `.`, `....` when empty.
+pub fn code_to_str(code: [u8; 4]) -> String {
+	let label: String = code
+		.iter()
+		.filter(|byte| **byte != 0)
+		.map(|byte| if byte.is_ascii_graphic() || *byte == b' ' { char::from(*byte) } else { '.' })
+		.collect();
+	if label.is_empty() { "....".to_owned() } else { label }
+}
+
+/// Parse an up-to-4-character ASCII code into NUL-padded bytes.
+pub fn str_to_code(text: &str) -> Result<[u8; 4]> {
+	if text.is_empty() || text.len() > 4 || !text.is_ascii() {
+		return Err(BlendError::InvalidBlockCode { code: text.to_owned() });
+	}
+	let mut out = [0_u8; 4];
+	out[..text.len()].copy_from_slice(text.as_bytes());
+	Ok(out)
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,40 @@
+use crate::blend::codes::{self, CODES, CodeKind};
+
+#[test]
+fn table_is_sorted_by_code_bytes_without_duplicates() {
+	assert!(CODES.windows(2).all(|pair| pair[0].code < pair[1].code));
+	for info in CODES {
+		assert_eq!(codes::lookup(info.code), Some(info));
+	}
+	assert!(codes::lookup(*b"ZZ\0\0").is_none());
+}
+
+#[test]
+fn classifies_codes() {
+	assert!(codes::is_id_code(codes::ID_OB));
+	assert!(codes::is_id_code(*b"LI\0\0"));
+	assert!(!codes::is_id_code(codes::DATA));
+	assert!(!codes::is_id_code(*b"ZZ\0\0"));
+	assert!(codes::is_raw_code(codes::DNA1) && codes::is_raw_code(codes::ENDB));
+	assert!(!codes::is_raw_code(codes::GLOB));
+	assert_eq!(
+		codes::lookup(codes::ID_SCE).map(|info| (info.kind, info.description)),
+		Some((CodeKind::Id, "Scene"))
+	);
+}
+
+#[test]
+fn converts_between_text_and_bytes() {
+	assert_eq!(codes::str_to_code("OB").expect("valid code"), codes::ID_OB);
+	assert_eq!(codes::str_to_code("DNA1").expect("valid code"), codes::DNA1);
+	assert!(codes::str_to_code("").is_err());
+	assert!(codes::str_to_code("TOOLONG").is_err());
+	assert!(codes::str_to_code("Ö").is_err());
+
+	assert_eq!(codes::code_to_str(codes::ID_OB), "OB");
+	assert_eq!(codes::code_to_str(*b"A\x01\0\0"), "A.");
+	assert_eq!(codes::code_to_str([0; 4]), "....");
+	for info in CODES {
+		assert_eq!(codes::str_to_code(&codes::code_to_str(info.code)).expect("round trip"), info.code);
+	}
+}
@@ -1,11 +1,25 @@
-use std::io::Read;
+use std::io::{Read, Write};
 
+use crate::blend::trace::trace_span;
 use crate::blend::{BlendError, Result};
 
-const BLEND_MAGIC: &[u8] = b"BLENDER";
+pub(crate) const BLEND_MAGIC: &[u8] = b"BLENDER";
 const MAX_DECOMPRESSED_BYTES: usize = 512 * 1024 * 1024;
 /// zstd frame magic used by compressed `.blend` files.
 pub const ZSTD_MAGIC: [u8; 4] = [0x28, 0xB5, 0x2F, 0xFD];
+/// gzip member magic used by pre-3.0 compressed `.blend` files.
+pub const GZIP_MAGIC: [u8; 2] = [0x1F, 0x8B];
+/// Default zstd level used when re-emitting compressed files.
+pub const DEFAULT_ZSTD_LEVEL: i32 = 3;
+/// Uncompressed bytes per zstd frame in emitted files.
+#[cfg(feature = "native")]
+const ZSTD_FRAME_BYTES: usize = 256 * 1024;
+/// Skippable-frame magic carrying the zstd seekable-format seek table.
+#[cfg(feature = "native")]
+const SEEK_TABLE_FRAME_MAGIC: u32 = 0x184D_2A5E;
+/// Magic closing the seek table footer.
+#[cfg(feature = "native")]
+const ZSTD_SEEKABLE_MAGIC: u32 = 0x8F92_EAB1;
 
 /// Compression mode detected for a source file.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -14,6 +28,35 @@ pub enum Compression {
 	None,
 	/// zstd-compressed stream.
 	Zstd,
+	/// gzip-compressed stream (Blender 2.x and earlier 3.x saves).
+	Gzip,
+}
+
+/// Compression backends compiled into this build.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CodecSupport {
+	/// gzip backend; gzip is always available.
+	pub gzip: &'static str,
+	/// zstd decoder backend (`libzstd` or `ruzstd`), if any.
+	pub zstd_decode: Option<&'static str>,
+	/// zstd encoder backend, if any.
+	pub zstd_encode: Option<&'static str>,
+}
+
+/// Report which codec backends the enabled cargo features provide.
+pub fn codec_support() -> CodecSupport {
+	let zstd_decode = if cfg!(feature = "native") {
+		Some("libzstd")
+	} else if cfg!(feature = "pure-zstd") {
+		Some("ruzstd")
+	} else {
+		None
+	};
+	CodecSupport {
+		gzip: "flate2",
+		zstd_decode,
+		zstd_encode: cfg!(feature = "native").then_some("libzstd"),
+	}
 }
 
 impl Compression {
@@ -22,26 +65,163 @@ impl Compression {
 		match self {
 			Self::None => "none",
 			Self::Zstd => "zstd",
+			Self::Gzip => "gzip",
 		}
 	}
 }
 
 /// Detect and decode compression, returning `(mode, decoded_bytes)`.
 pub fn decode_bytes(raw: Vec<u8>) -> Result<(Compression, Vec<u8>)> {
+	trace_span!("decode_bytes", bytes = raw.len());
 	if raw.starts_with(BLEND_MAGIC) {
 		return Ok((Compression::None, raw));
 	}
 
 	if raw.starts_with(&ZSTD_MAGIC) {
-		let out = decode_zstd(&raw)?;
-		return Ok((Compression::Zstd, out));
+		return Ok((Compression::Zstd, decode_zstd(&raw)?));
+	}
+
+	if raw.starts_with(&GZIP_MAGIC) {
+		let decoder = flate2::read::MultiGzDecoder::new(raw.as_slice());
+		return Ok((Compression::Gzip, read_limited(decoder)?));
 	}
 
 	Err(BlendError::UnknownMagic { magic: first4(&raw) })
 }
 
+/// Re-emit decoded `.blend` bytes with the requested compression.
+///
+/// `level` applies to zstd (1-22) and gzip (0-9); `None` picks each codec's
+/// default and an out-of-range level is rejected. zstd output uses the
+/// seekable format Blender reads: independent 256 KiB frames followed by a
+/// seek table in a skippable frame. The block stream is written unchanged,
+/// so decoding the output yields `bytes` again.
+pub fn encode_bytes(bytes: &[u8], compression: Compression, level: Option<i32>) -> Result<Vec<u8>> {
+	let range = match compression {
+		Compression::None => None,
+		Compression::Zstd => Some((1, 22)),
+		Compression::Gzip => Some((0, 9)),
+	};
+	if let (Some(level), Some((min, max))) = (level, range)
+		&& !(min..=max).contains(&level)
+	{
+		return Err(BlendError::InvalidCompressionLevel {
+			codec: compression.as_str(),
+			level,
+			min,
+			max,
+		});
+	}
+
+	match compression {
+		Compression::None => Ok(bytes.to_vec()),
+		Compression::Zstd => encode_zstd(bytes, level.unwrap_or(DEFAULT_ZSTD_LEVEL)),
+		Compression::Gzip => {
+			let level = level.map_or(flate2::Compression::default(), |value| flate2::Compression::new(value as u32));
+			let mut encoder = flate2::write::GzEncoder::new(Vec::new(), level);
+			encoder.write_all(bytes)?;
+			Ok(encoder.finish()?)
+		}
+	}
+}
+
+#[cfg(feature = "native")]
+fn decode_zstd(raw: &[u8]) -> Result<Vec<u8>> {
+	read_limited(zstd::stream::read::Decoder::new(raw)?)
+}
+
+#[cfg(all(not(feature = "native"), feature = "pure-zstd"))]
 fn decode_zstd(raw: &[u8]) -> Result<Vec<u8>> {
-	let mut decoder = zstd::stream::read::Decoder::new(raw)?;
+	decode_zstd_pure(raw)
+}
+
+/// Decode every zstd frame with the pure-Rust decoder, skipping skippable
+/// frames such as a seek table. Used where libzstd cannot be linked (wasm32).
+#[cfg(feature = "pure-zstd")]
+#[cfg_attr(all(feature = "native", not(test)), allow(dead_code))]
+pub(crate) fn decode_zstd_pure(mut raw: &[u8]) -> Result<Vec<u8>> {
+	use ruzstd::decoding::errors::{FrameDecoderError, ReadFrameHeaderError};
+	use ruzstd::decoding::{BlockDecodingStrategy, FrameDecoder};
+
+	let zstd_error = |err: FrameDecoderError| BlendError::Zstd { message: err.to_string() };
+	let mut decoder = FrameDecoder::new();
+	let mut out = Vec::new();
+	while !raw.is_empty() {
+		match decoder.reset(&mut raw) {
+			Ok(()) => {}
+			Err(FrameDecoderError::ReadFrameHeaderError(ReadFrameHeaderError::SkipFrame { length, .. })) => {
+				raw = raw.get(length as usize..).ok_or(BlendError::Zstd {
+					message: "skippable frame runs past end of input".to_owned(),
+				})?;
+				continue;
+			}
+			Err(err) => return Err(zstd_error(err)),
+		}
+
+		loop {
+			decoder
+				.decode_blocks(&mut raw, BlockDecodingStrategy::UptoBytes(1024 * 1024))
+				.map_err(zstd_error)?;
+			decoder.collect_to_writer(&mut out)?;
+			if out.len() > MAX_DECOMPRESSED_BYTES {
+				return Err(BlendError::DecompressedTooLarge { limit: MAX_DECOMPRESSED_BYTES });
+			}
+			if decoder.is_finished() {
+				break;
+			}
+		}
+		decoder.collect_to_writer(&mut out)?;
+	}
+
+	if !out.starts_with(BLEND_MAGIC) {
+		return Err(BlendError::NotBlendAfterDecompress);
+	}
+
+	Ok(out)
+}
+
+#[cfg(not(any(feature = "native", feature = "pure-zstd")))]
+fn decode_zstd(_raw: &[u8]) -> Result<Vec<u8>> {
+	Err(BlendError::CodecUnavailable {
+		operation: "zstd decoding",
+		feature: "pure-zstd",
+	})
+}
+
+/// Seekable-format zstd: one frame per [`ZSTD_FRAME_BYTES`] of input, then
+/// the seek table (`compressed, decompressed` sizes per frame, frame count,
+/// descriptor without checksums, and [`ZSTD_SEEKABLE_MAGIC`]).
+#[cfg(feature = "native")]
+fn encode_zstd(bytes: &[u8], level: i32) -> Result<Vec<u8>> {
+	let mut out = Vec::new();
+	let mut frames = Vec::new();
+	for chunk in bytes.chunks(ZSTD_FRAME_BYTES) {
+		let frame = zstd::bulk::compress(chunk, level)?;
+		frames.push((frame.len() as u32, chunk.len() as u32));
+		out.extend_from_slice(&frame);
+	}
+
+	out.extend_from_slice(&SEEK_TABLE_FRAME_MAGIC.to_le_bytes());
+	out.extend_from_slice(&(frames.len() as u32 * 8 + 9).to_le_bytes());
+	for (compressed, decompressed) in &frames {
+		out.extend_from_slice(&compressed.to_le_bytes());
+		out.extend_from_slice(&decompressed.to_le_bytes());
+	}
+	out.extend_from_slice(&(frames.len() as u32).to_le_bytes());
+	out.push(0);
+	out.extend_from_slice(&ZSTD_SEEKABLE_MAGIC.to_le_bytes());
+	Ok(out)
+}
+
+#[cfg(not(feature = "native"))]
+fn encode_zstd(_bytes: &[u8], _level: i32) -> Result<Vec<u8>> {
+	Err(BlendError::CodecUnavailable {
+		operation: "zstd encoding",
+		feature: "native",
+	})
+}
+
+fn read_limited(mut decoder: impl Read) -> Result<Vec<u8>> {
 	let mut out = Vec::new();
 	let mut buf = [0_u8; 8192];
 
@@ -0,0 +1,283 @@
+use std::collections::{BTreeMap, HashMap};
+use std::sync::Arc;
+
+use crate::blend::bytes::Cursor;
+use crate::blend::decl::parse_field_decl;
+use crate::blend::{BlendFile, Block, Dna, PointerIndex, PointerStorage, Result};
+
+/// Maximum embedded-struct nesting followed when collecting pointer fields.
+const MAX_NESTING: u32 = 16;
+
+/// How one non-null stored pointer relates to the file's blocks.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum PointerClass {
+	/// Lands inside a block with room for its pointee.
+	Resolved,
+	/// Lands in no block.
+	Unresolved,
+	/// Lands inside a block, but the pointed-to struct runs past the block's end.
+	OutOfBounds,
+}
+
+impl PointerClass {
+	/// Stable snake-case label.
+	pub fn as_str(self) -> &'static str {
+		match self {
+			Self::Resolved => "resolved",
+			Self::Unresolved => "unresolved",
+			Self::OutOfBounds => "out_of_bounds",
+		}
+	}
+}
+
+/// Non-null pointer counts by [`PointerClass`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct PointerCounts {
+	/// Pointers that resolve cleanly.
+	pub resolved: usize,
+	/// Pointers that resolve to no block.
+	pub unresolved: usize,
+	/// Pointers whose pointee overruns the block they land in.
+	pub out_of_bounds: usize,
+}
+
+impl PointerCounts {
+	/// Count one pointer of class `class`.
+	pub fn add(&mut self, class: PointerClass) {
+		match class {
+			PointerClass::Resolved => self.resolved += 1,
+			PointerClass::Unresolved => self.unresolved += 1,
+			PointerClass::OutOfBounds => self.out_of_bounds += 1,
+		}
+	}
+
+	/// All counted pointers.
+	pub fn total(&self) -> usize {
+		self.resolved + self.unresolved + self.out_of_bounds
+	}
+
+	/// Pointers that are unresolved or out of bounds.
+	pub fn dangling(&self) -> usize {
+		self.unresolved + self.out_of_bounds
+	}
+}
+
+/// Pointer counts for one typed block.
+#[derive(Debug, Clone)]
+pub struct BlockPointerStats {
+	/// Offset of the block header in the decoded file bytes.
+	pub block_offset: usize,
+	/// Block code.
+	pub code: [u8; 4],
+	/// Stored old address of the block.
+	pub old_ptr: u64,
+	/// SDNA struct name of the block elements.
+	pub type_name: Arc<str>,
+	/// Element count from the block header.
+	pub nr: u64,
+	/// Non-null pointers read from the block.
+	pub counts: PointerCounts,
+}
+
+/// Pointer counts for one field path of one struct type, across all blocks.
+#[derive(Debug, Clone)]
+pub struct FieldPointerStats {
+	/// SDNA struct name owning the field.
+	pub type_name: Arc<str>,
+	/// Dotted field path through embedded structs, e.g. `id.next`.
+	pub field: Arc<str>,
+	/// Non-null pointers read from the field.
+	pub counts: PointerCounts,
+	/// First unresolved or out-of-bounds value seen, for locating it.
+	pub first_dangling: Option<u64>,
+}
+
+/// Whole-file pointer integrity map from [`scan_dangling_pointers`].
+#[derive(Debug, Clone)]
+pub struct DanglingReport {
+	/// Pointer-ID storage mode the classification ran under.
+	pub storage: PointerStorage,
+	/// Typed blocks whose elements were read.
+	pub blocks_scanned: usize,
+	/// Counts over every scanned pointer.
+	pub totals: PointerCounts,
+	/// Per-block counts, in file order; blocks without pointer fields are omitted.
+	pub blocks: Vec<BlockPointerStats>,
+	/// Per-field counts, sorted by type then field.
+	pub fields: Vec<FieldPointerStats>,
+}
+
+/// One pointer slot in a struct, through embedded structs.
+#[derive(Debug, Clone)]
+pub(crate) struct PointerSlot {
+	/// Byte offset from the start of the outer struct.
+	pub offset: usize,
+	/// Dotted field path.
+	pub field: Arc<str>,
+	/// Size of the pointed-to struct; 0 when the pointee is not a struct.
+	pub target_size: usize,
+	/// Whether the field is a pointer to pointers (`Material **mat`), whose
+	/// target is a raw block holding a pointer array.
+	pub to_pointer_array: bool,
+}
+
+/// Classify every non-null pointer field of every typed block in `file`.
+///
+/// Unlike ref scans, which start from ID roots, this walks each block whose
+/// SDNA index names a struct, so pointers in unreachable data are counted
+/// too. Blocks whose payload is shorter than `nr` elements are read as far as
+/// whole elements go. In stable-ID files a pointer resolves only on an exact
+/// block address, so nothing is reported out of bounds for interior hits.
+pub fn scan_dangling_pointers(file: &BlendFile, dna: &Dna) -> Result<DanglingReport> {
+	let index = PointerIndex::build(file)?;
+	let mut slots: HashMap<u32, Vec<PointerSlot>> = HashMap::new();
+	let mut fields: BTreeMap<(Arc<str>, Arc<str>), FieldPointerStats> = BTreeMap::new();
+	let mut report = DanglingReport {
+		storage: index.storage(),
+		blocks_scanned: 0,
+		totals: PointerCounts::default(),
+		blocks: Vec::new(),
+		fields: Vec::new(),
+	};
+
+	for block in file.blocks() {
+		let block = block?;
+		let sdna_nr = block.head.sdna_nr;
+		// SDNA 0 marks raw data, which has no field layout to read.
+		if sdna_nr == 0 {
+			continue;
+		}
+		let Some(item) = dna.struct_by_sdna(sdna_nr) else {
+			continue;
+		};
+		let struct_size = usize::from(dna.tlen[item.type_idx as usize]);
+		if struct_size == 0 {
+			continue;
+		}
+		report.blocks_scanned += 1;
+
+		let block_slots = slots.entry(sdna_nr).or_insert_with(|| pointer_slots(dna, sdna_nr));
+		if block_slots.is_empty() {
+			continue;
+		}
+		let type_name: Arc<str> = Arc::from(dna.type_name(item.type_idx));
+
+		let mut counts = PointerCounts::default();
+		for_each_block_pointer(dna, &block, struct_size, block_slots, |_, slot, ptr| {
+			let class = classify(&index, ptr, slot.target_size);
+			counts.add(class);
+
+			let entry = fields.entry((type_name.clone(), slot.field.clone())).or_insert_with(|| FieldPointerStats {
+				type_name: type_name.clone(),
+				field: slot.field.clone(),
+				counts: PointerCounts::default(),
+				first_dangling: None,
+			});
+			entry.counts.add(class);
+			if class != PointerClass::Resolved && entry.first_dangling.is_none() {
+				entry.first_dangling = Some(ptr);
+			}
+		});
+
+		report.totals.resolved += counts.resolved;
+		report.totals.unresolved += counts.unresolved;
+		report.totals.out_of_bounds += counts.out_of_bounds;
+		report.blocks.push(BlockPointerStats {
+			block_offset: block.file_offset,
+			code: block.head.code,
+			old_ptr: block.head.old,
+			type_name,
+			nr: block.head.nr,
+			counts,
+		});
+	}
+
+	report.fields = fields.into_values().collect();
+	Ok(report)
+}
+
+/// Call `visit` with the element index, slot, and value of every non-null
+/// pointer in the whole `struct_size` elements of `block`.
+pub(crate) fn for_each_block_pointer(dna: &Dna, block: &Block<'_>, struct_size: usize, slots: &[PointerSlot], mut visit: impl FnMut(usize, &PointerSlot, u64)) {
+	for (element_index, element) in block.payload.chunks_exact(struct_size).take(block.head.nr as usize).enumerate() {
+		for slot in slots {
+			let Some(bytes) = element.get(slot.offset..) else {
+				continue;
+			};
+			let Ok(ptr) = Cursor::new(bytes).read_ptr(dna.pointer_size, dna.endianness) else {
+				continue;
+			};
+			if ptr != 0 {
+				visit(element_index, slot, ptr);
+			}
+		}
+	}
+}
+
+/// Classify one non-null pointer whose pointee struct is `target_size` bytes.
+fn classify(index: &PointerIndex<'_>, ptr: u64, target_size: usize) -> PointerClass {
+	let Some(resolved) = index.resolve(ptr) else {
+		return PointerClass::Unresolved;
+	};
+	let end = resolved.byte_offset.saturating_add(target_size);
+	if end > resolved.entry.block.payload.len() {
+		PointerClass::OutOfBounds
+	} else {
+		PointerClass::Resolved
+	}
+}
+
+/// Pointer slots of one struct, through embedded structs.
+pub(crate) fn pointer_slots(dna: &Dna, sdna_nr: u32) -> Vec<PointerSlot> {
+	let mut out = Vec::new();
+	collect_pointer_slots(dna, sdna_nr, 0, "", MAX_NESTING, &mut out);
+	out
+}
+
+fn collect_pointer_slots(dna: &Dna, sdna_nr: u32, base: usize, prefix: &str, depth_left: u32, out: &mut Vec<PointerSlot>) {
+	let Some(layout) = dna.struct_layout(sdna_nr) else {
+		return;
+	};
+	for field in layout {
+		let name = if prefix.is_empty() {
+			field.name.to_string()
+		} else {
+			format!("{prefix}.{}", field.name)
+		};
+		if field.is_ptr {
+			let field_name: Arc<str> = Arc::from(name);
+			let target_size = pointee_size(dna, &field.decl, &field.type_name);
+			let decl = parse_field_decl(&field.decl);
+			let to_pointer_array = decl.ptr_depth == 2 && !decl.is_func_ptr;
+			for idx in 0..field.count {
+				out.push(PointerSlot {
+					offset: base + field.offset + idx * field.element_size,
+					field: field_name.clone(),
+					target_size,
+					to_pointer_array,
+				});
+			}
+		} else if let Some(nested) = field.struct_sdna
+			&& depth_left > 0
+		{
+			for idx in 0..field.count {
+				collect_pointer_slots(dna, nested, base + field.offset + idx * field.element_size, &name, depth_left - 1, out);
+			}
+		}
+	}
+}
+
+/// Size of the struct a single-level pointer field points at, or 0.
+fn pointee_size(dna: &Dna, decl: &str, type_name: &str) -> usize {
+	let decl = parse_field_decl(decl);
+	if decl.ptr_depth != 1 || decl.is_func_ptr {
+		return 0;
+	}
+	dna.type_for_name
+		.get(type_name)
+		.filter(|type_idx| dna.struct_by_type_idx(**type_idx).is_some())
+		.map_or(0, |type_idx| usize::from(dna.tlen[usize::from(*type_idx)]))
+}
+
+#[cfg(test)]
+mod tests;
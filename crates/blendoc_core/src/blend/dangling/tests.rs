@@ -0,0 +1,67 @@
+use blendoc_testkit::builder::{BlendBuilder, HeaderKind, scene_file};
+
+use crate::blend::{BlendFile, PointerCounts, scan_dangling_pointers};
+
+/// `OBCube` with one resolved, one out-of-bounds, and one unresolved pointer field.
+fn mixed_pointers_file() -> Vec<u8> {
+	let mut builder = BlendBuilder::new(HeaderKind::Legacy8, 404, false);
+	let sdna = builder.sdna_mut();
+	sdna.add_type("char", 1);
+	sdna.add_type("void", 0);
+	sdna.add_struct("ID", &[("void", "*next"), ("void", "*prev"), ("void", "*lib"), ("char", "name[24]")]);
+	let object = sdna.add_struct("Object", &[("ID", "id"), ("Object", "*parent"), ("Object", "*track"), ("void", "*data")]);
+
+	let mut payload = builder.payload();
+	payload
+		.ptr(0x1000)
+		.ptr(0)
+		.ptr(0)
+		.name("OBCube", 24)
+		.ptr(0x1000)
+		.ptr(0x1000 + 60)
+		.ptr(0xdead_0000);
+	let cube = payload.finish();
+	builder.block(*b"OB\0\0", object, 0x1000, 1, cube).block(*b"DATA", 0, 0x2000, 1, vec![0; 16]);
+	builder.build()
+}
+
+#[test]
+fn classifies_each_pointer_field() {
+	let blend = BlendFile::from_bytes(mixed_pointers_file()).expect("synthetic file parses");
+	let dna = blend.dna().expect("dna parses");
+	let report = scan_dangling_pointers(&blend, &dna).expect("scan succeeds");
+
+	assert_eq!(
+		report.totals,
+		PointerCounts {
+			resolved: 2,
+			unresolved: 1,
+			out_of_bounds: 1,
+		}
+	);
+	assert_eq!(report.blocks.len(), 1);
+	assert_eq!(report.blocks[0].old_ptr, 0x1000);
+	assert_eq!(report.blocks[0].counts.dangling(), 2);
+
+	let field = |name: &str| report.fields.iter().find(|item| item.field.as_ref() == name).expect("field counted");
+	assert_eq!(field("id.next").counts.resolved, 1);
+	assert_eq!(field("parent").counts.resolved, 1);
+	assert_eq!(field("track").counts.out_of_bounds, 1);
+	assert_eq!(field("track").first_dangling, Some(0x1000 + 60));
+	assert_eq!(field("data").counts.unresolved, 1);
+	assert_eq!(field("data").first_dangling, Some(0xdead_0000));
+	assert!(report.fields.iter().all(|item| item.type_name.as_ref() == "Object"));
+}
+
+#[test]
+fn scene_files_have_no_dangling_pointers() {
+	for kind in [HeaderKind::Legacy4, HeaderKind::Legacy8, HeaderKind::Large] {
+		for big_endian in [false, true] {
+			let blend = BlendFile::from_bytes(scene_file(kind, big_endian)).expect("synthetic file parses");
+			let dna = blend.dna().expect("dna parses");
+			let report = scan_dangling_pointers(&blend, &dna).expect("scan succeeds");
+			assert_eq!(report.totals.dangling(), 0, "{kind:?} be={big_endian}: {report:?}");
+			assert!(report.totals.resolved > 0, "{kind:?} be={big_endian}");
+		}
+	}
+}
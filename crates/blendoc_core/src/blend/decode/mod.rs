@@ -1,7 +1,8 @@
 use crate::blend::bytes::Cursor;
 use crate::blend::decl::{FieldDecl, parse_field_decl};
+use crate::blend::dna::is_padding_field;
 use crate::blend::value::{FieldValue, StructValue, Value};
-use crate::blend::{BlendError, Block, Dna, Endianness, PointerIndex, Result};
+use crate::blend::{BlendError, Block, DecodePlugins, Dna, Endianness, PointerIndex, Result, Warning, WarningCollector, WarningKind};
 
 /// Runtime limits and behavior switches for SDNA decoding.
 #[derive(Debug, Clone)]
@@ -16,6 +17,10 @@ pub struct DecodeOptions {
 	pub decode_char_arrays_as_string: bool,
 	/// Error when decoded bytes do not consume full struct layout.
 	pub strict_layout: bool,
+	/// Optional sink for best-effort decode choices (lossy strings, leftover bytes).
+	pub warnings: Option<WarningCollector>,
+	/// Per-type decoders applied to matching struct instances.
+	pub plugins: Option<DecodePlugins>,
 }
 
 impl Default for DecodeOptions {
@@ -26,6 +31,8 @@ impl Default for DecodeOptions {
 			include_padding: false,
 			decode_char_arrays_as_string: true,
 			strict_layout: false,
+			warnings: None,
+			plugins: None,
 		}
 	}
 }
@@ -39,6 +46,8 @@ impl DecodeOptions {
 			include_padding: false,
 			decode_char_arrays_as_string: true,
 			strict_layout: false,
+			warnings: None,
+			plugins: None,
 		}
 	}
 }
@@ -149,13 +158,24 @@ fn decode_struct_impl(dna: &Dna, sdna_nr: u32, bytes: &[u8], opt: &DecodeOptions
 		if opt.strict_layout {
 			return Err(BlendError::DecodeLayoutMismatch { type_name, leftover });
 		}
+		if let Some(warnings) = &opt.warnings {
+			warnings.push(Warning::new(
+				WarningKind::LayoutLeftover,
+				None,
+				format!("{type_name}: {leftover} trailing bytes not covered by SDNA fields"),
+			));
+		}
 		let _ = cursor.read_exact(leftover)?;
 	}
 
-	Ok(StructValue {
+	let value = StructValue {
 		type_name: type_name.into_boxed_str(),
 		fields,
-	})
+	};
+	match opt.plugins.as_ref().and_then(|plugins| plugins.get(&value.type_name)) {
+		Some(decoder) => decoder.decode(value, bytes, dna),
+		None => Ok(value),
+	}
 }
 
 fn decode_field_value(
@@ -204,7 +224,11 @@ fn decode_field_value(
 	if opt.decode_char_arrays_as_string && type_name == "char" && element_count > 1 {
 		let bytes = cursor.read_exact(element_count)?;
 		let end = bytes.iter().position(|byte| *byte == 0).unwrap_or(bytes.len());
-		return Ok(Value::String(String::from_utf8_lossy(&bytes[..end]).into_owned().into_boxed_str()));
+		let text = String::from_utf8_lossy(&bytes[..end]);
+		if let (std::borrow::Cow::Owned(_), Some(warnings)) = (&text, &opt.warnings) {
+			warnings.push(Warning::new(WarningKind::LossyString, None, format!("{}: invalid UTF-8 replaced", decl.ident)));
+		}
+		return Ok(Value::String(text.into_owned().into_boxed_str()));
 	}
 
 	decode_primitive_values(cursor, type_name, usize::from(dna.tlen[field_type_idx as usize]), element_count, dna.endianness)
@@ -327,9 +351,5 @@ fn skip_field_storage(cursor: &mut Cursor<'_>, dna: &Dna, type_name: &str, field
 	Ok(())
 }
 
-fn is_padding_field(ident: &str, type_name: &str, inline_array: usize) -> bool {
-	(ident.starts_with("_pad") || ident.starts_with("pad")) && inline_array > 0 && matches!(type_name, "char" | "uchar" | "uint8_t")
-}
-
 #[cfg(test)]
 mod tests;
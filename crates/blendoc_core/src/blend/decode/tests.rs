@@ -52,7 +52,7 @@ mod fixtures_day3_scene {
 
 	use blendoc_testkit::fixture_path;
 
-	use crate::blend::{BlendFile, DecodeOptions, Value, decode_block_instances};
+	use crate::blend::{BlendFile, DecodeOptions, Value, codes, decode_block_instances};
 
 	#[test]
 	fn character_scene_decode_smoke() {
@@ -68,7 +68,7 @@ mod fixtures_day3_scene {
 		let blend = BlendFile::open(fixture_path(name)).expect("fixture opens");
 		let dna = blend.dna().expect("dna parses");
 		let block = blend
-			.find_first_block_by_code([b'S', b'C', 0, 0])
+			.find_first_block_by_code(codes::ID_SCE)
 			.expect("block iteration succeeds")
 			.expect("SC block exists");
 		let value = decode_block_instances(&dna, &block, &DecodeOptions::for_scene_inspect()).expect("decode succeeds");
@@ -139,18 +139,18 @@ mod unit_big_endian_decode {
 
 	#[test]
 	fn decodes_big_endian_numeric_and_pointer_fields() {
-		let dna = Dna {
-			endianness: Endianness::Big,
-			pointer_size: 8,
-			names: vec!["value".into(), "*next".into()],
-			types: vec!["uint".into(), "Node".into()],
-			tlen: vec![4, 12],
-			structs: vec![DnaStruct {
+		let dna = Dna::from_tables(
+			Endianness::Big,
+			8,
+			vec!["value".into(), "*next".into()],
+			vec!["uint".into(), "Node".into()],
+			vec![4, 12],
+			vec![DnaStruct {
 				type_idx: 1,
 				fields: vec![DnaField { type_idx: 0, name_idx: 0 }, DnaField { type_idx: 1, name_idx: 1 }],
 			}],
-			struct_for_type: vec![None, Some(0)],
-		};
+		)
+		.expect("valid dna tables");
 
 		let mut payload = Vec::new();
 		payload.extend_from_slice(&0x0102_0304_u32.to_be_bytes());
@@ -180,3 +180,47 @@ mod unit_big_endian_decode {
 		assert!(matches!(next.value, Value::Ptr(0x1122_3344_5566_7788)));
 	}
 }
+
+mod synthetic_decode_warnings {
+	use crate::blend::{BHead, Block, DecodeOptions, Dna, DnaField, DnaStruct, Endianness, WarningCollector, WarningKind, decode_block_instances};
+
+	#[test]
+	fn collector_records_lossy_strings_and_leftover_bytes() {
+		let dna = Dna::from_tables(
+			Endianness::Little,
+			8,
+			vec!["name[4]".into()],
+			vec!["char".into(), "Label".into()],
+			vec![1, 6],
+			vec![DnaStruct {
+				type_idx: 1,
+				fields: vec![DnaField { type_idx: 0, name_idx: 0 }],
+			}],
+		)
+		.expect("valid dna tables");
+
+		let payload = [b'a', 0xff, b'b', 0, 0, 0];
+		let block = Block {
+			head: BHead {
+				code: *b"DATA",
+				sdna_nr: 0,
+				old: 0x1000,
+				len: payload.len() as u64,
+				nr: 1,
+			},
+			payload: &payload,
+			file_offset: 0,
+		};
+
+		let warnings = WarningCollector::new();
+		let options = DecodeOptions {
+			warnings: Some(warnings.clone()),
+			..DecodeOptions::default()
+		};
+		decode_block_instances(&dna, &block, &options).expect("decode succeeds");
+
+		let kinds: Vec<_> = warnings.take().iter().map(|item| item.kind).collect();
+		assert_eq!(kinds, vec![WarningKind::LossyString, WarningKind::LayoutLeftover]);
+		assert!(warnings.is_empty());
+	}
+}
@@ -0,0 +1,167 @@
+use std::collections::{BTreeMap, HashMap};
+use std::sync::{Arc, Mutex, MutexGuard};
+
+use crate::blend::{DecodeOptions, Dna, PointerIndex, Result, StructValue, decode_ptr_instance};
+
+/// Entry budget of [`DecodeCache::default`].
+const DEFAULT_CAPACITY: usize = 4096;
+
+/// Shared least-recently-used memo of decoded struct instances keyed by canonical pointer.
+///
+/// Clones share one map, so a cache can be stored in [`ChasePolicy`] and
+/// reused across chases and printing. Ref scans and walks read pointer
+/// fields straight from block bytes without decoding structs, so they do not
+/// use it; repeated scans share a [`RefCache`] instead. Entries are keyed by
+/// pointer alone: a cache is only valid for the file and [`DecodeOptions`]
+/// it was filled from.
+///
+/// [`ChasePolicy`]: crate::blend::ChasePolicy
+/// [`RefCache`]: crate::blend::RefCache
+#[derive(Debug, Clone)]
+pub struct DecodeCache {
+	inner: Arc<Mutex<DecodeCacheState>>,
+}
+
+#[derive(Debug, Default)]
+struct DecodeCacheState {
+	capacity: usize,
+	/// Canonical pointer to value and its last-use stamp.
+	entries: HashMap<u64, (StructValue, u64)>,
+	/// Last-use stamp to canonical pointer; the first entry is evicted next.
+	recency: BTreeMap<u64, u64>,
+	clock: u64,
+	hits: usize,
+	misses: usize,
+	evictions: usize,
+}
+
+impl Default for DecodeCache {
+	fn default() -> Self {
+		Self::with_capacity(DEFAULT_CAPACITY)
+	}
+}
+
+impl DecodeCache {
+	/// Create an empty cache holding up to 4096 structs.
+	pub fn new() -> Self {
+		Self::default()
+	}
+
+	/// Create an empty cache holding up to `capacity` structs; `0` caches nothing.
+	pub fn with_capacity(capacity: usize) -> Self {
+		Self {
+			inner: Arc::new(Mutex::new(DecodeCacheState {
+				capacity,
+				..DecodeCacheState::default()
+			})),
+		}
+	}
+
+	/// Decode the struct `ptr` points into, reusing an earlier decode of the same element.
+	///
+	/// Returns the canonical pointer with the value, like [`decode_ptr_instance`].
+	/// Failed decodes are not cached.
+	pub fn decode_ptr<'a>(&self, dna: &Dna, index: &PointerIndex<'a>, ptr: u64, options: &DecodeOptions) -> Result<(u64, StructValue)> {
+		let canonical = index.canonicalize_ptr(dna, ptr)?;
+		let value = self.get_or_insert_with(canonical, || Ok(decode_ptr_instance(dna, index, canonical, options)?.1))?;
+		Ok((canonical, value))
+	}
+
+	/// Cached value for `canonical`, or the result of `decode` inserted under it.
+	///
+	/// `decode` runs without the lock held; errors are returned uncached.
+	pub fn get_or_insert_with(&self, canonical: u64, decode: impl FnOnce() -> Result<StructValue>) -> Result<StructValue> {
+		if let Some(value) = self.get(canonical) {
+			return Ok(value);
+		}
+		let value = decode()?;
+		self.insert(canonical, value.clone());
+		Ok(value)
+	}
+
+	/// Cached value for `canonical`, marking it most recently used.
+	pub fn get(&self, canonical: u64) -> Option<StructValue> {
+		let mut state = self.lock();
+		let state = &mut *state;
+		let stamp = state.clock;
+		let Some((value, used)) = state.entries.get_mut(&canonical) else {
+			state.misses += 1;
+			return None;
+		};
+		state.recency.remove(used);
+		state.recency.insert(stamp, canonical);
+		*used = stamp;
+		let value = value.clone();
+		state.clock += 1;
+		state.hits += 1;
+		Some(value)
+	}
+
+	/// Store `value` under `canonical`, evicting the least recently used entry when full.
+	pub fn insert(&self, canonical: u64, value: StructValue) {
+		let mut state = self.lock();
+		if state.capacity == 0 {
+			return;
+		}
+		let stamp = state.clock;
+		state.clock += 1;
+		if let Some((_, used)) = state.entries.insert(canonical, (value, stamp)) {
+			state.recency.remove(&used);
+		}
+		state.recency.insert(stamp, canonical);
+		while state.entries.len() > state.capacity {
+			let Some((_, oldest)) = state.recency.pop_first() else {
+				break;
+			};
+			state.entries.remove(&oldest);
+			state.evictions += 1;
+		}
+	}
+
+	/// Return the maximum number of cached structs.
+	pub fn capacity(&self) -> usize {
+		self.lock().capacity
+	}
+
+	/// Return number of cached structs.
+	pub fn len(&self) -> usize {
+		self.lock().entries.len()
+	}
+
+	/// Return whether no structs are cached.
+	pub fn is_empty(&self) -> bool {
+		self.lock().entries.is_empty()
+	}
+
+	/// Return number of lookups served from the cache.
+	pub fn hits(&self) -> usize {
+		self.lock().hits
+	}
+
+	/// Return number of lookups that found nothing cached.
+	pub fn misses(&self) -> usize {
+		self.lock().misses
+	}
+
+	/// Return number of entries dropped to stay within capacity.
+	pub fn evictions(&self) -> usize {
+		self.lock().evictions
+	}
+
+	/// Drop all cached structs and reset counters, keeping the capacity.
+	pub fn clear(&self) {
+		let mut state = self.lock();
+		let capacity = state.capacity;
+		*state = DecodeCacheState {
+			capacity,
+			..DecodeCacheState::default()
+		};
+	}
+
+	fn lock(&self) -> MutexGuard<'_, DecodeCacheState> {
+		self.inner.lock().unwrap_or_else(|poisoned| poisoned.into_inner())
+	}
+}
+
+#[cfg(test)]
+mod tests;
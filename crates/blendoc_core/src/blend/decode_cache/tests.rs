@@ -0,0 +1,60 @@
+use blendoc_testkit::builder::{HeaderKind, scene_file, scene_ptrs};
+
+use crate::blend::{BlendFile, ChasePolicy, DecodeCache, DecodeOptions, FieldPath, Value, chase_from_ptr, decode_ptr_instance};
+
+#[test]
+fn repeated_decodes_hit_and_least_recent_entry_is_evicted() {
+	let blend = BlendFile::from_bytes(scene_file(HeaderKind::Legacy8, false)).expect("synthetic file opens");
+	let dna = blend.dna().expect("dna parses");
+	let index = blend.pointer_index().expect("pointer index builds");
+	let [scene, object, mesh, _, world] = scene_ptrs(HeaderKind::Legacy8);
+	let options = DecodeOptions::default();
+
+	let cache = DecodeCache::with_capacity(2);
+	let (canonical, first) = cache.decode_ptr(&dna, &index, scene, &options).expect("scene decodes");
+	assert_eq!(canonical, scene);
+	let (_, second) = cache.decode_ptr(&dna, &index, scene, &options).expect("scene decodes again");
+	let direct = decode_ptr_instance(&dna, &index, scene, &options).expect("direct decode").1;
+	assert_eq!(format!("{first:?}"), format!("{second:?}"));
+	assert_eq!(format!("{first:?}"), format!("{direct:?}"));
+	assert_eq!((cache.len(), cache.hits(), cache.misses()), (1, 1, 1));
+
+	cache.decode_ptr(&dna, &index, object, &options).expect("object decodes");
+	cache.decode_ptr(&dna, &index, scene, &options).expect("scene is refreshed");
+	cache.decode_ptr(&dna, &index, world, &options).expect("world decodes");
+	assert_eq!(cache.len(), 2);
+	assert_eq!(cache.evictions(), 1);
+	assert!(cache.get(scene).is_some(), "recently used scene survives");
+	assert!(cache.get(object).is_none(), "least recently used object was evicted");
+
+	assert!(cache.decode_ptr(&dna, &index, mesh + 0x8000, &options).is_err());
+	cache.clear();
+	assert!(cache.is_empty());
+	assert_eq!((cache.capacity(), cache.hits()), (2, 0));
+
+	let disabled = DecodeCache::with_capacity(0);
+	disabled.decode_ptr(&dna, &index, scene, &options).expect("scene decodes");
+	assert!(disabled.is_empty());
+}
+
+#[test]
+fn chases_sharing_a_cache_decode_each_hop_once() {
+	let blend = BlendFile::from_bytes(scene_file(HeaderKind::Legacy8, false)).expect("synthetic file opens");
+	let dna = blend.dna().expect("dna parses");
+	let index = blend.pointer_index().expect("pointer index builds");
+	let [scene, ..] = scene_ptrs(HeaderKind::Legacy8);
+	let path = FieldPath::parse("camera.data.totvert").expect("path parses");
+	let cache = DecodeCache::new();
+	let policy = ChasePolicy {
+		decode_cache: Some(cache.clone()),
+		..ChasePolicy::default()
+	};
+
+	for round in 0..2 {
+		let result = chase_from_ptr(&dna, &index, scene, &path, &DecodeOptions::default(), &policy).expect("chase succeeds");
+		assert!(matches!(result.value, Value::I64(4)), "round {round}: {:?}", result.value);
+		assert_eq!(result.hops.len(), 3);
+	}
+	assert_eq!(cache.len(), 3, "scene, object, and mesh are cached");
+	assert_eq!((cache.hits(), cache.misses()), (3, 3));
+}
@@ -0,0 +1,389 @@
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use crate::blend::{BlendFile, DecodeOptions, Dna, IdRecord, PointerIndex, Result, StructValue, Value, codes, decode_struct_instance, scan_id_blocks};
+
+/// How one ID differs between the left and right file.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum IdChange {
+	/// ID exists only in the right file.
+	Added,
+	/// ID exists only in the left file.
+	Removed,
+	/// ID exists in both files with at least one differing field.
+	Changed,
+	/// ID exists in both files but its root struct did not decode on one side.
+	DecodeFailed,
+}
+
+impl IdChange {
+	/// Return a stable lowercase label.
+	pub fn as_str(self) -> &'static str {
+		match self {
+			Self::Added => "added",
+			Self::Removed => "removed",
+			Self::Changed => "changed",
+			Self::DecodeFailed => "decode_failed",
+		}
+	}
+}
+
+/// Options controlling ID-level file comparison.
+#[derive(Debug, Clone)]
+pub struct IdDiffOptions {
+	/// Report per-field differences for changed IDs.
+	pub fields: bool,
+	/// Maximum field differences kept per changed ID.
+	pub max_field_diffs: usize,
+	/// Decode options used for ID-root structs.
+	pub decode: DecodeOptions,
+	/// Compare and count only IDs whose root struct has this type name.
+	pub type_name: Option<String>,
+}
+
+impl Default for IdDiffOptions {
+	fn default() -> Self {
+		Self {
+			fields: false,
+			max_field_diffs: 64,
+			decode: DecodeOptions::default(),
+			type_name: None,
+		}
+	}
+}
+
+/// One differing leaf field between matched IDs.
+#[derive(Debug, Clone)]
+pub struct FieldDiff {
+	/// Dotted field path from the ID-root struct.
+	pub path: Arc<str>,
+	/// Left value, absent when the field only exists on the right.
+	pub left: Option<Value>,
+	/// Right value, absent when the field only exists on the left.
+	pub right: Option<Value>,
+}
+
+/// One added, removed, or changed ID.
+#[derive(Debug, Clone)]
+pub struct IdDiffEntry {
+	/// Kind of difference.
+	pub change: IdChange,
+	/// ID-root struct type name.
+	pub type_name: Box<str>,
+	/// `ID.name` used for matching.
+	pub id_name: Box<str>,
+	/// ID block pointer in the left file.
+	pub left_ptr: Option<u64>,
+	/// ID block pointer in the right file.
+	pub right_ptr: Option<u64>,
+	/// Differing leaf fields (only when [`IdDiffOptions::fields`] is set).
+	pub fields: Vec<FieldDiff>,
+	/// Whether field differences were cut at `max_field_diffs`.
+	pub fields_truncated: bool,
+}
+
+/// What a pointer field refers to, compared in place of session-specific addresses.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PtrTarget<'a> {
+	/// Null pointer.
+	Null,
+	/// Pointer into an ID block, by `ID.name` and byte offset inside the block.
+	Id {
+		/// `ID.name` of the target block.
+		name: &'a str,
+		/// Byte offset of the pointer inside the ID block.
+		offset: usize,
+	},
+	/// Pointer into a `DATA` block owned by an ID, by the owner's `ID.name`,
+	/// the block's position in the owner's run, and byte offset inside it.
+	Data {
+		/// `ID.name` of the ID owning the block.
+		owner: &'a str,
+		/// Zero-based position of the block among the `DATA` blocks after the ID.
+		block: usize,
+		/// Byte offset of the pointer inside the block.
+		offset: usize,
+	},
+	/// Pointer resolving to no block or to a block no ID owns, by stored address.
+	Address(u64),
+}
+
+/// Pointer resolution for the left and right struct in [`diff_struct_fields`].
+#[derive(Clone, Copy)]
+pub struct PtrTargets<'r> {
+	/// Resolves pointers read from the left struct.
+	pub left: &'r dyn Fn(u64) -> PtrTarget<'r>,
+	/// Resolves pointers read from the right struct.
+	pub right: &'r dyn Fn(u64) -> PtrTarget<'r>,
+}
+
+/// ID-level comparison output.
+#[derive(Debug, Clone)]
+pub struct IdDiffResult {
+	/// Added, removed, and changed IDs ordered by type then name.
+	pub entries: Vec<IdDiffEntry>,
+	/// Number of matched IDs with identical field values.
+	pub unchanged: usize,
+	/// ID count in the left file, of [`IdDiffOptions::type_name`] when set.
+	pub left_ids: usize,
+	/// ID count in the right file, of [`IdDiffOptions::type_name`] when set.
+	pub right_ids: usize,
+}
+
+/// Match ID-root records by type and name across two files and report differences.
+///
+/// Stored addresses are session-specific, so pointer fields compare by target:
+/// pointers into ID blocks by the target's `ID.name`, pointers into `DATA`
+/// blocks by their owning ID and position, and only pointers no ID's blocks
+/// hold by stored address. IDs sharing a type and name pair up in file order, and
+/// pairs whose root struct fails to decode are reported as
+/// [`IdChange::DecodeFailed`].
+pub fn diff_id_blocks(left: &BlendFile, left_dna: &Dna, right: &BlendFile, right_dna: &Dna, options: &IdDiffOptions) -> Result<IdDiffResult> {
+	let all_left = scan_id_blocks(left, left_dna)?;
+	let all_right = scan_id_blocks(right, right_dna)?;
+	// Pointers may target IDs of any type, so ownership keeps every record.
+	let wanted = |record: &&IdRecord| options.type_name.as_deref().is_none_or(|name| record.type_name.as_ref() == name);
+	let left_records: Vec<IdRecord> = all_left.iter().filter(wanted).cloned().collect();
+	let right_records: Vec<IdRecord> = all_right.iter().filter(wanted).cloned().collect();
+	let left_values = decode_id_roots(left, left_dna, &left_records, &options.decode)?;
+	let right_values = decode_id_roots(right, right_dna, &right_records, &options.decode)?;
+
+	let left_index = PointerIndex::build(left)?;
+	let right_index = PointerIndex::build(right)?;
+	let left_owners = block_owners(left, &all_left)?;
+	let right_owners = block_owners(right, &all_right)?;
+	let left_target = |ptr| ptr_target(&left_index, &left_owners, ptr);
+	let right_target = |ptr| ptr_target(&right_index, &right_owners, ptr);
+	let targets = PtrTargets {
+		left: &left_target,
+		right: &right_target,
+	};
+
+	let mut right_by_key: HashMap<IdKey, &IdRecord> = HashMap::new();
+	for (key, record) in keyed(&right_records) {
+		right_by_key.insert(key, record);
+	}
+
+	let mut entries = Vec::new();
+	let mut unchanged = 0;
+	for (key, record) in keyed(&left_records) {
+		let Some(other) = right_by_key.remove(&key) else {
+			entries.push(entry(IdChange::Removed, record, Some(record.old_ptr), None));
+			continue;
+		};
+
+		let (Some(a), Some(b)) = (left_values.get(&record.old_ptr), right_values.get(&other.old_ptr)) else {
+			entries.push(entry(IdChange::DecodeFailed, record, Some(record.old_ptr), Some(other.old_ptr)));
+			continue;
+		};
+		let limit = if options.fields { options.max_field_diffs } else { 1 };
+		let (fields, truncated) = diff_struct_fields(a, b, targets, limit);
+		if fields.is_empty() {
+			unchanged += 1;
+			continue;
+		}
+
+		let mut changed = entry(IdChange::Changed, record, Some(record.old_ptr), Some(other.old_ptr));
+		if options.fields {
+			changed.fields = fields;
+			changed.fields_truncated = truncated;
+		}
+		entries.push(changed);
+	}
+
+	for record in right_by_key.into_values() {
+		entries.push(entry(IdChange::Added, record, None, Some(record.old_ptr)));
+	}
+
+	entries.sort_by(|a, b| (&a.type_name, &a.id_name, a.change.as_str()).cmp(&(&b.type_name, &b.id_name, b.change.as_str())));
+
+	Ok(IdDiffResult {
+		entries,
+		unchanged,
+		left_ids: left_records.len(),
+		right_ids: right_records.len(),
+	})
+}
+
+/// Compare two decoded structs leaf by leaf, stopping after `limit` differences.
+///
+/// Pointer leaves are equal when `targets` resolves them to the same [`PtrTarget`].
+pub fn diff_struct_fields(left: &StructValue, right: &StructValue, targets: PtrTargets<'_>, limit: usize) -> (Vec<FieldDiff>, bool) {
+	let mut left_leaves = Vec::new();
+	let mut right_leaves = Vec::new();
+	collect_leaves(left, "", &mut left_leaves);
+	collect_leaves(right, "", &mut right_leaves);
+
+	// Duplicate leaf paths (repeated declarators) pair up by occurrence.
+	let mut right_by_path: HashMap<(&str, usize), &Value> = with_ordinals(&right_leaves).collect();
+	let limit = limit.max(1);
+	let mut out = Vec::new();
+
+	for (key, value) in with_ordinals(&left_leaves) {
+		let other = right_by_path.remove(&key);
+		if other.is_some_and(|other| values_equal(value, other, targets)) {
+			continue;
+		}
+		if out.len() >= limit {
+			return (out, true);
+		}
+		out.push(FieldDiff {
+			path: Arc::from(key.0),
+			left: Some(value.clone()),
+			right: other.cloned(),
+		});
+	}
+
+	for (key, value) in with_ordinals(&right_leaves) {
+		if !right_by_path.contains_key(&key) {
+			continue;
+		}
+		if out.len() >= limit {
+			return (out, true);
+		}
+		out.push(FieldDiff {
+			path: Arc::from(key.0),
+			left: None,
+			right: Some(value.clone()),
+		});
+	}
+
+	(out, false)
+}
+
+fn values_equal(left: &Value, right: &Value, targets: PtrTargets<'_>) -> bool {
+	match (left, right) {
+		(Value::Ptr(a), Value::Ptr(b)) => (targets.left)(*a) == (targets.right)(*b),
+		(Value::Null, Value::Null) => true,
+		(Value::Bool(a), Value::Bool(b)) => a == b,
+		(Value::I64(a), Value::I64(b)) => a == b,
+		(Value::U64(a), Value::U64(b)) => a == b,
+		(Value::F32(a), Value::F32(b)) => a.to_bits() == b.to_bits(),
+		(Value::F64(a), Value::F64(b)) => a.to_bits() == b.to_bits(),
+		(Value::Bytes(a), Value::Bytes(b)) => a == b,
+		(Value::String(a), Value::String(b)) => a == b,
+		(Value::Array(a), Value::Array(b)) => a.len() == b.len() && a.iter().zip(b).all(|(a, b)| values_equal(a, b, targets)),
+		(Value::Struct(a), Value::Struct(b)) => diff_struct_fields(a, b, targets, 1).0.is_empty(),
+		_ => false,
+	}
+}
+
+fn collect_leaves<'v>(value: &'v StructValue, prefix: &str, out: &mut Vec<(String, &'v Value)>) {
+	for field in &value.fields {
+		let path = format!("{prefix}{}", field.name);
+		match &field.value {
+			Value::Struct(nested) => collect_leaves(nested, &format!("{path}."), out),
+			leaf => out.push((path, leaf)),
+		}
+	}
+}
+
+fn with_ordinals<'a, 'v>(leaves: &'a [(String, &'v Value)]) -> impl Iterator<Item = ((&'a str, usize), &'v Value)> {
+	let mut seen: HashMap<&str, usize> = HashMap::new();
+	leaves.iter().map(move |(path, value)| {
+		let ordinal = seen.entry(path.as_str()).or_default();
+		let key = (path.as_str(), *ordinal);
+		*ordinal += 1;
+		(key, *value)
+	})
+}
+
+/// Match key: type name, ID name, and ordinal among same-named IDs.
+type IdKey = (Box<str>, Box<str>, usize);
+
+fn keyed(records: &[IdRecord]) -> Vec<(IdKey, &IdRecord)> {
+	let mut seen: HashMap<(&str, &str), usize> = HashMap::new();
+	records
+		.iter()
+		.map(|record| {
+			let ordinal = seen.entry((&record.type_name, &record.id_name)).or_default();
+			let key = (record.type_name.clone(), record.id_name.clone(), *ordinal);
+			*ordinal += 1;
+			(key, record)
+		})
+		.collect()
+}
+
+fn entry(change: IdChange, record: &IdRecord, left_ptr: Option<u64>, right_ptr: Option<u64>) -> IdDiffEntry {
+	IdDiffEntry {
+		change,
+		type_name: record.type_name.clone(),
+		id_name: record.id_name.clone(),
+		left_ptr,
+		right_ptr,
+		fields: Vec::new(),
+		fields_truncated: false,
+	}
+}
+
+/// Owning `ID.name` and position in the owner's run (0 for the ID block
+/// itself) per block file offset, using the footprint ownership of
+/// [`scan_id_footprints`](crate::blend::scan_id_footprints).
+fn block_owners<'a>(file: &BlendFile, records: &'a [IdRecord]) -> Result<HashMap<usize, (&'a str, usize)>> {
+	let by_ptr: HashMap<u64, &IdRecord> = records.iter().map(|record| (record.old_ptr, record)).collect();
+	let mut out = HashMap::new();
+	let mut owner = None;
+	for block in file.blocks() {
+		let block = block?;
+		if block.head.code != codes::DATA {
+			owner = by_ptr
+				.get(&block.head.old)
+				.filter(|record| record.sdna_nr == block.head.sdna_nr && record.code == block.head.code)
+				.map(|record| (record.id_name.as_ref(), 0));
+		} else if let Some((_, position)) = owner.as_mut() {
+			*position += 1;
+		}
+		if let Some(owned) = owner {
+			out.insert(block.file_offset, owned);
+		}
+	}
+	Ok(out)
+}
+
+fn ptr_target<'a>(index: &PointerIndex<'_>, owners: &HashMap<usize, (&'a str, usize)>, ptr: u64) -> PtrTarget<'a> {
+	if ptr == 0 {
+		return PtrTarget::Null;
+	}
+	let owned = index
+		.resolve(ptr)
+		.and_then(|resolved| Some((*owners.get(&resolved.entry.block.file_offset)?, resolved.byte_offset)));
+	match owned {
+		Some(((name, 0), offset)) => PtrTarget::Id { name, offset },
+		Some(((owner, block), offset)) => PtrTarget::Data {
+			owner,
+			block: block - 1,
+			offset,
+		},
+		None => PtrTarget::Address(ptr),
+	}
+}
+
+fn decode_id_roots(file: &BlendFile, dna: &Dna, records: &[IdRecord], options: &DecodeOptions) -> Result<HashMap<u64, StructValue>> {
+	let wanted: HashMap<u64, u32> = records.iter().map(|record| (record.old_ptr, record.sdna_nr)).collect();
+	let mut out = HashMap::with_capacity(wanted.len());
+
+	for block in file.blocks() {
+		let block = block?;
+		let Some(&sdna_nr) = wanted.get(&block.head.old) else {
+			continue;
+		};
+		if block.head.sdna_nr != sdna_nr {
+			continue;
+		}
+		let Some(item) = dna.struct_by_sdna(sdna_nr) else {
+			continue;
+		};
+		let size = usize::from(dna.tlen[item.type_idx as usize]);
+		let Some(bytes) = block.payload.get(..size) else {
+			continue;
+		};
+		if let Ok(value) = decode_struct_instance(dna, sdna_nr, bytes, options) {
+			out.insert(block.head.old, value);
+		}
+	}
+
+	Ok(out)
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,222 @@
+mod synthetic_fields {
+	use crate::blend::{FieldValue, PtrTarget, PtrTargets, StructValue, Value, diff_struct_fields};
+
+	fn field(name: &str, value: Value) -> FieldValue {
+		FieldValue { name: name.into(), value }
+	}
+
+	fn object(name: &str, data: u64, loc_x: f32, extra: Option<i64>) -> StructValue {
+		let mut fields = vec![
+			field(
+				"id",
+				Value::Struct(StructValue {
+					type_name: "ID".into(),
+					fields: vec![field("name", Value::String(name.into())), field("next", Value::Ptr(0))],
+				}),
+			),
+			field("data", Value::Ptr(data)),
+			field("loc", Value::Array(vec![Value::F32(loc_x), Value::F32(0.0), Value::F32(0.0)])),
+		];
+		if let Some(extra) = extra {
+			fields.push(field("extra", Value::I64(extra)));
+		}
+		StructValue {
+			type_name: "Object".into(),
+			fields,
+		}
+	}
+
+	fn by_address(ptr: u64) -> PtrTarget<'static> {
+		if ptr == 0 { PtrTarget::Null } else { PtrTarget::Address(ptr) }
+	}
+
+	const RAW: PtrTargets<'static> = PtrTargets {
+		left: &by_address,
+		right: &by_address,
+	};
+
+	#[test]
+	fn pointers_compare_by_resolved_target() {
+		// Left 0x1000 and right 0x9000 hold the same mesh; right 0x7000 holds another.
+		let left_target = |ptr| match ptr {
+			0x1000 => PtrTarget::Id { name: "MECube", offset: 0 },
+			other => by_address(other),
+		};
+		let right_target = |ptr| match ptr {
+			0x9000 => PtrTarget::Id { name: "MECube", offset: 0 },
+			0x7000 => PtrTarget::Id { name: "MECone", offset: 0 },
+			other => by_address(other),
+		};
+		let targets = PtrTargets {
+			left: &left_target,
+			right: &right_target,
+		};
+		let left = object("OBCube", 0x1000, 1.0, None);
+
+		let (fields, truncated) = diff_struct_fields(&left, &object("OBCube", 0x9000, 1.0, None), targets, 16);
+		assert!(fields.is_empty(), "{fields:?}");
+		assert!(!truncated);
+
+		for other in [0x7000, 0, 0x1000] {
+			let (fields, _) = diff_struct_fields(&left, &object("OBCube", other, 1.0, None), targets, 16);
+			assert_eq!(fields.len(), 1, "right data 0x{other:x}");
+			assert_eq!(fields[0].path.as_ref(), "data");
+		}
+
+		let (fields, _) = diff_struct_fields(&object("OBCube", 0x2000, 1.0, None), &object("OBCube", 0x2000, 1.0, None), RAW, 16);
+		assert!(fields.is_empty(), "non-ID targets match by address");
+	}
+
+	#[test]
+	fn reports_changed_and_one_sided_leaves_with_limit() {
+		let left = object("OBCube", 0x1000, 1.0, None);
+		let right = object("OBCube", 0x1000, 2.0, Some(7));
+
+		let (fields, truncated) = diff_struct_fields(&left, &right, RAW, 16);
+		let paths: Vec<_> = fields.iter().map(|item| item.path.as_ref()).collect();
+		assert_eq!(paths, vec!["loc", "extra"]);
+		assert!(fields[1].left.is_none());
+		assert!(matches!(fields[1].right, Some(Value::I64(7))));
+		assert!(!truncated);
+
+		let (fields, truncated) = diff_struct_fields(&left, &right, RAW, 1);
+		assert_eq!(fields.len(), 1);
+		assert!(truncated);
+	}
+
+	#[test]
+	fn repeated_leaf_paths_pair_by_occurrence() {
+		let make = |second: i64| StructValue {
+			type_name: "Pair".into(),
+			fields: vec![field("flag", Value::I64(1)), field("flag", Value::I64(second))],
+		};
+
+		let (fields, _) = diff_struct_fields(&make(2), &make(3), RAW, 16);
+		assert_eq!(fields.len(), 1);
+		assert!(matches!(fields[0].left, Some(Value::I64(2))));
+		assert!(matches!(fields[0].right, Some(Value::I64(3))));
+	}
+}
+
+mod synthetic_files {
+	use blendoc_testkit::builder::{BlendBuilder, HeaderKind};
+
+	use crate::blend::{BlendFile, IdChange, IdDiffOptions, IdDiffResult, diff_id_blocks};
+
+	/// `OBChild` whose `parent` points at `parent_name`, plus `OBParent` and `OBOther`.
+	///
+	/// `base` shifts every address so files mimic separate save sessions;
+	/// `short_child` cuts the child block to its `ID` so its root cannot decode.
+	fn file(base: u64, parent_name: &str, short_child: bool) -> BlendFile {
+		let mut builder = BlendBuilder::new(HeaderKind::Legacy8, 404, false);
+		let sdna = builder.sdna_mut();
+		sdna.add_type("char", 1);
+		sdna.add_type("void", 0);
+		sdna.add_struct("ID", &[("void", "*next"), ("void", "*prev"), ("void", "*lib"), ("char", "name[24]")]);
+		let object = sdna.add_struct("Object", &[("ID", "id"), ("Object", "*parent")]);
+
+		let parent_ptr = if parent_name == "OBParent" { base + 0x100 } else { base + 0x200 };
+		let mut child = builder.payload();
+		child.ptr(0).ptr(0).ptr(0).name("OBChild", 24);
+		if !short_child {
+			child.ptr(parent_ptr);
+		}
+		let child = child.finish();
+		let mut parent = builder.payload();
+		parent.ptr(0).ptr(0).ptr(0).name("OBParent", 24).ptr(0);
+		let parent = parent.finish();
+		let mut other = builder.payload();
+		other.ptr(0).ptr(0).ptr(0).name("OBOther", 24).ptr(0);
+		let other = other.finish();
+		builder
+			.block(*b"OB\0\0", object, base, 1, child)
+			.block(*b"OB\0\0", object, base + 0x100, 1, parent)
+			.block(*b"OB\0\0", object, base + 0x200, 1, other);
+		BlendFile::from_bytes(builder.build()).expect("synthetic file parses")
+	}
+
+	fn diff(left: &BlendFile, right: &BlendFile) -> IdDiffResult {
+		let options = IdDiffOptions {
+			fields: true,
+			..IdDiffOptions::default()
+		};
+		diff_id_blocks(left, &left.dna().expect("dna"), right, &right.dna().expect("dna"), &options).expect("diff runs")
+	}
+
+	#[test]
+	fn pointers_to_the_same_id_match_across_sessions() {
+		let left = file(0x1000, "OBParent", false);
+		let moved = diff(&left, &file(0x8000, "OBParent", false));
+		assert!(moved.entries.is_empty(), "{:?}", moved.entries);
+		assert_eq!(moved.unchanged, 3);
+
+		let retargeted = diff(&left, &file(0x8000, "OBOther", false));
+		assert_eq!(retargeted.entries.len(), 1);
+		assert_eq!(
+			(retargeted.entries[0].change, retargeted.entries[0].id_name.as_ref()),
+			(IdChange::Changed, "OBChild")
+		);
+		assert_eq!(retargeted.entries[0].fields[0].path.as_ref(), "parent");
+	}
+
+	/// `MEMesh` whose `verts` points at the first or second of its two `DATA` blocks.
+	fn mesh_file(base: u64, second_block: bool) -> BlendFile {
+		let mut builder = BlendBuilder::new(HeaderKind::Legacy8, 404, false);
+		let sdna = builder.sdna_mut();
+		sdna.add_type("char", 1);
+		sdna.add_type("void", 0);
+		sdna.add_type("float", 4);
+		sdna.add_struct("ID", &[("void", "*next"), ("void", "*prev"), ("void", "*lib"), ("char", "name[24]")]);
+		let mesh = sdna.add_struct("Mesh", &[("ID", "id"), ("float", "*verts")]);
+
+		let verts = if second_block { base + 0x200 } else { base + 0x100 };
+		let mut payload = builder.payload();
+		payload.ptr(0).ptr(0).ptr(0).name("MEMesh", 24).ptr(verts);
+		let me = payload.finish();
+		builder
+			.block(*b"ME\0\0", mesh, base, 1, me)
+			.block(*b"DATA", 0, base + 0x100, 1, vec![0; 12])
+			.block(*b"DATA", 0, base + 0x200, 1, vec![0; 12]);
+		BlendFile::from_bytes(builder.build()).expect("synthetic file parses")
+	}
+
+	#[test]
+	fn pointers_into_owned_data_match_across_sessions() {
+		let left = mesh_file(0x1000, false);
+		let moved = diff(&left, &mesh_file(0x8000, false));
+		assert!(moved.entries.is_empty(), "{:?}", moved.entries);
+		assert_eq!(moved.unchanged, 1);
+
+		let retargeted = diff(&left, &mesh_file(0x8000, true));
+		assert_eq!(retargeted.entries.len(), 1);
+		assert_eq!(retargeted.entries[0].fields[0].path.as_ref(), "verts");
+	}
+
+	#[test]
+	fn type_filter_scopes_counts_and_entries() {
+		let left = file(0x1000, "OBParent", false);
+		let right = file(0x8000, "OBOther", false);
+		let scoped = |type_name: &str| {
+			let options = IdDiffOptions {
+				type_name: Some(type_name.to_owned()),
+				..IdDiffOptions::default()
+			};
+			diff_id_blocks(&left, &left.dna().expect("dna"), &right, &right.dna().expect("dna"), &options).expect("diff runs")
+		};
+
+		let objects = scoped("Object");
+		assert_eq!((objects.left_ids, objects.right_ids, objects.unchanged, objects.entries.len()), (3, 3, 2, 1));
+		let meshes = scoped("Mesh");
+		assert_eq!((meshes.left_ids, meshes.right_ids, meshes.unchanged, meshes.entries.len()), (0, 0, 0, 0));
+	}
+
+	#[test]
+	fn undecodable_roots_are_reported_separately() {
+		let result = diff(&file(0x1000, "OBParent", false), &file(0x1000, "OBParent", true));
+		assert_eq!(result.entries.len(), 1);
+		assert_eq!(result.entries[0].change, IdChange::DecodeFailed);
+		assert_eq!(result.entries[0].change.as_str(), "decode_failed");
+		assert_eq!(result.entries[0].id_name.as_ref(), "OBChild");
+		assert_eq!(result.unchanged, 2);
+	}
+}
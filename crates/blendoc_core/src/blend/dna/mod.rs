@@ -1,4 +1,9 @@
-use crate::blend::bytes::Cursor;
+use std::collections::HashMap;
+use std::io::Write;
+
+use crate::blend::bytes::{ByteWriter, Cursor};
+use crate::blend::decl::parse_field_decl;
+use crate::blend::trace::trace_span;
 use crate::blend::{BlendError, Endianness, Result};
 
 /// Parsed SDNA schema tables.
@@ -12,12 +17,33 @@ pub struct Dna {
 	pub names: Vec<Box<str>>,
 	/// Type name strings from `TYPE`.
 	pub types: Vec<Box<str>>,
-	/// Type byte sizes from `TLEN`.
+	/// Type byte sizes from `TLEN`, with [`Dna::tlen_fixups`] applied.
 	pub tlen: Vec<u16>,
+	/// Struct sizes [`Dna::parse`] recomputed because `TLEN` was written for the other pointer width.
+	pub tlen_fixups: Vec<TlenFixup>,
 	/// Struct declarations from `STRC`.
 	pub structs: Vec<DnaStruct>,
 	/// Fast mapping `type_idx -> sdna_struct_idx`.
 	pub struct_for_type: Vec<Option<u32>>,
+	/// Fast mapping `type name -> type_idx` (first occurrence wins).
+	pub type_for_name: HashMap<Box<str>, u16>,
+	/// Fast mapping `field declarator -> name_idx` (first occurrence wins).
+	pub name_for_text: HashMap<Box<str>, u16>,
+}
+
+/// One struct `TLEN` recomputed for the file pointer width.
+///
+/// Some legacy writers stored struct sizes laid out for a different pointer
+/// width than the header declares, which shifts every field after an
+/// embedded pointer-holding struct.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TlenFixup {
+	/// Struct type index.
+	pub type_idx: u16,
+	/// Size stored in the file's `TLEN`; [`Dna::write`] emits it unchanged.
+	pub written: u16,
+	/// Size recomputed from the fields, now in [`Dna::tlen`].
+	pub computed: u16,
 }
 
 /// One struct declaration from SDNA.
@@ -38,9 +64,46 @@ pub struct DnaField {
 	pub name_idx: u16,
 }
 
+/// Byte placement of one SDNA field inside its owning struct.
+#[derive(Debug, Clone)]
+pub struct FieldLayout {
+	/// Field identifier (declarator without pointer/array syntax).
+	pub name: Box<str>,
+	/// Raw SDNA declarator text (for example `*next` or `loc[3]`).
+	pub decl: Box<str>,
+	/// Field type name.
+	pub type_name: Box<str>,
+	/// Byte offset from the start of the struct.
+	pub offset: usize,
+	/// Total bytes occupied by the field (all inline array elements).
+	pub size: usize,
+	/// Bytes per inline array element.
+	pub element_size: usize,
+	/// Flattened inline array element count.
+	pub count: usize,
+	/// Whether the field stores pointers.
+	pub is_ptr: bool,
+	/// SDNA struct index when the field embeds a struct by value.
+	pub struct_sdna: Option<u32>,
+	/// Whether the field is explicit padding (`_pad`/`pad` byte arrays) the decoder skips by default.
+	pub is_padding: bool,
+}
+
+/// Bytes of a struct that hold no data, from [`Dna::padding_holes`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PaddingHole {
+	/// Byte offset from the start of the struct.
+	pub offset: usize,
+	/// Hole length in bytes.
+	pub size: usize,
+	/// Padding field covering the hole; `None` for bytes past the last field.
+	pub field: Option<Box<str>>,
+}
+
 impl Dna {
 	/// Parse `DNA1` payload bytes into SDNA tables.
 	pub fn parse(payload: &[u8], endianness: Endianness, pointer_size: usize) -> Result<Self> {
+		trace_span!("dna.parse", bytes = payload.len());
 		let mut cursor = Cursor::new(payload);
 
 		expect_tag(&mut cursor, *b"SDNA")?;
@@ -92,8 +155,141 @@ impl Dna {
 			structs.push(DnaStruct { type_idx, fields });
 		}
 
+		let mut dna = Self::from_tables(endianness, pointer_size, names, types, tlen, structs)?;
+		dna.fix_pointer_width_tlen();
+		Ok(dna)
+	}
+
+	/// Replace struct sizes that only add up under the other pointer width.
+	///
+	/// A struct is corrected when its fields, packed with the file pointer
+	/// width, disagree with `TLEN` while packing them with the other width (4
+	/// vs 8) matches it exactly. Any other mismatch is left for
+	/// [`verify_dna_layout`](crate::blend::verify_dna_layout) to report.
+	fn fix_pointer_width_tlen(&mut self) {
+		let other = match self.pointer_size {
+			4 => 8,
+			8 => 4,
+			_ => return,
+		};
+		let native = self.packed_struct_sizes(self.pointer_size);
+		let foreign = self.packed_struct_sizes(other);
+
+		for (idx, item) in self.structs.iter().enumerate() {
+			let written = self.tlen[item.type_idx as usize];
+			let (Some(native), Some(foreign)) = (native[idx], foreign[idx]) else {
+				continue;
+			};
+			if native == usize::from(written) || foreign != usize::from(written) {
+				continue;
+			}
+			let Ok(computed) = u16::try_from(native) else {
+				continue;
+			};
+			self.tlen_fixups.push(TlenFixup {
+				type_idx: item.type_idx,
+				written,
+				computed,
+			});
+		}
+		for fixup in &self.tlen_fixups {
+			self.tlen[fixup.type_idx as usize] = fixup.computed;
+		}
+	}
+
+	/// Every struct's size with its fields packed back to back and pointers
+	/// `pointer_size` wide; `None` for recursive or out-of-range declarations.
+	fn packed_struct_sizes(&self, pointer_size: usize) -> Vec<Option<usize>> {
+		let mut sizes = vec![None; self.structs.len()];
+		for idx in 0..self.structs.len() {
+			self.packed_struct_size(idx, pointer_size, &mut sizes);
+		}
+		sizes.into_iter().map(Option::flatten).collect()
+	}
+
+	fn packed_struct_size(&self, idx: usize, pointer_size: usize, sizes: &mut [Option<Option<usize>>]) -> Option<usize> {
+		if let Some(size) = sizes[idx] {
+			return size;
+		}
+		// Marks the struct as in progress so an embedding cycle resolves to `None`.
+		sizes[idx] = Some(None);
+
+		let mut total = Some(0_usize);
+		for field in &self.structs[idx].fields {
+			let decl = parse_field_decl(self.field_name(field.name_idx));
+			let element = if decl.ptr_depth > 0 || decl.is_func_ptr {
+				Some(pointer_size)
+			} else if let Some(inner) = self.struct_for_type.get(field.type_idx as usize).copied().flatten() {
+				self.packed_struct_size(inner as usize, pointer_size, sizes)
+			} else {
+				self.tlen.get(field.type_idx as usize).copied().map(usize::from)
+			};
+			total = total
+				.zip(element)
+				.and_then(|(total, element)| total.checked_add(element.checked_mul(decl.inline_array)?));
+		}
+		sizes[idx] = Some(total);
+		total
+	}
+
+	/// Write the `DNA1` payload for these tables, the inverse of [`Dna::parse`].
+	///
+	/// Section padding is zero-filled and [`TlenFixup`]s are written back as
+	/// stored, so payloads written by Blender re-encode byte for byte. Names that were not valid UTF-8 keep their lossy form.
+	pub fn write(&self, out: &mut impl Write) -> Result<()> {
+		let endianness = self.endianness;
+		let mut bytes = ByteWriter::new();
+
+		bytes.write_bytes(b"SDNA");
+		bytes.write_bytes(b"NAME");
+		bytes.write_u32(encode_count("names", self.names.len(), u32::MAX as usize)? as u32, endianness);
+		for name in &self.names {
+			bytes.write_cstring_bytes(name.as_bytes());
+		}
+		bytes.align4();
+
+		bytes.write_bytes(b"TYPE");
+		bytes.write_u32(encode_count("types", self.types.len(), u32::MAX as usize)? as u32, endianness);
+		for type_name in &self.types {
+			bytes.write_cstring_bytes(type_name.as_bytes());
+		}
+		bytes.align4();
+
+		bytes.write_bytes(b"TLEN");
+		for idx in 0..self.types.len() {
+			let written = self.tlen_fixups.iter().find(|fixup| usize::from(fixup.type_idx) == idx);
+			let size = written.map_or_else(|| self.tlen.get(idx).copied().unwrap_or(0), |fixup| fixup.written);
+			bytes.write_u16(size, endianness);
+		}
+		bytes.align4();
+
+		bytes.write_bytes(b"STRC");
+		bytes.write_u32(encode_count("structs", self.structs.len(), u32::MAX as usize)? as u32, endianness);
+		for item in &self.structs {
+			bytes.write_u16(item.type_idx, endianness);
+			bytes.write_u16(encode_count("struct.fields", item.fields.len(), u16::MAX as usize)? as u16, endianness);
+			for field in &item.fields {
+				bytes.write_u16(field.type_idx, endianness);
+				bytes.write_u16(field.name_idx, endianness);
+			}
+		}
+
+		out.write_all(&bytes.into_bytes())?;
+		Ok(())
+	}
+
+	/// Build SDNA tables and lookup maps from already-parsed sections.
+	pub fn from_tables(
+		endianness: Endianness,
+		pointer_size: usize,
+		names: Vec<Box<str>>,
+		types: Vec<Box<str>>,
+		tlen: Vec<u16>,
+		structs: Vec<DnaStruct>,
+	) -> Result<Self> {
 		let mut struct_for_type = vec![None; types.len()];
 		for (idx, item) in structs.iter().enumerate() {
+			check_index("struct.type_idx", u32::from(item.type_idx), types.len())?;
 			let slot = &mut struct_for_type[item.type_idx as usize];
 			if let Some(first) = *slot {
 				return Err(BlendError::DnaDuplicateStructType {
@@ -105,14 +301,20 @@ impl Dna {
 			*slot = Some(idx as u32);
 		}
 
+		let type_for_name = first_index_map(&types);
+		let name_for_text = first_index_map(&names);
+
 		Ok(Self {
 			endianness,
 			pointer_size,
 			names,
 			types,
 			tlen,
+			tlen_fixups: Vec::new(),
 			structs,
 			struct_for_type,
+			type_for_name,
+			name_for_text,
 		})
 	}
 
@@ -128,6 +330,101 @@ impl Dna {
 			.and_then(|index| index.and_then(|value| self.structs.get(value as usize)))
 	}
 
+	/// Compute field offsets and sizes for one struct, in declaration order.
+	///
+	/// Sizes follow the decoder: pointers use the file pointer width and all
+	/// other fields use `TLEN` of their type times the inline array length.
+	pub fn struct_layout(&self, sdna_nr: u32) -> Option<Vec<FieldLayout>> {
+		let item = self.struct_by_sdna(sdna_nr)?;
+		let mut offset = 0_usize;
+		let mut out = Vec::with_capacity(item.fields.len());
+
+		for field in &item.fields {
+			let raw = self.field_name(field.name_idx);
+			let decl = parse_field_decl(raw);
+			let is_ptr = decl.ptr_depth > 0 || decl.is_func_ptr;
+			let element_size = if is_ptr {
+				self.pointer_size
+			} else {
+				usize::from(*self.tlen.get(field.type_idx as usize)?)
+			};
+			let size = element_size.saturating_mul(decl.inline_array);
+			let struct_sdna = if is_ptr {
+				None
+			} else {
+				self.struct_for_type.get(field.type_idx as usize).copied().flatten()
+			};
+			let type_name = self.type_name(field.type_idx);
+
+			out.push(FieldLayout {
+				name: decl.ident.into(),
+				decl: raw.into(),
+				type_name: type_name.into(),
+				offset,
+				size,
+				element_size,
+				count: decl.inline_array,
+				is_ptr,
+				struct_sdna,
+				is_padding: !is_ptr && is_padding_field(decl.ident, type_name, decl.inline_array),
+			});
+			offset = offset.saturating_add(size);
+		}
+
+		Some(out)
+	}
+
+	/// List the padding in struct `sdna_nr`: explicit padding fields, then any
+	/// bytes `TLEN` reserves past the last field.
+	///
+	/// `makesdna` rejects implicit alignment gaps, so padding is always spelled
+	/// out as fields; a trailing hole means `TLEN` and the field list disagree.
+	pub fn padding_holes(&self, sdna_nr: u32) -> Option<Vec<PaddingHole>> {
+		let item = self.struct_by_sdna(sdna_nr)?;
+		let layout = self.struct_layout(sdna_nr)?;
+		let mut out: Vec<PaddingHole> = layout
+			.iter()
+			.filter(|field| field.is_padding && field.size > 0)
+			.map(|field| PaddingHole {
+				offset: field.offset,
+				size: field.size,
+				field: Some(field.name.clone()),
+			})
+			.collect();
+		let end = layout.last().map_or(0, |field| field.offset + field.size);
+		let struct_size = usize::from(self.tlen[item.type_idx as usize]);
+		if struct_size > end {
+			out.push(PaddingHole {
+				offset: end,
+				size: struct_size - end,
+				field: None,
+			});
+		}
+		Some(out)
+	}
+
+	/// Look up type index by type name.
+	pub fn type_index(&self, name: &str) -> Option<u16> {
+		self.type_for_name.get(name).copied()
+	}
+
+	/// Look up field name index by exact declarator text (e.g. `*next`, `name[66]`).
+	pub fn name_index(&self, text: &str) -> Option<u16> {
+		self.name_for_text.get(text).copied()
+	}
+
+	/// Look up SDNA struct index by struct type name.
+	pub fn struct_index(&self, name: &str) -> Option<u32> {
+		let type_idx = self.type_index(name)?;
+		self.struct_for_type.get(type_idx as usize).copied().flatten()
+	}
+
+	/// Look up struct declaration and its SDNA index by struct type name.
+	pub fn struct_by_name(&self, name: &str) -> Option<(u32, &DnaStruct)> {
+		let sdna_nr = self.struct_index(name)?;
+		Some((sdna_nr, self.structs.get(sdna_nr as usize)?))
+	}
+
 	/// Return type name by type index.
 	pub fn type_name(&self, type_idx: u16) -> &str {
 		&self.types[type_idx as usize]
@@ -139,6 +436,11 @@ impl Dna {
 	}
 }
 
+/// Whether a field is a `_pad`/`pad` byte array Blender inserts for alignment.
+pub(crate) fn is_padding_field(ident: &str, type_name: &str, inline_array: usize) -> bool {
+	(ident.starts_with("_pad") || ident.starts_with("pad")) && inline_array > 0 && matches!(type_name, "char" | "uchar" | "uint8_t")
+}
+
 fn expect_tag(cursor: &mut Cursor<'_>, expected: [u8; 4]) -> Result<()> {
 	let at = cursor.pos();
 	let got = cursor.read_code4()?;
@@ -148,11 +450,30 @@ fn expect_tag(cursor: &mut Cursor<'_>, expected: [u8; 4]) -> Result<()> {
 	Ok(())
 }
 
+fn first_index_map(table: &[Box<str>]) -> HashMap<Box<str>, u16> {
+	let mut out = HashMap::with_capacity(table.len());
+	for (idx, item) in table.iter().enumerate() {
+		out.entry(item.clone()).or_insert(idx as u16);
+	}
+	out
+}
+
 fn read_lossy_string(cursor: &mut Cursor<'_>) -> Result<Box<str>> {
 	let bytes = cursor.read_cstring_bytes()?;
 	Ok(String::from_utf8_lossy(bytes).into_owned().into_boxed_str())
 }
 
+fn encode_count(field: &'static str, len: usize, max: usize) -> Result<usize> {
+	if len > max {
+		return Err(BlendError::EncodeOutOfRange {
+			field,
+			value: len as u64,
+			max: max as u64,
+		});
+	}
+	Ok(len)
+}
+
 fn check_index(kind: &'static str, idx: u32, len: usize) -> Result<()> {
 	if (idx as usize) >= len {
 		return Err(BlendError::DnaIndexOutOfRange {
@@ -33,3 +33,256 @@ mod fixtures_day2_dna {
 		}
 	}
 }
+
+mod synthetic_lookup {
+	use crate::blend::{BlendError, Dna, DnaField, DnaStruct, Endianness};
+
+	fn dna() -> Dna {
+		Dna::from_tables(
+			Endianness::Little,
+			8,
+			vec!["*next".into(), "name[66]".into(), "*next".into()],
+			vec!["char".into(), "ID".into(), "Object".into(), "ID".into()],
+			vec![1, 82, 90, 82],
+			vec![
+				DnaStruct {
+					type_idx: 1,
+					fields: vec![DnaField { type_idx: 0, name_idx: 1 }],
+				},
+				DnaStruct {
+					type_idx: 2,
+					fields: vec![DnaField { type_idx: 1, name_idx: 0 }],
+				},
+			],
+		)
+		.expect("valid dna tables")
+	}
+
+	#[test]
+	fn lookups_resolve_names_to_first_index() {
+		let dna = dna();
+
+		assert_eq!(dna.type_index("Object"), Some(2));
+		assert_eq!(dna.type_index("ID"), Some(1));
+		assert_eq!(dna.type_index("Missing"), None);
+		assert_eq!(dna.name_index("*next"), Some(0));
+		assert_eq!(dna.name_index("name[66]"), Some(1));
+		assert_eq!(dna.struct_index("Object"), Some(1));
+		assert_eq!(dna.struct_index("char"), None);
+
+		let (sdna_nr, item) = dna.struct_by_name("ID").expect("ID struct");
+		assert_eq!(sdna_nr, 0);
+		assert_eq!(item.type_idx, 1);
+	}
+
+	#[test]
+	fn lookups_scale_to_large_name_tables() {
+		let names: Vec<Box<str>> = (0..8000).map(|idx| format!("field_{idx}").into_boxed_str()).collect();
+		let dna = Dna::from_tables(Endianness::Little, 8, names, vec!["int".into()], vec![4], Vec::new()).expect("valid dna tables");
+
+		assert_eq!(dna.name_for_text.len(), 8000);
+		assert_eq!(dna.name_index("field_7999"), Some(7999));
+	}
+
+	#[test]
+	fn from_tables_rejects_duplicate_struct_types() {
+		let err = Dna::from_tables(
+			Endianness::Little,
+			8,
+			vec!["a".into()],
+			vec!["int".into()],
+			vec![4],
+			vec![
+				DnaStruct {
+					type_idx: 0,
+					fields: Vec::new(),
+				},
+				DnaStruct {
+					type_idx: 0,
+					fields: Vec::new(),
+				},
+			],
+		)
+		.expect_err("duplicate struct type");
+
+		assert!(matches!(
+			err,
+			BlendError::DnaDuplicateStructType {
+				type_idx: 0,
+				first: 0,
+				second: 1
+			}
+		));
+	}
+}
+
+mod synthetic_layout {
+	use crate::blend::{Dna, DnaField, DnaStruct, Endianness, PaddingHole};
+
+	#[test]
+	fn struct_layout_accumulates_offsets_for_ptrs_arrays_and_nested_structs() {
+		let dna = Dna::from_tables(
+			Endianness::Little,
+			8,
+			vec!["*next".into(), "loc[3]".into(), "inner".into(), "flag".into()],
+			vec!["float".into(), "int".into(), "Inner".into(), "Outer".into()],
+			vec![4, 4, 4, 32],
+			vec![
+				DnaStruct {
+					type_idx: 2,
+					fields: vec![DnaField { type_idx: 1, name_idx: 3 }],
+				},
+				DnaStruct {
+					type_idx: 3,
+					fields: vec![
+						DnaField { type_idx: 3, name_idx: 0 },
+						DnaField { type_idx: 0, name_idx: 1 },
+						DnaField { type_idx: 2, name_idx: 2 },
+						DnaField { type_idx: 1, name_idx: 3 },
+					],
+				},
+			],
+		)
+		.expect("valid dna tables");
+
+		let layout = dna.struct_layout(1).expect("layout");
+		let rows: Vec<_> = layout.iter().map(|item| (item.name.as_ref(), item.offset, item.size, item.is_ptr)).collect();
+		assert_eq!(
+			rows,
+			vec![("next", 0, 8, true), ("loc", 8, 12, false), ("inner", 20, 4, false), ("flag", 24, 4, false)]
+		);
+		assert_eq!(layout[1].count, 3);
+		assert_eq!(layout[1].element_size, 4);
+		assert_eq!(layout[2].struct_sdna, Some(0));
+		assert_eq!(layout[0].struct_sdna, None);
+		assert!(dna.struct_layout(9).is_none());
+	}
+
+	#[test]
+	fn padding_holes_list_pad_fields_and_tail() {
+		let dna = Dna::from_tables(
+			Endianness::Little,
+			8,
+			vec!["a".into(), "_pad[4]".into(), "*p".into()],
+			vec!["int".into(), "char".into(), "void".into(), "Holey".into()],
+			vec![4, 1, 0, 24],
+			vec![DnaStruct {
+				type_idx: 3,
+				fields: vec![
+					DnaField { type_idx: 0, name_idx: 0 },
+					DnaField { type_idx: 1, name_idx: 1 },
+					DnaField { type_idx: 2, name_idx: 2 },
+				],
+			}],
+		)
+		.expect("valid dna tables");
+
+		let layout = dna.struct_layout(0).expect("layout");
+		let padding: Vec<bool> = layout.iter().map(|field| field.is_padding).collect();
+		assert_eq!(padding, [false, true, false]);
+
+		let holes = dna.padding_holes(0).expect("holes");
+		assert_eq!(
+			holes,
+			[
+				PaddingHole {
+					offset: 4,
+					size: 4,
+					field: Some("_pad".into()),
+				},
+				PaddingHole {
+					offset: 16,
+					size: 8,
+					field: None,
+				},
+			]
+		);
+		assert!(dna.padding_holes(3).is_none());
+	}
+}
+
+mod legacy_pointer_width {
+	use crate::blend::{Dna, DnaField, DnaStruct, Endianness, TlenFixup};
+
+	/// `Link` (two pointers) embedded in `Holder` ahead of an int, with `TLEN`
+	/// sized for 8-byte pointers and `Plain` holding no pointers at all.
+	fn payload(pointer_size: usize) -> Vec<u8> {
+		let dna = Dna::from_tables(
+			Endianness::Little,
+			pointer_size,
+			vec!["*next".into(), "*prev".into(), "link".into(), "flag".into(), "value".into()],
+			vec!["int".into(), "Link".into(), "Holder".into(), "Plain".into()],
+			vec![4, 16, 20, 4],
+			vec![
+				DnaStruct {
+					type_idx: 1,
+					fields: vec![DnaField { type_idx: 1, name_idx: 0 }, DnaField { type_idx: 1, name_idx: 1 }],
+				},
+				DnaStruct {
+					type_idx: 2,
+					fields: vec![DnaField { type_idx: 1, name_idx: 2 }, DnaField { type_idx: 0, name_idx: 3 }],
+				},
+				DnaStruct {
+					type_idx: 3,
+					fields: vec![DnaField { type_idx: 0, name_idx: 4 }],
+				},
+			],
+		)
+		.expect("valid dna tables");
+		let mut bytes = Vec::new();
+		dna.write(&mut bytes).expect("dna writes");
+		bytes
+	}
+
+	#[test]
+	fn parse_recomputes_struct_sizes_for_file_pointer_width() {
+		let bytes = payload(4);
+		let dna = Dna::parse(&bytes, Endianness::Little, 4).expect("dna parses");
+
+		assert_eq!(
+			dna.tlen_fixups,
+			[
+				TlenFixup {
+					type_idx: 1,
+					written: 16,
+					computed: 8,
+				},
+				TlenFixup {
+					type_idx: 2,
+					written: 20,
+					computed: 12,
+				},
+			]
+		);
+		assert_eq!(dna.tlen, [4, 8, 12, 4]);
+
+		let layout = dna.struct_layout(1).expect("holder layout");
+		let rows: Vec<_> = layout.iter().map(|field| (field.name.as_ref(), field.offset, field.size)).collect();
+		assert_eq!(rows, [("link", 0, 8), ("flag", 8, 4)]);
+	}
+
+	#[test]
+	fn parse_keeps_matching_sizes_and_writes_stored_tlen_back() {
+		let bytes = payload(8);
+		let dna = Dna::parse(&bytes, Endianness::Little, 8).expect("dna parses");
+		assert!(dna.tlen_fixups.is_empty());
+		assert_eq!(dna.tlen, [4, 16, 20, 4]);
+
+		let legacy = Dna::parse(&bytes, Endianness::Little, 4).expect("dna parses");
+		let mut written = Vec::new();
+		legacy.write(&mut written).expect("dna writes");
+		assert_eq!(written, bytes);
+	}
+
+	#[test]
+	fn parse_leaves_mismatches_the_other_width_does_not_explain() {
+		let mut dna = Dna::parse(&payload(8), Endianness::Little, 8).expect("dna parses");
+		dna.tlen[3] = 12;
+		let mut bytes = Vec::new();
+		dna.write(&mut bytes).expect("dna writes");
+
+		let reparsed = Dna::parse(&bytes, Endianness::Little, 4).expect("dna parses");
+		assert_eq!(reparsed.tlen[3], 12);
+		assert!(reparsed.tlen_fixups.iter().all(|fixup| fixup.type_idx != 3));
+	}
+}
@@ -0,0 +1,230 @@
+use std::collections::{HashMap, HashSet};
+
+use crate::blend::{Dna, FieldLayout};
+
+/// How one SDNA struct differs between the left and right schema.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DnaStructChange {
+	/// Struct exists only in the right schema.
+	Added,
+	/// Struct exists only in the left schema.
+	Removed,
+	/// Struct exists in both schemas with a differing size or field list.
+	Changed,
+}
+
+impl DnaStructChange {
+	/// Return a stable lowercase label.
+	pub fn as_str(self) -> &'static str {
+		match self {
+			Self::Added => "added",
+			Self::Removed => "removed",
+			Self::Changed => "changed",
+		}
+	}
+}
+
+/// How one struct field differs between schemas.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DnaFieldChange {
+	/// Field exists only in the right struct.
+	Added,
+	/// Field exists only in the left struct.
+	Removed,
+	/// Field exists in both with a different type or declarator.
+	Retyped,
+}
+
+impl DnaFieldChange {
+	/// Return a stable lowercase label.
+	pub fn as_str(self) -> &'static str {
+		match self {
+			Self::Added => "added",
+			Self::Removed => "removed",
+			Self::Retyped => "retyped",
+		}
+	}
+}
+
+/// One added, removed, or retyped field.
+#[derive(Debug, Clone)]
+pub struct DnaFieldDiff {
+	/// Kind of difference.
+	pub change: DnaFieldChange,
+	/// Field identifier with pointer/array decoration stripped.
+	pub name: Box<str>,
+	/// Left type name and declarator, absent for added fields.
+	pub left: Option<DnaFieldSide>,
+	/// Right type name and declarator, absent for removed fields.
+	pub right: Option<DnaFieldSide>,
+}
+
+/// One side of a field comparison.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DnaFieldSide {
+	/// Field type name.
+	pub type_name: Box<str>,
+	/// Raw declarator text (e.g. `*next`, `name[66]`).
+	pub decl: Box<str>,
+	/// Byte offset inside the struct.
+	pub offset: usize,
+	/// Field size in bytes.
+	pub size: usize,
+}
+
+/// One added, removed, or changed struct.
+#[derive(Debug, Clone)]
+pub struct DnaStructDiff {
+	/// Kind of difference.
+	pub change: DnaStructChange,
+	/// Struct type name.
+	pub name: Box<str>,
+	/// `TLEN` size in the left schema.
+	pub left_size: Option<u16>,
+	/// `TLEN` size in the right schema.
+	pub right_size: Option<u16>,
+	/// Field differences for changed structs, in right-then-left declaration order.
+	pub fields: Vec<DnaFieldDiff>,
+	/// Whether fields present on both sides appear in a different order.
+	pub reordered: bool,
+}
+
+/// SDNA schema comparison output.
+#[derive(Debug, Clone)]
+pub struct DnaDiffResult {
+	/// Added, removed, and changed structs ordered by name.
+	pub structs: Vec<DnaStructDiff>,
+	/// Number of structs with identical size and field lists.
+	pub unchanged: usize,
+	/// Struct count in the left schema.
+	pub left_structs: usize,
+	/// Struct count in the right schema.
+	pub right_structs: usize,
+}
+
+/// Compare struct definitions between two SDNA schemas.
+///
+/// Structs match by type name and fields match by identifier, so a field whose
+/// type or declarator changed (e.g. `float x` → `double x`, `a[3]` → `a[4]`)
+/// reports as retyped rather than removed plus added.
+pub fn diff_dna(left: &Dna, right: &Dna) -> DnaDiffResult {
+	let left_layouts = struct_layouts(left);
+	let mut right_layouts: HashMap<&str, (u16, Vec<FieldLayout>)> = struct_layouts(right).into_iter().collect();
+	let right_structs = right_layouts.len();
+	let left_structs = left_layouts.len();
+
+	let mut structs = Vec::new();
+	let mut unchanged = 0;
+	for (name, (left_size, left_fields)) in left_layouts {
+		let Some((right_size, right_fields)) = right_layouts.remove(name) else {
+			structs.push(DnaStructDiff {
+				change: DnaStructChange::Removed,
+				name: name.into(),
+				left_size: Some(left_size),
+				right_size: None,
+				fields: Vec::new(),
+				reordered: false,
+			});
+			continue;
+		};
+
+		let fields = diff_fields(&left_fields, &right_fields);
+		let reordered = is_reordered(&left_fields, &right_fields);
+		if fields.is_empty() && left_size == right_size && !reordered {
+			unchanged += 1;
+			continue;
+		}
+		structs.push(DnaStructDiff {
+			change: DnaStructChange::Changed,
+			name: name.into(),
+			left_size: Some(left_size),
+			right_size: Some(right_size),
+			fields,
+			reordered,
+		});
+	}
+
+	for (name, (right_size, _)) in right_layouts {
+		structs.push(DnaStructDiff {
+			change: DnaStructChange::Added,
+			name: name.into(),
+			left_size: None,
+			right_size: Some(right_size),
+			fields: Vec::new(),
+			reordered: false,
+		});
+	}
+
+	structs.sort_by(|a, b| a.name.cmp(&b.name));
+
+	DnaDiffResult {
+		structs,
+		unchanged,
+		left_structs,
+		right_structs,
+	}
+}
+
+fn struct_layouts(dna: &Dna) -> Vec<(&str, (u16, Vec<FieldLayout>))> {
+	let mut out = Vec::with_capacity(dna.structs.len());
+	for (sdna_nr, item) in dna.structs.iter().enumerate() {
+		let Some(layout) = dna.struct_layout(sdna_nr as u32) else {
+			continue;
+		};
+		let size = dna.tlen.get(item.type_idx as usize).copied().unwrap_or(0);
+		out.push((dna.type_name(item.type_idx), (size, layout)));
+	}
+	out
+}
+
+fn diff_fields(left: &[FieldLayout], right: &[FieldLayout]) -> Vec<DnaFieldDiff> {
+	let left_by_name: HashMap<&str, &FieldLayout> = left.iter().map(|field| (field.name.as_ref(), field)).collect();
+	let right_by_name: HashMap<&str, &FieldLayout> = right.iter().map(|field| (field.name.as_ref(), field)).collect();
+	let mut out = Vec::new();
+
+	for field in right {
+		match left_by_name.get(field.name.as_ref()) {
+			None => out.push(field_diff(DnaFieldChange::Added, field, None, Some(field))),
+			Some(old) if old.type_name != field.type_name || old.decl != field.decl => {
+				out.push(field_diff(DnaFieldChange::Retyped, field, Some(old), Some(field)))
+			}
+			Some(_) => {}
+		}
+	}
+	for field in left {
+		if !right_by_name.contains_key(field.name.as_ref()) {
+			out.push(field_diff(DnaFieldChange::Removed, field, Some(field), None));
+		}
+	}
+
+	out
+}
+
+fn is_reordered<'a>(left: &'a [FieldLayout], right: &'a [FieldLayout]) -> bool {
+	let shared = |fields: &'a [FieldLayout], other: &[FieldLayout]| -> Vec<&'a str> {
+		let names: HashSet<&str> = other.iter().map(|field| field.name.as_ref()).collect();
+		fields.iter().map(|field| field.name.as_ref()).filter(|name| names.contains(name)).collect()
+	};
+	shared(left, right) != shared(right, left)
+}
+
+fn field_diff(change: DnaFieldChange, field: &FieldLayout, left: Option<&FieldLayout>, right: Option<&FieldLayout>) -> DnaFieldDiff {
+	DnaFieldDiff {
+		change,
+		name: field.name.clone(),
+		left: left.map(side),
+		right: right.map(side),
+	}
+}
+
+fn side(field: &FieldLayout) -> DnaFieldSide {
+	DnaFieldSide {
+		type_name: field.type_name.clone(),
+		decl: field.decl.clone(),
+		offset: field.offset,
+		size: field.size,
+	}
+}
+
+#[cfg(test)]
+mod tests;
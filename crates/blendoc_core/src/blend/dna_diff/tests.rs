@@ -0,0 +1,99 @@
+mod synthetic_schema {
+	use crate::blend::{Dna, DnaField, DnaFieldChange, DnaStruct, DnaStructChange, Endianness, diff_dna};
+
+	/// Build a schema with `Object { <obj_fields> }` plus a one-field marker struct named `extra`.
+	fn dna(obj_fields: &[(&str, &str)], obj_size: u16, extra: &str) -> Dna {
+		let types: Vec<Box<str>> = vec!["char".into(), "int".into(), "float".into(), "double".into(), "Object".into(), extra.into()];
+		let tlen = vec![1, 4, 4, 8, obj_size, 4];
+		let mut names: Vec<Box<str>> = Vec::new();
+		let mut fields = Vec::new();
+		for (type_name, decl) in obj_fields {
+			let type_idx = types.iter().position(|item| item.as_ref() == *type_name).expect("known type") as u16;
+			fields.push(DnaField {
+				type_idx,
+				name_idx: names.len() as u16,
+			});
+			names.push((*decl).into());
+		}
+		let marker = names.len() as u16;
+		names.push("marker".into());
+
+		Dna::from_tables(
+			Endianness::Little,
+			8,
+			names,
+			types,
+			tlen,
+			vec![
+				DnaStruct { type_idx: 4, fields },
+				DnaStruct {
+					type_idx: 5,
+					fields: vec![DnaField { type_idx: 1, name_idx: marker }],
+				},
+			],
+		)
+		.expect("valid dna tables")
+	}
+
+	#[test]
+	fn identical_schemas_have_no_entries() {
+		let left = dna(&[("int", "flag"), ("float", "loc[3]")], 16, "Old");
+		let right = dna(&[("int", "flag"), ("float", "loc[3]")], 16, "Old");
+		let result = diff_dna(&left, &right);
+
+		assert!(result.structs.is_empty());
+		assert_eq!(result.unchanged, 2);
+		assert_eq!(result.left_structs, 2);
+		assert_eq!(result.right_structs, 2);
+	}
+
+	#[test]
+	fn reports_struct_and_field_changes() {
+		let left = dna(&[("int", "flag"), ("float", "loc[3]"), ("char", "*gone")], 24, "Old");
+		let right = dna(&[("int", "flag"), ("double", "loc[3]"), ("int", "added")], 32, "New");
+		let result = diff_dna(&left, &right);
+
+		let summary: Vec<_> = result.structs.iter().map(|item| (item.name.as_ref(), item.change)).collect();
+		assert_eq!(
+			summary,
+			vec![
+				("New", DnaStructChange::Added),
+				("Object", DnaStructChange::Changed),
+				("Old", DnaStructChange::Removed),
+			]
+		);
+
+		let object = &result.structs[1];
+		assert_eq!(object.left_size, Some(24));
+		assert_eq!(object.right_size, Some(32));
+		assert!(!object.reordered);
+
+		let fields: Vec<_> = object.fields.iter().map(|item| (item.name.as_ref(), item.change)).collect();
+		assert_eq!(
+			fields,
+			vec![
+				("loc", DnaFieldChange::Retyped),
+				("added", DnaFieldChange::Added),
+				("gone", DnaFieldChange::Removed),
+			]
+		);
+
+		let loc = &object.fields[0];
+		assert_eq!(loc.left.as_ref().map(|side| side.type_name.as_ref()), Some("float"));
+		assert_eq!(loc.right.as_ref().map(|side| (side.type_name.as_ref(), side.size)), Some(("double", 24)));
+	}
+
+	#[test]
+	fn declarator_change_counts_as_retype_and_order_change_is_flagged() {
+		let left = dna(&[("int", "flag"), ("float", "loc[3]")], 16, "Old");
+		let right = dna(&[("float", "loc[4]"), ("int", "flag")], 16, "Old");
+		let result = diff_dna(&left, &right);
+
+		assert_eq!(result.structs.len(), 1);
+		let object = &result.structs[0];
+		assert!(object.reordered);
+		assert_eq!(object.fields.len(), 1);
+		assert_eq!(object.fields[0].change, DnaFieldChange::Retyped);
+		assert_eq!(object.fields[0].right.as_ref().map(|side| side.decl.as_ref()), Some("loc[4]"));
+	}
+}
@@ -0,0 +1,141 @@
+use crate::blend::{Dna, FieldLayout};
+
+/// Maximum embedded-struct nesting followed when working out struct alignment.
+const MAX_NESTING: u32 = 16;
+
+/// A field placed at an offset its type's alignment does not allow.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MisalignedField {
+	/// Field identifier.
+	pub name: Box<str>,
+	/// Byte offset the decoder places the field at.
+	pub offset: usize,
+	/// Alignment the field's type needs.
+	pub align: usize,
+}
+
+/// One struct whose recomputed size disagrees with `TLEN`, from [`verify_dna_layout`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StructSizeIssue {
+	/// SDNA struct index.
+	pub sdna_nr: u32,
+	/// Struct type name.
+	pub type_name: Box<str>,
+	/// Size recorded in `TLEN`.
+	pub tlen: usize,
+	/// Sum of the field sizes, as the decoder lays them out back to back.
+	pub fields_size: usize,
+	/// Size a C compiler would give the struct, with each field aligned and the tail rounded up.
+	pub computed: usize,
+	/// Struct alignment: the largest field alignment.
+	pub align: usize,
+	/// Fields off their natural alignment.
+	pub misaligned: Vec<MisalignedField>,
+}
+
+/// Result of [`verify_dna_layout`].
+#[derive(Debug, Clone, Default)]
+pub struct DnaLayoutReport {
+	/// Structs checked.
+	pub structs_checked: usize,
+	/// Inconsistent structs, in SDNA order.
+	pub issues: Vec<StructSizeIssue>,
+}
+
+impl DnaLayoutReport {
+	/// Whether every struct's fields add up to its `TLEN`.
+	pub fn is_consistent(&self) -> bool {
+		self.issues.is_empty()
+	}
+}
+
+/// Recompute every struct's size from its fields and compare it to `TLEN`.
+///
+/// Fields align to their natural alignment (pointers to the file pointer
+/// width, primitives to their size up to 8, embedded structs to their own
+/// largest field alignment) and the struct size rounds up to its alignment.
+/// `makesdna` only accepts DNA where this matches the packed field sizes, so
+/// any struct reported here points at a parser bug or an unusual build.
+pub fn verify_dna_layout(dna: &Dna) -> DnaLayoutReport {
+	let mut aligns = vec![None; dna.structs.len()];
+	let mut report = DnaLayoutReport::default();
+
+	for (idx, item) in dna.structs.iter().enumerate() {
+		let sdna_nr = idx as u32;
+		let Some(layout) = dna.struct_layout(sdna_nr) else {
+			continue;
+		};
+		report.structs_checked += 1;
+
+		let mut end = 0_usize;
+		let mut align = 1_usize;
+		let mut misaligned = Vec::new();
+		for field in &layout {
+			let field_align = field_align(dna, field, MAX_NESTING, &mut aligns);
+			align = align.max(field_align);
+			if field.offset % field_align != 0 {
+				misaligned.push(MisalignedField {
+					name: field.name.clone(),
+					offset: field.offset,
+					align: field_align,
+				});
+			}
+			end = end.next_multiple_of(field_align).saturating_add(field.size);
+		}
+
+		let tlen = usize::from(dna.tlen[item.type_idx as usize]);
+		let fields_size = layout.last().map_or(0, |field| field.offset + field.size);
+		let computed = end.next_multiple_of(align);
+		if computed != tlen || fields_size != tlen || !misaligned.is_empty() {
+			report.issues.push(StructSizeIssue {
+				sdna_nr,
+				type_name: dna.type_name(item.type_idx).into(),
+				tlen,
+				fields_size,
+				computed,
+				align,
+				misaligned,
+			});
+		}
+	}
+
+	report
+}
+
+fn field_align(dna: &Dna, field: &FieldLayout, depth_left: u32, aligns: &mut [Option<usize>]) -> usize {
+	if field.is_ptr {
+		return dna.pointer_size.max(1);
+	}
+	if let Some(nested) = field.struct_sdna {
+		return struct_align(dna, nested, depth_left, aligns);
+	}
+	primitive_align(field.element_size)
+}
+
+fn struct_align(dna: &Dna, sdna_nr: u32, depth_left: u32, aligns: &mut [Option<usize>]) -> usize {
+	if let Some(Some(align)) = aligns.get(sdna_nr as usize) {
+		return *align;
+	}
+	if depth_left == 0 {
+		return 1;
+	}
+	let align = dna
+		.struct_layout(sdna_nr)
+		.unwrap_or_default()
+		.iter()
+		.map(|field| field_align(dna, field, depth_left - 1, aligns))
+		.max()
+		.unwrap_or(1);
+	if let Some(slot) = aligns.get_mut(sdna_nr as usize) {
+		*slot = Some(align);
+	}
+	align
+}
+
+/// Largest power of two dividing `size`, capped at 8; 1 for zero-sized types.
+fn primitive_align(size: usize) -> usize {
+	if size == 0 { 1 } else { (1 << size.trailing_zeros()).min(8) }
+}
+
+#[cfg(test)]
+mod tests;
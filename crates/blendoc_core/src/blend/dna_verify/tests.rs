@@ -0,0 +1,93 @@
+use crate::blend::{Dna, DnaField, DnaStruct, Endianness, MisalignedField, verify_dna_layout};
+
+fn field(type_idx: u16, name_idx: u16) -> DnaField {
+	DnaField { type_idx, name_idx }
+}
+
+#[test]
+fn consistent_structs_report_nothing() {
+	let dna = Dna::from_tables(
+		Endianness::Little,
+		8,
+		vec!["*next".into(), "flag".into(), "mode".into(), "inner".into()],
+		vec!["int".into(), "Link".into(), "Outer".into()],
+		vec![4, 16, 24],
+		vec![
+			DnaStruct {
+				type_idx: 1,
+				fields: vec![field(1, 0), field(0, 1), field(0, 2)],
+			},
+			DnaStruct {
+				type_idx: 2,
+				fields: vec![field(1, 3), field(0, 1), field(0, 2)],
+			},
+		],
+	)
+	.expect("valid dna tables");
+
+	let report = verify_dna_layout(&dna);
+	assert_eq!(report.structs_checked, 2);
+	assert!(report.is_consistent(), "{:?}", report.issues);
+}
+
+#[test]
+fn reports_misaligned_fields_and_tlen_mismatches() {
+	let dna = Dna::from_tables(
+		Endianness::Little,
+		8,
+		vec!["c".into(), "i".into(), "d".into(), "inner".into()],
+		vec![
+			"char".into(),
+			"int".into(),
+			"double".into(),
+			"Packed".into(),
+			"Wide".into(),
+			"Holder".into(),
+			"Short".into(),
+		],
+		vec![1, 4, 8, 5, 8, 12, 8],
+		vec![
+			DnaStruct {
+				type_idx: 3,
+				fields: vec![field(0, 0), field(1, 1)],
+			},
+			DnaStruct {
+				type_idx: 4,
+				fields: vec![field(2, 2)],
+			},
+			DnaStruct {
+				type_idx: 5,
+				fields: vec![field(1, 1), field(4, 3)],
+			},
+			DnaStruct {
+				type_idx: 6,
+				fields: vec![field(1, 1)],
+			},
+		],
+	)
+	.expect("valid dna tables");
+
+	let report = verify_dna_layout(&dna);
+	assert_eq!(report.structs_checked, 4);
+	let names: Vec<&str> = report.issues.iter().map(|issue| issue.type_name.as_ref()).collect();
+	assert_eq!(names, ["Packed", "Holder", "Short"]);
+
+	let packed = &report.issues[0];
+	assert_eq!((packed.tlen, packed.fields_size, packed.computed, packed.align), (5, 5, 8, 4));
+	assert_eq!(
+		packed.misaligned,
+		[MisalignedField {
+			name: "i".into(),
+			offset: 1,
+			align: 4,
+		}]
+	);
+
+	let holder = &report.issues[1];
+	assert_eq!((holder.tlen, holder.fields_size, holder.computed, holder.align), (12, 12, 16, 8));
+	assert_eq!(holder.misaligned[0].name.as_ref(), "inner");
+
+	let short = &report.issues[2];
+	assert_eq!((short.tlen, short.fields_size, short.computed), (8, 4, 4));
+	assert!(short.misaligned.is_empty());
+}
@@ -30,6 +30,28 @@ pub enum BlendError {
 		/// Parsed header-size marker.
 		header_size: usize,
 	},
+	/// A value does not fit the on-disk field it is encoded into.
+	#[error("cannot encode {field}={value}: exceeds {max}")]
+	EncodeOutOfRange {
+		/// Encoded field name.
+		field: &'static str,
+		/// Value that was rejected.
+		value: u64,
+		/// Largest value the field can store.
+		max: u64,
+	},
+	/// A re-encoded file does not read back equal to its source.
+	#[error("round-trip mismatch: {detail}")]
+	RoundTripMismatch {
+		/// First difference found.
+		detail: String,
+	},
+	/// A prune plan names blocks the file does not hold.
+	#[error("prune plan does not match file: {detail}")]
+	PrunePlanMismatch {
+		/// First disagreement found.
+		detail: String,
+	},
 	/// Invalid or malformed file header.
 	#[error("invalid header")]
 	InvalidHeader,
@@ -110,10 +132,20 @@ pub enum BlendError {
 		got: String,
 	},
 	/// Requested `ID.name` entry was not found.
-	#[error("ID record not found: {name}")]
+	#[error("ID record not found: {name}{}", did_you_mean(suggestions))]
 	IdRecordNotFound {
 		/// Requested ID name.
 		name: String,
+		/// Close ID selectors, best first (see [`IdIndex::suggest`](crate::blend::IdIndex::suggest)).
+		suggestions: Vec<String>,
+	},
+	/// ID selector glob matched more than one ID.
+	#[error("ID selector {selector} is ambiguous: matches {}", matches.join(", "))]
+	IdSelectorAmbiguous {
+		/// User-provided selector.
+		selector: String,
+		/// Matching ID names.
+		matches: Vec<String>,
 	},
 	/// Linked-list walk field was not found on scanned owner value.
 	#[error("walk missing next field: {field}")]
@@ -121,6 +153,30 @@ pub enum BlendError {
 		/// Requested next-field path.
 		field: String,
 	},
+	/// Node-tree owner had neither `bNodeTree` type nor a non-null `nodetree` pointer.
+	#[error("no node tree on 0x{ptr:016x}")]
+	NodeTreeNotFound {
+		/// Canonical owner pointer.
+		ptr: u64,
+	},
+	/// A typed reader was pointed at a struct of another type.
+	#[error("expected {expected} at 0x{ptr:016x}, found {got}")]
+	UnexpectedStructType {
+		/// Canonical pointer that was decoded.
+		ptr: u64,
+		/// Struct type the reader handles.
+		expected: &'static str,
+		/// Struct type found at the pointer.
+		got: String,
+	},
+	/// A list path did not end on a `ListBase` struct.
+	#[error("{path} is not a ListBase (found {got})")]
+	NotListBase {
+		/// Requested field path.
+		path: String,
+		/// What the path resolved to instead.
+		got: String,
+	},
 	/// Walk start selection did not resolve to a pointer-like value.
 	#[error("walk invalid start value: {got}")]
 	WalkInvalidStart {
@@ -143,12 +199,34 @@ pub enum BlendError {
 		/// Requested 4-byte block code.
 		code: [u8; 4],
 	},
+	/// Requested block code occurrence index was past the last matching block.
+	#[error("block occurrence out of range: {code:?}[{index}] (found {count})")]
+	BlockOccurrenceOutOfRange {
+		/// Requested 4-byte block code.
+		code: [u8; 4],
+		/// Requested zero-based occurrence index.
+		index: usize,
+		/// Number of blocks found with this code.
+		count: usize,
+	},
 	/// CLI block code argument was invalid.
 	#[error("invalid block code: {code}")]
 	InvalidBlockCode {
 		/// User-provided code string.
 		code: String,
 	},
+	/// Compression level outside the codec's accepted range.
+	#[error("invalid {codec} compression level {level} (expected {min}..={max})")]
+	InvalidCompressionLevel {
+		/// Codec label (`zstd`, `gzip`).
+		codec: &'static str,
+		/// Requested level.
+		level: i32,
+		/// Lowest accepted level.
+		min: i32,
+		/// Highest accepted level.
+		max: i32,
+	},
 	/// CLI chase root selector combination was invalid.
 	#[error("invalid chase root selector (provide exactly one of --code, --ptr, --id)")]
 	InvalidChaseRoot,
@@ -261,4 +339,87 @@ pub enum BlendError {
 		/// Original user-provided path string.
 		path: String,
 	},
+	/// Single-result chase was given a path with slice or wildcard steps.
+	#[error("field path {path} can match several values")]
+	FieldPathFansOut {
+		/// Rendered path expression.
+		path: String,
+	},
+	/// Reading a `REV:PATH` blob through git failed.
+	#[error("git input {spec}: {message}")]
+	GitInput {
+		/// User-provided `REV:PATH` spec.
+		spec: String,
+		/// git stderr or spawn failure detail.
+		message: String,
+	},
+	/// `TEST` thumbnail block had invalid dimensions or too few pixel bytes.
+	#[error("invalid thumbnail {width}x{height} in {len}-byte TEST block")]
+	InvalidThumbnail {
+		/// Declared width.
+		width: i32,
+		/// Declared height.
+		height: i32,
+		/// Payload length.
+		len: usize,
+	},
+	/// Missing struct/field error annotated with close schema names and version notes.
+	#[error("{source} ({hint})")]
+	Hinted {
+		/// Original lookup error.
+		source: Box<BlendError>,
+		/// Rendered "did you mean" hint.
+		hint: String,
+	},
+	/// Search predicate expression is invalid.
+	#[error("invalid search predicate: {expr}")]
+	InvalidSearchPredicate {
+		/// Original user-provided predicate string.
+		expr: String,
+	},
+	/// Field expression could not be parsed.
+	#[error("invalid expression `{expr}`: {detail}")]
+	InvalidExpression {
+		/// Original user-provided expression.
+		expr: String,
+		/// What the parser rejected.
+		detail: String,
+	},
+	/// Decode plugin descriptor could not be parsed.
+	#[error("plugin descriptor line {line}: {message}")]
+	PluginDescriptor {
+		/// 1-based line number, `0` for whole-document problems.
+		line: usize,
+		/// Parse failure detail.
+		message: String,
+	},
+	/// Saved ID graph could not be read back.
+	#[error("invalid ID graph artifact: {message}")]
+	IdGraphArtifact {
+		/// Decode failure detail.
+		message: String,
+	},
+	/// Codec operation was compiled out of this build.
+	#[error("{operation} is not available in this build (enable the `{feature}` feature)")]
+	CodecUnavailable {
+		/// Operation that was requested, e.g. `zstd encoding`.
+		operation: &'static str,
+		/// Cargo feature that provides it.
+		feature: &'static str,
+	},
+	/// Pure-Rust zstd decoder rejected the stream.
+	#[error("zstd: {message}")]
+	Zstd {
+		/// Decoder failure detail.
+		message: String,
+	},
+}
+
+/// ` (did you mean `A`, `B`?)` for a non-empty suggestion list.
+fn did_you_mean(suggestions: &[String]) -> String {
+	if suggestions.is_empty() {
+		return String::new();
+	}
+	let names: Vec<String> = suggestions.iter().map(|name| format!("`{name}`")).collect();
+	format!(" (did you mean {}?)", names.join(", "))
 }
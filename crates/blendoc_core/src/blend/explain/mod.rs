@@ -0,0 +1,200 @@
+use std::collections::{BTreeMap, HashSet};
+
+use crate::blend::{DecodeOptions, Dna, PointerIndex, Result, StructValue, Value, WarningCollector, decode_ptr_instance, decode_struct_instance};
+
+/// One labelled set of decode options to compare.
+#[derive(Debug, Clone)]
+pub struct DecodeVariant {
+	/// Short label (`default`, `padding`, `strict`).
+	pub label: Box<str>,
+	/// Options used for this decode.
+	pub options: DecodeOptions,
+}
+
+/// How one variant's decode went.
+#[derive(Debug, Clone)]
+pub struct VariantOutcome {
+	/// Variant label.
+	pub label: Box<str>,
+	/// Decode error message, when the variant failed.
+	pub error: Option<String>,
+	/// Number of leaf fields produced.
+	pub leaves: usize,
+	/// Best-effort decode warnings raised by this variant.
+	pub warnings: usize,
+}
+
+/// One leaf field whose value differs between variants.
+#[derive(Debug, Clone)]
+pub struct FieldDivergence {
+	/// Dotted field path (`id.name`, `mat[1].flag`).
+	pub path: Box<str>,
+	/// Value per variant, in variant order; `None` when the field is absent or the decode failed.
+	pub values: Vec<Option<Value>>,
+}
+
+/// Field-by-field comparison of one struct decoded under several option sets.
+#[derive(Debug, Clone)]
+pub struct DecodeExplanation {
+	/// Outcome per variant, in input order.
+	pub variants: Vec<VariantOutcome>,
+	/// Leaf fields that are missing in some variants or decode to different values.
+	pub divergences: Vec<FieldDivergence>,
+	/// Leaf fields identical across every successful variant.
+	pub agreed: usize,
+}
+
+/// Variants toggling padding, strict layout, and char-array strings on top of `base`.
+pub fn standard_decode_variants(base: &DecodeOptions) -> Vec<DecodeVariant> {
+	let variant = |label: &str, include_padding: bool, strict_layout: bool, decode_char_arrays_as_string: bool| DecodeVariant {
+		label: label.into(),
+		options: DecodeOptions {
+			include_padding,
+			strict_layout,
+			decode_char_arrays_as_string,
+			warnings: None,
+			..base.clone()
+		},
+	};
+
+	vec![
+		variant("default", false, false, true),
+		variant("padding", true, false, true),
+		variant("strict", false, true, true),
+		variant("padding+strict", true, true, true),
+		variant("raw_chars", false, false, false),
+	]
+}
+
+/// Decode the struct element at `ptr` under each variant and report divergent fields.
+pub fn explain_decode_ptr(dna: &Dna, index: &PointerIndex<'_>, ptr: u64, variants: &[DecodeVariant]) -> DecodeExplanation {
+	explain_with(variants, |options| decode_ptr_instance(dna, index, ptr, options).map(|(_, value)| value))
+}
+
+/// Decode raw struct bytes under each variant and report divergent fields.
+pub fn explain_decode_struct(dna: &Dna, sdna_nr: u32, bytes: &[u8], variants: &[DecodeVariant]) -> DecodeExplanation {
+	explain_with(variants, |options| decode_struct_instance(dna, sdna_nr, bytes, options))
+}
+
+fn explain_with(variants: &[DecodeVariant], decode: impl Fn(&DecodeOptions) -> Result<StructValue>) -> DecodeExplanation {
+	let mut outcomes = Vec::with_capacity(variants.len());
+	let mut leaves: Vec<Option<BTreeMap<String, Value>>> = Vec::with_capacity(variants.len());
+
+	for variant in variants {
+		let collector = WarningCollector::new();
+		let options = DecodeOptions {
+			warnings: Some(collector.clone()),
+			..variant.options.clone()
+		};
+		match decode(&options) {
+			Ok(value) => {
+				let mut flat = BTreeMap::new();
+				flatten_struct("", &value, &mut flat);
+				outcomes.push(VariantOutcome {
+					label: variant.label.clone(),
+					error: None,
+					leaves: flat.len(),
+					warnings: collector.len(),
+				});
+				leaves.push(Some(flat));
+			}
+			Err(err) => {
+				outcomes.push(VariantOutcome {
+					label: variant.label.clone(),
+					error: Some(err.to_string()),
+					leaves: 0,
+					warnings: collector.len(),
+				});
+				leaves.push(None);
+			}
+		}
+	}
+
+	let mut paths: Vec<&String> = Vec::new();
+	let mut seen = HashSet::new();
+	for flat in leaves.iter().flatten() {
+		for path in flat.keys() {
+			if seen.insert(path) {
+				paths.push(path);
+			}
+		}
+	}
+	paths.sort();
+
+	let mut divergences = Vec::new();
+	let mut agreed = 0;
+	for path in paths {
+		let values: Vec<Option<&Value>> = leaves.iter().map(|flat| flat.as_ref().and_then(|flat| flat.get(path))).collect();
+		let present: Vec<Option<&Value>> = values.iter().zip(&leaves).filter(|(_, flat)| flat.is_some()).map(|(value, _)| *value).collect();
+		let uniform = present.windows(2).all(|pair| match (pair[0], pair[1]) {
+			(Some(left), Some(right)) => same_value(left, right),
+			(None, None) => true,
+			_ => false,
+		});
+		if uniform {
+			agreed += 1;
+		} else {
+			divergences.push(FieldDivergence {
+				path: path.as_str().into(),
+				values: values.into_iter().map(|value| value.cloned()).collect(),
+			});
+		}
+	}
+
+	DecodeExplanation {
+		variants: outcomes,
+		divergences,
+		agreed,
+	}
+}
+
+/// Flatten embedded structs (and arrays of structs) to dotted leaf paths.
+fn flatten_struct(prefix: &str, value: &StructValue, out: &mut BTreeMap<String, Value>) {
+	for field in &value.fields {
+		let path = format!("{prefix}{}", field.name);
+		flatten_value(path, &field.value, out);
+	}
+}
+
+fn flatten_value(path: String, value: &Value, out: &mut BTreeMap<String, Value>) {
+	match value {
+		Value::Struct(item) => flatten_struct(&format!("{path}."), item, out),
+		Value::Array(items) if items.iter().any(|item| matches!(item, Value::Struct(_))) => {
+			for (idx, item) in items.iter().enumerate() {
+				flatten_value(format!("{path}[{idx}]"), item, out);
+			}
+		}
+		_ => {
+			out.insert(path, value.clone());
+		}
+	}
+}
+
+/// Structural equality; floats compare bitwise so NaN payloads count as equal.
+fn same_value(left: &Value, right: &Value) -> bool {
+	match (left, right) {
+		(Value::Null, Value::Null) => true,
+		(Value::Bool(left), Value::Bool(right)) => left == right,
+		(Value::I64(left), Value::I64(right)) => left == right,
+		(Value::U64(left), Value::U64(right)) => left == right,
+		(Value::F32(left), Value::F32(right)) => left.to_bits() == right.to_bits(),
+		(Value::F64(left), Value::F64(right)) => left.to_bits() == right.to_bits(),
+		(Value::Bytes(left), Value::Bytes(right)) => left == right,
+		(Value::String(left), Value::String(right)) => left == right,
+		(Value::Ptr(left), Value::Ptr(right)) => left == right,
+		(Value::Array(left), Value::Array(right)) => left.len() == right.len() && left.iter().zip(right).all(|(left, right)| same_value(left, right)),
+		(Value::Struct(left), Value::Struct(right)) => {
+			left.type_name == right.type_name
+				&& left.fields.len() == right.fields.len()
+				&& left
+					.fields
+					.iter()
+					.zip(&right.fields)
+					.all(|(left, right)| left.name == right.name && same_value(&left.value, &right.value))
+		}
+		_ => false,
+	}
+}
+
+#[cfg(test)]
+mod tests;
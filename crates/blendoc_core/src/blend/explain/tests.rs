@@ -0,0 +1,61 @@
+mod synthetic_explain {
+	use crate::blend::{DecodeOptions, Dna, DnaField, DnaStruct, Endianness, Value, explain_decode_struct, standard_decode_variants};
+
+	/// `Thing { char name[4]; char _pad[4]; int value; }` with a 16-byte DNA size (4 uncovered bytes).
+	fn dna() -> Dna {
+		Dna::from_tables(
+			Endianness::Little,
+			8,
+			vec!["name[4]".into(), "_pad[4]".into(), "value".into()],
+			vec!["char".into(), "int".into(), "Thing".into()],
+			vec![1, 4, 16],
+			vec![DnaStruct {
+				type_idx: 2,
+				fields: vec![
+					DnaField { type_idx: 0, name_idx: 0 },
+					DnaField { type_idx: 0, name_idx: 1 },
+					DnaField { type_idx: 1, name_idx: 2 },
+				],
+			}],
+		)
+		.expect("valid dna tables")
+	}
+
+	fn payload() -> Vec<u8> {
+		let mut out = b"abc\0".to_vec();
+		out.extend_from_slice(&[0; 4]);
+		out.extend_from_slice(&7_i32.to_le_bytes());
+		out.extend_from_slice(&[0; 4]);
+		out
+	}
+
+	#[test]
+	fn reports_variant_failures_and_divergent_fields() {
+		let variants = standard_decode_variants(&DecodeOptions::default());
+		let labels: Vec<&str> = variants.iter().map(|item| item.label.as_ref()).collect();
+		assert_eq!(labels, vec!["default", "padding", "strict", "padding+strict", "raw_chars"]);
+
+		let result = explain_decode_struct(&dna(), 0, &payload(), &variants);
+
+		let default = &result.variants[0];
+		assert!(default.error.is_none());
+		assert_eq!(default.leaves, 2);
+		assert_eq!(default.warnings, 1, "leftover bytes are reported as a warning");
+		assert!(result.variants[2].error.as_deref().is_some_and(|err| err.contains("Thing")));
+		assert!(result.variants[3].error.is_some());
+		assert_eq!(result.variants[1].leaves, 3);
+
+		let paths: Vec<&str> = result.divergences.iter().map(|item| item.path.as_ref()).collect();
+		assert_eq!(paths, vec!["_pad", "name"]);
+		assert_eq!(result.agreed, 1, "value decodes identically everywhere");
+
+		let name = &result.divergences[1];
+		assert!(matches!(&name.values[0], Some(Value::String(text)) if text.as_ref() == "abc"));
+		assert!(matches!(&name.values[4], Some(Value::Array(_)) | Some(Value::Bytes(_))));
+		assert!(name.values[2].is_none());
+
+		let pad = &result.divergences[0];
+		assert!(pad.values[0].is_none());
+		assert!(pad.values[1].is_some());
+	}
+}
@@ -0,0 +1,667 @@
+use std::cmp::Ordering;
+
+use crate::blend::{BlendError, Result, StructValue, Value};
+
+/// Maximum parenthesis/operator nesting accepted by [`Expr::parse`].
+const MAX_DEPTH: usize = 64;
+
+/// Parsed expression over the fields of one decoded struct.
+///
+/// Supports number, string (`'..'` or `".."`), `true`/`false`/`null`
+/// literals; field references (`flag`, `id.name`, `loc[2]`); arithmetic
+/// `+ - * / %` (`+` concatenates when either side is a string); comparisons
+/// `== != < <= > >=` and `~=` (substring); `&&`, `||`, `!`; and the functions
+/// `len`, `abs`, `min`, `max`, `lower`, `upper`.
+///
+/// Fields resolve like [`FieldPredicate`] paths: a dotted path is followed
+/// from the struct root, a bare name matches the first field with that name
+/// at any depth. Evaluation never fails; missing fields and type mismatches
+/// produce [`Value::Null`], which compares false.
+///
+/// [`FieldPredicate`]: crate::blend::FieldPredicate
+#[derive(Debug, Clone, PartialEq)]
+pub struct Expr {
+	root: Node,
+}
+
+impl Expr {
+	/// Parse an expression such as `dimx*dimy > 4` or `len(name) > 60`.
+	pub fn parse(text: &str) -> Result<Self> {
+		let invalid = |detail: &str| BlendError::InvalidExpression {
+			expr: text.to_owned(),
+			detail: detail.to_owned(),
+		};
+		let tokens = tokenize(text).map_err(|detail| invalid(&detail))?;
+		let mut parser = Parser { tokens, pos: 0, depth: 0 };
+		let root = parser.or().map_err(|detail| invalid(&detail))?;
+		if let Some(token) = parser.tokens.get(parser.pos) {
+			return Err(invalid(&format!("unexpected {}", token.describe())));
+		}
+		Ok(Self { root })
+	}
+
+	/// Evaluate against one decoded struct.
+	pub fn eval(&self, value: &StructValue) -> Value {
+		self.root.eval(value)
+	}
+
+	/// Whether the expression evaluates to a truthy value (see [`is_truthy`]).
+	pub fn matches(&self, value: &StructValue) -> bool {
+		is_truthy(&self.eval(value))
+	}
+}
+
+/// Named computed column (`area=dimx*dimy`).
+#[derive(Debug, Clone, PartialEq)]
+pub struct ExprColumn {
+	/// Column name.
+	pub name: Box<str>,
+	/// Expression producing the column value.
+	pub expr: Expr,
+}
+
+impl ExprColumn {
+	/// Parse `name=expression`; the name must be a plain identifier.
+	pub fn parse(text: &str) -> Result<Self> {
+		let invalid = || BlendError::InvalidExpression {
+			expr: text.to_owned(),
+			detail: "expected name=expression".to_owned(),
+		};
+		let (name, expr) = text.split_once('=').ok_or_else(invalid)?;
+		let name = name.trim();
+		if !is_ident(name) || expr.starts_with('=') {
+			return Err(invalid());
+		}
+		Ok(Self {
+			name: name.into(),
+			expr: Expr::parse(expr)?,
+		})
+	}
+}
+
+/// Truthiness used by filters: non-zero numbers and pointers, non-empty strings, arrays, and bytes, `true`, and structs.
+pub fn is_truthy(value: &Value) -> bool {
+	match value {
+		Value::Null => false,
+		Value::Bool(flag) => *flag,
+		Value::I64(number) => *number != 0,
+		Value::U64(number) | Value::Ptr(number) => *number != 0,
+		Value::F32(number) => *number != 0.0,
+		Value::F64(number) => *number != 0.0,
+		Value::String(text) => !text.is_empty(),
+		Value::Bytes(bytes) => !bytes.is_empty(),
+		Value::Array(items) => !items.is_empty(),
+		Value::Struct(_) => true,
+	}
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum BinaryOp {
+	Add,
+	Sub,
+	Mul,
+	Div,
+	Rem,
+	Eq,
+	Ne,
+	Lt,
+	Le,
+	Gt,
+	Ge,
+	Contains,
+	And,
+	Or,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Func {
+	Len,
+	Abs,
+	Min,
+	Max,
+	Lower,
+	Upper,
+}
+
+impl Func {
+	fn from_name(name: &str) -> Option<Self> {
+		Some(match name {
+			"len" => Self::Len,
+			"abs" => Self::Abs,
+			"min" => Self::Min,
+			"max" => Self::Max,
+			"lower" => Self::Lower,
+			"upper" => Self::Upper,
+			_ => return None,
+		})
+	}
+
+	fn accepts(self, args: usize) -> bool {
+		match self {
+			Self::Min | Self::Max => args >= 1,
+			_ => args == 1,
+		}
+	}
+}
+
+#[derive(Debug, Clone, PartialEq)]
+struct Segment {
+	name: Box<str>,
+	indices: Vec<usize>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Node {
+	Literal(Literal),
+	Field(Vec<Segment>),
+	Neg(Box<Node>),
+	Not(Box<Node>),
+	Binary(BinaryOp, Box<Node>, Box<Node>),
+	Call(Func, Vec<Node>),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Literal {
+	Null,
+	Bool(bool),
+	Int(i64),
+	Float(f64),
+	Str(Box<str>),
+}
+
+impl Node {
+	fn eval(&self, root: &StructValue) -> Value {
+		match self {
+			Self::Literal(literal) => match literal {
+				Literal::Null => Value::Null,
+				Literal::Bool(flag) => Value::Bool(*flag),
+				Literal::Int(number) => Value::I64(*number),
+				Literal::Float(number) => Value::F64(*number),
+				Literal::Str(text) => Value::String(text.clone()),
+			},
+			Self::Field(path) => resolve(root, path).cloned().unwrap_or(Value::Null),
+			Self::Neg(inner) => match number(&inner.eval(root)) {
+				Some(Num::Int(number)) => number.checked_neg().map_or(Value::F64(-(number as f64)), Value::I64),
+				Some(Num::Float(number)) => Value::F64(-number),
+				None => Value::Null,
+			},
+			Self::Not(inner) => Value::Bool(!is_truthy(&inner.eval(root))),
+			Self::Binary(BinaryOp::And, lhs, rhs) => Value::Bool(is_truthy(&lhs.eval(root)) && is_truthy(&rhs.eval(root))),
+			Self::Binary(BinaryOp::Or, lhs, rhs) => Value::Bool(is_truthy(&lhs.eval(root)) || is_truthy(&rhs.eval(root))),
+			Self::Binary(op, lhs, rhs) => binary(*op, &lhs.eval(root), &rhs.eval(root)),
+			Self::Call(func, args) => call(*func, args.iter().map(|arg| arg.eval(root)).collect()),
+		}
+	}
+}
+
+#[derive(Debug, Clone, Copy)]
+enum Num {
+	Int(i64),
+	Float(f64),
+}
+
+impl Num {
+	fn as_f64(self) -> f64 {
+		match self {
+			Self::Int(number) => number as f64,
+			Self::Float(number) => number,
+		}
+	}
+
+	fn into_value(self) -> Value {
+		match self {
+			Self::Int(number) => Value::I64(number),
+			Self::Float(number) => Value::F64(number),
+		}
+	}
+}
+
+fn number(value: &Value) -> Option<Num> {
+	Some(match value {
+		Value::Bool(flag) => Num::Int(i64::from(*flag)),
+		Value::I64(number) => Num::Int(*number),
+		Value::U64(number) | Value::Ptr(number) => i64::try_from(*number).map_or(Num::Float(*number as f64), Num::Int),
+		Value::F32(number) => Num::Float(f64::from(*number)),
+		Value::F64(number) => Num::Float(*number),
+		_ => return None,
+	})
+}
+
+fn text(value: &Value) -> Option<String> {
+	match value {
+		Value::String(text) => Some(text.to_string()),
+		other => number(other).map(|number| match number {
+			Num::Int(number) => number.to_string(),
+			Num::Float(number) => number.to_string(),
+		}),
+	}
+}
+
+fn binary(op: BinaryOp, lhs: &Value, rhs: &Value) -> Value {
+	match op {
+		BinaryOp::Add if matches!(lhs, Value::String(_)) || matches!(rhs, Value::String(_)) => match (text(lhs), text(rhs)) {
+			(Some(lhs), Some(rhs)) => Value::String(format!("{lhs}{rhs}").into()),
+			_ => Value::Null,
+		},
+		BinaryOp::Add | BinaryOp::Sub | BinaryOp::Mul | BinaryOp::Div | BinaryOp::Rem => {
+			let (Some(lhs), Some(rhs)) = (number(lhs), number(rhs)) else {
+				return Value::Null;
+			};
+			arithmetic(op, lhs, rhs).map_or(Value::Null, Num::into_value)
+		}
+		BinaryOp::Contains => match (text(lhs), text(rhs)) {
+			(Some(lhs), Some(rhs)) => Value::Bool(lhs.contains(&rhs)),
+			_ => Value::Bool(false),
+		},
+		_ => {
+			let ordering = match (lhs, rhs) {
+				(Value::Null, Value::Null) => Some(Ordering::Equal),
+				(Value::String(lhs), Value::String(rhs)) => Some(lhs.cmp(rhs)),
+				_ => match (number(lhs), number(rhs)) {
+					(Some(Num::Int(lhs)), Some(Num::Int(rhs))) => Some(lhs.cmp(&rhs)),
+					(Some(lhs), Some(rhs)) => lhs.as_f64().partial_cmp(&rhs.as_f64()),
+					_ => None,
+				},
+			};
+			Value::Bool(match ordering {
+				Some(ordering) => compare(op, ordering),
+				None => op == BinaryOp::Ne,
+			})
+		}
+	}
+}
+
+fn arithmetic(op: BinaryOp, lhs: Num, rhs: Num) -> Option<Num> {
+	if let (Num::Int(lhs), Num::Int(rhs)) = (lhs, rhs) {
+		let exact = match op {
+			BinaryOp::Add => lhs.checked_add(rhs),
+			BinaryOp::Sub => lhs.checked_sub(rhs),
+			BinaryOp::Mul => lhs.checked_mul(rhs),
+			BinaryOp::Rem => return lhs.checked_rem(rhs).map(Num::Int),
+			_ => None,
+		};
+		if let Some(exact) = exact {
+			return Some(Num::Int(exact));
+		}
+	}
+	let (lhs, rhs) = (lhs.as_f64(), rhs.as_f64());
+	let out = match op {
+		BinaryOp::Add => lhs + rhs,
+		BinaryOp::Sub => lhs - rhs,
+		BinaryOp::Mul => lhs * rhs,
+		BinaryOp::Div if rhs != 0.0 => lhs / rhs,
+		BinaryOp::Rem if rhs != 0.0 => lhs % rhs,
+		_ => return None,
+	};
+	Some(Num::Float(out))
+}
+
+fn compare(op: BinaryOp, ordering: Ordering) -> bool {
+	match op {
+		BinaryOp::Eq => ordering == Ordering::Equal,
+		BinaryOp::Ne => ordering != Ordering::Equal,
+		BinaryOp::Lt => ordering == Ordering::Less,
+		BinaryOp::Le => ordering != Ordering::Greater,
+		BinaryOp::Gt => ordering == Ordering::Greater,
+		BinaryOp::Ge => ordering != Ordering::Less,
+		_ => false,
+	}
+}
+
+fn call(func: Func, args: Vec<Value>) -> Value {
+	match func {
+		Func::Len => match &args[0] {
+			Value::String(text) => Value::I64(text.chars().count() as i64),
+			Value::Bytes(bytes) => Value::I64(bytes.len() as i64),
+			Value::Array(items) => Value::I64(items.len() as i64),
+			_ => Value::Null,
+		},
+		Func::Abs => match number(&args[0]) {
+			Some(Num::Int(number)) => number.checked_abs().map_or(Value::F64((number as f64).abs()), Value::I64),
+			Some(Num::Float(number)) => Value::F64(number.abs()),
+			None => Value::Null,
+		},
+		Func::Min | Func::Max => {
+			let wanted = if func == Func::Min { Ordering::Less } else { Ordering::Greater };
+			let mut best: Option<Num> = None;
+			for arg in &args {
+				let Some(candidate) = number(arg) else {
+					return Value::Null;
+				};
+				let better = best.is_none_or(|best| candidate.as_f64().partial_cmp(&best.as_f64()) == Some(wanted));
+				if better {
+					best = Some(candidate);
+				}
+			}
+			best.map_or(Value::Null, Num::into_value)
+		}
+		Func::Lower => match &args[0] {
+			Value::String(text) => Value::String(text.to_lowercase().into()),
+			_ => Value::Null,
+		},
+		Func::Upper => match &args[0] {
+			Value::String(text) => Value::String(text.to_uppercase().into()),
+			_ => Value::Null,
+		},
+	}
+}
+
+fn resolve<'v>(root: &'v StructValue, path: &[Segment]) -> Option<&'v Value> {
+	let (first, rest) = path.split_first()?;
+	let mut current = if rest.is_empty() {
+		find_named(root, &first.name)?
+	} else {
+		&root.fields.iter().find(|field| field.name == first.name)?.value
+	};
+	current = index(current, &first.indices)?;
+	for segment in rest {
+		let Value::Struct(item) = current else {
+			return None;
+		};
+		current = index(&item.fields.iter().find(|field| field.name == segment.name)?.value, &segment.indices)?;
+	}
+	Some(current)
+}
+
+fn find_named<'v>(value: &'v StructValue, name: &str) -> Option<&'v Value> {
+	value.fields.iter().find_map(|field| {
+		if *field.name == *name {
+			Some(&field.value)
+		} else if let Value::Struct(nested) = &field.value {
+			find_named(nested, name)
+		} else {
+			None
+		}
+	})
+}
+
+fn index<'v>(mut value: &'v Value, indices: &[usize]) -> Option<&'v Value> {
+	for idx in indices {
+		let Value::Array(items) = value else {
+			return None;
+		};
+		value = items.get(*idx)?;
+	}
+	Some(value)
+}
+
+fn is_ident(text: &str) -> bool {
+	let mut chars = text.chars();
+	chars.next().is_some_and(|first| first.is_ascii_alphabetic() || first == '_') && chars.all(|next| next.is_ascii_alphanumeric() || next == '_')
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Token {
+	Number(Literal),
+	Str(Box<str>),
+	Ident(Box<str>),
+	Index(usize),
+	Dot,
+	Op(&'static str),
+	Open,
+	Close,
+	Comma,
+}
+
+impl Token {
+	fn describe(&self) -> String {
+		match self {
+			Self::Number(_) => "number".to_owned(),
+			Self::Str(_) => "string".to_owned(),
+			Self::Ident(name) => format!("`{name}`"),
+			Self::Index(idx) => format!("`[{idx}]`"),
+			Self::Dot => "`.`".to_owned(),
+			Self::Op(op) => format!("`{op}`"),
+			Self::Open => "`(`".to_owned(),
+			Self::Close => "`)`".to_owned(),
+			Self::Comma => "`,`".to_owned(),
+		}
+	}
+}
+
+/// Operator tokens, longest first so `<=` wins over `<`.
+const OPERATORS: [&str; 17] = ["==", "!=", "<=", ">=", "~=", "&&", "||", "=", "<", ">", "+", "-", "*", "/", "%", "!", "~"];
+
+fn tokenize(text: &str) -> std::result::Result<Vec<Token>, String> {
+	let bytes = text.as_bytes();
+	let mut tokens = Vec::new();
+	let mut pos = 0;
+	while pos < bytes.len() {
+		let byte = bytes[pos];
+		if byte.is_ascii_whitespace() {
+			pos += 1;
+		} else if byte.is_ascii_digit() {
+			let start = pos;
+			if text[pos..].starts_with("0x") || text[pos..].starts_with("0X") {
+				pos += 2;
+				while pos < bytes.len() && bytes[pos].is_ascii_hexdigit() {
+					pos += 1;
+				}
+				let value = u64::from_str_radix(&text[start + 2..pos], 16).map_err(|_| format!("bad hex literal `{}`", &text[start..pos]))?;
+				tokens.push(Token::Number(i64::try_from(value).map_or(Literal::Float(value as f64), Literal::Int)));
+				continue;
+			}
+			while pos < bytes.len() && (bytes[pos].is_ascii_digit() || bytes[pos] == b'.' || bytes[pos] == b'e' || bytes[pos] == b'E') {
+				pos += 1;
+			}
+			let raw = &text[start..pos];
+			let literal = match raw.parse::<i64>() {
+				Ok(number) => Literal::Int(number),
+				Err(_) => Literal::Float(raw.parse::<f64>().map_err(|_| format!("bad number `{raw}`"))?),
+			};
+			tokens.push(Token::Number(literal));
+		} else if byte.is_ascii_alphabetic() || byte == b'_' {
+			let start = pos;
+			while pos < bytes.len() && (bytes[pos].is_ascii_alphanumeric() || bytes[pos] == b'_') {
+				pos += 1;
+			}
+			tokens.push(Token::Ident(text[start..pos].into()));
+		} else if byte == b'\'' || byte == b'"' {
+			let end = text[pos + 1..].find(char::from(byte)).ok_or("unterminated string")?;
+			tokens.push(Token::Str(text[pos + 1..pos + 1 + end].into()));
+			pos += end + 2;
+		} else if byte == b'[' {
+			let end = text[pos..].find(']').ok_or("unterminated `[`")?;
+			let idx = text[pos + 1..pos + end]
+				.trim()
+				.parse::<usize>()
+				.map_err(|_| "array index must be a number".to_owned())?;
+			tokens.push(Token::Index(idx));
+			pos += end + 1;
+		} else if let Some(token) = match byte {
+			b'.' => Some(Token::Dot),
+			b'(' => Some(Token::Open),
+			b')' => Some(Token::Close),
+			b',' => Some(Token::Comma),
+			_ => None,
+		} {
+			tokens.push(token);
+			pos += 1;
+		} else if let Some(op) = OPERATORS.iter().find(|op| text[pos..].starts_with(**op)) {
+			if *op == "~" {
+				return Err("`~` must be `~=`".to_owned());
+			}
+			tokens.push(Token::Op(op));
+			pos += op.len();
+		} else {
+			let ch = text[pos..].chars().next().unwrap_or_default();
+			return Err(format!("unexpected character `{ch}`"));
+		}
+	}
+	Ok(tokens)
+}
+
+struct Parser {
+	tokens: Vec<Token>,
+	pos: usize,
+	depth: usize,
+}
+
+type ParseResult = std::result::Result<Node, String>;
+
+impl Parser {
+	fn peek(&self) -> Option<&Token> {
+		self.tokens.get(self.pos)
+	}
+
+	fn eat_op(&mut self, ops: &[&'static str]) -> Option<&'static str> {
+		match self.peek() {
+			Some(Token::Op(op)) if ops.contains(op) => {
+				let op = *op;
+				self.pos += 1;
+				Some(op)
+			}
+			_ => None,
+		}
+	}
+
+	fn or(&mut self) -> ParseResult {
+		let mut lhs = self.and()?;
+		while self.eat_op(&["||"]).is_some() {
+			lhs = Node::Binary(BinaryOp::Or, Box::new(lhs), Box::new(self.and()?));
+		}
+		Ok(lhs)
+	}
+
+	fn and(&mut self) -> ParseResult {
+		let mut lhs = self.comparison()?;
+		while self.eat_op(&["&&"]).is_some() {
+			lhs = Node::Binary(BinaryOp::And, Box::new(lhs), Box::new(self.comparison()?));
+		}
+		Ok(lhs)
+	}
+
+	fn comparison(&mut self) -> ParseResult {
+		let lhs = self.sum()?;
+		let Some(op) = self.eat_op(&["==", "=", "!=", "<", "<=", ">", ">=", "~="]) else {
+			return Ok(lhs);
+		};
+		let op = match op {
+			"==" | "=" => BinaryOp::Eq,
+			"!=" => BinaryOp::Ne,
+			"<" => BinaryOp::Lt,
+			"<=" => BinaryOp::Le,
+			">" => BinaryOp::Gt,
+			">=" => BinaryOp::Ge,
+			_ => BinaryOp::Contains,
+		};
+		Ok(Node::Binary(op, Box::new(lhs), Box::new(self.sum()?)))
+	}
+
+	fn sum(&mut self) -> ParseResult {
+		let mut lhs = self.product()?;
+		while let Some(op) = self.eat_op(&["+", "-"]) {
+			let op = if op == "+" { BinaryOp::Add } else { BinaryOp::Sub };
+			lhs = Node::Binary(op, Box::new(lhs), Box::new(self.product()?));
+		}
+		Ok(lhs)
+	}
+
+	fn product(&mut self) -> ParseResult {
+		let mut lhs = self.unary()?;
+		while let Some(op) = self.eat_op(&["*", "/", "%"]) {
+			let op = match op {
+				"*" => BinaryOp::Mul,
+				"/" => BinaryOp::Div,
+				_ => BinaryOp::Rem,
+			};
+			lhs = Node::Binary(op, Box::new(lhs), Box::new(self.unary()?));
+		}
+		Ok(lhs)
+	}
+
+	fn unary(&mut self) -> ParseResult {
+		self.depth += 1;
+		if self.depth > MAX_DEPTH {
+			return Err("expression nests too deeply".to_owned());
+		}
+		let node = match self.eat_op(&["-", "!"]) {
+			Some("-") => Node::Neg(Box::new(self.unary()?)),
+			Some(_) => Node::Not(Box::new(self.unary()?)),
+			None => self.primary()?,
+		};
+		self.depth -= 1;
+		Ok(node)
+	}
+
+	fn primary(&mut self) -> ParseResult {
+		let token = self.peek().cloned().ok_or("unexpected end of expression")?;
+		self.pos += 1;
+		match token {
+			Token::Number(literal) => Ok(Node::Literal(literal)),
+			Token::Str(text) => Ok(Node::Literal(Literal::Str(text))),
+			Token::Open => {
+				let inner = self.or()?;
+				self.expect(&Token::Close)?;
+				Ok(inner)
+			}
+			Token::Ident(name) => match name.as_ref() {
+				"true" => Ok(Node::Literal(Literal::Bool(true))),
+				"false" => Ok(Node::Literal(Literal::Bool(false))),
+				"null" => Ok(Node::Literal(Literal::Null)),
+				_ if self.peek() == Some(&Token::Open) => self.call(&name),
+				_ => self.field(name),
+			},
+			other => Err(format!("unexpected {}", other.describe())),
+		}
+	}
+
+	fn call(&mut self, name: &str) -> ParseResult {
+		let func = Func::from_name(name).ok_or_else(|| format!("unknown function `{name}`"))?;
+		self.pos += 1;
+		let mut args = Vec::new();
+		if self.peek() != Some(&Token::Close) {
+			loop {
+				args.push(self.or()?);
+				if self.peek() != Some(&Token::Comma) {
+					break;
+				}
+				self.pos += 1;
+			}
+		}
+		self.expect(&Token::Close)?;
+		if !func.accepts(args.len()) {
+			return Err(format!("wrong number of arguments to `{name}`"));
+		}
+		Ok(Node::Call(func, args))
+	}
+
+	fn field(&mut self, first: Box<str>) -> ParseResult {
+		let mut path = vec![Segment {
+			name: first,
+			indices: self.indices(),
+		}];
+		while self.peek() == Some(&Token::Dot) {
+			self.pos += 1;
+			let Some(Token::Ident(name)) = self.peek().cloned() else {
+				return Err("expected field name after `.`".to_owned());
+			};
+			self.pos += 1;
+			path.push(Segment { name, indices: self.indices() });
+		}
+		Ok(Node::Field(path))
+	}
+
+	fn indices(&mut self) -> Vec<usize> {
+		let mut out = Vec::new();
+		while let Some(Token::Index(idx)) = self.peek() {
+			out.push(*idx);
+			self.pos += 1;
+		}
+		out
+	}
+
+	fn expect(&mut self, token: &Token) -> std::result::Result<(), String> {
+		if self.peek() == Some(token) {
+			self.pos += 1;
+			return Ok(());
+		}
+		Err(match self.peek() {
+			Some(found) => format!("expected {}, found {}", token.describe(), found.describe()),
+			None => format!("expected {}", token.describe()),
+		})
+	}
+}
+
+#[cfg(test)]
+mod tests;
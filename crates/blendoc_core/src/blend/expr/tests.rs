@@ -0,0 +1,94 @@
+use crate::blend::{BlendError, Expr, ExprColumn, FieldValue, StructValue, Value, is_truthy};
+
+fn object() -> StructValue {
+	let field = |name: &str, value: Value| FieldValue { name: name.into(), value };
+	StructValue {
+		type_name: "Object".into(),
+		fields: vec![
+			field(
+				"id",
+				Value::Struct(StructValue {
+					type_name: "ID".into(),
+					fields: vec![field("name", Value::String("OBCube".into()))],
+				}),
+			),
+			field("dimx", Value::F32(2.0)),
+			field("dimy", Value::I64(3)),
+			field("flag", Value::U64(4)),
+			field("parent", Value::Ptr(0)),
+			field("loc", Value::Array(vec![Value::F32(1.0), Value::F32(2.0), Value::F32(3.5)])),
+		],
+	}
+}
+
+fn eval(text: &str) -> Value {
+	Expr::parse(text).expect("expression parses").eval(&object())
+}
+
+#[test]
+fn evaluates_arithmetic_with_precedence() {
+	assert!(matches!(eval("1 + 2 * 3"), Value::I64(7)));
+	assert!(matches!(eval("(1 + 2) * 3"), Value::I64(9)));
+	assert!(matches!(eval("7 % 4 - -1"), Value::I64(4)));
+	assert!(matches!(eval("dimx * dimy"), Value::F64(area) if area == 6.0));
+	assert!(matches!(eval("flag / 8"), Value::F64(half) if half == 0.5));
+	assert!(matches!(eval("1 / 0"), Value::Null));
+	assert!(matches!(eval("0x10 + 1"), Value::I64(17)));
+}
+
+#[test]
+fn resolves_bare_dotted_and_indexed_fields() {
+	assert!(matches!(eval("name"), Value::String(name) if &*name == "OBCube"));
+	assert!(matches!(eval("id.name"), Value::String(name) if &*name == "OBCube"));
+	assert!(matches!(eval("loc[2]"), Value::F32(z) if z == 3.5));
+	assert!(matches!(eval("missing"), Value::Null));
+	assert!(matches!(eval("loc[9]"), Value::Null));
+}
+
+#[test]
+fn compares_and_combines_conditions() {
+	let object = object();
+	let holds = |text: &str| Expr::parse(text).expect("expression parses").matches(&object);
+
+	assert!(holds("len(name) == 6"));
+	assert!(holds("name ~= 'Cu' && flag > 3"));
+	assert!(holds("lower(name) == \"obcube\" || false"));
+	assert!(holds("!parent"));
+	assert!(holds("parent == 0"));
+	assert!(holds("max(dimx, dimy, 1) = 3"));
+	assert!(holds("abs(-dimy) >= 3"));
+	assert!(holds("name + '!' == 'OBCube!'"));
+	assert!(!holds("missing > 0"));
+	assert!(holds("missing != 0"));
+	assert!(!holds("name > 3"));
+}
+
+#[test]
+fn rejects_malformed_expressions() {
+	for text in ["", "1 +", "(flag", "len(name, 2)", "nope(1)", "flag ~ 2", "'open", "a.", "flag $ 2", "1 2"] {
+		let err = Expr::parse(text).expect_err("expression should fail");
+		assert!(matches!(err, BlendError::InvalidExpression { .. }), "{text}: {err}");
+	}
+	let deep = format!("{}1{}", "(".repeat(200), ")".repeat(200));
+	assert!(Expr::parse(&deep).is_err());
+}
+
+#[test]
+fn parses_named_columns() {
+	let column = ExprColumn::parse("area=dimx*dimy").expect("column parses");
+	assert_eq!(&*column.name, "area");
+	assert!(matches!(column.expr.eval(&object()), Value::F64(area) if area == 6.0));
+
+	for text in ["dimx*dimy", "a==b", "2x=1", "=1"] {
+		assert!(ExprColumn::parse(text).is_err(), "{text}");
+	}
+}
+
+#[test]
+fn truthiness_follows_value_kind() {
+	assert!(!is_truthy(&Value::Null));
+	assert!(!is_truthy(&Value::String("".into())));
+	assert!(is_truthy(&Value::Ptr(0x10)));
+	assert!(!is_truthy(&Value::F64(0.0)));
+	assert!(is_truthy(&Value::Array(vec![Value::Null])));
+}
@@ -1,9 +1,14 @@
 use std::collections::HashMap;
 use std::fs;
+use std::io::Read;
+use std::ops::Deref;
 use std::path::Path;
 
+#[cfg(feature = "native")]
+use memmap2::Mmap;
+
 use crate::blend::compression::decode_bytes;
-use crate::blend::{BlendError, BlendHeader, Block, BlockIter, Compression, Dna, PointerIndex, Result};
+use crate::blend::{BlendError, BlendHeader, Block, BlockIter, Compression, Dna, PointerIndex, Result, codes};
 
 /// Opened blend container with decoded bytes and parsed header.
 pub struct BlendFile {
@@ -11,15 +16,88 @@ pub struct BlendFile {
 	pub header: BlendHeader,
 	/// Compression mode detected for source bytes.
 	pub compression: Compression,
-	bytes: Vec<u8>,
+	bytes: Backing,
 	blocks_offset: usize,
 }
 
+/// Storage behind decoded file bytes.
+enum Backing {
+	Owned(Vec<u8>),
+	#[cfg(feature = "native")]
+	Mapped(Mmap),
+}
+
+impl Deref for Backing {
+	type Target = [u8];
+
+	fn deref(&self) -> &[u8] {
+		match self {
+			Self::Owned(bytes) => bytes,
+			#[cfg(feature = "native")]
+			Self::Mapped(map) => map,
+		}
+	}
+}
+
 impl BlendFile {
 	/// Read, decode, and parse a blend file from disk.
 	pub fn open(path: impl AsRef<Path>) -> Result<Self> {
-		let raw = fs::read(path)?;
+		Self::from_bytes(fs::read(path)?)
+	}
+
+	/// Decode and parse a blend file from raw (possibly compressed) bytes.
+	pub fn from_bytes(raw: Vec<u8>) -> Result<Self> {
 		let (compression, bytes) = decode_bytes(raw)?;
+		Self::from_backing(compression, Backing::Owned(bytes))
+	}
+
+	/// Read a whole byte stream (pipe, subprocess output, archive entry) and parse it.
+	pub fn from_reader(mut reader: impl Read) -> Result<Self> {
+		let mut raw = Vec::new();
+		reader.read_to_end(&mut raw)?;
+		Self::from_bytes(raw)
+	}
+
+	/// Open a blend file through a read-only memory map.
+	///
+	/// Uncompressed files are never copied into memory, so block payloads are
+	/// only paged in when touched. zstd-compressed files still have to be
+	/// decompressed and fall back to the owned path of [`BlendFile::open`].
+	#[cfg(feature = "native")]
+	pub fn open_mmap(path: impl AsRef<Path>) -> Result<Self> {
+		let file = fs::File::open(path)?;
+		// SAFETY: the map is read-only and private to this value; like any
+		// mmap reader we assume the file is not truncated while it is open.
+		let map = unsafe { Mmap::map(&file)? };
+		if map.starts_with(crate::blend::compression::BLEND_MAGIC) {
+			return Self::from_backing(Compression::None, Backing::Mapped(map));
+		}
+
+		let (compression, bytes) = decode_bytes(map.to_vec())?;
+		Self::from_backing(compression, Backing::Owned(bytes))
+	}
+
+	/// Open a blend file from disk; without the `native` feature there is no
+	/// memory map and this is the same as [`BlendFile::open`].
+	#[cfg(not(feature = "native"))]
+	pub fn open_mmap(path: impl AsRef<Path>) -> Result<Self> {
+		Self::open(path)
+	}
+
+	/// Return whether this build can memory-map files (the `native` feature).
+	pub fn mmap_supported() -> bool {
+		cfg!(feature = "native")
+	}
+
+	/// Return whether block payloads are served from a memory map.
+	pub fn is_mapped(&self) -> bool {
+		#[cfg(feature = "native")]
+		return matches!(self.bytes, Backing::Mapped(_));
+		#[cfg(not(feature = "native"))]
+		false
+	}
+
+	fn from_backing(compression: Compression, bytes: Backing) -> Result<Self> {
 		let header = BlendHeader::parse(&bytes)?;
 		if header.header_size > bytes.len() {
 			return Err(BlendError::InvalidHeader);
@@ -58,7 +136,7 @@ impl BlendFile {
 			stats.block_count += 1;
 			stats.last_code = block.head.code;
 			*stats.codes.entry(block.head.code).or_insert(0) += 1;
-			if block.head.code == *b"DNA1" {
+			if block.head.code == codes::DNA1 {
 				stats.has_dna1 = true;
 			}
 			if block.head.is_endb() {
@@ -71,19 +149,37 @@ impl BlendFile {
 
 	/// Parse and return the first `DNA1` block as SDNA tables.
 	pub fn dna(&self) -> Result<Dna> {
-		let block = self.find_first_block_by_code(*b"DNA1")?.ok_or(BlendError::DnaNotFound)?;
+		let block = self.find_first_block_by_code(codes::DNA1)?.ok_or(BlendError::DnaNotFound)?;
 		Dna::parse(block.payload, self.header.endianness, self.header.pointer_size)
 	}
 
 	/// Find the first block matching a four-byte code.
 	pub fn find_first_block_by_code(&self, code: [u8; 4]) -> Result<Option<Block<'_>>> {
-		for block in self.blocks() {
+		self.find_blocks_by_code(code).next().transpose()
+	}
+
+	/// Iterate blocks matching a four-byte code in file order; parse errors are passed through.
+	pub fn find_blocks_by_code(&self, code: [u8; 4]) -> impl Iterator<Item = Result<Block<'_>>> + '_ {
+		self.blocks().filter(move |block| block.as_ref().map_or(true, |block| block.head.code == code))
+	}
+
+	/// Find the zero-based `nth` block matching a four-byte code.
+	///
+	/// Fails with [`BlendError::BlockNotFound`] when no block has the code and
+	/// with [`BlendError::BlockOccurrenceOutOfRange`] when `nth` is past the last one.
+	pub fn find_nth_block_by_code(&self, code: [u8; 4], nth: usize) -> Result<Block<'_>> {
+		let mut count = 0_usize;
+		for block in self.find_blocks_by_code(code) {
 			let block = block?;
-			if block.head.code == code {
-				return Ok(Some(block));
+			if count == nth {
+				return Ok(block);
 			}
+			count += 1;
+		}
+		if count == 0 {
+			return Err(BlendError::BlockNotFound { code });
 		}
-		Ok(None)
+		Err(BlendError::BlockOccurrenceOutOfRange { code, index: nth, count })
 	}
 
 	/// Build an index for old-pointer resolution.
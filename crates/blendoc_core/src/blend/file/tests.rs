@@ -29,3 +29,289 @@ mod fixtures_day1 {
 		assert_eq!(stats.last_code, *b"ENDB");
 	}
 }
+
+#[cfg(feature = "native")]
+mod synthetic_mmap {
+	use crate::blend::{BlendError, BlendFile, Compression, decode_bytes, encode_bytes};
+
+	fn write_minimal_blend(name: &str) -> std::path::PathBuf {
+		let mut bytes = b"BLENDER-v300".to_vec();
+		bytes.extend_from_slice(b"ENDB");
+		bytes.extend_from_slice(&[0_u8; 20]);
+
+		let path = std::env::temp_dir().join(format!("blendoc_{name}_{}.blend", std::process::id()));
+		std::fs::write(&path, bytes).expect("temp blend written");
+		path
+	}
+
+	#[test]
+	fn open_mmap_matches_owned_open_for_uncompressed_files() {
+		let path = write_minimal_blend("mmap");
+		let owned = BlendFile::open(&path).expect("owned open");
+		let mapped = BlendFile::open_mmap(&path).expect("mapped open");
+
+		assert!(!owned.is_mapped());
+		assert!(mapped.is_mapped());
+		assert_eq!(mapped.compression, Compression::None);
+		assert_eq!(mapped.header, owned.header);
+		assert_eq!(mapped.bytes(), owned.bytes());
+
+		let stats = mapped.scan_block_stats().expect("scan succeeds");
+		assert_eq!(stats.block_count, 1);
+		assert!(stats.has_endb);
+
+		std::fs::remove_file(path).expect("temp blend removed");
+	}
+
+	#[test]
+	fn recompressed_streams_decode_to_identical_bytes() {
+		let path = write_minimal_blend("recompress");
+		let original = BlendFile::open(&path).expect("owned open");
+
+		for compression in [Compression::Zstd, Compression::Gzip, Compression::None] {
+			let encoded = encode_bytes(original.bytes(), compression, None).expect("encode succeeds");
+			std::fs::write(&path, &encoded).expect("encoded blend written");
+
+			let reopened = BlendFile::open_mmap(&path).expect("encoded open");
+			assert_eq!(reopened.compression, compression);
+			assert_eq!(reopened.bytes(), original.bytes());
+		}
+		assert!(matches!(
+			encode_bytes(original.bytes(), Compression::Zstd, Some(23)),
+			Err(BlendError::InvalidCompressionLevel { level: 23, .. })
+		));
+		assert!(encode_bytes(original.bytes(), Compression::Gzip, Some(10)).is_err());
+
+		std::fs::remove_file(path).expect("temp blend removed");
+	}
+
+	#[test]
+	fn zstd_output_carries_a_seek_table() {
+		let mut bytes = b"BLENDER-v300".to_vec();
+		bytes.extend((0..600 * 1024).map(|idx| (idx % 251) as u8));
+		let encoded = encode_bytes(&bytes, Compression::Zstd, None).expect("encode succeeds");
+
+		let u32_at = |pos: usize| u32::from_le_bytes(encoded[pos..pos + 4].try_into().expect("4 bytes"));
+		let end = encoded.len();
+		assert_eq!(u32_at(end - 4), 0x8F92_EAB1);
+		assert_eq!(encoded[end - 5], 0, "no checksums");
+		let frames = u32_at(end - 9) as usize;
+		assert_eq!(frames, 3);
+
+		let table = end - 9 - frames * 8;
+		assert_eq!(u32_at(table - 8), 0x184D_2A5E);
+		assert_eq!(u32_at(table - 4) as usize, frames * 8 + 9);
+		let entries: Vec<(usize, usize)> = (0..frames)
+			.map(|idx| (u32_at(table + idx * 8) as usize, u32_at(table + idx * 8 + 4) as usize))
+			.collect();
+		assert_eq!(entries.iter().map(|entry| entry.0).sum::<usize>(), table - 8);
+		assert_eq!(entries.iter().map(|entry| entry.1).sum::<usize>(), bytes.len());
+		assert_eq!(entries[0].1, 256 * 1024);
+
+		let (first_frame, _) = encoded.split_at(entries[0].0);
+		assert_eq!(
+			zstd::bulk::decompress(first_frame, entries[0].1).expect("frame decodes alone"),
+			bytes[..entries[0].1]
+		);
+		assert_eq!(decode_bytes(encoded).expect("decodes").1, bytes);
+	}
+
+	#[test]
+	fn from_reader_parses_streamed_bytes() {
+		let path = write_minimal_blend("reader");
+		let bytes = std::fs::read(&path).expect("temp blend read");
+		std::fs::remove_file(path).expect("temp blend removed");
+
+		let blend = BlendFile::from_reader(bytes.as_slice()).expect("stream parses");
+		assert_eq!(blend.compression, Compression::None);
+		assert_eq!(blend.bytes(), bytes.as_slice());
+	}
+
+	#[test]
+	fn mapped_payload_chunks_cover_block_in_order() {
+		let payload: Vec<u8> = (0..10).collect();
+		let mut bytes = b"BLENDER-v300".to_vec();
+		bytes.extend_from_slice(b"DATA");
+		bytes.extend_from_slice(&(payload.len() as i32).to_le_bytes());
+		bytes.extend_from_slice(&0x1000_u64.to_le_bytes());
+		bytes.extend_from_slice(&0_u32.to_le_bytes());
+		bytes.extend_from_slice(&1_u32.to_le_bytes());
+		bytes.extend_from_slice(&payload);
+		bytes.extend_from_slice(b"ENDB");
+		bytes.extend_from_slice(&[0_u8; 20]);
+
+		let path = std::env::temp_dir().join(format!("blendoc_chunks_{}.blend", std::process::id()));
+		std::fs::write(&path, bytes).expect("temp blend written");
+		let mapped = BlendFile::open_mmap(&path).expect("mapped open");
+
+		let block = mapped.blocks().next().expect("data block present").expect("data block parses");
+		let mut chunks = block.payload_chunks(4);
+		assert_eq!(chunks.len(), 3);
+		assert_eq!(chunks.next(), Some(&payload[0..4]));
+		assert_eq!(chunks.offset(), 4);
+		assert_eq!(chunks.by_ref().map(<[u8]>::len).collect::<Vec<_>>(), vec![4, 2]);
+		assert_eq!(chunks.next(), None);
+		assert_eq!(block.payload_chunks(0).count(), payload.len());
+
+		std::fs::remove_file(path).expect("temp blend removed");
+	}
+}
+
+mod synthetic_nth_block {
+	use blendoc_testkit::builder::{BlendBuilder, HeaderKind};
+
+	use crate::blend::{BlendError, BlendFile};
+
+	#[test]
+	fn nth_block_by_code_walks_matches_in_file_order() {
+		let mut builder = BlendBuilder::new(HeaderKind::Legacy8, 402, false);
+		builder.sdna_mut().add_type("int", 4);
+		for (code, old) in [(*b"OB\0\0", 0x1000), (*b"ME\0\0", 0x2000), (*b"OB\0\0", 0x3000), (*b"OB\0\0", 0x4000)] {
+			builder.block(code, 0, old, 1, vec![0; 4]);
+		}
+		let blend = BlendFile::from_bytes(builder.build()).expect("synthetic file opens");
+
+		let olds: Vec<u64> = blend
+			.find_blocks_by_code(*b"OB\0\0")
+			.map(|block| block.expect("block parses").head.old)
+			.collect();
+		assert_eq!(olds, [0x1000, 0x3000, 0x4000]);
+		assert_eq!(blend.find_nth_block_by_code(*b"OB\0\0", 2).expect("third object").head.old, 0x4000);
+
+		let err = blend.find_nth_block_by_code(*b"OB\0\0", 3).expect_err("only three objects");
+		assert!(matches!(err, BlendError::BlockOccurrenceOutOfRange { index: 3, count: 3, .. }));
+		let err = blend.find_nth_block_by_code(*b"CA\0\0", 0).expect_err("no cameras");
+		assert!(matches!(err, BlendError::BlockNotFound { .. }));
+	}
+}
+
+/// Re-encode header, every block header, and the `DNA1` payload of `blend`,
+/// checking each against the bytes it was read from.
+fn assert_round_trip(blend: &crate::blend::BlendFile) {
+	let bytes = blend.bytes();
+	let mut header = Vec::new();
+	blend.header.write(&mut header).expect("header writes");
+	assert_eq!(header, bytes[..blend.header.header_size]);
+
+	let size = blend.header.bhead_layout().expect("layout").capabilities().size;
+	for block in blend.blocks() {
+		let block = block.expect("block parses");
+		let mut head = Vec::new();
+		block.head.write(&mut head, blend.header).expect("bhead writes");
+		assert_eq!(head, bytes[block.file_offset..block.file_offset + size], "block at {:#x}", block.file_offset);
+	}
+
+	let dna_block = blend.find_first_block_by_code(*b"DNA1").expect("scan").expect("DNA1 block");
+	let mut dna = Vec::new();
+	blend.dna().expect("dna").write(&mut dna).expect("dna writes");
+	// Writers may pad the block past the last struct; that tail must be zero.
+	assert_eq!(dna, dna_block.payload[..dna.len()]);
+	assert!(dna_block.payload[dna.len()..].iter().all(|byte| *byte == 0));
+}
+
+mod synthetic_round_trip {
+	use blendoc_testkit::builder::{HeaderKind, scene_file};
+
+	use crate::blend::BlendFile;
+
+	#[test]
+	fn scene_files_re_encode_byte_for_byte() {
+		for kind in [HeaderKind::Legacy4, HeaderKind::Legacy8, HeaderKind::Large] {
+			for big_endian in [false, true] {
+				let blend = BlendFile::from_bytes(scene_file(kind, big_endian)).expect("synthetic file parses");
+				super::assert_round_trip(&blend);
+			}
+		}
+	}
+}
+
+mod synthetic_big_endian_pipeline {
+	use blendoc_testkit::builder::{HeaderKind, scene_file, scene_ptrs};
+
+	use crate::blend::{
+		BlendFile, DecodeOptions, Endianness, IdGraphOptions, IdIndex, PointerIndex, RefScanOptions, build_id_graph, decode_ptr_instance, scan_id_blocks,
+		scan_refs_from_ptr,
+	};
+
+	/// Everything the pipeline reads from one file, rendered without byte order.
+	fn summary(bytes: Vec<u8>, kind: HeaderKind) -> Vec<String> {
+		let blend = BlendFile::from_bytes(bytes).expect("synthetic file parses");
+		let dna = blend.dna().expect("dna parses");
+		let index = PointerIndex::build(&blend).expect("pointer index builds");
+		let ids = IdIndex::build(scan_id_blocks(&blend, &dna).expect("ids scan"));
+		let [scene, object, _, verts, _] = scene_ptrs(kind);
+
+		let mut out = vec![format!("{:?} {}", dna.tlen, dna.structs.len())];
+		for block in blend.blocks() {
+			let block = block.expect("block parses");
+			out.push(format!("{:?} {}", block.head, block.file_offset));
+		}
+		for entry in index.entries() {
+			out.push(format!("{:#x}..{:#x}", entry.start_old, entry.end_old));
+		}
+		for record in &ids.records {
+			out.push(format!("{} {:#x} {:?}", record.id_name, record.old_ptr, record.lib));
+		}
+		let refs = scan_refs_from_ptr(&dna, &index, &ids, scene, &RefScanOptions::default()).expect("refs scan");
+		out.extend(
+			refs.iter()
+				.map(|item| format!("{} {:#x} {:?}", item.field, item.ptr, item.resolved.as_ref().map(|target| target.canonical))),
+		);
+		let graph = build_id_graph(&dna, &index, &ids, &IdGraphOptions::default()).expect("id graph builds");
+		out.extend(graph.edges.iter().map(|edge| format!("{:#x} -> {:#x} {}", edge.from, edge.to, edge.field)));
+		for ptr in [object, verts] {
+			let (canonical, value) = decode_ptr_instance(&dna, &index, ptr, &DecodeOptions::default()).expect("decode");
+			out.push(format!("{canonical:#x} {value:?}"));
+		}
+		out
+	}
+
+	#[test]
+	fn big_endian_files_read_the_same_as_little_endian() {
+		for kind in [HeaderKind::Legacy4, HeaderKind::Legacy8, HeaderKind::Large] {
+			let big = scene_file(kind, true);
+			assert_eq!(BlendFile::from_bytes(big.clone()).expect("parses").header.endianness, Endianness::Big);
+			let little = summary(scene_file(kind, false), kind);
+			let big = summary(big, kind);
+			assert!(little.len() > 10, "{kind:?}: {little:?}");
+			assert_eq!(big, little, "{kind:?}");
+		}
+	}
+}
+
+#[cfg(all(feature = "native", feature = "pure-zstd"))]
+mod synthetic_pure_zstd {
+	use blendoc_testkit::builder::{HeaderKind, scene_file};
+
+	use crate::blend::compression::decode_zstd_pure;
+	use crate::blend::{Compression, encode_bytes};
+
+	#[test]
+	fn pure_decoder_matches_libzstd_across_frames() {
+		let bytes = scene_file(HeaderKind::Large, false);
+		let (head, tail) = bytes.split_at(bytes.len() / 2);
+		let mut encoded = encode_bytes(head, Compression::Zstd, None).expect("encode head");
+		encoded.extend_from_slice(&encode_bytes(tail, Compression::Zstd, Some(19)).expect("encode tail"));
+		// Trailing skippable frame, as a seek table would be.
+		encoded.extend_from_slice(&0x184D_2A5E_u32.to_le_bytes());
+		encoded.extend_from_slice(&4_u32.to_le_bytes());
+		encoded.extend_from_slice(&[0xAA; 4]);
+
+		assert_eq!(decode_zstd_pure(&encoded).expect("pure decode"), bytes);
+		assert!(decode_zstd_pure(&encoded[..encoded.len() / 3]).is_err());
+	}
+}
+
+mod fixtures_round_trip {
+	use blendoc_testkit::fixture_path;
+
+	use crate::blend::BlendFile;
+
+	#[test]
+	fn fixtures_re_encode_byte_for_byte() {
+		for name in ["character.blend", "sword.blend", "v5.1_character.blend", "v5.1_sword.blend"] {
+			let blend = BlendFile::open(fixture_path(name)).expect("fixture opens");
+			super::assert_round_trip(&blend);
+		}
+	}
+}
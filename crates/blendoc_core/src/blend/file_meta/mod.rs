@@ -0,0 +1,227 @@
+use std::sync::Arc;
+
+use crate::blend::{BlendFile, DecodeOptions, Dna, Result, StructValue, Value, codes, decode_block_instances, version_label};
+
+/// When and by which Blender build a file was last saved, from its `GLOB` block and current scene.
+///
+/// Every field except `version` is optional: old files lack the build
+/// fields, and files saved without a scene have no frame settings.
+#[derive(Debug, Clone, Default)]
+pub struct FileMeta {
+	/// Header version (`402` for 4.2).
+	pub version: u16,
+	/// `FileGlobal.subversion`.
+	pub subversion: Option<i64>,
+	/// `FileGlobal.minversion`: oldest Blender version that can read the file.
+	pub min_version: Option<i64>,
+	/// `FileGlobal.minsubversion`.
+	pub min_subversion: Option<i64>,
+	/// Commit time of the saving build, in Unix seconds.
+	pub build_timestamp: Option<i64>,
+	/// Commit hash of the saving build.
+	pub build_hash: Option<Arc<str>>,
+	/// Path the file was saved to (`FileGlobal.filepath`, `filename` before 2.80).
+	pub saved_path: Option<Arc<str>>,
+	/// Active scene at save time.
+	pub scene: Option<SceneFrames>,
+}
+
+/// Frame settings of the scene active when a file was saved.
+#[derive(Debug, Clone)]
+pub struct SceneFrames {
+	/// Scene `ID.name`.
+	pub name: Arc<str>,
+	/// `RenderData.cfra`.
+	pub current: Option<i64>,
+	/// `RenderData.sfra`.
+	pub start: Option<i64>,
+	/// `RenderData.efra`.
+	pub end: Option<i64>,
+}
+
+impl FileMeta {
+	/// `4.2.3`-style label from the header version and subversion.
+	pub fn saved_version(&self) -> String {
+		match self.subversion {
+			Some(sub) => format!("{}.{sub}", version_label(self.version)),
+			None => version_label(self.version),
+		}
+	}
+
+	/// `min_version` as a `4.2.3`-style label.
+	pub fn min_version_label(&self) -> Option<String> {
+		let version = u16::try_from(self.min_version?).ok()?;
+		Some(match self.min_subversion {
+			Some(sub) => format!("{}.{sub}", version_label(version)),
+			None => version_label(version),
+		})
+	}
+
+	/// Build commit time as an RFC 3339 UTC timestamp.
+	pub fn build_date(&self) -> Option<String> {
+		self.build_timestamp.map(format_unix_time)
+	}
+}
+
+/// Read save metadata from the `GLOB` block and the scene it marks as current.
+///
+/// Missing blocks and fields leave the matching [`FileMeta`] fields empty;
+/// only decode failures of a present `GLOB` block are returned as `Err`.
+pub fn read_file_meta(file: &BlendFile, dna: &Dna) -> Result<FileMeta> {
+	let mut meta = FileMeta {
+		version: file.header.version,
+		..FileMeta::default()
+	};
+	let decode = DecodeOptions::default();
+
+	let mut current_scene = None;
+	if let Some(block) = file.find_first_block_by_code(codes::GLOB)?
+		&& let Value::Struct(global) = decode_block_instances(dna, &block, &decode)?
+	{
+		meta.subversion = int_field(&global, "subversion");
+		meta.min_version = int_field(&global, "minversion");
+		meta.min_subversion = int_field(&global, "minsubversion");
+		meta.build_timestamp = int_field(&global, "build_commit_timestamp").filter(|time| *time > 0);
+		meta.build_hash = string_field(&global, "build_hash").filter(|hash| hash.as_ref() != "unknown");
+		meta.saved_path = string_field(&global, "filepath").or_else(|| string_field(&global, "filename"));
+		current_scene = ptr_field(&global, "curscene").filter(|ptr| *ptr != 0);
+	}
+
+	let mut scene_block = None;
+	for block in file.find_blocks_by_code(codes::ID_SCE) {
+		let block = block?;
+		let is_current = current_scene == Some(block.head.old);
+		if is_current || scene_block.is_none() {
+			scene_block = Some(block);
+		}
+		if is_current {
+			break;
+		}
+	}
+	// A scene that no longer decodes under this SDNA leaves `scene` empty.
+	if let Some(block) = scene_block
+		&& let Ok(Value::Struct(scene)) = decode_block_instances(dna, &block, &decode)
+	{
+		let render = match field(&scene, "r") {
+			Some(Value::Struct(render)) => Some(render),
+			_ => None,
+		};
+		let name = match field(&scene, "id") {
+			Some(Value::Struct(id)) => string_field(id, "name"),
+			_ => None,
+		};
+		meta.scene = name.map(|name| SceneFrames {
+			name,
+			current: render.and_then(|render| int_field(render, "cfra")),
+			start: render.and_then(|render| int_field(render, "sfra")),
+			end: render.and_then(|render| int_field(render, "efra")),
+		});
+	}
+
+	Ok(meta)
+}
+
+/// Format Unix seconds as an RFC 3339 UTC timestamp (`2024-05-01T12:30:00Z`).
+pub fn format_unix_time(seconds: i64) -> String {
+	let days = seconds.div_euclid(86_400);
+	let secs = seconds.rem_euclid(86_400);
+	let (year, month, day) = civil_from_days(days);
+	format!("{year:04}-{month:02}-{day:02}T{:02}:{:02}:{:02}Z", secs / 3600, secs % 3600 / 60, secs % 60)
+}
+
+/// Parse `YYYY-MM-DD`, `YYYY-MM-DDTHH:MM[:SS][Z]`, or `@<unix seconds>` as UTC Unix seconds.
+pub fn parse_unix_time(text: &str) -> Option<i64> {
+	let text = text.trim();
+	if let Some(seconds) = text.strip_prefix('@') {
+		return seconds.parse().ok();
+	}
+	let text = text.strip_suffix('Z').unwrap_or(text);
+	let (date, time) = match text.split_once(['T', ' ']) {
+		Some((date, time)) => (date, Some(time)),
+		None => (text, None),
+	};
+
+	let mut parts = date.splitn(3, '-');
+	let year: i64 = parts.next()?.parse().ok()?;
+	let month: u32 = parts.next()?.parse().ok()?;
+	let day: u32 = parts.next()?.parse().ok()?;
+	if !(1..=12).contains(&month) || day == 0 || day > days_in_month(year, month) {
+		return None;
+	}
+
+	let mut seconds = 0;
+	if let Some(time) = time {
+		let mut parts = time.splitn(3, ':');
+		let hour: i64 = parts.next()?.parse().ok()?;
+		let minute: i64 = parts.next()?.parse().ok()?;
+		let second: i64 = parts.next().map_or(Some(0), |value| value.parse().ok())?;
+		if hour > 23 || minute > 59 || second > 60 {
+			return None;
+		}
+		seconds = hour * 3600 + minute * 60 + second;
+	}
+	Some(days_from_civil(year, month, day) * 86_400 + seconds)
+}
+
+fn days_in_month(year: i64, month: u32) -> u32 {
+	match month {
+		2 if year % 4 == 0 && (year % 100 != 0 || year % 400 == 0) => 29,
+		2 => 28,
+		4 | 6 | 9 | 11 => 30,
+		_ => 31,
+	}
+}
+
+/// Days since 1970-01-01 for a proleptic Gregorian date.
+fn days_from_civil(year: i64, month: u32, day: u32) -> i64 {
+	let year = if month <= 2 { year - 1 } else { year };
+	let era = year.div_euclid(400);
+	let year_of_era = year - era * 400;
+	let month = i64::from(month);
+	let day_of_year = (153 * (month + if month > 2 { -3 } else { 9 }) + 2) / 5 + i64::from(day) - 1;
+	let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+	era * 146_097 + day_of_era - 719_468
+}
+
+/// Inverse of [`days_from_civil`].
+fn civil_from_days(days: i64) -> (i64, u32, u32) {
+	let days = days + 719_468;
+	let era = days.div_euclid(146_097);
+	let day_of_era = days - era * 146_097;
+	let year_of_era = (day_of_era - day_of_era / 1460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+	let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+	let shifted_month = (5 * day_of_year + 2) / 153;
+	let day = (day_of_year - (153 * shifted_month + 2) / 5 + 1) as u32;
+	let month = if shifted_month < 10 { shifted_month + 3 } else { shifted_month - 9 } as u32;
+	let year = year_of_era + era * 400 + i64::from(month <= 2);
+	(year, month, day)
+}
+
+fn field<'v>(item: &'v StructValue, name: &str) -> Option<&'v Value> {
+	item.fields.iter().find(|field| field.name.as_ref() == name).map(|field| &field.value)
+}
+
+fn ptr_field(item: &StructValue, name: &str) -> Option<u64> {
+	match field(item, name)? {
+		Value::Ptr(ptr) => Some(*ptr),
+		_ => None,
+	}
+}
+
+fn string_field(item: &StructValue, name: &str) -> Option<Arc<str>> {
+	match field(item, name)? {
+		Value::String(value) if !value.is_empty() => Some(Arc::from(value.as_ref())),
+		_ => None,
+	}
+}
+
+fn int_field(item: &StructValue, name: &str) -> Option<i64> {
+	match field(item, name)? {
+		Value::I64(value) => Some(*value),
+		Value::U64(value) => i64::try_from(*value).ok(),
+		_ => None,
+	}
+}
+
+#[cfg(test)]
+mod tests;
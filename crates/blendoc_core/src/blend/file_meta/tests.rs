@@ -0,0 +1,102 @@
+mod synthetic_file_meta {
+	use blendoc_testkit::builder::{BlendBuilder, HeaderKind, SCENE_ID_NAMES, scene_file};
+
+	use crate::blend::{BlendFile, format_unix_time, parse_unix_time, read_file_meta};
+
+	/// `GLOB` (4.2 sub 3, build 2024-05-01T12:30:00Z) marking the second of two scenes as current.
+	fn saved_file() -> Vec<u8> {
+		let mut builder = BlendBuilder::new(HeaderKind::Legacy8, 402, false);
+		let sdna = builder.sdna_mut();
+		sdna.add_struct("Link", &[("Link", "*next"), ("Link", "*prev")]);
+		sdna.add_type("char", 1);
+		sdna.add_type("short", 2);
+		sdna.add_type("int", 4);
+		sdna.add_type("uint64_t", 8);
+		sdna.add_type("void", 0);
+		sdna.add_struct("ID", &[("void", "*next"), ("void", "*prev"), ("void", "*lib"), ("char", "name[24]")]);
+		sdna.add_struct("RenderData", &[("int", "cfra"), ("int", "sfra"), ("int", "efra"), ("int", "pad")]);
+		let scene = sdna.add_struct("Scene", &[("ID", "id"), ("RenderData", "r")]);
+		let global = sdna.add_struct(
+			"FileGlobal",
+			&[
+				("char", "subvstr[4]"),
+				("short", "subversion"),
+				("short", "minversion"),
+				("short", "minsubversion"),
+				("short", "_pad"),
+				("void", "*curscene"),
+				("uint64_t", "build_commit_timestamp"),
+				("char", "build_hash[16]"),
+				("char", "filepath[64]"),
+			],
+		);
+
+		let mut payload = builder.payload();
+		payload
+			.raw(b"   3")
+			.raw(&3_i16.to_le_bytes())
+			.raw(&400_i16.to_le_bytes())
+			.raw(&1_i16.to_le_bytes())
+			.raw(&[0; 2])
+			.ptr(0x2000)
+			.raw(&1_714_566_600_u64.to_le_bytes())
+			.name("a1b2c3d4e5f6", 16)
+			.name("/shots/sh010.blend", 64);
+		let global_payload = payload.finish();
+
+		let scene_payload = |name: &str, frames: [i32; 3]| {
+			let mut payload = builder.payload();
+			payload.ptr(0).ptr(0).ptr(0).name(name, 24).i32(frames[0]).i32(frames[1]).i32(frames[2]).i32(0);
+			payload.finish()
+		};
+		let first = scene_payload("SCFirst", [1, 1, 250]);
+		let main = scene_payload("SCMain", [42, 10, 120]);
+		builder
+			.block(*b"GLOB", global, 0x100, 1, global_payload)
+			.block(*b"SC\0\0", scene, 0x1000, 1, first)
+			.block(*b"SC\0\0", scene, 0x2000, 1, main);
+		builder.build()
+	}
+
+	#[test]
+	fn reads_build_version_and_current_scene() {
+		let blend = BlendFile::from_bytes(saved_file()).expect("synthetic file parses");
+		let meta = read_file_meta(&blend, &blend.dna().expect("dna parses")).expect("metadata reads");
+
+		assert_eq!(meta.saved_version(), "4.2.3");
+		assert_eq!(meta.min_version_label().as_deref(), Some("4.0.1"));
+		assert_eq!(meta.build_date().as_deref(), Some("2024-05-01T12:30:00Z"));
+		assert_eq!(meta.build_hash.as_deref(), Some("a1b2c3d4e5f6"));
+		assert_eq!(meta.saved_path.as_deref(), Some("/shots/sh010.blend"));
+		let scene = meta.scene.expect("current scene");
+		assert_eq!(scene.name.as_ref(), "SCMain");
+		assert_eq!((scene.current, scene.start, scene.end), (Some(42), Some(10), Some(120)));
+	}
+
+	#[test]
+	fn files_without_glob_fall_back_to_header_and_first_scene() {
+		let blend = BlendFile::from_bytes(scene_file(HeaderKind::Legacy8, false)).expect("synthetic file parses");
+		let meta = read_file_meta(&blend, &blend.dna().expect("dna parses")).expect("metadata reads");
+
+		assert_eq!(meta.subversion, None);
+		assert_eq!(meta.build_timestamp, None);
+		assert_eq!(meta.scene.as_ref().map(|scene| scene.name.as_ref()), Some(SCENE_ID_NAMES[0]));
+		assert_eq!(meta.scene.and_then(|scene| scene.current), None);
+	}
+
+	#[test]
+	fn unix_time_round_trips_through_dates() {
+		assert_eq!(parse_unix_time("2024-05-01T12:30:00Z"), Some(1_714_566_600));
+		assert_eq!(parse_unix_time("2024-05-01 12:30"), Some(1_714_566_600));
+		assert_eq!(parse_unix_time("2024-05-01"), Some(1_714_521_600));
+		assert_eq!(parse_unix_time("@1714566600"), Some(1_714_566_600));
+		assert_eq!(parse_unix_time("1969-12-31T23:59:59"), Some(-1));
+		assert_eq!(parse_unix_time("2023-02-29"), None);
+		assert_eq!(parse_unix_time("2024-13-01"), None);
+		assert_eq!(parse_unix_time("yesterday"), None);
+		for seconds in [0, -1, 951_782_400, 1_714_566_600, 4_102_444_800] {
+			assert_eq!(parse_unix_time(&format_unix_time(seconds)), Some(seconds));
+		}
+		assert_eq!(format_unix_time(951_782_400), "2000-02-29T00:00:00Z");
+	}
+}
@@ -0,0 +1,66 @@
+use crate::blend::{BlendFile, Block, IdIndex, Result, codes};
+
+/// Payload bytes written for one ID and its owned `DATA` blocks.
+#[derive(Debug, Clone)]
+pub struct IdFootprint {
+	/// ID block pointer.
+	pub id_ptr: u64,
+	/// ID block code.
+	pub code: [u8; 4],
+	/// Payload bytes of the ID-root block itself.
+	pub id_bytes: u64,
+	/// Number of `DATA` blocks written after the ID block.
+	pub data_blocks: usize,
+	/// Payload bytes of those `DATA` blocks.
+	pub data_bytes: u64,
+}
+
+impl IdFootprint {
+	/// ID-root plus owned data payload bytes.
+	pub fn total_bytes(&self) -> u64 {
+		self.id_bytes.saturating_add(self.data_bytes)
+	}
+}
+
+/// Attribute `DATA` blocks to the ID-root block written before them.
+///
+/// Blender writes each ID followed by its owned data, so ownership is
+/// inferred from file order: a run of `DATA` blocks belongs to the most
+/// recent ID block, and any other non-`DATA` block ends the run.
+pub fn scan_id_footprints(file: &BlendFile, ids: &IdIndex) -> Result<Vec<IdFootprint>> {
+	footprints_from_blocks(file.blocks(), ids)
+}
+
+pub(crate) fn footprints_from_blocks<'a>(blocks: impl Iterator<Item = Result<Block<'a>>>, ids: &IdIndex) -> Result<Vec<IdFootprint>> {
+	let mut out: Vec<IdFootprint> = Vec::with_capacity(ids.records.len());
+	let mut open = false;
+
+	for block in blocks {
+		let block = block?;
+		if block.head.code == codes::DATA {
+			if open && let Some(current) = out.last_mut() {
+				current.data_blocks += 1;
+				current.data_bytes = current.data_bytes.saturating_add(block.head.len);
+			}
+			continue;
+		}
+
+		open = ids
+			.get_by_ptr(block.head.old)
+			.is_some_and(|record| record.sdna_nr == block.head.sdna_nr && record.code == block.head.code);
+		if open {
+			out.push(IdFootprint {
+				id_ptr: block.head.old,
+				code: block.head.code,
+				id_bytes: block.head.len,
+				data_blocks: 0,
+				data_bytes: 0,
+			});
+		}
+	}
+
+	Ok(out)
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,65 @@
+mod synthetic_blocks {
+	use super::super::footprints_from_blocks;
+	use crate::blend::{BHead, Block, IdIndex, IdRecord};
+
+	fn block(code: &[u8; 4], old: u64, sdna_nr: u32, len: u64) -> crate::blend::Result<Block<'static>> {
+		Ok(Block {
+			head: BHead {
+				code: *code,
+				sdna_nr,
+				old,
+				len,
+				nr: 1,
+			},
+			payload: &[],
+			file_offset: 0,
+		})
+	}
+
+	fn record(code: &[u8; 4], old_ptr: u64, sdna_nr: u32, id_name: &str) -> IdRecord {
+		IdRecord {
+			old_ptr,
+			code: *code,
+			sdna_nr,
+			type_name: "Object".into(),
+			id_name: id_name.into(),
+			next: None,
+			prev: None,
+			lib: None,
+		}
+	}
+
+	#[test]
+	fn data_runs_attach_to_preceding_id_block() {
+		let ids = IdIndex::build(vec![record(b"OB\0\0", 0x1000, 3, "OBCube"), record(b"ME\0\0", 0x2000, 4, "MECube")]);
+		let blocks = vec![
+			block(b"DATA", 0x0900, 0, 64),
+			block(b"OB\0\0", 0x1000, 3, 96),
+			block(b"DATA", 0x1100, 0, 16),
+			block(b"DATA", 0x1200, 0, 32),
+			block(b"ME\0\0", 0x2000, 4, 128),
+			block(b"DATA", 0x2100, 0, 256),
+			block(b"DNA1", 0x9000, 0, 512),
+			block(b"DATA", 0x9100, 0, 8),
+		];
+
+		let footprints = footprints_from_blocks(blocks.into_iter(), &ids).expect("footprints");
+
+		assert_eq!(footprints.len(), 2);
+		assert_eq!(footprints[0].id_ptr, 0x1000);
+		assert_eq!((footprints[0].data_blocks, footprints[0].data_bytes), (2, 48));
+		assert_eq!(footprints[0].total_bytes(), 144);
+		assert_eq!(footprints[1].id_ptr, 0x2000);
+		assert_eq!((footprints[1].data_blocks, footprints[1].data_bytes), (1, 256));
+	}
+
+	#[test]
+	fn non_id_block_with_shared_pointer_does_not_open_a_run() {
+		let ids = IdIndex::build(vec![record(b"OB\0\0", 0x1000, 3, "OBCube")]);
+		let blocks = vec![block(b"TEST", 0x1000, 0, 4), block(b"DATA", 0x1100, 0, 16)];
+
+		let footprints = footprints_from_blocks(blocks.into_iter(), &ids).expect("footprints");
+
+		assert!(footprints.is_empty());
+	}
+}
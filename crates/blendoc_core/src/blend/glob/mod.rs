@@ -0,0 +1,48 @@
+/// Whether `pattern` contains glob metacharacters (`*` or `?`).
+pub fn is_glob(pattern: &str) -> bool {
+	pattern.contains(['*', '?'])
+}
+
+/// Match `text` against a glob where `*` spans any run of characters and `?` matches one.
+///
+/// Matching is anchored at both ends; all other characters compare literally,
+/// ASCII case-folded when `ignore_case` is set.
+pub fn glob_match(pattern: &str, text: &str, ignore_case: bool) -> bool {
+	let pattern: Vec<char> = pattern.chars().collect();
+	let text: Vec<char> = text.chars().collect();
+	let same = |a: char, b: char| if ignore_case { a.eq_ignore_ascii_case(&b) } else { a == b };
+
+	let (mut p, mut t) = (0, 0);
+	// Last `*` position and the text index it currently absorbs up to.
+	let mut star: Option<(usize, usize)> = None;
+
+	while t < text.len() {
+		match pattern.get(p) {
+			Some('*') => {
+				star = Some((p, t));
+				p += 1;
+			}
+			Some('?') => {
+				p += 1;
+				t += 1;
+			}
+			Some(&ch) if same(ch, text[t]) => {
+				p += 1;
+				t += 1;
+			}
+			_ => {
+				let Some((star_p, star_t)) = star else {
+					return false;
+				};
+				p = star_p + 1;
+				t = star_t + 1;
+				star = Some((star_p, star_t + 1));
+			}
+		}
+	}
+
+	pattern[p..].iter().all(|ch| *ch == '*')
+}
+
+#[cfg(test)]
+mod tests;
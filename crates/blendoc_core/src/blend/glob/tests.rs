@@ -0,0 +1,30 @@
+use crate::blend::{glob_match, is_glob};
+
+#[test]
+fn literal_patterns_match_exactly() {
+	assert!(glob_match("OBCube", "OBCube", false));
+	assert!(!glob_match("OBCube", "OBCube.001", false));
+	assert!(!glob_match("obcube", "OBCube", false));
+	assert!(glob_match("obcube", "OBCube", true));
+}
+
+#[test]
+fn star_and_question_mark_wildcards() {
+	assert!(glob_match("OB*", "OBCube", false));
+	assert!(glob_match("OB*", "OB", false));
+	assert!(glob_match("*.001", "MECube.001", false));
+	assert!(!glob_match("*.001", "MECube.002", false));
+	assert!(glob_match("*Cu*e*", "OBCube.001", false));
+	assert!(glob_match("OB????", "OBCube", false));
+	assert!(!glob_match("OB???", "OBCube", false));
+	assert!(glob_match("ma*_*", "MAmetal_rough", true));
+	assert!(!glob_match("", "OB", false));
+	assert!(glob_match("*", "", false));
+}
+
+#[test]
+fn glob_detection() {
+	assert!(is_glob("OB*"));
+	assert!(is_glob("OB?ube"));
+	assert!(!is_glob("OBCube"));
+}
@@ -0,0 +1,49 @@
+use crate::blend::glob_match;
+
+/// Include/exclude rules pruning nodes and edges during graph extraction.
+///
+/// Type and field patterns are globs (`*`, `?`) matched against the whole
+/// struct type name or source field path. An empty include list keeps
+/// everything; excludes win over includes. The root node is always kept.
+#[derive(Debug, Clone, Default)]
+pub struct GraphFilter {
+	/// Keep only nodes whose type name matches one of these globs.
+	pub include_types: Vec<String>,
+	/// Drop nodes whose type name matches one of these globs.
+	pub exclude_types: Vec<String>,
+	/// Keep only nodes resolved into blocks with one of these codes.
+	pub include_codes: Vec<[u8; 4]>,
+	/// Drop nodes resolved into blocks with one of these codes.
+	pub exclude_codes: Vec<[u8; 4]>,
+	/// Follow only edges whose field path matches one of these globs.
+	pub include_fields: Vec<String>,
+	/// Never follow edges whose field path matches one of these globs.
+	pub exclude_fields: Vec<String>,
+}
+
+impl GraphFilter {
+	/// Return whether no rule is set.
+	pub fn is_empty(&self) -> bool {
+		self.include_types.is_empty()
+			&& self.exclude_types.is_empty()
+			&& self.include_codes.is_empty()
+			&& self.exclude_codes.is_empty()
+			&& self.include_fields.is_empty()
+			&& self.exclude_fields.is_empty()
+	}
+
+	/// Return whether a node of `type_name` in a `code` block passes the type and code rules.
+	pub fn keeps_node(&self, type_name: &str, code: [u8; 4]) -> bool {
+		let type_ok = keeps(&self.include_types, &self.exclude_types, |pattern| glob_match(pattern, type_name, false));
+		type_ok && keeps(&self.include_codes, &self.exclude_codes, |candidate| *candidate == code)
+	}
+
+	/// Return whether an edge carried by `field` passes the field rules.
+	pub fn keeps_field(&self, field: &str) -> bool {
+		keeps(&self.include_fields, &self.exclude_fields, |pattern| glob_match(pattern, field, false))
+	}
+}
+
+fn keeps<T>(include: &[T], exclude: &[T], matches: impl Fn(&T) -> bool) -> bool {
+	(include.is_empty() || include.iter().any(&matches)) && !exclude.iter().any(matches)
+}
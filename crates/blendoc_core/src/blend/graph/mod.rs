@@ -1,7 +1,13 @@
 use std::collections::{HashMap, HashSet, VecDeque};
 use std::sync::Arc;
 
-use crate::blend::{BlendError, Dna, IdIndex, PointerIndex, RefScanOptions, Result, scan_refs_from_ptr};
+use crate::blend::cancel::is_cancelled;
+use crate::blend::ref_cache::scan_refs_cached;
+use crate::blend::{BlendError, CancelToken, Dna, IdIndex, PointerIndex, RefCache, RefScanOptions, Result, Warning, WarningKind};
+
+mod filter;
+
+pub use self::filter::GraphFilter;
 
 /// Runtime limits and filters for pointer-graph extraction.
 #[derive(Debug, Clone)]
@@ -18,6 +24,12 @@ pub struct GraphOptions {
 	pub id_only: bool,
 	/// Skip null pointers while collecting edges.
 	pub skip_null_ptrs: bool,
+	/// Optional shared memo for per-owner reference scans.
+	pub ref_cache: Option<RefCache>,
+	/// Node and edge rules; filtered nodes are neither kept nor expanded.
+	pub filter: GraphFilter,
+	/// Stops expansion early with [`GraphTruncation::Cancelled`] when cancelled.
+	pub cancel: Option<CancelToken>,
 }
 
 impl Default for GraphOptions {
@@ -29,6 +41,9 @@ impl Default for GraphOptions {
 			ref_scan: RefScanOptions::default(),
 			id_only: false,
 			skip_null_ptrs: true,
+			ref_cache: None,
+			filter: GraphFilter::default(),
+			cancel: None,
 		}
 	}
 }
@@ -68,6 +83,8 @@ pub enum GraphTruncation {
 	MaxNodes,
 	/// Expansion stopped due edge budget.
 	MaxEdges,
+	/// Expansion stopped because the cancel token fired.
+	Cancelled,
 }
 
 /// Extracted shallow pointer graph.
@@ -79,6 +96,8 @@ pub struct GraphResult {
 	pub edges: Vec<GraphEdge>,
 	/// Optional truncation marker.
 	pub truncated: Option<GraphTruncation>,
+	/// Non-fatal issues met while expanding nodes.
+	pub warnings: Vec<Warning>,
 }
 
 /// Build a depth-limited pointer graph from a root pointer.
@@ -102,24 +121,38 @@ pub fn build_graph_from_ptr<'a>(dna: &Dna, index: &PointerIndex<'a>, ids: &IdInd
 	let mut edges = Vec::new();
 	let mut edge_seen: HashSet<(u64, u64, Arc<str>)> = HashSet::new();
 
+	let mut warnings = Vec::new();
 	let mut truncated = None;
 	let mut hit_depth_limit = false;
 
 	'outer: while let Some((current, depth)) = queue.pop_front() {
+		if is_cancelled(options.cancel.as_ref()) {
+			truncated = Some(GraphTruncation::Cancelled);
+			break;
+		}
 		if depth >= options.max_depth {
 			hit_depth_limit = true;
 			continue;
 		}
 
-		let refs = scan_refs_from_ptr(dna, index, ids, current, &options.ref_scan)?;
-		for record in refs {
+		let refs = scan_refs_cached(dna, index, ids, current, &options.ref_scan, options.ref_cache.as_ref())?;
+		for record in refs.iter() {
 			if options.skip_null_ptrs && record.ptr == 0 {
 				continue;
 			}
+			if !options.filter.keeps_field(&record.field) {
+				continue;
+			}
 
-			let Some(target) = record.resolved else {
+			let Some(target) = &record.resolved else {
+				if record.ptr != 0 {
+					warnings.push(unresolved_warning(&record.owner_type, &record.field, record.ptr));
+				}
 				continue;
 			};
+			if target.canonical != root_canonical && !options.filter.keeps_node(&target.type_name, target.code) {
+				continue;
+			}
 
 			if !nodes_by_ptr.contains_key(&target.canonical) {
 				if nodes_by_ptr.len() >= options.max_nodes {
@@ -133,8 +166,8 @@ pub fn build_graph_from_ptr<'a>(dna: &Dna, index: &PointerIndex<'a>, ids: &IdInd
 						canonical: target.canonical,
 						code: target.code,
 						sdna_nr: target.sdna_nr,
-						type_name: target.type_name,
-						id_name: target.id_name,
+						type_name: target.type_name.clone(),
+						id_name: target.id_name.clone(),
 					},
 				);
 			}
@@ -178,7 +211,21 @@ pub fn build_graph_from_ptr<'a>(dna: &Dna, index: &PointerIndex<'a>, ids: &IdInd
 
 	edges.sort_by(|a, b| a.from.cmp(&b.from).then_with(|| a.to.cmp(&b.to)).then_with(|| a.field.cmp(&b.field)));
 
-	Ok(GraphResult { nodes, edges, truncated })
+	Ok(GraphResult {
+		nodes,
+		edges,
+		truncated,
+		warnings,
+	})
+}
+
+/// Build the warning emitted for a non-null pointer field that did not resolve.
+pub(crate) fn unresolved_warning(owner_type: &str, field: &str, ptr: u64) -> Warning {
+	Warning::new(
+		WarningKind::UnresolvedPtr,
+		Some(ptr),
+		format!("{owner_type}.{field} -> 0x{ptr:016x} unresolved"),
+	)
 }
 
 fn resolve_graph_node<'a>(dna: &Dna, index: &PointerIndex<'a>, ids: &IdIndex, ptr: u64) -> Result<GraphNode> {
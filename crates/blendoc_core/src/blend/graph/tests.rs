@@ -47,6 +47,9 @@ mod fixtures_day8_graph {
 				},
 				id_only: false,
 				skip_null_ptrs: true,
+				ref_cache: None,
+				filter: crate::blend::GraphFilter::default(),
+				cancel: None,
 			},
 		)
 		.expect("graph builds");
@@ -64,3 +67,109 @@ mod fixtures_day8_graph {
 		);
 	}
 }
+
+mod synthetic_graph_warnings {
+	use crate::blend::{BHead, Block, Dna, DnaField, DnaStruct, Endianness, GraphOptions, IdIndex, PointerIndex, PtrEntry, WarningKind, build_graph_from_ptr};
+
+	#[test]
+	fn unresolved_non_null_pointer_is_reported() {
+		let payload = 0xdead_0000_u64.to_le_bytes();
+		let index = PointerIndex::from_entries_for_test(vec![PtrEntry {
+			start_old: 0x1000,
+			end_old: 0x1008,
+			block: Block {
+				head: BHead {
+					code: *b"DATA",
+					sdna_nr: 0,
+					old: 0x1000,
+					len: 8,
+					nr: 1,
+				},
+				payload: &payload,
+				file_offset: 0,
+			},
+		}]);
+
+		let dna = Dna::from_tables(
+			Endianness::Little,
+			8,
+			vec!["*next".into()],
+			vec!["Node".into()],
+			vec![8],
+			vec![DnaStruct {
+				type_idx: 0,
+				fields: vec![DnaField { type_idx: 0, name_idx: 0 }],
+			}],
+		)
+		.expect("valid dna tables");
+
+		let graph = build_graph_from_ptr(&dna, &index, &IdIndex::build(Vec::new()), 0x1000, &GraphOptions::default()).expect("graph builds");
+
+		assert_eq!(graph.nodes.len(), 1);
+		assert_eq!(graph.warnings.len(), 1);
+		assert_eq!(graph.warnings[0].kind, WarningKind::UnresolvedPtr);
+		assert_eq!(graph.warnings[0].ptr, Some(0xdead_0000));
+		assert!(graph.warnings[0].message.contains("Node.next"));
+	}
+}
+
+mod synthetic_filters {
+	use blendoc_testkit::builder::{HeaderKind, SCENE_ID_NAMES, scene_file};
+
+	use crate::blend::{BlendFile, GraphFilter, GraphOptions, IdIndex, build_graph_from_ptr, scan_id_blocks};
+
+	fn node_types(filter: GraphFilter) -> Vec<String> {
+		let blend = BlendFile::from_bytes(scene_file(HeaderKind::Legacy8, false)).expect("synthetic file parses");
+		let dna = blend.dna().expect("dna parses");
+		let index = blend.pointer_index().expect("pointer index builds");
+		let ids = IdIndex::build(scan_id_blocks(&blend, &dna).expect("id scan succeeds"));
+		let root = ids.get_by_name(SCENE_ID_NAMES[0]).expect("scene id").old_ptr;
+		let options = GraphOptions {
+			max_depth: 4,
+			filter,
+			..GraphOptions::default()
+		};
+		let graph = build_graph_from_ptr(&dna, &index, &ids, root, &options).expect("graph builds");
+		let kept: Vec<u64> = graph.nodes.iter().map(|node| node.canonical).collect();
+		assert!(graph.edges.iter().all(|edge| kept.contains(&edge.from) && kept.contains(&edge.to)));
+		let mut types: Vec<String> = graph.nodes.iter().map(|node| node.type_name.to_string()).collect();
+		types.sort();
+		types
+	}
+
+	#[test]
+	fn type_code_and_field_rules_prune_the_walk() {
+		assert_eq!(node_types(GraphFilter::default()), ["MVert", "Mesh", "Object", "Scene", "World"]);
+
+		let no_world = GraphFilter {
+			exclude_types: vec!["Wor*".to_owned()],
+			..GraphFilter::default()
+		};
+		assert_eq!(node_types(no_world), ["MVert", "Mesh", "Object", "Scene"]);
+
+		// Excluding a node also stops the walk behind it.
+		let no_objects = GraphFilter {
+			exclude_codes: vec![*b"OB\0\0"],
+			..GraphFilter::default()
+		};
+		assert_eq!(node_types(no_objects), ["Scene", "World"]);
+
+		let only_ids = GraphFilter {
+			include_codes: vec![*b"OB\0\0", *b"ME\0\0"],
+			..GraphFilter::default()
+		};
+		assert_eq!(node_types(only_ids), ["Mesh", "Object", "Scene"]);
+
+		let no_camera = GraphFilter {
+			exclude_fields: vec!["camera*".to_owned()],
+			..GraphFilter::default()
+		};
+		assert_eq!(node_types(no_camera), ["Scene", "World"]);
+
+		let only_world = GraphFilter {
+			include_fields: vec!["world".to_owned()],
+			..GraphFilter::default()
+		};
+		assert_eq!(node_types(only_world), ["Scene", "World"]);
+	}
+}
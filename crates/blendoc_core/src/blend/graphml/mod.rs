@@ -0,0 +1,173 @@
+use std::io::{self, Write};
+use std::sync::Arc;
+
+use crate::blend::{GraphResult, IdGraphResult, codes};
+
+/// One exported node with its typed attributes.
+#[derive(Debug, Clone)]
+pub struct ExportNode {
+	/// Canonical pointer, rendered as the GraphML node id.
+	pub canonical: u64,
+	/// Block code containing the node.
+	pub code: [u8; 4],
+	/// SDNA index for the node type.
+	pub sdna_nr: u32,
+	/// Struct type name.
+	pub type_name: Arc<str>,
+	/// `ID.name` when the node is an ID root.
+	pub id_name: Option<Arc<str>>,
+}
+
+/// One exported directed edge.
+#[derive(Debug, Clone)]
+pub struct ExportEdge {
+	/// Source canonical pointer.
+	pub from: u64,
+	/// Target canonical pointer.
+	pub to: u64,
+	/// Source field path holding the pointer.
+	pub field: Arc<str>,
+}
+
+/// Graph serializer shared by pointer graphs and ID graphs.
+///
+/// GraphML output declares typed keys (`type`, `id_name`, `code`, `sdna_nr`,
+/// `label` on nodes; `field` on edges) so Gephi and yEd pick them up as
+/// attribute columns.
+#[derive(Debug, Clone, Default)]
+pub struct GraphExport {
+	/// Nodes in output order.
+	pub nodes: Vec<ExportNode>,
+	/// Edges in output order.
+	pub edges: Vec<ExportEdge>,
+}
+
+impl GraphExport {
+	/// Collect nodes and edges from a pointer graph.
+	pub fn from_graph(graph: &GraphResult) -> Self {
+		Self {
+			nodes: graph
+				.nodes
+				.iter()
+				.map(|node| ExportNode {
+					canonical: node.canonical,
+					code: node.code,
+					sdna_nr: node.sdna_nr,
+					type_name: node.type_name.clone(),
+					id_name: node.id_name.clone(),
+				})
+				.collect(),
+			edges: graph
+				.edges
+				.iter()
+				.map(|edge| ExportEdge {
+					from: edge.from,
+					to: edge.to,
+					field: edge.field.clone(),
+				})
+				.collect(),
+		}
+	}
+
+	/// Collect nodes and edges from a whole-file ID graph.
+	pub fn from_id_graph(graph: &IdGraphResult) -> Self {
+		Self {
+			nodes: graph
+				.nodes
+				.iter()
+				.map(|node| ExportNode {
+					canonical: node.canonical,
+					code: node.code,
+					sdna_nr: node.sdna_nr,
+					type_name: node.type_name.clone(),
+					id_name: Some(node.id_name.clone()),
+				})
+				.collect(),
+			edges: graph
+				.edges
+				.iter()
+				.map(|edge| ExportEdge {
+					from: edge.from,
+					to: edge.to,
+					field: edge.field.clone(),
+				})
+				.collect(),
+		}
+	}
+
+	/// Write the graph as a GraphML document.
+	pub fn write_graphml(&self, out: &mut impl Write) -> io::Result<()> {
+		writeln!(out, r#"<?xml version="1.0" encoding="UTF-8"?>"#)?;
+		writeln!(out, r#"<graphml xmlns="http://graphml.graphdrawing.org/xmlns">"#)?;
+		for (id, scope, kind) in KEYS {
+			writeln!(out, r#"  <key id="{id}" for="{scope}" attr.name="{id}" attr.type="{kind}"/>"#)?;
+		}
+		writeln!(out, r#"  <graph id="blendoc" edgedefault="directed">"#)?;
+
+		for node in &self.nodes {
+			let label = match &node.id_name {
+				Some(id_name) => id_name.to_string(),
+				None => format!("{}@0x{:016x}", node.type_name, node.canonical),
+			};
+			writeln!(out, r#"    <node id="0x{:016x}">"#, node.canonical)?;
+			write_data(out, "type", &node.type_name)?;
+			if let Some(id_name) = &node.id_name {
+				write_data(out, "id_name", id_name)?;
+			}
+			write_data(out, "code", &codes::code_to_str(node.code))?;
+			write_data(out, "sdna_nr", &node.sdna_nr.to_string())?;
+			write_data(out, "label", &label)?;
+			writeln!(out, "    </node>")?;
+		}
+
+		for (idx, edge) in self.edges.iter().enumerate() {
+			writeln!(out, r#"    <edge id="e{idx}" source="0x{:016x}" target="0x{:016x}">"#, edge.from, edge.to)?;
+			write_data(out, "field", &edge.field)?;
+			writeln!(out, "    </edge>")?;
+		}
+
+		writeln!(out, "  </graph>")?;
+		writeln!(out, "</graphml>")
+	}
+
+	/// Render the graph as a GraphML string.
+	pub fn to_graphml(&self) -> String {
+		let mut out = Vec::new();
+		self.write_graphml(&mut out).expect("writing to a Vec cannot fail");
+		String::from_utf8(out).expect("GraphML output is UTF-8")
+	}
+}
+
+/// Declared attribute keys: id (also the attribute name), scope, and GraphML type.
+const KEYS: [(&str, &str, &str); 6] = [
+	("type", "node", "string"),
+	("id_name", "node", "string"),
+	("code", "node", "string"),
+	("sdna_nr", "node", "int"),
+	("label", "node", "string"),
+	("field", "edge", "string"),
+];
+
+fn write_data(out: &mut impl Write, key: &str, value: &str) -> io::Result<()> {
+	writeln!(out, r#"      <data key="{key}">{}</data>"#, xml_escape(value))
+}
+
+fn xml_escape(input: &str) -> String {
+	let mut out = String::with_capacity(input.len());
+	for ch in input.chars() {
+		match ch {
+			'&' => out.push_str("&amp;"),
+			'<' => out.push_str("&lt;"),
+			'>' => out.push_str("&gt;"),
+			'"' => out.push_str("&quot;"),
+			'\'' => out.push_str("&apos;"),
+			// XML 1.0 forbids most control characters even when escaped.
+			ch if ch.is_control() && !matches!(ch, '\t' | '\n' | '\r') => out.push('\u{fffd}'),
+			ch => out.push(ch),
+		}
+	}
+	out
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,71 @@
+mod graphml_output {
+	use crate::blend::{GraphEdge, GraphExport, GraphNode, GraphResult, IdGraphEdge, IdGraphNode, IdGraphResult};
+
+	#[test]
+	fn pointer_graph_nodes_and_edges_carry_typed_attributes() {
+		let graph = GraphResult {
+			nodes: vec![
+				GraphNode {
+					canonical: 0x1000,
+					code: *b"SC\0\0",
+					sdna_nr: 3,
+					type_name: "Scene".into(),
+					id_name: Some("SCScene".into()),
+				},
+				GraphNode {
+					canonical: 0x2000,
+					code: *b"DATA",
+					sdna_nr: 7,
+					type_name: "ToolSettings".into(),
+					id_name: None,
+				},
+			],
+			edges: vec![GraphEdge {
+				from: 0x1000,
+				to: 0x2000,
+				field: "toolsettings".into(),
+			}],
+			truncated: None,
+			warnings: Vec::new(),
+		};
+
+		let xml = GraphExport::from_graph(&graph).to_graphml();
+
+		assert!(xml.starts_with("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n<graphml "));
+		assert!(xml.contains(r#"<key id="sdna_nr" for="node" attr.name="sdna_nr" attr.type="int"/>"#));
+		assert!(xml.contains(r#"<graph id="blendoc" edgedefault="directed">"#));
+		assert!(xml.contains(
+			"<node id=\"0x0000000000001000\">\n      <data key=\"type\">Scene</data>\n      <data key=\"id_name\">SCScene</data>\n      <data key=\"code\">SC</data>"
+		));
+		assert!(xml.contains("<data key=\"label\">ToolSettings@0x0000000000002000</data>"));
+		assert_eq!(xml.matches("<data key=\"id_name\">").count(), 1, "non-ID nodes omit id_name");
+		assert!(xml.contains("<edge id=\"e0\" source=\"0x0000000000001000\" target=\"0x0000000000002000\">\n      <data key=\"field\">toolsettings</data>"));
+		assert!(xml.ends_with("  </graph>\n</graphml>\n"));
+	}
+
+	#[test]
+	fn id_graph_text_is_xml_escaped() {
+		let graph = IdGraphResult {
+			nodes: vec![IdGraphNode {
+				canonical: 0x10,
+				code: *b"OB\0\0",
+				sdna_nr: 1,
+				type_name: "Object".into(),
+				id_name: "OB<a&b>\"\u{1}".into(),
+			}],
+			edges: vec![IdGraphEdge {
+				from: 0x10,
+				to: 0x10,
+				field: "parent".into(),
+			}],
+			truncated: None,
+			warnings: Vec::new(),
+			cycles: Vec::new(),
+		};
+
+		let xml = GraphExport::from_id_graph(&graph).to_graphml();
+
+		assert!(xml.contains("<data key=\"id_name\">OB&lt;a&amp;b&gt;&quot;\u{fffd}</data>"));
+		assert!(!xml.contains('\u{1}'));
+	}
+}
@@ -1,4 +1,6 @@
-use crate::blend::{BlendError, Result};
+use std::io::Write;
+
+use crate::blend::{BHeadLayout, BlendError, Result};
 
 /// Byte endianness marker stored in blend headers.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -59,6 +61,11 @@ impl BlendHeader {
 		Self::parse_legacy(bytes)
 	}
 
+	/// Negotiate the block-header layout used by this file.
+	pub fn bhead_layout(self) -> Result<BHeadLayout> {
+		BHeadLayout::from_header(self)
+	}
+
 	/// Return human-readable block-header layout label.
 	pub fn bhead_layout_label(self) -> &'static str {
 		if self.format_version == Self::LEGACY_FORMAT_VERSION {
@@ -68,6 +75,38 @@ impl BlendHeader {
 		}
 	}
 
+	/// Write the header bytes, the inverse of [`BlendHeader::parse`].
+	///
+	/// Legacy headers need a 4 or 8 byte pointer size and a three-digit
+	/// version; v1 headers need the 17-byte size and a four-digit version.
+	pub fn write(&self, out: &mut impl Write) -> Result<()> {
+		let marker = match self.endianness {
+			Endianness::Little => 'v',
+			Endianness::Big => 'V',
+		};
+		let text = match self.format_version {
+			Self::LEGACY_FORMAT_VERSION => {
+				let pointer = match self.pointer_size {
+					4 => '_',
+					8 => '-',
+					header_size => return Err(BlendError::UnsupportedPointerSize { header_size }),
+				};
+				check_digits("version", self.version, 999)?;
+				format!("BLENDER{pointer}{marker}{:03}", self.version)
+			}
+			Self::V1_FORMAT_VERSION => {
+				if self.header_size != Self::MIN_SIZE || self.pointer_size != 8 {
+					return Err(BlendError::UnsupportedPointerSize { header_size: self.header_size });
+				}
+				check_digits("version", self.version, 9999)?;
+				format!("BLENDER{:02}-{:02}{marker}{:04}", self.header_size, self.format_version, self.version)
+			}
+			version => return Err(BlendError::UnsupportedFormatVersion { version }),
+		};
+		out.write_all(text.as_bytes())?;
+		Ok(())
+	}
+
 	fn parse_v1(bytes: &[u8]) -> Result<Self> {
 		let header = bytes.get(0..Self::MIN_SIZE).ok_or(BlendError::InvalidHeader)?;
 
@@ -129,6 +168,17 @@ impl BlendHeader {
 	}
 }
 
+fn check_digits(field: &'static str, value: u16, max: u16) -> Result<()> {
+	if value > max {
+		return Err(BlendError::EncodeOutOfRange {
+			field,
+			value: u64::from(value),
+			max: u64::from(max),
+		});
+	}
+	Ok(())
+}
+
 fn parse_endianness_marker(byte: u8) -> Option<Endianness> {
 	match byte {
 		b'v' => Some(Endianness::Little),
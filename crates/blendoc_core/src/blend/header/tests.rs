@@ -37,3 +37,26 @@ fn parses_legacy_big_endian_header() {
 	assert_eq!(header.pointer_size, 4);
 	assert_eq!(header.endianness, Endianness::Big);
 }
+
+#[test]
+fn write_round_trips_header_bytes() {
+	for raw in [b"BLENDER17-01v0500".as_slice(), b"BLENDER17-01V0405", b"BLENDER-v302", b"BLENDER_V248"] {
+		let header = BlendHeader::parse(raw).expect("header parses");
+		let mut bytes = Vec::new();
+		header.write(&mut bytes).expect("header writes");
+		assert_eq!(bytes, raw);
+	}
+}
+
+#[test]
+fn write_rejects_unencodable_headers() {
+	let mut header = BlendHeader::parse(b"BLENDER-v302").expect("header parses");
+	header.version = 1000;
+	let err = header.write(&mut Vec::new()).expect_err("legacy version has three digits");
+	assert!(matches!(err, BlendError::EncodeOutOfRange { field: "version", .. }));
+
+	header.version = 302;
+	header.pointer_size = 2;
+	let err = header.write(&mut Vec::new()).expect_err("pointer size has no marker");
+	assert!(matches!(err, BlendError::UnsupportedPointerSize { header_size: 2 }));
+}
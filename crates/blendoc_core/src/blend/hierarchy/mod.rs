@@ -0,0 +1,268 @@
+use std::collections::HashSet;
+use std::sync::Arc;
+
+use crate::blend::{DecodeOptions, Dna, IdIndex, PointerIndex, Result, StructValue, Value, WalkOptions, codes, decode_ptr_instance, walk_listbase};
+
+/// Traversal limits for [`scene_hierarchies`].
+#[derive(Debug, Clone)]
+pub struct HierarchyOptions {
+	/// Maximum collection nesting below the scene's master collection.
+	pub max_depth: u32,
+	/// Maximum elements read from any one `ListBase`.
+	pub max_list_items: usize,
+}
+
+impl Default for HierarchyOptions {
+	fn default() -> Self {
+		Self {
+			max_depth: 64,
+			max_list_items: 100_000,
+		}
+	}
+}
+
+/// One object linked into a collection (or a legacy scene base).
+#[derive(Debug, Clone)]
+pub struct HierarchyObject {
+	/// Canonical `Object` pointer.
+	pub ptr: u64,
+	/// Object `ID.name`.
+	pub name: Arc<str>,
+	/// Raw `Object.type` value.
+	pub object_type: Option<i64>,
+	/// Canonical `Object.parent` pointer, when set.
+	pub parent: Option<u64>,
+	/// Parent object `ID.name`, when the parent resolves to an ID.
+	pub parent_name: Option<Arc<str>>,
+}
+
+impl HierarchyObject {
+	/// Blender object type label (`MESH`, `CAMERA`, ...), or `type:<n>` for unknown values.
+	pub fn type_label(&self) -> String {
+		match self.object_type {
+			Some(value) => object_type_label(value).map_or_else(|| format!("type:{value}"), str::to_owned),
+			None => "-".to_owned(),
+		}
+	}
+}
+
+/// One collection with its linked objects and child collections.
+#[derive(Debug, Clone)]
+pub struct HierarchyCollection {
+	/// Canonical `Collection` pointer.
+	pub ptr: u64,
+	/// Collection `ID.name` (the embedded master collection uses its stored name).
+	pub name: Arc<str>,
+	/// Objects from `Collection.gobject`, in list order.
+	pub objects: Vec<HierarchyObject>,
+	/// Child collections from `Collection.children`, in list order.
+	pub children: Vec<HierarchyCollection>,
+}
+
+/// Outliner-style hierarchy for one scene.
+#[derive(Debug, Clone)]
+pub struct SceneHierarchy {
+	/// Canonical `Scene` pointer.
+	pub scene_ptr: u64,
+	/// Scene `ID.name`.
+	pub scene_name: Arc<str>,
+	/// Master collection tree; `None` for pre-2.80 files without one.
+	pub master: Option<HierarchyCollection>,
+	/// Objects from the legacy `Scene.base` list (pre-2.80 files).
+	pub legacy_bases: Vec<HierarchyObject>,
+	/// Whether a depth/list limit, a broken list, or a collection cycle cut the tree short.
+	pub truncated: bool,
+}
+
+/// Blender `Object.type` label for known values.
+pub fn object_type_label(value: i64) -> Option<&'static str> {
+	Some(match value {
+		0 => "EMPTY",
+		1 => "MESH",
+		2 => "CURVE",
+		3 => "SURFACE",
+		4 => "FONT",
+		5 => "META",
+		10 => "LIGHT",
+		11 => "CAMERA",
+		12 => "SPEAKER",
+		13 => "LIGHT_PROBE",
+		22 => "LATTICE",
+		25 => "ARMATURE",
+		26 => "GPENCIL",
+		27 => "CURVES",
+		28 => "POINTCLOUD",
+		29 => "VOLUME",
+		30 => "GREASEPENCIL",
+		_ => return None,
+	})
+}
+
+/// Decode the collection/object hierarchy of every scene in the file.
+///
+/// Each scene's `master_collection` is expanded through `children`
+/// (`CollectionChild.collection`) and `gobject` (`CollectionObject.ob`)
+/// lists. Files without a master collection fall back to `Scene.base`
+/// (`Base.object`).
+pub fn scene_hierarchies<'a>(dna: &Dna, index: &PointerIndex<'a>, ids: &IdIndex, options: &HierarchyOptions) -> Result<Vec<SceneHierarchy>> {
+	let decode = DecodeOptions::default();
+	let mut out = Vec::new();
+	for record in ids.records.iter().filter(|record| record.code == codes::ID_SCE) {
+		let (scene_ptr, scene) = decode_ptr_instance(dna, index, record.old_ptr, &decode)?;
+		let mut reader = Reader {
+			dna,
+			index,
+			ids,
+			options,
+			decode: &decode,
+			truncated: false,
+		};
+
+		let master = match ptr_field(&scene, "master_collection").filter(|ptr| *ptr != 0) {
+			Some(ptr) => Some(reader.collection(ptr, 0, &mut HashSet::new())?),
+			None => None,
+		};
+		let mut legacy_bases = Vec::new();
+		for base_ptr in reader.list_items(&scene, "base")? {
+			let (_, base) = decode_ptr_instance(dna, index, base_ptr, &decode)?;
+			if let Some(object) = ptr_field(&base, "object").filter(|ptr| *ptr != 0) {
+				legacy_bases.push(reader.object(object)?);
+			}
+		}
+
+		out.push(SceneHierarchy {
+			scene_ptr,
+			scene_name: Arc::from(record.id_name.as_ref()),
+			master,
+			legacy_bases,
+			truncated: reader.truncated,
+		});
+	}
+	Ok(out)
+}
+
+struct Reader<'r, 'a> {
+	dna: &'r Dna,
+	index: &'r PointerIndex<'a>,
+	ids: &'r IdIndex,
+	options: &'r HierarchyOptions,
+	decode: &'r DecodeOptions,
+	truncated: bool,
+}
+
+impl Reader<'_, '_> {
+	fn collection(&mut self, ptr: u64, depth: u32, stack: &mut HashSet<u64>) -> Result<HierarchyCollection> {
+		let (canonical, collection) = decode_ptr_instance(self.dna, self.index, ptr, self.decode)?;
+		let name = self
+			.ids
+			.get_by_ptr(canonical)
+			.map(|record| Arc::from(record.id_name.as_ref()))
+			.or_else(|| id_name(&collection))
+			.unwrap_or_else(|| Arc::from(""));
+
+		let mut objects = Vec::new();
+		for item in self.list_items(&collection, "gobject")? {
+			let (_, link) = decode_ptr_instance(self.dna, self.index, item, self.decode)?;
+			if let Some(object) = ptr_field(&link, "ob").filter(|ptr| *ptr != 0) {
+				objects.push(self.object(object)?);
+			}
+		}
+
+		let mut children = Vec::new();
+		stack.insert(canonical);
+		for item in self.list_items(&collection, "children")? {
+			let (_, link) = decode_ptr_instance(self.dna, self.index, item, self.decode)?;
+			let Some(child) = ptr_field(&link, "collection").filter(|ptr| *ptr != 0) else {
+				continue;
+			};
+			let child_canonical = self.index.canonical_ptr(self.dna, child).unwrap_or(child);
+			if depth >= self.options.max_depth || stack.contains(&child_canonical) {
+				self.truncated = true;
+				continue;
+			}
+			children.push(self.collection(child, depth + 1, stack)?);
+		}
+		stack.remove(&canonical);
+
+		Ok(HierarchyCollection {
+			ptr: canonical,
+			name,
+			objects,
+			children,
+		})
+	}
+
+	fn object(&self, ptr: u64) -> Result<HierarchyObject> {
+		let (canonical, object) = decode_ptr_instance(self.dna, self.index, ptr, self.decode)?;
+		let parent = ptr_field(&object, "parent")
+			.filter(|ptr| *ptr != 0)
+			.map(|ptr| self.index.canonical_ptr(self.dna, ptr).unwrap_or(ptr));
+		Ok(HierarchyObject {
+			ptr: canonical,
+			name: self
+				.ids
+				.get_by_ptr(canonical)
+				.map(|record| Arc::from(record.id_name.as_ref()))
+				.or_else(|| id_name(&object))
+				.unwrap_or_else(|| Arc::from("")),
+			object_type: int_field(&object, "type"),
+			parent,
+			parent_name: parent.and_then(|ptr| self.ids.get_by_ptr(ptr)).map(|record| Arc::from(record.id_name.as_ref())),
+		})
+	}
+
+	/// Canonical element pointers of the `ListBase` field `list`, in `next` order.
+	fn list_items(&mut self, owner: &StructValue, list: &str) -> Result<Vec<u64>> {
+		let Some(Value::Struct(list_base)) = field(owner, list) else {
+			return Ok(Vec::new());
+		};
+		let walk = walk_listbase(
+			self.dna,
+			self.index,
+			self.ids,
+			list_base,
+			&WalkOptions {
+				max_steps: self.options.max_list_items,
+				..WalkOptions::default()
+			},
+		)?;
+		self.truncated |= !walk.is_complete();
+		Ok(walk.canonical_ptrs())
+	}
+}
+
+fn id_name(item: &StructValue) -> Option<Arc<str>> {
+	match field(item, "id")? {
+		Value::Struct(id) => string_field(id, "name"),
+		_ => None,
+	}
+}
+
+fn field<'v>(item: &'v StructValue, name: &str) -> Option<&'v Value> {
+	item.fields.iter().find(|field| field.name.as_ref() == name).map(|field| &field.value)
+}
+
+fn ptr_field(item: &StructValue, name: &str) -> Option<u64> {
+	match field(item, name)? {
+		Value::Ptr(ptr) => Some(*ptr),
+		_ => None,
+	}
+}
+
+fn string_field(item: &StructValue, name: &str) -> Option<Arc<str>> {
+	match field(item, name)? {
+		Value::String(value) if !value.is_empty() => Some(Arc::from(value.as_ref())),
+		_ => None,
+	}
+}
+
+fn int_field(item: &StructValue, name: &str) -> Option<i64> {
+	match field(item, name)? {
+		Value::I64(value) => Some(*value),
+		Value::U64(value) => i64::try_from(*value).ok(),
+		_ => None,
+	}
+}
+
+#[cfg(test)]
+mod tests;
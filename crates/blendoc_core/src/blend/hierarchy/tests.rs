@@ -0,0 +1,133 @@
+mod synthetic_scene {
+	use blendoc_testkit::builder::{BlendBuilder, HeaderKind};
+
+	use crate::blend::{BlendFile, HierarchyOptions, IdIndex, SceneHierarchy, scan_id_blocks, scene_hierarchies};
+
+	const SCENE: u64 = 0x1000;
+	const MASTER: u64 = 0x1100;
+	const PROPS: u64 = 0x2000;
+	const CAMERA: u64 = 0x3000;
+	const EMPTY: u64 = 0x3100;
+	const CUBE: u64 = 0x3200;
+	const OBJECT_LINKS: [u64; 3] = [0x4000, 0x4100, 0x4200];
+	const CHILD_LINKS: [u64; 2] = [0x5000, 0x5100];
+
+	/// `SCScene` master collection holds `OBCamera` and child `GRProps`, which
+	/// holds `OBEmpty` and its child `OBCube`. With `cyclic`, `GRProps` also
+	/// lists itself as a child collection.
+	fn scene_file(cyclic: bool) -> Vec<u8> {
+		let mut builder = BlendBuilder::new(HeaderKind::Legacy8, 300, false);
+		let sdna = builder.sdna_mut();
+		sdna.add_type("char", 1);
+		sdna.add_type("short", 2);
+		sdna.add_type("void", 0);
+		sdna.add_struct("ListBase", &[("void", "*first"), ("void", "*last")]);
+		sdna.add_struct("ID", &[("void", "*next"), ("void", "*prev"), ("void", "*lib"), ("char", "name[24]")]);
+		let scene = sdna.add_struct("Scene", &[("ID", "id"), ("Collection", "*master_collection"), ("ListBase", "base")]);
+		let collection = sdna.add_struct("Collection", &[("ID", "id"), ("ListBase", "gobject"), ("ListBase", "children")]);
+		let object_link = sdna.add_struct(
+			"CollectionObject",
+			&[("CollectionObject", "*next"), ("CollectionObject", "*prev"), ("Object", "*ob")],
+		);
+		let child_link = sdna.add_struct(
+			"CollectionChild",
+			&[("CollectionChild", "*next"), ("CollectionChild", "*prev"), ("Collection", "*collection")],
+		);
+		let object = sdna.add_struct("Object", &[("ID", "id"), ("Object", "*parent"), ("short", "type"), ("short", "pad[3]")]);
+
+		let id = |name: &str| {
+			let mut payload = builder.payload();
+			payload.ptr(0).ptr(0).ptr(0).name(name, 24);
+			payload.finish()
+		};
+		let object_payload = |name: &str, parent: u64, object_type: i16| {
+			let mut payload = builder.payload();
+			payload.raw(&id(name)).ptr(parent).raw(&object_type.to_le_bytes()).raw(&[0; 6]);
+			payload.finish()
+		};
+		let link_payload = |next: u64, prev: u64, target: u64| {
+			let mut payload = builder.payload();
+			payload.ptr(next).ptr(prev).ptr(target);
+			payload.finish()
+		};
+
+		let mut payload = builder.payload();
+		payload.raw(&id("SCScene")).ptr(MASTER).ptr(0).ptr(0);
+		let scene_payload = payload.finish();
+
+		let mut payload = builder.payload();
+		payload
+			.raw(&id("GRScene Collection"))
+			.ptr(OBJECT_LINKS[0])
+			.ptr(OBJECT_LINKS[0])
+			.ptr(CHILD_LINKS[0])
+			.ptr(CHILD_LINKS[0]);
+		let master_payload = payload.finish();
+
+		let mut payload = builder.payload();
+		payload.raw(&id("GRProps")).ptr(OBJECT_LINKS[1]).ptr(OBJECT_LINKS[2]);
+		if cyclic {
+			payload.ptr(CHILD_LINKS[1]).ptr(CHILD_LINKS[1]);
+		} else {
+			payload.ptr(0).ptr(0);
+		}
+		let props_payload = payload.finish();
+
+		let blocks = [
+			(*b"SC\0\0", scene, SCENE, scene_payload),
+			(*b"DATA", collection, MASTER, master_payload),
+			(*b"DATA", object_link, OBJECT_LINKS[0], link_payload(0, 0, CAMERA)),
+			(*b"DATA", child_link, CHILD_LINKS[0], link_payload(0, 0, PROPS)),
+			(*b"GR\0\0", collection, PROPS, props_payload),
+			(*b"DATA", object_link, OBJECT_LINKS[1], link_payload(OBJECT_LINKS[2], 0, EMPTY)),
+			(*b"DATA", object_link, OBJECT_LINKS[2], link_payload(0, OBJECT_LINKS[1], CUBE)),
+			(*b"DATA", child_link, CHILD_LINKS[1], link_payload(0, 0, PROPS)),
+			(*b"OB\0\0", object, CAMERA, object_payload("OBCamera", 0, 11)),
+			(*b"OB\0\0", object, EMPTY, object_payload("OBEmpty", 0, 0)),
+			(*b"OB\0\0", object, CUBE, object_payload("OBCube", EMPTY, 1)),
+		];
+		for (code, sdna_nr, old, payload) in blocks {
+			builder.block(code, sdna_nr, old, 1, payload);
+		}
+		builder.build()
+	}
+
+	fn hierarchies(cyclic: bool) -> Vec<SceneHierarchy> {
+		let blend = BlendFile::from_bytes(scene_file(cyclic)).expect("synthetic file opens");
+		let dna = blend.dna().expect("dna parses");
+		let index = blend.pointer_index().expect("pointer index builds");
+		let ids = IdIndex::build(scan_id_blocks(&blend, &dna).expect("id scan succeeds"));
+		scene_hierarchies(&dna, &index, &ids, &HierarchyOptions::default()).expect("hierarchy decodes")
+	}
+
+	#[test]
+	fn master_collection_expands_children_objects_and_parents() {
+		let scenes = hierarchies(false);
+		assert_eq!(scenes.len(), 1);
+		let scene = &scenes[0];
+		assert_eq!(scene.scene_name.as_ref(), "SCScene");
+		assert!(!scene.truncated);
+		assert!(scene.legacy_bases.is_empty());
+
+		let master = scene.master.as_ref().expect("master collection");
+		assert_eq!(master.name.as_ref(), "GRScene Collection");
+		assert_eq!(master.objects.len(), 1);
+		assert_eq!(master.objects[0].type_label(), "CAMERA");
+
+		let props = &master.children[0];
+		assert_eq!(props.name.as_ref(), "GRProps");
+		let names: Vec<&str> = props.objects.iter().map(|object| object.name.as_ref()).collect();
+		assert_eq!(names, ["OBEmpty", "OBCube"]);
+		assert_eq!(props.objects[1].type_label(), "MESH");
+		assert_eq!(props.objects[1].parent, Some(EMPTY));
+		assert_eq!(props.objects[1].parent_name.as_deref(), Some("OBEmpty"));
+	}
+
+	#[test]
+	fn collection_cycle_is_cut_and_reported() {
+		let scenes = hierarchies(true);
+		let props = &scenes[0].master.as_ref().expect("master collection").children[0];
+		assert!(props.children.is_empty());
+		assert!(scenes[0].truncated);
+	}
+}
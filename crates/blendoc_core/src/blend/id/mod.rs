@@ -1,10 +1,18 @@
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 
 use crate::blend::decl::parse_field_decl;
-use crate::blend::{BlendError, BlendFile, DecodeOptions, Dna, Result, StructValue, Value, decode_struct_instance};
+use crate::blend::suggest::edit_distance;
+use crate::blend::trace::trace_span;
+use crate::blend::{
+	BlendError, BlendFile, Block, DecodeOptions, Dna, Progress, ProgressStage, Result, StructValue, Value, Warning, WarningCollector, WarningKind,
+	decode_struct_instance, glob_match, is_glob,
+};
+
+/// Maximum number of near-miss names attached to [`BlendError::IdRecordNotFound`].
+pub const MAX_ID_SUGGESTIONS: usize = 5;
 
 /// One ID-root block summarized with extracted `ID` header fields.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub struct IdRecord {
 	/// Block old-pointer base.
 	pub old_ptr: u64,
@@ -24,26 +32,173 @@ pub struct IdRecord {
 	pub lib: Option<u64>,
 }
 
+/// Stable handle to an [`IdIndex`] entry.
+///
+/// Handles survive [`IdIndex::apply_delta`] as long as the ID keeps its
+/// pointer or its name, so long-lived clients can hold them across file
+/// changes instead of re-resolving names.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct IdHandle(u32);
+
+impl IdHandle {
+	/// Raw handle value, unique within one index's lifetime.
+	pub fn raw(self) -> u32 {
+		self.0
+	}
+}
+
+/// Changed and removed ID-root records to fold into an existing index.
+#[derive(Debug, Clone, Default)]
+pub struct IdDelta {
+	/// New or re-decoded records; matched to existing entries by pointer, then by name.
+	pub upserted: Vec<IdRecord>,
+	/// Pointers of ID-root blocks that no longer exist.
+	pub removed: Vec<u64>,
+}
+
+/// Handles affected by one [`IdIndex::apply_delta`] call.
+#[derive(Debug, Clone, Default)]
+pub struct IdIndexUpdate {
+	/// Entries that did not exist before.
+	pub added: Vec<IdHandle>,
+	/// Existing entries whose record changed (including pointer moves).
+	pub changed: Vec<IdHandle>,
+	/// Entries dropped from the index; these handles no longer resolve.
+	pub removed: Vec<IdHandle>,
+}
+
+impl IdIndexUpdate {
+	/// Whether the delta left the index unchanged.
+	pub fn is_empty(&self) -> bool {
+		self.added.is_empty() && self.changed.is_empty() && self.removed.is_empty()
+	}
+}
+
 /// Lookup index for ID records by canonical pointer and by `ID.name`.
+///
+/// Names are not unique: a linked library may bring in an ID named like a
+/// local one, so every record with a name is kept (see [`IdIndex::get_all_by_name`]).
 #[derive(Debug, Clone)]
 pub struct IdIndex {
 	/// All scanned ID records.
 	pub records: Vec<IdRecord>,
+	handles: Vec<IdHandle>,
+	next_handle: u32,
 	by_ptr: HashMap<u64, usize>,
-	by_name: HashMap<Box<str>, usize>,
+	by_name: HashMap<Box<str>, Vec<usize>>,
+	by_handle: HashMap<IdHandle, usize>,
 }
 
 impl IdIndex {
 	/// Build index maps for scanned ID records.
 	pub fn build(records: Vec<IdRecord>) -> Self {
-		let mut by_ptr = HashMap::new();
-		let mut by_name = HashMap::new();
-		for (idx, record) in records.iter().enumerate() {
-			by_ptr.entry(record.old_ptr).or_insert(idx);
-			by_name.entry(record.id_name.clone()).or_insert(idx);
+		let handles: Vec<IdHandle> = (0..records.len() as u32).map(IdHandle).collect();
+		let mut out = Self {
+			next_handle: handles.len() as u32,
+			records,
+			handles,
+			by_ptr: HashMap::new(),
+			by_name: HashMap::new(),
+			by_handle: HashMap::new(),
+		};
+		out.rebuild_maps();
+		out
+	}
+
+	fn rebuild_maps(&mut self) {
+		self.by_ptr.clear();
+		self.by_name.clear();
+		self.by_handle.clear();
+		for (idx, (record, handle)) in self.records.iter().zip(&self.handles).enumerate() {
+			self.by_ptr.entry(record.old_ptr).or_insert(idx);
+			self.by_name.entry(record.id_name.clone()).or_default().push(idx);
+			self.by_handle.insert(*handle, idx);
 		}
+	}
+
+	/// Handles parallel to `records`.
+	pub fn handles(&self) -> &[IdHandle] {
+		&self.handles
+	}
 
-		Self { records, by_ptr, by_name }
+	/// Look up an ID record by stable handle.
+	pub fn get_by_handle(&self, handle: IdHandle) -> Option<&IdRecord> {
+		let idx = self.by_handle.get(&handle)?;
+		self.records.get(*idx)
+	}
+
+	/// Stable handle for the record at a canonical pointer.
+	pub fn handle_by_ptr(&self, ptr: u64) -> Option<IdHandle> {
+		self.by_ptr.get(&ptr).map(|idx| self.handles[*idx])
+	}
+
+	/// Stable handle for the first record with an exact `ID.name`.
+	pub fn handle_by_name(&self, name: &str) -> Option<IdHandle> {
+		self.by_name.get(name).map(|slots| self.handles[slots[0]])
+	}
+
+	/// Fold changed and removed records into the index without rescanning the file.
+	///
+	/// An upserted record keeps the handle of the entry at the same pointer,
+	/// or failing that the entry with the same name (Blender moves IDs to new
+	/// addresses on every save). Records stay sorted by pointer.
+	pub fn apply_delta(&mut self, delta: IdDelta) -> IdIndexUpdate {
+		let mut update = IdIndexUpdate::default();
+		let removed_ptrs: HashSet<u64> = delta.removed.into_iter().collect();
+		let upserted_ptrs: HashSet<u64> = delta.upserted.iter().map(|record| record.old_ptr).collect();
+		let mut reused: HashSet<usize> = HashSet::new();
+
+		for record in delta.upserted {
+			let slot = self.by_ptr.get(&record.old_ptr).copied().or_else(|| {
+				// A same-named entry only moves when its old pointer is not itself being refreshed.
+				self.by_name
+					.get(&record.id_name)
+					.into_iter()
+					.flatten()
+					.copied()
+					.find(|idx| !upserted_ptrs.contains(&self.records[*idx].old_ptr) && !reused.contains(idx))
+			});
+
+			match slot {
+				Some(idx) => {
+					reused.insert(idx);
+					if self.records[idx] != record {
+						self.records[idx] = record;
+						update.changed.push(self.handles[idx]);
+					}
+				}
+				None => {
+					let handle = IdHandle(self.next_handle);
+					self.next_handle += 1;
+					self.records.push(record);
+					self.handles.push(handle);
+					reused.insert(self.records.len() - 1);
+					update.added.push(handle);
+				}
+			}
+		}
+
+		let mut keep = Vec::with_capacity(self.records.len());
+		for (idx, (record, handle)) in self.records.drain(..).zip(self.handles.drain(..)).enumerate() {
+			if removed_ptrs.contains(&record.old_ptr) && !reused.contains(&idx) {
+				update.removed.push(handle);
+			} else {
+				keep.push((record, handle));
+			}
+		}
+		keep.sort_by_key(|(record, _)| record.old_ptr);
+		(self.records, self.handles) = keep.into_iter().unzip();
+
+		self.rebuild_maps();
+		update
+	}
+
+	/// Re-decode changed blocks and fold them into the index.
+	///
+	/// `changed` blocks that are no longer ID roots are treated as removals.
+	pub fn update_from_blocks<'a>(&mut self, dna: &Dna, changed: impl IntoIterator<Item = Block<'a>>, removed: &[u64]) -> Result<IdIndexUpdate> {
+		let delta = id_delta_from_blocks(dna, changed, removed)?;
+		Ok(self.apply_delta(delta))
 	}
 
 	/// Look up an ID record by canonical pointer.
@@ -52,10 +207,122 @@ impl IdIndex {
 		self.records.get(*idx)
 	}
 
-	/// Look up an ID record by exact `ID.name`.
+	/// Look up the first ID record with an exact `ID.name`.
 	pub fn get_by_name(&self, name: &str) -> Option<&IdRecord> {
-		let idx = self.by_name.get(name)?;
-		self.records.get(*idx)
+		let slots = self.by_name.get(name)?;
+		self.records.get(slots[0])
+	}
+
+	/// Every ID record with an exact `ID.name`, in index order.
+	pub fn get_all_by_name(&self, name: &str) -> Vec<&IdRecord> {
+		self.by_name
+			.get(name)
+			.map(|slots| slots.iter().map(|idx| &self.records[*idx]).collect())
+			.unwrap_or_default()
+	}
+
+	/// Name of the library an ID is linked from: its `Library` ID's name
+	/// without the `LI` prefix (usually the library file name). `None` for
+	/// local IDs and libraries missing from the index.
+	pub fn library_name(&self, record: &IdRecord) -> Option<&str> {
+		let library = self.get_by_ptr(record.lib.filter(|lib| *lib != 0)?)?;
+		Some(library.id_name.get(2..).unwrap_or_default())
+	}
+
+	/// Shortest selector that [`IdIndex::resolve_selector`] resolves to `record`.
+	///
+	/// This is the plain name when it is unique, otherwise `name@library`
+	/// (`name@` for the local ID).
+	pub fn selector_for(&self, record: &IdRecord) -> String {
+		if self.by_name.get(&record.id_name).is_none_or(|slots| slots.len() < 2) {
+			return record.id_name.to_string();
+		}
+		format!("{}@{}", record.id_name, self.library_name(record).unwrap_or_default())
+	}
+
+	/// Return records whose `ID.name` matches a glob (`OB*`, `*.001`), in index order.
+	pub fn find_by_pattern(&self, pattern: &str, ignore_case: bool) -> Vec<&IdRecord> {
+		self.records.iter().filter(|record| glob_match(pattern, &record.id_name, ignore_case)).collect()
+	}
+
+	/// Resolve a user-facing ID selector to exactly one record.
+	///
+	/// Exact names win; otherwise the selector is matched as a glob, then
+	/// case-insensitively, and must select a single ID. A name shared by
+	/// several IDs resolves to the local one when exactly one is local.
+	///
+	/// `name@library.blend` keeps only IDs linked from that library (matched
+	/// against [`IdIndex::library_name`]), and `name@` only local IDs.
+	pub fn resolve_selector(&self, selector: &str) -> Result<&IdRecord> {
+		let exact = self.get_all_by_name(selector);
+		if !exact.is_empty() {
+			return self.pick(selector, exact);
+		}
+
+		let (name, library) = match selector.rsplit_once('@') {
+			Some((name, library)) => (name, Some(library)),
+			None => (selector, None),
+		};
+		let mut matches = self.get_all_by_name(name);
+		if matches.is_empty() && is_glob(name) {
+			matches = self.find_by_pattern(name, false);
+		}
+		if matches.is_empty() {
+			matches = self.find_by_pattern(name, true);
+		}
+		if let Some(library) = library {
+			matches.retain(|record| self.library_name(record).unwrap_or_default() == library);
+		}
+		if matches.is_empty() && !is_glob(name) {
+			return Err(BlendError::IdRecordNotFound {
+				name: selector.to_owned(),
+				suggestions: self
+					.suggest(name, MAX_ID_SUGGESTIONS)
+					.into_iter()
+					.map(|record| self.selector_for(record))
+					.collect(),
+			});
+		}
+		self.pick(selector, matches)
+	}
+
+	/// Records whose names are close to `name`, best first, at most `limit`.
+	///
+	/// Names are compared case-insensitively by Levenshtein distance, both in
+	/// full and without the two-letter ID code (so `Cubee` finds `OBCube`);
+	/// names containing `name` are kept too.
+	pub fn suggest(&self, name: &str, limit: usize) -> Vec<&IdRecord> {
+		let needle = name.to_ascii_lowercase();
+		let threshold = (needle.chars().count() / 3).max(2);
+
+		let mut scored: Vec<(usize, usize)> = Vec::new();
+		for (idx, record) in self.records.iter().enumerate() {
+			let full = record.id_name.to_ascii_lowercase();
+			let bare = full.get(2..).unwrap_or_default();
+			let distance = edit_distance(&needle, &full).min(edit_distance(&needle, bare));
+			let contains = needle.len() >= 3 && full.contains(&needle);
+			if distance <= threshold || contains {
+				scored.push((distance, idx));
+			}
+		}
+		scored.sort();
+		scored.into_iter().take(limit).map(|(_, idx)| &self.records[idx]).collect()
+	}
+
+	fn pick<'s>(&'s self, selector: &str, matches: Vec<&'s IdRecord>) -> Result<&'s IdRecord> {
+		let mut local = matches.iter().filter(|record| self.library_name(record).is_none());
+		match (matches.as_slice(), local.next(), local.next()) {
+			([record], ..) => Ok(record),
+			([], ..) => Err(BlendError::IdRecordNotFound {
+				name: selector.to_owned(),
+				suggestions: Vec::new(),
+			}),
+			([first, rest @ ..], Some(record), None) if rest.iter().all(|other| other.id_name == first.id_name) => Ok(record),
+			_ => Err(BlendError::IdSelectorAmbiguous {
+				selector: selector.to_owned(),
+				matches: matches.iter().map(|record| self.selector_for(record)).collect(),
+			}),
+		}
 	}
 }
 
@@ -65,78 +332,191 @@ struct IdLayout {
 	id_size: usize,
 }
 
+/// Per-record failure handling for [`scan_id_blocks_with`].
+#[derive(Debug, Clone, Default)]
+pub struct IdScanOptions {
+	/// Return the first undecodable `ID` header as an error instead of skipping its block.
+	pub strict: bool,
+	/// Receives one [`WarningKind::SkippedId`] per skipped block.
+	pub warnings: Option<WarningCollector>,
+	/// Receives a [`ProgressStage::Blocks`] update per scanned block.
+	pub progress: Option<Progress>,
+}
+
 /// Scan all blocks and extract `ID` headers for ID-root structs.
+///
+/// Blocks whose `ID` header does not decode are skipped; use
+/// [`scan_id_blocks_with`] to collect them as warnings or to fail instead.
 pub fn scan_id_blocks(file: &BlendFile, dna: &Dna) -> Result<Vec<IdRecord>> {
+	scan_id_blocks_with(file, dna, &IdScanOptions::default())
+}
+
+/// [`scan_id_blocks`] with explicit handling of undecodable `ID` headers.
+///
+/// Unreadable block headers and a schema without an `ID` struct are always
+/// errors, since no later record could be trusted.
+pub fn scan_id_blocks_with(file: &BlendFile, dna: &Dna, options: &IdScanOptions) -> Result<Vec<IdRecord>> {
+	trace_span!("ids.scan");
 	let layout = detect_id_layout(dna)?;
 	let id_roots = id_root_flags(dna);
-
-	let decode = DecodeOptions {
-		include_padding: true,
-		strict_layout: true,
-		..DecodeOptions::default()
-	};
+	let decode = id_decode_options();
 
 	let mut out = Vec::new();
-	for block in file.blocks() {
+	for (scanned, block) in file.blocks().enumerate() {
 		let block = block?;
+		if let Some(progress) = &options.progress {
+			progress.update(ProgressStage::Blocks, scanned as u64 + 1, None);
+		}
 		let is_id_root = id_roots.get(block.head.sdna_nr as usize).copied().unwrap_or(false);
 		if !is_id_root {
 			continue;
 		}
 
-		if block.payload.len() < layout.id_size {
-			return Err(BlendError::DecodePayloadTooSmall {
-				need: layout.id_size,
-				have: block.payload.len(),
-			});
+		match decode_id_record(dna, layout, &decode, &block) {
+			Ok(record) => out.push(record),
+			Err(err) if options.strict => return Err(err),
+			Err(err) => {
+				if let Some(warnings) = &options.warnings {
+					warnings.push(Warning::new(
+						WarningKind::SkippedId,
+						Some(block.head.old),
+						format!(
+							"{} block at offset {}: {err}",
+							String::from_utf8_lossy(&block.head.code).trim_end_matches('\0'),
+							block.file_offset
+						),
+					));
+				}
+			}
 		}
-
-		let id = decode_struct_instance(dna, layout.id_sdna, &block.payload[..layout.id_size], &decode)?;
-		let type_name = dna
-			.struct_by_sdna(block.head.sdna_nr)
-			.map(|item| dna.type_name(item.type_idx))
-			.unwrap_or("<unknown>")
-			.to_owned()
-			.into_boxed_str();
-
-		out.push(IdRecord {
-			old_ptr: block.head.old,
-			code: block.head.code,
-			sdna_nr: block.head.sdna_nr,
-			type_name,
-			id_name: extract_name_field(&id)?.into_boxed_str(),
-			next: extract_ptr_field(&id, "next"),
-			prev: extract_ptr_field(&id, "prev"),
-			lib: extract_ptr_field(&id, "lib"),
-		});
+	}
+	if let Some(progress) = &options.progress {
+		progress.finish(ProgressStage::Blocks);
 	}
 	out.sort_by_key(|item| item.old_ptr);
 
 	Ok(out)
 }
 
+/// Find the first ID-root record named `id_name`, stopping at the first match.
+///
+/// Only blocks whose code matches the two-letter ID-name prefix (`OB` for
+/// `OBCube`) have their `ID` header decoded, so lookups on large files skip
+/// nearly all payload decoding.
+pub fn find_id_block(file: &BlendFile, dna: &Dna, id_name: &str) -> Result<Option<IdRecord>> {
+	let Some(prefix) = id_name.as_bytes().get(..2) else {
+		return Ok(None);
+	};
+	let layout = detect_id_layout(dna)?;
+	let id_roots = id_root_flags(dna);
+	let decode = id_decode_options();
+
+	for block in file.blocks() {
+		let block = block?;
+		if &block.head.code[..2] != prefix || block.head.code[2..] != [0, 0] {
+			continue;
+		}
+		if !id_roots.get(block.head.sdna_nr as usize).copied().unwrap_or(false) {
+			continue;
+		}
+
+		// A corrupt block with the same prefix must not hide a later match.
+		let Ok(record) = decode_id_record(dna, layout, &decode, &block) else {
+			continue;
+		};
+		if record.id_name.as_ref() == id_name {
+			return Ok(Some(record));
+		}
+	}
+
+	Ok(None)
+}
+
+/// Decode `ID` headers for changed blocks into an [`IdDelta`].
+///
+/// Non-ID-root blocks among `changed` are reported as removed so an ID
+/// whose block was replaced by plain data drops out of the index.
+pub fn id_delta_from_blocks<'a>(dna: &Dna, changed: impl IntoIterator<Item = Block<'a>>, removed: &[u64]) -> Result<IdDelta> {
+	let layout = detect_id_layout(dna)?;
+	let id_roots = id_root_flags(dna);
+	let decode = id_decode_options();
+
+	let mut delta = IdDelta {
+		upserted: Vec::new(),
+		removed: removed.to_vec(),
+	};
+	for block in changed {
+		if id_roots.get(block.head.sdna_nr as usize).copied().unwrap_or(false) {
+			delta.upserted.push(decode_id_record(dna, layout, &decode, &block)?);
+		} else {
+			delta.removed.push(block.head.old);
+		}
+	}
+
+	Ok(delta)
+}
+
+fn id_decode_options() -> DecodeOptions {
+	DecodeOptions {
+		include_padding: true,
+		strict_layout: true,
+		..DecodeOptions::default()
+	}
+}
+
+fn decode_id_record(dna: &Dna, layout: IdLayout, decode: &DecodeOptions, block: &Block<'_>) -> Result<IdRecord> {
+	if block.payload.len() < layout.id_size {
+		return Err(BlendError::DecodePayloadTooSmall {
+			need: layout.id_size,
+			have: block.payload.len(),
+		});
+	}
+
+	let id = decode_struct_instance(dna, layout.id_sdna, &block.payload[..layout.id_size], decode)?;
+	let type_name = dna
+		.struct_by_sdna(block.head.sdna_nr)
+		.map(|item| dna.type_name(item.type_idx))
+		.unwrap_or("<unknown>")
+		.to_owned()
+		.into_boxed_str();
+
+	Ok(IdRecord {
+		old_ptr: block.head.old,
+		code: block.head.code,
+		sdna_nr: block.head.sdna_nr,
+		type_name,
+		id_name: extract_name_field(&id)?.into_boxed_str(),
+		next: extract_ptr_field(&id, "next"),
+		prev: extract_ptr_field(&id, "prev"),
+		lib: extract_ptr_field(&id, "lib"),
+	})
+}
+
 fn detect_id_layout(dna: &Dna) -> Result<IdLayout> {
-	let Some(id_type_idx) = dna.types.iter().position(|item| item.as_ref() == "ID") else {
+	let Some(id_type_idx) = dna.type_index("ID") else {
 		return Err(BlendError::DnaStructNotFound { name: "ID".to_owned() });
 	};
 
-	let Some(id_sdna) = dna.struct_for_type.get(id_type_idx).and_then(|item| *item) else {
+	let Some(id_sdna) = dna.struct_for_type.get(id_type_idx as usize).and_then(|item| *item) else {
 		return Err(BlendError::DnaStructNotFound { name: "ID".to_owned() });
 	};
 
-	let id_size = usize::from(dna.tlen[id_type_idx]);
+	let id_size = usize::from(dna.tlen[id_type_idx as usize]);
 	Ok(IdLayout { id_sdna, id_size })
 }
 
 fn id_root_flags(dna: &Dna) -> Vec<bool> {
 	let mut out = vec![false; dna.structs.len()];
+	let Some(id_type_idx) = dna.type_index("ID") else {
+		return out;
+	};
 
 	for (sdna_idx, item) in dna.structs.iter().enumerate() {
 		let Some(first) = item.fields.first() else {
 			continue;
 		};
 
-		if dna.type_name(first.type_idx) != "ID" {
+		if first.type_idx != id_type_idx {
 			continue;
 		}
 
@@ -2,7 +2,7 @@ mod fixtures_day5_ids {
 
 	use blendoc_testkit::fixture_path;
 
-	use crate::blend::{BlendFile, scan_id_blocks};
+	use crate::blend::{BlendFile, IdIndex, codes, find_id_block, scan_id_blocks};
 
 	#[test]
 	fn character_ids_scan_smoke() {
@@ -21,11 +21,40 @@ mod fixtures_day5_ids {
 
 		assert!(!rows.is_empty(), "expected at least one ID-root block");
 		assert!(
-			rows.iter().any(|row| row.code == [b'S', b'C', 0, 0] || row.type_name.as_ref() == "Scene"),
+			rows.iter().any(|row| row.code == codes::ID_SCE || row.type_name.as_ref() == "Scene"),
 			"expected Scene-derived ID entry"
 		);
 		assert!(rows.iter().all(|row| !row.id_name.trim().is_empty()), "expected non-empty ID names");
 	}
+
+	#[test]
+	fn find_id_block_matches_full_scan() {
+		let blend = BlendFile::open(fixture_path("character.blend")).expect("fixture opens");
+		let dna = blend.dna().expect("dna parses");
+		let rows = scan_id_blocks(&blend, &dna).expect("id scan succeeds");
+
+		for row in rows.iter().take(16) {
+			let found = find_id_block(&blend, &dna, &row.id_name).expect("lookup succeeds").expect("id found");
+			assert_eq!(found.id_name, row.id_name);
+			assert_eq!(found.code, row.code);
+		}
+		assert!(find_id_block(&blend, &dna, "OBdefinitely_missing").expect("lookup succeeds").is_none());
+		assert!(find_id_block(&blend, &dna, "O").expect("lookup succeeds").is_none());
+	}
+
+	#[test]
+	fn rescanning_every_block_is_a_no_op_update() {
+		let blend = BlendFile::open(fixture_path("character.blend")).expect("fixture opens");
+		let dna = blend.dna().expect("dna parses");
+		let mut ids = IdIndex::build(scan_id_blocks(&blend, &dna).expect("id scan succeeds"));
+		let handles = ids.handles().to_vec();
+
+		let blocks = blend.blocks().collect::<Result<Vec<_>, _>>().expect("blocks parse");
+		let update = ids.update_from_blocks(&dna, blocks, &[]).expect("update succeeds");
+
+		assert!(update.is_empty());
+		assert_eq!(ids.handles(), handles.as_slice());
+	}
 }
 
 mod id_root_detection {
@@ -34,13 +63,13 @@ mod id_root_detection {
 
 	#[test]
 	fn id_root_detection_handles_non_id_roots() {
-		let dna = Dna {
-			endianness: crate::blend::Endianness::Little,
-			pointer_size: 8,
-			names: vec!["id".into(), "other".into()],
-			types: vec!["ID".into(), "Scene".into(), "NoIdRoot".into()],
-			tlen: vec![8, 24, 16],
-			structs: vec![
+		let dna = Dna::from_tables(
+			crate::blend::Endianness::Little,
+			8,
+			vec!["id".into(), "other".into()],
+			vec!["ID".into(), "Scene".into(), "NoIdRoot".into()],
+			vec![8, 24, 16],
+			vec![
 				DnaStruct {
 					type_idx: 0,
 					fields: vec![DnaField { type_idx: 0, name_idx: 1 }],
@@ -54,10 +83,283 @@ mod id_root_detection {
 					fields: vec![DnaField { type_idx: 2, name_idx: 1 }],
 				},
 			],
-			struct_for_type: vec![Some(0), Some(1), Some(2)],
-		};
+		)
+		.expect("valid dna tables");
 
 		let roots = id_root_flags(&dna);
 		assert_eq!(roots, vec![false, true, false]);
 	}
 }
+
+mod synthetic_id_selectors {
+	use crate::blend::{BlendError, IdIndex, IdRecord};
+
+	fn record(old_ptr: u64, id_name: &str) -> IdRecord {
+		IdRecord {
+			old_ptr,
+			code: [id_name.as_bytes()[0], id_name.as_bytes()[1], 0, 0],
+			sdna_nr: 0,
+			type_name: "Object".into(),
+			id_name: id_name.into(),
+			next: None,
+			prev: None,
+			lib: None,
+		}
+	}
+
+	fn index() -> IdIndex {
+		IdIndex::build(vec![
+			record(0x1000, "OBCube"),
+			record(0x2000, "OBCube.001"),
+			record(0x3000, "MECube.001"),
+			record(0x4000, "MAMetal"),
+		])
+	}
+
+	#[test]
+	fn find_by_pattern_matches_globs_in_index_order() {
+		let ids = index();
+		let names = |pattern: &str, ignore_case: bool| -> Vec<String> {
+			ids.find_by_pattern(pattern, ignore_case)
+				.iter()
+				.map(|record| record.id_name.to_string())
+				.collect()
+		};
+
+		assert_eq!(names("OB*", false), vec!["OBCube", "OBCube.001"]);
+		assert_eq!(names("*.001", false), vec!["OBCube.001", "MECube.001"]);
+		assert!(names("ma*", false).is_empty());
+		assert_eq!(names("ma*", true), vec!["MAMetal"]);
+	}
+
+	#[test]
+	fn resolve_selector_prefers_exact_then_unique_match() {
+		let ids = index();
+
+		assert_eq!(ids.resolve_selector("OBCube").expect("exact").old_ptr, 0x1000);
+		assert_eq!(ids.resolve_selector("ME*").expect("unique glob").old_ptr, 0x3000);
+		assert_eq!(ids.resolve_selector("mametal").expect("case-insensitive").old_ptr, 0x4000);
+
+		match ids.resolve_selector("OB*") {
+			Err(BlendError::IdSelectorAmbiguous { matches, .. }) => assert_eq!(matches, vec!["OBCube", "OBCube.001"]),
+			other => panic!("expected ambiguous selector, got {other:?}"),
+		}
+		assert!(matches!(ids.resolve_selector("CA*"), Err(BlendError::IdRecordNotFound { .. })));
+	}
+
+	#[test]
+	fn missing_names_carry_close_suggestions() {
+		let ids = index();
+		let names = |name: &str, limit: usize| -> Vec<String> { ids.suggest(name, limit).iter().map(|record| record.id_name.to_string()).collect() };
+
+		assert_eq!(names("OBCubee", 5), ["OBCube"]);
+		assert_eq!(names("cube", 5), ["OBCube", "OBCube.001", "MECube.001"]);
+		assert_eq!(names("cube", 1), ["OBCube"]);
+		assert!(names("Zebra", 5).is_empty());
+
+		let err = ids.resolve_selector("OBCubee").expect_err("missing");
+		assert!(matches!(&err, BlendError::IdRecordNotFound { suggestions, .. } if suggestions == &["OBCube"]));
+		assert_eq!(err.to_string(), "ID record not found: OBCubee (did you mean `OBCube`?)");
+		assert_eq!(ids.resolve_selector("Zebra").expect_err("missing").to_string(), "ID record not found: Zebra");
+	}
+}
+
+mod synthetic_name_collisions {
+	use crate::blend::{BlendError, IdIndex, IdRecord};
+
+	fn record(old_ptr: u64, id_name: &str, lib: Option<u64>) -> IdRecord {
+		IdRecord {
+			old_ptr,
+			code: [id_name.as_bytes()[0], id_name.as_bytes()[1], 0, 0],
+			sdna_nr: 0,
+			type_name: "Object".into(),
+			id_name: id_name.into(),
+			next: None,
+			prev: None,
+			lib,
+		}
+	}
+
+	/// Local `OBCube` plus one linked from each of two libraries, and a
+	/// `MECube` linked from both.
+	fn index() -> IdIndex {
+		IdIndex::build(vec![
+			record(0x1000, "LIprops.blend", None),
+			record(0x1100, "LIrig.blend", None),
+			record(0x2000, "OBCube", None),
+			record(0x2100, "OBCube", Some(0x1000)),
+			record(0x2200, "OBCube", Some(0x1100)),
+			record(0x3000, "MECube", Some(0x1000)),
+			record(0x3100, "MECube", Some(0x1100)),
+		])
+	}
+
+	#[test]
+	fn duplicate_names_are_all_indexed() {
+		let ids = index();
+		let ptrs: Vec<u64> = ids.get_all_by_name("OBCube").iter().map(|record| record.old_ptr).collect();
+		assert_eq!(ptrs, [0x2000, 0x2100, 0x2200]);
+		assert_eq!(ids.get_by_name("OBCube").map(|record| record.old_ptr), Some(0x2000));
+		assert!(ids.get_all_by_name("OBMissing").is_empty());
+		assert_eq!(ids.library_name(ids.get_all_by_name("OBCube")[2]), Some("rig.blend"));
+	}
+
+	#[test]
+	fn library_suffix_disambiguates_and_local_wins_plain_names() {
+		let ids = index();
+		let resolve = |selector: &str| ids.resolve_selector(selector).map(|record| record.old_ptr);
+
+		assert_eq!(resolve("OBCube").expect("local preferred"), 0x2000);
+		assert_eq!(resolve("OBCube@").expect("local only"), 0x2000);
+		assert_eq!(resolve("OBCube@props.blend").expect("props copy"), 0x2100);
+		assert_eq!(resolve("OB*@rig.blend").expect("glob within library"), 0x2200);
+		assert_eq!(resolve("mecube@rig.blend").expect("case-insensitive within library"), 0x3100);
+		assert!(matches!(resolve("OBCube@other.blend"), Err(BlendError::IdRecordNotFound { .. })));
+
+		match resolve("MECube") {
+			Err(BlendError::IdSelectorAmbiguous { matches, .. }) => assert_eq!(matches, ["MECube@props.blend", "MECube@rig.blend"]),
+			other => panic!("expected ambiguous selector, got {other:?}"),
+		}
+		for record in &ids.records {
+			assert_eq!(resolve(&ids.selector_for(record)).expect("selector round-trips"), record.old_ptr);
+		}
+	}
+}
+
+mod synthetic_incremental_index {
+	use crate::blend::{IdDelta, IdIndex, IdRecord};
+
+	fn record(old_ptr: u64, id_name: &str) -> IdRecord {
+		IdRecord {
+			old_ptr,
+			code: [id_name.as_bytes()[0], id_name.as_bytes()[1], 0, 0],
+			sdna_nr: 0,
+			type_name: "Object".into(),
+			id_name: id_name.into(),
+			next: None,
+			prev: None,
+			lib: None,
+		}
+	}
+
+	#[test]
+	fn handles_survive_changes_moves_and_unrelated_removals() {
+		let mut ids = IdIndex::build(vec![record(0x1000, "OBCube"), record(0x2000, "OBLamp"), record(0x3000, "MEMesh")]);
+		let cube = ids.handle_by_name("OBCube").expect("cube handle");
+		let lamp = ids.handle_by_ptr(0x2000).expect("lamp handle");
+		let mesh = ids.handle_by_name("MEMesh").expect("mesh handle");
+
+		let mut renamed = record(0x1000, "OBBox");
+		renamed.next = Some(0x2000);
+		let update = ids.apply_delta(IdDelta {
+			upserted: vec![renamed, record(0x5000, "OBLamp"), record(0x4000, "CACam")],
+			removed: vec![0x2000, 0x3000],
+		});
+
+		assert_eq!(update.changed, vec![cube, lamp]);
+		assert_eq!(update.removed, vec![mesh]);
+		assert_eq!(update.added.len(), 1);
+
+		assert_eq!(ids.get_by_handle(cube).map(|item| item.id_name.as_ref()), Some("OBBox"));
+		assert_eq!(ids.get_by_handle(lamp).map(|item| item.old_ptr), Some(0x5000));
+		assert!(ids.get_by_handle(mesh).is_none());
+		assert!(ids.get_by_name("OBCube").is_none());
+		assert_eq!(ids.get_by_ptr(0x4000).map(|item| item.id_name.as_ref()), Some("CACam"));
+		assert_eq!(ids.handle_by_ptr(0x4000), Some(update.added[0]));
+
+		let ptrs: Vec<u64> = ids.records.iter().map(|item| item.old_ptr).collect();
+		assert_eq!(ptrs, vec![0x1000, 0x4000, 0x5000]);
+	}
+
+	#[test]
+	fn unchanged_upserts_report_nothing_and_new_handles_are_fresh() {
+		let mut ids = IdIndex::build(vec![record(0x1000, "OBCube")]);
+		let update = ids.apply_delta(IdDelta {
+			upserted: vec![record(0x1000, "OBCube")],
+			removed: Vec::new(),
+		});
+		assert!(update.is_empty());
+
+		let removed = ids.apply_delta(IdDelta {
+			upserted: Vec::new(),
+			removed: vec![0x1000],
+		});
+		let added = ids.apply_delta(IdDelta {
+			upserted: vec![record(0x1000, "OBCube")],
+			removed: Vec::new(),
+		});
+		assert_ne!(removed.removed[0], added.added[0], "handles are never reused");
+	}
+}
+
+mod synthetic_fail_soft_scan {
+	use blendoc_testkit::builder::{BlendBuilder, HeaderKind};
+
+	use crate::blend::{BlendError, BlendFile, IdScanOptions, WarningCollector, WarningKind, find_id_block, scan_id_blocks, scan_id_blocks_with};
+
+	/// `OBBefore`, an `Object` block truncated inside its `ID` header, then `OBAfter`.
+	fn corrupt_file() -> Vec<u8> {
+		let mut builder = BlendBuilder::new(HeaderKind::Legacy8, 404, false);
+		let sdna = builder.sdna_mut();
+		sdna.add_struct("Link", &[("Link", "*next"), ("Link", "*prev")]);
+		sdna.add_type("char", 1);
+		sdna.add_type("void", 0);
+		sdna.add_struct("ID", &[("void", "*next"), ("void", "*prev"), ("void", "*lib"), ("char", "name[24]")]);
+		let object = sdna.add_struct("Object", &[("ID", "id"), ("void", "*data")]);
+
+		let id = |builder: &BlendBuilder, name: &str| {
+			let mut payload = builder.payload();
+			payload.ptr(0).ptr(0).ptr(0).name(name, 24).ptr(0);
+			payload.finish()
+		};
+		let before = id(&builder, "OBBefore");
+		let after = id(&builder, "OBAfter");
+		builder
+			.block(*b"OB\0\0", object, 0x1000, 1, before)
+			.block(*b"OB\0\0", object, 0x2000, 1, vec![0; 8])
+			.block(*b"OB\0\0", object, 0x3000, 1, after);
+		builder.build()
+	}
+
+	#[test]
+	fn undecodable_id_is_skipped_with_warning() {
+		let blend = BlendFile::from_bytes(corrupt_file()).expect("synthetic file parses");
+		let dna = blend.dna().expect("dna parses");
+
+		let warnings = WarningCollector::new();
+		let options = IdScanOptions {
+			warnings: Some(warnings.clone()),
+			..IdScanOptions::default()
+		};
+		let names: Vec<String> = scan_id_blocks_with(&blend, &dna, &options)
+			.expect("fail-soft scan succeeds")
+			.iter()
+			.map(|record| record.id_name.to_string())
+			.collect();
+		assert_eq!(names, ["OBBefore", "OBAfter"]);
+
+		let warnings = warnings.take();
+		assert_eq!(warnings.len(), 1);
+		assert_eq!(warnings[0].kind, WarningKind::SkippedId);
+		assert_eq!(warnings[0].ptr, Some(0x2000));
+		assert!(warnings[0].message.starts_with("OB block at offset"), "{}", warnings[0].message);
+
+		assert_eq!(scan_id_blocks(&blend, &dna).expect("default scan succeeds").len(), 2);
+		assert!(find_id_block(&blend, &dna, "OBAfter").expect("lookup succeeds").is_some());
+	}
+
+	#[test]
+	fn strict_scan_restores_first_error() {
+		let blend = BlendFile::from_bytes(corrupt_file()).expect("synthetic file parses");
+		let dna = blend.dna().expect("dna parses");
+
+		let options = IdScanOptions {
+			strict: true,
+			..IdScanOptions::default()
+		};
+		assert!(matches!(
+			scan_id_blocks_with(&blend, &dna, &options),
+			Err(BlendError::DecodePayloadTooSmall { have: 8, .. })
+		));
+	}
+}
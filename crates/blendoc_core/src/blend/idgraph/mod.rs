@@ -1,7 +1,10 @@
-use std::collections::HashSet;
+use std::collections::{BTreeMap, HashSet};
 use std::sync::Arc;
 
-use crate::blend::{Dna, IdIndex, PointerIndex, RefScanOptions, Result, scan_refs_from_ptr};
+use crate::blend::cancel::is_cancelled;
+use crate::blend::graph::unresolved_warning;
+use crate::blend::ref_cache::scan_refs_cached;
+use crate::blend::{CancelToken, Dna, IdIndex, PointerIndex, Progress, ProgressStage, RefCache, RefScanOptions, Result, Warning};
 
 /// Options for whole-file ID-to-ID graph extraction.
 #[derive(Debug, Clone)]
@@ -12,6 +15,14 @@ pub struct IdGraphOptions {
 	pub max_edges: usize,
 	/// Keep self-edges when source and target canonical pointers match.
 	pub include_self: bool,
+	/// Optional shared memo for per-owner reference scans.
+	pub ref_cache: Option<RefCache>,
+	/// Fill [`IdGraphResult::cycles`] with multi-node strongly connected components.
+	pub detect_cycles: bool,
+	/// Receives a [`ProgressStage::Ids`] update per scanned owner.
+	pub progress: Option<Progress>,
+	/// Stops the owner scan early with [`IdGraphTruncation::Cancelled`] when cancelled.
+	pub cancel: Option<CancelToken>,
 }
 
 impl Default for IdGraphOptions {
@@ -23,6 +34,10 @@ impl Default for IdGraphOptions {
 			},
 			max_edges: 100_000,
 			include_self: false,
+			ref_cache: None,
+			detect_cycles: false,
+			progress: None,
+			cancel: None,
 		}
 	}
 }
@@ -32,6 +47,8 @@ impl Default for IdGraphOptions {
 pub enum IdGraphTruncation {
 	/// Edge budget was reached.
 	MaxEdges,
+	/// Owner scan stopped because the cancel token fired.
+	Cancelled,
 }
 
 /// One ID graph node.
@@ -60,6 +77,15 @@ pub struct IdGraphEdge {
 	pub field: Arc<str>,
 }
 
+/// IDs that reach each other through ID pointers (a strongly connected component).
+#[derive(Debug, Clone)]
+pub struct IdGraphCycle {
+	/// Member canonical pointers, ascending.
+	pub members: Vec<u64>,
+	/// Edges whose source and target are both members, in graph edge order.
+	pub edges: Vec<IdGraphEdge>,
+}
+
 /// Full ID graph extraction result.
 #[derive(Debug, Clone)]
 pub struct IdGraphResult {
@@ -69,6 +95,110 @@ pub struct IdGraphResult {
 	pub edges: Vec<IdGraphEdge>,
 	/// Optional truncation reason.
 	pub truncated: Option<IdGraphTruncation>,
+	/// Non-fatal issues met while scanning owners.
+	pub warnings: Vec<Warning>,
+	/// Cycles found when [`IdGraphOptions::detect_cycles`] is set; empty otherwise.
+	pub cycles: Vec<IdGraphCycle>,
+}
+
+impl IdGraphResult {
+	/// Strongly connected components with more than one member, ordered by smallest member.
+	///
+	/// Self-edges never form a cycle on their own.
+	pub fn find_cycles(&self) -> Vec<IdGraphCycle> {
+		let mut adjacency: BTreeMap<u64, Vec<u64>> = BTreeMap::new();
+		for edge in &self.edges {
+			adjacency.entry(edge.from).or_default().push(edge.to);
+			adjacency.entry(edge.to).or_default();
+		}
+
+		let mut cycles: Vec<IdGraphCycle> = strongly_connected(&adjacency)
+			.into_iter()
+			.filter(|members| members.len() > 1)
+			.map(|mut members| {
+				members.sort_unstable();
+				let edges = self
+					.edges
+					.iter()
+					.filter(|edge| members.binary_search(&edge.from).is_ok() && members.binary_search(&edge.to).is_ok())
+					.cloned()
+					.collect();
+				IdGraphCycle { members, edges }
+			})
+			.collect();
+		cycles.sort_by_key(|cycle| cycle.members[0]);
+		cycles
+	}
+}
+
+/// Tarjan's algorithm with an explicit stack, so deep reference chains cannot overflow.
+fn strongly_connected(adjacency: &BTreeMap<u64, Vec<u64>>) -> Vec<Vec<u64>> {
+	struct Visit {
+		index: usize,
+		low: usize,
+		on_stack: bool,
+	}
+
+	let mut visits: BTreeMap<u64, Visit> = BTreeMap::new();
+	let mut stack = Vec::new();
+	let mut components = Vec::new();
+	for &root in adjacency.keys() {
+		if visits.contains_key(&root) {
+			continue;
+		}
+		// (node, index of the next successor to look at)
+		let mut work = vec![(root, 0_usize)];
+		while let Some(&mut (node, ref mut next)) = work.last_mut() {
+			if *next == 0 && !visits.contains_key(&node) {
+				let index = visits.len();
+				visits.insert(
+					node,
+					Visit {
+						index,
+						low: index,
+						on_stack: true,
+					},
+				);
+				stack.push(node);
+			}
+			let successors = &adjacency[&node];
+			if let Some(&succ) = successors.get(*next) {
+				*next += 1;
+				match visits.get(&succ) {
+					None => work.push((succ, 0)),
+					Some(visit) if visit.on_stack => {
+						let index = visit.index;
+						let low = &mut visits.get_mut(&node).expect("visited").low;
+						*low = (*low).min(index);
+					}
+					Some(_) => {}
+				}
+				continue;
+			}
+
+			work.pop();
+			let (index, low) = {
+				let visit = &visits[&node];
+				(visit.index, visit.low)
+			};
+			if let Some(&(parent, _)) = work.last() {
+				let parent_low = &mut visits.get_mut(&parent).expect("visited").low;
+				*parent_low = (*parent_low).min(low);
+			}
+			if index == low {
+				let mut component = Vec::new();
+				while let Some(member) = stack.pop() {
+					visits.get_mut(&member).expect("visited").on_stack = false;
+					component.push(member);
+					if member == node {
+						break;
+					}
+				}
+				components.push(component);
+			}
+		}
+	}
+	components
 }
 
 /// Build a whole-file graph over ID-root records and ID-to-ID pointer fields.
@@ -88,12 +218,24 @@ pub fn build_id_graph<'a>(dna: &Dna, index: &PointerIndex<'a>, ids: &IdIndex, op
 
 	let mut edges = Vec::new();
 	let mut seen = HashSet::new();
+	let mut warnings = Vec::new();
 	let mut truncated = None;
 
-	'outer: for owner in &ids.records {
-		let refs = scan_refs_from_ptr(dna, index, ids, owner.old_ptr, &options.ref_scan)?;
-		for record in refs {
-			let Some(target) = record.resolved else {
+	let total = ids.records.len() as u64;
+	'outer: for (done, owner) in ids.records.iter().enumerate() {
+		if is_cancelled(options.cancel.as_ref()) {
+			truncated = Some(IdGraphTruncation::Cancelled);
+			break;
+		}
+		if let Some(progress) = &options.progress {
+			progress.update(ProgressStage::Ids, done as u64 + 1, Some(total));
+		}
+		let refs = scan_refs_cached(dna, index, ids, owner.old_ptr, &options.ref_scan, options.ref_cache.as_ref())?;
+		for record in refs.iter() {
+			let Some(target) = &record.resolved else {
+				if record.ptr != 0 {
+					warnings.push(unresolved_warning(&record.owner_type, &record.field, record.ptr));
+				}
 				continue;
 			};
 			if target.id_name.is_none() {
@@ -121,6 +263,10 @@ pub fn build_id_graph<'a>(dna: &Dna, index: &PointerIndex<'a>, ids: &IdIndex, op
 		}
 	}
 
+	if let Some(progress) = &options.progress {
+		progress.finish(ProgressStage::Ids);
+	}
+
 	edges.sort_by(|left, right| {
 		left.from
 			.cmp(&right.from)
@@ -128,8 +274,22 @@ pub fn build_id_graph<'a>(dna: &Dna, index: &PointerIndex<'a>, ids: &IdIndex, op
 			.then_with(|| left.field.cmp(&right.field))
 	});
 
-	Ok(IdGraphResult { nodes, edges, truncated })
+	let mut graph = IdGraphResult {
+		nodes,
+		edges,
+		truncated,
+		warnings,
+		cycles: Vec::new(),
+	};
+	if options.detect_cycles {
+		graph.cycles = graph.find_cycles();
+	}
+	Ok(graph)
 }
 
+mod store;
+
+pub use store::{IdGraphKey, IdGraphSource, load_or_build_id_graph};
+
 #[cfg(test)]
 mod tests;
@@ -0,0 +1,265 @@
+use std::io::{Read, Write};
+use std::path::Path;
+use std::sync::Arc;
+
+use crate::blend::bytes::Cursor;
+use crate::blend::{
+	BlendError, BlendFile, Dna, IdGraphEdge, IdGraphNode, IdGraphOptions, IdGraphResult, IdGraphTruncation, IdIndex, PointerIndex, Result, Warning,
+	WarningKind, build_id_graph,
+};
+
+/// Leading bytes of a saved ID graph.
+const MAGIC: &[u8; 8] = b"BDIDGRPH";
+/// Artifact layout version; bump on any change to the encoding.
+const FORMAT_VERSION: u32 = 1;
+
+/// Identity of the file bytes and options an ID graph was built from.
+///
+/// A saved graph is only reused when its key matches the current file and
+/// options exactly.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct IdGraphKey {
+	/// FNV-1a 64 hash of the decoded file bytes.
+	pub content_hash: u64,
+	/// `IdGraphOptions::ref_scan.max_depth`.
+	pub max_depth: u32,
+	/// `IdGraphOptions::ref_scan.max_array_elems`.
+	pub max_array_elems: u64,
+	/// `IdGraphOptions::max_edges`.
+	pub max_edges: u64,
+	/// `IdGraphOptions::include_self`.
+	pub include_self: bool,
+}
+
+impl IdGraphKey {
+	/// Key for building a graph over `file` with `options`.
+	pub fn new(file: &BlendFile, options: &IdGraphOptions) -> Self {
+		Self {
+			content_hash: fnv1a(file.bytes()),
+			max_depth: options.ref_scan.max_depth,
+			max_array_elems: options.ref_scan.max_array_elems as u64,
+			max_edges: options.max_edges as u64,
+			include_self: options.include_self,
+		}
+	}
+}
+
+/// Where [`load_or_build_id_graph`] got its graph from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum IdGraphSource {
+	/// A saved artifact matched the file and options.
+	Loaded,
+	/// The graph was built and the artifact (re)written.
+	Built,
+}
+
+impl IdGraphResult {
+	/// Serialize this graph, tagged with the key it was built under.
+	pub fn write_to(&self, key: &IdGraphKey, out: &mut impl Write) -> Result<()> {
+		let mut buf = Vec::new();
+		buf.extend_from_slice(MAGIC);
+		put_u32(&mut buf, FORMAT_VERSION);
+		put_u64(&mut buf, key.content_hash);
+		put_u32(&mut buf, key.max_depth);
+		put_u64(&mut buf, key.max_array_elems);
+		put_u64(&mut buf, key.max_edges);
+		buf.push(u8::from(key.include_self));
+		buf.push(match self.truncated {
+			None => 0,
+			Some(IdGraphTruncation::MaxEdges) => 1,
+			Some(IdGraphTruncation::Cancelled) => 2,
+		});
+
+		put_u32(&mut buf, len_u32(self.nodes.len())?);
+		for node in &self.nodes {
+			put_u64(&mut buf, node.canonical);
+			buf.extend_from_slice(&node.code);
+			put_u32(&mut buf, node.sdna_nr);
+			put_str(&mut buf, &node.type_name)?;
+			put_str(&mut buf, &node.id_name)?;
+		}
+
+		put_u32(&mut buf, len_u32(self.edges.len())?);
+		for edge in &self.edges {
+			put_u64(&mut buf, edge.from);
+			put_u64(&mut buf, edge.to);
+			put_str(&mut buf, &edge.field)?;
+		}
+
+		put_u32(&mut buf, len_u32(self.warnings.len())?);
+		for warning in &self.warnings {
+			put_str(&mut buf, warning.kind.as_str())?;
+			match warning.ptr {
+				Some(ptr) => {
+					buf.push(1);
+					put_u64(&mut buf, ptr);
+				}
+				None => buf.push(0),
+			}
+			put_str(&mut buf, &warning.message)?;
+		}
+
+		out.write_all(&buf)?;
+		Ok(())
+	}
+
+	/// Deserialize a graph written by [`IdGraphResult::write_to`] with its key.
+	///
+	/// Cycles are not stored; the result always has empty `cycles`.
+	pub fn read_from(input: &mut impl Read) -> Result<(IdGraphKey, Self)> {
+		let mut raw = Vec::new();
+		input.read_to_end(&mut raw)?;
+		let mut cursor = Cursor::new(&raw);
+
+		if cursor.read_exact(MAGIC.len()).ok() != Some(MAGIC.as_slice()) {
+			return Err(artifact_error("not an ID graph artifact"));
+		}
+		let version = cursor.read_u32_le()?;
+		if version != FORMAT_VERSION {
+			return Err(artifact_error(format!("format version {version}, expected {FORMAT_VERSION}")));
+		}
+		let key = IdGraphKey {
+			content_hash: cursor.read_u64_le()?,
+			max_depth: cursor.read_u32_le()?,
+			max_array_elems: cursor.read_u64_le()?,
+			max_edges: cursor.read_u64_le()?,
+			include_self: read_u8(&mut cursor)? != 0,
+		};
+		let truncated = match read_u8(&mut cursor)? {
+			0 => None,
+			1 => Some(IdGraphTruncation::MaxEdges),
+			2 => Some(IdGraphTruncation::Cancelled),
+			other => return Err(artifact_error(format!("unknown truncation tag {other}"))),
+		};
+
+		let node_count = cursor.read_u32_le()?;
+		let mut nodes = Vec::new();
+		for _ in 0..node_count {
+			nodes.push(IdGraphNode {
+				canonical: cursor.read_u64_le()?,
+				code: cursor.read_code4()?,
+				sdna_nr: cursor.read_u32_le()?,
+				type_name: read_str(&mut cursor)?,
+				id_name: read_str(&mut cursor)?,
+			});
+		}
+
+		let edge_count = cursor.read_u32_le()?;
+		let mut edges = Vec::new();
+		for _ in 0..edge_count {
+			edges.push(IdGraphEdge {
+				from: cursor.read_u64_le()?,
+				to: cursor.read_u64_le()?,
+				field: read_str(&mut cursor)?,
+			});
+		}
+
+		let warning_count = cursor.read_u32_le()?;
+		let mut warnings = Vec::new();
+		for _ in 0..warning_count {
+			let label = read_str(&mut cursor)?;
+			let kind = WarningKind::from_label(&label).ok_or_else(|| artifact_error(format!("unknown warning kind `{label}`")))?;
+			let ptr = match read_u8(&mut cursor)? {
+				0 => None,
+				_ => Some(cursor.read_u64_le()?),
+			};
+			warnings.push(Warning::new(kind, ptr, read_str(&mut cursor)?));
+		}
+
+		if cursor.remaining() != 0 {
+			return Err(artifact_error(format!("{} trailing bytes", cursor.remaining())));
+		}
+		Ok((
+			key,
+			Self {
+				nodes,
+				edges,
+				truncated,
+				warnings,
+				cycles: Vec::new(),
+			},
+		))
+	}
+}
+
+/// Reuse the graph saved at `artifact` when it was built from the same file
+/// bytes and options; otherwise build it and save it there.
+///
+/// A missing, unreadable, or stale artifact is silently rebuilt. The new
+/// artifact is written to a sibling temp file and renamed into place, so a
+/// concurrent reader never sees a partial graph. A cancelled build is
+/// returned without being saved.
+pub fn load_or_build_id_graph(
+	file: &BlendFile,
+	dna: &Dna,
+	index: &PointerIndex<'_>,
+	ids: &IdIndex,
+	options: &IdGraphOptions,
+	artifact: &Path,
+) -> Result<(IdGraphResult, IdGraphSource)> {
+	let key = IdGraphKey::new(file, options);
+	if let Ok(mut saved) = std::fs::File::open(artifact)
+		&& let Ok((saved_key, graph)) = IdGraphResult::read_from(&mut std::io::BufReader::new(&mut saved))
+		&& saved_key == key
+	{
+		let mut graph = graph;
+		if options.detect_cycles {
+			graph.cycles = graph.find_cycles();
+		}
+		return Ok((graph, IdGraphSource::Loaded));
+	}
+
+	let graph = build_id_graph(dna, index, ids, options)?;
+	if matches!(graph.truncated, Some(IdGraphTruncation::Cancelled)) {
+		return Ok((graph, IdGraphSource::Built));
+	}
+	let mut temp_name = artifact.as_os_str().to_owned();
+	temp_name.push(format!(".tmp{}", std::process::id()));
+	let temp = std::path::PathBuf::from(temp_name);
+	let mut out = std::io::BufWriter::new(std::fs::File::create(&temp)?);
+	graph.write_to(&key, &mut out)?;
+	out.into_inner().map_err(|err| BlendError::Io(err.into_error()))?;
+	std::fs::rename(&temp, artifact)?;
+	Ok((graph, IdGraphSource::Built))
+}
+
+fn artifact_error(message: impl Into<String>) -> BlendError {
+	BlendError::IdGraphArtifact { message: message.into() }
+}
+
+fn len_u32(len: usize) -> Result<u32> {
+	u32::try_from(len).map_err(|_| artifact_error(format!("{len} entries exceed the u32 length field")))
+}
+
+fn put_u32(buf: &mut Vec<u8>, value: u32) {
+	buf.extend_from_slice(&value.to_le_bytes());
+}
+
+fn put_u64(buf: &mut Vec<u8>, value: u64) {
+	buf.extend_from_slice(&value.to_le_bytes());
+}
+
+fn put_str(buf: &mut Vec<u8>, value: &str) -> Result<()> {
+	put_u32(buf, len_u32(value.len())?);
+	buf.extend_from_slice(value.as_bytes());
+	Ok(())
+}
+
+fn read_u8(cursor: &mut Cursor<'_>) -> Result<u8> {
+	Ok(cursor.read_exact(1)?[0])
+}
+
+fn read_str(cursor: &mut Cursor<'_>) -> Result<Arc<str>> {
+	let len = cursor.read_u32_le()? as usize;
+	let bytes = cursor.read_exact(len)?;
+	std::str::from_utf8(bytes).map(Arc::from).map_err(|_| artifact_error("string is not UTF-8"))
+}
+
+fn fnv1a(bytes: &[u8]) -> u64 {
+	let mut hash = 0xcbf2_9ce4_8422_2325_u64;
+	for byte in bytes {
+		hash ^= u64::from(*byte);
+		hash = hash.wrapping_mul(0x0000_0100_0000_01b3);
+	}
+	hash
+}
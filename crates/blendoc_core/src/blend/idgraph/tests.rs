@@ -34,6 +34,10 @@ mod fixtures_day11_idgraph {
 				},
 				max_edges: 100_000,
 				include_self: false,
+				ref_cache: None,
+				detect_cycles: false,
+				progress: None,
+				cancel: None,
 			},
 		)
 		.expect("id graph builds");
@@ -47,3 +51,100 @@ mod fixtures_day11_idgraph {
 		);
 	}
 }
+
+mod persisted_idgraph {
+	use blendoc_testkit::builder::{HeaderKind, scene_file};
+
+	use crate::blend::{BlendFile, IdGraphKey, IdGraphOptions, IdGraphResult, IdGraphSource, IdIndex, build_id_graph, load_or_build_id_graph, scan_id_blocks};
+
+	#[test]
+	fn artifact_round_trips_and_is_reused_until_stale() {
+		let blend = BlendFile::from_bytes(scene_file(HeaderKind::Legacy8, false)).expect("synthetic file parses");
+		let dna = blend.dna().expect("dna parses");
+		let index = blend.pointer_index().expect("pointer index builds");
+		let ids = IdIndex::build(scan_id_blocks(&blend, &dna).expect("id scan succeeds"));
+		let options = IdGraphOptions::default();
+		let built = build_id_graph(&dna, &index, &ids, &options).expect("id graph builds");
+		assert!(!built.edges.is_empty());
+
+		let key = IdGraphKey::new(&blend, &options);
+		let mut bytes = Vec::new();
+		built.write_to(&key, &mut bytes).expect("graph serializes");
+		let (read_key, read) = IdGraphResult::read_from(&mut bytes.as_slice()).expect("graph deserializes");
+		assert_eq!(read_key, key);
+		assert_eq!(format!("{read:?}"), format!("{built:?}"));
+		assert!(
+			IdGraphResult::read_from(&mut &bytes[..bytes.len() - 1]).is_err(),
+			"truncated artifact is rejected"
+		);
+
+		let artifact = std::env::temp_dir().join(format!("blendoc_idgraph_store_{}.idgraph", std::process::id()));
+		let _ = std::fs::remove_file(&artifact);
+		let load = |options: &IdGraphOptions| load_or_build_id_graph(&blend, &dna, &index, &ids, options, &artifact).expect("graph loads or builds");
+		assert_eq!(load(&options).1, IdGraphSource::Built);
+		let (loaded, source) = load(&options);
+		assert_eq!(source, IdGraphSource::Loaded);
+		assert_eq!(format!("{loaded:?}"), format!("{built:?}"));
+
+		let narrower = IdGraphOptions {
+			max_edges: 1,
+			..IdGraphOptions::default()
+		};
+		assert_eq!(load(&narrower).1, IdGraphSource::Built, "different options rebuild");
+		std::fs::write(&artifact, b"garbage").expect("artifact overwritten");
+		assert_eq!(load(&options).1, IdGraphSource::Built, "corrupt artifact rebuilds");
+		std::fs::remove_file(&artifact).expect("artifact removed");
+	}
+}
+
+mod cycle_detection {
+	use blendoc_testkit::builder::{HeaderKind, scene_file};
+
+	use crate::blend::{BlendFile, IdGraphEdge, IdGraphOptions, IdGraphResult, IdIndex, build_id_graph, scan_id_blocks};
+
+	fn edge(from: u64, to: u64, field: &str) -> IdGraphEdge {
+		IdGraphEdge { from, to, field: field.into() }
+	}
+
+	#[test]
+	fn multi_node_components_are_cycles() {
+		let graph = IdGraphResult {
+			nodes: Vec::new(),
+			edges: vec![
+				edge(1, 2, "parent"),
+				edge(2, 3, "driver"),
+				edge(3, 1, "target"),
+				edge(3, 4, "data"),
+				edge(4, 5, "mat[0]"),
+				edge(5, 4, "nodetree"),
+				edge(6, 6, "self"),
+			],
+			truncated: None,
+			warnings: Vec::new(),
+			cycles: Vec::new(),
+		};
+
+		let cycles = graph.find_cycles();
+		let members: Vec<&[u64]> = cycles.iter().map(|cycle| cycle.members.as_slice()).collect();
+		assert_eq!(members, [&[1, 2, 3][..], &[4, 5][..]]);
+		let fields: Vec<&str> = cycles[0].edges.iter().map(|edge| edge.field.as_ref()).collect();
+		assert_eq!(fields, ["parent", "driver", "target"]);
+		assert_eq!(cycles[1].edges.len(), 2);
+	}
+
+	#[test]
+	fn acyclic_scene_reports_no_cycles() {
+		let blend = BlendFile::from_bytes(scene_file(HeaderKind::Legacy8, false)).expect("synthetic file parses");
+		let dna = blend.dna().expect("dna parses");
+		let index = blend.pointer_index().expect("pointer index builds");
+		let ids = IdIndex::build(scan_id_blocks(&blend, &dna).expect("id scan succeeds"));
+		let options = IdGraphOptions {
+			detect_cycles: true,
+			..IdGraphOptions::default()
+		};
+
+		let graph = build_id_graph(&dna, &index, &ids, &options).expect("id graph builds");
+		assert!(!graph.edges.is_empty());
+		assert!(graph.cycles.is_empty());
+	}
+}
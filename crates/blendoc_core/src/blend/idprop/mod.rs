@@ -0,0 +1,407 @@
+use std::sync::Arc;
+
+use crate::blend::{
+	BlendError, DecodeOptions, Dna, Endianness, IdIndex, PointerIndex, Result, StructValue, Value, WalkOptions, decode_ptr_instance, decode_struct_instance,
+	walk_listbase,
+};
+
+/// `IDProperty.type` codes (`eIDPropertyType`).
+const IDP_STRING: i64 = 0;
+const IDP_INT: i64 = 1;
+const IDP_FLOAT: i64 = 2;
+const IDP_ARRAY: i64 = 5;
+const IDP_GROUP: i64 = 6;
+const IDP_ID: i64 = 7;
+const IDP_DOUBLE: i64 = 8;
+const IDP_IDPARRAY: i64 = 9;
+const IDP_BOOLEAN: i64 = 10;
+/// `IDProperty.subtype` of a string holding raw bytes rather than UTF-8.
+const IDP_STRING_SUB_BYTE: i64 = 1;
+
+/// Traversal limits for [`read_id_properties`] and [`read_idproperty`].
+#[derive(Debug, Clone)]
+pub struct IdPropOptions {
+	/// Maximum group/array nesting followed below the root property.
+	pub max_depth: u32,
+	/// Maximum children read per group and elements per array.
+	pub max_items: usize,
+}
+
+impl Default for IdPropOptions {
+	fn default() -> Self {
+		Self {
+			max_depth: 32,
+			max_items: 100_000,
+		}
+	}
+}
+
+/// Decoded value of one [`IdProperty`].
+#[derive(Debug, Clone, PartialEq)]
+pub enum IdPropValue {
+	/// `IDP_STRING` with UTF-8 content, trailing NUL removed.
+	String(Box<str>),
+	/// `IDP_STRING` with the byte subtype.
+	Bytes(Vec<u8>),
+	/// `IDP_INT`.
+	Int(i32),
+	/// `IDP_FLOAT`.
+	Float(f32),
+	/// `IDP_DOUBLE`.
+	Double(f64),
+	/// `IDP_BOOLEAN`.
+	Bool(bool),
+	/// `IDP_ARRAY` or `IDP_IDPARRAY` elements.
+	Array(Vec<IdPropValue>),
+	/// `IDP_GROUP` children in list order.
+	Group(Vec<IdProperty>),
+	/// `IDP_ID` reference.
+	Id {
+		/// Stored ID pointer (0 when unset).
+		ptr: u64,
+		/// Target `ID.name`, when the pointer resolves to an ID.
+		name: Option<Arc<str>>,
+	},
+	/// A type code this reader does not know, or data that did not resolve.
+	Unsupported {
+		/// Raw `IDProperty.type`.
+		type_code: i64,
+	},
+}
+
+impl IdPropValue {
+	/// Type label (`int`, `group`, `array[float]`, ...).
+	pub fn type_label(&self) -> String {
+		let label = match self {
+			Self::String(_) => "string",
+			Self::Bytes(_) => "bytes",
+			Self::Int(_) => "int",
+			Self::Float(_) => "float",
+			Self::Double(_) => "double",
+			Self::Bool(_) => "bool",
+			Self::Array(items) => return format!("array[{}]", items.first().map_or_else(|| "-".to_owned(), Self::type_label)),
+			Self::Group(_) => "group",
+			Self::Id { .. } => "id",
+			Self::Unsupported { type_code } => return format!("type:{type_code}"),
+		};
+		label.to_owned()
+	}
+}
+
+/// One named `IDProperty`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct IdProperty {
+	/// Canonical `IDProperty` pointer.
+	pub ptr: u64,
+	/// Property name (key in its parent group).
+	pub name: Arc<str>,
+	/// Decoded value.
+	pub value: IdPropValue,
+}
+
+/// Custom properties attached to one ID.
+#[derive(Debug, Clone)]
+pub struct IdPropertiesInfo {
+	/// Canonical owner ID pointer.
+	pub owner_ptr: u64,
+	/// Owner `ID.name`.
+	pub owner_id: Option<Arc<str>>,
+	/// Children of the `ID.properties` root group (user custom properties).
+	pub properties: Vec<IdProperty>,
+	/// Children of the `ID.system_properties` root group (Blender 5.0+).
+	pub system_properties: Vec<IdProperty>,
+	/// Whether a limit or a broken pointer cut the tree short.
+	pub truncated: bool,
+}
+
+impl IdPropertiesInfo {
+	/// Whether the ID stores no properties at all.
+	pub fn is_empty(&self) -> bool {
+		self.properties.is_empty() && self.system_properties.is_empty()
+	}
+}
+
+/// Read the `IDProperty` trees under an ID's `id.properties` and `id.system_properties`.
+///
+/// IDs without stored properties return an empty [`IdPropertiesInfo`].
+pub fn read_id_properties<'a>(dna: &Dna, index: &PointerIndex<'a>, ids: &IdIndex, id_ptr: u64, options: &IdPropOptions) -> Result<IdPropertiesInfo> {
+	let mut reader = IdPropReader::new(dna, index, ids, options);
+	let (owner_ptr, owner) = decode_ptr_instance(dna, index, id_ptr, &reader.decode)?;
+	let Some(Value::Struct(id)) = field(&owner, "id") else {
+		return Err(BlendError::UnexpectedStructType {
+			ptr: owner_ptr,
+			expected: "ID root",
+			got: owner.type_name.to_string(),
+		});
+	};
+
+	let properties = reader.root(ptr_field(id, "properties").unwrap_or(0))?;
+	let system_properties = reader.root(ptr_field(id, "system_properties").unwrap_or(0))?;
+	Ok(IdPropertiesInfo {
+		owner_ptr,
+		owner_id: ids.get_by_ptr(owner_ptr).map(|record| Arc::from(record.id_name.as_ref())),
+		properties,
+		system_properties,
+		truncated: reader.truncated,
+	})
+}
+
+/// Decode the `IDProperty` at `prop_ptr` and everything below it.
+pub fn read_idproperty<'a>(dna: &Dna, index: &PointerIndex<'a>, ids: &IdIndex, prop_ptr: u64, options: &IdPropOptions) -> Result<IdProperty> {
+	let mut reader = IdPropReader::new(dna, index, ids, options);
+	let (ptr, prop) = decode_ptr_instance(dna, index, prop_ptr, &reader.decode)?;
+	reader.property(ptr, &prop, 0)
+}
+
+struct IdPropReader<'r, 'a> {
+	dna: &'r Dna,
+	index: &'r PointerIndex<'a>,
+	ids: &'r IdIndex,
+	options: &'r IdPropOptions,
+	decode: DecodeOptions,
+	truncated: bool,
+}
+
+impl<'r, 'a> IdPropReader<'r, 'a> {
+	fn new(dna: &'r Dna, index: &'r PointerIndex<'a>, ids: &'r IdIndex, options: &'r IdPropOptions) -> Self {
+		Self {
+			dna,
+			index,
+			ids,
+			options,
+			decode: DecodeOptions::default(),
+			truncated: false,
+		}
+	}
+
+	/// Children of a root group; a non-group root is returned as its only entry.
+	fn root(&mut self, ptr: u64) -> Result<Vec<IdProperty>> {
+		if ptr == 0 {
+			return Ok(Vec::new());
+		}
+		let (ptr, prop) = decode_ptr_instance(self.dna, self.index, ptr, &self.decode)?;
+		let root = self.property(ptr, &prop, 0)?;
+		Ok(match root.value {
+			IdPropValue::Group(children) => children,
+			_ => vec![root],
+		})
+	}
+
+	fn property(&mut self, ptr: u64, prop: &StructValue, depth: u32) -> Result<IdProperty> {
+		if prop.type_name.as_ref() != "IDProperty" {
+			return Err(BlendError::UnexpectedStructType {
+				ptr,
+				expected: "IDProperty",
+				got: prop.type_name.to_string(),
+			});
+		}
+		Ok(IdProperty {
+			ptr,
+			name: string_field(prop, "name").unwrap_or_else(|| Arc::from("")),
+			value: self.value(prop, depth)?,
+		})
+	}
+
+	fn value(&mut self, prop: &StructValue, depth: u32) -> Result<IdPropValue> {
+		let type_code = int_field(prop, "type").unwrap_or(-1);
+		let Some(Value::Struct(data)) = field(prop, "data") else {
+			return Ok(IdPropValue::Unsupported { type_code });
+		};
+		let val = int_field(data, "val").unwrap_or(0);
+		let val2 = int_field(data, "val2").unwrap_or(0);
+		let pointer = ptr_field(data, "pointer").unwrap_or(0);
+		let len = int_field(prop, "len").unwrap_or(0).max(0) as usize;
+
+		let value = match type_code {
+			IDP_INT => IdPropValue::Int(val as i32),
+			IDP_FLOAT => IdPropValue::Float(f32::from_bits(val as u32)),
+			IDP_BOOLEAN => IdPropValue::Bool(val != 0),
+			IDP_DOUBLE => IdPropValue::Double(f64::from_bits(self.join_words(val, val2))),
+			IDP_STRING => {
+				let Some(bytes) = self.raw(pointer, len, 1) else {
+					return Ok(self.broken(type_code));
+				};
+				if int_field(prop, "subtype") == Some(IDP_STRING_SUB_BYTE) {
+					IdPropValue::Bytes(bytes.to_vec())
+				} else {
+					let end = bytes.iter().position(|byte| *byte == 0).unwrap_or(bytes.len());
+					IdPropValue::String(String::from_utf8_lossy(&bytes[..end]).into())
+				}
+			}
+			IDP_ID => IdPropValue::Id {
+				ptr: pointer,
+				name: (pointer != 0)
+					.then(|| self.index.canonical_ptr(self.dna, pointer))
+					.flatten()
+					.and_then(|ptr| self.ids.get_by_ptr(ptr))
+					.map(|record| Arc::from(record.id_name.as_ref())),
+			},
+			IDP_GROUP if depth >= self.options.max_depth => {
+				self.truncated = true;
+				IdPropValue::Group(Vec::new())
+			}
+			IDP_GROUP => {
+				let Some(Value::Struct(list_base)) = field(data, "group") else {
+					return Ok(self.broken(type_code));
+				};
+				let walk = walk_listbase(
+					self.dna,
+					self.index,
+					self.ids,
+					list_base,
+					&WalkOptions {
+						max_steps: self.options.max_items,
+						..WalkOptions::default()
+					},
+				)?;
+				self.truncated |= !walk.is_complete();
+				let mut children = Vec::new();
+				for child_ptr in walk.canonical_ptrs() {
+					let (child_ptr, child) = decode_ptr_instance(self.dna, self.index, child_ptr, &self.decode)?;
+					children.push(self.property(child_ptr, &child, depth + 1)?);
+				}
+				IdPropValue::Group(children)
+			}
+			IDP_ARRAY => self.array(pointer, len, int_field(prop, "subtype").unwrap_or(-1), depth),
+			IDP_IDPARRAY => self.idp_array(pointer, len, depth)?,
+			_ => IdPropValue::Unsupported { type_code },
+		};
+		Ok(value)
+	}
+
+	/// `IDP_ARRAY`: `len` packed elements of the subtype, or group pointers.
+	fn array(&mut self, pointer: u64, len: usize, subtype: i64, depth: u32) -> IdPropValue {
+		let count = self.capped(len);
+		let width = match subtype {
+			IDP_INT | IDP_FLOAT => 4,
+			IDP_DOUBLE => 8,
+			IDP_BOOLEAN => 1,
+			IDP_GROUP => self.dna.pointer_size,
+			_ => return IdPropValue::Unsupported { type_code: IDP_ARRAY },
+		};
+		let Some(bytes) = self.raw(pointer, count, width) else {
+			return self.broken(IDP_ARRAY);
+		};
+		let endianness = self.dna.endianness;
+		let mut items = Vec::with_capacity(count);
+		for chunk in bytes.chunks_exact(width) {
+			let word = read_word(chunk, 0, width, endianness).unwrap_or(0);
+			items.push(match subtype {
+				IDP_INT => IdPropValue::Int(word as u32 as i32),
+				IDP_FLOAT => IdPropValue::Float(f32::from_bits(word as u32)),
+				IDP_DOUBLE => IdPropValue::Double(f64::from_bits(word)),
+				IDP_BOOLEAN => IdPropValue::Bool(word != 0),
+				_ if depth >= self.options.max_depth => {
+					self.truncated = true;
+					IdPropValue::Group(Vec::new())
+				}
+				_ => match decode_ptr_instance(self.dna, self.index, word, &self.decode) {
+					Ok((ptr, prop)) => self.property(ptr, &prop, depth + 1).map_or_else(|_| self.broken(IDP_GROUP), |prop| prop.value),
+					Err(_) => self.broken(IDP_GROUP),
+				},
+			});
+		}
+		IdPropValue::Array(items)
+	}
+
+	/// `IDP_IDPARRAY`: `len` inline `IDProperty` structs.
+	fn idp_array(&mut self, pointer: u64, len: usize, depth: u32) -> Result<IdPropValue> {
+		if depth >= self.options.max_depth {
+			self.truncated = true;
+			return Ok(IdPropValue::Array(Vec::new()));
+		}
+		// Elements are read from the block bytes: stable-ID pointers do not support element arithmetic.
+		let Some(resolved) = (pointer != 0).then(|| self.index.resolve_typed(self.dna, pointer)).flatten() else {
+			return Ok(self.broken(IDP_IDPARRAY));
+		};
+		let sdna_nr = resolved.base.entry.block.head.sdna_nr;
+		let stride = resolved.struct_size.max(1);
+		let payload = resolved.base.payload();
+		let mut items = Vec::new();
+		for idx in 0..self.capped(len) {
+			let start = resolved.base.byte_offset + idx * stride;
+			let Some(bytes) = payload.get(start..start + stride) else {
+				self.truncated = true;
+				break;
+			};
+			let prop = decode_struct_instance(self.dna, sdna_nr, bytes, &self.decode)?;
+			items.push(self.value(&prop, depth + 1)?);
+		}
+		Ok(IdPropValue::Array(items))
+	}
+
+	/// `count * width` bytes at `ptr`, or `None` when it does not resolve or the block is short.
+	fn raw(&self, ptr: u64, count: usize, width: usize) -> Option<&'a [u8]> {
+		if count == 0 {
+			return Some(&[]);
+		}
+		let resolved = self.index.resolve(ptr)?;
+		let start = resolved.byte_offset;
+		resolved.payload().get(start..start.checked_add(count.checked_mul(width)?)?)
+	}
+
+	fn capped(&mut self, len: usize) -> usize {
+		if len > self.options.max_items {
+			self.truncated = true;
+		}
+		len.min(self.options.max_items)
+	}
+
+	fn broken(&mut self, type_code: i64) -> IdPropValue {
+		self.truncated = true;
+		IdPropValue::Unsupported { type_code }
+	}
+
+	/// 64-bit value stored across `data.val` then `data.val2`.
+	fn join_words(&self, val: i64, val2: i64) -> u64 {
+		let (low, high) = match self.dna.endianness {
+			Endianness::Little => (val, val2),
+			Endianness::Big => (val2, val),
+		};
+		(u64::from(high as u32) << 32) | u64::from(low as u32)
+	}
+}
+
+fn field<'v>(item: &'v StructValue, name: &str) -> Option<&'v Value> {
+	item.fields.iter().find(|field| field.name.as_ref() == name).map(|field| &field.value)
+}
+
+fn ptr_field(item: &StructValue, name: &str) -> Option<u64> {
+	match field(item, name)? {
+		Value::Ptr(ptr) => Some(*ptr),
+		_ => None,
+	}
+}
+
+fn string_field(item: &StructValue, name: &str) -> Option<Arc<str>> {
+	match field(item, name)? {
+		Value::String(value) => Some(Arc::from(value.as_ref())),
+		_ => None,
+	}
+}
+
+fn int_field(item: &StructValue, name: &str) -> Option<i64> {
+	match field(item, name)? {
+		Value::I64(value) => Some(*value),
+		Value::U64(value) => i64::try_from(*value).ok(),
+		_ => None,
+	}
+}
+
+fn read_word(bytes: &[u8], start: usize, width: usize, endianness: Endianness) -> Option<u64> {
+	let raw = bytes.get(start..start.checked_add(width)?)?;
+	let mut buf = [0_u8; 8];
+	match endianness {
+		Endianness::Little => {
+			buf[..width].copy_from_slice(raw);
+			Some(u64::from_le_bytes(buf))
+		}
+		Endianness::Big => {
+			buf[8 - width..].copy_from_slice(raw);
+			Some(u64::from_be_bytes(buf))
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,184 @@
+use blendoc_testkit::builder::{BlendBuilder, HeaderKind};
+
+use crate::blend::{BlendFile, IdIndex, IdPropOptions, IdPropValue, read_id_properties, read_idproperty, scan_id_blocks};
+
+const OBJECT: u64 = 0x1000;
+const MESH: u64 = 0x1100;
+const ROOT: u64 = 0x2000;
+const ANSWER: u64 = 0x2100;
+const SCALE: u64 = 0x2200;
+const PRECISE: u64 = 0x2300;
+const LABEL: u64 = 0x2400;
+const LABEL_TEXT: u64 = 0x2480;
+const WEIGHTS: u64 = 0x2500;
+const WEIGHTS_DATA: u64 = 0x2580;
+const NESTED: u64 = 0x2600;
+const FLAG: u64 = 0x2700;
+const TARGET: u64 = 0x2800;
+
+/// `OBCube` with a root property group holding one property of each scalar
+/// type, a string, a float array, a nested group, and an ID reference to `MECube`.
+fn props_file(big_endian: bool) -> Vec<u8> {
+	let mut builder = BlendBuilder::new(HeaderKind::Legacy8, 404, big_endian);
+	let sdna = builder.sdna_mut();
+	sdna.add_type("char", 1);
+	sdna.add_type("short", 2);
+	sdna.add_type("int", 4);
+	sdna.add_type("float", 4);
+	sdna.add_type("void", 0);
+	sdna.add_struct("Link", &[("Link", "*next"), ("Link", "*prev")]);
+	sdna.add_struct("ListBase", &[("void", "*first"), ("void", "*last")]);
+	sdna.add_struct(
+		"ID",
+		&[
+			("void", "*next"),
+			("void", "*prev"),
+			("void", "*lib"),
+			("char", "name[24]"),
+			("IDProperty", "*properties"),
+		],
+	);
+	sdna.add_struct(
+		"IDPropertyData",
+		&[("void", "*pointer"), ("ListBase", "group"), ("int", "val"), ("int", "val2")],
+	);
+	let prop = sdna.add_struct(
+		"IDProperty",
+		&[
+			("IDProperty", "*next"),
+			("IDProperty", "*prev"),
+			("char", "type"),
+			("char", "subtype"),
+			("short", "flag"),
+			("char", "name[32]"),
+			("IDPropertyData", "data"),
+			("int", "len"),
+			("int", "totallen"),
+		],
+	);
+	let object = sdna.add_struct("Object", &[("ID", "id")]);
+	let mesh = sdna.add_struct("Mesh", &[("ID", "id")]);
+
+	let id = |name: &str, properties: u64| {
+		let mut payload = builder.payload();
+		payload.ptr(0).ptr(0).ptr(0).name(name, 24).ptr(properties);
+		payload.finish()
+	};
+	let property = |next: u64, prev: u64, kind: u8, subtype: u8, name: &str, pointer: u64, group: (u64, u64), val: (u32, u32), len: i32| {
+		let mut payload = builder.payload();
+		payload
+			.ptr(next)
+			.ptr(prev)
+			.raw(&[kind, subtype, 0, 0])
+			.name(name, 32)
+			.ptr(pointer)
+			.ptr(group.0)
+			.ptr(group.1)
+			.u32(val.0)
+			.u32(val.1)
+			.i32(len)
+			.i32(len);
+		payload.finish()
+	};
+
+	let bits = 2.25_f64.to_bits();
+	let (low, high) = (bits as u32, (bits >> 32) as u32);
+	let double_words = if big_endian { (high, low) } else { (low, high) };
+	let mut payload = builder.payload();
+	payload.name("hello", 6);
+	let label_text = payload.finish();
+	let mut payload = builder.payload();
+	payload.f32(0.5).f32(1.0);
+	let weights = payload.finish();
+
+	let blocks = [
+		(*b"OB\0\0", object, OBJECT, id("OBCube", ROOT)),
+		(*b"DATA", prop, ROOT, property(0, 0, 6, 0, "", 0, (ANSWER, TARGET), (0, 0), 0)),
+		(*b"DATA", prop, ANSWER, property(SCALE, 0, 1, 0, "answer", 0, (0, 0), (42, 0), 0)),
+		(
+			*b"DATA",
+			prop,
+			SCALE,
+			property(PRECISE, ANSWER, 2, 0, "scale", 0, (0, 0), (1.5_f32.to_bits(), 0), 0),
+		),
+		(*b"DATA", prop, PRECISE, property(LABEL, SCALE, 8, 0, "precise", 0, (0, 0), double_words, 0)),
+		(*b"DATA", prop, LABEL, property(WEIGHTS, PRECISE, 0, 0, "label", LABEL_TEXT, (0, 0), (0, 0), 6)),
+		(*b"DATA", 0, LABEL_TEXT, label_text),
+		(
+			*b"DATA",
+			prop,
+			WEIGHTS,
+			property(NESTED, LABEL, 5, 2, "weights", WEIGHTS_DATA, (0, 0), (0, 0), 2),
+		),
+		(*b"DATA", 0, WEIGHTS_DATA, weights),
+		(*b"DATA", prop, NESTED, property(TARGET, WEIGHTS, 6, 0, "nested", 0, (FLAG, FLAG), (0, 0), 0)),
+		(*b"DATA", prop, FLAG, property(0, 0, 10, 0, "flag", 0, (0, 0), (1, 0), 0)),
+		(*b"DATA", prop, TARGET, property(0, NESTED, 7, 0, "target", MESH, (0, 0), (0, 0), 0)),
+		(*b"ME\0\0", mesh, MESH, id("MECube", 0)),
+	];
+	for (code, sdna_nr, old, payload) in blocks {
+		builder.block(code, sdna_nr, old, 1, payload);
+	}
+	builder.build()
+}
+
+#[test]
+fn id_properties_decode_typed_tree() {
+	for big_endian in [false, true] {
+		let blend = BlendFile::from_bytes(props_file(big_endian)).expect("synthetic file opens");
+		let dna = blend.dna().expect("dna parses");
+		let index = blend.pointer_index().expect("pointer index builds");
+		let ids = IdIndex::build(scan_id_blocks(&blend, &dna).expect("ids scan"));
+
+		let info = read_id_properties(&dna, &index, &ids, OBJECT, &IdPropOptions::default()).expect("properties read");
+		assert_eq!(info.owner_id.as_deref(), Some("OBCube"));
+		assert!(!info.truncated);
+		let values: Vec<(&str, &IdPropValue)> = info.properties.iter().map(|prop| (prop.name.as_ref(), &prop.value)).collect();
+		assert_eq!(values.len(), 7, "big_endian={big_endian}");
+		assert_eq!(values[0], ("answer", &IdPropValue::Int(42)));
+		assert_eq!(values[1], ("scale", &IdPropValue::Float(1.5)));
+		assert_eq!(values[2], ("precise", &IdPropValue::Double(2.25)), "big_endian={big_endian}");
+		assert_eq!(values[3], ("label", &IdPropValue::String("hello".into())));
+		assert_eq!(
+			values[4],
+			("weights", &IdPropValue::Array(vec![IdPropValue::Float(0.5), IdPropValue::Float(1.0)]))
+		);
+		let IdPropValue::Group(nested) = values[5].1 else {
+			panic!("nested group expected, got {:?}", values[5].1);
+		};
+		assert_eq!(nested[0].name.as_ref(), "flag");
+		assert_eq!(nested[0].value, IdPropValue::Bool(true));
+		assert_eq!(
+			values[6].1,
+			&IdPropValue::Id {
+				ptr: MESH,
+				name: Some("MECube".into())
+			}
+		);
+		assert_eq!(values[4].1.type_label(), "array[float]");
+
+		let mesh = read_id_properties(&dna, &index, &ids, MESH, &IdPropOptions::default()).expect("mesh read");
+		assert!(mesh.is_empty());
+	}
+}
+
+#[test]
+fn depth_limit_marks_truncation() {
+	let blend = BlendFile::from_bytes(props_file(false)).expect("synthetic file opens");
+	let dna = blend.dna().expect("dna parses");
+	let index = blend.pointer_index().expect("pointer index builds");
+	let ids = IdIndex::build(scan_id_blocks(&blend, &dna).expect("ids scan"));
+	let options = IdPropOptions {
+		max_depth: 1,
+		..IdPropOptions::default()
+	};
+
+	let root = read_idproperty(&dna, &index, &ids, ROOT, &options).expect("root read");
+	let IdPropValue::Group(children) = root.value else {
+		panic!("root group expected");
+	};
+	assert_eq!(children[5].value, IdPropValue::Group(Vec::new()));
+
+	let info = read_id_properties(&dna, &index, &ids, OBJECT, &options).expect("properties read");
+	assert!(info.truncated);
+}
@@ -0,0 +1,184 @@
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+use crate::blend::libtree::declared_to_path;
+use crate::blend::{
+	BlendFile, DecodeOptions, Dna, Result, StructValue, Value, codes, decode_ptr_instance, packed_file_bytes, scan_id_blocks, scan_packed_files,
+};
+
+/// PNG signature followed by the `IHDR` chunk length and tag.
+const PNG_IHDR_PREFIX: &[u8] = b"\x89PNG\r\n\x1a\n\0\0\0\x0dIHDR";
+
+/// Where an image's pixels come from (`Image.source`).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ImageSource {
+	/// Single image file (`IMA_SRC_FILE`).
+	File,
+	/// Numbered image sequence (`IMA_SRC_SEQUENCE`).
+	Sequence,
+	/// Movie file (`IMA_SRC_MOVIE`).
+	Movie,
+	/// Generated in Blender (`IMA_SRC_GENERATED`).
+	Generated,
+	/// Render or compositor viewer (`IMA_SRC_VIEWER`).
+	Viewer,
+	/// UDIM tile set (`IMA_SRC_TILED`).
+	Tiled,
+	/// Value not known to this version.
+	Other(i64),
+}
+
+impl ImageSource {
+	fn from_raw(value: i64) -> Self {
+		match value {
+			1 => Self::File,
+			2 => Self::Sequence,
+			3 => Self::Movie,
+			4 => Self::Generated,
+			5 => Self::Viewer,
+			6 => Self::Tiled,
+			other => Self::Other(other),
+		}
+	}
+
+	/// Stable machine label for JSON/text output.
+	pub fn as_str(self) -> &'static str {
+		match self {
+			Self::File => "file",
+			Self::Sequence => "sequence",
+			Self::Movie => "movie",
+			Self::Generated => "generated",
+			Self::Viewer => "viewer",
+			Self::Tiled => "tiled",
+			Self::Other(_) => "other",
+		}
+	}
+
+	/// Whether pixels are read from `filepath` on disk unless packed.
+	pub fn reads_file(self) -> bool {
+		matches!(self, Self::File | Self::Sequence | Self::Movie | Self::Tiled)
+	}
+}
+
+/// One `Image` (`IM`) ID.
+#[derive(Debug, Clone)]
+pub struct ImageRecord {
+	/// Canonical `Image` pointer.
+	pub ptr: u64,
+	/// `ID.name` (for example `IMwood.png`).
+	pub id_name: Arc<str>,
+	/// `ID.lib` pointer for images linked from a library.
+	pub lib: Option<u64>,
+	/// Stored path (`filepath`, `name` before 2.80); `None` when empty.
+	pub filepath: Option<Arc<str>>,
+	/// Pixel source.
+	pub source: ImageSource,
+	/// Input colorspace (`colorspace_settings.name`); `None` when empty.
+	pub colorspace: Option<Arc<str>>,
+	/// Number of attributed `PackedFile` payloads.
+	pub packed_files: usize,
+	/// Total declared size of those payloads in bytes.
+	pub packed_bytes: u64,
+	/// Pixel size: `gen_x`/`gen_y` for generated images, the PNG header of a packed PNG otherwise.
+	pub resolution: Option<(u32, u32)>,
+}
+
+impl ImageRecord {
+	/// Whether any packed payload is embedded for this image.
+	pub fn is_packed(&self) -> bool {
+		self.packed_files > 0
+	}
+
+	/// Whether the stored path is absolute rather than Blender-relative (`//`).
+	pub fn is_absolute(&self) -> bool {
+		self.filepath.as_deref().is_some_and(|path| !path.starts_with("//"))
+	}
+
+	/// Stored path as a filesystem path, resolving `//` against `blend_dir`.
+	pub fn resolved_path(&self, blend_dir: &Path) -> Option<PathBuf> {
+		self.filepath.as_deref().map(|path| declared_to_path(blend_dir, path))
+	}
+}
+
+/// Decode every `Image` ID with its path, source, colorspace, and packed payloads.
+///
+/// Packed payloads come from [`scan_packed_files`]; when no generated size is
+/// stored, the resolution is read from the `IHDR` of a packed PNG. Results
+/// follow file order.
+pub fn scan_images(file: &BlendFile, dna: &Dna) -> Result<Vec<ImageRecord>> {
+	let index = file.pointer_index()?;
+	let decode = DecodeOptions {
+		max_depth: 2,
+		..DecodeOptions::default()
+	};
+
+	let mut packed: HashMap<u64, Vec<_>> = HashMap::new();
+	for record in scan_packed_files(file, dna)? {
+		if let Some(owner) = record.owner_ptr {
+			packed.entry(owner).or_default().push(record);
+		}
+	}
+
+	let mut out = Vec::new();
+	for record in scan_id_blocks(file, dna)? {
+		if record.code != codes::ID_IM {
+			continue;
+		}
+		let (ptr, image) = decode_ptr_instance(dna, &index, record.old_ptr, &decode)?;
+		let source = ImageSource::from_raw(int_field(&image, "source").unwrap_or(1));
+		let payloads = packed.remove(&ptr).unwrap_or_default();
+
+		let generated = match (int_field(&image, "gen_x"), int_field(&image, "gen_y")) {
+			(Some(x), Some(y)) if source == ImageSource::Generated => u32::try_from(x).ok().zip(u32::try_from(y).ok()),
+			_ => None,
+		};
+		let resolution = generated.or_else(|| payloads.iter().find_map(|payload| packed_file_bytes(&index, payload).and_then(png_size)));
+
+		out.push(ImageRecord {
+			ptr,
+			id_name: Arc::from(record.id_name.as_ref()),
+			lib: record.lib.filter(|lib| *lib != 0),
+			filepath: string_field(&image, "filepath").or_else(|| string_field(&image, "name")),
+			source,
+			colorspace: match field(&image, "colorspace_settings") {
+				Some(Value::Struct(settings)) => string_field(settings, "name"),
+				_ => None,
+			},
+			packed_files: payloads.len(),
+			packed_bytes: payloads.iter().map(|payload| payload.size as u64).sum(),
+			resolution,
+		});
+	}
+	Ok(out)
+}
+
+/// Width and height from a PNG `IHDR` chunk.
+fn png_size(bytes: &[u8]) -> Option<(u32, u32)> {
+	let header = bytes.strip_prefix(PNG_IHDR_PREFIX)?;
+	let width = u32::from_be_bytes(header.get(0..4)?.try_into().ok()?);
+	let height = u32::from_be_bytes(header.get(4..8)?.try_into().ok()?);
+	Some((width, height))
+}
+
+fn field<'v>(item: &'v StructValue, name: &str) -> Option<&'v Value> {
+	item.fields.iter().find(|field| field.name.as_ref() == name).map(|field| &field.value)
+}
+
+fn int_field(item: &StructValue, name: &str) -> Option<i64> {
+	match field(item, name)? {
+		Value::I64(value) => Some(*value),
+		Value::U64(value) => i64::try_from(*value).ok(),
+		_ => None,
+	}
+}
+
+fn string_field(item: &StructValue, name: &str) -> Option<Arc<str>> {
+	match field(item, name)? {
+		Value::String(value) if !value.is_empty() => Some(Arc::from(value.as_ref())),
+		_ => None,
+	}
+}
+
+#[cfg(test)]
+mod tests;
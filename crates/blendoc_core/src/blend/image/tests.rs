@@ -0,0 +1,112 @@
+use std::path::Path;
+
+use blendoc_testkit::builder::{BlendBuilder, HeaderKind};
+
+use crate::blend::{BlendFile, ImageSource, scan_images};
+
+const PACKED_IMAGE: u64 = 0x1000;
+const PACKED: u64 = 0x1100;
+const PACKED_DATA: u64 = 0x1200;
+const FILE_IMAGE: u64 = 0x2000;
+const GENERATED_IMAGE: u64 = 0x3000;
+
+/// `IMpacked` (relative path, packed 2x3 PNG header), `IMdisk` (absolute
+/// path, `Filmic sRGB`), and `IMgrid` (generated 512x256).
+fn images_file() -> Vec<u8> {
+	let mut builder = BlendBuilder::new(HeaderKind::Legacy8, 404, false);
+	let sdna = builder.sdna_mut();
+	sdna.add_type("char", 1);
+	sdna.add_type("short", 2);
+	sdna.add_type("int", 4);
+	sdna.add_type("void", 0);
+	sdna.add_struct("ID", &[("void", "*next"), ("void", "*prev"), ("void", "*lib"), ("char", "name[24]")]);
+	sdna.add_struct("ColorManagedColorspaceSettings", &[("char", "name[16]")]);
+	let packed = sdna.add_struct("PackedFile", &[("int", "size"), ("int", "seek"), ("void", "*data")]);
+	let image = sdna.add_struct(
+		"Image",
+		&[
+			("ID", "id"),
+			("char", "filepath[32]"),
+			("ColorManagedColorspaceSettings", "colorspace_settings"),
+			("PackedFile", "*packedfile"),
+			("short", "source"),
+			("short", "pad"),
+			("int", "gen_x"),
+			("int", "gen_y"),
+		],
+	);
+
+	let image_payload = |name: &str, path: &str, colorspace: &str, packedfile: u64, source: i32, size: (i32, i32)| {
+		let mut payload = builder.payload();
+		payload
+			.ptr(0)
+			.ptr(0)
+			.ptr(0)
+			.name(name, 24)
+			.name(path, 32)
+			.name(colorspace, 16)
+			.ptr(packedfile)
+			.raw(&(source as i16).to_le_bytes())
+			.raw(&[0, 0])
+			.i32(size.0)
+			.i32(size.1);
+		payload.finish()
+	};
+	let mut png = b"\x89PNG\r\n\x1a\n\0\0\0\x0dIHDR".to_vec();
+	png.extend_from_slice(&2_u32.to_be_bytes());
+	png.extend_from_slice(&3_u32.to_be_bytes());
+	let mut payload = builder.payload();
+	payload.i32(png.len() as i32).i32(0).ptr(PACKED_DATA);
+	let packed_payload = payload.finish();
+
+	let blocks = [
+		(
+			*b"IM\0\0",
+			image,
+			PACKED_IMAGE,
+			image_payload("IMpacked", "//tex/wood.png", "sRGB", PACKED, 1, (0, 0)),
+		),
+		(*b"DATA", packed, PACKED, packed_payload),
+		(*b"DATA", 0, PACKED_DATA, png),
+		(
+			*b"IM\0\0",
+			image,
+			FILE_IMAGE,
+			image_payload("IMdisk", "/nonexistent/disk.exr", "Filmic sRGB", 0, 1, (0, 0)),
+		),
+		(*b"IM\0\0", image, GENERATED_IMAGE, image_payload("IMgrid", "", "", 0, 4, (512, 256))),
+	];
+	for (code, sdna_nr, old, payload) in blocks {
+		builder.block(code, sdna_nr, old, 1, payload);
+	}
+	builder.build()
+}
+
+#[test]
+fn images_report_source_paths_colorspace_and_packed_size() {
+	let blend = BlendFile::from_bytes(images_file()).expect("synthetic file opens");
+	let dna = blend.dna().expect("dna parses");
+	let images = scan_images(&blend, &dna).expect("images scan");
+	let names: Vec<&str> = images.iter().map(|image| image.id_name.as_ref()).collect();
+	assert_eq!(names, ["IMpacked", "IMdisk", "IMgrid"]);
+
+	let packed = &images[0];
+	assert!(packed.is_packed());
+	assert_eq!(packed.packed_bytes, 24);
+	assert_eq!(packed.resolution, Some((2, 3)));
+	assert_eq!(packed.colorspace.as_deref(), Some("sRGB"));
+	assert!(!packed.is_absolute());
+	assert_eq!(packed.resolved_path(Path::new("/proj")), Some(Path::new("/proj/tex/wood.png").to_path_buf()));
+
+	let disk = &images[1];
+	assert_eq!(disk.source, ImageSource::File);
+	assert!(!disk.is_packed() && disk.is_absolute());
+	assert_eq!(disk.resolution, None);
+	assert_eq!(disk.colorspace.as_deref(), Some("Filmic sRGB"));
+
+	let generated = &images[2];
+	assert_eq!(generated.source, ImageSource::Generated);
+	assert!(!generated.source.reads_file());
+	assert_eq!(generated.filepath, None);
+	assert_eq!(generated.resolution, Some((512, 256)));
+}
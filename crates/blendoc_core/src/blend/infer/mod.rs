@@ -0,0 +1,451 @@
+use std::collections::{HashMap, HashSet};
+
+use crate::blend::decl::parse_field_decl;
+use crate::blend::{BlendFile, Block, Dna, Endianness, FieldLayout, IdIndex, PointerIndex, Result, codes};
+
+/// Embedded-struct nesting followed while collecting owner pointer fields.
+const MAX_EMBED_DEPTH: u32 = 4;
+
+/// Limits for [`infer_raw_blocks`].
+#[derive(Debug, Clone)]
+pub struct InferOptions {
+	/// Infer only the block starting at this pointer (typed or not) instead of all raw `DATA` blocks.
+	pub target: Option<u64>,
+	/// Maximum raw blocks reported.
+	pub max_blocks: usize,
+	/// Maximum ranked guesses per block.
+	pub max_candidates: usize,
+	/// Payload prefix sampled for value statistics.
+	pub sample_bytes: usize,
+}
+
+impl Default for InferOptions {
+	fn default() -> Self {
+		Self {
+			target: None,
+			max_blocks: 10_000,
+			max_candidates: 5,
+			sample_bytes: 4096,
+		}
+	}
+}
+
+/// Evidence source behind one [`InferCandidate`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum InferSource {
+	/// Declared type of the struct field pointing at the block.
+	OwnerField,
+	/// Non-null words resolve to indexed blocks.
+	PointerDensity,
+	/// Words read as finite, moderately sized `float`s.
+	FloatValues,
+	/// Words read as small `int`s.
+	IntValues,
+	/// Payload is NUL-terminated printable text.
+	Text,
+	/// An SDNA struct whose size divides the payload and whose pointer slots resolve.
+	StructLayout,
+}
+
+impl InferSource {
+	/// Stable machine label for JSON/text output.
+	pub fn as_str(self) -> &'static str {
+		match self {
+			Self::OwnerField => "owner_field",
+			Self::PointerDensity => "pointer_density",
+			Self::FloatValues => "float_values",
+			Self::IntValues => "int_values",
+			Self::Text => "text",
+			Self::StructLayout => "struct_layout",
+		}
+	}
+}
+
+/// One ranked element-type guess.
+#[derive(Debug, Clone)]
+pub struct InferCandidate {
+	/// Element type label (`float[3]`, `MDeformWeight`, `Object*`, `char[]`, ...).
+	pub label: Box<str>,
+	/// Evidence source.
+	pub source: InferSource,
+	/// Bytes per element.
+	pub element_size: usize,
+	/// Element count implied by the payload length.
+	pub count: u64,
+	/// Confidence in `0.0..=1.0`.
+	pub score: f32,
+}
+
+/// First typed struct field found pointing at a block.
+#[derive(Debug, Clone)]
+pub struct InferOwner {
+	/// Start pointer of the owning block.
+	pub owner_ptr: u64,
+	/// Owning struct type name.
+	pub owner_type: Box<str>,
+	/// `ID.name` when the owning block is an ID root.
+	pub owner_id: Option<Box<str>>,
+	/// Field path on the owner (`ldata.layers.data`).
+	pub field: Box<str>,
+	/// Field declarator (`*data`, `**mat`).
+	pub decl: Box<str>,
+	/// Declared field type name.
+	pub field_type: Box<str>,
+	/// Number of typed pointer slots referencing the block.
+	pub refs: usize,
+}
+
+/// Inference result for one block.
+#[derive(Debug, Clone)]
+pub struct BlockInference {
+	/// Block start pointer.
+	pub old_ptr: u64,
+	/// Block code.
+	pub code: [u8; 4],
+	/// Header SDNA index.
+	pub sdna_nr: u32,
+	/// Payload length in bytes.
+	pub len: u64,
+	/// Header element count.
+	pub nr: u64,
+	/// Referencing field, when any typed block points at this one.
+	pub owner: Option<InferOwner>,
+	/// Fraction of sampled pointer-width words that resolve to indexed blocks.
+	pub pointer_density: f32,
+	/// Fraction of sampled 4-byte words that read as plausible non-zero floats.
+	pub float_plausibility: f32,
+	/// Fraction of sampled 4-byte words that read as small non-zero ints.
+	pub int_plausibility: f32,
+	/// Ranked guesses, best first.
+	pub candidates: Vec<InferCandidate>,
+}
+
+/// Inference over the raw blocks of one file.
+#[derive(Debug, Clone)]
+pub struct InferReport {
+	/// Raw blocks matched before the `max_blocks` cap.
+	pub raw_blocks: usize,
+	/// Per-block results in file order.
+	pub blocks: Vec<BlockInference>,
+}
+
+/// Guess element types for raw `DATA` blocks (`sdna_nr` 0).
+///
+/// Each block is scored from payload size divisibility, the density of
+/// resolvable pointers, float/int plausibility of its words, and the
+/// declared type of the first typed field that points at it.
+pub fn infer_raw_blocks<'a>(file: &'a BlendFile, dna: &Dna, index: &PointerIndex<'a>, ids: &IdIndex, options: &InferOptions) -> Result<InferReport> {
+	let mut targets = Vec::new();
+	let mut raw_blocks = 0_usize;
+	for block in file.blocks() {
+		let block = block?;
+		let selected = match options.target {
+			Some(ptr) => block.head.old == ptr,
+			None => is_raw_data(&block),
+		};
+		if selected {
+			raw_blocks += 1;
+			if targets.len() < options.max_blocks {
+				targets.push(block);
+			}
+		}
+	}
+
+	let owners = scan_owners(file, dna, ids, &targets)?;
+	let blocks = targets
+		.iter()
+		.map(|block| infer_block(dna, index, block, owners.get(&block.head.old).cloned(), options))
+		.collect();
+	Ok(InferReport { raw_blocks, blocks })
+}
+
+fn is_raw_data(block: &Block<'_>) -> bool {
+	block.head.code == codes::DATA && block.head.sdna_nr == 0 && block.head.old != 0
+}
+
+/// First typed pointer slot referencing each target block start.
+fn scan_owners(file: &BlendFile, dna: &Dna, ids: &IdIndex, targets: &[Block<'_>]) -> Result<HashMap<u64, InferOwner>> {
+	let wanted: HashSet<u64> = targets.iter().map(|block| block.head.old).collect();
+	let mut owners: HashMap<u64, InferOwner> = HashMap::new();
+	if wanted.is_empty() {
+		return Ok(owners);
+	}
+
+	let mut layouts: HashMap<u32, Option<Vec<FieldLayout>>> = HashMap::new();
+	for block in file.blocks() {
+		let block = block?;
+		if codes::is_raw_code(block.head.code) || is_raw_data(&block) {
+			continue;
+		}
+		let Some(item) = dna.struct_by_sdna(block.head.sdna_nr) else {
+			continue;
+		};
+		let struct_size = dna.tlen.get(usize::from(item.type_idx)).copied().map_or(0, usize::from);
+		if struct_size == 0 {
+			continue;
+		}
+		let owner_type = dna.type_name(item.type_idx);
+		let count = (block.payload.len() / struct_size).min(usize::try_from(block.head.nr).unwrap_or(usize::MAX));
+		for element in 0..count {
+			let bytes = &block.payload[element * struct_size..(element + 1) * struct_size];
+			let mut hits = Vec::new();
+			collect_ptr_slots(dna, &mut layouts, block.head.sdna_nr, bytes, "", 0, &mut hits);
+			for (ptr, field, decl, field_type) in hits {
+				if !wanted.contains(&ptr) {
+					continue;
+				}
+				owners.entry(ptr).and_modify(|owner| owner.refs += 1).or_insert_with(|| InferOwner {
+					owner_ptr: block.head.old,
+					owner_type: owner_type.into(),
+					owner_id: ids.get_by_ptr(block.head.old).map(|record| record.id_name.clone()),
+					field: field.into(),
+					decl,
+					field_type,
+					refs: 1,
+				});
+			}
+		}
+	}
+	Ok(owners)
+}
+
+type PtrSlot = (u64, String, Box<str>, Box<str>);
+
+fn collect_ptr_slots(
+	dna: &Dna,
+	layouts: &mut HashMap<u32, Option<Vec<FieldLayout>>>,
+	sdna_nr: u32,
+	bytes: &[u8],
+	prefix: &str,
+	depth: u32,
+	out: &mut Vec<PtrSlot>,
+) {
+	let fields = layouts.entry(sdna_nr).or_insert_with(|| dna.struct_layout(sdna_nr)).clone();
+	let Some(fields) = fields else {
+		return;
+	};
+	for field in fields {
+		let path = if prefix.is_empty() {
+			field.name.to_string()
+		} else {
+			format!("{prefix}.{}", field.name)
+		};
+		if field.is_ptr {
+			for slot in 0..field.count {
+				let start = field.offset + slot * field.element_size;
+				if let Some(ptr) = read_word(bytes, start, dna.pointer_size, dna.endianness).filter(|ptr| *ptr != 0) {
+					out.push((ptr, path.clone(), field.decl.clone(), field.type_name.clone()));
+				}
+			}
+		} else if let Some(child) = field.struct_sdna
+			&& depth < MAX_EMBED_DEPTH
+		{
+			for slot in 0..field.count {
+				let start = field.offset + slot * field.element_size;
+				if let Some(inner) = bytes.get(start..start + field.element_size) {
+					collect_ptr_slots(dna, layouts, child, inner, &path, depth + 1, out);
+				}
+			}
+		}
+	}
+}
+
+/// Score one block; `owner` comes from a whole-file pointer slot scan.
+fn infer_block<'a>(dna: &Dna, index: &PointerIndex<'a>, block: &Block<'a>, owner: Option<InferOwner>, options: &InferOptions) -> BlockInference {
+	let len = block.payload.len();
+	let sample = &block.payload[..len.min(options.sample_bytes)];
+	let ptr_size = dna.pointer_size;
+	let pointer_density = pointer_density(index, sample, ptr_size, dna.endianness);
+	let (float_plausibility, int_plausibility) = word_plausibility(sample, dna.endianness);
+
+	let mut candidates = Vec::new();
+	let mut push = |label: String, source: InferSource, element_size: usize, score: f32| {
+		if element_size > 0 && len.is_multiple_of(element_size) && score > 0.0 {
+			candidates.push(InferCandidate {
+				label: label.into(),
+				source,
+				element_size,
+				count: (len / element_size) as u64,
+				score: score.clamp(0.0, 1.0),
+			});
+		}
+	};
+
+	let mut owner_typed = false;
+	if let Some(owner) = &owner {
+		let decl = parse_field_decl(&owner.decl);
+		let field_type = owner.field_type.as_ref();
+		if decl.ptr_depth >= 2 {
+			owner_typed = true;
+			push(format!("{field_type}*"), InferSource::OwnerField, ptr_size, 0.9);
+		} else if field_type != "void" {
+			owner_typed = true;
+			let size = type_size(dna, field_type);
+			push(field_type.to_owned(), InferSource::OwnerField, size, 0.95);
+		}
+	}
+
+	if pointer_density > 0.0 {
+		push("void*".to_owned(), InferSource::PointerDensity, ptr_size, 0.85 * pointer_density);
+	}
+	if float_plausibility > 0.0 {
+		for (rank, width) in [3_usize, 4, 2, 1].into_iter().enumerate() {
+			let label = if width == 1 { "float".to_owned() } else { format!("float[{width}]") };
+			push(label, InferSource::FloatValues, 4 * width, 0.75 * float_plausibility - 0.05 * rank as f32);
+		}
+	}
+	if int_plausibility > 0.0 {
+		push("int".to_owned(), InferSource::IntValues, 4, 0.6 * int_plausibility);
+	}
+	if looks_like_text(block.payload) {
+		push("char[]".to_owned(), InferSource::Text, 1, 0.8);
+	}
+	if !owner_typed {
+		for (label, size, score) in struct_layout_matches(dna, index, block.payload, options.sample_bytes) {
+			push(label, InferSource::StructLayout, size, score);
+		}
+	}
+
+	candidates.sort_by(|left, right| right.score.total_cmp(&left.score).then(left.label.cmp(&right.label)));
+	candidates.truncate(options.max_candidates);
+
+	BlockInference {
+		old_ptr: block.head.old,
+		code: block.head.code,
+		sdna_nr: block.head.sdna_nr,
+		len: block.head.len,
+		nr: block.head.nr,
+		owner,
+		pointer_density,
+		float_plausibility,
+		int_plausibility,
+		candidates,
+	}
+}
+
+fn type_size(dna: &Dna, type_name: &str) -> usize {
+	dna.type_index(type_name)
+		.and_then(|idx| dna.tlen.get(usize::from(idx)).copied())
+		.map_or(0, usize::from)
+}
+
+/// Resolvable non-null words over all pointer-width words; zero when no word resolves.
+fn pointer_density<'a>(index: &PointerIndex<'a>, sample: &[u8], ptr_size: usize, endianness: Endianness) -> f32 {
+	let words = sample.len() / ptr_size;
+	if words == 0 {
+		return 0.0;
+	}
+	let resolved = (0..words)
+		.filter_map(|word| read_word(sample, word * ptr_size, ptr_size, endianness))
+		.filter(|ptr| *ptr != 0 && index.resolve(*ptr).is_some())
+		.count();
+	resolved as f32 / words as f32
+}
+
+/// Fractions of 4-byte words that read as plausible floats and as small ints.
+///
+/// Zero words count for neither, so all-zero payloads stay unscored.
+fn word_plausibility(sample: &[u8], endianness: Endianness) -> (f32, f32) {
+	let words = sample.len() / 4;
+	if words == 0 {
+		return (0.0, 0.0);
+	}
+	let mut floats = 0_usize;
+	let mut ints = 0_usize;
+	for word in 0..words {
+		let Some(bits) = read_word(sample, word * 4, 4, endianness).map(|value| value as u32) else {
+			continue;
+		};
+		if bits == 0 {
+			continue;
+		}
+		let value = f32::from_bits(bits).abs();
+		if value.is_finite() && (1.0e-6..1.0e7).contains(&value) {
+			floats += 1;
+		}
+		if (bits as i32).unsigned_abs() < 1_000_000 {
+			ints += 1;
+		}
+	}
+	(floats as f32 / words as f32, ints as f32 / words as f32)
+}
+
+/// At least three printable bytes, a NUL terminator, and only NUL padding after it.
+fn looks_like_text(payload: &[u8]) -> bool {
+	let Some(end) = payload.iter().position(|byte| *byte == 0) else {
+		return false;
+	};
+	end >= 3
+		&& payload[..end]
+			.iter()
+			.all(|byte| byte.is_ascii_graphic() || *byte == b' ' || byte.is_ascii_whitespace())
+		&& payload[end..].iter().all(|byte| *byte == 0)
+}
+
+/// Structs with pointer fields whose size divides the payload and whose
+/// sampled pointer slots are all null or resolvable, with at least one resolving.
+fn struct_layout_matches<'a>(dna: &Dna, index: &PointerIndex<'a>, payload: &[u8], sample_bytes: usize) -> Vec<(String, usize, f32)> {
+	let mut out = Vec::new();
+	for (sdna_nr, item) in dna.structs.iter().enumerate() {
+		let size = dna.tlen.get(usize::from(item.type_idx)).copied().map_or(0, usize::from);
+		if size < 16 || payload.is_empty() || !payload.len().is_multiple_of(size) {
+			continue;
+		}
+		let Some(layout) = dna.struct_layout(sdna_nr as u32) else {
+			continue;
+		};
+		let slots: Vec<usize> = layout
+			.iter()
+			.filter(|field| field.is_ptr)
+			.flat_map(|field| (0..field.count).map(move |slot| field.offset + slot * field.element_size))
+			.collect();
+		if slots.is_empty() {
+			continue;
+		}
+
+		let elements = (payload.len().min(sample_bytes.max(size)) / size).max(1);
+		let mut total = 0_usize;
+		let mut resolved = 0_usize;
+		let mut valid = true;
+		for element in 0..elements {
+			for slot in &slots {
+				let Some(ptr) = read_word(payload, element * size + slot, dna.pointer_size, dna.endianness) else {
+					continue;
+				};
+				total += 1;
+				if ptr == 0 {
+					continue;
+				}
+				if index.resolve(ptr).is_some() {
+					resolved += 1;
+				} else {
+					valid = false;
+				}
+			}
+		}
+		if valid && resolved > 0 {
+			out.push((dna.type_name(item.type_idx).to_owned(), size, 0.5 + 0.3 * resolved as f32 / total.max(1) as f32));
+		}
+	}
+	out.sort_by(|left, right| right.2.total_cmp(&left.2).then(left.0.cmp(&right.0)));
+	out.truncate(3);
+	out
+}
+
+fn read_word(bytes: &[u8], start: usize, width: usize, endianness: Endianness) -> Option<u64> {
+	let raw = bytes.get(start..start.checked_add(width)?)?;
+	let mut buf = [0_u8; 8];
+	match endianness {
+		Endianness::Little => {
+			buf[..width].copy_from_slice(raw);
+			Some(u64::from_le_bytes(buf))
+		}
+		Endianness::Big => {
+			buf[8 - width..].copy_from_slice(raw);
+			Some(u64::from_be_bytes(buf))
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests;
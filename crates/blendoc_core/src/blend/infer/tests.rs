@@ -0,0 +1,104 @@
+mod synthetic_raw_blocks {
+	use blendoc_testkit::builder::{BlendBuilder, HeaderKind};
+
+	use crate::blend::{BlendFile, IdIndex, InferOptions, InferReport, InferSource, infer_raw_blocks, scan_id_blocks};
+
+	const MESH: u64 = 0x1000;
+	const VERTS: u64 = 0x2000;
+	const MATS: u64 = 0x3000;
+	const MATERIAL: u64 = 0x4000;
+	const NOTE: u64 = 0x5000;
+
+	/// `MEMesh.vdata` (`void*`) points at four raw `float[3]` positions,
+	/// `MEMesh.mat` (`Material**`) at a raw pointer array, and one orphan raw
+	/// block holds a NUL-terminated string.
+	fn raw_file() -> Vec<u8> {
+		let mut builder = BlendBuilder::new(HeaderKind::Legacy8, 300, false);
+		let sdna = builder.sdna_mut();
+		sdna.add_type("char", 1);
+		sdna.add_type("int", 4);
+		sdna.add_type("float", 4);
+		sdna.add_type("void", 0);
+		sdna.add_struct("Link", &[("Link", "*next"), ("Link", "*prev")]);
+		sdna.add_struct("ID", &[("void", "*next"), ("void", "*prev"), ("void", "*lib"), ("char", "name[24]")]);
+		let mesh = sdna.add_struct(
+			"Mesh",
+			&[("ID", "id"), ("void", "*vdata"), ("Material", "**mat"), ("int", "totvert"), ("int", "totcol")],
+		);
+		let material = sdna.add_struct("Material", &[("ID", "id"), ("float", "r")]);
+
+		let id = |name: &str| {
+			let mut payload = builder.payload();
+			payload.ptr(0).ptr(0).ptr(0).name(name, 24);
+			payload.finish()
+		};
+		let mut payload = builder.payload();
+		payload.raw(&id("MEMesh")).ptr(VERTS).ptr(MATS).i32(4).i32(2);
+		let mesh_payload = payload.finish();
+		let mut payload = builder.payload();
+		payload.raw(&id("MAMaterial")).f32(0.8);
+		let material_payload = payload.finish();
+
+		let mut payload = builder.payload();
+		for idx in 0..4 {
+			payload.f32(idx as f32 + 0.25).f32(0.5).f32(1.0);
+		}
+		let verts_payload = payload.finish();
+		let mut payload = builder.payload();
+		payload.ptr(MATERIAL).ptr(MATERIAL);
+		let mats_payload = payload.finish();
+		let mut payload = builder.payload();
+		payload.name("hello", 8);
+		let note_payload = payload.finish();
+
+		builder
+			.block(*b"ME\0\0", mesh, MESH, 1, mesh_payload)
+			.block(*b"DATA", 0, VERTS, 1, verts_payload)
+			.block(*b"DATA", 0, MATS, 1, mats_payload)
+			.block(*b"MA\0\0", material, MATERIAL, 1, material_payload)
+			.block(*b"DATA", 0, NOTE, 1, note_payload);
+		builder.build()
+	}
+
+	fn infer(options: &InferOptions) -> InferReport {
+		let blend = BlendFile::from_bytes(raw_file()).expect("synthetic file opens");
+		let dna = blend.dna().expect("dna parses");
+		let index = blend.pointer_index().expect("pointer index builds");
+		let ids = IdIndex::build(scan_id_blocks(&blend, &dna).expect("id scan succeeds"));
+		infer_raw_blocks(&blend, &dna, &index, &ids, options).expect("inference succeeds")
+	}
+
+	#[test]
+	fn raw_blocks_rank_owner_float_and_text_guesses() {
+		let report = infer(&InferOptions::default());
+		assert_eq!(report.raw_blocks, 3);
+
+		let verts = report.blocks.iter().find(|block| block.old_ptr == VERTS).expect("verts block");
+		let owner = verts.owner.as_ref().expect("verts owner");
+		assert_eq!((owner.owner_id.as_deref(), owner.field.as_ref()), (Some("MEMesh"), "vdata"));
+		assert_eq!(verts.candidates[0].label.as_ref(), "float[3]");
+		assert_eq!(verts.candidates[0].count, 4);
+		assert!(verts.float_plausibility > 0.9);
+
+		let mats = report.blocks.iter().find(|block| block.old_ptr == MATS).expect("mats block");
+		assert_eq!(mats.candidates[0].label.as_ref(), "Material*");
+		assert_eq!(mats.candidates[0].source, InferSource::OwnerField);
+		assert!((mats.pointer_density - 1.0).abs() < f32::EPSILON);
+
+		let note = report.blocks.iter().find(|block| block.old_ptr == NOTE).expect("note block");
+		assert!(note.owner.is_none());
+		assert_eq!(note.candidates[0].source, InferSource::Text);
+	}
+
+	#[test]
+	fn target_selects_one_typed_block() {
+		let report = infer(&InferOptions {
+			target: Some(MATERIAL),
+			..InferOptions::default()
+		});
+		assert_eq!(report.blocks.len(), 1);
+		assert_eq!(report.blocks[0].old_ptr, MATERIAL);
+		// Only the raw `mat` array points here, and raw blocks carry no field names.
+		assert!(report.blocks[0].owner.is_none());
+	}
+}
@@ -0,0 +1,100 @@
+//! JSON payloads shared by the embedding bindings (`blendoc_ffi`, `blendoc_wasm`).
+//!
+//! Enabled by the `json` feature. Shapes match `blendoc show --json`, so a
+//! host gets the same document whichever binding it loads.
+
+use serde_json::{Map, Value as JsonValue};
+
+use crate::blend::{
+	ChasePolicy, ChaseStopReason, DecodeOptions, Dna, FieldPath, IdIndex, IdRecord, PointerIndex, Result, Value, chase_from_ptr, codes, decode_ptr_instance,
+};
+
+/// Pointer rendered as fixed-width lowercase hex.
+pub fn ptr_hex(ptr: u64) -> String {
+	format!("0x{ptr:016x}")
+}
+
+/// Decoded value in the same JSON shape as `blendoc show --json`.
+pub fn value_json(value: &Value) -> JsonValue {
+	match value {
+		Value::Null => JsonValue::Null,
+		Value::Bool(v) => serde_json::json!(v),
+		Value::I64(v) => serde_json::json!(v),
+		Value::U64(v) => serde_json::json!(v),
+		Value::F32(v) => serde_json::json!(v),
+		Value::F64(v) => serde_json::json!(v),
+		Value::Bytes(v) => JsonValue::Array(v.iter().map(|item| serde_json::json!(item)).collect()),
+		Value::String(v) => serde_json::json!(v),
+		Value::Ptr(v) => serde_json::json!(ptr_hex(*v)),
+		Value::Array(items) => JsonValue::Array(items.iter().map(value_json).collect()),
+		Value::Struct(item) => {
+			let fields: Map<String, JsonValue> = item.fields.iter().map(|field| (field.name.to_string(), value_json(&field.value))).collect();
+			serde_json::json!({ "type": item.type_name.as_ref(), "fields": fields })
+		}
+	}
+}
+
+/// Compact label for why a chase stopped, e.g. `UnresolvedPtr(0x...)`.
+pub fn stop_reason(reason: &ChaseStopReason) -> String {
+	match reason {
+		ChaseStopReason::NullPtr => "NullPtr".to_owned(),
+		ChaseStopReason::UnresolvedPtr(ptr) => format!("UnresolvedPtr({})", ptr_hex(*ptr)),
+		ChaseStopReason::Cycle(ptr) => format!("Cycle({})", ptr_hex(*ptr)),
+		ChaseStopReason::MissingField { struct_name, field } => format!("MissingField({struct_name}.{field})"),
+		ChaseStopReason::ExpectedStruct { got } => format!("ExpectedStruct(got={got})"),
+		ChaseStopReason::ExpectedArray { got } => format!("ExpectedArray(got={got})"),
+		ChaseStopReason::IndexOob { index, len } => format!("IndexOob(index={index},len={len})"),
+	}
+}
+
+/// Array of ID rows with `ptr`, `code`, `type`, and `name`.
+pub fn ids_json(records: &[IdRecord]) -> JsonValue {
+	let rows = records
+		.iter()
+		.map(|record| {
+			serde_json::json!({
+				"ptr": ptr_hex(record.old_ptr),
+				"code": codes::code_to_str(record.code),
+				"type": record.type_name.as_ref(),
+				"name": record.id_name.as_ref(),
+			})
+		})
+		.collect();
+	JsonValue::Array(rows)
+}
+
+/// Chase `path` from the ID matching `selector`, or decode the whole ID
+/// struct when `path` is `None`, as `{root, root_ptr, path, value, stop}`.
+pub fn chase_json(dna: &Dna, index: &PointerIndex<'_>, ids: &IdIndex, selector: &str, path: Option<&str>) -> Result<JsonValue> {
+	let root = ids.resolve_selector(selector)?;
+	let decode = DecodeOptions::default();
+
+	let (value, stop) = match path {
+		Some(path) => {
+			let field_path = FieldPath::parse(path)?;
+			let result = chase_from_ptr(dna, index, root.old_ptr, &field_path, &decode, &ChasePolicy::default())?;
+			let stop = result.stop.map(|stop| {
+				serde_json::json!({
+					"step": stop.step_index,
+					"reason": stop_reason(&stop.reason),
+				})
+			});
+			(result.value, stop)
+		}
+		None => {
+			let (_, value) = decode_ptr_instance(dna, index, root.old_ptr, &decode)?;
+			(Value::Struct(value), None)
+		}
+	};
+
+	Ok(serde_json::json!({
+		"root": root.id_name.as_ref(),
+		"root_ptr": ptr_hex(root.old_ptr),
+		"path": path,
+		"value": value_json(&value),
+		"stop": stop,
+	}))
+}
+
+#[cfg(test)]
+mod tests;
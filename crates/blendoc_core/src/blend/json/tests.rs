@@ -0,0 +1,36 @@
+use blendoc_testkit::builder::{HeaderKind, SCENE_ID_NAMES, scene_file};
+
+use crate::blend::json::{chase_json, ids_json, ptr_hex, stop_reason};
+use crate::blend::{BlendError, BlendFile, ChaseStopReason, IdIndex, codes, scan_id_blocks};
+
+#[test]
+fn ids_and_chase_payloads_share_one_shape() {
+	let blend = BlendFile::from_bytes(scene_file(HeaderKind::Legacy8, false)).expect("synthetic file parses");
+	let dna = blend.dna().expect("dna parses");
+	let ids = IdIndex::build(scan_id_blocks(&blend, &dna).expect("ids scan"));
+	let index = blend.pointer_index().expect("index builds");
+
+	let rows = ids_json(&ids.records);
+	let names: Vec<&str> = rows.as_array().expect("array").iter().filter_map(|row| row["name"].as_str()).collect();
+	assert_eq!(names, SCENE_ID_NAMES);
+	assert_eq!(rows[1]["ptr"], ptr_hex(ids.records[1].old_ptr));
+	assert_eq!(rows[1]["code"], codes::code_to_str(ids.records[1].code));
+
+	let chased = chase_json(&dna, &index, &ids, SCENE_ID_NAMES[1], Some("data")).expect("chase runs");
+	assert_eq!(chased["value"]["fields"]["id"]["fields"]["name"], SCENE_ID_NAMES[2]);
+	assert_eq!(chased["path"], "data");
+	assert!(chased["stop"].is_null());
+
+	let whole = chase_json(&dna, &index, &ids, SCENE_ID_NAMES[1], None).expect("decode runs");
+	assert_eq!(whole["value"]["type"], "Object");
+	assert!(whole["path"].is_null());
+
+	let missing = chase_json(&dna, &index, &ids, "OBMissing", None);
+	assert!(matches!(missing, Err(BlendError::IdRecordNotFound { .. })), "{missing:?}");
+}
+
+#[test]
+fn stop_reasons_render_compactly() {
+	assert_eq!(stop_reason(&ChaseStopReason::UnresolvedPtr(0x10)), "UnresolvedPtr(0x0000000000000010)");
+	assert_eq!(stop_reason(&ChaseStopReason::IndexOob { index: 3, len: 2 }), "IndexOob(index=3,len=2)");
+}
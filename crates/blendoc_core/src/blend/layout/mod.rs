@@ -0,0 +1,98 @@
+use crate::blend::Dna;
+
+/// Maximum embedded-struct nesting expanded into leaf spans.
+const MAX_NESTING: u32 = 16;
+
+/// One leaf field of a [`LayoutMap`], placed relative to the outer struct.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LayoutSpan {
+	/// Dotted path through embedded structs, with `[i]` for struct array elements.
+	pub path: Box<str>,
+	/// Field type name; pointers keep their pointee type.
+	pub type_name: Box<str>,
+	/// Byte offset from the start of the outer struct.
+	pub offset: usize,
+	/// Bytes the field occupies, all inline array elements included.
+	pub size: usize,
+	/// Inline array element count (1 for scalars).
+	pub count: usize,
+	/// Whether the field stores pointers.
+	pub is_ptr: bool,
+}
+
+impl LayoutSpan {
+	/// Exclusive end offset.
+	pub fn end(&self) -> usize {
+		self.offset + self.size
+	}
+}
+
+/// Flattened byte layout of one SDNA struct, from [`layout_map`].
+#[derive(Debug, Clone)]
+pub struct LayoutMap {
+	/// Struct type name.
+	pub type_name: Box<str>,
+	/// Struct size from `TLEN`.
+	pub size: usize,
+	/// Leaf fields in offset order.
+	pub spans: Vec<LayoutSpan>,
+}
+
+impl LayoutMap {
+	/// Leaf field covering byte `offset`, if any.
+	pub fn span_at(&self, offset: usize) -> Option<&LayoutSpan> {
+		let idx = self.spans.partition_point(|span| span.offset <= offset);
+		self.spans[..idx].last().filter(|span| offset < span.end())
+	}
+
+	/// Bytes the leaf fields account for; differs from [`Self::size`] when SDNA and `TLEN` disagree.
+	pub fn fields_size(&self) -> usize {
+		self.spans.last().map_or(0, LayoutSpan::end)
+	}
+}
+
+/// Flatten struct `sdna_nr` into leaf field spans, expanding embedded structs.
+///
+/// Offsets come from [`Dna::struct_layout`], so they match what the decoder
+/// reads. Embedded struct arrays expand per element (`mat[1].name`); pointer
+/// and primitive arrays stay one span. Returns `None` for an unknown index.
+pub fn layout_map(dna: &Dna, sdna_nr: u32) -> Option<LayoutMap> {
+	let item = dna.struct_by_sdna(sdna_nr)?;
+	let mut spans = Vec::new();
+	collect_spans(dna, sdna_nr, 0, "", MAX_NESTING, &mut spans);
+	Some(LayoutMap {
+		type_name: dna.type_name(item.type_idx).into(),
+		size: usize::from(dna.tlen[item.type_idx as usize]),
+		spans,
+	})
+}
+
+fn collect_spans(dna: &Dna, sdna_nr: u32, base: usize, prefix: &str, depth_left: u32, out: &mut Vec<LayoutSpan>) {
+	let Some(layout) = dna.struct_layout(sdna_nr) else {
+		return;
+	};
+	for field in layout {
+		let path = format!("{prefix}{}", field.name);
+		let offset = base + field.offset;
+		if let Some(nested) = field.struct_sdna
+			&& depth_left > 0
+		{
+			for idx in 0..field.count {
+				let element = if field.count == 1 { format!("{path}.") } else { format!("{path}[{idx}].") };
+				collect_spans(dna, nested, offset + idx * field.element_size, &element, depth_left - 1, out);
+			}
+			continue;
+		}
+		out.push(LayoutSpan {
+			path: path.into(),
+			type_name: field.type_name,
+			offset,
+			size: field.size,
+			count: field.count,
+			is_ptr: field.is_ptr,
+		});
+	}
+}
+
+#[cfg(test)]
+mod tests;
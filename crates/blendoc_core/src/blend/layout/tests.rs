@@ -0,0 +1,47 @@
+use blendoc_testkit::builder::{BlendBuilder, HeaderKind, scene_file};
+
+use crate::blend::{BlendFile, layout_map};
+
+#[test]
+fn flattens_embedded_structs_into_leaf_spans() {
+	let blend = BlendFile::from_bytes(scene_file(HeaderKind::Legacy8, false)).expect("synthetic file parses");
+	let dna = blend.dna().expect("dna parses");
+	let sdna_nr = dna.struct_for_type[usize::from(dna.type_for_name["Object"])].expect("Object is a struct");
+	let map = layout_map(&dna, sdna_nr).expect("layout map");
+
+	let spans: Vec<(&str, usize, usize, bool)> = map.spans.iter().map(|span| (span.path.as_ref(), span.offset, span.size, span.is_ptr)).collect();
+	assert_eq!(
+		spans,
+		[
+			("id.next", 0, 8, true),
+			("id.prev", 8, 8, true),
+			("id.lib", 16, 8, true),
+			("id.name", 24, 24, false),
+			("data", 48, 8, true),
+			("loc", 56, 12, false),
+		]
+	);
+	assert_eq!(map.type_name.as_ref(), "Object");
+	assert_eq!(map.size, map.fields_size());
+	assert_eq!(map.span_at(30).map(|span| span.path.as_ref()), Some("id.name"));
+	assert_eq!(map.span_at(48).map(|span| span.path.as_ref()), Some("data"));
+	assert!(map.span_at(68).is_none());
+}
+
+#[test]
+fn expands_struct_arrays_per_element() {
+	let mut builder = BlendBuilder::new(HeaderKind::Legacy4, 280, false);
+	let sdna = builder.sdna_mut();
+	sdna.add_type("int", 4);
+	sdna.add_struct("Pair", &[("int", "a"), ("int", "b")]);
+	let outer = sdna.add_struct("Outer", &[("Pair", "pairs[2]"), ("int", "*flags")]);
+	let blend = BlendFile::from_bytes(builder.build()).expect("synthetic file parses");
+	let dna = blend.dna().expect("dna parses");
+
+	let map = layout_map(&dna, outer).expect("layout map");
+	let paths: Vec<&str> = map.spans.iter().map(|span| span.path.as_ref()).collect();
+	assert_eq!(paths, ["pairs[0].a", "pairs[0].b", "pairs[1].a", "pairs[1].b", "flags"]);
+	assert_eq!(map.spans[3].offset, 12);
+	assert_eq!((map.spans[4].offset, map.spans[4].size), (16, 4));
+	assert!(layout_map(&dna, 99).is_none());
+}
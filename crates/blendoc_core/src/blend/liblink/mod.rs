@@ -1,7 +1,9 @@
+use std::collections::HashMap;
 use std::sync::Arc;
 
 use crate::blend::{
-	BlendFile, DecodeOptions, Dna, IdIndex, RefRecord, RefScanOptions, Result, StructValue, Value, decode_ptr_instance, scan_id_blocks, scan_refs_from_ptr,
+	BlendFile, DecodeOptions, Dna, IdIndex, IdRecord, RefRecord, RefScanOptions, Result, StructValue, Value, codes, decode_ptr_instance, scan_id_blocks,
+	scan_refs_from_ptr,
 };
 
 /// One declared external library record from a `Library` (`LI`) ID block.
@@ -188,8 +190,71 @@ pub fn scan_id_link_provenance(file: &BlendFile, dna: &Dna) -> Result<Vec<IdLink
 	Ok(out)
 }
 
+/// Source-library labels for linked IDs, keyed by canonical ID pointer.
+///
+/// Labels use a `lib://<file name>` form derived from `Library.name`
+/// (`//props/weapons.blend` becomes `lib://weapons.blend`). An empty map
+/// (the `Default`) annotates nothing.
+#[derive(Debug, Clone, Default)]
+pub struct LibraryNamespaces {
+	by_id: HashMap<u64, Arc<str>>,
+}
+
+impl LibraryNamespaces {
+	/// Map each ID whose `ID.lib` names a declared library to that library's label.
+	pub fn build(records: &[IdRecord], libraries: &[LibraryRecord]) -> Self {
+		let labels: HashMap<u64, Arc<str>> = libraries
+			.iter()
+			.map(|library| (library.id_ptr, namespace_label(&library.library_path)))
+			.collect();
+		let by_id = records
+			.iter()
+			.filter_map(|record| {
+				let label = labels.get(&record.lib.filter(|ptr| *ptr != 0)?)?;
+				Some((record.old_ptr, label.clone()))
+			})
+			.collect();
+		Self { by_id }
+	}
+
+	/// Source-library label for one ID pointer, when the ID is linked.
+	pub fn get(&self, id_ptr: u64) -> Option<&str> {
+		self.by_id.get(&id_ptr).map(AsRef::as_ref)
+	}
+
+	/// Render `name` with a ` [lib://...]` suffix when `id_ptr` is linked.
+	pub fn annotate(&self, id_ptr: u64, name: &str) -> String {
+		match self.get(id_ptr) {
+			Some(label) => format!("{name} [{label}]"),
+			None => name.to_owned(),
+		}
+	}
+
+	/// Number of annotated IDs.
+	pub fn len(&self) -> usize {
+		self.by_id.len()
+	}
+
+	/// Whether no IDs are annotated.
+	pub fn is_empty(&self) -> bool {
+		self.by_id.is_empty()
+	}
+}
+
+/// Scan ID and library records and build their [`LibraryNamespaces`].
+pub fn scan_library_namespaces(file: &BlendFile, dna: &Dna) -> Result<LibraryNamespaces> {
+	let records = scan_id_blocks(file, dna)?;
+	let libraries = scan_library_records(file, dna)?;
+	Ok(LibraryNamespaces::build(&records, &libraries))
+}
+
+fn namespace_label(library_path: &str) -> Arc<str> {
+	let file_name = library_path.rsplit(['/', '\\']).find(|part| !part.is_empty()).unwrap_or(library_path);
+	Arc::from(format!("lib://{file_name}"))
+}
+
 fn is_library_id(code: [u8; 4], type_name: &str) -> bool {
-	code == [b'L', b'I', 0, 0] || type_name == "Library"
+	code == codes::ID_LI || type_name == "Library"
 }
 
 fn extract_struct_string_field(value: &StructValue, field_name: &str) -> Option<Arc<str>> {
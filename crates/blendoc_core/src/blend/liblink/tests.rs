@@ -74,3 +74,43 @@ mod fixture_provenance {
 		);
 	}
 }
+
+mod namespaces {
+	use crate::blend::{IdRecord, LibraryNamespaces, LibraryRecord};
+
+	fn record(old_ptr: u64, id_name: &str, lib: Option<u64>) -> IdRecord {
+		IdRecord {
+			old_ptr,
+			code: [id_name.as_bytes()[0], id_name.as_bytes()[1], 0, 0],
+			sdna_nr: 0,
+			type_name: "ID".into(),
+			id_name: id_name.into(),
+			next: None,
+			prev: None,
+			lib,
+		}
+	}
+
+	#[test]
+	fn linked_ids_get_library_file_label() {
+		let libraries = vec![LibraryRecord {
+			id_ptr: 0x9000,
+			id_name: "LIweapons.blend".into(),
+			library_path: "//props/weapons.blend".into(),
+			is_relative: true,
+		}];
+		let records = vec![
+			record(0x1000, "OBSword", Some(0x9000)),
+			record(0x2000, "OBLocal", None),
+			record(0x3000, "OBDangling", Some(0x7000)),
+		];
+
+		let namespaces = LibraryNamespaces::build(&records, &libraries);
+
+		assert_eq!(namespaces.len(), 1);
+		assert_eq!(namespaces.get(0x1000), Some("lib://weapons.blend"));
+		assert_eq!(namespaces.annotate(0x1000, "OBSword"), "OBSword [lib://weapons.blend]");
+		assert_eq!(namespaces.annotate(0x2000, "OBLocal"), "OBLocal");
+		assert_eq!(namespaces.get(0x3000), None);
+	}
+}
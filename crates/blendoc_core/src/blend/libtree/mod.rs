@@ -0,0 +1,291 @@
+use std::collections::{HashMap, HashSet};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+use crate::blend::{BlendFile, LibraryRecord, Result, scan_library_records};
+
+/// How one library in a [`LibraryTree`] was resolved.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum LibraryStatus {
+	/// Found and opened; its own libraries are listed as children.
+	Opened,
+	/// Found on disk but not opened because the depth limit was reached.
+	Found,
+	/// No file at the declared path, next to the owner, or under the search root.
+	Missing,
+	/// Found but could not be read as a blend file.
+	Unreadable {
+		/// Open or parse error.
+		error: Arc<str>,
+	},
+	/// Already open further up this branch; not expanded again.
+	Cycle,
+	/// Already expanded elsewhere in the tree; not expanded again.
+	Repeated,
+}
+
+impl LibraryStatus {
+	/// Stable snake-case label.
+	pub fn as_str(&self) -> &'static str {
+		match self {
+			Self::Opened => "opened",
+			Self::Found => "found",
+			Self::Missing => "missing",
+			Self::Unreadable { .. } => "unreadable",
+			Self::Cycle => "cycle",
+			Self::Repeated => "repeated",
+		}
+	}
+
+	/// Whether the library could not be located or read.
+	pub fn is_problem(&self) -> bool {
+		matches!(self, Self::Missing | Self::Unreadable { .. })
+	}
+}
+
+/// One file in a [`LibraryTree`].
+#[derive(Debug, Clone)]
+pub struct LibraryNode {
+	/// `Library` ID name in the parent file; `None` for the root.
+	pub library_id: Option<Arc<str>>,
+	/// Path as stored in the parent file (the root's own path for the root).
+	pub declared_path: Arc<str>,
+	/// File the declared path resolved to, when one was found.
+	pub resolved_path: Option<PathBuf>,
+	/// Whether the file was found only by name under the search root.
+	pub via_search_root: bool,
+	/// Resolution outcome.
+	pub status: LibraryStatus,
+	/// Libraries linked by this file, in `Library` ID order.
+	pub children: Vec<LibraryNode>,
+}
+
+impl LibraryNode {
+	/// Visit this node and its descendants depth-first with their depth (root = 0).
+	pub fn walk(&self, visit: &mut impl FnMut(&LibraryNode, usize)) {
+		self.walk_at(0, visit);
+	}
+
+	fn walk_at(&self, depth: usize, visit: &mut impl FnMut(&LibraryNode, usize)) {
+		visit(self, depth);
+		for child in &self.children {
+			child.walk_at(depth + 1, visit);
+		}
+	}
+}
+
+/// Limits and lookup roots for [`build_library_tree`].
+#[derive(Debug, Clone)]
+pub struct LibraryTreeOptions {
+	/// Directory searched by file name when a declared path does not exist.
+	pub search_root: Option<PathBuf>,
+	/// Levels of libraries opened below the root; `0` opens only the root.
+	pub max_depth: usize,
+	/// Maximum files opened, including the root.
+	pub max_files: usize,
+}
+
+impl Default for LibraryTreeOptions {
+	fn default() -> Self {
+		Self {
+			search_root: None,
+			max_depth: 16,
+			max_files: 1000,
+		}
+	}
+}
+
+/// Cross-file library dependency tree rooted at one blend file.
+#[derive(Debug, Clone)]
+pub struct LibraryTree {
+	/// Root file node.
+	pub root: LibraryNode,
+	/// Files opened while building the tree.
+	pub files_opened: usize,
+}
+
+impl LibraryTree {
+	/// Nodes whose library is missing or unreadable, in tree order.
+	pub fn problems(&self) -> Vec<&LibraryNode> {
+		let mut out = Vec::new();
+		collect_problems(&self.root, &mut out);
+		out
+	}
+}
+
+fn collect_problems<'a>(node: &'a LibraryNode, out: &mut Vec<&'a LibraryNode>) {
+	if node.status.is_problem() {
+		out.push(node);
+	}
+	for child in &node.children {
+		collect_problems(child, out);
+	}
+}
+
+/// Open `root` and follow its linked libraries recursively.
+///
+/// Blender-relative `//` paths resolve against the directory of the file that
+/// declares them; absolute paths are used as stored. When neither exists,
+/// the file name is looked up under [`LibraryTreeOptions::search_root`].
+/// Each file is expanded once: later links to it are marked
+/// [`LibraryStatus::Repeated`], links back up the branch [`LibraryStatus::Cycle`].
+/// Only a failure to open `root` itself is returned as `Err`.
+pub fn build_library_tree(root: &Path, options: &LibraryTreeOptions) -> Result<LibraryTree> {
+	let file = BlendFile::open_mmap(root)?;
+	let records = scan_library_records(&file, &file.dna()?)?;
+	drop(file);
+
+	let mut walker = TreeWalker {
+		options,
+		by_name: None,
+		expanded: HashSet::new(),
+		branch: Vec::new(),
+		files_opened: 1,
+	};
+	let key = canonical_key(root);
+	walker.expanded.insert(key.clone());
+	walker.branch.push(key);
+	let children = walker.children(root, records, 0);
+
+	Ok(LibraryTree {
+		root: LibraryNode {
+			library_id: None,
+			declared_path: Arc::from(root.display().to_string()),
+			resolved_path: Some(root.to_path_buf()),
+			via_search_root: false,
+			status: LibraryStatus::Opened,
+			children,
+		},
+		files_opened: walker.files_opened,
+	})
+}
+
+struct TreeWalker<'a> {
+	options: &'a LibraryTreeOptions,
+	/// Lazily built `file name -> paths` map of the search root.
+	by_name: Option<HashMap<String, Vec<PathBuf>>>,
+	expanded: HashSet<PathBuf>,
+	branch: Vec<PathBuf>,
+	files_opened: usize,
+}
+
+impl TreeWalker<'_> {
+	fn children(&mut self, owner: &Path, records: Vec<LibraryRecord>, depth: usize) -> Vec<LibraryNode> {
+		let base = owner.parent().unwrap_or(Path::new(""));
+		records.into_iter().map(|record| self.node(base, record, depth + 1)).collect()
+	}
+
+	fn node(&mut self, base: &Path, record: LibraryRecord, depth: usize) -> LibraryNode {
+		let mut node = LibraryNode {
+			library_id: Some(record.id_name.clone()),
+			declared_path: record.library_path.clone(),
+			resolved_path: None,
+			via_search_root: false,
+			status: LibraryStatus::Missing,
+			children: Vec::new(),
+		};
+
+		let direct = declared_to_path(base, &record.library_path);
+		let found = if direct.is_file() {
+			Some(direct)
+		} else {
+			node.via_search_root = true;
+			self.search(&direct)
+		};
+		let Some(path) = found else {
+			node.via_search_root = false;
+			return node;
+		};
+		node.resolved_path = Some(path.clone());
+
+		let key = canonical_key(&path);
+		if self.branch.contains(&key) {
+			node.status = LibraryStatus::Cycle;
+			return node;
+		}
+		if self.expanded.contains(&key) {
+			node.status = LibraryStatus::Repeated;
+			return node;
+		}
+		if depth > self.options.max_depth || self.files_opened >= self.options.max_files {
+			node.status = LibraryStatus::Found;
+			return node;
+		}
+
+		self.files_opened += 1;
+		let records = match open_records(&path) {
+			Ok(records) => records,
+			Err(err) => {
+				node.status = LibraryStatus::Unreadable {
+					error: Arc::from(err.to_string()),
+				};
+				return node;
+			}
+		};
+		self.expanded.insert(key.clone());
+		self.branch.push(key);
+		node.children = self.children(&path, records, depth);
+		self.branch.pop();
+		node.status = LibraryStatus::Opened;
+		node
+	}
+
+	/// Find a file with the same name as `missing` under the search root.
+	fn search(&mut self, missing: &Path) -> Option<PathBuf> {
+		let root = self.options.search_root.as_deref()?;
+		let name = missing.file_name()?.to_str()?.to_owned();
+		let by_name = self.by_name.get_or_insert_with(|| index_dir(root));
+		by_name.get(&name)?.first().cloned()
+	}
+}
+
+fn open_records(path: &Path) -> Result<Vec<LibraryRecord>> {
+	let file = BlendFile::open_mmap(path)?;
+	scan_library_records(&file, &file.dna()?)
+}
+
+/// Turn a stored library path into a filesystem path relative to `base`.
+pub(crate) fn declared_to_path(base: &Path, declared: &str) -> PathBuf {
+	// Paths saved on Windows keep `\` separators.
+	let normalized = declared.replace('\\', "/");
+	match normalized.strip_prefix("//") {
+		Some(relative) => base.join(relative),
+		None => PathBuf::from(normalized),
+	}
+}
+
+fn canonical_key(path: &Path) -> PathBuf {
+	fs::canonicalize(path).unwrap_or_else(|_| path.to_path_buf())
+}
+
+/// Map file names to paths for every regular file below `root`, sorted.
+fn index_dir(root: &Path) -> HashMap<String, Vec<PathBuf>> {
+	let mut out: HashMap<String, Vec<PathBuf>> = HashMap::new();
+	let mut pending = vec![root.to_path_buf()];
+	while let Some(dir) = pending.pop() {
+		let Ok(entries) = fs::read_dir(&dir) else {
+			continue;
+		};
+		for entry in entries.flatten() {
+			let path = entry.path();
+			let Ok(kind) = entry.file_type() else {
+				continue;
+			};
+			if kind.is_dir() {
+				pending.push(path);
+			} else if kind.is_file()
+				&& let Some(name) = path.file_name().and_then(|name| name.to_str())
+			{
+				out.entry(name.to_owned()).or_default().push(path);
+			}
+		}
+	}
+	for paths in out.values_mut() {
+		paths.sort();
+	}
+	out
+}
+
+#[cfg(test)]
+mod tests;
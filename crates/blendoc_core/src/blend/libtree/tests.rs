@@ -0,0 +1,108 @@
+mod synthetic_libtree {
+	use std::path::{Path, PathBuf};
+
+	use blendoc_testkit::builder::{BlendBuilder, HeaderKind};
+
+	use crate::blend::{LibraryStatus, LibraryTreeOptions, build_library_tree};
+
+	/// File declaring one `Library` ID per `(id name, path)` pair.
+	fn library_file(libraries: &[(&str, &str)]) -> Vec<u8> {
+		let mut builder = BlendBuilder::new(HeaderKind::Legacy8, 404, false);
+		let sdna = builder.sdna_mut();
+		sdna.add_struct("Link", &[("Link", "*next"), ("Link", "*prev")]);
+		sdna.add_type("char", 1);
+		sdna.add_type("void", 0);
+		sdna.add_struct("ID", &[("void", "*next"), ("void", "*prev"), ("void", "*lib"), ("char", "name[24]")]);
+		let library = sdna.add_struct("Library", &[("ID", "id"), ("char", "name[64]")]);
+
+		for (idx, (id_name, path)) in libraries.iter().enumerate() {
+			let mut payload = builder.payload();
+			payload.ptr(0).ptr(0).ptr(0).name(id_name, 24).name(path, 64);
+			let library_payload = payload.finish();
+			builder.block(*b"LI\0\0", library, 0x1000 + idx as u64 * 0x100, 1, library_payload);
+		}
+		builder.build()
+	}
+
+	fn write(path: &Path, bytes: &[u8]) {
+		std::fs::create_dir_all(path.parent().expect("parent dir")).expect("dir created");
+		std::fs::write(path, bytes).expect("blend written");
+	}
+
+	/// `root -> {lib/a, missing, broken, c by search}` with `a -> {root, shared/c}`.
+	fn fixture_dir() -> PathBuf {
+		let dir = std::env::temp_dir().join(format!("blendoc_libtree_{}", std::process::id()));
+		let _ = std::fs::remove_dir_all(&dir);
+		write(
+			&dir.join("root.blend"),
+			&library_file(&[
+				("LIa.blend", "//lib/a.blend"),
+				("LImissing.blend", "//missing.blend"),
+				("LIbroken.blend", "//broken.blend"),
+				("LIc.blend", "/nowhere/assets/c.blend"),
+			]),
+		);
+		write(
+			&dir.join("lib").join("a.blend"),
+			&library_file(&[("LIroot.blend", "//../root.blend"), ("LIc.blend", "//..\\shared\\c.blend")]),
+		);
+		write(&dir.join("shared").join("c.blend"), &library_file(&[]));
+		write(&dir.join("broken.blend"), b"not a blend file");
+		dir
+	}
+
+	#[test]
+	fn follows_relative_links_and_reports_problems() {
+		let dir = fixture_dir();
+		let tree = build_library_tree(
+			&dir.join("root.blend"),
+			&LibraryTreeOptions {
+				search_root: Some(dir.clone()),
+				..LibraryTreeOptions::default()
+			},
+		)
+		.expect("library tree");
+		let _ = std::fs::remove_dir_all(&dir);
+
+		let root = &tree.root;
+		assert_eq!(root.status, LibraryStatus::Opened);
+		let statuses: Vec<&str> = root.children.iter().map(|node| node.status.as_str()).collect();
+		assert_eq!(statuses, ["opened", "missing", "unreadable", "repeated"]);
+
+		let a = &root.children[0];
+		let a_children: Vec<&str> = a.children.iter().map(|node| node.status.as_str()).collect();
+		assert_eq!(a_children, ["cycle", "opened"]);
+		assert!(!a.children[1].via_search_root);
+
+		let c = &root.children[3];
+		assert!(c.via_search_root);
+		assert!(c.resolved_path.as_ref().is_some_and(|path| path.ends_with("shared/c.blend")));
+
+		let problems: Vec<&str> = tree.problems().iter().map(|node| node.declared_path.as_ref()).collect();
+		assert_eq!(problems, ["//missing.blend", "//broken.blend"]);
+		assert_eq!(tree.files_opened, 4);
+	}
+
+	#[test]
+	fn depth_limit_stops_before_opening() {
+		let dir = std::env::temp_dir().join(format!("blendoc_libtree_depth_{}", std::process::id()));
+		let _ = std::fs::remove_dir_all(&dir);
+		write(&dir.join("root.blend"), &library_file(&[("LIa.blend", "//a.blend")]));
+		write(&dir.join("a.blend"), &library_file(&[("LIb.blend", "//b.blend")]));
+
+		let tree = build_library_tree(
+			&dir.join("root.blend"),
+			&LibraryTreeOptions {
+				max_depth: 0,
+				..LibraryTreeOptions::default()
+			},
+		)
+		.expect("library tree");
+		let _ = std::fs::remove_dir_all(&dir);
+
+		assert_eq!(tree.root.children.len(), 1);
+		assert_eq!(tree.root.children[0].status, LibraryStatus::Found);
+		assert!(tree.root.children[0].children.is_empty());
+		assert_eq!(tree.files_opened, 1);
+	}
+}
@@ -0,0 +1,91 @@
+use crate::blend::{
+	BlendError, ChasePolicy, DecodeOptions, Dna, FieldPath, IdIndex, PointerIndex, Result, StructValue, Value, WalkItem, WalkOptions, WalkStop, WalkStopReason,
+	chase_from_ptr, walk_ptr_chain,
+};
+
+/// Items of one `ListBase { first, last }`, visited through their `next` links.
+#[derive(Debug, Clone)]
+pub struct ListBaseWalk {
+	/// Stored `ListBase.first`.
+	pub first: u64,
+	/// Stored `ListBase.last`.
+	pub last: u64,
+	/// Visited items in list order.
+	pub items: Vec<WalkItem>,
+	/// Why the walk ended; `NullNext` for a fully walked list.
+	pub stop: Option<WalkStop>,
+}
+
+impl ListBaseWalk {
+	/// Whether the walk reached the null `next` of the final item.
+	pub fn is_complete(&self) -> bool {
+		matches!(self.stop.as_ref().map(|stop| &stop.reason), Some(WalkStopReason::NullNext))
+	}
+
+	/// Whether the last visited item is the one `ListBase.last` points at.
+	///
+	/// `false` on a complete walk means the `next` chain and `last` disagree.
+	pub fn last_matches(&self, dna: &Dna, index: &PointerIndex<'_>) -> bool {
+		match self.items.last() {
+			Some(item) => index.canonical_ptr(dna, self.last) == Some(item.canonical),
+			None => self.last == 0,
+		}
+	}
+
+	/// Canonical item pointers in list order.
+	pub fn canonical_ptrs(&self) -> Vec<u64> {
+		self.items.iter().map(|item| item.canonical).collect()
+	}
+}
+
+/// Walk an already decoded `ListBase` value from `first` along `options.next_field`.
+pub fn walk_listbase<'a>(dna: &Dna, index: &PointerIndex<'a>, ids: &IdIndex, list_base: &StructValue, options: &WalkOptions) -> Result<ListBaseWalk> {
+	let first = ptr_field(list_base, "first").unwrap_or(0);
+	let last = ptr_field(list_base, "last").unwrap_or(0);
+	let walk = walk_ptr_chain(dna, index, ids, first, options)?;
+	Ok(ListBaseWalk {
+		first,
+		last,
+		items: walk.items,
+		stop: walk.stop,
+	})
+}
+
+/// Chase `path` from `owner_ptr` to a `ListBase` field and walk its items.
+///
+/// `path` uses [`FieldPath`] syntax and may cross pointers, so
+/// `nodetree.nodes` works from a material. Paths that do not end on a struct
+/// with `first`/`last` pointers fail with [`BlendError::NotListBase`].
+pub fn iter_listbase<'a>(dna: &Dna, index: &PointerIndex<'a>, ids: &IdIndex, owner_ptr: u64, path: &str, options: &WalkOptions) -> Result<ListBaseWalk> {
+	let parsed = FieldPath::parse(path)?;
+	let decode = DecodeOptions {
+		include_padding: true,
+		..DecodeOptions::for_scene_inspect()
+	};
+	let result = chase_from_ptr(dna, index, owner_ptr, &parsed, &decode, &ChasePolicy::default())?;
+	match &result.value {
+		Value::Struct(item) if result.stop.is_none() && ptr_field(item, "first").is_some() && ptr_field(item, "last").is_some() => {
+			walk_listbase(dna, index, ids, item, options)
+		}
+		other => Err(BlendError::NotListBase {
+			path: path.to_owned(),
+			got: match other {
+				_ if result.stop.is_some() => "an incomplete chase".to_owned(),
+				Value::Struct(item) => item.type_name.to_string(),
+				Value::Ptr(_) => "a pointer".to_owned(),
+				Value::Array(_) => "an array".to_owned(),
+				_ => "a scalar".to_owned(),
+			},
+		}),
+	}
+}
+
+fn ptr_field(item: &StructValue, name: &str) -> Option<u64> {
+	match &item.fields.iter().find(|field| field.name.as_ref() == name)?.value {
+		Value::Ptr(ptr) => Some(*ptr),
+		_ => None,
+	}
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,84 @@
+mod synthetic_listbase {
+	use blendoc_testkit::builder::{BlendBuilder, HeaderKind};
+
+	use crate::blend::{BlendError, BlendFile, IdIndex, WalkOptions, iter_listbase, scan_id_blocks};
+
+	const SCENE: u64 = 0x1000;
+	const LAYERS: [u64; 3] = [0x2000, 0x2100, 0x2200];
+
+	/// `SCScene` whose `view_layers` lists three `ViewLayer`s; `last` names element `last_idx`.
+	fn list_file(last_idx: usize) -> Vec<u8> {
+		let mut builder = BlendBuilder::new(HeaderKind::Legacy8, 404, false);
+		let sdna = builder.sdna_mut();
+		sdna.add_struct("Link", &[("Link", "*next"), ("Link", "*prev")]);
+		sdna.add_type("char", 1);
+		sdna.add_type("int", 4);
+		sdna.add_type("void", 0);
+		sdna.add_struct("ID", &[("void", "*next"), ("void", "*prev"), ("void", "*lib"), ("char", "name[24]")]);
+		sdna.add_struct("ListBase", &[("void", "*first"), ("void", "*last")]);
+		let scene = sdna.add_struct("Scene", &[("ID", "id"), ("ListBase", "view_layers"), ("int", "flag")]);
+		let layer = sdna.add_struct("ViewLayer", &[("ViewLayer", "*next"), ("ViewLayer", "*prev"), ("char", "name[16]")]);
+
+		let mut payload = builder.payload();
+		payload.ptr(0).ptr(0).ptr(0).name("SCScene", 24).ptr(LAYERS[0]).ptr(LAYERS[last_idx]).i32(0);
+		let scene_payload = payload.finish();
+		builder.block(*b"SC\0\0", scene, SCENE, 1, scene_payload);
+		for (idx, ptr) in LAYERS.iter().enumerate() {
+			let mut payload = builder.payload();
+			payload
+				.ptr(LAYERS.get(idx + 1).copied().unwrap_or(0))
+				.ptr(if idx == 0 { 0 } else { LAYERS[idx - 1] })
+				.name(&format!("Layer{idx}"), 16);
+			let layer_payload = payload.finish();
+			builder.block(*b"DATA", layer, *ptr, 1, layer_payload);
+		}
+		builder.build()
+	}
+
+	#[test]
+	fn view_layers_walk_in_order() {
+		let blend = BlendFile::from_bytes(list_file(2)).expect("synthetic file parses");
+		let dna = blend.dna().expect("dna");
+		let index = blend.pointer_index().expect("pointer index");
+		let ids = IdIndex::build(scan_id_blocks(&blend, &dna).expect("ids"));
+
+		let walk = iter_listbase(&dna, &index, &ids, SCENE, "view_layers", &WalkOptions::default()).expect("list walk");
+		assert_eq!(walk.canonical_ptrs(), LAYERS);
+		assert_eq!((walk.first, walk.last), (LAYERS[0], LAYERS[2]));
+		assert!(walk.is_complete());
+		assert!(walk.last_matches(&dna, &index));
+		assert!(walk.items.iter().all(|item| item.type_name.as_ref() == "ViewLayer"));
+
+		let short = iter_listbase(
+			&dna,
+			&index,
+			&ids,
+			SCENE,
+			"view_layers",
+			&WalkOptions {
+				max_steps: 2,
+				..WalkOptions::default()
+			},
+		)
+		.expect("limited walk");
+		assert_eq!(short.items.len(), 2);
+		assert!(!short.is_complete());
+	}
+
+	#[test]
+	fn stale_last_and_non_list_paths_are_reported() {
+		let blend = BlendFile::from_bytes(list_file(1)).expect("synthetic file parses");
+		let dna = blend.dna().expect("dna");
+		let index = blend.pointer_index().expect("pointer index");
+		let ids = IdIndex::build(scan_id_blocks(&blend, &dna).expect("ids"));
+
+		let walk = iter_listbase(&dna, &index, &ids, SCENE, "view_layers", &WalkOptions::default()).expect("list walk");
+		assert!(walk.is_complete());
+		assert!(!walk.last_matches(&dna, &index));
+
+		let err = iter_listbase(&dna, &index, &ids, SCENE, "id", &WalkOptions::default()).expect_err("ID is not a list");
+		assert!(matches!(err, BlendError::NotListBase { ref got, .. } if got == "ID"), "{err}");
+		let err = iter_listbase(&dna, &index, &ids, SCENE, "flag", &WalkOptions::default()).expect_err("int is not a list");
+		assert!(matches!(err, BlendError::NotListBase { .. }), "{err}");
+	}
+}
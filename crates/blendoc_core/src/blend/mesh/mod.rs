@@ -0,0 +1,261 @@
+use std::sync::Arc;
+
+use crate::blend::{BlendError, DecodeOptions, Dna, IdIndex, PointerIndex, Result, StructValue, Value, decode_ptr_instance, decode_struct_instance};
+
+/// Element domain a `CustomData` block stores layers for.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MeshDomain {
+	/// Vertex data (`vdata` / `vert_data`).
+	Vert,
+	/// Edge data (`edata` / `edge_data`).
+	Edge,
+	/// Face-corner data (`ldata` / `corner_data`).
+	Corner,
+	/// Face data (`pdata` / `face_data`).
+	Face,
+	/// Legacy tessellated faces (`fdata` / `fdata_legacy`).
+	TessFace,
+}
+
+impl MeshDomain {
+	/// Stable machine label for JSON/text output.
+	pub fn as_str(self) -> &'static str {
+		match self {
+			Self::Vert => "vert",
+			Self::Edge => "edge",
+			Self::Corner => "corner",
+			Self::Face => "face",
+			Self::TessFace => "tessface",
+		}
+	}
+}
+
+/// `CustomData` field names per domain, current name first.
+const DOMAIN_FIELDS: [(MeshDomain, &[&str]); 5] = [
+	(MeshDomain::Vert, &["vert_data", "vdata"]),
+	(MeshDomain::Edge, &["edge_data", "edata"]),
+	(MeshDomain::Corner, &["corner_data", "ldata"]),
+	(MeshDomain::Face, &["face_data", "pdata"]),
+	(MeshDomain::TessFace, &["fdata_legacy", "fdata"]),
+];
+
+/// One `CustomDataLayer` entry.
+#[derive(Debug, Clone)]
+pub struct MeshLayer {
+	/// Domain the layer belongs to.
+	pub domain: MeshDomain,
+	/// Raw `CustomDataLayer.type` (`eCustomDataType`).
+	pub layer_type: i64,
+	/// Layer name (`UVMap`, `.position`, ...); empty for unnamed layers.
+	pub name: Arc<str>,
+	/// Stored `CustomDataLayer.data` pointer.
+	pub data_ptr: u64,
+	/// Payload bytes of the block `data` points at, when it resolves.
+	pub data_bytes: Option<u64>,
+}
+
+impl MeshLayer {
+	/// `eCustomDataType` label (`PROP_FLOAT3`, `MLOOPUV`, ...), or `type:<n>` for unknown values.
+	pub fn type_label(&self) -> String {
+		custom_data_type_label(self.layer_type).map_or_else(|| format!("type:{}", self.layer_type), str::to_owned)
+	}
+}
+
+/// Element counts and custom-data layers of one `Mesh`.
+#[derive(Debug, Clone)]
+pub struct MeshSummary {
+	/// Canonical `Mesh` pointer.
+	pub ptr: u64,
+	/// Mesh `ID.name`.
+	pub id_name: Option<Arc<str>>,
+	/// Vertex count (`verts_num` / `totvert`).
+	pub verts: Option<i64>,
+	/// Edge count (`edges_num` / `totedge`).
+	pub edges: Option<i64>,
+	/// Face-corner count (`corners_num` / `totloop`).
+	pub corners: Option<i64>,
+	/// Face count (`faces_num` / `totpoly`).
+	pub faces: Option<i64>,
+	/// Legacy tessellated face count (`totface_legacy` / `totface`).
+	pub tess_faces: Option<i64>,
+	/// Material slot count (`totcol`).
+	pub materials: Option<i64>,
+	/// Layers across all domains, in domain then list order.
+	pub layers: Vec<MeshLayer>,
+}
+
+impl MeshSummary {
+	/// Sum of resolved layer payload bytes.
+	pub fn layer_bytes(&self) -> u64 {
+		self.layers.iter().filter_map(|layer| layer.data_bytes).sum()
+	}
+}
+
+/// Blender `eCustomDataType` label for known values.
+pub fn custom_data_type_label(value: i64) -> Option<&'static str> {
+	const LABELS: [&str; 54] = [
+		"MVERT",
+		"MSTICKY",
+		"MDEFORMVERT",
+		"MEDGE",
+		"MFACE",
+		"MTFACE",
+		"MCOL",
+		"ORIGINDEX",
+		"NORMAL",
+		"FACEMAP",
+		"PROP_FLOAT",
+		"PROP_INT32",
+		"PROP_STRING",
+		"ORIGSPACE",
+		"ORCO",
+		"MTEXPOLY",
+		"MLOOPUV",
+		"PROP_BYTE_COLOR",
+		"TANGENT",
+		"MDISPS",
+		"PREVIEW_MCOL",
+		"ID_MCOL",
+		"TEXTURE_MLOOPCOL",
+		"CLOTH_ORCO",
+		"RECAST",
+		"MPOLY",
+		"MLOOP",
+		"SHAPE_KEYINDEX",
+		"SHAPEKEY",
+		"BWEIGHT",
+		"CREASE",
+		"ORIGSPACE_MLOOP",
+		"PREVIEW_MLOOPCOL",
+		"BM_ELEM_PYPTR",
+		"PAINT_MASK",
+		"GRID_PAINT_MASK",
+		"MVERT_SKIN",
+		"FREESTYLE_EDGE",
+		"FREESTYLE_FACE",
+		"MLOOPTANGENT",
+		"TESSLOOPNORMAL",
+		"CUSTOMLOOPNORMAL",
+		"SCULPT_FACE_SETS",
+		"LOCATION",
+		"RADIUS",
+		"PROP_INT8",
+		"HAIRMAPPING",
+		"PROP_COLOR",
+		"PROP_FLOAT3",
+		"PROP_FLOAT2",
+		"PROP_BOOL",
+		"HAIRLENGTH",
+		"PROP_QUATERNION",
+		"PROP_INT32_2D",
+	];
+	usize::try_from(value).ok().and_then(|idx| LABELS.get(idx)).copied()
+}
+
+/// Decode a `Mesh` and its `CustomData` layer headers.
+///
+/// Counts read Blender 4.x names first (`verts_num`, `corners_num`, ...) and
+/// fall back to the pre-4.0 `tot*` fields. Layer data is never decoded; only
+/// the size of the block each `CustomDataLayer.data` points at is reported.
+pub fn read_mesh_summary<'a>(dna: &Dna, index: &PointerIndex<'a>, ids: &IdIndex, mesh_ptr: u64) -> Result<MeshSummary> {
+	let decode = DecodeOptions::default();
+	let (ptr, mesh) = decode_ptr_instance(dna, index, mesh_ptr, &decode)?;
+	if mesh.type_name.as_ref() != "Mesh" {
+		return Err(BlendError::UnexpectedStructType {
+			ptr,
+			expected: "Mesh",
+			got: mesh.type_name.to_string(),
+		});
+	}
+
+	let mut layers = Vec::new();
+	for (domain, names) in DOMAIN_FIELDS {
+		let Some(Value::Struct(custom_data)) = names.iter().find_map(|name| field(&mesh, name)) else {
+			continue;
+		};
+		read_layers(dna, index, &decode, custom_data, domain, &mut layers)?;
+	}
+
+	Ok(MeshSummary {
+		ptr,
+		id_name: ids.get_by_ptr(ptr).map(|record| Arc::from(record.id_name.as_ref())),
+		verts: first_int(&mesh, &["verts_num", "totvert"]),
+		edges: first_int(&mesh, &["edges_num", "totedge"]),
+		corners: first_int(&mesh, &["corners_num", "totloop"]),
+		faces: first_int(&mesh, &["faces_num", "totpoly"]),
+		tess_faces: first_int(&mesh, &["totface_legacy", "totface"]),
+		materials: first_int(&mesh, &["totcol"]),
+		layers,
+	})
+}
+
+fn read_layers<'a>(
+	dna: &Dna,
+	index: &PointerIndex<'a>,
+	decode: &DecodeOptions,
+	custom_data: &StructValue,
+	domain: MeshDomain,
+	out: &mut Vec<MeshLayer>,
+) -> Result<()> {
+	let count = first_int(custom_data, &["totlayer"]).unwrap_or(0);
+	let layers_ptr = ptr_field(custom_data, "layers").unwrap_or(0);
+	if count <= 0 || layers_ptr == 0 {
+		return Ok(());
+	}
+
+	// Layers are read from the block bytes: stable-ID pointers do not support element arithmetic.
+	let Some(resolved) = index.resolve_typed(dna, layers_ptr) else {
+		return Ok(());
+	};
+	let sdna_nr = resolved.base.entry.block.head.sdna_nr;
+	let stride = resolved.struct_size.max(1);
+	let payload = resolved.base.payload();
+	for layer_idx in 0..count as usize {
+		let start = resolved.base.byte_offset + layer_idx * stride;
+		let Some(bytes) = payload.get(start..start + stride) else {
+			break;
+		};
+		let layer = decode_struct_instance(dna, sdna_nr, bytes, decode)?;
+		let data_ptr = ptr_field(&layer, "data").unwrap_or(0);
+		out.push(MeshLayer {
+			domain,
+			layer_type: first_int(&layer, &["type"]).unwrap_or(-1),
+			name: string_field(&layer, "name").unwrap_or_else(|| Arc::from("")),
+			data_ptr,
+			data_bytes: (data_ptr != 0)
+				.then(|| index.resolve(data_ptr))
+				.flatten()
+				.map(|hit| hit.entry.block.head.len.saturating_sub(hit.byte_offset as u64)),
+		});
+	}
+	Ok(())
+}
+
+fn field<'v>(item: &'v StructValue, name: &str) -> Option<&'v Value> {
+	item.fields.iter().find(|field| field.name.as_ref() == name).map(|field| &field.value)
+}
+
+fn ptr_field(item: &StructValue, name: &str) -> Option<u64> {
+	match field(item, name)? {
+		Value::Ptr(ptr) => Some(*ptr),
+		_ => None,
+	}
+}
+
+fn string_field(item: &StructValue, name: &str) -> Option<Arc<str>> {
+	match field(item, name)? {
+		Value::String(value) => Some(Arc::from(value.as_ref())),
+		_ => None,
+	}
+}
+
+fn first_int(item: &StructValue, names: &[&str]) -> Option<i64> {
+	names.iter().find_map(|name| match field(item, name)? {
+		Value::I64(value) => Some(*value),
+		Value::U64(value) => i64::try_from(*value).ok(),
+		_ => None,
+	})
+}
+
+#[cfg(test)]
+mod tests;
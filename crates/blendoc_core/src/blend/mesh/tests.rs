@@ -0,0 +1,98 @@
+mod synthetic_mesh {
+	use blendoc_testkit::builder::{BlendBuilder, HeaderKind};
+
+	use crate::blend::{BlendError, BlendFile, IdIndex, MeshDomain, read_mesh_summary, scan_id_blocks};
+
+	const MESH: u64 = 0x1000;
+	const VERT_LAYERS: u64 = 0x2000;
+	const POSITIONS: u64 = 0x3000;
+	const WEIGHTS: u64 = 0x3100;
+	const OBJECT: u64 = 0x4000;
+
+	/// `MECube` with 8 verts: a `.position` `PROP_FLOAT3` layer (96 bytes)
+	/// and a `weight` `PROP_FLOAT` layer whose data pointer dangles.
+	fn mesh_file() -> Vec<u8> {
+		let mut builder = BlendBuilder::new(HeaderKind::Legacy8, 402, false);
+		let sdna = builder.sdna_mut();
+		sdna.add_type("char", 1);
+		sdna.add_type("int", 4);
+		sdna.add_type("float", 4);
+		sdna.add_type("void", 0);
+		sdna.add_struct("Link", &[("Link", "*next"), ("Link", "*prev")]);
+		sdna.add_struct("ID", &[("void", "*next"), ("void", "*prev"), ("void", "*lib"), ("char", "name[24]")]);
+		sdna.add_struct("CustomData", &[("CustomDataLayer", "*layers"), ("int", "totlayer"), ("int", "maxlayer")]);
+		let layer = sdna.add_struct("CustomDataLayer", &[("int", "type"), ("int", "flag"), ("char", "name[16]"), ("void", "*data")]);
+		let mesh = sdna.add_struct(
+			"Mesh",
+			&[
+				("ID", "id"),
+				("CustomData", "vert_data"),
+				("CustomData", "corner_data"),
+				("int", "verts_num"),
+				("int", "corners_num"),
+			],
+		);
+		let object = sdna.add_struct("Object", &[("ID", "id")]);
+
+		let id = |name: &str| {
+			let mut payload = builder.payload();
+			payload.ptr(0).ptr(0).ptr(0).name(name, 24);
+			payload.finish()
+		};
+		let mut payload = builder.payload();
+		payload.raw(&id("MECube")).ptr(VERT_LAYERS).i32(2).i32(2).ptr(0).i32(0).i32(0).i32(8).i32(24);
+		let mesh_payload = payload.finish();
+
+		let mut payload = builder.payload();
+		payload.i32(48).i32(0).name(".position", 16).ptr(POSITIONS);
+		payload.i32(10).i32(0).name("weight", 16).ptr(0x9999);
+		let layers_payload = payload.finish();
+
+		let mut payload = builder.payload();
+		for idx in 0..24 {
+			payload.f32(idx as f32);
+		}
+		let positions_payload = payload.finish();
+		let mut payload = builder.payload();
+		payload.raw(&[0; 4]);
+		let weights_payload = payload.finish();
+		let object_payload = id("OBCube");
+
+		builder
+			.block(*b"ME\0\0", mesh, MESH, 1, mesh_payload)
+			.block(*b"DATA", layer, VERT_LAYERS, 2, layers_payload)
+			.block(*b"DATA", 0, POSITIONS, 1, positions_payload)
+			.block(*b"DATA", 0, WEIGHTS, 1, weights_payload)
+			.block(*b"OB\0\0", object, OBJECT, 1, object_payload);
+		builder.build()
+	}
+
+	#[test]
+	fn mesh_summary_reports_counts_and_layers() {
+		let blend = BlendFile::from_bytes(mesh_file()).expect("synthetic file opens");
+		let dna = blend.dna().expect("dna parses");
+		let index = blend.pointer_index().expect("pointer index builds");
+		let ids = IdIndex::build(scan_id_blocks(&blend, &dna).expect("id scan succeeds"));
+
+		let summary = read_mesh_summary(&dna, &index, &ids, MESH).expect("mesh decodes");
+		assert_eq!(summary.id_name.as_deref(), Some("MECube"));
+		assert_eq!((summary.verts, summary.corners, summary.edges), (Some(8), Some(24), None));
+
+		let layers: Vec<_> = summary
+			.layers
+			.iter()
+			.map(|layer| (layer.domain, layer.type_label(), layer.name.as_ref(), layer.data_bytes))
+			.collect();
+		assert_eq!(
+			layers,
+			[
+				(MeshDomain::Vert, "PROP_FLOAT3".to_owned(), ".position", Some(96)),
+				(MeshDomain::Vert, "PROP_FLOAT".to_owned(), "weight", None),
+			]
+		);
+		assert_eq!(summary.layer_bytes(), 96);
+
+		let err = read_mesh_summary(&dna, &index, &ids, OBJECT).expect_err("object is not a mesh");
+		assert!(matches!(err, BlendError::UnexpectedStructType { expected: "Mesh", .. }));
+	}
+}
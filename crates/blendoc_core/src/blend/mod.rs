@@ -1,66 +1,231 @@
+mod alignment;
+mod anim;
 mod bhead;
+mod bloat;
 mod block;
 mod bytes;
+mod cancel;
+mod changes;
 mod chase;
 mod chase_path;
+mod closure;
+pub mod codes;
 mod compression;
+mod dangling;
 mod decl;
 mod decode;
+mod decode_cache;
+mod diff;
 mod dna;
+mod dna_diff;
+mod dna_verify;
 mod error;
+mod explain;
+mod expr;
 mod file;
+mod file_meta;
+mod footprint;
+mod glob;
 mod graph;
+mod graphml;
 mod header;
+mod hierarchy;
 mod id;
 mod idgraph;
+mod idprop;
+mod image;
+mod infer;
+#[cfg(feature = "json")]
+pub mod json;
+mod layout;
 mod liblink;
+mod libtree;
+mod listbase;
+mod mesh;
+mod name_index;
+mod nodetree;
+mod orphans;
+mod packed;
+mod parallel;
 mod path;
+mod plugin;
 mod pointer;
+mod preview;
+#[cfg(feature = "native")]
+mod probe;
+mod progress;
+mod project;
+mod prune;
+mod ref_cache;
 mod refs;
 mod route;
+mod search;
+mod session;
+mod size_stats;
+mod strings;
+mod suggest;
+mod text_form;
+mod thumbnail;
+mod trace;
+mod validate;
 mod value;
 mod walk;
+mod warning;
+mod writer;
 mod xref;
 
-/// Parsed block header record.
-pub use bhead::BHead;
+/// Inter-block padding and alignment detection.
+pub use alignment::{BlockAlignment, scan_block_alignment};
+/// Action, F-Curve, and driver inspection.
+pub use anim::{ActionInfo, AnimDataInfo, AnimOptions, DriverInfo, DriverTarget, DriverVariable, FCurveInfo, read_action, read_anim_data};
+/// Parsed block header record and layout negotiation.
+pub use bhead::{BHead, BHeadCapabilities, BHeadFieldOrder, BHeadLayout};
+/// Ranked "why is this file big" findings.
+pub use bloat::{BloatFinding, BloatKind, BloatOptions, BloatReport, analyze_bloat};
 /// Block container and iterator types.
-pub use block::{Block, BlockIter};
+pub use block::{Block, BlockIter, MAX_BLOCK_ALIGNMENT, PayloadChunks};
+/// Cooperative cancellation for graph, route, and reference traversals.
+pub use cancel::CancelToken;
+/// Per-ID content snapshots and change subscriptions across reloads.
+pub use changes::{IdChangeEvent, IdChangeFeed, IdChangeFilter, IdSnapshot, IdSnapshotEntry, SubscriptionId, diff_snapshots};
 /// One-step pointer chase helpers.
 pub use chase::{ChaseMeta, chase_ptr_to_struct, chase_scene_camera};
 /// Generic path-based pointer chase API.
-pub use chase_path::{ChasePolicy, ChaseResult, ChaseStop, ChaseStopReason, StopMode, chase_from_block_code, chase_from_ptr};
-/// Compression detection result.
-pub use compression::Compression;
+pub use chase_path::{
+	ChaseMatch, ChasePolicy, ChaseResult, ChaseStop, ChaseStopReason, StopMode, chase_all_from_block, chase_all_from_ptr, chase_from_block,
+	chase_from_block_code, chase_from_ptr,
+};
+/// Transitive dependency and dependent closures over the ID graph.
+pub use closure::{ClosureDirection, ClosureEntry, ClosureResult, dependency_closure, dependents_closure};
+/// Compression detection, decoding, and re-encoding.
+pub use compression::{CodecSupport, Compression, codec_support, decode_bytes, encode_bytes};
+/// Whole-file pointer integrity classification.
+pub use dangling::{BlockPointerStats, DanglingReport, FieldPointerStats, PointerClass, PointerCounts, scan_dangling_pointers};
 /// SDNA-driven decoding entry points and options.
 pub use decode::{DecodeOptions, decode_block_instances, decode_ptr_instance, decode_struct_instance};
+/// Shared LRU memo of decoded structs keyed by canonical pointer.
+pub use decode_cache::DecodeCache;
+/// ID-level comparison between two files.
+pub use diff::{FieldDiff, IdChange, IdDiffEntry, IdDiffOptions, IdDiffResult, PtrTarget, PtrTargets, diff_id_blocks, diff_struct_fields};
 /// SDNA schema representation.
-pub use dna::{Dna, DnaField, DnaStruct};
+pub use dna::{Dna, DnaField, DnaStruct, FieldLayout, PaddingHole, TlenFixup};
+/// SDNA schema comparison between two files.
+pub use dna_diff::{DnaDiffResult, DnaFieldChange, DnaFieldDiff, DnaFieldSide, DnaStructChange, DnaStructDiff, diff_dna};
+/// SDNA struct size and alignment consistency check.
+pub use dna_verify::{DnaLayoutReport, MisalignedField, StructSizeIssue, verify_dna_layout};
 /// Error and result aliases.
 pub use error::{BlendError, Result};
+/// Side-by-side decode under several option sets.
+pub use explain::{DecodeExplanation, DecodeVariant, FieldDivergence, VariantOutcome, explain_decode_ptr, explain_decode_struct, standard_decode_variants};
+/// Arithmetic/string expressions over decoded struct fields.
+pub use expr::{Expr, ExprColumn, is_truthy};
 /// File abstraction and block statistics.
 pub use file::{BlendFile, BlockStats};
+/// Save-time build, version, and scene-frame metadata plus UTC date helpers.
+pub use file_meta::{FileMeta, SceneFrames, format_unix_time, parse_unix_time, read_file_meta};
+/// Per-ID owned data block footprint.
+pub use footprint::{IdFootprint, scan_id_footprints};
+/// Glob matching for ID-name selectors.
+pub use glob::{glob_match, is_glob};
 /// Graph extraction types and entry points.
-pub use graph::{GraphEdge, GraphNode, GraphOptions, GraphResult, GraphTruncation, build_graph_from_ptr};
+pub use graph::{GraphEdge, GraphFilter, GraphNode, GraphOptions, GraphResult, GraphTruncation, build_graph_from_ptr};
+/// GraphML export for pointer and ID graphs.
+pub use graphml::{ExportEdge, ExportNode, GraphExport};
 /// File header representation and byte-order marker.
 pub use header::{BlendHeader, Endianness};
+/// Scene collection/object hierarchy.
+pub use hierarchy::{HierarchyCollection, HierarchyObject, HierarchyOptions, SceneHierarchy, object_type_label, scene_hierarchies};
 /// ID-root block scan output and helpers.
-pub use id::{IdIndex, IdRecord, scan_id_blocks};
-/// Whole-file ID graph extraction types and entry points.
-pub use idgraph::{IdGraphEdge, IdGraphNode, IdGraphOptions, IdGraphResult, IdGraphTruncation, build_id_graph};
+pub use id::{
+	IdDelta, IdHandle, IdIndex, IdIndexUpdate, IdRecord, IdScanOptions, MAX_ID_SUGGESTIONS, find_id_block, id_delta_from_blocks, scan_id_blocks,
+	scan_id_blocks_with,
+};
+/// Whole-file ID graph extraction, persistence, and entry points.
+pub use idgraph::{
+	IdGraphCycle, IdGraphEdge, IdGraphKey, IdGraphNode, IdGraphOptions, IdGraphResult, IdGraphSource, IdGraphTruncation, build_id_graph, load_or_build_id_graph,
+};
+/// Custom property (`IDProperty`) tree decoding.
+pub use idprop::{IdPropOptions, IdPropValue, IdPropertiesInfo, IdProperty, read_id_properties, read_idproperty};
+/// `Image` ID path, source, colorspace, and packed-data report.
+pub use image::{ImageRecord, ImageSource, scan_images};
+/// Heuristic element-type inference for raw data blocks.
+pub use infer::{BlockInference, InferCandidate, InferOptions, InferOwner, InferReport, InferSource, infer_raw_blocks};
+/// Flattened per-field byte layout of SDNA structs.
+pub use layout::{LayoutMap, LayoutSpan, layout_map};
 /// Linked-library provenance records and scan helpers.
-pub use liblink::{IdLinkProvenance, LibraryRecord, LinkConfidence, LinkSignal, scan_id_link_provenance, scan_library_records};
+pub use liblink::{
+	IdLinkProvenance, LibraryNamespaces, LibraryRecord, LinkConfidence, LinkSignal, scan_id_link_provenance, scan_library_namespaces, scan_library_records,
+};
+/// Recursive library dependency tree across linked blend files.
+pub use libtree::{LibraryNode, LibraryStatus, LibraryTree, LibraryTreeOptions, build_library_tree};
+/// `ListBase` traversal from an owner pointer and field path.
+pub use listbase::{ListBaseWalk, iter_listbase, walk_listbase};
+/// Mesh element counts and custom-data layer summaries.
+pub use mesh::{MeshDomain, MeshLayer, MeshSummary, custom_data_type_label, read_mesh_summary};
+/// Budgeted prefix/trigram lookup over ID names and stored paths.
+pub use name_index::{NameEntry, NameIndex, NameIndexOptions, NameKind};
+/// Node-tree (`bNodeTree`/`bNode`/`bNodeSocket`/`bNodeLink`) inspection.
+pub use nodetree::{NodeLink, NodeSocket, NodeTree, NodeTreeOptions, TreeNode, read_node_tree};
+/// IDs without inbound references from other IDs.
+pub use orphans::{OrphanId, OrphanOptions, OrphanReport, find_orphan_ids, find_orphan_ids_in_graph};
+/// Packed file discovery and payload access.
+pub use packed::{PackedFileRecord, packed_file_bytes, scan_packed_files};
+/// Bounded worker-pool block decoding with deterministic ordering.
+pub use parallel::{BlockDecode, decode_blocks_parallel, effective_threads, map_blocks_parallel};
 /// Field path parser types.
 pub use path::{FieldPath, PathStep};
+/// Per-struct-type decode plugins and TOML descriptors.
+pub use plugin::{DecodePlugins, StructDecoder, StructDescriptor};
+/// Pointer index lookup counters.
+#[cfg(feature = "probe-stats")]
+pub use pointer::PointerIndexStats;
 /// Pointer index and resolution types.
 pub use pointer::{PointerIndex, PointerStorage, PtrEntry, ResolvedPtr, TypedResolvedPtr};
+/// Per-ID `PreviewImage` pixel extraction.
+pub use preview::{IdPreview, PreviewRect, decode_preview_rects, scan_id_previews};
+/// Block-header-only open probe.
+#[cfg(feature = "native")]
+pub use probe::{OpenProbe, probe_file};
+/// Progress callbacks for long-running block and ID scans.
+pub use progress::{Progress, ProgressSink, ProgressStage};
+/// External file and library references aggregated across a project directory.
+pub use project::{
+	ExternalRef, ExternalRefKind, ProjectAudit, ProjectAuditOptions, ProjectRefUser, ProjectTarget, audit_project, blend_files_below, scan_external_refs,
+};
+/// Prune planning and block removal for a set of IDs.
+pub use prune::{BrokenRef, PrunePlan, PrunedBlock, plan_prune, write_pruned};
+/// Shared memo of per-owner pointer-reference scans.
+pub use ref_cache::RefCache;
 /// Pointer-reference scan output and options.
 pub use refs::{RefRecord, RefScanOptions, RefTarget, scan_refs_from_ptr};
 /// Route-finding types and entry points.
-pub use route::{RouteEdge, RouteOptions, RouteResult, RouteTruncation, find_route_between_ptrs};
+pub use route::{
+	RouteEdge, RouteOptions, RouteResult, RouteTruncation, RoutesResult, find_route_between_ptrs, find_route_to_code, find_routes_between_ptrs,
+	find_routes_to_code,
+};
+/// Whole-file decoded value search with field predicates.
+pub use search::{FieldPredicate, SearchField, SearchHit, SearchOp, SearchOptions, SearchResult, search_block, search_blocks};
+/// Bounded cache of parsed files for long-lived processes.
+pub use session::{CachedFile, FileCache, FileCacheStats};
+/// Per-SDNA-type and per-ID-type payload size accounting.
+pub use size_stats::{IdTypeSizeStats, RawCodeSizeStats, SizeStats, TypeSizeStats, scan_size_stats};
+/// Raw string occurrence search across block payloads.
+pub use strings::{StringHit, find_string_in_block, find_string_occurrences};
+/// Close-name suggestions and known Blender-version renames for missing schema names.
+pub use suggest::{KNOWN_RENAMES, KnownRename, NameHint, RenameDirection, enrich_error, field_name_hint, struct_name_hint, version_label};
+/// Canonical text form of a file's logical content, and diffs between forms.
+pub use text_form::{TEXT_FORM_HEADER, TextFormChange, TextFormOptions, diff_text_forms, text_form};
+/// Embedded `TEST` block thumbnail decoding.
+pub use thumbnail::{Thumbnail, decode_thumbnail, extract_thumbnail};
+/// Structural integrity checks with severity-tagged findings.
+pub use validate::{Severity, ValidateOptions, ValidationCheck, ValidationIssue, ValidationReport, validate_file};
 /// Decoded runtime value types.
 pub use value::{FieldValue, StructValue, Value};
 /// Linked-list walk types and entry points.
 pub use walk::{WalkItem, WalkOptions, WalkResult, WalkStop, WalkStopReason, walk_ptr_chain};
+/// Non-fatal data-quality warnings and collector.
+pub use warning::{Warning, WarningCollector, WarningKind};
+/// Block-by-block file re-emission and round-trip verification.
+pub use writer::{verify_round_trip, write_blend};
 /// Inbound reference query types and entry points.
-pub use xref::{InboundRef, XrefOptions, find_inbound_refs_to_ptr};
+pub use xref::{InboundRef, InboundRefLocation, XrefOptions, find_inbound_refs_to_ptr};
@@ -0,0 +1,256 @@
+use std::collections::{HashMap, HashSet};
+use std::sync::Arc;
+
+use crate::blend::{BlendFile, DecodeOptions, Dna, IdRecord, Result, StructValue, Value, codes, decode_ptr_instance, scan_id_blocks};
+
+/// Which string a [`NameEntry`] indexes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum NameKind {
+	/// `ID.name` of any ID root.
+	IdName,
+	/// `Library` file path of a linked library.
+	LibraryPath,
+	/// External file path stored on an ID root (`Image.filepath`, `Sound.filepath`, ...).
+	FilePath,
+}
+
+impl NameKind {
+	/// Stable machine label for JSON/text output.
+	pub fn as_str(self) -> &'static str {
+		match self {
+			Self::IdName => "id",
+			Self::LibraryPath => "library",
+			Self::FilePath => "file",
+		}
+	}
+}
+
+/// One indexed string and the ID it belongs to.
+#[derive(Debug, Clone)]
+pub struct NameEntry {
+	/// What `text` is.
+	pub kind: NameKind,
+	/// Indexed string as stored in the file.
+	pub text: Arc<str>,
+	/// Owning ID block pointer.
+	pub id_ptr: u64,
+	/// Owning ID block code.
+	pub code: [u8; 4],
+	/// Owning `ID.name`.
+	pub id_name: Arc<str>,
+}
+
+/// Size limits for [`NameIndex::build`].
+#[derive(Debug, Clone)]
+pub struct NameIndexOptions {
+	/// Maximum indexed strings.
+	pub max_entries: usize,
+	/// Approximate heap budget for entries, prefix keys, and trigram postings.
+	pub max_bytes: usize,
+}
+
+impl Default for NameIndexOptions {
+	fn default() -> Self {
+		Self {
+			max_entries: 1_000_000,
+			max_bytes: 64 << 20,
+		}
+	}
+}
+
+/// In-memory lookup over ID names, library paths, and external file paths.
+///
+/// Built once per load so interactive front ends can autocomplete and search
+/// without rescanning blocks. Prefix lookups use a sorted key list (ID names
+/// are also keyed without their two-letter code, paths also by file name);
+/// substring lookups intersect trigram postings. Matching is ASCII
+/// case-insensitive.
+#[derive(Debug, Clone, Default)]
+pub struct NameIndex {
+	entries: Vec<NameEntry>,
+	lowered: Vec<Box<str>>,
+	prefixes: Vec<(Box<str>, u32)>,
+	trigrams: HashMap<[u8; 3], Vec<u32>>,
+	bytes: usize,
+	truncated: bool,
+}
+
+impl NameIndex {
+	/// Index every ID name plus the library and external file paths on ID roots.
+	pub fn build(file: &BlendFile, dna: &Dna, options: &NameIndexOptions) -> Result<Self> {
+		let index = file.pointer_index()?;
+		let decode = DecodeOptions {
+			max_depth: 2,
+			..DecodeOptions::default()
+		};
+
+		let mut entries = Vec::new();
+		for record in scan_id_blocks(file, dna)? {
+			entries.push(entry(NameKind::IdName, record.id_name.as_ref(), &record));
+			let Ok((_, root)) = decode_ptr_instance(dna, &index, record.old_ptr, &decode) else {
+				continue;
+			};
+			if record.code == codes::ID_LI {
+				// `Library.name` held the path before `filepath` was introduced.
+				if let Some(path) = string_field(&root, "filepath").or_else(|| string_field(&root, "name")) {
+					entries.push(entry(NameKind::LibraryPath, path, &record));
+				}
+			} else if let Some(path) = string_field(&root, "filepath") {
+				entries.push(entry(NameKind::FilePath, path, &record));
+			}
+		}
+		Ok(Self::from_entries(entries, options))
+	}
+
+	/// Index prepared entries, stopping once a limit in `options` is reached.
+	pub fn from_entries(entries: impl IntoIterator<Item = NameEntry>, options: &NameIndexOptions) -> Self {
+		let mut out = Self::default();
+		for entry in entries {
+			if out.entries.len() >= options.max_entries || !out.push(entry, options.max_bytes) {
+				out.truncated = true;
+				break;
+			}
+		}
+		out.prefixes.sort();
+		out
+	}
+
+	/// Indexed entries, in build order.
+	pub fn entries(&self) -> &[NameEntry] {
+		&self.entries
+	}
+
+	/// Number of indexed strings.
+	pub fn len(&self) -> usize {
+		self.entries.len()
+	}
+
+	/// Whether nothing was indexed.
+	pub fn is_empty(&self) -> bool {
+		self.entries.is_empty()
+	}
+
+	/// Whether a limit stopped indexing before every string was added.
+	pub fn truncated(&self) -> bool {
+		self.truncated
+	}
+
+	/// Approximate heap bytes charged against [`NameIndexOptions::max_bytes`].
+	pub fn approx_bytes(&self) -> usize {
+		self.bytes
+	}
+
+	/// Entries with a key starting with `prefix`, in key order.
+	///
+	/// `"cu"` matches `OBCube` through its code-less key and
+	/// `//textures/wood.png` through its file-name key.
+	pub fn complete(&self, prefix: &str, kind: Option<NameKind>, limit: usize) -> Vec<&NameEntry> {
+		let prefix = prefix.to_ascii_lowercase();
+		let start = self.prefixes.partition_point(|(key, _)| key.as_ref() < prefix.as_str());
+		let mut seen = HashSet::new();
+		self.prefixes[start..]
+			.iter()
+			.take_while(|(key, _)| key.starts_with(&prefix))
+			.map(|(_, slot)| *slot)
+			.filter(|slot| seen.insert(*slot))
+			.map(|slot| &self.entries[slot as usize])
+			.filter(|entry| kind.is_none_or(|kind| kind == entry.kind))
+			.take(limit)
+			.collect()
+	}
+
+	/// Entries containing `needle`, in build order.
+	pub fn find(&self, needle: &str, kind: Option<NameKind>, limit: usize) -> Vec<&NameEntry> {
+		let needle = needle.to_ascii_lowercase();
+		let matches = |slot: &u32| {
+			let slot = *slot as usize;
+			self.lowered[slot].contains(&needle) && kind.is_none_or(|kind| kind == self.entries[slot].kind)
+		};
+
+		let candidates: Vec<u32> = if needle.len() < 3 {
+			(0..self.entries.len() as u32).collect()
+		} else {
+			let mut lists: Vec<&Vec<u32>> = Vec::new();
+			for gram in trigrams(&needle) {
+				match self.trigrams.get(&gram) {
+					Some(list) => lists.push(list),
+					None => return Vec::new(),
+				}
+			}
+			lists.sort_by_key(|list| list.len());
+			let mut out = lists[0].clone();
+			for list in &lists[1..] {
+				out.retain(|slot| list.binary_search(slot).is_ok());
+			}
+			out
+		};
+
+		candidates
+			.iter()
+			.filter(|slot| matches(slot))
+			.take(limit)
+			.map(|slot| &self.entries[*slot as usize])
+			.collect()
+	}
+
+	/// Add one entry unless it would exceed `max_bytes`.
+	fn push(&mut self, entry: NameEntry, max_bytes: usize) -> bool {
+		let slot = self.entries.len() as u32;
+		let lowered = entry.text.to_ascii_lowercase();
+		let mut keys = vec![lowered.clone()];
+		match entry.kind {
+			NameKind::IdName => keys.extend(lowered.get(2..).filter(|rest| !rest.is_empty()).map(str::to_owned)),
+			NameKind::LibraryPath | NameKind::FilePath => keys.extend(
+				lowered
+					.rsplit(['/', '\\'])
+					.next()
+					.filter(|name| !name.is_empty() && *name != lowered)
+					.map(str::to_owned),
+			),
+		}
+		let grams: HashSet<[u8; 3]> = trigrams(&lowered).collect();
+
+		let cost = size_of::<NameEntry>()
+			+ entry.text.len()
+			+ lowered.len()
+			+ keys.iter().map(|key| key.len() + size_of::<(Box<str>, u32)>()).sum::<usize>()
+			+ grams.len() * size_of::<u32>();
+		if self.bytes.saturating_add(cost) > max_bytes {
+			return false;
+		}
+		self.bytes += cost;
+
+		for gram in grams {
+			// Slots are pushed in increasing order, so postings stay sorted for `binary_search`.
+			self.trigrams.entry(gram).or_default().push(slot);
+		}
+		self.prefixes.extend(keys.into_iter().map(|key| (key.into_boxed_str(), slot)));
+		self.lowered.push(lowered.into_boxed_str());
+		self.entries.push(entry);
+		true
+	}
+}
+
+fn entry(kind: NameKind, text: &str, record: &IdRecord) -> NameEntry {
+	NameEntry {
+		kind,
+		text: Arc::from(text),
+		id_ptr: record.old_ptr,
+		code: record.code,
+		id_name: Arc::from(record.id_name.as_ref()),
+	}
+}
+
+fn trigrams(text: &str) -> impl Iterator<Item = [u8; 3]> + '_ {
+	text.as_bytes().windows(3).map(|window| [window[0], window[1], window[2]])
+}
+
+fn string_field<'v>(item: &'v StructValue, name: &str) -> Option<&'v str> {
+	match &item.fields.iter().find(|field| field.name.as_ref() == name)?.value {
+		Value::String(value) if !value.is_empty() => Some(value),
+		_ => None,
+	}
+}
+
+#[cfg(test)]
+mod tests;
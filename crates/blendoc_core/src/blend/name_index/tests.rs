@@ -0,0 +1,94 @@
+mod synthetic_names {
+	use blendoc_testkit::builder::{BlendBuilder, HeaderKind};
+
+	use crate::blend::{BlendFile, NameEntry, NameIndex, NameIndexOptions, NameKind};
+
+	/// `LIprops.blend` linking `//lib/props.blend`, `IMWood` loading
+	/// `//textures/Wood_Diffuse.png`, and objects `OBCube` and `OBCubeLight`.
+	fn names_file() -> Vec<u8> {
+		let mut builder = BlendBuilder::new(HeaderKind::Legacy8, 404, false);
+		let sdna = builder.sdna_mut();
+		sdna.add_struct("Link", &[("Link", "*next"), ("Link", "*prev")]);
+		sdna.add_type("char", 1);
+		sdna.add_type("void", 0);
+		sdna.add_struct("ID", &[("void", "*next"), ("void", "*prev"), ("void", "*lib"), ("char", "name[24]")]);
+		let library = sdna.add_struct("Library", &[("ID", "id"), ("char", "filepath[64]")]);
+		let image = sdna.add_struct("Image", &[("ID", "id"), ("char", "filepath[64]")]);
+		let object = sdna.add_struct("Object", &[("ID", "id")]);
+
+		let root = |builder: &BlendBuilder, name: &str, path: Option<&str>| {
+			let mut payload = builder.payload();
+			payload.ptr(0).ptr(0).ptr(0).name(name, 24);
+			if let Some(path) = path {
+				payload.name(path, 64);
+			}
+			payload.finish()
+		};
+		let library_payload = root(&builder, "LIprops.blend", Some("//lib/props.blend"));
+		let image_payload = root(&builder, "IMWood", Some("//textures/Wood_Diffuse.png"));
+		let cube_payload = root(&builder, "OBCube", None);
+		let light_payload = root(&builder, "OBCubeLight", None);
+		builder
+			.block(*b"LI\0\0", library, 0x1000, 1, library_payload)
+			.block(*b"IM\0\0", image, 0x2000, 1, image_payload)
+			.block(*b"OB\0\0", object, 0x3000, 1, cube_payload)
+			.block(*b"OB\0\0", object, 0x4000, 1, light_payload);
+		builder.build()
+	}
+
+	fn texts(entries: &[&NameEntry]) -> Vec<String> {
+		entries.iter().map(|entry| entry.text.to_string()).collect()
+	}
+
+	#[test]
+	fn build_indexes_id_names_and_paths() {
+		let blend = BlendFile::from_bytes(names_file()).expect("synthetic file parses");
+		let dna = blend.dna().expect("dna");
+		let index = NameIndex::build(&blend, &dna, &NameIndexOptions::default()).expect("index");
+
+		assert_eq!(index.len(), 6);
+		assert!(!index.truncated());
+		let library = index.find("props", Some(NameKind::LibraryPath), 10);
+		assert_eq!(texts(&library), ["//lib/props.blend"]);
+		assert_eq!(library[0].id_name.as_ref(), "LIprops.blend");
+		let image = index.complete("wood_", None, 10);
+		assert_eq!(texts(&image), ["//textures/Wood_Diffuse.png"]);
+		assert_eq!((image[0].kind, image[0].id_ptr), (NameKind::FilePath, 0x2000));
+	}
+
+	#[test]
+	fn prefix_and_substring_queries_are_case_insensitive() {
+		let blend = BlendFile::from_bytes(names_file()).expect("synthetic file parses");
+		let dna = blend.dna().expect("dna");
+		let index = NameIndex::build(&blend, &dna, &NameIndexOptions::default()).expect("index");
+
+		assert_eq!(texts(&index.complete("cu", Some(NameKind::IdName), 10)), ["OBCube", "OBCubeLight"]);
+		assert_eq!(texts(&index.complete("OBCUBEL", None, 10)), ["OBCubeLight"]);
+		assert_eq!(index.complete("cu", None, 1).len(), 1);
+		assert_eq!(texts(&index.find("BELIG", None, 10)), ["OBCubeLight"]);
+		assert_eq!(texts(&index.find("ob", Some(NameKind::IdName), 10)), ["OBCube", "OBCubeLight"]);
+		assert!(index.find("missing", None, 10).is_empty());
+	}
+
+	#[test]
+	fn byte_budget_truncates_index() {
+		let entry = |text: &str| NameEntry {
+			kind: NameKind::IdName,
+			text: text.into(),
+			id_ptr: 0,
+			code: *b"OB\0\0",
+			id_name: text.into(),
+		};
+		let entries = (0..100).map(|idx| entry(&format!("OBObject{idx:03}")));
+		let options = NameIndexOptions {
+			max_bytes: 2048,
+			..NameIndexOptions::default()
+		};
+		let index = NameIndex::from_entries(entries, &options);
+
+		assert!(index.truncated());
+		assert!(index.len() < 100);
+		assert!(index.approx_bytes() <= 2048);
+		assert_eq!(index.complete("object0", None, usize::MAX).len(), index.len());
+	}
+}
@@ -0,0 +1,240 @@
+use std::sync::Arc;
+
+use crate::blend::{BlendError, DecodeOptions, Dna, IdIndex, PointerIndex, Result, StructValue, Value, WalkOptions, decode_ptr_instance, walk_listbase};
+
+/// Node-tree traversal budgets.
+#[derive(Debug, Clone)]
+pub struct NodeTreeOptions {
+	/// Maximum nodes read from `bNodeTree.nodes`.
+	pub max_nodes: usize,
+	/// Maximum links read from `bNodeTree.links`.
+	pub max_links: usize,
+	/// Maximum sockets read per `bNode.inputs`/`bNode.outputs` list.
+	pub max_sockets: usize,
+}
+
+impl Default for NodeTreeOptions {
+	fn default() -> Self {
+		Self {
+			max_nodes: 1024,
+			max_links: 4096,
+			max_sockets: 256,
+		}
+	}
+}
+
+/// One `bNodeSocket` from a node's input or output list.
+#[derive(Debug, Clone)]
+pub struct NodeSocket {
+	/// Canonical socket pointer.
+	pub ptr: u64,
+	/// UI name (`bNodeSocket.name`).
+	pub name: Arc<str>,
+	/// Stable identifier (`bNodeSocket.identifier`), when stored.
+	pub identifier: Option<Arc<str>>,
+	/// Socket type idname (`NodeSocketColor`, ...), when stored.
+	pub idname: Option<Arc<str>>,
+}
+
+/// One `bNode` from `bNodeTree.nodes`.
+#[derive(Debug, Clone)]
+pub struct TreeNode {
+	/// Canonical node pointer.
+	pub ptr: u64,
+	/// Node name (`bNode.name`).
+	pub name: Arc<str>,
+	/// Node type idname (`ShaderNodeBsdfPrincipled`, ...), when stored.
+	pub idname: Option<Arc<str>>,
+	/// Legacy integer node type (`bNode.type` / `type_legacy`), when stored.
+	pub legacy_type: Option<i64>,
+	/// Input sockets in list order.
+	pub inputs: Vec<NodeSocket>,
+	/// Output sockets in list order.
+	pub outputs: Vec<NodeSocket>,
+}
+
+impl TreeNode {
+	/// Type label: idname when present, else `type:<N>`.
+	pub fn type_label(&self) -> String {
+		match (&self.idname, self.legacy_type) {
+			(Some(idname), _) => idname.to_string(),
+			(None, Some(value)) => format!("type:{value}"),
+			(None, None) => "<unknown>".to_owned(),
+		}
+	}
+}
+
+/// One `bNodeLink` from `bNodeTree.links`, with canonicalized endpoints.
+#[derive(Debug, Clone)]
+pub struct NodeLink {
+	/// Canonical link pointer.
+	pub ptr: u64,
+	/// Source node pointer.
+	pub from_node: u64,
+	/// Source output socket pointer.
+	pub from_socket: u64,
+	/// Target node pointer.
+	pub to_node: u64,
+	/// Target input socket pointer.
+	pub to_socket: u64,
+}
+
+/// Nodes and links read from one `bNodeTree`.
+#[derive(Debug, Clone)]
+pub struct NodeTree {
+	/// Canonical tree pointer.
+	pub ptr: u64,
+	/// Tree type idname (`ShaderNodeTree`, ...), when stored.
+	pub idname: Option<Arc<str>>,
+	/// ID name when the tree is a standalone `NT` datablock.
+	pub id_name: Option<Arc<str>>,
+	/// Nodes in list order.
+	pub nodes: Vec<TreeNode>,
+	/// Links in list order.
+	pub links: Vec<NodeLink>,
+	/// Whether a node, link, or socket list hit its budget or stopped early.
+	pub truncated: bool,
+}
+
+impl NodeTree {
+	/// Find a node by canonical pointer.
+	pub fn node(&self, ptr: u64) -> Option<&TreeNode> {
+		self.nodes.iter().find(|node| node.ptr == ptr)
+	}
+
+	/// Find a socket on any node by canonical pointer.
+	pub fn socket(&self, ptr: u64) -> Option<&NodeSocket> {
+		self.nodes
+			.iter()
+			.flat_map(|node| node.inputs.iter().chain(&node.outputs))
+			.find(|socket| socket.ptr == ptr)
+	}
+}
+
+/// Read the node tree owned by `owner_ptr`.
+///
+/// The owner is either a `bNodeTree` (standalone `NT` datablock or embedded
+/// tree pointer) or any struct with a `nodetree` pointer field (`Material`,
+/// `World`, `Light`, `Scene`, ...). `nodes`, `links`, and socket lists are
+/// walked as `ListBase`s through [`walk_ptr_chain`].
+pub fn read_node_tree<'a>(dna: &Dna, index: &PointerIndex<'a>, ids: &IdIndex, owner_ptr: u64, options: &NodeTreeOptions) -> Result<NodeTree> {
+	let decode = DecodeOptions::default();
+	let (owner_canonical, owner) = decode_ptr_instance(dna, index, owner_ptr, &decode)?;
+	let (tree_ptr, tree) = if owner.type_name.as_ref() == "bNodeTree" {
+		(owner_canonical, owner)
+	} else {
+		let ptr = ptr_field(&owner, "nodetree")
+			.filter(|ptr| *ptr != 0)
+			.ok_or(BlendError::NodeTreeNotFound { ptr: owner_canonical })?;
+		decode_ptr_instance(dna, index, ptr, &decode)?
+	};
+
+	let mut truncated = false;
+	let mut nodes = Vec::new();
+	for node_ptr in list_items(dna, index, ids, &tree, "nodes", options.max_nodes, &mut truncated)? {
+		let (_, node) = decode_ptr_instance(dna, index, node_ptr, &decode)?;
+		let inputs = read_sockets(dna, index, ids, &node, "inputs", options.max_sockets, &mut truncated)?;
+		let outputs = read_sockets(dna, index, ids, &node, "outputs", options.max_sockets, &mut truncated)?;
+		nodes.push(TreeNode {
+			ptr: node_ptr,
+			name: string_field(&node, "name").unwrap_or_else(|| Arc::from("")),
+			idname: string_field(&node, "idname"),
+			legacy_type: int_field(&node, "type_legacy").or_else(|| int_field(&node, "type")),
+			inputs,
+			outputs,
+		});
+	}
+
+	let mut links = Vec::new();
+	for link_ptr in list_items(dna, index, ids, &tree, "links", options.max_links, &mut truncated)? {
+		let (_, link) = decode_ptr_instance(dna, index, link_ptr, &decode)?;
+		let endpoint = |name: &str| ptr_field(&link, name).map(|ptr| index.canonical_ptr(dna, ptr).unwrap_or(ptr)).unwrap_or(0);
+		links.push(NodeLink {
+			ptr: link_ptr,
+			from_node: endpoint("fromnode"),
+			from_socket: endpoint("fromsock"),
+			to_node: endpoint("tonode"),
+			to_socket: endpoint("tosock"),
+		});
+	}
+
+	Ok(NodeTree {
+		ptr: tree_ptr,
+		idname: string_field(&tree, "idname"),
+		id_name: ids.get_by_ptr(tree_ptr).map(|record| Arc::from(record.id_name.as_ref())),
+		nodes,
+		links,
+		truncated,
+	})
+}
+
+fn read_sockets<'a>(
+	dna: &Dna,
+	index: &PointerIndex<'a>,
+	ids: &IdIndex,
+	node: &StructValue,
+	list: &str,
+	max: usize,
+	truncated: &mut bool,
+) -> Result<Vec<NodeSocket>> {
+	let decode = DecodeOptions::default();
+	let mut out = Vec::new();
+	for ptr in list_items(dna, index, ids, node, list, max, truncated)? {
+		let (_, socket) = decode_ptr_instance(dna, index, ptr, &decode)?;
+		out.push(NodeSocket {
+			ptr,
+			name: string_field(&socket, "name").unwrap_or_else(|| Arc::from("")),
+			identifier: string_field(&socket, "identifier"),
+			idname: string_field(&socket, "idname"),
+		});
+	}
+	Ok(out)
+}
+
+/// Canonical element pointers of the `ListBase` field `list`, in `next` order.
+fn list_items<'a>(dna: &Dna, index: &PointerIndex<'a>, ids: &IdIndex, owner: &StructValue, list: &str, max: usize, truncated: &mut bool) -> Result<Vec<u64>> {
+	let Some(Value::Struct(list_base)) = field(owner, list) else {
+		return Ok(Vec::new());
+	};
+	let walk = walk_listbase(
+		dna,
+		index,
+		ids,
+		list_base,
+		&WalkOptions {
+			max_steps: max,
+			..WalkOptions::default()
+		},
+	)?;
+	*truncated |= !walk.is_complete();
+	Ok(walk.canonical_ptrs())
+}
+
+fn field<'v>(item: &'v StructValue, name: &str) -> Option<&'v Value> {
+	item.fields.iter().find(|field| field.name.as_ref() == name).map(|field| &field.value)
+}
+
+fn ptr_field(item: &StructValue, name: &str) -> Option<u64> {
+	match field(item, name)? {
+		Value::Ptr(ptr) => Some(*ptr),
+		_ => None,
+	}
+}
+
+fn string_field(item: &StructValue, name: &str) -> Option<Arc<str>> {
+	match field(item, name)? {
+		Value::String(value) if !value.is_empty() => Some(Arc::from(value.as_ref())),
+		_ => None,
+	}
+}
+
+fn int_field(item: &StructValue, name: &str) -> Option<i64> {
+	match field(item, name)? {
+		Value::I64(value) => Some(*value),
+		Value::U64(value) => i64::try_from(*value).ok(),
+		_ => None,
+	}
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,160 @@
+mod synthetic_material {
+	use blendoc_testkit::builder::{BlendBuilder, HeaderKind};
+
+	use crate::blend::{BlendError, BlendFile, IdIndex, NodeTreeOptions, read_node_tree, scan_id_blocks};
+
+	const MATERIAL: u64 = 0x1000;
+	const TREE: u64 = 0x2000;
+	const NODES: [u64; 2] = [0x3000, 0x3100];
+	const SOCKETS: [u64; 3] = [0x4000, 0x4100, 0x4200];
+	const LINK: u64 = 0x5000;
+	const WORLD: u64 = 0x6000;
+
+	/// `MAMaterial.nodetree`: `Principled BSDF.BSDF -> Material Output.Surface`,
+	/// plus a `WOWorld` with no tree.
+	fn material_file() -> Vec<u8> {
+		let mut builder = BlendBuilder::new(HeaderKind::Legacy8, 300, false);
+		let sdna = builder.sdna_mut();
+		sdna.add_type("char", 1);
+		sdna.add_type("int", 4);
+		sdna.add_type("short", 2);
+		sdna.add_type("void", 0);
+		sdna.add_struct("ListBase", &[("void", "*first"), ("void", "*last")]);
+		sdna.add_struct("ID", &[("void", "*next"), ("void", "*prev"), ("void", "*lib"), ("char", "name[24]")]);
+		let material = sdna.add_struct("Material", &[("ID", "id"), ("bNodeTree", "*nodetree")]);
+		let world = sdna.add_struct("World", &[("ID", "id"), ("bNodeTree", "*nodetree")]);
+		let tree = sdna.add_struct("bNodeTree", &[("char", "idname[32]"), ("ListBase", "nodes"), ("ListBase", "links")]);
+		let node = sdna.add_struct(
+			"bNode",
+			&[
+				("bNode", "*next"),
+				("bNode", "*prev"),
+				("ListBase", "inputs"),
+				("ListBase", "outputs"),
+				("char", "name[32]"),
+				("char", "idname[32]"),
+				("short", "type"),
+				("short", "pad[3]"),
+			],
+		);
+		let socket = sdna.add_struct(
+			"bNodeSocket",
+			&[
+				("bNodeSocket", "*next"),
+				("bNodeSocket", "*prev"),
+				("char", "name[32]"),
+				("char", "identifier[32]"),
+			],
+		);
+		let link = sdna.add_struct(
+			"bNodeLink",
+			&[
+				("bNodeLink", "*next"),
+				("bNodeLink", "*prev"),
+				("bNode", "*fromnode"),
+				("bNode", "*tonode"),
+				("bNodeSocket", "*fromsock"),
+				("bNodeSocket", "*tosock"),
+			],
+		);
+
+		let id = |name: &str| {
+			let mut payload = builder.payload();
+			payload.ptr(0).ptr(0).ptr(0).name(name, 24);
+			payload.finish()
+		};
+		let mut payload = builder.payload();
+		payload.raw(&id("MAMaterial")).ptr(TREE);
+		let material_payload = payload.finish();
+		let mut payload = builder.payload();
+		payload.raw(&id("WOWorld")).ptr(0);
+		let world_payload = payload.finish();
+
+		let mut payload = builder.payload();
+		payload.name("ShaderNodeTree", 32).ptr(NODES[0]).ptr(NODES[1]).ptr(LINK).ptr(LINK);
+		let tree_payload = payload.finish();
+
+		let mut payload = builder.payload();
+		payload
+			.ptr(NODES[1])
+			.ptr(0)
+			.ptr(SOCKETS[0])
+			.ptr(SOCKETS[0])
+			.ptr(SOCKETS[1])
+			.ptr(SOCKETS[1])
+			.name("Principled BSDF", 32)
+			.name("ShaderNodeBsdfPrincipled", 32)
+			.raw(&[0; 8]);
+		let bsdf_payload = payload.finish();
+		let mut payload = builder.payload();
+		payload
+			.ptr(0)
+			.ptr(NODES[0])
+			.ptr(SOCKETS[2])
+			.ptr(SOCKETS[2])
+			.ptr(0)
+			.ptr(0)
+			.name("Material Output", 32)
+			.name("", 32)
+			.raw(&[0xA8, 0, 0, 0, 0, 0, 0, 0]);
+		let output_payload = payload.finish();
+
+		let socket_payload = |name: &str| {
+			let mut payload = builder.payload();
+			payload.ptr(0).ptr(0).name(name, 32).name(name, 32);
+			payload.finish()
+		};
+		let mut payload = builder.payload();
+		payload.ptr(0).ptr(0).ptr(NODES[0]).ptr(NODES[1]).ptr(SOCKETS[1]).ptr(SOCKETS[2]);
+		let link_payload = payload.finish();
+
+		let (base_color, bsdf, surface) = (socket_payload("Base Color"), socket_payload("BSDF"), socket_payload("Surface"));
+		builder
+			.block(*b"MA\0\0", material, MATERIAL, 1, material_payload)
+			.block(*b"DATA", tree, TREE, 1, tree_payload)
+			.block(*b"DATA", node, NODES[0], 1, bsdf_payload)
+			.block(*b"DATA", socket, SOCKETS[0], 1, base_color)
+			.block(*b"DATA", socket, SOCKETS[1], 1, bsdf)
+			.block(*b"DATA", node, NODES[1], 1, output_payload)
+			.block(*b"DATA", socket, SOCKETS[2], 1, surface)
+			.block(*b"DATA", link, LINK, 1, link_payload)
+			.block(*b"WO\0\0", world, WORLD, 1, world_payload);
+		builder.build()
+	}
+
+	#[test]
+	fn material_tree_lists_nodes_sockets_and_links() {
+		let blend = BlendFile::from_bytes(material_file()).expect("synthetic file opens");
+		let dna = blend.dna().expect("dna parses");
+		let index = blend.pointer_index().expect("pointer index builds");
+		let ids = IdIndex::build(scan_id_blocks(&blend, &dna).expect("id scan succeeds"));
+
+		let tree = read_node_tree(&dna, &index, &ids, MATERIAL, &NodeTreeOptions::default()).expect("tree reads");
+
+		assert_eq!(tree.ptr, TREE);
+		assert_eq!(tree.idname.as_deref(), Some("ShaderNodeTree"));
+		assert!(!tree.truncated);
+		let names: Vec<&str> = tree.nodes.iter().map(|node| node.name.as_ref()).collect();
+		assert_eq!(names, ["Principled BSDF", "Material Output"]);
+		assert_eq!(tree.nodes[0].type_label(), "ShaderNodeBsdfPrincipled");
+		assert_eq!(tree.nodes[1].type_label(), "type:168");
+		assert_eq!(tree.nodes[0].inputs[0].name.as_ref(), "Base Color");
+		assert_eq!(tree.nodes[0].outputs[0].identifier.as_deref(), Some("BSDF"));
+
+		assert_eq!(tree.links.len(), 1);
+		let link = &tree.links[0];
+		assert_eq!(tree.node(link.from_node).map(|node| node.name.as_ref()), Some("Principled BSDF"));
+		assert_eq!(tree.socket(link.to_socket).map(|socket| socket.name.as_ref()), Some("Surface"));
+	}
+
+	#[test]
+	fn owner_without_tree_reports_missing_tree() {
+		let blend = BlendFile::from_bytes(material_file()).expect("synthetic file opens");
+		let dna = blend.dna().expect("dna parses");
+		let index = blend.pointer_index().expect("pointer index builds");
+		let ids = IdIndex::build(scan_id_blocks(&blend, &dna).expect("id scan succeeds"));
+
+		let err = read_node_tree(&dna, &index, &ids, WORLD, &NodeTreeOptions::default()).expect_err("world has no tree");
+		assert!(matches!(err, BlendError::NodeTreeNotFound { ptr: WORLD }));
+	}
+}
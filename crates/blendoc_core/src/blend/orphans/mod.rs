@@ -0,0 +1,135 @@
+use std::collections::HashSet;
+use std::sync::Arc;
+
+use crate::blend::{
+	DecodeOptions, Dna, IdGraphOptions, IdGraphResult, IdIndex, PointerIndex, Result, StructValue, Value, build_id_graph, codes, decode_ptr_instance,
+};
+
+/// ID types that are used by the file itself rather than by other IDs.
+const ROOT_CODES: [[u8; 4]; 6] = [codes::ID_SCE, codes::ID_WM, codes::ID_SCR, codes::ID_SCRN, codes::ID_WS, codes::ID_LI];
+/// `ID.flag` bit Blender sets for "fake user" IDs kept without real users.
+const LIB_FAKEUSER: i64 = 1 << 9;
+
+/// One ID that no other ID references.
+#[derive(Debug, Clone)]
+pub struct OrphanId {
+	/// Canonical pointer of the ID block.
+	pub id_ptr: u64,
+	/// Source block code.
+	pub code: [u8; 4],
+	/// Struct type name.
+	pub type_name: Arc<str>,
+	/// `ID.name`.
+	pub id_name: Arc<str>,
+	/// Stored `ID.us` user count, when the `ID` struct has one.
+	pub users: Option<i64>,
+	/// Whether the fake-user flag keeps the ID alive on save.
+	pub fake_user: bool,
+}
+
+/// Filters for [`find_orphan_ids`].
+#[derive(Debug, Clone)]
+pub struct OrphanOptions {
+	/// ID graph settings used to collect inbound references.
+	pub graph: IdGraphOptions,
+	/// Keep only IDs with this block code.
+	pub code: Option<[u8; 4]>,
+	/// Keep only IDs with this struct type name.
+	pub type_name: Option<String>,
+	/// Keep IDs held only by a fake user, as Blender's orphan-data view does.
+	pub include_fake_users: bool,
+}
+
+impl Default for OrphanOptions {
+	fn default() -> Self {
+		Self {
+			graph: IdGraphOptions::default(),
+			code: None,
+			type_name: None,
+			include_fake_users: true,
+		}
+	}
+}
+
+/// Result of one [`find_orphan_ids`] run.
+#[derive(Debug, Clone)]
+pub struct OrphanReport {
+	/// Orphans sorted by type name, then ID name.
+	pub orphans: Vec<OrphanId>,
+	/// IDs that passed the code and type filters.
+	pub ids_checked: usize,
+	/// Whether the ID graph hit its edge budget; orphans may then be false positives.
+	pub truncated: bool,
+}
+
+/// Find IDs with no inbound reference from another ID.
+///
+/// References through `ID` list links (`id.next`, `id.prev`, `id.lib`) do
+/// not count, and file-level roots (scenes, window managers, screens,
+/// workspaces, libraries) are never reported.
+pub fn find_orphan_ids(dna: &Dna, index: &PointerIndex<'_>, ids: &IdIndex, options: &OrphanOptions) -> Result<OrphanReport> {
+	let graph = build_id_graph(dna, index, ids, &options.graph)?;
+	Ok(find_orphan_ids_in_graph(dna, index, ids, &graph, options))
+}
+
+/// [`find_orphan_ids`] over an ID graph built earlier (or loaded from disk).
+///
+/// `options.graph` is ignored; `graph` should have been built from the same file.
+pub fn find_orphan_ids_in_graph(dna: &Dna, index: &PointerIndex<'_>, ids: &IdIndex, graph: &IdGraphResult, options: &OrphanOptions) -> OrphanReport {
+	let used: HashSet<u64> = graph.edges.iter().filter(|edge| !edge.field.starts_with("id.")).map(|edge| edge.to).collect();
+
+	let decode = DecodeOptions {
+		max_depth: 2,
+		..DecodeOptions::default()
+	};
+	let mut ids_checked = 0;
+	let mut orphans = Vec::new();
+	for record in &ids.records {
+		if options.code.is_some_and(|code| code != record.code) || options.type_name.as_deref().is_some_and(|name| name != record.type_name.as_ref()) {
+			continue;
+		}
+		ids_checked += 1;
+		if used.contains(&record.old_ptr) || ROOT_CODES.contains(&record.code) {
+			continue;
+		}
+		let root = decode_ptr_instance(dna, index, record.old_ptr, &decode).ok().map(|(_, root)| root);
+		let id = root.as_ref().and_then(id_struct);
+		let fake_user = id.and_then(|id| int_field(id, "flag")).is_some_and(|flag| flag & LIB_FAKEUSER != 0);
+		if fake_user && !options.include_fake_users {
+			continue;
+		}
+		orphans.push(OrphanId {
+			id_ptr: record.old_ptr,
+			code: record.code,
+			type_name: Arc::from(record.type_name.as_ref()),
+			id_name: Arc::from(record.id_name.as_ref()),
+			users: id.and_then(|id| int_field(id, "us")),
+			fake_user,
+		});
+	}
+	orphans.sort_by(|left, right| left.type_name.cmp(&right.type_name).then_with(|| left.id_name.cmp(&right.id_name)));
+
+	OrphanReport {
+		orphans,
+		ids_checked,
+		truncated: graph.truncated.is_some(),
+	}
+}
+
+fn id_struct(root: &StructValue) -> Option<&StructValue> {
+	match &root.fields.iter().find(|field| field.name.as_ref() == "id")?.value {
+		Value::Struct(id) => Some(id),
+		_ => None,
+	}
+}
+
+fn int_field(value: &StructValue, name: &str) -> Option<i64> {
+	match value.fields.iter().find(|field| field.name.as_ref() == name)?.value {
+		Value::I64(value) => Some(value),
+		Value::U64(value) => Some(value as i64),
+		_ => None,
+	}
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,97 @@
+mod synthetic_orphans {
+	use blendoc_testkit::builder::{BlendBuilder, HeaderKind};
+
+	use crate::blend::{BlendFile, IdIndex, OrphanOptions, find_orphan_ids, scan_id_blocks};
+
+	/// `SCScene` -> `OBCube` -> `MECube`, plus unused `MEOrphan`, `MAOrphan`,
+	/// and `MAFake` held only by its fake user.
+	fn orphan_file() -> Vec<u8> {
+		let mut builder = BlendBuilder::new(HeaderKind::Legacy8, 404, false);
+		let sdna = builder.sdna_mut();
+		sdna.add_struct("Link", &[("Link", "*next"), ("Link", "*prev")]);
+		sdna.add_type("char", 1);
+		sdna.add_type("int", 4);
+		sdna.add_type("void", 0);
+		sdna.add_struct(
+			"ID",
+			&[
+				("void", "*next"),
+				("void", "*prev"),
+				("void", "*lib"),
+				("char", "name[24]"),
+				("int", "us"),
+				("int", "flag"),
+			],
+		);
+		let material = sdna.add_struct("Material", &[("ID", "id"), ("void", "*nodetree")]);
+		let mesh = sdna.add_struct("Mesh", &[("ID", "id"), ("void", "*verts")]);
+		let object = sdna.add_struct("Object", &[("ID", "id"), ("void", "*data")]);
+		let scene = sdna.add_struct("Scene", &[("ID", "id"), ("Object", "*camera")]);
+
+		let id = |builder: &BlendBuilder, name: &str, users: i32, flag: i32, ptr: u64| {
+			let mut payload = builder.payload();
+			payload.ptr(0).ptr(0).ptr(0).name(name, 24).i32(users).i32(flag).ptr(ptr);
+			payload.finish()
+		};
+		let scene_payload = id(&builder, "SCScene", 0, 0, 0x2000);
+		let object_payload = id(&builder, "OBCube", 1, 0, 0x3000);
+		let mesh_payload = id(&builder, "MECube", 1, 0, 0);
+		let orphan_mesh = id(&builder, "MEOrphan", 0, 0, 0);
+		let orphan_material = id(&builder, "MAOrphan", 0, 0, 0);
+		let fake_material = id(&builder, "MAFake", 1, 1 << 9, 0);
+		builder
+			.block(*b"SC\0\0", scene, 0x1000, 1, scene_payload)
+			.block(*b"OB\0\0", object, 0x2000, 1, object_payload)
+			.block(*b"ME\0\0", mesh, 0x3000, 1, mesh_payload)
+			.block(*b"ME\0\0", mesh, 0x4000, 1, orphan_mesh)
+			.block(*b"MA\0\0", material, 0x5000, 1, orphan_material)
+			.block(*b"MA\0\0", material, 0x6000, 1, fake_material);
+		builder.build()
+	}
+
+	#[test]
+	fn unreferenced_ids_are_reported_with_user_counts() {
+		let blend = BlendFile::from_bytes(orphan_file()).expect("synthetic file parses");
+		let dna = blend.dna().expect("dna");
+		let index = blend.pointer_index().expect("pointer index");
+		let ids = IdIndex::build(scan_id_blocks(&blend, &dna).expect("ids"));
+
+		let report = find_orphan_ids(&dna, &index, &ids, &OrphanOptions::default()).expect("orphans");
+		let names: Vec<&str> = report.orphans.iter().map(|item| item.id_name.as_ref()).collect();
+		assert_eq!(names, ["MAFake", "MAOrphan", "MEOrphan"]);
+		assert_eq!(report.ids_checked, 6);
+		assert!(!report.truncated);
+
+		let fake = &report.orphans[0];
+		assert!(fake.fake_user);
+		assert_eq!(fake.users, Some(1));
+		assert_eq!(report.orphans[2].users, Some(0));
+		assert!(!report.orphans[2].fake_user);
+	}
+
+	#[test]
+	fn type_and_fake_user_filters_apply() {
+		let blend = BlendFile::from_bytes(orphan_file()).expect("synthetic file parses");
+		let dna = blend.dna().expect("dna");
+		let index = blend.pointer_index().expect("pointer index");
+		let ids = IdIndex::build(scan_id_blocks(&blend, &dna).expect("ids"));
+
+		let options = OrphanOptions {
+			type_name: Some("Material".to_owned()),
+			include_fake_users: false,
+			..OrphanOptions::default()
+		};
+		let report = find_orphan_ids(&dna, &index, &ids, &options).expect("orphans");
+		let names: Vec<&str> = report.orphans.iter().map(|item| item.id_name.as_ref()).collect();
+		assert_eq!(names, ["MAOrphan"]);
+		assert_eq!(report.ids_checked, 2);
+
+		let options = OrphanOptions {
+			code: Some(*b"ME\0\0"),
+			..OrphanOptions::default()
+		};
+		let report = find_orphan_ids(&dna, &index, &ids, &options).expect("orphans");
+		assert_eq!(report.orphans.len(), 1);
+		assert_eq!(report.orphans[0].id_name.as_ref(), "MEOrphan");
+	}
+}
@@ -0,0 +1,161 @@
+use std::collections::HashSet;
+
+use crate::blend::{
+	BlendFile, DecodeOptions, Dna, PointerIndex, Result, StructValue, Value, chase_ptr_to_struct, decode_block_instances, decode_ptr_instance, scan_id_blocks,
+};
+
+/// Upper bound on `ImagePackedFile` list items followed per image.
+const MAX_LIST_ITEMS: usize = 4096;
+
+/// One `PackedFile` payload embedded in the blend file.
+#[derive(Debug, Clone)]
+pub struct PackedFileRecord {
+	/// `PackedFile` struct pointer.
+	pub packed_ptr: u64,
+	/// Pointer to the raw packed bytes.
+	pub data_ptr: u64,
+	/// Declared payload size in bytes.
+	pub size: usize,
+	/// Owning ID block pointer, when attributed.
+	pub owner_ptr: Option<u64>,
+	/// Owning ID name, when attributed.
+	pub owner_id_name: Option<Box<str>>,
+	/// Owning ID-root struct type name, when attributed.
+	pub owner_type: Option<Box<str>>,
+	/// Original file path recorded for the packed data.
+	pub filepath: Option<Box<str>>,
+}
+
+/// Find packed files and attribute them to their owning IDs.
+///
+/// Owners are found through direct `packedfile` pointers on ID roots
+/// (images, sounds, fonts, libraries, volumes) and through the
+/// `Image.packedfiles` list; remaining `PackedFile` blocks are reported
+/// without an owner.
+pub fn scan_packed_files(file: &BlendFile, dna: &Dna) -> Result<Vec<PackedFileRecord>> {
+	let Some(packed_sdna) = dna.struct_index("PackedFile") else {
+		return Ok(Vec::new());
+	};
+	let index = file.pointer_index()?;
+	let decode = DecodeOptions {
+		max_depth: 4,
+		..DecodeOptions::default()
+	};
+
+	let mut out = Vec::new();
+	let mut seen = HashSet::new();
+	for record in scan_id_blocks(file, dna)? {
+		let Ok((_, owner)) = decode_ptr_instance(dna, &index, record.old_ptr, &decode) else {
+			continue;
+		};
+		let owner_path = string_field(&owner, "filepath").or_else(|| string_field(&owner, "name"));
+
+		let mut found = Vec::new();
+		if let Some(ptr) = ptr_field(&owner, "packedfile").filter(|ptr| *ptr != 0) {
+			found.push((ptr, owner_path.clone()));
+		}
+		if let Some(Value::Struct(list)) = field(&owner, "packedfiles") {
+			found.extend(image_packed_files(dna, &index, list, &decode));
+		}
+
+		for (packed_ptr, filepath) in found {
+			if !seen.insert(packed_ptr) {
+				continue;
+			}
+			let Some(mut item) = read_packed_file(dna, &index, packed_ptr, &decode) else {
+				continue;
+			};
+			item.owner_ptr = Some(record.old_ptr);
+			item.owner_id_name = Some(record.id_name.clone());
+			item.owner_type = Some(record.type_name.clone());
+			item.filepath = filepath;
+			out.push(item);
+		}
+	}
+
+	for block in file.blocks() {
+		let block = block?;
+		if block.head.sdna_nr != packed_sdna || seen.contains(&block.head.old) {
+			continue;
+		}
+		let Ok(Value::Struct(value)) = decode_block_instances(dna, &block, &decode) else {
+			continue;
+		};
+		if let Some(item) = packed_from_value(block.head.old, &value) {
+			seen.insert(block.head.old);
+			out.push(item);
+		}
+	}
+
+	Ok(out)
+}
+
+/// Borrow the raw bytes of one packed file from the pointer index.
+pub fn packed_file_bytes<'a>(index: &PointerIndex<'a>, record: &PackedFileRecord) -> Option<&'a [u8]> {
+	index.resolve(record.data_ptr)?.slice_from(record.size)
+}
+
+/// Follow `Image.packedfiles` (`ListBase` of `ImagePackedFile`).
+fn image_packed_files(dna: &Dna, index: &PointerIndex<'_>, list: &StructValue, decode: &DecodeOptions) -> Vec<(u64, Option<Box<str>>)> {
+	let mut out = Vec::new();
+	let mut next = ptr_field(list, "first").unwrap_or(0);
+	let mut visited = HashSet::new();
+
+	while next != 0 && visited.insert(next) && visited.len() <= MAX_LIST_ITEMS {
+		let Ok(Some((_, item))) = chase_ptr_to_struct(dna, index, next, decode) else {
+			break;
+		};
+		if let Some(ptr) = ptr_field(&item, "packedfile").filter(|ptr| *ptr != 0) {
+			out.push((ptr, string_field(&item, "filepath")));
+		}
+		next = ptr_field(&item, "next").unwrap_or(0);
+	}
+
+	out
+}
+
+fn read_packed_file(dna: &Dna, index: &PointerIndex<'_>, ptr: u64, decode: &DecodeOptions) -> Option<PackedFileRecord> {
+	let (_, value) = chase_ptr_to_struct(dna, index, ptr, decode).ok()??;
+	packed_from_value(ptr, &value)
+}
+
+fn packed_from_value(packed_ptr: u64, value: &StructValue) -> Option<PackedFileRecord> {
+	if value.type_name.as_ref() != "PackedFile" {
+		return None;
+	}
+	let size = match field(value, "size")? {
+		Value::I64(size) => usize::try_from(*size).ok()?,
+		Value::U64(size) => usize::try_from(*size).ok()?,
+		_ => return None,
+	};
+	Some(PackedFileRecord {
+		packed_ptr,
+		data_ptr: ptr_field(value, "data").unwrap_or(0),
+		size,
+		owner_ptr: None,
+		owner_id_name: None,
+		owner_type: None,
+		filepath: None,
+	})
+}
+
+fn field<'v>(item: &'v StructValue, name: &str) -> Option<&'v Value> {
+	item.fields.iter().find(|field| field.name.as_ref() == name).map(|field| &field.value)
+}
+
+fn ptr_field(item: &StructValue, name: &str) -> Option<u64> {
+	match field(item, name)? {
+		Value::Ptr(ptr) => Some(*ptr),
+		_ => None,
+	}
+}
+
+fn string_field(item: &StructValue, name: &str) -> Option<Box<str>> {
+	match field(item, name)? {
+		Value::String(value) if !value.is_empty() => Some(value.clone()),
+		_ => None,
+	}
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,68 @@
+mod synthetic_packed {
+	use super::super::packed_from_value;
+	use crate::blend::{BHead, Block, FieldValue, PointerIndex, PtrEntry, StructValue, Value, packed_file_bytes};
+
+	fn packed_file(size: i64, data: u64) -> StructValue {
+		StructValue {
+			type_name: "PackedFile".into(),
+			fields: vec![
+				FieldValue {
+					name: "size".into(),
+					value: Value::I64(size),
+				},
+				FieldValue {
+					name: "seek".into(),
+					value: Value::I64(0),
+				},
+				FieldValue {
+					name: "data".into(),
+					value: Value::Ptr(data),
+				},
+			],
+		}
+	}
+
+	fn raw_block(old: u64, payload: &[u8]) -> PtrEntry<'_> {
+		PtrEntry {
+			start_old: old,
+			end_old: old + payload.len() as u64,
+			block: Block {
+				head: BHead {
+					code: *b"DATA",
+					sdna_nr: 0,
+					old,
+					len: payload.len() as u64,
+					nr: 1,
+				},
+				payload,
+				file_offset: 0,
+			},
+		}
+	}
+
+	#[test]
+	fn reads_size_and_data_pointer() {
+		let record = packed_from_value(0x5000, &packed_file(5, 0x6000)).expect("packed file");
+		assert_eq!(record.packed_ptr, 0x5000);
+		assert_eq!(record.data_ptr, 0x6000);
+		assert_eq!(record.size, 5);
+		assert!(record.owner_ptr.is_none());
+
+		assert!(packed_from_value(0x5000, &packed_file(-1, 0x6000)).is_none());
+		let mut other = packed_file(5, 0x6000);
+		other.type_name = "Image".into();
+		assert!(packed_from_value(0x5000, &other).is_none());
+	}
+
+	#[test]
+	fn payload_bytes_respect_declared_size() {
+		let bytes = *b"\x89PNG\r\n\x1a\n";
+		let index = PointerIndex::from_entries_for_test(vec![raw_block(0x6000, &bytes)]);
+
+		let record = packed_from_value(0x5000, &packed_file(4, 0x6000)).expect("packed file");
+		assert_eq!(packed_file_bytes(&index, &record), Some(&bytes[..4]));
+
+		let record = packed_from_value(0x5000, &packed_file(64, 0x6000)).expect("packed file");
+		assert!(packed_file_bytes(&index, &record).is_none());
+	}
+}
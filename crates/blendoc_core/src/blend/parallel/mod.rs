@@ -0,0 +1,74 @@
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::thread;
+
+use crate::blend::{Block, DecodeOptions, Dna, Result, Value, decode_block_instances};
+
+/// One block paired with its decode outcome.
+#[derive(Debug)]
+pub struct BlockDecode<'a> {
+	/// Source block.
+	pub block: Block<'a>,
+	/// Decoded block value, or the per-block decode error.
+	pub value: Result<Value>,
+}
+
+/// Resolve a requested worker count, treating `0` as "use available parallelism".
+pub fn effective_threads(requested: usize) -> usize {
+	if requested > 0 {
+		return requested;
+	}
+	thread::available_parallelism().map(usize::from).unwrap_or(1)
+}
+
+/// Apply `f` to every block on a bounded worker pool.
+///
+/// Output order matches input order regardless of scheduling; pass blocks in
+/// file order (as yielded by `BlendFile::blocks`) to get offset-ordered output.
+pub fn map_blocks_parallel<'a, T, F>(blocks: &[Block<'a>], threads: usize, f: F) -> Vec<T>
+where
+	T: Send,
+	F: Fn(&Block<'a>) -> T + Sync,
+{
+	let workers = effective_threads(threads).min(blocks.len());
+	if workers <= 1 {
+		return blocks.iter().map(f).collect();
+	}
+
+	let next = AtomicUsize::new(0);
+	let mut tagged: Vec<(usize, T)> = thread::scope(|scope| {
+		let handles: Vec<_> = (0..workers)
+			.map(|_| {
+				scope.spawn(|| {
+					let mut out = Vec::new();
+					loop {
+						let idx = next.fetch_add(1, Ordering::Relaxed);
+						let Some(block) = blocks.get(idx) else {
+							break;
+						};
+						out.push((idx, f(block)));
+					}
+					out
+				})
+			})
+			.collect();
+
+		handles
+			.into_iter()
+			.flat_map(|handle| handle.join().unwrap_or_else(|panic| std::panic::resume_unwind(panic)))
+			.collect()
+	});
+
+	tagged.sort_by_key(|(idx, _)| *idx);
+	tagged.into_iter().map(|(_, value)| value).collect()
+}
+
+/// Decode every block on a bounded worker pool, keeping input order.
+pub fn decode_blocks_parallel<'a>(dna: &Dna, blocks: &[Block<'a>], options: &DecodeOptions, threads: usize) -> Vec<BlockDecode<'a>> {
+	map_blocks_parallel(blocks, threads, |block| BlockDecode {
+		block: *block,
+		value: decode_block_instances(dna, block, options),
+	})
+}
+
+#[cfg(test)]
+mod tests;
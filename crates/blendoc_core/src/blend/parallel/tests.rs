@@ -0,0 +1,62 @@
+mod synthetic_parallel {
+	use crate::blend::{BHead, Block, DecodeOptions, Dna, DnaField, DnaStruct, Endianness, Value, decode_blocks_parallel, map_blocks_parallel};
+
+	fn blocks(payloads: &[[u8; 4]]) -> Vec<Block<'_>> {
+		payloads
+			.iter()
+			.enumerate()
+			.map(|(idx, payload)| Block {
+				head: BHead {
+					code: *b"DATA",
+					sdna_nr: 0,
+					old: 0x1000 + idx as u64 * 0x10,
+					len: 4,
+					nr: 1,
+				},
+				payload,
+				file_offset: idx * 28,
+			})
+			.collect()
+	}
+
+	#[test]
+	fn output_order_matches_input_for_any_thread_count() {
+		let payloads: Vec<[u8; 4]> = (0..64_u32).map(u32::to_le_bytes).collect();
+		let blocks = blocks(&payloads);
+
+		for threads in [1, 2, 7, 64, 128] {
+			let offsets = map_blocks_parallel(&blocks, threads, |block| block.file_offset);
+			let expected: Vec<_> = blocks.iter().map(|block| block.file_offset).collect();
+			assert_eq!(offsets, expected, "threads={threads}");
+		}
+	}
+
+	#[test]
+	fn decode_keeps_per_block_results() {
+		let dna = Dna::from_tables(
+			Endianness::Little,
+			8,
+			vec!["value".into()],
+			vec!["int".into(), "Item".into()],
+			vec![4, 4],
+			vec![DnaStruct {
+				type_idx: 1,
+				fields: vec![DnaField { type_idx: 0, name_idx: 0 }],
+			}],
+		)
+		.expect("valid dna tables");
+
+		let payloads: Vec<[u8; 4]> = (0..10_i32).map(i32::to_le_bytes).collect();
+		let mut blocks = blocks(&payloads);
+		blocks[3].head.sdna_nr = 9;
+
+		let decoded = decode_blocks_parallel(&dna, &blocks, &DecodeOptions::default(), 4);
+		assert_eq!(decoded.len(), 10);
+		assert!(decoded[3].value.is_err());
+
+		let Ok(Value::Struct(item)) = &decoded[5].value else {
+			panic!("expected struct decode");
+		};
+		assert!(matches!(item.fields[0].value, Value::I64(5)));
+	}
+}
@@ -1,3 +1,5 @@
+use std::fmt;
+
 use crate::blend::{BlendError, Result};
 
 /// One parsed operation in a field path expression.
@@ -7,6 +9,24 @@ pub enum PathStep {
 	Field(String),
 	/// Select an array element by zero-based index.
 	Index(usize),
+	/// Select an array element counted from the end; `[-1]` is `IndexFromEnd(1)`.
+	IndexFromEnd(usize),
+	/// Select every array element in `start..end`; a missing `end` runs to the last element.
+	Slice {
+		/// First selected index.
+		start: usize,
+		/// Exclusive end index.
+		end: Option<usize>,
+	},
+	/// Select every field of a struct or every element of an array.
+	Wildcard,
+}
+
+impl PathStep {
+	/// Return whether this step can select more than one value.
+	pub fn fans_out(&self) -> bool {
+		matches!(self, Self::Slice { .. } | Self::Wildcard)
+	}
 }
 
 /// Parsed field path expression.
@@ -18,9 +38,13 @@ pub struct FieldPath {
 
 impl FieldPath {
 	/// Parse dotted field syntax with optional `[index]` selectors.
+	///
+	/// Segments are field names or `*`; selectors are `[n]`, `[-n]`,
+	/// `[a..b]` (either bound optional), or `[*]`.
 	pub fn parse(input: &str) -> Result<Self> {
+		let invalid = || BlendError::InvalidFieldPath { path: input.to_owned() };
 		if input.is_empty() {
-			return Err(BlendError::InvalidFieldPath { path: input.to_owned() });
+			return Err(invalid());
 		}
 
 		let bytes = input.as_bytes();
@@ -28,50 +52,91 @@ impl FieldPath {
 		let mut steps = Vec::new();
 
 		while idx < bytes.len() {
-			let start = idx;
-			while idx < bytes.len() {
-				let byte = bytes[idx];
-				if byte.is_ascii_alphanumeric() || byte == b'_' {
-					idx += 1;
-				} else {
-					break;
+			if bytes[idx] == b'*' {
+				idx += 1;
+				steps.push(PathStep::Wildcard);
+			} else {
+				let start = idx;
+				while idx < bytes.len() {
+					let byte = bytes[idx];
+					if byte.is_ascii_alphanumeric() || byte == b'_' {
+						idx += 1;
+					} else {
+						break;
+					}
 				}
-			}
 
-			if idx == start {
-				return Err(BlendError::InvalidFieldPath { path: input.to_owned() });
-			}
+				if idx == start {
+					return Err(invalid());
+				}
 
-			steps.push(PathStep::Field(input[start..idx].to_owned()));
+				steps.push(PathStep::Field(input[start..idx].to_owned()));
+			}
 
 			while idx < bytes.len() && bytes[idx] == b'[' {
-				idx += 1;
-				let n_start = idx;
-				while idx < bytes.len() && bytes[idx].is_ascii_digit() {
-					idx += 1;
-				}
-				if idx == n_start || idx >= bytes.len() || bytes[idx] != b']' {
-					return Err(BlendError::InvalidFieldPath { path: input.to_owned() });
-				}
-
-				let number = input[n_start..idx]
-					.parse::<usize>()
-					.map_err(|_| BlendError::InvalidFieldPath { path: input.to_owned() })?;
-				steps.push(PathStep::Index(number));
-				idx += 1;
+				let close = input[idx..].find(']').map(|offset| idx + offset).ok_or_else(invalid)?;
+				steps.push(parse_selector(&input[idx + 1..close]).ok_or_else(invalid)?);
+				idx = close + 1;
 			}
 
 			if idx < bytes.len() {
 				if bytes[idx] != b'.' {
-					return Err(BlendError::InvalidFieldPath { path: input.to_owned() });
+					return Err(invalid());
 				}
 				idx += 1;
 				if idx >= bytes.len() {
-					return Err(BlendError::InvalidFieldPath { path: input.to_owned() });
+					return Err(invalid());
 				}
 			}
 		}
 
 		Ok(Self { steps })
 	}
+
+	/// Return whether any step can select more than one value.
+	pub fn fans_out(&self) -> bool {
+		self.steps.iter().any(PathStep::fans_out)
+	}
+}
+
+impl fmt::Display for FieldPath {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		for (idx, step) in self.steps.iter().enumerate() {
+			let dot = if idx == 0 { "" } else { "." };
+			match step {
+				PathStep::Field(name) => write!(f, "{dot}{name}")?,
+				PathStep::Wildcard => write!(f, "{dot}*")?,
+				PathStep::Index(index) => write!(f, "[{index}]")?,
+				PathStep::IndexFromEnd(back) => write!(f, "[-{back}]")?,
+				PathStep::Slice { start, end } => match end {
+					Some(end) => write!(f, "[{start}..{end}]")?,
+					None => write!(f, "[{start}..]")?,
+				},
+			}
+		}
+		Ok(())
+	}
+}
+
+/// Parse the text between `[` and `]`.
+fn parse_selector(body: &str) -> Option<PathStep> {
+	let number = |text: &str| -> Option<usize> {
+		if text.is_empty() || !text.bytes().all(|byte| byte.is_ascii_digit()) {
+			return None;
+		}
+		text.parse().ok()
+	};
+
+	if body == "*" {
+		return Some(PathStep::Wildcard);
+	}
+	if let Some(back) = body.strip_prefix('-') {
+		return number(back).filter(|back| *back > 0).map(PathStep::IndexFromEnd);
+	}
+	if let Some((start, end)) = body.split_once("..") {
+		let start = if start.is_empty() { 0 } else { number(start)? };
+		let end = if end.is_empty() { None } else { Some(number(end)?) };
+		return Some(PathStep::Slice { start, end });
+	}
+	number(body).map(PathStep::Index)
 }
@@ -0,0 +1,278 @@
+use super::StructDecoder;
+use crate::blend::{BlendError, Dna, Result, StructValue, Value};
+
+/// Declarative [`StructDecoder`] read from one `[[struct]]` TOML entry.
+///
+/// ```toml
+/// [[struct]]
+/// type = "StudioAssetMeta"
+/// summary = "{name} v{version}"
+/// hide = ["_pad0", "runtime_cache"]
+///
+/// [struct.flags.state]
+/// DIRTY = 1
+/// LOCKED = 0x4
+///
+/// [struct.enum.kind]
+/// PROP = 0
+/// CHARACTER = 1
+/// ```
+///
+/// Flag fields decode to `"DIRTY|LOCKED"`-style strings, enum fields to the
+/// matching name (unknown values stay numeric), hidden fields are dropped,
+/// and `summary` placeholders name fields or dotted nested-field paths.
+#[derive(Debug, Clone, Default)]
+pub struct StructDescriptor {
+	/// Struct type name the entry applies to.
+	pub type_name: String,
+	/// Printer label template with `{field}` placeholders.
+	pub summary: Option<String>,
+	/// Fields removed from decoded values.
+	pub hide: Vec<String>,
+	/// Bit names per flag field.
+	pub flags: Vec<(String, Vec<(String, u64)>)>,
+	/// Value names per enum field.
+	pub enums: Vec<(String, Vec<(String, i64)>)>,
+}
+
+impl StructDescriptor {
+	/// Parse every `[[struct]]` entry of a descriptor document.
+	///
+	/// Accepts the TOML subset shown on [`StructDescriptor`]: `[[struct]]`
+	/// headers, `[struct.flags.<field>]` and `[struct.enum.<field>]` tables,
+	/// strings, integers (decimal or `0x` hex), and single-line string arrays.
+	pub fn parse_all(text: &str) -> Result<Vec<Self>> {
+		let mut out: Vec<Self> = Vec::new();
+		let mut section = Section::Struct;
+		for (idx, raw) in text.lines().enumerate() {
+			let line_no = idx + 1;
+			let err = |message: String| BlendError::PluginDescriptor { line: line_no, message };
+			let line = strip_comment(raw).trim();
+			if line.is_empty() {
+				continue;
+			}
+
+			if line == "[[struct]]" {
+				out.push(Self::default());
+				section = Section::Struct;
+				continue;
+			}
+			if let Some(header) = line.strip_prefix('[').and_then(|rest| rest.strip_suffix(']')) {
+				let current = out.last_mut().ok_or_else(|| err("table before the first [[struct]]".to_owned()))?;
+				section = match header.trim().split('.').collect::<Vec<_>>().as_slice() {
+					["struct", "flags", field] => {
+						current.flags.push((field.to_string(), Vec::new()));
+						Section::Flags
+					}
+					["struct", "enum", field] => {
+						current.enums.push((field.to_string(), Vec::new()));
+						Section::Enum
+					}
+					_ => return Err(err(format!("unknown table [{header}]"))),
+				};
+				continue;
+			}
+
+			let (key, value) = line.split_once('=').ok_or_else(|| err(format!("expected `key = value`, got `{line}`")))?;
+			let key = unquote_key(key.trim());
+			let value = value.trim();
+			let current = out.last_mut().ok_or_else(|| err("key before the first [[struct]]".to_owned()))?;
+			match section {
+				Section::Struct => match key {
+					"type" => current.type_name = parse_string(value).map_err(err)?,
+					"summary" => current.summary = Some(parse_string(value).map_err(err)?),
+					"hide" => current.hide = parse_string_array(value).map_err(err)?,
+					_ => return Err(err(format!("unknown key `{key}`"))),
+				},
+				Section::Flags => {
+					let bits = parse_int(value).map_err(err)? as u64;
+					current.flags.last_mut().expect("flags table opened").1.push((key.to_owned(), bits));
+				}
+				Section::Enum => {
+					let number = parse_int(value).map_err(err)?;
+					current.enums.last_mut().expect("enum table opened").1.push((key.to_owned(), number));
+				}
+			}
+		}
+
+		if let Some(missing) = out.iter().position(|item| item.type_name.is_empty()) {
+			return Err(BlendError::PluginDescriptor {
+				line: 0,
+				message: format!("[[struct]] entry {} has no `type`", missing + 1),
+			});
+		}
+		Ok(out)
+	}
+
+	fn rewrite(&self, value: &mut Value, field: &str) {
+		if let Some((_, bits)) = self.flags.iter().find(|(name, _)| name == field)
+			&& let Some(number) = as_u64(value)
+		{
+			*value = Value::String(flag_label(number, bits).into_boxed_str());
+			return;
+		}
+		if let Some((_, names)) = self.enums.iter().find(|(name, _)| name == field)
+			&& let Some(number) = as_i64(value)
+			&& let Some((name, _)) = names.iter().find(|(_, item)| *item == number)
+		{
+			*value = Value::String(Box::from(name.as_str()));
+		}
+	}
+}
+
+impl StructDecoder for StructDescriptor {
+	fn decode(&self, mut value: StructValue, _raw: &[u8], _dna: &Dna) -> Result<StructValue> {
+		value.fields.retain(|field| !self.hide.iter().any(|name| name.as_str() == field.name.as_ref()));
+		for field in &mut value.fields {
+			self.rewrite(&mut field.value, &field.name);
+		}
+		Ok(value)
+	}
+
+	fn summary(&self, value: &StructValue) -> Option<String> {
+		let template = self.summary.as_deref()?;
+		let mut out = String::new();
+		let mut rest = template;
+		while let Some(start) = rest.find('{') {
+			out.push_str(&rest[..start]);
+			let Some(end) = rest[start..].find('}') else {
+				break;
+			};
+			let path = &rest[start + 1..start + end];
+			out.push_str(&lookup(value, path).map(render).unwrap_or_else(|| "?".to_owned()));
+			rest = &rest[start + end + 1..];
+		}
+		out.push_str(rest);
+		Some(out)
+	}
+}
+
+#[derive(Clone, Copy)]
+enum Section {
+	Struct,
+	Flags,
+	Enum,
+}
+
+fn flag_label(number: u64, bits: &[(String, u64)]) -> String {
+	let mut names = Vec::new();
+	let mut left = number;
+	for (name, bit) in bits {
+		if *bit != 0 && number & bit == *bit {
+			names.push(name.clone());
+			left &= !bit;
+		}
+	}
+	if left != 0 || names.is_empty() {
+		names.push(format!("0x{left:x}"));
+	}
+	names.join("|")
+}
+
+fn lookup<'a>(value: &'a StructValue, path: &str) -> Option<&'a Value> {
+	let (head, rest) = match path.split_once('.') {
+		Some((head, rest)) => (head, Some(rest)),
+		None => (path, None),
+	};
+	let field = &value.fields.iter().find(|field| field.name.as_ref() == head)?.value;
+	match (rest, field) {
+		(None, field) => Some(field),
+		(Some(rest), Value::Struct(inner)) => lookup(inner, rest),
+		_ => None,
+	}
+}
+
+fn render(value: &Value) -> String {
+	match value {
+		Value::String(text) => text.to_string(),
+		Value::I64(number) => number.to_string(),
+		Value::U64(number) => number.to_string(),
+		Value::F32(number) => number.to_string(),
+		Value::F64(number) => number.to_string(),
+		Value::Bool(flag) => flag.to_string(),
+		Value::Ptr(ptr) => format!("0x{ptr:x}"),
+		_ => "?".to_owned(),
+	}
+}
+
+fn as_u64(value: &Value) -> Option<u64> {
+	match value {
+		Value::U64(number) => Some(*number),
+		Value::I64(number) => Some(*number as u64),
+		_ => None,
+	}
+}
+
+fn as_i64(value: &Value) -> Option<i64> {
+	match value {
+		Value::I64(number) => Some(*number),
+		Value::U64(number) => i64::try_from(*number).ok(),
+		_ => None,
+	}
+}
+
+/// Drop a `#` comment that is not inside a string.
+fn strip_comment(line: &str) -> &str {
+	let mut in_string = false;
+	let mut escaped = false;
+	for (idx, ch) in line.char_indices() {
+		match ch {
+			'\\' if in_string => escaped = !escaped,
+			'"' if !escaped => in_string = !in_string,
+			'#' if !in_string => return &line[..idx],
+			_ => escaped = false,
+		}
+		if ch != '\\' {
+			escaped = false;
+		}
+	}
+	line
+}
+
+fn unquote_key(key: &str) -> &str {
+	key.strip_prefix('"').and_then(|rest| rest.strip_suffix('"')).unwrap_or(key)
+}
+
+fn parse_string(value: &str) -> std::result::Result<String, String> {
+	let inner = value
+		.strip_prefix('"')
+		.and_then(|rest| rest.strip_suffix('"'))
+		.ok_or_else(|| format!("expected a quoted string, got `{value}`"))?;
+	let mut out = String::with_capacity(inner.len());
+	let mut chars = inner.chars();
+	while let Some(ch) = chars.next() {
+		if ch != '\\' {
+			out.push(ch);
+			continue;
+		}
+		match chars.next() {
+			Some('n') => out.push('\n'),
+			Some('t') => out.push('\t'),
+			Some(other @ ('"' | '\\')) => out.push(other),
+			other => return Err(format!("unsupported escape `\\{}`", other.map(String::from).unwrap_or_default())),
+		}
+	}
+	Ok(out)
+}
+
+fn parse_string_array(value: &str) -> std::result::Result<Vec<String>, String> {
+	let inner = value
+		.strip_prefix('[')
+		.and_then(|rest| rest.strip_suffix(']'))
+		.ok_or_else(|| format!("expected a single-line array, got `{value}`"))?;
+	inner.split(',').map(str::trim).filter(|item| !item.is_empty()).map(parse_string).collect()
+}
+
+fn parse_int(value: &str) -> std::result::Result<i64, String> {
+	let (negative, digits) = match value.strip_prefix('-') {
+		Some(rest) => (true, rest),
+		None => (false, value),
+	};
+	let digits = digits.replace('_', "");
+	let parsed = match digits.strip_prefix("0x") {
+		Some(hex) => i64::from_str_radix(hex, 16),
+		None => digits.parse::<i64>(),
+	};
+	let number = parsed.map_err(|_| format!("expected an integer, got `{value}`"))?;
+	Ok(if negative { -number } else { number })
+}
@@ -0,0 +1,93 @@
+use std::collections::HashMap;
+use std::fmt;
+use std::sync::Arc;
+
+use crate::blend::{Dna, Result, StructValue};
+
+mod descriptor;
+
+pub use descriptor::StructDescriptor;
+
+/// Custom decode and print behavior for one DNA struct type.
+///
+/// Register implementations on [`DecodePlugins`] and set them on
+/// `DecodeOptions::plugins`; decoding then hands every instance of the
+/// registered type to [`StructDecoder::decode`] after generic decoding.
+pub trait StructDecoder: Send + Sync {
+	/// Rewrite the generically decoded `value`; `raw` is the struct's bytes as stored.
+	fn decode(&self, value: StructValue, raw: &[u8], dna: &Dna) -> Result<StructValue> {
+		let _ = (raw, dna);
+		Ok(value)
+	}
+
+	/// One-line label printers show next to the type name.
+	fn summary(&self, value: &StructValue) -> Option<String> {
+		let _ = value;
+		None
+	}
+}
+
+/// Registry of [`StructDecoder`]s keyed by struct type name.
+///
+/// Clones share the registered decoders.
+#[derive(Clone, Default)]
+pub struct DecodePlugins {
+	by_type: HashMap<Box<str>, Arc<dyn StructDecoder>>,
+}
+
+impl DecodePlugins {
+	/// Create an empty registry.
+	pub fn new() -> Self {
+		Self::default()
+	}
+
+	/// Register `decoder` for `type_name`, replacing any earlier registration.
+	pub fn register(&mut self, type_name: &str, decoder: impl StructDecoder + 'static) -> &mut Self {
+		self.by_type.insert(Box::from(type_name), Arc::new(decoder));
+		self
+	}
+
+	/// Register every `[[struct]]` entry of a TOML descriptor document.
+	///
+	/// Returns the number of struct types registered.
+	pub fn load_descriptors(&mut self, text: &str) -> Result<usize> {
+		let descriptors = StructDescriptor::parse_all(text)?;
+		let count = descriptors.len();
+		for descriptor in descriptors {
+			let type_name = descriptor.type_name.clone();
+			self.register(&type_name, descriptor);
+		}
+		Ok(count)
+	}
+
+	/// Decoder registered for `type_name`.
+	pub fn get(&self, type_name: &str) -> Option<&dyn StructDecoder> {
+		self.by_type.get(type_name).map(|decoder| decoder.as_ref())
+	}
+
+	/// Printer label for `value` from its type's decoder.
+	pub fn summary(&self, value: &StructValue) -> Option<String> {
+		self.get(&value.type_name)?.summary(value)
+	}
+
+	/// Registered type names, sorted.
+	pub fn type_names(&self) -> Vec<&str> {
+		let mut names: Vec<&str> = self.by_type.keys().map(|name| name.as_ref()).collect();
+		names.sort_unstable();
+		names
+	}
+
+	/// Whether no decoder is registered.
+	pub fn is_empty(&self) -> bool {
+		self.by_type.is_empty()
+	}
+}
+
+impl fmt::Debug for DecodePlugins {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		f.debug_struct("DecodePlugins").field("types", &self.type_names()).finish()
+	}
+}
+
+#[cfg(test)]
+mod tests;
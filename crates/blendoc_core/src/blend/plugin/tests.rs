@@ -0,0 +1,117 @@
+mod synthetic_plugins {
+	use blendoc_testkit::builder::{BlendBuilder, HeaderKind};
+
+	use crate::blend::{
+		BlendError, BlendFile, DecodeOptions, DecodePlugins, Dna, Result, StructDecoder, StructDescriptor, StructValue, Value, decode_block_instances,
+	};
+
+	const DESCRIPTOR: &str = r#"
+# studio pipeline metadata
+[[struct]]
+type = "StudioMeta"
+summary = "{name} v{version} [{state}]"
+hide = ["cache"]
+
+[struct.flags.state]
+DIRTY = 1
+LOCKED = 0x4
+
+[struct.enum.kind]
+PROP = 0
+CHARACTER = 1
+"#;
+
+	/// One `StudioMeta` block: `name="hero"`, `version=3`, `state=5`, `kind=1`, `cache=99`.
+	fn studio_file() -> Vec<u8> {
+		let mut builder = BlendBuilder::new(HeaderKind::Legacy8, 404, false);
+		let sdna = builder.sdna_mut();
+		sdna.add_struct("Link", &[("Link", "*next"), ("Link", "*prev")]);
+		sdna.add_type("char", 1);
+		sdna.add_type("int", 4);
+		let meta = sdna.add_struct(
+			"StudioMeta",
+			&[("char", "name[16]"), ("int", "version"), ("int", "state"), ("int", "kind"), ("int", "cache")],
+		);
+		let mut payload = builder.payload();
+		payload.name("hero", 16).i32(3).i32(5).i32(1).i32(99);
+		let meta_payload = payload.finish();
+		builder.block(*b"DATA", meta, 0x1000, 1, meta_payload);
+		builder.build()
+	}
+
+	fn decode_meta(plugins: Option<DecodePlugins>) -> StructValue {
+		let blend = BlendFile::from_bytes(studio_file()).expect("synthetic file parses");
+		let dna = blend.dna().expect("dna parses");
+		let block = blend.find_first_block_by_code(*b"DATA").expect("scan").expect("DATA block");
+		let options = DecodeOptions {
+			plugins,
+			..DecodeOptions::default()
+		};
+		match decode_block_instances(&dna, &block, &options).expect("decode succeeds") {
+			Value::Struct(value) => value,
+			other => panic!("expected struct, got {other:?}"),
+		}
+	}
+
+	fn field<'a>(value: &'a StructValue, name: &str) -> Option<&'a Value> {
+		value.fields.iter().find(|field| field.name.as_ref() == name).map(|field| &field.value)
+	}
+
+	#[test]
+	fn descriptor_rewrites_flags_enums_and_hidden_fields() {
+		let mut plugins = DecodePlugins::new();
+		assert_eq!(plugins.load_descriptors(DESCRIPTOR).expect("descriptor parses"), 1);
+		let value = decode_meta(Some(plugins.clone()));
+
+		assert!(matches!(field(&value, "state"), Some(Value::String(text)) if text.as_ref() == "DIRTY|LOCKED"));
+		assert!(matches!(field(&value, "kind"), Some(Value::String(text)) if text.as_ref() == "CHARACTER"));
+		assert!(field(&value, "cache").is_none());
+		assert_eq!(plugins.summary(&value).as_deref(), Some("hero v3 [DIRTY|LOCKED]"));
+
+		let generic = decode_meta(None);
+		assert!(matches!(field(&generic, "state"), Some(Value::I64(5))));
+		assert!(matches!(field(&generic, "cache"), Some(Value::I64(99))));
+	}
+
+	struct VersionBump;
+
+	impl StructDecoder for VersionBump {
+		fn decode(&self, mut value: StructValue, raw: &[u8], _dna: &Dna) -> Result<StructValue> {
+			assert_eq!(raw.len(), 32);
+			for field in &mut value.fields {
+				if field.name.as_ref() == "version"
+					&& let Value::I64(number) = &mut field.value
+				{
+					*number += 100;
+				}
+			}
+			Ok(value)
+		}
+	}
+
+	#[test]
+	fn registered_decoder_sees_raw_bytes_and_replaces_descriptor() {
+		let mut plugins = DecodePlugins::new();
+		plugins.load_descriptors(DESCRIPTOR).expect("descriptor parses");
+		plugins.register("StudioMeta", VersionBump);
+		let value = decode_meta(Some(plugins.clone()));
+
+		assert!(matches!(field(&value, "version"), Some(Value::I64(103))));
+		assert!(matches!(field(&value, "state"), Some(Value::I64(5))));
+		assert_eq!(plugins.summary(&value), None);
+		assert_eq!(plugins.type_names(), ["StudioMeta"]);
+	}
+
+	#[test]
+	fn descriptor_errors_carry_line_numbers() {
+		let line = |text: &str| match StructDescriptor::parse_all(text) {
+			Err(BlendError::PluginDescriptor { line, .. }) => line,
+			other => panic!("expected descriptor error, got {other:?}"),
+		};
+		assert_eq!(line("type = \"X\""), 1);
+		assert_eq!(line("[[struct]]\ntype = \"X\"\ncolour = \"red\""), 3);
+		assert_eq!(line("[[struct]]\ntype = \"X\"\n[struct.flags.f]\nA = nope"), 4);
+		assert_eq!(line("[[struct]]\n[struct.other]"), 2);
+		assert_eq!(line("[[struct]]\nsummary = \"{a}\""), 0);
+	}
+}
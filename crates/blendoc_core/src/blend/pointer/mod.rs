@@ -1,6 +1,9 @@
 use std::collections::HashMap;
+#[cfg(feature = "probe-stats")]
+use std::sync::atomic::{AtomicU64, Ordering};
 
-use crate::blend::{BlendError, BlendFile, Block, Dna, Result};
+use crate::blend::trace::trace_span;
+use crate::blend::{BlendError, BlendFile, Block, Dna, Result, Warning, WarningKind};
 
 /// Heuristic storage mode for pointer-like IDs in a `.blend` file.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -12,12 +15,96 @@ pub enum PointerStorage {
 }
 
 /// Index for resolving stored old-address identifiers to blocks.
+///
+/// Entries are kept sorted by start address. A lookup first tries an exact
+/// start match through a hash map, then (for address-range files) binary
+/// searches the sorted starts, so every lookup costs at most
+/// `floor(log2(len)) + 2` comparisons. Ranges only overlap in stable-ID
+/// files, which never take the binary-search path.
+///
+/// With the `probe-stats` feature, lookups also bump relaxed atomic counters
+/// read back through `stats()`; without it `resolve` touches no shared state.
 #[derive(Debug)]
 pub struct PointerIndex<'a> {
 	starts: Vec<u64>,
 	entries: Vec<PtrEntry<'a>>,
 	by_start: HashMap<u64, usize>,
 	storage: PointerStorage,
+	counters: ProbeCounters,
+}
+
+/// Lookup counters of a [`PointerIndex`], from [`PointerIndex::stats`].
+#[cfg(feature = "probe-stats")]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct PointerIndexStats {
+	/// Number of indexed ranges.
+	pub entries: usize,
+	/// Non-null lookups through [`PointerIndex::resolve`].
+	pub lookups: u64,
+	/// Lookups answered by an exact start match.
+	pub exact_hits: u64,
+	/// Lookups answered by binary search into a range.
+	pub range_hits: u64,
+	/// Lookups that resolved to no range.
+	pub misses: u64,
+	/// Binary-search comparisons summed over all lookups.
+	pub probes: u64,
+	/// Most comparisons a single lookup needed.
+	pub max_probes: u64,
+}
+
+/// Relaxed atomic counters behind [`PointerIndexStats`]; lookups take `&self`.
+#[cfg(feature = "probe-stats")]
+#[derive(Debug, Default)]
+struct ProbeCounters {
+	lookups: AtomicU64,
+	exact_hits: AtomicU64,
+	range_hits: AtomicU64,
+	misses: AtomicU64,
+	probes: AtomicU64,
+	max_probes: AtomicU64,
+}
+
+/// Stand-in without `probe-stats`: every record call compiles to nothing.
+#[cfg(not(feature = "probe-stats"))]
+#[derive(Debug, Default)]
+struct ProbeCounters {}
+
+#[cfg(feature = "probe-stats")]
+impl ProbeCounters {
+	fn lookup(&self) {
+		self.lookups.fetch_add(1, Ordering::Relaxed);
+	}
+
+	fn exact_hit(&self) {
+		self.exact_hits.fetch_add(1, Ordering::Relaxed);
+	}
+
+	fn range_hit(&self) {
+		self.range_hits.fetch_add(1, Ordering::Relaxed);
+	}
+
+	fn miss(&self) {
+		self.misses.fetch_add(1, Ordering::Relaxed);
+	}
+
+	fn search(&self, probes: u64) {
+		self.probes.fetch_add(probes, Ordering::Relaxed);
+		self.max_probes.fetch_max(probes, Ordering::Relaxed);
+	}
+}
+
+#[cfg(not(feature = "probe-stats"))]
+impl ProbeCounters {
+	fn lookup(&self) {}
+
+	fn exact_hit(&self) {}
+
+	fn range_hit(&self) {}
+
+	fn miss(&self) {}
+
+	fn search(&self, _probes: u64) {}
 }
 
 /// One indexed pointer range entry.
@@ -72,11 +159,13 @@ impl<'a> PointerIndex<'a> {
 			entries,
 			by_start,
 			storage,
+			counters: ProbeCounters::default(),
 		}
 	}
 
 	/// Scan a file and build pointer ranges for non-empty blocks.
 	pub fn build(file: &'a BlendFile) -> Result<Self> {
+		trace_span!("pointer_index.build");
 		let mut entries = Vec::new();
 
 		for block in file.blocks() {
@@ -98,32 +187,55 @@ impl<'a> PointerIndex<'a> {
 		if ptr == 0 {
 			return None;
 		}
+		let counters = &self.counters;
+		counters.lookup();
 
 		if let Some(idx) = self.by_start.get(&ptr).copied() {
+			counters.exact_hit();
 			let entry = self.entries[idx];
 			return Some(ResolvedPtr { entry, byte_offset: 0 });
 		}
 
 		if self.storage == PointerStorage::StableIds {
+			counters.miss();
 			return None;
 		}
 
-		let idx = self.starts.partition_point(|start| *start <= ptr);
-		if idx == 0 {
-			return None;
-		}
+		let mut probes = 0_u64;
+		let idx = self.starts.partition_point(|start| {
+			probes += 1;
+			*start <= ptr
+		});
+		counters.search(probes);
 
-		let entry = self.entries[idx - 1];
-		if ptr >= entry.end_old {
+		let entry = idx.checked_sub(1).map(|idx| self.entries[idx]).filter(|entry| ptr < entry.end_old);
+		let Some(entry) = entry else {
+			counters.miss();
 			return None;
-		}
+		};
 
+		counters.range_hit();
 		Some(ResolvedPtr {
 			entry,
 			byte_offset: (ptr - entry.start_old) as usize,
 		})
 	}
 
+	/// Snapshot of lookup counters since the index was built.
+	#[cfg(feature = "probe-stats")]
+	pub fn stats(&self) -> PointerIndexStats {
+		let counters = &self.counters;
+		PointerIndexStats {
+			entries: self.entries.len(),
+			lookups: counters.lookups.load(Ordering::Relaxed),
+			exact_hits: counters.exact_hits.load(Ordering::Relaxed),
+			range_hits: counters.range_hits.load(Ordering::Relaxed),
+			misses: counters.misses.load(Ordering::Relaxed),
+			probes: counters.probes.load(Ordering::Relaxed),
+			max_probes: counters.max_probes.load(Ordering::Relaxed),
+		}
+	}
+
 	/// Return detected pointer-ID storage behavior for this file.
 	pub fn storage(&self) -> PointerStorage {
 		self.storage
@@ -190,6 +302,35 @@ impl<'a> PointerIndex<'a> {
 		Ok(canonical)
 	}
 
+	/// Report indexed ranges that start inside an earlier range.
+	///
+	/// Range lookups resolve into the entry with the greatest start, so the
+	/// overlapped tail of the earlier range is shadowed.
+	pub fn overlap_warnings(&self) -> Vec<Warning> {
+		let mut out = Vec::new();
+		let mut widest: Option<&PtrEntry<'a>> = None;
+
+		for entry in &self.entries {
+			if let Some(prev) = widest
+				&& entry.start_old < prev.end_old
+			{
+				out.push(Warning::new(
+					WarningKind::OverlappingPtrRange,
+					Some(entry.start_old),
+					format!(
+						"range 0x{:016x}..0x{:016x} overlaps 0x{:016x}..0x{:016x}",
+						entry.start_old, entry.end_old, prev.start_old, prev.end_old
+					),
+				));
+			}
+			if widest.is_none_or(|prev| entry.end_old > prev.end_old) {
+				widest = Some(entry);
+			}
+		}
+
+		out
+	}
+
 	/// Return all indexed entries in sorted order.
 	pub fn entries(&self) -> &[PtrEntry<'a>] {
 		&self.entries
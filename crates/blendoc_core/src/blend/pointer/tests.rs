@@ -113,3 +113,87 @@ mod stable_pointer_ids {
 		assert!(inside.is_none(), "stable-id mode should not resolve non-exact identifiers");
 	}
 }
+
+mod synthetic_overlaps {
+	use crate::blend::{BHead, Block, PointerIndex, PtrEntry, WarningKind};
+
+	#[test]
+	fn overlap_warnings_flag_ranges_inside_earlier_entries() {
+		let payload = [0_u8; 32];
+		let entry = |start_old: u64, len: u64| PtrEntry {
+			start_old,
+			end_old: start_old + len,
+			block: Block {
+				head: BHead {
+					code: *b"DATA",
+					sdna_nr: 0,
+					old: start_old,
+					len,
+					nr: 1,
+				},
+				payload: &payload[..len as usize],
+				file_offset: 0,
+			},
+		};
+
+		let index = PointerIndex::from_entries_for_test(vec![entry(0x1000, 32), entry(0x1010, 8), entry(0x1018, 8), entry(0x2000, 8)]);
+		let warnings = index.overlap_warnings();
+
+		assert_eq!(warnings.len(), 2);
+		assert!(warnings.iter().all(|item| item.kind == WarningKind::OverlappingPtrRange));
+		assert_eq!(warnings[0].ptr, Some(0x1010));
+		assert_eq!(warnings[1].ptr, Some(0x1018));
+	}
+}
+
+#[cfg(feature = "probe-stats")]
+mod lookup_stats {
+	use crate::blend::{BHead, Block, PointerIndex, PtrEntry};
+
+	#[test]
+	fn stats_count_exact_range_and_missed_lookups_within_log_bound() {
+		let payload = [0_u8; 16];
+		let count = 1000_u64;
+		let entries = (0..count)
+			.map(|idx| {
+				let start_old = 0x10_0000 + idx * 0x100;
+				PtrEntry {
+					start_old,
+					end_old: start_old + 16,
+					block: Block {
+						head: BHead {
+							code: *b"DATA",
+							sdna_nr: 0,
+							old: start_old,
+							len: 16,
+							nr: 1,
+						},
+						payload: &payload,
+						file_offset: 0,
+					},
+				}
+			})
+			.collect();
+		let index = PointerIndex::from_entries_for_test(entries);
+		assert_eq!(index.stats().lookups, 0);
+
+		assert!(index.resolve(0).is_none(), "null is not a lookup");
+		for idx in 0..count {
+			let start = 0x10_0000 + idx * 0x100;
+			assert_eq!(index.resolve(start).expect("exact start").byte_offset, 0);
+			assert_eq!(index.resolve(start + 8).expect("inside range").byte_offset, 8);
+			assert!(index.resolve(start + 0x80).is_none(), "gap between ranges");
+		}
+		assert!(index.resolve(0x10).is_none(), "before the first range");
+
+		let stats = index.stats();
+		assert_eq!(stats.entries, count as usize);
+		assert_eq!(stats.lookups, count * 3 + 1);
+		assert_eq!(stats.exact_hits, count);
+		assert_eq!(stats.range_hits, count);
+		assert_eq!(stats.misses, count + 1);
+		let bound = u64::from(count.ilog2()) + 2;
+		assert!(stats.max_probes <= bound, "max_probes {} exceeds log bound {bound}", stats.max_probes);
+		assert!(stats.probes <= (count * 2 + 1) * bound);
+	}
+}
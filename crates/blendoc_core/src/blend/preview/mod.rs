@@ -0,0 +1,156 @@
+use crate::blend::{BlendFile, DecodeOptions, Dna, PointerIndex, Result, StructValue, Value, chase_ptr_to_struct, decode_ptr_instance, scan_id_blocks};
+
+/// Slot labels for `PreviewImage` rects, indexed like Blender's `eIconSizes`.
+const SIZE_LABELS: [&str; 2] = ["icon", "preview"];
+
+/// One decoded preview rect.
+#[derive(Debug, Clone)]
+pub struct PreviewRect {
+	/// Size slot label (`icon` or `preview`).
+	pub size: &'static str,
+	/// Width in pixels.
+	pub width: u32,
+	/// Height in pixels.
+	pub height: u32,
+	/// RGBA8 pixels in top-down row order.
+	pub rgba: Vec<u8>,
+}
+
+/// Stored previews attached to one ID.
+#[derive(Debug, Clone)]
+pub struct IdPreview {
+	/// ID block pointer.
+	pub id_ptr: u64,
+	/// ID block code.
+	pub code: [u8; 4],
+	/// ID-root struct type name.
+	pub type_name: Box<str>,
+	/// `ID.name` of the owner.
+	pub id_name: Box<str>,
+	/// `PreviewImage` pointer.
+	pub preview_ptr: u64,
+	/// Rects whose pixel data was written to the file.
+	pub rects: Vec<PreviewRect>,
+}
+
+/// Scan ID roots for a `preview` field and extract stored `PreviewImage` rects.
+///
+/// Blender normally writes only the large preview rect; slots without pixel
+/// data are omitted, and IDs with no stored rect are skipped entirely.
+pub fn scan_id_previews(file: &BlendFile, dna: &Dna) -> Result<Vec<IdPreview>> {
+	let index = file.pointer_index()?;
+	let records = scan_id_blocks(file, dna)?;
+	let decode = DecodeOptions {
+		max_depth: 4,
+		..DecodeOptions::default()
+	};
+
+	let mut out = Vec::new();
+	for record in records {
+		let Ok((_, owner)) = decode_ptr_instance(dna, &index, record.old_ptr, &decode) else {
+			continue;
+		};
+		let Some(preview_ptr) = ptr_field(&owner, "preview").filter(|ptr| *ptr != 0) else {
+			continue;
+		};
+		let Ok(Some((_, preview))) = chase_ptr_to_struct(dna, &index, preview_ptr, &decode) else {
+			continue;
+		};
+
+		let rects = decode_preview_rects(&index, &preview);
+		if rects.is_empty() {
+			continue;
+		}
+		out.push(IdPreview {
+			id_ptr: record.old_ptr,
+			code: record.code,
+			type_name: record.type_name,
+			id_name: record.id_name,
+			preview_ptr,
+			rects,
+		});
+	}
+
+	Ok(out)
+}
+
+/// Read pixel rects referenced by a decoded `PreviewImage`.
+pub fn decode_preview_rects(index: &PointerIndex<'_>, preview: &StructValue) -> Vec<PreviewRect> {
+	let widths = uint_array(preview, "w");
+	let heights = uint_array(preview, "h");
+	let rect_ptrs = ptr_array(preview, "rect");
+
+	let mut out = Vec::new();
+	for (slot, size) in SIZE_LABELS.iter().enumerate() {
+		let (Some(&width), Some(&height), Some(&ptr)) = (widths.get(slot), heights.get(slot), rect_ptrs.get(slot)) else {
+			continue;
+		};
+		if width == 0 || height == 0 || ptr == 0 {
+			continue;
+		}
+		let Some(len) = (width as usize).checked_mul(height as usize).and_then(|pixels| pixels.checked_mul(4)) else {
+			continue;
+		};
+		let Some(bytes) = index.resolve(ptr).and_then(|resolved| resolved.slice_from(len)) else {
+			continue;
+		};
+
+		out.push(PreviewRect {
+			size,
+			width,
+			height,
+			rgba: flip_rows(bytes, width as usize * 4),
+		});
+	}
+	out
+}
+
+/// Blender stores rects bottom-up; image formats expect top-down rows.
+pub(crate) fn flip_rows(bytes: &[u8], stride: usize) -> Vec<u8> {
+	let mut out = Vec::with_capacity(bytes.len());
+	for row in bytes.chunks_exact(stride).rev() {
+		out.extend_from_slice(row);
+	}
+	out
+}
+
+fn field<'v>(item: &'v StructValue, name: &str) -> Option<&'v Value> {
+	item.fields.iter().find(|field| field.name.as_ref() == name).map(|field| &field.value)
+}
+
+fn ptr_field(item: &StructValue, name: &str) -> Option<u64> {
+	match field(item, name)? {
+		Value::Ptr(ptr) => Some(*ptr),
+		_ => None,
+	}
+}
+
+fn uint_array(item: &StructValue, name: &str) -> Vec<u32> {
+	let Some(Value::Array(values)) = field(item, name) else {
+		return Vec::new();
+	};
+	values
+		.iter()
+		.map(|value| match value {
+			Value::U64(value) => u32::try_from(*value).unwrap_or(0),
+			Value::I64(value) => u32::try_from(*value).unwrap_or(0),
+			_ => 0,
+		})
+		.collect()
+}
+
+fn ptr_array(item: &StructValue, name: &str) -> Vec<u64> {
+	let Some(Value::Array(values)) = field(item, name) else {
+		return Vec::new();
+	};
+	values
+		.iter()
+		.map(|value| match value {
+			Value::Ptr(ptr) => *ptr,
+			_ => 0,
+		})
+		.collect()
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,66 @@
+mod synthetic_rects {
+	use crate::blend::{BHead, Block, FieldValue, PointerIndex, PtrEntry, StructValue, Value, decode_preview_rects};
+
+	fn preview(w: [u64; 2], h: [u64; 2], rect: [u64; 2]) -> StructValue {
+		let array = |values: [Value; 2]| Value::Array(values.to_vec());
+		StructValue {
+			type_name: "PreviewImage".into(),
+			fields: vec![
+				FieldValue {
+					name: "w".into(),
+					value: array([Value::U64(w[0]), Value::U64(w[1])]),
+				},
+				FieldValue {
+					name: "h".into(),
+					value: array([Value::U64(h[0]), Value::U64(h[1])]),
+				},
+				FieldValue {
+					name: "rect".into(),
+					value: array([Value::Ptr(rect[0]), Value::Ptr(rect[1])]),
+				},
+			],
+		}
+	}
+
+	fn raw_block(old: u64, payload: &[u8]) -> PtrEntry<'_> {
+		PtrEntry {
+			start_old: old,
+			end_old: old + payload.len() as u64,
+			block: Block {
+				head: BHead {
+					code: *b"DATA",
+					sdna_nr: 0,
+					old,
+					len: payload.len() as u64,
+					nr: 1,
+				},
+				payload,
+				file_offset: 0,
+			},
+		}
+	}
+
+	#[test]
+	fn stored_rect_is_flipped_to_top_down_rows() {
+		// 2x2 image stored bottom-up: row0 = red, red; row1 = blue, blue.
+		let pixels = [255, 0, 0, 255, 255, 0, 0, 255, 0, 0, 255, 255, 0, 0, 255, 255];
+		let index = PointerIndex::from_entries_for_test(vec![raw_block(0x8000, &pixels)]);
+
+		let rects = decode_preview_rects(&index, &preview([16, 2], [16, 2], [0, 0x8000]));
+
+		assert_eq!(rects.len(), 1);
+		assert_eq!(rects[0].size, "preview");
+		assert_eq!((rects[0].width, rects[0].height), (2, 2));
+		assert_eq!(&rects[0].rgba[..4], &[0, 0, 255, 255]);
+		assert_eq!(&rects[0].rgba[8..12], &[255, 0, 0, 255]);
+	}
+
+	#[test]
+	fn short_or_missing_rect_data_is_skipped() {
+		let pixels = [0_u8; 8];
+		let index = PointerIndex::from_entries_for_test(vec![raw_block(0x8000, &pixels)]);
+
+		assert!(decode_preview_rects(&index, &preview([0, 4], [0, 4], [0, 0x8000])).is_empty());
+		assert!(decode_preview_rects(&index, &preview([0, 2], [0, 1], [0, 0x9000])).is_empty());
+	}
+}
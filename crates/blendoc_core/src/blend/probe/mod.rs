@@ -0,0 +1,369 @@
+use std::collections::HashSet;
+use std::fs::File;
+use std::io::{self, BufReader, Read, Seek, SeekFrom};
+use std::path::Path;
+
+use crate::blend::bytes::Cursor;
+use crate::blend::compression::{BLEND_MAGIC, GZIP_MAGIC, ZSTD_MAGIC};
+use crate::blend::{BHeadLayout, BlendError, BlendHeader, Compression, Result, codes};
+
+/// Magic of the skippable frame holding a zstd seek table.
+const SKIPPABLE_SEEK_TABLE_MAGIC: u32 = 0x184D_2A5E;
+/// Trailing magic of the zstd seekable format footer.
+const SEEKABLE_MAGIC: u32 = 0x8F92_EAB1;
+/// Footer size: frame count, descriptor byte, and seekable magic.
+const SEEK_FOOTER_SIZE: u64 = 9;
+
+/// Summary of a block-header-only scan from [`probe_file`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct OpenProbe {
+	/// Parsed file header.
+	pub header: BlendHeader,
+	/// Block-header layout negotiated from the header.
+	pub bhead_layout: BHeadLayout,
+	/// Compression detected from the leading magic.
+	pub compression: Compression,
+	/// Size of the file on disk.
+	pub file_size: u64,
+	/// Decoded bytes covered by the scan (through `ENDB`, or to end of data).
+	pub decoded_size: u64,
+	/// Number of block headers read, including `ENDB`.
+	pub block_count: u32,
+	/// Number of distinct block codes.
+	pub code_count: usize,
+	/// Sum of declared payload lengths (skipped, never read).
+	pub payload_bytes: u64,
+	/// Decoded offset of the first `DNA1` block header.
+	pub dna_offset: Option<u64>,
+	/// Decoded offset of the `ENDB` block header.
+	pub endb_offset: Option<u64>,
+	/// Frames listed in a zstd seek table; 0 when the file is not seekable zstd.
+	pub seek_frames: usize,
+	/// Seek-table frames actually decompressed to reach every block header.
+	pub frames_decoded: usize,
+}
+
+impl OpenProbe {
+	/// Return whether both the `DNA1` and `ENDB` blocks were found.
+	pub fn is_complete(&self) -> bool {
+		self.dna_offset.is_some() && self.endb_offset.is_some()
+	}
+}
+
+/// Probe a `.blend` file by reading block headers only.
+///
+/// Payloads are skipped, never read: raw files seek past them, and zstd files
+/// written in the seekable format jump straight to the frame holding the next
+/// block header. Plain zstd and gzip streams still decompress sequentially but
+/// are never buffered whole. The scan stops at `ENDB`.
+pub fn probe_file(path: impl AsRef<Path>) -> Result<OpenProbe> {
+	let mut file = File::open(path)?;
+	let file_size = file.metadata()?.len();
+	let mut magic = [0_u8; 7];
+	let magic_len = fill(&mut file, &mut magic)?;
+	let magic = &magic[..magic_len];
+	file.seek(SeekFrom::Start(0))?;
+
+	let (compression, mut source) = if magic.starts_with(BLEND_MAGIC) {
+		(Compression::None, Source::Raw(BufReader::new(file)))
+	} else if magic.starts_with(&ZSTD_MAGIC) {
+		let source = match read_seek_table(&mut file, file_size)? {
+			Some(frames) => Source::Seekable(SeekableZstd::new(file, frames)),
+			None => Source::Stream(Box::new(zstd::stream::read::Decoder::new(file)?)),
+		};
+		(Compression::Zstd, source)
+	} else if magic.starts_with(&GZIP_MAGIC) {
+		(
+			Compression::Gzip,
+			Source::Stream(Box::new(flate2::read::MultiGzDecoder::new(BufReader::new(file)))),
+		)
+	} else {
+		let mut first4 = [0_u8; 4];
+		first4[..magic.len().min(4)].copy_from_slice(&magic[..magic.len().min(4)]);
+		return Err(BlendError::UnknownMagic { magic: first4 });
+	};
+
+	let mut prefix = [0_u8; BlendHeader::MIN_SIZE];
+	let prefix_len = fill(&mut source, &mut prefix)?;
+	if compression != Compression::None && !prefix[..prefix_len].starts_with(BLEND_MAGIC) {
+		return Err(BlendError::NotBlendAfterDecompress);
+	}
+	let header = BlendHeader::parse(&prefix[..prefix_len])?;
+	let bhead_layout = header.bhead_layout()?;
+	let head_size = bhead_layout.capabilities().size;
+
+	let mut probe = OpenProbe {
+		header,
+		bhead_layout,
+		compression,
+		file_size,
+		decoded_size: 0,
+		block_count: 0,
+		code_count: 0,
+		payload_bytes: 0,
+		dna_offset: None,
+		endb_offset: None,
+		seek_frames: source.seek_frames(),
+		frames_decoded: 0,
+	};
+	// Header bytes read past `header_size` already belong to the first block header.
+	let mut pending = prefix[header.header_size.min(prefix_len)..prefix_len].to_vec();
+	let mut pos = header.header_size as u64;
+	let mut codes = HashSet::new();
+	let mut head = vec![0_u8; head_size];
+
+	loop {
+		let carried = pending.len();
+		head[..carried].copy_from_slice(&pending);
+		pending.clear();
+		let read = carried + fill(&mut source, &mut head[carried..])?;
+		if read == 0 {
+			break;
+		}
+		if read < head_size {
+			return Err(BlendError::UnexpectedEof {
+				at: pos as usize,
+				need: head_size,
+				rem: read,
+			});
+		}
+
+		let at = pos;
+		let bhead = bhead_layout.parse(&mut Cursor::new(&head), header.endianness)?;
+		pos += head_size as u64;
+		probe.block_count += 1;
+		codes.insert(bhead.code);
+		if bhead.code == codes::DNA1 && probe.dna_offset.is_none() {
+			probe.dna_offset = Some(at);
+		}
+		if bhead.is_endb() {
+			probe.endb_offset = Some(at);
+			break;
+		}
+
+		let skipped = source.skip(bhead.len)?;
+		if skipped < bhead.len {
+			return Err(BlendError::BlockLenOutOfRange {
+				at: at as usize,
+				len: bhead.len,
+				rem: skipped as usize,
+			});
+		}
+		pos += bhead.len;
+		probe.payload_bytes += bhead.len;
+	}
+
+	probe.decoded_size = pos;
+	probe.code_count = codes.len();
+	probe.frames_decoded = source.frames_decoded();
+	Ok(probe)
+}
+
+/// Decoded byte stream the probe walks.
+enum Source {
+	/// Uncompressed file; payloads are skipped with a relative seek.
+	Raw(BufReader<File>),
+	/// Sequential decompressor; payloads are decoded and discarded.
+	Stream(Box<dyn Read>),
+	/// zstd seekable format; payloads spanning frames jump via the seek table.
+	Seekable(SeekableZstd),
+}
+
+impl Read for Source {
+	fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+		match self {
+			Self::Raw(reader) => reader.read(buf),
+			Self::Stream(reader) => reader.read(buf),
+			Self::Seekable(reader) => reader.read(buf),
+		}
+	}
+}
+
+impl Source {
+	/// Advance `len` decoded bytes, returning how many were available.
+	fn skip(&mut self, len: u64) -> io::Result<u64> {
+		match self {
+			Self::Raw(reader) => {
+				let pos = reader.stream_position()?;
+				let end = reader.get_ref().metadata()?.len();
+				let step = len.min(end.saturating_sub(pos));
+				reader.seek_relative(step as i64)?;
+				Ok(step)
+			}
+			Self::Stream(reader) => io::copy(&mut reader.take(len), &mut io::sink()),
+			Self::Seekable(reader) => reader.skip(len),
+		}
+	}
+
+	fn seek_frames(&self) -> usize {
+		match self {
+			Self::Seekable(reader) => reader.frames.len(),
+			_ => 0,
+		}
+	}
+
+	fn frames_decoded(&self) -> usize {
+		match self {
+			Self::Seekable(reader) => reader.opened,
+			_ => 0,
+		}
+	}
+}
+
+/// One frame entry from a zstd seek table.
+#[derive(Debug, Clone, Copy)]
+struct SeekFrame {
+	compressed_offset: u64,
+	compressed_size: u64,
+	decoded_offset: u64,
+	decoded_size: u64,
+}
+
+/// Read the seek table at the end of a zstd seekable file, if present.
+///
+/// A table whose frame sizes do not add up to the file size is ignored so
+/// the caller falls back to sequential decoding.
+fn read_seek_table(file: &mut File, file_size: u64) -> Result<Option<Vec<SeekFrame>>> {
+	if file_size < SEEK_FOOTER_SIZE + 8 {
+		return Ok(None);
+	}
+	let mut footer = [0_u8; SEEK_FOOTER_SIZE as usize];
+	file.seek(SeekFrom::Start(file_size - SEEK_FOOTER_SIZE))?;
+	file.read_exact(&mut footer)?;
+	let frame_count = u64::from(u32::from_le_bytes([footer[0], footer[1], footer[2], footer[3]]));
+	let has_checksums = footer[4] & 0x80 != 0;
+	if u32::from_le_bytes([footer[5], footer[6], footer[7], footer[8]]) != SEEKABLE_MAGIC {
+		file.seek(SeekFrom::Start(0))?;
+		return Ok(None);
+	}
+
+	let entry_size = if has_checksums { 12 } else { 8 };
+	let table_size = frame_count * entry_size + SEEK_FOOTER_SIZE;
+	let Some(table_start) = file_size.checked_sub(table_size + 8) else {
+		file.seek(SeekFrom::Start(0))?;
+		return Ok(None);
+	};
+	let mut table = vec![0_u8; (table_size + 8) as usize];
+	file.seek(SeekFrom::Start(table_start))?;
+	file.read_exact(&mut table)?;
+	file.seek(SeekFrom::Start(0))?;
+
+	let word = |at: usize| u32::from_le_bytes([table[at], table[at + 1], table[at + 2], table[at + 3]]);
+	if word(0) != SKIPPABLE_SEEK_TABLE_MAGIC || u64::from(word(4)) != table_size {
+		return Ok(None);
+	}
+
+	let mut frames = Vec::with_capacity(frame_count as usize);
+	let (mut compressed_offset, mut decoded_offset) = (0_u64, 0_u64);
+	for entry in 0..frame_count as usize {
+		let at = 8 + entry * entry_size as usize;
+		let frame = SeekFrame {
+			compressed_offset,
+			compressed_size: u64::from(word(at)),
+			decoded_offset,
+			decoded_size: u64::from(word(at + 4)),
+		};
+		compressed_offset += frame.compressed_size;
+		decoded_offset += frame.decoded_size;
+		frames.push(frame);
+	}
+	Ok((compressed_offset == table_start).then_some(frames))
+}
+
+type FrameDecoder = zstd::stream::read::Decoder<'static, BufReader<io::Take<File>>>;
+
+/// Reader over a zstd seekable file that decodes one frame at a time.
+struct SeekableZstd {
+	file: File,
+	frames: Vec<SeekFrame>,
+	/// Frame the decoder is positioned in.
+	frame: usize,
+	decoder: Option<FrameDecoder>,
+	/// Decoded offset of the next byte `read` returns.
+	pos: u64,
+	/// Number of frames a decoder was opened for.
+	opened: usize,
+}
+
+impl SeekableZstd {
+	fn new(file: File, frames: Vec<SeekFrame>) -> Self {
+		Self {
+			file,
+			frames,
+			frame: 0,
+			decoder: None,
+			pos: 0,
+			opened: 0,
+		}
+	}
+
+	fn open_frame(&mut self, index: usize) -> io::Result<&mut FrameDecoder> {
+		let frame = self.frames[index];
+		self.file.seek(SeekFrom::Start(frame.compressed_offset))?;
+		let compressed = self.file.try_clone()?.take(frame.compressed_size);
+		self.frame = index;
+		self.opened += 1;
+		Ok(self.decoder.insert(zstd::stream::read::Decoder::new(compressed)?))
+	}
+
+	fn skip(&mut self, len: u64) -> io::Result<u64> {
+		let total = self.frames.last().map_or(0, |frame| frame.decoded_offset + frame.decoded_size);
+		let target = self.pos.saturating_add(len).min(total);
+		let Some(index) = self.frames.iter().position(|frame| target < frame.decoded_offset + frame.decoded_size) else {
+			// Skipping to the very end: nothing left to decode.
+			let skipped = target - self.pos;
+			self.frame = self.frames.len();
+			self.decoder = None;
+			self.pos = target;
+			return Ok(skipped);
+		};
+
+		let start = self.pos;
+		let discard = if index == self.frame && self.decoder.is_some() {
+			target - self.pos
+		} else {
+			self.open_frame(index)?;
+			target - self.frames[index].decoded_offset
+		};
+		let decoder = self.decoder.as_mut().expect("frame decoder open");
+		let discarded = io::copy(&mut decoder.take(discard), &mut io::sink())?;
+		self.pos = target - (discard - discarded);
+		Ok(self.pos - start)
+	}
+}
+
+impl Read for SeekableZstd {
+	fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+		while self.frame < self.frames.len() {
+			let decoder = match self.decoder.as_mut() {
+				Some(decoder) => decoder,
+				None => self.open_frame(self.frame)?,
+			};
+			let read = decoder.read(buf)?;
+			if read > 0 {
+				self.pos += read as u64;
+				return Ok(read);
+			}
+			self.decoder = None;
+			self.frame += 1;
+		}
+		Ok(0)
+	}
+}
+
+/// Read until `buf` is full or the reader is exhausted.
+fn fill(reader: &mut impl Read, buf: &mut [u8]) -> io::Result<usize> {
+	let mut read = 0;
+	while read < buf.len() {
+		match reader.read(&mut buf[read..]) {
+			Ok(0) => break,
+			Ok(count) => read += count,
+			Err(err) if err.kind() == io::ErrorKind::Interrupted => {}
+			Err(err) => return Err(err),
+		}
+	}
+	Ok(read)
+}
+
+#[cfg(test)]
+mod tests;
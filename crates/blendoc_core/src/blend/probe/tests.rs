@@ -0,0 +1,97 @@
+mod synthetic_probe {
+	use blendoc_testkit::builder::{BlendBuilder, HeaderKind, scene_file};
+
+	use crate::blend::{BlendError, BlendFile, Compression, OpenProbe, encode_bytes, probe_file};
+
+	/// One Large-header file with a 256 KiB raw payload between the header and `DNA1`.
+	fn padded_file() -> Vec<u8> {
+		let mut builder = BlendBuilder::new(HeaderKind::Large, 500, false);
+		builder.sdna_mut().add_struct("Link", &[("Link", "*next"), ("Link", "*prev")]);
+		builder.block(*b"DATA", 0, 0x1000, 1, vec![0x5a; 256 * 1024]);
+		builder.build()
+	}
+
+	/// Encode `bytes` in the zstd seekable format with fixed-size frames.
+	fn seekable_zstd(bytes: &[u8], frame_size: usize) -> Vec<u8> {
+		let mut out = Vec::new();
+		let mut table = Vec::new();
+		for chunk in bytes.chunks(frame_size) {
+			let frame = zstd::bulk::compress(chunk, 3).expect("frame compresses");
+			table.extend_from_slice(&(frame.len() as u32).to_le_bytes());
+			table.extend_from_slice(&(chunk.len() as u32).to_le_bytes());
+			out.extend_from_slice(&frame);
+		}
+		let frame_count = bytes.len().div_ceil(frame_size) as u32;
+		table.extend_from_slice(&frame_count.to_le_bytes());
+		table.push(0);
+		table.extend_from_slice(&0x8F92_EAB1_u32.to_le_bytes());
+		out.extend_from_slice(&0x184D_2A5E_u32.to_le_bytes());
+		out.extend_from_slice(&(table.len() as u32).to_le_bytes());
+		out.extend_from_slice(&table);
+		out
+	}
+
+	fn probe_bytes(tag: &str, bytes: &[u8]) -> crate::blend::Result<OpenProbe> {
+		let target = std::env::temp_dir().join(format!("blendoc_probe_{tag}_{}.blend", std::process::id()));
+		std::fs::write(&target, bytes).expect("synthetic file written");
+		let probe = probe_file(&target);
+		std::fs::remove_file(&target).expect("synthetic file removed");
+		probe
+	}
+
+	#[test]
+	fn probe_matches_full_open_for_every_compression() {
+		for kind in [HeaderKind::Legacy4, HeaderKind::Legacy8, HeaderKind::Large] {
+			let raw = scene_file(kind, false);
+			let blend = BlendFile::from_bytes(raw.clone()).expect("synthetic file parses");
+			let stats = blend.scan_block_stats().expect("blocks scan");
+
+			for compression in [Compression::None, Compression::Zstd, Compression::Gzip] {
+				let encoded = encode_bytes(&raw, compression, None).expect("file encodes");
+				let probe = probe_bytes(compression.as_str(), &encoded).expect("probe succeeds");
+				assert_eq!(probe.header, blend.header);
+				assert_eq!(probe.compression, compression);
+				assert_eq!(probe.file_size, encoded.len() as u64);
+				assert_eq!(probe.decoded_size, raw.len() as u64);
+				assert_eq!(probe.block_count, stats.block_count);
+				assert_eq!(probe.code_count, stats.codes.len());
+				assert!(probe.is_complete());
+				let dna = blend.find_first_block_by_code(*b"DNA1").expect("blocks scan").expect("DNA1 present");
+				assert_eq!(probe.dna_offset, Some(dna.file_offset as u64));
+				// The zstd encoder writes a one-frame seek table for these small files.
+				assert_eq!(probe.seek_frames, usize::from(compression == Compression::Zstd));
+			}
+		}
+	}
+
+	#[test]
+	fn seekable_zstd_skips_frames_inside_payloads() {
+		let raw = padded_file();
+		let seekable = seekable_zstd(&raw, 4096);
+		let probe = probe_bytes("seekable", &seekable).expect("probe succeeds");
+
+		assert_eq!(probe.compression, Compression::Zstd);
+		assert_eq!(probe.block_count, 3);
+		assert_eq!(probe.decoded_size, raw.len() as u64);
+		assert!(probe.is_complete());
+		assert_eq!(probe.seek_frames, raw.len().div_ceil(4096));
+		assert!(probe.frames_decoded <= 3, "decoded {} of {} frames", probe.frames_decoded, probe.seek_frames);
+		assert_eq!(
+			BlendFile::from_bytes(seekable).expect("seekable file still decodes whole").bytes(),
+			raw.as_slice()
+		);
+	}
+
+	#[test]
+	fn truncated_payload_and_missing_endb_are_reported() {
+		let raw = padded_file();
+		let truncated = probe_bytes("truncated", &raw[..raw.len() / 2]);
+		assert!(matches!(truncated, Err(BlendError::BlockLenOutOfRange { .. })), "{truncated:?}");
+
+		let endb_size = 32;
+		let open_ended = probe_bytes("no_endb", &raw[..raw.len() - endb_size]).expect("probe succeeds");
+		assert!(open_ended.dna_offset.is_some());
+		assert_eq!(open_ended.endb_offset, None);
+		assert!(!open_ended.is_complete());
+	}
+}
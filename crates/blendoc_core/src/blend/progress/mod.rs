@@ -0,0 +1,69 @@
+use std::fmt;
+use std::sync::Arc;
+
+/// Unit of work a long-running scan reports progress in.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ProgressStage {
+	/// File blocks visited by a block scan.
+	Blocks,
+	/// ID records processed by an ID graph or inbound-reference scan.
+	Ids,
+}
+
+impl ProgressStage {
+	/// Stable machine label for progress output.
+	pub fn as_str(self) -> &'static str {
+		match self {
+			Self::Blocks => "blocks",
+			Self::Ids => "ids",
+		}
+	}
+}
+
+/// Receiver of scan progress.
+///
+/// Scans call [`ProgressSink::update`] once per unit of work, so
+/// implementations should throttle any expensive rendering themselves.
+pub trait ProgressSink: Send + Sync {
+	/// `done` units of `stage` are complete, out of `total` when known up front.
+	fn update(&self, stage: ProgressStage, done: u64, total: Option<u64>);
+
+	/// `stage` is complete; called once after the last update.
+	fn finish(&self, stage: ProgressStage) {
+		let _ = stage;
+	}
+}
+
+/// Shared handle to a [`ProgressSink`], stored in scan options.
+///
+/// Clones report to the same sink.
+#[derive(Clone)]
+pub struct Progress {
+	sink: Arc<dyn ProgressSink>,
+}
+
+impl Progress {
+	/// Wrap a sink.
+	pub fn new(sink: impl ProgressSink + 'static) -> Self {
+		Self { sink: Arc::new(sink) }
+	}
+
+	/// Forward one update to the sink.
+	pub fn update(&self, stage: ProgressStage, done: u64, total: Option<u64>) {
+		self.sink.update(stage, done, total);
+	}
+
+	/// Forward stage completion to the sink.
+	pub fn finish(&self, stage: ProgressStage) {
+		self.sink.finish(stage);
+	}
+}
+
+impl fmt::Debug for Progress {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		f.write_str("Progress")
+	}
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,77 @@
+use std::sync::{Arc, Mutex};
+
+use blendoc_testkit::builder::{HeaderKind, scene_file};
+
+use crate::blend::{
+	BlendFile, IdGraphOptions, IdIndex, IdScanOptions, Progress, ProgressSink, ProgressStage, XrefOptions, build_id_graph, find_inbound_refs_to_ptr,
+	scan_id_blocks_with,
+};
+
+/// One recorded `(stage, done, total)` update.
+type Event = (ProgressStage, u64, Option<u64>);
+
+/// Records every update and finish call.
+#[derive(Clone, Default)]
+struct Recorder {
+	events: Arc<Mutex<Vec<Event>>>,
+	finished: Arc<Mutex<Vec<ProgressStage>>>,
+}
+
+impl ProgressSink for Recorder {
+	fn update(&self, stage: ProgressStage, done: u64, total: Option<u64>) {
+		self.events.lock().expect("events lock").push((stage, done, total));
+	}
+
+	fn finish(&self, stage: ProgressStage) {
+		self.finished.lock().expect("finished lock").push(stage);
+	}
+}
+
+#[test]
+fn scans_report_blocks_then_ids() {
+	let blend = BlendFile::from_bytes(scene_file(HeaderKind::Legacy8, false)).expect("synthetic file parses");
+	let dna = blend.dna().expect("dna parses");
+	let index = blend.pointer_index().expect("pointer index builds");
+	let recorder = Recorder::default();
+	let progress = Some(Progress::new(recorder.clone()));
+
+	let scan = IdScanOptions {
+		progress: progress.clone(),
+		..IdScanOptions::default()
+	};
+	let ids = IdIndex::build(scan_id_blocks_with(&blend, &dna, &scan).expect("id scan succeeds"));
+	let blocks = blend.blocks().count() as u64;
+	{
+		let events = recorder.events.lock().expect("events lock");
+		assert_eq!(events.len() as u64, blocks);
+		assert_eq!(events.last(), Some(&(ProgressStage::Blocks, blocks, None)));
+	}
+
+	let graph = IdGraphOptions {
+		progress: progress.clone(),
+		..IdGraphOptions::default()
+	};
+	build_id_graph(&dna, &index, &ids, &graph).expect("id graph builds");
+	let xref = XrefOptions {
+		progress,
+		..XrefOptions::default()
+	};
+	find_inbound_refs_to_ptr(&dna, &index, &ids, ids.records[0].old_ptr, &xref).expect("xref succeeds");
+
+	let total = ids.records.len() as u64;
+	let events = recorder.events.lock().expect("events lock");
+	let id_updates: Vec<u64> = events
+		.iter()
+		.filter(|(stage, ..)| *stage == ProgressStage::Ids)
+		.map(|(_, done, count)| {
+			assert_eq!(*count, Some(total));
+			*done
+		})
+		.collect();
+	let expected: Vec<u64> = (1..=total).chain(1..=total).collect();
+	assert_eq!(id_updates, expected);
+	assert_eq!(
+		*recorder.finished.lock().expect("finished lock"),
+		[ProgressStage::Blocks, ProgressStage::Ids, ProgressStage::Ids]
+	);
+}
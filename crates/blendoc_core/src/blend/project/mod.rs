@@ -0,0 +1,308 @@
+use std::collections::{BTreeMap, HashSet};
+use std::fs;
+use std::path::{Component, Path, PathBuf};
+use std::sync::Arc;
+
+use crate::blend::libtree::declared_to_path;
+use crate::blend::{BlendFile, DecodeOptions, Dna, Result, StructValue, Value, codes, decode_ptr_instance, scan_id_blocks, scan_library_records};
+
+/// Which kind of path an [`ExternalRef`] stores.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum ExternalRefKind {
+	/// Linked library `.blend` path.
+	Library,
+	/// External file path on an ID root (`Image.filepath`, `Sound.filepath`, ...).
+	File,
+}
+
+impl ExternalRefKind {
+	/// Stable machine label for JSON/text output.
+	pub fn as_str(self) -> &'static str {
+		match self {
+			Self::Library => "library",
+			Self::File => "file",
+		}
+	}
+}
+
+/// One path to another file stored in a blend file.
+#[derive(Debug, Clone)]
+pub struct ExternalRef {
+	/// Library link or external file.
+	pub kind: ExternalRefKind,
+	/// Path as stored, possibly Blender-relative (`//`).
+	pub declared_path: Arc<str>,
+	/// Owning ID block code.
+	pub code: [u8; 4],
+	/// Owning `ID.name`.
+	pub id_name: Arc<str>,
+}
+
+/// List library paths and the `filepath` of every other ID root.
+pub fn scan_external_refs(file: &BlendFile, dna: &Dna) -> Result<Vec<ExternalRef>> {
+	let mut out: Vec<ExternalRef> = scan_library_records(file, dna)?
+		.into_iter()
+		.filter(|record| !record.library_path.is_empty())
+		.map(|record| ExternalRef {
+			kind: ExternalRefKind::Library,
+			declared_path: record.library_path,
+			code: codes::ID_LI,
+			id_name: record.id_name,
+		})
+		.collect();
+
+	let index = file.pointer_index()?;
+	let decode = DecodeOptions {
+		max_depth: 2,
+		..DecodeOptions::default()
+	};
+	for record in scan_id_blocks(file, dna)? {
+		if record.code == codes::ID_LI {
+			continue;
+		}
+		let Ok((_, root)) = decode_ptr_instance(dna, &index, record.old_ptr, &decode) else {
+			continue;
+		};
+		if let Some(path) = string_field(&root, "filepath") {
+			out.push(ExternalRef {
+				kind: ExternalRefKind::File,
+				declared_path: Arc::from(path),
+				code: record.code,
+				id_name: Arc::from(record.id_name),
+			});
+		}
+	}
+	Ok(out)
+}
+
+/// Settings for [`audit_project`].
+#[derive(Debug, Clone)]
+pub struct ProjectAuditOptions {
+	/// Lowercase extensions of files counted as assets when looking for unreferenced ones.
+	pub asset_extensions: Vec<String>,
+}
+
+impl Default for ProjectAuditOptions {
+	fn default() -> Self {
+		let extensions = [
+			"png", "jpg", "jpeg", "exr", "hdr", "tif", "tiff", "tga", "bmp", "webp", "psd", "wav", "mp3", "ogg", "flac", "mp4", "mov", "avi", "mkv", "abc",
+			"usd", "usda", "usdc", "usdz", "vdb", "ttf", "otf",
+		];
+		Self {
+			asset_extensions: extensions.into_iter().map(str::to_owned).collect(),
+		}
+	}
+}
+
+/// One blend file and ID that reference a [`ProjectTarget`].
+#[derive(Debug, Clone)]
+pub struct ProjectRefUser {
+	/// Referencing blend file.
+	pub blend: PathBuf,
+	/// Owning `ID.name`.
+	pub id_name: Arc<str>,
+	/// Path as stored in `blend`.
+	pub declared_path: Arc<str>,
+}
+
+/// One resolved file referenced from the project, with every reference to it.
+#[derive(Debug, Clone)]
+pub struct ProjectTarget {
+	/// Resolved path: canonical when the file exists, lexically normalized otherwise.
+	pub path: PathBuf,
+	/// Library link or external file.
+	pub kind: ExternalRefKind,
+	/// Whether the file exists on disk.
+	pub exists: bool,
+	/// Whether the path lies outside the project root.
+	pub outside_root: bool,
+	/// References in blend-path then ID order.
+	pub users: Vec<ProjectRefUser>,
+}
+
+impl ProjectTarget {
+	/// Number of distinct blend files referencing this target.
+	pub fn blend_count(&self) -> usize {
+		self.users.iter().map(|user| &user.blend).collect::<HashSet<_>>().len()
+	}
+}
+
+/// External references aggregated across every blend file under a directory.
+#[derive(Debug, Clone)]
+pub struct ProjectAudit {
+	/// Canonical project root.
+	pub root: PathBuf,
+	/// Blend files scanned, sorted.
+	pub blends: Vec<PathBuf>,
+	/// Blend files that could not be opened or parsed, with the error.
+	pub unreadable: Vec<(PathBuf, Arc<str>)>,
+	/// Referenced files sorted by path.
+	pub targets: Vec<ProjectTarget>,
+	/// Asset files under the root that no blend references, sorted.
+	pub unreferenced: Vec<PathBuf>,
+}
+
+impl ProjectAudit {
+	/// Targets referenced by at least `min_blends` distinct blend files.
+	pub fn shared(&self, min_blends: usize) -> Vec<&ProjectTarget> {
+		self.targets.iter().filter(|target| target.blend_count() >= min_blends).collect()
+	}
+
+	/// Targets whose path leaves the project root.
+	pub fn outside_root(&self) -> Vec<&ProjectTarget> {
+		self.targets.iter().filter(|target| target.outside_root).collect()
+	}
+
+	/// Targets with no file on disk.
+	pub fn missing(&self) -> Vec<&ProjectTarget> {
+		self.targets.iter().filter(|target| !target.exists).collect()
+	}
+
+	/// `path` relative to the root when inside it, unchanged otherwise.
+	pub fn display_path<'p>(&self, path: &'p Path) -> &'p Path {
+		path.strip_prefix(&self.root).unwrap_or(path)
+	}
+}
+
+/// Scan every `.blend` under `root` and cross-reference their external paths.
+///
+/// Hidden directories (names starting with `.`) are skipped. `//` paths
+/// resolve against the directory of the blend that stores them. Only a
+/// failure to read `root` itself is returned as `Err`; unreadable blend
+/// files are listed in [`ProjectAudit::unreadable`].
+pub fn audit_project(root: &Path, options: &ProjectAuditOptions) -> Result<ProjectAudit> {
+	let root = fs::canonicalize(root)?;
+	let (blends, assets) = list_project_files(&root, options)?;
+
+	let mut unreadable = Vec::new();
+	let mut targets: BTreeMap<(PathBuf, ExternalRefKind), Vec<ProjectRefUser>> = BTreeMap::new();
+	for blend in &blends {
+		let refs = match BlendFile::open_mmap(blend).and_then(|file| scan_external_refs(&file, &file.dna()?)) {
+			Ok(refs) => refs,
+			Err(err) => {
+				unreadable.push((blend.clone(), Arc::from(err.to_string())));
+				continue;
+			}
+		};
+		let base = blend.parent().unwrap_or(&root);
+		for item in refs {
+			let path = resolve_target(&declared_to_path(base, &item.declared_path));
+			targets.entry((path, item.kind)).or_default().push(ProjectRefUser {
+				blend: blend.clone(),
+				id_name: item.id_name,
+				declared_path: item.declared_path,
+			});
+		}
+	}
+
+	let referenced: HashSet<&PathBuf> = targets.keys().map(|(path, _)| path).collect();
+	let unreferenced = assets.iter().filter(|asset| !referenced.contains(asset)).cloned().collect();
+	let targets = targets
+		.into_iter()
+		.map(|((path, kind), users)| ProjectTarget {
+			exists: path.is_file(),
+			outside_root: !path.starts_with(&root),
+			path,
+			kind,
+			users,
+		})
+		.collect();
+
+	Ok(ProjectAudit {
+		root,
+		blends,
+		unreadable,
+		targets,
+		unreferenced,
+	})
+}
+
+/// Sorted `.blend` files in `root`, and in its non-hidden subdirectories when
+/// `recursive` is set.
+///
+/// Extensions match case-insensitively. Only a failure to read `root` itself
+/// is returned as `Err`; unreadable subdirectories are skipped.
+pub fn blend_files_below(root: &Path, recursive: bool) -> Result<Vec<PathBuf>> {
+	let mut blends = Vec::new();
+	walk_files(root, recursive, |path, extension| {
+		if extension == Some("blend") {
+			blends.push(path);
+		}
+	})?;
+	blends.sort();
+	Ok(blends)
+}
+
+/// Sorted blend files and asset files below `root`, skipping hidden directories.
+fn list_project_files(root: &Path, options: &ProjectAuditOptions) -> Result<(Vec<PathBuf>, Vec<PathBuf>)> {
+	let mut blends = Vec::new();
+	let mut assets = Vec::new();
+	walk_files(root, true, |path, extension| match extension {
+		Some("blend") => blends.push(path),
+		Some(ext) if options.asset_extensions.iter().any(|known| known == ext) => assets.push(path),
+		_ => {}
+	})?;
+	blends.sort();
+	assets.sort();
+	Ok((blends, assets))
+}
+
+/// Call `visit` with every file below `root` and its lowercased extension.
+///
+/// Hidden directories are never entered; subdirectories only when `recursive`.
+fn walk_files(root: &Path, recursive: bool, mut visit: impl FnMut(PathBuf, Option<&str>)) -> Result<()> {
+	let mut pending = vec![root.to_path_buf()];
+	let mut first = true;
+	while let Some(dir) = pending.pop() {
+		let entries = match fs::read_dir(&dir) {
+			Ok(entries) => entries,
+			Err(err) if first => return Err(err.into()),
+			Err(_) => continue,
+		};
+		first = false;
+		for entry in entries.flatten() {
+			let path = entry.path();
+			let Ok(kind) = entry.file_type() else {
+				continue;
+			};
+			let hidden = path.file_name().and_then(|name| name.to_str()).is_some_and(|name| name.starts_with('.'));
+			if kind.is_dir() && recursive && !hidden {
+				pending.push(path);
+			} else if kind.is_file() {
+				let extension = path.extension().and_then(|ext| ext.to_str()).map(str::to_ascii_lowercase);
+				visit(path, extension.as_deref());
+			}
+		}
+	}
+	Ok(())
+}
+
+/// Canonicalize an existing path; otherwise fold `.` and `..` lexically.
+fn resolve_target(path: &Path) -> PathBuf {
+	if let Ok(canonical) = fs::canonicalize(path) {
+		return canonical;
+	}
+	let mut out = PathBuf::new();
+	for component in path.components() {
+		match component {
+			Component::CurDir => {}
+			Component::ParentDir => {
+				if !out.pop() {
+					out.push("..");
+				}
+			}
+			other => out.push(other),
+		}
+	}
+	out
+}
+
+fn string_field<'v>(item: &'v StructValue, name: &str) -> Option<&'v str> {
+	match &item.fields.iter().find(|field| field.name.as_ref() == name)?.value {
+		Value::String(value) if !value.is_empty() => Some(value),
+		_ => None,
+	}
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,130 @@
+mod synthetic_project {
+	use std::path::{Path, PathBuf};
+
+	use blendoc_testkit::builder::{BlendBuilder, HeaderKind};
+
+	use crate::blend::{BlendFile, ExternalRefKind, ProjectAuditOptions, audit_project, blend_files_below, scan_external_refs};
+
+	/// File with one `Library` per library path and one `Image` per image path.
+	fn project_file(libraries: &[&str], images: &[&str]) -> Vec<u8> {
+		let mut builder = BlendBuilder::new(HeaderKind::Legacy8, 404, false);
+		let sdna = builder.sdna_mut();
+		sdna.add_type("char", 1);
+		sdna.add_type("void", 0);
+		sdna.add_struct("ID", &[("void", "*next"), ("void", "*prev"), ("void", "*lib"), ("char", "name[24]")]);
+		let library = sdna.add_struct("Library", &[("ID", "id"), ("char", "name[64]")]);
+		let image = sdna.add_struct("Image", &[("ID", "id"), ("char", "filepath[64]")]);
+
+		for (idx, path) in libraries.iter().enumerate() {
+			let mut payload = builder.payload();
+			payload.ptr(0).ptr(0).ptr(0).name(&format!("LIlib{idx}"), 24).name(path, 64);
+			let payload = payload.finish();
+			builder.block(*b"LI\0\0", library, 0x1000 + idx as u64 * 0x100, 1, payload);
+		}
+		for (idx, path) in images.iter().enumerate() {
+			let mut payload = builder.payload();
+			payload.ptr(0).ptr(0).ptr(0).name(&format!("IMimage{idx}"), 24).name(path, 64);
+			let payload = payload.finish();
+			builder.block(*b"IM\0\0", image, 0x2000 + idx as u64 * 0x100, 1, payload);
+		}
+		builder.build()
+	}
+
+	fn write(path: &Path, bytes: &[u8]) {
+		std::fs::create_dir_all(path.parent().expect("parent dir")).expect("dir created");
+		std::fs::write(path, bytes).expect("file written");
+	}
+
+	/// `a` and `sub/b` share `tex/wood.png`; `a` also links `sub/b` and reaches outside the root.
+	fn fixture_dir() -> PathBuf {
+		let dir = std::env::temp_dir().join(format!("blendoc_project_{}", std::process::id()));
+		let _ = std::fs::remove_dir_all(&dir);
+		write(&dir.join("a.blend"), &project_file(&["//sub/b.blend"], &["//tex/wood.png", "//../outside.png"]));
+		write(&dir.join("sub").join("b.blend"), &project_file(&[], &["//..\\tex\\wood.png", "//missing.png"]));
+		write(&dir.join("broken.blend"), b"not a blend file");
+		write(&dir.join("tex").join("wood.png"), b"png");
+		write(&dir.join("tex").join("unused.PNG"), b"png");
+		write(&dir.join(".git").join("ignored.png"), b"png");
+		dir
+	}
+
+	#[test]
+	fn external_refs_list_libraries_and_file_paths() {
+		let blend = BlendFile::from_bytes(project_file(&["//lib.blend"], &["//tex/a.png"])).expect("file parses");
+		let refs = scan_external_refs(&blend, &blend.dna().expect("dna")).expect("refs scan");
+		let summary: Vec<(ExternalRefKind, &str, &str)> = refs
+			.iter()
+			.map(|item| (item.kind, item.id_name.as_ref(), item.declared_path.as_ref()))
+			.collect();
+		assert_eq!(
+			summary,
+			[
+				(ExternalRefKind::Library, "LIlib0", "//lib.blend"),
+				(ExternalRefKind::File, "IMimage0", "//tex/a.png"),
+			]
+		);
+	}
+
+	#[test]
+	fn audit_reports_shared_outside_missing_and_unreferenced() {
+		let dir = fixture_dir();
+		let audit = audit_project(&dir, &ProjectAuditOptions::default()).expect("audit runs");
+		let _ = std::fs::remove_dir_all(&dir);
+		let rel = |path: &Path| audit.display_path(path).to_string_lossy().into_owned();
+
+		assert_eq!(
+			audit.blends.iter().map(|path| rel(path)).collect::<Vec<_>>(),
+			["a.blend", "broken.blend", "sub/b.blend"]
+		);
+		assert_eq!(audit.unreadable.len(), 1);
+		assert!(audit.unreadable[0].0.ends_with("broken.blend"));
+
+		let shared: Vec<String> = audit.shared(2).iter().map(|target| rel(&target.path)).collect();
+		assert_eq!(shared, ["tex/wood.png"]);
+		assert_eq!(audit.shared(2)[0].users.len(), 2);
+
+		let outside = audit.outside_root();
+		assert_eq!(outside.len(), 1);
+		assert!(outside[0].path.ends_with("outside.png"));
+		assert!(!outside[0].exists);
+
+		let missing: Vec<String> = audit.missing().iter().map(|target| rel(&target.path)).collect();
+		assert!(missing.contains(&"sub/missing.png".to_owned()), "{missing:?}");
+
+		let library = audit
+			.targets
+			.iter()
+			.find(|target| target.kind == ExternalRefKind::Library)
+			.expect("library target");
+		assert_eq!(rel(&library.path), "sub/b.blend");
+		assert!(library.exists);
+
+		let unreferenced: Vec<String> = audit.unreferenced.iter().map(|path| rel(path)).collect();
+		assert_eq!(unreferenced, ["tex/unused.PNG"]);
+	}
+
+	#[test]
+	fn blend_files_below_optionally_recurses_and_skips_hidden_dirs() {
+		let dir = std::env::temp_dir().join(format!("blendoc_project_walk_{}", std::process::id()));
+		let _ = std::fs::remove_dir_all(&dir);
+		write(&dir.join("a.blend"), b"");
+		write(&dir.join("B.BLEND"), b"");
+		write(&dir.join("notes.txt"), b"");
+		write(&dir.join("sub").join("c.blend"), b"");
+		write(&dir.join(".cache").join("d.blend"), b"");
+		let shallow = blend_files_below(&dir, false).expect("root readable");
+		let deep = blend_files_below(&dir, true).expect("root readable");
+		let missing = blend_files_below(&dir.join("missing"), true);
+		let _ = std::fs::remove_dir_all(&dir);
+		let rel = |paths: Vec<PathBuf>| -> Vec<String> {
+			paths
+				.iter()
+				.map(|path| path.strip_prefix(&dir).expect("below root").to_string_lossy().into_owned())
+				.collect()
+		};
+
+		assert_eq!(rel(shallow), ["B.BLEND", "a.blend"]);
+		assert_eq!(rel(deep), ["B.BLEND", "a.blend", "sub/c.blend"]);
+		assert!(missing.is_err());
+	}
+}
@@ -0,0 +1,218 @@
+use std::collections::{HashMap, HashSet};
+use std::io::Write;
+use std::sync::Arc;
+
+use crate::blend::bytes::Cursor;
+use crate::blend::dangling::{for_each_block_pointer, pointer_slots};
+use crate::blend::writer::write_blocks;
+use crate::blend::{BlendError, BlendFile, Dna, IdIndex, PointerIndex, Result, codes};
+
+/// One block a [`PrunePlan`] removes.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PrunedBlock {
+	/// Offset of the block header in the decoded file bytes.
+	pub block_offset: usize,
+	/// Block code.
+	pub code: [u8; 4],
+	/// Stored old address of the block.
+	pub old_ptr: u64,
+	/// Header plus payload bytes the block occupies.
+	pub bytes: u64,
+	/// Pruned ID the block belongs to (its own pointer for the ID block).
+	pub owner: u64,
+}
+
+/// A pointer in a kept ID that lands in a pruned block.
+#[derive(Debug, Clone)]
+pub struct BrokenRef {
+	/// Kept ID holding the pointer.
+	pub owner: u64,
+	/// `ID.name` of the kept ID.
+	pub owner_name: Arc<str>,
+	/// Field path of the pointer: plain for the ID block itself,
+	/// `Type.field` (with `[element]` for arrays) for its `DATA` blocks, and
+	/// a trailing `[i]` for entries of a pointer array.
+	pub field: Arc<str>,
+	/// Stored pointer value.
+	pub ptr: u64,
+	/// Pruned ID owning the block the pointer lands in.
+	pub target: u64,
+}
+
+/// Everything removing a set of IDs would change, from [`plan_prune`].
+#[derive(Debug, Clone, Default)]
+pub struct PrunePlan {
+	/// Pruned ID pointers, in request order.
+	pub ids: Vec<u64>,
+	/// Blocks removed, in file order.
+	pub blocks: Vec<PrunedBlock>,
+	/// Pointers from kept IDs into removed blocks; they dangle after the prune.
+	pub broken_refs: Vec<BrokenRef>,
+	/// Decoded file size before the prune.
+	pub input_bytes: u64,
+	/// Decoded bytes the removed blocks occupy.
+	pub removed_bytes: u64,
+}
+
+impl PrunePlan {
+	/// Decoded file size after the prune.
+	pub fn output_bytes(&self) -> u64 {
+		self.input_bytes.saturating_sub(self.removed_bytes)
+	}
+}
+
+/// Work out which blocks removing the IDs at `remove` drops and which kept IDs it breaks.
+///
+/// Each pruned ID takes its ID block and the run of `DATA` blocks written
+/// after it, the same ownership [`scan_id_footprints`] uses. Nothing is
+/// cascaded: IDs only the pruned ones referenced stay, and pointers into
+/// removed blocks from any block a kept ID owns, including entries of raw
+/// pointer arrays such as `Mesh.mat`, are reported as [`BrokenRef`]s with
+/// the field they sit in. Pointers not in `ids` fail with
+/// [`BlendError::IdRecordNotFound`].
+///
+/// [`scan_id_footprints`]: crate::blend::scan_id_footprints
+pub fn plan_prune(file: &BlendFile, dna: &Dna, ids: &IdIndex, remove: &[u64]) -> Result<PrunePlan> {
+	for ptr in remove {
+		if ids.get_by_ptr(*ptr).is_none() {
+			return Err(BlendError::IdRecordNotFound {
+				name: format!("0x{ptr:x}"),
+				suggestions: Vec::new(),
+			});
+		}
+	}
+	let removed: HashSet<u64> = remove.iter().copied().collect();
+	let head_size = file.header.bhead_layout()?.capabilities().size as u64;
+
+	let mut plan = PrunePlan {
+		ids: remove.to_vec(),
+		input_bytes: file.bytes().len() as u64,
+		..PrunePlan::default()
+	};
+	let mut owner = None;
+	let mut kept = Vec::new();
+	for block in file.blocks() {
+		let block = block?;
+		if block.head.code != codes::DATA {
+			owner = ids
+				.get_by_ptr(block.head.old)
+				.filter(|record| record.sdna_nr == block.head.sdna_nr && record.code == block.head.code)
+				.map(|record| record.old_ptr);
+		}
+		let Some(id) = owner else {
+			continue;
+		};
+		if !removed.contains(&id) {
+			kept.push((id, block));
+			continue;
+		}
+		let bytes = head_size + block.payload.len() as u64;
+		plan.removed_bytes += bytes;
+		plan.blocks.push(PrunedBlock {
+			block_offset: block.file_offset,
+			code: block.head.code,
+			old_ptr: block.head.old,
+			bytes,
+			owner: id,
+		});
+	}
+
+	let owner_at: HashMap<usize, u64> = plan.blocks.iter().map(|block| (block.block_offset, block.owner)).collect();
+	let index = PointerIndex::build(file)?;
+	let mut slots = HashMap::new();
+	for (id, block) in kept {
+		let sdna_nr = block.head.sdna_nr;
+		// SDNA 0 marks raw data, reached only through the pointer arrays below.
+		if sdna_nr == 0 {
+			continue;
+		}
+		let Some(item) = dna.struct_by_sdna(sdna_nr) else {
+			continue;
+		};
+		let struct_size = usize::from(dna.tlen[item.type_idx as usize]);
+		if struct_size == 0 {
+			continue;
+		}
+		let Some(record) = ids.get_by_ptr(id) else {
+			continue;
+		};
+		let owner_name: Arc<str> = Arc::from(record.id_name.as_ref());
+		let type_name = dna.type_name(item.type_idx);
+		let block_slots = slots.entry(sdna_nr).or_insert_with(|| pointer_slots(dna, sdna_nr));
+		let mut found = Vec::new();
+		for_each_block_pointer(dna, &block, struct_size, block_slots, |element, slot, ptr| {
+			let path = if block.head.code != codes::DATA {
+				slot.field.to_string()
+			} else if block.head.nr > 1 {
+				format!("{type_name}[{element}].{}", slot.field)
+			} else {
+				format!("{type_name}.{}", slot.field)
+			};
+			let Some(resolved) = index.resolve(ptr) else {
+				return;
+			};
+			if let Some(target) = owner_at.get(&resolved.entry.block.file_offset) {
+				found.push((path, ptr, *target));
+			} else if slot.to_pointer_array && resolved.entry.block.head.sdna_nr == 0 {
+				let entries = resolved.entry.block.payload.get(resolved.byte_offset..).unwrap_or_default();
+				for (i, bytes) in entries.chunks_exact(dna.pointer_size).enumerate() {
+					let Ok(entry) = Cursor::new(bytes).read_ptr(dna.pointer_size, dna.endianness) else {
+						break;
+					};
+					if let Some(target) = index.resolve(entry).and_then(|hit| owner_at.get(&hit.entry.block.file_offset)) {
+						found.push((format!("{path}[{i}]"), entry, *target));
+					}
+				}
+			}
+		});
+		plan.broken_refs.extend(found.into_iter().map(|(field, ptr, target)| BrokenRef {
+			owner: id,
+			owner_name: owner_name.clone(),
+			field: Arc::from(field),
+			ptr,
+			target,
+		}));
+	}
+
+	Ok(plan)
+}
+
+/// Re-emit `file` without the blocks in `blocks`, returning the bytes written.
+///
+/// Every planned block must still sit at its recorded offset with the same
+/// code and old pointer, so a plan computed against another revision of the
+/// file fails with [`BlendError::PrunePlanMismatch`] before anything is written
+/// to `out`.
+pub fn write_pruned(file: &BlendFile, blocks: &[PrunedBlock], out: &mut impl Write) -> Result<u64> {
+	let planned: HashMap<usize, &PrunedBlock> = blocks.iter().map(|block| (block.block_offset, block)).collect();
+	let mut matched = 0_usize;
+	for block in file.blocks() {
+		let block = block?;
+		let Some(expected) = planned.get(&block.file_offset) else {
+			continue;
+		};
+		if (expected.code, expected.old_ptr) != (block.head.code, block.head.old) {
+			return Err(BlendError::PrunePlanMismatch {
+				detail: format!(
+					"block at 0x{:x} is {} 0x{:x}, plan expects {} 0x{:x}",
+					block.file_offset,
+					codes::code_to_str(block.head.code),
+					block.head.old,
+					codes::code_to_str(expected.code),
+					expected.old_ptr
+				),
+			});
+		}
+		matched += 1;
+	}
+	if matched != planned.len() {
+		return Err(BlendError::PrunePlanMismatch {
+			detail: format!("{} planned block(s) are not block starts in this file", planned.len() - matched),
+		});
+	}
+
+	write_blocks(file, out, |block| !planned.contains_key(&block.file_offset))
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,143 @@
+use blendoc_testkit::builder::{BlendBuilder, HeaderKind, SCENE_ID_NAMES, scene_file};
+
+use crate::blend::{BlendError, BlendFile, IdIndex, plan_prune, scan_id_blocks, write_pruned};
+
+fn scene() -> (BlendFile, IdIndex) {
+	let blend = BlendFile::from_bytes(scene_file(HeaderKind::Legacy8, false)).expect("synthetic file parses");
+	let dna = blend.dna().expect("dna parses");
+	let ids = IdIndex::build(scan_id_blocks(&blend, &dna).expect("ids scan"));
+	(blend, ids)
+}
+
+#[test]
+fn plan_removes_owned_data_and_reports_broken_refs() {
+	let (blend, ids) = scene();
+	let dna = blend.dna().expect("dna parses");
+	let mesh = ids.get_by_name(SCENE_ID_NAMES[2]).expect("mesh id").old_ptr;
+	let object = ids.get_by_name(SCENE_ID_NAMES[1]).expect("object id").old_ptr;
+
+	let plan = plan_prune(&blend, &dna, &ids, &[mesh]).expect("plan builds");
+
+	let codes: Vec<[u8; 4]> = plan.blocks.iter().map(|block| block.code).collect();
+	assert_eq!(codes, [*b"ME\0\0", *b"DATA"]);
+	assert!(plan.blocks.iter().all(|block| block.owner == mesh));
+	assert_eq!(plan.removed_bytes, plan.blocks.iter().map(|block| block.bytes).sum::<u64>());
+	assert_eq!(plan.output_bytes(), blend.bytes().len() as u64 - plan.removed_bytes);
+
+	assert_eq!(plan.broken_refs.len(), 1, "{:?}", plan.broken_refs);
+	let broken = &plan.broken_refs[0];
+	assert_eq!((broken.owner, broken.field.as_ref(), broken.ptr, broken.target), (object, "data", mesh, mesh));
+}
+
+/// A collection linking `OBCube` through a `CollectionObject` DATA block, and
+/// a mesh whose `mat` pointer array (raw DATA) holds `MAMat`.
+fn linked_file() -> (BlendFile, IdIndex) {
+	let mut builder = BlendBuilder::new(HeaderKind::Legacy8, 404, false);
+	let sdna = builder.sdna_mut();
+	sdna.add_struct("Link", &[("Link", "*next"), ("Link", "*prev")]);
+	sdna.add_type("char", 1);
+	sdna.add_type("void", 0);
+	sdna.add_struct("ID", &[("void", "*next"), ("void", "*prev"), ("void", "*lib"), ("char", "name[24]")]);
+	sdna.add_struct("ListBase", &[("void", "*first"), ("void", "*last")]);
+	let object = sdna.add_struct("Object", &[("ID", "id")]);
+	let collection = sdna.add_struct("Collection", &[("ID", "id"), ("ListBase", "gobject")]);
+	let collection_object = sdna.add_struct(
+		"CollectionObject",
+		&[("CollectionObject", "*next"), ("CollectionObject", "*prev"), ("Object", "*ob")],
+	);
+	let material = sdna.add_struct("Material", &[("ID", "id")]);
+	let mesh = sdna.add_struct("Mesh", &[("ID", "id"), ("Material", "**mat")]);
+
+	let id = |builder: &BlendBuilder, name: &str| {
+		let mut payload = builder.payload();
+		payload.ptr(0).ptr(0).ptr(0).name(name, 24);
+		payload
+	};
+	let mut payload = id(&builder, "GRScene");
+	payload.ptr(0x2000).ptr(0x2000);
+	let group = payload.finish();
+	let mut payload = builder.payload();
+	payload.ptr(0).ptr(0).ptr(0x3000);
+	let link = payload.finish();
+	let cube = id(&builder, "OBCube").finish();
+	let mut payload = id(&builder, "MEMesh");
+	payload.ptr(0x5000);
+	let me = payload.finish();
+	let mut payload = builder.payload();
+	payload.ptr(0x6000);
+	let mats = payload.finish();
+	let mat = id(&builder, "MAMat").finish();
+	builder
+		.block(*b"GR\0\0", collection, 0x1000, 1, group)
+		.block(*b"DATA", collection_object, 0x2000, 1, link)
+		.block(*b"OB\0\0", object, 0x3000, 1, cube)
+		.block(*b"ME\0\0", mesh, 0x4000, 1, me)
+		.block(*b"DATA", 0, 0x5000, 1, mats)
+		.block(*b"MA\0\0", material, 0x6000, 1, mat);
+
+	let blend = BlendFile::from_bytes(builder.build()).expect("synthetic file parses");
+	let dna = blend.dna().expect("dna parses");
+	let ids = IdIndex::build(scan_id_blocks(&blend, &dna).expect("ids scan"));
+	(blend, ids)
+}
+
+#[test]
+fn broken_refs_cover_data_blocks_and_pointer_arrays_of_kept_ids() {
+	let (blend, ids) = linked_file();
+	let dna = blend.dna().expect("dna parses");
+	let ptr = |name: &str| ids.get_by_name(name).expect("id").old_ptr;
+
+	let plan = plan_prune(&blend, &dna, &ids, &[ptr("OBCube"), ptr("MAMat")]).expect("plan builds");
+
+	let broken: Vec<_> = plan
+		.broken_refs
+		.iter()
+		.map(|broken| (broken.owner_name.as_ref(), broken.field.as_ref(), broken.ptr, broken.target))
+		.collect();
+	assert_eq!(
+		broken,
+		[
+			("GRScene", "CollectionObject.ob", 0x3000, ptr("OBCube")),
+			("MEMesh", "mat[0]", 0x6000, ptr("MAMat")),
+		]
+	);
+}
+
+#[test]
+fn write_pruned_drops_planned_blocks() {
+	let (blend, ids) = scene();
+	let dna = blend.dna().expect("dna parses");
+	let mesh = ids.get_by_name(SCENE_ID_NAMES[2]).expect("mesh id").old_ptr;
+	let plan = plan_prune(&blend, &dna, &ids, &[mesh]).expect("plan builds");
+
+	let mut out = Vec::new();
+	let written = write_pruned(&blend, &plan.blocks, &mut out).expect("prune writes");
+	assert_eq!(written, plan.output_bytes());
+
+	let pruned = BlendFile::from_bytes(out).expect("pruned file parses");
+	let pruned_dna = pruned.dna().expect("dna parses");
+	let names: Vec<String> = scan_id_blocks(&pruned, &pruned_dna)
+		.expect("ids scan")
+		.iter()
+		.map(|record| record.id_name.to_string())
+		.collect();
+	assert!(!names.iter().any(|name| name == SCENE_ID_NAMES[2]), "{names:?}");
+	assert_eq!(names.len(), SCENE_ID_NAMES.len() - 1);
+}
+
+#[test]
+fn stale_plans_and_unknown_ids_are_rejected() {
+	let (blend, ids) = scene();
+	let dna = blend.dna().expect("dna parses");
+	assert!(matches!(plan_prune(&blend, &dna, &ids, &[0xdead]), Err(BlendError::IdRecordNotFound { .. })));
+
+	let world = ids.get_by_name(SCENE_ID_NAMES[3]).expect("world id").old_ptr;
+	let mut plan = plan_prune(&blend, &dna, &ids, &[world]).expect("plan builds");
+	plan.blocks[0].old_ptr += 1;
+	let mut out = Vec::new();
+	assert!(matches!(
+		write_pruned(&blend, &plan.blocks, &mut out),
+		Err(BlendError::PrunePlanMismatch { .. })
+	));
+	assert!(out.is_empty());
+}
@@ -0,0 +1,99 @@
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex, MutexGuard};
+
+use crate::blend::{Dna, IdIndex, PointerIndex, RefRecord, RefScanOptions, Result, scan_refs_from_ptr};
+
+/// Cache key: canonical owner plus the scan limits that shape its output.
+type RefCacheKey = (u64, u32, usize);
+
+/// Shared memo of `scan_refs_from_ptr` results keyed by canonical owner pointer.
+///
+/// Clones share one map, so a cache can be stored in graph, route, and xref
+/// options and reused across calls. Entries embed `ID.name` annotations, so a
+/// cache is only valid for the file and ID index it was filled from.
+#[derive(Debug, Clone, Default)]
+pub struct RefCache {
+	inner: Arc<Mutex<RefCacheState>>,
+}
+
+#[derive(Debug, Default)]
+struct RefCacheState {
+	entries: HashMap<RefCacheKey, Arc<[RefRecord]>>,
+	hits: usize,
+	misses: usize,
+}
+
+impl RefCache {
+	/// Create an empty cache.
+	pub fn new() -> Self {
+		Self::default()
+	}
+
+	/// Scan pointer fields from `ptr`, reusing a previous scan of the same owner.
+	///
+	/// Failed scans are not cached.
+	pub fn scan<'a>(&self, dna: &Dna, index: &PointerIndex<'a>, ids: &IdIndex, ptr: u64, options: &RefScanOptions) -> Result<Arc<[RefRecord]>> {
+		let owner = index.canonicalize_ptr(dna, ptr)?;
+		let key = (owner, options.max_depth, options.max_array_elems);
+
+		{
+			let mut state = self.lock();
+			if let Some(refs) = state.entries.get(&key).cloned() {
+				state.hits += 1;
+				return Ok(refs);
+			}
+		}
+
+		let refs: Arc<[RefRecord]> = scan_refs_from_ptr(dna, index, ids, owner, options)?.into();
+		let mut state = self.lock();
+		state.misses += 1;
+		Ok(state.entries.entry(key).or_insert(refs).clone())
+	}
+
+	/// Return number of cached owner scans.
+	pub fn len(&self) -> usize {
+		self.lock().entries.len()
+	}
+
+	/// Return whether no scans are cached.
+	pub fn is_empty(&self) -> bool {
+		self.lock().entries.is_empty()
+	}
+
+	/// Return number of scans served from the cache.
+	pub fn hits(&self) -> usize {
+		self.lock().hits
+	}
+
+	/// Return number of scans that had to read owner bytes.
+	pub fn misses(&self) -> usize {
+		self.lock().misses
+	}
+
+	/// Drop all cached scans and reset counters.
+	pub fn clear(&self) {
+		*self.lock() = RefCacheState::default();
+	}
+
+	fn lock(&self) -> MutexGuard<'_, RefCacheState> {
+		self.inner.lock().unwrap_or_else(|poisoned| poisoned.into_inner())
+	}
+}
+
+/// Scan refs through `cache` when one is configured, or directly otherwise.
+pub(crate) fn scan_refs_cached<'a>(
+	dna: &Dna,
+	index: &PointerIndex<'a>,
+	ids: &IdIndex,
+	ptr: u64,
+	options: &RefScanOptions,
+	cache: Option<&RefCache>,
+) -> Result<Arc<[RefRecord]>> {
+	match cache {
+		Some(cache) => cache.scan(dna, index, ids, ptr, options),
+		None => Ok(scan_refs_from_ptr(dna, index, ids, ptr, options)?.into()),
+	}
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,150 @@
+mod fixtures_ref_cache {
+	use std::sync::Arc;
+
+	use blendoc_testkit::fixture_path;
+
+	use crate::blend::{BlendFile, IdIndex, RefCache, RefScanOptions, scan_id_blocks, scan_refs_from_ptr};
+
+	#[test]
+	fn repeated_scans_share_one_entry() {
+		let blend = BlendFile::open(fixture_path("character.blend")).expect("fixture opens");
+		let dna = blend.dna().expect("dna parses");
+		let index = blend.pointer_index().expect("pointer index builds");
+		let ids = IdIndex::build(scan_id_blocks(&blend, &dna).expect("id scan succeeds"));
+		let scene = ids.records.iter().find(|item| item.code == [b'S', b'C', 0, 0]).expect("scene id record exists");
+
+		let cache = RefCache::new();
+		let options = RefScanOptions::default();
+		let first = cache.scan(&dna, &index, &ids, scene.old_ptr, &options).expect("scan succeeds");
+		let second = cache.scan(&dna, &index, &ids, scene.old_ptr, &options).expect("scan succeeds");
+
+		assert!(Arc::ptr_eq(&first, &second));
+		assert_eq!((cache.len(), cache.hits(), cache.misses()), (1, 1, 1));
+
+		let direct = scan_refs_from_ptr(&dna, &index, &ids, scene.old_ptr, &options).expect("scan succeeds");
+		assert_eq!(direct.len(), first.len());
+
+		let deeper = RefScanOptions { max_depth: 2, ..options };
+		cache.scan(&dna, &index, &ids, scene.old_ptr, &deeper).expect("scan succeeds");
+		assert_eq!(cache.len(), 2, "scan limits are part of the key");
+
+		cache.clear();
+		assert!(cache.is_empty());
+		assert_eq!(cache.hits(), 0);
+	}
+}
+
+mod shared_across_calls {
+	use crate::blend::{
+		BHead, Block, Dna, DnaField, DnaStruct, IdGraphOptions, IdIndex, IdRecord, PointerIndex, PtrEntry, RefCache, XrefOptions, build_id_graph,
+		find_inbound_refs_to_ptr,
+	};
+
+	#[test]
+	fn idgraph_fills_cache_reused_by_xref() {
+		let owner_payload = 0x3000_u64.to_le_bytes();
+		let target_payload = [0_u8; 8];
+
+		let owner_block = Block {
+			head: BHead {
+				code: *b"SC\0\0",
+				sdna_nr: 0,
+				old: 0x1000,
+				len: owner_payload.len() as u64,
+				nr: 1,
+			},
+			payload: &owner_payload,
+			file_offset: 0,
+		};
+		let target_block = Block {
+			head: BHead {
+				code: *b"WO\0\0",
+				sdna_nr: 1,
+				old: 0x3000,
+				len: target_payload.len() as u64,
+				nr: 1,
+			},
+			payload: &target_payload,
+			file_offset: 64,
+		};
+
+		let index = PointerIndex::from_entries_for_test(vec![
+			PtrEntry {
+				start_old: 0x1000,
+				end_old: 0x1008,
+				block: owner_block,
+			},
+			PtrEntry {
+				start_old: 0x3000,
+				end_old: 0x3008,
+				block: target_block,
+			},
+		]);
+
+		let dna = Dna::from_tables(
+			crate::blend::Endianness::Little,
+			8,
+			vec!["*world".into(), "id[8]".into()],
+			vec!["char".into(), "Owner".into(), "Target".into()],
+			vec![1, 8, 8],
+			vec![
+				DnaStruct {
+					type_idx: 1,
+					fields: vec![DnaField { type_idx: 2, name_idx: 0 }],
+				},
+				DnaStruct {
+					type_idx: 2,
+					fields: vec![DnaField { type_idx: 0, name_idx: 1 }],
+				},
+			],
+		)
+		.expect("valid dna tables");
+
+		let record = |old_ptr: u64, code: [u8; 4], sdna_nr: u32, type_name: &str, id_name: &str| IdRecord {
+			old_ptr,
+			code,
+			sdna_nr,
+			type_name: type_name.into(),
+			id_name: id_name.into(),
+			next: None,
+			prev: None,
+			lib: None,
+		};
+		let ids = IdIndex::build(vec![
+			record(0x1000, *b"SC\0\0", 0, "Owner", "SCOwner"),
+			record(0x3000, *b"WO\0\0", 1, "Target", "WOTarget"),
+		]);
+
+		let cache = RefCache::new();
+		let graph = build_id_graph(
+			&dna,
+			&index,
+			&ids,
+			&IdGraphOptions {
+				ref_cache: Some(cache.clone()),
+				..IdGraphOptions::default()
+			},
+		)
+		.expect("id graph builds");
+		assert_eq!(graph.edges.len(), 1);
+		assert_eq!((cache.len(), cache.hits(), cache.misses()), (2, 0, 2));
+
+		let cached = find_inbound_refs_to_ptr(
+			&dna,
+			&index,
+			&ids,
+			0x3000,
+			&XrefOptions {
+				ref_cache: Some(cache.clone()),
+				..XrefOptions::default()
+			},
+		)
+		.expect("xref succeeds");
+		assert_eq!((cache.hits(), cache.misses()), (2, 2), "xref should not rescan owners");
+
+		let uncached = find_inbound_refs_to_ptr(&dna, &index, &ids, 0x3000, &XrefOptions::default()).expect("xref succeeds");
+		assert_eq!(cached.len(), 1);
+		assert_eq!(cached[0].field, uncached[0].field);
+		assert_eq!(cached[0].from, uncached[0].from);
+	}
+}
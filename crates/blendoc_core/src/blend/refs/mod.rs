@@ -2,6 +2,7 @@ use std::sync::Arc;
 
 use crate::blend::bytes::Cursor;
 use crate::blend::decl::parse_field_decl;
+use crate::blend::trace::trace_span;
 use crate::blend::{BlendError, Dna, IdIndex, PointerIndex, Result};
 
 /// Runtime limits for pointer-reference scanning.
@@ -31,6 +32,8 @@ pub struct RefRecord {
 	pub owner_type: Arc<str>,
 	/// Field path (`field` or `field.sub[i]`) where pointer was found.
 	pub field: Arc<str>,
+	/// Byte offset of the pointer within the owner struct.
+	pub offset: usize,
 	/// Raw pointer value from struct bytes.
 	pub ptr: u64,
 	/// Resolution metadata when pointer maps to a known struct element.
@@ -50,10 +53,13 @@ pub struct RefTarget {
 	pub type_name: Arc<str>,
 	/// Optional ID name annotation when target is an ID-root block.
 	pub id_name: Option<Arc<str>>,
+	/// Element index of the target within its block.
+	pub element_index: usize,
 }
 
 /// Scan pointer fields from a resolved struct pointer.
 pub fn scan_refs_from_ptr<'a>(dna: &Dna, index: &PointerIndex<'a>, id_index: &IdIndex, root_ptr: u64, options: &RefScanOptions) -> Result<Vec<RefRecord>> {
+	trace_span!("refs.scan");
 	let (owner_canonical, typed) = index.resolve_canonical_typed(dna, root_ptr)?;
 	let element_index = typed.element_index.ok_or(BlendError::ChasePtrOutOfBounds { ptr: root_ptr })?;
 
@@ -88,7 +94,7 @@ pub fn scan_refs_from_ptr<'a>(dna: &Dna, index: &PointerIndex<'a>, id_index: &Id
 		out: &mut out,
 	};
 
-	scanner.scan_struct(owner_sdna, owner_bytes, "", options.max_depth)?;
+	scanner.scan_struct(owner_sdna, owner_bytes, 0, "", options.max_depth)?;
 	Ok(out)
 }
 
@@ -103,7 +109,7 @@ struct RefScanner<'a, 'b, 'c> {
 }
 
 impl<'a, 'b, 'c> RefScanner<'a, 'b, 'c> {
-	fn scan_struct(&mut self, sdna_nr: u32, bytes: &[u8], prefix: &str, depth_left: u32) -> Result<()> {
+	fn scan_struct(&mut self, sdna_nr: u32, bytes: &[u8], base: usize, prefix: &str, depth_left: u32) -> Result<()> {
 		let item = self.dna.struct_by_sdna(sdna_nr).ok_or(BlendError::DecodeMissingSdna { sdna_nr })?;
 		let mut cursor = Cursor::new(bytes);
 
@@ -124,6 +130,7 @@ impl<'a, 'b, 'c> RefScanner<'a, 'b, 'c> {
 
 			if decl.ptr_depth > 0 || decl.is_func_ptr {
 				for idx in 0..count {
+					let offset = base + cursor.pos();
 					let ptr = cursor.read_ptr(self.dna.pointer_size, self.dna.endianness)?;
 					let field_name = if count == 1 {
 						format!("{prefix}{}", decl.ident)
@@ -134,6 +141,7 @@ impl<'a, 'b, 'c> RefScanner<'a, 'b, 'c> {
 						owner_canonical: self.owner_canonical,
 						owner_type: self.owner_type.clone(),
 						field: Arc::<str>::from(field_name),
+						offset,
 						ptr,
 						resolved: self.resolve_target(ptr),
 					});
@@ -153,9 +161,10 @@ impl<'a, 'b, 'c> RefScanner<'a, 'b, 'c> {
 				&& depth_left > 0
 				&& count == 1
 			{
+				let nested_base = base + cursor.pos();
 				let nested_bytes = cursor.read_exact(element_size)?;
 				let next_prefix = format!("{prefix}{}.", decl.ident);
-				self.scan_struct(nested_sdna, nested_bytes, &next_prefix, depth_left - 1)?;
+				self.scan_struct(nested_sdna, nested_bytes, nested_base, &next_prefix, depth_left - 1)?;
 				continue;
 			}
 
@@ -192,6 +201,7 @@ impl<'a, 'b, 'c> RefScanner<'a, 'b, 'c> {
 			sdna_nr: typed.base.entry.block.head.sdna_nr,
 			type_name,
 			id_name,
+			element_index,
 		})
 	}
 }
@@ -2,7 +2,7 @@ mod fixtures_day7_refs {
 
 	use blendoc_testkit::fixture_path;
 
-	use crate::blend::{BlendFile, IdIndex, RefScanOptions, scan_id_blocks, scan_refs_from_ptr};
+	use crate::blend::{BlendFile, IdIndex, RefScanOptions, codes, scan_id_blocks, scan_refs_from_ptr};
 
 	#[test]
 	fn character_scene_refs_include_world() {
@@ -21,7 +21,7 @@ mod fixtures_day7_refs {
 		let ids = scan_id_blocks(&blend, &dna).expect("id scan succeeds");
 		let id_index = IdIndex::build(ids.clone());
 
-		let scene = ids.iter().find(|item| item.code == [b'S', b'C', 0, 0]).expect("scene id record exists");
+		let scene = ids.iter().find(|item| item.code == codes::ID_SCE).expect("scene id record exists");
 
 		let refs = scan_refs_from_ptr(
 			&dna,
@@ -110,13 +110,13 @@ mod pointer_arrays {
 			},
 		]);
 
-		let dna = Dna {
-			endianness: crate::blend::Endianness::Little,
-			pointer_size: 8,
-			names: vec!["*arr[2]".into(), "nested".into(), "*first".into()],
-			types: vec!["Owner".into(), "Nested".into()],
-			tlen: vec![24, 8],
-			structs: vec![
+		let dna = Dna::from_tables(
+			crate::blend::Endianness::Little,
+			8,
+			vec!["*arr[2]".into(), "nested".into(), "*first".into()],
+			vec!["Owner".into(), "Nested".into()],
+			vec![24, 8],
+			vec![
 				DnaStruct {
 					type_idx: 0,
 					fields: vec![DnaField { type_idx: 1, name_idx: 0 }, DnaField { type_idx: 1, name_idx: 1 }],
@@ -126,8 +126,8 @@ mod pointer_arrays {
 					fields: vec![DnaField { type_idx: 1, name_idx: 2 }],
 				},
 			],
-			struct_for_type: vec![Some(0), Some(1)],
-		};
+		)
+		.expect("valid dna tables");
 
 		let id_index = IdIndex::build(Vec::new());
 		let refs = scan_refs_from_ptr(
@@ -151,7 +151,7 @@ mod pointer_arrays {
 mod stable_ids {
 	use blendoc_testkit::fixture_path;
 
-	use crate::blend::{BlendFile, IdIndex, PointerStorage, RefScanOptions, scan_id_blocks, scan_refs_from_ptr};
+	use crate::blend::{BlendFile, IdIndex, PointerStorage, RefScanOptions, codes, scan_id_blocks, scan_refs_from_ptr};
 
 	#[test]
 	fn v51_character_scene_refs_resolve_exact_ids_only() {
@@ -171,7 +171,7 @@ mod stable_ids {
 
 		let ids = scan_id_blocks(&blend, &dna).expect("id scan succeeds");
 		let id_index = IdIndex::build(ids.clone());
-		let scene = ids.iter().find(|item| item.code == [b'S', b'C', 0, 0]).expect("scene id record exists");
+		let scene = ids.iter().find(|item| item.code == codes::ID_SCE).expect("scene id record exists");
 
 		let refs = scan_refs_from_ptr(
 			&dna,
@@ -200,3 +200,67 @@ mod stable_ids {
 		}
 	}
 }
+
+mod pointer_size_32 {
+	use crate::blend::{BHead, Block, Dna, DnaField, DnaStruct, Endianness, IdIndex, PointerIndex, PtrEntry, RefScanOptions, scan_refs_from_ptr};
+
+	#[test]
+	fn four_byte_big_endian_pointers_are_scanned_and_resolved() {
+		let mut owner_payload = [0_u8; 12];
+		owner_payload[0..4].copy_from_slice(&0x2000_u32.to_be_bytes());
+		owner_payload[4..8].copy_from_slice(&7_u32.to_be_bytes());
+		owner_payload[8..12].copy_from_slice(&0x3000_u32.to_be_bytes());
+		let target_payload = [0_u8; 12];
+
+		let index = PointerIndex::from_entries_for_test(vec![
+			entry(0x1000, &owner_payload),
+			entry(0x2000, &target_payload),
+			entry(0x3000, &target_payload),
+		]);
+
+		let dna = Dna::from_tables(
+			Endianness::Big,
+			4,
+			vec!["*first".into(), "count".into(), "*second".into()],
+			vec!["int".into(), "Pair".into()],
+			vec![4, 12],
+			vec![DnaStruct {
+				type_idx: 1,
+				fields: vec![
+					DnaField { type_idx: 1, name_idx: 0 },
+					DnaField { type_idx: 0, name_idx: 1 },
+					DnaField { type_idx: 1, name_idx: 2 },
+				],
+			}],
+		)
+		.expect("valid dna tables");
+
+		let refs = scan_refs_from_ptr(&dna, &index, &IdIndex::build(Vec::new()), 0x1000, &RefScanOptions::default()).expect("ref scan succeeds");
+
+		assert_eq!(refs.len(), 2);
+		assert_eq!(refs[0].field.as_ref(), "first");
+		assert_eq!(refs[0].ptr, 0x2000);
+		assert_eq!(refs[0].resolved.as_ref().map(|item| item.canonical), Some(0x2000));
+		assert_eq!(refs[1].field.as_ref(), "second");
+		assert_eq!(refs[1].ptr, 0x3000);
+		assert_eq!(refs[1].resolved.as_ref().map(|item| item.canonical), Some(0x3000));
+	}
+
+	fn entry(old: u64, payload: &[u8]) -> PtrEntry<'_> {
+		PtrEntry {
+			start_old: old,
+			end_old: old + payload.len() as u64,
+			block: Block {
+				head: BHead {
+					code: *b"DATA",
+					sdna_nr: 0,
+					old,
+					len: payload.len() as u64,
+					nr: 1,
+				},
+				payload,
+				file_offset: 0,
+			},
+		}
+	}
+}
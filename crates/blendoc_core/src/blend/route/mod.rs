@@ -1,9 +1,12 @@
+use std::collections::hash_map::Entry;
 use std::collections::{HashMap, HashSet, VecDeque};
 use std::sync::Arc;
 
-use crate::blend::{BlendError, Dna, IdIndex, PointerIndex, RefScanOptions, Result, scan_refs_from_ptr};
+use crate::blend::cancel::is_cancelled;
+use crate::blend::ref_cache::scan_refs_cached;
+use crate::blend::{BlendError, CancelToken, Dna, IdIndex, PointerIndex, RefCache, RefScanOptions, Result};
 
-/// Runtime limits for shortest-route traversal.
+/// Runtime limits for route traversal.
 #[derive(Debug, Clone)]
 pub struct RouteOptions {
 	/// Maximum BFS expansion depth.
@@ -12,8 +15,14 @@ pub struct RouteOptions {
 	pub max_nodes: usize,
 	/// Maximum number of explored edges.
 	pub max_edges: usize,
+	/// Maximum number of routes [`find_routes_between_ptrs`] and [`find_routes_to_code`] return.
+	pub max_routes: usize,
 	/// Per-node reference scan behavior.
 	pub ref_scan: RefScanOptions,
+	/// Optional shared memo for per-owner reference scans.
+	pub ref_cache: Option<RefCache>,
+	/// Stops the search early with [`RouteTruncation::Cancelled`] when cancelled.
+	pub cancel: Option<CancelToken>,
 }
 
 impl Default for RouteOptions {
@@ -22,7 +31,10 @@ impl Default for RouteOptions {
 			max_depth: 6,
 			max_nodes: 20_000,
 			max_edges: 100_000,
+			max_routes: 1,
 			ref_scan: RefScanOptions::default(),
+			ref_cache: None,
+			cancel: None,
 		}
 	}
 }
@@ -36,6 +48,8 @@ pub enum RouteTruncation {
 	MaxNodes,
 	/// Search reached the configured edge budget.
 	MaxEdges,
+	/// Search stopped because the cancel token fired.
+	Cancelled,
 }
 
 /// One edge in a reconstructed route.
@@ -60,6 +74,29 @@ pub struct RouteResult {
 	pub visited_edges: usize,
 	/// Optional truncation reason when budgets stopped search.
 	pub truncated: Option<RouteTruncation>,
+	/// Canonical pointer of the target that ended the search, when found.
+	pub reached: Option<u64>,
+}
+
+/// Distinct simple routes found by [`find_routes_between_ptrs`] or [`find_routes_to_code`].
+#[derive(Debug, Clone)]
+pub struct RoutesResult {
+	/// Routes ordered shortest first, then by target pointer and field names.
+	pub routes: Vec<Vec<RouteEdge>>,
+	/// Number of unique nodes seen across all routes explored.
+	pub visited_nodes: usize,
+	/// Number of explored resolved edges.
+	pub visited_edges: usize,
+	/// Optional truncation reason when budgets stopped search.
+	pub truncated: Option<RouteTruncation>,
+}
+
+impl RoutesResult {
+	/// Canonical pointer where route `idx` ends; an empty route ends at `from`.
+	pub fn reached(&self, idx: usize, from: u64) -> Option<u64> {
+		let route = self.routes.get(idx)?;
+		Some(route.last().map_or(from, |edge| edge.to))
+	}
 }
 
 /// Find a shortest pointer route between two pointers.
@@ -71,15 +108,65 @@ pub fn find_route_between_ptrs<'a>(
 	to_ptr: u64,
 	options: &RouteOptions,
 ) -> Result<RouteResult> {
-	let from = index.canonicalize_ptr(dna, from_ptr)?;
 	let to = index.canonicalize_ptr(dna, to_ptr)?;
+	find_route_impl(dna, index, ids, from_ptr, |canonical, _| canonical == to, options)
+}
+
+/// Find a shortest pointer route from one pointer to any block with the given code.
+///
+/// Search stops at the first node reached whose containing block matches `code`.
+pub fn find_route_to_code<'a>(dna: &Dna, index: &PointerIndex<'a>, ids: &IdIndex, from_ptr: u64, code: [u8; 4], options: &RouteOptions) -> Result<RouteResult> {
+	find_route_impl(dna, index, ids, from_ptr, |_, target_code| target_code == code, options)
+}
+
+/// Enumerate up to `options.max_routes` distinct simple routes between two pointers, shortest first.
+///
+/// Routes never revisit a node; two routes differ when any hop differs,
+/// including hops between the same nodes through different fields. Search
+/// stops early once `max_routes` routes are found or a budget runs out.
+pub fn find_routes_between_ptrs<'a>(
+	dna: &Dna,
+	index: &PointerIndex<'a>,
+	ids: &IdIndex,
+	from_ptr: u64,
+	to_ptr: u64,
+	options: &RouteOptions,
+) -> Result<RoutesResult> {
+	let to = index.canonicalize_ptr(dna, to_ptr)?;
+	find_routes_impl(dna, index, ids, from_ptr, |canonical, _| canonical == to, options)
+}
+
+/// Enumerate up to `options.max_routes` distinct simple routes to blocks with the given code.
+///
+/// Routes end at the first matching block along each path.
+pub fn find_routes_to_code<'a>(
+	dna: &Dna,
+	index: &PointerIndex<'a>,
+	ids: &IdIndex,
+	from_ptr: u64,
+	code: [u8; 4],
+	options: &RouteOptions,
+) -> Result<RoutesResult> {
+	find_routes_impl(dna, index, ids, from_ptr, |_, target_code| target_code == code, options)
+}
+
+fn find_route_impl<'a>(
+	dna: &Dna,
+	index: &PointerIndex<'a>,
+	ids: &IdIndex,
+	from_ptr: u64,
+	is_target: impl Fn(u64, [u8; 4]) -> bool,
+	options: &RouteOptions,
+) -> Result<RouteResult> {
+	let (from, from_typed) = index.resolve_canonical_typed(dna, from_ptr)?;
 
-	if from == to {
+	if is_target(from, from_typed.base.entry.block.head.code) {
 		return Ok(RouteResult {
 			path: Some(Vec::new()),
 			visited_nodes: 1,
 			visited_edges: 0,
 			truncated: None,
+			reached: Some(from),
 		});
 	}
 
@@ -95,15 +182,19 @@ pub fn find_route_between_ptrs<'a>(
 	let mut hit_depth_limit = false;
 
 	'outer: while let Some((current, depth)) = queue.pop_front() {
+		if is_cancelled(options.cancel.as_ref()) {
+			truncated = Some(RouteTruncation::Cancelled);
+			break;
+		}
 		if depth >= options.max_depth {
 			hit_depth_limit = true;
 			continue;
 		}
 
-		let refs = scan_refs_from_ptr(dna, index, ids, current, &options.ref_scan)?;
+		let refs = scan_refs_cached(dna, index, ids, current, &options.ref_scan, options.ref_cache.as_ref())?;
 		let mut next_edges = Vec::new();
-		for record in refs {
-			let Some(target) = record.resolved else {
+		for record in refs.iter() {
+			let Some(target) = &record.resolved else {
 				continue;
 			};
 
@@ -113,12 +204,12 @@ pub fn find_route_between_ptrs<'a>(
 				break 'outer;
 			}
 
-			next_edges.push((target.canonical, record.field));
+			next_edges.push((target.canonical, target.code, record.field.clone()));
 		}
 
-		next_edges.sort_by(|left, right| left.0.cmp(&right.0).then_with(|| left.1.cmp(&right.1)));
+		next_edges.sort_by(|left, right| left.0.cmp(&right.0).then_with(|| left.2.cmp(&right.2)));
 
-		for (next, via_field) in next_edges {
+		for (next, next_code, via_field) in next_edges {
 			if visited.contains(&next) {
 				continue;
 			}
@@ -131,13 +222,14 @@ pub fn find_route_between_ptrs<'a>(
 			visited.insert(next);
 			parents.insert(next, (current, via_field.clone()));
 
-			if next == to {
-				let path = reconstruct_route(from, to, &parents)?;
+			if is_target(next, next_code) {
+				let path = reconstruct_route(from, next, &parents)?;
 				return Ok(RouteResult {
 					path: Some(path),
 					visited_nodes: visited.len(),
 					visited_edges,
 					truncated,
+					reached: Some(next),
 				});
 			}
 
@@ -154,9 +246,102 @@ pub fn find_route_between_ptrs<'a>(
 		visited_nodes: visited.len(),
 		visited_edges,
 		truncated,
+		reached: None,
 	})
 }
 
+/// Resolved outgoing hop: target canonical pointer, target block code, and source field.
+type NextEdge = (u64, [u8; 4], Arc<str>);
+
+/// Breadth-first search over partial routes, so complete routes come out shortest first.
+fn find_routes_impl<'a>(
+	dna: &Dna,
+	index: &PointerIndex<'a>,
+	ids: &IdIndex,
+	from_ptr: u64,
+	is_target: impl Fn(u64, [u8; 4]) -> bool,
+	options: &RouteOptions,
+) -> Result<RoutesResult> {
+	let (from, from_typed) = index.resolve_canonical_typed(dna, from_ptr)?;
+	let mut result = RoutesResult {
+		routes: Vec::new(),
+		visited_nodes: 1,
+		visited_edges: 0,
+		truncated: None,
+	};
+	if options.max_routes == 0 {
+		return Ok(result);
+	}
+	// No simple route can leave the start node and come back to it.
+	if is_target(from, from_typed.base.entry.block.head.code) {
+		result.routes.push(Vec::new());
+		return Ok(result);
+	}
+
+	let mut seen = HashSet::from([from]);
+	let mut adjacency: HashMap<u64, Vec<NextEdge>> = HashMap::new();
+	let mut queue: VecDeque<Vec<RouteEdge>> = VecDeque::from([Vec::new()]);
+	let mut hit_depth_limit = false;
+
+	'outer: while let Some(partial) = queue.pop_front() {
+		if is_cancelled(options.cancel.as_ref()) {
+			result.truncated = Some(RouteTruncation::Cancelled);
+			break;
+		}
+		if partial.len() as u32 >= options.max_depth {
+			hit_depth_limit = true;
+			continue;
+		}
+
+		let current = partial.last().map_or(from, |edge| edge.to);
+		if let Entry::Vacant(slot) = adjacency.entry(current) {
+			let refs = scan_refs_cached(dna, index, ids, current, &options.ref_scan, options.ref_cache.as_ref())?;
+			let mut next_edges: Vec<_> = refs
+				.iter()
+				.filter_map(|record| record.resolved.as_ref().map(|target| (target.canonical, target.code, record.field.clone())))
+				.collect();
+			next_edges.sort_by(|left, right| left.0.cmp(&right.0).then_with(|| left.2.cmp(&right.2)));
+			slot.insert(next_edges);
+		}
+
+		for (next, next_code, via_field) in &adjacency[&current] {
+			result.visited_edges += 1;
+			if result.visited_edges > options.max_edges {
+				result.truncated = Some(RouteTruncation::MaxEdges);
+				break 'outer;
+			}
+			if *next == from || partial.iter().any(|edge| edge.to == *next) {
+				continue;
+			}
+			if seen.insert(*next) && seen.len() > options.max_nodes {
+				result.truncated = Some(RouteTruncation::MaxNodes);
+				break 'outer;
+			}
+
+			let mut extended = partial.clone();
+			extended.push(RouteEdge {
+				from: current,
+				to: *next,
+				field: via_field.clone(),
+			});
+			if is_target(*next, *next_code) {
+				result.routes.push(extended);
+				if result.routes.len() >= options.max_routes {
+					break 'outer;
+				}
+			} else {
+				queue.push_back(extended);
+			}
+		}
+	}
+
+	if result.truncated.is_none() && hit_depth_limit && result.routes.len() < options.max_routes {
+		result.truncated = Some(RouteTruncation::MaxDepth);
+	}
+	result.visited_nodes = seen.len();
+	Ok(result)
+}
+
 fn reconstruct_route(from: u64, to: u64, parents: &HashMap<u64, (u64, Arc<str>)>) -> Result<Vec<RouteEdge>> {
 	let mut out = Vec::new();
 	let mut current = to;
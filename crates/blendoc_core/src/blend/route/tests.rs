@@ -43,10 +43,13 @@ mod fixtures_day10_route {
 				max_depth: 3,
 				max_nodes: 4096,
 				max_edges: 16384,
+				max_routes: 1,
 				ref_scan: RefScanOptions {
 					max_depth: 1,
 					max_array_elems: 4096,
 				},
+				ref_cache: None,
+				cancel: None,
 			},
 		)
 		.expect("route search succeeds");
@@ -62,6 +65,7 @@ mod fixtures_day10_route {
 mod synthetic_chain {
 	use crate::blend::{
 		BHead, Block, Dna, DnaField, DnaStruct, IdIndex, IdRecord, PointerIndex, PtrEntry, RefScanOptions, RouteOptions, find_route_between_ptrs,
+		find_route_to_code, find_routes_between_ptrs,
 	};
 
 	#[test]
@@ -122,18 +126,18 @@ mod synthetic_chain {
 			},
 		]);
 
-		let dna = Dna {
-			endianness: crate::blend::Endianness::Little,
-			pointer_size: 8,
-			names: vec!["*next".into()],
-			types: vec!["Node".into()],
-			tlen: vec![8],
-			structs: vec![DnaStruct {
+		let dna = Dna::from_tables(
+			crate::blend::Endianness::Little,
+			8,
+			vec!["*next".into()],
+			vec!["Node".into()],
+			vec![8],
+			vec![DnaStruct {
 				type_idx: 0,
 				fields: vec![DnaField { type_idx: 0, name_idx: 0 }],
 			}],
-			struct_for_type: vec![Some(0)],
-		};
+		)
+		.expect("valid dna tables");
 
 		let ids = IdIndex::build(vec![
 			IdRecord {
@@ -178,10 +182,13 @@ mod synthetic_chain {
 				max_depth: 3,
 				max_nodes: 64,
 				max_edges: 64,
+				max_routes: 1,
 				ref_scan: RefScanOptions {
 					max_depth: 0,
 					max_array_elems: 64,
 				},
+				ref_cache: None,
+				cancel: None,
 			},
 		)
 		.expect("route succeeds");
@@ -195,4 +202,142 @@ mod synthetic_chain {
 		assert_eq!(path[1].to, 0x3000);
 		assert_eq!(path[1].field.as_ref(), "next");
 	}
+
+	#[test]
+	fn code_target_stops_at_first_matching_block() {
+		let payload_a = 0x2000_u64.to_le_bytes();
+		let payload_b = 0x3000_u64.to_le_bytes();
+		let payload_c = 0_u64.to_le_bytes();
+
+		let index = PointerIndex::from_entries_for_test(vec![
+			PtrEntry {
+				start_old: 0x1000,
+				end_old: 0x1008,
+				block: coded_block(*b"SC\0\0", 0x1000, &payload_a),
+			},
+			PtrEntry {
+				start_old: 0x2000,
+				end_old: 0x2008,
+				block: coded_block(*b"IM\0\0", 0x2000, &payload_b),
+			},
+			PtrEntry {
+				start_old: 0x3000,
+				end_old: 0x3008,
+				block: coded_block(*b"IM\0\0", 0x3000, &payload_c),
+			},
+		]);
+
+		let dna = Dna::from_tables(
+			crate::blend::Endianness::Little,
+			8,
+			vec!["*next".into()],
+			vec!["Node".into()],
+			vec![8],
+			vec![DnaStruct {
+				type_idx: 0,
+				fields: vec![DnaField { type_idx: 0, name_idx: 0 }],
+			}],
+		)
+		.expect("valid dna tables");
+		let ids = IdIndex::build(Vec::new());
+		let options = RouteOptions {
+			max_depth: 3,
+			max_nodes: 64,
+			max_edges: 64,
+			max_routes: 1,
+			ref_scan: RefScanOptions {
+				max_depth: 0,
+				max_array_elems: 64,
+			},
+			ref_cache: None,
+			cancel: None,
+		};
+
+		let result = find_route_to_code(&dna, &index, &ids, 0x1000, *b"IM\0\0", &options).expect("route succeeds");
+		assert_eq!(result.reached, Some(0x2000));
+		let path = result.path.expect("path should be found");
+		assert_eq!(path.len(), 1);
+		assert_eq!(path[0].to, 0x2000);
+
+		let missing = find_route_to_code(&dna, &index, &ids, 0x1000, *b"OB\0\0", &options).expect("route search succeeds");
+		assert!(missing.path.is_none());
+		assert_eq!(missing.reached, None);
+		assert_eq!(missing.visited_nodes, 3);
+	}
+
+	#[test]
+	fn enumerates_alternative_routes_shortest_first() {
+		// A.next -> B, A.alt -> C, B.next -> D, B.alt -> C, C.next -> D.
+		let node = |next: u64, alt: u64| [next.to_le_bytes(), alt.to_le_bytes()].concat();
+		let payloads = [node(0x2000, 0x3000), node(0x4000, 0x3000), node(0x4000, 0), node(0, 0)];
+		let olds = [0x1000_u64, 0x2000, 0x3000, 0x4000];
+		let index = PointerIndex::from_entries_for_test(
+			olds.iter()
+				.zip(&payloads)
+				.map(|(old, payload)| PtrEntry {
+					start_old: *old,
+					end_old: old + 16,
+					block: coded_block(*b"DATA", *old, payload),
+				})
+				.collect(),
+		);
+		let dna = Dna::from_tables(
+			crate::blend::Endianness::Little,
+			8,
+			vec!["*next".into(), "*alt".into()],
+			vec!["Node".into()],
+			vec![16],
+			vec![DnaStruct {
+				type_idx: 0,
+				fields: vec![DnaField { type_idx: 0, name_idx: 0 }, DnaField { type_idx: 0, name_idx: 1 }],
+			}],
+		)
+		.expect("valid dna tables");
+		let ids = IdIndex::build(Vec::new());
+		let mut options = RouteOptions {
+			max_routes: 8,
+			ref_scan: RefScanOptions {
+				max_depth: 0,
+				max_array_elems: 64,
+			},
+			..RouteOptions::default()
+		};
+
+		let result = find_routes_between_ptrs(&dna, &index, &ids, 0x1000, 0x4000, &options).expect("routes succeed");
+		let hops: Vec<Vec<(u64, &str)>> = result
+			.routes
+			.iter()
+			.map(|route| route.iter().map(|edge| (edge.to, edge.field.as_ref())).collect())
+			.collect();
+		assert_eq!(
+			hops,
+			[
+				vec![(0x2000, "next"), (0x4000, "next")],
+				vec![(0x3000, "alt"), (0x4000, "next")],
+				vec![(0x2000, "next"), (0x3000, "alt"), (0x4000, "next")],
+			]
+		);
+		assert!(result.truncated.is_none());
+		assert_eq!(result.reached(0, 0x1000), Some(0x4000));
+
+		options.max_routes = 1;
+		let single = find_routes_between_ptrs(&dna, &index, &ids, 0x1000, 0x4000, &options).expect("routes succeed");
+		let shortest = find_route_between_ptrs(&dna, &index, &ids, 0x1000, 0x4000, &options).expect("route succeeds");
+		assert_eq!(single.routes.len(), 1);
+		assert_eq!(single.routes[0].len(), shortest.path.expect("path found").len());
+	}
+
+	fn coded_block(code: [u8; 4], old: u64, payload: &[u8]) -> Block<'_> {
+		Block {
+			head: BHead {
+				code,
+				sdna_nr: 0,
+				old,
+				len: payload.len() as u64,
+				nr: 1,
+			},
+			payload,
+			file_offset: 0,
+		}
+	}
 }
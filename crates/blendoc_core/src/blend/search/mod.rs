@@ -0,0 +1,410 @@
+use std::sync::Arc;
+
+use crate::blend::{
+	BlendError, BlendFile, Block, DecodeOptions, Dna, Expr, ExprColumn, Result, StructValue, Value, codes, decode_struct_instance, effective_threads,
+	map_blocks_parallel,
+};
+
+/// Comparison operator used by a field predicate.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SearchOp {
+	/// `=` / `==`
+	Eq,
+	/// `!=`
+	Ne,
+	/// `<`
+	Lt,
+	/// `<=`
+	Le,
+	/// `>`
+	Gt,
+	/// `>=`
+	Ge,
+	/// `~=` (substring match on string values)
+	Contains,
+}
+
+impl SearchOp {
+	/// Operator tokens in match priority order (longest first).
+	const TOKENS: [(&'static str, SearchOp); 8] = [
+		("==", SearchOp::Eq),
+		("!=", SearchOp::Ne),
+		("<=", SearchOp::Le),
+		(">=", SearchOp::Ge),
+		("~=", SearchOp::Contains),
+		("=", SearchOp::Eq),
+		("<", SearchOp::Lt),
+		(">", SearchOp::Gt),
+	];
+
+	/// Canonical operator token.
+	pub fn as_str(self) -> &'static str {
+		match self {
+			Self::Eq => "=",
+			Self::Ne => "!=",
+			Self::Lt => "<",
+			Self::Le => "<=",
+			Self::Gt => ">",
+			Self::Ge => ">=",
+			Self::Contains => "~=",
+		}
+	}
+}
+
+/// One `field <op> value` condition applied to decoded struct fields.
+///
+/// A field without dots matches any leaf with that name at any depth
+/// (`name` matches `id.name`); a dotted field must match the full path.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FieldPredicate {
+	/// Field name or dotted field path.
+	pub field: String,
+	/// Comparison operator.
+	pub op: SearchOp,
+	/// Right-hand literal, compared numerically when both sides are numeric.
+	pub value: String,
+}
+
+impl FieldPredicate {
+	/// Parse `field<op>value` (for example `flag!=0` or `id.name~=Cube`).
+	pub fn parse(expr: &str) -> Result<Self> {
+		let invalid = || BlendError::InvalidSearchPredicate { expr: expr.to_owned() };
+
+		let (pos, token, op) = SearchOp::TOKENS
+			.iter()
+			.filter_map(|(token, op)| expr.find(token).map(|pos| (pos, *token, *op)))
+			.min_by_key(|(pos, token, _)| (*pos, std::cmp::Reverse(token.len())))
+			.ok_or_else(invalid)?;
+
+		let field = expr[..pos].trim();
+		if field.is_empty() || !field.bytes().all(|byte| byte.is_ascii_alphanumeric() || byte == b'_' || byte == b'.') {
+			return Err(invalid());
+		}
+
+		Ok(Self {
+			field: field.to_owned(),
+			op,
+			value: expr[pos + token.len()..].trim().to_owned(),
+		})
+	}
+
+	/// Return whether a field path is addressed by this predicate.
+	pub fn matches_path(&self, path: &str) -> bool {
+		if self.field.contains('.') {
+			return path == self.field;
+		}
+		path.rsplit('.').next() == Some(self.field.as_str())
+	}
+
+	/// Evaluate this predicate against one decoded leaf value.
+	pub fn matches_value(&self, value: &Value) -> bool {
+		match value {
+			Value::String(text) => self.compare_text(text),
+			Value::Bool(flag) => self.compare_number(f64::from(u8::from(*flag))),
+			Value::I64(number) => self.compare_number(*number as f64),
+			Value::U64(number) => self.compare_number(*number as f64),
+			Value::F32(number) => self.compare_number(f64::from(*number)),
+			Value::F64(number) => self.compare_number(*number),
+			Value::Ptr(ptr) => self.compare_ptr(*ptr),
+			Value::Null | Value::Bytes(_) | Value::Array(_) | Value::Struct(_) => false,
+		}
+	}
+
+	fn compare_text(&self, text: &str) -> bool {
+		match self.op {
+			SearchOp::Contains => text.contains(self.value.as_str()),
+			op => ordering_matches(op, text.cmp(self.value.as_str())),
+		}
+	}
+
+	fn compare_number(&self, number: f64) -> bool {
+		let rhs = match self.value.strip_prefix("0x").or_else(|| self.value.strip_prefix("0X")) {
+			Some(hex) => u64::from_str_radix(hex, 16).map(|rhs| rhs as f64).ok(),
+			None => self.value.parse::<f64>().ok(),
+		};
+		let Some(rhs) = rhs else {
+			return self.op == SearchOp::Contains && number.to_string().contains(self.value.as_str());
+		};
+		match number.partial_cmp(&rhs) {
+			Some(ordering) => ordering_matches(self.op, ordering),
+			None => self.op == SearchOp::Ne,
+		}
+	}
+
+	fn compare_ptr(&self, ptr: u64) -> bool {
+		let rhs = match self.value.strip_prefix("0x").or_else(|| self.value.strip_prefix("0X")) {
+			Some(hex) => u64::from_str_radix(hex, 16).ok(),
+			None => self.value.parse::<u64>().ok(),
+		};
+		rhs.is_some_and(|rhs| ordering_matches(self.op, ptr.cmp(&rhs)))
+	}
+}
+
+fn ordering_matches(op: SearchOp, ordering: std::cmp::Ordering) -> bool {
+	use std::cmp::Ordering::{Equal, Greater, Less};
+
+	match op {
+		SearchOp::Eq => ordering == Equal,
+		SearchOp::Ne => ordering != Equal,
+		SearchOp::Lt => ordering == Less,
+		SearchOp::Le => ordering != Greater,
+		SearchOp::Gt => ordering == Greater,
+		SearchOp::Ge => ordering != Less,
+		SearchOp::Contains => ordering == Equal,
+	}
+}
+
+/// Whole-file search filters and limits.
+#[derive(Debug, Clone)]
+pub struct SearchOptions {
+	/// Only scan blocks whose SDNA struct has this type name.
+	pub type_name: Option<String>,
+	/// Only scan blocks with this block code.
+	pub code: Option<[u8; 4]>,
+	/// Conditions that must all hold for an element to match.
+	pub predicates: Vec<FieldPredicate>,
+	/// Expressions that must all be truthy for an element to match.
+	pub filters: Vec<Expr>,
+	/// Computed columns evaluated for every hit.
+	pub columns: Vec<ExprColumn>,
+	/// Decode behavior for each scanned element.
+	pub decode: DecodeOptions,
+	/// Maximum number of returned hits.
+	pub max_results: usize,
+	/// Worker threads decoding blocks; `0` uses available parallelism.
+	pub threads: usize,
+}
+
+impl Default for SearchOptions {
+	fn default() -> Self {
+		Self {
+			type_name: None,
+			code: None,
+			predicates: Vec::new(),
+			filters: Vec::new(),
+			columns: Vec::new(),
+			decode: DecodeOptions::default(),
+			max_results: 1024,
+			threads: 1,
+		}
+	}
+}
+
+impl SearchOptions {
+	/// Add one `--where` condition.
+	///
+	/// `field<op>number` and `field<op>0xPTR` keep [`FieldPredicate`]
+	/// semantics (every matching leaf is reported); anything else, such as
+	/// `dimx > dimy`, `totvert > totedge + 1`, or `len(name) > 60`, becomes an
+	/// [`Expr`] filter, where a bare right-hand word is a field reference.
+	pub fn add_where(&mut self, expr: &str) -> Result<()> {
+		match FieldPredicate::parse(expr) {
+			Ok(predicate) if is_number_literal(&predicate.value) => self.predicates.push(predicate),
+			_ => self.filters.push(Expr::parse(expr)?),
+		}
+		Ok(())
+	}
+}
+
+/// Whether a predicate right-hand side is a plain number or hex pointer.
+fn is_number_literal(value: &str) -> bool {
+	match value.strip_prefix("0x").or_else(|| value.strip_prefix("0X")) {
+		Some(hex) => u64::from_str_radix(hex, 16).is_ok(),
+		None => value.parse::<f64>().is_ok_and(f64::is_finite),
+	}
+}
+
+/// One field value that satisfied a predicate.
+#[derive(Debug, Clone)]
+pub struct SearchField {
+	/// Dotted field path inside the element.
+	pub path: Arc<str>,
+	/// Matched leaf value.
+	pub value: Value,
+}
+
+/// One struct element satisfying every predicate.
+#[derive(Debug, Clone)]
+pub struct SearchHit {
+	/// Block code containing the element.
+	pub code: [u8; 4],
+	/// Canonical pointer for the element (`block.old + index * size`).
+	pub canonical: u64,
+	/// Element index inside its block.
+	pub element_index: usize,
+	/// Element struct type name.
+	pub type_name: Arc<str>,
+	/// Leaf fields that satisfied the predicates.
+	pub fields: Vec<SearchField>,
+	/// Values of [`SearchOptions::columns`], in order.
+	pub columns: Vec<Value>,
+}
+
+/// Search output plus scan accounting.
+#[derive(Debug, Clone)]
+pub struct SearchResult {
+	/// Matching elements in file order.
+	pub hits: Vec<SearchHit>,
+	/// Number of blocks decoded after code/type filtering.
+	pub scanned_blocks: usize,
+	/// Number of elements that failed to decode and were skipped.
+	pub decode_failures: usize,
+	/// Whether `max_results` stopped the scan early.
+	pub truncated: bool,
+}
+
+/// Decode every SDNA-typed block and collect elements matching all predicates.
+///
+/// With [`SearchOptions::threads`] above one, blocks are searched on a worker
+/// pool and merged in file order, so the result matches a serial scan.
+pub fn search_blocks(file: &BlendFile, dna: &Dna, options: &SearchOptions) -> Result<SearchResult> {
+	let mut result = empty_result();
+	if effective_threads(options.threads) <= 1 {
+		for block in file.blocks() {
+			let block = block?;
+			if search_block(dna, &block, options, &mut result) {
+				result.truncated = true;
+				break;
+			}
+		}
+		return Ok(result);
+	}
+
+	// A block error only surfaces if the scan reaches it, as in the serial path.
+	let mut blocks = Vec::new();
+	let mut stream_error = None;
+	for block in file.blocks() {
+		match block {
+			Ok(block) => blocks.push(block),
+			Err(err) => {
+				stream_error = Some(err);
+				break;
+			}
+		}
+	}
+
+	let per_block = map_blocks_parallel(&blocks, options.threads, |block| {
+		let mut local = empty_result();
+		let truncated = search_block(dna, block, options, &mut local);
+		(local, truncated)
+	});
+	for (block, (local, truncated)) in blocks.iter().zip(per_block) {
+		if truncated || local.hits.len() > options.max_results - result.hits.len() {
+			// Redo the block that fills `max_results` against the merged hits so the
+			// counters stop exactly where a serial scan would.
+			search_block(dna, block, options, &mut result);
+			result.truncated = true;
+			return Ok(result);
+		}
+		result.hits.extend(local.hits);
+		result.scanned_blocks += local.scanned_blocks;
+		result.decode_failures += local.decode_failures;
+	}
+
+	match stream_error {
+		Some(err) => Err(err),
+		None => Ok(result),
+	}
+}
+
+fn empty_result() -> SearchResult {
+	SearchResult {
+		hits: Vec::new(),
+		scanned_blocks: 0,
+		decode_failures: 0,
+		truncated: false,
+	}
+}
+
+/// Search one block, appending hits; returns `true` once `max_results` is reached.
+pub fn search_block(dna: &Dna, block: &Block<'_>, options: &SearchOptions, result: &mut SearchResult) -> bool {
+	if block.head.code == codes::DNA1 || block.head.is_endb() {
+		return false;
+	}
+	if options.code.is_some_and(|code| code != block.head.code) {
+		return false;
+	}
+
+	let Some(item) = dna.struct_by_sdna(block.head.sdna_nr) else {
+		return false;
+	};
+	let type_name = dna.type_name(item.type_idx);
+	if options.type_name.as_deref().is_some_and(|wanted| wanted != type_name) {
+		return false;
+	}
+
+	let struct_size = usize::from(dna.tlen[item.type_idx as usize]);
+	if struct_size == 0 {
+		return false;
+	}
+
+	result.scanned_blocks += 1;
+	let type_name = Arc::<str>::from(type_name);
+	let count = usize::try_from(block.head.nr).unwrap_or(usize::MAX).min(block.payload.len() / struct_size);
+
+	for element_index in 0..count {
+		let start = element_index * struct_size;
+		let bytes = &block.payload[start..start + struct_size];
+		let Ok(value) = decode_struct_instance(dna, block.head.sdna_nr, bytes, &options.decode) else {
+			result.decode_failures += 1;
+			continue;
+		};
+
+		let Some(fields) = match_predicates(&value, &options.predicates) else {
+			continue;
+		};
+		if !options.filters.iter().all(|filter| filter.matches(&value)) {
+			continue;
+		}
+
+		if result.hits.len() >= options.max_results {
+			return true;
+		}
+		result.hits.push(SearchHit {
+			code: block.head.code,
+			canonical: block.head.old.saturating_add(start as u64),
+			element_index,
+			type_name: type_name.clone(),
+			fields,
+			columns: options.columns.iter().map(|column| column.expr.eval(&value)).collect(),
+		});
+	}
+
+	false
+}
+
+fn match_predicates(value: &StructValue, predicates: &[FieldPredicate]) -> Option<Vec<SearchField>> {
+	let mut leaves = Vec::new();
+	collect_leaves(value, "", &mut leaves);
+
+	let mut out = Vec::new();
+	for predicate in predicates {
+		let mut matched = false;
+		for (path, leaf) in &leaves {
+			if predicate.matches_path(path) && predicate.matches_value(leaf) {
+				matched = true;
+				out.push(SearchField {
+					path: Arc::<str>::from(path.as_str()),
+					value: (*leaf).clone(),
+				});
+			}
+		}
+		if !matched {
+			return None;
+		}
+	}
+	Some(out)
+}
+
+fn collect_leaves<'v>(value: &'v StructValue, prefix: &str, out: &mut Vec<(String, &'v Value)>) {
+	for field in &value.fields {
+		let path = format!("{prefix}{}", field.name);
+		match &field.value {
+			Value::Struct(nested) => collect_leaves(nested, &format!("{path}."), out),
+			leaf => out.push((path, leaf)),
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,227 @@
+mod predicate_parse {
+	use crate::blend::{BlendError, FieldPredicate, SearchOp, Value};
+
+	#[test]
+	fn parses_operators_by_position_and_length() {
+		let cases = [
+			("flag!=0", "flag", SearchOp::Ne, "0"),
+			("id.name~=Cube", "id.name", SearchOp::Contains, "Cube"),
+			("lay<=3", "lay", SearchOp::Le, "3"),
+			("type == 2", "type", SearchOp::Eq, "2"),
+			("name=a=b", "name", SearchOp::Eq, "a=b"),
+			("size>1.5", "size", SearchOp::Gt, "1.5"),
+		];
+		for (expr, field, op, value) in cases {
+			let predicate = FieldPredicate::parse(expr).expect("predicate parses");
+			assert_eq!(predicate.field, field, "{expr}");
+			assert_eq!(predicate.op, op, "{expr}");
+			assert_eq!(predicate.value, value, "{expr}");
+		}
+	}
+
+	#[test]
+	fn rejects_missing_operator_or_field() {
+		for expr in ["flag", "=3", "bad field=1"] {
+			let err = FieldPredicate::parse(expr).expect_err("predicate should fail");
+			assert!(matches!(err, BlendError::InvalidSearchPredicate { .. }), "{expr}");
+		}
+	}
+
+	#[test]
+	fn compares_numbers_strings_and_pointers() {
+		let ne = FieldPredicate::parse("flag!=0").expect("predicate parses");
+		assert!(ne.matches_value(&Value::I64(4)));
+		assert!(!ne.matches_value(&Value::U64(0)));
+
+		let contains = FieldPredicate::parse("name~=Cu").expect("predicate parses");
+		assert!(contains.matches_value(&Value::String("OBCube".into())));
+		assert!(!contains.matches_value(&Value::String("OBLamp".into())));
+
+		let ptr = FieldPredicate::parse("data=0x2000").expect("predicate parses");
+		assert!(ptr.matches_value(&Value::Ptr(0x2000)));
+
+		assert!(ne.matches_path("flag"));
+		assert!(ne.matches_path("id.flag"));
+		assert!(!FieldPredicate::parse("id.flag!=0").expect("predicate parses").matches_path("flag"));
+	}
+}
+
+mod synthetic_search {
+	use crate::blend::{BHead, Block, Dna, DnaField, DnaStruct, Endianness, ExprColumn, FieldPredicate, SearchOptions, SearchResult, Value, search_block};
+
+	#[test]
+	fn search_block_filters_elements_by_all_predicates() {
+		let dna = Dna::from_tables(
+			Endianness::Little,
+			8,
+			vec!["name[8]".into(), "flag".into()],
+			vec!["char".into(), "int".into(), "Item".into()],
+			vec![1, 4, 12],
+			vec![DnaStruct {
+				type_idx: 2,
+				fields: vec![DnaField { type_idx: 0, name_idx: 0 }, DnaField { type_idx: 1, name_idx: 1 }],
+			}],
+		)
+		.expect("valid dna tables");
+
+		let mut payload = Vec::new();
+		for (name, flag) in [(&b"Cube\0\0\0\0"[..], 1_i32), (b"Cube.001", 0), (b"Lamp\0\0\0\0", 1)] {
+			payload.extend_from_slice(name);
+			payload.extend_from_slice(&flag.to_le_bytes());
+		}
+		let block = Block {
+			head: BHead {
+				code: *b"DATA",
+				sdna_nr: 0,
+				old: 0x1000,
+				len: payload.len() as u64,
+				nr: 3,
+			},
+			payload: &payload,
+			file_offset: 0,
+		};
+
+		let options = SearchOptions {
+			type_name: Some("Item".to_owned()),
+			predicates: vec![
+				FieldPredicate::parse("name~=Cube").expect("predicate parses"),
+				FieldPredicate::parse("flag!=0").expect("predicate parses"),
+			],
+			..SearchOptions::default()
+		};
+		let mut result = SearchResult {
+			hits: Vec::new(),
+			scanned_blocks: 0,
+			decode_failures: 0,
+			truncated: false,
+		};
+
+		assert!(!search_block(&dna, &block, &options, &mut result));
+		assert_eq!(result.scanned_blocks, 1);
+		assert_eq!(result.hits.len(), 1);
+		assert_eq!(result.hits[0].element_index, 0);
+		assert_eq!(result.hits[0].canonical, 0x1000);
+		assert_eq!(result.hits[0].fields.len(), 2);
+
+		let other_type = SearchOptions {
+			type_name: Some("Object".to_owned()),
+			..options
+		};
+		let mut skipped = SearchResult {
+			hits: Vec::new(),
+			scanned_blocks: 0,
+			decode_failures: 0,
+			truncated: false,
+		};
+		search_block(&dna, &block, &other_type, &mut skipped);
+		assert_eq!(skipped.scanned_blocks, 0);
+
+		let mut computed = SearchOptions {
+			type_name: Some("Item".to_owned()),
+			columns: vec![ExprColumn::parse("twice=flag*2").expect("column parses")],
+			..SearchOptions::default()
+		};
+		computed.add_where("len(name) > 4 || flag == 0").expect("filter parses");
+		computed.add_where("flag=0").expect("predicate parses");
+		assert_eq!((computed.predicates.len(), computed.filters.len()), (1, 1));
+		let mut filtered = SearchResult {
+			hits: Vec::new(),
+			scanned_blocks: 0,
+			decode_failures: 0,
+			truncated: false,
+		};
+		search_block(&dna, &block, &computed, &mut filtered);
+		assert_eq!(filtered.hits.len(), 1);
+		assert_eq!(filtered.hits[0].element_index, 1);
+		assert!(matches!(filtered.hits[0].columns[..], [Value::I64(0)]));
+	}
+
+	#[test]
+	fn where_compares_fields_and_arithmetic_through_expressions() {
+		let dna = Dna::from_tables(
+			Endianness::Little,
+			8,
+			vec!["dimx".into(), "dimy".into()],
+			vec!["int".into(), "Size".into()],
+			vec![4, 8],
+			vec![DnaStruct {
+				type_idx: 1,
+				fields: vec![DnaField { type_idx: 0, name_idx: 0 }, DnaField { type_idx: 0, name_idx: 1 }],
+			}],
+		)
+		.expect("valid dna tables");
+
+		let mut payload = Vec::new();
+		for (dimx, dimy) in [(3_i32, 2_i32), (2, 2), (5, 1)] {
+			payload.extend_from_slice(&dimx.to_le_bytes());
+			payload.extend_from_slice(&dimy.to_le_bytes());
+		}
+		let block = Block {
+			head: BHead {
+				code: *b"DATA",
+				sdna_nr: 0,
+				old: 0x1000,
+				len: payload.len() as u64,
+				nr: 3,
+			},
+			payload: &payload,
+			file_offset: 0,
+		};
+
+		let hits = |expr: &str| -> Vec<usize> {
+			let mut options = SearchOptions::default();
+			options.add_where(expr).expect("where parses");
+			let mut result = SearchResult {
+				hits: Vec::new(),
+				scanned_blocks: 0,
+				decode_failures: 0,
+				truncated: false,
+			};
+			search_block(&dna, &block, &options, &mut result);
+			result.hits.iter().map(|hit| hit.element_index).collect()
+		};
+
+		assert_eq!(hits("dimx > dimy"), [0, 2]);
+		assert_eq!(hits("dimx > dimy + 1"), [2]);
+		assert_eq!(hits("dimx >= 2*2"), [2]);
+		assert_eq!(hits("dimx = dimy"), [1]);
+		assert_eq!(hits("dimx < 0x4"), [0, 1]);
+
+		let mut routed = SearchOptions::default();
+		routed.add_where("dimx > dimy").expect("where parses");
+		routed.add_where("dimx > 2").expect("where parses");
+		routed.add_where("dimx > -1.5").expect("where parses");
+		assert_eq!((routed.predicates.len(), routed.filters.len()), (2, 1));
+	}
+}
+
+mod parallel_search {
+	use blendoc_testkit::builder::{HeaderKind, scene_file};
+
+	use crate::blend::{BlendFile, SearchOptions, search_blocks};
+
+	#[test]
+	fn threaded_search_matches_serial_including_truncation() {
+		let blend = BlendFile::from_bytes(scene_file(HeaderKind::Legacy8, false)).expect("synthetic file parses");
+		let dna = blend.dna().expect("dna");
+		let summary = |threads: usize, max_results: usize| {
+			let options = SearchOptions {
+				threads,
+				max_results,
+				..SearchOptions::default()
+			};
+			let result = search_blocks(&blend, &dna, &options).expect("search runs");
+			let hits: Vec<u64> = result.hits.iter().map(|hit| hit.canonical).collect();
+			(hits, result.scanned_blocks, result.decode_failures, result.truncated)
+		};
+
+		let all = summary(1, usize::MAX);
+		assert!(all.0.len() > 2, "{all:?}");
+		for max_results in [0, 1, 2, all.0.len() - 1, all.0.len(), usize::MAX] {
+			let serial = summary(1, max_results);
+			for threads in [2, 4, 0] {
+				assert_eq!(summary(threads, max_results), serial, "threads={threads} max_results={max_results}");
+			}
+		}
+	}
+}
@@ -0,0 +1,212 @@
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex, MutexGuard};
+use std::time::SystemTime;
+
+use crate::blend::{BlendFile, Dna, IdIndex, IdScanOptions, PointerIndex, RefCache, Result, Warning, WarningCollector, scan_id_blocks_with};
+
+/// Size and modification time a cached file was read at.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct FileStamp {
+	len: u64,
+	modified: Option<SystemTime>,
+}
+
+impl FileStamp {
+	fn read(path: &Path) -> Result<Self> {
+		let metadata = std::fs::metadata(path)?;
+		Ok(Self {
+			len: metadata.len(),
+			modified: metadata.modified().ok(),
+		})
+	}
+}
+
+/// One parsed file with the indexes every query needs.
+///
+/// The pointer index borrows the file bytes, so it is rebuilt per query with
+/// [`CachedFile::pointer_index`]; the SDNA, ID index, and reference-scan memo
+/// are built once per load.
+pub struct CachedFile {
+	/// Canonical path the file was read from.
+	pub path: PathBuf,
+	/// Parsed file, fully read into memory so later writes to disk cannot tear it.
+	pub blend: BlendFile,
+	/// Parsed SDNA schema.
+	pub dna: Dna,
+	/// ID-root records from a fail-soft scan.
+	pub ids: IdIndex,
+	/// Undecodable ID blocks skipped by the scan.
+	pub skipped_ids: Vec<Warning>,
+	/// Shared reference-scan memo, valid for this load only.
+	pub ref_cache: RefCache,
+	stamp: FileStamp,
+}
+
+impl CachedFile {
+	/// Read and index `path` without caching it.
+	pub fn load(path: impl AsRef<Path>) -> Result<Self> {
+		let path = path.as_ref();
+		let stamp = FileStamp::read(path)?;
+		let blend = BlendFile::open(path)?;
+		let dna = blend.dna()?;
+		let warnings = WarningCollector::new();
+		let options = IdScanOptions {
+			warnings: Some(warnings.clone()),
+			..IdScanOptions::default()
+		};
+		let ids = IdIndex::build(scan_id_blocks_with(&blend, &dna, &options)?);
+		Ok(Self {
+			path: path.to_path_buf(),
+			blend,
+			dna,
+			ids,
+			skipped_ids: warnings.take(),
+			ref_cache: RefCache::new(),
+			stamp,
+		})
+	}
+
+	/// Build the pointer index over this file's blocks.
+	pub fn pointer_index(&self) -> Result<PointerIndex<'_>> {
+		self.blend.pointer_index()
+	}
+
+	/// Return whether the file on disk no longer matches this load.
+	pub fn is_stale(&self) -> bool {
+		FileStamp::read(&self.path).map_or(true, |stamp| stamp != self.stamp)
+	}
+}
+
+/// Counters describing how a [`FileCache`] served lookups.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct FileCacheStats {
+	/// Number of files currently held.
+	pub files: usize,
+	/// Lookups answered from memory.
+	pub hits: usize,
+	/// Lookups that read a file for the first time.
+	pub misses: usize,
+	/// Lookups that re-read a file changed on disk.
+	pub reloads: usize,
+	/// Files dropped to stay within capacity.
+	pub evictions: usize,
+}
+
+/// Bounded in-memory cache of parsed files keyed by canonical path.
+///
+/// Clones share one cache. A lookup re-reads a file whose size or
+/// modification time changed since it was loaded; when the cache is full the
+/// least recently used file is dropped. Files already handed out stay alive
+/// until their last [`Arc`] goes away.
+#[derive(Clone)]
+pub struct FileCache {
+	inner: Arc<Mutex<FileCacheState>>,
+}
+
+struct FileCacheState {
+	capacity: usize,
+	entries: HashMap<PathBuf, CacheEntry>,
+	clock: u64,
+	stats: FileCacheStats,
+}
+
+struct CacheEntry {
+	file: Arc<CachedFile>,
+	last_used: u64,
+}
+
+impl FileCache {
+	/// Create an empty cache holding at most `capacity` files (at least one).
+	pub fn new(capacity: usize) -> Self {
+		Self {
+			inner: Arc::new(Mutex::new(FileCacheState {
+				capacity: capacity.max(1),
+				entries: HashMap::new(),
+				clock: 0,
+				stats: FileCacheStats::default(),
+			})),
+		}
+	}
+
+	/// Return the parsed file at `path`, loading or reloading it as needed.
+	///
+	/// Loading happens outside the lock, so concurrent lookups of other
+	/// files are not blocked; two racing loads of one path keep the later one.
+	pub fn get(&self, path: impl AsRef<Path>) -> Result<Arc<CachedFile>> {
+		let path = std::fs::canonicalize(path)?;
+		let reload = {
+			let mut state = self.lock();
+			state.clock += 1;
+			let clock = state.clock;
+			match state.entries.get_mut(&path) {
+				Some(entry) if !entry.file.is_stale() => {
+					entry.last_used = clock;
+					let file = entry.file.clone();
+					state.stats.hits += 1;
+					return Ok(file);
+				}
+				Some(_) => true,
+				None => false,
+			}
+		};
+
+		let file = Arc::new(CachedFile::load(&path)?);
+		let mut state = self.lock();
+		if reload {
+			state.stats.reloads += 1;
+		} else {
+			state.stats.misses += 1;
+		}
+		if !state.entries.contains_key(&path) && state.entries.len() >= state.capacity {
+			state.evict_oldest();
+		}
+		let last_used = state.clock;
+		state.entries.insert(path, CacheEntry { file: file.clone(), last_used });
+		Ok(file)
+	}
+
+	/// Drop the cached copy of `path`; returns whether one was held.
+	pub fn evict(&self, path: impl AsRef<Path>) -> bool {
+		let path = std::fs::canonicalize(&path).unwrap_or_else(|_| path.as_ref().to_path_buf());
+		self.lock().entries.remove(&path).is_some()
+	}
+
+	/// Drop every cached file; counters are kept.
+	pub fn clear(&self) {
+		self.lock().entries.clear();
+	}
+
+	/// Return canonical paths of the cached files, sorted.
+	pub fn paths(&self) -> Vec<PathBuf> {
+		let mut paths: Vec<PathBuf> = self.lock().entries.keys().cloned().collect();
+		paths.sort();
+		paths
+	}
+
+	/// Return current size and lookup counters.
+	pub fn stats(&self) -> FileCacheStats {
+		let state = self.lock();
+		FileCacheStats {
+			files: state.entries.len(),
+			..state.stats
+		}
+	}
+
+	fn lock(&self) -> MutexGuard<'_, FileCacheState> {
+		self.inner.lock().unwrap_or_else(|poisoned| poisoned.into_inner())
+	}
+}
+
+impl FileCacheState {
+	fn evict_oldest(&mut self) {
+		let oldest = self.entries.iter().min_by_key(|(_, entry)| entry.last_used).map(|(path, _)| path.clone());
+		if let Some(path) = oldest {
+			self.entries.remove(&path);
+			self.stats.evictions += 1;
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,66 @@
+mod synthetic_file_cache {
+	use std::sync::Arc;
+
+	use blendoc_testkit::builder::{HeaderKind, SCENE_ID_NAMES, scene_file};
+
+	use crate::blend::{FileCache, FileCacheStats};
+
+	fn temp_file(tag: &str) -> std::path::PathBuf {
+		let target = std::env::temp_dir().join(format!("blendoc_file_cache_{tag}_{}.blend", std::process::id()));
+		std::fs::write(&target, scene_file(HeaderKind::Legacy8, false)).expect("synthetic file written");
+		target
+	}
+
+	#[test]
+	fn repeated_lookups_share_one_load() {
+		let target = temp_file("shared");
+		let cache = FileCache::new(4);
+
+		let first = cache.get(&target).expect("file loads");
+		let second = cache.get(&target).expect("file served from cache");
+		assert!(Arc::ptr_eq(&first, &second));
+		assert_eq!(first.ids.records.len(), SCENE_ID_NAMES.len());
+		assert!(first.pointer_index().is_ok());
+		assert_eq!(
+			cache.stats(),
+			FileCacheStats {
+				files: 1,
+				hits: 1,
+				misses: 1,
+				..FileCacheStats::default()
+			}
+		);
+
+		// A size change marks the entry stale even within one mtime tick.
+		let mut bytes = std::fs::read(&target).expect("synthetic file read");
+		bytes.extend_from_slice(&[0; 16]);
+		std::fs::write(&target, bytes).expect("synthetic file rewritten");
+		let reloaded = cache.get(&target).expect("file reloads");
+		assert!(!Arc::ptr_eq(&first, &reloaded));
+		assert_eq!(cache.stats().reloads, 1);
+
+		assert!(cache.evict(&target));
+		assert!(cache.paths().is_empty());
+		std::fs::remove_file(&target).expect("synthetic file removed");
+	}
+
+	#[test]
+	fn least_recently_used_file_is_evicted() {
+		let (a, b, c) = (temp_file("lru_a"), temp_file("lru_b"), temp_file("lru_c"));
+		let cache = FileCache::new(2);
+
+		cache.get(&a).expect("a loads");
+		cache.get(&b).expect("b loads");
+		cache.get(&a).expect("a served from cache");
+		cache.get(&c).expect("c loads");
+
+		let paths = cache.paths();
+		assert_eq!(cache.stats().evictions, 1);
+		assert!(paths.contains(&std::fs::canonicalize(&a).expect("a canonicalizes")));
+		assert!(!paths.contains(&std::fs::canonicalize(&b).expect("b canonicalizes")));
+
+		for target in [a, b, c] {
+			std::fs::remove_file(&target).expect("synthetic file removed");
+		}
+	}
+}
@@ -0,0 +1,190 @@
+use std::collections::HashMap;
+
+use crate::blend::footprint::IdFootprint;
+use crate::blend::{BlendFile, Block, Dna, IdIndex, Result, codes, scan_id_footprints};
+
+/// Payload accounting for all blocks sharing one SDNA struct index.
+#[derive(Debug, Clone)]
+pub struct TypeSizeStats {
+	/// SDNA struct index from the block headers.
+	pub sdna_nr: u32,
+	/// Struct type name, or `<sdna N>` when the index is out of range.
+	pub type_name: Box<str>,
+	/// SDNA struct size in bytes.
+	pub struct_size: u64,
+	/// Number of blocks.
+	pub blocks: usize,
+	/// Sum of block element counts (`nr`).
+	pub elements: u64,
+	/// Sum of block payload lengths.
+	pub payload_bytes: u64,
+}
+
+impl TypeSizeStats {
+	/// Mean payload bytes per block.
+	pub fn avg_block_bytes(&self) -> u64 {
+		self.payload_bytes / self.blocks.max(1) as u64
+	}
+}
+
+/// Payload accounting for non-struct blocks (`DNA1`, `TEST`, `REND`, `ENDB`).
+#[derive(Debug, Clone)]
+pub struct RawCodeSizeStats {
+	/// Block code.
+	pub code: [u8; 4],
+	/// Number of blocks.
+	pub blocks: usize,
+	/// Sum of block payload lengths.
+	pub payload_bytes: u64,
+}
+
+/// ID footprints aggregated per ID block code.
+#[derive(Debug, Clone)]
+pub struct IdTypeSizeStats {
+	/// ID block code (`OB\0\0`, `ME\0\0`, ...).
+	pub code: [u8; 4],
+	/// ID struct type name.
+	pub type_name: Box<str>,
+	/// Number of IDs.
+	pub ids: usize,
+	/// Payload bytes of the ID-root blocks.
+	pub id_bytes: u64,
+	/// Number of owned `DATA` blocks.
+	pub data_blocks: usize,
+	/// Payload bytes of owned `DATA` blocks.
+	pub data_bytes: u64,
+}
+
+impl IdTypeSizeStats {
+	/// ID-root plus owned data payload bytes.
+	pub fn total_bytes(&self) -> u64 {
+		self.id_bytes.saturating_add(self.data_bytes)
+	}
+}
+
+/// Per-type payload size report for a whole file.
+#[derive(Debug, Clone)]
+pub struct SizeStats {
+	/// Number of blocks scanned.
+	pub block_count: usize,
+	/// Sum of all block payload lengths.
+	pub payload_bytes: u64,
+	/// Per-SDNA-type totals, largest payload first.
+	pub types: Vec<TypeSizeStats>,
+	/// Non-struct block totals, largest payload first.
+	pub raw: Vec<RawCodeSizeStats>,
+	/// Per-ID-type footprint totals, largest total first.
+	pub id_types: Vec<IdTypeSizeStats>,
+	/// `DATA` blocks not attributed to any ID footprint.
+	pub unowned_data_blocks: usize,
+	/// Payload bytes of unattributed `DATA` blocks.
+	pub unowned_data_bytes: u64,
+}
+
+/// Aggregate payload bytes per SDNA type and per ID type.
+///
+/// ID-type totals reuse [`scan_id_footprints`], so `DATA` blocks are
+/// attributed to the ID block written before them.
+pub fn scan_size_stats(file: &BlendFile, dna: &Dna, ids: &IdIndex) -> Result<SizeStats> {
+	let footprints = scan_id_footprints(file, ids)?;
+	size_stats_from_blocks(file.blocks(), dna, ids, &footprints)
+}
+
+pub(crate) fn size_stats_from_blocks<'a>(
+	blocks: impl Iterator<Item = Result<Block<'a>>>,
+	dna: &Dna,
+	ids: &IdIndex,
+	footprints: &[IdFootprint],
+) -> Result<SizeStats> {
+	let mut block_count = 0_usize;
+	let mut payload_bytes = 0_u64;
+	let mut data_blocks = 0_usize;
+	let mut data_bytes = 0_u64;
+	let mut types: HashMap<u32, TypeSizeStats> = HashMap::new();
+	let mut raw: HashMap<[u8; 4], RawCodeSizeStats> = HashMap::new();
+
+	for block in blocks {
+		let head = block?.head;
+		block_count += 1;
+		payload_bytes = payload_bytes.saturating_add(head.len);
+		if head.code == codes::DATA {
+			data_blocks += 1;
+			data_bytes = data_bytes.saturating_add(head.len);
+		}
+
+		if codes::is_raw_code(head.code) {
+			let entry = raw.entry(head.code).or_insert(RawCodeSizeStats {
+				code: head.code,
+				blocks: 0,
+				payload_bytes: 0,
+			});
+			entry.blocks += 1;
+			entry.payload_bytes = entry.payload_bytes.saturating_add(head.len);
+			continue;
+		}
+
+		let entry = types.entry(head.sdna_nr).or_insert_with(|| new_type_stats(dna, head.sdna_nr));
+		entry.blocks += 1;
+		entry.elements = entry.elements.saturating_add(head.nr);
+		entry.payload_bytes = entry.payload_bytes.saturating_add(head.len);
+	}
+
+	let mut id_types: HashMap<[u8; 4], IdTypeSizeStats> = HashMap::new();
+	for footprint in footprints {
+		let entry = id_types.entry(footprint.code).or_insert_with(|| IdTypeSizeStats {
+			code: footprint.code,
+			type_name: ids
+				.get_by_ptr(footprint.id_ptr)
+				.map(|record| record.type_name.clone())
+				.unwrap_or_else(|| "<unknown>".into()),
+			ids: 0,
+			id_bytes: 0,
+			data_blocks: 0,
+			data_bytes: 0,
+		});
+		entry.ids += 1;
+		entry.id_bytes = entry.id_bytes.saturating_add(footprint.id_bytes);
+		entry.data_blocks += footprint.data_blocks;
+		entry.data_bytes = entry.data_bytes.saturating_add(footprint.data_bytes);
+	}
+	let owned_blocks: usize = footprints.iter().map(|item| item.data_blocks).sum();
+	let owned_bytes: u64 = footprints.iter().map(|item| item.data_bytes).sum();
+
+	let mut types: Vec<_> = types.into_values().collect();
+	types.sort_by(|left, right| right.payload_bytes.cmp(&left.payload_bytes).then(left.sdna_nr.cmp(&right.sdna_nr)));
+	let mut raw: Vec<_> = raw.into_values().collect();
+	raw.sort_by(|left, right| right.payload_bytes.cmp(&left.payload_bytes).then(left.code.cmp(&right.code)));
+	let mut id_types: Vec<_> = id_types.into_values().collect();
+	id_types.sort_by(|left, right| right.total_bytes().cmp(&left.total_bytes()).then(left.code.cmp(&right.code)));
+
+	Ok(SizeStats {
+		block_count,
+		payload_bytes,
+		types,
+		raw,
+		id_types,
+		unowned_data_blocks: data_blocks.saturating_sub(owned_blocks),
+		unowned_data_bytes: data_bytes.saturating_sub(owned_bytes),
+	})
+}
+
+fn new_type_stats(dna: &Dna, sdna_nr: u32) -> TypeSizeStats {
+	let (type_name, struct_size) = match dna.struct_by_sdna(sdna_nr) {
+		Some(item) => (
+			dna.type_name(item.type_idx).into(),
+			dna.tlen.get(usize::from(item.type_idx)).copied().map_or(0, u64::from),
+		),
+		None => (format!("<sdna {sdna_nr}>").into_boxed_str(), 0),
+	};
+	TypeSizeStats {
+		sdna_nr,
+		type_name,
+		struct_size,
+		blocks: 0,
+		elements: 0,
+		payload_bytes: 0,
+	}
+}
+
+#[cfg(test)]
+mod tests;
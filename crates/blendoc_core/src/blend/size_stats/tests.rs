@@ -0,0 +1,88 @@
+mod synthetic_blocks {
+	use super::super::size_stats_from_blocks;
+	use crate::blend::footprint::footprints_from_blocks;
+	use crate::blend::{BHead, Block, Dna, DnaStruct, Endianness, IdIndex, IdRecord};
+
+	fn block(code: &[u8; 4], old: u64, sdna_nr: u32, len: u64, nr: u64) -> crate::blend::Result<Block<'static>> {
+		Ok(Block {
+			head: BHead {
+				code: *code,
+				sdna_nr,
+				old,
+				len,
+				nr,
+			},
+			payload: &[],
+			file_offset: 0,
+		})
+	}
+
+	fn blocks() -> Vec<crate::blend::Result<Block<'static>>> {
+		vec![
+			block(b"OB\0\0", 0x1000, 1, 96, 1),
+			block(b"DATA", 0x1100, 2, 64, 4),
+			block(b"ME\0\0", 0x2000, 0, 48, 1),
+			block(b"DATA", 0x2100, 2, 1600, 100),
+			block(b"DNA1", 0, 0, 512, 1),
+			block(b"DATA", 0x3000, 2, 32, 2),
+			block(b"ENDB", 0, 0, 0, 0),
+		]
+	}
+
+	fn dna() -> Dna {
+		Dna::from_tables(
+			Endianness::Little,
+			8,
+			vec![],
+			vec!["Mesh".into(), "Object".into(), "MVert".into()],
+			vec![48, 96, 16],
+			vec![
+				DnaStruct { type_idx: 0, fields: vec![] },
+				DnaStruct { type_idx: 1, fields: vec![] },
+				DnaStruct { type_idx: 2, fields: vec![] },
+			],
+		)
+		.expect("valid dna tables")
+	}
+
+	fn record(code: &[u8; 4], old_ptr: u64, sdna_nr: u32, type_name: &str, id_name: &str) -> IdRecord {
+		IdRecord {
+			old_ptr,
+			code: *code,
+			sdna_nr,
+			type_name: type_name.into(),
+			id_name: id_name.into(),
+			next: None,
+			prev: None,
+			lib: None,
+		}
+	}
+
+	#[test]
+	fn totals_group_by_sdna_type_and_id_type() {
+		let ids = IdIndex::build(vec![
+			record(b"OB\0\0", 0x1000, 1, "Object", "OBCube"),
+			record(b"ME\0\0", 0x2000, 0, "Mesh", "MECube"),
+		]);
+		let footprints = footprints_from_blocks(blocks().into_iter(), &ids).expect("footprints");
+		let stats = size_stats_from_blocks(blocks().into_iter(), &dna(), &ids, &footprints).expect("stats");
+
+		assert_eq!(stats.block_count, 7);
+		assert_eq!(stats.payload_bytes, 96 + 64 + 48 + 1600 + 512 + 32);
+
+		let first = &stats.types[0];
+		assert_eq!(first.type_name.as_ref(), "MVert");
+		assert_eq!((first.blocks, first.elements, first.payload_bytes), (3, 106, 1696));
+		assert_eq!(first.struct_size, 16);
+		assert_eq!(first.avg_block_bytes(), 565);
+		assert_eq!(stats.types.len(), 3);
+
+		assert_eq!(stats.raw.len(), 2);
+		assert_eq!(&stats.raw[0].code, b"DNA1");
+
+		assert_eq!(&stats.id_types[0].code, b"ME\0\0");
+		assert_eq!(stats.id_types[0].total_bytes(), 48 + 1600);
+		assert_eq!(stats.id_types[1].total_bytes(), 96 + 64);
+		assert_eq!((stats.unowned_data_blocks, stats.unowned_data_bytes), (1, 32));
+	}
+}
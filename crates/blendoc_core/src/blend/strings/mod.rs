@@ -0,0 +1,150 @@
+use std::sync::Arc;
+
+use crate::blend::decl::parse_field_decl;
+use crate::blend::{BlendFile, Block, Dna, Result, codes};
+
+/// Longest surrounding text captured on each side of a match.
+const MAX_CONTEXT_BYTES: usize = 256;
+
+/// One raw byte-string occurrence inside a block payload.
+#[derive(Debug, Clone)]
+pub struct StringHit {
+	/// Block code containing the match.
+	pub code: [u8; 4],
+	/// Stored old pointer of the containing block.
+	pub block_old: u64,
+	/// SDNA index of the containing block.
+	pub sdna_nr: u32,
+	/// Struct type name of the containing block, when SDNA resolves it.
+	pub type_name: Option<Arc<str>>,
+	/// Absolute file offset of the match.
+	pub file_offset: usize,
+	/// Offset of the match inside the block payload.
+	pub payload_offset: usize,
+	/// Struct element index containing the match.
+	pub element_index: Option<usize>,
+	/// SDNA field path (`field` or `field.sub[i]`) containing the match.
+	pub field: Option<Arc<str>>,
+	/// NUL-delimited text surrounding the match, lossily decoded.
+	pub text: Arc<str>,
+	/// Whether a NUL, quote, bracket, or the payload edge sits on both sides
+	/// of the match (`Cube`, `objects["Cube"]`), rather than it being part of
+	/// a longer name (`xCube`, `Cube.001`).
+	pub whole_token: bool,
+}
+
+/// Find every occurrence of `needle` across all data block payloads.
+///
+/// `DNA1` and `ENDB` blocks are skipped since they never hold user data.
+pub fn find_string_occurrences(file: &BlendFile, dna: &Dna, needle: &[u8]) -> Result<Vec<StringHit>> {
+	let mut out = Vec::new();
+	for block in file.blocks() {
+		let block = block?;
+		if block.head.code == codes::DNA1 || block.head.is_endb() {
+			continue;
+		}
+		out.extend(find_string_in_block(dna, &block, needle));
+	}
+	Ok(out)
+}
+
+/// Find every occurrence of `needle` in one block payload.
+pub fn find_string_in_block(dna: &Dna, block: &Block<'_>, needle: &[u8]) -> Vec<StringHit> {
+	if needle.is_empty() || block.payload.len() < needle.len() {
+		return Vec::new();
+	}
+
+	let item = dna.struct_by_sdna(block.head.sdna_nr);
+	let type_name = item.map(|item| Arc::<str>::from(dna.type_name(item.type_idx)));
+	let struct_size = item.map(|item| usize::from(dna.tlen[item.type_idx as usize])).unwrap_or(0);
+
+	let mut out = Vec::new();
+	for (offset, window) in block.payload.windows(needle.len()).enumerate() {
+		if window != needle {
+			continue;
+		}
+
+		let (element_index, field) = if struct_size > 0 && offset / struct_size < block.head.nr as usize {
+			let element_offset = offset % struct_size;
+			let field = field_path_at(dna, block.head.sdna_nr, element_offset, "");
+			(Some(offset / struct_size), field.map(Arc::<str>::from))
+		} else {
+			(None, None)
+		};
+
+		out.push(StringHit {
+			code: block.head.code,
+			block_old: block.head.old,
+			sdna_nr: block.head.sdna_nr,
+			type_name: type_name.clone(),
+			file_offset: block.file_offset + offset,
+			payload_offset: offset,
+			element_index,
+			field,
+			text: surrounding_text(block.payload, offset, needle.len()),
+			whole_token: is_token_edge(offset.checked_sub(1).map(|before| block.payload[before]))
+				&& is_token_edge(block.payload.get(offset + needle.len()).copied()),
+		});
+	}
+	out
+}
+
+fn field_path_at(dna: &Dna, sdna_nr: u32, offset: usize, prefix: &str) -> Option<String> {
+	let item = dna.struct_by_sdna(sdna_nr)?;
+	let mut start = 0_usize;
+
+	for field in &item.fields {
+		let type_name = dna.type_name(field.type_idx);
+		let decl = parse_field_decl(dna.field_name(field.name_idx));
+		let is_ptr = decl.ptr_depth > 0 || decl.is_func_ptr;
+		let element_size = if is_ptr {
+			dna.pointer_size
+		} else if type_name == "void" {
+			1
+		} else {
+			usize::from(dna.tlen[field.type_idx as usize]).max(1)
+		};
+		let total = element_size.saturating_mul(decl.inline_array);
+
+		if offset < start + total {
+			let relative = offset - start;
+			let element = relative / element_size;
+			let name = if decl.inline_array == 1 || matches!(type_name, "char" | "uchar") {
+				format!("{prefix}{}", decl.ident)
+			} else {
+				format!("{prefix}{}[{element}]", decl.ident)
+			};
+
+			if !is_ptr && let Some(nested_sdna) = dna.struct_for_type.get(field.type_idx as usize).and_then(|value| *value) {
+				let nested_prefix = format!("{name}.");
+				return field_path_at(dna, nested_sdna, relative % element_size, &nested_prefix).or(Some(name));
+			}
+			return Some(name);
+		}
+
+		start += total;
+	}
+
+	None
+}
+
+/// Whether `byte` (`None` past the payload) ends a token around a match.
+fn is_token_edge(byte: Option<u8>) -> bool {
+	byte.is_none_or(|byte| matches!(byte, 0 | b'"' | b'\'' | b'[' | b']'))
+}
+
+fn surrounding_text(payload: &[u8], offset: usize, len: usize) -> Arc<str> {
+	let floor = offset.saturating_sub(MAX_CONTEXT_BYTES);
+	let start = payload[floor..offset].iter().rposition(|byte| *byte == 0).map_or(floor, |pos| floor + pos + 1);
+
+	let ceil = (offset + len + MAX_CONTEXT_BYTES).min(payload.len());
+	let end = payload[offset + len..ceil]
+		.iter()
+		.position(|byte| *byte == 0)
+		.map_or(ceil, |pos| offset + len + pos);
+
+	Arc::<str>::from(String::from_utf8_lossy(&payload[start..end]).as_ref())
+}
+
+#[cfg(test)]
+mod tests;
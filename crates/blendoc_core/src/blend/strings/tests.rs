@@ -0,0 +1,101 @@
+mod synthetic_strings {
+	use crate::blend::{BHead, Block, Dna, DnaField, DnaStruct, Endianness, find_string_in_block};
+
+	fn dna() -> Dna {
+		Dna::from_tables(
+			Endianness::Little,
+			8,
+			vec!["*next".into(), "label[8]".into(), "name[8]".into(), "inner".into()],
+			vec!["char".into(), "Inner".into(), "Outer".into()],
+			vec![1, 8, 24],
+			vec![
+				DnaStruct {
+					type_idx: 1,
+					fields: vec![DnaField { type_idx: 0, name_idx: 2 }],
+				},
+				DnaStruct {
+					type_idx: 2,
+					fields: vec![
+						DnaField { type_idx: 2, name_idx: 0 },
+						DnaField { type_idx: 0, name_idx: 1 },
+						DnaField { type_idx: 1, name_idx: 3 },
+					],
+				},
+			],
+		)
+		.expect("valid dna tables")
+	}
+
+	#[test]
+	fn hits_report_field_paths_and_context() {
+		let mut payload = [0_u8; 48];
+		payload[8..13].copy_from_slice(b"xCube");
+		payload[40..44].copy_from_slice(b"Cube");
+
+		let block = Block {
+			head: BHead {
+				code: *b"DATA",
+				sdna_nr: 1,
+				old: 0x1000,
+				len: 48,
+				nr: 2,
+			},
+			payload: &payload,
+			file_offset: 100,
+		};
+
+		let hits = find_string_in_block(&dna(), &block, b"Cube");
+		assert_eq!(hits.len(), 2);
+
+		assert_eq!(hits[0].payload_offset, 9);
+		assert_eq!(hits[0].file_offset, 109);
+		assert_eq!(hits[0].element_index, Some(0));
+		assert_eq!(hits[0].field.as_deref(), Some("label"));
+		assert_eq!(hits[0].text.as_ref(), "xCube");
+		assert_eq!(hits[0].type_name.as_deref(), Some("Outer"));
+		assert!(!hits[0].whole_token);
+
+		assert_eq!(hits[1].element_index, Some(1));
+		assert_eq!(hits[1].field.as_deref(), Some("inner.name"));
+		assert_eq!(hits[1].text.as_ref(), "Cube");
+		assert!(hits[1].whole_token);
+	}
+
+	#[test]
+	fn quoted_and_bracketed_names_are_whole_tokens() {
+		let payload = b"objects[\"Cube\"]\0Cube.001\0['Cube']";
+		let block = Block {
+			head: BHead {
+				code: *b"DATA",
+				sdna_nr: 0,
+				old: 0x1000,
+				len: payload.len() as u64,
+				nr: 1,
+			},
+			payload,
+			file_offset: 0,
+		};
+
+		let hits = find_string_in_block(&dna(), &block, b"Cube");
+		let tokens: Vec<bool> = hits.iter().map(|hit| hit.whole_token).collect();
+		assert_eq!(tokens, [true, false, true]);
+	}
+
+	#[test]
+	fn empty_needle_matches_nothing() {
+		let payload = [0_u8; 24];
+		let block = Block {
+			head: BHead {
+				code: *b"DATA",
+				sdna_nr: 1,
+				old: 0x1000,
+				len: 24,
+				nr: 1,
+			},
+			payload: &payload,
+			file_offset: 0,
+		};
+
+		assert!(find_string_in_block(&dna(), &block, b"").is_empty());
+	}
+}
@@ -0,0 +1,311 @@
+use std::fmt;
+
+use crate::blend::{BlendError, Dna};
+
+/// Maximum number of close-name suggestions kept per hint.
+const MAX_SUGGESTIONS: usize = 3;
+
+/// Known SDNA struct/field rename across Blender versions.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct KnownRename {
+	/// Struct type name the rename applies to.
+	pub struct_name: &'static str,
+	/// Old field name, or `None` when the struct itself was renamed.
+	pub old: Option<&'static str>,
+	/// Replacement struct/field name, or a short description when there is no 1:1 successor.
+	pub new: &'static str,
+	/// Blender version (decimal digits, e.g. `306`) that introduced the change.
+	pub version: u16,
+}
+
+/// Renames and removals that commonly break scripts written for another Blender version.
+pub const KNOWN_RENAMES: &[KnownRename] = &[
+	KnownRename {
+		struct_name: "Lamp",
+		old: None,
+		new: "Light",
+		version: 280,
+	},
+	KnownRename {
+		struct_name: "Group",
+		old: None,
+		new: "Collection",
+		version: 280,
+	},
+	KnownRename {
+		struct_name: "SceneRenderLayer",
+		old: None,
+		new: "ViewLayer",
+		version: 280,
+	},
+	KnownRename {
+		struct_name: "Object",
+		old: Some("dup_group"),
+		new: "instance_collection",
+		version: 280,
+	},
+	KnownRename {
+		struct_name: "Scene",
+		old: Some("base"),
+		new: "view_layers",
+		version: 280,
+	},
+	KnownRename {
+		struct_name: "Mesh",
+		old: Some("mloopuv"),
+		new: "UV map attributes in corner data",
+		version: 305,
+	},
+	KnownRename {
+		struct_name: "Mesh",
+		old: Some("mvert"),
+		new: "`position` attribute in vertex data",
+		version: 306,
+	},
+	KnownRename {
+		struct_name: "Mesh",
+		old: Some("medge"),
+		new: "`.edge_verts` attribute in edge data",
+		version: 306,
+	},
+	KnownRename {
+		struct_name: "Mesh",
+		old: Some("mloop"),
+		new: "`.corner_vert`/`.corner_edge` attributes in corner data",
+		version: 306,
+	},
+	KnownRename {
+		struct_name: "Mesh",
+		old: Some("mpoly"),
+		new: "poly_offset_indices",
+		version: 306,
+	},
+	KnownRename {
+		struct_name: "Mesh",
+		old: Some("poly_offset_indices"),
+		new: "face_offset_indices",
+		version: 400,
+	},
+	KnownRename {
+		struct_name: "Mesh",
+		old: Some("vdata"),
+		new: "vert_data",
+		version: 400,
+	},
+	KnownRename {
+		struct_name: "Mesh",
+		old: Some("edata"),
+		new: "edge_data",
+		version: 400,
+	},
+	KnownRename {
+		struct_name: "Mesh",
+		old: Some("pdata"),
+		new: "face_data",
+		version: 400,
+	},
+	KnownRename {
+		struct_name: "Mesh",
+		old: Some("ldata"),
+		new: "corner_data",
+		version: 400,
+	},
+	KnownRename {
+		struct_name: "Mesh",
+		old: Some("totvert"),
+		new: "verts_num",
+		version: 400,
+	},
+	KnownRename {
+		struct_name: "Mesh",
+		old: Some("totedge"),
+		new: "edges_num",
+		version: 400,
+	},
+	KnownRename {
+		struct_name: "Mesh",
+		old: Some("totpoly"),
+		new: "faces_num",
+		version: 400,
+	},
+	KnownRename {
+		struct_name: "Mesh",
+		old: Some("totloop"),
+		new: "corners_num",
+		version: 400,
+	},
+];
+
+/// Which side of a [`KnownRename`] the requested name matched.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RenameDirection {
+	/// Requested the old name; the file may be newer than the rename.
+	Removed,
+	/// Requested the new name; the file may be older than the rename.
+	Introduced,
+}
+
+/// "Did you mean" context for a struct or field name absent from a file's DNA.
+#[derive(Debug, Clone)]
+pub struct NameHint {
+	/// Requested name as `Struct` or `Struct.field`.
+	pub requested: Box<str>,
+	/// Closest names present in the schema, best first.
+	pub suggestions: Vec<Box<str>>,
+	/// Matching known-rename entry and which side matched.
+	pub rename: Option<(&'static KnownRename, RenameDirection)>,
+	/// Blender version of the inspected file.
+	pub file_version: u16,
+}
+
+impl NameHint {
+	/// Whether the hint carries any suggestion or rename note.
+	pub fn is_empty(&self) -> bool {
+		self.suggestions.is_empty() && self.rename.is_none()
+	}
+}
+
+impl fmt::Display for NameHint {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		let mut parts = Vec::new();
+		if !self.suggestions.is_empty() {
+			let names: Vec<String> = self.suggestions.iter().map(|name| format!("`{name}`")).collect();
+			parts.push(format!("did you mean {}?", names.join(", ")));
+		}
+		if let Some((rename, direction)) = self.rename {
+			let old = match rename.old {
+				Some(field) => format!("{}.{field}", rename.struct_name),
+				None => rename.struct_name.to_owned(),
+			};
+			parts.push(match direction {
+				RenameDirection::Removed => format!(
+					"`{old}` was replaced by {} in Blender {}",
+					quote_name(rename.new),
+					version_label(rename.version)
+				),
+				RenameDirection::Introduced => format!("`{}` replaced `{old}` in Blender {}", self.requested, version_label(rename.version)),
+			});
+		}
+		parts.push(format!("file is Blender {}", version_label(self.file_version)));
+		f.write_str(&parts.join("; "))
+	}
+}
+
+/// Build a hint for a struct type name that is not in `dna`.
+pub fn struct_name_hint(dna: &Dna, file_version: u16, name: &str) -> NameHint {
+	let candidates = dna.structs.iter().map(|item| dna.type_name(item.type_idx));
+	let rename = KNOWN_RENAMES
+		.iter()
+		.filter(|rename| rename.old.is_none())
+		.find_map(|rename| rename_direction(rename, rename.struct_name, rename.new, name));
+
+	NameHint {
+		requested: name.into(),
+		suggestions: closest(name, candidates),
+		rename,
+		file_version,
+	}
+}
+
+/// Build a hint for a field that is not declared on `struct_name` in `dna`.
+///
+/// Suggestions come from the struct's field identifiers (pointer and array
+/// decoration stripped); an unknown struct yields rename notes only.
+pub fn field_name_hint(dna: &Dna, file_version: u16, struct_name: &str, field: &str) -> NameHint {
+	let fields = dna.struct_index(struct_name).and_then(|sdna_nr| dna.struct_layout(sdna_nr)).unwrap_or_default();
+	let candidates = fields.iter().map(|item| item.name.as_ref());
+	let rename = KNOWN_RENAMES
+		.iter()
+		.filter(|rename| rename.struct_name == struct_name)
+		.find_map(|rename| rename_direction(rename, rename.old?, rename.new, field));
+
+	NameHint {
+		requested: format!("{struct_name}.{field}").into(),
+		suggestions: closest(field, candidates)
+			.into_iter()
+			.map(|name| format!("{struct_name}.{name}").into())
+			.collect(),
+		rename,
+		file_version,
+	}
+}
+
+/// Attach a [`NameHint`] to missing struct/field errors; other errors pass through.
+pub fn enrich_error(err: BlendError, dna: &Dna, file_version: u16) -> BlendError {
+	let hint = match &err {
+		BlendError::DnaStructNotFound { name } => struct_name_hint(dna, file_version, name),
+		BlendError::ChaseMissingField { struct_name, field } => field_name_hint(dna, file_version, struct_name, field),
+		_ => return err,
+	};
+	if hint.is_empty() {
+		return err;
+	}
+	BlendError::Hinted {
+		source: Box::new(err),
+		hint: hint.to_string(),
+	}
+}
+
+/// Format a decimal Blender version (`279`, `306`, `402`) as `2.79`, `3.6`, `4.2`.
+pub fn version_label(version: u16) -> String {
+	let (major, minor) = (version / 100, version % 100);
+	if major < 3 {
+		format!("{major}.{minor:02}")
+	} else {
+		format!("{major}.{minor}")
+	}
+}
+
+/// Backtick bare identifiers; descriptive replacements are already formatted.
+fn quote_name(name: &str) -> String {
+	if name.contains(' ') { name.to_owned() } else { format!("`{name}`") }
+}
+
+fn rename_direction(rename: &'static KnownRename, old: &str, new: &str, requested: &str) -> Option<(&'static KnownRename, RenameDirection)> {
+	if requested == old {
+		Some((rename, RenameDirection::Removed))
+	} else if requested == new {
+		Some((rename, RenameDirection::Introduced))
+	} else {
+		None
+	}
+}
+
+/// Rank candidates by case-insensitive edit distance, keeping substring matches.
+fn closest<'a>(requested: &str, candidates: impl Iterator<Item = &'a str>) -> Vec<Box<str>> {
+	let needle = requested.to_ascii_lowercase();
+	let threshold = (needle.chars().count() / 3).max(2);
+
+	let mut scored: Vec<(usize, &str)> = Vec::new();
+	for candidate in candidates {
+		let lower = candidate.to_ascii_lowercase();
+		let distance = edit_distance(&needle, &lower);
+		let contains = needle.len() >= 3 && (lower.contains(&needle) || needle.contains(&lower));
+		if distance <= threshold || contains {
+			scored.push((distance, candidate));
+		}
+	}
+
+	scored.sort();
+	scored.dedup_by(|a, b| a.1 == b.1);
+	scored.into_iter().take(MAX_SUGGESTIONS).map(|(_, name)| name.into()).collect()
+}
+
+/// Levenshtein distance over `char`s.
+pub(crate) fn edit_distance(a: &str, b: &str) -> usize {
+	let b: Vec<char> = b.chars().collect();
+	let mut row: Vec<usize> = (0..=b.len()).collect();
+	for (i, ca) in a.chars().enumerate() {
+		let mut diagonal = row[0];
+		row[0] = i + 1;
+		for (j, cb) in b.iter().enumerate() {
+			let above = row[j + 1];
+			row[j + 1] = if ca == *cb { diagonal } else { 1 + diagonal.min(above).min(row[j]) };
+			diagonal = above;
+		}
+	}
+	row[b.len()]
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,83 @@
+mod synthetic_hints {
+	use crate::blend::{BlendError, Dna, DnaField, DnaStruct, Endianness, RenameDirection, enrich_error, field_name_hint, struct_name_hint, version_label};
+
+	fn dna() -> Dna {
+		Dna::from_tables(
+			Endianness::Little,
+			8,
+			vec!["*vert_data".into(), "verts_num".into(), "faces_num".into(), "loc[3]".into()],
+			vec!["int".into(), "float".into(), "void".into(), "Mesh".into(), "Object".into()],
+			vec![4, 4, 0, 16, 12],
+			vec![
+				DnaStruct {
+					type_idx: 3,
+					fields: vec![
+						DnaField { type_idx: 2, name_idx: 0 },
+						DnaField { type_idx: 0, name_idx: 1 },
+						DnaField { type_idx: 0, name_idx: 2 },
+					],
+				},
+				DnaStruct {
+					type_idx: 4,
+					fields: vec![DnaField { type_idx: 1, name_idx: 3 }],
+				},
+			],
+		)
+		.expect("valid dna tables")
+	}
+
+	#[test]
+	fn suggests_close_struct_and_field_names() {
+		let dna = dna();
+
+		let hint = struct_name_hint(&dna, 402, "Objetc");
+		assert_eq!(hint.suggestions, vec!["Object".into()]);
+		assert!(hint.rename.is_none());
+
+		let hint = field_name_hint(&dna, 402, "Mesh", "vert_num");
+		assert_eq!(hint.suggestions.first().map(AsRef::as_ref), Some("Mesh.verts_num"));
+
+		let hint = field_name_hint(&dna, 402, "Object", "unrelated_name");
+		assert!(hint.is_empty());
+	}
+
+	#[test]
+	fn known_renames_report_direction_and_versions() {
+		let dna = dna();
+
+		let hint = field_name_hint(&dna, 402, "Mesh", "totvert");
+		let (rename, direction) = hint.rename.expect("known rename");
+		assert_eq!(rename.new, "verts_num");
+		assert_eq!(direction, RenameDirection::Removed);
+		let text = hint.to_string();
+		assert!(text.contains("`Mesh.totvert` was replaced by `verts_num` in Blender 4.0"), "{text}");
+		assert!(text.ends_with("file is Blender 4.2"), "{text}");
+
+		let hint = struct_name_hint(&dna, 279, "Light");
+		assert_eq!(hint.rename.map(|item| item.1), Some(RenameDirection::Introduced));
+		assert!(hint.to_string().contains("file is Blender 2.79"));
+	}
+
+	#[test]
+	fn enrich_error_wraps_only_hinted_lookups() {
+		let dna = dna();
+
+		let err = enrich_error(BlendError::DnaStructNotFound { name: "Mesj".to_owned() }, &dna, 402);
+		assert!(matches!(&err, BlendError::Hinted { source, .. } if matches!(**source, BlendError::DnaStructNotFound { .. })));
+		assert!(err.to_string().contains("did you mean `Mesh`?"));
+
+		let err = enrich_error(BlendError::DnaNotFound, &dna, 402);
+		assert!(matches!(err, BlendError::DnaNotFound));
+
+		let err = enrich_error(BlendError::DnaStructNotFound { name: "Zzzzzzzz".to_owned() }, &dna, 402);
+		assert!(matches!(err, BlendError::DnaStructNotFound { .. }));
+	}
+
+	#[test]
+	fn version_labels_follow_blender_numbering() {
+		assert_eq!(version_label(279), "2.79");
+		assert_eq!(version_label(280), "2.80");
+		assert_eq!(version_label(306), "3.6");
+		assert_eq!(version_label(402), "4.2");
+	}
+}
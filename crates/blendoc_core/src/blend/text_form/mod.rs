@@ -0,0 +1,288 @@
+use std::collections::HashMap;
+use std::fmt::Write as _;
+
+use crate::blend::changes::{Fnv, hash_block, hash_value};
+use crate::blend::{BlendFile, DecodeOptions, Dna, IdIndex, IdRecord, PointerIndex, Result, Value, codes, decode_ptr_instance, scan_id_blocks};
+
+/// First line of every text form; bumped when the format changes.
+pub const TEXT_FORM_HEADER: &str = "blendoc-text 1";
+
+/// `ID` fields that change on every save or only matter at runtime.
+const VOLATILE_ID_FIELDS: [&str; 9] = [
+	"next",
+	"prev",
+	"newid",
+	"orig_id",
+	"py_instance",
+	"session_uid",
+	"session_uuid",
+	"recalc",
+	"tag",
+];
+
+/// Knobs for [`text_form`].
+#[derive(Debug, Clone)]
+pub struct TextFormOptions {
+	/// Arrays longer than this are written as a length and hash instead of element by element.
+	pub max_inline_array: usize,
+	/// Decode behavior for ID roots.
+	pub decode: DecodeOptions,
+}
+
+impl Default for TextFormOptions {
+	fn default() -> Self {
+		Self {
+			max_inline_array: 16,
+			decode: DecodeOptions::default(),
+		}
+	}
+}
+
+/// Render the logical content of `file` as canonical, line-oriented text.
+///
+/// One `[Type "IDname"]` section per ID, sorted by type and name, lists the
+/// ID root's fields as `path = value` lines in declaration order. Pointers
+/// to IDs are written as `-> Type "IDname"`, other pointers only as `<data>`
+/// or `<dangling>`, so addresses that change on every save never show up.
+/// Arrays past [`TextFormOptions::max_inline_array`], byte payloads, and the
+/// `DATA` blocks an ID owns are summarized by length and an FNV-1a hash that
+/// ignores pointer values. Output is identical for identical content, which
+/// makes it suitable for storing next to the binary in version control.
+pub fn text_form(file: &BlendFile, dna: &Dna, options: &TextFormOptions) -> Result<String> {
+	let ids = IdIndex::build(scan_id_blocks(file, dna)?);
+	let index = PointerIndex::build(file)?;
+	let owned = owned_data(file, dna, &ids, &options.decode)?;
+
+	let mut records: Vec<&IdRecord> = ids.records.iter().collect();
+	records.sort_by(|a, b| (&a.type_name, &a.id_name).cmp(&(&b.type_name, &b.id_name)));
+
+	let mut out = String::new();
+	let _ = writeln!(out, "{TEXT_FORM_HEADER}");
+	let _ = writeln!(out, "version = {}", file.header.version);
+	let _ = writeln!(out, "pointer_size = {}", file.header.pointer_size);
+	let _ = writeln!(out, "endianness = {}", file.header.endianness.as_str());
+	let _ = writeln!(out, "ids = {}", records.len());
+
+	let render = Renderer {
+		ids: &ids,
+		index: &index,
+		max_inline_array: options.max_inline_array,
+	};
+	let mut ordinal: HashMap<(&str, &str), usize> = HashMap::new();
+	for record in records {
+		let seen = ordinal.entry((&record.type_name, &record.id_name)).or_default();
+		let _ = write!(out, "\n[{} {:?}", record.type_name, record.id_name.as_ref());
+		if let Some(lib) = record.lib.filter(|lib| *lib != 0) {
+			let _ = write!(out, " from {}", render.ptr(lib));
+		}
+		if *seen > 0 {
+			let _ = write!(out, " #{seen}");
+		}
+		*seen += 1;
+		out.push_str("]\n");
+		let _ = writeln!(out, "code = {}", codes::code_to_str(record.code));
+
+		match decode_ptr_instance(dna, &index, record.old_ptr, &options.decode) {
+			Ok((_, value)) => {
+				for field in &value.fields {
+					if field.name.as_ref() == "id"
+						&& let Value::Struct(id) = &field.value
+					{
+						for id_field in id.fields.iter().filter(|item| !VOLATILE_ID_FIELDS.contains(&item.name.as_ref())) {
+							render.value(&mut out, &format!("id.{}", id_field.name), &id_field.value);
+						}
+						continue;
+					}
+					render.value(&mut out, &field.name, &field.value);
+				}
+			}
+			Err(err) => {
+				let _ = writeln!(out, "error = {:?}", err.to_string());
+			}
+		}
+		if let Some(data) = owned.get(&record.old_ptr).filter(|data| data.blocks > 0) {
+			let _ = writeln!(out, "owned_data = {} blocks, {} bytes, fnv {:016x}", data.blocks, data.bytes, data.hash);
+		}
+	}
+	Ok(out)
+}
+
+/// One line that differs between two text forms, from [`diff_text_forms`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TextFormChange {
+	/// Section header (`[Object "OBCube"]`), empty for the file header lines.
+	pub section: Box<str>,
+	/// Line key: the text before ` = `, or the whole line when it has none.
+	pub key: Box<str>,
+	/// Line in the earlier form, `None` when added.
+	pub before: Option<Box<str>>,
+	/// Line in the later form, `None` when removed.
+	pub after: Option<Box<str>>,
+}
+
+/// Compare two text forms section by section and key by key.
+///
+/// Changes follow the later form's order, with lines only in the earlier form
+/// listed after the section's other changes and removed sections last.
+pub fn diff_text_forms(before: &str, after: &str) -> Vec<TextFormChange> {
+	let before = parse_sections(before);
+	let after = parse_sections(after);
+	let before_by_header: HashMap<&str, &Vec<(&str, &str)>> = before.iter().map(|(header, lines)| (*header, lines)).collect();
+	let mut out = Vec::new();
+
+	for (header, lines) in &after {
+		let old_lines: Vec<(&str, &str)> = before_by_header.get(header).map(|lines| lines.to_vec()).unwrap_or_default();
+		let old_by_key: HashMap<&str, &str> = old_lines.iter().copied().collect();
+		let new_keys: HashMap<&str, ()> = lines.iter().map(|(key, _)| (*key, ())).collect();
+		for (key, line) in lines {
+			let previous = old_by_key.get(key).copied();
+			if previous != Some(*line) {
+				out.push(change(header, key, previous, Some(line)));
+			}
+		}
+		for (key, line) in old_lines.iter().filter(|(key, _)| !new_keys.contains_key(key)) {
+			out.push(change(header, key, Some(line), None));
+		}
+	}
+
+	let after_headers: HashMap<&str, ()> = after.iter().map(|(header, _)| (*header, ())).collect();
+	for (header, lines) in before.iter().filter(|(header, _)| !after_headers.contains_key(header)) {
+		for (key, line) in lines {
+			out.push(change(header, key, Some(line), None));
+		}
+	}
+	out
+}
+
+fn change(section: &str, key: &str, before: Option<&str>, after: Option<&str>) -> TextFormChange {
+	TextFormChange {
+		section: section.into(),
+		key: key.into(),
+		before: before.map(Into::into),
+		after: after.map(Into::into),
+	}
+}
+
+/// Split a text form into `(header, [(key, line)])` sections; blank lines are dropped.
+fn parse_sections(text: &str) -> Vec<(&str, Vec<(&str, &str)>)> {
+	let mut sections = vec![("", Vec::new())];
+	for line in text.lines().filter(|line| !line.trim().is_empty()) {
+		if line.starts_with('[') && line.ends_with(']') {
+			sections.push((line, Vec::new()));
+			continue;
+		}
+		let key = line.split_once(" = ").map_or(line, |(key, _)| key);
+		if let Some((_, lines)) = sections.last_mut() {
+			lines.push((key, line));
+		}
+	}
+	sections
+}
+
+#[derive(Debug, Clone, Copy, Default)]
+struct OwnedData {
+	blocks: usize,
+	bytes: u64,
+	hash: u64,
+}
+
+/// Count and hash the `DATA` blocks written after each ID root.
+fn owned_data(file: &BlendFile, dna: &Dna, ids: &IdIndex, decode: &DecodeOptions) -> Result<HashMap<u64, OwnedData>> {
+	let mut out: HashMap<u64, OwnedData> = HashMap::new();
+	let mut owner = None;
+	for block in file.blocks() {
+		let block = block?;
+		if block.head.code != codes::DATA {
+			owner = ids
+				.get_by_ptr(block.head.old)
+				.filter(|record| record.sdna_nr == block.head.sdna_nr && record.code == block.head.code)
+				.map(|record| record.old_ptr);
+			continue;
+		}
+		let Some(owner) = owner else {
+			continue;
+		};
+		let entry = out.entry(owner).or_insert_with(|| OwnedData {
+			hash: Fnv::new().finish(),
+			..OwnedData::default()
+		});
+		let mut hasher = Fnv::seeded(entry.hash);
+		hash_block(dna, &block, decode, &mut hasher);
+		entry.hash = hasher.finish();
+		entry.blocks += 1;
+		entry.bytes = entry.bytes.saturating_add(block.head.len);
+	}
+	Ok(out)
+}
+
+struct Renderer<'a, 'b> {
+	ids: &'a IdIndex,
+	index: &'a PointerIndex<'b>,
+	max_inline_array: usize,
+}
+
+impl Renderer<'_, '_> {
+	fn value(&self, out: &mut String, path: &str, value: &Value) {
+		match value {
+			Value::Struct(item) => {
+				for field in &item.fields {
+					self.value(out, &format!("{path}.{}", field.name), &field.value);
+				}
+			}
+			Value::Array(items) if items.len() > self.max_inline_array => {
+				let _ = writeln!(out, "{path} = <array len={} fnv={:016x}>", items.len(), hash(value));
+			}
+			Value::Array(items) if items.iter().any(|item| matches!(item, Value::Struct(_) | Value::Array(_))) => {
+				for (idx, item) in items.iter().enumerate() {
+					self.value(out, &format!("{path}[{idx}]"), item);
+				}
+			}
+			Value::Array(items) => {
+				let rendered: Vec<String> = items.iter().map(|item| self.scalar(item)).collect();
+				let _ = writeln!(out, "{path} = [{}]", rendered.join(", "));
+			}
+			leaf => {
+				let _ = writeln!(out, "{path} = {}", self.scalar(leaf));
+			}
+		}
+	}
+
+	fn scalar(&self, value: &Value) -> String {
+		match value {
+			Value::Null => "null".to_owned(),
+			Value::Bool(flag) => flag.to_string(),
+			Value::I64(number) => number.to_string(),
+			Value::U64(number) => number.to_string(),
+			Value::F32(number) => format!("{number:?}"),
+			Value::F64(number) => format!("{number:?}"),
+			Value::String(text) => format!("{:?}", text.as_ref()),
+			Value::Bytes(bytes) => format!("<bytes len={} fnv={:016x}>", bytes.len(), hash(value)),
+			Value::Ptr(ptr) => self.ptr(*ptr),
+			Value::Array(items) => format!("<array len={} fnv={:016x}>", items.len(), hash(value)),
+			Value::Struct(item) => format!("<{}>", item.type_name),
+		}
+	}
+
+	fn ptr(&self, ptr: u64) -> String {
+		if ptr == 0 {
+			return "null".to_owned();
+		}
+		if let Some(record) = self.ids.get_by_ptr(ptr) {
+			return format!("-> {} {:?}", record.type_name, record.id_name.as_ref());
+		}
+		if self.index.resolve(ptr).is_some() {
+			"<data>".to_owned()
+		} else {
+			"<dangling>".to_owned()
+		}
+	}
+}
+
+fn hash(value: &Value) -> u64 {
+	let mut hasher = Fnv::new();
+	hash_value(value, &mut hasher);
+	hasher.finish()
+}
+
+#[cfg(test)]
+mod tests;
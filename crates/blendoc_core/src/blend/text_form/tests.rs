@@ -0,0 +1,67 @@
+use blendoc_testkit::builder::{HeaderKind, scene_file};
+
+use crate::blend::{BlendFile, TEXT_FORM_HEADER, TextFormOptions, diff_text_forms, text_form};
+
+fn render(kind: HeaderKind, big_endian: bool, options: &TextFormOptions) -> String {
+	let blend = BlendFile::from_bytes(scene_file(kind, big_endian)).expect("synthetic file parses");
+	let dna = blend.dna().expect("dna parses");
+	text_form(&blend, &dna, options).expect("text form renders")
+}
+
+#[test]
+fn renders_sorted_sections_with_id_targets_and_owned_data() {
+	let text = render(HeaderKind::Legacy8, false, &TextFormOptions::default());
+	assert!(text.starts_with(TEXT_FORM_HEADER));
+
+	let headers: Vec<&str> = text.lines().filter(|line| line.starts_with('[')).collect();
+	assert_eq!(
+		headers,
+		["[Mesh \"MECamera\"]", "[Object \"OBCamera\"]", "[Scene \"SCScene\"]", "[World \"WOWorld\"]"]
+	);
+	assert!(text.contains("\ndata = -> Mesh \"MECamera\"\n"), "{text}");
+	assert!(text.contains("\nloc = [1.0, 2.0, 3.0]\n"), "{text}");
+	assert!(text.contains("\nowned_data = 1 blocks, "), "{text}");
+	assert!(!text.contains("0x"), "addresses leak into the text form:\n{text}");
+	assert!(!text.contains("id.next ="), "{text}");
+}
+
+#[test]
+fn pointer_addresses_do_not_change_the_form() {
+	let legacy = render(HeaderKind::Legacy8, false, &TextFormOptions::default());
+	let stable_ids = render(HeaderKind::Large, false, &TextFormOptions::default());
+	let changes = diff_text_forms(&legacy, &stable_ids);
+	assert!(changes.iter().all(|change| change.section.is_empty()), "{changes:?}");
+	assert_eq!(changes.iter().map(|change| change.key.as_ref()).collect::<Vec<_>>(), ["version"]);
+
+	assert!(diff_text_forms(&legacy, &legacy).is_empty());
+}
+
+#[test]
+fn long_arrays_are_hashed() {
+	let options = TextFormOptions {
+		max_inline_array: 2,
+		..TextFormOptions::default()
+	};
+	let text = render(HeaderKind::Legacy8, false, &options);
+	assert!(text.contains("\nloc = <array len=3 fnv="), "{text}");
+}
+
+#[test]
+fn diff_reports_changed_added_and_removed_lines() {
+	let before = "blendoc-text 1\n\n[Object \"OBA\"]\ncode = OB\nloc = [1.0]\nold = 1\n\n[Object \"OBGone\"]\ncode = OB\n";
+	let after = "blendoc-text 1\n\n[Object \"OBA\"]\ncode = OB\nloc = [2.0]\nnew = 1\n";
+	let changes = diff_text_forms(before, after);
+	let summary: Vec<(&str, &str, Option<&str>, Option<&str>)> = changes
+		.iter()
+		.map(|change| (change.section.as_ref(), change.key.as_ref(), change.before.as_deref(), change.after.as_deref()))
+		.collect();
+	assert_eq!(
+		summary,
+		[
+			("[Object \"OBA\"]", "loc", Some("loc = [1.0]"), Some("loc = [2.0]")),
+			("[Object \"OBA\"]", "new", None, Some("new = 1")),
+			("[Object \"OBA\"]", "old", Some("old = 1"), None),
+			("[Object \"OBGone\"]", "code", Some("code = OB"), None),
+		]
+	);
+}
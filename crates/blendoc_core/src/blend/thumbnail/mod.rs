@@ -0,0 +1,55 @@
+use crate::blend::bytes::Cursor;
+use crate::blend::preview::flip_rows;
+use crate::blend::{BlendError, BlendFile, Endianness, Result, codes};
+
+/// File thumbnail decoded from the `TEST` block.
+#[derive(Debug, Clone)]
+pub struct Thumbnail {
+	/// Width in pixels.
+	pub width: u32,
+	/// Height in pixels.
+	pub height: u32,
+	/// RGBA8 pixels in top-down row order.
+	pub rgba: Vec<u8>,
+}
+
+/// Decode the embedded thumbnail, or `None` when the file was saved without one.
+pub fn extract_thumbnail(file: &BlendFile) -> Result<Option<Thumbnail>> {
+	let Some(block) = file.find_first_block_by_code(codes::TEST)? else {
+		return Ok(None);
+	};
+	decode_thumbnail(block.payload, file.header.endianness).map(Some)
+}
+
+/// Decode a `TEST` payload: `int width, int height` followed by bottom-up RGBA8 rows.
+pub fn decode_thumbnail(payload: &[u8], endianness: Endianness) -> Result<Thumbnail> {
+	let mut cursor = Cursor::new(payload);
+	let width = cursor.read_i32(endianness)?;
+	let height = cursor.read_i32(endianness)?;
+
+	let invalid = || BlendError::InvalidThumbnail {
+		width,
+		height,
+		len: payload.len(),
+	};
+	let (Ok(w), Ok(h)) = (u32::try_from(width), u32::try_from(height)) else {
+		return Err(invalid());
+	};
+	if w == 0 || h == 0 {
+		return Err(invalid());
+	}
+	let len = (w as usize)
+		.checked_mul(h as usize)
+		.and_then(|pixels| pixels.checked_mul(4))
+		.ok_or_else(invalid)?;
+	let pixels = cursor.read_exact(len).map_err(|_| invalid())?;
+
+	Ok(Thumbnail {
+		width: w,
+		height: h,
+		rgba: flip_rows(pixels, w as usize * 4),
+	})
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,43 @@
+mod synthetic_test_block {
+	use crate::blend::{BlendError, Endianness, decode_thumbnail};
+
+	fn payload(width: i32, height: i32, pixels: &[u8], endianness: Endianness) -> Vec<u8> {
+		let mut out = Vec::new();
+		for value in [width, height] {
+			match endianness {
+				Endianness::Little => out.extend_from_slice(&value.to_le_bytes()),
+				Endianness::Big => out.extend_from_slice(&value.to_be_bytes()),
+			}
+		}
+		out.extend_from_slice(pixels);
+		out
+	}
+
+	#[test]
+	fn decodes_dimensions_and_flips_rows() {
+		// 1x2 image stored bottom-up: row0 = red, row1 = blue.
+		let pixels = [255, 0, 0, 255, 0, 0, 255, 255];
+		for endianness in [Endianness::Little, Endianness::Big] {
+			let thumb = decode_thumbnail(&payload(1, 2, &pixels, endianness), endianness).expect("thumbnail decodes");
+			assert_eq!((thumb.width, thumb.height), (1, 2));
+			assert_eq!(thumb.rgba, vec![0, 0, 255, 255, 255, 0, 0, 255]);
+		}
+	}
+
+	#[test]
+	fn rejects_bad_dimensions_and_short_pixel_data() {
+		let short = payload(2, 2, &[0; 8], Endianness::Little);
+		assert!(matches!(
+			decode_thumbnail(&short, Endianness::Little),
+			Err(BlendError::InvalidThumbnail { width: 2, height: 2, .. })
+		));
+
+		let negative = payload(-1, 4, &[], Endianness::Little);
+		assert!(matches!(
+			decode_thumbnail(&negative, Endianness::Little),
+			Err(BlendError::InvalidThumbnail { .. })
+		));
+
+		assert!(matches!(decode_thumbnail(&[1, 0], Endianness::Little), Err(BlendError::UnexpectedEof { .. })));
+	}
+}
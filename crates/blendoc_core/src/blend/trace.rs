@@ -0,0 +1,16 @@
+//! `tracing` spans around hot paths, compiled out without the `tracing` feature.
+//!
+//! Span names are stable so subscribers can aggregate them: `decode_bytes`,
+//! `dna.parse`, `pointer_index.build`, `ids.scan`, `refs.scan`, and `blocks`.
+//! `blocks` is entered only while a [`BlockIter`](crate::blend::BlockIter)
+//! is producing the next block, so its busy time is pure iteration cost.
+
+/// Enter a span named `$name` until the end of the enclosing block.
+macro_rules! trace_span {
+	($name:literal $(, $($fields:tt)*)?) => {
+		#[cfg(feature = "tracing")]
+		let _span = tracing::info_span!($name $(, $($fields)*)?).entered();
+	};
+}
+
+pub(crate) use trace_span;
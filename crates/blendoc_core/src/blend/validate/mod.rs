@@ -0,0 +1,433 @@
+use std::collections::{BTreeMap, HashMap};
+use std::sync::Arc;
+
+use crate::blend::bytes::Cursor;
+use crate::blend::dangling::{PointerSlot, pointer_slots};
+use crate::blend::{BlendFile, Block, Dna, PointerIndex, PointerStorage, Result, codes, id_delta_from_blocks, map_blocks_parallel};
+
+/// How serious a [`ValidationIssue`] is.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Severity {
+	/// Unusual but readable; Blender itself tolerates it.
+	Info,
+	/// Likely data loss or a stale reference; reading continues.
+	Warning,
+	/// Structural damage; Blender may refuse or mis-read the file.
+	Error,
+}
+
+impl Severity {
+	/// Stable lowercase label.
+	pub fn as_str(self) -> &'static str {
+		match self {
+			Self::Info => "info",
+			Self::Warning => "warning",
+			Self::Error => "error",
+		}
+	}
+}
+
+/// Which check produced a [`ValidationIssue`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ValidationCheck {
+	/// A block header could not be parsed, so the block stream ends early.
+	BlockStream,
+	/// No `DNA1` block, or its payload did not parse.
+	Dna,
+	/// No terminating `ENDB` block.
+	MissingEndb,
+	/// Block `sdna_nr` is not a struct index in the file's SDNA.
+	SdnaOutOfRange,
+	/// Block payload length differs from `nr * struct size`.
+	PayloadSize,
+	/// Two block address ranges overlap.
+	OverlappingPtrRange,
+	/// Non-null pointer field that resolves to no block.
+	DanglingPtr,
+	/// Two IDs of the same library share one `ID.name`.
+	DuplicateIdName,
+}
+
+impl ValidationCheck {
+	/// Stable snake-case label.
+	pub fn as_str(self) -> &'static str {
+		match self {
+			Self::BlockStream => "block_stream",
+			Self::Dna => "dna",
+			Self::MissingEndb => "missing_endb",
+			Self::SdnaOutOfRange => "sdna_out_of_range",
+			Self::PayloadSize => "payload_size",
+			Self::OverlappingPtrRange => "overlapping_ptr_range",
+			Self::DanglingPtr => "dangling_ptr",
+			Self::DuplicateIdName => "duplicate_id_name",
+		}
+	}
+}
+
+/// One finding from [`validate_file`].
+#[derive(Debug, Clone)]
+pub struct ValidationIssue {
+	/// How serious the finding is.
+	pub severity: Severity,
+	/// Check that produced it.
+	pub check: ValidationCheck,
+	/// Offset of the offending block header in the decoded file bytes.
+	pub block_offset: Option<usize>,
+	/// Pointer the finding refers to (block address or first dangling value).
+	pub ptr: Option<u64>,
+	/// Human-readable detail.
+	pub message: Arc<str>,
+}
+
+/// Toggles and limits for [`validate_file`].
+#[derive(Debug, Clone)]
+pub struct ValidateOptions {
+	/// Read every pointer field of every typed block and report unresolved ones.
+	pub check_pointers: bool,
+	/// Maximum issues kept per check; further findings only bump the suppressed count.
+	pub max_issues_per_check: usize,
+	/// Worker threads for the pointer check; `0` uses available parallelism.
+	pub threads: usize,
+}
+
+impl Default for ValidateOptions {
+	fn default() -> Self {
+		Self {
+			check_pointers: true,
+			max_issues_per_check: 100,
+			threads: 1,
+		}
+	}
+}
+
+/// Findings of one [`validate_file`] run.
+#[derive(Debug, Clone, Default)]
+pub struct ValidationReport {
+	/// Kept findings, in check order.
+	pub issues: Vec<ValidationIssue>,
+	/// Findings dropped by [`ValidateOptions::max_issues_per_check`].
+	pub suppressed: usize,
+	/// Blocks parsed from the file.
+	pub blocks_checked: usize,
+	/// Non-null pointer values read by the dangling-pointer check.
+	pub pointers_checked: usize,
+}
+
+impl ValidationReport {
+	/// Number of kept findings at `severity`.
+	pub fn count(&self, severity: Severity) -> usize {
+		self.issues.iter().filter(|issue| issue.severity == severity).count()
+	}
+
+	/// Whether no [`Severity::Error`] finding was kept.
+	pub fn is_valid(&self) -> bool {
+		self.count(Severity::Error) == 0
+	}
+
+	/// Most serious kept severity, if any finding was kept.
+	pub fn max_severity(&self) -> Option<Severity> {
+		self.issues.iter().map(|issue| issue.severity).max()
+	}
+}
+
+/// Run structural integrity checks over `file`.
+///
+/// Checks run in order: block stream, `DNA1`/`ENDB` presence, per-block SDNA
+/// index and payload size, overlapping address ranges, duplicate ID names,
+/// and dangling pointers. Later checks need the schema, so a missing or
+/// broken `DNA1` ends the run after the block checks. Problems in the file
+/// are reported as issues; `Err` is reserved for failures outside it.
+pub fn validate_file(file: &BlendFile, options: &ValidateOptions) -> Result<ValidationReport> {
+	let mut out = Collector::new(options.max_issues_per_check);
+
+	let mut blocks = Vec::new();
+	for block in file.blocks() {
+		match block {
+			Ok(block) => blocks.push(block),
+			Err(err) => {
+				out.push(Severity::Error, ValidationCheck::BlockStream, None, None, format!("block stream stops: {err}"));
+				break;
+			}
+		}
+	}
+	out.report.blocks_checked = blocks.len();
+
+	if !blocks.last().is_some_and(|block| block.head.is_endb()) {
+		out.push(
+			Severity::Error,
+			ValidationCheck::MissingEndb,
+			None,
+			None,
+			"file does not end with an ENDB block",
+		);
+	}
+
+	let Some(dna_block) = blocks.iter().find(|block| block.head.code == codes::DNA1) else {
+		out.push(Severity::Error, ValidationCheck::Dna, None, None, "no DNA1 block; schema checks skipped");
+		return Ok(out.finish());
+	};
+	let dna = match Dna::parse(dna_block.payload, file.header.endianness, file.header.pointer_size) {
+		Ok(dna) => dna,
+		Err(err) => {
+			out.push(
+				Severity::Error,
+				ValidationCheck::Dna,
+				Some(dna_block.file_offset),
+				None,
+				format!("DNA1 does not parse: {err}; schema checks skipped"),
+			);
+			return Ok(out.finish());
+		}
+	};
+
+	let typed = check_block_shapes(&dna, &blocks, &mut out);
+
+	let index = PointerIndex::build(file)?;
+	if index.storage() == PointerStorage::AddressRanges {
+		for warning in index.overlap_warnings() {
+			let block_offset = warning.ptr.and_then(|ptr| index.resolve(ptr)).map(|resolved| resolved.entry.block.file_offset);
+			out.push(
+				Severity::Warning,
+				ValidationCheck::OverlappingPtrRange,
+				block_offset,
+				warning.ptr,
+				warning.message.to_string(),
+			);
+		}
+	}
+
+	check_id_names(&dna, &typed, &mut out);
+
+	if options.check_pointers {
+		check_pointers(&dna, &index, &typed, options.threads, &mut out);
+	}
+
+	Ok(out.finish())
+}
+
+/// Report bad SDNA indices and payload sizes; return blocks safe to read as structs.
+fn check_block_shapes<'a>(dna: &Dna, blocks: &[Block<'a>], out: &mut Collector) -> Vec<(Block<'a>, usize)> {
+	let mut typed = Vec::new();
+	for block in blocks {
+		let head = &block.head;
+		let Some(item) = dna.struct_by_sdna(head.sdna_nr) else {
+			out.push(
+				Severity::Error,
+				ValidationCheck::SdnaOutOfRange,
+				Some(block.file_offset),
+				Some(head.old),
+				format!(
+					"{} block has sdna_nr {} but SDNA has {} structs",
+					codes::code_to_str(head.code),
+					head.sdna_nr,
+					dna.structs.len()
+				),
+			);
+			continue;
+		};
+		// SDNA 0 marks raw data (and `DNA1`/`ENDB`/`REND`), whose length is free-form.
+		if head.sdna_nr == 0 {
+			continue;
+		}
+
+		let struct_size = usize::from(dna.tlen[item.type_idx as usize]);
+		let expected = (struct_size as u64).saturating_mul(head.nr);
+		let type_name = dna.type_name(item.type_idx);
+		if head.len < expected {
+			out.push(
+				Severity::Error,
+				ValidationCheck::PayloadSize,
+				Some(block.file_offset),
+				Some(head.old),
+				format!(
+					"{} block holds {} bytes, short of {} x {type_name} ({expected} bytes)",
+					codes::code_to_str(head.code),
+					head.len,
+					head.nr
+				),
+			);
+			continue;
+		}
+		if head.len > expected {
+			out.push(
+				Severity::Warning,
+				ValidationCheck::PayloadSize,
+				Some(block.file_offset),
+				Some(head.old),
+				format!(
+					"{} block holds {} bytes, {} past {} x {type_name}",
+					codes::code_to_str(head.code),
+					head.len,
+					head.len - expected,
+					head.nr
+				),
+			);
+		}
+		if struct_size > 0 {
+			typed.push((*block, struct_size));
+		}
+	}
+	typed
+}
+
+fn check_id_names(dna: &Dna, typed: &[(Block<'_>, usize)], out: &mut Collector) {
+	// Only blocks that passed the size check, so one short ID block cannot hide the rest.
+	let records = match id_delta_from_blocks(dna, typed.iter().map(|(block, _)| *block), &[]) {
+		Ok(delta) => delta.upserted,
+		Err(err) => {
+			out.push(
+				Severity::Warning,
+				ValidationCheck::DuplicateIdName,
+				None,
+				None,
+				format!("ID scan failed: {err}"),
+			);
+			return;
+		}
+	};
+
+	let mut by_name: BTreeMap<(u64, &str), Vec<u64>> = BTreeMap::new();
+	for record in &records {
+		by_name
+			.entry((record.lib.unwrap_or(0), record.id_name.as_ref()))
+			.or_default()
+			.push(record.old_ptr);
+	}
+	for ((lib, name), ptrs) in by_name {
+		if ptrs.len() < 2 {
+			continue;
+		}
+		let owner = if lib == 0 { "local".to_owned() } else { format!("library 0x{lib:x}") };
+		let list = ptrs.iter().map(|ptr| format!("0x{ptr:x}")).collect::<Vec<_>>().join(", ");
+		out.push(
+			Severity::Error,
+			ValidationCheck::DuplicateIdName,
+			None,
+			Some(ptrs[0]),
+			format!("{} {owner} IDs named {name}: {list}", ptrs.len()),
+		);
+	}
+}
+
+/// Unresolved pointers seen in one field of one struct type.
+struct Dangling {
+	count: usize,
+	first_ptr: u64,
+	first_block: usize,
+}
+
+fn check_pointers(dna: &Dna, index: &PointerIndex<'_>, typed: &[(Block<'_>, usize)], threads: usize, out: &mut Collector) {
+	// Pointer slots and struct size per SDNA index, shared read-only by the workers.
+	let mut fields: HashMap<u32, (Vec<PointerSlot>, usize)> = HashMap::new();
+	for (block, struct_size) in typed {
+		fields
+			.entry(block.head.sdna_nr)
+			.or_insert_with(|| (pointer_slots(dna, block.head.sdna_nr), *struct_size));
+	}
+	let blocks: Vec<Block<'_>> = typed.iter().map(|(block, _)| *block).collect();
+
+	// Blocks are scanned on the worker pool and merged in file order, so the
+	// first dangling pointer per field is the same for any thread count.
+	let scanned = map_blocks_parallel(&blocks, threads, |block| {
+		let (slots, struct_size) = &fields[&block.head.sdna_nr];
+		let mut checked = 0_usize;
+		let mut misses = Vec::new();
+		if slots.is_empty() {
+			return (checked, misses);
+		}
+		for element in block.payload.chunks_exact(*struct_size).take(block.head.nr as usize) {
+			for (slot_idx, slot) in slots.iter().enumerate() {
+				let Some(bytes) = element.get(slot.offset..) else {
+					continue;
+				};
+				let Ok(ptr) = Cursor::new(bytes).read_ptr(dna.pointer_size, dna.endianness) else {
+					continue;
+				};
+				if ptr == 0 {
+					continue;
+				}
+				checked += 1;
+				if index.resolve(ptr).is_none() {
+					misses.push((slot_idx, ptr));
+				}
+			}
+		}
+		(checked, misses)
+	});
+
+	let mut dangling: BTreeMap<(Arc<str>, Arc<str>), Dangling> = BTreeMap::new();
+	for (block, (checked, misses)) in blocks.iter().zip(scanned) {
+		out.report.pointers_checked += checked;
+		if misses.is_empty() {
+			continue;
+		}
+		let sdna_nr = block.head.sdna_nr;
+		let (slots, _) = &fields[&sdna_nr];
+		let type_name: Arc<str> = dna
+			.struct_by_sdna(sdna_nr)
+			.map_or_else(|| Arc::from("?"), |item| Arc::from(dna.type_name(item.type_idx)));
+		for (slot_idx, ptr) in misses {
+			dangling
+				.entry((type_name.clone(), slots[slot_idx].field.clone()))
+				.and_modify(|entry| entry.count += 1)
+				.or_insert(Dangling {
+					count: 1,
+					first_ptr: ptr,
+					first_block: block.file_offset,
+				});
+		}
+	}
+
+	for ((type_name, field), entry) in dangling {
+		out.push(
+			Severity::Warning,
+			ValidationCheck::DanglingPtr,
+			Some(entry.first_block),
+			Some(entry.first_ptr),
+			format!(
+				"{type_name}.{field}: {} non-null pointer(s) resolve to no block (first 0x{:x})",
+				entry.count, entry.first_ptr
+			),
+		);
+	}
+}
+
+/// Issue sink enforcing the per-check cap.
+struct Collector {
+	report: ValidationReport,
+	per_check: HashMap<ValidationCheck, usize>,
+	max_per_check: usize,
+}
+
+impl Collector {
+	fn new(max_per_check: usize) -> Self {
+		Self {
+			report: ValidationReport::default(),
+			per_check: HashMap::new(),
+			max_per_check,
+		}
+	}
+
+	fn push(&mut self, severity: Severity, check: ValidationCheck, block_offset: Option<usize>, ptr: Option<u64>, message: impl Into<Arc<str>>) {
+		let seen = self.per_check.entry(check).or_default();
+		if *seen >= self.max_per_check {
+			self.report.suppressed += 1;
+			return;
+		}
+		*seen += 1;
+		self.report.issues.push(ValidationIssue {
+			severity,
+			check,
+			block_offset,
+			ptr,
+			message: message.into(),
+		});
+	}
+
+	fn finish(self) -> ValidationReport {
+		self.report
+	}
+}
+
+#[cfg(test)]
+mod tests;
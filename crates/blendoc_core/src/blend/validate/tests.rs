@@ -0,0 +1,124 @@
+mod synthetic_validate {
+	use blendoc_testkit::builder::{BlendBuilder, HeaderKind, scene_file};
+
+	use crate::blend::{BlendFile, Severity, ValidateOptions, ValidationCheck, ValidationReport, validate_file};
+
+	fn validate(bytes: Vec<u8>, options: &ValidateOptions) -> ValidationReport {
+		let blend = BlendFile::from_bytes(bytes).expect("synthetic file parses");
+		validate_file(&blend, options).expect("validation runs")
+	}
+
+	fn checks(report: &ValidationReport) -> Vec<(ValidationCheck, Severity)> {
+		report.issues.iter().map(|issue| (issue.check, issue.severity)).collect()
+	}
+
+	/// Two `OBCube` objects, one with a dangling `parent`, a short `Object`
+	/// block, and a block whose `sdna_nr` is past the struct table.
+	fn broken_file() -> Vec<u8> {
+		let mut builder = BlendBuilder::new(HeaderKind::Legacy8, 404, false);
+		let sdna = builder.sdna_mut();
+		sdna.add_struct("Link", &[("Link", "*next"), ("Link", "*prev")]);
+		sdna.add_type("char", 1);
+		sdna.add_type("void", 0);
+		sdna.add_struct("ID", &[("void", "*next"), ("void", "*prev"), ("void", "*lib"), ("char", "name[24]")]);
+		let object = sdna.add_struct("Object", &[("ID", "id"), ("Object", "*parent")]);
+
+		let cube = |builder: &BlendBuilder, parent: u64| {
+			let mut payload = builder.payload();
+			payload.ptr(0).ptr(0).ptr(0).name("OBCube", 24).ptr(parent);
+			payload.finish()
+		};
+		let first = cube(&builder, 0x1000);
+		let second = cube(&builder, 0xdead_0000);
+		builder
+			.block(*b"OB\0\0", object, 0x1000, 1, first)
+			.block(*b"OB\0\0", object, 0x2000, 1, second)
+			.block(*b"DATA", object, 0x3000, 2, vec![0; 8])
+			.block(*b"DATA", 99, 0x4000, 1, vec![0; 4]);
+		builder.build()
+	}
+
+	#[test]
+	fn well_formed_scenes_have_no_issues() {
+		for kind in [HeaderKind::Legacy4, HeaderKind::Legacy8, HeaderKind::Large] {
+			let report = validate(scene_file(kind, false), &ValidateOptions::default());
+			assert!(report.issues.is_empty(), "{kind:?}: {:?}", report.issues);
+			assert!(report.is_valid());
+			assert!(report.pointers_checked > 0);
+		}
+	}
+
+	#[test]
+	fn broken_file_reports_each_check() {
+		let report = validate(broken_file(), &ValidateOptions::default());
+		let found = checks(&report);
+
+		assert!(found.contains(&(ValidationCheck::SdnaOutOfRange, Severity::Error)), "{found:?}");
+		assert!(found.contains(&(ValidationCheck::PayloadSize, Severity::Error)), "{found:?}");
+		assert!(found.contains(&(ValidationCheck::DuplicateIdName, Severity::Error)), "{found:?}");
+		let dangling: Vec<_> = report.issues.iter().filter(|issue| issue.check == ValidationCheck::DanglingPtr).collect();
+		assert_eq!(dangling.len(), 1);
+		assert_eq!(dangling[0].ptr, Some(0xdead_0000));
+		assert!(dangling[0].message.starts_with("Object.parent: 1 "), "{}", dangling[0].message);
+		assert!(!report.is_valid());
+		assert_eq!(report.max_severity(), Some(Severity::Error));
+
+		let report = validate(
+			broken_file(),
+			&ValidateOptions {
+				check_pointers: false,
+				..ValidateOptions::default()
+			},
+		);
+		assert!(checks(&report).iter().all(|(check, _)| *check != ValidationCheck::DanglingPtr));
+	}
+
+	#[test]
+	fn threaded_pointer_check_matches_serial() {
+		let summary = |report: ValidationReport| {
+			let issues: Vec<_> = report
+				.issues
+				.iter()
+				.map(|issue| (issue.check, issue.block_offset, issue.ptr, issue.message.to_string()))
+				.collect();
+			(issues, report.pointers_checked, report.suppressed)
+		};
+		for bytes in [broken_file(), scene_file(HeaderKind::Legacy8, false)] {
+			let serial = summary(validate(bytes.clone(), &ValidateOptions::default()));
+			for threads in [2, 0] {
+				let options = ValidateOptions {
+					threads,
+					..ValidateOptions::default()
+				};
+				assert_eq!(summary(validate(bytes.clone(), &options)), serial, "threads={threads}");
+			}
+		}
+	}
+
+	#[test]
+	fn truncated_files_miss_endb_and_dna() {
+		let mut bytes = scene_file(HeaderKind::Legacy8, false);
+		bytes.truncate(bytes.len() - 24);
+		let report = validate(bytes, &ValidateOptions::default());
+		assert_eq!(checks(&report), [(ValidationCheck::MissingEndb, Severity::Error)]);
+
+		let mut bytes = b"BLENDER-v404".to_vec();
+		bytes.extend_from_slice(b"ENDB");
+		bytes.extend_from_slice(&[0_u8; 20]);
+		let report = validate(bytes, &ValidateOptions::default());
+		assert_eq!(checks(&report), [(ValidationCheck::Dna, Severity::Error)]);
+	}
+
+	#[test]
+	fn per_check_cap_counts_suppressed_issues() {
+		let report = validate(
+			broken_file(),
+			&ValidateOptions {
+				max_issues_per_check: 0,
+				..ValidateOptions::default()
+			},
+		);
+		assert!(report.issues.is_empty());
+		assert!(report.suppressed >= 4);
+	}
+}
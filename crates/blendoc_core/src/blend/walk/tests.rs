@@ -116,18 +116,18 @@ mod unit_walk_chain {
 			},
 		]);
 
-		let dna = Dna {
-			endianness: crate::blend::Endianness::Little,
-			pointer_size: 8,
-			names: vec!["*next".into()],
-			types: vec!["Node".into()],
-			tlen: vec![8],
-			structs: vec![DnaStruct {
+		let dna = Dna::from_tables(
+			crate::blend::Endianness::Little,
+			8,
+			vec!["*next".into()],
+			vec!["Node".into()],
+			vec![8],
+			vec![DnaStruct {
 				type_idx: 0,
 				fields: vec![DnaField { type_idx: 0, name_idx: 0 }],
 			}],
-			struct_for_type: vec![Some(0)],
-		};
+		)
+		.expect("valid dna tables");
 
 		let ids = IdIndex::build(vec![
 			IdRecord {
@@ -187,3 +187,54 @@ mod unit_walk_chain {
 		assert!(matches!(stop.reason, WalkStopReason::NullNext));
 	}
 }
+
+mod pointer_size_32 {
+	use std::sync::Arc;
+
+	use crate::blend::{BHead, Block, Dna, DnaField, DnaStruct, Endianness, IdIndex, PointerIndex, PtrEntry, WalkOptions, WalkStopReason, walk_ptr_chain};
+
+	#[test]
+	fn walk_follows_four_byte_next_pointers() {
+		let payload_a = 0x2000_u32.to_le_bytes();
+		let payload_b = 0_u32.to_le_bytes();
+		let entry = |old: u64, payload| PtrEntry {
+			start_old: old,
+			end_old: old + 4,
+			block: Block {
+				head: BHead {
+					code: *b"DATA",
+					sdna_nr: 0,
+					old,
+					len: 4,
+					nr: 1,
+				},
+				payload,
+				file_offset: 0,
+			},
+		};
+		let index = PointerIndex::from_entries_for_test(vec![entry(0x1000, &payload_a[..]), entry(0x2000, &payload_b[..])]);
+
+		let dna = Dna::from_tables(
+			Endianness::Little,
+			4,
+			vec!["*next".into()],
+			vec!["Node".into()],
+			vec![4],
+			vec![DnaStruct {
+				type_idx: 0,
+				fields: vec![DnaField { type_idx: 0, name_idx: 0 }],
+			}],
+		)
+		.expect("valid dna tables");
+
+		let options = WalkOptions {
+			next_field: Arc::<str>::from("next"),
+			..WalkOptions::default()
+		};
+		let result = walk_ptr_chain(&dna, &index, &IdIndex::build(Vec::new()), 0x1000, &options).expect("walk succeeds");
+
+		assert_eq!(result.items.len(), 2);
+		let stop = result.stop.expect("expected stop");
+		assert!(matches!(stop.reason, WalkStopReason::NullNext));
+	}
+}
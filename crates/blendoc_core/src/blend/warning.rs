@@ -0,0 +1,104 @@
+use std::sync::{Arc, Mutex};
+
+/// Category of a non-fatal data-quality issue.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum WarningKind {
+	/// Non-null pointer did not resolve to an indexed struct element.
+	UnresolvedPtr,
+	/// Text bytes were not valid UTF-8 and were decoded lossily.
+	LossyString,
+	/// Struct bytes were left over after decoding all SDNA fields.
+	LayoutLeftover,
+	/// Two pointer index ranges overlap; lookups favor the later start.
+	OverlappingPtrRange,
+	/// ID-root block skipped because its `ID` header did not decode.
+	SkippedId,
+}
+
+impl WarningKind {
+	/// Return a stable snake-case label.
+	pub fn as_str(self) -> &'static str {
+		match self {
+			Self::UnresolvedPtr => "unresolved_ptr",
+			Self::LossyString => "lossy_string",
+			Self::LayoutLeftover => "layout_leftover",
+			Self::OverlappingPtrRange => "overlapping_ptr_range",
+			Self::SkippedId => "skipped_id",
+		}
+	}
+
+	/// Parse a label produced by [`WarningKind::as_str`].
+	pub fn from_label(label: &str) -> Option<Self> {
+		[
+			Self::UnresolvedPtr,
+			Self::LossyString,
+			Self::LayoutLeftover,
+			Self::OverlappingPtrRange,
+			Self::SkippedId,
+		]
+		.into_iter()
+		.find(|kind| kind.as_str() == label)
+	}
+}
+
+/// One best-effort choice made while producing a result.
+#[derive(Debug, Clone)]
+pub struct Warning {
+	/// Warning category.
+	pub kind: WarningKind,
+	/// Pointer the warning refers to, when one applies.
+	pub ptr: Option<u64>,
+	/// Human-readable detail (type and field context).
+	pub message: Arc<str>,
+}
+
+impl Warning {
+	/// Build a warning from its parts.
+	pub fn new(kind: WarningKind, ptr: Option<u64>, message: impl Into<Arc<str>>) -> Self {
+		Self {
+			kind,
+			ptr,
+			message: message.into(),
+		}
+	}
+}
+
+/// Shared sink collecting warnings from APIs that return plain values.
+///
+/// Clones share one buffer, so a collector can be stored in options and
+/// drained by the caller after the call returns.
+#[derive(Debug, Clone, Default)]
+pub struct WarningCollector {
+	inner: Arc<Mutex<Vec<Warning>>>,
+}
+
+impl WarningCollector {
+	/// Create an empty collector.
+	pub fn new() -> Self {
+		Self::default()
+	}
+
+	/// Record one warning.
+	pub fn push(&self, warning: Warning) {
+		self.lock().push(warning);
+	}
+
+	/// Remove and return all collected warnings.
+	pub fn take(&self) -> Vec<Warning> {
+		std::mem::take(&mut *self.lock())
+	}
+
+	/// Return number of collected warnings.
+	pub fn len(&self) -> usize {
+		self.lock().len()
+	}
+
+	/// Return whether no warnings were collected.
+	pub fn is_empty(&self) -> bool {
+		self.lock().is_empty()
+	}
+
+	fn lock(&self) -> std::sync::MutexGuard<'_, Vec<Warning>> {
+		self.inner.lock().unwrap_or_else(|poisoned| poisoned.into_inner())
+	}
+}
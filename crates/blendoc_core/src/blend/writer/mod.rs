@@ -0,0 +1,97 @@
+use std::io::Write;
+
+use crate::blend::{BHead, BlendError, BlendFile, Block, Dna, Result, codes};
+
+/// Re-emit `file` block by block through the header, `BHead`, and SDNA writers.
+///
+/// The header and every block header are re-encoded; the `DNA1` payload is
+/// rebuilt from the parsed schema, keeping any bytes past the encoded tables.
+/// Other payloads are copied verbatim and anything after `ENDB` is dropped.
+/// Returns the number of bytes written (uncompressed).
+pub fn write_blend(file: &BlendFile, out: &mut impl Write) -> Result<u64> {
+	write_blocks(file, out, |_| true)
+}
+
+/// [`write_blend`] restricted to the blocks `keep` accepts.
+pub(crate) fn write_blocks(file: &BlendFile, out: &mut impl Write, mut keep: impl FnMut(&Block<'_>) -> bool) -> Result<u64> {
+	let mut written = Vec::new();
+	file.header.write(&mut written)?;
+	out.write_all(&written)?;
+	let mut total = written.len() as u64;
+
+	for block in file.blocks() {
+		let block = block?;
+		if !keep(&block) {
+			continue;
+		}
+		written.clear();
+		block.head.write(&mut written, file.header)?;
+		if block.head.code == codes::DNA1 {
+			let dna = Dna::parse(block.payload, file.header.endianness, file.header.pointer_size)?;
+			let start = written.len();
+			dna.write(&mut written)?;
+			let encoded = written.len() - start;
+			written.extend_from_slice(block.payload.get(encoded..).unwrap_or_default());
+		} else {
+			written.extend_from_slice(block.payload);
+		}
+		out.write_all(&written)?;
+		total += written.len() as u64;
+		if block.head.is_endb() {
+			break;
+		}
+	}
+
+	Ok(total)
+}
+
+/// Check that `copy` reads back equal to `original`.
+///
+/// Compares the parsed header, then every block's header fields and payload
+/// bytes in order, and fails with [`BlendError::RoundTripMismatch`] at the
+/// first difference. Returns the number of blocks compared.
+pub fn verify_round_trip(original: &BlendFile, copy: &BlendFile) -> Result<usize> {
+	if original.header != copy.header {
+		return Err(mismatch(format!("header {:?} became {:?}", original.header, copy.header)));
+	}
+
+	let mut left = original.blocks();
+	let mut right = copy.blocks();
+	let mut index = 0_usize;
+	loop {
+		match (left.next().transpose()?, right.next().transpose()?) {
+			(None, None) => return Ok(index),
+			(Some(block), None) => {
+				return Err(mismatch(format!(
+					"block {index} ({}) is missing from the copy",
+					codes::code_to_str(block.head.code)
+				)));
+			}
+			(None, Some(block)) => return Err(mismatch(format!("copy has extra block {index} ({})", codes::code_to_str(block.head.code)))),
+			(Some(a), Some(b)) => {
+				if !same_head(&a.head, &b.head) {
+					return Err(mismatch(format!("block {index} header {:?} became {:?}", a.head, b.head)));
+				}
+				if a.payload != b.payload {
+					let at = a.payload.iter().zip(b.payload).position(|(x, y)| x != y).unwrap_or(a.payload.len());
+					return Err(mismatch(format!(
+						"block {index} ({}) payload differs at byte {at}",
+						codes::code_to_str(a.head.code)
+					)));
+				}
+			}
+		}
+		index += 1;
+	}
+}
+
+fn same_head(a: &BHead, b: &BHead) -> bool {
+	(a.code, a.sdna_nr, a.old, a.len, a.nr) == (b.code, b.sdna_nr, b.old, b.len, b.nr)
+}
+
+fn mismatch(detail: String) -> BlendError {
+	BlendError::RoundTripMismatch { detail }
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,75 @@
+mod synthetic_writer {
+	use blendoc_testkit::builder::{HeaderKind, scene_file};
+
+	use crate::blend::{BlendError, BlendFile, verify_round_trip, write_blend};
+
+	#[test]
+	fn scene_files_copy_byte_for_byte() {
+		for kind in [HeaderKind::Legacy4, HeaderKind::Legacy8, HeaderKind::Large] {
+			for big_endian in [false, true] {
+				let bytes = scene_file(kind, big_endian);
+				let original = BlendFile::from_bytes(bytes.clone()).expect("synthetic file parses");
+
+				let mut out = Vec::new();
+				let written = write_blend(&original, &mut out).expect("copy writes");
+				assert_eq!(written, out.len() as u64);
+				assert_eq!(out, bytes, "{kind:?} big_endian={big_endian}");
+
+				let copy = BlendFile::from_bytes(out).expect("copy parses");
+				assert!(verify_round_trip(&original, &copy).expect("copy verifies") > 2);
+			}
+		}
+	}
+
+	#[test]
+	fn trailing_bytes_after_endb_are_dropped() {
+		let mut bytes = scene_file(HeaderKind::Legacy8, false);
+		let clean = bytes.len();
+		bytes.extend_from_slice(b"junk");
+		let original = BlendFile::from_bytes(bytes).expect("synthetic file parses");
+
+		let mut out = Vec::new();
+		write_blend(&original, &mut out).expect("copy writes");
+		assert_eq!(out.len(), clean);
+	}
+
+	#[test]
+	fn verify_reports_first_difference() {
+		let bytes = scene_file(HeaderKind::Legacy8, false);
+		let original = BlendFile::from_bytes(bytes.clone()).expect("synthetic file parses");
+
+		let mut changed = bytes.clone();
+		// First payload byte after the 12-byte header and the first 24-byte block header.
+		changed[12 + 24] ^= 0xff;
+		let copy = BlendFile::from_bytes(changed).expect("changed copy parses");
+		let err = verify_round_trip(&original, &copy).expect_err("payloads differ");
+		assert!(
+			matches!(err, BlendError::RoundTripMismatch { ref detail } if detail.contains("block 0")),
+			"{err}"
+		);
+
+		let big = BlendFile::from_bytes(scene_file(HeaderKind::Legacy8, true)).expect("big-endian file parses");
+		let err = verify_round_trip(&original, &big).expect_err("headers differ");
+		assert!(
+			matches!(err, BlendError::RoundTripMismatch { ref detail } if detail.starts_with("header")),
+			"{err}"
+		);
+	}
+}
+
+mod fixtures_writer {
+	use blendoc_testkit::fixture_path;
+
+	use crate::blend::{BlendFile, verify_round_trip, write_blend};
+
+	#[test]
+	fn fixtures_copy_and_verify() {
+		for name in ["character.blend", "sword.blend", "v5.1_character.blend", "v5.1_sword.blend"] {
+			let original = BlendFile::open(fixture_path(name)).expect("fixture opens");
+			let mut out = Vec::new();
+			write_blend(&original, &mut out).expect("copy writes");
+			let copy = BlendFile::from_bytes(out).expect("copy parses");
+			verify_round_trip(&original, &copy).expect("copy verifies");
+		}
+	}
+}
@@ -1,6 +1,8 @@
 use std::sync::Arc;
 
-use crate::blend::{Dna, IdIndex, PointerIndex, RefScanOptions, Result, scan_refs_from_ptr};
+use crate::blend::cancel::is_cancelled;
+use crate::blend::ref_cache::scan_refs_cached;
+use crate::blend::{CancelToken, Dna, IdIndex, PointerIndex, Progress, ProgressStage, RefCache, RefScanOptions, Result};
 
 /// One inbound reference into a target canonical pointer.
 #[derive(Debug, Clone)]
@@ -13,6 +15,21 @@ pub struct InboundRef {
 	pub from_id: Option<Arc<str>>,
 	/// Field path on the owner that points to the target.
 	pub field: Arc<str>,
+	/// Where the pointer is stored, when [`XrefOptions::include_locations`] is set.
+	pub location: Option<InboundRefLocation>,
+}
+
+/// Byte position of one inbound pointer, for hexdump-level debugging.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct InboundRefLocation {
+	/// Absolute file offset of the owner block header.
+	pub block_offset: usize,
+	/// Offset of the pointer inside the owner block payload.
+	pub payload_offset: usize,
+	/// Offset of the pointer inside the owner struct.
+	pub field_offset: usize,
+	/// Element index of the canonical target within its block.
+	pub target_element: usize,
 }
 
 /// Configuration for inbound reference queries.
@@ -24,6 +41,14 @@ pub struct XrefOptions {
 	pub max_results: usize,
 	/// Include unresolved refs that match the raw target pointer.
 	pub include_unresolved: bool,
+	/// Optional shared memo for per-owner reference scans.
+	pub ref_cache: Option<RefCache>,
+	/// Fill [`InboundRef::location`] for each match.
+	pub include_locations: bool,
+	/// Receives a [`ProgressStage::Ids`] update per scanned owner.
+	pub progress: Option<Progress>,
+	/// Stops the owner scan early when cancelled; matches found so far are returned.
+	pub cancel: Option<CancelToken>,
 }
 
 impl Default for XrefOptions {
@@ -32,18 +57,32 @@ impl Default for XrefOptions {
 			ref_scan: RefScanOptions::default(),
 			max_results: 1024,
 			include_unresolved: false,
+			ref_cache: None,
+			include_locations: false,
+			progress: None,
+			cancel: None,
 		}
 	}
 }
 
 /// Find inbound references to a canonicalized target pointer.
+///
+/// A cancelled scan returns the matches found so far; callers tell it apart
+/// from a complete one by checking [`CancelToken::is_cancelled`].
 pub fn find_inbound_refs_to_ptr<'a>(dna: &Dna, index: &PointerIndex<'a>, ids: &IdIndex, target_ptr: u64, options: &XrefOptions) -> Result<Vec<InboundRef>> {
 	let target_canonical = index.canonicalize_ptr(dna, target_ptr)?;
 
+	let total = ids.records.len() as u64;
 	let mut out = Vec::new();
-	for owner in &ids.records {
-		let refs = scan_refs_from_ptr(dna, index, ids, owner.old_ptr, &options.ref_scan)?;
-		for record in refs {
+	for (done, owner) in ids.records.iter().enumerate() {
+		if is_cancelled(options.cancel.as_ref()) {
+			break;
+		}
+		if let Some(progress) = &options.progress {
+			progress.update(ProgressStage::Ids, done as u64 + 1, Some(total));
+		}
+		let refs = scan_refs_cached(dna, index, ids, owner.old_ptr, &options.ref_scan, options.ref_cache.as_ref())?;
+		for record in refs.iter() {
 			let matches = match &record.resolved {
 				Some(target) => target.canonical == target_canonical,
 				None => options.include_unresolved && record.ptr == target_ptr,
@@ -52,20 +91,39 @@ pub fn find_inbound_refs_to_ptr<'a>(dna: &Dna, index: &PointerIndex<'a>, ids: &I
 				continue;
 			}
 
+			let location = if options.include_locations {
+				let (_, typed) = index.resolve_canonical_typed(dna, owner.old_ptr)?;
+				let element_start = typed.element_index.unwrap_or(0) * typed.struct_size;
+				Some(InboundRefLocation {
+					block_offset: typed.base.entry.block.file_offset,
+					payload_offset: element_start + record.offset,
+					field_offset: record.offset,
+					target_element: record.resolved.as_ref().map_or(0, |target| target.element_index),
+				})
+			} else {
+				None
+			};
 			out.push(InboundRef {
 				from: owner.old_ptr,
 				from_type: Arc::<str>::from(owner.type_name.as_ref()),
 				from_id: Some(Arc::<str>::from(owner.id_name.as_ref())),
-				field: record.field,
+				field: record.field.clone(),
+				location,
 			});
 
 			if out.len() >= options.max_results {
+				if let Some(progress) = &options.progress {
+					progress.finish(ProgressStage::Ids);
+				}
 				out.sort_by(|left, right| left.from.cmp(&right.from).then_with(|| left.field.cmp(&right.field)));
 				return Ok(out);
 			}
 		}
 	}
 
+	if let Some(progress) = &options.progress {
+		progress.finish(ProgressStage::Ids);
+	}
 	out.sort_by(|left, right| left.from.cmp(&right.from).then_with(|| left.field.cmp(&right.field)));
 	Ok(out)
 }
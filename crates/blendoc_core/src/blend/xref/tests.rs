@@ -2,7 +2,7 @@ mod fixtures_day9_xref {
 
 	use blendoc_testkit::fixture_path;
 
-	use crate::blend::{BlendFile, IdIndex, RefScanOptions, XrefOptions, find_inbound_refs_to_ptr, scan_id_blocks};
+	use crate::blend::{BlendFile, IdIndex, RefScanOptions, XrefOptions, codes, find_inbound_refs_to_ptr, scan_id_blocks};
 
 	#[test]
 	fn character_world_xref_includes_scene_world_field() {
@@ -23,7 +23,7 @@ mod fixtures_day9_xref {
 		let world = ids
 			.records
 			.iter()
-			.find(|item| item.type_name.as_ref() == "World" || item.code == [b'W', b'O', 0, 0])
+			.find(|item| item.type_name.as_ref() == "World" || item.code == codes::ID_WO)
 			.expect("world id record exists");
 
 		let inbound = find_inbound_refs_to_ptr(
@@ -38,6 +38,10 @@ mod fixtures_day9_xref {
 				},
 				max_results: 512,
 				include_unresolved: false,
+				ref_cache: None,
+				include_locations: false,
+				progress: None,
+				cancel: None,
 			},
 		)
 		.expect("xref query succeeds");
@@ -53,7 +57,8 @@ mod fixtures_day9_xref {
 
 mod nested_inbound {
 	use crate::blend::{
-		BHead, Block, Dna, DnaField, DnaStruct, IdIndex, IdRecord, PointerIndex, PtrEntry, RefScanOptions, XrefOptions, find_inbound_refs_to_ptr,
+		BHead, Block, Dna, DnaField, DnaStruct, IdIndex, IdRecord, InboundRefLocation, PointerIndex, PtrEntry, RefScanOptions, XrefOptions,
+		find_inbound_refs_to_ptr,
 	};
 
 	#[test]
@@ -98,13 +103,13 @@ mod nested_inbound {
 			},
 		]);
 
-		let dna = Dna {
-			endianness: crate::blend::Endianness::Little,
-			pointer_size: 8,
-			names: vec!["id[8]".into(), "nested".into(), "*first".into()],
-			types: vec!["char".into(), "Owner".into(), "Nested".into(), "Target".into()],
-			tlen: vec![1, 16, 8, 8],
-			structs: vec![
+		let dna = Dna::from_tables(
+			crate::blend::Endianness::Little,
+			8,
+			vec!["id[8]".into(), "nested".into(), "*first".into()],
+			vec!["char".into(), "Owner".into(), "Nested".into(), "Target".into()],
+			vec![1, 16, 8, 8],
+			vec![
 				DnaStruct {
 					type_idx: 1,
 					fields: vec![DnaField { type_idx: 0, name_idx: 0 }, DnaField { type_idx: 2, name_idx: 1 }],
@@ -115,8 +120,8 @@ mod nested_inbound {
 				},
 				DnaStruct { type_idx: 3, fields: vec![] },
 			],
-			struct_for_type: vec![None, Some(0), Some(1), Some(2)],
-		};
+		)
+		.expect("valid dna tables");
 
 		let ids = IdIndex::build(vec![IdRecord {
 			old_ptr: 0x1000,
@@ -141,10 +146,26 @@ mod nested_inbound {
 				},
 				max_results: 32,
 				include_unresolved: false,
+				ref_cache: None,
+				include_locations: true,
+				progress: None,
+				cancel: None,
 			},
 		)
 		.expect("xref succeeds");
 
-		assert!(refs.iter().any(|item| item.from == 0x1000 && item.field.as_ref() == "nested.first"));
+		let inbound = refs
+			.iter()
+			.find(|item| item.from == 0x1000 && item.field.as_ref() == "nested.first")
+			.expect("nested inbound reference");
+		assert_eq!(
+			inbound.location,
+			Some(InboundRefLocation {
+				block_offset: 0,
+				payload_offset: 8,
+				field_offset: 8,
+				target_element: 0,
+			})
+		);
 	}
 }
@@ -0,0 +1,63 @@
+/* C ABI for the blendoc .blend inspector. */
+#ifndef BLENDOC_H
+#define BLENDOC_H
+
+#include <stddef.h>
+#include <stdint.h>
+
+#ifdef __cplusplus
+extern "C" {
+#endif
+
+/* Bumped on any incompatible change to the functions below. */
+#define BLENDOC_ABI_VERSION 1
+
+/* Opaque handle to one parsed .blend file. */
+typedef struct BlendocFile BlendocFile;
+
+/* ABI version the library was built with; compare against BLENDOC_ABI_VERSION. */
+uint32_t blendoc_abi_version(void);
+
+/*
+ * Message for the last failed call on this thread, or NULL.
+ * Valid until the next blendoc call on the same thread.
+ */
+const char *blendoc_last_error(void);
+
+/* Parse the file at `path` (UTF-8). Returns NULL on error. */
+BlendocFile *blendoc_open(const char *path);
+
+/* Release a handle from blendoc_open. NULL is ignored. */
+void blendoc_close(BlendocFile *file);
+
+/* Number of ID-root records. */
+size_t blendoc_id_count(const BlendocFile *file);
+
+/*
+ * ID name (e.g. "OBCube") of record `index`, or NULL when out of range.
+ * Owned by the handle; valid until blendoc_close.
+ */
+const char *blendoc_id_name(const BlendocFile *file, size_t index);
+
+/*
+ * JSON array of ID records: [{"ptr", "code", "type", "name"}, ...].
+ * Free with blendoc_string_free. Returns NULL on error.
+ */
+char *blendoc_ids_json(const BlendocFile *file);
+
+/*
+ * Chase a field path (e.g. "data.mat[0]") from the ID named `id_name`.
+ * `path` may be NULL to decode the whole ID struct.
+ * Returns JSON {"root", "root_ptr", "path", "value", "stop"}; free with
+ * blendoc_string_free. Returns NULL on error.
+ */
+char *blendoc_chase_json(const BlendocFile *file, const char *id_name, const char *path);
+
+/* Free a string returned by a *_json function. NULL is ignored. */
+void blendoc_string_free(char *value);
+
+#ifdef __cplusplus
+}
+#endif
+
+#endif /* BLENDOC_H */
@@ -0,0 +1,201 @@
+//! C ABI for embedding the blendoc inspector in C and C++ hosts.
+//!
+//! The matching declarations live in `include/blendoc.h`. Every entry point
+//! catches panics and reports failures through a NULL return plus
+//! [`blendoc_last_error`]; strings returned by `*_json` functions are owned by
+//! the caller and released with [`blendoc_string_free`].
+
+use std::cell::RefCell;
+use std::ffi::{CStr, CString, c_char};
+use std::panic::{AssertUnwindSafe, catch_unwind};
+
+use blendoc::blend::CachedFile;
+use blendoc::blend::json::{chase_json, ids_json};
+
+/// Version of the C ABI; mirrors `BLENDOC_ABI_VERSION` in `blendoc.h`.
+pub const ABI_VERSION: u32 = 1;
+
+/// Opaque handle to one parsed `.blend` file.
+pub struct BlendocFile {
+	loaded: CachedFile,
+	names: Vec<CString>,
+}
+
+thread_local! {
+	static LAST_ERROR: RefCell<Option<CString>> = const { RefCell::new(None) };
+}
+
+/// Run `body`, recording its error or panic as the thread's last error.
+fn guard<T>(fallback: T, body: impl FnOnce() -> Result<T, String>) -> T {
+	LAST_ERROR.with(|slot| slot.borrow_mut().take());
+	let message = match catch_unwind(AssertUnwindSafe(body)) {
+		Ok(Ok(value)) => return value,
+		Ok(Err(message)) => message,
+		Err(_) => "blendoc panicked".to_owned(),
+	};
+	let message = CString::new(message.replace('\0', " ")).unwrap_or_default();
+	LAST_ERROR.with(|slot| *slot.borrow_mut() = Some(message));
+	fallback
+}
+
+/// Borrow a NUL-terminated UTF-8 argument.
+///
+/// # Safety
+///
+/// `value` must be NULL or point to a NUL-terminated string that outlives `'a`.
+unsafe fn arg_str<'a>(value: *const c_char, name: &str) -> Result<&'a str, String> {
+	if value.is_null() {
+		return Err(format!("`{name}` is NULL"));
+	}
+	// SAFETY: non-null and NUL-terminated per the caller contract.
+	unsafe { CStr::from_ptr(value) }.to_str().map_err(|_| format!("`{name}` is not UTF-8"))
+}
+
+/// Borrow the handle behind a `const BlendocFile *`.
+///
+/// # Safety
+///
+/// `file` must be NULL or a live pointer from [`blendoc_open`].
+unsafe fn arg_file<'a>(file: *const BlendocFile) -> Result<&'a BlendocFile, String> {
+	// SAFETY: live handle or NULL per the caller contract.
+	unsafe { file.as_ref() }.ok_or_else(|| "`file` is NULL".to_owned())
+}
+
+fn into_c_string(value: String) -> Result<*mut c_char, String> {
+	CString::new(value).map(CString::into_raw).map_err(|err| err.to_string())
+}
+
+/// ABI version the library was built with.
+#[unsafe(no_mangle)]
+pub extern "C" fn blendoc_abi_version() -> u32 {
+	ABI_VERSION
+}
+
+/// Message for the last failed call on this thread, or NULL.
+///
+/// The pointer stays valid until the next blendoc call on the same thread.
+#[unsafe(no_mangle)]
+pub extern "C" fn blendoc_last_error() -> *const c_char {
+	LAST_ERROR.with(|slot| slot.borrow().as_ref().map_or(std::ptr::null(), |message| message.as_ptr()))
+}
+
+/// Parse the file at `path`; NULL on error.
+///
+/// # Safety
+///
+/// `path` must be NULL or a NUL-terminated string.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn blendoc_open(path: *const c_char) -> *mut BlendocFile {
+	guard(std::ptr::null_mut(), || {
+		// SAFETY: forwarded caller contract.
+		let path = unsafe { arg_str(path, "path") }?;
+		let loaded = CachedFile::load(path).map_err(|err| err.to_string())?;
+		let names = loaded
+			.ids
+			.records
+			.iter()
+			.map(|record| CString::new(record.id_name.replace('\0', "")).unwrap_or_default())
+			.collect();
+		Ok(Box::into_raw(Box::new(BlendocFile { loaded, names })))
+	})
+}
+
+/// Release a handle from [`blendoc_open`]; NULL is ignored.
+///
+/// # Safety
+///
+/// `file` must be NULL or a pointer from [`blendoc_open`] not yet closed.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn blendoc_close(file: *mut BlendocFile) {
+	if !file.is_null() {
+		// SAFETY: allocated by `blendoc_open` and not yet freed.
+		drop(unsafe { Box::from_raw(file) });
+	}
+}
+
+/// Number of ID-root records; 0 for a NULL handle.
+///
+/// # Safety
+///
+/// `file` must be NULL or a live pointer from [`blendoc_open`].
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn blendoc_id_count(file: *const BlendocFile) -> usize {
+	// SAFETY: forwarded caller contract.
+	guard(0, || Ok(unsafe { arg_file(file) }?.names.len()))
+}
+
+/// ID name of record `index`, or NULL when out of range.
+///
+/// The string is owned by the handle and valid until [`blendoc_close`].
+///
+/// # Safety
+///
+/// `file` must be NULL or a live pointer from [`blendoc_open`].
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn blendoc_id_name(file: *const BlendocFile, index: usize) -> *const c_char {
+	guard(std::ptr::null(), || {
+		// SAFETY: forwarded caller contract.
+		let file = unsafe { arg_file(file) }?;
+		let name = file
+			.names
+			.get(index)
+			.ok_or_else(|| format!("ID index {index} out of range ({} IDs)", file.names.len()))?;
+		Ok(name.as_ptr())
+	})
+}
+
+/// JSON array of ID records; free with [`blendoc_string_free`].
+///
+/// # Safety
+///
+/// `file` must be NULL or a live pointer from [`blendoc_open`].
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn blendoc_ids_json(file: *const BlendocFile) -> *mut c_char {
+	guard(std::ptr::null_mut(), || {
+		// SAFETY: forwarded caller contract.
+		let file = unsafe { arg_file(file) }?;
+		into_c_string(ids_json(&file.loaded.ids.records).to_string())
+	})
+}
+
+/// Chase `path` from the ID named `id_name` and return the result as JSON.
+///
+/// A NULL `path` decodes the whole ID struct.
+///
+/// # Safety
+///
+/// `file` must be NULL or a live pointer from [`blendoc_open`]; `id_name`
+/// and `path` must be NULL or NUL-terminated strings.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn blendoc_chase_json(file: *const BlendocFile, id_name: *const c_char, path: *const c_char) -> *mut c_char {
+	guard(std::ptr::null_mut(), || {
+		// SAFETY: forwarded caller contract.
+		let file = unsafe { arg_file(file) }?;
+		// SAFETY: forwarded caller contract.
+		let id_name = unsafe { arg_str(id_name, "id_name") }?;
+		// SAFETY: forwarded caller contract.
+		let path = if path.is_null() { None } else { Some(unsafe { arg_str(path, "path") }?) };
+
+		let CachedFile { dna, ids, .. } = &file.loaded;
+		let index = file.loaded.pointer_index().map_err(|err| err.to_string())?;
+		let payload = chase_json(dna, &index, ids, id_name, path).map_err(|err| err.to_string())?;
+		into_c_string(payload.to_string())
+	})
+}
+
+/// Free a string returned by a `*_json` function; NULL is ignored.
+///
+/// # Safety
+///
+/// `value` must be NULL or a pointer returned by a `*_json` function and not
+/// yet freed.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn blendoc_string_free(value: *mut c_char) {
+	if !value.is_null() {
+		// SAFETY: allocated by `CString::into_raw` in this crate.
+		drop(unsafe { CString::from_raw(value) });
+	}
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,67 @@
+use std::ffi::{CStr, CString};
+
+use blendoc_testkit::builder::{HeaderKind, SCENE_ID_NAMES, scene_file};
+
+use crate::{
+	blendoc_abi_version, blendoc_chase_json, blendoc_close, blendoc_id_count, blendoc_id_name, blendoc_ids_json, blendoc_last_error, blendoc_open,
+	blendoc_string_free,
+};
+
+/// Copy and free an owned `*_json` result.
+fn take_json(raw: *mut std::ffi::c_char) -> serde_json::Value {
+	assert!(!raw.is_null(), "call failed: {}", last_error());
+	// SAFETY: non-null result of a `*_json` call, freed right after.
+	let text = unsafe { CStr::from_ptr(raw) }.to_str().expect("utf-8 json").to_owned();
+	// SAFETY: owned string from a `*_json` call.
+	unsafe { blendoc_string_free(raw) };
+	serde_json::from_str(&text).expect("valid json")
+}
+
+fn last_error() -> String {
+	let raw = blendoc_last_error();
+	if raw.is_null() {
+		return String::new();
+	}
+	// SAFETY: thread-local message valid until the next call.
+	unsafe { CStr::from_ptr(raw) }.to_string_lossy().into_owned()
+}
+
+#[test]
+fn open_list_chase_close() {
+	let target = std::env::temp_dir().join(format!("blendoc_ffi_{}.blend", std::process::id()));
+	std::fs::write(&target, scene_file(HeaderKind::Legacy8, false)).expect("synthetic file written");
+	let path = CString::new(target.to_string_lossy().into_owned()).expect("path has no NUL");
+
+	assert_eq!(blendoc_abi_version(), crate::ABI_VERSION);
+	// SAFETY: every pointer below is either a live handle or a NUL-terminated literal.
+	unsafe {
+		let file = blendoc_open(path.as_ptr());
+		std::fs::remove_file(&target).expect("synthetic file removed");
+		assert!(!file.is_null(), "open failed: {}", last_error());
+
+		assert_eq!(blendoc_id_count(file), SCENE_ID_NAMES.len());
+		let first = CStr::from_ptr(blendoc_id_name(file, 0)).to_str().expect("utf-8 name");
+		assert_eq!(first, SCENE_ID_NAMES[0]);
+		assert!(blendoc_id_name(file, SCENE_ID_NAMES.len()).is_null());
+		assert!(last_error().contains("out of range"));
+
+		let ids = take_json(blendoc_ids_json(file));
+		let names: Vec<&str> = ids.as_array().expect("array").iter().filter_map(|row| row["name"].as_str()).collect();
+		assert_eq!(names, SCENE_ID_NAMES);
+
+		let object = CString::new(SCENE_ID_NAMES[1]).expect("name has no NUL");
+		let chased = take_json(blendoc_chase_json(file, object.as_ptr(), c"data".as_ptr()));
+		assert_eq!(chased["value"]["fields"]["id"]["fields"]["name"], SCENE_ID_NAMES[2]);
+		assert!(chased["stop"].is_null());
+
+		let whole = take_json(blendoc_chase_json(file, object.as_ptr(), std::ptr::null()));
+		assert_eq!(whole["value"]["type"], "Object");
+
+		assert!(blendoc_chase_json(file, c"OBMissing".as_ptr(), std::ptr::null()).is_null());
+		assert!(!last_error().is_empty());
+
+		blendoc_close(file);
+		assert!(blendoc_open(c"/nonexistent/blendoc.blend".as_ptr()).is_null());
+		assert!(!last_error().is_empty());
+	}
+}
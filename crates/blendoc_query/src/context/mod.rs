@@ -0,0 +1,52 @@
+use blendoc::blend::{BlendFile, Dna, IdIndex, PointerIndex, RefCache, Result, scan_id_blocks};
+
+/// Decoded schema, pointer index, ID index, and ref-scan cache shared by query evaluation.
+///
+/// Building a context is the expensive step; keep one per file and run
+/// as many queries against it as needed.
+pub struct QueryContext<'a> {
+	dna: Dna,
+	index: PointerIndex<'a>,
+	ids: IdIndex,
+	refs: RefCache,
+}
+
+impl<'a> QueryContext<'a> {
+	/// Parse DNA and build pointer and ID indexes for a blend file.
+	pub fn open(file: &'a BlendFile) -> Result<Self> {
+		let dna = file.dna()?;
+		let index = file.pointer_index()?;
+		let ids = IdIndex::build(scan_id_blocks(file, &dna)?);
+		Ok(Self::from_parts(dna, index, ids))
+	}
+
+	/// Assemble a context from already-built parts.
+	pub fn from_parts(dna: Dna, index: PointerIndex<'a>, ids: IdIndex) -> Self {
+		Self {
+			dna,
+			index,
+			ids,
+			refs: RefCache::new(),
+		}
+	}
+
+	/// Parsed SDNA schema.
+	pub fn dna(&self) -> &Dna {
+		&self.dna
+	}
+
+	/// Pointer index over file blocks.
+	pub fn index(&self) -> &PointerIndex<'a> {
+		&self.index
+	}
+
+	/// ID-root records.
+	pub fn ids(&self) -> &IdIndex {
+		&self.ids
+	}
+
+	/// Per-owner reference scans reused across queries on this context.
+	pub fn refs(&self) -> &RefCache {
+		&self.refs
+	}
+}
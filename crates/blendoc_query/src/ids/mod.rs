@@ -0,0 +1,258 @@
+use std::collections::HashSet;
+
+use blendoc::blend::{ChasePolicy, DecodeOptions, FieldPath, IdRecord, RefScanOptions, Result, chase_from_ptr, glob_match};
+
+use crate::QueryContext;
+
+/// One filter applied to ID-root records.
+#[derive(Debug, Clone)]
+pub enum IdPredicate {
+	/// Derived struct type name equals the value (`Object`).
+	OfType(String),
+	/// Block code starts with the value (`OB`).
+	Code(String),
+	/// `ID.name` matches a glob (`OB*`, `*.001`).
+	Named {
+		/// Glob pattern.
+		pattern: String,
+		/// Compare ASCII case-insensitively.
+		ignore_case: bool,
+	},
+	/// Field path resolves to a value on the ID struct, following pointers.
+	WithField(String),
+	/// ID holds a direct pointer to the selected ID.
+	Referencing(String),
+	/// ID is directly pointed at by the selected ID.
+	ReferencedBy(String),
+}
+
+/// Builder for queries over ID-root records.
+///
+/// Building only records predicates; nothing is scanned until the query
+/// is evaluated against a [`QueryContext`].
+#[derive(Debug, Clone, Default)]
+pub struct IdQuery {
+	predicates: Vec<IdPredicate>,
+	limit: Option<usize>,
+	ref_scan: RefScanOptions,
+}
+
+impl IdQuery {
+	/// Keep IDs whose derived struct type matches exactly.
+	pub fn of_type(self, type_name: impl Into<String>) -> Self {
+		self.filter(IdPredicate::OfType(type_name.into()))
+	}
+
+	/// Keep IDs whose block code starts with `code` (`OB`, `MA`).
+	pub fn with_code(self, code: impl Into<String>) -> Self {
+		self.filter(IdPredicate::Code(code.into()))
+	}
+
+	/// Keep IDs whose `ID.name` matches a glob.
+	pub fn named(self, pattern: impl Into<String>) -> Self {
+		self.filter(IdPredicate::Named {
+			pattern: pattern.into(),
+			ignore_case: false,
+		})
+	}
+
+	/// Keep IDs whose `ID.name` matches a glob, ignoring ASCII case.
+	pub fn named_ignore_case(self, pattern: impl Into<String>) -> Self {
+		self.filter(IdPredicate::Named {
+			pattern: pattern.into(),
+			ignore_case: true,
+		})
+	}
+
+	/// Keep IDs where a field path (`loc`, `data.mat[0]`) resolves without stopping.
+	pub fn with_field(self, path: impl Into<String>) -> Self {
+		self.filter(IdPredicate::WithField(path.into()))
+	}
+
+	/// Keep IDs with a direct pointer to the ID selected by `selector`.
+	pub fn referencing(self, selector: impl Into<String>) -> Self {
+		self.filter(IdPredicate::Referencing(selector.into()))
+	}
+
+	/// Keep IDs the ID selected by `selector` points at directly.
+	pub fn referenced_by(self, selector: impl Into<String>) -> Self {
+		self.filter(IdPredicate::ReferencedBy(selector.into()))
+	}
+
+	/// Add an arbitrary predicate.
+	pub fn filter(mut self, predicate: IdPredicate) -> Self {
+		self.predicates.push(predicate);
+		self
+	}
+
+	/// Stop after `max` matches.
+	pub fn limit(mut self, max: usize) -> Self {
+		self.limit = Some(max);
+		self
+	}
+
+	/// Override reference-scan options used by `referencing`/`referenced_by`.
+	pub fn ref_scan(mut self, options: RefScanOptions) -> Self {
+		self.ref_scan = options;
+		self
+	}
+
+	/// Recorded predicates in insertion order.
+	pub fn predicates(&self) -> &[IdPredicate] {
+		&self.predicates
+	}
+
+	/// Resolve selectors and return a lazy iterator over matching records.
+	///
+	/// ID selectors and field paths are validated here; per-record work
+	/// happens as the iterator is advanced, cheapest predicates first.
+	pub fn iter<'c, 'a>(&self, ctx: &'c QueryContext<'a>) -> Result<IdMatches<'c, 'a>> {
+		let mut checks = Vec::with_capacity(self.predicates.len());
+		for predicate in &self.predicates {
+			checks.push(compile(ctx, predicate, &self.ref_scan)?);
+		}
+		checks.sort_by_key(Check::cost);
+
+		Ok(IdMatches {
+			ctx,
+			records: ctx.ids().records.iter(),
+			checks,
+			ref_scan: self.ref_scan,
+			remaining: self.limit,
+		})
+	}
+
+	/// Evaluate the query and collect all matches in index order.
+	pub fn run<'c>(&self, ctx: &'c QueryContext<'_>) -> Result<Vec<&'c IdRecord>> {
+		self.iter(ctx)?.collect()
+	}
+
+	/// Evaluate until the first match.
+	pub fn first<'c>(&self, ctx: &'c QueryContext<'_>) -> Result<Option<&'c IdRecord>> {
+		self.iter(ctx)?.next().transpose()
+	}
+
+	/// Count matches.
+	pub fn count(&self, ctx: &QueryContext<'_>) -> Result<usize> {
+		let mut count = 0;
+		for item in self.iter(ctx)? {
+			item?;
+			count += 1;
+		}
+		Ok(count)
+	}
+}
+
+/// Lazy iterator over records matching an [`IdQuery`].
+pub struct IdMatches<'c, 'a> {
+	ctx: &'c QueryContext<'a>,
+	records: std::slice::Iter<'c, IdRecord>,
+	checks: Vec<Check>,
+	ref_scan: RefScanOptions,
+	remaining: Option<usize>,
+}
+
+impl<'c> Iterator for IdMatches<'c, '_> {
+	type Item = Result<&'c IdRecord>;
+
+	fn next(&mut self) -> Option<Self::Item> {
+		if self.remaining == Some(0) {
+			return None;
+		}
+
+		while let Some(record) = self.records.next() {
+			match self.matches(record) {
+				Ok(true) => {
+					if let Some(remaining) = &mut self.remaining {
+						*remaining -= 1;
+					}
+					return Some(Ok(record));
+				}
+				Ok(false) => {}
+				Err(err) => return Some(Err(err)),
+			}
+		}
+
+		None
+	}
+}
+
+impl IdMatches<'_, '_> {
+	fn matches(&self, record: &IdRecord) -> Result<bool> {
+		for check in &self.checks {
+			if !self.check(check, record)? {
+				return Ok(false);
+			}
+		}
+		Ok(true)
+	}
+
+	fn check(&self, check: &Check, record: &IdRecord) -> Result<bool> {
+		let ctx = self.ctx;
+		Ok(match check {
+			Check::Type(type_name) => record.type_name.as_ref() == type_name,
+			Check::Code(code) => record.code.starts_with(code),
+			Check::Name { pattern, ignore_case } => glob_match(pattern, &record.id_name, *ignore_case),
+			Check::TargetOf(targets) => targets.contains(&record.old_ptr),
+			Check::Field(path) => {
+				let result = chase_from_ptr(ctx.dna(), ctx.index(), record.old_ptr, path, &DecodeOptions::default(), &ChasePolicy::default())?;
+				result.stop.is_none()
+			}
+			Check::PointsAt(target) => ctx
+				.refs()
+				.scan(ctx.dna(), ctx.index(), ctx.ids(), record.old_ptr, &self.ref_scan)?
+				.iter()
+				.any(|item| item.resolved.as_ref().is_some_and(|resolved| resolved.canonical == *target)),
+		})
+	}
+}
+
+/// Predicate with selectors resolved and paths parsed.
+enum Check {
+	Type(String),
+	Code(Vec<u8>),
+	Name { pattern: String, ignore_case: bool },
+	TargetOf(HashSet<u64>),
+	Field(FieldPath),
+	PointsAt(u64),
+}
+
+impl Check {
+	/// Relative per-record cost; cheaper checks run first to short-circuit.
+	fn cost(&self) -> u8 {
+		match self {
+			Check::Type(_) | Check::Code(_) | Check::Name { .. } | Check::TargetOf(_) => 0,
+			Check::Field(_) => 1,
+			Check::PointsAt(_) => 2,
+		}
+	}
+}
+
+fn compile(ctx: &QueryContext<'_>, predicate: &IdPredicate, ref_scan: &RefScanOptions) -> Result<Check> {
+	Ok(match predicate {
+		IdPredicate::OfType(type_name) => Check::Type(type_name.clone()),
+		IdPredicate::Code(code) => Check::Code(code.as_bytes().to_vec()),
+		IdPredicate::Named { pattern, ignore_case } => Check::Name {
+			pattern: pattern.clone(),
+			ignore_case: *ignore_case,
+		},
+		IdPredicate::WithField(path) => Check::Field(FieldPath::parse(path)?),
+		IdPredicate::Referencing(selector) => {
+			let target = ctx.ids().resolve_selector(selector)?;
+			Check::PointsAt(ctx.index().canonicalize_ptr(ctx.dna(), target.old_ptr)?)
+		}
+		IdPredicate::ReferencedBy(selector) => {
+			let source = ctx.ids().resolve_selector(selector)?;
+			let targets = ctx
+				.refs()
+				.scan(ctx.dna(), ctx.index(), ctx.ids(), source.old_ptr, ref_scan)?
+				.iter()
+				.filter_map(|item| item.resolved.as_ref().map(|resolved| resolved.canonical))
+				.collect();
+			Check::TargetOf(targets)
+		}
+	})
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,148 @@
+mod synthetic_id_query {
+	use blendoc::blend::{BHead, BlendError, Block, Dna, DnaField, DnaStruct, Endianness, IdIndex, IdRecord, PointerIndex, PtrEntry};
+
+	use crate::{Query, QueryContext};
+
+	const OBJECT: u32 = 1;
+	const MATERIAL: u32 = 2;
+
+	/// `ID { char name[8]; }`, `Object { ID id; Material *mat; float loc[2]; }`, `Material { ID id; }`.
+	fn dna() -> Dna {
+		Dna::from_tables(
+			Endianness::Little,
+			8,
+			vec!["name[8]".into(), "id".into(), "*mat".into(), "loc[2]".into()],
+			vec!["char".into(), "float".into(), "ID".into(), "Object".into(), "Material".into()],
+			vec![1, 4, 8, 24, 8],
+			vec![
+				DnaStruct {
+					type_idx: 2,
+					fields: vec![DnaField { type_idx: 0, name_idx: 0 }],
+				},
+				DnaStruct {
+					type_idx: 3,
+					fields: vec![
+						DnaField { type_idx: 2, name_idx: 1 },
+						DnaField { type_idx: 4, name_idx: 2 },
+						DnaField { type_idx: 1, name_idx: 3 },
+					],
+				},
+				DnaStruct {
+					type_idx: 4,
+					fields: vec![DnaField { type_idx: 2, name_idx: 1 }],
+				},
+			],
+		)
+		.expect("valid dna tables")
+	}
+
+	fn object_payload(name: &str, mat: u64) -> Vec<u8> {
+		let mut out = name_bytes(name);
+		out.extend_from_slice(&mat.to_le_bytes());
+		out.extend_from_slice(&1.0_f32.to_le_bytes());
+		out.extend_from_slice(&2.0_f32.to_le_bytes());
+		out
+	}
+
+	fn name_bytes(name: &str) -> Vec<u8> {
+		let mut out = name.as_bytes().to_vec();
+		out.resize(8, 0);
+		out
+	}
+
+	fn block<'a>(code: &[u8; 2], sdna_nr: u32, old: u64, payload: &'a [u8]) -> Block<'a> {
+		Block {
+			head: BHead {
+				code: [code[0], code[1], 0, 0],
+				sdna_nr,
+				old,
+				len: payload.len() as u64,
+				nr: 1,
+			},
+			payload,
+			file_offset: 0,
+		}
+	}
+
+	fn record(code: &[u8; 2], sdna_nr: u32, old_ptr: u64, type_name: &str, id_name: &str) -> IdRecord {
+		IdRecord {
+			old_ptr,
+			code: [code[0], code[1], 0, 0],
+			sdna_nr,
+			type_name: type_name.into(),
+			id_name: id_name.into(),
+			next: None,
+			prev: None,
+			lib: None,
+		}
+	}
+
+	fn with_context(test: impl FnOnce(&QueryContext<'_>)) {
+		let cube = object_payload("OBCube", 0x3000);
+		let empty = object_payload("OBEmpty", 0);
+		let gold = name_bytes("MAGold");
+
+		let blocks = [
+			block(b"OB", OBJECT, 0x1000, &cube),
+			block(b"OB", OBJECT, 0x2000, &empty),
+			block(b"MA", MATERIAL, 0x3000, &gold),
+		];
+		let index = PointerIndex::from_entries_for_test(
+			blocks
+				.iter()
+				.map(|block| PtrEntry {
+					start_old: block.head.old,
+					end_old: block.head.old + block.head.len,
+					block: *block,
+				})
+				.collect(),
+		);
+		let ids = IdIndex::build(vec![
+			record(b"OB", OBJECT, 0x1000, "Object", "OBCube"),
+			record(b"OB", OBJECT, 0x2000, "Object", "OBEmpty"),
+			record(b"MA", MATERIAL, 0x3000, "Material", "MAGold"),
+		]);
+
+		test(&QueryContext::from_parts(dna(), index, ids));
+	}
+
+	fn names(records: Vec<&IdRecord>) -> Vec<&str> {
+		records.into_iter().map(|record| record.id_name.as_ref()).collect()
+	}
+
+	#[test]
+	fn metadata_predicates_filter_records() {
+		with_context(|ctx| {
+			assert_eq!(names(Query::ids().of_type("Object").run(ctx).expect("query runs")), vec!["OBCube", "OBEmpty"]);
+			assert_eq!(names(Query::ids().with_code("MA").run(ctx).expect("query runs")), vec!["MAGold"]);
+			assert_eq!(names(Query::ids().named_ignore_case("ob*y").run(ctx).expect("query runs")), vec!["OBEmpty"]);
+			assert_eq!(Query::ids().named("ob*").count(ctx).expect("query runs"), 0);
+		});
+	}
+
+	#[test]
+	fn field_and_reference_predicates_compose() {
+		with_context(|ctx| {
+			assert_eq!(names(Query::ids().with_field("loc").run(ctx).expect("query runs")), vec!["OBCube", "OBEmpty"]);
+			assert_eq!(names(Query::ids().with_field("mat.id.name").run(ctx).expect("query runs")), vec!["OBCube"]);
+
+			let query = Query::ids().of_type("Object").with_field("loc").referencing("MAGold");
+			assert_eq!(names(query.run(ctx).expect("query runs")), vec!["OBCube"]);
+			assert_eq!(names(Query::ids().referenced_by("OBCube").run(ctx).expect("query runs")), vec!["MAGold"]);
+		});
+	}
+
+	#[test]
+	fn limit_stops_iteration_and_unknown_selectors_fail_up_front() {
+		with_context(|ctx| {
+			let first = Query::ids().of_type("Object").first(ctx).expect("query runs").expect("match exists");
+			assert_eq!(first.id_name.as_ref(), "OBCube");
+			assert_eq!(Query::ids().limit(2).count(ctx).expect("query runs"), 2);
+
+			assert!(matches!(
+				Query::ids().referencing("MASilver").iter(ctx),
+				Err(BlendError::IdRecordNotFound { .. })
+			));
+		});
+	}
+}
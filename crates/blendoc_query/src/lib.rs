@@ -0,0 +1,32 @@
+//! Composable, lazily evaluated queries over `.blend` ID records.
+//!
+//! ```no_run
+//! use blendoc::blend::BlendFile;
+//! use blendoc_query::{Query, QueryContext};
+//!
+//! # fn main() -> blendoc::blend::Result<()> {
+//! let file = BlendFile::open("fixtures/character.blend")?;
+//! let ctx = QueryContext::open(&file)?;
+//! let objects = Query::ids().of_type("Object").with_field("loc").referencing("MAGold").run(&ctx)?;
+//! # Ok(())
+//! # }
+//! ```
+
+mod context;
+mod ids;
+
+/// Shared decoded state queries evaluate against.
+pub use context::QueryContext;
+/// ID query builder, predicates, and lazy result iterator.
+pub use ids::{IdMatches, IdPredicate, IdQuery};
+
+/// Entry point for building queries.
+#[derive(Debug, Clone, Copy)]
+pub struct Query;
+
+impl Query {
+	/// Start a query over ID-root records.
+	pub fn ids() -> IdQuery {
+		IdQuery::default()
+	}
+}
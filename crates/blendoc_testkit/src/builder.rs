@@ -0,0 +1,416 @@
+//! In-memory `.blend` writer for synthetic test files.
+
+/// Block-header layout written by [`BlendBuilder`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HeaderKind {
+	/// Legacy `BLENDER_` header with `BHead4` blocks (4-byte pointers).
+	Legacy4,
+	/// Legacy `BLENDER-` header with `BHead8` blocks (8-byte pointers).
+	Legacy8,
+	/// `BLENDER17-01` header with `LargeBHead8` blocks.
+	Large,
+}
+
+impl HeaderKind {
+	/// Pointer width implied by the layout.
+	pub fn pointer_size(self) -> usize {
+		match self {
+			Self::Legacy4 => 4,
+			Self::Legacy8 | Self::Large => 8,
+		}
+	}
+}
+
+/// SDNA table builder that computes struct sizes from field declarations.
+#[derive(Debug, Clone)]
+pub struct SdnaBuilder {
+	pointer_size: usize,
+	names: Vec<String>,
+	types: Vec<(String, u16)>,
+	structs: Vec<(u16, Vec<(u16, u16)>)>,
+}
+
+impl SdnaBuilder {
+	/// Create an empty table set for the given pointer width.
+	pub fn new(pointer_size: usize) -> Self {
+		Self {
+			pointer_size,
+			names: Vec::new(),
+			types: Vec::new(),
+			structs: Vec::new(),
+		}
+	}
+
+	/// Register a primitive (or opaque) type, returning its type index.
+	pub fn add_type(&mut self, name: &str, size: u16) -> u16 {
+		if let Some(idx) = self.types.iter().position(|(existing, _)| existing == name) {
+			self.types[idx].1 = size;
+			return idx as u16;
+		}
+		self.types.push((name.to_owned(), size));
+		(self.types.len() - 1) as u16
+	}
+
+	/// Register a struct from `(type, declaration)` pairs, returning its SDNA index.
+	///
+	/// Field types must already be registered; pointer fields may name types
+	/// that are declared later.
+	pub fn add_struct(&mut self, name: &str, fields: &[(&str, &str)]) -> u32 {
+		let mut size = 0_usize;
+		let mut encoded = Vec::with_capacity(fields.len());
+		for (type_name, decl) in fields {
+			let type_idx = match self.types.iter().position(|(existing, _)| existing == type_name) {
+				Some(idx) => idx as u16,
+				None => self.add_type(type_name, 0),
+			};
+			let is_ptr = decl.starts_with('*') || decl.starts_with("(*");
+			let element = if is_ptr {
+				self.pointer_size
+			} else {
+				usize::from(self.types[usize::from(type_idx)].1)
+			};
+			size += element * array_len(decl);
+			encoded.push((type_idx, self.add_name(decl)));
+		}
+
+		let type_idx = self.add_type(name, size as u16);
+		self.structs.push((type_idx, encoded));
+		(self.structs.len() - 1) as u32
+	}
+
+	/// Size in bytes of a registered type.
+	pub fn type_size(&self, name: &str) -> Option<usize> {
+		self.types.iter().find(|(existing, _)| existing == name).map(|(_, size)| usize::from(*size))
+	}
+
+	/// Encode as a `DNA1` block payload.
+	pub fn to_bytes(&self, big_endian: bool) -> Vec<u8> {
+		let u32_bytes = |value: u32| if big_endian { value.to_be_bytes() } else { value.to_le_bytes() };
+		let u16_bytes = |value: u16| if big_endian { value.to_be_bytes() } else { value.to_le_bytes() };
+
+		let mut out = b"SDNANAME".to_vec();
+		out.extend_from_slice(&u32_bytes(self.names.len() as u32));
+		for name in &self.names {
+			out.extend_from_slice(name.as_bytes());
+			out.push(0);
+		}
+		pad4(&mut out);
+
+		out.extend_from_slice(b"TYPE");
+		out.extend_from_slice(&u32_bytes(self.types.len() as u32));
+		for (name, _) in &self.types {
+			out.extend_from_slice(name.as_bytes());
+			out.push(0);
+		}
+		pad4(&mut out);
+
+		out.extend_from_slice(b"TLEN");
+		for (_, size) in &self.types {
+			out.extend_from_slice(&u16_bytes(*size));
+		}
+		pad4(&mut out);
+
+		out.extend_from_slice(b"STRC");
+		out.extend_from_slice(&u32_bytes(self.structs.len() as u32));
+		for (type_idx, fields) in &self.structs {
+			out.extend_from_slice(&u16_bytes(*type_idx));
+			out.extend_from_slice(&u16_bytes(fields.len() as u16));
+			for (field_type, field_name) in fields {
+				out.extend_from_slice(&u16_bytes(*field_type));
+				out.extend_from_slice(&u16_bytes(*field_name));
+			}
+		}
+		out
+	}
+
+	fn add_name(&mut self, decl: &str) -> u16 {
+		if let Some(idx) = self.names.iter().position(|existing| existing == decl) {
+			return idx as u16;
+		}
+		self.names.push(decl.to_owned());
+		(self.names.len() - 1) as u16
+	}
+}
+
+/// Endian- and pointer-width-aware payload writer.
+#[derive(Debug, Clone)]
+pub struct PayloadWriter {
+	big_endian: bool,
+	pointer_size: usize,
+	bytes: Vec<u8>,
+}
+
+impl PayloadWriter {
+	/// Start an empty payload.
+	pub fn new(big_endian: bool, pointer_size: usize) -> Self {
+		Self {
+			big_endian,
+			pointer_size,
+			bytes: Vec::new(),
+		}
+	}
+
+	/// Append a pointer-width stored address.
+	pub fn ptr(&mut self, value: u64) -> &mut Self {
+		if self.pointer_size == 4 {
+			self.u32(value as u32)
+		} else if self.big_endian {
+			self.raw(&value.to_be_bytes())
+		} else {
+			self.raw(&value.to_le_bytes())
+		}
+	}
+
+	/// Append a 32-bit unsigned integer.
+	pub fn u32(&mut self, value: u32) -> &mut Self {
+		if self.big_endian {
+			self.raw(&value.to_be_bytes())
+		} else {
+			self.raw(&value.to_le_bytes())
+		}
+	}
+
+	/// Append a 32-bit signed integer.
+	pub fn i32(&mut self, value: i32) -> &mut Self {
+		self.u32(value as u32)
+	}
+
+	/// Append a 32-bit float.
+	pub fn f32(&mut self, value: f32) -> &mut Self {
+		self.u32(value.to_bits())
+	}
+
+	/// Append `text` as a NUL-padded fixed-width `char` array.
+	pub fn name(&mut self, text: &str, width: usize) -> &mut Self {
+		let mut field = vec![0_u8; width];
+		let len = text.len().min(width.saturating_sub(1));
+		field[..len].copy_from_slice(&text.as_bytes()[..len]);
+		self.raw(&field)
+	}
+
+	/// Append raw bytes unchanged.
+	pub fn raw(&mut self, bytes: &[u8]) -> &mut Self {
+		self.bytes.extend_from_slice(bytes);
+		self
+	}
+
+	/// Finish and return the payload bytes.
+	pub fn finish(&mut self) -> Vec<u8> {
+		std::mem::take(&mut self.bytes)
+	}
+}
+
+#[derive(Debug, Clone)]
+struct BlockSpec {
+	code: [u8; 4],
+	sdna_nr: u32,
+	old: u64,
+	nr: u64,
+	payload: Vec<u8>,
+}
+
+/// Uncompressed `.blend` writer: header, blocks, then `DNA1` and `ENDB`.
+#[derive(Debug, Clone)]
+pub struct BlendBuilder {
+	kind: HeaderKind,
+	big_endian: bool,
+	version: u16,
+	sdna: SdnaBuilder,
+	blocks: Vec<BlockSpec>,
+}
+
+impl BlendBuilder {
+	/// Create a builder for one header layout and three/four-digit Blender version.
+	pub fn new(kind: HeaderKind, version: u16, big_endian: bool) -> Self {
+		Self {
+			kind,
+			big_endian,
+			version,
+			sdna: SdnaBuilder::new(kind.pointer_size()),
+			blocks: Vec::new(),
+		}
+	}
+
+	/// Header layout.
+	pub fn kind(&self) -> HeaderKind {
+		self.kind
+	}
+
+	/// Mutable SDNA tables written into `DNA1`.
+	pub fn sdna_mut(&mut self) -> &mut SdnaBuilder {
+		&mut self.sdna
+	}
+
+	/// Payload writer matching this file's byte order and pointer width.
+	pub fn payload(&self) -> PayloadWriter {
+		PayloadWriter::new(self.big_endian, self.kind.pointer_size())
+	}
+
+	/// Append a block; `DNA1` and `ENDB` are added by [`BlendBuilder::build`].
+	pub fn block(&mut self, code: [u8; 4], sdna_nr: u32, old: u64, nr: u64, payload: Vec<u8>) -> &mut Self {
+		self.blocks.push(BlockSpec {
+			code,
+			sdna_nr,
+			old,
+			nr,
+			payload,
+		});
+		self
+	}
+
+	/// Encode the file.
+	pub fn build(&self) -> Vec<u8> {
+		let marker = if self.big_endian { 'V' } else { 'v' };
+		let mut out = match self.kind {
+			HeaderKind::Legacy4 => format!("BLENDER_{marker}{:03}", self.version).into_bytes(),
+			HeaderKind::Legacy8 => format!("BLENDER-{marker}{:03}", self.version).into_bytes(),
+			HeaderKind::Large => format!("BLENDER17-01{marker}{:04}", self.version).into_bytes(),
+		};
+
+		let dna = BlockSpec {
+			code: *b"DNA1",
+			sdna_nr: 0,
+			old: 0,
+			nr: 1,
+			payload: self.sdna.to_bytes(self.big_endian),
+		};
+		let endb = BlockSpec {
+			code: *b"ENDB",
+			sdna_nr: 0,
+			old: 0,
+			nr: 0,
+			payload: Vec::new(),
+		};
+		for block in self.blocks.iter().chain([&dna, &endb]) {
+			self.write_bhead(&mut out, block);
+			out.extend_from_slice(&block.payload);
+		}
+		out
+	}
+
+	fn write_bhead(&self, out: &mut Vec<u8>, block: &BlockSpec) {
+		let mut head = self.payload();
+		head.raw(&block.code);
+		match self.kind {
+			HeaderKind::Legacy4 | HeaderKind::Legacy8 => {
+				head.i32(block.payload.len() as i32).ptr(block.old).u32(block.sdna_nr).i32(block.nr as i32);
+			}
+			HeaderKind::Large => {
+				let wide = |value: u64| if self.big_endian { value.to_be_bytes() } else { value.to_le_bytes() };
+				head.u32(block.sdna_nr)
+					.raw(&wide(block.old))
+					.raw(&wide(block.payload.len() as u64))
+					.raw(&wide(block.nr));
+			}
+		}
+		out.extend_from_slice(&head.finish());
+	}
+}
+
+fn array_len(decl: &str) -> usize {
+	let mut count = 1_usize;
+	let mut rest = decl;
+	while let Some(start) = rest.find('[') {
+		let Some(end) = rest[start..].find(']') else {
+			break;
+		};
+		count *= rest[start + 1..start + end].parse::<usize>().unwrap_or(1);
+		rest = &rest[start + end + 1..];
+	}
+	count
+}
+
+fn pad4(out: &mut Vec<u8>) {
+	while !out.len().is_multiple_of(4) {
+		out.push(0);
+	}
+}
+
+/// ID names written by [`scene_file`], in block order.
+pub const SCENE_ID_NAMES: [&str; 4] = ["SCScene", "OBCamera", "MECamera", "WOWorld"];
+
+/// `Object.loc` stored by [`scene_file`].
+pub const SCENE_OBJECT_LOC: [f32; 3] = [1.0, 2.0, 3.0];
+
+/// Number of `MVert` elements in the mesh `DATA` block written by [`scene_file`].
+pub const SCENE_VERT_COUNT: u64 = 4;
+
+/// Default Blender version written for each header layout by [`scene_file`].
+pub fn scene_version(kind: HeaderKind) -> u16 {
+	match kind {
+		HeaderKind::Legacy4 => 279,
+		HeaderKind::Legacy8 => 300,
+		HeaderKind::Large => 501,
+	}
+}
+
+/// Stored `old` identifiers used by [`scene_file`]: scene, object, mesh, vertex data, world.
+///
+/// Legacy layouts use disjoint address ranges; `Large` uses small stable IDs
+/// whose ranges overlap, as Blender 5.x writes them.
+pub fn scene_ptrs(kind: HeaderKind) -> [u64; 5] {
+	match kind {
+		HeaderKind::Large => [1, 2, 3, 4, 5],
+		HeaderKind::Legacy4 | HeaderKind::Legacy8 => [0x1000, 0x2000, 0x3000, 0x3100, 0x4000],
+	}
+}
+
+/// Build a small uncompressed scene file.
+///
+/// `SCScene.camera -> OBCamera.data -> MECamera.mvert` (one owned `DATA`
+/// block of [`SCENE_VERT_COUNT`] `MVert`s) and `SCScene.world -> WOWorld`.
+pub fn scene_file(kind: HeaderKind, big_endian: bool) -> Vec<u8> {
+	let mut builder = BlendBuilder::new(kind, scene_version(kind), big_endian);
+	let sdna = builder.sdna_mut();
+	sdna.add_type("char", 1);
+	sdna.add_type("int", 4);
+	sdna.add_type("float", 4);
+	sdna.add_type("void", 0);
+	sdna.add_struct("ID", &[("void", "*next"), ("void", "*prev"), ("Library", "*lib"), ("char", "name[24]")]);
+	sdna.add_struct("Library", &[("ID", "id"), ("char", "filepath[64]")]);
+	let mvert = sdna.add_struct("MVert", &[("float", "co[3]")]);
+	let mesh = sdna.add_struct("Mesh", &[("ID", "id"), ("MVert", "*mvert"), ("int", "totvert")]);
+	let object = sdna.add_struct("Object", &[("ID", "id"), ("void", "*data"), ("float", "loc[3]")]);
+	let world = sdna.add_struct("World", &[("ID", "id"), ("float", "horr")]);
+	let scene = sdna.add_struct("Scene", &[("ID", "id"), ("Object", "*camera"), ("World", "*world")]);
+
+	let [sc, ob, me, verts, wo] = scene_ptrs(kind);
+	let id = |builder: &BlendBuilder, name: &str, next: u64, prev: u64| {
+		let mut payload = builder.payload();
+		payload.ptr(next).ptr(prev).ptr(0).name(name, 24);
+		payload.finish()
+	};
+
+	let mut payload = builder.payload();
+	payload.raw(&id(&builder, SCENE_ID_NAMES[0], 0, 0)).ptr(ob).ptr(wo);
+	let scene_payload = payload.finish();
+
+	let mut payload = builder.payload();
+	payload.raw(&id(&builder, SCENE_ID_NAMES[1], 0, 0)).ptr(me);
+	for value in SCENE_OBJECT_LOC {
+		payload.f32(value);
+	}
+	let object_payload = payload.finish();
+
+	let mut payload = builder.payload();
+	payload.raw(&id(&builder, SCENE_ID_NAMES[2], 0, 0)).ptr(verts).i32(SCENE_VERT_COUNT as i32);
+	let mesh_payload = payload.finish();
+
+	let mut payload = builder.payload();
+	for idx in 0..SCENE_VERT_COUNT {
+		payload.f32(idx as f32).f32(0.0).f32(1.0);
+	}
+	let vert_payload = payload.finish();
+
+	let mut payload = builder.payload();
+	payload.raw(&id(&builder, SCENE_ID_NAMES[3], 0, 0)).f32(0.05);
+	let world_payload = payload.finish();
+
+	builder
+		.block(*b"SC\0\0", scene, sc, 1, scene_payload)
+		.block(*b"OB\0\0", object, ob, 1, object_payload)
+		.block(*b"ME\0\0", mesh, me, 1, mesh_payload)
+		.block(*b"DATA", mvert, verts, SCENE_VERT_COUNT, vert_payload)
+		.block(*b"WO\0\0", world, wo, 1, world_payload);
+	builder.build()
+}
@@ -1,9 +1,12 @@
 //! Shared test helpers for workspace crates.
 
+use std::io::Write;
 use std::path::{Path, PathBuf};
-use std::process::{Command, Output};
+use std::process::{Command, Output, Stdio};
 use std::sync::OnceLock;
 
+pub mod builder;
+
 static BLENDOC_BIN: OnceLock<PathBuf> = OnceLock::new();
 
 /// Resolve the workspace root path.
@@ -38,6 +41,19 @@ pub fn run_blendoc(args: &[&str]) -> Output {
 	Command::new(blendoc_bin()).args(args).output().expect("blendoc command executes")
 }
 
+/// Run the `blendoc` CLI with `stdin` piped to it.
+pub fn run_blendoc_with_stdin(args: &[&str], stdin: &[u8]) -> Output {
+	let mut child = Command::new(blendoc_bin())
+		.args(args)
+		.stdin(Stdio::piped())
+		.stdout(Stdio::piped())
+		.stderr(Stdio::piped())
+		.spawn()
+		.expect("blendoc command spawns");
+	child.stdin.take().expect("stdin piped").write_all(stdin).expect("stdin written");
+	child.wait_with_output().expect("blendoc command executes")
+}
+
 /// Run `blendoc` and parse stdout as JSON.
 pub fn run_blendoc_json(args: &[&str]) -> serde_json::Value {
 	let output = run_blendoc(args);
@@ -0,0 +1,71 @@
+//! wasm-bindgen bindings for inspecting `.blend` files in the browser.
+//!
+//! Built for `wasm32-unknown-unknown`, this crate never touches the
+//! filesystem: a web viewer hands the bytes of a dropped file to
+//! [`open_bytes`] and queries the returned [`BlendView`]. zstd-compressed
+//! files decode through the pure-Rust decoder enabled by blendoc's
+//! `pure-zstd` feature. Query results are JSON strings in the same shapes as
+//! the C ABI in `blendoc_ffi`.
+
+use blendoc::blend::json::{chase_json, ids_json};
+use blendoc::blend::{BlendFile, Dna, IdIndex, IdScanOptions, scan_id_blocks_with};
+use wasm_bindgen::prelude::*;
+
+/// One parsed `.blend` file held in wasm memory.
+#[wasm_bindgen]
+pub struct BlendView {
+	blend: BlendFile,
+	dna: Dna,
+	ids: IdIndex,
+}
+
+/// Decode and index the raw (possibly compressed) bytes of a `.blend` file.
+#[wasm_bindgen]
+pub fn open_bytes(bytes: &[u8]) -> Result<BlendView, JsError> {
+	BlendView::parse(bytes.to_vec()).map_err(|message| JsError::new(&message))
+}
+
+#[wasm_bindgen]
+impl BlendView {
+	/// JSON array of ID records with `ptr`, `code`, `type`, and `name`.
+	pub fn ids(&self) -> String {
+		ids_json(&self.ids.records).to_string()
+	}
+
+	/// Decode the ID named `id`, optionally chasing `path` from it, as JSON.
+	pub fn show(&self, id: &str, path: Option<String>) -> Result<String, JsError> {
+		self.show_json(id, path.as_deref()).map_err(|message| JsError::new(&message))
+	}
+
+	/// Compression label of the source bytes (`none`, `zstd`, or `gzip`).
+	pub fn compression(&self) -> String {
+		self.blend.compression.as_str().to_owned()
+	}
+
+	/// Blender version from the file header, e.g. `405`.
+	pub fn version(&self) -> u16 {
+		self.blend.header.version
+	}
+}
+
+impl BlendView {
+	fn parse(raw: Vec<u8>) -> Result<Self, String> {
+		let blend = BlendFile::from_bytes(raw).map_err(|err| err.to_string())?;
+		let dna = blend.dna().map_err(|err| err.to_string())?;
+		let records = scan_id_blocks_with(&blend, &dna, &IdScanOptions::default()).map_err(|err| err.to_string())?;
+		Ok(Self {
+			ids: IdIndex::build(records),
+			blend,
+			dna,
+		})
+	}
+
+	fn show_json(&self, id: &str, path: Option<&str>) -> Result<String, String> {
+		let index = self.blend.pointer_index().map_err(|err| err.to_string())?;
+		let payload = chase_json(&self.dna, &index, &self.ids, id, path).map_err(|err| err.to_string())?;
+		Ok(payload.to_string())
+	}
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,40 @@
+use blendoc::blend::{Compression, encode_bytes};
+use blendoc_testkit::builder::{HeaderKind, SCENE_ID_NAMES, scene_file};
+
+use crate::BlendView;
+
+fn json(text: &str) -> serde_json::Value {
+	serde_json::from_str(text).expect("valid json")
+}
+
+#[test]
+fn open_bytes_lists_and_shows_ids() {
+	let view = BlendView::parse(scene_file(HeaderKind::Large, false)).expect("synthetic file parses");
+	assert_eq!(view.compression(), "none");
+
+	let ids = json(&view.ids());
+	let names: Vec<&str> = ids.as_array().expect("array").iter().filter_map(|row| row["name"].as_str()).collect();
+	assert_eq!(names, SCENE_ID_NAMES);
+
+	let chased = json(&view.show_json(SCENE_ID_NAMES[1], Some("data")).expect("chase succeeds"));
+	assert_eq!(chased["value"]["fields"]["id"]["fields"]["name"], SCENE_ID_NAMES[2]);
+	assert!(chased["stop"].is_null());
+
+	let whole = json(&view.show_json(SCENE_ID_NAMES[1], None).expect("decode succeeds"));
+	assert_eq!(whole["value"]["type"], "Object");
+	assert!(view.show_json("OBMissing", None).is_err());
+}
+
+#[test]
+fn open_bytes_accepts_compressed_input() {
+	let raw = scene_file(HeaderKind::Legacy8, false);
+	// zstd is encoded with ruzstd so the test also runs without libzstd.
+	let zstd = ruzstd::encoding::compress_to_vec(raw.as_slice(), ruzstd::encoding::CompressionLevel::Fastest);
+	let gzip = encode_bytes(&raw, Compression::Gzip, None).expect("encode succeeds");
+	for (compression, encoded) in [(Compression::Zstd, zstd), (Compression::Gzip, gzip)] {
+		let view = BlendView::parse(encoded).expect("compressed file parses");
+		assert_eq!(view.compression(), compression.as_str());
+		assert_eq!(json(&view.ids()).as_array().map(Vec::len), Some(SCENE_ID_NAMES.len()));
+	}
+	assert!(BlendView::parse(b"not a blend".to_vec()).is_err());
+}
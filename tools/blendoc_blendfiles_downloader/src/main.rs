@@ -1,11 +1,14 @@
 use std::collections::BTreeMap;
 use std::path::{Component, Path, PathBuf};
 use std::process::Command;
+use std::time::Duration;
 use std::{fs, io};
 
 use clap::Parser;
 
 const FIXTURE_MAP_FILE: &str = "blendfiles_map.json";
+/// Suffix of the in-progress file a download resumes from.
+const PARTIAL_SUFFIX: &str = "part";
 
 type FixtureMap = BTreeMap<String, BTreeMap<String, String>>;
 type DynError = Box<dyn std::error::Error + Send + Sync>;
@@ -26,6 +29,26 @@ struct Args {
 	/// Re-download files even when the target already exists.
 	#[arg(long)]
 	overwrite: bool,
+	/// Fetch `<base-url>/<folder>/<relative path>` instead of each entry's URL.
+	#[arg(long, value_name = "BASE_URL")]
+	mirror: Option<String>,
+	/// Attempts after the first failure before giving up on a file.
+	#[arg(long, default_value_t = 3)]
+	retries: u32,
+	/// Delay before the first retry; doubles on each later one.
+	#[arg(long, default_value_t = 1000)]
+	backoff_ms: u64,
+	/// Transfer rate cap passed to curl/wget, e.g. `500k` or `2m`.
+	#[arg(long, value_name = "RATE")]
+	limit_rate: Option<String>,
+}
+
+/// Per-file transfer settings.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct TransferOptions {
+	retries: u32,
+	backoff: Duration,
+	limit_rate: Option<String>,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -60,7 +83,12 @@ fn run(args: Args) -> Result<(), DynError> {
 		return Err(format!("folder '{folder}' not found in {}", map_path.display()).into());
 	}
 
-	let plan = build_download_plan(&fixtures, &root, args.folder.as_deref())?;
+	let mut plan = build_download_plan(&fixtures, &root, args.folder.as_deref())?;
+	if let Some(mirror) = args.mirror.as_deref() {
+		for item in &mut plan {
+			item.url = mirror_url(mirror, item);
+		}
+	}
 	if plan.is_empty() {
 		println!("No files matched the requested filters.");
 		return Ok(());
@@ -74,11 +102,16 @@ fn run(args: Args) -> Result<(), DynError> {
 		return Ok(());
 	}
 
+	let transfer = TransferOptions {
+		retries: args.retries,
+		backoff: Duration::from_millis(args.backoff_ms),
+		limit_rate: args.limit_rate,
+	};
 	let mut downloaded = 0usize;
 	let mut skipped = 0usize;
 
 	for item in plan {
-		match download_file(&item, args.overwrite) {
+		match download_file(&item, args.overwrite, &transfer) {
 			Ok(DownloadOutcome::Downloaded) => {
 				downloaded += 1;
 				println!("GET\t{}\t<=\t{}", item.destination.display(), item.url);
@@ -155,7 +188,25 @@ fn validate_relative_path(path: &str) -> Result<(), DynError> {
 	Ok(())
 }
 
-fn download_file(item: &DownloadItem, overwrite: bool) -> Result<DownloadOutcome, DynError> {
+/// Entry URL rewritten onto a mirror laid out like the fixture tree.
+fn mirror_url(base: &str, item: &DownloadItem) -> String {
+	format!("{}/{}/{}", base.trim_end_matches('/'), item.folder, item.relative_path)
+}
+
+/// In-progress path next to `destination`, kept across runs so transfers resume.
+fn partial_path(destination: &Path) -> PathBuf {
+	let mut name = destination.file_name().unwrap_or_default().to_os_string();
+	name.push(".");
+	name.push(PARTIAL_SUFFIX);
+	destination.with_file_name(name)
+}
+
+/// Wait before retry `attempt` (1-based): `backoff`, then doubling, capped at 64x.
+fn backoff_delay(backoff: Duration, attempt: u32) -> Duration {
+	backoff.saturating_mul(1 << attempt.saturating_sub(1).min(6))
+}
+
+fn download_file(item: &DownloadItem, overwrite: bool, transfer: &TransferOptions) -> Result<DownloadOutcome, DynError> {
 	if item.destination.exists() && !overwrite {
 		return Ok(DownloadOutcome::SkippedExists);
 	}
@@ -164,27 +215,49 @@ fn download_file(item: &DownloadItem, overwrite: bool) -> Result<DownloadOutcome
 		fs::create_dir_all(parent)?;
 	}
 
-	run_download_command(&item.url, &item.destination)?;
+	let partial = partial_path(&item.destination);
+	let mut attempt = 0;
+	loop {
+		match run_download_command(&item.url, &partial, transfer.limit_rate.as_deref()) {
+			Ok(()) => break,
+			Err(err) if attempt < transfer.retries => {
+				attempt += 1;
+				let delay = backoff_delay(transfer.backoff, attempt);
+				eprintln!("RETRY	{}	{attempt}/{}	in {}ms: {err}", item.url, transfer.retries, delay.as_millis());
+				std::thread::sleep(delay);
+			}
+			Err(err) => return Err(err),
+		}
+	}
+	fs::rename(&partial, &item.destination)?;
 
 	Ok(DownloadOutcome::Downloaded)
 }
 
-fn run_download_command(url: &str, destination: &Path) -> Result<(), DynError> {
-	let curl_status = Command::new("curl")
-		.arg("--fail")
+/// Fetch `url` into `destination`, continuing from its current length when it exists.
+fn run_download_command(url: &str, destination: &Path, limit_rate: Option<&str>) -> Result<(), DynError> {
+	let mut curl = Command::new("curl");
+	curl.arg("--fail")
 		.arg("--location")
 		.arg("--silent")
 		.arg("--show-error")
-		.arg("--output")
-		.arg(destination)
-		.arg(url)
-		.status();
+		.arg("--continue-at")
+		.arg("-");
+	if let Some(rate) = limit_rate {
+		curl.arg("--limit-rate").arg(rate);
+	}
+	let curl_status = curl.arg("--output").arg(destination).arg(url).status();
 
 	match curl_status {
 		Ok(status) if status.success() => Ok(()),
 		Ok(status) => Err(format!("curl exited with status {status} for {url}").into()),
 		Err(err) if err.kind() == io::ErrorKind::NotFound => {
-			let wget_status = Command::new("wget").arg("--quiet").arg("--output-document").arg(destination).arg(url).status();
+			let mut wget = Command::new("wget");
+			wget.arg("--quiet").arg("--continue");
+			if let Some(rate) = limit_rate {
+				wget.arg(format!("--limit-rate={rate}"));
+			}
+			let wget_status = wget.arg("--output-document").arg(destination).arg(url).status();
 
 			match wget_status {
 				Ok(status) if status.success() => Ok(()),
@@ -53,3 +53,28 @@ fn resolve_root_maps_blendfiles_to_fixture_tree() {
 
 	assert!(resolved.ends_with(Path::new("fixtures").join("blendfiles")));
 }
+
+#[test]
+fn mirror_rewrites_urls_onto_fixture_layout() {
+	let fixtures = map_with_entries(&[("shaderball", &[("textures/sky.exr", "https://example.com/sky.exr")])]);
+	let plan = build_download_plan(&fixtures, Path::new("fixtures/blendfiles"), None).expect("plan builds");
+
+	assert_eq!(
+		mirror_url("https://mirror.local/blendfiles/", &plan[0]),
+		"https://mirror.local/blendfiles/shaderball/textures/sky.exr"
+	);
+}
+
+#[test]
+fn partial_downloads_sit_next_to_the_destination() {
+	assert_eq!(partial_path(Path::new("a/b/main.blend")), Path::new("a/b/main.blend.part"));
+}
+
+#[test]
+fn backoff_doubles_and_caps() {
+	let base = Duration::from_millis(100);
+
+	assert_eq!(backoff_delay(base, 1), base);
+	assert_eq!(backoff_delay(base, 3), Duration::from_millis(400));
+	assert_eq!(backoff_delay(base, 40), Duration::from_millis(6400));
+}